@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
 use std::str::FromStr;
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -60,6 +61,193 @@ pub enum SnapshotEntityType {
     Page,
 }
 
+/// Moderation status of an ingested comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "comment_status", rename_all = "snake_case")
+)]
+pub enum CommentStatus {
+    Pending,
+    Approved,
+    Spam,
+}
+
+impl CommentStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Spam => "spam",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Pending => "Pending",
+            Self::Approved => "Approved",
+            Self::Spam => "Spam",
+        }
+    }
+}
+
+impl FromStr for CommentStatus {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "approved" => Ok(Self::Approved),
+            "spam" => Ok(Self::Spam),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Outcome of the latest render job for a post or page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "render_status", rename_all = "snake_case")
+)]
+pub enum RenderStatus {
+    #[default]
+    Ok,
+    Failed,
+}
+
+impl RenderStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// What each RSS and Atom item carries besides its title and link.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "feed_content_mode", rename_all = "snake_case")
+)]
+pub enum FeedContentMode {
+    /// The plain-text excerpt.
+    #[default]
+    Excerpt,
+    /// The rendered summary, falling back to the excerpt.
+    Summary,
+    /// The full rendered post.
+    Full,
+}
+
+impl FeedContentMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Excerpt => "excerpt",
+            Self::Summary => "summary",
+            Self::Full => "full",
+        }
+    }
+}
+
+impl FromStr for FeedContentMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "excerpt" => Ok(Self::Excerpt),
+            "summary" => Ok(Self::Summary),
+            "full" => Ok(Self::Full),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What the public site serves at `/`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "homepage_mode", rename_all = "snake_case")
+)]
+pub enum HomepageMode {
+    /// The post feed.
+    #[default]
+    Feed,
+    /// A published page, with the feed moved to `/posts`.
+    Page,
+}
+
+impl HomepageMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Feed => "feed",
+            Self::Page => "page",
+        }
+    }
+}
+
+impl FromStr for HomepageMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "feed" => Ok(Self::Feed),
+            "page" => Ok(Self::Page),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How the post feed at the site root lays out its posts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "homepage_layout", rename_all = "snake_case")
+)]
+pub enum HomepageLayout {
+    /// One post per row.
+    #[default]
+    List,
+    /// Cards in a multi-column grid.
+    Grid,
+    /// The first pinned or most recent post featured above a list.
+    Magazine,
+}
+
+impl HomepageLayout {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::List => "list",
+            Self::Grid => "grid",
+            Self::Magazine => "magazine",
+        }
+    }
+}
+
+impl FromStr for HomepageLayout {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "list" => Ok(Self::List),
+            "grid" => Ok(Self::Grid),
+            "magazine" => Ok(Self::Magazine),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Status of an API key.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
@@ -136,6 +324,8 @@ pub enum ApiScope {
     AuditRead,
     SnapshotRead,
     SnapshotWrite,
+    CommentWrite,
+    RenderPreview,
 }
 
 impl ApiScope {
@@ -158,6 +348,8 @@ impl ApiScope {
             Self::AuditRead => "audit_read",
             Self::SnapshotRead => "snapshot_read",
             Self::SnapshotWrite => "snapshot_write",
+            Self::CommentWrite => "comment_write",
+            Self::RenderPreview => "render_preview",
         }
     }
 
@@ -180,6 +372,8 @@ impl ApiScope {
             Self::AuditRead => "Audit read",
             Self::SnapshotRead => "Snapshot read",
             Self::SnapshotWrite => "Snapshot write",
+            Self::CommentWrite => "Comment write",
+            Self::RenderPreview => "Render preview",
         }
     }
 
@@ -202,6 +396,8 @@ impl ApiScope {
             Self::AuditRead,
             Self::SnapshotRead,
             Self::SnapshotWrite,
+            Self::CommentWrite,
+            Self::RenderPreview,
         ]
     }
 }
@@ -233,6 +429,8 @@ impl FromStr for ApiScope {
             "audit_read" => Ok(Self::AuditRead),
             "snapshot_read" => Ok(Self::SnapshotRead),
             "snapshot_write" => Ok(Self::SnapshotWrite),
+            "comment_write" => Ok(Self::CommentWrite),
+            "render_preview" => Ok(Self::RenderPreview),
             _ => Err(()),
         }
     }
@@ -249,6 +447,11 @@ fn default_page_status() -> PageStatus {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PostCreateRequest {
     pub title: String,
+    /// Explicit slug; leave out to derive one from the title.
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Blank to derive the excerpt from the body.
+    #[serde(default)]
     pub excerpt: String,
     pub body_markdown: String,
     pub summary_markdown: Option<String>,
@@ -265,6 +468,8 @@ pub struct PostCreateRequest {
 pub struct PostUpdateRequest {
     pub slug: String,
     pub title: String,
+    /// Blank to derive the excerpt from the body.
+    #[serde(default)]
     pub excerpt: String,
     pub body_markdown: String,
     pub summary_markdown: Option<String>,
@@ -275,6 +480,10 @@ pub struct PostUpdateRequest {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PostPinRequest {
     pub pinned: bool,
+    /// Position among the pinned posts, lowest first; omit to keep the
+    /// current one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin_order: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -311,6 +520,14 @@ pub struct PostStatusRequest {
     pub archived_at: Option<OffsetDateTime>,
 }
 
+/// Language of a post and the group linking it to its translations.
+/// `null` (or a blank `lang`) clears the field.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PostTranslationRequest {
+    pub lang: Option<String>,
+    pub translation_group: Option<Uuid>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PostTagsRequest {
     pub tag_ids: Vec<Uuid>,
@@ -326,6 +543,8 @@ pub struct PageCreateRequest {
     pub scheduled_at: Option<OffsetDateTime>,
     pub published_at: Option<OffsetDateTime>,
     pub archived_at: Option<OffsetDateTime>,
+    /// Page template (`default`, `wide` or `landing`); omitted means `default`.
+    pub template: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -333,6 +552,8 @@ pub struct PageUpdateRequest {
     pub slug: String,
     pub title: String,
     pub body_markdown: String,
+    /// Page template to switch to; omitted keeps the current one.
+    pub template: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -351,6 +572,14 @@ pub struct PageBodyRequest {
     pub body_markdown: String,
 }
 
+/// Language of a page and the group linking it to its translations.
+/// `null` (or a blank `lang`) clears the field.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PageTranslationRequest {
+    pub lang: Option<String>,
+    pub translation_group: Option<Uuid>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PageStatusRequest {
     pub status: PageStatus,
@@ -390,6 +619,20 @@ pub struct TagDescriptionRequest {
     pub description: Option<String>,
 }
 
+/// Whether a bulk assignment attaches the tag to posts or detaches it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagAssignMode {
+    Add,
+    Remove,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TagAssignRequest {
+    pub post_ids: Vec<Uuid>,
+    pub mode: TagAssignMode,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct NavigationCreateRequest {
     pub label: String,
@@ -462,6 +705,70 @@ pub struct SettingsPatchRequest {
     pub public_site_url: Option<String>,
     pub global_toc_enabled: Option<bool>,
     pub favicon_svg: Option<String>,
+    /// PNG or ICO upload to serve as the favicon; `null` goes back to the SVG.
+    #[serde(
+        default,
+        deserialize_with = "present_or_null",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub favicon_upload_id: Option<Option<Uuid>>,
+    pub maintenance_enabled: Option<bool>,
+    pub maintenance_html: Option<String>,
+    pub maintenance_retry_after_secs: Option<i32>,
+    pub date_format: Option<String>,
+    pub feed_content_mode: Option<FeedContentMode>,
+    pub feed_item_limit: Option<i32>,
+    pub homepage_mode: Option<HomepageMode>,
+    /// Published page served at `/` in `page` mode; `null` clears it.
+    #[serde(
+        default,
+        deserialize_with = "present_or_null",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub homepage_page_id: Option<Option<Uuid>>,
+    pub homepage_layout: Option<HomepageLayout>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommentCreateRequest {
+    pub post_id: Uuid,
+    pub author: String,
+    pub body: String,
+    /// Address the commenter posted from, as seen by the forwarding service.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_ip: Option<IpAddr>,
+}
+
+/// Content kind whose sanitisation policy a render preview applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderPreviewTarget {
+    Post,
+    Page,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RenderPreviewRequest {
+    pub markdown: String,
+    pub target: RenderPreviewTarget,
+    /// `false` returns the HTML before sanitisation together with a summary of
+    /// what the sanitizer strips.
+    #[serde(default = "default_sanitized")]
+    pub sanitized: bool,
+}
+
+fn default_sanitized() -> bool {
+    true
+}
+
+/// Keep a field sent as `null` apart from one left out: `Some(None)` clears
+/// the value, `None` leaves it alone.
+fn present_or_null<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Serialize)]
@@ -513,3 +820,27 @@ pub struct ApiKeyInfoResponse {
     pub revoked_at: Option<OffsetDateTime>,
     pub last_used_at: Option<OffsetDateTime>,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyRotateAllResponse {
+    pub rotated: Vec<RotatedApiKey>,
+    /// Keys left on their old secret; empty when every rotation succeeded.
+    pub failed: Vec<FailedApiKeyRotation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RotatedApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    /// New token; it is shown only in this response.
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FailedApiKeyRotation {
+    pub id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub error: String,
+}
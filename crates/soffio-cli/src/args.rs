@@ -31,6 +31,14 @@ pub struct Cli {
     #[arg(hide = true, env = "SOFFIO_API_KEY")]
     pub api_key_env: Option<String>,
 
+    /// Output format for results and errors
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Json)]
+    pub output: OutputFormat,
+
+    /// Retries of a request the server rate-limits (429) before giving up
+    #[arg(long, global = true, default_value_t = 3)]
+    pub max_retries: u32,
+
     #[command(subcommand)]
     pub command: Commands,
 }
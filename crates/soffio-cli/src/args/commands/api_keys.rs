@@ -10,4 +10,6 @@ pub struct ApiKeysCmd {
 pub enum ApiKeysAction {
     /// Show current API key metadata/scopes
     Me,
+    /// Rotate every active key, this one included, and print the new tokens
+    RotateAll,
 }
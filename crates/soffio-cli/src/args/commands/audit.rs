@@ -19,6 +19,15 @@ pub enum AuditCmd {
         entity_type: Option<String>,
         #[arg(long)]
         search: Option<String>,
+        /// Only entries whose recorded changes include this field (e.g. slug)
+        #[arg(long)]
+        field: Option<String>,
+        /// Only entries created at or after this RFC 3339 timestamp
+        #[arg(long)]
+        from: Option<String>,
+        /// Only entries created before this RFC 3339 timestamp
+        #[arg(long)]
+        to: Option<String>,
         #[arg(long, default_value_t = 20)]
         limit: u32,
         #[arg(long)]
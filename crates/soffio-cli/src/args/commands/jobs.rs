@@ -21,4 +21,6 @@ pub enum JobsCmd {
         #[arg(long)]
         cursor: Option<String>,
     },
+    /// Get a job with its timeline, failed attempts and related audit entries
+    Get { id: String },
 }
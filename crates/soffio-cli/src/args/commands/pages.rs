@@ -51,6 +51,9 @@ pub enum PagesCmd {
         published_at: Option<String>,
         #[arg(long)]
         archived_at: Option<String>,
+        /// Page template: default, wide or landing
+        #[arg(long)]
+        template: Option<String>,
     },
     /// Update a page
     Update {
@@ -64,6 +67,12 @@ pub enum PagesCmd {
         body: Option<String>,
         #[arg(long)]
         body_file: Option<PathBuf>,
+        /// Page template to switch to; omit to keep the current one
+        #[arg(long)]
+        template: Option<String>,
+        /// `ETag` from a previous read; `*` overwrites whatever is stored
+        #[arg(long, default_value = "*")]
+        if_match: String,
     },
     /// Patch title only
     PatchTitle {
@@ -71,6 +80,9 @@ pub enum PagesCmd {
         id: Uuid,
         #[arg(long)]
         title: String,
+        /// `ETag` from a previous read; `*` overwrites whatever is stored
+        #[arg(long, default_value = "*")]
+        if_match: String,
     },
     /// Patch body
     PatchBody {
@@ -80,6 +92,9 @@ pub enum PagesCmd {
         body: Option<String>,
         #[arg(long)]
         body_file: Option<PathBuf>,
+        /// `ETag` from a previous read; `*` overwrites whatever is stored
+        #[arg(long, default_value = "*")]
+        if_match: String,
     },
     /// Update status and times
     Status {
@@ -94,6 +109,26 @@ pub enum PagesCmd {
         #[arg(long)]
         archived_at: Option<String>,
     },
-    /// Delete a page
+    /// Set language and translation group; omit both to clear
+    Translation {
+        #[arg(long)]
+        id: Uuid,
+        #[arg(long)]
+        lang: Option<String>,
+        #[arg(long)]
+        translation_group: Option<Uuid>,
+    },
+    /// Move a page to the trash
     Delete { id: Uuid },
+    /// List trashed pages
+    Trash {
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+        #[arg(long)]
+        cursor: Option<String>,
+    },
+    /// Restore a trashed page
+    Restore { id: Uuid },
+    /// Permanently delete a trashed page
+    Purge { id: Uuid },
 }
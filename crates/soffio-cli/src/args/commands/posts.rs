@@ -39,7 +39,11 @@ pub enum PostsCmd {
     Create {
         #[arg(long)]
         title: String,
+        /// Leave out to derive the slug from the title
         #[arg(long)]
+        slug: Option<String>,
+        /// Leave out to derive the excerpt from the body
+        #[arg(long, default_value = "")]
         excerpt: String,
         #[arg(long)]
         body: Option<String>,
@@ -68,7 +72,8 @@ pub enum PostsCmd {
         slug: String,
         #[arg(long)]
         title: String,
-        #[arg(long)]
+        /// Leave out to derive the excerpt from the body
+        #[arg(long, default_value = "")]
         excerpt: String,
         #[arg(long)]
         body: Option<String>,
@@ -80,6 +85,9 @@ pub enum PostsCmd {
         summary_file: Option<PathBuf>,
         #[arg(long, default_value_t = false)]
         pinned: bool,
+        /// `ETag` from a previous read; `*` overwrites whatever is stored
+        #[arg(long, default_value = "*")]
+        if_match: String,
     },
     /// Patch title only
     PatchTitle {
@@ -87,6 +95,9 @@ pub enum PostsCmd {
         id: Uuid,
         #[arg(long)]
         title: String,
+        /// `ETag` from a previous read; `*` overwrites whatever is stored
+        #[arg(long, default_value = "*")]
+        if_match: String,
     },
     /// Patch excerpt
     PatchExcerpt {
@@ -94,6 +105,9 @@ pub enum PostsCmd {
         id: Uuid,
         #[arg(long)]
         excerpt: String,
+        /// `ETag` from a previous read; `*` overwrites whatever is stored
+        #[arg(long, default_value = "*")]
+        if_match: String,
     },
     /// Patch body (supports file input)
     PatchBody {
@@ -103,6 +117,19 @@ pub enum PostsCmd {
         body: Option<String>,
         #[arg(long)]
         body_file: Option<PathBuf>,
+        /// `ETag` from a previous read; `*` overwrites whatever is stored
+        #[arg(long, default_value = "*")]
+        if_match: String,
+    },
+    /// Watch a markdown file and send it as the post body on every save
+    Watch {
+        #[arg(long)]
+        id: Uuid,
+        #[arg(long)]
+        file: PathBuf,
+        /// Quiet time after a save before the body is sent; saves in between count once
+        #[arg(long, default_value_t = 300)]
+        debounce_ms: u64,
     },
     /// Patch summary (supports file input)
     PatchSummary {
@@ -112,6 +139,9 @@ pub enum PostsCmd {
         summary: Option<String>,
         #[arg(long)]
         summary_file: Option<PathBuf>,
+        /// `ETag` from a previous read; `*` overwrites whatever is stored
+        #[arg(long, default_value = "*")]
+        if_match: String,
     },
     /// Update status and schedule times
     Status {
@@ -125,6 +155,9 @@ pub enum PostsCmd {
         published_at: Option<String>,
         #[arg(long)]
         archived_at: Option<String>,
+        /// Wait (up to 30 seconds) for the post's render and publish jobs to finish
+        #[arg(long, default_value_t = false)]
+        wait_render: bool,
     },
     /// Replace tag list
     Tags {
@@ -139,7 +172,30 @@ pub enum PostsCmd {
         id: Uuid,
         #[arg(long, default_value_t = true)]
         pinned: bool,
+        /// Position among the pinned posts, lowest first
+        #[arg(long)]
+        order: Option<i32>,
+    },
+    /// Set language and translation group; omit both to clear
+    Translation {
+        #[arg(long)]
+        id: Uuid,
+        #[arg(long)]
+        lang: Option<String>,
+        #[arg(long)]
+        translation_group: Option<Uuid>,
     },
-    /// Delete a post
+    /// Move a post to the trash
     Delete { id: Uuid },
+    /// List trashed posts
+    Trash {
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+        #[arg(long)]
+        cursor: Option<String>,
+    },
+    /// Restore a trashed post
+    Restore { id: Uuid },
+    /// Permanently delete a trashed post
+    Purge { id: Uuid },
 }
@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
+use uuid::Uuid;
+
+use super::super::{FeedContentModeArg, HomepageLayoutArg, HomepageModeArg};
 
 #[derive(Parser, Debug)]
 pub struct SettingsArgs {
@@ -54,4 +57,38 @@ pub struct SettingsPatchArgs {
     pub favicon_svg: Option<String>,
     #[arg(long)]
     pub favicon_svg_file: Option<PathBuf>,
+    /// PNG or ICO upload served as the favicon instead of the SVG
+    #[arg(long, conflicts_with = "clear_favicon_upload")]
+    pub favicon_upload_id: Option<Uuid>,
+    /// Go back to serving the SVG favicon
+    #[arg(long)]
+    pub clear_favicon_upload: bool,
+    /// Serve the public site as 503 while admin and the API stay up
+    #[arg(long)]
+    pub maintenance_enabled: Option<bool>,
+    #[arg(long)]
+    pub maintenance_html: Option<String>,
+    #[arg(long)]
+    pub maintenance_html_file: Option<PathBuf>,
+    #[arg(long)]
+    pub maintenance_retry_after_secs: Option<i32>,
+    /// strftime pattern for dates shown to readers, e.g. "%Y-%m-%d"
+    #[arg(long)]
+    pub date_format: Option<String>,
+    /// What feed items carry: the excerpt, the rendered summary or the full post
+    #[arg(long)]
+    pub feed_content_mode: Option<FeedContentModeArg>,
+    /// Most items listed in the RSS and Atom feeds (1-100)
+    #[arg(long)]
+    pub feed_item_limit: Option<i32>,
+    /// Serve the post feed or a page at `/`; in page mode the feed moves to `/posts`
+    #[arg(long)]
+    pub homepage_mode: Option<HomepageModeArg>,
+    /// Published page served at `/` in page mode
+    #[arg(long)]
+    pub homepage_page_id: Option<Uuid>,
+    /// Lay out the post feed at `/` as a list, a card grid, or a magazine with the
+    /// first pinned or most recent post featured
+    #[arg(long)]
+    pub homepage_layout: Option<HomepageLayoutArg>,
 }
@@ -31,6 +31,15 @@ pub enum UploadsCmd {
     },
     /// Upload a file
     Upload { file: PathBuf },
+    /// Upload every file matching the patterns, skipping ones already uploaded
+    Push {
+        /// Files or glob patterns, e.g. 'assets/**/*.png'
+        #[arg(required = true)]
+        patterns: Vec<String>,
+        /// Uploads in flight at once
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..))]
+        parallel: u8,
+    },
     /// Delete an upload
     Delete { id: Uuid },
 }
@@ -2,6 +2,17 @@ use std::fmt;
 
 use clap::ValueEnum;
 
+/// How command results (and errors) are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Pretty-printed JSON; the stable, machine-readable default.
+    #[default]
+    Json,
+    /// Aligned columns for list responses, field/value rows otherwise.
+    Table,
+    Yaml,
+}
+
 #[derive(Clone, Copy, Debug, ValueEnum)]
 pub enum PostStatusArg {
     Draft,
@@ -59,3 +70,23 @@ pub enum NavDestArg {
     Internal,
     External,
 }
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum FeedContentModeArg {
+    Excerpt,
+    Summary,
+    Full,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum HomepageModeArg {
+    Feed,
+    Page,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum HomepageLayoutArg {
+    List,
+    Grid,
+    Magazine,
+}
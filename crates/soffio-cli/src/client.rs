@@ -1,12 +1,20 @@
 #![deny(clippy::all, clippy::pedantic)]
 
-use reqwest::header::{AUTHORIZATION, HeaderValue};
-use reqwest::{Client, Method, Response, Url};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue, IF_MATCH, RETRY_AFTER};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::args::Cli;
+use crate::args::{Cli, OutputFormat};
 use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Retries of a rate-limited (429) request unless `--max-retries` says otherwise.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+/// Wait before the first retry when the server gives no hint; doubles each retry.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// Longest wait between retries, whatever the server asks for.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Error)]
 pub enum CliError {
@@ -27,8 +35,31 @@ pub enum CliError {
     Http(#[from] reqwest::Error),
     #[error("server error: {0}")]
     Server(String),
+    #[error("status {status} body {body}")]
+    Api { status: StatusCode, body: String },
     #[error("invalid input: {0}")]
     InvalidInput(String),
+    #[error("{failed} of {total} files failed")]
+    Partial { failed: usize, total: usize },
+}
+
+impl CliError {
+    /// Stable name of the error kind, for machine-readable error output.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::MissingSite => "missing_site",
+            Self::MissingKey => "missing_key",
+            Self::KeyFile(_) => "key_file",
+            Self::InputFile { .. } => "input_file",
+            Self::Url(_) => "invalid_url",
+            Self::Http(_) => "http",
+            Self::Server(_) => "server",
+            Self::InvalidInput(_) => "invalid_input",
+            Self::Api { .. } => "api",
+            Self::Partial { .. } => "partial",
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -36,13 +67,50 @@ pub struct Ctx {
     pub client: Client,
     pub base: Url,
     pub key: String,
+    pub output: OutputFormat,
+    pub max_retries: u32,
 }
 
 impl Ctx {
     pub fn new(site: &str, key: String) -> Result<Self, CliError> {
         let base = Url::parse(site)?.join("/")?;
         let client = Client::builder().user_agent(Self::user_agent()).build()?;
-        Ok(Self { client, base, key })
+        Ok(Self {
+            client,
+            base,
+            key,
+            output: OutputFormat::default(),
+            max_retries: DEFAULT_MAX_RETRIES,
+        })
+    }
+
+    #[must_use]
+    pub fn with_output(mut self, output: OutputFormat) -> Self {
+        self.output = output;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Send the request `build` makes, sending a fresh one while the server
+    /// answers 429 and retries remain. Any other response is returned as is.
+    pub async fn send<F>(&self, build: F) -> Result<Response, CliError>
+    where
+        F: Fn() -> Result<RequestBuilder, CliError>,
+    {
+        let mut attempt = 0;
+        loop {
+            let resp = build()?.send().await?;
+            if resp.status() != StatusCode::TOO_MANY_REQUESTS || attempt >= self.max_retries {
+                return Ok(resp);
+            }
+            tokio::time::sleep(retry_delay(resp.headers(), attempt)).await;
+            attempt += 1;
+        }
     }
 
     pub fn user_agent() -> &'static str {
@@ -74,15 +142,52 @@ impl Ctx {
             }
         }
 
-        let mut req = self
-            .client
-            .request(method, url)
-            .header(AUTHORIZATION, self.auth_header()?);
-        if let Some(b) = body {
-            req = req.json(&b);
+        let resp = self
+            .send(|| {
+                let req = self
+                    .client
+                    .request(method.clone(), url.clone())
+                    .header(AUTHORIZATION, self.auth_header()?);
+                Ok(match &body {
+                    Some(b) => req.json(b),
+                    None => req,
+                })
+            })
+            .await?;
+        Self::handle(resp).await
+    }
+
+    /// Send `body` to `path` guarded by an `If-Match` precondition (`*` to
+    /// overwrite).
+    pub async fn request_if_match<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&[(&str, String)]>,
+        if_match: &str,
+        body: serde_json::Value,
+    ) -> Result<T, CliError> {
+        let if_match =
+            HeaderValue::from_str(if_match).map_err(|e| CliError::InvalidInput(e.to_string()))?;
+        let mut url = self.url(path)?;
+        if let Some(q) = query {
+            url.set_query(None);
+            let mut qp = url.query_pairs_mut();
+            for (k, v) in q {
+                qp.append_pair(k, v);
+            }
         }
 
-        let resp = req.send().await?;
+        let resp = self
+            .send(|| {
+                Ok(self
+                    .client
+                    .request(method.clone(), url.clone())
+                    .header(AUTHORIZATION, self.auth_header()?)
+                    .header(IF_MATCH, if_match.clone())
+                    .json(&body))
+            })
+            .await?;
         Self::handle(resp).await
     }
 
@@ -102,19 +207,22 @@ impl Ctx {
             }
         }
 
-        let mut req = self
-            .client
-            .request(method, url)
-            .header(AUTHORIZATION, self.auth_header()?);
-        if let Some(b) = body {
-            req = req.json(&b);
-        }
-
-        let resp = req.send().await?;
+        let resp = self
+            .send(|| {
+                let req = self
+                    .client
+                    .request(method.clone(), url.clone())
+                    .header(AUTHORIZATION, self.auth_header()?);
+                Ok(match &body {
+                    Some(b) => req.json(b),
+                    None => req,
+                })
+            })
+            .await?;
         let status = resp.status();
         if !status.is_success() {
             let text = resp.text().await.unwrap_or_default();
-            return Err(CliError::Server(format!("status {status} body {text}")));
+            return Err(CliError::Api { status, body: text });
         }
         Ok(())
     }
@@ -124,7 +232,7 @@ impl Ctx {
         let bytes = resp.bytes().await?;
         if !status.is_success() {
             let text = String::from_utf8_lossy(&bytes).into_owned();
-            return Err(CliError::Server(format!("status {status} body {text}")));
+            return Err(CliError::Api { status, body: text });
         }
         let val = serde_json::from_slice(&bytes)
             .map_err(|e| CliError::Server(format!("failed to parse body: {e}")))?;
@@ -147,20 +255,46 @@ impl Ctx {
         }
 
         let resp = self
-            .client
-            .request(method, url)
-            .header(AUTHORIZATION, self.auth_header()?)
-            .send()
+            .send(|| {
+                Ok(self
+                    .client
+                    .request(method.clone(), url.clone())
+                    .header(AUTHORIZATION, self.auth_header()?))
+            })
             .await?;
         let status = resp.status();
         if !status.is_success() {
             let text = resp.text().await.unwrap_or_default();
-            return Err(CliError::Server(format!("status {status} body {text}")));
+            return Err(CliError::Api { status, body: text });
         }
         Ok(())
     }
 }
 
+/// How long to wait before retry number `attempt + 1`: what `Retry-After` or
+/// `X-RateLimit-Reset` asks for, else exponential backoff, capped either way.
+#[must_use]
+pub(crate) fn retry_delay(headers: &HeaderMap, attempt: u32) -> Duration {
+    let hinted = [RETRY_AFTER.as_str(), "x-ratelimit-reset"]
+        .into_iter()
+        .find_map(|name| headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok())
+        .map(|secs| Duration::from_secs(seconds_until(secs)));
+    let backoff = RETRY_BASE_DELAY.saturating_mul(2_u32.saturating_pow(attempt));
+    hinted.unwrap_or(backoff).min(RETRY_MAX_DELAY)
+}
+
+/// Reset headers carry either seconds to wait or a Unix timestamp.
+fn seconds_until(value: u64) -> u64 {
+    const TIMESTAMP_FLOOR: u64 = 1_000_000_000;
+    if value < TIMESTAMP_FLOOR {
+        return value;
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    value.saturating_sub(now)
+}
+
 pub fn build_ctx_from_cli(cli: &Cli) -> Result<Ctx, CliError> {
     let site = cli.site.clone().ok_or(CliError::MissingSite)?;
     let key = if let Some(path) = &cli.key_file {
@@ -172,5 +306,7 @@ pub fn build_ctx_from_cli(cli: &Cli) -> Result<Ctx, CliError> {
         cli.api_key_env.clone().ok_or(CliError::MissingKey)?
     };
 
-    Ctx::new(&site, key)
+    Ok(Ctx::new(&site, key)?
+        .with_output(cli.output)
+        .with_max_retries(cli.max_retries))
 }
@@ -1,11 +1,11 @@
 #![deny(clippy::all, clippy::pedantic)]
 
 use reqwest::Method;
-use soffio_api_types::ApiKeyInfoResponse;
+use soffio_api_types::{ApiKeyInfoResponse, ApiKeyRotateAllResponse};
 
 use crate::args::ApiKeysCmd;
 use crate::client::{CliError, Ctx};
-use crate::print::print_json;
+use crate::print::print_output;
 
 pub async fn handle(ctx: &Ctx, cmd: ApiKeysCmd) -> Result<(), CliError> {
     match cmd.action {
@@ -13,7 +13,22 @@ pub async fn handle(ctx: &Ctx, cmd: ApiKeysCmd) -> Result<(), CliError> {
             let res: ApiKeyInfoResponse = ctx
                 .request(Method::GET, "api/v1/api-keys/me", None, None)
                 .await?;
-            print_json(&res)?;
+            print_output(ctx.output, &res)?;
+        }
+        crate::args::ApiKeysAction::RotateAll => {
+            let res: ApiKeyRotateAllResponse = ctx
+                .request(Method::POST, "api/v1/api-keys/rotate-all", None, None)
+                .await?;
+            // The new tokens are printed before any failure is reported: they
+            // are not retrievable again.
+            print_output(ctx.output, &res)?;
+            if !res.failed.is_empty() {
+                return Err(CliError::Server(format!(
+                    "{} of {} active keys were not rotated and keep their old token",
+                    res.failed.len(),
+                    res.failed.len() + res.rotated.len()
+                )));
+            }
         }
     }
     Ok(())
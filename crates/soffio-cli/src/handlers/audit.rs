@@ -4,7 +4,7 @@ use reqwest::Method;
 
 use crate::args::AuditCmd;
 use crate::client::{CliError, Ctx};
-use crate::print::print_json;
+use crate::print::print_output;
 
 pub async fn handle(ctx: &Ctx, cmd: AuditCmd) -> Result<(), CliError> {
     match cmd {
@@ -13,18 +13,39 @@ pub async fn handle(ctx: &Ctx, cmd: AuditCmd) -> Result<(), CliError> {
             action,
             entity_type,
             search,
+            field,
+            from,
+            to,
             limit,
             cursor,
-        } => list(ctx, actor, action, entity_type, search, limit, cursor).await,
+        } => {
+            list(
+                ctx,
+                actor,
+                action,
+                entity_type,
+                search,
+                field,
+                from,
+                to,
+                limit,
+                cursor,
+            )
+            .await
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn list(
     ctx: &Ctx,
     actor: Option<String>,
     action: Option<String>,
     entity_type: Option<String>,
     search: Option<String>,
+    field: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
     limit: u32,
     cursor: Option<String>,
 ) -> Result<(), CliError> {
@@ -41,12 +62,21 @@ async fn list(
     if let Some(s) = search {
         q.push(("search", s));
     }
+    if let Some(f) = field {
+        q.push(("field", f));
+    }
+    if let Some(f) = from {
+        q.push(("from", f));
+    }
+    if let Some(t) = to {
+        q.push(("to", t));
+    }
     if let Some(c) = cursor {
         q.push(("cursor", c));
     }
     let res: serde_json::Value = ctx
         .request(Method::GET, "api/v1/audit", Some(&q), None)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
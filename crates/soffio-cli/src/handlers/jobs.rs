@@ -4,7 +4,7 @@ use reqwest::Method;
 
 use crate::args::JobsCmd;
 use crate::client::{CliError, Ctx};
-use crate::print::print_json;
+use crate::print::print_output;
 
 pub async fn handle(ctx: &Ctx, cmd: JobsCmd) -> Result<(), CliError> {
     match cmd {
@@ -15,6 +15,7 @@ pub async fn handle(ctx: &Ctx, cmd: JobsCmd) -> Result<(), CliError> {
             limit,
             cursor,
         } => list(ctx, state, job_type, search, limit, cursor).await,
+        JobsCmd::Get { id } => get(ctx, &id).await,
     }
 }
 
@@ -42,6 +43,13 @@ async fn list(
     let res: serde_json::Value = ctx
         .request(Method::GET, "api/v1/jobs", Some(&q), None)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
+    Ok(())
+}
+
+async fn get(ctx: &Ctx, id: &str) -> Result<(), CliError> {
+    let path = format!("api/v1/jobs/{id}");
+    let res: serde_json::Value = ctx.request(Method::GET, &path, None, None).await?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
@@ -11,7 +11,7 @@ use uuid::Uuid;
 use crate::args::{NavCmd, NavDestArg};
 use crate::client::{CliError, Ctx};
 use crate::io::to_value;
-use crate::print::print_json;
+use crate::print::print_output;
 
 pub async fn handle(ctx: &Ctx, cmd: NavCmd) -> Result<(), CliError> {
     match cmd {
@@ -137,14 +137,14 @@ async fn list(
     let res: serde_json::Value = ctx
         .request(Method::GET, "api/v1/navigation", Some(&q), None)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
 async fn get(ctx: &Ctx, id: Uuid) -> Result<(), CliError> {
     let path = format!("api/v1/navigation/{id}");
     let res: serde_json::Value = ctx.request(Method::GET, &path, None, None).await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -176,7 +176,7 @@ async fn create(ctx: &Ctx, input: NavigationCreateInput) -> Result<(), CliError>
             Some(to_value(payload)?),
         )
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -205,7 +205,7 @@ async fn update(ctx: &Ctx, input: NavigationUpdateInput) -> Result<(), CliError>
     let res: serde_json::Value = ctx
         .request(Method::PATCH, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -215,7 +215,7 @@ async fn patch_label(ctx: &Ctx, id: Uuid, label: String) -> Result<(), CliError>
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -235,7 +235,7 @@ async fn patch_destination(
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -245,7 +245,7 @@ async fn patch_sort(ctx: &Ctx, id: Uuid, sort_order: i32) -> Result<(), CliError
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -255,7 +255,7 @@ async fn patch_visibility(ctx: &Ctx, id: Uuid, visible: bool) -> Result<(), CliE
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -265,7 +265,7 @@ async fn patch_open(ctx: &Ctx, id: Uuid, open_in_new_tab: bool) -> Result<(), Cl
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -5,14 +5,14 @@ use std::path::PathBuf;
 use reqwest::Method;
 use soffio_api_types::{
     PageBodyRequest, PageCreateRequest, PageStatus, PageStatusRequest, PageTitleRequest,
-    PageUpdateRequest,
+    PageTranslationRequest, PageUpdateRequest,
 };
 use uuid::Uuid;
 
 use crate::args::{PageStatusArg, PagesCmd};
 use crate::client::{CliError, Ctx};
 use crate::io::{parse_time_opt, read_value, to_value};
-use crate::print::print_json;
+use crate::print::print_output;
 
 struct PageCreateInput {
     slug: Option<String>,
@@ -23,6 +23,15 @@ struct PageCreateInput {
     scheduled_at: Option<String>,
     published_at: Option<String>,
     archived_at: Option<String>,
+    template: Option<String>,
+}
+
+struct PageUpdateInput {
+    slug: String,
+    title: String,
+    body: Option<String>,
+    body_file: Option<PathBuf>,
+    template: Option<String>,
 }
 
 pub async fn handle(ctx: &Ctx, cmd: PagesCmd) -> Result<(), CliError> {
@@ -44,6 +53,7 @@ pub async fn handle(ctx: &Ctx, cmd: PagesCmd) -> Result<(), CliError> {
             scheduled_at,
             published_at,
             archived_at,
+            template,
         } => {
             let input = PageCreateInput {
                 slug,
@@ -54,6 +64,7 @@ pub async fn handle(ctx: &Ctx, cmd: PagesCmd) -> Result<(), CliError> {
                 scheduled_at,
                 published_at,
                 archived_at,
+                template,
             };
             create(ctx, input).await
         }
@@ -63,13 +74,29 @@ pub async fn handle(ctx: &Ctx, cmd: PagesCmd) -> Result<(), CliError> {
             title,
             body,
             body_file,
-        } => update(ctx, id, slug, title, body, body_file).await,
-        PagesCmd::PatchTitle { id, title } => patch_title(ctx, id, title).await,
+            template,
+            if_match,
+        } => {
+            let input = PageUpdateInput {
+                slug,
+                title,
+                body,
+                body_file,
+                template,
+            };
+            update(ctx, id, input, &if_match).await
+        }
+        PagesCmd::PatchTitle {
+            id,
+            title,
+            if_match,
+        } => patch_title(ctx, id, title, &if_match).await,
         PagesCmd::PatchBody {
             id,
             body,
             body_file,
-        } => patch_body(ctx, id, body, body_file).await,
+            if_match,
+        } => patch_body(ctx, id, body, body_file, &if_match).await,
         PagesCmd::Status {
             id,
             status,
@@ -77,7 +104,15 @@ pub async fn handle(ctx: &Ctx, cmd: PagesCmd) -> Result<(), CliError> {
             published_at,
             archived_at,
         } => update_status(ctx, id, status, scheduled_at, published_at, archived_at).await,
+        PagesCmd::Translation {
+            id,
+            lang,
+            translation_group,
+        } => set_translation(ctx, id, lang, translation_group).await,
         PagesCmd::Delete { id } => delete(ctx, id).await,
+        PagesCmd::Trash { limit, cursor } => list_trash(ctx, limit, cursor).await,
+        PagesCmd::Restore { id } => restore(ctx, id).await,
+        PagesCmd::Purge { id } => purge(ctx, id).await,
     }
 }
 
@@ -105,7 +140,7 @@ async fn list(
     let res: serde_json::Value = ctx
         .request(Method::GET, "api/v1/pages", Some(&q), None)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -121,7 +156,7 @@ async fn get(ctx: &Ctx, id: Option<Uuid>, slug: Option<String>) -> Result<(), Cl
     };
 
     let res: serde_json::Value = ctx.request(Method::GET, &path, None, None).await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -135,6 +170,7 @@ async fn create(ctx: &Ctx, input: PageCreateInput) -> Result<(), CliError> {
         scheduled_at,
         published_at,
         archived_at,
+        template,
     } = input;
 
     let body_markdown = read_value(body, body_file)?;
@@ -146,43 +182,51 @@ async fn create(ctx: &Ctx, input: PageCreateInput) -> Result<(), CliError> {
         scheduled_at: parse_time_opt(scheduled_at)?,
         published_at: parse_time_opt(published_at)?,
         archived_at: parse_time_opt(archived_at)?,
+        template,
     };
     let res: serde_json::Value = ctx
         .request(Method::POST, "api/v1/pages", None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
 async fn update(
     ctx: &Ctx,
     id: Uuid,
-    slug: String,
-    title: String,
-    body: Option<String>,
-    body_file: Option<PathBuf>,
+    input: PageUpdateInput,
+    if_match: &str,
 ) -> Result<(), CliError> {
+    let PageUpdateInput {
+        slug,
+        title,
+        body,
+        body_file,
+        template,
+    } = input;
+
     let body_markdown = read_value(body, body_file)?;
     let payload = PageUpdateRequest {
         slug,
         title,
         body_markdown,
+        template,
     };
     let path = format!("api/v1/pages/{id}");
     let res: serde_json::Value = ctx
-        .request(Method::PATCH, &path, None, Some(to_value(payload)?))
+        .request_if_match(Method::PATCH, &path, None, if_match, to_value(payload)?)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
-async fn patch_title(ctx: &Ctx, id: Uuid, title: String) -> Result<(), CliError> {
+async fn patch_title(ctx: &Ctx, id: Uuid, title: String, if_match: &str) -> Result<(), CliError> {
     let payload = PageTitleRequest { title };
     let path = format!("api/v1/pages/{id}/title");
     let res: serde_json::Value = ctx
-        .request(Method::POST, &path, None, Some(to_value(payload)?))
+        .request_if_match(Method::POST, &path, None, if_match, to_value(payload)?)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -191,14 +235,15 @@ async fn patch_body(
     id: Uuid,
     body: Option<String>,
     body_file: Option<PathBuf>,
+    if_match: &str,
 ) -> Result<(), CliError> {
     let body_markdown = read_value(body, body_file)?;
     let payload = PageBodyRequest { body_markdown };
     let path = format!("api/v1/pages/{id}/body");
     let res: serde_json::Value = ctx
-        .request(Method::POST, &path, None, Some(to_value(payload)?))
+        .request_if_match(Method::POST, &path, None, if_match, to_value(payload)?)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -220,7 +265,25 @@ async fn update_status(
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
+    Ok(())
+}
+
+async fn set_translation(
+    ctx: &Ctx,
+    id: Uuid,
+    lang: Option<String>,
+    translation_group: Option<Uuid>,
+) -> Result<(), CliError> {
+    let payload = PageTranslationRequest {
+        lang,
+        translation_group,
+    };
+    let path = format!("api/v1/pages/{id}/translation");
+    let res: serde_json::Value = ctx
+        .request(Method::POST, &path, None, Some(to_value(payload)?))
+        .await?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -231,6 +294,32 @@ async fn delete(ctx: &Ctx, id: Uuid) -> Result<(), CliError> {
     Ok(())
 }
 
+async fn list_trash(ctx: &Ctx, limit: u32, cursor: Option<String>) -> Result<(), CliError> {
+    let mut q = vec![("limit", limit.to_string())];
+    if let Some(c) = cursor {
+        q.push(("cursor", c));
+    }
+    let res: serde_json::Value = ctx
+        .request(Method::GET, "api/v1/pages/trash", Some(&q), None)
+        .await?;
+    print_output(ctx.output, &res)?;
+    Ok(())
+}
+
+async fn restore(ctx: &Ctx, id: Uuid) -> Result<(), CliError> {
+    let path = format!("api/v1/pages/trash/{id}/restore");
+    let res: serde_json::Value = ctx.request(Method::POST, &path, None, None).await?;
+    print_output(ctx.output, &res)?;
+    Ok(())
+}
+
+async fn purge(ctx: &Ctx, id: Uuid) -> Result<(), CliError> {
+    let path = format!("api/v1/pages/trash/{id}");
+    ctx.request_no_body(Method::DELETE, &path, None).await?;
+    println!("purged");
+    Ok(())
+}
+
 impl From<PageStatusArg> for PageStatus {
     fn from(value: PageStatusArg) -> Self {
         match value {
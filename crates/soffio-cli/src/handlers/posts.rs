@@ -1,19 +1,25 @@
 #![deny(clippy::all, clippy::pedantic)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use reqwest::Method;
+use serde_json::json;
 use soffio_api_types::{
     PostBodyRequest, PostCreateRequest, PostExcerptRequest, PostPinRequest, PostStatus,
-    PostStatusRequest, PostSummaryRequest, PostTagsRequest, PostTitleRequest, PostUpdateRequest,
+    PostStatusRequest, PostSummaryRequest, PostTagsRequest, PostTitleRequest,
+    PostTranslationRequest, PostUpdateRequest,
 };
+use tokio::sync::mpsc::UnboundedReceiver;
 use uuid::Uuid;
 
 use crate::args::{PostStatusArg, PostsCmd};
 use crate::client::{CliError, Ctx};
 use crate::io::{parse_time_opt, read_opt_value, read_value, to_value};
-use crate::print::print_json;
+use crate::print::{print_error, print_output};
+use crate::watch::{settled, watch_file};
 
+#[allow(clippy::too_many_lines)]
 pub async fn handle(ctx: &Ctx, cmd: PostsCmd) -> Result<(), CliError> {
     match cmd {
         PostsCmd::List {
@@ -27,6 +33,7 @@ pub async fn handle(ctx: &Ctx, cmd: PostsCmd) -> Result<(), CliError> {
         PostsCmd::Get { id, slug } => get(ctx, id, slug).await,
         PostsCmd::Create {
             title,
+            slug,
             excerpt,
             body,
             body_file,
@@ -40,6 +47,7 @@ pub async fn handle(ctx: &Ctx, cmd: PostsCmd) -> Result<(), CliError> {
         } => {
             let input = PostCreateInput {
                 title,
+                slug,
                 excerpt,
                 body,
                 body_file,
@@ -63,6 +71,7 @@ pub async fn handle(ctx: &Ctx, cmd: PostsCmd) -> Result<(), CliError> {
             summary,
             summary_file,
             pinned,
+            if_match,
         } => {
             let input = PostUpdateInput {
                 id,
@@ -74,36 +83,73 @@ pub async fn handle(ctx: &Ctx, cmd: PostsCmd) -> Result<(), CliError> {
                 summary,
                 summary_file,
                 pinned,
+                if_match,
             };
             update(ctx, input).await
         }
-        PostsCmd::PatchTitle { id, title } => patch_title(ctx, id, title).await,
-        PostsCmd::PatchExcerpt { id, excerpt } => patch_excerpt(ctx, id, excerpt).await,
+        PostsCmd::PatchTitle {
+            id,
+            title,
+            if_match,
+        } => patch_title(ctx, id, title, &if_match).await,
+        PostsCmd::PatchExcerpt {
+            id,
+            excerpt,
+            if_match,
+        } => patch_excerpt(ctx, id, excerpt, &if_match).await,
         PostsCmd::PatchBody {
             id,
             body,
             body_file,
-        } => patch_body(ctx, id, body, body_file).await,
+            if_match,
+        } => patch_body(ctx, id, body, body_file, &if_match).await,
+        PostsCmd::Watch {
+            id,
+            file,
+            debounce_ms,
+        } => watch(ctx, id, &file, Duration::from_millis(debounce_ms)).await,
         PostsCmd::PatchSummary {
             id,
             summary,
             summary_file,
-        } => patch_summary(ctx, id, summary, summary_file).await,
+            if_match,
+        } => patch_summary(ctx, id, summary, summary_file, &if_match).await,
         PostsCmd::Status {
             id,
             status,
             scheduled_at,
             published_at,
             archived_at,
-        } => update_status(ctx, id, status, scheduled_at, published_at, archived_at).await,
+            wait_render,
+        } => {
+            update_status(
+                ctx,
+                id,
+                status,
+                scheduled_at,
+                published_at,
+                archived_at,
+                wait_render,
+            )
+            .await
+        }
         PostsCmd::Tags { id, tag_ids } => replace_tags(ctx, id, tag_ids).await,
-        PostsCmd::Pin { id, pinned } => pin(ctx, id, pinned).await,
+        PostsCmd::Pin { id, pinned, order } => pin(ctx, id, pinned, order).await,
+        PostsCmd::Translation {
+            id,
+            lang,
+            translation_group,
+        } => set_translation(ctx, id, lang, translation_group).await,
         PostsCmd::Delete { id } => delete(ctx, id).await,
+        PostsCmd::Trash { limit, cursor } => list_trash(ctx, limit, cursor).await,
+        PostsCmd::Restore { id } => restore(ctx, id).await,
+        PostsCmd::Purge { id } => purge(ctx, id).await,
     }
 }
 
 struct PostCreateInput {
     title: String,
+    slug: Option<String>,
     excerpt: String,
     body: Option<String>,
     body_file: Option<PathBuf>,
@@ -126,6 +172,7 @@ struct PostUpdateInput {
     summary: Option<String>,
     summary_file: Option<PathBuf>,
     pinned: bool,
+    if_match: String,
 }
 
 async fn list(
@@ -156,7 +203,7 @@ async fn list(
     let res: serde_json::Value = ctx
         .request(Method::GET, "api/v1/posts", Some(&q), None)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -172,13 +219,14 @@ async fn get(ctx: &Ctx, id: Option<Uuid>, slug: Option<String>) -> Result<(), Cl
     };
 
     let res: serde_json::Value = ctx.request(Method::GET, &path, None, None).await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
 async fn create(ctx: &Ctx, input: PostCreateInput) -> Result<(), CliError> {
     let PostCreateInput {
         title,
+        slug,
         excerpt,
         body,
         body_file,
@@ -195,6 +243,7 @@ async fn create(ctx: &Ctx, input: PostCreateInput) -> Result<(), CliError> {
     let summary_markdown = read_opt_value(summary, summary_file)?;
     let payload = PostCreateRequest {
         title,
+        slug,
         excerpt,
         body_markdown,
         summary_markdown,
@@ -207,7 +256,7 @@ async fn create(ctx: &Ctx, input: PostCreateInput) -> Result<(), CliError> {
     let res: serde_json::Value = ctx
         .request(Method::POST, "api/v1/posts", None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -222,6 +271,7 @@ async fn update(ctx: &Ctx, input: PostUpdateInput) -> Result<(), CliError> {
         summary,
         summary_file,
         pinned,
+        if_match,
     } = input;
 
     let body_markdown = read_value(body, body_file)?;
@@ -236,29 +286,34 @@ async fn update(ctx: &Ctx, input: PostUpdateInput) -> Result<(), CliError> {
     };
     let path = format!("api/v1/posts/{id}");
     let res: serde_json::Value = ctx
-        .request(Method::PATCH, &path, None, Some(to_value(payload)?))
+        .request_if_match(Method::PATCH, &path, None, &if_match, to_value(payload)?)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
-async fn patch_title(ctx: &Ctx, id: Uuid, title: String) -> Result<(), CliError> {
+async fn patch_title(ctx: &Ctx, id: Uuid, title: String, if_match: &str) -> Result<(), CliError> {
     let payload = PostTitleRequest { title };
     let path = format!("api/v1/posts/{id}/title");
     let res: serde_json::Value = ctx
-        .request(Method::POST, &path, None, Some(to_value(payload)?))
+        .request_if_match(Method::POST, &path, None, if_match, to_value(payload)?)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
-async fn patch_excerpt(ctx: &Ctx, id: Uuid, excerpt: String) -> Result<(), CliError> {
+async fn patch_excerpt(
+    ctx: &Ctx,
+    id: Uuid,
+    excerpt: String,
+    if_match: &str,
+) -> Result<(), CliError> {
     let payload = PostExcerptRequest { excerpt };
     let path = format!("api/v1/posts/{id}/excerpt");
     let res: serde_json::Value = ctx
-        .request(Method::POST, &path, None, Some(to_value(payload)?))
+        .request_if_match(Method::POST, &path, None, if_match, to_value(payload)?)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -267,30 +322,92 @@ async fn patch_body(
     id: Uuid,
     body: Option<String>,
     body_file: Option<PathBuf>,
+    if_match: &str,
 ) -> Result<(), CliError> {
     let body_markdown = read_value(body, body_file)?;
     let payload = PostBodyRequest { body_markdown };
     let path = format!("api/v1/posts/{id}/body");
     let res: serde_json::Value = ctx
-        .request(Method::POST, &path, None, Some(to_value(payload)?))
+        .request_if_match(Method::POST, &path, None, if_match, to_value(payload)?)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
+    Ok(())
+}
+
+async fn watch(ctx: &Ctx, id: Uuid, file: &Path, debounce: Duration) -> Result<(), CliError> {
+    let (_watcher, saves) = watch_file(file)?;
+    eprintln!(
+        "watching {} for post {id}; press Ctrl-C to stop",
+        file.display()
+    );
+    push_saves(ctx, id, file, saves, debounce).await;
     Ok(())
 }
 
+/// Send `file` as the body of post `id` after each settled burst of `saves`,
+/// until they end. Failures are printed and the next save is tried again, so
+/// a dropped connection does not end the session.
+pub(crate) async fn push_saves(
+    ctx: &Ctx,
+    id: Uuid,
+    file: &Path,
+    mut saves: UnboundedReceiver<()>,
+    debounce: Duration,
+) {
+    let mut last_sent: Option<String> = None;
+    while settled(&mut saves, debounce).await {
+        let body_markdown = match read_value(None, Some(file.to_path_buf())) {
+            Ok(body) => body,
+            Err(err) => {
+                print_error(ctx.output, &err);
+                continue;
+            }
+        };
+        if last_sent.as_deref() == Some(body_markdown.as_str()) {
+            continue;
+        }
+        match push_body(ctx, id, body_markdown.clone()).await {
+            Ok(()) => last_sent = Some(body_markdown),
+            Err(err) => print_error(ctx.output, &err),
+        }
+    }
+}
+
+async fn push_body(ctx: &Ctx, id: Uuid, body_markdown: String) -> Result<(), CliError> {
+    let payload = PostBodyRequest { body_markdown };
+    let path = format!("api/v1/posts/{id}/body");
+    let query = [("wait_render", "true".to_string())];
+    // The file is the source of truth while watching, so each save overwrites.
+    let res: serde_json::Value = ctx
+        .request_if_match(Method::POST, &path, Some(&query), "*", to_value(payload)?)
+        .await?;
+    let progress = &res["render_progress"];
+    print_output(
+        ctx.output,
+        &json!({
+            "id": res["id"],
+            "updated_at": res["updated_at"],
+            "render": progress["state"],
+            "job_id": progress["job_id"],
+            "error": progress["error"],
+        }),
+    )
+}
+
 async fn patch_summary(
     ctx: &Ctx,
     id: Uuid,
     summary: Option<String>,
     summary_file: Option<PathBuf>,
+    if_match: &str,
 ) -> Result<(), CliError> {
     let summary_markdown = read_opt_value(summary, summary_file)?;
     let payload = PostSummaryRequest { summary_markdown };
     let path = format!("api/v1/posts/{id}/summary");
     let res: serde_json::Value = ctx
-        .request(Method::POST, &path, None, Some(to_value(payload)?))
+        .request_if_match(Method::POST, &path, None, if_match, to_value(payload)?)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -301,6 +418,7 @@ async fn update_status(
     scheduled_at: Option<String>,
     published_at: Option<String>,
     archived_at: Option<String>,
+    wait_render: bool,
 ) -> Result<(), CliError> {
     let payload = PostStatusRequest {
         status: status.into(),
@@ -309,10 +427,11 @@ async fn update_status(
         archived_at: parse_time_opt(archived_at)?,
     };
     let path = format!("api/v1/posts/{id}/status");
+    let query = [("wait_render", wait_render.to_string())];
     let res: serde_json::Value = ctx
-        .request(Method::POST, &path, None, Some(to_value(payload)?))
+        .request(Method::POST, &path, Some(&query), Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -331,13 +450,34 @@ async fn replace_tags(ctx: &Ctx, id: Uuid, tag_ids: String) -> Result<(), CliErr
     Ok(())
 }
 
-async fn pin(ctx: &Ctx, id: Uuid, pinned: bool) -> Result<(), CliError> {
-    let payload = PostPinRequest { pinned };
+async fn pin(ctx: &Ctx, id: Uuid, pinned: bool, order: Option<i32>) -> Result<(), CliError> {
+    let payload = PostPinRequest {
+        pinned,
+        pin_order: order,
+    };
     let path = format!("api/v1/posts/{id}/pin");
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
+    Ok(())
+}
+
+async fn set_translation(
+    ctx: &Ctx,
+    id: Uuid,
+    lang: Option<String>,
+    translation_group: Option<Uuid>,
+) -> Result<(), CliError> {
+    let payload = PostTranslationRequest {
+        lang,
+        translation_group,
+    };
+    let path = format!("api/v1/posts/{id}/translation");
+    let res: serde_json::Value = ctx
+        .request(Method::POST, &path, None, Some(to_value(payload)?))
+        .await?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -348,6 +488,32 @@ async fn delete(ctx: &Ctx, id: Uuid) -> Result<(), CliError> {
     Ok(())
 }
 
+async fn list_trash(ctx: &Ctx, limit: u32, cursor: Option<String>) -> Result<(), CliError> {
+    let mut q = vec![("limit", limit.to_string())];
+    if let Some(c) = cursor {
+        q.push(("cursor", c));
+    }
+    let res: serde_json::Value = ctx
+        .request(Method::GET, "api/v1/posts/trash", Some(&q), None)
+        .await?;
+    print_output(ctx.output, &res)?;
+    Ok(())
+}
+
+async fn restore(ctx: &Ctx, id: Uuid) -> Result<(), CliError> {
+    let path = format!("api/v1/posts/trash/{id}/restore");
+    let res: serde_json::Value = ctx.request(Method::POST, &path, None, None).await?;
+    print_output(ctx.output, &res)?;
+    Ok(())
+}
+
+async fn purge(ctx: &Ctx, id: Uuid) -> Result<(), CliError> {
+    let path = format!("api/v1/posts/trash/{id}");
+    ctx.request_no_body(Method::DELETE, &path, None).await?;
+    println!("purged");
+    Ok(())
+}
+
 impl From<PostStatusArg> for PostStatus {
     fn from(value: PostStatusArg) -> Self {
         match value {
@@ -1,12 +1,14 @@
 #![deny(clippy::all, clippy::pedantic)]
 
 use reqwest::Method;
-use soffio_api_types::SettingsPatchRequest;
+use soffio_api_types::{FeedContentMode, HomepageLayout, HomepageMode, SettingsPatchRequest};
 
-use crate::args::{SettingsCmd, SettingsPatchArgs};
+use crate::args::{
+    FeedContentModeArg, HomepageLayoutArg, HomepageModeArg, SettingsCmd, SettingsPatchArgs,
+};
 use crate::client::{CliError, Ctx};
 use crate::io::{read_opt_value, to_value};
-use crate::print::print_json;
+use crate::print::print_output;
 
 pub async fn handle(ctx: &Ctx, cmd: SettingsCmd) -> Result<(), CliError> {
     match cmd {
@@ -19,7 +21,7 @@ async fn get(ctx: &Ctx) -> Result<(), CliError> {
     let res: serde_json::Value = ctx
         .request(Method::GET, "api/v1/site/settings", None, None)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -43,9 +45,27 @@ async fn patch(ctx: &Ctx, settings: SettingsPatchArgs) -> Result<(), CliError> {
         global_toc_enabled,
         favicon_svg,
         favicon_svg_file,
+        favicon_upload_id,
+        clear_favicon_upload,
+        maintenance_enabled,
+        maintenance_html,
+        maintenance_html_file,
+        maintenance_retry_after_secs,
+        date_format,
+        feed_content_mode,
+        feed_item_limit,
+        homepage_mode,
+        homepage_page_id,
+        homepage_layout,
     } = settings;
 
     let favicon_svg = read_opt_value(favicon_svg, favicon_svg_file)?;
+    let maintenance_html = read_opt_value(maintenance_html, maintenance_html_file)?;
+    let favicon_upload_id = if clear_favicon_upload {
+        Some(None)
+    } else {
+        favicon_upload_id.map(Some)
+    };
     let payload = SettingsPatchRequest {
         brand_title,
         brand_href,
@@ -64,6 +84,16 @@ async fn patch(ctx: &Ctx, settings: SettingsPatchArgs) -> Result<(), CliError> {
         public_site_url,
         global_toc_enabled,
         favicon_svg,
+        favicon_upload_id,
+        maintenance_enabled,
+        maintenance_html,
+        maintenance_retry_after_secs,
+        date_format,
+        feed_content_mode: feed_content_mode.map(FeedContentMode::from),
+        feed_item_limit,
+        homepage_mode: homepage_mode.map(HomepageMode::from),
+        homepage_page_id: homepage_page_id.map(Some),
+        homepage_layout: homepage_layout.map(HomepageLayout::from),
     };
     let res: serde_json::Value = ctx
         .request(
@@ -73,6 +103,35 @@ async fn patch(ctx: &Ctx, settings: SettingsPatchArgs) -> Result<(), CliError> {
             Some(to_value(payload)?),
         )
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
+
+impl From<FeedContentModeArg> for FeedContentMode {
+    fn from(value: FeedContentModeArg) -> Self {
+        match value {
+            FeedContentModeArg::Excerpt => FeedContentMode::Excerpt,
+            FeedContentModeArg::Summary => FeedContentMode::Summary,
+            FeedContentModeArg::Full => FeedContentMode::Full,
+        }
+    }
+}
+
+impl From<HomepageModeArg> for HomepageMode {
+    fn from(value: HomepageModeArg) -> Self {
+        match value {
+            HomepageModeArg::Feed => HomepageMode::Feed,
+            HomepageModeArg::Page => HomepageMode::Page,
+        }
+    }
+}
+
+impl From<HomepageLayoutArg> for HomepageLayout {
+    fn from(value: HomepageLayoutArg) -> Self {
+        match value {
+            HomepageLayoutArg::List => HomepageLayout::List,
+            HomepageLayoutArg::Grid => HomepageLayout::Grid,
+            HomepageLayoutArg::Magazine => HomepageLayout::Magazine,
+        }
+    }
+}
@@ -32,12 +32,12 @@ pub async fn handle(ctx: &Ctx, cmd: SnapshotsCmd) -> Result<(), CliError> {
             let resp: serde_json::Value = ctx
                 .request(Method::GET, "/api/v1/snapshots", Some(&q), None)
                 .await?;
-            print::json_value(&resp)?;
+            print::print_output(ctx.output, &resp)?;
         }
         SnapshotsCmd::Get { id } => {
             let path = format!("/api/v1/snapshots/{id}");
             let resp: serde_json::Value = ctx.request(Method::GET, &path, None, None).await?;
-            print::json_value(&resp)?;
+            print::print_output(ctx.output, &resp)?;
         }
         SnapshotsCmd::Create {
             entity_type,
@@ -52,7 +52,7 @@ pub async fn handle(ctx: &Ctx, cmd: SnapshotsCmd) -> Result<(), CliError> {
             let resp: serde_json::Value = ctx
                 .request(Method::POST, "/api/v1/snapshots", None, Some(body))
                 .await?;
-            print::json_value(&resp)?;
+            print::print_output(ctx.output, &resp)?;
         }
         SnapshotsCmd::Rollback { id } => {
             let path = format!("/api/v1/snapshots/{id}/rollback");
@@ -11,7 +11,7 @@ use uuid::Uuid;
 use crate::args::TagsCmd;
 use crate::client::{CliError, Ctx};
 use crate::io::{read_opt_value, to_value};
-use crate::print::print_json;
+use crate::print::print_output;
 
 pub async fn handle(ctx: &Ctx, cmd: TagsCmd) -> Result<(), CliError> {
     match cmd {
@@ -71,7 +71,7 @@ async fn list(
     let res: serde_json::Value = ctx
         .request(Method::GET, "api/v1/tags", Some(&q), None)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -87,7 +87,7 @@ async fn get(ctx: &Ctx, id: Option<Uuid>, slug: Option<String>) -> Result<(), Cl
     };
 
     let res: serde_json::Value = ctx.request(Method::GET, &path, None, None).await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -107,7 +107,7 @@ async fn create(
     let res: serde_json::Value = ctx
         .request(Method::POST, "api/v1/tags", None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -129,7 +129,7 @@ async fn update(
     let res: serde_json::Value = ctx
         .request(Method::PATCH, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -139,7 +139,7 @@ async fn patch_pin(ctx: &Ctx, id: Uuid, pinned: bool) -> Result<(), CliError> {
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -149,7 +149,7 @@ async fn patch_name(ctx: &Ctx, id: Uuid, name: String) -> Result<(), CliError> {
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
@@ -165,7 +165,7 @@ async fn patch_description(
     let res: serde_json::Value = ctx
         .request(Method::POST, &path, None, Some(to_value(payload)?))
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
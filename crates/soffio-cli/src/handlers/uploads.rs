@@ -1,15 +1,22 @@
 #![deny(clippy::all, clippy::pedantic)]
 
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Write as _;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use reqwest::Method;
+use futures::stream::{self, StreamExt};
 use reqwest::header::AUTHORIZATION;
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::args::UploadsCmd;
 use crate::client::{CliError, Ctx};
-use crate::print::print_json;
+use crate::print::print_output;
 
 pub async fn handle(ctx: &Ctx, cmd: UploadsCmd) -> Result<(), CliError> {
     match cmd {
@@ -22,6 +29,7 @@ pub async fn handle(ctx: &Ctx, cmd: UploadsCmd) -> Result<(), CliError> {
         } => list(ctx, content_type, search, month, limit, cursor).await,
         UploadsCmd::Get { id } => get(ctx, id).await,
         UploadsCmd::Upload { file } => upload(ctx, file).await,
+        UploadsCmd::Push { patterns, parallel } => push(ctx, &patterns, parallel.into()).await,
         UploadsCmd::Delete { id } => delete(ctx, id).await,
     }
 }
@@ -50,43 +58,269 @@ async fn list(
     let res: serde_json::Value = ctx
         .request(Method::GET, "api/v1/uploads", Some(&q), None)
         .await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
 async fn get(ctx: &Ctx, id: Uuid) -> Result<(), CliError> {
     let path = format!("api/v1/uploads/{id}");
     let res: serde_json::Value = ctx.request(Method::GET, &path, None, None).await?;
-    print_json(&res)?;
+    print_output(ctx.output, &res)?;
     Ok(())
 }
 
 async fn upload(ctx: &Ctx, file: PathBuf) -> Result<(), CliError> {
-    let url = ctx.url("api/v1/uploads")?;
     let data = fs::read(&file).map_err(CliError::KeyFile)?;
-    let part = reqwest::multipart::Part::bytes(data).file_name(
-        file.file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("upload.bin")
-            .to_string(),
-    );
-    let form = reqwest::multipart::Form::new().part("file", part);
+    let res = upload_bytes(ctx, file_name(&file), data).await?;
+    print_output(ctx.output, &res)?;
+    Ok(())
+}
+
+fn file_name(file: &Path) -> String {
+    file.file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("upload.bin")
+        .to_string()
+}
+
+async fn upload_bytes(ctx: &Ctx, file_name: String, data: Vec<u8>) -> Result<Value, CliError> {
+    let url = ctx.url("api/v1/uploads")?;
+    // A multipart body is consumed by sending, so every retry builds a new one.
     let resp = ctx
-        .client
-        .post(url)
-        .header(AUTHORIZATION, ctx.auth_header()?)
-        .multipart(form)
-        .send()
+        .send(|| {
+            let part = reqwest::multipart::Part::bytes(data.clone()).file_name(file_name.clone());
+            let form = reqwest::multipart::Form::new().part("file", part);
+            Ok(ctx
+                .client
+                .post(url.clone())
+                .header(AUTHORIZATION, ctx.auth_header()?)
+                .multipart(form))
+        })
         .await?;
     let status = resp.status();
     let text = resp.text().await.unwrap_or_default();
     if !status.is_success() {
-        return Err(CliError::Server(format!("status {status} body {text}")));
+        return Err(CliError::Api { status, body: text });
+    }
+    serde_json::from_str(&text).map_err(|e| CliError::Server(format!("failed to parse body: {e}")))
+}
+
+/// What `uploads push` did with one file.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PushResult {
+    pub(crate) file: String,
+    /// `uploaded`, `skipped` (the contents were already uploaded) or `failed`.
+    pub(crate) status: &'static str,
+    pub(crate) id: Option<String>,
+    pub(crate) error: Option<String>,
+}
+
+impl PushResult {
+    fn done(file: &Path, status: &'static str, id: String) -> Self {
+        Self {
+            file: file.display().to_string(),
+            status,
+            id: Some(id),
+            error: None,
+        }
+    }
+
+    fn failed(file: &Path, err: &CliError) -> Self {
+        Self {
+            file: file.display().to_string(),
+            status: "failed",
+            id: None,
+            error: Some(failure_message(err)),
+        }
+    }
+}
+
+async fn push(ctx: &Ctx, patterns: &[String], parallel: usize) -> Result<(), CliError> {
+    let results = push_all(ctx, patterns, parallel).await?;
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    let (uploaded, skipped, failed) = (count("uploaded"), count("skipped"), count("failed"));
+    print_output(
+        ctx.output,
+        &json!({
+            "items": results,
+            "uploaded": uploaded,
+            "skipped": skipped,
+            "failed": failed,
+        }),
+    )?;
+    if failed > 0 {
+        return Err(CliError::Partial {
+            failed,
+            total: results.len(),
+        });
     }
-    println!("{text}");
     Ok(())
 }
 
+/// Upload every file matching `patterns`, at most `parallel` at a time.
+///
+/// Files whose SHA-256 is already stored on the server are skipped, and so
+/// are repeats of the same contents within the batch. A failing file is
+/// reported in its result and does not stop the others. Results follow the
+/// order of the matched files.
+pub(crate) async fn push_all(
+    ctx: &Ctx,
+    patterns: &[String],
+    parallel: usize,
+) -> Result<Vec<PushResult>, CliError> {
+    let files = expand_patterns(patterns)?;
+
+    // Hash everything first so repeated contents go up only once.
+    let checksums: Vec<Result<String, CliError>> = files.iter().map(|f| checksum(f)).collect();
+    let mut first_with: HashMap<&str, usize> = HashMap::new();
+    let mut unique = Vec::new();
+    for (index, sum) in checksums.iter().enumerate() {
+        let Ok(sum) = sum else { continue };
+        if let Entry::Vacant(slot) = first_with.entry(sum.as_str()) {
+            slot.insert(index);
+            unique.push(index);
+        }
+    }
+
+    let pushed: HashMap<usize, PushResult> = stream::iter(unique)
+        .map(|index| {
+            let file = &files[index];
+            let sum = checksums[index].as_deref().unwrap_or_default();
+            async move { (index, push_one(ctx, file, sum).await) }
+        })
+        .buffer_unordered(parallel.max(1))
+        .collect()
+        .await;
+
+    let results = files
+        .iter()
+        .zip(&checksums)
+        .enumerate()
+        .map(|(index, (file, sum))| match sum {
+            Err(err) => PushResult::failed(file, err),
+            Ok(sum) => {
+                let first = &pushed[&first_with[sum.as_str()]];
+                if first_with[sum.as_str()] == index {
+                    first.clone()
+                } else {
+                    PushResult {
+                        file: file.display().to_string(),
+                        status: if first.id.is_some() {
+                            "skipped"
+                        } else {
+                            "failed"
+                        },
+                        ..first.clone()
+                    }
+                }
+            }
+        })
+        .collect();
+    Ok(results)
+}
+
+async fn push_one(ctx: &Ctx, file: &Path, checksum: &str) -> PushResult {
+    match find_by_checksum(ctx, checksum).await {
+        Ok(Some(id)) => return PushResult::done(file, "skipped", id),
+        Ok(None) => {}
+        Err(err) => return PushResult::failed(file, &err),
+    }
+    let data = match fs::read(file) {
+        Ok(data) => data,
+        Err(source) => return PushResult::failed(file, &input_error(file, source)),
+    };
+    match upload_bytes(ctx, file_name(file), data).await {
+        Ok(res) => PushResult::done(file, "uploaded", id_of(&res)),
+        // Someone else uploaded the same contents since the lookup.
+        Err(err @ CliError::Api { status, .. }) if status == StatusCode::CONFLICT => {
+            match find_by_checksum(ctx, checksum).await {
+                Ok(Some(id)) => PushResult::done(file, "skipped", id),
+                _ => PushResult::failed(file, &err),
+            }
+        }
+        Err(err) => PushResult::failed(file, &err),
+    }
+}
+
+async fn find_by_checksum(ctx: &Ctx, checksum: &str) -> Result<Option<String>, CliError> {
+    let q = [
+        ("checksum", checksum.to_string()),
+        ("limit", "1".to_string()),
+    ];
+    let res: Value = ctx
+        .request(Method::GET, "api/v1/uploads", Some(&q), None)
+        .await?;
+    Ok(res["items"].get(0).map(id_of))
+}
+
+fn id_of(upload: &Value) -> String {
+    upload["id"]
+        .as_str()
+        .map_or_else(|| upload["id"].to_string(), str::to_string)
+}
+
+/// Files matching `patterns`, in order and without repeats. A pattern that
+/// matches nothing is an error, so a typo is not silently skipped.
+fn expand_patterns(patterns: &[String]) -> Result<Vec<PathBuf>, CliError> {
+    let mut files = Vec::new();
+    for pattern in patterns {
+        let paths = glob::glob(pattern)
+            .map_err(|err| CliError::InvalidInput(format!("invalid pattern {pattern}: {err}")))?;
+        let mut matched = false;
+        for path in paths {
+            let path = path.map_err(|err| {
+                let path = err.path().to_path_buf();
+                input_error(&path, err.into())
+            })?;
+            if path.is_file() {
+                matched = true;
+                if !files.contains(&path) {
+                    files.push(path);
+                }
+            }
+        }
+        if !matched {
+            return Err(CliError::InvalidInput(format!("no files match {pattern}")));
+        }
+    }
+    Ok(files)
+}
+
+fn checksum(file: &Path) -> Result<String, CliError> {
+    let data = fs::read(file).map_err(|source| input_error(file, source))?;
+    Ok(sha256_hex(&data))
+}
+
+/// Lowercase hex SHA-256, as the server records it for uploads.
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .fold(String::with_capacity(64), |mut out, byte| {
+            let _ = write!(out, "{byte:02x}");
+            out
+        })
+}
+
+fn input_error(file: &Path, source: std::io::Error) -> CliError {
+    CliError::InputFile {
+        path: file.display().to_string(),
+        source,
+    }
+}
+
+/// The API's own message for API errors, which is all a per-file report needs.
+fn failure_message(err: &CliError) -> String {
+    if let CliError::Api { status, body } = err {
+        let message = serde_json::from_str::<Value>(body)
+            .ok()
+            .and_then(|body| body["error"]["message"].as_str().map(str::to_string));
+        if let Some(message) = message {
+            return format!("{message} ({status})");
+        }
+    }
+    err.to_string()
+}
+
 async fn delete(ctx: &Ctx, id: Uuid) -> Result<(), CliError> {
     let path = format!("api/v1/uploads/{id}");
     ctx.request_no_body(Method::DELETE, &path, None).await?;
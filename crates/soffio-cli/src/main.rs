@@ -9,6 +9,9 @@ mod io;
 mod print;
 #[cfg(test)]
 mod tests;
+mod watch;
+
+use std::process::ExitCode;
 
 use clap::Parser;
 
@@ -19,8 +22,19 @@ use handlers::{
 };
 
 #[tokio::main]
-async fn main() -> Result<(), CliError> {
+async fn main() -> ExitCode {
     let cli = Cli::parse();
+    let output = cli.output;
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            print::print_error(output, &err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), CliError> {
     let ctx = build_ctx_from_cli(&cli)?;
 
     match cli.command {
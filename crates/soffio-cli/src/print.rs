@@ -1,15 +1,228 @@
 #![deny(clippy::all, clippy::pedantic)]
 
-use crate::client::CliError;
 use serde::Serialize;
+use serde_json::{Map, Value, json};
+use yaml_rust2::{Yaml, YamlEmitter};
+
+use crate::args::OutputFormat;
+use crate::client::CliError;
+
+/// Longest table cell; longer text is cut with an ellipsis.
+const MAX_CELL_CHARS: usize = 48;
+
+/// Columns shown first in tables when present; the rest follow by name.
+const LEADING_COLUMNS: [&str; 5] = ["id", "slug", "name", "title", "status"];
 
-pub fn print_json<T: Serialize>(value: &T) -> Result<(), CliError> {
-    let out = serde_json::to_string_pretty(value)
-        .map_err(|e| CliError::Server(format!("failed to render output: {e}")))?;
+pub fn print_output<T: Serialize>(format: OutputFormat, value: &T) -> Result<(), CliError> {
+    let out = render(format, value)?;
     println!("{out}");
     Ok(())
 }
 
-pub fn json_value(value: &serde_json::Value) -> Result<(), CliError> {
-    print_json(value)
+/// Write `err` to stderr in `format`: an `{"error": {...}}` document for JSON
+/// and YAML, a plain `error: ...` line for tables.
+pub fn print_error(format: OutputFormat, err: &CliError) {
+    let out = match format {
+        OutputFormat::Table => api_error_lines(err),
+        OutputFormat::Json | OutputFormat::Yaml => render(format, &error_value(err)).ok(),
+    };
+    eprintln!("{}", out.unwrap_or_else(|| format!("error: {err}")));
+}
+
+/// Human-readable lines for an API error body: the message, then the hint,
+/// documentation link and whether a retry may help. `None` when the body is
+/// not an API error object.
+fn api_error_lines(err: &CliError) -> Option<String> {
+    let CliError::Api { status, body } = err else {
+        return None;
+    };
+    let body: Value = serde_json::from_str(body).ok()?;
+    let error = body.get("error")?;
+    let code = error.get("code")?.as_str()?;
+    let message = error.get("message").and_then(Value::as_str).unwrap_or(code);
+
+    let mut lines = vec![format!("error: {message} ({code}, status {status})")];
+    if let Some(hint) = error.get("hint").and_then(Value::as_str) {
+        lines.push(format!("  hint: {hint}"));
+    }
+    if let Some(url) = error.get("docs_url").and_then(Value::as_str) {
+        lines.push(format!("  docs: {url}"));
+    }
+    if let Some(retryable) = error.get("retryable").and_then(Value::as_bool) {
+        lines.push(format!(
+            "  retryable: {}",
+            if retryable { "yes" } else { "no" }
+        ));
+    }
+    Some(lines.join("\n"))
+}
+
+pub fn render<T: Serialize>(format: OutputFormat, value: &T) -> Result<String, CliError> {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(value).map_err(|err| render_error(&err)),
+        OutputFormat::Yaml => yaml(&serde_json::to_value(value).map_err(|err| render_error(&err))?),
+        OutputFormat::Table => Ok(table(
+            &serde_json::to_value(value).map_err(|err| render_error(&err))?,
+        )),
+    }
+}
+
+fn render_error<E: std::fmt::Display>(err: &E) -> CliError {
+    CliError::Server(format!("failed to render output: {err}"))
+}
+
+fn error_value(err: &CliError) -> Value {
+    let mut error = json!({ "kind": err.kind(), "message": err.to_string() });
+    if let CliError::Api { status, body } = err {
+        error["status"] = json!(status.as_u16());
+        // The API answers with its own error object; keep it structured.
+        error["response"] =
+            serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.clone()));
+    }
+    json!({ "error": error })
+}
+
+fn yaml(value: &Value) -> Result<String, CliError> {
+    let mut out = String::new();
+    YamlEmitter::new(&mut out)
+        .dump(&to_yaml(value))
+        .map_err(|err| render_error(&err))?;
+    Ok(out)
+}
+
+fn to_yaml(value: &Value) -> Yaml {
+    match value {
+        Value::Null => Yaml::Null,
+        Value::Bool(flag) => Yaml::Boolean(*flag),
+        Value::Number(number) => number
+            .as_i64()
+            .map_or_else(|| Yaml::Real(number.to_string()), Yaml::Integer),
+        Value::String(text) => Yaml::String(text.clone()),
+        Value::Array(items) => Yaml::Array(items.iter().map(to_yaml).collect()),
+        Value::Object(fields) => Yaml::Hash(
+            fields
+                .iter()
+                .map(|(key, value)| (Yaml::String(key.clone()), to_yaml(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Lists (a bare array or an object with `items`) become one row per item;
+/// any other object becomes field/value rows.
+fn table(value: &Value) -> String {
+    match value {
+        Value::Array(rows) => rows_table(rows),
+        Value::Object(fields) => match fields.get("items") {
+            Some(Value::Array(rows)) => {
+                // Cursors and counts follow the rows.
+                let footer = fields
+                    .iter()
+                    .filter(|(key, value)| *key != "items" && !value.is_null())
+                    .map(|(key, value)| format!("\n{key}: {}", cell(value)));
+                rows_table(rows) + &footer.collect::<String>()
+            }
+            _ => fields_table(fields),
+        },
+        other => cell(other),
+    }
+}
+
+fn rows_table(rows: &[Value]) -> String {
+    if rows.is_empty() {
+        return "(no items)".to_string();
+    }
+
+    let mut columns: Vec<&str> = Vec::new();
+    for fields in rows.iter().filter_map(Value::as_object) {
+        for (key, value) in fields {
+            if fits_cell(value) && !columns.contains(&key.as_str()) {
+                columns.push(key);
+            }
+        }
+    }
+    if columns.is_empty() {
+        let body = rows.iter().map(|row| vec![cell(row)]).collect();
+        return grid(&["VALUE".to_string()], body);
+    }
+    columns.sort_by_key(|column| {
+        let rank = LEADING_COLUMNS
+            .iter()
+            .position(|leading| leading == column)
+            .unwrap_or(LEADING_COLUMNS.len());
+        (rank, *column)
+    });
+
+    let header: Vec<String> = columns.iter().map(|column| column.to_uppercase()).collect();
+    let body = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| row.get(column).map_or_else(String::new, cell))
+                .collect()
+        })
+        .collect();
+    grid(&header, body)
+}
+
+fn fields_table(fields: &Map<String, Value>) -> String {
+    let body = fields
+        .iter()
+        .map(|(key, value)| vec![key.clone(), cell(value)])
+        .collect();
+    grid(&["FIELD".to_string(), "VALUE".to_string()], body)
+}
+
+/// Scalars and lists of scalars get a column; nested objects would not fit.
+fn fits_cell(value: &Value) -> bool {
+    match value {
+        Value::Object(_) => false,
+        Value::Array(items) => items
+            .iter()
+            .all(|item| !matches!(item, Value::Object(_) | Value::Array(_))),
+        _ => true,
+    }
+}
+
+fn cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) if fits_cell(value) => {
+            items.iter().map(cell).collect::<Vec<_>>().join(", ")
+        }
+        other => other.to_string(),
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > MAX_CELL_CHARS {
+        let cut: String = text.chars().take(MAX_CELL_CHARS - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        text
+    }
+}
+
+/// Left-aligned columns separated by two spaces.
+fn grid(header: &[String], body: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|title| title.chars().count()).collect();
+    for row in &body {
+        for (width, text) in widths.iter_mut().zip(row) {
+            *width = (*width).max(text.chars().count());
+        }
+    }
+
+    std::iter::once(header.to_vec())
+        .chain(body)
+        .map(|row| {
+            row.iter()
+                .zip(widths.iter().copied())
+                .map(|(text, width)| format!("{text:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
@@ -1,15 +1,16 @@
 #![deny(clippy::all, clippy::pedantic)]
 
+use clap::Parser;
 use httpmock::MockServer;
 use tempfile::NamedTempFile;
 use uuid::Uuid;
 
 use crate::args::{
-    ApiKeysAction, ApiKeysCmd, AuditCmd, NavCmd, PostStatusArg, PostsCmd, SettingsCmd,
-    SettingsPatchArgs,
+    ApiKeysAction, ApiKeysCmd, AuditCmd, FeedContentModeArg, NavCmd, OutputFormat, PostStatusArg,
+    PostsCmd, SettingsCmd, SettingsPatchArgs, UploadsCmd,
 };
-use crate::client::{CliError, Ctx, build_ctx_from_cli};
-use crate::handlers::{audit, navigation, posts, settings};
+use crate::client::{CliError, Ctx, build_ctx_from_cli, retry_delay};
+use crate::handlers::{audit, navigation, posts, settings, uploads};
 
 fn ctx(server: &MockServer) -> Ctx {
     Ctx::new(&server.base_url(), "key".into()).expect("ctx")
@@ -28,6 +29,8 @@ fn build_ctx_prefers_key_file() -> Result<(), CliError> {
         site: Some("https://example.com".to_string()),
         key_file: Some(file.path().to_path_buf()),
         api_key_env: Some("env-key".to_string()),
+        output: OutputFormat::Json,
+        max_retries: 3,
         command: crate::args::Commands::ApiKeys(ApiKeysCmd {
             action: ApiKeysAction::Me,
         }),
@@ -45,6 +48,8 @@ fn build_ctx_errors_without_key() {
         site: Some("https://example.com".to_string()),
         key_file: None,
         api_key_env: None,
+        output: OutputFormat::Json,
+        max_retries: 3,
         command: crate::args::Commands::ApiKeys(ApiKeysCmd {
             action: ApiKeysAction::Me,
         }),
@@ -68,6 +73,143 @@ fn parse_time_rejects_invalid() {
     assert!(matches!(err, CliError::InvalidInput(_)));
 }
 
+#[test]
+fn table_output_lists_items_in_aligned_columns() -> Result<(), CliError> {
+    let list = serde_json::json!({
+        "items": [
+            { "id": "1", "name": "Rust", "pinned": true, "meta": { "nested": 1 } },
+            { "id": "22", "name": "A very long tag name that needs to be cut down to fit in", "pinned": false },
+        ],
+        "next_cursor": "abc",
+    });
+
+    let out = crate::print::render(OutputFormat::Table, &list)?;
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(
+        lines[0],
+        "ID  NAME                                             PINNED"
+    );
+    assert_eq!(
+        lines[1],
+        "1   Rust                                             true"
+    );
+    assert!(lines[2].starts_with("22  A very long tag name that needs to be cut down…"));
+    assert!(lines[2].ends_with("  false"));
+    assert_eq!(lines[3], "next_cursor: abc");
+    assert!(!out.contains("nested"));
+
+    let single = serde_json::json!({ "id": "1", "tags": ["a", "b"] });
+    assert_eq!(
+        crate::print::render(OutputFormat::Table, &single)?,
+        "FIELD  VALUE\nid     1\ntags   a, b"
+    );
+    Ok(())
+}
+
+#[test]
+fn json_and_yaml_output_keep_the_full_shape() -> Result<(), CliError> {
+    let list = serde_json::json!({ "items": [{ "id": "1", "meta": { "nested": 1 } }] });
+
+    let json: serde_json::Value =
+        serde_json::from_str(&crate::print::render(OutputFormat::Json, &list)?)
+            .expect("json output parses");
+    assert_eq!(json, list);
+
+    let yaml = crate::print::render(OutputFormat::Yaml, &list)?;
+    assert!(yaml.contains("items:"), "{yaml}");
+    assert!(yaml.contains("nested: 1"), "{yaml}");
+    Ok(())
+}
+
+#[test]
+fn retry_delay_honours_the_server_hint_and_backs_off_otherwise() {
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    use std::time::Duration;
+
+    let mut headers = HeaderMap::new();
+    assert_eq!(retry_delay(&headers, 0), Duration::from_millis(500));
+    assert_eq!(retry_delay(&headers, 2), Duration::from_secs(2));
+    assert_eq!(retry_delay(&headers, 20), Duration::from_secs(30));
+
+    headers.insert("x-ratelimit-reset", HeaderValue::from_static("4"));
+    assert_eq!(retry_delay(&headers, 3), Duration::from_secs(4));
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+    assert_eq!(retry_delay(&headers, 0), Duration::from_secs(7));
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+    assert_eq!(retry_delay(&headers, 0), Duration::from_secs(30));
+}
+
+/// The first request to `path` is rate limited; later ones succeed.
+fn rate_limited_once<'a>(
+    server: &'a MockServer,
+    path: &str,
+) -> (httpmock::Mock<'a>, httpmock::Mock<'a>) {
+    let first = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let limited = server.mock(|when, then| {
+        when.path(path.to_string())
+            .is_true(move |_| first.swap(false, std::sync::atomic::Ordering::SeqCst));
+        then.status(429)
+            .header("retry-after", "0")
+            .header("content-type", "application/json")
+            .body(r#"{"status":429,"code":"rate_limited","message":"Too Many Requests"}"#);
+    });
+    let ok = server.mock(|when, then| {
+        when.path(path.to_string());
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"items":[]}"#);
+    });
+    (limited, ok)
+}
+
+#[tokio::test]
+async fn rate_limited_request_is_retried_until_it_succeeds() -> Result<(), CliError> {
+    let server = MockServer::start();
+    let (limited, ok) = rate_limited_once(&server, "/api/v1/tags");
+
+    let res: serde_json::Value = ctx(&server)
+        .request(reqwest::Method::GET, "api/v1/tags", None, None)
+        .await?;
+    assert_eq!(res["items"], serde_json::json!([]));
+    limited.assert_calls(1);
+    ok.assert_calls(1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn retries_stop_at_max_retries_and_other_errors_fail_at_once() {
+    let server = MockServer::start();
+    let limited = server.mock(|when, then| {
+        when.path("/api/v1/tags");
+        then.status(429).header("retry-after", "0");
+    });
+    let forbidden = server.mock(|when, then| {
+        when.path("/api/v1/posts");
+        then.status(403).body("forbidden");
+    });
+    let ctx = ctx(&server).with_max_retries(2);
+
+    let err = ctx
+        .request_no_body(reqwest::Method::GET, "api/v1/tags", None)
+        .await
+        .expect_err("still rate limited");
+    assert!(matches!(
+        err,
+        CliError::Api { status, .. } if status == reqwest::StatusCode::TOO_MANY_REQUESTS
+    ));
+    limited.assert_calls(3);
+
+    let err = ctx
+        .request_no_body(reqwest::Method::GET, "api/v1/posts", None)
+        .await
+        .expect_err("forbidden");
+    assert!(matches!(
+        err,
+        CliError::Api { status, .. } if status == reqwest::StatusCode::FORBIDDEN
+    ));
+    forbidden.assert_calls(1);
+}
+
 #[tokio::test]
 async fn api_keys_me_hits_endpoint() -> Result<(), CliError> {
     let server = MockServer::start();
@@ -87,6 +229,30 @@ async fn api_keys_me_hits_endpoint() -> Result<(), CliError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn api_keys_rotate_all_reports_keys_left_unrotated() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method("POST").path("/api/v1/api-keys/rotate-all");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"rotated":[{"id":"00000000-0000-0000-0000-000000000001","name":"ci","prefix":"abc","token":"sk_abc_secret"}],"failed":[{"id":"00000000-0000-0000-0000-000000000002","name":"deploy","prefix":"def","error":"key not found"}]}"#);
+    });
+
+    let ctx = ctx(&server);
+    let cmd = ApiKeysCmd {
+        action: ApiKeysAction::RotateAll,
+    };
+    let err = crate::handlers::api_keys::handle(&ctx, cmd)
+        .await
+        .expect_err("a failed rotation is an error");
+    mock.assert();
+    assert!(
+        matches!(&err, CliError::Server(message) if message.contains("1 of 2")),
+        "{err}"
+    );
+}
+
 #[tokio::test]
 async fn posts_list_hits_endpoint() -> Result<(), CliError> {
     let server = MockServer::start();
@@ -135,6 +301,7 @@ async fn posts_create_reads_body_file() -> Result<(), CliError> {
         &ctx,
         PostsCmd::Create {
             title: "T".into(),
+            slug: None,
             excerpt: "E".into(),
             body: None,
             body_file: Some(body_file.path().to_path_buf()),
@@ -152,6 +319,182 @@ async fn posts_create_reads_body_file() -> Result<(), CliError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn posts_watch_sends_quick_saves_as_one_update() {
+    let server = MockServer::start();
+    let id = Uuid::new_v4();
+    let mock = server.mock(|when, then| {
+        when.method("POST")
+            .path(format!("/api/v1/posts/{id}/body"))
+            .query_param("wait_render", "true")
+            .json_body(serde_json::json!({ "body_markdown": "second save" }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"id":"p1","render_progress":{"state":"rendered"}}"#);
+    });
+
+    let file = tmp_file("second save");
+    let (saves, rx) = tokio::sync::mpsc::unbounded_channel();
+    saves.send(()).expect("first save");
+    saves.send(()).expect("second save");
+    drop(saves);
+
+    posts::push_saves(
+        &ctx(&server),
+        id,
+        file.path(),
+        rx,
+        std::time::Duration::from_millis(50),
+    )
+    .await;
+    mock.assert_calls(1);
+}
+
+#[tokio::test]
+async fn posts_watch_keeps_going_after_a_failed_update() {
+    let server = MockServer::start();
+    let id = Uuid::new_v4();
+    let path = format!("/api/v1/posts/{id}/body");
+    let first = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let failed = server.mock(|when, then| {
+        when.path(path.clone())
+            .is_true(move |_| first.swap(false, std::sync::atomic::Ordering::SeqCst));
+        then.status(503).body("unavailable");
+    });
+    let ok = server.mock(|when, then| {
+        when.path(path.clone());
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"id":"p1","render_progress":{"state":"rendered"}}"#);
+    });
+
+    let file = tmp_file("BODY");
+    let (saves, rx) = tokio::sync::mpsc::unbounded_channel();
+    let debounce = std::time::Duration::from_millis(10);
+    let ctx = ctx(&server);
+    tokio::join!(
+        posts::push_saves(&ctx, id, file.path(), rx, debounce),
+        async {
+            saves.send(()).expect("first save");
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            saves.send(()).expect("second save");
+            drop(saves);
+        }
+    );
+    failed.assert_calls(1);
+    ok.assert_calls(1);
+}
+
+#[tokio::test]
+async fn uploads_push_reports_each_file_and_skips_known_contents() -> Result<(), CliError> {
+    let dir = tempfile::tempdir().expect("tmp dir");
+    for (name, contents) in [
+        ("a.txt", "fresh"),
+        ("b.txt", "fresh"),
+        ("huge.bin", "too large"),
+        ("known.txt", "already there"),
+    ] {
+        std::fs::write(dir.path().join(name), contents).expect("write file");
+    }
+    let known = uploads::sha256_hex(b"already there");
+
+    let server = MockServer::start();
+    let lookup_known = server.mock(|when, then| {
+        when.method("GET")
+            .path("/api/v1/uploads")
+            .query_param("checksum", known.clone());
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"items":[{"id":"u-known"}]}"#);
+    });
+    let lookup_other = server.mock(|when, then| {
+        when.method("GET").path("/api/v1/uploads");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"items":[]}"#);
+    });
+    let too_large = server.mock(|when, then| {
+        when.method("POST")
+            .path("/api/v1/uploads")
+            .body_includes(r#"filename="huge.bin""#);
+        then.status(413)
+            .header("content-type", "application/json")
+            .body(r#"{"error":{"code":"payload_too_large","message":"Request body too large","retryable":false}}"#);
+    });
+    let uploaded = server.mock(|when, then| {
+        when.method("POST").path("/api/v1/uploads");
+        then.status(201)
+            .header("content-type", "application/json")
+            .body(r#"{"id":"u-new"}"#);
+    });
+
+    let ctx = ctx(&server);
+    let patterns = vec![format!("{}/*", dir.path().display())];
+    let results = uploads::push_all(&ctx, &patterns, 3).await?;
+    let summary: Vec<(String, &str, Option<&str>)> = results
+        .iter()
+        .map(|result| {
+            let name = std::path::Path::new(&result.file)
+                .file_name()
+                .expect("file name")
+                .to_string_lossy()
+                .into_owned();
+            (name, result.status, result.id.as_deref())
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("a.txt".to_string(), "uploaded", Some("u-new")),
+            ("b.txt".to_string(), "skipped", Some("u-new")),
+            ("huge.bin".to_string(), "failed", None),
+            ("known.txt".to_string(), "skipped", Some("u-known")),
+        ]
+    );
+    assert_eq!(
+        results[2].error.as_deref(),
+        Some("Request body too large (413 Payload Too Large)")
+    );
+    lookup_known.assert_calls(1);
+    lookup_other.assert_calls(2);
+    too_large.assert_calls(1);
+    uploaded.assert_calls(1);
+
+    let err = uploads::handle(
+        &ctx,
+        UploadsCmd::Push {
+            patterns,
+            parallel: 2,
+        },
+    )
+    .await
+    .expect_err("one file failed");
+    assert!(matches!(
+        err,
+        CliError::Partial {
+            failed: 1,
+            total: 4
+        }
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn uploads_push_rejects_a_pattern_without_matches() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().expect("tmp dir");
+    let err = uploads::push_all(
+        &ctx(&server),
+        &[format!("{}/*.png", dir.path().display())],
+        4,
+    )
+    .await
+    .expect_err("nothing to upload");
+    assert!(
+        matches!(err, CliError::InvalidInput(message) if message.starts_with("no files match"))
+    );
+}
+
 #[tokio::test]
 async fn navigation_patch_open_hits_endpoint() -> Result<(), CliError> {
     let server = MockServer::start();
@@ -179,7 +522,9 @@ async fn navigation_patch_open_hits_endpoint() -> Result<(), CliError> {
 async fn settings_patch_reads_favicon_file() -> Result<(), CliError> {
     let server = MockServer::start();
     let mock = server.mock(|when, then| {
-        when.method("PATCH").path("/api/v1/site/settings");
+        when.method("PATCH")
+            .path("/api/v1/site/settings")
+            .json_body_includes(r#"{"feed_content_mode":"full"}"#);
         then.status(200)
             .header("content-type", "application/json")
             .body("{}");
@@ -208,6 +553,18 @@ async fn settings_patch_reads_favicon_file() -> Result<(), CliError> {
             global_toc_enabled: None,
             favicon_svg: None,
             favicon_svg_file: Some(favicon.path().to_path_buf()),
+            favicon_upload_id: None,
+            clear_favicon_upload: false,
+            maintenance_enabled: None,
+            maintenance_html: None,
+            maintenance_html_file: None,
+            maintenance_retry_after_secs: None,
+            date_format: None,
+            feed_content_mode: Some(FeedContentModeArg::Full),
+            feed_item_limit: None,
+            homepage_mode: None,
+            homepage_page_id: None,
+            homepage_layout: None,
         })),
     )
     .await?;
@@ -215,6 +572,75 @@ async fn settings_patch_reads_favicon_file() -> Result<(), CliError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn settings_patch_clears_favicon_upload_with_null() -> Result<(), CliError> {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method("PATCH")
+            .path("/api/v1/site/settings")
+            .json_body_includes(r#"{"favicon_upload_id":null}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .body("{}");
+    });
+
+    let args = SettingsPatchArgs::try_parse_from(["patch", "--clear-favicon-upload"])
+        .expect("parse patch args");
+    let ctx = ctx(&server);
+    settings::handle(&ctx, SettingsCmd::Patch(Box::new(args))).await?;
+    mock.assert();
+    Ok(())
+}
+
+#[tokio::test]
+async fn settings_patch_sets_page_homepage() -> Result<(), CliError> {
+    let server = MockServer::start();
+    let page_id = Uuid::new_v4();
+    let mock = server.mock(|when, then| {
+        when.method("PATCH")
+            .path("/api/v1/site/settings")
+            .json_body_includes(format!(
+                r#"{{"homepage_mode":"page","homepage_page_id":"{page_id}"}}"#
+            ));
+        then.status(200)
+            .header("content-type", "application/json")
+            .body("{}");
+    });
+
+    let args = SettingsPatchArgs::try_parse_from([
+        "patch",
+        "--homepage-mode",
+        "page",
+        "--homepage-page-id",
+        &page_id.to_string(),
+    ])
+    .expect("parse patch args");
+    let ctx = ctx(&server);
+    settings::handle(&ctx, SettingsCmd::Patch(Box::new(args))).await?;
+    mock.assert();
+    Ok(())
+}
+
+#[tokio::test]
+async fn settings_patch_sets_homepage_layout() -> Result<(), CliError> {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method("PATCH")
+            .path("/api/v1/site/settings")
+            .json_body_includes(r#"{"homepage_layout":"magazine"}"#);
+        then.status(200)
+            .header("content-type", "application/json")
+            .body("{}");
+    });
+
+    let args = SettingsPatchArgs::try_parse_from(["patch", "--homepage-layout", "magazine"])
+        .expect("parse patch args");
+    let ctx = ctx(&server);
+    settings::handle(&ctx, SettingsCmd::Patch(Box::new(args))).await?;
+    mock.assert();
+    Ok(())
+}
+
 #[tokio::test]
 async fn audit_list_filters() -> Result<(), CliError> {
     let server = MockServer::start();
@@ -237,6 +663,9 @@ async fn audit_list_filters() -> Result<(), CliError> {
             action: Some("update_post".into()),
             entity_type: None,
             search: None,
+            field: None,
+            from: None,
+            to: None,
             limit: 5,
             cursor: None,
         },
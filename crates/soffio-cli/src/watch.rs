@@ -0,0 +1,58 @@
+#![deny(clippy::all, clippy::pedantic)]
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+
+use crate::client::CliError;
+
+/// Report every save of `file` on the returned channel until the watcher is
+/// dropped.
+///
+/// The parent directory is watched rather than the file itself: many editors
+/// save by writing a temporary file and renaming it over the original, which
+/// a watch on the old inode would miss.
+pub fn watch_file(file: &Path) -> Result<(RecommendedWatcher, UnboundedReceiver<()>), CliError> {
+    let name = file
+        .file_name()
+        .ok_or_else(|| CliError::InvalidInput(format!("{} is not a file", file.display())))?
+        .to_owned();
+    let dir = match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let (tx, rx) = unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let Ok(event) = event else { return };
+        let saved = (event.kind.is_create() || event.kind.is_modify())
+            && event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == Some(name.as_os_str()));
+        if saved {
+            let _ = tx.send(());
+        }
+    })
+    .map_err(|err| watch_error(&dir, &err))?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|err| watch_error(&dir, &err))?;
+    Ok((watcher, rx))
+}
+
+/// Wait for the next save, then until `quiet` passes without another one, so
+/// a burst of saves counts once. Returns `false` once no more saves can come.
+pub async fn settled(saves: &mut UnboundedReceiver<()>, quiet: Duration) -> bool {
+    if saves.recv().await.is_none() {
+        return false;
+    }
+    while let Ok(Some(())) = tokio::time::timeout(quiet, saves.recv()).await {}
+    true
+}
+
+fn watch_error(dir: &Path, err: &notify::Error) -> CliError {
+    CliError::InvalidInput(format!("cannot watch {}: {err}", dir.display()))
+}
@@ -2,6 +2,7 @@
 
 use assert_cmd::Command;
 use httpmock::MockServer;
+use predicates::boolean::PredicateBooleanExt;
 use predicates::str::contains;
 use std::io::Write;
 use tempfile::NamedTempFile;
@@ -48,7 +49,84 @@ fn missing_site_fails_fast() {
         .env_remove("SOFFIO_API_KEY_FILE")
         .assert()
         .failure()
-        .stderr(contains("MissingSite"));
+        .stderr(contains(r#""kind": "missing_site""#));
+}
+
+#[test]
+fn errors_follow_the_output_format() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method("GET").path("/api/v1/tags");
+        then.status(403)
+            .header("content-type", "application/json")
+            .body(
+                r#"{"error":{"code":"forbidden","message":"Forbidden","hint":"API key lacks tag_read","docs_url":"https://docs.example.com/errors#forbidden","retryable":false}}"#,
+            );
+    });
+    let key = key_file("cli-test-key");
+
+    let assert = Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .args(["--output", "json", "tags", "list"])
+        .assert()
+        .failure();
+    let error: serde_json::Value =
+        serde_json::from_slice(&assert.get_output().stderr).expect("json error");
+    assert_eq!(error["error"]["kind"], "api");
+    assert_eq!(error["error"]["status"], 403);
+    assert_eq!(error["error"]["response"]["error"]["code"], "forbidden");
+    assert_eq!(error["error"]["response"]["error"]["retryable"], false);
+
+    Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .args(["tags", "list", "--output", "table"])
+        .assert()
+        .failure()
+        .stderr(
+            contains("error: Forbidden (forbidden, status 403 Forbidden)")
+                .and(contains("hint: API key lacks tag_read"))
+                .and(contains("docs: https://docs.example.com/errors#forbidden"))
+                .and(contains("retryable: no")),
+        );
+    mock.assert_calls(2);
+}
+
+#[test]
+fn tags_list_renders_as_table_or_json() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method("GET").path("/api/v1/tags");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"items":[{"id":"t1","slug":"rust","name":"Rust","count":3},{"id":"t2","slug":"web","name":"Web","count":12}],"next_cursor":null}"#);
+    });
+    let key = key_file("cli-test-key");
+
+    let table = Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .args(["--output", "table", "tags", "list"])
+        .assert()
+        .success();
+    let table = String::from_utf8_lossy(&table.get_output().stdout).into_owned();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "ID  SLUG  NAME  COUNT");
+    assert_eq!(lines[1], "t1  rust  Rust  3");
+    assert_eq!(lines[2], "t2  web   Web   12");
+
+    let json = Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .args(["--output", "json", "tags", "list"])
+        .assert()
+        .success();
+    let json: serde_json::Value =
+        serde_json::from_slice(&json.get_output().stdout).expect("json output");
+    assert_eq!(json["items"].as_array().map(Vec::len), Some(2));
+    assert_eq!(json["items"][1]["slug"], "web");
+    mock.assert_calls(2);
 }
 
 #[test]
@@ -339,3 +417,198 @@ fn snapshots_rollback_hits_endpoint() {
 
     mock.assert();
 }
+
+#[test]
+fn posts_update_sends_if_match() {
+    let server = MockServer::start();
+    let post_id = Uuid::new_v4();
+    let mock = server.mock(|when, then| {
+        when.method("PATCH")
+            .path(format!("/api/v1/posts/{post_id}"))
+            .header("if-match", "\"1760616000123456\"");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(format!(r#"{{"id":"{post_id}","slug":"p"}}"#));
+    });
+
+    let key = key_file("cli-test-key");
+    Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .arg("posts")
+        .arg("update")
+        .arg("--id")
+        .arg(post_id.to_string())
+        .arg("--slug")
+        .arg("p")
+        .arg("--title")
+        .arg("Post")
+        .arg("--body")
+        .arg("b")
+        .arg("--if-match")
+        .arg("\"1760616000123456\"")
+        .assert()
+        .success();
+
+    mock.assert();
+}
+
+#[test]
+fn posts_patch_title_sends_if_match() {
+    let server = MockServer::start();
+    let post_id = Uuid::new_v4();
+    let mock = server.mock(|when, then| {
+        when.method("POST")
+            .path(format!("/api/v1/posts/{post_id}/title"))
+            .header("if-match", "\"1760616000123456\"");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(format!(r#"{{"id":"{post_id}","slug":"p"}}"#));
+    });
+
+    let key = key_file("cli-test-key");
+    Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .arg("posts")
+        .arg("patch-title")
+        .arg("--id")
+        .arg(post_id.to_string())
+        .arg("--title")
+        .arg("Post")
+        .arg("--if-match")
+        .arg("\"1760616000123456\"")
+        .assert()
+        .success();
+
+    mock.assert();
+}
+
+#[test]
+fn pages_update_defaults_to_unconditional_if_match() {
+    let server = MockServer::start();
+    let page_id = Uuid::new_v4();
+    let mock = server.mock(|when, then| {
+        when.method("PATCH")
+            .path(format!("/api/v1/pages/{page_id}"))
+            .header("if-match", "*");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(format!(r#"{{"id":"{page_id}","slug":"about"}}"#));
+    });
+
+    let key = key_file("cli-test-key");
+    Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .arg("pages")
+        .arg("update")
+        .arg("--id")
+        .arg(page_id.to_string())
+        .arg("--slug")
+        .arg("about")
+        .arg("--title")
+        .arg("About")
+        .arg("--body")
+        .arg("b")
+        .assert()
+        .success();
+
+    mock.assert();
+}
+
+#[test]
+fn posts_restore_hits_trash_endpoint() {
+    let server = MockServer::start();
+    let post_id = Uuid::new_v4();
+    let mock = server.mock(|when, then| {
+        when.method("POST")
+            .path(format!("/api/v1/posts/trash/{post_id}/restore"));
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(format!(r#"{{"id":"{post_id}","slug":"hello"}}"#));
+    });
+
+    let key = key_file("cli-test-key");
+    Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .arg("posts")
+        .arg("restore")
+        .arg(post_id.to_string())
+        .assert()
+        .success();
+
+    mock.assert();
+}
+
+#[test]
+fn pages_purge_hits_trash_endpoint() {
+    let server = MockServer::start();
+    let page_id = Uuid::new_v4();
+    let mock = server.mock(|when, then| {
+        when.method("DELETE")
+            .path(format!("/api/v1/pages/trash/{page_id}"));
+        then.status(204);
+    });
+
+    let key = key_file("cli-test-key");
+    Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .arg("pages")
+        .arg("purge")
+        .arg(page_id.to_string())
+        .assert()
+        .success()
+        .stdout(contains("purged"));
+
+    mock.assert();
+}
+
+#[test]
+fn rate_limited_command_retries_and_succeeds() {
+    let server = MockServer::start();
+    let first = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let limited = server.mock(|when, then| {
+        when.method("GET")
+            .path("/api/v1/tags")
+            .is_true(move |_| first.swap(false, std::sync::atomic::Ordering::SeqCst));
+        then.status(429)
+            .header("retry-after", "0")
+            .header("x-ratelimit-reset", "0");
+    });
+    let ok = server.mock(|when, then| {
+        when.method("GET").path("/api/v1/tags");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"items":[{"id":"t1","slug":"rust","name":"Rust"}],"next_cursor":null}"#);
+    });
+    let key = key_file("cli-test-key");
+
+    Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .args(["--max-retries", "1", "tags", "list"])
+        .assert()
+        .success()
+        .stdout(contains(r#""slug": "rust""#));
+    limited.assert_calls(1);
+    ok.assert_calls(1);
+
+    // With retries disabled the same 429 fails the command.
+    let first = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/api/v1/tags")
+            .is_true(move |_| first.swap(false, std::sync::atomic::Ordering::SeqCst));
+        then.status(429).header("retry-after", "0");
+    });
+    Command::new(assert_cmd::cargo::cargo_bin!("soffio-cli"))
+        .env("SOFFIO_SITE_URL", server.base_url())
+        .env("SOFFIO_API_KEY_FILE", key.path())
+        .args(["--max-retries", "0", "tags", "list"])
+        .assert()
+        .failure()
+        .stderr(contains(r#""status": 429"#));
+}
@@ -31,10 +31,9 @@ interface UploadEntryEventDetail {
   suppressPanel?: boolean;
 }
 
-interface WorkerForms {
+interface WorkerForm {
   form: HTMLFormElement;
-  idField: HTMLInputElement;
-  fileField: HTMLInputElement;
+  slots: HTMLElement;
 }
 
 interface ActionContext {
@@ -48,6 +47,8 @@ type ActionHandlerMap = Record<string, ActionHandler>;
 
 const uploadQueues = new WeakMap<HTMLFormElement, UploadQueueState>();
 const codeCopyResetTimers = new WeakMap<HTMLButtonElement, number>();
+const headingLinkResetTimers = new WeakMap<HTMLAnchorElement, number>();
+const HEADING_LINK_RESET_MS = 2000;
 let activeQueueForm: HTMLFormElement | null = null;
 
 const getFormElements = (form: HTMLFormElement | null): HTMLFormControlsCollection | null => {
@@ -289,31 +290,116 @@ const waitForEntryResult = (entryId: string, suppressPanel: boolean): Promise<Up
     window.addEventListener(UPLOAD_QUEUE_EVENT, handler);
   });
 
-const configureUploadWorker = (form: HTMLFormElement, entry: UploadQueueEntry): WorkerForms | null => {
+// Each entry becomes a `queue_entry_id` field followed by its `file` field,
+// which is how the server pairs them.
+const configureUploadWorker = (form: HTMLFormElement, entries: UploadQueueEntry[]): WorkerForm | null => {
   const workerForm = findAuxForm(form, 'upload-queue-upload');
-  if (!workerForm) {
+  if (!workerForm || typeof DataTransfer === 'undefined') {
     return null;
   }
 
-  const idField = workerForm.querySelector<HTMLInputElement>('input[name="queue_entry_id"]');
-  const fileField = workerForm.querySelector<HTMLInputElement>('input[type="file"][name="file"]');
-  if (!idField || !fileField) {
+  const slots = workerForm.querySelector<HTMLElement>('[data-role="upload-queue-slots"]');
+  if (!slots) {
     return null;
   }
 
-  idField.value = entry.id ?? '';
-
-  if (entry.file instanceof File && typeof DataTransfer !== 'undefined') {
+  slots.replaceChildren();
+  for (const entry of entries) {
+    if (!(entry.file instanceof File)) {
+      continue;
+    }
+    const idField = document.createElement('input');
+    idField.type = 'hidden';
+    idField.name = 'queue_entry_id';
+    idField.value = entry.id ?? '';
+
+    const fileField = document.createElement('input');
+    fileField.type = 'file';
+    fileField.name = 'file';
     const transfer = new DataTransfer();
     transfer.items.add(entry.file);
     fileField.files = transfer.files;
-  } else {
-    fileField.value = '';
+
+    slots.append(idField, fileField);
+  }
+
+  return { form: workerForm, slots };
+};
+
+// Room left in a request for the multipart headers of each file.
+const UPLOAD_PART_OVERHEAD_BYTES = 1024;
+
+// Group entries into requests that stay under `requestLimit` bytes; without
+// a limit everything goes in one request.
+const planUploadBatches = (entries: UploadQueueEntry[], requestLimit: number | null): UploadQueueEntry[][] => {
+  const batches: UploadQueueEntry[][] = [];
+  let current: UploadQueueEntry[] = [];
+  let currentBytes = 0;
+  for (const entry of entries) {
+    const size = (entry.file?.size ?? 0) + UPLOAD_PART_OVERHEAD_BYTES;
+    if (requestLimit !== null && current.length > 0 && currentBytes + size > requestLimit) {
+      batches.push(current);
+      current = [];
+      currentBytes = 0;
+    }
+    current.push(entry);
+    currentBytes += size;
+  }
+  if (current.length > 0) {
+    batches.push(current);
   }
+  return batches;
+};
 
-  return { form: workerForm, idField, fileField };
+const dropzoneFor = (target: EventTarget | null): HTMLElement | null => {
+  if (!(target instanceof Element)) {
+    return null;
+  }
+  return target.closest<HTMLElement>('[data-role="upload-dropzone"]');
 };
 
+document.addEventListener('dragover', (event) => {
+  const zone = dropzoneFor(event.target);
+  if (!zone) {
+    return;
+  }
+  event.preventDefault();
+  zone.dataset.dragging = 'true';
+});
+
+document.addEventListener('dragleave', (event) => {
+  const zone = dropzoneFor(event.target);
+  if (zone && !zone.contains(event.relatedTarget as Node | null)) {
+    delete zone.dataset.dragging;
+  }
+});
+
+// Dropped files join the ones already picked; the change event queues them
+// like a regular selection.
+document.addEventListener('drop', (event) => {
+  const zone = dropzoneFor(event.target);
+  if (!zone) {
+    return;
+  }
+  event.preventDefault();
+  delete zone.dataset.dragging;
+
+  const input = zone.querySelector<HTMLInputElement>('input[type="file"]');
+  const dropped = event.dataTransfer?.files;
+  if (!input || !dropped || dropped.length === 0 || typeof DataTransfer === 'undefined') {
+    return;
+  }
+  const transfer = new DataTransfer();
+  for (const file of Array.from(input.files ?? [])) {
+    transfer.items.add(file);
+  }
+  for (const file of Array.from(dropped)) {
+    transfer.items.add(file);
+  }
+  input.files = transfer.files;
+  input.dispatchEvent(new Event('change', { bubbles: true }));
+});
+
 window.addEventListener(UPLOAD_QUEUE_EVENT, (event) => {
   const detail = (event as CustomEvent<UploadEntryEventDetail>).detail;
   if (!detail || !detail.id) {
@@ -438,6 +524,37 @@ const actionHandlers: ActionHandlerMap = {
     codeCopyResetTimers.set(button, timer);
   },
 
+  async copyHeadingLink(ctxArg) {
+    const { el } = (ctxArg ?? {}) as ActionContext;
+    const anchor = el instanceof Element ? el.closest('a.heading-anchor') : null;
+    if (!(anchor instanceof HTMLAnchorElement)) {
+      return;
+    }
+
+    // The click still follows the link, so the address bar shows the same URL.
+    const previous = headingLinkResetTimers.get(anchor);
+    if (typeof previous === 'number') {
+      window.clearTimeout(previous);
+    }
+
+    try {
+      if (!navigator.clipboard || typeof navigator.clipboard.writeText !== 'function') {
+        throw new Error('Clipboard API unavailable');
+      }
+      await navigator.clipboard.writeText(anchor.href);
+      anchor.dataset.copyState = 'success';
+    } catch (error) {
+      void error;
+      anchor.dataset.copyState = 'error';
+    }
+
+    const timer = window.setTimeout(() => {
+      anchor.dataset.copyState = 'idle';
+      headingLinkResetTimers.delete(anchor);
+    }, HEADING_LINK_RESET_MS);
+    headingLinkResetTimers.set(anchor, timer);
+  },
+
   queueFiles(ctxArg) {
     const { el } = (ctxArg ?? {}) as ActionContext;
     if (!(el instanceof HTMLInputElement)) {
@@ -523,39 +640,41 @@ const actionHandlers: ActionHandlerMap = {
         }
       }
 
-      for (const entry of state.entries) {
-        if (!(entry.file instanceof File)) {
-          continue;
-        }
-        if (entry.status === 'error' || entry.status === 'success') {
-          continue;
-        }
-        if (limitBytes !== null && entry.file.size > limitBytes) {
-          continue;
-        }
-
-        if (!entry.id) {
-          entry.id = generateQueueId();
+      const requestLimit = Number(form.dataset?.uploadRequestLimit);
+      const requestLimitBytes = Number.isFinite(requestLimit) && requestLimit > 0 ? requestLimit : null;
+      const ready = state.entries.filter(
+        (entry) =>
+          entry.file instanceof File &&
+          entry.status !== 'error' &&
+          entry.status !== 'success' &&
+          (limitBytes === null || entry.file.size <= limitBytes),
+      );
+
+      for (const batch of planUploadBatches(ready, requestLimitBytes)) {
+        for (const entry of batch) {
+          if (!entry.id) {
+            entry.id = generateQueueId();
+          }
+          entry.status = 'uploading';
+          entry.message = null;
         }
-
-        entry.status = 'uploading';
-        entry.message = null;
         postQueueSnapshot(form, state);
 
-        const worker = configureUploadWorker(form, entry);
+        const worker = configureUploadWorker(form, batch);
         if (!worker) {
-          entry.status = 'error';
-          entry.message = 'Upload form unavailable';
+          for (const entry of batch) {
+            entry.status = 'error';
+            entry.message = 'Upload form unavailable';
+          }
           postQueueSnapshot(form, state);
           continue;
         }
 
-        const suppressPanel = Boolean(worker.form.querySelector("input[name=\"suppress_panel_patch\"]"));
-        const resultPromise = waitForEntryResult(entry.id, suppressPanel);
+        const suppressPanel = Boolean(worker.form.querySelector('input[name="suppress_panel_patch"]'));
+        const results = Promise.all(batch.map((entry) => waitForEntryResult(entry.id ?? '', suppressPanel)));
         worker.form.requestSubmit();
-        await resultPromise;
-        worker.idField.value = '';
-        worker.fileField.value = '';
+        await results;
+        worker.slots.replaceChildren();
       }
     } finally {
       state.processing = false;
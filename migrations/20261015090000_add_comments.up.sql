@@ -0,0 +1,20 @@
+-- Moderated comments ingested from an external comment service
+
+CREATE TYPE comment_status AS ENUM ('pending', 'approved', 'spam');
+
+-- Extend API scopes for comment ingest
+ALTER TYPE api_scope ADD VALUE IF NOT EXISTS 'comment_write';
+
+CREATE TABLE comments (
+    id UUID PRIMARY KEY,
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    author TEXT NOT NULL,
+    body TEXT NOT NULL,
+    status comment_status NOT NULL DEFAULT 'pending',
+    source_ip TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_comments_post_status ON comments(post_id, status, created_at);
+CREATE INDEX idx_comments_status_created_at ON comments(status, created_at DESC, id DESC);
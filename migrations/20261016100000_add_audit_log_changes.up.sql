@@ -0,0 +1,6 @@
+-- Shallow field diffs recorded alongside update audit entries
+
+ALTER TABLE audit_logs ADD COLUMN changes JSONB;
+
+CREATE INDEX audit_logs_changes_idx
+    ON audit_logs USING GIN (changes);
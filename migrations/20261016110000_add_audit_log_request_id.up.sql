@@ -0,0 +1,3 @@
+-- Correlation id of the HTTP request that produced an audit entry
+
+ALTER TABLE audit_logs ADD COLUMN request_id TEXT;
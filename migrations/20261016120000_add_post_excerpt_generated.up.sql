@@ -0,0 +1,3 @@
+-- Distinguish excerpts derived from the post body from ones written by an editor
+
+ALTER TABLE posts ADD COLUMN excerpt_generated BOOLEAN NOT NULL DEFAULT FALSE;
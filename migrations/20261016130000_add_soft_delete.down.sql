@@ -0,0 +1,12 @@
+DELETE FROM posts WHERE deleted_at IS NOT NULL;
+DELETE FROM pages WHERE deleted_at IS NOT NULL;
+
+DROP INDEX IF EXISTS posts_deleted_at_idx;
+DROP INDEX IF EXISTS pages_deleted_at_idx;
+DROP INDEX IF EXISTS posts_slug_key;
+DROP INDEX IF EXISTS pages_slug_key;
+ALTER TABLE posts ADD CONSTRAINT posts_slug_key UNIQUE (slug);
+ALTER TABLE pages ADD CONSTRAINT pages_slug_key UNIQUE (slug);
+
+ALTER TABLE posts DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE pages DROP COLUMN IF EXISTS deleted_at;
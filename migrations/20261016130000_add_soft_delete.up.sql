@@ -0,0 +1,13 @@
+-- Soft delete: trashed posts and pages keep their row until restored or purged
+
+ALTER TABLE posts ADD COLUMN deleted_at TIMESTAMPTZ;
+ALTER TABLE pages ADD COLUMN deleted_at TIMESTAMPTZ;
+
+-- Only live rows claim a slug, so trashing frees it for reuse
+ALTER TABLE posts DROP CONSTRAINT posts_slug_key;
+ALTER TABLE pages DROP CONSTRAINT pages_slug_key;
+CREATE UNIQUE INDEX posts_slug_key ON posts (slug) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX pages_slug_key ON pages (slug) WHERE deleted_at IS NULL;
+
+CREATE INDEX posts_deleted_at_idx ON posts (deleted_at DESC, id DESC) WHERE deleted_at IS NOT NULL;
+CREATE INDEX pages_deleted_at_idx ON pages (deleted_at DESC, id DESC) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,4 @@
+ALTER TABLE site_settings
+    DROP COLUMN IF EXISTS maintenance_retry_after_secs,
+    DROP COLUMN IF EXISTS maintenance_html,
+    DROP COLUMN IF EXISTS maintenance_enabled;
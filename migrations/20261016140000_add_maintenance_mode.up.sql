@@ -0,0 +1,7 @@
+-- Maintenance mode: the public site answers 503 while admin and the API stay up
+
+ALTER TABLE site_settings
+    ADD COLUMN maintenance_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN maintenance_html TEXT NOT NULL DEFAULT '<!doctype html><html><head><meta charset="utf-8"><title>Maintenance</title></head><body><h1>Down for maintenance</h1><p>We will be back shortly.</p></body></html>',
+    ADD COLUMN maintenance_retry_after_secs INTEGER NOT NULL DEFAULT 300
+        CHECK (maintenance_retry_after_secs > 0);
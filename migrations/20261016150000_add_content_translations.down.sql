@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS posts_translation_lang_key;
+DROP INDEX IF EXISTS pages_translation_lang_key;
+
+ALTER TABLE posts
+    DROP COLUMN IF EXISTS translation_group,
+    DROP COLUMN IF EXISTS lang;
+ALTER TABLE pages
+    DROP COLUMN IF EXISTS translation_group,
+    DROP COLUMN IF EXISTS lang;
@@ -0,0 +1,17 @@
+-- Per-language content: a post or page may declare its language and join a
+-- translation group shared by the variants of the same content
+
+ALTER TABLE posts
+    ADD COLUMN lang TEXT CHECK (lang ~ '^[a-z]{2,3}(-[A-Za-z0-9]{2,8})*$'),
+    ADD COLUMN translation_group UUID;
+ALTER TABLE pages
+    ADD COLUMN lang TEXT CHECK (lang ~ '^[a-z]{2,3}(-[A-Za-z0-9]{2,8})*$'),
+    ADD COLUMN translation_group UUID;
+
+-- A group holds at most one live variant per language
+CREATE UNIQUE INDEX posts_translation_lang_key
+    ON posts (translation_group, lang)
+    WHERE translation_group IS NOT NULL AND deleted_at IS NULL;
+CREATE UNIQUE INDEX pages_translation_lang_key
+    ON pages (translation_group, lang)
+    WHERE translation_group IS NOT NULL AND deleted_at IS NULL;
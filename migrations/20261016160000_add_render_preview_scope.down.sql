@@ -0,0 +1,31 @@
+-- Remove the render_preview scope by recreating the type
+
+UPDATE api_keys
+SET scopes = array_remove(scopes, 'render_preview'::api_scope);
+
+ALTER TYPE api_scope RENAME TO api_scope_old;
+
+CREATE TYPE api_scope AS ENUM (
+    'post_read',
+    'post_write',
+    'page_read',
+    'page_write',
+    'tag_read',
+    'tag_write',
+    'navigation_read',
+    'navigation_write',
+    'upload_read',
+    'upload_write',
+    'settings_read',
+    'settings_write',
+    'job_read',
+    'audit_read',
+    'snapshot_read',
+    'snapshot_write',
+    'comment_write'
+);
+
+ALTER TABLE api_keys
+    ALTER COLUMN scopes TYPE api_scope[] USING scopes::text::api_scope[];
+
+DROP TYPE api_scope_old;
@@ -0,0 +1,3 @@
+-- Scope for the render preview endpoint
+
+ALTER TYPE api_scope ADD VALUE IF NOT EXISTS 'render_preview';
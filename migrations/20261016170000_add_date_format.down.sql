@@ -0,0 +1,2 @@
+ALTER TABLE site_settings
+    DROP COLUMN IF EXISTS date_format;
@@ -0,0 +1,5 @@
+-- Site-wide strftime pattern for dates shown to readers
+
+ALTER TABLE site_settings
+    ADD COLUMN date_format TEXT NOT NULL DEFAULT '%B %-d, %Y'
+        CHECK (btrim(date_format) <> '');
@@ -0,0 +1,11 @@
+ALTER TABLE pages
+    DROP COLUMN IF EXISTS render_failed_at,
+    DROP COLUMN IF EXISTS render_error,
+    DROP COLUMN IF EXISTS render_status;
+
+ALTER TABLE posts
+    DROP COLUMN IF EXISTS render_failed_at,
+    DROP COLUMN IF EXISTS render_error,
+    DROP COLUMN IF EXISTS render_status;
+
+DROP TYPE IF EXISTS render_status;
@@ -0,0 +1,13 @@
+-- Outcome of the latest render job for each post and page
+
+CREATE TYPE render_status AS ENUM ('ok', 'failed');
+
+ALTER TABLE posts
+    ADD COLUMN render_status render_status NOT NULL DEFAULT 'ok',
+    ADD COLUMN render_error TEXT,
+    ADD COLUMN render_failed_at TIMESTAMPTZ;
+
+ALTER TABLE pages
+    ADD COLUMN render_status render_status NOT NULL DEFAULT 'ok',
+    ADD COLUMN render_error TEXT,
+    ADD COLUMN render_failed_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+ALTER TABLE pages DROP COLUMN IF EXISTS render_warnings;
+ALTER TABLE posts DROP COLUMN IF EXISTS render_warnings;
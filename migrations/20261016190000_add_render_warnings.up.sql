@@ -0,0 +1,4 @@
+-- Non-blocking content warnings from the last successful render, such as
+-- links to missing posts or images referencing deleted uploads.
+ALTER TABLE posts ADD COLUMN render_warnings JSONB NOT NULL DEFAULT '[]'::jsonb;
+ALTER TABLE pages ADD COLUMN render_warnings JSONB NOT NULL DEFAULT '[]'::jsonb;
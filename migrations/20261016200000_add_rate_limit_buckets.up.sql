@@ -0,0 +1,10 @@
+-- Token buckets for the Postgres API rate limit backend, shared by every node.
+-- The contents are disposable, so the table skips the WAL; a missing bucket
+-- is simply a full one.
+CREATE UNLOGGED TABLE rate_limit_buckets (
+    bucket_key TEXT PRIMARY KEY,
+    tokens DOUBLE PRECISION NOT NULL,
+    refilled_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX rate_limit_buckets_refilled_at_idx ON rate_limit_buckets (refilled_at);
@@ -0,0 +1,16 @@
+-- Responses to API create requests sent with an Idempotency-Key, replayed
+-- when the same API key retries with the same key. A row without a response
+-- marks a request that is still running.
+CREATE TABLE api_idempotency_keys (
+    api_key_id UUID NOT NULL REFERENCES api_keys (id) ON DELETE CASCADE,
+    idempotency_key TEXT NOT NULL,
+    request_hash TEXT NOT NULL,
+    response_status SMALLINT,
+    response_content_type TEXT,
+    response_body BYTEA,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (api_key_id, idempotency_key)
+);
+
+CREATE INDEX api_idempotency_keys_expires_at_idx ON api_idempotency_keys (expires_at);
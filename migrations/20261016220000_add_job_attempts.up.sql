@@ -0,0 +1,11 @@
+-- One row per failed run of a queued job. apalis keeps only the latest error
+-- on the job itself, and retrying a job from the admin resets its attempts.
+CREATE TABLE job_attempts (
+    id UUID PRIMARY KEY,
+    job_id TEXT NOT NULL REFERENCES apalis.jobs (id) ON DELETE CASCADE,
+    attempt INTEGER NOT NULL,
+    error TEXT NOT NULL,
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX job_attempts_job_id_idx ON job_attempts (job_id, failed_at);
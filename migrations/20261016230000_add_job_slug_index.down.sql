@@ -0,0 +1 @@
+DROP INDEX IF EXISTS apalis.apalis_jobs_slug_idx;
@@ -0,0 +1,2 @@
+-- Render progress looks up the latest jobs naming a post or page slug.
+CREATE INDEX apalis_jobs_slug_idx ON apalis.jobs ((job ->> 'slug'), id DESC);
@@ -0,0 +1,5 @@
+ALTER TABLE site_settings
+    DROP COLUMN IF EXISTS feed_item_limit,
+    DROP COLUMN IF EXISTS feed_content_mode;
+
+DROP TYPE IF EXISTS feed_content_mode;
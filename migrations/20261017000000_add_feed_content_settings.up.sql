@@ -0,0 +1,8 @@
+-- What RSS and Atom items carry, and how many of them a feed lists
+
+CREATE TYPE feed_content_mode AS ENUM ('excerpt', 'summary', 'full');
+
+ALTER TABLE site_settings
+    ADD COLUMN feed_content_mode feed_content_mode NOT NULL DEFAULT 'excerpt',
+    ADD COLUMN feed_item_limit INTEGER NOT NULL DEFAULT 20
+        CHECK (feed_item_limit BETWEEN 1 AND 100);
@@ -0,0 +1,10 @@
+DROP INDEX IF EXISTS uploads_checksum_unique;
+-- Corrupted records of files uploaded again would violate the full constraint.
+DELETE FROM uploads a
+    USING uploads b
+    WHERE a.checksum = b.checksum AND a.checksum_mismatch AND NOT b.checksum_mismatch;
+ALTER TABLE uploads ADD CONSTRAINT uploads_checksum_unique UNIQUE (checksum);
+
+ALTER TABLE uploads
+    DROP COLUMN IF EXISTS checksum_mismatch,
+    DROP COLUMN IF EXISTS last_verified_at;
@@ -0,0 +1,9 @@
+-- When each stored file was last re-hashed, and whether it still matched
+
+ALTER TABLE uploads
+    ADD COLUMN last_verified_at TIMESTAMPTZ,
+    ADD COLUMN checksum_mismatch BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- A corrupted copy must not stop the intact file from being uploaded again.
+ALTER TABLE uploads DROP CONSTRAINT uploads_checksum_unique;
+CREATE UNIQUE INDEX uploads_checksum_unique ON uploads (checksum) WHERE NOT checksum_mismatch;
@@ -0,0 +1,5 @@
+-- Raster favicon (PNG or ICO) picked from the uploads; the inline SVG stays
+-- the fallback
+
+ALTER TABLE site_settings
+    ADD COLUMN favicon_upload_id UUID REFERENCES uploads (id) ON DELETE SET NULL;
@@ -0,0 +1,5 @@
+-- Layout a page is rendered with; names are checked against the template
+-- registry when a page is saved
+
+ALTER TABLE pages
+    ADD COLUMN template TEXT NOT NULL DEFAULT 'default';
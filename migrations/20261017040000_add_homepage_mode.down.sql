@@ -0,0 +1,5 @@
+ALTER TABLE site_settings
+    DROP COLUMN IF EXISTS homepage_page_id,
+    DROP COLUMN IF EXISTS homepage_mode;
+
+DROP TYPE IF EXISTS homepage_mode;
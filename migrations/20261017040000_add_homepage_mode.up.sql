@@ -0,0 +1,8 @@
+-- What the public site serves at `/`: the post feed, or a published page with
+-- the feed moved to `/posts`
+
+CREATE TYPE homepage_mode AS ENUM ('feed', 'page');
+
+ALTER TABLE site_settings
+    ADD COLUMN homepage_mode homepage_mode NOT NULL DEFAULT 'feed',
+    ADD COLUMN homepage_page_id UUID REFERENCES pages (id) ON DELETE SET NULL;
@@ -0,0 +1,20 @@
+-- Editor autosaves, kept apart from the live post so saving never publishes.
+-- One row per existing post, plus one per admin for the new-post editor.
+
+CREATE TABLE post_drafts (
+    id UUID PRIMARY KEY,
+    post_id UUID REFERENCES posts (id) ON DELETE CASCADE,
+    author TEXT NOT NULL,
+    title TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    excerpt TEXT NOT NULL,
+    body_markdown TEXT NOT NULL,
+    summary_markdown TEXT,
+    saved_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX post_drafts_post_id_key ON post_drafts (post_id)
+    WHERE post_id IS NOT NULL;
+
+CREATE UNIQUE INDEX post_drafts_new_post_author_key ON post_drafts (author)
+    WHERE post_id IS NULL;
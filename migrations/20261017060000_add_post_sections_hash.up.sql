@@ -0,0 +1,6 @@
+-- Hash of the body markdown, public site URL and renderer settings the stored
+-- post_sections were rendered from. Incremental renders only reuse sections
+-- whose hash matches the markdown being edited.
+
+ALTER TABLE posts
+    ADD COLUMN sections_hash TEXT;
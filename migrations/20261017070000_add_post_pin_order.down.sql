@@ -0,0 +1,20 @@
+DROP INDEX IF EXISTS posts_published_keyset_idx;
+DROP INDEX IF EXISTS posts_admin_primary_time_idx;
+
+ALTER TABLE posts DROP CONSTRAINT IF EXISTS posts_pin_order_unpinned_zero;
+ALTER TABLE posts DROP COLUMN IF EXISTS pin_order;
+
+CREATE INDEX posts_published_keyset_idx
+    ON posts (pinned DESC, published_at DESC NULLS LAST, id DESC)
+    WHERE status = 'published';
+
+CREATE INDEX posts_admin_primary_time_idx
+    ON posts (
+        pinned DESC,
+        (CASE
+            WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
+            ELSE COALESCE(updated_at, created_at)
+        END) DESC,
+        id DESC
+    )
+    INCLUDE (status);
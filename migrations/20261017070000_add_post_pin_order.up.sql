@@ -0,0 +1,24 @@
+-- Position of a pinned post among the other pinned posts, lowest first.
+-- Unpinned posts keep 0 so the column never reorders them.
+
+ALTER TABLE posts ADD COLUMN pin_order INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE posts
+    ADD CONSTRAINT posts_pin_order_unpinned_zero CHECK (pinned OR pin_order = 0);
+
+DROP INDEX IF EXISTS posts_published_keyset_idx;
+CREATE INDEX posts_published_keyset_idx
+    ON posts (pinned DESC, pin_order, published_at DESC NULLS LAST, id DESC)
+    WHERE status = 'published';
+
+DROP INDEX IF EXISTS posts_admin_primary_time_idx;
+CREATE INDEX posts_admin_primary_time_idx
+    ON posts (
+        pinned DESC,
+        pin_order,
+        (CASE
+            WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
+            ELSE COALESCE(updated_at, created_at)
+        END) DESC,
+        id DESC
+    )
+    INCLUDE (status);
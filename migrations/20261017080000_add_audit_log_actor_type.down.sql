@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS audit_logs_actor_type_idx;
+
+ALTER TABLE audit_logs
+    DROP COLUMN IF EXISTS actor_key_id,
+    DROP COLUMN IF EXISTS actor_type;
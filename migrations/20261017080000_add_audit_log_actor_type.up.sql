@@ -0,0 +1,12 @@
+-- Origin of the actor behind an audit entry, and the API key it used
+
+ALTER TABLE audit_logs
+    ADD COLUMN actor_type TEXT NOT NULL DEFAULT 'admin'
+        CHECK (actor_type IN ('admin', 'api_key', 'system')),
+    ADD COLUMN actor_key_id UUID;
+
+UPDATE audit_logs SET actor_type = 'api_key' WHERE actor LIKE 'api\_key:%' OR actor LIKE 'api-key:%';
+UPDATE audit_logs SET actor_type = 'system' WHERE actor IN ('system', 'sighup', 'cli', 'import-markdown');
+
+CREATE INDEX audit_logs_actor_type_idx
+    ON audit_logs (actor_type, created_at DESC, id DESC);
@@ -0,0 +1,4 @@
+ALTER TABLE site_settings
+    DROP COLUMN IF EXISTS homepage_layout;
+
+DROP TYPE IF EXISTS homepage_layout;
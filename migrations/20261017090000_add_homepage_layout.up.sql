@@ -0,0 +1,7 @@
+-- How the post feed at the site root lays out its posts: one per row, a card
+-- grid, or a featured post above a list
+
+CREATE TYPE homepage_layout AS ENUM ('list', 'grid', 'magazine');
+
+ALTER TABLE site_settings
+    ADD COLUMN homepage_layout homepage_layout NOT NULL DEFAULT 'list';
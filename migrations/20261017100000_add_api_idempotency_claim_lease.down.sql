@@ -0,0 +1,3 @@
+ALTER TABLE api_idempotency_keys
+    DROP COLUMN IF EXISTS claimed_at,
+    DROP COLUMN IF EXISTS claim_token;
@@ -0,0 +1,6 @@
+-- Claims now carry a token naming the request that owns them and the time
+-- they were taken, so a claim left behind by an abandoned request can be
+-- taken over once its lease runs out.
+ALTER TABLE api_idempotency_keys
+    ADD COLUMN claim_token UUID NOT NULL DEFAULT gen_random_uuid(),
+    ADD COLUMN claimed_at TIMESTAMPTZ NOT NULL DEFAULT now();
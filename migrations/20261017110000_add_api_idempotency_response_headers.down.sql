@@ -0,0 +1,3 @@
+ALTER TABLE api_idempotency_keys
+    DROP COLUMN IF EXISTS response_etag,
+    DROP COLUMN IF EXISTS response_location;
@@ -0,0 +1,5 @@
+-- Headers a replayed response must carry besides its body: where the created
+-- resource lives and the ETag a follow-up If-Match needs.
+ALTER TABLE api_idempotency_keys
+    ADD COLUMN response_location TEXT,
+    ADD COLUMN response_etag TEXT;
@@ -0,0 +1,121 @@
+//! Who is behind the write currently being served.
+//!
+//! Services take the actor's name explicitly. Its origin and, for API keys,
+//! the key id are scoped to the task like the request id: the admin session
+//! and API auth middleware set them for requests, publish jobs restore the
+//! actor recorded in their payload, and anything else runs as the system.
+
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::application::api_keys::ApiPrincipal;
+use crate::domain::types::ActorType;
+
+/// Actor name for cron jobs, signals and other unattended work.
+pub const SYSTEM_ACTOR: &str = "system";
+
+tokio::task_local! {
+    static ACTOR: Actor;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Actor {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub actor_type: ActorType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<Uuid>,
+}
+
+impl Actor {
+    pub fn admin(username: &str) -> Self {
+        Self {
+            name: username.to_string(),
+            actor_type: ActorType::Admin,
+            api_key_id: None,
+        }
+    }
+
+    pub fn system() -> Self {
+        Self {
+            name: SYSTEM_ACTOR.to_string(),
+            actor_type: ActorType::System,
+            api_key_id: None,
+        }
+    }
+}
+
+impl From<&ApiPrincipal> for Actor {
+    fn from(principal: &ApiPrincipal) -> Self {
+        Self {
+            name: format!("api-key:{}:{}", principal.prefix, principal.name),
+            actor_type: ActorType::ApiKey,
+            api_key_id: Some(principal.key_id),
+        }
+    }
+}
+
+/// Run `future` with `actor` as the current actor.
+pub async fn scope<F>(actor: Actor, future: F) -> F::Output
+where
+    F: Future,
+{
+    ACTOR.scope(actor, future).await
+}
+
+/// The actor of the request or job being served; the system outside both.
+pub fn current() -> Actor {
+    ACTOR
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| Actor::system())
+}
+
+/// Add the current actor to a job payload object as `actor`, so the job is
+/// attributed to whoever enqueued it.
+pub fn tag_job_payload(payload: &mut serde_json::Value) {
+    if let (Ok(actor), Some(object)) = (ACTOR.try_with(Clone::clone), payload.as_object_mut())
+        && let Ok(value) = serde_json::to_value(actor)
+    {
+        object.entry("actor").or_insert(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn actor_defaults_to_the_system_outside_a_scope() {
+        assert_eq!(current(), Actor::system());
+
+        let mut untagged = serde_json::json!({ "slug": "hello" });
+        tag_job_payload(&mut untagged);
+        assert!(untagged.get("actor").is_none());
+    }
+
+    #[tokio::test]
+    async fn api_key_actor_is_visible_inside_scope_and_tags_jobs() {
+        let key_id = Uuid::new_v4();
+        let principal = ApiPrincipal {
+            key_id,
+            name: "deploy".to_string(),
+            prefix: "sk_abc".to_string(),
+            scopes: Vec::new(),
+        };
+
+        let mut payload = serde_json::json!({ "slug": "hello" });
+        scope(Actor::from(&principal), async {
+            let actor = current();
+            assert_eq!(actor.name, "api-key:sk_abc:deploy");
+            assert_eq!(actor.actor_type, ActorType::ApiKey);
+            tag_job_payload(&mut payload);
+        })
+        .await;
+
+        let tagged: Actor = serde_json::from_value(payload["actor"].clone()).expect("actor");
+        assert_eq!(tagged.api_key_id, Some(key_id));
+        assert_eq!(payload["actor"]["type"], "api_key");
+    }
+}
@@ -1,22 +1,137 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use serde::Serialize;
+use serde_json::Value;
 use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
 use uuid::Uuid;
 
-use crate::application::pagination::{AuditCursor, CursorPage, PageRequest};
+use crate::application::pagination::{AuditCursor, CursorKeys, CursorPage, PageRequest};
 use crate::application::repos::{AuditQueryFilter, AuditRepo, RepoError};
+use crate::application::{actor, request_id};
 use crate::domain::entities::AuditLogRecord;
 
+/// Longest string value kept verbatim in a recorded change.
+const MAX_CHANGE_TEXT_CHARS: usize = 200;
+/// Bookkeeping fields that move on every write and say nothing about the edit.
+const IGNORED_CHANGE_FIELDS: &[&str] = &["id", "created_at", "updated_at"];
+/// Entries fetched per query while exporting.
+const EXPORT_PAGE_SIZE: u32 = 200;
+/// Upper bound on exported entries; narrow the filter for more.
+pub const EXPORT_MAX_ROWS: usize = 50_000;
+const EXPORT_COLUMNS: &[&str] = &[
+    "id",
+    "created_at",
+    "actor",
+    "actor_type",
+    "actor_key_id",
+    "action",
+    "entity_type",
+    "entity_id",
+    "request_id",
+    "changes",
+    "payload",
+];
+
+/// Shallow field diff between two versions of an entity.
+///
+/// Only top-level fields are compared. String values longer than
+/// [`MAX_CHANGE_TEXT_CHARS`] are truncated, derived `*_html` fields are
+/// skipped, and secret-like fields (`*_hash`, `*password*`, `*secret*`,
+/// `*token*`) are never recorded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct AuditChanges(BTreeMap<String, AuditFieldChange>);
+
+/// Old and new value of a single changed field.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditFieldChange {
+    pub old: Value,
+    pub new: Value,
+}
+
+impl AuditChanges {
+    /// Diff two serializable snapshots of the same entity.
+    pub fn between<T: Serialize>(before: &T, after: &T) -> Self {
+        let (Ok(Value::Object(before)), Ok(Value::Object(after))) =
+            (serde_json::to_value(before), serde_json::to_value(after))
+        else {
+            return Self::default();
+        };
+
+        let fields: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+        let mut changes = BTreeMap::new();
+        for field in fields {
+            if !is_recordable_field(field) {
+                continue;
+            }
+            let old = before.get(field).cloned().unwrap_or(Value::Null);
+            let new = after.get(field).cloned().unwrap_or(Value::Null);
+            if old != new {
+                changes.insert(
+                    field.clone(),
+                    AuditFieldChange {
+                        old: truncate_value(old),
+                        new: truncate_value(new),
+                    },
+                );
+            }
+        }
+
+        Self(changes)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &AuditFieldChange)> {
+        self.0
+            .iter()
+            .map(|(field, change)| (field.as_str(), change))
+    }
+}
+
+fn is_recordable_field(field: &str) -> bool {
+    let name = field.to_ascii_lowercase();
+    let secret = name.ends_with("_hash")
+        || name.contains("password")
+        || name.contains("secret")
+        || name.contains("token");
+    !secret && !name.ends_with("_html") && !IGNORED_CHANGE_FIELDS.contains(&name.as_str())
+}
+
+fn truncate_value(value: Value) -> Value {
+    match value {
+        Value::String(text) if text.chars().count() > MAX_CHANGE_TEXT_CHARS => {
+            let mut truncated: String = text.chars().take(MAX_CHANGE_TEXT_CHARS).collect();
+            truncated.push('…');
+            Value::String(truncated)
+        }
+        other => other,
+    }
+}
+
 /// Thin wrapper around the audit repository to simplify logging admin actions.
 #[derive(Clone)]
 pub struct AdminAuditService {
     repo: Arc<dyn AuditRepo>,
+    cursor_keys: CursorKeys,
 }
 
 impl AdminAuditService {
     pub fn new(repo: Arc<dyn AuditRepo>) -> Self {
-        Self { repo }
+        Self {
+            repo,
+            cursor_keys: CursorKeys::ephemeral(),
+        }
+    }
+
+    /// Read back cursors with the keys the repositories sign them with.
+    pub fn with_cursor_keys(mut self, cursor_keys: CursorKeys) -> Self {
+        self.cursor_keys = cursor_keys;
+        self
     }
 
     pub async fn record<S>(
@@ -27,6 +142,44 @@ impl AdminAuditService {
         entity_id: Option<&str>,
         payload: Option<&S>,
     ) -> Result<(), RepoError>
+    where
+        S: Serialize,
+    {
+        self.append(actor, action, entity_type, entity_id, payload, None)
+            .await
+    }
+
+    /// Record an update together with the fields it changed.
+    pub async fn record_with_changes<S>(
+        &self,
+        actor: &str,
+        action: &str,
+        entity_type: &str,
+        entity_id: Option<&str>,
+        payload: Option<&S>,
+        changes: &AuditChanges,
+    ) -> Result<(), RepoError>
+    where
+        S: Serialize,
+    {
+        let changes = if changes.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_value(changes).map_err(RepoError::from_persistence)?)
+        };
+        self.append(actor, action, entity_type, entity_id, payload, changes)
+            .await
+    }
+
+    async fn append<S>(
+        &self,
+        actor: &str,
+        action: &str,
+        entity_type: &str,
+        entity_id: Option<&str>,
+        payload: Option<&S>,
+        changes: Option<Value>,
+    ) -> Result<(), RepoError>
     where
         S: Serialize,
     {
@@ -35,13 +188,18 @@ impl AdminAuditService {
             None => None,
         };
 
+        let origin = actor::current();
         let record = AuditLogRecord {
             id: Uuid::new_v4(),
             actor: actor.to_string(),
+            actor_type: origin.actor_type,
+            actor_key_id: origin.api_key_id,
             action: action.to_string(),
             entity_type: entity_type.to_string(),
             entity_id: entity_id.map(|value| value.to_string()),
             payload_text,
+            changes,
+            request_id: request_id::current(),
             created_at: OffsetDateTime::now_utc(),
         };
 
@@ -94,4 +252,164 @@ impl AdminAuditService {
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<AuditLogRecord>, RepoError> {
         self.repo.find_by_id(id).await
     }
+
+    /// Entries matching `filter` as CSV, newest first, with a header row.
+    /// At most [`EXPORT_MAX_ROWS`] entries are included.
+    pub async fn export_csv(&self, filter: &AuditQueryFilter) -> Result<String, RepoError> {
+        let mut csv = String::new();
+        push_csv_row(&mut csv, EXPORT_COLUMNS.iter().copied());
+
+        let mut cursor = None;
+        let mut rows = 0;
+        loop {
+            let page = self
+                .repo
+                .list_filtered(PageRequest::new(EXPORT_PAGE_SIZE, cursor), filter)
+                .await?;
+            for record in page.items.iter().take(EXPORT_MAX_ROWS - rows) {
+                push_record(&mut csv, record);
+                rows += 1;
+            }
+            cursor = match page.next_cursor {
+                Some(next) if rows < EXPORT_MAX_ROWS => {
+                    Some(AuditCursor::decode(&self.cursor_keys, &next)?)
+                }
+                _ => break,
+            };
+        }
+
+        Ok(csv)
+    }
+}
+
+fn push_record(csv: &mut String, record: &AuditLogRecord) {
+    let id = record.id.to_string();
+    let created_at = record
+        .created_at
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| record.created_at.to_string());
+    let changes = record
+        .changes
+        .as_ref()
+        .map(Value::to_string)
+        .unwrap_or_default();
+    let actor_key_id = record
+        .actor_key_id
+        .map(|id| id.to_string())
+        .unwrap_or_default();
+    push_csv_row(
+        csv,
+        [
+            id.as_str(),
+            created_at.as_str(),
+            record.actor.as_str(),
+            record.actor_type.as_str(),
+            actor_key_id.as_str(),
+            record.action.as_str(),
+            record.entity_type.as_str(),
+            record.entity_id.as_deref().unwrap_or_default(),
+            record.request_id.as_deref().unwrap_or_default(),
+            changes.as_str(),
+            record.payload_text.as_deref().unwrap_or_default(),
+        ],
+    );
+}
+
+/// Append one RFC 4180 record terminated by CRLF.
+fn push_csv_row<'a>(csv: &mut String, fields: impl IntoIterator<Item = &'a str>) {
+    for (index, field) in fields.into_iter().enumerate() {
+        if index > 0 {
+            csv.push(',');
+        }
+        push_csv_field(csv, field);
+    }
+    csv.push_str("\r\n");
+}
+
+/// Fields containing a comma, quote or line break are quoted, with inner
+/// quotes doubled.
+fn push_csv_field(csv: &mut String, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        csv.push('"');
+        csv.push_str(&field.replace('"', "\"\""));
+        csv.push('"');
+    } else {
+        csv.push_str(field);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Entity<'a> {
+        id: u32,
+        slug: &'a str,
+        body_markdown: String,
+        body_html: &'a str,
+        key_hash: &'a str,
+        updated_at: u32,
+    }
+
+    fn entity(slug: &str, body_markdown: String) -> Entity<'_> {
+        Entity {
+            id: 1,
+            slug,
+            body_markdown,
+            body_html: "<p>x</p>",
+            key_hash: "aaa",
+            updated_at: 1,
+        }
+    }
+
+    #[test]
+    fn unchanged_fields_are_not_recorded() {
+        let before = entity("hello", "body".into());
+        let after = entity("hello-world", "body".into());
+
+        let changes = AuditChanges::between(&before, &after);
+        let fields: Vec<_> = changes.fields().map(|(field, _)| field).collect();
+        assert_eq!(fields, vec!["slug"]);
+        assert_eq!(
+            serde_json::to_value(&changes).expect("serialize"),
+            serde_json::json!({ "slug": { "old": "hello", "new": "hello-world" } })
+        );
+    }
+
+    #[test]
+    fn long_text_values_are_truncated() {
+        let before = entity("a", "x".repeat(MAX_CHANGE_TEXT_CHARS + 50));
+        let after = entity("a", "y".repeat(MAX_CHANGE_TEXT_CHARS));
+
+        let changes = AuditChanges::between(&before, &after);
+        let (_, change) = changes.fields().next().expect("body change");
+        let old = change.old.as_str().expect("string");
+        assert_eq!(old.chars().count(), MAX_CHANGE_TEXT_CHARS + 1);
+        assert!(old.ends_with('…'));
+        assert_eq!(change.new, Value::String("y".repeat(MAX_CHANGE_TEXT_CHARS)));
+    }
+
+    #[test]
+    fn secret_derived_and_bookkeeping_fields_are_excluded() {
+        let before = entity("a", "body".into());
+        let mut after = entity("a", "body".into());
+        after.id = 2;
+        after.body_html = "<p>y</p>";
+        after.key_hash = "bbb";
+        after.updated_at = 2;
+
+        assert!(AuditChanges::between(&before, &after).is_empty());
+        assert!(!is_recordable_field("password"));
+        assert!(!is_recordable_field("session_secret"));
+        assert!(!is_recordable_field("token_prefix"));
+        assert!(is_recordable_field("title"));
+    }
+
+    #[test]
+    fn csv_fields_with_commas_quotes_and_newlines_are_quoted() {
+        let mut csv = String::new();
+        push_csv_row(&mut csv, ["plain", "Hello, \"world\"", "two\nlines", ""]);
+        assert_eq!(csv, "plain,\"Hello, \"\"world\"\"\",\"two\nlines\",\r\n");
+    }
 }
@@ -0,0 +1,350 @@
+//! Admin account management and cookie-backed sessions.
+
+use std::sync::Arc;
+
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, KeyInit, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+use time::{Duration, OffsetDateTime};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::application::repos::{AdminUsersRepo, RepoError};
+use crate::domain::entities::AdminUserRecord;
+use crate::util::secret::random_secret;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const MAX_USERNAME_CHARS: usize = 64;
+const MIN_PASSWORD_CHARS: usize = 12;
+const CSRF_CONTEXT: &[u8] = b"csrf:";
+
+#[derive(Debug, Error)]
+pub enum AdminAuthError {
+    #[error("invalid username or password")]
+    InvalidCredentials,
+    #[error("{0}")]
+    ConstraintViolation(&'static str),
+    #[error("admin user `{0}` already exists")]
+    DuplicateUsername(String),
+    #[error("password hashing failed: {0}")]
+    Hash(String),
+    #[error(transparent)]
+    Repo(#[from] RepoError),
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateAdminUserCommand {
+    pub username: String,
+    pub password: String,
+}
+
+/// Authenticated admin session decoded from a signed cookie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminSession {
+    pub username: String,
+    pub expires_at: OffsetDateTime,
+    nonce: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionPayload {
+    u: String,
+    exp: i64,
+    n: String,
+}
+
+/// HMAC keys used to sign session cookies and derive CSRF tokens.
+#[derive(Clone)]
+pub struct AdminSessionKeys {
+    secret: Arc<[u8]>,
+    ttl: Duration,
+}
+
+impl AdminSessionKeys {
+    pub fn new(secret: impl Into<Vec<u8>>, ttl: Duration) -> Self {
+        Self {
+            secret: Arc::from(secret.into()),
+            ttl,
+        }
+    }
+
+    /// Keys backed by a random per-process secret; sessions do not survive restarts.
+    pub fn ephemeral(ttl: Duration) -> Self {
+        Self::new(random_secret(), ttl)
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Start a session for `username` and return it with its signed cookie value.
+    ///
+    /// Should the session end out of range, it is issued already expired.
+    pub fn issue(&self, username: &str, now: OffsetDateTime) -> (AdminSession, String) {
+        let expires_at = now.checked_add(self.ttl).unwrap_or(now);
+        // The cookie carries whole seconds; so does the session it describes.
+        let expires_at = expires_at.replace_nanosecond(0).unwrap_or(expires_at);
+        let session = AdminSession {
+            username: username.to_string(),
+            expires_at,
+            nonce: Uuid::new_v4().simple().to_string(),
+        };
+        let payload = SessionPayload {
+            u: session.username.clone(),
+            exp: session.expires_at.unix_timestamp(),
+            n: session.nonce.clone(),
+        };
+        // Serializing a struct of strings and integers cannot fail.
+        let encoded = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&payload).unwrap_or_default());
+        let signature = URL_SAFE_NO_PAD.encode(self.sign(encoded.as_bytes()));
+        (session, format!("{encoded}.{signature}"))
+    }
+
+    /// Decode a cookie value, rejecting tampered or expired sessions.
+    pub fn verify(&self, cookie: &str, now: OffsetDateTime) -> Option<AdminSession> {
+        let (encoded, signature) = cookie.split_once('.')?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        let mut mac = self.mac();
+        mac.update(encoded.as_bytes());
+        mac.verify_slice(&signature).ok()?;
+
+        let bytes = URL_SAFE_NO_PAD.decode(encoded).ok()?;
+        let payload: SessionPayload = serde_json::from_slice(&bytes).ok()?;
+        let expires_at = OffsetDateTime::from_unix_timestamp(payload.exp).ok()?;
+        if expires_at <= now {
+            return None;
+        }
+
+        Some(AdminSession {
+            username: payload.u,
+            expires_at,
+            nonce: payload.n,
+        })
+    }
+
+    /// CSRF token bound to a single session.
+    pub fn csrf_token(&self, session: &AdminSession) -> String {
+        URL_SAFE_NO_PAD.encode(self.csrf_mac(session).finalize().into_bytes())
+    }
+
+    pub fn verify_csrf(&self, session: &AdminSession, token: &str) -> bool {
+        let Ok(bytes) = URL_SAFE_NO_PAD.decode(token.trim()) else {
+            return false;
+        };
+        self.csrf_mac(session).verify_slice(&bytes).is_ok()
+    }
+
+    fn csrf_mac(&self, session: &AdminSession) -> HmacSha256 {
+        let mut mac = self.mac();
+        mac.update(CSRF_CONTEXT);
+        mac.update(session.nonce.as_bytes());
+        mac
+    }
+
+    fn sign(&self, data: &[u8]) -> Vec<u8> {
+        let mut mac = self.mac();
+        mac.update(data);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        <HmacSha256 as KeyInit>::new_from_slice(&self.secret)
+            .expect("hmac accepts keys of any length")
+    }
+}
+
+impl From<&crate::config::AdminAuthSettings> for AdminSessionKeys {
+    fn from(settings: &crate::config::AdminAuthSettings) -> Self {
+        let ttl = Duration::try_from(settings.session_ttl)
+            .expect("admin_auth.session_ttl_seconds is bounded at load");
+        match settings.session_secret.as_ref() {
+            Some(secret) => Self::new(secret.as_bytes().to_vec(), ttl),
+            None => {
+                warn!(
+                    target = "soffio::admin::auth",
+                    "admin_auth.session_secret is not set; admin sessions will not survive restarts"
+                );
+                Self::ephemeral(ttl)
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AdminAuthService {
+    users: Arc<dyn AdminUsersRepo>,
+    keys: AdminSessionKeys,
+}
+
+impl AdminAuthService {
+    pub fn new(users: Arc<dyn AdminUsersRepo>, keys: AdminSessionKeys) -> Self {
+        Self { users, keys }
+    }
+
+    pub fn keys(&self) -> &AdminSessionKeys {
+        &self.keys
+    }
+
+    /// Create an admin account with an argon2id password hash.
+    pub async fn create_user(
+        &self,
+        command: CreateAdminUserCommand,
+    ) -> Result<AdminUserRecord, AdminAuthError> {
+        let username = normalize_username(&command.username)?;
+        if command.password.chars().count() < MIN_PASSWORD_CHARS {
+            return Err(AdminAuthError::ConstraintViolation(
+                "password must be at least 12 characters",
+            ));
+        }
+
+        let password_hash = hash_password(command.password).await?;
+        let now = OffsetDateTime::now_utc();
+        let record = AdminUserRecord {
+            id: Uuid::new_v4(),
+            username,
+            password_hash,
+            created_at: now,
+            updated_at: now,
+        };
+
+        match self.users.insert_admin_user(record.clone()).await {
+            Ok(()) => Ok(record),
+            Err(RepoError::Duplicate { .. }) => {
+                Err(AdminAuthError::DuplicateUsername(record.username))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Check credentials and return the matching account.
+    pub async fn authenticate(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<AdminUserRecord, AdminAuthError> {
+        let username =
+            normalize_username(username).map_err(|_| AdminAuthError::InvalidCredentials)?;
+        let Some(user) = self.users.find_admin_user_by_username(&username).await? else {
+            return Err(AdminAuthError::InvalidCredentials);
+        };
+
+        if verify_password(password.to_string(), user.password_hash.clone()).await? {
+            Ok(user)
+        } else {
+            Err(AdminAuthError::InvalidCredentials)
+        }
+    }
+}
+
+fn normalize_username(raw: &str) -> Result<String, AdminAuthError> {
+    let username = raw.trim();
+    let valid_chars = username
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'));
+    if username.is_empty() || username.chars().count() > MAX_USERNAME_CHARS || !valid_chars {
+        return Err(AdminAuthError::ConstraintViolation(
+            "username must be 1-64 characters of letters, digits, `-`, `_` or `.`",
+        ));
+    }
+    Ok(username.to_ascii_lowercase())
+}
+
+async fn hash_password(password: String) -> Result<String, AdminAuthError> {
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::encode_b64(Uuid::new_v4().as_bytes())
+            .map_err(|err| AdminAuthError::Hash(err.to_string()))?;
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|err| AdminAuthError::Hash(err.to_string()))
+    })
+    .await
+    .map_err(|err| AdminAuthError::Hash(err.to_string()))?
+}
+
+async fn verify_password(password: String, stored: String) -> Result<bool, AdminAuthError> {
+    tokio::task::spawn_blocking(move || {
+        let parsed =
+            PasswordHash::new(&stored).map_err(|err| AdminAuthError::Hash(err.to_string()))?;
+        Ok(Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok())
+    })
+    .await
+    .map_err(|err| AdminAuthError::Hash(err.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> AdminSessionKeys {
+        AdminSessionKeys::new(b"test-secret".to_vec(), Duration::hours(1))
+    }
+
+    #[test]
+    fn issued_cookie_round_trips() {
+        let keys = keys();
+        let now = OffsetDateTime::now_utc();
+        let (session, cookie) = keys.issue("editor", now);
+
+        assert_eq!(keys.verify(&cookie, now), Some(session));
+    }
+
+    #[test]
+    fn tampered_or_expired_cookie_is_rejected() {
+        let keys = keys();
+        let now = OffsetDateTime::now_utc();
+        let (_, cookie) = keys.issue("editor", now);
+
+        let (payload, signature) = cookie.split_once('.').expect("signed cookie");
+        let forged = URL_SAFE_NO_PAD.encode(
+            serde_json::to_vec(&SessionPayload {
+                u: "root".into(),
+                exp: (now + Duration::hours(1)).unix_timestamp(),
+                n: "x".into(),
+            })
+            .expect("payload"),
+        );
+        assert!(keys.verify(&format!("{forged}.{signature}"), now).is_none());
+        assert!(keys.verify(payload, now).is_none());
+        assert!(keys.verify(&cookie, now + Duration::hours(2)).is_none());
+
+        let other = AdminSessionKeys::new(b"other-secret".to_vec(), Duration::hours(1));
+        assert!(other.verify(&cookie, now).is_none());
+    }
+
+    #[test]
+    fn sessions_past_the_representable_range_are_already_expired() {
+        let keys = AdminSessionKeys::new(b"test-secret".to_vec(), Duration::MAX);
+        let now = OffsetDateTime::now_utc();
+        let (_, cookie) = keys.issue("editor", now);
+
+        assert!(keys.verify(&cookie, now).is_none());
+    }
+
+    #[test]
+    fn csrf_token_is_bound_to_session() {
+        let keys = keys();
+        let now = OffsetDateTime::now_utc();
+        let (first, _) = keys.issue("editor", now);
+        let (second, _) = keys.issue("editor", now);
+
+        let token = keys.csrf_token(&first);
+        assert!(keys.verify_csrf(&first, &token));
+        assert!(!keys.verify_csrf(&second, &token));
+        assert!(!keys.verify_csrf(&first, "not-a-token"));
+    }
+
+    #[test]
+    fn usernames_are_validated_and_lowercased() {
+        assert_eq!(normalize_username(" Admin ").expect("valid"), "admin");
+        assert!(normalize_username("").is_err());
+        assert!(normalize_username("has space").is_err());
+    }
+}
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
+use crate::application::chrome::ChromeCache;
 use crate::application::error::HttpError;
 use crate::application::repos::{RepoError, SettingsRepo};
+use crate::cache::L0Store;
+use crate::domain::routes::BasePath;
 use crate::presentation::admin::views::{
     AdminBrandView, AdminChrome, AdminMetaView, AdminNavigationItemView, AdminNavigationView,
 };
@@ -11,38 +14,65 @@ const NAV_ITEMS: &[(&str, &str)] = &[
     ("/", "Dashboard"),
     ("/posts", "Posts"),
     ("/pages", "Pages"),
+    ("/schedule", "Calendar"),
     ("/tags", "Tags"),
     ("/navigation", "Navigation"),
     ("/uploads", "Uploads"),
+    ("/comments", "Comments"),
     ("/jobs", "Jobs"),
     ("/audit", "Audit"),
     ("/api-keys", "API keys"),
     ("/settings", "Site settings"),
 ];
 
+/// The settings-derived part of the admin chrome; the active navigation
+/// item and page title are filled in per request.
+#[derive(Clone)]
+struct AdminChromeData {
+    brand_title: String,
+    public_site_url: String,
+}
+
 #[derive(Clone)]
 pub struct AdminChromeService {
     settings: Arc<dyn SettingsRepo>,
+    cache: Option<Arc<L0Store>>,
+    data: Arc<ChromeCache<AdminChromeData>>,
+    base_path: BasePath,
 }
 
 impl AdminChromeService {
     pub fn new(settings: Arc<dyn SettingsRepo>) -> Self {
-        Self { settings }
+        Self {
+            settings,
+            cache: None,
+            data: Arc::new(ChromeCache::new()),
+            base_path: BasePath::default(),
+        }
+    }
+
+    /// Keep the settings-derived chrome between settings changes.
+    pub fn with_cache(mut self, cache: Option<Arc<L0Store>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Point the "View site" link under `base_path`.
+    pub fn with_base_path(mut self, base_path: BasePath) -> Self {
+        self.base_path = base_path;
+        self
     }
 
     pub async fn load(&self, active_path: &str) -> Result<AdminChrome, HttpError> {
-        let settings = self
-            .settings
-            .load_site_settings()
-            .await
-            .map_err(repo_failure)?;
+        let data = match &self.cache {
+            Some(cache) => self.data.get_or_build(cache, || self.load_data()).await?,
+            None => self.load_data().await?,
+        };
 
         let brand = AdminBrandView {
-            title: format!("{} Admin", settings.brand_title),
+            title: data.brand_title,
         };
 
-        let public_site_url = normalize_public_site_url(&settings.public_site_url);
-
         let mut items: Vec<AdminNavigationItemView> = NAV_ITEMS
             .iter()
             .map(|(href, label)| AdminNavigationItemView {
@@ -55,7 +85,7 @@ impl AdminChromeService {
 
         items.push(AdminNavigationItemView {
             label: "View site".to_string(),
-            href: public_site_url,
+            href: data.public_site_url,
             is_active: false,
             open_in_new_tab: true,
         });
@@ -80,6 +110,21 @@ impl AdminChromeService {
             meta,
         })
     }
+
+    async fn load_data(&self) -> Result<AdminChromeData, HttpError> {
+        let settings = self
+            .settings
+            .load_site_settings()
+            .await
+            .map_err(repo_failure)?;
+
+        Ok(AdminChromeData {
+            brand_title: format!("{} Admin", settings.brand_title),
+            public_site_url: self
+                .base_path
+                .site_url_or_relative(&settings.public_site_url),
+        })
+    }
 }
 
 fn repo_failure(err: RepoError) -> HttpError {
@@ -90,11 +135,3 @@ fn repo_failure(err: RepoError) -> HttpError {
         err.to_string(),
     )
 }
-
-fn normalize_public_site_url(url: &str) -> String {
-    if url.ends_with('/') {
-        url.to_string()
-    } else {
-        format!("{url}/")
-    }
-}
@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+use thiserror::Error;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::application::admin::audit::AdminAuditService;
+use crate::application::pagination::{CommentCursor, CursorPage, PageRequest};
+use crate::application::repos::{CommentQueryFilter, CommentsRepo, PostsRepo, RepoError};
+use crate::cache::CacheTrigger;
+use crate::domain::entities::CommentRecord;
+use crate::domain::types::{CommentStatus, PostStatus};
+
+const MAX_AUTHOR_CHARS: usize = 120;
+
+#[derive(Debug, Error)]
+pub enum AdminCommentError {
+    #[error("comment not found")]
+    NotFound,
+    #[error("post not found")]
+    PostNotFound,
+    #[error("{0}")]
+    ConstraintViolation(&'static str),
+    #[error(transparent)]
+    Repo(#[from] RepoError),
+}
+
+#[derive(Debug, Clone)]
+pub struct IngestCommentCommand {
+    pub post_id: Uuid,
+    pub author: String,
+    pub body: String,
+    pub source_ip: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct AdminCommentService {
+    repo: Arc<dyn CommentsRepo>,
+    posts: Arc<dyn PostsRepo>,
+    audit: AdminAuditService,
+    cache_trigger: Option<Arc<CacheTrigger>>,
+    max_body_chars: usize,
+}
+
+impl AdminCommentService {
+    pub fn new(
+        repo: Arc<dyn CommentsRepo>,
+        posts: Arc<dyn PostsRepo>,
+        audit: AdminAuditService,
+        max_body_chars: usize,
+    ) -> Self {
+        Self {
+            repo,
+            posts,
+            audit,
+            cache_trigger: None,
+            max_body_chars,
+        }
+    }
+
+    /// Attach a cache trigger for invalidating post pages after moderation.
+    pub fn with_cache_trigger(mut self, trigger: Arc<CacheTrigger>) -> Self {
+        self.cache_trigger = Some(trigger);
+        self
+    }
+
+    /// Attach an optional cache trigger.
+    pub fn with_cache_trigger_opt(mut self, trigger: Option<Arc<CacheTrigger>>) -> Self {
+        self.cache_trigger = trigger;
+        self
+    }
+
+    /// Accept an externally submitted comment into the moderation queue.
+    ///
+    /// Comments are stored as plain text in the `pending` state and stay
+    /// hidden from the public site until a moderator approves them.
+    pub async fn ingest(
+        &self,
+        actor: &str,
+        command: IngestCommentCommand,
+    ) -> Result<CommentRecord, AdminCommentError> {
+        let author = command.author.trim();
+        if author.is_empty() || author.chars().count() > MAX_AUTHOR_CHARS {
+            return Err(AdminCommentError::ConstraintViolation("author"));
+        }
+        let body = command.body.trim();
+        if body.is_empty() || body.chars().count() > self.max_body_chars {
+            return Err(AdminCommentError::ConstraintViolation("body"));
+        }
+
+        let post = self
+            .posts
+            .find_by_id(command.post_id)
+            .await?
+            .ok_or(AdminCommentError::PostNotFound)?;
+        if post.status != PostStatus::Published {
+            return Err(AdminCommentError::PostNotFound);
+        }
+
+        let now = OffsetDateTime::now_utc();
+        let record = CommentRecord {
+            id: Uuid::new_v4(),
+            post_id: post.id,
+            author: author.to_string(),
+            body: body.to_string(),
+            status: CommentStatus::Pending,
+            source_ip: command.source_ip,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.repo.insert_comment(record.clone()).await?;
+
+        let snapshot = CommentSnapshot::from(&record);
+        self.audit
+            .record(
+                actor,
+                "comment.create",
+                "comment",
+                Some(&record.id.to_string()),
+                Some(&snapshot),
+            )
+            .await?;
+
+        Ok(record)
+    }
+
+    pub async fn list(
+        &self,
+        filter: &CommentQueryFilter,
+        page: PageRequest<CommentCursor>,
+    ) -> Result<CursorPage<CommentRecord>, AdminCommentError> {
+        self.repo
+            .list_comments(filter, page)
+            .await
+            .map_err(AdminCommentError::from)
+    }
+
+    pub async fn load(&self, id: Uuid) -> Result<CommentRecord, AdminCommentError> {
+        self.repo
+            .find_comment(id)
+            .await?
+            .ok_or(AdminCommentError::NotFound)
+    }
+
+    /// Count comments grouped by moderation status.
+    pub async fn status_counts(
+        &self,
+        base_filter: &CommentQueryFilter,
+    ) -> Result<AdminCommentStatusCounts, AdminCommentError> {
+        let filter_total = CommentQueryFilter {
+            status: None,
+            ..(base_filter.clone())
+        };
+        let filter_pending = CommentQueryFilter {
+            status: Some(CommentStatus::Pending),
+            ..(base_filter.clone())
+        };
+        let filter_approved = CommentQueryFilter {
+            status: Some(CommentStatus::Approved),
+            ..(base_filter.clone())
+        };
+        let filter_spam = CommentQueryFilter {
+            status: Some(CommentStatus::Spam),
+            ..(base_filter.clone())
+        };
+
+        let (total, pending, approved, spam) = tokio::try_join!(
+            self.repo.count_comments(&filter_total),
+            self.repo.count_comments(&filter_pending),
+            self.repo.count_comments(&filter_approved),
+            self.repo.count_comments(&filter_spam),
+        )?;
+
+        Ok(AdminCommentStatusCounts {
+            total,
+            pending,
+            approved,
+            spam,
+        })
+    }
+
+    pub async fn approve(&self, actor: &str, id: Uuid) -> Result<CommentRecord, AdminCommentError> {
+        self.set_status(actor, id, CommentStatus::Approved, "comment.approve")
+            .await
+    }
+
+    pub async fn mark_spam(
+        &self,
+        actor: &str,
+        id: Uuid,
+    ) -> Result<CommentRecord, AdminCommentError> {
+        self.set_status(actor, id, CommentStatus::Spam, "comment.spam")
+            .await
+    }
+
+    async fn set_status(
+        &self,
+        actor: &str,
+        id: Uuid,
+        status: CommentStatus,
+        action: &str,
+    ) -> Result<CommentRecord, AdminCommentError> {
+        let existing = self.load(id).await?;
+        let updated = self.repo.update_comment_status(existing.id, status).await?;
+
+        let snapshot = CommentSnapshot::from(&updated);
+        self.audit
+            .record(
+                actor,
+                action,
+                "comment",
+                Some(&updated.id.to_string()),
+                Some(&snapshot),
+            )
+            .await?;
+
+        if let Some(trigger) = &self.cache_trigger
+            && let Some(post) = self.posts.find_by_id(updated.post_id).await?
+        {
+            trigger.post_upserted(post.id, &post.slug).await;
+        }
+
+        Ok(updated)
+    }
+}
+
+/// Status counts for comment moderation filters.
+#[derive(Debug, Clone)]
+pub struct AdminCommentStatusCounts {
+    pub total: u64,
+    pub pending: u64,
+    pub approved: u64,
+    pub spam: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct CommentSnapshot<'a> {
+    post_id: Uuid,
+    author: &'a str,
+    status: CommentStatus,
+}
+
+impl<'a> From<&'a CommentRecord> for CommentSnapshot<'a> {
+    fn from(record: &'a CommentRecord) -> Self {
+        Self {
+            post_id: record.post_id,
+            author: &record.author,
+            status: record.status,
+        }
+    }
+}
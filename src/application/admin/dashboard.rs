@@ -3,11 +3,13 @@ mod navigation_panel;
 mod pages_panel;
 mod posts_panel;
 mod tags_panel;
+mod upcoming;
 mod uploads_panel;
 
 use std::sync::Arc;
 
 use axum::http::StatusCode;
+use chrono_tz::Tz;
 
 use crate::application::{
     error::HttpError,
@@ -15,6 +17,8 @@ use crate::application::{
 };
 use crate::presentation::admin::views::AdminDashboardView;
 
+pub use upcoming::{ScheduledKind, UpcomingItem};
+
 const SOURCE: &str = "application::admin::dashboard::AdminDashboardService";
 const POSTS_FAILURE_MESSAGE: &str = "Failed to compute post dashboard metrics";
 const PAGES_FAILURE_MESSAGE: &str = "Failed to compute page dashboard metrics";
@@ -24,6 +28,7 @@ const NAVIGATION_FAILURE_MESSAGE: &str = "Failed to compute navigation dashboard
 const NAVIGATION_LIST_FAILURE_MESSAGE: &str = "Failed to enumerate navigation entries";
 const UPLOADS_FAILURE_MESSAGE: &str = "Failed to compute upload dashboard metrics";
 const API_KEYS_FAILURE_MESSAGE: &str = "Failed to compute API key dashboard metrics";
+const UPCOMING_FAILURE_MESSAGE: &str = "Failed to list scheduled posts and pages";
 
 const DOCUMENT_CONTENT_TYPES: &[&str] = &[
     "application/pdf",
@@ -78,16 +83,25 @@ impl AdminDashboardService {
         }
     }
 
-    pub async fn overview(&self) -> Result<AdminDashboardView, HttpError> {
-        let (posts_panel, pages_panel, tags_panel, navigation_panel, uploads_panel, api_keys_panel) =
-            tokio::try_join!(
-                self.collect_posts_panel(),
-                self.collect_pages_panel(),
-                self.collect_tags_panel(),
-                self.collect_navigation_panel(),
-                self.collect_uploads_panel(),
-                self.collect_api_keys_panel(),
-            )?;
+    /// Dashboard metrics and upcoming items, with times shown in `tz`.
+    pub async fn overview(&self, tz: Tz) -> Result<AdminDashboardView, HttpError> {
+        let (
+            posts_panel,
+            pages_panel,
+            tags_panel,
+            navigation_panel,
+            uploads_panel,
+            api_keys_panel,
+            upcoming,
+        ) = tokio::try_join!(
+            self.collect_posts_panel(),
+            self.collect_pages_panel(),
+            self.collect_tags_panel(),
+            self.collect_navigation_panel(),
+            self.collect_uploads_panel(),
+            self.collect_api_keys_panel(),
+            self.collect_upcoming(tz),
+        )?;
 
         Ok(AdminDashboardView {
             title: "Dashboard".to_string(),
@@ -100,6 +114,8 @@ impl AdminDashboardService {
                 api_keys_panel,
             ],
             empty_message: "No assets have been created yet.".to_string(),
+            upcoming,
+            upcoming_empty_message: "Nothing is scheduled.".to_string(),
         })
     }
 }
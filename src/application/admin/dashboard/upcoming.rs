@@ -0,0 +1,113 @@
+use chrono_tz::Tz;
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::application::error::HttpError;
+use crate::domain::entities::ScheduledRecord;
+use crate::presentation::admin::views::{AdminUpcomingItemView, format_timestamp};
+
+use super::{AdminDashboardService, UPCOMING_FAILURE_MESSAGE, repo_failure};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledKind {
+    Post,
+    Page,
+}
+
+/// A draft post or page waiting for its publication time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpcomingItem {
+    pub kind: ScheduledKind,
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub scheduled_at: OffsetDateTime,
+    /// The publication time has passed and the item is still a draft.
+    pub stuck: bool,
+}
+
+impl UpcomingItem {
+    fn new(kind: ScheduledKind, record: ScheduledRecord, now: OffsetDateTime) -> Self {
+        Self {
+            kind,
+            id: record.id,
+            slug: record.slug,
+            title: record.title,
+            stuck: record.scheduled_at < now,
+            scheduled_at: record.scheduled_at,
+        }
+    }
+
+    fn into_view(self, tz: Tz) -> AdminUpcomingItemView {
+        let (kind, edit_href) = match self.kind {
+            ScheduledKind::Post => ("Post", format!("/posts/{}/edit", self.id)),
+            ScheduledKind::Page => ("Page", format!("/pages/{}/edit", self.id)),
+        };
+        AdminUpcomingItemView {
+            kind,
+            title: self.title,
+            edit_href,
+            scheduled_at: format_timestamp(self.scheduled_at, tz),
+            stuck: self.stuck,
+        }
+    }
+}
+
+impl AdminDashboardService {
+    /// The first `limit` scheduled posts and pages together, soonest first.
+    ///
+    /// Drafts whose publication time passed before `now` stay in the list,
+    /// at its head, flagged as stuck.
+    pub async fn upcoming(
+        &self,
+        limit: usize,
+        now: OffsetDateTime,
+    ) -> Result<Vec<UpcomingItem>, HttpError> {
+        let per_kind = u32::try_from(limit).unwrap_or(u32::MAX);
+        let (posts, pages) = tokio::try_join!(
+            async {
+                self.posts
+                    .list_scheduled_posts(per_kind)
+                    .await
+                    .map_err(|err| repo_failure(UPCOMING_FAILURE_MESSAGE, err))
+            },
+            async {
+                self.pages
+                    .list_scheduled_pages(per_kind)
+                    .await
+                    .map_err(|err| repo_failure(UPCOMING_FAILURE_MESSAGE, err))
+            },
+        )?;
+
+        let mut items: Vec<UpcomingItem> = posts
+            .into_iter()
+            .map(|record| UpcomingItem::new(ScheduledKind::Post, record, now))
+            .chain(
+                pages
+                    .into_iter()
+                    .map(|record| UpcomingItem::new(ScheduledKind::Page, record, now)),
+            )
+            .collect();
+        items.sort_by(|a, b| {
+            a.scheduled_at
+                .cmp(&b.scheduled_at)
+                .then_with(|| a.title.cmp(&b.title))
+        });
+        items.truncate(limit);
+        Ok(items)
+    }
+
+    pub(super) async fn collect_upcoming(
+        &self,
+        tz: Tz,
+    ) -> Result<Vec<AdminUpcomingItemView>, HttpError> {
+        let items = self
+            .upcoming(UPCOMING_LIMIT, OffsetDateTime::now_utc())
+            .await?;
+        Ok(items.into_iter().map(|item| item.into_view(tz)).collect())
+    }
+}
+
+const UPCOMING_LIMIT: usize = 8;
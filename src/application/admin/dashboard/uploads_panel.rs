@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::application::format::{ADMIN_TIMESTAMP_FORMAT, format_local};
 use crate::application::{error::HttpError, repos::UploadQueryFilter};
 use crate::presentation::admin::views::{AdminDashboardPanelView, AdminMetricView};
 use crate::util::bytes::format_bytes;
@@ -41,8 +42,21 @@ impl AdminDashboardService {
             }
         };
 
-        let (total, content_counts, total_bytes) =
-            tokio::try_join!(total_future, content_counts_future, total_bytes_future)?;
+        let integrity_future = {
+            let repo = Arc::clone(&repo);
+            async move {
+                repo.integrity_summary()
+                    .await
+                    .map_err(|err| repo_failure(UPLOADS_FAILURE_MESSAGE, err))
+            }
+        };
+
+        let (total, content_counts, total_bytes, integrity) = tokio::try_join!(
+            total_future,
+            content_counts_future,
+            total_bytes_future,
+            integrity_future
+        )?;
 
         let mut images = 0_u64;
         let mut documents = 0_u64;
@@ -85,6 +99,17 @@ impl AdminDashboardService {
                 value: other,
                 hint: Some("Media, archives, and miscellaneous uploads".to_string()),
             },
+            AdminMetricView {
+                label: "Checksum mismatches".to_string(),
+                value: integrity.mismatched,
+                hint: Some(match integrity.last_verified_at {
+                    Some(at) => format!(
+                        "Last verified {} UTC",
+                        format_local(at, chrono_tz::UTC, ADMIN_TIMESTAMP_FORMAT)
+                    ),
+                    None => "Not verified yet; run `soffio uploads verify-all`".to_string(),
+                }),
+            },
         ];
 
         Ok(AdminDashboardPanelView {
@@ -7,11 +7,15 @@ use time::OffsetDateTime;
 use crate::application::admin::audit::AdminAuditService;
 use crate::application::pagination::{CursorPage, JobCursor, PageRequest};
 use crate::application::repos::{
-    JobQueryFilter, JobsRepo, NewJobRecord, RepoError, UpdateJobStateParams,
+    AuditQueryFilter, JobQueryFilter, JobsRepo, NewJobRecord, RepoError, UpdateJobStateParams,
 };
-use crate::domain::entities::JobRecord;
+use crate::application::request_id;
+use crate::domain::entities::{AuditLogRecord, JobAttemptRecord, JobRecord};
 use crate::domain::types::{JobState, JobType};
 
+/// Most audit entries listed alongside a job.
+const RELATED_AUDIT_LIMIT: u32 = 50;
+
 #[derive(Debug, Error)]
 pub enum AdminJobError {
     #[error("job not found")]
@@ -39,6 +43,93 @@ pub struct UpdateJobStatusCommand {
     pub priority: Option<i32>,
 }
 
+/// A job with everything needed to debug it: when it ran, how each failed
+/// run ended, what it acted on and what the request that queued it changed.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobDetail {
+    #[serde(flatten)]
+    pub job: JobRecord,
+    pub timeline: JobTimeline,
+    /// Failed runs, oldest first.
+    pub attempt_history: Vec<JobAttemptRecord>,
+    pub entity: Option<JobEntity>,
+    /// Id of the HTTP request that queued the job.
+    pub request_id: Option<String>,
+    /// Audit entries recorded while serving that request, newest first.
+    pub related_audit: Vec<AuditLogRecord>,
+}
+
+/// When a job became due, was picked up and finished.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JobTimeline {
+    #[serde(with = "time::serde::rfc3339")]
+    pub queued_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub locked_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub finished_at: Option<OffsetDateTime>,
+    /// Time between becoming due and being picked up by a worker.
+    pub wait_ms: Option<i64>,
+    /// Time between being picked up and finishing.
+    pub run_ms: Option<i64>,
+}
+
+impl JobTimeline {
+    /// Derived from `run_at`, `lock_at` and `done_at`. A retried job's
+    /// `run_at` moves past the previous lock, so a wait is only reported when
+    /// the lock came after it.
+    pub fn of(job: &JobRecord) -> Self {
+        let elapsed_ms = |from: OffsetDateTime, to: OffsetDateTime| {
+            (to >= from).then(|| (to - from).whole_milliseconds() as i64)
+        };
+        Self {
+            queued_at: job.run_at,
+            locked_at: job.lock_at,
+            finished_at: job.done_at,
+            wait_ms: job.lock_at.and_then(|lock| elapsed_ms(job.run_at, lock)),
+            run_ms: job
+                .lock_at
+                .zip(job.done_at)
+                .and_then(|(lock, done)| elapsed_ms(lock, done)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobEntityKind {
+    Post,
+    Page,
+}
+
+/// The post or page a job acts on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JobEntity {
+    pub kind: JobEntityKind,
+    pub slug: String,
+}
+
+impl JobEntity {
+    /// Read from the `slug` every render and publish payload carries.
+    pub fn from_job(job: &JobRecord) -> Option<Self> {
+        let kind = match job.job_type {
+            JobType::RenderPage | JobType::PublishPage | JobType::PingSitemap => {
+                JobEntityKind::Page
+            }
+            JobType::RenderPost
+            | JobType::RenderPostSections
+            | JobType::RenderPostSection
+            | JobType::RenderSummary
+            | JobType::PublishPost => JobEntityKind::Post,
+        };
+        let slug = job.payload.get("slug")?.as_str()?.trim();
+        (!slug.is_empty()).then(|| Self {
+            kind,
+            slug: slug.to_string(),
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct AdminJobService {
     repo: Arc<dyn JobsRepo>,
@@ -56,9 +147,11 @@ impl AdminJobService {
         command: ScheduleJobCommand,
     ) -> Result<(), AdminJobError> {
         let run_at = command.scheduled_at.unwrap_or_else(OffsetDateTime::now_utc);
+        let mut payload = command.payload;
+        request_id::tag_job_payload(&mut payload);
         let new_job = NewJobRecord {
             job_type: command.job_type,
-            payload: command.payload,
+            payload,
             run_at,
             max_attempts: command.max_attempts.unwrap_or(25),
             priority: command.priority.unwrap_or(0),
@@ -93,6 +186,39 @@ impl AdminJobService {
         self.repo.find_job(id).await?.ok_or(AdminJobError::NotFound)
     }
 
+    pub async fn load_job_detail(&self, id: &str) -> Result<JobDetail, AdminJobError> {
+        let job = self.load_job(id).await?;
+        let request_id = job
+            .payload
+            .get("request_id")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string);
+
+        let related_audit = match &request_id {
+            Some(request_id) => {
+                let filter = AuditQueryFilter {
+                    request_id: Some(request_id.clone()),
+                    ..AuditQueryFilter::default()
+                };
+                self.audit
+                    .list_filtered(PageRequest::new(RELATED_AUDIT_LIMIT, None), &filter)
+                    .await?
+                    .items
+            }
+            None => Vec::new(),
+        };
+        let attempt_history = self.repo.list_job_attempts(&job.id).await?;
+
+        Ok(JobDetail {
+            timeline: JobTimeline::of(&job),
+            entity: JobEntity::from_job(&job),
+            attempt_history,
+            request_id,
+            related_audit,
+            job,
+        })
+    }
+
     pub async fn retry_job(&self, actor: &str, id: &str) -> Result<JobRecord, AdminJobError> {
         let job = self.load_job(id).await?;
         let command = UpdateJobStatusCommand {
@@ -287,3 +413,59 @@ struct JobStatusSnapshot<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     error_text: Option<&'a str>,
 }
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn job(job_type: JobType, payload: serde_json::Value) -> JobRecord {
+        JobRecord {
+            id: "01JOB".to_string(),
+            job_type,
+            payload,
+            state: JobState::Done,
+            attempts: 1,
+            max_attempts: 25,
+            run_at: datetime!(2026-05-01 12:00:00 UTC),
+            lock_at: Some(datetime!(2026-05-01 12:00:02 UTC)),
+            lock_by: None,
+            done_at: Some(datetime!(2026-05-01 12:00:02.750 UTC)),
+            last_error: None,
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn timeline_splits_waiting_from_running() {
+        let timeline = JobTimeline::of(&job(JobType::RenderPost, serde_json::json!({})));
+        assert_eq!(timeline.wait_ms, Some(2_000));
+        assert_eq!(timeline.run_ms, Some(750));
+
+        let mut retried = job(JobType::RenderPost, serde_json::json!({}));
+        retried.run_at = datetime!(2026-05-01 12:05:00 UTC);
+        retried.done_at = None;
+        let timeline = JobTimeline::of(&retried);
+        assert_eq!(timeline.wait_ms, None);
+        assert_eq!(timeline.run_ms, None);
+    }
+
+    #[test]
+    fn entity_comes_from_the_payload_slug() {
+        let entity = JobEntity::from_job(&job(
+            JobType::PublishPage,
+            serde_json::json!({ "slug": "about", "request_id": "req-1" }),
+        ));
+        assert_eq!(
+            entity,
+            Some(JobEntity {
+                kind: JobEntityKind::Page,
+                slug: "about".to_string(),
+            })
+        );
+
+        let blank = job(JobType::RenderPost, serde_json::json!({ "slug": " " }));
+        assert_eq!(JobEntity::from_job(&blank), None);
+    }
+}
@@ -1,12 +1,15 @@
 //! Application services for the administrative surface.
 
 pub mod audit;
+pub mod auth;
 pub mod chrome;
+pub mod comments;
 pub mod dashboard;
 pub mod jobs;
 pub mod navigation;
 pub mod pages;
 pub mod posts;
+pub mod schedule;
 pub mod settings;
 pub mod snapshot_types;
 pub mod snapshots;
@@ -5,7 +5,7 @@ use thiserror::Error;
 use uuid::Uuid;
 
 use crate::application::{
-    admin::audit::AdminAuditService,
+    admin::audit::{AdminAuditService, AuditChanges},
     pagination::{CursorPage, NavigationCursor, PageRequest},
     repos::{
         CreateNavigationItemParams, NavigationQueryFilter, NavigationRepo, NavigationWriteRepo,
@@ -234,15 +234,20 @@ impl AdminNavigationService {
             open_in_new_tab: command.open_in_new_tab,
         };
 
+        let previous = self.reader.find_by_id(command.id).await?;
         let item = self.writer.update_navigation_item(params).await?;
         let snapshot = NavigationSnapshot::from(&item);
+        let changes = previous
+            .map(|previous| AuditChanges::between(&previous, &item))
+            .unwrap_or_default();
         self.audit
-            .record(
+            .record_with_changes(
                 actor,
                 "navigation.update",
                 "navigation",
                 Some(&item.id.to_string()),
                 Some(&snapshot),
+                &changes,
             )
             .await?;
 
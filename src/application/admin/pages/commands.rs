@@ -1,23 +1,31 @@
 use time::OffsetDateTime;
+use tracing::{debug, warn};
 use uuid::Uuid;
 
+use crate::application::admin::audit::AuditChanges;
 use crate::application::jobs::{
-    PUBLISH_JOB_WAIT_TIMEOUT, enqueue_publish_page_job, wait_for_job_completion,
+    PUBLISH_JOB_WAIT_TIMEOUT, enqueue_ping_sitemap_job, enqueue_publish_page_job,
+    wait_for_job_completion,
 };
 use crate::application::render::{
     RenderRequest, RenderService, RenderTarget, enqueue_render_page_job, render_service,
 };
 use crate::application::repos::{
     CreatePageParams, RepoError, UpdatePageParams, UpdatePageStatusParams,
+    UpdatePageTranslationParams,
 };
-use crate::domain::entities::PageRecord;
-use crate::domain::slug::{SlugAsyncError, SlugError, generate_unique_slug_async};
+use crate::domain::entities::{NavigationItemRecord, PageRecord};
+use crate::domain::routes::{join_url, reserved_page_slug_route};
+use crate::domain::slug::{SlugAsyncError, SlugError, derive_slug, generate_unique_slug_async};
+use crate::domain::translations::validate_translation;
 use crate::domain::types::PageStatus;
+use crate::presentation::page_templates::DEFAULT_PAGE_TEMPLATE;
 
 use super::service::AdminPageService;
 use super::types::{
-    AdminPageError, CreatePageCommand, PageSummarySnapshot, UpdatePageContentCommand,
-    UpdatePageStatusCommand, ensure_non_empty, normalize_public_site_url, normalize_status,
+    AdminPageError, CreatePageCommand, PageNavigationSnapshot, PageRerenderSnapshot,
+    PageSummarySnapshot, PageTranslationSnapshot, UpdatePageContentCommand,
+    UpdatePageStatusCommand, ensure_non_empty, ensure_page_template, normalize_status,
 };
 
 impl AdminPageService {
@@ -37,15 +45,31 @@ impl AdminPageService {
             scheduled_at,
             published_at,
             archived_at,
+            template,
         } = command;
 
-        let reader = self.reader.clone();
+        let template = template.unwrap_or_else(|| DEFAULT_PAGE_TEMPLATE.to_string());
+        ensure_page_template(&template)?;
+
         let slug_is_custom = slug.is_some();
+        if let Some(custom) = slug.as_deref() {
+            ensure_slug_not_reserved(custom.trim())?;
+            if let Ok(derived) = derive_slug(custom) {
+                ensure_slug_not_reserved(&derived)?;
+            }
+        }
+
+        // Title-derived slugs step past reserved words the same way they
+        // step past taken slugs (`posts` becomes `posts-2`).
+        let reader = self.reader.clone();
         let slug_source = slug.as_deref().unwrap_or(title.as_str());
         let slug = match generate_unique_slug_async(slug_source, move |candidate| {
             let reader = reader.clone();
             let candidate = candidate.to_string();
             async move {
+                if reserved_page_slug_route(&candidate).is_some() {
+                    return Ok(false);
+                }
                 reader
                     .find_by_slug(&candidate)
                     .await
@@ -73,13 +97,12 @@ impl AdminPageService {
         let timestamps = normalize_status(status, scheduled_at, published_at, archived_at)?;
 
         let site_settings = self.settings.load_site_settings().await?;
-        let public_site_url = normalize_public_site_url(&site_settings.public_site_url);
 
         let render_request = RenderRequest::new(
             RenderTarget::PageBody { slug: slug.clone() },
             body_markdown.clone(),
         )
-        .with_public_site_url(&public_site_url);
+        .with_public_site_url(&site_settings.public_site_url);
         let render_output = render_service().render(&render_request)?;
 
         let params = CreatePageParams {
@@ -91,6 +114,7 @@ impl AdminPageService {
             scheduled_at: timestamps.scheduled_at,
             published_at: timestamps.published_at,
             archived_at: timestamps.archived_at,
+            template,
         };
 
         let page = self.writer.create_page(params).await?;
@@ -118,6 +142,33 @@ impl AdminPageService {
         Ok(page)
     }
 
+    /// Copy `id` into a new draft titled "… (Copy)" with the same body and
+    /// template. Its slug is the source slug with `-copy` appended, stepping
+    /// on to `-copy-2`, `-copy-3` … while those are taken.
+    pub async fn duplicate_page(
+        &self,
+        actor: &str,
+        id: Uuid,
+    ) -> Result<PageRecord, AdminPageError> {
+        let source = self
+            .reader
+            .find_by_id(id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+
+        let command = CreatePageCommand {
+            slug: Some(format!("{} copy", source.slug)),
+            title: format!("{} (Copy)", source.title),
+            body_markdown: source.body_markdown,
+            status: PageStatus::Draft,
+            scheduled_at: None,
+            published_at: None,
+            archived_at: None,
+            template: Some(source.template),
+        };
+        self.create_page(actor, command).await
+    }
+
     pub async fn update_page(
         &self,
         actor: &str,
@@ -126,16 +177,20 @@ impl AdminPageService {
         ensure_non_empty(&command.slug, "slug")?;
         ensure_non_empty(&command.title, "title")?;
         ensure_non_empty(&command.body_markdown, "body_markdown")?;
+        if let Some(template) = command.template.as_deref() {
+            ensure_page_template(template)?;
+        }
 
-        let previous_slug = self
+        let previous = self
             .reader
             .find_by_id(command.id)
             .await?
-            .map(|page| page.slug)
-            .ok_or_else(|| RepoError::from_persistence("page not found"))?;
+            .ok_or(RepoError::NotFound)?;
+        if command.slug != previous.slug {
+            ensure_slug_not_reserved(&command.slug)?;
+        }
 
         let site_settings = self.settings.load_site_settings().await?;
-        let public_site_url = normalize_public_site_url(&site_settings.public_site_url);
 
         let render_request = RenderRequest::new(
             RenderTarget::PageBody {
@@ -143,7 +198,7 @@ impl AdminPageService {
             },
             command.body_markdown.clone(),
         )
-        .with_public_site_url(&public_site_url);
+        .with_public_site_url(&site_settings.public_site_url);
         let render_output = render_service().render(&render_request)?;
 
         let params = UpdatePageParams {
@@ -152,6 +207,10 @@ impl AdminPageService {
             title: command.title,
             body_markdown: command.body_markdown,
             rendered_html: render_output.html,
+            template: command
+                .template
+                .unwrap_or_else(|| previous.template.clone()),
+            expected_updated_at: command.expected_updated_at,
         };
 
         let page = self.writer.update_page(params).await?;
@@ -160,24 +219,31 @@ impl AdminPageService {
             title: page.title.as_str(),
             status: page.status,
         };
+        let changes = AuditChanges::between(&previous, &page);
         self.audit
-            .record(
+            .record_with_changes(
                 actor,
                 "page.update",
                 "page",
                 Some(&page.id.to_string()),
                 Some(&snapshot),
+                &changes,
             )
             .await?;
         self.enqueue_render_job(&page).await?;
 
         // Trigger cache invalidation
         if let Some(trigger) = &self.cache_trigger {
-            let previous_slug = (previous_slug != page.slug).then_some(previous_slug.as_str());
+            let previous_slug = (previous.slug != page.slug).then_some(previous.slug.as_str());
             trigger
                 .page_upserted_with_previous_slug(page.id, &page.slug, previous_slug)
                 .await;
         }
+        if page.status == PageStatus::Published
+            && (page.slug != previous.slug || page.title != previous.title)
+        {
+            self.refresh_navigation_links(&page).await?;
+        }
 
         Ok(page)
     }
@@ -195,8 +261,11 @@ impl AdminPageService {
                 .schedule_page_publication(command.id, publish_at)
                 .await?;
 
+            // Enqueue with the stored (microsecond) schedule so the job can
+            // compare it against the row exactly.
+            let scheduled_at = scheduled.scheduled_at.unwrap_or(publish_at);
             let job_id =
-                enqueue_publish_page_job(self.jobs.as_ref(), scheduled.slug.clone(), publish_at)
+                enqueue_publish_page_job(self.jobs.as_ref(), scheduled.slug.clone(), scheduled_at)
                     .await?;
 
             let mut page = scheduled;
@@ -220,6 +289,11 @@ impl AdminPageService {
 
             Ok(page)
         } else {
+            let was_published = self
+                .reader
+                .find_by_id(command.id)
+                .await?
+                .is_some_and(|page| page.status == PageStatus::Published);
             let normalized = normalize_status(
                 command.status,
                 command.scheduled_at,
@@ -242,35 +316,94 @@ impl AdminPageService {
             if let Some(trigger) = &self.cache_trigger {
                 trigger.page_upserted(page.id, &page.slug).await;
             }
+            if was_published && page.status != PageStatus::Published {
+                self.page_visibility_changed(actor, &page).await;
+            }
 
             Ok(page)
         }
     }
 
+    /// Publish the page a publish job was enqueued for.
+    ///
+    /// `expected_publish_at` is the schedule recorded in the job payload. The
+    /// call is idempotent: when the page is already published at that time,
+    /// or a concurrent worker or reschedule got there first, it returns
+    /// `Ok(None)` without auditing or invalidating anything again. The audit
+    /// entry is attributed to `actor`, who scheduled the publication.
     pub async fn publish_scheduled_by_slug(
         &self,
+        actor: &str,
         slug: &str,
-    ) -> Result<PageRecord, AdminPageError> {
+        expected_publish_at: Option<OffsetDateTime>,
+    ) -> Result<Option<PageRecord>, AdminPageError> {
         let Some(page) = self.reader.find_by_slug(slug).await? else {
             return Err(AdminPageError::Repo(RepoError::NotFound));
         };
 
-        let publish_at = page.scheduled_at.unwrap_or_else(OffsetDateTime::now_utc);
-        let params = UpdatePageStatusParams {
-            id: page.id,
-            status: PageStatus::Published,
-            scheduled_at: None,
-            published_at: Some(publish_at),
-            archived_at: None,
-        };
+        if page.status == PageStatus::Published
+            && expected_publish_at.is_none_or(|at| page.published_at == Some(at))
+        {
+            return Ok(None);
+        }
 
-        let page = self.writer.update_page_status(params).await?;
-        self.record_status_audit("system", &page).await?;
+        let expected_scheduled_at = expected_publish_at.or(page.scheduled_at);
+        let publish_at = expected_scheduled_at.unwrap_or_else(OffsetDateTime::now_utc);
+        let Some(page) = self
+            .writer
+            .publish_scheduled_page(page.id, expected_scheduled_at, publish_at)
+            .await?
+        else {
+            return Ok(None);
+        };
+        self.record_status_audit(actor, &page).await?;
 
         // Trigger cache invalidation
         if let Some(trigger) = &self.cache_trigger {
             trigger.page_upserted(page.id, &page.slug).await;
         }
+        self.page_visibility_changed(actor, &page).await;
+
+        Ok(Some(page))
+    }
+
+    /// Set or clear the page's language and translation group.
+    pub async fn update_translation(
+        &self,
+        actor: &str,
+        id: Uuid,
+        lang: Option<String>,
+        translation_group: Option<Uuid>,
+    ) -> Result<PageRecord, AdminPageError> {
+        let lang = validate_translation(lang.as_deref(), translation_group)
+            .map_err(|err| AdminPageError::ConstraintViolation(err.message()))?;
+        let page = self
+            .writer
+            .update_page_translation(UpdatePageTranslationParams {
+                id,
+                lang,
+                translation_group,
+            })
+            .await?;
+
+        let snapshot = PageTranslationSnapshot {
+            slug: page.slug.as_str(),
+            lang: page.lang.as_deref(),
+            translation_group: page.translation_group,
+        };
+        self.audit
+            .record(
+                actor,
+                "page.translation",
+                "page",
+                Some(&page.id.to_string()),
+                Some(&snapshot),
+            )
+            .await?;
+
+        if let Some(trigger) = &self.cache_trigger {
+            trigger.page_upserted(page.id, &page.slug).await;
+        }
 
         Ok(page)
     }
@@ -281,6 +414,11 @@ impl AdminPageService {
         id: Uuid,
         slug: &str,
     ) -> Result<(), AdminPageError> {
+        let published = self
+            .reader
+            .find_by_id(id)
+            .await?
+            .filter(|page| page.status == PageStatus::Published);
         self.writer.delete_page(id).await?;
         self.audit
             .record(
@@ -296,10 +434,100 @@ impl AdminPageService {
         if let Some(trigger) = &self.cache_trigger {
             trigger.page_deleted(id, slug).await;
         }
+        if let Some(page) = &published {
+            self.page_visibility_changed(actor, page).await;
+        }
 
         Ok(())
     }
 
+    /// Take a page out of the trash. Fails with a duplicate error when
+    /// another page has claimed its slug in the meantime.
+    pub async fn restore_page(&self, actor: &str, id: Uuid) -> Result<PageRecord, AdminPageError> {
+        let page = self.writer.restore_page(id).await?;
+
+        let snapshot = PageSummarySnapshot {
+            slug: page.slug.as_str(),
+            title: page.title.as_str(),
+            status: page.status,
+        };
+        self.audit
+            .record(
+                actor,
+                "page.restore",
+                "page",
+                Some(&page.id.to_string()),
+                Some(&snapshot),
+            )
+            .await?;
+
+        if let Some(trigger) = &self.cache_trigger {
+            trigger.page_upserted(page.id, &page.slug).await;
+        }
+        if page.status == PageStatus::Published {
+            self.page_visibility_changed(actor, &page).await;
+        }
+
+        Ok(page)
+    }
+
+    /// Permanently delete a trashed page.
+    pub async fn purge_page(&self, actor: &str, id: Uuid) -> Result<(), AdminPageError> {
+        self.writer.purge_page(id).await?;
+        self.audit
+            .record(
+                actor,
+                "page.purge",
+                "page",
+                Some(&id.to_string()),
+                Option::<&PageSummarySnapshot<'_>>::None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Permanently delete pages trashed before `cutoff`.
+    pub async fn purge_trash_before(&self, cutoff: OffsetDateTime) -> Result<u64, AdminPageError> {
+        self.writer
+            .purge_trashed_pages(cutoff)
+            .await
+            .map_err(AdminPageError::from)
+    }
+
+    /// Queue a fresh render of `page`'s current markdown, e.g. after changing
+    /// render settings. The render job refreshes the cache when it finishes.
+    pub async fn rerender_page(
+        &self,
+        actor: &str,
+        page: &PageRecord,
+    ) -> Result<String, AdminPageError> {
+        let job_id = enqueue_render_page_job(
+            self.jobs.as_ref(),
+            page.slug.clone(),
+            page.body_markdown.clone(),
+            None,
+        )
+        .await?;
+
+        let snapshot = PageRerenderSnapshot {
+            slug: page.slug.as_str(),
+            title: page.title.as_str(),
+            job_id: job_id.as_str(),
+        };
+        self.audit
+            .record(
+                actor,
+                "page.rerender",
+                "page",
+                Some(&page.id.to_string()),
+                Some(&snapshot),
+            )
+            .await?;
+
+        Ok(job_id)
+    }
+
     async fn enqueue_render_job(&self, page: &PageRecord) -> Result<(), AdminPageError> {
         enqueue_render_page_job(
             self.jobs.as_ref(),
@@ -312,6 +540,92 @@ impl AdminPageService {
         Ok(())
     }
 
+    /// Follow-up for a page that was just published, unpublished, trashed
+    /// or restored: rebuild the navigation linking to it, note links left
+    /// pointing at a hidden page, and ping search engines with the sitemap.
+    ///
+    /// The status change has already been written by then, so failures here
+    /// are logged rather than reported as a failed write.
+    async fn page_visibility_changed(&self, actor: &str, page: &PageRecord) {
+        if let Err(err) = self.notify_visibility_change(actor, page).await {
+            warn!(
+                target = "application::admin::pages",
+                slug = page.slug,
+                error = %err,
+                "page visibility follow-up failed"
+            );
+        }
+    }
+
+    async fn notify_visibility_change(
+        &self,
+        actor: &str,
+        page: &PageRecord,
+    ) -> Result<(), AdminPageError> {
+        let links = self.refresh_navigation_links(page).await?;
+        if page.status != PageStatus::Published && !links.is_empty() {
+            let snapshot = PageNavigationSnapshot {
+                slug: page.slug.as_str(),
+                status: page.status,
+                navigation: links.iter().map(|item| item.label.as_str()).collect(),
+            };
+            warn!(
+                target = "application::admin::pages",
+                slug = page.slug,
+                navigation = ?snapshot.navigation,
+                "visible navigation links to a hidden page"
+            );
+            self.audit
+                .record(
+                    actor,
+                    "page.navigation_hidden",
+                    "page",
+                    Some(&page.id.to_string()),
+                    Some(&snapshot),
+                )
+                .await?;
+        }
+        self.enqueue_sitemap_ping(page).await
+    }
+
+    /// Rebuild the navigation chrome when visible items link to `page`, and
+    /// return those items.
+    async fn refresh_navigation_links(
+        &self,
+        page: &PageRecord,
+    ) -> Result<Vec<NavigationItemRecord>, AdminPageError> {
+        let links = self.navigation.list_visible_links_to_page(page.id).await?;
+        if !links.is_empty()
+            && let Some(trigger) = &self.cache_trigger
+        {
+            trigger.navigation_updated().await;
+        }
+        Ok(links)
+    }
+
+    async fn enqueue_sitemap_ping(&self, page: &PageRecord) -> Result<(), AdminPageError> {
+        if self.sitemap_ping_urls.is_empty() {
+            return Ok(());
+        }
+        let settings = self.settings.load_site_settings().await?;
+        let Some(site_url) = self.base_path.site_url(&settings.public_site_url) else {
+            debug!(
+                target = "application::admin::pages",
+                slug = page.slug,
+                "sitemap ping skipped: public_site_url is not set"
+            );
+            return Ok(());
+        };
+        enqueue_ping_sitemap_job(
+            self.jobs.as_ref(),
+            page.slug.clone(),
+            join_url(&site_url, "sitemap.xml"),
+            self.sitemap_ping_urls.clone(),
+        )
+        .await?;
+        Ok(())
+    }
+
     async fn record_status_audit(
         &self,
         actor: &str,
@@ -334,3 +648,13 @@ impl AdminPageService {
         Ok(())
     }
 }
+
+fn ensure_slug_not_reserved(slug: &str) -> Result<(), AdminPageError> {
+    match reserved_page_slug_route(slug) {
+        Some(route) => Err(AdminPageError::ReservedSlug {
+            slug: slug.to_string(),
+            route,
+        }),
+        None => Ok(()),
+    }
+}
@@ -2,10 +2,13 @@ use uuid::Uuid;
 
 use crate::{
     application::{
-        pagination::{CursorPage, PageCursor},
+        pagination::{CursorPage, PageCursor, PageRequest, TrashCursor},
         repos::PageQueryFilter,
     },
-    domain::{entities::PageRecord, types::PageStatus},
+    domain::{
+        entities::{NavigationItemRecord, PageRecord, TrashedRecord},
+        types::PageStatus,
+    },
 };
 
 use super::{
@@ -14,6 +17,18 @@ use super::{
 };
 
 impl AdminPageService {
+    /// Visible navigation items linking to the page, including ones the
+    /// public menu leaves out because the page is not published.
+    pub async fn navigation_links(
+        &self,
+        page_id: Uuid,
+    ) -> Result<Vec<NavigationItemRecord>, AdminPageError> {
+        self.navigation
+            .list_visible_links_to_page(page_id)
+            .await
+            .map_err(AdminPageError::from)
+    }
+
     pub async fn list(
         &self,
         status: Option<PageStatus>,
@@ -27,6 +42,16 @@ impl AdminPageService {
             .map_err(AdminPageError::from)
     }
 
+    pub async fn list_trash(
+        &self,
+        page: PageRequest<TrashCursor>,
+    ) -> Result<CursorPage<TrashedRecord>, AdminPageError> {
+        self.writer
+            .list_trashed_pages(page)
+            .await
+            .map_err(AdminPageError::from)
+    }
+
     pub async fn find_by_slug(&self, slug: &str) -> Result<Option<PageRecord>, AdminPageError> {
         self.reader
             .find_by_slug(slug)
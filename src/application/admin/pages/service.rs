@@ -8,10 +8,11 @@ use crate::{
         snapshot_types::{PageSnapshotPayload, PageSnapshotSource},
     },
     application::repos::{
-        JobsRepo, PagesRepo, PagesWriteRepo, RepoError, RestorePageSnapshotParams, SettingsRepo,
+        JobsRepo, NavigationRepo, PagesRepo, PagesWriteRepo, RepoError, RestorePageSnapshotParams,
+        SettingsRepo,
     },
     cache::CacheTrigger,
-    domain::entities::PageRecord,
+    domain::{entities::PageRecord, routes::BasePath},
 };
 
 use super::types::AdminPageError;
@@ -23,7 +24,12 @@ pub struct AdminPageService {
     pub(crate) jobs: Arc<dyn JobsRepo>,
     pub(crate) audit: AdminAuditService,
     pub(crate) settings: Arc<dyn SettingsRepo>,
+    pub(crate) navigation: Arc<dyn NavigationRepo>,
     pub(crate) cache_trigger: Option<Arc<CacheTrigger>>,
+    /// Endpoints from `sitemap.ping_urls`.
+    pub(crate) sitemap_ping_urls: Vec<String>,
+    /// Prefix the public site, and so `sitemap.xml`, is mounted under.
+    pub(crate) base_path: BasePath,
 }
 
 impl AdminPageService {
@@ -33,6 +39,7 @@ impl AdminPageService {
         jobs: Arc<dyn JobsRepo>,
         audit: AdminAuditService,
         settings: Arc<dyn SettingsRepo>,
+        navigation: Arc<dyn NavigationRepo>,
     ) -> Self {
         Self {
             reader,
@@ -40,7 +47,10 @@ impl AdminPageService {
             jobs,
             audit,
             settings,
+            navigation,
             cache_trigger: None,
+            sitemap_ping_urls: Vec::new(),
+            base_path: BasePath::default(),
         }
     }
 
@@ -56,6 +66,14 @@ impl AdminPageService {
         self
     }
 
+    /// Ping `ping_urls` with the sitemap under `base_path` whenever a page is
+    /// published or unpublished.
+    pub fn with_sitemap_ping(mut self, ping_urls: Vec<String>, base_path: BasePath) -> Self {
+        self.sitemap_ping_urls = ping_urls;
+        self.base_path = base_path;
+        self
+    }
+
     pub async fn snapshot_source(&self, id: Uuid) -> Result<PageSnapshotSource, AdminPageError> {
         let page = self
             .reader
@@ -86,6 +104,7 @@ impl AdminPageService {
             scheduled_at: payload.scheduled_at,
             published_at: payload.published_at,
             archived_at: payload.archived_at,
+            template: payload.template,
         };
 
         let page = self.writer.restore_page_snapshot(params).await?;
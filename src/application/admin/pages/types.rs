@@ -6,12 +6,15 @@ use uuid::Uuid;
 use crate::{
     application::{render::RenderError, repos::RepoError},
     domain::types::PageStatus,
+    presentation::page_templates::is_page_template,
 };
 
 #[derive(Debug, Error)]
 pub enum AdminPageError {
     #[error("{0}")]
     ConstraintViolation(&'static str),
+    #[error("slug `{slug}` is reserved by the public route `{route}`")]
+    ReservedSlug { slug: String, route: &'static str },
     #[error(transparent)]
     Render(#[from] RenderError),
     #[error(transparent)]
@@ -27,6 +30,8 @@ pub struct CreatePageCommand {
     pub scheduled_at: Option<OffsetDateTime>,
     pub published_at: Option<OffsetDateTime>,
     pub archived_at: Option<OffsetDateTime>,
+    /// Page template name; `None` uses the default template.
+    pub template: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +40,10 @@ pub struct UpdatePageContentCommand {
     pub slug: String,
     pub title: String,
     pub body_markdown: String,
+    /// Page template name; `None` keeps the current one.
+    pub template: Option<String>,
+    /// `updated_at` the caller last read; a newer row fails the update.
+    pub expected_updated_at: Option<OffsetDateTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +71,29 @@ pub struct PageSummarySnapshot<'a> {
     pub status: PageStatus,
 }
 
+/// Audit note for a page hidden while visible navigation still links to it.
+#[derive(Debug, Clone, Serialize)]
+pub struct PageNavigationSnapshot<'a> {
+    pub slug: &'a str,
+    pub status: PageStatus,
+    /// Labels of the navigation items, which the public menu now leaves out.
+    pub navigation: Vec<&'a str>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PageRerenderSnapshot<'a> {
+    pub slug: &'a str,
+    pub title: &'a str,
+    pub job_id: &'a str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PageTranslationSnapshot<'a> {
+    pub slug: &'a str,
+    pub lang: Option<&'a str>,
+    pub translation_group: Option<Uuid>,
+}
+
 pub(super) struct StatusTimestamps {
     pub(super) scheduled_at: Option<OffsetDateTime>,
     pub(super) published_at: Option<OffsetDateTime>,
@@ -98,19 +130,16 @@ pub(super) fn normalize_status(
     }
 }
 
-pub(super) fn ensure_non_empty(value: &str, field: &'static str) -> Result<(), AdminPageError> {
-    if value.trim().is_empty() {
-        return Err(AdminPageError::ConstraintViolation(field));
+pub(super) fn ensure_page_template(name: &str) -> Result<(), AdminPageError> {
+    if !is_page_template(name) {
+        return Err(AdminPageError::ConstraintViolation("template"));
     }
     Ok(())
 }
 
-pub(super) fn normalize_public_site_url(url: &str) -> String {
-    let trimmed = url.trim();
-    if trimmed.is_empty() {
-        return String::new();
+pub(super) fn ensure_non_empty(value: &str, field: &'static str) -> Result<(), AdminPageError> {
+    if value.trim().is_empty() {
+        return Err(AdminPageError::ConstraintViolation(field));
     }
-
-    let without_trailing = trimmed.trim_end_matches('/');
-    format!("{without_trailing}/")
+    Ok(())
 }
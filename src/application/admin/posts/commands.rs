@@ -1,37 +1,52 @@
 use std::collections::{BTreeSet, HashMap};
 
-use time::OffsetDateTime;
+use time::{Date, OffsetDateTime};
 use uuid::Uuid;
 
+use crate::application::admin::audit::AuditChanges;
 use crate::application::jobs::{
     PUBLISH_JOB_WAIT_TIMEOUT, enqueue_publish_post_job, wait_for_job_completion,
 };
-use crate::application::render::enqueue_render_post_job;
+use crate::application::render::{
+    derive_excerpt, enqueue_render_post_edit_job, enqueue_render_post_job,
+};
 use crate::application::repos::{
     CreatePostParams, RepoError, UpdatePostParams, UpdatePostPinnedParams, UpdatePostStatusParams,
+    UpdatePostTranslationParams,
 };
 use crate::domain::entities::PostRecord;
-use crate::domain::slug::{SlugAsyncError, SlugError, generate_unique_slug_async};
+use crate::domain::slug::{SlugAsyncError, SlugError, SlugStrategy, unique_slug_from_base_async};
+use crate::domain::translations::validate_translation;
 use crate::domain::types::PostStatus;
 
 use super::service::AdminPostService;
 use super::types::{
-    AdminPostError, CreatePostCommand, PostSummarySnapshot, PostTagsSnapshot,
-    UpdatePostContentCommand, UpdatePostStatusCommand, ensure_non_empty, normalize_status,
+    AdminPostError, CreatePostCommand, POST_SLUG_CONSTRAINT, PostRerenderSnapshot,
+    PostSummarySnapshot, PostTagsSnapshot, PostTranslationSnapshot, UpdatePostContentCommand,
+    UpdatePostStatusCommand, editable_excerpt, ensure_non_empty, normalize_status,
 };
 
+/// Insert attempts for a derived slug before giving up on a racing create.
+const CREATE_SLUG_ATTEMPTS: usize = 3;
+
 impl AdminPostService {
-    pub async fn create_post(
+    async fn derive_unique_slug(
         &self,
-        actor: &str,
-        command: CreatePostCommand,
-    ) -> Result<PostRecord, AdminPostError> {
-        ensure_non_empty(&command.title, "title")?;
-        ensure_non_empty(&command.excerpt, "excerpt")?;
-        ensure_non_empty(&command.body_markdown, "body_markdown")?;
-
+        seed: &str,
+        strategy: SlugStrategy,
+        date: Date,
+    ) -> Result<String, AdminPostError> {
+        let base = match strategy.derive(seed, date) {
+            Ok(base) => base,
+            Err(SlugError::EmptyInput | SlugError::Unrepresentable { .. }) => {
+                return Err(AdminPostError::ConstraintViolation("title"));
+            }
+            Err(SlugError::Exhausted { .. }) => {
+                return Err(AdminPostError::ConstraintViolation("slug"));
+            }
+        };
         let reader = self.reader.clone();
-        let slug = match generate_unique_slug_async(&command.title, move |candidate| {
+        match unique_slug_from_base_async(base, move |candidate| {
             let reader = reader.clone();
             let candidate = candidate.to_string();
             async move {
@@ -43,17 +58,98 @@ impl AdminPostService {
         })
         .await
         {
-            Ok(slug) => slug,
+            Ok(slug) => Ok(slug),
             Err(SlugAsyncError::Slug(err)) => match err {
                 SlugError::EmptyInput | SlugError::Unrepresentable { .. } => {
-                    return Err(AdminPostError::ConstraintViolation("title"));
-                }
-                SlugError::Exhausted { .. } => {
-                    return Err(AdminPostError::ConstraintViolation("slug"));
+                    Err(AdminPostError::ConstraintViolation("title"))
                 }
+                SlugError::Exhausted { .. } => Err(AdminPostError::ConstraintViolation("slug")),
             },
-            Err(SlugAsyncError::Predicate(err)) => return Err(AdminPostError::Repo(err)),
+            Err(SlugAsyncError::Predicate(err)) => Err(AdminPostError::Repo(err)),
+        }
+    }
+
+    pub async fn create_post(
+        &self,
+        actor: &str,
+        command: CreatePostCommand,
+    ) -> Result<PostRecord, AdminPostError> {
+        let slug_seed = command.title.clone();
+        self.create_post_with_slug_seed(actor, command, &slug_seed, self.slug_strategy)
+            .await
+    }
+
+    /// Copy `id` into a new draft titled "… (Copy)" with the same body,
+    /// excerpt, summary and tags. Its slug is the source slug with `-copy`
+    /// appended, stepping on to `-copy-2`, `-copy-3` … while those are taken.
+    pub async fn duplicate_post(
+        &self,
+        actor: &str,
+        id: Uuid,
+    ) -> Result<PostRecord, AdminPostError> {
+        let source = self
+            .reader
+            .find_by_id(id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+        let tag_ids: Vec<Uuid> = self
+            .tags
+            .list_for_post(source.id)
+            .await?
+            .into_iter()
+            .map(|tag| tag.id)
+            .collect();
+
+        let command = CreatePostCommand {
+            slug: None,
+            title: format!("{} (Copy)", source.title),
+            excerpt: editable_excerpt(&source),
+            body_markdown: source.body_markdown.clone(),
+            summary_markdown: source.summary_markdown.clone(),
+            status: PostStatus::Draft,
+            pinned: false,
+            scheduled_at: None,
+            published_at: None,
+            archived_at: None,
         };
+        // The source slug already carries any date or hash, so only the
+        // `-copy` part is derived.
+        let post = self
+            .create_post_with_slug_seed(
+                actor,
+                command,
+                &format!("{} copy", source.slug),
+                SlugStrategy::default(),
+            )
+            .await?;
+
+        if !tag_ids.is_empty() {
+            self.replace_tags(actor, &post, &tag_ids).await?;
+        }
+
+        Ok(post)
+    }
+
+    /// Create a post whose slug, unless given explicitly, is derived from
+    /// `slug_seed` with `strategy`, dated by when the post is published.
+    async fn create_post_with_slug_seed(
+        &self,
+        actor: &str,
+        command: CreatePostCommand,
+        slug_seed: &str,
+        strategy: SlugStrategy,
+    ) -> Result<PostRecord, AdminPostError> {
+        ensure_non_empty(&command.title, "title")?;
+        ensure_non_empty(&command.body_markdown, "body_markdown")?;
+        let explicit_slug = match command.slug.as_deref().map(str::trim) {
+            Some(slug) if !slug.is_empty() => {
+                strategy.validate(slug)?;
+                Some(slug.to_string())
+            }
+            _ => None,
+        };
+        let (excerpt, excerpt_generated) =
+            self.resolve_excerpt(command.excerpt, &command.body_markdown)?;
 
         let timestamps = normalize_status(
             command.status,
@@ -62,10 +158,17 @@ impl AdminPostService {
             command.archived_at,
         )?;
 
-        let params = CreatePostParams {
-            slug,
+        let slug_date = timestamps
+            .published_at
+            .or(timestamps.scheduled_at)
+            .unwrap_or_else(OffsetDateTime::now_utc)
+            .date();
+
+        let mut params = CreatePostParams {
+            slug: String::new(),
             title: command.title,
-            excerpt: command.excerpt,
+            excerpt,
+            excerpt_generated,
             body_markdown: command.body_markdown,
             status: command.status,
             pinned: command.pinned,
@@ -76,7 +179,29 @@ impl AdminPostService {
             summary_html: None,
         };
 
-        let post = self.writer.create_post(params).await?;
+        // The uniqueness probe and the insert are not atomic, so a concurrent
+        // create can claim the derived slug first; re-derive a bounded number
+        // of times. Explicit slugs are never rewritten.
+        let mut attempt = 1;
+        let post = loop {
+            params.slug = match &explicit_slug {
+                Some(slug) => slug.clone(),
+                None => {
+                    self.derive_unique_slug(slug_seed, strategy, slug_date)
+                        .await?
+                }
+            };
+            match self.writer.create_post(params.clone()).await {
+                Ok(post) => break post,
+                Err(RepoError::Duplicate { constraint }) if constraint == POST_SLUG_CONSTRAINT => {
+                    if explicit_slug.is_some() || attempt >= CREATE_SLUG_ATTEMPTS {
+                        return Err(AdminPostError::SlugConflict { slug: params.slug });
+                    }
+                    attempt += 1;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        };
 
         let snapshot = PostSummarySnapshot {
             slug: post.slug.as_str(),
@@ -108,55 +233,72 @@ impl AdminPostService {
         actor: &str,
         command: UpdatePostContentCommand,
     ) -> Result<PostRecord, AdminPostError> {
-        ensure_non_empty(&command.slug, "slug")?;
+        let slug = command.slug.trim().to_string();
+        ensure_non_empty(&slug, "slug")?;
         ensure_non_empty(&command.title, "title")?;
-        ensure_non_empty(&command.excerpt, "excerpt")?;
         ensure_non_empty(&command.body_markdown, "body_markdown")?;
+        let (excerpt, excerpt_generated) =
+            self.resolve_excerpt(command.excerpt, &command.body_markdown)?;
 
-        let previous_slug = self
+        let previous = self
             .reader
             .find_by_id(command.id)
             .await?
-            .map(|post| post.slug)
-            .ok_or_else(|| RepoError::from_persistence("post not found"))?;
+            .ok_or(RepoError::NotFound)?;
+        // Slugs that predate validation stay editable as long as they are kept.
+        if slug != previous.slug {
+            self.slug_strategy.validate(&slug)?;
+        }
+        let before = self.placement_before_write(&previous).await;
 
         let params = UpdatePostParams {
             id: command.id,
-            slug: command.slug,
+            slug: slug.clone(),
             title: command.title,
-            excerpt: command.excerpt,
+            excerpt,
+            excerpt_generated,
             body_markdown: command.body_markdown,
             pinned: command.pinned,
             summary_markdown: command.summary_markdown,
             summary_html: None,
+            expected_updated_at: command.expected_updated_at,
         };
 
-        let post = self.writer.update_post(params).await?;
+        let post = match self.writer.update_post(params).await {
+            Ok(post) => post,
+            Err(RepoError::Duplicate { constraint }) if constraint == POST_SLUG_CONSTRAINT => {
+                return Err(AdminPostError::SlugConflict { slug });
+            }
+            Err(err) => return Err(err.into()),
+        };
 
         let snapshot = PostSummarySnapshot {
             slug: post.slug.as_str(),
             title: post.title.as_str(),
             status: post.status,
         };
+        let changes = AuditChanges::between(&previous, &post);
         self.audit
-            .record(
+            .record_with_changes(
                 actor,
                 "post.update",
                 "post",
                 Some(&post.id.to_string()),
                 Some(&snapshot),
+                &changes,
             )
             .await?;
 
-        self.enqueue_render_jobs(&post).await?;
+        if previous.body_markdown == post.body_markdown {
+            self.enqueue_render_jobs(&post).await?;
+        } else {
+            self.enqueue_edit_render_jobs(&previous, &post).await?;
+        }
 
         // Trigger cache invalidation
-        if let Some(trigger) = &self.cache_trigger {
-            let previous_slug = (previous_slug != post.slug).then_some(previous_slug.as_str());
-            trigger
-                .post_upserted_with_previous_slug(post.id, &post.slug, previous_slug)
-                .await;
-        }
+        let previous_slug = (previous.slug != post.slug).then_some(previous.slug.as_str());
+        self.notify_post_listing_change(&post, previous_slug, before)
+            .await;
 
         Ok(post)
     }
@@ -174,8 +316,11 @@ impl AdminPostService {
                 .schedule_post_publication(command.id, publish_at)
                 .await?;
 
+            // Enqueue with the stored (microsecond) schedule so the job can
+            // compare it against the row exactly.
+            let scheduled_at = scheduled.scheduled_at.unwrap_or(publish_at);
             let job_id =
-                enqueue_publish_post_job(self.jobs.as_ref(), scheduled.slug.clone(), publish_at)
+                enqueue_publish_post_job(self.jobs.as_ref(), scheduled.slug.clone(), scheduled_at)
                     .await?;
 
             let mut post = scheduled;
@@ -226,32 +371,46 @@ impl AdminPostService {
         }
     }
 
+    /// Publish the post a publish job was enqueued for.
+    ///
+    /// `expected_publish_at` is the schedule recorded in the job payload. The
+    /// call is idempotent: when the post is already published at that time,
+    /// or a concurrent worker or reschedule got there first, it returns
+    /// `Ok(None)` without auditing or invalidating anything again. The audit
+    /// entry is attributed to `actor`, who scheduled the publication.
     pub async fn publish_scheduled_by_slug(
         &self,
+        actor: &str,
         slug: &str,
-    ) -> Result<PostRecord, AdminPostError> {
+        expected_publish_at: Option<OffsetDateTime>,
+    ) -> Result<Option<PostRecord>, AdminPostError> {
         let Some(post) = self.reader.find_by_slug(slug).await? else {
             return Err(AdminPostError::Repo(RepoError::NotFound));
         };
 
-        let publish_at = post.scheduled_at.unwrap_or_else(OffsetDateTime::now_utc);
-        let params = UpdatePostStatusParams {
-            id: post.id,
-            status: PostStatus::Published,
-            scheduled_at: None,
-            published_at: Some(publish_at),
-            archived_at: None,
-        };
+        if post.status == PostStatus::Published
+            && expected_publish_at.is_none_or(|at| post.published_at == Some(at))
+        {
+            return Ok(None);
+        }
 
-        let post = self.writer.update_post_status(params).await?;
-        self.record_status_audit("system", &post).await?;
+        let expected_scheduled_at = expected_publish_at.or(post.scheduled_at);
+        let publish_at = expected_scheduled_at.unwrap_or_else(OffsetDateTime::now_utc);
+        let Some(post) = self
+            .writer
+            .publish_scheduled_post(post.id, expected_scheduled_at, publish_at)
+            .await?
+        else {
+            return Ok(None);
+        };
+        self.record_status_audit(actor, &post).await?;
 
         // Trigger cache invalidation
         if let Some(trigger) = &self.cache_trigger {
             trigger.post_upserted(post.id, &post.slug).await;
         }
 
-        Ok(post)
+        Ok(Some(post))
     }
 
     pub async fn delete_post(
@@ -279,15 +438,90 @@ impl AdminPostService {
         Ok(())
     }
 
+    /// Take a post out of the trash. Fails with a duplicate error when
+    /// another post has claimed its slug in the meantime.
+    pub async fn restore_post(&self, actor: &str, id: Uuid) -> Result<PostRecord, AdminPostError> {
+        let post = self.writer.restore_post(id).await?;
+
+        let snapshot = PostSummarySnapshot {
+            slug: post.slug.as_str(),
+            title: post.title.as_str(),
+            status: post.status,
+        };
+        self.audit
+            .record(
+                actor,
+                "post.restore",
+                "post",
+                Some(&post.id.to_string()),
+                Some(&snapshot),
+            )
+            .await?;
+
+        if let Some(trigger) = &self.cache_trigger {
+            trigger.post_upserted(post.id, &post.slug).await;
+        }
+
+        Ok(post)
+    }
+
+    /// Permanently delete a trashed post.
+    pub async fn purge_post(&self, actor: &str, id: Uuid) -> Result<(), AdminPostError> {
+        self.writer.purge_post(id).await?;
+        self.audit
+            .record(
+                actor,
+                "post.purge",
+                "post",
+                Some(&id.to_string()),
+                Option::<&PostSummarySnapshot<'_>>::None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Permanently delete posts trashed before `cutoff`.
+    pub async fn purge_trash_before(&self, cutoff: OffsetDateTime) -> Result<u64, AdminPostError> {
+        self.writer
+            .purge_trashed_posts(cutoff)
+            .await
+            .map_err(AdminPostError::from)
+    }
+
+    /// Pin or unpin the post. `pin_order` moves a pinned post among the
+    /// others, lowest first; `None` keeps its current position.
     pub async fn update_pin_state(
         &self,
         actor: &str,
         id: Uuid,
         pinned: bool,
+        pin_order: Option<i32>,
     ) -> Result<PostRecord, AdminPostError> {
+        if let Some(order) = pin_order {
+            if order < 0 {
+                return Err(AdminPostError::ConstraintViolation(
+                    "pin order must not be negative",
+                ));
+            }
+            if !pinned {
+                return Err(AdminPostError::ConstraintViolation(
+                    "pin order only applies to pinned posts",
+                ));
+            }
+        }
+
+        let before = match self.reader.find_by_id(id).await? {
+            Some(previous) => self.placement_before_write(&previous).await,
+            None => None,
+        };
         let post = self
             .writer
-            .update_post_pinned(UpdatePostPinnedParams { id, pinned })
+            .update_post_pinned(UpdatePostPinnedParams {
+                id,
+                pinned,
+                pin_order,
+            })
             .await?;
 
         let snapshot = PostSummarySnapshot {
@@ -312,6 +546,45 @@ impl AdminPostService {
             .await?;
 
         // Trigger cache invalidation
+        self.notify_post_listing_change(&post, None, before).await;
+
+        Ok(post)
+    }
+
+    /// Set or clear the post's language and translation group.
+    pub async fn update_translation(
+        &self,
+        actor: &str,
+        id: Uuid,
+        lang: Option<String>,
+        translation_group: Option<Uuid>,
+    ) -> Result<PostRecord, AdminPostError> {
+        let lang = validate_translation(lang.as_deref(), translation_group)
+            .map_err(|err| AdminPostError::ConstraintViolation(err.message()))?;
+        let post = self
+            .writer
+            .update_post_translation(UpdatePostTranslationParams {
+                id,
+                lang,
+                translation_group,
+            })
+            .await?;
+
+        let snapshot = PostTranslationSnapshot {
+            slug: post.slug.as_str(),
+            lang: post.lang.as_deref(),
+            translation_group: post.translation_group,
+        };
+        self.audit
+            .record(
+                actor,
+                "post.translation",
+                "post",
+                Some(&post.id.to_string()),
+                Some(&snapshot),
+            )
+            .await?;
+
         if let Some(trigger) = &self.cache_trigger {
             trigger.post_upserted(post.id, &post.slug).await;
         }
@@ -333,6 +606,7 @@ impl AdminPostService {
             }
         }
 
+        let before = self.placement_before_write(post).await;
         self.writer.replace_post_tags(post.id, &normalized).await?;
 
         let tag_slugs = self.resolve_tag_slugs(&normalized).await?;
@@ -354,9 +628,7 @@ impl AdminPostService {
             .await?;
 
         // Trigger cache invalidation so tag-derived and post detail caches refresh.
-        if let Some(trigger) = &self.cache_trigger {
-            trigger.post_upserted(post.id, &post.slug).await;
-        }
+        self.notify_post_listing_change(post, None, before).await;
 
         Ok(())
     }
@@ -383,6 +655,55 @@ impl AdminPostService {
         Ok(slugs)
     }
 
+    /// Keep an explicit excerpt as written; a blank one is derived from the body.
+    fn resolve_excerpt(
+        &self,
+        excerpt: String,
+        body_markdown: &str,
+    ) -> Result<(String, bool), AdminPostError> {
+        if !excerpt.trim().is_empty() {
+            return Ok((excerpt, false));
+        }
+
+        let derived = derive_excerpt(body_markdown, self.excerpt_length);
+        ensure_non_empty(&derived, "excerpt")?;
+        Ok((derived, true))
+    }
+
+    /// Queue a fresh render of `post`'s current markdown, e.g. after changing
+    /// render settings. The render job refreshes the cache when it finishes.
+    pub async fn rerender_post(
+        &self,
+        actor: &str,
+        post: &PostRecord,
+    ) -> Result<String, AdminPostError> {
+        let job_id = enqueue_render_post_job(
+            self.jobs.as_ref(),
+            post.slug.clone(),
+            post.body_markdown.clone(),
+            post.summary_markdown.clone(),
+            None,
+        )
+        .await?;
+
+        let snapshot = PostRerenderSnapshot {
+            slug: post.slug.as_str(),
+            title: post.title.as_str(),
+            job_id: job_id.as_str(),
+        };
+        self.audit
+            .record(
+                actor,
+                "post.rerender",
+                "post",
+                Some(&post.id.to_string()),
+                Some(&snapshot),
+            )
+            .await?;
+
+        Ok(job_id)
+    }
+
     async fn enqueue_render_jobs(&self, post: &PostRecord) -> Result<(), AdminPostError> {
         enqueue_render_post_job(
             self.jobs.as_ref(),
@@ -396,6 +717,25 @@ impl AdminPostService {
         Ok(())
     }
 
+    /// Like `enqueue_render_jobs`, but lets the render reuse the sections of
+    /// `previous` whose markdown is unchanged.
+    async fn enqueue_edit_render_jobs(
+        &self,
+        previous: &PostRecord,
+        post: &PostRecord,
+    ) -> Result<(), AdminPostError> {
+        enqueue_render_post_edit_job(
+            self.jobs.as_ref(),
+            post.slug.clone(),
+            previous.body_markdown.clone(),
+            post.body_markdown.clone(),
+            post.summary_markdown.clone(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
     async fn record_status_audit(
         &self,
         actor: &str,
@@ -0,0 +1,227 @@
+use std::collections::BTreeSet;
+
+use time::UtcOffset;
+use tracing::warn;
+
+use crate::application::pagination::PostCursor;
+use crate::application::repos::{PostListScope, PostQueryFilter};
+use crate::cache::PostListing;
+use crate::domain::entities::PostRecord;
+use crate::domain::posts::month_key_for;
+use crate::domain::types::PostStatus;
+
+use super::service::AdminPostService;
+use super::types::AdminPostError;
+
+/// Largest homepage size the feed allows; posts within this many of the
+/// newest are treated as appearing on the homepage.
+const RECENT_LISTING_WINDOW: u32 = 48;
+
+/// Where a post shows up in public listings at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ListingPlacement {
+    public: bool,
+    tags: BTreeSet<String>,
+    month: Option<String>,
+    recent: bool,
+}
+
+impl ListingPlacement {
+    /// Listings to invalidate when a post moves from `before` to `after`.
+    fn change(before: &Self, after: &Self) -> PostListing {
+        let mut listing = PostListing::default();
+        for placement in [before, after] {
+            if !placement.public {
+                continue;
+            }
+            listing.tags.extend(placement.tags.iter().cloned());
+            listing.months.extend(placement.month.iter().cloned());
+            listing.recent |= placement.recent;
+        }
+        listing.counts_changed = (before.public || after.public)
+            && (before.public != after.public
+                || before.tags != after.tags
+                || before.month != after.month);
+        listing
+    }
+}
+
+impl AdminPostService {
+    /// Capture where `post` is listed before a write, when a cache is wired.
+    ///
+    /// Returns `None` when there is no cache or the lookup fails; the
+    /// follow-up notification then falls back to invalidating every listing.
+    pub(crate) async fn placement_before_write(
+        &self,
+        post: &PostRecord,
+    ) -> Option<ListingPlacement> {
+        self.cache_trigger.as_ref()?;
+        self.listing_placement_or_warn(post).await
+    }
+
+    /// Notify the cache that `post` changed, scoped to the listings it moved
+    /// between when `before` is known.
+    pub(crate) async fn notify_post_listing_change(
+        &self,
+        post: &PostRecord,
+        previous_slug: Option<&str>,
+        before: Option<ListingPlacement>,
+    ) {
+        let Some(trigger) = &self.cache_trigger else {
+            return;
+        };
+
+        let after = match before {
+            Some(_) => self.listing_placement_or_warn(post).await,
+            None => None,
+        };
+        match before.zip(after) {
+            Some((before, after)) => {
+                trigger
+                    .post_upserted_in_listing(
+                        post.id,
+                        &post.slug,
+                        previous_slug,
+                        ListingPlacement::change(&before, &after),
+                    )
+                    .await;
+            }
+            None => {
+                trigger
+                    .post_upserted_with_previous_slug(post.id, &post.slug, previous_slug)
+                    .await;
+            }
+        }
+    }
+
+    /// Notify the cache that `post` changed in a way that cannot move it
+    /// between listings, resolving its placement once for both sides.
+    pub(crate) async fn notify_post_listing_unmoved(
+        &self,
+        post: &PostRecord,
+        previous_slug: Option<&str>,
+    ) {
+        let Some(trigger) = &self.cache_trigger else {
+            return;
+        };
+
+        match self.listing_placement_or_warn(post).await {
+            Some(placement) => {
+                trigger
+                    .post_upserted_in_listing(
+                        post.id,
+                        &post.slug,
+                        previous_slug,
+                        ListingPlacement::change(&placement, &placement),
+                    )
+                    .await;
+            }
+            None => {
+                trigger
+                    .post_upserted_with_previous_slug(post.id, &post.slug, previous_slug)
+                    .await;
+            }
+        }
+    }
+
+    async fn listing_placement_or_warn(&self, post: &PostRecord) -> Option<ListingPlacement> {
+        match self.listing_placement(post).await {
+            Ok(placement) => Some(placement),
+            Err(err) => {
+                warn!(
+                    post_id = %post.id,
+                    error = %err,
+                    "Failed to resolve post listings; invalidating all listings"
+                );
+                None
+            }
+        }
+    }
+
+    /// Resolve the public listings `post` currently appears on.
+    async fn listing_placement(
+        &self,
+        post: &PostRecord,
+    ) -> Result<ListingPlacement, AdminPostError> {
+        let Some(published_at) = post
+            .published_at
+            .filter(|_| post.status == PostStatus::Published)
+        else {
+            return Ok(ListingPlacement::default());
+        };
+
+        let tags = self
+            .tags
+            .list_for_post(post.id)
+            .await?
+            .into_iter()
+            .map(|tag| tag.slug)
+            .collect();
+
+        // Rank the post from its own sort key rather than listing the window.
+        let recent = post.pinned
+            || self
+                .reader
+                .count_posts_before(
+                    PostListScope::Public,
+                    &PostQueryFilter::default(),
+                    &PostCursor::published(published_at, post.id, post.pinned, post.pin_order),
+                )
+                .await?
+                <= u64::from(RECENT_LISTING_WINDOW);
+
+        Ok(ListingPlacement {
+            public: true,
+            tags,
+            month: Some(month_key_for(published_at.to_offset(UtcOffset::UTC).date())),
+            recent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placement(tags: &[&str], month: &str, recent: bool) -> ListingPlacement {
+        ListingPlacement {
+            public: true,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            month: Some(month.to_string()),
+            recent,
+        }
+    }
+
+    #[test]
+    fn content_edit_keeps_counts_and_scopes_to_current_listings() {
+        let current = placement(&["rust"], "2019-03", false);
+        let listing = ListingPlacement::change(&current, &current);
+
+        assert_eq!(listing.tags, BTreeSet::from(["rust".to_string()]));
+        assert_eq!(listing.months, BTreeSet::from(["2019-03".to_string()]));
+        assert!(!listing.recent);
+        assert!(!listing.counts_changed);
+    }
+
+    #[test]
+    fn retagging_covers_old_and_new_tags() {
+        let before = placement(&["rust"], "2026-10", true);
+        let after = placement(&["go"], "2026-10", true);
+        let listing = ListingPlacement::change(&before, &after);
+
+        assert_eq!(
+            listing.tags,
+            BTreeSet::from(["go".to_string(), "rust".to_string()])
+        );
+        assert!(listing.recent);
+        assert!(listing.counts_changed);
+    }
+
+    #[test]
+    fn drafts_are_not_listed() {
+        let draft = ListingPlacement::default();
+        let listing = ListingPlacement::change(&draft, &draft);
+
+        assert_eq!(listing, PostListing::default());
+    }
+}
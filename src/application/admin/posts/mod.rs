@@ -1,4 +1,5 @@
 mod commands;
+mod listing;
 mod queries;
 mod service;
 pub mod types;
@@ -6,6 +7,6 @@ pub mod types;
 pub use service::*;
 pub use types::{
     AdminPostError, AdminPostStatusCounts, CreatePostCommand, PostSummarySnapshot,
-    StatusTimestamps, UpdatePostContentCommand, UpdatePostStatusCommand, ensure_non_empty,
-    normalize_status,
+    PostWithRenderProgress, StatusTimestamps, UpdatePostContentCommand, UpdatePostStatusCommand,
+    editable_excerpt, ensure_non_empty, normalize_status,
 };
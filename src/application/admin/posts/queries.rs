@@ -1,12 +1,16 @@
+use std::time::Duration;
+
+use chrono_tz::Tz;
 use uuid::Uuid;
 
-use crate::application::pagination::{CursorPage, PageRequest, PostCursor};
+use crate::application::jobs::{RenderProgress, post_render_progress, wait_for_post_render};
+use crate::application::pagination::{CursorPage, PageRequest, PostCursor, TrashCursor};
 use crate::application::repos::{PostListScope, PostQueryFilter, PostTagCount};
-use crate::domain::entities::{PostRecord, PostSectionRecord};
+use crate::domain::entities::{PostRecord, PostSectionRecord, TrashedRecord};
 use crate::domain::types::PostStatus;
 
 use super::service::AdminPostService;
-use super::types::{AdminPostError, AdminPostStatusCounts};
+use super::types::{AdminPostError, AdminPostStatusCounts, PostWithRenderProgress};
 
 impl AdminPostService {
     pub async fn list(
@@ -21,6 +25,16 @@ impl AdminPostService {
             .map_err(AdminPostError::from)
     }
 
+    pub async fn list_trash(
+        &self,
+        page: PageRequest<TrashCursor>,
+    ) -> Result<CursorPage<TrashedRecord>, AdminPostError> {
+        self.writer
+            .list_trashed_posts(page)
+            .await
+            .map_err(AdminPostError::from)
+    }
+
     pub async fn load_post(&self, id: Uuid) -> Result<Option<PostRecord>, AdminPostError> {
         self.reader
             .find_by_id(id)
@@ -28,6 +42,33 @@ impl AdminPostService {
             .map_err(AdminPostError::from)
     }
 
+    /// Progress of the latest render and publish jobs for `post`.
+    pub async fn render_progress(
+        &self,
+        post: &PostRecord,
+    ) -> Result<RenderProgress, AdminPostError> {
+        post_render_progress(self.jobs.as_ref(), post)
+            .await
+            .map_err(AdminPostError::from)
+    }
+
+    /// `post` with its render progress; with `wait`, first give in-flight
+    /// jobs up to that long to finish.
+    pub async fn with_render_progress(
+        &self,
+        post: PostRecord,
+        wait: Option<Duration>,
+    ) -> Result<PostWithRenderProgress, AdminPostError> {
+        let render_progress = match wait {
+            Some(timeout) => wait_for_post_render(self.jobs.as_ref(), &post, timeout).await?,
+            None => self.render_progress(&post).await?,
+        };
+        Ok(PostWithRenderProgress {
+            post,
+            render_progress,
+        })
+    }
+
     pub async fn load_sections(
         &self,
         post_id: Uuid,
@@ -99,6 +140,26 @@ impl AdminPostService {
             .map_err(AdminPostError::from)
     }
 
+    /// Published-post counts by month in `time_zone`, newest first, limited
+    /// to the `limit` most recent months as in the public month filter.
+    pub async fn month_aggregations(
+        &self,
+        time_zone: Tz,
+        limit: i32,
+    ) -> Result<Vec<crate::domain::posts::MonthCount>, AdminPostError> {
+        let mut counts = self
+            .reader
+            .list_local_month_counts(
+                PostListScope::Public,
+                &PostQueryFilter::default(),
+                time_zone,
+            )
+            .await
+            .map_err(AdminPostError::from)?;
+        counts.truncate(limit.max(0) as usize);
+        Ok(counts)
+    }
+
     pub async fn tag_counts(
         &self,
         status: Option<PostStatus>,
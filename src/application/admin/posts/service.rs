@@ -9,6 +9,10 @@ use crate::application::repos::{
 };
 use crate::cache::CacheTrigger;
 use crate::domain::entities::PostRecord;
+use crate::domain::slug::SlugStrategy;
+
+/// Excerpt length used when the service is not configured explicitly.
+pub const DEFAULT_EXCERPT_LENGTH: usize = 200;
 
 #[derive(Clone)]
 pub struct AdminPostService {
@@ -19,6 +23,8 @@ pub struct AdminPostService {
     pub(crate) tags: Arc<dyn TagsRepo>,
     pub(crate) audit: AdminAuditService,
     pub(crate) cache_trigger: Option<Arc<CacheTrigger>>,
+    pub(crate) excerpt_length: usize,
+    pub(crate) slug_strategy: SlugStrategy,
 }
 
 impl AdminPostService {
@@ -38,6 +44,8 @@ impl AdminPostService {
             tags,
             audit,
             cache_trigger: None,
+            excerpt_length: DEFAULT_EXCERPT_LENGTH,
+            slug_strategy: SlugStrategy::default(),
         }
     }
 
@@ -53,6 +61,18 @@ impl AdminPostService {
         self
     }
 
+    /// Set the maximum length of excerpts derived from the post body.
+    pub fn with_excerpt_length(mut self, length: usize) -> Self {
+        self.excerpt_length = length;
+        self
+    }
+
+    /// Set how slugs are derived from the titles of new posts.
+    pub fn with_slug_strategy(mut self, strategy: SlugStrategy) -> Self {
+        self.slug_strategy = strategy;
+        self
+    }
+
     pub async fn snapshot_source(
         &self,
         id: uuid::Uuid,
@@ -94,6 +114,7 @@ impl AdminPostService {
             slug: payload.slug,
             title: payload.title,
             excerpt: payload.excerpt,
+            excerpt_generated: payload.excerpt_generated,
             body_markdown: payload.body_markdown,
             summary_markdown: payload.summary_markdown,
             summary_html: payload.summary_html,
@@ -120,22 +141,27 @@ impl AdminPostService {
     }
 
     /// Trigger cache invalidation after background materialization completes.
+    ///
+    /// Materialization never moves a post between listings, so the current
+    /// placement serves as both sides of the change.
     pub(crate) async fn notify_post_materialized(
         &self,
         post_id: uuid::Uuid,
         slug: &str,
     ) -> Result<(), crate::application::admin::posts::types::AdminPostError> {
-        let previous_slug = self
-            .reader
-            .find_by_id(post_id)
-            .await?
-            .map(|record| record.slug);
-        if let Some(trigger) = &self.cache_trigger {
-            let previous_slug = previous_slug.filter(|value| value != slug);
-            trigger
-                .post_upserted_with_previous_slug(post_id, slug, previous_slug.as_deref())
-                .await;
-        }
+        let Some(post) = self.reader.find_by_id(post_id).await? else {
+            if let Some(trigger) = &self.cache_trigger {
+                trigger.post_upserted(post_id, slug).await;
+            }
+            return Ok(());
+        };
+        let previous_slug = (post.slug != slug).then(|| post.slug.clone());
+        let post = PostRecord {
+            slug: slug.to_string(),
+            ..post
+        };
+        self.notify_post_listing_unmoved(&post, previous_slug.as_deref())
+            .await;
         Ok(())
     }
 }
@@ -3,16 +3,35 @@ use thiserror::Error;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::{application::repos::RepoError, domain::types::PostStatus};
+use crate::{
+    application::{jobs::RenderProgress, repos::RepoError},
+    domain::{entities::PostRecord, slug::SlugValidationError, types::PostStatus},
+};
+
+/// Unique index guarding live post slugs.
+pub const POST_SLUG_CONSTRAINT: &str = "posts_slug_key";
 
 #[derive(Debug, Error)]
 pub enum AdminPostError {
     #[error("{0}")]
     ConstraintViolation(&'static str),
+    #[error("invalid slug: {0}")]
+    InvalidSlug(#[from] SlugValidationError),
+    #[error("slug `{slug}` is already in use")]
+    SlugConflict { slug: String },
     #[error(transparent)]
     Repo(#[from] RepoError),
 }
 
+/// A post with the progress of its latest render and publish jobs, as the
+/// API returns it.
+#[derive(Debug, Clone, Serialize)]
+pub struct PostWithRenderProgress {
+    #[serde(flatten)]
+    pub post: PostRecord,
+    pub render_progress: RenderProgress,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct PostSummarySnapshot<'a> {
     pub slug: &'a str,
@@ -20,6 +39,20 @@ pub struct PostSummarySnapshot<'a> {
     pub status: PostStatus,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct PostRerenderSnapshot<'a> {
+    pub slug: &'a str,
+    pub title: &'a str,
+    pub job_id: &'a str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PostTranslationSnapshot<'a> {
+    pub slug: &'a str,
+    pub lang: Option<&'a str>,
+    pub translation_group: Option<Uuid>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct PostTagsSnapshot<'a> {
     pub slug: &'a str,
@@ -29,6 +62,8 @@ pub struct PostTagsSnapshot<'a> {
 
 #[derive(Debug, Clone)]
 pub struct CreatePostCommand {
+    /// Explicit slug; `None` derives one from the title with `-N` suffixing.
+    pub slug: Option<String>,
     pub title: String,
     pub excerpt: String,
     pub body_markdown: String,
@@ -49,6 +84,8 @@ pub struct UpdatePostContentCommand {
     pub body_markdown: String,
     pub pinned: bool,
     pub summary_markdown: Option<String>,
+    /// `updated_at` the caller last read; a newer row fails the update.
+    pub expected_updated_at: Option<OffsetDateTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -105,6 +142,16 @@ pub fn normalize_status(
     }
 }
 
+/// The excerpt an editor should see: blank when it was derived from the body,
+/// so saving again re-derives it instead of freezing the generated text.
+pub fn editable_excerpt(post: &PostRecord) -> String {
+    if post.excerpt_generated {
+        String::new()
+    } else {
+        post.excerpt.clone()
+    }
+}
+
 pub fn ensure_non_empty(value: &str, field: &'static str) -> Result<(), AdminPostError> {
     if value.trim().is_empty() {
         return Err(AdminPostError::ConstraintViolation(field));
@@ -0,0 +1,223 @@
+//! Content calendar: scheduled and published posts and pages by day.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono_tz::Tz;
+use serde::{Serialize, Serializer};
+use thiserror::Error;
+use time::{Date, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::application::repos::{PagesRepo, PostsRepo, RepoError};
+use crate::domain::entities::CalendarRecord;
+use crate::util::timezone;
+
+use super::dashboard::ScheduledKind;
+
+/// Most days one calendar query may span.
+pub const MAX_SCHEDULE_DAYS: i64 = 366;
+
+#[derive(Debug, Error)]
+pub enum AdminScheduleError {
+    #[error("`to` is before `from`")]
+    InvalidRange,
+    #[error("the range spans more than {MAX_SCHEDULE_DAYS} days")]
+    RangeTooLong,
+    #[error(transparent)]
+    Repo(#[from] RepoError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleItemState {
+    /// A draft that publishes at `at`.
+    Scheduled,
+    /// Went live at `at`.
+    Published,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScheduleItem {
+    pub kind: ScheduledKind,
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub state: ScheduleItemState,
+    #[serde(with = "time::serde::rfc3339")]
+    pub at: OffsetDateTime,
+}
+
+impl ScheduleItem {
+    fn new(kind: ScheduledKind, record: CalendarRecord) -> Self {
+        Self {
+            kind,
+            id: record.id,
+            slug: record.slug,
+            title: record.title,
+            state: if record.scheduled {
+                ScheduleItemState::Scheduled
+            } else {
+                ScheduleItemState::Published
+            },
+            at: record.at,
+        }
+    }
+}
+
+/// The items dated on one day in the site timezone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScheduleDay {
+    #[serde(serialize_with = "serialize_date")]
+    pub date: Date,
+    pub items: Vec<ScheduleItem>,
+}
+
+#[derive(Clone)]
+pub struct AdminScheduleService {
+    posts: Arc<dyn PostsRepo>,
+    pages: Arc<dyn PagesRepo>,
+}
+
+impl AdminScheduleService {
+    pub fn new(posts: Arc<dyn PostsRepo>, pages: Arc<dyn PagesRepo>) -> Self {
+        Self { posts, pages }
+    }
+
+    /// Posts and pages dated from `from` through `to`, both whole days in
+    /// `tz`, grouped by day. Days without items are left out.
+    pub async fn calendar(
+        &self,
+        from: Date,
+        to: Date,
+        tz: Tz,
+    ) -> Result<Vec<ScheduleDay>, AdminScheduleError> {
+        if to < from {
+            return Err(AdminScheduleError::InvalidRange);
+        }
+        if (to - from).whole_days() >= MAX_SCHEDULE_DAYS {
+            return Err(AdminScheduleError::RangeTooLong);
+        }
+        let start = timezone::local_date_start(from, tz).ok_or(AdminScheduleError::InvalidRange)?;
+        let end = to
+            .next_day()
+            .and_then(|day| timezone::local_date_start(day, tz))
+            .ok_or(AdminScheduleError::InvalidRange)?;
+
+        let (posts, pages) = tokio::try_join!(
+            self.posts.list_calendar_posts(start, end),
+            self.pages.list_calendar_pages(start, end),
+        )?;
+
+        let items = posts
+            .into_iter()
+            .map(|record| ScheduleItem::new(ScheduledKind::Post, record))
+            .chain(
+                pages
+                    .into_iter()
+                    .map(|record| ScheduleItem::new(ScheduledKind::Page, record)),
+            )
+            .collect();
+        Ok(group_by_day(items, tz))
+    }
+}
+
+/// Group `items` by their date in `tz`: days in order, and items by time,
+/// then title, within a day.
+pub fn group_by_day(items: Vec<ScheduleItem>, tz: Tz) -> Vec<ScheduleDay> {
+    let mut days: BTreeMap<Date, Vec<ScheduleItem>> = BTreeMap::new();
+    for item in items {
+        days.entry(timezone::localized_date(item.at, tz))
+            .or_default()
+            .push(item);
+    }
+
+    days.into_iter()
+        .map(|(date, mut items)| {
+            items.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.title.cmp(&b.title)));
+            ScheduleDay { date, items }
+        })
+        .collect()
+}
+
+fn serialize_date<S: Serializer>(date: &Date, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    fn item(title: &str, at: OffsetDateTime) -> ScheduleItem {
+        ScheduleItem {
+            kind: ScheduledKind::Post,
+            id: Uuid::new_v4(),
+            slug: title.to_lowercase(),
+            title: title.to_string(),
+            state: ScheduleItemState::Scheduled,
+            at,
+        }
+    }
+
+    fn titles_by_day(days: &[ScheduleDay]) -> Vec<(Date, Vec<&str>)> {
+        days.iter()
+            .map(|day| {
+                let titles = day.items.iter().map(|item| item.title.as_str()).collect();
+                (day.date, titles)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn items_move_across_month_boundaries_with_the_site_timezone() {
+        let items = vec![
+            item("Late", datetime!(2026-10-31 15:30 UTC)),
+            item("Halloween", datetime!(2026-10-31 09:00 UTC)),
+            item("Early", datetime!(2026-11-01 02:00 UTC)),
+        ];
+
+        let tokyo = group_by_day(items.clone(), chrono_tz::Asia::Tokyo);
+        assert_eq!(
+            titles_by_day(&tokyo),
+            [
+                (date!(2026 - 10 - 31), vec!["Halloween"]),
+                (date!(2026 - 11 - 01), vec!["Late", "Early"]),
+            ]
+        );
+
+        let los_angeles = group_by_day(items, chrono_tz::America::Los_Angeles);
+        assert_eq!(
+            titles_by_day(&los_angeles),
+            [(date!(2026 - 10 - 31), vec!["Halloween", "Late", "Early"]),]
+        );
+    }
+
+    #[test]
+    fn items_on_one_day_sort_by_time_then_title() {
+        let at = datetime!(2026-12-31 23:00 UTC);
+        let days = group_by_day(
+            vec![
+                item("Beta", at),
+                item("Alpha", at),
+                item("First", at - time::Duration::hours(1)),
+            ],
+            chrono_tz::UTC,
+        );
+
+        assert_eq!(
+            titles_by_day(&days),
+            [(date!(2026 - 12 - 31), vec!["First", "Alpha", "Beta"])]
+        );
+    }
+
+    #[test]
+    fn days_serialize_as_calendar_dates() {
+        let day = ScheduleDay {
+            date: date!(2027 - 01 - 05),
+            items: Vec::new(),
+        };
+        let json = serde_json::to_value(&day).expect("serialize day");
+        assert_eq!(json["date"], "2027-01-05");
+    }
+}
@@ -0,0 +1,387 @@
+use std::sync::Arc;
+
+use chrono_tz::Tz;
+use serde::Serialize;
+use soffio_api_types::SettingsPatchRequest;
+use thiserror::Error;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::application::admin::audit::{AdminAuditService, AuditChanges};
+use crate::application::repos::{PagesRepo, RepoError, SettingsRepo, UploadsRepo};
+use crate::cache::CacheTrigger;
+use crate::domain::entities::SiteSettingsRecord;
+use crate::domain::types::{FeedContentMode, HomepageLayout, HomepageMode, PageStatus};
+
+mod validator;
+
+pub use validator::{
+    FAVICON_UPLOAD_TYPES, MAX_ADMIN_PAGE_SIZE, MAX_DESCRIPTION_CHARS, MAX_FAVICON_SVG_LENGTH,
+    MAX_FEED_ITEM_LIMIT, MAX_FILTER_LIMIT, MAX_PAGE_SIZE, MAX_RETRY_AFTER_SECS, MAX_TITLE_CHARS,
+    MIN_ADMIN_PAGE_SIZE, SettingsFieldError, SettingsValidationError, SettingsValidator,
+    parse_timezone,
+};
+
+#[derive(Debug, Error)]
+pub enum AdminSettingsError {
+    #[error(transparent)]
+    Invalid(#[from] SettingsValidationError),
+    #[error(transparent)]
+    Repo(#[from] RepoError),
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateSettingsCommand {
+    pub homepage_size: i32,
+    pub admin_page_size: i32,
+    pub show_tag_aggregations: bool,
+    pub show_month_aggregations: bool,
+    pub tag_filter_limit: i32,
+    pub month_filter_limit: i32,
+    pub global_toc_enabled: bool,
+    pub brand_title: String,
+    pub brand_href: String,
+    pub footer_copy: String,
+    pub public_site_url: String,
+    pub favicon_svg: String,
+    pub favicon_upload_id: Option<Uuid>,
+    pub timezone: Tz,
+    pub date_format: String,
+    pub meta_title: String,
+    pub meta_description: String,
+    pub og_title: String,
+    pub og_description: String,
+    pub maintenance_enabled: bool,
+    pub maintenance_html: String,
+    pub maintenance_retry_after_secs: i32,
+    pub feed_content_mode: FeedContentMode,
+    pub feed_item_limit: i32,
+    pub homepage_mode: HomepageMode,
+    pub homepage_page_id: Option<Uuid>,
+    pub homepage_layout: HomepageLayout,
+}
+
+#[derive(Clone)]
+pub struct AdminSettingsService {
+    repo: Arc<dyn SettingsRepo>,
+    uploads: Arc<dyn UploadsRepo>,
+    pages: Arc<dyn PagesRepo>,
+    audit: AdminAuditService,
+    cache_trigger: Option<Arc<CacheTrigger>>,
+}
+
+impl AdminSettingsService {
+    pub fn new(
+        repo: Arc<dyn SettingsRepo>,
+        uploads: Arc<dyn UploadsRepo>,
+        pages: Arc<dyn PagesRepo>,
+        audit: AdminAuditService,
+    ) -> Self {
+        Self {
+            repo,
+            uploads,
+            pages,
+            audit,
+            cache_trigger: None,
+        }
+    }
+
+    /// Set the cache trigger for this service.
+    pub fn with_cache_trigger(mut self, trigger: Arc<CacheTrigger>) -> Self {
+        self.cache_trigger = Some(trigger);
+        self
+    }
+
+    /// Set the cache trigger for this service (optional).
+    pub fn with_cache_trigger_opt(mut self, trigger: Option<Arc<CacheTrigger>>) -> Self {
+        self.cache_trigger = trigger;
+        self
+    }
+
+    pub async fn load(&self) -> Result<SiteSettingsRecord, AdminSettingsError> {
+        self.repo
+            .load_site_settings()
+            .await
+            .map_err(AdminSettingsError::from)
+    }
+
+    pub async fn update(
+        &self,
+        actor: &str,
+        command: UpdateSettingsCommand,
+    ) -> Result<SiteSettingsRecord, AdminSettingsError> {
+        SettingsValidator::validate_command(&command)?;
+        self.check_favicon_upload(command.favicon_upload_id).await?;
+
+        let previous = self.repo.load_site_settings().await?;
+        let mut record = previous.clone();
+        record.homepage_size = command.homepage_size;
+        record.admin_page_size = command.admin_page_size;
+        record.show_tag_aggregations = command.show_tag_aggregations;
+        record.show_month_aggregations = command.show_month_aggregations;
+        record.tag_filter_limit = command.tag_filter_limit;
+        record.month_filter_limit = command.month_filter_limit;
+        record.global_toc_enabled = command.global_toc_enabled;
+        record.brand_title = command.brand_title;
+        record.brand_href = command.brand_href;
+        record.footer_copy = command.footer_copy;
+        record.public_site_url = command.public_site_url;
+        record.favicon_svg = command.favicon_svg;
+        record.favicon_upload_id = command.favicon_upload_id;
+        record.timezone = command.timezone;
+        record.date_format = command.date_format;
+        record.meta_title = command.meta_title;
+        record.meta_description = command.meta_description;
+        record.og_title = command.og_title;
+        record.og_description = command.og_description;
+        record.maintenance_enabled = command.maintenance_enabled;
+        record.maintenance_html = command.maintenance_html;
+        record.maintenance_retry_after_secs = command.maintenance_retry_after_secs;
+        record.feed_content_mode = command.feed_content_mode;
+        record.feed_item_limit = command.feed_item_limit;
+        record.homepage_mode = command.homepage_mode;
+        record.homepage_page_id = command.homepage_page_id;
+        record.homepage_layout = command.homepage_layout;
+        self.check_homepage(&previous, &record).await?;
+
+        self.persist(actor, previous, record).await
+    }
+
+    /// Apply the fields `patch` sets, validating only those; values it leaves
+    /// out are kept as stored.
+    pub async fn patch(
+        &self,
+        actor: &str,
+        patch: SettingsPatchRequest,
+    ) -> Result<SiteSettingsRecord, AdminSettingsError> {
+        SettingsValidator::validate_patch(&patch)?;
+        if let Some(id) = patch.favicon_upload_id {
+            self.check_favicon_upload(id).await?;
+        }
+
+        let previous = self.repo.load_site_settings().await?;
+        let mut record = previous.clone();
+        if let Some(value) = patch.homepage_size {
+            record.homepage_size = value;
+        }
+        if let Some(value) = patch.admin_page_size {
+            record.admin_page_size = value;
+        }
+        if let Some(value) = patch.show_tag_aggregations {
+            record.show_tag_aggregations = value;
+        }
+        if let Some(value) = patch.show_month_aggregations {
+            record.show_month_aggregations = value;
+        }
+        if let Some(value) = patch.tag_filter_limit {
+            record.tag_filter_limit = value;
+        }
+        if let Some(value) = patch.month_filter_limit {
+            record.month_filter_limit = value;
+        }
+        if let Some(value) = patch.global_toc_enabled {
+            record.global_toc_enabled = value;
+        }
+        if let Some(value) = patch.brand_title {
+            record.brand_title = value;
+        }
+        if let Some(value) = patch.brand_href {
+            record.brand_href = value;
+        }
+        if let Some(value) = patch.footer_copy {
+            record.footer_copy = value;
+        }
+        if let Some(value) = patch.public_site_url {
+            record.public_site_url = value;
+        }
+        if let Some(value) = patch.favicon_svg {
+            record.favicon_svg = value;
+        }
+        if let Some(value) = patch.favicon_upload_id {
+            record.favicon_upload_id = value;
+        }
+        if let Some(value) = patch.timezone {
+            record.timezone = parse_timezone(&value)
+                .ok_or_else(|| SettingsValidationError::single("timezone", "is not recognised"))?;
+        }
+        if let Some(value) = patch.date_format {
+            record.date_format = value;
+        }
+        if let Some(value) = patch.meta_title {
+            record.meta_title = value;
+        }
+        if let Some(value) = patch.meta_description {
+            record.meta_description = value;
+        }
+        if let Some(value) = patch.og_title {
+            record.og_title = value;
+        }
+        if let Some(value) = patch.og_description {
+            record.og_description = value;
+        }
+        if let Some(value) = patch.maintenance_enabled {
+            record.maintenance_enabled = value;
+        }
+        if let Some(value) = patch.maintenance_html {
+            record.maintenance_html = value;
+        }
+        if let Some(value) = patch.maintenance_retry_after_secs {
+            record.maintenance_retry_after_secs = value;
+        }
+        if let Some(value) = patch.feed_content_mode {
+            record.feed_content_mode = value;
+        }
+        if let Some(value) = patch.feed_item_limit {
+            record.feed_item_limit = value;
+        }
+        if let Some(value) = patch.homepage_mode {
+            record.homepage_mode = value;
+        }
+        if let Some(value) = patch.homepage_page_id {
+            record.homepage_page_id = value;
+        }
+        if let Some(value) = patch.homepage_layout {
+            record.homepage_layout = value;
+        }
+        self.check_homepage(&previous, &record).await?;
+
+        self.persist(actor, previous, record).await
+    }
+
+    /// A favicon upload must exist and be one of [`FAVICON_UPLOAD_TYPES`].
+    async fn check_favicon_upload(&self, id: Option<Uuid>) -> Result<(), AdminSettingsError> {
+        let Some(id) = id else {
+            return Ok(());
+        };
+        let usable = self
+            .uploads
+            .find_upload(id)
+            .await?
+            .is_some_and(|upload| FAVICON_UPLOAD_TYPES.contains(&upload.content_type.as_str()));
+        if usable {
+            Ok(())
+        } else {
+            Err(SettingsValidationError::single(
+                "favicon_upload_id",
+                "must name an uploaded PNG or ICO image",
+            )
+            .into())
+        }
+    }
+
+    /// Page mode needs a homepage page, and a newly chosen one must be
+    /// published. An unchanged choice is not rechecked, so other settings
+    /// still save after the page is unpublished; `/` serves the feed then.
+    async fn check_homepage(
+        &self,
+        previous: &SiteSettingsRecord,
+        record: &SiteSettingsRecord,
+    ) -> Result<(), AdminSettingsError> {
+        if record.homepage_mode == HomepageMode::Page && record.homepage_page_id.is_none() {
+            return Err(SettingsValidationError::single(
+                "homepage_page_id",
+                "is required when homepage_mode is `page`",
+            )
+            .into());
+        }
+        let unchanged = record.homepage_mode == previous.homepage_mode
+            && record.homepage_page_id == previous.homepage_page_id;
+        let Some(id) = record.homepage_page_id.filter(|_| !unchanged) else {
+            return Ok(());
+        };
+        let published = self.pages.find_by_id(id).await?.is_some_and(|page| {
+            page.status == PageStatus::Published && page.published_at.is_some()
+        });
+        if published {
+            Ok(())
+        } else {
+            Err(
+                SettingsValidationError::single("homepage_page_id", "must name a published page")
+                    .into(),
+            )
+        }
+    }
+
+    async fn persist(
+        &self,
+        actor: &str,
+        previous: SiteSettingsRecord,
+        mut record: SiteSettingsRecord,
+    ) -> Result<SiteSettingsRecord, AdminSettingsError> {
+        record.updated_at = OffsetDateTime::now_utc();
+
+        self.repo.upsert_site_settings(record.clone()).await?;
+        let latest = self.repo.load_site_settings().await?;
+
+        let snapshot = SettingsSnapshot::from(&latest);
+        let changes = AuditChanges::between(&previous, &latest);
+        self.audit
+            .record_with_changes(
+                actor,
+                "settings.update",
+                "settings",
+                None,
+                Some(&snapshot),
+                &changes,
+            )
+            .await?;
+
+        // Trigger cache invalidation
+        if let Some(trigger) = &self.cache_trigger {
+            trigger.site_settings_updated().await;
+        }
+
+        Ok(latest)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SettingsSnapshot<'a> {
+    homepage_size: i32,
+    admin_page_size: i32,
+    show_tag_aggregations: bool,
+    show_month_aggregations: bool,
+    tag_filter_limit: i32,
+    month_filter_limit: i32,
+    global_toc_enabled: bool,
+    brand_title: &'a str,
+    brand_href: &'a str,
+    public_site_url: &'a str,
+    favicon_upload_id: Option<Uuid>,
+    timezone: &'a str,
+    date_format: &'a str,
+    maintenance_enabled: bool,
+    maintenance_retry_after_secs: i32,
+    feed_content_mode: &'a str,
+    feed_item_limit: i32,
+    homepage_mode: &'a str,
+    homepage_page_id: Option<Uuid>,
+    homepage_layout: &'a str,
+}
+
+impl<'a> From<&'a SiteSettingsRecord> for SettingsSnapshot<'a> {
+    fn from(record: &'a SiteSettingsRecord) -> Self {
+        Self {
+            homepage_size: record.homepage_size,
+            admin_page_size: record.admin_page_size,
+            show_tag_aggregations: record.show_tag_aggregations,
+            show_month_aggregations: record.show_month_aggregations,
+            tag_filter_limit: record.tag_filter_limit,
+            month_filter_limit: record.month_filter_limit,
+            global_toc_enabled: record.global_toc_enabled,
+            brand_title: record.brand_title.as_str(),
+            brand_href: record.brand_href.as_str(),
+            public_site_url: record.public_site_url.as_str(),
+            favicon_upload_id: record.favicon_upload_id,
+            timezone: record.timezone.name(),
+            date_format: record.date_format.as_str(),
+            maintenance_enabled: record.maintenance_enabled,
+            maintenance_retry_after_secs: record.maintenance_retry_after_secs,
+            feed_content_mode: record.feed_content_mode.as_str(),
+            feed_item_limit: record.feed_item_limit,
+            homepage_mode: record.homepage_mode.as_str(),
+            homepage_page_id: record.homepage_page_id,
+            homepage_layout: record.homepage_layout.as_str(),
+        }
+    }
+}
@@ -0,0 +1,720 @@
+//! Validation rules shared by every path that writes site settings.
+//!
+//! The admin form and the API both run these checks, so a bad value is
+//! rejected when it is submitted rather than when the public site first uses
+//! it. Rules are per field: a partial update checks only the fields it sets.
+
+use chrono_tz::{TZ_VARIANTS, Tz};
+use serde::Serialize;
+use soffio_api_types::SettingsPatchRequest;
+use thiserror::Error;
+use url::Url;
+
+use crate::application::format;
+use crate::domain::routes::site_root;
+
+use super::UpdateSettingsCommand;
+
+pub const MAX_PAGE_SIZE: i32 = 100;
+/// Admin lists stay useful between these; per-request `limit` overrides are
+/// clamped to the same range.
+pub const MIN_ADMIN_PAGE_SIZE: i32 = 5;
+pub const MAX_ADMIN_PAGE_SIZE: i32 = 200;
+pub const MAX_FILTER_LIMIT: i32 = 100;
+/// Full-content feeds grow quickly; readers only fetch the newest items.
+pub const MAX_FEED_ITEM_LIMIT: i32 = 100;
+/// One day; longer hints are ignored by most clients anyway.
+pub const MAX_RETRY_AFTER_SECS: i32 = 86_400;
+pub const MAX_FAVICON_SVG_LENGTH: usize = 8 * 1024;
+/// Content types an upload may have to be served as the raster favicon.
+pub const FAVICON_UPLOAD_TYPES: [&str; 3] =
+    ["image/png", "image/x-icon", "image/vnd.microsoft.icon"];
+pub const MAX_TITLE_CHARS: usize = 120;
+pub const MAX_DESCRIPTION_CHARS: usize = 320;
+
+/// A rejected value and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettingsFieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid settings: {}", summarize(.errors))]
+pub struct SettingsValidationError {
+    pub errors: Vec<SettingsFieldError>,
+}
+
+impl SettingsValidationError {
+    pub(super) fn single(field: &'static str, message: &str) -> Self {
+        Self {
+            errors: vec![SettingsFieldError {
+                field,
+                message: message.to_string(),
+            }],
+        }
+    }
+
+    pub fn field(&self, field: &str) -> Option<&SettingsFieldError> {
+        self.errors.iter().find(|error| error.field == field)
+    }
+}
+
+fn summarize(errors: &[SettingsFieldError]) -> String {
+    errors
+        .iter()
+        .map(|error| format!("`{}` {}", error.field, error.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Collects every field error instead of stopping at the first.
+#[derive(Debug, Default)]
+pub struct SettingsValidator {
+    errors: Vec<SettingsFieldError>,
+}
+
+impl SettingsValidator {
+    /// Check every field of a full update.
+    pub fn validate_command(
+        command: &UpdateSettingsCommand,
+    ) -> Result<(), SettingsValidationError> {
+        let mut validator = Self::default();
+        validator.size("homepage_size", command.homepage_size, MAX_PAGE_SIZE);
+        validator.range(
+            "admin_page_size",
+            command.admin_page_size,
+            MIN_ADMIN_PAGE_SIZE,
+            MAX_ADMIN_PAGE_SIZE,
+        );
+        validator.size(
+            "tag_filter_limit",
+            command.tag_filter_limit,
+            MAX_FILTER_LIMIT,
+        );
+        validator.size(
+            "month_filter_limit",
+            command.month_filter_limit,
+            MAX_FILTER_LIMIT,
+        );
+        validator.text("brand_title", &command.brand_title, Some(MAX_TITLE_CHARS));
+        validator.href("brand_href", &command.brand_href);
+        validator.site_url("public_site_url", &command.public_site_url);
+        validator.favicon_svg(&command.favicon_svg);
+        validator.date_format(&command.date_format);
+        validator.text("meta_title", &command.meta_title, Some(MAX_TITLE_CHARS));
+        validator.text(
+            "meta_description",
+            &command.meta_description,
+            Some(MAX_DESCRIPTION_CHARS),
+        );
+        validator.text("og_title", &command.og_title, Some(MAX_TITLE_CHARS));
+        validator.text(
+            "og_description",
+            &command.og_description,
+            Some(MAX_DESCRIPTION_CHARS),
+        );
+        validator.text("maintenance_html", &command.maintenance_html, None);
+        validator.size(
+            "maintenance_retry_after_secs",
+            command.maintenance_retry_after_secs,
+            MAX_RETRY_AFTER_SECS,
+        );
+        validator.size(
+            "feed_item_limit",
+            command.feed_item_limit,
+            MAX_FEED_ITEM_LIMIT,
+        );
+        validator.finish()
+    }
+
+    /// Check only the fields a partial update sets.
+    pub fn validate_patch(patch: &SettingsPatchRequest) -> Result<(), SettingsValidationError> {
+        let mut validator = Self::default();
+        if let Some(value) = patch.homepage_size {
+            validator.size("homepage_size", value, MAX_PAGE_SIZE);
+        }
+        if let Some(value) = patch.admin_page_size {
+            validator.range(
+                "admin_page_size",
+                value,
+                MIN_ADMIN_PAGE_SIZE,
+                MAX_ADMIN_PAGE_SIZE,
+            );
+        }
+        if let Some(value) = patch.tag_filter_limit {
+            validator.size("tag_filter_limit", value, MAX_FILTER_LIMIT);
+        }
+        if let Some(value) = patch.month_filter_limit {
+            validator.size("month_filter_limit", value, MAX_FILTER_LIMIT);
+        }
+        if let Some(value) = &patch.brand_title {
+            validator.text("brand_title", value, Some(MAX_TITLE_CHARS));
+        }
+        if let Some(value) = &patch.brand_href {
+            validator.href("brand_href", value);
+        }
+        if let Some(value) = &patch.public_site_url {
+            validator.site_url("public_site_url", value);
+        }
+        if let Some(value) = &patch.favicon_svg {
+            validator.favicon_svg(value);
+        }
+        if let Some(value) = &patch.timezone {
+            validator.timezone(value);
+        }
+        if let Some(value) = &patch.date_format {
+            validator.date_format(value);
+        }
+        if let Some(value) = &patch.meta_title {
+            validator.text("meta_title", value, Some(MAX_TITLE_CHARS));
+        }
+        if let Some(value) = &patch.meta_description {
+            validator.text("meta_description", value, Some(MAX_DESCRIPTION_CHARS));
+        }
+        if let Some(value) = &patch.og_title {
+            validator.text("og_title", value, Some(MAX_TITLE_CHARS));
+        }
+        if let Some(value) = &patch.og_description {
+            validator.text("og_description", value, Some(MAX_DESCRIPTION_CHARS));
+        }
+        if let Some(value) = &patch.maintenance_html {
+            validator.text("maintenance_html", value, None);
+        }
+        if let Some(value) = patch.maintenance_retry_after_secs {
+            validator.size("maintenance_retry_after_secs", value, MAX_RETRY_AFTER_SECS);
+        }
+        if let Some(value) = patch.feed_item_limit {
+            validator.size("feed_item_limit", value, MAX_FEED_ITEM_LIMIT);
+        }
+        validator.finish()
+    }
+
+    fn finish(self) -> Result<(), SettingsValidationError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SettingsValidationError {
+                errors: self.errors,
+            })
+        }
+    }
+
+    fn reject(&mut self, field: &'static str, message: impl Into<String>) {
+        self.errors.push(SettingsFieldError {
+            field,
+            message: message.into(),
+        });
+    }
+
+    fn size(&mut self, field: &'static str, value: i32, max: i32) {
+        self.range(field, value, 1, max);
+    }
+
+    fn range(&mut self, field: &'static str, value: i32, min: i32, max: i32) {
+        if !(min..=max).contains(&value) {
+            self.reject(field, format!("must be between {min} and {max}"));
+        }
+    }
+
+    fn text(&mut self, field: &'static str, value: &str, max_chars: Option<usize>) {
+        if value.trim().is_empty() {
+            self.reject(field, "cannot be empty");
+        } else if let Some(max) = max_chars
+            && value.chars().count() > max
+        {
+            self.reject(field, format!("must be at most {max} characters"));
+        }
+    }
+
+    fn site_url(&mut self, field: &'static str, value: &str) {
+        if value.trim().is_empty() {
+            self.reject(field, "cannot be empty");
+        } else if site_root(value).is_none() {
+            self.reject(
+                field,
+                "must be an absolute http(s) URL without a query or fragment",
+            );
+        }
+    }
+
+    /// Links may also be root-relative, like the default `/`.
+    fn href(&mut self, field: &'static str, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            self.reject(field, "cannot be empty");
+        } else if !(is_root_relative(value) || is_http_url(value)) {
+            self.reject(field, "must be a path starting with `/` or an http(s) URL");
+        }
+    }
+
+    fn timezone(&mut self, value: &str) {
+        if parse_timezone(value).is_none() {
+            self.reject(
+                "timezone",
+                "must be an IANA timezone name such as `Europe/Berlin`",
+            );
+        }
+    }
+
+    fn date_format(&mut self, value: &str) {
+        if !format::is_valid_date_format(value) {
+            self.reject("date_format", "must be a valid strftime pattern");
+        }
+    }
+
+    fn favicon_svg(&mut self, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            self.reject("favicon_svg", "cannot be empty");
+        } else if value.len() > MAX_FAVICON_SVG_LENGTH {
+            self.reject(
+                "favicon_svg",
+                format!("must be at most {MAX_FAVICON_SVG_LENGTH} bytes"),
+            );
+        } else if let Err(reason) = check_svg_document(value) {
+            self.reject("favicon_svg", reason);
+        }
+    }
+}
+
+/// Resolve an IANA timezone name, ignoring ASCII case.
+///
+/// Zone names never differ only by case, so `america/new_york` can only mean
+/// `America/New_York`; store the returned zone's `name()` to keep the
+/// canonical spelling.
+pub fn parse_timezone(value: &str) -> Option<Tz> {
+    let value = value.trim();
+    value.parse::<Tz>().ok().or_else(|| {
+        TZ_VARIANTS
+            .iter()
+            .copied()
+            .find(|zone| zone.name().eq_ignore_ascii_case(value))
+    })
+}
+
+fn is_http_url(value: &str) -> bool {
+    Url::parse(value)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some())
+}
+
+fn is_root_relative(value: &str) -> bool {
+    value.starts_with('/') && !value.starts_with("//")
+}
+
+/// Lightweight well-formedness and safety check for an inline SVG document.
+///
+/// Not a full XML parser: it checks that tags nest and close, that the single
+/// root is `<svg>`, and that nothing can run script when the icon is inlined.
+fn check_svg_document(svg: &str) -> Result<(), &'static str> {
+    let mut open: Vec<&str> = Vec::new();
+    let mut root_seen = false;
+    let mut rest = svg;
+
+    loop {
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        if open.is_empty() && !rest[..text_end].trim().is_empty() {
+            return Err("has text outside the <svg> element");
+        }
+        rest = &rest[text_end..];
+        if rest.is_empty() {
+            break;
+        }
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").ok_or("has an unterminated comment")?;
+            rest = &after[end + 3..];
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after
+                .find("]]>")
+                .ok_or("has an unterminated CDATA section")?;
+            rest = &after[end + 3..];
+        } else if let Some(after) = rest.strip_prefix("<?") {
+            let end = after
+                .find("?>")
+                .ok_or("has an unterminated processing instruction")?;
+            rest = &after[end + 2..];
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            if root_seen {
+                return Err("has a declaration after the <svg> element");
+            }
+            let end = after.find('>').ok_or("has an unterminated declaration")?;
+            rest = &after[end + 1..];
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').ok_or("has an unterminated end tag")?;
+            if open.pop() != Some(after[..end].trim()) {
+                return Err("has a mismatched end tag");
+            }
+            rest = &after[end + 1..];
+        } else {
+            let after = &rest[1..];
+            let end = start_tag_end(after).ok_or("has an unterminated start tag")?;
+            let (tag, self_closing) = match after[..end].strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (&after[..end], false),
+            };
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            let name = &tag[..name_end];
+            if name.is_empty() {
+                return Err("has a malformed start tag");
+            }
+            if open.is_empty() {
+                if root_seen {
+                    return Err("must have a single <svg> root element");
+                }
+                if !name.eq_ignore_ascii_case("svg") {
+                    return Err("must have <svg> as its root element");
+                }
+                root_seen = true;
+            }
+            if name.eq_ignore_ascii_case("script") {
+                return Err("cannot contain scripts");
+            }
+            check_attributes(&tag[name_end..])?;
+            if !self_closing {
+                open.push(name);
+            }
+            rest = &after[end + 1..];
+        }
+    }
+
+    if !open.is_empty() {
+        return Err("has an unclosed element");
+    }
+    if !root_seen {
+        return Err("is missing the <svg> element");
+    }
+    Ok(())
+}
+
+/// Index of the `>` closing a start tag, skipping quoted attribute values.
+fn start_tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, ch) in tag.char_indices() {
+        match (quote, ch) {
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(open), _) if ch == open => quote = None,
+            (None, '>') => return Some(index),
+            (None, '<') => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn check_attributes(mut attrs: &str) -> Result<(), &'static str> {
+    loop {
+        attrs = attrs.trim_start();
+        if attrs.is_empty() {
+            return Ok(());
+        }
+        let name_end = attrs
+            .find(|ch: char| ch == '=' || ch.is_whitespace())
+            .unwrap_or(attrs.len());
+        let name = &attrs[..name_end];
+        if name.len() > 2
+            && name
+                .get(..2)
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case("on"))
+        {
+            return Err("cannot contain event handler attributes");
+        }
+
+        let after_name = attrs[name_end..].trim_start();
+        let Some(value) = after_name.strip_prefix('=') else {
+            return Err("has an attribute without a value");
+        };
+        let value = value.trim_start();
+        let quote = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => quote,
+            _ => return Err("has an unquoted attribute value"),
+        };
+        let close = value[1..]
+            .find(quote)
+            .ok_or("has an unterminated attribute value")?;
+        if value[1..close + 1]
+            .trim_start()
+            .to_ascii_lowercase()
+            .starts_with("javascript:")
+        {
+            return Err("cannot contain javascript: URLs");
+        }
+        attrs = &value[close + 2..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::types::{FeedContentMode, HomepageLayout, HomepageMode};
+
+    fn valid_command() -> UpdateSettingsCommand {
+        UpdateSettingsCommand {
+            homepage_size: 6,
+            admin_page_size: 20,
+            show_tag_aggregations: true,
+            show_month_aggregations: true,
+            tag_filter_limit: 16,
+            month_filter_limit: 16,
+            global_toc_enabled: false,
+            brand_title: "Soffio".into(),
+            brand_href: "/".into(),
+            footer_copy: String::new(),
+            public_site_url: "https://example.com/".into(),
+            favicon_svg: r#"<svg xmlns="http://www.w3.org/2000/svg"><rect width="1"/></svg>"#
+                .into(),
+            favicon_upload_id: None,
+            timezone: chrono_tz::UTC,
+            date_format: "%Y-%m-%d".into(),
+            meta_title: "Soffio".into(),
+            meta_description: "Description".into(),
+            og_title: "Soffio".into(),
+            og_description: "Description".into(),
+            maintenance_enabled: false,
+            maintenance_html: "<h1>Back soon</h1>".into(),
+            maintenance_retry_after_secs: 300,
+            feed_content_mode: FeedContentMode::Excerpt,
+            feed_item_limit: 20,
+            homepage_mode: HomepageMode::Feed,
+            homepage_page_id: None,
+            homepage_layout: HomepageLayout::List,
+        }
+    }
+
+    fn empty_patch() -> SettingsPatchRequest {
+        serde_json::from_str("{}").expect("empty patch")
+    }
+
+    fn rejected_fields(result: Result<(), SettingsValidationError>) -> Vec<&'static str> {
+        result
+            .err()
+            .map(|err| err.errors.iter().map(|error| error.field).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn valid_command_passes() {
+        assert_eq!(
+            SettingsValidator::validate_command(&valid_command()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn sizes_must_be_within_bounds() {
+        let mut command = valid_command();
+        command.homepage_size = 0;
+        command.admin_page_size = MIN_ADMIN_PAGE_SIZE - 1;
+        command.tag_filter_limit = -3;
+        command.month_filter_limit = MAX_FILTER_LIMIT + 1;
+        command.maintenance_retry_after_secs = MAX_RETRY_AFTER_SECS + 1;
+        command.feed_item_limit = MAX_FEED_ITEM_LIMIT + 1;
+
+        assert_eq!(
+            rejected_fields(SettingsValidator::validate_command(&command)),
+            [
+                "homepage_size",
+                "admin_page_size",
+                "tag_filter_limit",
+                "month_filter_limit",
+                "maintenance_retry_after_secs",
+                "feed_item_limit",
+            ]
+        );
+
+        command = valid_command();
+        command.homepage_size = MAX_PAGE_SIZE;
+        command.admin_page_size = MAX_ADMIN_PAGE_SIZE;
+        command.maintenance_retry_after_secs = 1;
+        assert!(SettingsValidator::validate_command(&command).is_ok());
+    }
+
+    #[test]
+    fn site_url_must_be_absolute_http() {
+        for bad in [
+            "",
+            "example.com",
+            "/blog",
+            "ftp://example.com",
+            "javascript:alert(1)",
+            "https://example.com/?ref=feed",
+            "https://example.com/#top",
+        ] {
+            let mut command = valid_command();
+            command.public_site_url = bad.into();
+            assert_eq!(
+                rejected_fields(SettingsValidator::validate_command(&command)),
+                ["public_site_url"],
+                "{bad}"
+            );
+        }
+        let mut command = valid_command();
+        command.public_site_url = "http://localhost:3000".into();
+        assert!(SettingsValidator::validate_command(&command).is_ok());
+    }
+
+    #[test]
+    fn brand_href_accepts_paths_and_http_urls() {
+        for good in ["/", "/about", "https://example.com/home"] {
+            let mut command = valid_command();
+            command.brand_href = good.into();
+            assert!(
+                SettingsValidator::validate_command(&command).is_ok(),
+                "{good}"
+            );
+        }
+        for bad in ["", "about", "//evil.example", "javascript:alert(1)"] {
+            let mut command = valid_command();
+            command.brand_href = bad.into();
+            assert_eq!(
+                rejected_fields(SettingsValidator::validate_command(&command)),
+                ["brand_href"],
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn timezone_must_be_iana_name() {
+        let mut patch = empty_patch();
+        patch.timezone = Some("Mars/Olympus".into());
+        assert_eq!(
+            rejected_fields(SettingsValidator::validate_patch(&patch)),
+            ["timezone"]
+        );
+        patch.timezone = Some("America/New_York".into());
+        assert!(SettingsValidator::validate_patch(&patch).is_ok());
+    }
+
+    #[test]
+    fn timezone_names_resolve_to_canonical_spelling() {
+        for (value, canonical) in [
+            ("America/New_York", "America/New_York"),
+            (" america/new_york ", "America/New_York"),
+            ("EUROPE/BERLIN", "Europe/Berlin"),
+            ("utc", "UTC"),
+        ] {
+            assert_eq!(
+                parse_timezone(value).map(|zone| zone.name()),
+                Some(canonical),
+                "{value:?}"
+            );
+        }
+        assert_eq!(parse_timezone("Mars/Phobos"), None);
+        assert_eq!(parse_timezone(""), None);
+    }
+
+    #[test]
+    fn date_format_must_parse() {
+        let mut command = valid_command();
+        command.date_format = "%Q".into();
+        assert_eq!(
+            rejected_fields(SettingsValidator::validate_command(&command)),
+            ["date_format"]
+        );
+    }
+
+    #[test]
+    fn text_fields_are_required_and_length_limited() {
+        let mut command = valid_command();
+        command.brand_title = " ".into();
+        command.meta_title = "t".repeat(MAX_TITLE_CHARS + 1);
+        command.meta_description = "d".repeat(MAX_DESCRIPTION_CHARS + 1);
+        command.og_title = String::new();
+        command.og_description = "é".repeat(MAX_DESCRIPTION_CHARS + 1);
+        command.maintenance_html = String::new();
+
+        let err = SettingsValidator::validate_command(&command).expect_err("rejected");
+        assert_eq!(
+            err.errors
+                .iter()
+                .map(|error| error.field)
+                .collect::<Vec<_>>(),
+            [
+                "brand_title",
+                "meta_title",
+                "meta_description",
+                "og_title",
+                "og_description",
+                "maintenance_html",
+            ]
+        );
+        assert_eq!(
+            err.field("meta_title").map(|error| error.message.as_str()),
+            Some("must be at most 120 characters")
+        );
+
+        // Limits count characters, not bytes.
+        command = valid_command();
+        command.og_description = "é".repeat(MAX_DESCRIPTION_CHARS);
+        assert!(SettingsValidator::validate_command(&command).is_ok());
+    }
+
+    #[test]
+    fn favicon_must_be_well_formed_svg() {
+        for good in [
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"></svg>"#,
+            "<?xml version=\"1.0\"?>\n<!-- icon --><svg><g><path d='M0 0h1'/></g></svg>",
+            "<svg><style><![CDATA[rect > g { fill: red }]]></style></svg>",
+        ] {
+            assert_eq!(check_svg_document(good), Ok(()), "{good}");
+        }
+
+        for (bad, reason) in [
+            ("<svg><g></svg>", "has a mismatched end tag"),
+            ("<svg><g>", "has an unclosed element"),
+            ("<div></div>", "must have <svg> as its root element"),
+            (
+                "<svg></svg><svg></svg>",
+                "must have a single <svg> root element",
+            ),
+            ("hello <svg></svg>", "has text outside the <svg> element"),
+            ("<svg width=1></svg>", "has an unquoted attribute value"),
+            ("<svg width=\"1></svg>", "has an unterminated start tag"),
+            ("no markup", "has text outside the <svg> element"),
+        ] {
+            assert_eq!(check_svg_document(bad), Err(reason), "{bad}");
+        }
+    }
+
+    #[test]
+    fn favicon_cannot_carry_script() {
+        for bad in [
+            "<svg><script>alert(1)</script></svg>",
+            "<svg><SCRIPT/></svg>",
+            r#"<svg onload="alert(1)"></svg>"#,
+            r#"<svg><a href=" javascript:alert(1)"><rect/></a></svg>"#,
+        ] {
+            let mut patch = empty_patch();
+            patch.favicon_svg = Some(bad.into());
+            assert_eq!(
+                rejected_fields(SettingsValidator::validate_patch(&patch)),
+                ["favicon_svg"],
+                "{bad}"
+            );
+        }
+
+        let mut patch = empty_patch();
+        patch.favicon_svg = Some(format!("<svg>{}</svg>", " ".repeat(MAX_FAVICON_SVG_LENGTH)));
+        assert_eq!(
+            rejected_fields(SettingsValidator::validate_patch(&patch)),
+            ["favicon_svg"]
+        );
+    }
+
+    #[test]
+    fn patch_checks_only_provided_fields() {
+        assert!(SettingsValidator::validate_patch(&empty_patch()).is_ok());
+
+        let mut patch = empty_patch();
+        patch.brand_title = Some("Renamed".into());
+        assert!(SettingsValidator::validate_patch(&patch).is_ok());
+
+        patch.homepage_size = Some(0);
+        patch.public_site_url = Some("not a url".into());
+        assert_eq!(
+            rejected_fields(SettingsValidator::validate_patch(&patch)),
+            ["homepage_size", "public_site_url"]
+        );
+    }
+}
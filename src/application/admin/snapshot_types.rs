@@ -8,6 +8,7 @@ use crate::domain::{
     snapshots::{SnapshotError, Snapshotable},
     types::SnapshotEntityType,
 };
+use crate::presentation::page_templates::DEFAULT_PAGE_TEMPLATE;
 
 #[derive(Debug, Clone)]
 pub struct PostSnapshotSource {
@@ -36,6 +37,8 @@ pub struct PostSnapshotPayload {
     pub slug: String,
     pub title: String,
     pub excerpt: String,
+    #[serde(default)]
+    pub excerpt_generated: bool,
     pub body_markdown: String,
     pub summary_markdown: Option<String>,
     pub summary_html: Option<String>,
@@ -96,6 +99,7 @@ impl Snapshotable for PostSnapshotSource {
             slug: self.post.slug.clone(),
             title: self.post.title.clone(),
             excerpt: self.post.excerpt.clone(),
+            excerpt_generated: self.post.excerpt_generated,
             body_markdown: self.post.body_markdown.clone(),
             summary_markdown: self.post.summary_markdown.clone(),
             summary_html: self.post.summary_html.clone(),
@@ -135,6 +139,12 @@ pub struct PageSnapshotPayload {
     pub scheduled_at: Option<OffsetDateTime>,
     pub published_at: Option<OffsetDateTime>,
     pub archived_at: Option<OffsetDateTime>,
+    #[serde(default = "default_page_template")]
+    pub template: String,
+}
+
+fn default_page_template() -> String {
+    DEFAULT_PAGE_TEMPLATE.to_string()
 }
 
 #[derive(Debug, Clone)]
@@ -174,6 +184,7 @@ impl Snapshotable for PageSnapshotSource {
             scheduled_at: self.page.scheduled_at,
             published_at: self.page.published_at,
             archived_at: self.page.archived_at,
+            template: self.page.template.clone(),
         })
     }
 
@@ -1,13 +1,17 @@
+use std::collections::{HashMap, HashSet};
+
 use serde::Serialize;
 use uuid::Uuid;
 
-use crate::application::repos::{CreateTagParams, RepoError, UpdateTagParams};
+use crate::application::admin::audit::AuditChanges;
+use crate::application::repos::{CreateTagParams, RepoError, TagAssignmentMode, UpdateTagParams};
 use crate::domain::entities::TagRecord;
 use crate::domain::slug::{SlugAsyncError, SlugError, generate_unique_slug_async};
 
 use super::service::AdminTagService;
 use super::types::{
-    AdminTagError, CreateTagCommand, UpdateTagCommand, ensure_non_empty, normalize_optional_text,
+    AdminTagError, CreateTagCommand, TagAssignment, TagAssignmentResult, TagAssignmentStatus,
+    UpdateTagCommand, ensure_non_empty, normalize_optional_text,
 };
 
 impl AdminTagService {
@@ -104,7 +108,7 @@ impl AdminTagService {
             .reader
             .find_by_id(id)
             .await?
-            .ok_or_else(|| RepoError::from_persistence("tag not found"))?;
+            .ok_or(RepoError::NotFound)?;
 
         let params = UpdateTagParams {
             id,
@@ -119,13 +123,15 @@ impl AdminTagService {
             slug: tag.slug.as_str(),
             name: tag.name.as_str(),
         };
+        let changes = AuditChanges::between(&existing, &tag);
         self.audit
-            .record(
+            .record_with_changes(
                 actor,
                 "tag.update",
                 "tag",
                 Some(&tag.id.to_string()),
                 Some(&snapshot),
+                &changes,
             )
             .await?;
 
@@ -146,7 +152,7 @@ impl AdminTagService {
             .reader
             .find_by_id(id)
             .await?
-            .ok_or_else(|| AdminTagError::Repo(RepoError::from_persistence("tag not found")))?;
+            .ok_or(AdminTagError::Repo(RepoError::NotFound))?;
 
         let command = UpdateTagCommand {
             id,
@@ -181,6 +187,93 @@ impl AdminTagService {
 
         Ok(())
     }
+
+    /// Add the tag to, or remove it from, every listed post at once.
+    ///
+    /// Posts that do not exist or sit in the trash are reported as not found
+    /// rather than failing the whole request.
+    pub async fn assign_tag(
+        &self,
+        actor: &str,
+        id: Uuid,
+        post_ids: &[Uuid],
+        mode: TagAssignmentMode,
+    ) -> Result<TagAssignment, AdminTagError> {
+        if post_ids.is_empty() {
+            return Err(AdminTagError::ConstraintViolation("post_ids"));
+        }
+
+        let tag = self
+            .reader
+            .find_by_id(id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+
+        let mut seen = HashSet::new();
+        let requested: Vec<Uuid> = post_ids
+            .iter()
+            .copied()
+            .filter(|post_id| seen.insert(*post_id))
+            .collect();
+
+        let changes = self.writer.assign_tag(tag.id, &requested, mode).await?;
+        let slugs: HashMap<Uuid, &str> = changes
+            .posts
+            .iter()
+            .map(|(post_id, slug)| (*post_id, slug.as_str()))
+            .collect();
+        let changed: HashSet<Uuid> = changes.changed.iter().copied().collect();
+
+        let results = requested
+            .iter()
+            .map(|&post_id| {
+                let status = if !slugs.contains_key(&post_id) {
+                    TagAssignmentStatus::NotFound
+                } else if !changed.contains(&post_id) {
+                    TagAssignmentStatus::Unchanged
+                } else {
+                    match mode {
+                        TagAssignmentMode::Add => TagAssignmentStatus::Added,
+                        TagAssignmentMode::Remove => TagAssignmentStatus::Removed,
+                    }
+                };
+                TagAssignmentResult { post_id, status }
+            })
+            .collect();
+
+        if !changes.changed.is_empty() {
+            let snapshot = TagAssignmentSnapshot {
+                slug: tag.slug.as_str(),
+                mode,
+                posts: &changes.changed,
+            };
+            self.audit
+                .record(
+                    actor,
+                    "tag.assign",
+                    "tag",
+                    Some(&tag.id.to_string()),
+                    Some(&snapshot),
+                )
+                .await?;
+
+            // Refresh the affected posts' pages and every tag-derived listing.
+            if let Some(trigger) = &self.cache_trigger {
+                for post_id in &changes.changed {
+                    if let Some(slug) = slugs.get(post_id) {
+                        trigger.post_upserted(*post_id, slug).await;
+                    }
+                }
+                trigger.tags_changed().await;
+            }
+        }
+
+        Ok(TagAssignment {
+            tag_id: tag.id,
+            mode,
+            results,
+        })
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -188,3 +281,10 @@ struct TagSnapshot<'a> {
     slug: &'a str,
     name: &'a str,
 }
+
+#[derive(Debug, Serialize)]
+struct TagAssignmentSnapshot<'a> {
+    slug: &'a str,
+    mode: TagAssignmentMode,
+    posts: &'a [Uuid],
+}
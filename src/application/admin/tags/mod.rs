@@ -7,4 +7,7 @@ mod types;
 mod tests;
 
 pub use service::AdminTagService;
-pub use types::{AdminTagError, AdminTagStatusCounts, CreateTagCommand, UpdateTagCommand};
+pub use types::{
+    AdminTagError, AdminTagStatusCounts, CreateTagCommand, TagAssignment, TagAssignmentResult,
+    TagAssignmentStatus, UpdateTagCommand,
+};
@@ -1,5 +1,6 @@
 use uuid::Uuid;
 
+use crate::application::feed;
 use crate::application::pagination::{CursorPage, PageRequest, TagCursor};
 use crate::application::repos::{TagListRecord, TagQueryFilter, TagWithCount};
 use crate::domain::entities::TagRecord;
@@ -20,6 +21,16 @@ impl AdminTagService {
             .map_err(AdminTagError::from)
     }
 
+    /// Published-post counts for the tags the public filter shows: pinned
+    /// tags first, then the `limit` most used.
+    pub async fn aggregations(&self, limit: i32) -> Result<Vec<TagWithCount>, AdminTagError> {
+        let counts = self.list_with_counts().await?;
+        Ok(feed::visible_tags(&counts, limit)
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
     pub async fn list_for_post(&self, post_id: Uuid) -> Result<Vec<TagRecord>, AdminTagError> {
         self.reader
             .list_for_post(post_id)
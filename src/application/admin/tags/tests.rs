@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
@@ -7,8 +8,9 @@ use uuid::Uuid;
 use crate::application::admin::audit::AdminAuditService;
 use crate::application::pagination::{AuditCursor, CursorPage, PageRequest, TagCursor};
 use crate::application::repos::{
-    AuditQueryFilter, AuditRepo, CreateTagParams, RepoError, TagListRecord, TagQueryFilter,
-    TagWithCount, TagsRepo, TagsWriteRepo, UpdateTagParams,
+    AuditQueryFilter, AuditRepo, CreateTagParams, RepoError, TagAssignmentChanges,
+    TagAssignmentMode, TagListRecord, TagQueryFilter, TagWithCount, TagsRepo, TagsWriteRepo,
+    UpdateTagParams,
 };
 use crate::domain::entities::{AuditLogRecord, TagRecord};
 use crate::domain::posts::MonthCount;
@@ -31,6 +33,13 @@ impl TagsRepo for StubTagsRepo {
         Ok(Vec::new())
     }
 
+    async fn tags_for_posts(
+        &self,
+        _post_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<TagRecord>>, RepoError> {
+        Ok(HashMap::new())
+    }
+
     async fn list_with_counts(&self) -> Result<Vec<TagWithCount>, RepoError> {
         Ok(Vec::new())
     }
@@ -94,6 +103,15 @@ impl TagsWriteRepo for RecordingTagsWriter {
         self.deleted.lock().expect("record deleted ids").push(id);
         Ok(())
     }
+
+    async fn assign_tag(
+        &self,
+        _tag_id: Uuid,
+        _post_ids: &[Uuid],
+        _mode: TagAssignmentMode,
+    ) -> Result<TagAssignmentChanges, RepoError> {
+        unreachable!("not used in these tests")
+    }
 }
 
 fn sample_tag(id: Uuid) -> TagRecord {
@@ -2,6 +2,8 @@ use serde::Serialize;
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::application::repos::TagAssignmentMode;
+
 #[derive(Debug, Error)]
 pub enum AdminTagError {
     #[error("{0}")]
@@ -27,6 +29,31 @@ pub struct UpdateTagCommand {
     pub pinned: bool,
 }
 
+/// Outcome of a bulk tag assignment for one requested post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagAssignmentStatus {
+    Added,
+    Removed,
+    /// The post already had (or already lacked) the tag.
+    Unchanged,
+    /// No such post, or it is in the trash.
+    NotFound,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagAssignmentResult {
+    pub post_id: Uuid,
+    pub status: TagAssignmentStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagAssignment {
+    pub tag_id: Uuid,
+    pub mode: TagAssignmentMode,
+    pub results: Vec<TagAssignmentResult>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct AdminTagStatusCounts {
     pub total: u64,
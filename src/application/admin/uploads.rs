@@ -1,15 +1,23 @@
+use std::collections::{BTreeSet, HashSet};
 use std::sync::Arc;
 
+use futures::{StreamExt, TryStreamExt, stream};
 use serde::Serialize;
 use thiserror::Error;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::application::admin::audit::AdminAuditService;
-use crate::application::pagination::{CursorPage, PageRequest, UploadCursor};
+use crate::application::pagination::{CursorKeys, CursorPage, PageRequest, UploadCursor};
 use crate::application::repos::{
-    RepoError, UploadContentTypeCount, UploadMonthCount, UploadQueryFilter, UploadsRepo,
+    RepoError, UploadContentTypeCount, UploadIntegritySummary, UploadMonthCount, UploadQueryFilter,
+    UploadsRepo,
 };
 use crate::domain::entities::UploadRecord;
+use crate::infra::uploads::{UploadStorage, UploadStorageError};
+
+/// Uploads fetched per page while verifying every upload.
+const VERIFY_PAGE_SIZE: u32 = 200;
 
 #[derive(Debug, Error)]
 pub enum AdminUploadError {
@@ -17,17 +25,70 @@ pub enum AdminUploadError {
     NotFound,
     #[error(transparent)]
     Repo(#[from] RepoError),
+    /// The stored file could not be read.
+    #[error(transparent)]
+    Storage(#[from] UploadStorageError),
+}
+
+/// Outcome of re-hashing one stored upload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UploadVerification {
+    pub id: Uuid,
+    pub expected_checksum: String,
+    /// `None` when the stored file is missing.
+    pub actual_checksum: Option<String>,
+    pub matches: bool,
+    pub verified_at: OffsetDateTime,
+}
+
+/// Counts from verifying every upload, as written to the audit log.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UploadIntegrityReport {
+    pub checked: u64,
+    pub matched: u64,
+    /// Files whose contents no longer match, including missing ones.
+    pub mismatched: u64,
+    /// Subset of `mismatched` whose file is gone.
+    pub missing: u64,
+    /// Sorted ids of every mismatched upload.
+    pub mismatched_ids: Vec<Uuid>,
+}
+
+impl UploadIntegrityReport {
+    fn add(&mut self, verification: &UploadVerification) {
+        self.checked += 1;
+        if verification.matches {
+            self.matched += 1;
+            return;
+        }
+        self.mismatched += 1;
+        if verification.actual_checksum.is_none() {
+            self.missing += 1;
+        }
+        self.mismatched_ids.push(verification.id);
+    }
 }
 
 #[derive(Clone)]
 pub struct AdminUploadService {
     repo: Arc<dyn UploadsRepo>,
     audit: AdminAuditService,
+    cursor_keys: CursorKeys,
 }
 
 impl AdminUploadService {
     pub fn new(repo: Arc<dyn UploadsRepo>, audit: AdminAuditService) -> Self {
-        Self { repo, audit }
+        Self {
+            repo,
+            audit,
+            cursor_keys: CursorKeys::ephemeral(),
+        }
+    }
+
+    /// Read back cursors with the keys the repositories sign them with.
+    pub fn with_cursor_keys(mut self, cursor_keys: CursorKeys) -> Self {
+        self.cursor_keys = cursor_keys;
+        self
     }
 
     pub async fn register_upload(
@@ -49,6 +110,31 @@ impl AdminUploadService {
         Ok(())
     }
 
+    /// Names for a batch of new uploads, in order. A name already used by an
+    /// upload or by an earlier file of the batch gets `-1`, `-2`, … added
+    /// before its extension.
+    pub async fn unique_filenames(
+        &self,
+        names: &[String],
+    ) -> Result<Vec<String>, AdminUploadError> {
+        let stems: BTreeSet<String> = names
+            .iter()
+            .map(|name| split_extension(name).0.to_string())
+            .collect();
+        let stems: Vec<String> = stems.into_iter().collect();
+        let mut taken: HashSet<String> = self
+            .repo
+            .filenames_with_prefixes(&stems)
+            .await?
+            .into_iter()
+            .collect();
+
+        Ok(names
+            .iter()
+            .map(|name| claim_filename(name, &mut taken))
+            .collect())
+    }
+
     pub async fn find_upload(&self, id: Uuid) -> Result<Option<UploadRecord>, AdminUploadError> {
         self.repo
             .find_upload(id)
@@ -120,6 +206,112 @@ impl AdminUploadService {
 
         Ok(record)
     }
+
+    /// Re-hash the stored file of upload `id` and record whether it still
+    /// matches its checksum.
+    pub async fn verify_upload(
+        &self,
+        actor: &str,
+        id: Uuid,
+        storage: &UploadStorage,
+    ) -> Result<UploadVerification, AdminUploadError> {
+        let record = self
+            .repo
+            .find_upload(id)
+            .await?
+            .ok_or(AdminUploadError::NotFound)?;
+
+        let verification = self.check_stored_file(&record, storage).await?;
+        self.audit
+            .record(
+                actor,
+                "upload.verify",
+                "upload",
+                Some(&record.id.to_string()),
+                Some(&verification),
+            )
+            .await?;
+
+        Ok(verification)
+    }
+
+    /// Re-hash every stored upload, `concurrency` files at a time, and
+    /// write the resulting report to the audit log.
+    pub async fn verify_all(
+        &self,
+        actor: &str,
+        storage: &UploadStorage,
+        concurrency: usize,
+    ) -> Result<UploadIntegrityReport, AdminUploadError> {
+        let filter = UploadQueryFilter::default();
+        let mut report = UploadIntegrityReport::default();
+        let mut cursor = None;
+
+        loop {
+            let page = self
+                .repo
+                .list_uploads(&filter, PageRequest::new(VERIFY_PAGE_SIZE, cursor))
+                .await?;
+
+            let verifications: Vec<UploadVerification> = stream::iter(&page.items)
+                .map(|record| self.check_stored_file(record, storage))
+                .buffer_unordered(concurrency.max(1))
+                .try_collect()
+                .await?;
+            for verification in &verifications {
+                report.add(verification);
+            }
+
+            match page.next_cursor {
+                Some(token) => {
+                    cursor = Some(
+                        UploadCursor::decode(&self.cursor_keys, &token).map_err(RepoError::from)?,
+                    );
+                }
+                None => break,
+            }
+        }
+
+        report.mismatched_ids.sort_unstable();
+        self.audit
+            .record(actor, "upload.verify_all", "upload", None, Some(&report))
+            .await?;
+
+        Ok(report)
+    }
+
+    pub async fn integrity_summary(&self) -> Result<UploadIntegritySummary, AdminUploadError> {
+        self.repo
+            .integrity_summary()
+            .await
+            .map_err(AdminUploadError::from)
+    }
+
+    async fn check_stored_file(
+        &self,
+        record: &UploadRecord,
+        storage: &UploadStorage,
+    ) -> Result<UploadVerification, AdminUploadError> {
+        let actual_checksum = match storage.checksum(&record.stored_path).await {
+            Ok(checksum) => Some(checksum),
+            Err(UploadStorageError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        let matches = actual_checksum.as_deref() == Some(record.checksum.as_str());
+        let verified_at = OffsetDateTime::now_utc();
+
+        self.repo
+            .record_verification(record.id, verified_at, matches)
+            .await?;
+
+        Ok(UploadVerification {
+            id: record.id,
+            expected_checksum: record.checksum.clone(),
+            actual_checksum,
+            matches,
+            verified_at,
+        })
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -140,3 +332,28 @@ impl<'a> From<&'a UploadRecord> for UploadSnapshot<'a> {
         }
     }
 }
+
+/// `name` split before its last `.`; a leading dot is part of the stem.
+fn split_extension(name: &str) -> (&str, Option<&str>) {
+    match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (name, None),
+    }
+}
+
+/// First of `name`, `stem-1.ext`, `stem-2.ext`, … not in `taken`, which
+/// then holds it.
+fn claim_filename(name: &str, taken: &mut HashSet<String>) -> String {
+    let (stem, extension) = split_extension(name);
+    let mut candidate = name.to_string();
+    let mut suffix = 0u32;
+    while taken.contains(&candidate) {
+        suffix += 1;
+        candidate = match extension {
+            Some(extension) => format!("{stem}-{suffix}.{extension}"),
+            None => format!("{stem}-{suffix}"),
+        };
+    }
+    taken.insert(candidate.clone());
+    candidate
+}
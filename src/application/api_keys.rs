@@ -7,14 +7,16 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::application::repos::{
-    ApiKeyPageRequest, ApiKeyQueryFilter, ApiKeysRepo, CreateApiKeyParams, RepoError,
-    UpdateApiKeyMetadataParams, UpdateApiKeySecretParams,
+    ApiKeyPageRequest, ApiKeyQueryFilter, ApiKeyStatusFilter, ApiKeysRepo, CreateApiKeyParams,
+    RepoError, UpdateApiKeyMetadataParams, UpdateApiKeySecretParams,
 };
 use crate::cache::CacheTrigger;
 use crate::domain::api_keys::{ApiKeyRecord, ApiKeyStatus, ApiScope};
 
 const TOKEN_PREFIX: &str = "sk";
 const MIN_SECRET_LEN: usize = 32;
+/// Keys fetched per page when collecting every active key.
+const BULK_PAGE_SIZE: u32 = 200;
 
 #[derive(Debug, Error)]
 pub enum ApiKeyError {
@@ -61,6 +63,15 @@ pub struct ApiKeyIssued {
     pub token: String,
 }
 
+/// Outcome of [`ApiKeyService::rotate_all`].
+#[derive(Debug, Default)]
+pub struct ApiKeyBulkRotation {
+    /// Rotated keys with their new tokens, which are not stored anywhere else.
+    pub rotated: Vec<ApiKeyIssued>,
+    /// Keys that still carry their old secret, with the reason.
+    pub failed: Vec<(ApiKeyRecord, ApiKeyError)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ApiPrincipal {
     pub key_id: Uuid,
@@ -175,6 +186,47 @@ impl ApiKeyService {
         Ok(ApiKeyIssued { record, token })
     }
 
+    /// Rotate every active key, one at a time.
+    ///
+    /// Each rotation commits on its own, so a key that fails does not undo the
+    /// ones before it or stop the ones after it; the outcome lists both sides.
+    /// Only listing the keys aborts the whole run, before anything changed.
+    pub async fn rotate_all(&self) -> Result<ApiKeyBulkRotation, ApiKeyError> {
+        let filter = ApiKeyQueryFilter {
+            status: Some(ApiKeyStatusFilter::Active),
+            ..ApiKeyQueryFilter::default()
+        };
+        let mut keys = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self
+                .repo
+                .list_keys(
+                    &filter,
+                    ApiKeyPageRequest {
+                        limit: BULK_PAGE_SIZE,
+                        cursor,
+                    },
+                )
+                .await?;
+            keys.extend(page.items);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        let now = OffsetDateTime::now_utc();
+        let mut outcome = ApiKeyBulkRotation::default();
+        for key in keys.into_iter().filter(|key| key.is_active_at(now)) {
+            match self.rotate(key.id).await {
+                Ok(issued) => outcome.rotated.push(issued),
+                Err(err) => outcome.failed.push((key, err)),
+            }
+        }
+        Ok(outcome)
+    }
+
     pub async fn update(&self, cmd: UpdateApiKeyCommand) -> Result<ApiKeyRecord, ApiKeyError> {
         if cmd.scopes.is_empty() {
             return Err(ApiKeyError::InvalidScopes);
@@ -1,86 +1,198 @@
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, Instant};
 
 use axum::http::StatusCode;
+use tracing::warn;
+use uuid::Uuid;
 
 use crate::application::error::HttpError;
-use crate::application::pagination::{NavigationCursor, PageRequest};
-use crate::application::repos::{NavigationQueryFilter, NavigationRepo, RepoError, SettingsRepo};
+use crate::application::pagination::{CursorKeys, NavigationCursor, PageRequest};
+use crate::application::repos::{
+    NavigationQueryFilter, NavigationRepo, PagesRepo, RepoError, SettingsRepo,
+};
 use crate::cache::L0Store;
-use crate::domain::entities::NavigationItemRecord;
-use crate::domain::types::NavigationDestinationType;
+use crate::domain::entities::{NavigationItemRecord, PageRecord, SiteSettingsRecord};
+use crate::domain::routes::BasePath;
+use crate::domain::types::{HomepageMode, NavigationDestinationType, PageStatus};
 use crate::presentation::views::{
     BrandView, FooterView, LayoutChrome, NavigationLinkView, NavigationView, PageMetaView,
 };
 
 const SOURCE: &str = "application::chrome::ChromeService";
+/// Document language for content that does not declare its own.
+const DEFAULT_DOCUMENT_LANG: &str = "en";
+/// Longest a built chrome is served without re-reading its sources, in case
+/// a write reached the database without going through the cache trigger.
+pub(crate) const CHROME_TTL: Duration = Duration::from_secs(60);
+
+/// A chrome built from settings and navigation, kept until the L0 chrome
+/// version moves on or [`CHROME_TTL`] passes.
+pub(crate) struct ChromeCache<T> {
+    slot: RwLock<Option<ChromeCacheEntry<T>>>,
+}
+
+struct ChromeCacheEntry<T> {
+    version: u64,
+    built_at: Instant,
+    value: T,
+}
+
+impl<T: Clone> ChromeCache<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            slot: RwLock::new(None),
+        }
+    }
+
+    /// The cached value if it is still current, otherwise the result of
+    /// `build`, which is kept for the next caller.
+    pub(crate) async fn get_or_build<F, Fut, E>(&self, l0: &L0Store, build: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let expired = {
+            let slot = self.slot.read().unwrap_or_else(PoisonError::into_inner);
+            match slot.as_ref() {
+                Some(entry) if entry.built_at.elapsed() >= CHROME_TTL => true,
+                Some(entry) if entry.version == l0.chrome_version() => {
+                    return Ok(entry.value.clone());
+                }
+                _ => false,
+            }
+        };
+        if expired {
+            // The L0 copies are as old as the chrome; reload them too.
+            l0.invalidate_site_settings();
+            l0.invalidate_navigation();
+        }
+
+        // Read the version first so a write landing mid-build leaves the
+        // entry stale rather than caching old data under the new version.
+        let version = l0.chrome_version();
+        let value = build().await?;
+        *self.slot.write().unwrap_or_else(PoisonError::into_inner) = Some(ChromeCacheEntry {
+            version,
+            built_at: Instant::now(),
+            value: value.clone(),
+        });
+        Ok(value)
+    }
+}
+
+#[derive(Clone)]
+struct BuiltChrome {
+    chrome: LayoutChrome,
+    /// Configured homepage page, as a dependency of responses using the chrome.
+    homepage_page_id: Option<Uuid>,
+}
 
 #[derive(Clone)]
 pub struct ChromeService {
     navigation: Arc<dyn NavigationRepo>,
     settings: Arc<dyn SettingsRepo>,
+    pages: Arc<dyn PagesRepo>,
     cache: Option<Arc<L0Store>>,
+    built: Arc<ChromeCache<BuiltChrome>>,
+    base_path: BasePath,
+    cursor_keys: CursorKeys,
 }
 
 impl ChromeService {
     pub fn new(
         navigation: Arc<dyn NavigationRepo>,
         settings: Arc<dyn SettingsRepo>,
+        pages: Arc<dyn PagesRepo>,
         cache: Option<Arc<L0Store>>,
     ) -> Self {
         Self {
             navigation,
             settings,
+            pages,
             cache,
+            built: Arc::new(ChromeCache::new()),
+            base_path: BasePath::default(),
+            cursor_keys: CursorKeys::ephemeral(),
         }
     }
 
+    /// Prefix internal links and the canonical root with `base_path`.
+    pub fn with_base_path(mut self, base_path: BasePath) -> Self {
+        self.base_path = base_path;
+        self
+    }
+
+    /// Read back cursors with the keys the repositories sign them with.
+    pub fn with_cursor_keys(mut self, cursor_keys: CursorKeys) -> Self {
+        self.cursor_keys = cursor_keys;
+        self
+    }
+
     pub async fn load(&self) -> Result<LayoutChrome, HttpError> {
         // Record dependencies for L1 cache invalidation
         crate::cache::deps::record(crate::cache::EntityKey::SiteSettings);
         crate::cache::deps::record(crate::cache::EntityKey::Navigation);
 
-        let settings = if let Some(cache) = &self.cache {
-            if let Some(cached) = cache.get_site_settings() {
-                cached
-            } else {
-                let settings = self
-                    .settings
-                    .load_site_settings()
-                    .await
-                    .map_err(|err| repo_failure("load_site_settings", err))?;
-                cache.set_site_settings(settings.clone());
-                settings
-            }
-        } else {
-            self.settings
-                .load_site_settings()
-                .await
-                .map_err(|err| repo_failure("load_site_settings", err))?
+        let Some(cache) = &self.cache else {
+            return self.build().await.map(|built| built.chrome);
         };
+        let built = self.built.get_or_build(cache, || self.build()).await?;
+        if let Some(id) = built.homepage_page_id {
+            crate::cache::deps::record(crate::cache::EntityKey::Page(id));
+        }
+        Ok(built.chrome)
+    }
+
+    async fn build(&self) -> Result<BuiltChrome, HttpError> {
+        let settings = self.site_settings().await?;
+        let homepage = self.homepage_page(&settings).await?;
 
         let navigation_items = if let Some(cache) = &self.cache {
             if let Some(cached) = cache.get_navigation() {
                 cached
             } else {
-                let items = load_navigation_items(self.navigation.as_ref()).await?;
+                let items =
+                    load_navigation_items(self.navigation.as_ref(), &self.cursor_keys).await?;
                 cache.set_navigation(items.clone());
                 items
             }
         } else {
-            load_navigation_items(self.navigation.as_ref()).await?
+            load_navigation_items(self.navigation.as_ref(), &self.cursor_keys).await?
         };
 
         let mut entries = Vec::new();
         for item in navigation_items.into_iter().filter(|item| item.visible) {
-            entries.push(map_navigation_item(&item)?);
+            let mut entry = map_navigation_item(&item, &self.base_path)?;
+            if homepage
+                .as_ref()
+                .is_some_and(|page| item.destination_page_id == Some(page.id))
+            {
+                entry.href = self.base_path.join("/");
+            }
+            entries.push(entry);
         }
 
-        let public_site_url = normalize_public_site_url(&settings.public_site_url);
+        // The homepage page is served at `/`, so a brand link naming its own
+        // path goes there instead.
+        let brand_href = match &homepage {
+            Some(page) if settings.brand_href.trim_matches('/') == page.slug => "/",
+            _ => settings.brand_href.as_str(),
+        };
+
+        let canonical_root = self
+            .base_path
+            .site_url_or_relative(&settings.public_site_url);
 
+        // Recorded on cache hits too, so publishing or editing the page
+        // still purges the responses that carry this chrome.
+        let homepage_page_id = settings
+            .homepage_page_id
+            .filter(|_| settings.homepage_mode == HomepageMode::Page);
         let chrome = LayoutChrome {
             brand: BrandView {
                 title: settings.brand_title.clone(),
-                href: settings.brand_href.clone(),
+                href: root_relative_href(&self.base_path, brand_href),
             },
             navigation: NavigationView { entries },
             footer: FooterView {
@@ -91,11 +203,91 @@ impl ChromeService {
                 description: settings.meta_description.clone(),
                 og_title: settings.og_title.clone(),
                 og_description: settings.og_description.clone(),
-                canonical: public_site_url.clone(),
+                canonical: canonical_root,
+                lang: DEFAULT_DOCUMENT_LANG.to_string(),
+                alternates: Vec::new(),
             },
+            base_path: self.base_path.as_str().to_string(),
+            raster_favicon: settings.favicon_upload_id.is_some(),
+            homepage_slug: homepage.map(|page| page.slug),
+        };
+
+        Ok(BuiltChrome {
+            chrome,
+            homepage_page_id,
+        })
+    }
+
+    /// The page served at `/` in page mode.
+    ///
+    /// `None` in feed mode, and in page mode while the configured page is
+    /// unset, unpublished or deleted; `/` then serves the feed.
+    async fn homepage_page(
+        &self,
+        settings: &SiteSettingsRecord,
+    ) -> Result<Option<PageRecord>, HttpError> {
+        if settings.homepage_mode != HomepageMode::Page {
+            return Ok(None);
+        }
+        let Some(id) = settings.homepage_page_id else {
+            warn!(
+                target = SOURCE,
+                "homepage mode is `page` without a page, serving the feed at /"
+            );
+            return Ok(None);
+        };
+        crate::cache::deps::record(crate::cache::EntityKey::Page(id));
+
+        let cached = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get_page_by_id(id));
+        let page = match cached {
+            Some(page) => Some(page),
+            None => {
+                let fetched = self
+                    .pages
+                    .find_by_id(id)
+                    .await
+                    .map_err(|err| repo_failure("find_by_id", err))?;
+                if let (Some(cache), Some(page)) = (&self.cache, &fetched) {
+                    cache.set_page(page.clone());
+                }
+                fetched
+            }
         };
 
-        Ok(chrome)
+        let page =
+            page.filter(|page| page.status == PageStatus::Published && page.published_at.is_some());
+        if page.is_none() {
+            warn!(
+                target = SOURCE,
+                page_id = %id,
+                "homepage page is missing or unpublished, serving the feed at /"
+            );
+        }
+        Ok(page)
+    }
+
+    /// Current site settings, served from the L0 cache when one is wired.
+    pub async fn site_settings(&self) -> Result<SiteSettingsRecord, HttpError> {
+        let Some(cache) = &self.cache else {
+            return self
+                .settings
+                .load_site_settings()
+                .await
+                .map_err(|err| repo_failure("load_site_settings", err));
+        };
+        if let Some(cached) = cache.get_site_settings() {
+            return Ok(cached);
+        }
+        let settings = self
+            .settings
+            .load_site_settings()
+            .await
+            .map_err(|err| repo_failure("load_site_settings", err))?;
+        cache.set_site_settings(settings.clone());
+        Ok(settings)
     }
 }
 
@@ -110,6 +302,7 @@ fn repo_failure(operation: &'static str, err: RepoError) -> HttpError {
 
 async fn load_navigation_items(
     repo: &dyn NavigationRepo,
+    cursor_keys: &CursorKeys,
 ) -> Result<Vec<NavigationItemRecord>, HttpError> {
     let mut cursor = None;
     let filter = NavigationQueryFilter::default();
@@ -124,7 +317,7 @@ async fn load_navigation_items(
 
         cursor = match page.next_cursor {
             Some(token) => {
-                let decoded = NavigationCursor::decode(&token)
+                let decoded = NavigationCursor::decode(cursor_keys, &token)
                     .map_err(|err| repo_failure("decode_navigation_cursor", err.into()))?;
                 Some(decoded)
             }
@@ -135,7 +328,10 @@ async fn load_navigation_items(
     Ok(navigation_items)
 }
 
-fn map_navigation_item(item: &NavigationItemRecord) -> Result<NavigationLinkView, HttpError> {
+fn map_navigation_item(
+    item: &NavigationItemRecord,
+    base_path: &BasePath,
+) -> Result<NavigationLinkView, HttpError> {
     match item.destination_type {
         NavigationDestinationType::Internal => {
             let slug = item.destination_page_slug.as_deref().ok_or_else(|| {
@@ -147,14 +343,9 @@ fn map_navigation_item(item: &NavigationItemRecord) -> Result<NavigationLinkView
                 )
             })?;
 
-            let mut href = String::from("/");
-            if !slug.is_empty() {
-                href.push_str(slug);
-            }
-
             Ok(NavigationLinkView {
                 label: item.label.clone(),
-                href,
+                href: base_path.join(slug),
                 target: None,
                 rel: None,
             })
@@ -184,7 +375,12 @@ fn map_navigation_item(item: &NavigationItemRecord) -> Result<NavigationLinkView
     }
 }
 
-fn normalize_public_site_url(url: &str) -> String {
-    let trimmed = url.trim_end_matches('/');
-    format!("{trimmed}/")
+/// Root-relative hrefs (`/about`) move under the base path; absolute and
+/// protocol-relative URLs are left alone.
+fn root_relative_href(base_path: &BasePath, href: &str) -> String {
+    if href.starts_with('/') && !href.starts_with("//") {
+        base_path.join(href)
+    } else {
+        href.to_string()
+    }
 }
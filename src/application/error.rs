@@ -6,13 +6,19 @@ use axum::{
 };
 use thiserror::Error;
 
-use crate::{application::feed::FeedError, domain::error::DomainError, infra::error::InfraError};
+use crate::{
+    application::{feed::FeedError, request_id},
+    domain::error::DomainError,
+    infra::error::InfraError,
+};
 
 #[derive(Debug, Clone)]
 pub struct ErrorReport {
     pub source: &'static str,
     pub status: StatusCode,
     pub messages: Vec<String>,
+    /// Correlation id of the request that failed, when served over HTTP.
+    pub request_id: Option<String>,
 }
 
 impl ErrorReport {
@@ -28,6 +34,7 @@ impl ErrorReport {
             source,
             status,
             messages,
+            request_id: request_id::current(),
         }
     }
 
@@ -40,6 +47,7 @@ impl ErrorReport {
             source,
             status,
             messages: vec![message.into()],
+            request_id: request_id::current(),
         }
     }
 
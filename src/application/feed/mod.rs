@@ -3,27 +3,32 @@ use std::sync::Arc;
 use askama::Template;
 use axum::response::Response;
 use datastar::prelude::ElementPatchMode;
-use serde_json::json;
 use uuid::Uuid;
 
 use crate::application::error::HttpError;
-use crate::application::pagination::{PageRequest, PostCursor};
+use crate::application::format;
+use crate::application::metadata::structured_data::{
+    Blog, BlogPosting, BreadcrumbList, StructuredData, WebSite,
+};
+use crate::application::pagination::{CursorKeys, PageRequest, PostCursor};
 use crate::application::repos::{
-    PostListScope, PostQueryFilter, PostsRepo, SectionsRepo, SettingsRepo, TagWithCount, TagsRepo,
+    CommentsRepo, PostListScope, PostQueryFilter, PostsRepo, SectionsRepo, SettingsRepo,
+    TagWithCount, TagsRepo,
 };
 use crate::application::stream::StreamBuilder;
 use crate::cache::{L0Store, hash_cursor_str, hash_post_list_key};
 use crate::domain::entities::{PostRecord, SiteSettingsRecord, TagRecord};
 use crate::domain::posts;
+use crate::domain::routes::{BasePath, join_url, post_path};
 use crate::domain::sections::PostSectionNode;
 use crate::domain::sections::build_section_tree;
-use crate::domain::types::PostStatus;
+use crate::domain::translations::normalize_lang;
+use crate::domain::types::{HomepageLayout, PostStatus};
 use crate::presentation::views::{
     self, FeedLoaderContext, FeedLoaderTemplate, PageContext, PostCard, PostCardsAppendTemplate,
-    PostDetailContext, PostSectionEvent, PostTocEvent, PostTocView, TemplateRenderError,
-    build_tag_badges,
+    PostCommentView, PostDetailContext, PostSectionEvent, PostTocEvent, PostTocView,
+    TemplateRenderError, TranslationLinkView, build_tag_badges, build_translation_links,
 };
-use crate::util::timezone;
 
 mod presentation;
 mod sections;
@@ -31,7 +36,7 @@ mod service;
 mod summaries;
 mod types;
 
-pub use types::{AppendPayload, FeedError, FeedFilter, FeedService};
+pub use types::{AppendPayload, ArchivePeriod, FeedError, FeedFilter, FeedService};
 
 const DEFAULT_PAGE_SIZE: usize = 6;
 
@@ -45,3 +50,7 @@ pub fn build_datastar_append_response(
 pub(crate) fn order_tags_with_pins(counts: &[TagWithCount]) -> Vec<&TagWithCount> {
     summaries::order_tags_with_pins(counts)
 }
+
+pub(crate) fn visible_tags(counts: &[TagWithCount], limit: i32) -> Vec<&TagWithCount> {
+    summaries::visible_tags(counts, limit)
+}
@@ -3,18 +3,18 @@ use super::*;
 pub(super) fn record_to_card(
     record: &PostRecord,
     tags: &[TagRecord],
-    timezone: chrono_tz::Tz,
+    settings: &SiteSettingsRecord,
 ) -> PostCard {
     let published_at = record.published_at.unwrap_or(record.created_at);
-    let localized = timezone::localized_datetime(published_at, timezone);
-    let date = timezone::localized_date(published_at, timezone);
+    let published = format::local_timestamp(published_at, settings.timezone, &settings.date_format);
 
     PostCard {
         slug: record.slug.clone(),
+        path: post_path(&record.slug),
         title: record.title.clone(),
         excerpt: record.excerpt.clone(),
-        iso_date: localized.to_rfc3339(),
-        published: posts::format_human_date(date),
+        iso_date: published.iso,
+        published: published.display,
         badges: build_tag_badges(
             tags.iter()
                 .map(|tag| (tag.slug.as_str(), tag.name.as_str())),
@@ -23,40 +23,145 @@ pub(super) fn record_to_card(
     }
 }
 
-pub(super) fn build_posts_ld_json(
-    cards: &[PostCard],
+/// Take the post the magazine layout features out of the first page of the
+/// feed: the first pinned post, otherwise the most recent one.
+pub(super) fn take_featured(
+    layout: HomepageLayout,
+    first_page: bool,
+    cards: &mut Vec<PostCard>,
+) -> Option<PostCard> {
+    if layout != HomepageLayout::Magazine || !first_page || cards.is_empty() {
+        return None;
+    }
+    // Pinned posts list first, the rest newest first.
+    let index = cards.iter().position(|card| card.is_pinned).unwrap_or(0);
+    Some(cards.remove(index))
+}
+
+/// Heading of an archive page, linking the nearest earlier and later periods
+/// of the same kind that have posts.
+pub(super) fn build_archive_view(
+    period: &ArchivePeriod,
+    month_counts: &[posts::MonthCount],
+) -> views::ArchiveView {
+    let key = period.key();
+    // Month counts list newest first.
+    let link = |month: &posts::MonthCount| {
+        period
+            .sibling_for_month(&month.key)
+            .map(|sibling| views::ArchiveLinkView {
+                label: sibling.label(),
+                path: sibling.path(),
+            })
+    };
+    let previous = month_counts
+        .iter()
+        .find(|month| month.key < key && !period.contains_month(&month.key))
+        .and_then(link);
+    let next = month_counts
+        .iter()
+        .rev()
+        .find(|month| month.key > key && !period.contains_month(&month.key))
+        .and_then(link);
+
+    views::ArchiveView {
+        title: period.label(),
+        previous,
+        next,
+    }
+}
+
+/// Structured data for a listing page: the site on the home page, the
+/// listed posts, and a breadcrumb trail on tag and month archives.
+///
+/// `site_url` is the absolute site root, base path included, with a trailing slash.
+pub(super) fn build_listing_ld_json(
+    records: &[PostRecord],
     filter: &FeedFilter,
-    public_site_url: &str,
-    blog_name: &str,
+    site_url: &str,
+    settings: &SiteSettingsRecord,
+    tag_counts: &[TagWithCount],
+    month_counts: &[posts::MonthCount],
 ) -> Option<String> {
-    if cards.is_empty() {
-        return None;
+    let listing_url = join_url(site_url, &filter.base_path());
+    let mut data = StructuredData::new();
+
+    match filter {
+        FeedFilter::All => {
+            data = data.with(
+                WebSite::new(&settings.meta_title, site_url)
+                    .description(&settings.meta_description),
+            );
+        }
+        FeedFilter::Tag(slug) => {
+            let name = tag_counts
+                .iter()
+                .find(|tag| &tag.slug == slug)
+                .map_or(slug.as_str(), |tag| tag.name.as_str());
+            data = data.with(
+                BreadcrumbList::new()
+                    .item(&settings.meta_title, site_url)
+                    .item(name, &listing_url),
+            );
+        }
+        FeedFilter::Archive(period) => {
+            data = data.with(
+                BreadcrumbList::new()
+                    .item(&settings.meta_title, site_url)
+                    .item(period.label(), &listing_url),
+            );
+        }
+        FeedFilter::Month(key) => {
+            let label = month_counts
+                .iter()
+                .find(|month| &month.key == key)
+                .map_or(key.as_str(), |month| month.label.as_str());
+            data = data.with(
+                BreadcrumbList::new()
+                    .item(&settings.meta_title, site_url)
+                    .item(label, &listing_url),
+            );
+        }
     }
 
-    let site_url = normalize_public_site_url(public_site_url);
-    let blog_url = format!("{site_url}{}", filter.base_path());
+    if !records.is_empty() {
+        let blog = records.iter().fold(
+            Blog::new(&settings.meta_title, &listing_url),
+            |blog, record| {
+                blog.post(
+                    BlogPosting::new(
+                        &record.title,
+                        join_url(site_url, &post_path(&record.slug)),
+                        record.published_at.unwrap_or(record.created_at),
+                    )
+                    .description(&record.excerpt),
+                )
+            },
+        );
+        data = data.with(blog);
+    }
 
-    let blog_posts = cards
-        .iter()
-        .map(|card| {
-            json!({
-                "@type": "BlogPosting",
-                "headline": card.title,
-                "description": card.excerpt,
-                "datePublished": card.iso_date,
-                "url": format!("{site_url}posts/{}", card.slug),
-            })
-        })
-        .collect::<Vec<_>>();
-
-    serde_json::to_string(&json!({
-        "@context": "https://schema.org",
-        "@type": "Blog",
-        "name": blog_name,
-        "url": blog_url,
-        "blogPost": blog_posts,
-    }))
-    .ok()
+    data.to_script_json()
+}
+
+/// Structured data for a post page.
+pub(super) fn build_post_ld_json(
+    post: &PostRecord,
+    tags: &[TagRecord],
+    site_url: &str,
+) -> Option<String> {
+    let posting = BlogPosting::new(
+        &post.title,
+        join_url(site_url, &post_path(&post.slug)),
+        post.published_at.unwrap_or(post.created_at),
+    )
+    .date_modified(post.updated_at)
+    .description(&post.excerpt)
+    .word_count_of(&post.body_markdown)
+    .keywords(tags.iter().map(|tag| tag.name.clone()))
+    .in_language(post.lang.clone());
+
+    StructuredData::new().with(posting).to_script_json()
 }
 
 pub(super) fn build_datastar_append_response(
@@ -68,6 +173,7 @@ pub(super) fn build_datastar_append_response(
         cards,
         next_cursor,
         total_visible,
+        base_path,
     } = payload;
 
     let appended_count = cards.len();
@@ -76,6 +182,7 @@ pub(super) fn build_datastar_append_response(
         let template = PostCardsAppendTemplate {
             posts: cards,
             offset,
+            base_path: base_path.clone(),
         };
         Some(template.render().map_err(|err| {
             HttpError::from(TemplateRenderError::new(
@@ -93,6 +200,7 @@ pub(super) fn build_datastar_append_response(
             has_results: total_visible > 0,
             next_cursor,
             load_more_query,
+            base_path,
         },
     }
     .render()
@@ -135,8 +243,3 @@ pub(super) fn homepage_page_limit(settings: &SiteSettingsRecord) -> u32 {
         clamped
     }
 }
-
-fn normalize_public_site_url(url: &str) -> String {
-    let trimmed = url.trim_end_matches('/');
-    format!("{trimmed}/")
-}
@@ -14,12 +14,33 @@ impl FeedService {
             tags,
             settings,
             cache,
+            comments: None,
+            base_path: BasePath::default(),
+            cursor_keys: CursorKeys::ephemeral(),
         }
     }
 
+    /// Read back cursors with the keys the repositories sign them with.
+    pub fn with_cursor_keys(mut self, cursor_keys: CursorKeys) -> Self {
+        self.cursor_keys = cursor_keys;
+        self
+    }
+
+    /// Prefix feed links and absolute post URLs with `base_path`.
+    pub fn with_base_path(mut self, base_path: BasePath) -> Self {
+        self.base_path = base_path;
+        self
+    }
+
+    /// Attach the comments repository so post pages list approved comments.
+    pub fn with_comments(mut self, comments: Arc<dyn CommentsRepo>) -> Self {
+        self.comments = Some(comments);
+        self
+    }
+
     fn decode_cursor(&self, cursor: Option<&str>) -> Result<Option<PostCursor>, FeedError> {
         cursor
-            .map(PostCursor::decode)
+            .map(|cursor| PostCursor::decode(&self.cursor_keys, cursor))
             .transpose()
             .map_err(|err| FeedError::InvalidCursor(err.to_string()))
     }
@@ -30,7 +51,7 @@ impl FeedService {
         cursor: Option<&str>,
     ) -> Result<PageContext, FeedError> {
         // Record derived collection dependencies for cache invalidation
-        crate::cache::deps::record(crate::cache::EntityKey::PostsIndex);
+        record_listing_dependencies(&filter, cursor);
         crate::cache::deps::record(crate::cache::EntityKey::PostAggTags);
         crate::cache::deps::record(crate::cache::EntityKey::PostAggMonths);
 
@@ -67,42 +88,23 @@ impl FeedService {
                 .await?
         };
 
-        let total_filtered = self
-            .posts
-            .count_posts(PostListScope::Public, &query_filter)
-            .await?;
-
-        let total_all = self
-            .posts
-            .count_posts(PostListScope::Public, &PostQueryFilter::default())
-            .await?;
-
-        let tag_counts = if let Some(cache) = &self.cache {
-            if let Some(cached) = cache.get_tag_counts() {
-                cached
-            } else {
-                let tags = self.tags.list_with_counts().await?;
-                cache.set_tag_counts(tags.clone());
-                tags
-            }
-        } else {
-            self.tags.list_with_counts().await?
-        };
-        let month_counts = if let Some(cache) = &self.cache {
-            if let Some(cached) = cache.get_month_counts() {
-                cached
-            } else {
-                let months = self
-                    .posts
-                    .list_month_counts(PostListScope::Public, &PostQueryFilter::default())
-                    .await?;
-                cache.set_month_counts(months.clone());
-                months
-            }
-        } else {
-            self.posts
-                .list_month_counts(PostListScope::Public, &PostQueryFilter::default())
-                .await?
+        let (tag_counts, month_counts) = self.load_aggregates().await?;
+        let total_all: u64 = month_counts.iter().map(|month| month.count as u64).sum();
+        let total_filtered = match &filter {
+            FeedFilter::All => total_all,
+            FeedFilter::Tag(slug) => tag_counts
+                .iter()
+                .find(|tag| &tag.slug == slug)
+                .map_or(0, |tag| u64::try_from(tag.count).unwrap_or(0)),
+            FeedFilter::Month(key) => month_counts
+                .iter()
+                .find(|month| &month.key == key)
+                .map_or(0, |month| month.count as u64),
+            FeedFilter::Archive(period) => month_counts
+                .iter()
+                .filter(|month| period.contains_month(&month.key))
+                .map(|month| month.count as u64)
+                .sum(),
         };
 
         let tag_summaries = if settings.show_tag_aggregations {
@@ -111,10 +113,15 @@ impl FeedService {
             Vec::new()
         };
 
+        // A year archive matches no month key, so no month is highlighted.
+        let active_month = match &filter {
+            FeedFilter::Archive(period) => Some(period.key()),
+            _ => filter.month().map(str::to_string),
+        };
         let month_summaries = if settings.show_month_aggregations {
             summaries::build_month_summaries(
                 &month_counts,
-                filter.month(),
+                active_month.as_deref(),
                 total_all,
                 settings.month_filter_limit,
             )
@@ -122,24 +129,39 @@ impl FeedService {
             Vec::new()
         };
 
-        let mut cards = Vec::with_capacity(page.items.len());
-        for record in &page.items {
-            let tags = self.tags.list_for_post(record.id).await?;
-            cards.push(presentation::record_to_card(
-                record,
-                &tags,
-                settings.timezone,
-            ));
-        }
+        let mut cards = self.post_cards(&page.items, &settings).await?;
+
+        let layout = match filter {
+            FeedFilter::All => settings.homepage_layout,
+            FeedFilter::Tag(_) | FeedFilter::Month(_) | FeedFilter::Archive(_) => {
+                HomepageLayout::List
+            }
+        };
+        let featured = presentation::take_featured(layout, decoded_cursor.is_none(), &mut cards);
+
+        // Structured data needs absolute URLs; without them it is left out.
+        let ld_json = self
+            .base_path
+            .site_url(&settings.public_site_url)
+            .and_then(|site_url| {
+                presentation::build_listing_ld_json(
+                    &page.items,
+                    &filter,
+                    &site_url,
+                    &settings,
+                    &tag_counts,
+                    &month_counts,
+                )
+            });
 
-        let posts_ld_json = presentation::build_posts_ld_json(
-            &cards,
-            &filter,
-            &settings.public_site_url,
-            &settings.meta_title,
-        );
+        let archive = match &filter {
+            FeedFilter::Archive(period) => {
+                Some(presentation::build_archive_view(period, &month_counts))
+            }
+            _ => None,
+        };
 
-        let post_count = cards.len();
+        let post_count = cards.len() + usize::from(featured.is_some());
         Ok(PageContext {
             posts: cards,
             post_count,
@@ -151,7 +173,11 @@ impl FeedService {
             show_month_filters: settings.show_month_aggregations,
             next_cursor: page.next_cursor,
             load_more_query: filter.load_more_query(),
-            posts_ld_json,
+            ld_json,
+            base_path: self.base_path.as_str().to_string(),
+            layout: layout.as_str(),
+            featured,
+            archive,
         })
     }
 
@@ -160,6 +186,8 @@ impl FeedService {
         filter: FeedFilter,
         cursor: Option<&str>,
     ) -> Result<AppendPayload, FeedError> {
+        record_listing_dependencies(&filter, cursor);
+
         let decoded_cursor = self.decode_cursor(cursor)?;
         let query_filter = filter.to_query_filter();
         let settings = self.load_site_settings().await?;
@@ -192,15 +220,7 @@ impl FeedService {
                 .await?
         };
 
-        let mut cards = Vec::with_capacity(page.items.len());
-        for record in &page.items {
-            let tags = self.tags.list_for_post(record.id).await?;
-            cards.push(presentation::record_to_card(
-                record,
-                &tags,
-                settings.timezone,
-            ));
-        }
+        let cards = self.post_cards(&page.items, &settings).await?;
 
         let offset = if let Some(cursor) = decoded_cursor {
             self.posts
@@ -218,6 +238,7 @@ impl FeedService {
             cards,
             next_cursor: page.next_cursor,
             total_visible,
+            base_path: self.base_path.as_str().to_string(),
         })
     }
 
@@ -250,6 +271,39 @@ impl FeedService {
         self.build_post_context(post).await.map(Some)
     }
 
+    /// Slug of the variant of `slug` to serve for `lang`: its published
+    /// translation in that language when there is one, otherwise `slug`
+    /// itself. `None` when `slug` is not a published post.
+    pub async fn translation_slug(
+        &self,
+        slug: &str,
+        lang: &str,
+    ) -> Result<Option<String>, FeedError> {
+        let Some(post) = self.posts.find_by_slug(slug).await? else {
+            return Ok(None);
+        };
+        if post.status != PostStatus::Published || post.published_at.is_none() {
+            return Ok(None);
+        }
+        let (Some(lang), Some(group)) = (normalize_lang(lang), post.translation_group) else {
+            return Ok(Some(post.slug));
+        };
+        if post
+            .lang
+            .as_deref()
+            .is_some_and(|current| current.eq_ignore_ascii_case(&lang))
+        {
+            return Ok(Some(post.slug));
+        }
+
+        let translations = self.posts.list_post_translations(group).await?;
+        let slug = translations
+            .into_iter()
+            .find(|translation| translation.lang.eq_ignore_ascii_case(&lang))
+            .map_or(post.slug, |translation| translation.slug);
+        Ok(Some(slug))
+    }
+
     pub async fn post_preview(&self, id: Uuid) -> Result<Option<PostDetailContext>, FeedError> {
         let Some(post) = self.posts.find_by_id(id).await? else {
             return Ok(None);
@@ -275,14 +329,42 @@ impl FeedService {
         };
 
         let published_at = post.published_at.unwrap_or(post.created_at);
-        let localized = timezone::localized_datetime(published_at, settings.timezone);
-        let date = timezone::localized_date(published_at, settings.timezone);
+        let published =
+            format::local_timestamp(published_at, settings.timezone, &settings.date_format);
+
+        let translations = self.post_translations(&post).await?;
+        let ld_json = self
+            .base_path
+            .site_url(&settings.public_site_url)
+            .and_then(|site_url| presentation::build_post_ld_json(&post, &tags, &site_url));
+
+        let comments = match &self.comments {
+            Some(repo) => repo
+                .list_approved_for_post(post.id)
+                .await?
+                .into_iter()
+                .map(|comment| {
+                    let created = format::local_timestamp(
+                        comment.created_at,
+                        settings.timezone,
+                        &settings.date_format,
+                    );
+                    PostCommentView {
+                        author: comment.author,
+                        body: comment.body,
+                        published: created.display,
+                        iso_date: created.iso,
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        };
 
         Ok(PostDetailContext {
             slug: post.slug,
             title: post.title,
-            published: posts::format_human_date(date),
-            iso_date: localized.to_rfc3339(),
+            published: published.display,
+            iso_date: published.iso,
             tags: build_tag_badges(
                 tags.iter()
                     .map(|tag| (tag.slug.as_str(), tag.name.as_str())),
@@ -295,9 +377,31 @@ impl FeedService {
             has_mermaid_diagrams,
             toc,
             is_pinned: post.pinned,
+            comments,
+            lang: post.lang,
+            translations,
+            ld_json,
         })
     }
 
+    async fn post_translations(
+        &self,
+        post: &PostRecord,
+    ) -> Result<Vec<TranslationLinkView>, FeedError> {
+        let Some(group) = post.translation_group else {
+            return Ok(Vec::new());
+        };
+
+        // Alternates follow every sibling's publish state and slug. Any post
+        // change invalidates the sitemap, so depending on it covers siblings.
+        crate::cache::deps::record(crate::cache::EntityKey::Sitemap);
+
+        let translations = self.posts.list_post_translations(group).await?;
+        Ok(build_translation_links(translations.iter().map(
+            |translation| (translation.lang.as_str(), translation.slug.as_str()),
+        )))
+    }
+
     pub async fn is_known_tag(&self, tag: &str) -> Result<bool, FeedError> {
         crate::cache::deps::record(crate::cache::EntityKey::PostAggTags);
 
@@ -337,6 +441,42 @@ impl FeedService {
         Ok(months.iter().any(|entry| entry.key == month))
     }
 
+    /// Public tag and month counts, read from the cache when both are held
+    /// there and otherwise fetched together.
+    async fn load_aggregates(
+        &self,
+    ) -> Result<(Vec<TagWithCount>, Vec<posts::MonthCount>), FeedError> {
+        if let Some(cache) = &self.cache
+            && let (Some(tags), Some(months)) = (cache.get_tag_counts(), cache.get_month_counts())
+        {
+            return Ok((tags, months));
+        }
+
+        let aggregates = self.posts.list_public_aggregates().await?;
+        if let Some(cache) = &self.cache {
+            cache.set_tag_counts(aggregates.tags.clone());
+            cache.set_month_counts(aggregates.months.clone());
+        }
+        Ok((aggregates.tags, aggregates.months))
+    }
+
+    /// Cards for a page of posts, with their tags fetched in one lookup.
+    async fn post_cards(
+        &self,
+        records: &[PostRecord],
+        settings: &SiteSettingsRecord,
+    ) -> Result<Vec<PostCard>, FeedError> {
+        let ids: Vec<Uuid> = records.iter().map(|record| record.id).collect();
+        let mut tags = self.tags.tags_for_posts(&ids).await?;
+        Ok(records
+            .iter()
+            .map(|record| {
+                let tags = tags.remove(&record.id).unwrap_or_default();
+                presentation::record_to_card(record, &tags, settings)
+            })
+            .collect())
+    }
+
     async fn load_site_settings(&self) -> Result<SiteSettingsRecord, FeedError> {
         // Record site settings dependency for cache invalidation
         crate::cache::deps::record(crate::cache::EntityKey::SiteSettings);
@@ -362,3 +502,26 @@ impl FeedService {
         Ok(settings)
     }
 }
+
+/// Record the listing a page of posts belongs to, so that a post edit only
+/// invalidates the listings that can include it.
+fn record_listing_dependencies(filter: &FeedFilter, cursor: Option<&str>) {
+    use crate::cache::EntityKey;
+
+    crate::cache::deps::record(EntityKey::PostsIndex);
+    let keys = match filter {
+        FeedFilter::All if cursor.is_none() => vec![EntityKey::PostsRecent],
+        FeedFilter::All => vec![EntityKey::PostsPaged],
+        FeedFilter::Tag(tag) => vec![EntityKey::PostsTag(tag.clone())],
+        FeedFilter::Month(month) => vec![EntityKey::PostsMonth(month.clone())],
+        // A year archive depends on each of its months.
+        FeedFilter::Archive(period) => period
+            .month_keys()
+            .into_iter()
+            .map(EntityKey::PostsMonth)
+            .collect(),
+    };
+    for key in keys {
+        crate::cache::deps::record(key);
+    }
+}
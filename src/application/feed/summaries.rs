@@ -13,6 +13,26 @@ pub(super) fn order_tags_with_pins(counts: &[TagWithCount]) -> Vec<&TagWithCount
     ordered
 }
 
+/// Tags in filter order: every pinned tag, then at most `limit` others.
+pub(super) fn visible_tags(counts: &[TagWithCount], limit: i32) -> Vec<&TagWithCount> {
+    let limit = limit.max(0) as usize;
+    let mut non_pinned_added = 0;
+
+    order_tags_with_pins(counts)
+        .into_iter()
+        .filter(|entry| {
+            if entry.pinned {
+                return true;
+            }
+            if non_pinned_added >= limit {
+                return false;
+            }
+            non_pinned_added += 1;
+            true
+        })
+        .collect()
+}
+
 pub(super) fn build_tag_summaries(
     counts: &[TagWithCount],
     active_tag: Option<&str>,
@@ -27,19 +47,7 @@ pub(super) fn build_tag_summaries(
         is_active: active_tag.is_none(),
     });
 
-    let ordered = order_tags_with_pins(counts);
-    let limit = settings.tag_filter_limit.max(0) as usize;
-    let mut non_pinned_added = 0;
-
-    for entry in ordered {
-        if !entry.pinned && non_pinned_added >= limit {
-            continue;
-        }
-
-        if !entry.pinned {
-            non_pinned_added += 1;
-        }
-
+    for entry in visible_tags(counts, settings.tag_filter_limit) {
         summaries.push(views::TagSummary {
             label: format!("#{}", entry.name),
             path: format!("/tags/{}", entry.slug),
@@ -2,10 +2,12 @@ use std::sync::Arc;
 
 use thiserror::Error;
 
+use crate::application::pagination::CursorKeys;
 use crate::application::repos::{
-    PostQueryFilter, PostsRepo, RepoError, SectionsRepo, SettingsRepo, TagsRepo,
+    CommentsRepo, PostQueryFilter, PostsRepo, RepoError, SectionsRepo, SettingsRepo, TagsRepo,
 };
 use crate::cache::L0Store;
+use crate::domain::routes::BasePath;
 use crate::domain::sections::SectionTreeError;
 
 #[derive(Clone)]
@@ -13,6 +15,102 @@ pub enum FeedFilter {
     All,
     Tag(String),
     Month(String),
+    Archive(ArchivePeriod),
+}
+
+/// A year or month served by the `/archive` pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchivePeriod {
+    year: i32,
+    month: Option<u8>,
+}
+
+impl ArchivePeriod {
+    /// Parse the `{YYYY}` and optional `{MM}` path segments. Anything but a
+    /// four-digit year from 0001 and a two-digit month from 01 to 12 is
+    /// rejected.
+    pub fn parse(year: &str, month: Option<&str>) -> Option<Self> {
+        let year = parse_digits(year, 4).filter(|year| *year >= 1)?;
+        let month = match month {
+            Some(month) => {
+                let month = parse_digits(month, 2).filter(|month| (1..=12).contains(month))?;
+                Some(u8::try_from(month).ok()?)
+            }
+            None => None,
+        };
+        Some(Self { year, month })
+    }
+
+    /// Parse a month key (`YYYY-MM`) or a year (`YYYY`).
+    fn from_key(key: &str) -> Option<Self> {
+        match key.split_once('-') {
+            Some((year, month)) => Self::parse(year, Some(month)),
+            None => Self::parse(key, None),
+        }
+    }
+
+    /// `YYYY-MM` for a month, `YYYY` for a year.
+    pub fn key(&self) -> String {
+        match self.month {
+            Some(month) => format!("{:04}-{month:02}", self.year),
+            None => format!("{:04}", self.year),
+        }
+    }
+
+    pub fn path(&self) -> String {
+        match self.month {
+            Some(month) => format!("/archive/{:04}/{month:02}", self.year),
+            None => format!("/archive/{:04}", self.year),
+        }
+    }
+
+    /// `March 2024` for a month, `2024` for a year.
+    pub fn label(&self) -> String {
+        match self
+            .month
+            .and_then(|month| time::Month::try_from(month).ok())
+        {
+            Some(month) => format!("{month} {}", self.year),
+            None => self.year.to_string(),
+        }
+    }
+
+    /// Month keys (`YYYY-MM`) the period covers.
+    pub(super) fn month_keys(&self) -> Vec<String> {
+        match self.month {
+            Some(_) => vec![self.key()],
+            None => (1..=12)
+                .map(|month| format!("{:04}-{month:02}", self.year))
+                .collect(),
+        }
+    }
+
+    /// Whether the month `key` (`YYYY-MM`) falls in this period.
+    pub(super) fn contains_month(&self, key: &str) -> bool {
+        match self.month {
+            Some(_) => key == self.key(),
+            None => key.get(..4) == Some(self.key().as_str()),
+        }
+    }
+
+    /// The same kind of period for the month `key` (`YYYY-MM`).
+    pub(super) fn sibling_for_month(&self, key: &str) -> Option<Self> {
+        let period = Self::from_key(key)?;
+        Some(match self.month {
+            Some(_) => period,
+            None => Self {
+                month: None,
+                ..period
+            },
+        })
+    }
+}
+
+fn parse_digits(value: &str, len: usize) -> Option<i32> {
+    if value.len() != len || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
 }
 
 impl FeedFilter {
@@ -35,6 +133,10 @@ impl FeedFilter {
             FeedFilter::All => String::new(),
             FeedFilter::Tag(value) => format!("&tag={value}"),
             FeedFilter::Month(value) => format!("&month={value}"),
+            FeedFilter::Archive(period) => match period.month {
+                Some(_) => format!("&month={}", period.key()),
+                None => format!("&year={}", period.key()),
+            },
         }
     }
 
@@ -43,6 +145,7 @@ impl FeedFilter {
             FeedFilter::All => "/".to_string(),
             FeedFilter::Tag(value) => format!("/tags/{value}"),
             FeedFilter::Month(value) => format!("/months/{value}"),
+            FeedFilter::Archive(period) => period.path(),
         }
     }
 
@@ -52,6 +155,10 @@ impl FeedFilter {
             FeedFilter::All => {}
             FeedFilter::Tag(tag) => filter.tag = Some(tag.clone()),
             FeedFilter::Month(month) => filter.month = Some(month.clone()),
+            FeedFilter::Archive(period) => match period.month {
+                Some(_) => filter.month = Some(period.key()),
+                None => filter.year = Some(period.year),
+            },
         }
         filter
     }
@@ -63,6 +170,7 @@ pub struct AppendPayload {
     pub cards: Vec<crate::presentation::views::PostCard>,
     pub next_cursor: Option<String>,
     pub total_visible: usize,
+    pub base_path: String,
 }
 
 #[derive(Clone)]
@@ -72,6 +180,9 @@ pub struct FeedService {
     pub(super) tags: Arc<dyn TagsRepo>,
     pub(super) settings: Arc<dyn SettingsRepo>,
     pub(super) cache: Option<Arc<L0Store>>,
+    pub(super) comments: Option<Arc<dyn CommentsRepo>>,
+    pub(super) base_path: BasePath,
+    pub(super) cursor_keys: CursorKeys,
 }
 
 #[derive(Debug, Error)]
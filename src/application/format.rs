@@ -0,0 +1,113 @@
+//! Timestamp display in the site timezone.
+//!
+//! Timestamps are stored in UTC. Everything shown to readers goes through
+//! [`local_timestamp`], which converts to the configured timezone (DST-aware via
+//! `chrono-tz`) and formats the date with the site's `date_format`, a chrono
+//! `strftime` pattern.
+
+use std::fmt::Write;
+
+use chrono::format::{Item, StrftimeItems};
+use chrono_tz::Tz;
+use time::OffsetDateTime;
+
+use crate::util::timezone;
+
+/// Pattern used when the site does not configure one: `March 9, 2025`.
+pub const DEFAULT_DATE_FORMAT: &str = "%B %-d, %Y";
+
+/// Pattern for admin tables, where the time of day matters.
+pub const ADMIN_TIMESTAMP_FORMAT: &str = "%Y/%m/%d %H:%M:%S";
+
+/// A timestamp rendered for display next to its machine-readable form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalTimestamp {
+    /// Formatted with the site pattern, for humans.
+    pub display: String,
+    /// RFC 3339 with the local offset, for `<time datetime>` and structured data.
+    pub iso: String,
+}
+
+/// Whether `pattern` is a non-empty strftime pattern chrono can format.
+pub fn is_valid_date_format(pattern: &str) -> bool {
+    !pattern.trim().is_empty()
+        && StrftimeItems::new(pattern).all(|item| !matches!(item, Item::Error))
+}
+
+/// Format `time` in `tz` with `pattern`.
+///
+/// An invalid pattern falls back to [`DEFAULT_DATE_FORMAT`] instead of failing
+/// the page; patterns are validated when settings are saved.
+pub fn format_local(time: OffsetDateTime, tz: Tz, pattern: &str) -> String {
+    let localized = timezone::localized_datetime(time, tz);
+    let mut formatted = String::new();
+    if write!(formatted, "{}", localized.format(pattern)).is_ok() {
+        return formatted;
+    }
+
+    formatted.clear();
+    write!(formatted, "{}", localized.format(DEFAULT_DATE_FORMAT))
+        .expect("default date format is valid");
+    formatted
+}
+
+/// Display and ISO forms of `time` in the site timezone.
+pub fn local_timestamp(time: OffsetDateTime, tz: Tz, pattern: &str) -> LocalTimestamp {
+    LocalTimestamp {
+        display: format_local(time, tz, pattern),
+        iso: timezone::localized_datetime(time, tz).to_rfc3339(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn default_pattern_matches_previous_human_dates() {
+        let time = datetime!(2025-01-05 12:00 UTC);
+
+        assert_eq!(
+            format_local(time, Tz::UTC, DEFAULT_DATE_FORMAT),
+            "January 5, 2025"
+        );
+    }
+
+    #[test]
+    fn formats_across_a_dst_boundary() {
+        // New York springs forward at 02:00 local on 2025-03-09.
+        let tz = chrono_tz::America::New_York;
+        let pattern = "%Y-%m-%d %H:%M %Z";
+
+        let before = local_timestamp(datetime!(2025-03-09 06:30 UTC), tz, pattern);
+        let after = local_timestamp(datetime!(2025-03-09 07:30 UTC), tz, pattern);
+
+        assert_eq!(before.display, "2025-03-09 01:30 EST");
+        assert_eq!(before.iso, "2025-03-09T01:30:00-05:00");
+        assert_eq!(after.display, "2025-03-09 03:30 EDT");
+        assert_eq!(after.iso, "2025-03-09T03:30:00-04:00");
+    }
+
+    #[test]
+    fn date_changes_with_the_local_day() {
+        let tz = chrono_tz::Asia::Tokyo;
+
+        assert_eq!(
+            format_local(datetime!(2025-12-31 16:00 UTC), tz, "%Y-%m-%d"),
+            "2026-01-01"
+        );
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected_and_fall_back() {
+        assert!(is_valid_date_format("%d.%m.%Y"));
+        assert!(!is_valid_date_format("%Q"));
+        assert!(!is_valid_date_format("  "));
+
+        assert_eq!(
+            format_local(datetime!(2025-01-05 12:00 UTC), Tz::UTC, "%Q"),
+            "January 5, 2025"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use apalis::prelude::{Attempt, Error as ApalisError, TaskId};
+use tracing::warn;
+
+use crate::application::repos::JobsRepo;
+
+/// Keep the error of a failed run in `job_attempts`, then hand `result` back
+/// to apalis unchanged.
+///
+/// apalis overwrites `last_error` on every run, so without this only the
+/// latest failure of a retried job would survive. A failure to record the
+/// attempt is logged and never masks the job's own outcome.
+pub async fn record_failed_attempt<J>(
+    repo: &J,
+    task_id: &TaskId,
+    attempt: &Attempt,
+    result: Result<(), ApalisError>,
+) -> Result<(), ApalisError>
+where
+    J: JobsRepo + ?Sized,
+{
+    if let Err(err) = &result {
+        let job_id = task_id.to_string();
+        let attempt = i32::try_from(attempt.current()).unwrap_or(i32::MAX);
+        if let Err(record_err) = repo
+            .record_job_attempt(&job_id, attempt, &err.to_string())
+            .await
+        {
+            warn!(
+                target = "application::jobs::record_failed_attempt",
+                job_id = %job_id,
+                attempt,
+                error = %record_err,
+                "failed to record job attempt"
+            );
+        }
+    }
+    result
+}
@@ -40,3 +40,13 @@ where
     let boxed: BoxError = Box::new(err);
     ApalisError::Failed(Arc::new(boxed))
 }
+
+/// Convert an error that retrying cannot fix into an [`ApalisError::Abort`],
+/// so the job is killed instead of retried.
+pub fn job_aborted<E>(err: E) -> ApalisError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let boxed: BoxError = Box::new(err);
+    ApalisError::Abort(Arc::new(boxed))
+}
@@ -0,0 +1,286 @@
+//! Periodic maintenance tick driven by `scheduler.cadence`.
+//!
+//! Each tick runs every enabled [`MaintenanceTask`] in turn. A task that
+//! fails or panics is logged and the rest still run; a tick that comes due
+//! while the previous one is still running is skipped rather than stacked.
+
+use std::error::Error as StdError;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::FutureExt;
+use metrics::counter;
+use time::OffsetDateTime;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{debug, warn};
+
+use crate::cache::L0Store;
+
+const SOURCE: &str = "application::jobs::maintenance";
+const METRIC_TICK_SKIPPED_TOTAL: &str = "soffio_maintenance_tick_skipped_total";
+const METRIC_TASK_FAILED_TOTAL: &str = "soffio_maintenance_task_failed_total";
+
+pub type MaintenanceError = Box<dyn StdError + Send + Sync>;
+
+/// One housekeeping step of the maintenance tick.
+#[async_trait]
+pub trait MaintenanceTask: Send + Sync {
+    /// Name used in logs and metrics.
+    fn name(&self) -> &'static str;
+
+    /// Run the step once and return how many entries it removed.
+    async fn run(&self) -> Result<u64, MaintenanceError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickOutcome {
+    /// Every task ran; `failed` of them returned an error or panicked.
+    Ran { failed: usize },
+    /// The previous tick was still running.
+    Skipped,
+}
+
+pub struct MaintenanceTick {
+    tasks: Vec<Arc<dyn MaintenanceTask>>,
+    running: AtomicBool,
+}
+
+/// Clears the running flag when a tick ends, even if it unwinds.
+struct RunningGuard<'a>(&'a AtomicBool);
+
+impl Drop for RunningGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl MaintenanceTick {
+    pub fn new(tasks: Vec<Arc<dyn MaintenanceTask>>) -> Self {
+        Self {
+            tasks,
+            running: AtomicBool::new(false),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Run every task once, unless a previous tick is still in progress.
+    pub async fn tick(&self) -> TickOutcome {
+        if self
+            .running
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            counter!(METRIC_TICK_SKIPPED_TOTAL).increment(1);
+            warn!(
+                target = SOURCE,
+                "previous maintenance tick still running; skipping this one"
+            );
+            return TickOutcome::Skipped;
+        }
+        let _guard = RunningGuard(&self.running);
+
+        let mut failed = 0;
+        for task in &self.tasks {
+            let name = task.name();
+            match AssertUnwindSafe(task.run()).catch_unwind().await {
+                Ok(Ok(removed)) => {
+                    debug!(
+                        target = SOURCE,
+                        task = name,
+                        removed,
+                        "maintenance task done"
+                    );
+                }
+                Ok(Err(err)) => {
+                    failed += 1;
+                    counter!(METRIC_TASK_FAILED_TOTAL, "task" => name).increment(1);
+                    warn!(target = SOURCE, task = name, error = %err, "maintenance task failed");
+                }
+                Err(_) => {
+                    failed += 1;
+                    counter!(METRIC_TASK_FAILED_TOTAL, "task" => name).increment(1);
+                    warn!(target = SOURCE, task = name, "maintenance task panicked");
+                }
+            }
+        }
+        TickOutcome::Ran { failed }
+    }
+
+    /// Tick every `cadence`, forever. Each tick runs on its own task so a
+    /// slow one does not hold back the clock; the next one finds it still
+    /// running and is skipped.
+    pub async fn run(self: Arc<Self>, cadence: Duration) {
+        let mut interval = tokio::time::interval_at(Instant::now() + cadence, cadence);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let tick = self.clone();
+            tokio::spawn(async move {
+                tick.tick().await;
+            });
+        }
+    }
+}
+
+/// Drops API keys from the L0 cache once they expire. Authentication
+/// rechecks expiry on every request, so this only frees their slots early.
+pub struct ExpiredCacheEntries {
+    pub l0: Arc<L0Store>,
+}
+
+#[async_trait]
+impl MaintenanceTask for ExpiredCacheEntries {
+    fn name(&self) -> &'static str {
+        "expired_cache_entries"
+    }
+
+    async fn run(&self) -> Result<u64, MaintenanceError> {
+        let removed = self.l0.evict_inactive_api_keys(OffsetDateTime::now_utc());
+        Ok(removed as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use tokio::sync::Notify;
+
+    use super::*;
+
+    /// Holds its tick open until released.
+    #[derive(Default)]
+    struct SlowTask {
+        started: Notify,
+        release: Notify,
+        runs: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl MaintenanceTask for SlowTask {
+        fn name(&self) -> &'static str {
+            "slow"
+        }
+
+        async fn run(&self) -> Result<u64, MaintenanceError> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            self.started.notify_one();
+            self.release.notified().await;
+            Ok(0)
+        }
+    }
+
+    struct Failing;
+
+    #[async_trait]
+    impl MaintenanceTask for Failing {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn run(&self) -> Result<u64, MaintenanceError> {
+            Err("backend unavailable".into())
+        }
+    }
+
+    struct Panicking;
+
+    #[async_trait]
+    impl MaintenanceTask for Panicking {
+        fn name(&self) -> &'static str {
+            "panicking"
+        }
+
+        async fn run(&self) -> Result<u64, MaintenanceError> {
+            panic!("bug in a maintenance task")
+        }
+    }
+
+    #[derive(Default)]
+    struct Counting(AtomicUsize);
+
+    #[async_trait]
+    impl MaintenanceTask for Counting {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn run(&self) -> Result<u64, MaintenanceError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(1)
+        }
+    }
+
+    #[tokio::test]
+    async fn a_tick_is_skipped_while_the_previous_one_runs() {
+        let slow = Arc::new(SlowTask::default());
+        let tick = Arc::new(MaintenanceTick::new(vec![slow.clone()]));
+
+        let first = tokio::spawn({
+            let tick = tick.clone();
+            async move { tick.tick().await }
+        });
+        slow.started.notified().await;
+
+        assert_eq!(tick.tick().await, TickOutcome::Skipped);
+        assert_eq!(tick.tick().await, TickOutcome::Skipped);
+
+        slow.release.notify_one();
+        assert_eq!(
+            first.await.expect("first tick"),
+            TickOutcome::Ran { failed: 0 }
+        );
+        assert_eq!(slow.runs.load(Ordering::SeqCst), 1);
+
+        // Once the slow tick finishes the next one runs again.
+        let next = tokio::spawn({
+            let tick = tick.clone();
+            async move { tick.tick().await }
+        });
+        slow.started.notified().await;
+        slow.release.notify_one();
+        assert_eq!(
+            next.await.expect("next tick"),
+            TickOutcome::Ran { failed: 0 }
+        );
+        assert_eq!(slow.runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn scheduled_ticks_do_not_overlap_a_slow_tick() {
+        let slow = Arc::new(SlowTask::default());
+        let tick = Arc::new(MaintenanceTick::new(vec![slow.clone()]));
+        let runner = tokio::spawn(tick.clone().run(Duration::from_millis(5)));
+
+        slow.started.notified().await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(slow.runs.load(Ordering::SeqCst), 1);
+
+        slow.release.notify_one();
+        slow.started.notified().await;
+        assert_eq!(slow.runs.load(Ordering::SeqCst), 2);
+
+        runner.abort();
+        slow.release.notify_one();
+    }
+
+    #[tokio::test]
+    async fn failing_and_panicking_tasks_do_not_stop_the_others() {
+        let counting = Arc::new(Counting::default());
+        let tick = MaintenanceTick::new(vec![
+            Arc::new(Failing),
+            Arc::new(Panicking),
+            counting.clone(),
+        ]);
+
+        assert_eq!(tick.tick().await, TickOutcome::Ran { failed: 2 });
+        assert_eq!(tick.tick().await, TickOutcome::Ran { failed: 2 });
+        assert_eq!(counting.0.load(Ordering::SeqCst), 2);
+    }
+}
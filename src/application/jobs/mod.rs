@@ -1,14 +1,35 @@
+mod attempts;
 mod context;
 mod expire_api_keys;
+mod maintenance;
+mod ping_sitemap;
 mod publish;
+mod purge_trash;
 mod queue;
+mod render_progress;
+mod warm_cache;
 
-pub use context::{JobWorkerContext, PUBLISH_JOB_WAIT_TIMEOUT, job_failed};
+pub use attempts::record_failed_attempt;
+pub use context::{JobWorkerContext, PUBLISH_JOB_WAIT_TIMEOUT, job_aborted, job_failed};
 pub use expire_api_keys::{
     ExpireApiKeysContext, ExpireApiKeysJob, expire_api_keys_schedule, process_expire_api_keys_job,
 };
+pub use maintenance::{
+    ExpiredCacheEntries, MaintenanceError, MaintenanceTask, MaintenanceTick, TickOutcome,
+};
+pub use ping_sitemap::{
+    PingSitemapJobPayload, SitemapPingError, enqueue_ping_sitemap_job, process_ping_sitemap_job,
+};
 pub use publish::{
     PublishPageJobPayload, PublishPostJobPayload, enqueue_publish_page_job,
     enqueue_publish_post_job, process_publish_page_job, process_publish_post_job,
 };
+pub use purge_trash::{
+    PurgeTrashContext, PurgeTrashJob, process_purge_trash_job, purge_trash_schedule,
+};
 pub use queue::{enqueue_job, wait_for_job_completion};
+pub use render_progress::{
+    POST_RENDER_JOB_TYPES, RenderProgress, RenderProgressState, post_render_progress,
+    wait_for_post_render,
+};
+pub use warm_cache::{WarmCacheContext, WarmCacheJob, process_warm_cache_job, warm_cache_schedule};
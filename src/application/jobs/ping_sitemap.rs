@@ -0,0 +1,149 @@
+//! Outbound job that tells search engines the sitemap changed.
+
+use std::time::Duration;
+
+use apalis::prelude::Error as ApalisError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::{
+    application::repos::{JobsRepo, RepoError},
+    domain::types::JobType,
+};
+
+use super::{context::job_failed, queue::enqueue_job};
+
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PingSitemapJobPayload {
+    /// Page whose publication changed the sitemap.
+    pub slug: String,
+    /// Absolute URL of `sitemap.xml`.
+    pub sitemap_url: String,
+    /// Endpoints from `sitemap.ping_urls` at the time the job was enqueued.
+    pub ping_urls: Vec<String>,
+}
+
+/// A ping that may succeed when retried.
+#[derive(Debug, Error)]
+#[error("sitemap ping to `{endpoint}` failed: {reason}")]
+pub struct SitemapPingError {
+    endpoint: String,
+    reason: String,
+}
+
+pub async fn enqueue_ping_sitemap_job<J: JobsRepo + ?Sized>(
+    repo: &J,
+    slug: String,
+    sitemap_url: String,
+    ping_urls: Vec<String>,
+) -> Result<String, RepoError> {
+    let payload = PingSitemapJobPayload {
+        slug,
+        sitemap_url,
+        ping_urls,
+    };
+    enqueue_job(repo, JobType::PingSitemap, &payload, None, 5, 0).await
+}
+
+/// Request every ping endpoint with the sitemap URL.
+///
+/// Endpoints that reject the ping (4xx) are logged and skipped; network
+/// errors and 5xx responses fail the job, and the retry pings every
+/// endpoint again.
+pub async fn process_ping_sitemap_job(payload: PingSitemapJobPayload) -> Result<(), ApalisError> {
+    let client = reqwest::Client::builder()
+        .timeout(PING_TIMEOUT)
+        .build()
+        .map_err(job_failed)?;
+
+    let mut retryable = None;
+    for endpoint in &payload.ping_urls {
+        let url = match ping_request_url(endpoint, &payload.sitemap_url) {
+            Ok(url) => url,
+            Err(err) => {
+                warn!(
+                    target = "application::jobs::process_ping_sitemap_job",
+                    endpoint,
+                    error = %err,
+                    "invalid sitemap ping endpoint"
+                );
+                continue;
+            }
+        };
+
+        match client.get(url).send().await {
+            Ok(response) if response.status().is_success() => {
+                info!(
+                    target = "application::jobs::process_ping_sitemap_job",
+                    endpoint,
+                    slug = payload.slug,
+                    "sitemap pinged"
+                );
+            }
+            Ok(response) if response.status().is_client_error() => {
+                warn!(
+                    target = "application::jobs::process_ping_sitemap_job",
+                    endpoint,
+                    status = response.status().as_u16(),
+                    "sitemap ping rejected"
+                );
+            }
+            Ok(response) => {
+                retryable = Some(SitemapPingError {
+                    endpoint: endpoint.clone(),
+                    reason: format!("status {}", response.status()),
+                });
+            }
+            Err(err) => {
+                retryable = Some(SitemapPingError {
+                    endpoint: endpoint.clone(),
+                    reason: err.to_string(),
+                });
+            }
+        }
+    }
+
+    match retryable {
+        Some(err) => Err(job_failed(err)),
+        None => Ok(()),
+    }
+}
+
+/// `endpoint` with the sitemap URL appended as its `sitemap` query parameter.
+fn ping_request_url(endpoint: &str, sitemap_url: &str) -> Result<Url, url::ParseError> {
+    let mut url = Url::parse(endpoint)?;
+    url.query_pairs_mut().append_pair("sitemap", sitemap_url);
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ping_url_carries_the_encoded_sitemap_url() {
+        let url = ping_request_url(
+            "https://search.test/ping",
+            "https://example.com/blog/sitemap.xml",
+        )
+        .expect("valid endpoint");
+        assert_eq!(
+            url.as_str(),
+            "https://search.test/ping?sitemap=https%3A%2F%2Fexample.com%2Fblog%2Fsitemap.xml"
+        );
+    }
+
+    #[test]
+    fn ping_url_keeps_existing_query_parameters() {
+        let url = ping_request_url("https://search.test/ping?key=abc", "https://example.com/")
+            .expect("valid endpoint");
+        assert_eq!(
+            url.as_str(),
+            "https://search.test/ping?key=abc&sitemap=https%3A%2F%2Fexample.com%2F"
+        );
+    }
+}
@@ -4,23 +4,43 @@ use time::OffsetDateTime;
 use tracing::info;
 
 use crate::{
-    application::repos::{JobsRepo, RepoError},
+    application::{
+        actor::{self, Actor},
+        admin::{pages::AdminPageError, posts::AdminPostError},
+        repos::{JobsRepo, RepoError},
+    },
     domain::types::JobType,
 };
 
 use super::{
-    context::{JobWorkerContext, job_failed},
+    context::{JobWorkerContext, job_aborted, job_failed},
     queue::enqueue_job,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublishPostJobPayload {
     pub slug: String,
+    /// Schedule the job was enqueued for; absent in payloads written before
+    /// publish jobs became idempotent.
+    #[serde(default)]
+    pub publish_at: Option<OffsetDateTime>,
+    /// Who scheduled the publication, added by [`enqueue_job`]; absent when
+    /// the system did, or in payloads written before jobs carried an actor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<Actor>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublishPageJobPayload {
     pub slug: String,
+    /// Schedule the job was enqueued for; absent in payloads written before
+    /// publish jobs became idempotent.
+    #[serde(default)]
+    pub publish_at: Option<OffsetDateTime>,
+    /// Who scheduled the publication, added by [`enqueue_job`]; absent when
+    /// the system did, or in payloads written before jobs carried an actor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<Actor>,
 }
 
 pub async fn enqueue_publish_post_job<J: JobsRepo + ?Sized>(
@@ -28,7 +48,11 @@ pub async fn enqueue_publish_post_job<J: JobsRepo + ?Sized>(
     slug: String,
     run_at: OffsetDateTime,
 ) -> Result<String, RepoError> {
-    let payload = PublishPostJobPayload { slug };
+    let payload = PublishPostJobPayload {
+        slug,
+        publish_at: Some(run_at),
+        actor: None,
+    };
     enqueue_job(repo, JobType::PublishPost, &payload, Some(run_at), 10, 10).await
 }
 
@@ -37,7 +61,11 @@ pub async fn enqueue_publish_page_job<J: JobsRepo + ?Sized>(
     slug: String,
     run_at: OffsetDateTime,
 ) -> Result<String, RepoError> {
-    let payload = PublishPageJobPayload { slug };
+    let payload = PublishPageJobPayload {
+        slug,
+        publish_at: Some(run_at),
+        actor: None,
+    };
     enqueue_job(repo, JobType::PublishPage, &payload, Some(run_at), 10, 10).await
 }
 
@@ -46,14 +74,23 @@ pub async fn process_publish_post_job(
     context: Data<JobWorkerContext>,
 ) -> Result<(), ApalisError> {
     let ctx = &*context;
-    ctx.admin_posts
-        .publish_scheduled_by_slug(&payload.slug)
-        .await
-        .map_err(job_failed)?;
+    let actor = payload.actor.unwrap_or_else(Actor::system);
+    let published = actor::scope(
+        actor.clone(),
+        ctx.admin_posts
+            .publish_scheduled_by_slug(&actor.name, &payload.slug, payload.publish_at),
+    )
+    .await
+    .map_err(|err| match &err {
+        AdminPostError::Repo(repo) if repo.is_transient() => job_failed(err),
+        _ => job_aborted(err),
+    })?;
 
     info!(
         target = "application::jobs::process_publish_post_job",
         slug = payload.slug,
+        actor = actor.name,
+        already_done = published.is_none(),
         "post published"
     );
 
@@ -65,14 +102,23 @@ pub async fn process_publish_page_job(
     context: Data<JobWorkerContext>,
 ) -> Result<(), ApalisError> {
     let ctx = &*context;
-    ctx.admin_pages
-        .publish_scheduled_by_slug(&payload.slug)
-        .await
-        .map_err(job_failed)?;
+    let actor = payload.actor.unwrap_or_else(Actor::system);
+    let published = actor::scope(
+        actor.clone(),
+        ctx.admin_pages
+            .publish_scheduled_by_slug(&actor.name, &payload.slug, payload.publish_at),
+    )
+    .await
+    .map_err(|err| match &err {
+        AdminPageError::Repo(repo) if repo.is_transient() => job_failed(err),
+        _ => job_aborted(err),
+    })?;
 
     info!(
         target = "application::jobs::process_publish_page_job",
         slug = payload.slug,
+        actor = actor.name,
+        already_done = published.is_none(),
         "page published"
     );
 
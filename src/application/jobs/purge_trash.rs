@@ -0,0 +1,96 @@
+//! Cron job for purging posts and pages that have sat in the trash past the
+//! configured retention.
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use apalis::prelude::*;
+use apalis_cron::Schedule;
+use time::OffsetDateTime;
+
+use crate::application::admin::pages::AdminPageService;
+use crate::application::admin::posts::AdminPostService;
+
+/// Marker struct for the cron-triggered purge job.
+/// Must implement `From<chrono::DateTime<chrono::Utc>>` for apalis-cron compatibility.
+#[derive(Default, Debug, Clone)]
+pub struct PurgeTrashJob;
+
+impl From<chrono::DateTime<chrono::Utc>> for PurgeTrashJob {
+    fn from(_: chrono::DateTime<chrono::Utc>) -> Self {
+        Self
+    }
+}
+
+/// Context for the purge job worker.
+#[derive(Clone)]
+pub struct PurgeTrashContext {
+    pub posts: Arc<AdminPostService>,
+    pub pages: Arc<AdminPageService>,
+    pub retention: Duration,
+}
+
+/// Process the purge job: permanently delete records trashed before the
+/// retention window.
+pub async fn process_purge_trash_job(
+    _job: PurgeTrashJob,
+    ctx: Data<PurgeTrashContext>,
+) -> Result<(), apalis::prelude::Error> {
+    let Some(cutoff) = purge_cutoff(OffsetDateTime::now_utc(), ctx.retention) else {
+        return Ok(());
+    };
+
+    match ctx.posts.purge_trash_before(cutoff).await {
+        Ok(count) if count > 0 => {
+            tracing::info!(purged_count = count, "Purged trashed posts");
+        }
+        Err(err) => {
+            tracing::warn!(error = %err, "Failed to purge trashed posts");
+        }
+        _ => {}
+    }
+    match ctx.pages.purge_trash_before(cutoff).await {
+        Ok(count) if count > 0 => {
+            tracing::info!(purged_count = count, "Purged trashed pages");
+        }
+        Err(err) => {
+            tracing::warn!(error = %err, "Failed to purge trashed pages");
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Records trashed before the returned instant are due for purging; `None`
+/// when the retention reaches back past the representable range.
+fn purge_cutoff(now: OffsetDateTime, retention: Duration) -> Option<OffsetDateTime> {
+    now.checked_sub(time::Duration::try_from(retention).ok()?)
+}
+
+/// Create the cron schedule for trash purging.
+/// Runs daily at 03:30: "0 30 3 * * *"
+pub fn purge_trash_schedule() -> Schedule {
+    Schedule::from_str("0 30 3 * * *").expect("Invalid cron expression for purge_trash")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_parses_correctly() {
+        let schedule = purge_trash_schedule();
+        let upcoming: Vec<_> = schedule.upcoming(chrono::Utc).take(3).collect();
+        assert_eq!(upcoming.len(), 3);
+    }
+
+    #[test]
+    fn cutoff_lies_one_retention_before_now() {
+        let now = OffsetDateTime::from_unix_timestamp(1_760_616_000).expect("timestamp");
+        let cutoff = purge_cutoff(now, Duration::from_secs(30 * 86_400)).expect("cutoff");
+        assert_eq!(now - cutoff, time::Duration::days(30));
+
+        assert!(purge_cutoff(now, Duration::from_secs(u64::MAX)).is_none());
+    }
+}
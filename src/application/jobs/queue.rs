@@ -4,7 +4,11 @@ use time::OffsetDateTime;
 use tokio::time::{Instant, sleep};
 
 use crate::{
-    application::repos::{JobsRepo, NewJobRecord, RepoError},
+    application::{
+        actor,
+        repos::{JobsRepo, NewJobRecord, RepoError},
+        request_id,
+    },
     domain::types::{JobState, JobType},
 };
 
@@ -23,8 +27,10 @@ where
     J: JobsRepo + ?Sized,
     P: serde::Serialize,
 {
-    let payload = serde_json::to_value(payload)
+    let mut payload = serde_json::to_value(payload)
         .map_err(|err| RepoError::from_persistence(err.to_string()))?;
+    request_id::tag_job_payload(&mut payload);
+    actor::tag_job_payload(&mut payload);
     let record = NewJobRecord {
         job_type,
         payload,
@@ -0,0 +1,325 @@
+//! Where a post's output stands, read from its latest render and publish jobs.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use time::OffsetDateTime;
+use tokio::time::{Instant, sleep};
+
+use crate::{
+    application::repos::{JobsRepo, RepoError},
+    domain::{
+        entities::{JobRecord, PostRecord},
+        types::{JobState, JobType, RenderStatus},
+    },
+};
+
+/// Jobs whose outcome decides a post's render progress.
+pub const POST_RENDER_JOB_TYPES: [JobType; 2] = [JobType::RenderPost, JobType::PublishPost];
+
+/// Recent jobs inspected per post; older ones have long been superseded.
+const RECENT_JOB_LIMIT: u32 = 10;
+
+const RENDER_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderProgressState {
+    /// A job is due (or due for a retry) but no worker has picked it up.
+    Pending,
+    /// A worker is running a job.
+    Rendering,
+    /// The latest render succeeded.
+    Rendered,
+    /// The latest render or publish failed for good.
+    Failed,
+}
+
+impl RenderProgressState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Rendering => "rendering",
+            Self::Rendered => "rendered",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Whether a job is still on its way.
+    pub fn is_in_flight(self) -> bool {
+        matches!(self, Self::Pending | Self::Rendering)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenderProgress {
+    pub state: RenderProgressState,
+    /// Job the state was read from; `None` when it comes from the stored
+    /// render status because no deciding job is on record.
+    pub job_id: Option<String>,
+    /// Error of the failed job, or of the failed run a pending retry follows.
+    pub error: Option<String>,
+}
+
+impl RenderProgress {
+    /// Derive the progress from `jobs` in any order; ULID job ids tell which
+    /// was enqueued last.
+    ///
+    /// A running job wins over a waiting one, and either wins over finished
+    /// jobs. Otherwise the newest failed job or successful render decides: a
+    /// successful publish says nothing about the rendered output. With no
+    /// deciding job, `stored` (the post's own render status) is reported.
+    /// Jobs scheduled for later than `now` are ignored.
+    pub fn derive(
+        jobs: &[JobRecord],
+        now: OffsetDateTime,
+        stored: RenderStatus,
+        stored_error: Option<&str>,
+    ) -> Self {
+        let mut jobs: Vec<&JobRecord> = jobs.iter().collect();
+        jobs.sort_by(|a, b| b.id.cmp(&a.id));
+
+        if let Some(job) = jobs.iter().find(|job| job.state == JobState::Running) {
+            return Self::from_job(RenderProgressState::Rendering, job);
+        }
+        if let Some(job) = jobs.iter().find(|job| is_waiting(job, now)) {
+            return Self::from_job(RenderProgressState::Pending, job);
+        }
+
+        let deciding = jobs.iter().find_map(|job| match job.state {
+            JobState::Failed | JobState::Killed => Some((RenderProgressState::Failed, job)),
+            JobState::Done if job.job_type != JobType::PublishPost => {
+                Some((RenderProgressState::Rendered, job))
+            }
+            _ => None,
+        });
+        match deciding {
+            Some((state, job)) => Self::from_job(state, job),
+            None => match stored {
+                RenderStatus::Ok => Self {
+                    state: RenderProgressState::Rendered,
+                    job_id: None,
+                    error: None,
+                },
+                RenderStatus::Failed => Self {
+                    state: RenderProgressState::Failed,
+                    job_id: None,
+                    error: stored_error.map(str::to_string),
+                },
+            },
+        }
+    }
+
+    fn from_job(state: RenderProgressState, job: &JobRecord) -> Self {
+        Self {
+            state,
+            job_id: Some(job.id.clone()),
+            error: match state {
+                RenderProgressState::Rendered => None,
+                _ => job.last_error.clone(),
+            },
+        }
+    }
+}
+
+/// Due and not yet running: pending, or failed with retries left.
+fn is_waiting(job: &JobRecord, now: OffsetDateTime) -> bool {
+    match job.state {
+        JobState::Pending | JobState::Scheduled => job.run_at <= now,
+        JobState::Failed => job.attempts < job.max_attempts,
+        _ => false,
+    }
+}
+
+/// Current render progress of `post`.
+pub async fn post_render_progress<J>(
+    repo: &J,
+    post: &PostRecord,
+) -> Result<RenderProgress, RepoError>
+where
+    J: JobsRepo + ?Sized,
+{
+    let jobs = repo
+        .latest_jobs_for_entity(&POST_RENDER_JOB_TYPES, &post.slug, RECENT_JOB_LIMIT)
+        .await?;
+    Ok(RenderProgress::derive(
+        &jobs,
+        OffsetDateTime::now_utc(),
+        post.render_status,
+        post.render_error.as_deref(),
+    ))
+}
+
+/// Poll until no job for `post` is in flight or `timeout` elapses, returning
+/// the last progress seen; a timeout is not an error.
+pub async fn wait_for_post_render<J>(
+    repo: &J,
+    post: &PostRecord,
+    timeout: Duration,
+) -> Result<RenderProgress, RepoError>
+where
+    J: JobsRepo + ?Sized,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        let progress = post_render_progress(repo, post).await?;
+        if !progress.state.is_in_flight() || Instant::now() >= deadline {
+            return Ok(progress);
+        }
+        sleep(RENDER_WAIT_POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    const NOW: OffsetDateTime = datetime!(2026-05-01 12:00 UTC);
+
+    fn job(id: &str, job_type: JobType, state: JobState) -> JobRecord {
+        JobRecord {
+            id: id.to_string(),
+            job_type,
+            payload: serde_json::json!({ "slug": "hello" }),
+            state,
+            attempts: 1,
+            max_attempts: 3,
+            run_at: datetime!(2026-05-01 11:59 UTC),
+            lock_at: None,
+            lock_by: None,
+            done_at: None,
+            last_error: (state == JobState::Failed || state == JobState::Killed)
+                .then(|| format!("{id} failed")),
+            priority: 0,
+        }
+    }
+
+    fn derive(jobs: &[JobRecord]) -> RenderProgress {
+        RenderProgress::derive(jobs, NOW, RenderStatus::Ok, None)
+    }
+
+    #[test]
+    fn the_newest_finished_render_decides_regardless_of_record_order() {
+        let failed = job(
+            "01J0000000000000000000000A",
+            JobType::RenderPost,
+            JobState::Killed,
+        );
+        let done = job(
+            "01J0000000000000000000000B",
+            JobType::RenderPost,
+            JobState::Done,
+        );
+
+        let progress = derive(&[done.clone(), failed.clone()]);
+        assert_eq!(progress.state, RenderProgressState::Rendered);
+        assert_eq!(progress.job_id.as_deref(), Some(done.id.as_str()));
+        assert_eq!(progress, derive(&[failed.clone(), done.clone()]));
+
+        let newer_failure = job(
+            "01J0000000000000000000000C",
+            JobType::RenderPost,
+            JobState::Killed,
+        );
+        let progress = derive(&[newer_failure, done, failed]);
+        assert_eq!(progress.state, RenderProgressState::Failed);
+        assert_eq!(
+            progress.error.as_deref(),
+            Some("01J0000000000000000000000C failed")
+        );
+    }
+
+    #[test]
+    fn in_flight_jobs_win_over_newer_finished_ones() {
+        let running = job(
+            "01J0000000000000000000000A",
+            JobType::RenderPost,
+            JobState::Running,
+        );
+        let pending = job(
+            "01J0000000000000000000000B",
+            JobType::PublishPost,
+            JobState::Pending,
+        );
+        let done = job(
+            "01J0000000000000000000000C",
+            JobType::RenderPost,
+            JobState::Done,
+        );
+
+        assert_eq!(
+            derive(&[done.clone(), pending.clone(), running]).state,
+            RenderProgressState::Rendering
+        );
+        assert_eq!(derive(&[done, pending]).state, RenderProgressState::Pending);
+    }
+
+    #[test]
+    fn a_failed_job_with_retries_left_is_pending_with_its_error() {
+        let mut retrying = job(
+            "01J0000000000000000000000B",
+            JobType::RenderPost,
+            JobState::Failed,
+        );
+        let done = job(
+            "01J0000000000000000000000A",
+            JobType::RenderPost,
+            JobState::Done,
+        );
+
+        let progress = derive(&[done.clone(), retrying.clone()]);
+        assert_eq!(progress.state, RenderProgressState::Pending);
+        assert_eq!(
+            progress.error.as_deref(),
+            Some("01J0000000000000000000000B failed")
+        );
+
+        retrying.attempts = retrying.max_attempts;
+        assert_eq!(derive(&[done, retrying]).state, RenderProgressState::Failed);
+    }
+
+    #[test]
+    fn publish_jobs_scheduled_later_or_done_do_not_decide() {
+        let mut scheduled = job(
+            "01J0000000000000000000000C",
+            JobType::PublishPost,
+            JobState::Pending,
+        );
+        scheduled.run_at = datetime!(2026-06-01 00:00 UTC);
+        let published = job(
+            "01J0000000000000000000000B",
+            JobType::PublishPost,
+            JobState::Done,
+        );
+        let failed = job(
+            "01J0000000000000000000000A",
+            JobType::RenderPost,
+            JobState::Killed,
+        );
+
+        let progress = derive(&[scheduled, published, failed.clone()]);
+        assert_eq!(progress.state, RenderProgressState::Failed);
+        assert_eq!(progress.job_id.as_deref(), Some(failed.id.as_str()));
+    }
+
+    #[test]
+    fn without_jobs_the_stored_render_status_is_reported() {
+        assert_eq!(
+            RenderProgress::derive(&[], NOW, RenderStatus::Ok, None),
+            RenderProgress {
+                state: RenderProgressState::Rendered,
+                job_id: None,
+                error: None,
+            }
+        );
+        assert_eq!(
+            RenderProgress::derive(&[], NOW, RenderStatus::Failed, Some("bad math")),
+            RenderProgress {
+                state: RenderProgressState::Failed,
+                job_id: None,
+                error: Some("bad math".to_string()),
+            }
+        );
+    }
+}
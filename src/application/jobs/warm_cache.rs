@@ -0,0 +1,59 @@
+//! Cron job that re-warms the cache for the configured hot paths.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use apalis::prelude::*;
+use apalis_cron::Schedule;
+
+use crate::cache::CacheWarmer;
+
+/// Marker struct for the cron-triggered warm cycle.
+/// Must implement `From<chrono::DateTime<chrono::Utc>>` for apalis-cron compatibility.
+#[derive(Default, Debug, Clone)]
+pub struct WarmCacheJob;
+
+impl From<chrono::DateTime<chrono::Utc>> for WarmCacheJob {
+    fn from(_: chrono::DateTime<chrono::Utc>) -> Self {
+        Self
+    }
+}
+
+/// Context for the warm cycle worker.
+#[derive(Clone)]
+pub struct WarmCacheContext {
+    pub warmer: Arc<CacheWarmer>,
+}
+
+/// Process the warm cycle: refresh L0 and render every hot path into L1.
+pub async fn process_warm_cache_job(
+    _job: WarmCacheJob,
+    ctx: Data<WarmCacheContext>,
+) -> Result<(), apalis::prelude::Error> {
+    let warmed = ctx.warmer.warm_cycle().await;
+    tracing::debug!(warmed, "Scheduled cache warm cycle finished");
+    Ok(())
+}
+
+/// Parse the cron schedule for the warm cycle from `cache.warmup_cron`.
+pub fn warm_cache_schedule(expression: &str) -> Result<Schedule, String> {
+    Schedule::from_str(expression)
+        .map_err(|err| format!("invalid cron expression `{expression}`: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_parses_default_expression() {
+        let schedule = warm_cache_schedule("0 */5 * * * *").expect("valid schedule");
+        let upcoming: Vec<_> = schedule.upcoming(chrono::Utc).take(2).collect();
+        assert_eq!(upcoming.len(), 2);
+    }
+
+    #[test]
+    fn schedule_rejects_invalid_expression() {
+        assert!(warm_cache_schedule("every five minutes").is_err());
+    }
+}
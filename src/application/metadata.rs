@@ -1,4 +1,5 @@
-//! Shared asset metadata extraction infrastructure.
+//! Shared asset metadata extraction infrastructure, and structured data for
+//! public pages in [`structured_data`].
 
 use std::{borrow::Cow, num::NonZeroU32, path::Path};
 
@@ -10,6 +11,9 @@ use crate::domain::uploads::{
     METADATA_HEIGHT, METADATA_WIDTH, MetadataValidationError, UploadMetadata,
 };
 
+#[path = "metadata/structured_data.rs"]
+pub mod structured_data;
+
 pub(crate) const MAX_DIMENSION: u32 = 10_000;
 
 /// Global registry accessor.
@@ -0,0 +1,379 @@
+//! schema.org structured data (JSON-LD) for public pages.
+//!
+//! Services assemble typed nodes into a [`StructuredData`] graph; templates
+//! only embed the serialized result in a `<script type="application/ld+json">`.
+
+use serde::Serialize;
+use time::OffsetDateTime;
+
+const SCHEMA_CONTEXT: &str = "https://schema.org";
+
+/// A JSON-LD document holding one or more nodes in `@graph`.
+#[derive(Debug, Clone, Serialize)]
+pub struct StructuredData {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(rename = "@graph")]
+    graph: Vec<Node>,
+}
+
+impl Default for StructuredData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StructuredData {
+    pub fn new() -> Self {
+        Self {
+            context: SCHEMA_CONTEXT,
+            graph: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, node: impl Into<Node>) -> Self {
+        self.graph.push(node.into());
+        self
+    }
+
+    /// Serialized document, safe to place inside a `<script>` element; `None`
+    /// when no node was added.
+    pub fn to_script_json(&self) -> Option<String> {
+        if self.graph.is_empty() {
+            return None;
+        }
+        // `<` is escaped so content such as `</script>` cannot end the element.
+        serde_json::to_string(self).ok().map(|json| {
+            json.replace('<', "\\u003c")
+                .replace('>', "\\u003e")
+                .replace('&', "\\u0026")
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Node {
+    Blog(Blog),
+    BlogPosting(BlogPosting),
+    WebSite(WebSite),
+    BreadcrumbList(BreadcrumbList),
+}
+
+impl From<Blog> for Node {
+    fn from(node: Blog) -> Self {
+        Self::Blog(node)
+    }
+}
+
+impl From<BlogPosting> for Node {
+    fn from(node: BlogPosting) -> Self {
+        Self::BlogPosting(node)
+    }
+}
+
+impl From<WebSite> for Node {
+    fn from(node: WebSite) -> Self {
+        Self::WebSite(node)
+    }
+}
+
+impl From<BreadcrumbList> for Node {
+    fn from(node: BreadcrumbList) -> Self {
+        Self::BreadcrumbList(node)
+    }
+}
+
+/// The site itself, emitted on the home page.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct WebSite {
+    name: String,
+    url: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    potential_action: Option<SearchAction>,
+}
+
+impl WebSite {
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            description: String::new(),
+            potential_action: None,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Advertise a site search; `url_template` contains `{search_term_string}`
+    /// where the query goes.
+    pub fn search_action(mut self, url_template: impl Into<String>) -> Self {
+        self.potential_action = Some(SearchAction {
+            target: url_template.into(),
+            query_input: "required name=search_term_string",
+        });
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "kebab-case")]
+struct SearchAction {
+    target: String,
+    query_input: &'static str,
+}
+
+/// A listing of posts.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Blog {
+    name: String,
+    url: String,
+    blog_post: Vec<BlogPosting>,
+}
+
+impl Blog {
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            blog_post: Vec::new(),
+        }
+    }
+
+    pub fn post(mut self, post: BlogPosting) -> Self {
+        self.blog_post.push(post);
+        self
+    }
+}
+
+/// One post. Dates serialize as RFC 3339.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct BlogPosting {
+    headline: String,
+    url: String,
+    #[serde(with = "time::serde::rfc3339")]
+    date_published: OffsetDateTime,
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    date_modified: Option<OffsetDateTime>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<Person>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    word_count: Option<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    keywords: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_language: Option<String>,
+}
+
+impl BlogPosting {
+    pub fn new(
+        headline: impl Into<String>,
+        url: impl Into<String>,
+        date_published: OffsetDateTime,
+    ) -> Self {
+        Self {
+            headline: headline.into(),
+            url: url.into(),
+            date_published,
+            date_modified: None,
+            description: String::new(),
+            author: None,
+            image: None,
+            word_count: None,
+            keywords: Vec::new(),
+            in_language: None,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn date_modified(mut self, date_modified: OffsetDateTime) -> Self {
+        self.date_modified = Some(date_modified);
+        self
+    }
+
+    pub fn author(mut self, name: impl Into<String>) -> Self {
+        self.author = Some(Person { name: name.into() });
+        self
+    }
+
+    /// Absolute URL of the post's social image.
+    pub fn image(mut self, url: impl Into<String>) -> Self {
+        self.image = Some(url.into());
+        self
+    }
+
+    /// Word count of a Markdown body, ignoring tokens without letters or
+    /// digits such as `##`, list markers and code fences.
+    pub fn word_count_of(mut self, markdown: &str) -> Self {
+        let words = markdown
+            .split_whitespace()
+            .filter(|token| token.chars().any(char::is_alphanumeric))
+            .count();
+        self.word_count = Some(words);
+        self
+    }
+
+    pub fn keywords<I, S>(mut self, keywords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.keywords = keywords.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn in_language(mut self, lang: Option<String>) -> Self {
+        self.in_language = lang;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type")]
+struct Person {
+    name: String,
+}
+
+/// Trail from the home page to the current listing.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct BreadcrumbList {
+    item_list_element: Vec<ListItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "@type")]
+struct ListItem {
+    position: usize,
+    name: String,
+    item: String,
+}
+
+impl BreadcrumbList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append the next crumb; positions count from 1.
+    pub fn item(mut self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        self.item_list_element.push(ListItem {
+            position: self.item_list_element.len() + 1,
+            name: name.into(),
+            item: url.into(),
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn parse(data: &StructuredData) -> Value {
+        let json = data.to_script_json().expect("graph is not empty");
+        serde_json::from_str(&json).expect("valid json")
+    }
+
+    fn assert_rfc3339(value: &Value) {
+        let text = value.as_str().expect("date is a string");
+        chrono::DateTime::parse_from_rfc3339(text).expect("RFC 3339 date");
+    }
+
+    #[test]
+    fn blog_posting_carries_required_fields_and_rfc3339_dates() {
+        let posting = BlogPosting::new(
+            "Hello",
+            "https://example.com/posts/hello",
+            datetime!(2025-01-05 12:00 UTC),
+        )
+        .date_modified(datetime!(2025-01-06 08:30 +02:00))
+        .word_count_of("## Intro\n\nTwo words - and `code`\n\n```\n```")
+        .keywords(["rust"])
+        .author("Ada")
+        .image("https://example.com/og.png");
+        let json = parse(&StructuredData::new().with(posting));
+
+        assert_eq!(json["@context"], "https://schema.org");
+        let node = &json["@graph"][0];
+        assert_eq!(node["@type"], "BlogPosting");
+        assert_eq!(node["headline"], "Hello");
+        assert_eq!(node["url"], "https://example.com/posts/hello");
+        assert_rfc3339(&node["datePublished"]);
+        assert_rfc3339(&node["dateModified"]);
+        assert_eq!(node["wordCount"], 5);
+        assert_eq!(node["keywords"][0], "rust");
+        assert_eq!(node["author"]["@type"], "Person");
+        assert_eq!(node["author"]["name"], "Ada");
+        assert_eq!(node["image"], "https://example.com/og.png");
+        assert!(node.get("description").is_none());
+        assert!(node.get("inLanguage").is_none());
+    }
+
+    #[test]
+    fn breadcrumbs_are_numbered_from_one() {
+        let crumbs = BreadcrumbList::new()
+            .item("Home", "https://example.com/")
+            .item("rust", "https://example.com/tags/rust");
+        let json = parse(&StructuredData::new().with(crumbs));
+
+        let items = json["@graph"][0]["itemListElement"]
+            .as_array()
+            .expect("items");
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["@type"], "ListItem");
+        assert_eq!(items[0]["position"], 1);
+        assert_eq!(items[1]["position"], 2);
+        assert_eq!(items[1]["item"], "https://example.com/tags/rust");
+    }
+
+    #[test]
+    fn script_json_cannot_close_the_script_element() {
+        let site = WebSite::new("</script><b>", "https://example.com/");
+        let json = StructuredData::new()
+            .with(site)
+            .to_script_json()
+            .expect("json");
+
+        assert!(!json.contains("</script>"));
+        let parsed: Value = serde_json::from_str(&json).expect("valid json");
+        assert_eq!(parsed["@graph"][0]["name"], "</script><b>");
+        assert!(StructuredData::new().to_script_json().is_none());
+    }
+
+    #[test]
+    fn website_search_action_names_the_query_input() {
+        let site = WebSite::new("Site", "https://example.com/")
+            .search_action("https://example.com/search?q={search_term_string}");
+        let json = parse(&StructuredData::new().with(site));
+
+        let action = &json["@graph"][0]["potentialAction"];
+        assert_eq!(action["@type"], "SearchAction");
+        assert_eq!(
+            action["target"],
+            "https://example.com/search?q={search_term_string}"
+        );
+        assert_eq!(action["query-input"], "required name=search_term_string");
+    }
+}
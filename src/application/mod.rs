@@ -1,16 +1,19 @@
 //! Application services layer scaffolding.
 
+pub mod actor;
 pub mod admin;
 pub mod api_keys;
 pub mod chrome;
 pub mod error;
 pub mod feed;
+pub mod format;
 pub mod jobs;
 pub mod metadata;
 pub mod page;
 pub mod pagination;
 pub mod render;
 pub mod repos;
+pub mod request_id;
 pub mod site;
 pub mod sitemap;
 pub mod snapshot_preview;
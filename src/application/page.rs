@@ -6,8 +6,10 @@ use uuid::Uuid;
 use crate::application::error::HttpError;
 use crate::application::repos::{PagesRepo, RepoError};
 use crate::cache::L0Store;
+use crate::domain::entities::PageRecord;
+use crate::domain::translations::normalize_lang;
 use crate::domain::types::PageStatus;
-use crate::presentation::views::PageView;
+use crate::presentation::views::{PageView, TranslationLinkView, build_translation_links};
 
 const SOURCE: &str = "application::page::PageService";
 
@@ -56,6 +58,7 @@ impl PageService {
             return Ok(None);
         }
 
+        let translations = self.page_translations(&record).await?;
         let rendered_html = record.rendered_html;
         let (contains_code, contains_math, contains_mermaid) = render_feature_flags(&rendered_html);
 
@@ -65,9 +68,54 @@ impl PageService {
             contains_code,
             contains_math,
             contains_mermaid,
+            lang: record.lang,
+            translations,
+            template: record.template,
         }))
     }
 
+    /// Slug of the variant of `slug` to serve for `lang`: its published
+    /// translation in that language when there is one, otherwise `slug`
+    /// itself. `None` when `slug` is not a published page.
+    pub async fn translation_slug(
+        &self,
+        slug: &str,
+        lang: &str,
+    ) -> Result<Option<String>, HttpError> {
+        let Some(record) = self
+            .pages
+            .find_by_slug(slug)
+            .await
+            .map_err(|err| repo_failure("find_by_slug", err))?
+        else {
+            return Ok(None);
+        };
+        if record.status != PageStatus::Published || record.published_at.is_none() {
+            return Ok(None);
+        }
+        let (Some(lang), Some(group)) = (normalize_lang(lang), record.translation_group) else {
+            return Ok(Some(record.slug));
+        };
+        if record
+            .lang
+            .as_deref()
+            .is_some_and(|current| current.eq_ignore_ascii_case(&lang))
+        {
+            return Ok(Some(record.slug));
+        }
+
+        let translations = self
+            .pages
+            .list_page_translations(group)
+            .await
+            .map_err(|err| repo_failure("list_page_translations", err))?;
+        let slug = translations
+            .into_iter()
+            .find(|translation| translation.lang.eq_ignore_ascii_case(&lang))
+            .map_or(record.slug, |translation| translation.slug);
+        Ok(Some(slug))
+    }
+
     pub async fn page_preview(&self, id: Uuid) -> Result<Option<PageView>, HttpError> {
         let record = self
             .pages
@@ -84,9 +132,34 @@ impl PageService {
                 contains_code,
                 contains_math,
                 contains_mermaid,
+                lang: record.lang,
+                translations: Vec::new(),
+                template: record.template,
             }
         }))
     }
+
+    async fn page_translations(
+        &self,
+        record: &PageRecord,
+    ) -> Result<Vec<TranslationLinkView>, HttpError> {
+        let Some(group) = record.translation_group else {
+            return Ok(Vec::new());
+        };
+
+        // Any page change invalidates the sitemap, so depending on it keeps
+        // the alternates in step with every sibling.
+        crate::cache::deps::record(crate::cache::EntityKey::Sitemap);
+
+        let translations = self
+            .pages
+            .list_page_translations(group)
+            .await
+            .map_err(|err| repo_failure("list_page_translations", err))?;
+        Ok(build_translation_links(translations.iter().map(
+            |translation| (translation.lang.as_str(), translation.slug.as_str()),
+        )))
+    }
 }
 
 fn repo_failure(operation: &'static str, err: RepoError) -> HttpError {
@@ -119,8 +192,9 @@ mod tests {
     use crate::application::pagination::{CursorPage, PageCursor};
     use crate::application::repos::{PageQueryFilter, RepoError};
     use crate::cache::CacheConfig;
-    use crate::domain::entities::PageRecord;
+    use crate::domain::entities::{CalendarRecord, ScheduledRecord, TranslationRecord};
     use crate::domain::posts::MonthCount;
+    use crate::domain::types::RenderStatus;
 
     struct StubPagesRepo {
         calls: Arc<AtomicUsize>,
@@ -167,6 +241,28 @@ mod tests {
         ) -> Result<Vec<MonthCount>, RepoError> {
             Ok(Vec::new())
         }
+
+        async fn list_page_translations(
+            &self,
+            _translation_group: Uuid,
+        ) -> Result<Vec<TranslationRecord>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_scheduled_pages(
+            &self,
+            _limit: u32,
+        ) -> Result<Vec<ScheduledRecord>, RepoError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_calendar_pages(
+            &self,
+            _from: OffsetDateTime,
+            _to: OffsetDateTime,
+        ) -> Result<Vec<CalendarRecord>, RepoError> {
+            Ok(Vec::new())
+        }
     }
 
     fn sample_page(slug: &str) -> PageRecord {
@@ -181,6 +277,12 @@ mod tests {
             scheduled_at: None,
             published_at: Some(now),
             archived_at: None,
+            lang: None,
+            translation_group: None,
+            template: "default".to_string(),
+            render_status: RenderStatus::Ok,
+            render_error: None,
+            render_failed_at: None,
             created_at: now,
             updated_at: now,
         }
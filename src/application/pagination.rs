@@ -1,17 +1,32 @@
 //! Shared cursor pagination helpers.
+//!
+//! A cursor token is its JSON payload followed by a truncated HMAC-SHA256 of
+//! the cursor kind and payload, base64url-encoded as one value. Tokens are
+//! verified before their payload is parsed, so a tampered cursor, or one from
+//! another listing, is rejected as invalid rather than read. The key is a
+//! [`CursorKeys`] handed to the repositories that issue cursors and to the
+//! handlers and services that read them back.
+
+use std::sync::Arc;
 
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, KeyInit, Mac};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use thiserror::Error;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::domain::types::PostStatus;
+use crate::util::secret::random_secret;
 
 #[path = "pagination/api_key.rs"]
 mod api_key;
 #[path = "pagination/audit.rs"]
 mod audit;
+#[path = "pagination/comment.rs"]
+mod comment;
 #[path = "pagination/job.rs"]
 mod job;
 #[path = "pagination/navigation.rs"]
@@ -24,17 +39,21 @@ mod post;
 mod snapshot;
 #[path = "pagination/tag.rs"]
 mod tag;
+#[path = "pagination/trash.rs"]
+mod trash;
 #[path = "pagination/upload.rs"]
 mod upload;
 
 pub use api_key::ApiKeyCursor;
 pub use audit::AuditCursor;
+pub use comment::CommentCursor;
 pub use job::JobCursor;
 pub use navigation::NavigationCursor;
 pub use page::PageCursor;
 pub use post::PostCursor;
 pub use snapshot::SnapshotCursor;
 pub use tag::TagCursor;
+pub use trash::TrashCursor;
 pub use upload::UploadCursor;
 /// Cursor-aware pagination request.
 #[derive(Debug, Clone, Copy)]
@@ -75,31 +94,116 @@ pub enum PaginationError {
     InvalidCursor(String),
 }
 
+const CURSOR_CONTEXT: &[u8] = b"pagination-cursor:";
+const SIGNATURE_LEN: usize = 16;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// HMAC key cursor tokens are signed with.
+///
+/// Every node serving the same site needs the same key, or a cursor issued
+/// by one node is rejected by the next.
+#[derive(Clone)]
+pub struct CursorKeys {
+    secret: Arc<[u8]>,
+}
+
+impl CursorKeys {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: Arc::from(secret.into()),
+        }
+    }
+
+    /// Keys backed by a random per-process secret; cursors stop verifying on
+    /// restart and on any other node.
+    pub fn ephemeral() -> Self {
+        Self::new(random_secret())
+    }
+
+    fn mac(&self, kind: &str) -> HmacSha256 {
+        let mut mac = <HmacSha256 as KeyInit>::new_from_slice(&self.secret)
+            .expect("hmac accepts keys of any length");
+        mac.update(CURSOR_CONTEXT);
+        mac.update(kind.as_bytes());
+        mac.update(b":");
+        mac
+    }
+
+    fn encode_payload<P: Serialize>(&self, kind: &str, payload: &P) -> String {
+        let mut bytes = serde_json::to_vec(payload).unwrap_or_else(|err| {
+            panic!("serializing {kind} cursor payload should succeed: {err}")
+        });
+        let mut mac = self.mac(kind);
+        mac.update(&bytes);
+        bytes.extend_from_slice(&mac.finalize().into_bytes()[..SIGNATURE_LEN]);
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    fn decode_payload<P: DeserializeOwned>(
+        &self,
+        kind: &str,
+        cursor: &str,
+    ) -> Result<P, PaginationError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
+        let Some(split) = bytes
+            .len()
+            .checked_sub(SIGNATURE_LEN)
+            .filter(|split| *split > 0)
+        else {
+            return Err(PaginationError::InvalidCursor(
+                "cursor is too short".to_string(),
+            ));
+        };
+        let (payload, signature) = bytes.split_at(split);
+        let mut mac = self.mac(kind);
+        mac.update(payload);
+        mac.verify_truncated_left(signature)
+            .map_err(|_| PaginationError::InvalidCursor("signature mismatch".to_string()))?;
+        serde_json::from_slice(payload)
+            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))
+    }
+}
+
+impl From<&crate::config::AdminAuthSettings> for CursorKeys {
+    fn from(settings: &crate::config::AdminAuthSettings) -> Self {
+        match settings.session_secret.as_ref() {
+            Some(secret) => Self::new(secret.as_bytes().to_vec()),
+            None => Self::ephemeral(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn post_cursor_round_trip_public() {
+        let keys = CursorKeys::ephemeral();
         let id = Uuid::new_v4();
         let when = OffsetDateTime::now_utc();
-        let cursor = PostCursor::published(when, id, true);
-        let encoded = cursor.encode();
-        let decoded = PostCursor::decode(&encoded).expect("decoded cursor");
+        let cursor = PostCursor::published(when, id, true, 3);
+        let encoded = cursor.encode(&keys);
+        let decoded = PostCursor::decode(&keys, &encoded).expect("decoded cursor");
 
         assert_eq!(decoded.id(), id);
         assert_eq!(decoded.sort_key(), when);
         assert_eq!(decoded.status(), Some(PostStatus::Published));
         assert!(decoded.pinned());
+        assert_eq!(decoded.pin_order(), 3);
     }
 
     #[test]
     fn page_cursor_round_trip() {
+        let keys = CursorKeys::ephemeral();
         let id = Uuid::new_v4();
         let primary_time = OffsetDateTime::now_utc();
         let cursor = PageCursor::new(primary_time, id);
-        let encoded = cursor.encode();
-        let decoded = PageCursor::decode(&encoded).expect("decoded page cursor");
+        let encoded = cursor.encode(&keys);
+        let decoded = PageCursor::decode(&keys, &encoded).expect("decoded page cursor");
 
         assert_eq!(decoded.primary_time(), primary_time);
         assert_eq!(decoded.id(), id);
@@ -107,11 +211,12 @@ mod tests {
 
     #[test]
     fn tag_cursor_round_trip() {
+        let keys = CursorKeys::ephemeral();
         let id = Uuid::new_v4();
         let primary_time = OffsetDateTime::now_utc();
         let cursor = TagCursor::new(true, primary_time, id);
-        let encoded = cursor.encode();
-        let decoded = TagCursor::decode(&encoded).expect("decoded tag cursor");
+        let encoded = cursor.encode(&keys);
+        let decoded = TagCursor::decode(&keys, &encoded).expect("decoded tag cursor");
 
         assert!(decoded.pinned());
         assert_eq!(decoded.primary_time(), primary_time);
@@ -120,11 +225,12 @@ mod tests {
 
     #[test]
     fn upload_cursor_round_trip() {
+        let keys = CursorKeys::ephemeral();
         let id = Uuid::new_v4();
         let created_at = OffsetDateTime::now_utc();
         let cursor = UploadCursor::new(created_at, id);
-        let encoded = cursor.encode();
-        let decoded = UploadCursor::decode(&encoded).expect("decoded upload cursor");
+        let encoded = cursor.encode(&keys);
+        let decoded = UploadCursor::decode(&keys, &encoded).expect("decoded upload cursor");
 
         assert_eq!(decoded.id(), id);
         assert_eq!(decoded.created_at(), created_at);
@@ -132,11 +238,12 @@ mod tests {
 
     #[test]
     fn navigation_cursor_round_trip() {
+        let keys = CursorKeys::ephemeral();
         let id = Uuid::new_v4();
         let primary_time = OffsetDateTime::now_utc();
         let cursor = NavigationCursor::new(10, primary_time, id);
-        let encoded = cursor.encode();
-        let decoded = NavigationCursor::decode(&encoded).expect("decoded navigation cursor");
+        let encoded = cursor.encode(&keys);
+        let decoded = NavigationCursor::decode(&keys, &encoded).expect("decoded navigation cursor");
 
         assert_eq!(decoded.sort_order(), 10);
         assert_eq!(decoded.primary_time(), primary_time);
@@ -145,10 +252,11 @@ mod tests {
 
     #[test]
     fn job_cursor_round_trip() {
+        let keys = CursorKeys::ephemeral();
         let when = OffsetDateTime::now_utc();
         let cursor = JobCursor::new(when, "job-1");
-        let encoded = cursor.encode();
-        let decoded = JobCursor::decode(&encoded).expect("decoded job cursor");
+        let encoded = cursor.encode(&keys);
+        let decoded = JobCursor::decode(&keys, &encoded).expect("decoded job cursor");
 
         assert_eq!(decoded.run_at(), when);
         assert_eq!(decoded.id(), "job-1");
@@ -156,23 +264,38 @@ mod tests {
 
     #[test]
     fn audit_cursor_round_trip() {
+        let keys = CursorKeys::ephemeral();
         let id = Uuid::new_v4();
         let when = OffsetDateTime::now_utc();
         let cursor = AuditCursor::new(when, id);
-        let encoded = cursor.encode();
-        let decoded = AuditCursor::decode(&encoded).expect("decoded audit cursor");
+        let encoded = cursor.encode(&keys);
+        let decoded = AuditCursor::decode(&keys, &encoded).expect("decoded audit cursor");
 
         assert_eq!(decoded.created_at(), when);
         assert_eq!(decoded.id(), id);
     }
 
+    #[test]
+    fn comment_cursor_round_trip() {
+        let keys = CursorKeys::ephemeral();
+        let id = Uuid::new_v4();
+        let created_at = OffsetDateTime::now_utc();
+        let cursor = CommentCursor::new(created_at, id);
+        let encoded = cursor.encode(&keys);
+        let decoded = CommentCursor::decode(&keys, &encoded).expect("decoded comment cursor");
+
+        assert_eq!(decoded.created_at(), created_at);
+        assert_eq!(decoded.id(), id);
+    }
+
     #[test]
     fn snapshot_cursor_round_trip() {
+        let keys = CursorKeys::ephemeral();
         let id = Uuid::new_v4();
         let when = OffsetDateTime::now_utc();
         let cursor = SnapshotCursor::new(when, id);
-        let encoded = cursor.encode();
-        let decoded = SnapshotCursor::decode(&encoded).expect("decoded snapshot cursor");
+        let encoded = cursor.encode(&keys);
+        let decoded = SnapshotCursor::decode(&keys, &encoded).expect("decoded snapshot cursor");
 
         assert_eq!(decoded.created_at(), when);
         assert_eq!(decoded.id(), id);
@@ -180,7 +303,53 @@ mod tests {
 
     #[test]
     fn decoding_invalid_cursor_reports_error() {
-        let err = PostCursor::decode("not-base64").expect_err("invalid cursor rejected");
+        let keys = CursorKeys::ephemeral();
+        let err = PostCursor::decode(&keys, "not-base64").expect_err("invalid cursor rejected");
         assert!(matches!(err, PaginationError::InvalidCursor(_)));
     }
+
+    #[test]
+    fn tampered_cursors_are_rejected() {
+        let keys = CursorKeys::ephemeral();
+        let encoded = PostCursor::published(OffsetDateTime::now_utc(), Uuid::new_v4(), false, 0)
+            .encode(&keys);
+        let bytes = URL_SAFE_NO_PAD.decode(&encoded).expect("base64 cursor");
+
+        for index in 0..bytes.len() {
+            let mut flipped = bytes.clone();
+            flipped[index] ^= 0x01;
+            let err = PostCursor::decode(&keys, &URL_SAFE_NO_PAD.encode(flipped))
+                .expect_err("bit-flipped cursor rejected");
+            assert!(
+                matches!(err, PaginationError::InvalidCursor(_)),
+                "byte {index}"
+            );
+        }
+
+        // An unsigned payload, as tokens were before signing.
+        let payload = &bytes[..bytes.len() - SIGNATURE_LEN];
+        assert!(PostCursor::decode(&keys, &URL_SAFE_NO_PAD.encode(payload)).is_err());
+        assert!(PostCursor::decode(&keys, "").is_err());
+    }
+
+    #[test]
+    fn cursors_do_not_verify_for_another_listing() {
+        let keys = CursorKeys::ephemeral();
+        let when = OffsetDateTime::now_utc();
+        let id = Uuid::new_v4();
+        // Comment and upload cursors share a payload shape.
+        let comment = CommentCursor::new(when, id).encode(&keys);
+        assert!(UploadCursor::decode(&keys, &comment).is_err());
+        assert!(CommentCursor::decode(&keys, &comment).is_ok());
+    }
+
+    #[test]
+    fn cursors_verify_only_under_the_keys_that_signed_them() {
+        let cursor = PageCursor::new(OffsetDateTime::now_utc(), Uuid::new_v4());
+        let encoded = cursor.encode(&CursorKeys::new(b"node-a".to_vec()));
+
+        assert!(PageCursor::decode(&CursorKeys::new(b"node-a".to_vec()), &encoded).is_ok());
+        assert!(PageCursor::decode(&CursorKeys::new(b"node-b".to_vec()), &encoded).is_err());
+        assert!(PageCursor::decode(&CursorKeys::ephemeral(), &encoded).is_err());
+    }
 }
@@ -26,22 +26,16 @@ impl AuditCursor {
         self.id
     }
 
-    pub fn encode(&self) -> String {
+    pub fn encode(&self, keys: &CursorKeys) -> String {
         let payload = AuditCursorPayload {
             created_at: self.created_at,
             id: self.id,
         };
-        let serialized =
-            serde_json::to_vec(&payload).expect("serializing audit cursor payload should succeed");
-        URL_SAFE_NO_PAD.encode(serialized)
+        keys.encode_payload("audit", &payload)
     }
 
-    pub fn decode(cursor: &str) -> Result<Self, PaginationError> {
-        let bytes = URL_SAFE_NO_PAD
-            .decode(cursor)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
-        let payload: AuditCursorPayload = serde_json::from_slice(&bytes)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
+    pub fn decode(keys: &CursorKeys, cursor: &str) -> Result<Self, PaginationError> {
+        let payload: AuditCursorPayload = keys.decode_payload("audit", cursor)?;
         Ok(Self {
             created_at: payload.created_at,
             id: payload.id,
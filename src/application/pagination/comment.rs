@@ -0,0 +1,44 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct CommentCursorPayload {
+    created_at: OffsetDateTime,
+    id: Uuid,
+}
+
+/// Cursor for paginating comments in reverse chronological order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentCursor {
+    created_at: OffsetDateTime,
+    id: Uuid,
+}
+
+impl CommentCursor {
+    pub fn new(created_at: OffsetDateTime, id: Uuid) -> Self {
+        Self { created_at, id }
+    }
+
+    pub fn created_at(&self) -> OffsetDateTime {
+        self.created_at
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn encode(&self, keys: &CursorKeys) -> String {
+        let payload = CommentCursorPayload {
+            created_at: self.created_at,
+            id: self.id,
+        };
+        keys.encode_payload("comment", &payload)
+    }
+
+    pub fn decode(keys: &CursorKeys, cursor: &str) -> Result<Self, PaginationError> {
+        let payload: CommentCursorPayload = keys.decode_payload("comment", cursor)?;
+        Ok(Self {
+            created_at: payload.created_at,
+            id: payload.id,
+        })
+    }
+}
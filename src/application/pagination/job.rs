@@ -29,22 +29,16 @@ impl JobCursor {
         &self.id
     }
 
-    pub fn encode(&self) -> String {
+    pub fn encode(&self, keys: &CursorKeys) -> String {
         let payload = JobCursorPayload {
             run_at: self.run_at,
             id: self.id.clone(),
         };
-        let serialized =
-            serde_json::to_vec(&payload).expect("serializing job cursor payload should succeed");
-        URL_SAFE_NO_PAD.encode(serialized)
+        keys.encode_payload("job", &payload)
     }
 
-    pub fn decode(cursor: &str) -> Result<Self, PaginationError> {
-        let bytes = URL_SAFE_NO_PAD
-            .decode(cursor)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
-        let payload: JobCursorPayload = serde_json::from_slice(&bytes)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
+    pub fn decode(keys: &CursorKeys, cursor: &str) -> Result<Self, PaginationError> {
+        let payload: JobCursorPayload = keys.decode_payload("job", cursor)?;
         Ok(Self {
             run_at: payload.run_at,
             id: payload.id,
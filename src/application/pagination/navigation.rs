@@ -36,23 +36,17 @@ impl NavigationCursor {
         self.id
     }
 
-    pub fn encode(&self) -> String {
+    pub fn encode(&self, keys: &CursorKeys) -> String {
         let payload = NavigationCursorPayload {
             sort_order: self.sort_order,
             primary_time: self.primary_time,
             id: self.id,
         };
-        let serialized = serde_json::to_vec(&payload)
-            .expect("serializing navigation cursor payload should succeed");
-        URL_SAFE_NO_PAD.encode(serialized)
+        keys.encode_payload("navigation", &payload)
     }
 
-    pub fn decode(cursor: &str) -> Result<Self, PaginationError> {
-        let bytes = URL_SAFE_NO_PAD
-            .decode(cursor)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
-        let payload: NavigationCursorPayload = serde_json::from_slice(&bytes)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
+    pub fn decode(keys: &CursorKeys, cursor: &str) -> Result<Self, PaginationError> {
+        let payload: NavigationCursorPayload = keys.decode_payload("navigation", cursor)?;
         Ok(Self {
             sort_order: payload.sort_order,
             primary_time: payload.primary_time,
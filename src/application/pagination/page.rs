@@ -26,22 +26,16 @@ impl PageCursor {
         self.id
     }
 
-    pub fn encode(&self) -> String {
+    pub fn encode(&self, keys: &CursorKeys) -> String {
         let payload = PageCursorPayload {
             primary_time: self.primary_time,
             id: self.id,
         };
-        let serialized =
-            serde_json::to_vec(&payload).expect("serializing page cursor payload should succeed");
-        URL_SAFE_NO_PAD.encode(serialized)
+        keys.encode_payload("page", &payload)
     }
 
-    pub fn decode(cursor: &str) -> Result<Self, PaginationError> {
-        let bytes = URL_SAFE_NO_PAD
-            .decode(cursor)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
-        let payload: PageCursorPayload = serde_json::from_slice(&bytes)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
+    pub fn decode(keys: &CursorKeys, cursor: &str) -> Result<Self, PaginationError> {
+        let payload: PageCursorPayload = keys.decode_payload("page", cursor)?;
         Ok(Self {
             primary_time: payload.primary_time,
             id: payload.id,
@@ -15,6 +15,8 @@ struct PostCursorPayload {
     id: Uuid,
     #[serde(default)]
     pinned: bool,
+    #[serde(default)]
+    pin_order: i32,
 }
 
 /// Cursor for paginating posts in public or administrative contexts.
@@ -25,28 +27,37 @@ pub struct PostCursor {
     sort_key: OffsetDateTime,
     id: Uuid,
     pinned: bool,
+    pin_order: i32,
 }
 
 impl PostCursor {
     /// Construct a cursor for public listings (published posts only).
-    pub fn published(sort_key: OffsetDateTime, id: Uuid, pinned: bool) -> Self {
+    pub fn published(sort_key: OffsetDateTime, id: Uuid, pinned: bool, pin_order: i32) -> Self {
         Self {
             scope: CursorScope::Public,
             status: Some(PostStatus::Published),
             sort_key,
             id,
             pinned,
+            pin_order,
         }
     }
 
     /// Construct a cursor for administrative listings scoped to a status filter.
-    pub fn admin(status: PostStatus, sort_key: OffsetDateTime, id: Uuid, pinned: bool) -> Self {
+    pub fn admin(
+        status: PostStatus,
+        sort_key: OffsetDateTime,
+        id: Uuid,
+        pinned: bool,
+        pin_order: i32,
+    ) -> Self {
         Self {
             scope: CursorScope::Admin,
             status: Some(status),
             sort_key,
             id,
             pinned,
+            pin_order,
         }
     }
 
@@ -66,31 +77,31 @@ impl PostCursor {
         self.pinned
     }
 
-    pub fn encode(&self) -> String {
+    pub fn pin_order(&self) -> i32 {
+        self.pin_order
+    }
+
+    pub fn encode(&self, keys: &CursorKeys) -> String {
         let payload = PostCursorPayload {
             scope: self.scope,
             status: self.status,
             sort_key: self.sort_key,
             id: self.id,
             pinned: self.pinned,
+            pin_order: self.pin_order,
         };
-        let serialized =
-            serde_json::to_vec(&payload).expect("serializing post cursor payload should succeed");
-        URL_SAFE_NO_PAD.encode(serialized)
+        keys.encode_payload("post", &payload)
     }
 
-    pub fn decode(cursor: &str) -> Result<Self, PaginationError> {
-        let bytes = URL_SAFE_NO_PAD
-            .decode(cursor)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
-        let payload: PostCursorPayload = serde_json::from_slice(&bytes)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
+    pub fn decode(keys: &CursorKeys, cursor: &str) -> Result<Self, PaginationError> {
+        let payload: PostCursorPayload = keys.decode_payload("post", cursor)?;
         Ok(Self {
             scope: payload.scope,
             status: payload.status,
             sort_key: payload.sort_key,
             id: payload.id,
             pinned: payload.pinned,
+            pin_order: payload.pin_order,
         })
     }
 }
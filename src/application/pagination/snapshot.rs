@@ -26,22 +26,16 @@ impl SnapshotCursor {
         self.id
     }
 
-    pub fn encode(&self) -> String {
+    pub fn encode(&self, keys: &CursorKeys) -> String {
         let payload = SnapshotCursorPayload {
             created_at: self.created_at,
             id: self.id,
         };
-        let serialized = serde_json::to_vec(&payload)
-            .expect("serializing snapshot cursor payload should succeed");
-        URL_SAFE_NO_PAD.encode(serialized)
+        keys.encode_payload("snapshot", &payload)
     }
 
-    pub fn decode(cursor: &str) -> Result<Self, PaginationError> {
-        let bytes = URL_SAFE_NO_PAD
-            .decode(cursor)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
-        let payload: SnapshotCursorPayload = serde_json::from_slice(&bytes)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
+    pub fn decode(keys: &CursorKeys, cursor: &str) -> Result<Self, PaginationError> {
+        let payload: SnapshotCursorPayload = keys.decode_payload("snapshot", cursor)?;
         Ok(Self {
             created_at: payload.created_at,
             id: payload.id,
@@ -37,23 +37,17 @@ impl TagCursor {
         self.id
     }
 
-    pub fn encode(&self) -> String {
+    pub fn encode(&self, keys: &CursorKeys) -> String {
         let payload = TagCursorPayload {
             pinned: self.pinned,
             primary_time: self.primary_time,
             id: self.id,
         };
-        let serialized =
-            serde_json::to_vec(&payload).expect("serializing tag cursor payload should succeed");
-        URL_SAFE_NO_PAD.encode(serialized)
+        keys.encode_payload("tag", &payload)
     }
 
-    pub fn decode(cursor: &str) -> Result<Self, PaginationError> {
-        let bytes = URL_SAFE_NO_PAD
-            .decode(cursor)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
-        let payload: TagCursorPayload = serde_json::from_slice(&bytes)
-            .map_err(|err| PaginationError::InvalidCursor(err.to_string()))?;
+    pub fn decode(keys: &CursorKeys, cursor: &str) -> Result<Self, PaginationError> {
+        let payload: TagCursorPayload = keys.decode_payload("tag", cursor)?;
         Ok(Self {
             pinned: payload.pinned,
             primary_time: payload.primary_time,
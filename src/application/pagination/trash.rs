@@ -0,0 +1,44 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct TrashCursorPayload {
+    deleted_at: OffsetDateTime,
+    id: Uuid,
+}
+
+/// Cursor for trash pagination (most recently trashed first).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrashCursor {
+    deleted_at: OffsetDateTime,
+    id: Uuid,
+}
+
+impl TrashCursor {
+    pub fn new(deleted_at: OffsetDateTime, id: Uuid) -> Self {
+        Self { deleted_at, id }
+    }
+
+    pub fn deleted_at(&self) -> OffsetDateTime {
+        self.deleted_at
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn encode(&self, keys: &CursorKeys) -> String {
+        let payload = TrashCursorPayload {
+            deleted_at: self.deleted_at,
+            id: self.id,
+        };
+        keys.encode_payload("trash", &payload)
+    }
+
+    pub fn decode(keys: &CursorKeys, cursor: &str) -> Result<Self, PaginationError> {
+        let payload: TrashCursorPayload = keys.decode_payload("trash", cursor)?;
+        Ok(Self {
+            deleted_at: payload.deleted_at,
+            id: payload.id,
+        })
+    }
+}
@@ -1,50 +1,139 @@
 use apalis::prelude::Error as ApalisError;
 use tokio::task::JoinHandle;
+use tracing::warn;
 use uuid::Uuid;
 
 use crate::{
     application::{
         jobs::{JobWorkerContext, job_failed},
-        repos::SettingsRepo,
+        render::ContentWarning,
+        repos::{RepoError, SettingsRepo},
     },
-    infra::db::{PersistedPostSectionOwned, PostgresRepositories},
+    infra::db::{PersistedPostSectionOwned, PostgresRepositories, map_sqlx_error},
 };
 
+use super::payloads::post_content_hash;
 use super::{JobConsistencyError, RenderedSection};
 
+/// Attempts at the persistence transaction before a contended render fails.
+pub(super) const PERSIST_ATTEMPTS: u32 = 3;
+
+#[cfg(test)]
+tokio::task_local! {
+    /// Test-only fail point: each persistence attempt in scope pops one error
+    /// and raises it right after the sections are written.
+    pub(super) static PERSIST_FAULTS: std::cell::RefCell<std::collections::VecDeque<RepoError>>;
+}
+
+/// What the sections being stored were rendered from.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct SectionsSource<'a> {
+    /// Sections hash recorded alongside the sections.
+    pub(super) hash: &'a str,
+    /// For sections stitched from an incremental render, the sections hash
+    /// of the stored sections it reused; they must still be in place.
+    pub(super) reused_hash: Option<&'a str>,
+}
+
+/// Store rendered sections, summary and content warnings for `post_id`,
+/// unless the post's markdown no longer hashes to `content_hash` or the
+/// sections an incremental render reused were replaced meanwhile. Returns
+/// whether the results were stored.
+///
+/// Sections, summary and warnings commit in one transaction, so readers
+/// never see a body whose sections and summary disagree. A transaction that
+/// loses a serialization or deadlock race is retried from the start.
 pub(super) async fn persist_sections_and_summary(
     repos: &PostgresRepositories,
     post_id: Uuid,
+    content_hash: &str,
+    source: SectionsSource<'_>,
     sections: &[PersistedPostSectionOwned],
     summary_html: Option<&str>,
-) -> Result<(), ApalisError> {
-    let mut tx = repos.begin().await.map_err(job_failed)?;
+    warnings: &[ContentWarning],
+) -> Result<bool, ApalisError> {
+    let mut attempt = 1;
+    loop {
+        match persist_once(
+            repos,
+            post_id,
+            content_hash,
+            source,
+            sections,
+            summary_html,
+            warnings,
+        )
+        .await
+        {
+            Err(RepoError::Contention) if attempt < PERSIST_ATTEMPTS => {
+                warn!(
+                    target = "application::render::persist_sections_and_summary",
+                    post_id = %post_id,
+                    attempt,
+                    "render persistence conflicted with a concurrent write; retrying"
+                );
+                attempt += 1;
+            }
+            result => return result.map_err(job_failed),
+        }
+    }
+}
+
+async fn persist_once(
+    repos: &PostgresRepositories,
+    post_id: Uuid,
+    content_hash: &str,
+    source: SectionsSource<'_>,
+    sections: &[PersistedPostSectionOwned],
+    summary_html: Option<&str>,
+    warnings: &[ContentWarning],
+) -> Result<bool, RepoError> {
+    let mut tx = repos.begin().await.map_err(map_sqlx_error)?;
 
     // Lock posts row first to align lock order with snapshot rollbacks.
-    repos
-        .lock_post_for_update(&mut tx, post_id)
-        .await
-        .map_err(job_failed)?;
+    let (body_markdown, summary_markdown) = repos.lock_post_for_update(&mut tx, post_id).await?;
+    if post_content_hash(&body_markdown, summary_markdown.as_deref()) != content_hash {
+        tx.rollback().await.map_err(map_sqlx_error)?;
+        return Ok(false);
+    }
+    if let Some(reused_hash) = source.reused_hash {
+        let stored_hash = repos.post_sections_hash(&mut tx, post_id).await?;
+        if stored_hash.as_deref() != Some(reused_hash) {
+            tx.rollback().await.map_err(map_sqlx_error)?;
+            return Ok(false);
+        }
+    }
 
     repos
         .replace_post_sections_bulk(&mut tx, post_id, sections)
-        .await
-        .map_err(job_failed)?;
+        .await?;
+
+    #[cfg(test)]
+    if let Some(fault) = PERSIST_FAULTS
+        .try_with(|faults| faults.borrow_mut().pop_front())
+        .ok()
+        .flatten()
+    {
+        // Dropping `tx` rolls it back, as an error from the next write would.
+        return Err(fault);
+    }
 
+    repos
+        .update_post_sections_hash(&mut tx, post_id, source.hash)
+        .await?;
     if let Some(summary_html) = summary_html {
         repos
             .update_post_summary_html(&mut tx, post_id, summary_html)
-            .await
-            .map_err(job_failed)?;
+            .await?;
     }
 
+    repos.clear_post_render_failure(&mut tx, post_id).await?;
     repos
-        .update_post_updated_at(&mut tx, post_id)
-        .await
-        .map_err(job_failed)?;
+        .replace_post_render_warnings(&mut tx, post_id, warnings)
+        .await?;
 
-    tx.commit().await.map_err(job_failed)?;
-    Ok(())
+    tx.commit().await.map_err(map_sqlx_error)?;
+    Ok(true)
 }
 
 pub(super) async fn join_children(
@@ -94,15 +183,5 @@ pub(super) async fn load_public_site_url(ctx: &JobWorkerContext) -> Result<Strin
         .load_site_settings()
         .await
         .map_err(job_failed)?;
-    Ok(normalize_public_site_url(&settings.public_site_url))
-}
-
-fn normalize_public_site_url(url: &str) -> String {
-    let trimmed = url.trim();
-    if trimmed.is_empty() {
-        return String::new();
-    }
-
-    let without_trailing = trimmed.trim_end_matches('/');
-    format!("{without_trailing}/")
+    Ok(settings.public_site_url)
 }
@@ -10,6 +10,7 @@ use super::types::RenderedSection;
 mod helpers;
 mod payloads;
 mod process;
+mod stitch;
 #[cfg(test)]
 mod tests;
 
@@ -28,6 +29,9 @@ pub use process::{
 /// race conditions: the job worker uses these values directly instead of
 /// re-reading from the database (which might return stale data due to separate
 /// connection pools).
+///
+/// When a job for the same slug and content is still queued, no new job is
+/// enqueued and the queued job's id is returned instead.
 pub async fn enqueue_render_post_job<J: JobsRepo + ?Sized>(
     repo: &J,
     slug: String,
@@ -35,19 +39,44 @@ pub async fn enqueue_render_post_job<J: JobsRepo + ?Sized>(
     summary_markdown: Option<String>,
     scheduled_at: Option<OffsetDateTime>,
 ) -> Result<String, RepoError> {
-    enqueue_job(
-        repo,
-        JobType::RenderPost,
-        &RenderPostJobPayload {
-            slug,
-            body_markdown,
-            summary_markdown,
-        },
-        scheduled_at,
-        25,
-        0,
-    )
-    .await
+    let payload = RenderPostJobPayload::new(slug, body_markdown, summary_markdown);
+    enqueue_render_post_payload(repo, &payload, scheduled_at).await
+}
+
+/// Schedules a post render after its body changed from `previous_body_markdown`.
+///
+/// The job re-renders only the top-level sections whose markdown changed and
+/// keeps the stored sections of the rest, falling back to a full render when
+/// the heading structure changed.
+pub async fn enqueue_render_post_edit_job<J: JobsRepo + ?Sized>(
+    repo: &J,
+    slug: String,
+    previous_body_markdown: String,
+    body_markdown: String,
+    summary_markdown: Option<String>,
+) -> Result<String, RepoError> {
+    let payload = RenderPostJobPayload::new(slug, body_markdown, summary_markdown)
+        .with_previous_body_markdown(previous_body_markdown);
+    enqueue_render_post_payload(repo, &payload, None).await
+}
+
+async fn enqueue_render_post_payload<J: JobsRepo + ?Sized>(
+    repo: &J,
+    payload: &RenderPostJobPayload,
+    scheduled_at: Option<OffsetDateTime>,
+) -> Result<String, RepoError> {
+    let identity = serde_json::json!({
+        "slug": payload.slug,
+        "content_hash": payload.content_hash,
+    });
+    if let Some(queued) = repo
+        .find_pending_job(JobType::RenderPost, &identity)
+        .await?
+    {
+        return Ok(queued);
+    }
+
+    enqueue_job(repo, JobType::RenderPost, payload, scheduled_at, 25, 0).await
 }
 
 pub async fn enqueue_render_page_job<J: JobsRepo + ?Sized>(
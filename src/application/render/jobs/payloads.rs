@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::application::render::RenderedSection;
@@ -8,6 +9,56 @@ pub struct RenderPostJobPayload {
     pub slug: String,
     pub body_markdown: String,
     pub summary_markdown: Option<String>,
+    /// Hash of the markdown above, used to spot duplicate and stale renders.
+    /// Empty on payloads enqueued before it existed.
+    #[serde(default)]
+    pub content_hash: String,
+    /// Body markdown before the edit that queued this render. When set, only
+    /// the top-level sections that changed are re-rendered where possible.
+    #[serde(default)]
+    pub previous_body_markdown: Option<String>,
+}
+
+impl RenderPostJobPayload {
+    pub fn new(slug: String, body_markdown: String, summary_markdown: Option<String>) -> Self {
+        let content_hash = post_content_hash(&body_markdown, summary_markdown.as_deref());
+        Self {
+            slug,
+            body_markdown,
+            summary_markdown,
+            content_hash,
+            previous_body_markdown: None,
+        }
+    }
+
+    pub fn with_previous_body_markdown(mut self, previous_body_markdown: String) -> Self {
+        self.previous_body_markdown = Some(previous_body_markdown);
+        self
+    }
+}
+
+/// Hash identifying the markdown a post render works from.
+pub fn post_content_hash(body_markdown: &str, summary_markdown: Option<&str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(body_markdown.len().to_le_bytes());
+    hasher.update(body_markdown.as_bytes());
+    if let Some(summary) = summary_markdown {
+        hasher.update(summary.as_bytes());
+    } else {
+        hasher.update([0xff]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Hash identifying what a post's sections are rendered from: the body
+/// markdown, the public site URL and the renderer's settings fingerprint.
+pub fn sections_hash(body_markdown: &str, public_site_url: &str, settings: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [body_markdown, public_site_url, settings] {
+        hasher.update(part.len().to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    hex::encode(hasher.finalize())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +67,14 @@ pub struct RenderPostSectionsJobPayload {
     pub post_id: Uuid,
     pub slug: String,
     pub markdown: String,
+    /// Headings of the post before `markdown` when it is a single chunk of
+    /// the post rather than the whole body.
+    #[serde(default)]
+    pub preceding_headings: Vec<String>,
+    /// Public site URL the parent render works with; read from the site
+    /// settings when absent.
+    #[serde(default)]
+    pub public_site_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
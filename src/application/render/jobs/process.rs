@@ -1,39 +1,136 @@
 use std::time::Instant;
 
 use apalis::prelude::{Data, Error as ApalisError};
+use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
-use tracing::{info, warn};
+use tracing::{info, instrument, warn};
 use uuid::Uuid;
 
 use crate::application::jobs::{JobWorkerContext, job_failed};
-use crate::application::render::runtime::{InFlightError, RenderArtifact, RenderMailboxError};
-use crate::application::render::{RenderRequest, RenderService, RenderTarget};
+use crate::application::render::runtime::{
+    InFlightError, RenderArtifact, RenderMailboxError, RenderOutcome,
+};
+use crate::application::render::service::collect_post_references;
+use crate::application::render::{
+    ContentWarning, RenderRequest, RenderService, RenderTarget, check_content_references,
+    plan_incremental_render,
+};
+use crate::domain::routes::site_root;
+use crate::infra::db::PersistedPostSectionOwned;
 
 use super::helpers::{
-    convert_section, join_children, load_public_site_url, persist_sections_and_summary,
+    SectionsSource, convert_section, join_children, load_public_site_url,
+    persist_sections_and_summary,
 };
+use super::payloads::{post_content_hash, sections_hash};
+use super::stitch::{sections_in_document_order, stitch_sections};
 use super::{
     JobConsistencyError, RenderPageJobPayload, RenderPostJobPayload, RenderPostSectionJobPayload,
     RenderPostSectionsJobPayload, RenderSummaryJobPayload,
 };
 
 /// Container job responsible for coordinating render tasks and committing results.
+///
+/// Renders are keyed by slug and content hash. A job whose content is already
+/// being rendered waits for that render's outcome instead of repeating it, and
+/// a render whose content has been replaced by a newer edit discards its
+/// results so the newest markdown always wins.
+#[instrument(name = "render_job", skip_all, fields(job = "render_post", slug = %payload.slug))]
 pub async fn process_render_post_job(
     payload: RenderPostJobPayload,
     context: Data<JobWorkerContext>,
 ) -> Result<(), ApalisError> {
     let started_at = Instant::now();
     let ctx = &*context;
-    let repositories = ctx.repositories.clone();
+    let content_hash =
+        post_content_hash(&payload.body_markdown, payload.summary_markdown.as_deref());
+    let outcome_key = format!("{}:{content_hash}", payload.slug);
+
+    // Subscribe before acquiring so the outcome of an identical render in
+    // flight cannot be published before we start listening.
+    let mut outcome = ctx.render_mailbox.subscribe_outcome(&outcome_key);
+    let guard = match ctx.inflight_renders.acquire(&payload.slug, &content_hash) {
+        Ok(guard) => guard,
+        Err(InFlightError::Duplicate { slug }) => {
+            info!(
+                target = "application::render::process_render_post_job",
+                slug = %slug,
+                "identical render already in flight; awaiting its outcome"
+            );
+            let outcome = outcome
+                .wait_for(Option::is_some)
+                .await
+                .map(|value| value.clone());
+            return match outcome {
+                Err(_) => Err(job_failed(JobConsistencyError::new(
+                    "render outcome channel closed",
+                ))),
+                Ok(Some(RenderOutcome::Failed(message))) => {
+                    Err(job_failed(JobConsistencyError::new(message)))
+                }
+                _ => Ok(()),
+            };
+        }
+    };
+
+    let result = render_post(ctx, &payload, &content_hash).await;
+
+    // Release the slug before publishing, so a job that subscribes after the
+    // outcome is gone can acquire it and render itself.
+    drop(guard);
+    ctx.render_mailbox.publish_outcome(
+        &outcome_key,
+        match &result {
+            Ok(outcome) => outcome.clone(),
+            Err(err) => RenderOutcome::Failed(err.to_string()),
+        },
+    );
+
+    if let Err(err) = &result {
+        record_render_failure(
+            ctx,
+            RenderTarget::PostBody {
+                slug: payload.slug.clone(),
+            },
+            err,
+        )
+        .await;
+    }
 
+    let outcome = result?;
+    info!(
+        target = "application::render::process_render_post_job",
+        slug = %payload.slug,
+        outcome = ?outcome,
+        elapsed_ms = started_at.elapsed().as_millis() as u64,
+        "post render finished"
+    );
+
+    Ok(())
+}
+
+/// Sections and content warnings rendered for a post body.
+struct RenderedBody {
+    sections: Vec<PersistedPostSectionOwned>,
+    warnings: Vec<ContentWarning>,
+    /// Sections hash of the stored sections an incremental render reused.
+    reused_hash: Option<String>,
+}
+
+async fn render_post(
+    ctx: &JobWorkerContext,
+    payload: &RenderPostJobPayload,
+    content_hash: &str,
+) -> Result<RenderOutcome, ApalisError> {
     // Use payload data directly to avoid race conditions with separate connection pools.
     // The body_markdown and summary_markdown were captured at enqueue time.
-    let body_markdown = payload.body_markdown.clone();
+    let body_markdown = payload.body_markdown.as_str();
     let summary_markdown = payload.summary_markdown.clone();
 
     // Only fetch post_id from database (immutable identifier).
-    let Some(post_id) = sqlx::query_scalar!("SELECT id FROM posts WHERE slug = $1", payload.slug)
-        .fetch_optional(repositories.pool())
+    let Some(post_id) = ctx
+        .repositories
+        .find_post_id_by_slug_immediate(&payload.slug)
         .await
         .map_err(job_failed)?
     else {
@@ -43,37 +140,14 @@ pub async fn process_render_post_job(
         ))));
     };
 
-    let guard = match ctx.inflight_renders.acquire(post_id) {
-        Ok(guard) => guard,
-        Err(InFlightError::AlreadyRunning { post_id }) => {
-            warn!(
-                target = "application::render::process_render_post_job",
-                post_id = %post_id,
-                slug = %payload.slug,
-                "render already in flight; cancelling duplicate"
-            );
-            return Ok(());
-        }
-    };
+    let public_site_url = load_public_site_url(ctx).await?;
+    let settings = ctx.renderer.settings_fingerprint();
+    let body_hash = sections_hash(body_markdown, &public_site_url, &settings);
 
     let mut child_handles: Vec<JoinHandle<Result<(), ApalisError>>> = Vec::new();
 
     // Child render steps run as in-memory tasks; they are intentionally not enqueued
     // as standalone jobs to keep the pipeline fast and avoid polluting the job queue.
-    let sections_tracking = Uuid::new_v4().to_string();
-    let sections_receiver = ctx.render_mailbox.register(sections_tracking.clone());
-    let sections_payload = RenderPostSectionsJobPayload {
-        tracking_id: sections_tracking.clone(),
-        post_id,
-        slug: payload.slug.clone(),
-        markdown: body_markdown,
-    };
-    let sections_ctx = ctx.clone();
-    let sections_handle = tokio::spawn(async move {
-        process_render_post_sections_job(sections_payload, Data::new(sections_ctx)).await
-    });
-    child_handles.push(sections_handle);
-
     let summary_state = if let Some(summary_markdown) = summary_markdown {
         let tracking_id = Uuid::new_v4().to_string();
         let receiver = ctx.render_mailbox.register(tracking_id.clone());
@@ -93,23 +167,26 @@ pub async fn process_render_post_job(
         None
     };
 
-    let sections_artifact = sections_receiver.await.map_err(|_| {
-        job_failed(JobConsistencyError::new(
-            "sections render result channel dropped",
-        ))
-    })?;
-
-    let sections = match sections_artifact {
-        RenderArtifact::Sections(sections) => sections,
-        RenderArtifact::Cancelled(_) => {
-            return Err(job_failed(JobConsistencyError::new(
-                "sections render cancelled",
-            )));
+    let incremental = match payload.previous_body_markdown.as_deref() {
+        Some(previous) => {
+            render_changed_sections(
+                ctx,
+                post_id,
+                &payload.slug,
+                previous,
+                body_markdown,
+                &public_site_url,
+                &settings,
+            )
+            .await?
         }
-        RenderArtifact::Section(_) | RenderArtifact::SummaryHtml(_) => {
-            return Err(job_failed(JobConsistencyError::new(
-                "unexpected render artifact variant for sections",
-            )));
+        None => None,
+    };
+    let mut body = match incremental {
+        Some(body) => body,
+        None => {
+            render_all_sections(ctx, post_id, &payload.slug, body_markdown, &public_site_url)
+                .await?
         }
     };
 
@@ -141,35 +218,246 @@ pub async fn process_render_post_job(
         None
     };
 
-    persist_sections_and_summary(
+    let mut persisted = persist_sections_and_summary(
         ctx.repositories.as_ref(),
         post_id,
-        &sections,
+        content_hash,
+        SectionsSource {
+            hash: &body_hash,
+            reused_hash: body.reused_hash.as_deref(),
+        },
+        &body.sections,
         summary_html.as_deref(),
+        &body.warnings,
     )
     .await?;
 
-    ctx.admin_posts
-        .notify_post_materialized(post_id, &payload.slug)
-        .await
-        .map_err(job_failed)?;
+    if !persisted && body.reused_hash.is_some() {
+        // The reused sections were replaced, or the markdown moved on; a full
+        // render settles which.
+        info!(
+            target = "application::render::process_render_post_job",
+            slug = %payload.slug,
+            "stored sections changed during incremental render; rendering in full"
+        );
+        body = render_all_sections(ctx, post_id, &payload.slug, body_markdown, &public_site_url)
+            .await?;
+        persisted = persist_sections_and_summary(
+            ctx.repositories.as_ref(),
+            post_id,
+            content_hash,
+            SectionsSource {
+                hash: &body_hash,
+                reused_hash: None,
+            },
+            &body.sections,
+            summary_html.as_deref(),
+            &body.warnings,
+        )
+        .await?;
+    }
 
-    drop(guard);
+    if persisted {
+        ctx.admin_posts
+            .notify_post_materialized(post_id, &payload.slug)
+            .await
+            .map_err(job_failed)?;
+    }
 
     join_children(child_handles).await?;
 
+    if !persisted {
+        info!(
+            target = "application::render::process_render_post_job",
+            slug = %payload.slug,
+            "post changed while rendering; discarded stale render"
+        );
+        return Ok(RenderOutcome::Superseded);
+    }
+
     info!(
         target = "application::render::process_render_post_job",
         slug = %payload.slug,
-        sections = sections.len(),
+        sections = body.sections.len(),
+        incremental = body.reused_hash.is_some(),
         summary = summary_html.is_some(),
-        elapsed_ms = started_at.elapsed().as_millis() as u64,
+        warnings = body.warnings.len(),
         "post render persisted"
     );
 
-    Ok(())
+    Ok(RenderOutcome::Persisted)
 }
 
+/// Render the whole body of a post into sections.
+async fn render_all_sections(
+    ctx: &JobWorkerContext,
+    post_id: Uuid,
+    slug: &str,
+    markdown: &str,
+    public_site_url: &str,
+) -> Result<RenderedBody, ApalisError> {
+    let pending = spawn_sections_render(
+        ctx,
+        post_id,
+        slug,
+        markdown.to_string(),
+        Vec::new(),
+        public_site_url,
+    );
+    let (sections, warnings) = await_sections_render(pending).await?;
+    Ok(RenderedBody {
+        sections,
+        warnings,
+        reused_hash: None,
+    })
+}
+
+/// Re-render only the top-level sections of a post whose markdown changed
+/// since `previous`, reusing the stored sections of the others.
+///
+/// Returns `None` when a full render is needed instead: the edit changed the
+/// heading outline, or the stored sections were not rendered from `previous`
+/// with the current site URL and renderer settings.
+async fn render_changed_sections(
+    ctx: &JobWorkerContext,
+    post_id: Uuid,
+    slug: &str,
+    previous: &str,
+    current: &str,
+    public_site_url: &str,
+    settings: &str,
+) -> Result<Option<RenderedBody>, ApalisError> {
+    let Some(plan) = plan_incremental_render(previous, current) else {
+        return Ok(None);
+    };
+    let reused_hash = sections_hash(previous, public_site_url, settings);
+    let (stored_hash, stored) = ctx
+        .repositories
+        .load_post_sections_with_hash(post_id)
+        .await
+        .map_err(job_failed)?;
+    if stored_hash.as_deref() != Some(reused_hash.as_str()) {
+        return Ok(None);
+    }
+    let Ok(stored) = sections_in_document_order(stored) else {
+        return Ok(None);
+    };
+
+    let pending: Vec<_> = plan
+        .chunks
+        .iter()
+        .filter(|chunk| chunk.changed)
+        .map(|chunk| {
+            spawn_sections_render(
+                ctx,
+                post_id,
+                slug,
+                chunk.markdown.clone(),
+                chunk.preceding_headings.clone(),
+                public_site_url,
+            )
+        })
+        .collect();
+    let changed = pending.len();
+
+    // The text before the first heading belongs to no section, but a full
+    // render still checks its links.
+    let mut html = if plan.preamble.trim().is_empty() {
+        String::new()
+    } else {
+        let request = RenderRequest::new(
+            RenderTarget::PostBody {
+                slug: slug.to_string(),
+            },
+            plan.preamble.clone(),
+        )
+        .with_public_site_url(public_site_url);
+        ctx.renderer.render(&request).map_err(job_failed)?.html
+    };
+
+    let mut rendered = Vec::with_capacity(changed);
+    for pending in pending {
+        rendered.push(await_sections_render(pending).await?.0);
+    }
+    let Some(sections) = stitch_sections(&plan, stored, rendered) else {
+        return Ok(None);
+    };
+
+    for section in &sections {
+        html.push_str(&section.heading_html);
+        html.push_str(&section.body_html);
+    }
+    let references = collect_post_references(&html, site_root(public_site_url).as_deref())
+        .map_err(job_failed)?;
+    let warnings = check_content_references(ctx.repositories.as_ref(), &references).await;
+
+    info!(
+        target = "application::render::process_render_post_job",
+        slug = %slug,
+        chunks = plan.chunks.len(),
+        changed,
+        "re-rendered changed sections"
+    );
+
+    Ok(Some(RenderedBody {
+        sections,
+        warnings,
+        reused_hash: Some(reused_hash),
+    }))
+}
+
+type PendingSections = (
+    oneshot::Receiver<RenderArtifact>,
+    JoinHandle<Result<(), ApalisError>>,
+);
+
+fn spawn_sections_render(
+    ctx: &JobWorkerContext,
+    post_id: Uuid,
+    slug: &str,
+    markdown: String,
+    preceding_headings: Vec<String>,
+    public_site_url: &str,
+) -> PendingSections {
+    let tracking_id = Uuid::new_v4().to_string();
+    let receiver = ctx.render_mailbox.register(tracking_id.clone());
+    let payload = RenderPostSectionsJobPayload {
+        tracking_id,
+        post_id,
+        slug: slug.to_string(),
+        markdown,
+        preceding_headings,
+        public_site_url: Some(public_site_url.to_string()),
+    };
+    let sections_ctx = ctx.clone();
+    let handle = tokio::spawn(async move {
+        process_render_post_sections_job(payload, Data::new(sections_ctx)).await
+    });
+    (receiver, handle)
+}
+
+async fn await_sections_render(
+    (receiver, handle): PendingSections,
+) -> Result<(Vec<PersistedPostSectionOwned>, Vec<ContentWarning>), ApalisError> {
+    let artifact = receiver.await.map_err(|_| {
+        job_failed(JobConsistencyError::new(
+            "sections render result channel dropped",
+        ))
+    })?;
+    join_children(vec![handle]).await?;
+
+    match artifact {
+        RenderArtifact::Sections(sections, warnings) => Ok((sections, warnings)),
+        RenderArtifact::Cancelled(_) => Err(job_failed(JobConsistencyError::new(
+            "sections render cancelled",
+        ))),
+        RenderArtifact::Section(_) | RenderArtifact::SummaryHtml(_) => Err(job_failed(
+            JobConsistencyError::new("unexpected render artifact variant for sections"),
+        )),
+    }
+}
+
+#[instrument(name = "render_job", skip_all, fields(job = "render_post_sections", slug = %payload.slug, tracking_id = %payload.tracking_id))]
 pub async fn process_render_post_sections_job(
     payload: RenderPostSectionsJobPayload,
     context: Data<JobWorkerContext>,
@@ -178,7 +466,10 @@ pub async fn process_render_post_sections_job(
     let ctx = &*context;
     let renderer = ctx.renderer.clone();
     let tracking_id = payload.tracking_id.clone();
-    let public_site_url = load_public_site_url(ctx).await?;
+    let public_site_url = match payload.public_site_url.clone() {
+        Some(public_site_url) => public_site_url,
+        None => load_public_site_url(ctx).await?,
+    };
 
     let request = RenderRequest::new(
         RenderTarget::PostBody {
@@ -186,9 +477,11 @@ pub async fn process_render_post_sections_job(
         },
         payload.markdown.clone(),
     )
-    .with_public_site_url(&public_site_url);
+    .with_public_site_url(&public_site_url)
+    .with_preceding_headings(payload.preceding_headings.clone());
 
     let output = renderer.render(&request).map_err(job_failed)?;
+    let warnings = check_content_references(ctx.repositories.as_ref(), &output.references).await;
 
     let sections = output.sections.unwrap_or_default();
 
@@ -244,11 +537,15 @@ pub async fn process_render_post_sections_job(
     );
 
     ctx.render_mailbox
-        .deliver(&tracking_id, RenderArtifact::Sections(persisted_sections))
+        .deliver(
+            &tracking_id,
+            RenderArtifact::Sections(persisted_sections, warnings),
+        )
         .map_err(|err| job_failed(JobConsistencyError::new(err.to_string())))
 }
 
 /// Leaf job: converts a rendered section into a persistable artifact.
+#[instrument(name = "render_job", skip_all, fields(job = "render_post_section", slug = %payload.slug, tracking_id = %payload.tracking_id))]
 pub async fn process_render_post_section_job(
     payload: RenderPostSectionJobPayload,
     context: Data<JobWorkerContext>,
@@ -272,6 +569,7 @@ pub async fn process_render_post_section_job(
 }
 
 /// Leaf job responsible for rendering the summary markdown.
+#[instrument(name = "render_job", skip_all, fields(job = "render_summary", slug = %payload.slug, tracking_id = %payload.tracking_id))]
 pub async fn process_render_summary_job(
     payload: RenderSummaryJobPayload,
     context: Data<JobWorkerContext>,
@@ -317,11 +615,23 @@ pub async fn process_render_summary_job(
     }
 }
 
+#[instrument(name = "render_job", skip_all, fields(job = "render_page", slug = %payload.slug))]
 pub async fn process_render_page_job(
     payload: RenderPageJobPayload,
     context: Data<JobWorkerContext>,
 ) -> Result<(), ApalisError> {
     let ctx = &*context;
+    let result = render_page(ctx, &payload).await;
+    if let Err(err) = &result {
+        record_render_failure(ctx, RenderTarget::PageBody { slug: payload.slug }, err).await;
+    }
+    result
+}
+
+async fn render_page(
+    ctx: &JobWorkerContext,
+    payload: &RenderPageJobPayload,
+) -> Result<(), ApalisError> {
     let renderer = ctx.renderer.clone();
     let public_site_url = load_public_site_url(ctx).await?;
 
@@ -341,17 +651,26 @@ pub async fn process_render_page_job(
         RenderTarget::PageBody {
             slug: payload.slug.clone(),
         },
-        payload.markdown,
+        payload.markdown.clone(),
     )
     .with_public_site_url(&public_site_url);
 
     let output = renderer.render(&request).map_err(job_failed)?;
+    let warnings = check_content_references(ctx.repositories.as_ref(), &output.references).await;
 
     let mut tx = ctx.repositories.begin().await.map_err(job_failed)?;
     ctx.repositories
         .update_page_rendered_html(&mut tx, page_id, &output.html)
         .await
         .map_err(job_failed)?;
+    ctx.repositories
+        .clear_page_render_failure(&mut tx, page_id)
+        .await
+        .map_err(job_failed)?;
+    ctx.repositories
+        .replace_page_render_warnings(&mut tx, page_id, &warnings)
+        .await
+        .map_err(job_failed)?;
 
     tx.commit().await.map_err(job_failed)?;
     ctx.admin_pages
@@ -360,3 +679,29 @@ pub async fn process_render_page_job(
         .map_err(job_failed)?;
     Ok(())
 }
+
+/// Store a failed render on the post or page so editors can see it. Recording
+/// is best effort: the job still fails with the original error.
+async fn record_render_failure(ctx: &JobWorkerContext, target: RenderTarget, err: &ApalisError) {
+    let message = err.to_string();
+    let recorded = match &target {
+        RenderTarget::PageBody { slug } => {
+            ctx.repositories
+                .record_page_render_failure(slug, &message)
+                .await
+        }
+        RenderTarget::PostBody { slug } | RenderTarget::PostSummary { slug } => {
+            ctx.repositories
+                .record_post_render_failure(slug, &message)
+                .await
+        }
+    };
+    if let Err(record_err) = recorded {
+        warn!(
+            target = "application::render::record_render_failure",
+            slug = %target.slug(),
+            error = %record_err,
+            "failed to record render failure"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::application::render::IncrementalPlan;
+use crate::domain::entities::PostSectionRecord;
+use crate::domain::sections::{PostSectionNode, SectionTreeError, build_section_tree};
+use crate::infra::db::PersistedPostSectionOwned;
+
+/// Stored sections flattened into document order, the order a render
+/// produces them in.
+pub(super) fn sections_in_document_order(
+    records: Vec<PostSectionRecord>,
+) -> Result<Vec<PersistedPostSectionOwned>, SectionTreeError> {
+    fn flatten(nodes: &[PostSectionNode], sections: &mut Vec<PersistedPostSectionOwned>) {
+        for node in nodes {
+            sections.push(PersistedPostSectionOwned {
+                id: node.id,
+                parent_id: None,
+                position: 0,
+                level: i16::from(node.level),
+                heading_html: node.heading_html.clone(),
+                heading_text: node.heading_text.clone(),
+                body_html: node.body_html.clone(),
+                contains_code: node.contains_code,
+                contains_math: node.contains_math,
+                contains_mermaid: node.contains_mermaid,
+                anchor_slug: node.anchor_slug.clone(),
+            });
+            flatten(&node.children, sections);
+        }
+    }
+
+    let tree = build_section_tree(records)?;
+    let mut sections = Vec::new();
+    flatten(&tree, &mut sections);
+    Ok(sections)
+}
+
+/// Splice the sections rendered for the changed chunks of `plan`, given in
+/// chunk order, between the stored sections of the unchanged chunks, then
+/// link parents and positions as a full render would.
+///
+/// Untouched sections keep their ids. Returns `None` when the pieces do not
+/// add up to the outline of `plan`.
+pub(super) fn stitch_sections(
+    plan: &IncrementalPlan,
+    stored: Vec<PersistedPostSectionOwned>,
+    rendered: Vec<Vec<PersistedPostSectionOwned>>,
+) -> Option<Vec<PersistedPostSectionOwned>> {
+    if stored.len() != plan.section_count() {
+        return None;
+    }
+
+    let mut stored = stored.into_iter();
+    let mut rendered = rendered.into_iter();
+    let mut sections = Vec::with_capacity(plan.section_count());
+    for chunk in &plan.chunks {
+        let previous: Vec<_> = stored.by_ref().take(chunk.section_count).collect();
+        if !chunk.changed {
+            sections.extend(previous);
+            continue;
+        }
+        let fresh = rendered.next()?;
+        if fresh.len() != chunk.section_count {
+            return None;
+        }
+        sections.extend(fresh);
+    }
+    if rendered.next().is_some() {
+        return None;
+    }
+
+    link_sections(&mut sections);
+    Some(sections)
+}
+
+/// Assign each section the nearest preceding section with a lower level as
+/// its parent, and number siblings from one.
+fn link_sections(sections: &mut [PersistedPostSectionOwned]) {
+    let mut stack: Vec<(i16, usize)> = Vec::new();
+    let mut child_counts = vec![0i32; sections.len()];
+    let mut root_position = 0i32;
+
+    for idx in 0..sections.len() {
+        let level = sections[idx].level;
+        while let Some(&(parent_level, _)) = stack.last() {
+            if parent_level < level {
+                break;
+            }
+            stack.pop();
+        }
+
+        let parent = stack.last().map(|&(_, parent)| parent);
+        let position = match parent {
+            Some(parent) => {
+                child_counts[parent] += 1;
+                child_counts[parent]
+            }
+            None => {
+                root_position += 1;
+                root_position
+            }
+        };
+        let parent_id = parent.map(|parent| sections[parent].id);
+
+        sections[idx].parent_id = parent_id;
+        sections[idx].position = position;
+        stack.push((level, idx));
+    }
+}
@@ -1,24 +1,40 @@
 use super::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
 use sqlx::PgPool;
+use time::OffsetDateTime;
 use tokio::time::{Duration, timeout};
 use uuid::Uuid;
 
-use super::helpers::persist_sections_and_summary;
-use crate::application::repos::{CreatePostParams, PostsWriteRepo};
+use super::helpers::{
+    PERSIST_ATTEMPTS, PERSIST_FAULTS, SectionsSource, convert_section, persist_sections_and_summary,
+};
+use super::payloads::post_content_hash;
+use super::stitch::stitch_sections;
+use crate::application::render::{
+    ComrakRenderService, RenderRequest, RenderService, RenderTarget, plan_incremental_render,
+};
+use crate::application::repos::{CreatePostParams, PostsWriteRepo, RepoError, SectionsRepo};
 use crate::domain::types::PostStatus;
 use crate::infra::db::{PersistedPostSectionOwned, PostgresRepositories};
 
+const FULL_RENDER: SectionsSource<'static> = SectionsSource {
+    hash: "sections",
+    reused_hash: None,
+};
+
 /// Verifies that RenderPostJobPayload correctly serializes and deserializes
 /// body_markdown and summary_markdown fields. This is critical for the race
 /// condition fix: the payload must carry complete content so the worker
 /// doesn't need to re-read from the database.
 #[test]
 fn render_post_payload_carries_complete_content() {
-    let payload = RenderPostJobPayload {
-        slug: "test-post".into(),
-        body_markdown: "# Heading\n\nParagraph with **bold** text.".into(),
-        summary_markdown: Some("Summary content here.".into()),
-    };
+    let payload = RenderPostJobPayload::new(
+        "test-post".into(),
+        "# Heading\n\nParagraph with **bold** text.".into(),
+        Some("Summary content here.".into()),
+    );
 
     let json = serde_json::to_string(&payload).unwrap();
     let deserialized: RenderPostJobPayload = serde_json::from_str(&json).unwrap();
@@ -37,11 +53,7 @@ fn render_post_payload_carries_complete_content() {
 /// Verifies that RenderPostJobPayload handles None summary_markdown correctly.
 #[test]
 fn render_post_payload_handles_none_summary() {
-    let payload = RenderPostJobPayload {
-        slug: "no-summary".into(),
-        body_markdown: "Body only".into(),
-        summary_markdown: None,
-    };
+    let payload = RenderPostJobPayload::new("no-summary".into(), "Body only".into(), None);
 
     let json = serde_json::to_string(&payload).unwrap();
     let deserialized: RenderPostJobPayload = serde_json::from_str(&json).unwrap();
@@ -53,11 +65,11 @@ fn render_post_payload_handles_none_summary() {
 #[test]
 fn render_post_payload_preserves_large_content() {
     let large_body = "# Title\n\n".to_string() + &"Lorem ipsum dolor sit amet. ".repeat(1000);
-    let payload = RenderPostJobPayload {
-        slug: "large-post".into(),
-        body_markdown: large_body.clone(),
-        summary_markdown: Some("Short summary".into()),
-    };
+    let payload = RenderPostJobPayload::new(
+        "large-post".into(),
+        large_body.clone(),
+        Some("Short summary".into()),
+    );
 
     let json = serde_json::to_string(&payload).unwrap();
     let deserialized: RenderPostJobPayload = serde_json::from_str(&json).unwrap();
@@ -74,6 +86,7 @@ async fn persist_sections_locks_posts_before_sections(pool: PgPool) {
             slug: "lock-order-test".to_string(),
             title: "Lock Order Test".to_string(),
             excerpt: "excerpt".to_string(),
+            excerpt_generated: false,
             body_markdown: "body".to_string(),
             status: PostStatus::Draft,
             pinned: false,
@@ -145,7 +158,19 @@ async fn persist_sections_locks_posts_before_sections(pool: PgPool) {
     let mut handle = tokio::spawn({
         let repos = repos.clone();
         let post_id = post.id;
-        async move { persist_sections_and_summary(&repos, post_id, &sections, None).await }
+        let content_hash = post_content_hash("body", None);
+        async move {
+            persist_sections_and_summary(
+                &repos,
+                post_id,
+                &content_hash,
+                FULL_RENDER,
+                &sections,
+                None,
+                &[],
+            )
+            .await
+        }
     });
 
     tokio::task::yield_now().await;
@@ -159,7 +184,7 @@ async fn persist_sections_locks_posts_before_sections(pool: PgPool) {
         .await
         .expect("persist completes after lock release")
         .expect("task join ok");
-    assert!(result.is_ok(), "persist failed: {result:?}");
+    assert!(matches!(result, Ok(true)), "persist failed: {result:?}");
 
     let row = sqlx::query!(
         r#"
@@ -175,3 +200,383 @@ async fn persist_sections_locks_posts_before_sections(pool: PgPool) {
 
     assert_eq!(row.id, new_section_id);
 }
+
+#[test]
+fn render_post_payload_hash_tracks_content() {
+    let v1 = RenderPostJobPayload::new("post".into(), "body".into(), None);
+    let same = RenderPostJobPayload::new("post".into(), "body".into(), None);
+    let edited = RenderPostJobPayload::new("post".into(), "body!".into(), None);
+    let summarized = RenderPostJobPayload::new("post".into(), "body".into(), Some(String::new()));
+
+    assert_eq!(v1.content_hash, same.content_hash);
+    assert_ne!(v1.content_hash, edited.content_hash);
+    assert_ne!(v1.content_hash, summarized.content_hash);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn enqueue_render_post_reuses_queued_identical_job(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool);
+
+    let first = enqueue_render_post_job(&repos, "post".into(), "v1".into(), None, None)
+        .await
+        .expect("enqueue v1");
+    let duplicate = enqueue_render_post_job(&repos, "post".into(), "v1".into(), None, None)
+        .await
+        .expect("enqueue v1 again");
+    let edited = enqueue_render_post_job(&repos, "post".into(), "v2".into(), None, None)
+        .await
+        .expect("enqueue v2");
+
+    assert_eq!(first, duplicate);
+    assert_ne!(first, edited);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn persist_discards_render_of_outdated_content(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool);
+    let post = repos
+        .create_post(CreatePostParams {
+            slug: "stale-render".to_string(),
+            title: "Stale Render".to_string(),
+            excerpt: "excerpt".to_string(),
+            excerpt_generated: false,
+            body_markdown: "v2".to_string(),
+            status: PostStatus::Draft,
+            pinned: false,
+            scheduled_at: None,
+            published_at: None,
+            archived_at: None,
+            summary_markdown: None,
+            summary_html: None,
+        })
+        .await
+        .expect("create post");
+
+    let sections = vec![PersistedPostSectionOwned {
+        id: Uuid::new_v4(),
+        parent_id: None,
+        position: 0,
+        level: 1,
+        heading_html: "<h2>Old</h2>".to_string(),
+        heading_text: "Old".to_string(),
+        body_html: "<p>v1</p>".to_string(),
+        contains_code: false,
+        contains_math: false,
+        contains_mermaid: false,
+        anchor_slug: "old".to_string(),
+    }];
+
+    let persisted = persist_sections_and_summary(
+        &repos,
+        post.id,
+        &post_content_hash("v1", None),
+        FULL_RENDER,
+        &sections,
+        None,
+        &[],
+    )
+    .await
+    .expect("persist");
+
+    assert!(!persisted);
+    assert!(
+        repos
+            .list_sections(post.id)
+            .await
+            .expect("list sections")
+            .is_empty()
+    );
+}
+
+fn rendered_section(anchor: &str) -> PersistedPostSectionOwned {
+    PersistedPostSectionOwned {
+        id: Uuid::new_v4(),
+        parent_id: None,
+        position: 0,
+        level: 1,
+        heading_html: format!("<h2>{anchor}</h2>"),
+        heading_text: anchor.to_string(),
+        body_html: format!("<p>{anchor}</p>"),
+        contains_code: false,
+        contains_math: false,
+        contains_mermaid: false,
+        anchor_slug: anchor.to_string(),
+    }
+}
+
+/// Persist `sections` and `summary_html` with `faults` injected after the
+/// sections write, one per attempt.
+async fn persist_with_faults(
+    repos: &PostgresRepositories,
+    post_id: Uuid,
+    sections: &[PersistedPostSectionOwned],
+    summary_html: &str,
+    faults: Vec<RepoError>,
+) -> Result<bool, apalis::prelude::Error> {
+    let content_hash = post_content_hash("body", Some("summary"));
+    PERSIST_FAULTS
+        .scope(
+            RefCell::new(VecDeque::from(faults)),
+            persist_sections_and_summary(
+                repos,
+                post_id,
+                &content_hash,
+                FULL_RENDER,
+                sections,
+                Some(summary_html),
+                &[],
+            ),
+        )
+        .await
+}
+
+async fn rendered_state(
+    repos: &PostgresRepositories,
+    post_id: Uuid,
+) -> (Vec<Uuid>, Option<String>, OffsetDateTime) {
+    let sections = repos
+        .list_sections(post_id)
+        .await
+        .expect("list sections")
+        .into_iter()
+        .map(|section| section.id)
+        .collect();
+    let (summary_html, updated_at) = sqlx::query_as::<_, (Option<String>, OffsetDateTime)>(
+        "SELECT summary_html, updated_at FROM posts WHERE id = $1",
+    )
+    .bind(post_id)
+    .fetch_one(repos.pool())
+    .await
+    .expect("load post");
+    (sections, summary_html, updated_at)
+}
+
+async fn create_rendered_post(repos: &PostgresRepositories, slug: &str) -> (Uuid, Uuid) {
+    let post = repos
+        .create_post(CreatePostParams {
+            slug: slug.to_string(),
+            title: slug.to_string(),
+            excerpt: "excerpt".to_string(),
+            excerpt_generated: false,
+            body_markdown: "body".to_string(),
+            status: PostStatus::Draft,
+            pinned: false,
+            scheduled_at: None,
+            published_at: None,
+            archived_at: None,
+            summary_markdown: Some("summary".to_string()),
+            summary_html: None,
+        })
+        .await
+        .expect("create post");
+
+    let previous = rendered_section("previous");
+    let persisted = persist_with_faults(
+        repos,
+        post.id,
+        std::slice::from_ref(&previous),
+        "<p>previous</p>",
+        Vec::new(),
+    )
+    .await
+    .expect("persist previous render");
+    assert!(persisted);
+    (post.id, previous.id)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn persist_failure_after_sections_keeps_previous_render(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool);
+    let (post_id, previous_id) = create_rendered_post(&repos, "atomic-render").await;
+    let before = rendered_state(&repos, post_id).await;
+    assert_eq!(before.0, vec![previous_id]);
+    assert_eq!(before.1.as_deref(), Some("<p>previous</p>"));
+
+    let result = persist_with_faults(
+        &repos,
+        post_id,
+        &[rendered_section("next")],
+        "<p>next</p>",
+        vec![RepoError::from_persistence("injected failure")],
+    )
+    .await;
+
+    assert!(result.is_err(), "injected failure should fail the job");
+    assert_eq!(rendered_state(&repos, post_id).await, before);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn persist_retries_contended_transactions(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool);
+    let (post_id, _) = create_rendered_post(&repos, "contended-render").await;
+
+    let next = rendered_section("next");
+    let persisted = persist_with_faults(
+        &repos,
+        post_id,
+        std::slice::from_ref(&next),
+        "<p>next</p>",
+        vec![RepoError::Contention],
+    )
+    .await
+    .expect("retry after contention");
+    assert!(persisted);
+    let (sections, summary_html, _) = rendered_state(&repos, post_id).await;
+    assert_eq!(sections, vec![next.id]);
+    assert_eq!(summary_html.as_deref(), Some("<p>next</p>"));
+
+    let before = rendered_state(&repos, post_id).await;
+    let result = persist_with_faults(
+        &repos,
+        post_id,
+        &[rendered_section("last")],
+        "<p>last</p>",
+        (0..PERSIST_ATTEMPTS)
+            .map(|_| RepoError::Contention)
+            .collect(),
+    )
+    .await;
+    assert!(result.is_err(), "retries are bounded");
+    assert_eq!(rendered_state(&repos, post_id).await, before);
+}
+
+const STITCH_CORPUS: &str = "Intro with a [link](https://example.com).\n\n## Setup\n\nInstall it.\n\n### Linux\n\nUse `apt`.\n\n## Usage\n\nRun it.\n\n```rust\nfn main() {}\n```\n\n## Setup\n\nAgain, $x^2$.\n";
+
+fn render_sections(request: RenderRequest) -> Vec<PersistedPostSectionOwned> {
+    let output = ComrakRenderService::default()
+        .render(&request)
+        .expect("render succeeds");
+    output
+        .sections
+        .unwrap_or_default()
+        .iter()
+        .map(|section| convert_section(section, "stitched").expect("convert section"))
+        .collect()
+}
+
+fn render_body(markdown: &str) -> Vec<PersistedPostSectionOwned> {
+    render_sections(RenderRequest::new(
+        RenderTarget::PostBody {
+            slug: "stitched".into(),
+        },
+        markdown.to_string(),
+    ))
+}
+
+/// Render `previous`, then bring its sections up to `current` the way an
+/// incremental render job does. Falls back to a full render without a plan.
+fn render_edit(previous: &str, current: &str) -> Vec<PersistedPostSectionOwned> {
+    let stored = render_body(previous);
+    let Some(plan) = plan_incremental_render(previous, current) else {
+        return render_body(current);
+    };
+    let rendered = plan
+        .chunks
+        .iter()
+        .filter(|chunk| chunk.changed)
+        .map(|chunk| {
+            render_sections(
+                RenderRequest::new(
+                    RenderTarget::PostBody {
+                        slug: "stitched".into(),
+                    },
+                    chunk.markdown.clone(),
+                )
+                .with_preceding_headings(chunk.preceding_headings.clone()),
+            )
+        })
+        .collect();
+    stitch_sections(&plan, stored, rendered).expect("stitch sections")
+}
+
+/// Checksum of everything a reader sees of the sections, with parents named
+/// by index since ids differ between renders.
+fn sections_checksum(sections: &[PersistedPostSectionOwned]) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for section in sections {
+        let parent = section
+            .parent_id
+            .and_then(|parent| sections.iter().position(|other| other.id == parent));
+        hasher.update(format!(
+            "{}|{}|{:?}|{}|{}|{}|{}|{}|{}\n",
+            section.level,
+            section.position,
+            parent,
+            section.anchor_slug,
+            section.heading_text,
+            section.contains_code,
+            section.contains_math,
+            section.contains_mermaid,
+            section.heading_html,
+        ));
+        hasher.update(&section.body_html);
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[test]
+fn stitched_sections_match_a_full_render() {
+    let scenarios = [
+        (
+            "edit middle section",
+            STITCH_CORPUS.replace("Run it.", "Run it **twice**, see [setup](#setup-2)."),
+        ),
+        (
+            "edit nested section",
+            STITCH_CORPUS.replace("Use `apt`.", "Use `apt` or `dnf`."),
+        ),
+        (
+            "edit preamble and last section",
+            STITCH_CORPUS
+                .replace("Intro with", "A new intro with")
+                .replace("Again, $x^2$.", "Again."),
+        ),
+        (
+            "add section",
+            format!("{STITCH_CORPUS}\n## Extra\n\nMore.\n"),
+        ),
+        (
+            "delete first section",
+            STITCH_CORPUS.replace("## Setup\n\nInstall it.\n\n### Linux\n\nUse `apt`.\n\n", ""),
+        ),
+    ];
+
+    for (name, edited) in &scenarios {
+        let stitched = render_edit(STITCH_CORPUS, edited);
+        let full = render_body(edited);
+        assert_eq!(
+            sections_checksum(&stitched),
+            sections_checksum(&full),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn stitching_keeps_the_ids_of_untouched_sections() {
+    let edited = STITCH_CORPUS.replace("Run it.", "Run it twice.");
+    let stored = render_body(STITCH_CORPUS);
+    let plan = plan_incremental_render(STITCH_CORPUS, &edited).expect("plan");
+    let changed = &plan.chunks[1];
+    assert!(changed.changed);
+    let rendered = render_sections(
+        RenderRequest::new(
+            RenderTarget::PostBody {
+                slug: "stitched".into(),
+            },
+            changed.markdown.clone(),
+        )
+        .with_preceding_headings(changed.preceding_headings.clone()),
+    );
+    let fresh_id = rendered[0].id;
+
+    let stitched = stitch_sections(&plan, stored.clone(), vec![rendered]).expect("stitch");
+
+    let ids: Vec<Uuid> = stitched.iter().map(|section| section.id).collect();
+    assert_eq!(ids, [stored[0].id, stored[1].id, fresh_id, stored[3].id]);
+    assert_eq!(stitched[1].parent_id, Some(stored[0].id));
+    assert_eq!(stitched[3].anchor_slug, "setup-2");
+    assert!(stitch_sections(&plan, stored[1..].to_vec(), Vec::new()).is_none());
+}
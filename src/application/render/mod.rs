@@ -6,21 +6,33 @@
 //! background worker executing within the Phase 6 scheduler.
 
 mod jobs;
+mod preview;
 mod runtime;
+mod self_test;
 mod service;
 mod types;
+mod validation;
 
 pub use jobs::{
     RenderPageJobPayload, RenderPostJobPayload, RenderPostSectionJobPayload,
     RenderPostSectionsJobPayload, RenderSummaryJobPayload, enqueue_render_page_job,
-    enqueue_render_post_job, process_render_page_job, process_render_post_job,
-    process_render_post_section_job, process_render_post_sections_job, process_render_summary_job,
+    enqueue_render_post_edit_job, enqueue_render_post_job, process_render_page_job,
+    process_render_post_job, process_render_post_section_job, process_render_post_sections_job,
+    process_render_summary_job,
 };
-pub use runtime::{InFlightRenders, RenderArtifact, RenderMailbox};
+pub use preview::{
+    PreviewDebouncer, PreviewTarget, RenderPreview, RenderPreviewCommand, RenderPreviewError,
+    RenderPreviewService, SanitizerDiff,
+};
+pub use runtime::{InFlightRenders, RenderArtifact, RenderMailbox, RenderOutcome};
+pub use self_test::{FeatureStatus, RenderSelfTest, run_render_self_test};
 pub use service::{
-    ComrakRenderService, RenderConfigError, RenderPipelineConfig, configure_render_service,
+    ComrakRenderService, IncrementalPlan, MarkdownChunk, RenderConfigError, RenderPipelineConfig,
+    configure_render_service, derive_excerpt, plan_incremental_render, render_plaintext,
     render_service,
 };
 pub use types::{
-    RenderError, RenderOutput, RenderRequest, RenderService, RenderTarget, RenderedSection,
+    ContentReferences, ContentWarning, RenderError, RenderOutput, RenderRequest, RenderService,
+    RenderTarget, RenderedSection, SanitizationPolicy,
 };
+pub use validation::{ContentReferenceResolver, check_content_references};
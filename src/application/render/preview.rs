@@ -0,0 +1,298 @@
+//! Render previews for editors and sanitizer diagnostics.
+//!
+//! A preview runs the same pipeline as the render jobs but persists nothing.
+//! When the caller asks for unsanitized output, the preview also reports which
+//! elements and attributes the sanitizer would have removed.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use lol_html::{RewriteStrSettings, element, rewrite_str};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::service::ComrakRenderService;
+use super::types::{
+    RenderError, RenderRequest, RenderService, RenderTarget, RenderedSection, ResourceHints,
+};
+
+/// Slug used for preview render targets; heading anchors are derived from it.
+const PREVIEW_SLUG: &str = "preview";
+
+/// Kind of content being previewed, which selects the sanitisation policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewTarget {
+    Post,
+    Page,
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderPreviewCommand {
+    pub markdown: String,
+    pub target: PreviewTarget,
+    /// Return sanitized HTML; `false` returns the raw pipeline output plus a
+    /// summary of what the sanitizer strips.
+    pub sanitized: bool,
+}
+
+/// Elements and attributes present in the unsanitized output but missing from
+/// the sanitized one, with how often each was removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SanitizerDiff {
+    /// Removed elements keyed by tag name.
+    pub elements: BTreeMap<String, u32>,
+    /// Removed attributes keyed `tag[attribute]`.
+    pub attributes: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderPreview {
+    pub html: String,
+    pub sanitized: bool,
+    pub sections: Vec<RenderedSection>,
+    pub contains_code: bool,
+    pub contains_math: bool,
+    pub contains_mermaid: bool,
+    pub resource_hints: ResourceHints,
+    /// Mermaid blocks shown as code because the Mermaid CLI is unavailable.
+    pub mermaid_fallbacks: u32,
+    /// Only present for unsanitized previews.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stripped: Option<SanitizerDiff>,
+}
+
+#[derive(Debug, Error)]
+pub enum RenderPreviewError {
+    #[error("markdown exceeds the {limit} byte preview limit")]
+    TooLarge { limit: usize },
+    #[error(transparent)]
+    Render(#[from] RenderError),
+}
+
+#[derive(Clone)]
+pub struct RenderPreviewService {
+    renderer: Arc<ComrakRenderService>,
+    max_markdown_bytes: usize,
+}
+
+impl RenderPreviewService {
+    pub fn new(renderer: Arc<ComrakRenderService>, max_markdown_bytes: usize) -> Self {
+        Self {
+            renderer,
+            max_markdown_bytes,
+        }
+    }
+
+    pub fn max_markdown_bytes(&self) -> usize {
+        self.max_markdown_bytes
+    }
+
+    /// Render `command.markdown` on the blocking pool.
+    pub async fn preview(
+        &self,
+        command: RenderPreviewCommand,
+    ) -> Result<RenderPreview, RenderPreviewError> {
+        if command.markdown.len() > self.max_markdown_bytes {
+            return Err(RenderPreviewError::TooLarge {
+                limit: self.max_markdown_bytes,
+            });
+        }
+
+        let renderer = Arc::clone(&self.renderer);
+        tokio::task::spawn_blocking(move || render_preview(&renderer, command))
+            .await
+            .map_err(|err| RenderError::Document {
+                message: format!("preview task failed: {err}"),
+            })?
+    }
+}
+
+/// Latest-wins debounce for live previews, keyed by caller.
+///
+/// Each request waits out the quiet period and is dropped if a newer request
+/// from the same caller arrived meanwhile, so a burst of keystrokes costs one
+/// render instead of one per keystroke.
+pub struct PreviewDebouncer {
+    delay: Duration,
+    next_ticket: AtomicU64,
+    latest: Mutex<HashMap<String, u64>>,
+}
+
+impl PreviewDebouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            next_ticket: AtomicU64::new(0),
+            latest: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait out the quiet period; `false` means a newer request for `key`
+    /// superseded this one and it should not render.
+    pub async fn settle(&self, key: &str) -> bool {
+        if self.delay.is_zero() {
+            return true;
+        }
+
+        let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+        self.lock_latest().insert(key.to_string(), ticket);
+
+        tokio::time::sleep(self.delay).await;
+
+        let mut latest = self.lock_latest();
+        if latest.get(key) == Some(&ticket) {
+            latest.remove(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn lock_latest(&self) -> std::sync::MutexGuard<'_, HashMap<String, u64>> {
+        self.latest
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn render_preview(
+    renderer: &ComrakRenderService,
+    command: RenderPreviewCommand,
+) -> Result<RenderPreview, RenderPreviewError> {
+    let target = match command.target {
+        PreviewTarget::Post => RenderTarget::PostBody {
+            slug: PREVIEW_SLUG.to_string(),
+        },
+        PreviewTarget::Page => RenderTarget::PageBody {
+            slug: PREVIEW_SLUG.to_string(),
+        },
+    };
+    let request = RenderRequest::new(target, command.markdown);
+    let output = renderer.render(&request)?;
+
+    let (html, stripped) = if command.sanitized {
+        (output.html, None)
+    } else {
+        let raw = renderer.render_unsanitized(&request)?;
+        let stripped = sanitizer_diff(&raw, &output.html)?;
+        (raw, Some(stripped))
+    };
+
+    Ok(RenderPreview {
+        html,
+        sanitized: command.sanitized,
+        sections: output.sections.unwrap_or_default(),
+        contains_code: output.contains_code,
+        contains_math: output.contains_math,
+        contains_mermaid: output.contains_mermaid,
+        resource_hints: output.resource_hints,
+        mermaid_fallbacks: output.mermaid_fallbacks,
+        stripped,
+    })
+}
+
+/// Compare element and attribute counts between raw and sanitized HTML.
+///
+/// Only removals are reported: the pipeline adds attributes after sanitising
+/// (heading ids, `rel`, iframe `sandbox`), which are not interesting here.
+fn sanitizer_diff(raw: &str, sanitized: &str) -> Result<SanitizerDiff, RenderError> {
+    let before = count_markup(raw)?;
+    let after = count_markup(sanitized)?;
+
+    Ok(SanitizerDiff {
+        elements: removed(before.elements, &after.elements),
+        attributes: removed(before.attributes, &after.attributes),
+    })
+}
+
+fn removed(before: BTreeMap<String, u32>, after: &BTreeMap<String, u32>) -> BTreeMap<String, u32> {
+    before
+        .into_iter()
+        .filter_map(|(key, count)| {
+            let remaining = after.get(&key).copied().unwrap_or(0);
+            (count > remaining).then(|| (key, count - remaining))
+        })
+        .collect()
+}
+
+fn count_markup(html: &str) -> Result<SanitizerDiff, RenderError> {
+    let counts = Rc::new(RefCell::new(SanitizerDiff::default()));
+
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("*", {
+                let counts = Rc::clone(&counts);
+                move |el| {
+                    let tag = el.tag_name();
+                    let mut counts = counts.borrow_mut();
+                    for attribute in el.attributes() {
+                        *counts
+                            .attributes
+                            .entry(format!("{tag}[{}]", attribute.name()))
+                            .or_default() += 1;
+                    }
+                    *counts.elements.entry(tag).or_default() += 1;
+                    Ok(())
+                }
+            })],
+            ..RewriteStrSettings::default()
+        },
+    )
+    .map_err(|err| RenderError::Document {
+        message: err.to_string(),
+    })?;
+
+    Ok(counts.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn debouncer_lets_only_the_latest_request_through() {
+        let debouncer = Arc::new(PreviewDebouncer::new(Duration::from_millis(50)));
+
+        let first = tokio::spawn({
+            let debouncer = Arc::clone(&debouncer);
+            async move { debouncer.settle("admin:alice").await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let other_user = tokio::spawn({
+            let debouncer = Arc::clone(&debouncer);
+            async move { debouncer.settle("admin:bob").await }
+        });
+        let second = debouncer.settle("admin:alice").await;
+
+        assert!(!first.await.expect("join"));
+        assert!(second);
+        assert!(other_user.await.expect("join"));
+    }
+
+    #[test]
+    fn sanitizer_diff_reports_only_removals() {
+        let raw = r#"<p onclick="x()">Hi</p><script>alert(1)</script><iframe src="https://evil.example/"></iframe>"#;
+        let sanitized = r#"<p>Hi</p><iframe sandbox=""></iframe>"#;
+
+        let diff = sanitizer_diff(raw, sanitized).expect("diff");
+
+        assert_eq!(diff.elements, BTreeMap::from([("script".to_string(), 1)]));
+        assert_eq!(
+            diff.attributes,
+            BTreeMap::from([
+                ("iframe[src]".to_string(), 1),
+                ("p[onclick]".to_string(), 1),
+            ])
+        );
+    }
+}
@@ -2,9 +2,9 @@ use std::sync::Arc;
 
 use dashmap::DashMap;
 use thiserror::Error;
-use tokio::sync::oneshot;
-use uuid::Uuid;
+use tokio::sync::{oneshot, watch};
 
+use crate::application::render::ContentWarning;
 use crate::infra::db::PersistedPostSectionOwned;
 
 /// Coordinated mailbox used by render jobs to exchange in-memory artifacts.
@@ -13,15 +13,38 @@ use crate::infra::db::PersistedPostSectionOwned;
 /// [`JobWorkerContext`]. Each child job publishes its result using the
 /// `tracking_id` embedded in the payload, while the parent job awaits the
 /// matching channel.
+///
+/// Top-level post renders also publish their final [`RenderOutcome`] here,
+/// keyed by slug and content hash, so a job skipped as a duplicate can wait
+/// for the render that actually ran.
 #[derive(Default, Clone)]
 pub struct RenderMailbox {
     inner: Arc<DashMap<String, oneshot::Sender<RenderArtifact>>>,
+    outcomes: Arc<DashMap<String, watch::Sender<Option<RenderOutcome>>>>,
 }
 
 impl RenderMailbox {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(DashMap::new()),
+            outcomes: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Watch for the outcome of the render identified by `key`. The value
+    /// stays `None` until [`Self::publish_outcome`] is called for that key.
+    pub fn subscribe_outcome(&self, key: &str) -> watch::Receiver<Option<RenderOutcome>> {
+        self.outcomes
+            .entry(key.to_string())
+            .or_insert_with(|| watch::channel(None).0)
+            .subscribe()
+    }
+
+    /// Publish the final outcome of the render identified by `key` to every
+    /// current subscriber.
+    pub fn publish_outcome(&self, key: &str, outcome: RenderOutcome) {
+        if let Some((_key, sender)) = self.outcomes.remove(key) {
+            sender.send_replace(Some(outcome));
         }
     }
 
@@ -65,25 +88,42 @@ pub enum RenderMailboxError {
     Aborted(String),
 }
 
+/// How a top-level post render ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderOutcome {
+    /// The rendered sections and summary were stored.
+    Persisted,
+    /// The post's markdown changed while rendering; the results were discarded.
+    Superseded,
+    /// The render failed with the given error.
+    Failed(String),
+}
+
 /// Results that can be exchanged between render jobs.
 #[derive(Debug, Clone)]
 pub enum RenderArtifact {
-    Sections(Vec<PersistedPostSectionOwned>),
+    /// Rendered sections with the content warnings found in the post body.
+    Sections(Vec<PersistedPostSectionOwned>, Vec<ContentWarning>),
     Section(PersistedPostSectionOwned),
     SummaryHtml(String),
     Cancelled(RenderMailboxError),
 }
 
-/// Tracks posts that currently have an in-flight render container job.
+/// Tracks the post renders currently in flight, keyed by slug and the
+/// content hash being rendered.
+///
+/// A render of content identical to the one in flight is a duplicate and is
+/// refused; a render of different content supersedes it, and the older render
+/// discards its results when it finds the stored markdown has moved on.
 #[derive(Default, Clone)]
 pub struct InFlightRenders {
-    posts: Arc<DashMap<Uuid, ()>>,
+    posts: Arc<DashMap<String, String>>,
 }
 
 #[derive(Debug, Error)]
 pub enum InFlightError {
-    #[error("render already in progress for post {post_id}")]
-    AlreadyRunning { post_id: Uuid },
+    #[error("identical render already in progress for post `{slug}`")]
+    Duplicate { slug: String },
 }
 
 impl InFlightRenders {
@@ -93,29 +133,90 @@ impl InFlightRenders {
         }
     }
 
-    pub fn acquire(&self, post_id: Uuid) -> Result<RenderGuard, InFlightError> {
+    pub fn acquire(&self, slug: &str, content_hash: &str) -> Result<RenderGuard, InFlightError> {
         use dashmap::mapref::entry::Entry;
 
-        match self.posts.entry(post_id) {
+        match self.posts.entry(slug.to_string()) {
+            Entry::Occupied(occupied) if occupied.get() == content_hash => {
+                return Err(InFlightError::Duplicate {
+                    slug: slug.to_string(),
+                });
+            }
+            Entry::Occupied(mut occupied) => {
+                occupied.insert(content_hash.to_string());
+            }
             Entry::Vacant(vacant) => {
-                vacant.insert(());
-                Ok(RenderGuard {
-                    post_id,
-                    posts: Arc::clone(&self.posts),
-                })
+                vacant.insert(content_hash.to_string());
             }
-            Entry::Occupied(_) => Err(InFlightError::AlreadyRunning { post_id }),
         }
+
+        Ok(RenderGuard {
+            slug: slug.to_string(),
+            content_hash: content_hash.to_string(),
+            posts: Arc::clone(&self.posts),
+        })
     }
 }
 
 pub struct RenderGuard {
-    post_id: Uuid,
-    posts: Arc<DashMap<Uuid, ()>>,
+    slug: String,
+    content_hash: String,
+    posts: Arc<DashMap<String, String>>,
 }
 
 impl Drop for RenderGuard {
     fn drop(&mut self) {
-        self.posts.remove(&self.post_id);
+        // A newer render may have taken over the slug; leave its entry alone.
+        self.posts
+            .remove_if(&self.slug, |_, hash| *hash == self.content_hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_render_is_a_duplicate_until_released() {
+        let inflight = InFlightRenders::new();
+        let guard = inflight.acquire("post", "v1").expect("first render");
+
+        assert!(matches!(
+            inflight.acquire("post", "v1"),
+            Err(InFlightError::Duplicate { .. })
+        ));
+        drop(guard);
+        assert!(inflight.acquire("post", "v1").is_ok());
+    }
+
+    #[test]
+    fn newer_content_supersedes_without_being_released_by_the_old_render() {
+        let inflight = InFlightRenders::new();
+        let v1 = inflight.acquire("post", "v1").expect("v1");
+        let _v2 = inflight.acquire("post", "v2").expect("v2 supersedes v1");
+
+        drop(v1);
+        assert!(matches!(
+            inflight.acquire("post", "v2"),
+            Err(InFlightError::Duplicate { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn outcome_reaches_waiters_subscribed_before_it_is_published() {
+        let mailbox = RenderMailbox::new();
+        let mut early = mailbox.subscribe_outcome("post:v1");
+        let mut late = mailbox.subscribe_outcome("post:v1");
+
+        mailbox.publish_outcome("post:v1", RenderOutcome::Persisted);
+
+        for receiver in [&mut early, &mut late] {
+            let outcome = receiver
+                .wait_for(Option::is_some)
+                .await
+                .expect("outcome published")
+                .clone();
+            assert_eq!(outcome, Some(RenderOutcome::Persisted));
+        }
     }
 }
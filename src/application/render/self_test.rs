@@ -0,0 +1,111 @@
+//! Render pipeline self-test for deployment checks.
+//!
+//! Renders a small fixed document through the shared pipeline and reports
+//! whether syntax highlighting, math and Mermaid each produced their output in
+//! this environment.
+
+use serde::Serialize;
+
+use super::service::ComrakRenderService;
+use super::types::{RenderRequest, RenderService, RenderTarget};
+
+const SELF_TEST_SLUG: &str = "render-self-test";
+
+const SELF_TEST_MARKDOWN: &str = "\
+```rust
+fn main() {
+    println!(\"ok\");
+}
+```
+
+Inline math $e^{i\\pi} + 1 = 0$.
+
+```mermaid
+graph TD;
+    A-->B;
+```
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureStatus {
+    Ok,
+    /// The feature is not available here by configuration or environment.
+    Disabled,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderSelfTest {
+    pub code: FeatureStatus,
+    pub math: FeatureStatus,
+    pub mermaid: FeatureStatus,
+    /// Set when the document failed to render at all.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RenderSelfTest {
+    /// `true` unless a feature failed; a disabled Mermaid CLI is not a failure.
+    pub fn healthy(&self) -> bool {
+        ![self.code, self.math, self.mermaid].contains(&FeatureStatus::Error)
+    }
+}
+
+/// Render the self-test document. Blocking: Mermaid shells out to its CLI.
+pub fn run_render_self_test(renderer: &ComrakRenderService) -> RenderSelfTest {
+    let request = RenderRequest::new(
+        RenderTarget::PostBody {
+            slug: SELF_TEST_SLUG.to_string(),
+        },
+        SELF_TEST_MARKDOWN,
+    );
+
+    let output = match renderer.render(&request) {
+        Ok(output) => output,
+        Err(err) => {
+            return RenderSelfTest {
+                code: FeatureStatus::Error,
+                math: FeatureStatus::Error,
+                mermaid: FeatureStatus::Error,
+                error: Some(err.to_string()),
+            };
+        }
+    };
+
+    let status = |ok: bool| {
+        if ok {
+            FeatureStatus::Ok
+        } else {
+            FeatureStatus::Error
+        }
+    };
+
+    let mermaid = if !renderer.mermaid_enabled() {
+        FeatureStatus::Disabled
+    } else {
+        status(output.contains_mermaid && output.mermaid_fallbacks == 0)
+    };
+
+    RenderSelfTest {
+        code: status(output.contains_code && output.html.contains("syntax-")),
+        math: status(output.contains_math && output.html.contains("katex")),
+        mermaid,
+        error: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::render::render_service;
+
+    #[test]
+    fn code_and_math_pass_regardless_of_mermaid() {
+        let report = run_render_self_test(&render_service());
+
+        assert_eq!(report.code, FeatureStatus::Ok);
+        assert_eq!(report.math, FeatureStatus::Ok);
+        assert!(report.error.is_none());
+    }
+}
@@ -1,29 +1,92 @@
-use std::{borrow::Cow, collections::HashSet};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
 
 use ammonia::Builder as AmmoniaBuilder;
 use comrak::options::{ListStyleType, Options};
 
+use crate::config::EmbedProvider;
+
+use super::embed::{EMBED_SANDBOX, is_allowed_embed_src};
+
 pub(crate) fn default_options() -> Options<'static> {
     let mut options = Options::default();
     configure_extensions(&mut options);
     options
 }
 
-pub(crate) fn build_post_sanitizer() -> AmmoniaBuilder<'static> {
-    base_builder()
+pub(crate) fn build_post_sanitizer(embeds: &[EmbedProvider]) -> AmmoniaBuilder<'static> {
+    let mut builder = base_builder();
+    if embeds.is_empty() {
+        return builder;
+    }
+
+    // Players produced by the embed rewrite: only enabled provider hosts keep a
+    // `src`, and the sandbox is always imposed regardless of the markup.
+    builder.add_tags(&["iframe"]);
+    builder.add_tag_attributes(
+        "iframe",
+        &[
+            "src",
+            "title",
+            "allow",
+            "allowfullscreen",
+            "loading",
+            "referrerpolicy",
+        ],
+    );
+    builder.set_tag_attribute_value("iframe", "sandbox", EMBED_SANDBOX);
+    builder.add_tag_attributes("figure", &["data-embed-provider"]);
+
+    let embeds = embeds.to_vec();
+    builder.attribute_filter(move |element, attribute, value| {
+        if element.eq_ignore_ascii_case("iframe")
+            && attribute.eq_ignore_ascii_case("src")
+            && !is_allowed_embed_src(&embeds, value)
+        {
+            None
+        } else {
+            Some(Cow::Borrowed(value))
+        }
+    });
+
+    builder
 }
 
 pub(crate) fn build_page_sanitizer() -> AmmoniaBuilder<'static> {
     let mut builder = base_builder();
 
-    builder.add_tags(&["style", "video", "audio", "source", "picture", "track"]);
+    builder.add_tags(&[
+        "style", "video", "audio", "source", "picture", "track", "iframe",
+    ]);
     builder.rm_clean_content_tags(&["style"]);
     builder.add_generic_attributes(&["style"]);
     builder.add_generic_attribute_prefixes(&["data-"]);
+    builder.add_tag_attributes(
+        "iframe",
+        &[
+            "src",
+            "width",
+            "height",
+            "allow",
+            "allowfullscreen",
+            "loading",
+            "referrerpolicy",
+            "sandbox",
+        ],
+    );
 
-    builder.attribute_filter(|_element, attribute, value| {
+    builder.attribute_filter(|element, attribute, value| {
         if attribute.eq_ignore_ascii_case("style") {
             sanitize_style_attribute(value).map(Cow::Owned)
+        } else if element.eq_ignore_ascii_case("iframe") && attribute.eq_ignore_ascii_case("src") {
+            // Embeds must load over HTTPS; drop anything else rather than the whole frame.
+            value
+                .trim()
+                .get(..8)
+                .filter(|scheme| scheme.eq_ignore_ascii_case("https://"))
+                .map(|_| Cow::Borrowed(value))
         } else {
             Some(Cow::Borrowed(value))
         }
@@ -32,6 +95,31 @@ pub(crate) fn build_page_sanitizer() -> AmmoniaBuilder<'static> {
     builder
 }
 
+pub(crate) fn build_comment_sanitizer() -> AmmoniaBuilder<'static> {
+    let mut builder = AmmoniaBuilder::default();
+
+    builder.tags(HashSet::from([
+        "a",
+        "blockquote",
+        "br",
+        "code",
+        "del",
+        "em",
+        "li",
+        "ol",
+        "p",
+        "pre",
+        "strong",
+        "ul",
+    ]));
+    builder.generic_attributes(HashSet::new());
+    builder.tag_attributes(HashMap::from([("a", HashSet::from(["href"]))]));
+    builder.url_schemes(HashSet::from(["http", "https", "mailto"]));
+    builder.link_rel(Some("nofollow ugc noopener noreferrer"));
+
+    builder
+}
+
 fn base_builder() -> AmmoniaBuilder<'static> {
     let mut builder = AmmoniaBuilder::default();
 
@@ -422,7 +510,32 @@ fn is_unsafe_url(url: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{contains_unsafe_url, sanitize_style_attribute};
+    use super::{EmbedProvider, contains_unsafe_url, sanitize_style_attribute};
+    use crate::application::render::{
+        RenderRequest, RenderService, RenderTarget, SanitizationPolicy, render_service,
+    };
+
+    const STYLED_MARKDOWN: &str = "<div style=\"color: red;\">Styled</div>\n";
+
+    fn render_styled(target: RenderTarget, policy: Option<SanitizationPolicy>) -> String {
+        let mut request = RenderRequest::new(target, STYLED_MARKDOWN);
+        if let Some(policy) = policy {
+            request = request.with_sanitization(policy);
+        }
+        render_service().render(&request).expect("render").html
+    }
+
+    fn post_body() -> RenderTarget {
+        RenderTarget::PostBody {
+            slug: "styled".to_string(),
+        }
+    }
+
+    fn page_body() -> RenderTarget {
+        RenderTarget::PageBody {
+            slug: "styled".to_string(),
+        }
+    }
 
     #[test]
     fn sanitize_style_attribute_preserves_safe_rules() {
@@ -466,9 +579,90 @@ mod tests {
         assert!(html.contains("style=\"color: red\""));
     }
 
+    #[test]
+    fn comment_sanitizer_strips_script_and_media() {
+        let sanitizer = super::build_comment_sanitizer();
+        let html = sanitizer
+            .clean(
+                "<p onclick=\"x()\">Hi <strong>there</strong></p><script>alert(1)</script>\
+                 <img src=\"https://example.com/a.png\"><a href=\"javascript:alert(1)\">x</a>",
+            )
+            .to_string();
+
+        assert!(html.contains("<p>Hi <strong>there</strong></p>"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("alert(1)"));
+        assert!(!html.contains("<img"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("javascript:"));
+    }
+
+    #[test]
+    fn comment_sanitizer_marks_links_as_user_generated() {
+        let sanitizer = super::build_comment_sanitizer();
+        let html = sanitizer
+            .clean("<a href=\"https://example.com\">site</a>")
+            .to_string();
+
+        assert!(html.contains("rel=\"nofollow ugc noopener noreferrer\""));
+    }
+
+    #[test]
+    fn page_sanitizer_allows_https_iframe() {
+        let sanitizer = super::build_page_sanitizer();
+        let html = sanitizer
+            .clean("<iframe src=\"https://www.youtube.com/embed/abc\" width=\"560\" allowfullscreen></iframe>")
+            .to_string();
+
+        assert!(html.contains("<iframe"));
+        assert!(html.contains("src=\"https://www.youtube.com/embed/abc\""));
+        assert!(html.contains("allowfullscreen"));
+
+        let insecure = sanitizer
+            .clean("<iframe src=\"http://example.com/embed\"></iframe>")
+            .to_string();
+        assert!(!insecure.contains("http://example.com"));
+    }
+
+    #[test]
+    fn post_sanitizer_strips_iframe() {
+        let sanitizer = super::build_post_sanitizer(&[]);
+        let html = sanitizer
+            .clean("<iframe src=\"https://www.youtube.com/embed/abc\"></iframe>")
+            .to_string();
+
+        assert!(!html.contains("<iframe"));
+    }
+
+    #[test]
+    fn post_sanitizer_keeps_enabled_embed_players_only() {
+        let sanitizer = super::build_post_sanitizer(&EmbedProvider::ALL);
+        let html = sanitizer
+            .clean(
+                "<figure data-embed-provider=\"youtube\"><iframe src=\"https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ\" \
+                 allowfullscreen onload=\"x()\"></iframe></figure>",
+            )
+            .to_string();
+        assert!(html.contains("src=\"https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ\""));
+        assert!(html.contains("data-embed-provider=\"youtube\""));
+        assert!(html.contains("sandbox=\"allow-scripts"));
+        assert!(!html.contains("onload"));
+
+        let foreign = sanitizer
+            .clean("<iframe src=\"https://evil.example/embed/x\"></iframe>")
+            .to_string();
+        assert!(!foreign.contains("evil.example"));
+
+        let vimeo_only = super::build_post_sanitizer(&[EmbedProvider::Vimeo]);
+        let html = vimeo_only
+            .clean("<iframe src=\"https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ\"></iframe>")
+            .to_string();
+        assert!(!html.contains("youtube-nocookie"));
+    }
+
     #[test]
     fn post_sanitizer_preserves_strikethrough() {
-        let sanitizer = super::build_post_sanitizer();
+        let sanitizer = super::build_post_sanitizer(&[]);
         let html = sanitizer
             .clean("<p><del>Removed</del> text</p>")
             .to_string();
@@ -478,9 +672,45 @@ mod tests {
 
     #[test]
     fn post_sanitizer_preserves_underline() {
-        let sanitizer = super::build_post_sanitizer();
+        let sanitizer = super::build_post_sanitizer(&[]);
         let html = sanitizer.clean("<p><u>Underline</u> text</p>").to_string();
 
         assert!(html.contains("<u>Underline</u>"));
     }
+
+    #[test]
+    fn render_applies_the_policy_of_the_target() {
+        let post = render_styled(post_body(), None);
+        assert!(post.contains("Styled"));
+        assert!(
+            !post.contains("style="),
+            "post policy strips inline styles: {post}"
+        );
+
+        let page = render_styled(page_body(), None);
+        assert!(
+            page.contains("style=\"color: red\""),
+            "page policy keeps inline styles: {page}"
+        );
+    }
+
+    #[test]
+    fn render_prefers_an_explicit_policy() {
+        let post = render_styled(post_body(), Some(SanitizationPolicy::Page));
+        assert!(post.contains("style=\"color: red\""), "{post}");
+
+        let page = render_styled(page_body(), Some(SanitizationPolicy::Post));
+        assert!(!page.contains("style="), "{page}");
+    }
+
+    #[test]
+    fn render_with_comment_policy_strips_script() {
+        let request = RenderRequest::new(post_body(), "Hi <script>alert(1)</script>\n")
+            .with_sanitization(SanitizationPolicy::Comment);
+        let html = render_service().render(&request).expect("render").html;
+
+        assert!(html.contains("Hi"), "{html}");
+        assert!(!html.contains("<script"), "{html}");
+        assert!(!html.contains("alert(1)"), "{html}");
+    }
 }
@@ -0,0 +1,142 @@
+//! Embedded players for bare links to known video providers.
+
+use url::Url;
+
+use crate::config::EmbedProvider;
+
+const YOUTUBE_EMBED_HOST: &str = "www.youtube-nocookie.com";
+const VIMEO_EMBED_HOST: &str = "player.vimeo.com";
+
+/// Sandbox applied to every embedded player; enough for playback, nothing more.
+pub(crate) const EMBED_SANDBOX: &str =
+    "allow-scripts allow-same-origin allow-presentation allow-popups";
+
+/// Resolve a link to a privacy-friendly player URL for one of `providers`.
+pub(crate) fn embed_for(
+    providers: &[EmbedProvider],
+    link: &str,
+) -> Option<(EmbedProvider, String)> {
+    let url = Url::parse(link.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.to_ascii_lowercase();
+
+    providers.iter().find_map(|&provider| {
+        let src = match provider {
+            EmbedProvider::YouTube => youtube_video_id(&url, &host)
+                .map(|id| format!("https://{YOUTUBE_EMBED_HOST}/embed/{id}")),
+            EmbedProvider::Vimeo => vimeo_video_id(&url, &host)
+                .map(|id| format!("https://{VIMEO_EMBED_HOST}/video/{id}?dnt=1")),
+        }?;
+        Some((provider, src))
+    })
+}
+
+/// Whether an iframe `src` points at the player of an enabled provider.
+pub(crate) fn is_allowed_embed_src(providers: &[EmbedProvider], src: &str) -> bool {
+    let Ok(url) = Url::parse(src.trim()) else {
+        return false;
+    };
+    if url.scheme() != "https" {
+        return false;
+    }
+    let host = url.host_str().unwrap_or_default();
+
+    providers.iter().any(|provider| match provider {
+        EmbedProvider::YouTube => host == YOUTUBE_EMBED_HOST && url.path().starts_with("/embed/"),
+        EmbedProvider::Vimeo => host == VIMEO_EMBED_HOST && url.path().starts_with("/video/"),
+    })
+}
+
+/// Player markup for a `src` produced by [`embed_for`]; video ids are
+/// validated there, so the URL needs no further escaping.
+pub(crate) fn build_embed_html(provider: EmbedProvider, src: &str) -> String {
+    let title = match provider {
+        EmbedProvider::YouTube => "YouTube video",
+        EmbedProvider::Vimeo => "Vimeo video",
+    };
+    format!(
+        "<figure data-embed-provider=\"{provider}\"><iframe src=\"{src}\" title=\"{title}\" \
+         loading=\"lazy\" referrerpolicy=\"strict-origin-when-cross-origin\" \
+         allow=\"encrypted-media; picture-in-picture; fullscreen\" allowfullscreen \
+         sandbox=\"{EMBED_SANDBOX}\"></iframe></figure>",
+        provider = provider.as_str(),
+    )
+}
+
+fn youtube_video_id(url: &Url, host: &str) -> Option<String> {
+    let candidate = match host {
+        "youtu.be" => url.path_segments()?.next()?.to_string(),
+        "youtube.com" | "www.youtube.com" | "m.youtube.com" => {
+            let mut segments = url.path_segments()?;
+            match segments.next()? {
+                "watch" => url
+                    .query_pairs()
+                    .find(|(key, _)| key == "v")
+                    .map(|(_, value)| value.into_owned())?,
+                "embed" | "shorts" | "live" => segments.next()?.to_string(),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    let valid = candidate.len() == 11
+        && candidate
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'));
+    valid.then_some(candidate)
+}
+
+fn vimeo_video_id(url: &Url, host: &str) -> Option<String> {
+    let mut segments = url.path_segments()?;
+    let candidate = match host {
+        "vimeo.com" | "www.vimeo.com" => segments.next()?,
+        VIMEO_EMBED_HOST if segments.next()? == "video" => segments.next()?,
+        _ => return None,
+    };
+
+    let valid = !candidate.is_empty() && candidate.chars().all(|ch| ch.is_ascii_digit());
+    valid.then(|| candidate.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [EmbedProvider; 2] = EmbedProvider::ALL;
+
+    #[test]
+    fn youtube_links_resolve_to_nocookie_player() {
+        for link in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://youtu.be/dQw4w9WgXcQ",
+            "https://m.youtube.com/shorts/dQw4w9WgXcQ",
+        ] {
+            let (provider, src) = embed_for(&ALL, link).expect("youtube embed");
+            assert_eq!(provider, EmbedProvider::YouTube);
+            assert_eq!(src, "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ");
+            assert!(is_allowed_embed_src(&ALL, &src));
+        }
+    }
+
+    #[test]
+    fn vimeo_links_resolve_to_player() {
+        let (provider, src) = embed_for(&ALL, "https://vimeo.com/76979871").expect("vimeo embed");
+        assert_eq!(provider, EmbedProvider::Vimeo);
+        assert_eq!(src, "https://player.vimeo.com/video/76979871?dnt=1");
+    }
+
+    #[test]
+    fn unknown_or_disabled_links_are_not_embedded() {
+        assert!(embed_for(&ALL, "https://example.com/watch?v=dQw4w9WgXcQ").is_none());
+        assert!(embed_for(&ALL, "https://www.youtube.com/channel/abc").is_none());
+        assert!(embed_for(&[EmbedProvider::Vimeo], "https://youtu.be/dQw4w9WgXcQ").is_none());
+        assert!(!is_allowed_embed_src(
+            &[EmbedProvider::Vimeo],
+            "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ"
+        ));
+        assert!(!is_allowed_embed_src(&ALL, "https://evil.example/embed/x"));
+    }
+}
@@ -0,0 +1,245 @@
+use comrak::{Arena, nodes::NodeValue, parse_document};
+
+use super::config::default_options;
+use super::rewrite::heading_text;
+
+/// Slice of a post's markdown running from a top-level heading to the next
+/// one, rendered on its own by an incremental render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownChunk {
+    pub markdown: String,
+    /// Text of every heading before the chunk; their anchors are reserved
+    /// so the chunk renders with the anchors it has in the whole post.
+    pub preceding_headings: Vec<String>,
+    /// Number of sections the chunk renders to, one per heading.
+    pub section_count: usize,
+    /// Whether the chunk differs from the previous markdown.
+    pub changed: bool,
+}
+
+/// How to bring the sections rendered from a post's previous markdown up to
+/// date with its current markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncrementalPlan {
+    /// Markdown before the first heading. It renders to no section, but its
+    /// links still count towards the post's content warnings.
+    pub preamble: String,
+    pub chunks: Vec<MarkdownChunk>,
+}
+
+impl IncrementalPlan {
+    pub fn section_count(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.section_count).sum()
+    }
+}
+
+/// Plan re-rendering only the top-level sections that changed between
+/// `previous` and `current`.
+///
+/// Returns `None` when a full render is needed: when the headings differ in
+/// text, level or order (anchors and the outline would shift), or when either
+/// markdown has content whose rendering depends on other sections, such as
+/// footnotes, link reference definitions or raw HTML.
+pub fn plan_incremental_render(previous: &str, current: &str) -> Option<IncrementalPlan> {
+    let previous = split_outline(previous)?;
+    let current = split_outline(current)?;
+    if previous.headings().ne(current.headings()) {
+        return None;
+    }
+
+    let mut preceding_headings = Vec::new();
+    let chunks = current
+        .chunks
+        .into_iter()
+        .zip(previous.chunks)
+        .map(|(chunk, before)| {
+            let planned = MarkdownChunk {
+                changed: chunk.markdown != before.markdown,
+                preceding_headings: preceding_headings.clone(),
+                section_count: chunk.headings.len(),
+                markdown: chunk.markdown,
+            };
+            preceding_headings.extend(chunk.headings.into_iter().map(|(_, text)| text));
+            planned
+        })
+        .collect();
+
+    Some(IncrementalPlan {
+        preamble: current.preamble,
+        chunks,
+    })
+}
+
+struct Outline {
+    preamble: String,
+    chunks: Vec<OutlineChunk>,
+}
+
+struct OutlineChunk {
+    markdown: String,
+    headings: Vec<(u8, String)>,
+}
+
+impl Outline {
+    fn headings(&self) -> impl Iterator<Item = &(u8, String)> {
+        self.chunks.iter().flat_map(|chunk| chunk.headings.iter())
+    }
+}
+
+/// Split `markdown` into the text before its first heading and one chunk per
+/// root section, the way the section outline nests headings.
+fn split_outline(markdown: &str) -> Option<Outline> {
+    // Line numbers below follow comrak, which also ends lines at a lone `\r`.
+    if markdown.replace("\r\n", "\n").contains('\r') {
+        return None;
+    }
+    // Reference definitions resolve across the whole document. Matching any
+    // `]:` is stricter than needed, which only costs a full render.
+    if markdown.contains("]:") {
+        return None;
+    }
+
+    let options = default_options();
+    let arena = Arena::new();
+    let root = parse_document(&arena, markdown, &options);
+
+    // (first line, level, text) of every heading, in document order.
+    let mut headings: Vec<(usize, u8, String)> = Vec::new();
+    for node in root.descendants() {
+        let (line, level) = {
+            let data = node.data.borrow();
+            match &data.value {
+                NodeValue::Heading(heading) => (data.sourcepos.start.line, heading.level),
+                NodeValue::FootnoteDefinition(_)
+                | NodeValue::FootnoteReference(_)
+                | NodeValue::HtmlBlock(_)
+                | NodeValue::HtmlInline(_) => return None,
+                _ => continue,
+            }
+        };
+        // A heading inside a quote or list cannot start a chunk of its own.
+        if !node
+            .parent()
+            .is_some_and(|parent| std::ptr::eq(parent, root))
+        {
+            return None;
+        }
+        headings.push((line, level, heading_text(node)));
+    }
+    if headings.is_empty() {
+        return None;
+    }
+
+    // A heading is a root section unless an earlier heading has a lower level.
+    let mut roots = Vec::new();
+    let mut min_level = u8::MAX;
+    for (idx, (_, level, _)) in headings.iter().enumerate() {
+        if *level <= min_level {
+            min_level = *level;
+            roots.push(idx);
+        }
+    }
+
+    let lines: Vec<&str> = markdown.split_inclusive('\n').collect();
+    let first_line = |heading: usize| headings[heading].0.saturating_sub(1).min(lines.len());
+    let preamble = lines[..first_line(0)].concat();
+    let chunks = roots
+        .iter()
+        .enumerate()
+        .map(|(idx, &start)| {
+            let end = roots.get(idx + 1).copied();
+            let line_end = end.map(first_line).unwrap_or(lines.len());
+            OutlineChunk {
+                markdown: lines[first_line(start)..line_end].concat(),
+                headings: headings[start..end.unwrap_or(headings.len())]
+                    .iter()
+                    .map(|(_, level, text)| (*level, text.clone()))
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Some(Outline { preamble, chunks })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POST: &str = "Intro line.\n\n## Setup\n\nInstall it.\n\n### Linux\n\nUse apt.\n\n## Usage\n\nRun it.\n\n## Setup\n\nAgain.\n";
+
+    #[test]
+    fn plan_splits_at_root_headings_and_marks_edited_chunks() {
+        let edited = POST.replace("Run it.", "Run it twice.");
+        let plan = plan_incremental_render(POST, &edited).expect("plan");
+
+        assert_eq!(plan.preamble, "Intro line.\n\n");
+        assert_eq!(plan.section_count(), 4);
+        let chunks: Vec<(&str, usize, bool)> = plan
+            .chunks
+            .iter()
+            .map(|chunk| (chunk.markdown.as_str(), chunk.section_count, chunk.changed))
+            .collect();
+        assert_eq!(
+            chunks,
+            [
+                (
+                    "## Setup\n\nInstall it.\n\n### Linux\n\nUse apt.\n\n",
+                    2,
+                    false
+                ),
+                ("## Usage\n\nRun it twice.\n\n", 1, true),
+                ("## Setup\n\nAgain.\n", 1, false),
+            ]
+        );
+        assert_eq!(plan.chunks[1].preceding_headings, ["Setup", "Linux"]);
+        assert_eq!(
+            plan.chunks[2].preceding_headings,
+            ["Setup", "Linux", "Usage"]
+        );
+    }
+
+    #[test]
+    fn plan_treats_a_leading_deeper_heading_as_its_own_root() {
+        let markdown = "### Aside\n\nx\n\n## Main\n\ny\n\n### Detail\n\nz\n";
+        let plan = plan_incremental_render(markdown, markdown).expect("plan");
+
+        let counts: Vec<usize> = plan.chunks.iter().map(|c| c.section_count).collect();
+        assert_eq!(counts, [1, 2]);
+        assert!(plan.chunks.iter().all(|chunk| !chunk.changed));
+        assert!(plan.preamble.is_empty());
+    }
+
+    #[test]
+    fn plan_keeps_crlf_line_endings_and_setext_headings() {
+        let markdown = "Setup\r\n=====\r\n\r\nA\r\n\r\nUsage\r\n=====\r\n\r\nB\r\n";
+        let edited = markdown.replace("B\r\n", "C\r\n");
+        let plan = plan_incremental_render(markdown, &edited).expect("plan");
+
+        assert_eq!(plan.chunks[0].markdown, "Setup\r\n=====\r\n\r\nA\r\n\r\n");
+        assert_eq!(plan.chunks[1].markdown, "Usage\r\n=====\r\n\r\nC\r\n");
+        assert!(plan.chunks[1].changed);
+    }
+
+    #[test]
+    fn plan_falls_back_when_sections_depend_on_each_other() {
+        let renamed = POST.replace("## Usage", "## Running");
+        let added = format!("{POST}\n## Extra\n\nMore.\n");
+        let removed = POST.replace("## Setup\n\nAgain.\n", "");
+        let relevelled = POST.replace("### Linux", "## Linux");
+        for edited in [&renamed, &added, &removed, &relevelled] {
+            assert!(plan_incremental_render(POST, edited).is_none(), "{edited}");
+        }
+
+        for body in [
+            "## A\n\nSee[^1].\n\n## B\n\n[^1]: note\n",
+            "## A\n\nInline^[note].\n",
+            "## A\n\n[link][ref]\n\n## B\n\n[ref]: /posts/x\n",
+            "## A\n\n<div>\n\n## B\n\n</div>\n",
+            "## A\n\n> ## Quoted\n",
+            "No headings at all.\n",
+        ] {
+            assert!(plan_incremental_render(body, body).is_none(), "{body}");
+        }
+    }
+}
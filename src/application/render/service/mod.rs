@@ -1,10 +1,17 @@
 mod config;
+mod embed;
 mod highlight;
+mod incremental;
 mod math;
 mod mermaid;
+mod plaintext;
 mod rewrite;
 mod sections;
 
+pub use incremental::{IncrementalPlan, MarkdownChunk, plan_incremental_render};
+pub use plaintext::{derive_excerpt, render_plaintext};
+pub(crate) use sections::collect_post_references;
+
 use std::{path::PathBuf, sync::Arc};
 
 use comrak::{Arena, format_html, nodes::AstNode, parse_document};
@@ -14,12 +21,16 @@ use thiserror::Error;
 use tracing::warn;
 
 use crate::application::render::types::{
-    RenderError, RenderOutput, RenderRequest, RenderService, RenderTarget,
+    RenderError, RenderOutput, RenderRequest, RenderService, RenderTarget, SanitizationPolicy,
+};
+use crate::config::{
+    DEFAULT_MERMAID_CACHE_DIR, DEFAULT_MERMAID_CLI_PATH, EmbedProvider, HeadingAnchorPlacement,
 };
-use crate::config::{DEFAULT_MERMAID_CACHE_DIR, DEFAULT_MERMAID_CLI_PATH};
 
 use self::mermaid::{MermaidRenderError, MermaidRenderer};
-use config::{build_page_sanitizer, build_post_sanitizer, default_options};
+use config::{
+    build_comment_sanitizer, build_page_sanitizer, build_post_sanitizer, default_options,
+};
 use rewrite::rewrite_ast;
 use sections::{ProcessedHtml, post_process};
 
@@ -30,6 +41,9 @@ pub struct ComrakRenderService {
     class_style: ClassStyle,
     post_sanitizer: ammonia::Builder<'static>,
     page_sanitizer: ammonia::Builder<'static>,
+    comment_sanitizer: ammonia::Builder<'static>,
+    embed_providers: Vec<EmbedProvider>,
+    heading_anchors: HeadingAnchorPlacement,
     mermaid: Option<MermaidRenderer>,
 }
 
@@ -42,9 +56,10 @@ impl ComrakRenderService {
         let syntax_set: SyntaxSet =
             from_uncompressed_data(syntax_bytes).expect("syntax pack must be valid");
         let class_style = ClassStyle::SpacedPrefixed { prefix: "syntax-" };
-        let post_sanitizer = build_post_sanitizer();
-        let page_sanitizer = build_page_sanitizer();
         let config = active_render_config();
+        let post_sanitizer = build_post_sanitizer(&config.embed_providers);
+        let page_sanitizer = build_page_sanitizer();
+        let comment_sanitizer = build_comment_sanitizer();
         let mermaid = match MermaidRenderer::new(
             config.mermaid_cli_path.clone(),
             config.mermaid_cache_dir.clone(),
@@ -62,6 +77,9 @@ impl ComrakRenderService {
             class_style,
             post_sanitizer,
             page_sanitizer,
+            comment_sanitizer,
+            embed_providers: config.embed_providers.clone(),
+            heading_anchors: config.heading_anchors,
             mermaid,
         }
     }
@@ -91,17 +109,19 @@ impl RenderService for ComrakRenderService {
             &self.syntax_set,
             &self.class_style,
             self.mermaid.as_ref(),
+            &self.embed_providers,
             request.target.slug(),
+            &request.preceding_headings,
         )?;
 
         let rendered_html = render_html_stage(root, &self.options)?;
 
-        let sanitized_html = sanitize_stage(
-            rendered_html,
-            &request.target,
-            &self.post_sanitizer,
-            &self.page_sanitizer,
-        )?;
+        let sanitizer = match request.sanitization_policy() {
+            SanitizationPolicy::Post => &self.post_sanitizer,
+            SanitizationPolicy::Page => &self.page_sanitizer,
+            SanitizationPolicy::Comment => &self.comment_sanitizer,
+        };
+        let sanitized_html = sanitize_stage(rendered_html, sanitizer)?;
 
         let restored_html = restore_stage(sanitized_html, &rewrite_outcome);
 
@@ -109,6 +129,7 @@ impl RenderService for ComrakRenderService {
             &restored_html,
             &request.target,
             &rewrite_outcome.headings,
+            self.heading_anchors,
             request.public_site_url.as_deref(),
         )?;
         let ProcessedHtml {
@@ -119,6 +140,7 @@ impl RenderService for ComrakRenderService {
             contains_mermaid: processed_contains_mermaid,
             resource_hints,
             content_metrics,
+            references,
         } = processed;
 
         let contains_code = rewrite_outcome.contains_code || processed_contains_code;
@@ -140,12 +162,35 @@ impl RenderService for ComrakRenderService {
 
         output.resource_hints = resource_hints;
         output.content_metrics = content_metrics;
+        output.mermaid_fallbacks = rewrite_outcome.mermaid_fallbacks;
+        output.references = references;
 
         Ok(output)
     }
 }
 
 impl ComrakRenderService {
+    /// Whether the Mermaid CLI initialised; without it diagrams render as code.
+    pub fn mermaid_enabled(&self) -> bool {
+        self.mermaid.is_some()
+    }
+
+    /// Identifies the settings that shape rendered HTML, so output rendered
+    /// under different settings is never stitched together.
+    pub fn settings_fingerprint(&self) -> String {
+        let embeds = self
+            .embed_providers
+            .iter()
+            .map(|provider| provider.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "anchors={};embeds={embeds};mermaid={}",
+            self.heading_anchors.as_str(),
+            self.mermaid_enabled()
+        )
+    }
+
     /// Render markdown into HTML while skipping the sanitisation stage. This is
     /// intended for diagnostics when refining sanitizer rules.
     pub fn render_unsanitized(&self, request: &RenderRequest) -> Result<String, RenderError> {
@@ -157,7 +202,9 @@ impl ComrakRenderService {
             &self.syntax_set,
             &self.class_style,
             self.mermaid.as_ref(),
+            &self.embed_providers,
             request.target.slug(),
+            &request.preceding_headings,
         )?;
 
         let rendered_html = render_html_stage(root, &self.options)?;
@@ -171,6 +218,8 @@ impl ComrakRenderService {
 pub struct RenderPipelineConfig {
     pub mermaid_cli_path: PathBuf,
     pub mermaid_cache_dir: PathBuf,
+    pub embed_providers: Vec<EmbedProvider>,
+    pub heading_anchors: HeadingAnchorPlacement,
 }
 
 impl Default for RenderPipelineConfig {
@@ -178,6 +227,8 @@ impl Default for RenderPipelineConfig {
         Self {
             mermaid_cli_path: PathBuf::from(DEFAULT_MERMAID_CLI_PATH),
             mermaid_cache_dir: PathBuf::from(DEFAULT_MERMAID_CACHE_DIR),
+            embed_providers: EmbedProvider::ALL.to_vec(),
+            heading_anchors: HeadingAnchorPlacement::default(),
         }
     }
 }
@@ -187,6 +238,8 @@ impl From<&crate::config::RenderSettings> for RenderPipelineConfig {
         Self {
             mermaid_cli_path: settings.mermaid_cli_path.clone(),
             mermaid_cache_dir: settings.mermaid_cache_dir.clone(),
+            embed_providers: settings.embed_providers.clone(),
+            heading_anchors: settings.heading_anchors,
         }
     }
 }
@@ -224,9 +277,19 @@ fn rewrite_stage<'a>(
     syntax_set: &SyntaxSet,
     class_style: &ClassStyle,
     mermaid_renderer: Option<&MermaidRenderer>,
+    embeds: &[EmbedProvider],
     slug: &str,
+    preceding_headings: &[String],
 ) -> Result<rewrite::RewriteOutcome, RenderError> {
-    rewrite_ast(root, syntax_set, class_style, mermaid_renderer, slug)
+    rewrite_ast(
+        root,
+        syntax_set,
+        class_style,
+        mermaid_renderer,
+        embeds,
+        slug,
+        preceding_headings,
+    )
 }
 
 fn render_html_stage<'a>(
@@ -242,14 +305,8 @@ fn render_html_stage<'a>(
 
 fn sanitize_stage(
     html: String,
-    target: &RenderTarget,
-    post_sanitizer: &ammonia::Builder<'static>,
-    page_sanitizer: &ammonia::Builder<'static>,
+    sanitizer: &ammonia::Builder<'static>,
 ) -> Result<String, RenderError> {
-    let sanitizer = match target {
-        RenderTarget::PageBody { .. } => page_sanitizer,
-        _ => post_sanitizer,
-    };
     Ok(sanitizer.clean(&html).to_string())
 }
 
@@ -279,7 +336,8 @@ fn post_process_stage(
     html: &str,
     target: &RenderTarget,
     headings: &[rewrite::HeadingInfo],
+    heading_anchors: HeadingAnchorPlacement,
     public_site_url: Option<&str>,
 ) -> Result<ProcessedHtml, RenderError> {
-    post_process(html, target, headings, public_site_url)
+    post_process(html, target, headings, heading_anchors, public_site_url)
 }
@@ -0,0 +1,113 @@
+//! Plain-text rendering of markdown for excerpts and other text-only surfaces.
+
+use comrak::{
+    Arena,
+    nodes::{AstNode, NodeValue},
+    parse_document,
+};
+
+use super::config::default_options;
+
+const ELLIPSIS: char = '…';
+
+/// Render markdown to a single line of readable text.
+///
+/// Code blocks, raw HTML, images, display math and footnote definitions are
+/// dropped; everything else contributes its text with whitespace collapsed.
+pub fn render_plaintext(markdown: &str) -> String {
+    let arena = Arena::new();
+    let options = default_options();
+    let root = parse_document(&arena, markdown, &options);
+
+    let mut buffer = String::new();
+    collect_text(root, &mut buffer);
+    buffer.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Derive an excerpt of at most `max_chars` characters (plus an ellipsis when
+/// truncated) from the plain-text rendering of `markdown`.
+pub fn derive_excerpt(markdown: &str, max_chars: usize) -> String {
+    truncate_at_word(&render_plaintext(markdown), max_chars)
+}
+
+fn collect_text<'a>(node: &'a AstNode<'a>, buffer: &mut String) {
+    let is_block = {
+        let data = node.data.borrow();
+        match &data.value {
+            NodeValue::Text(text) => buffer.push_str(text),
+            NodeValue::Code(code) => buffer.push_str(&code.literal),
+            NodeValue::Math(math) if !math.display_math => buffer.push_str(&math.literal),
+            NodeValue::LineBreak | NodeValue::SoftBreak => buffer.push(' '),
+            NodeValue::CodeBlock(_)
+            | NodeValue::HtmlBlock(_)
+            | NodeValue::HtmlInline(_)
+            | NodeValue::Image(_)
+            | NodeValue::Math(_)
+            | NodeValue::FootnoteDefinition(_) => return,
+            _ => {}
+        }
+        data.value.block()
+    };
+
+    let mut child = node.first_child();
+    while let Some(next) = child {
+        collect_text(next, buffer);
+        child = next.next_sibling();
+    }
+
+    if is_block {
+        buffer.push(' ');
+    }
+}
+
+fn truncate_at_word(text: &str, max_chars: usize) -> String {
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return text.to_string();
+    };
+
+    let head = &text[..cut];
+    let head = if text[cut..].starts_with(char::is_whitespace) {
+        head
+    } else {
+        match head.rfind(char::is_whitespace) {
+            Some(boundary) if boundary > 0 => &head[..boundary],
+            _ => head,
+        }
+    };
+
+    let head =
+        head.trim_end_matches(|ch: char| ch.is_whitespace() || matches!(ch, ',' | ';' | ':'));
+    format!("{head}{ELLIPSIS}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plaintext_keeps_prose_and_drops_code_blocks() {
+        let markdown =
+            "# Title\n\nSome *emphasis* and `code`.\n\n```rust\nfn main() {}\n```\n\n- one\n- two";
+        assert_eq!(
+            render_plaintext(markdown),
+            "Title Some emphasis and code. one two"
+        );
+    }
+
+    #[test]
+    fn short_text_is_returned_untouched() {
+        assert_eq!(derive_excerpt("Hello world.", 50), "Hello world.");
+    }
+
+    #[test]
+    fn long_text_is_cut_at_a_word_boundary_with_ellipsis() {
+        let excerpt = derive_excerpt("The quick brown fox jumps over the lazy dog", 18);
+        assert_eq!(excerpt, "The quick brown…");
+    }
+
+    #[test]
+    fn truncation_counts_characters_not_bytes() {
+        let excerpt = derive_excerpt("日本語のテキスト です", 4);
+        assert_eq!(excerpt, "日本語の…");
+    }
+}
@@ -5,9 +5,11 @@ use syntect::html::ClassStyle;
 use syntect::parsing::SyntaxSet;
 use tracing::warn;
 
-use crate::{application::render::types::RenderError, domain::slug::AnchorSlugger};
+use crate::{
+    application::render::types::RenderError, config::EmbedProvider, domain::slug::AnchorSlugger,
+};
 
-use super::{highlight, math, mermaid::MermaidRenderer};
+use super::{embed, highlight, math, mermaid::MermaidRenderer};
 
 #[path = "rewrite/media.rs"]
 mod media;
@@ -34,6 +36,8 @@ pub(crate) struct RewriteOutcome {
     pub(crate) contains_code: bool,
     pub(crate) contains_math: bool,
     pub(crate) contains_mermaid: bool,
+    /// Mermaid blocks left as code because the CLI was missing or failed.
+    pub(crate) mermaid_fallbacks: u32,
     pub(crate) headings: Vec<HeadingInfo>,
     pub(crate) mermaid_fragments: Vec<MermaidFragment>,
     pub(crate) math_fragments: Vec<MathFragment>,
@@ -54,18 +58,32 @@ pub(crate) struct MathFragment {
     pub(crate) is_block: bool,
 }
 
+/// Rewrite `root` in place. `preceding_headings` are the headings of the
+/// document that come before this markdown; their anchors are reserved so a
+/// slice of a post gets the same anchors it has in the whole post.
 pub(crate) fn rewrite_ast<'a>(
     root: &'a AstNode<'a>,
     syntax_set: &SyntaxSet,
     class_style: &ClassStyle,
     mermaid: Option<&MermaidRenderer>,
+    embeds: &[EmbedProvider],
     slug: &str,
+    preceding_headings: &[String],
 ) -> Result<RewriteOutcome, RenderError> {
-    let mut walker = RewriteWalker::new(syntax_set, class_style, mermaid, slug);
+    let mut walker = RewriteWalker::new(syntax_set, class_style, mermaid, embeds, slug);
+    for heading in preceding_headings {
+        walker.anchor_for(heading)?;
+    }
     walker.visit_nodes(root)?;
     Ok(walker.outcome)
 }
 
+/// Heading text as anchors and sections see it, with whitespace runs collapsed.
+pub(crate) fn heading_text(node: &AstNode<'_>) -> String {
+    let text = utils::collect_heading_text(node);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 struct RewriteWalker<'a> {
     syntax_set: &'a SyntaxSet,
     class_style: &'a ClassStyle,
@@ -73,6 +91,7 @@ struct RewriteWalker<'a> {
     slugger: AnchorSlugger,
     heading_stack: Vec<usize>,
     mermaid: Option<&'a MermaidRenderer>,
+    embeds: &'a [EmbedProvider],
     slug: &'a str,
 }
 
@@ -81,6 +100,7 @@ impl<'a> RewriteWalker<'a> {
         syntax_set: &'a SyntaxSet,
         class_style: &'a ClassStyle,
         mermaid: Option<&'a MermaidRenderer>,
+        embeds: &'a [EmbedProvider],
         slug: &'a str,
     ) -> Self {
         Self {
@@ -90,10 +110,19 @@ impl<'a> RewriteWalker<'a> {
             slugger: AnchorSlugger::new(),
             heading_stack: Vec::new(),
             mermaid,
+            embeds,
             slug,
         }
     }
 
+    fn anchor_for(&mut self, heading: &str) -> Result<String, RenderError> {
+        self.slugger
+            .anchor_for(heading)
+            .map_err(|err| RenderError::Anchoring {
+                message: err.to_string(),
+            })
+    }
+
     fn visit_nodes(&mut self, node: &AstNode<'_>) -> Result<(), RenderError> {
         if {
             let data = node.data.borrow();
@@ -103,13 +132,8 @@ impl<'a> RewriteWalker<'a> {
         }
 
         if let Some(level) = utils::heading_level(node) {
-            let text = utils::collect_heading_text(node);
-            let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
-            let slug = self.slugger.anchor_for(normalized.trim()).map_err(|err| {
-                RenderError::Anchoring {
-                    message: err.to_string(),
-                }
-            })?;
+            let normalized = heading_text(node);
+            let slug = self.anchor_for(normalized.trim())?;
             while let Some(&idx) = self.heading_stack.last() {
                 if self.outcome.headings[idx].level < level {
                     break;
@@ -128,6 +152,11 @@ impl<'a> RewriteWalker<'a> {
             self.heading_stack.push(self.outcome.headings.len() - 1);
         }
 
+        if self.handle_embed_paragraph(node) {
+            // The paragraph was replaced by a player; its link children are gone.
+            return Ok(());
+        }
+
         if self.handle_math_node(node)? {
             // Math nodes are fully handled; skip further processing.
         } else if let Some((info, literal)) = utils::extract_code_block(node) {
@@ -169,6 +198,30 @@ impl<'a> RewriteWalker<'a> {
         Ok(())
     }
 
+    fn handle_embed_paragraph(&mut self, node: &AstNode<'_>) -> bool {
+        if self.embeds.is_empty() {
+            return false;
+        }
+        let Some(link) = utils::bare_link_url(node) else {
+            return false;
+        };
+        let Some((provider, src)) = embed::embed_for(self.embeds, &link) else {
+            return false;
+        };
+
+        {
+            let mut data = node.data.borrow_mut();
+            data.value = NodeValue::HtmlBlock(NodeHtmlBlock {
+                block_type: 0,
+                literal: embed::build_embed_html(provider, &src),
+            });
+        }
+        while let Some(child) = node.first_child() {
+            child.detach();
+        }
+        true
+    }
+
     fn handle_math_node(&mut self, node: &AstNode<'_>) -> Result<bool, RenderError> {
         let math_data = {
             let data = node.data.borrow();
@@ -351,6 +404,7 @@ impl<'a> RewriteWalker<'a> {
                 });
 
         self.outcome.contains_code = true;
+        self.outcome.mermaid_fallbacks = self.outcome.mermaid_fallbacks.saturating_add(1);
         if let Some(&idx) = self.heading_stack.last() {
             self.outcome.headings[idx].has_block_code = true;
         }
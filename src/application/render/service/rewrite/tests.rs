@@ -16,7 +16,8 @@ fn rewrite_inline_math_renders_with_katex() {
     let root = parse_document(&arena, "$a^2$", &options);
     let (syntax_set, class_style) = syntax_and_style();
 
-    let outcome = rewrite_ast(root, &syntax_set, &class_style, None, "math-test").expect("rewrite");
+    let outcome =
+        rewrite_ast(root, &syntax_set, &class_style, None, &[], "math-test", &[]).expect("rewrite");
     assert!(outcome.contains_math);
     assert_eq!(outcome.math_fragments.len(), 1);
 
@@ -46,13 +47,76 @@ fn rewrite_mermaid_without_renderer_falls_back_to_code() {
     let root = parse_document(&arena, markdown, &options);
     let (syntax_set, class_style) = syntax_and_style();
 
-    let outcome =
-        rewrite_ast(root, &syntax_set, &class_style, None, "mermaid-test").expect("rewrite");
+    let outcome = rewrite_ast(
+        root,
+        &syntax_set,
+        &class_style,
+        None,
+        &[],
+        "mermaid-test",
+        &[],
+    )
+    .expect("rewrite");
     assert!(outcome.contains_code);
     assert!(!outcome.contains_mermaid);
+    assert_eq!(outcome.mermaid_fallbacks, 1);
 
     let mut html = String::new();
     format_html(root, &options, &mut html).expect("html");
     assert!(html.contains("<pre"));
     assert!(html.contains("syntax-highlight"));
 }
+
+#[test]
+fn rewrite_embeds_bare_youtube_link() {
+    let options = crate::application::render::service::config::default_options();
+    let arena = Arena::new();
+    let markdown = "Intro\n\nhttps://www.youtube.com/watch?v=dQw4w9WgXcQ\n\nOutro";
+    let root = parse_document(&arena, markdown, &options);
+    let (syntax_set, class_style) = syntax_and_style();
+
+    rewrite_ast(
+        root,
+        &syntax_set,
+        &class_style,
+        None,
+        &EmbedProvider::ALL,
+        "embed-test",
+        &[],
+    )
+    .expect("rewrite");
+
+    let mut html = String::new();
+    format_html(root, &options, &mut html).expect("html");
+    assert!(html.contains("<figure data-embed-provider=\"youtube\"><iframe"));
+    assert!(html.contains("src=\"https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ\""));
+    assert!(html.contains("sandbox=\""));
+    assert!(!html.contains("<a href=\"https://www.youtube.com"));
+}
+
+#[test]
+fn rewrite_leaves_other_bare_links_untouched() {
+    let options = crate::application::render::service::config::default_options();
+    let arena = Arena::new();
+    let markdown =
+        "https://example.com/watch?v=dQw4w9WgXcQ\n\nSee https://youtu.be/dQw4w9WgXcQ inline.";
+    let root = parse_document(&arena, markdown, &options);
+    let (syntax_set, class_style) = syntax_and_style();
+
+    rewrite_ast(
+        root,
+        &syntax_set,
+        &class_style,
+        None,
+        &EmbedProvider::ALL,
+        "embed-test",
+        &[],
+    )
+    .expect("rewrite");
+
+    let mut html = String::new();
+    format_html(root, &options, &mut html).expect("html");
+    assert!(!html.contains("<iframe"));
+    assert!(html.contains("<a href=\"https://example.com/watch?v=dQw4w9WgXcQ\">"));
+    assert!(html.contains("<a href=\"https://youtu.be/dQw4w9WgXcQ\">"));
+}
@@ -28,6 +28,30 @@ pub(super) fn extract_code_block(node: &AstNode<'_>) -> Option<(String, String)>
     }
 }
 
+/// URL of a paragraph whose only content is a single link showing its own address.
+pub(super) fn bare_link_url(node: &AstNode<'_>) -> Option<String> {
+    if !matches!(node.data.borrow().value, NodeValue::Paragraph) {
+        return None;
+    }
+
+    let mut link = None;
+    let mut child = node.first_child();
+    while let Some(current) = child {
+        child = current.next_sibling();
+        let data = current.data.borrow();
+        match &data.value {
+            NodeValue::Link(target) if link.is_none() => link = Some((current, target.url.clone())),
+            NodeValue::Text(text) if text.trim().is_empty() => {}
+            NodeValue::SoftBreak | NodeValue::LineBreak => {}
+            _ => return None,
+        }
+    }
+
+    let (link_node, url) = link?;
+    let text = collect_inline_text(link_node);
+    (text.trim() == url.trim()).then_some(url)
+}
+
 pub(super) fn heading_level(node: &AstNode<'_>) -> Option<u8> {
     let data = node.data.borrow();
     if let NodeValue::Heading(heading) = &data.value {
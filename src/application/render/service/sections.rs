@@ -1,17 +1,21 @@
 use url::Url;
 
 use crate::application::render::types::{
-    ContentMetrics, RenderError, RenderTarget, RenderedSection, ResourceHints,
+    ContentMetrics, ContentReferences, RenderError, RenderTarget, RenderedSection, ResourceHints,
 };
+use crate::config::HeadingAnchorPlacement;
 
 use super::rewrite::HeadingInfo;
 
 #[path = "sections/outline.rs"]
 mod outline;
+#[path = "sections/references.rs"]
+mod references;
 #[path = "sections/semantics.rs"]
 mod semantics;
 
-use outline::{apply_heading_ids, build_sections};
+use outline::{apply_heading_anchors, apply_heading_ids, build_sections};
+use references::collect_references;
 use semantics::{
     AugmentOutcome, augment_code_blocks_only, augment_semantics, build_content_metrics,
     build_resource_hints,
@@ -25,40 +29,61 @@ pub(crate) struct ProcessedHtml {
     pub(crate) contains_mermaid: bool,
     pub(crate) resource_hints: ResourceHints,
     pub(crate) content_metrics: ContentMetrics,
+    pub(crate) references: ContentReferences,
 }
 
 pub(crate) fn post_process(
     sanitized_html: &str,
     target: &RenderTarget,
     headings: &[HeadingInfo],
+    heading_anchors: HeadingAnchorPlacement,
     public_site_url: Option<&str>,
 ) -> Result<ProcessedHtml, RenderError> {
     match target {
         RenderTarget::PostBody { .. } => {
-            process_post_html(sanitized_html, headings, public_site_url)
+            process_post_html(sanitized_html, headings, heading_anchors, public_site_url)
         }
-        _ => {
-            let html = augment_code_blocks_only(sanitized_html)?;
-            let contains_code =
-                html.contains("syntax-") || html.contains("<pre") || html.contains("<code");
-            let contains_math = html.contains("data-math-style");
-            let contains_mermaid = html.contains("data-role=\"diagram-mermaid\"");
-            Ok(ProcessedHtml {
-                html,
-                sections: None,
-                contains_code,
-                contains_math,
-                contains_mermaid,
-                resource_hints: ResourceHints::default(),
-                content_metrics: ContentMetrics::default(),
-            })
+        RenderTarget::PageBody { .. } => {
+            let mut processed = process_plain_html(sanitized_html)?;
+            let site_url = public_site_url.and_then(|value| Url::parse(value).ok());
+            processed.references = collect_references(&processed.html, site_url.as_ref())?;
+            Ok(processed)
         }
+        RenderTarget::PostSummary { .. } => process_plain_html(sanitized_html),
     }
 }
 
+/// References in a post body assembled from separately rendered parts, as
+/// [`post_process`] would collect them from the whole body.
+pub(crate) fn collect_post_references(
+    html: &str,
+    public_site_url: Option<&str>,
+) -> Result<ContentReferences, RenderError> {
+    let site_url = public_site_url.and_then(|value| Url::parse(value).ok());
+    collect_references(html, site_url.as_ref())
+}
+
+fn process_plain_html(sanitized_html: &str) -> Result<ProcessedHtml, RenderError> {
+    let html = augment_code_blocks_only(sanitized_html)?;
+    let contains_code = html.contains("syntax-") || html.contains("<pre") || html.contains("<code");
+    let contains_math = html.contains("data-math-style");
+    let contains_mermaid = html.contains("data-role=\"diagram-mermaid\"");
+    Ok(ProcessedHtml {
+        html,
+        sections: None,
+        contains_code,
+        contains_math,
+        contains_mermaid,
+        resource_hints: ResourceHints::default(),
+        content_metrics: ContentMetrics::default(),
+        references: ContentReferences::default(),
+    })
+}
+
 fn process_post_html(
     sanitized_html: &str,
     headings: &[HeadingInfo],
+    heading_anchors: HeadingAnchorPlacement,
     public_site_url: Option<&str>,
 ) -> Result<ProcessedHtml, RenderError> {
     let site_url = public_site_url.and_then(|value| Url::parse(value).ok());
@@ -74,6 +99,7 @@ fn process_post_html(
             html: augmented_html,
             ..
         } = augmentation;
+        let references = collect_references(&augmented_html, site_url.as_ref())?;
 
         return Ok(ProcessedHtml {
             html: augmented_html,
@@ -83,12 +109,15 @@ fn process_post_html(
             contains_mermaid,
             resource_hints,
             content_metrics: metrics,
+            references,
         });
     }
 
     let html_with_ids = apply_heading_ids(sanitized_html, headings)?;
     let augmentation = augment_semantics(&html_with_ids, site_url.as_ref())?;
-    let sections = build_sections(&augmentation.html, headings)?;
+    // Added after augmentation so the word count and link annotation skip them.
+    let anchored_html = apply_heading_anchors(&augmentation.html, heading_anchors)?;
+    let sections = build_sections(&anchored_html, headings)?;
     let metrics = build_content_metrics(&augmentation);
     let resource_hints = build_resource_hints(&augmentation);
     let contains_code =
@@ -97,19 +126,17 @@ fn process_post_html(
         sections.iter().any(|section| section.contains_math) || metrics.math_blocks_count > 0;
     let contains_mermaid = sections.iter().any(|section| section.contains_mermaid)
         || metrics.mermaid_diagram_count > 0;
-    let AugmentOutcome {
-        html: augmented_html,
-        ..
-    } = augmentation;
+    let references = collect_references(&anchored_html, site_url.as_ref())?;
 
     Ok(ProcessedHtml {
-        html: augmented_html,
+        html: anchored_html,
         sections: Some(sections),
         contains_code,
         contains_math,
         contains_mermaid,
         resource_hints,
         content_metrics: metrics,
+        references,
     })
 }
 
@@ -160,6 +187,24 @@ mod tests {
         assert!(outcome.html.contains("data-copy-label-success=\"Copied\""));
     }
 
+    #[test]
+    fn collect_references_keeps_same_site_paths_and_repeated_ids() {
+        let html = "<h2 id=\"intro\">A</h2><h3 id=\"intro\">B</h3>\
+            <p><a href=\"/posts/hello?ref=feed#top\">post</a>\
+            <a href=\"https://example.com/about\">page</a>\
+            <a href=\"https://other.com/posts/x\">external</a>\
+            <a href=\"#intro\">anchor</a>\
+            <a href=\"relative\">relative</a>\
+            <img src=\"/uploads/2026/10/cat.png\" alt=\"\"></p>";
+
+        let references =
+            collect_references(html, site("https://example.com/").as_ref()).expect("collect");
+
+        assert_eq!(references.link_paths, vec!["/about", "/posts/hello"]);
+        assert_eq!(references.image_paths, vec!["/uploads/2026/10/cat.png"]);
+        assert_eq!(references.duplicate_ids, vec!["intro"]);
+    }
+
     #[test]
     fn page_target_post_process_also_injects_copy_button() {
         let html = "<pre class=\"syntax-highlight syntax-lang-rust\" data-language=\"rust\"><code class=\"language-rust syntax-code\">fn main() {}\n</code></pre>";
@@ -169,6 +214,7 @@ mod tests {
                 slug: "about".to_string(),
             },
             &[],
+            HeadingAnchorPlacement::default(),
             None,
         )
         .expect("post process");
@@ -176,4 +222,61 @@ mod tests {
         assert!(output.html.contains("data-role=\"code-copy-button\""));
         assert!(output.html.contains("@copyCodeBlockText()"));
     }
+
+    fn heading(level: u8, slug: &str, text: &str) -> HeadingInfo {
+        HeadingInfo {
+            level,
+            slug: slug.to_string(),
+            text: text.to_string(),
+            has_block_code: false,
+            has_math_block: false,
+            has_inline_math: false,
+            has_mermaid_block: false,
+        }
+    }
+
+    #[test]
+    fn heading_anchors_follow_placement_and_skip_link_annotation() {
+        let html = "<h2>Setup</h2><p>Two words</p><h2>Setup</h2>";
+        let headings = [heading(2, "setup", "Setup"), heading(2, "setup-2", "Setup")];
+        let target = RenderTarget::PostBody {
+            slug: "post".to_string(),
+        };
+
+        let after = post_process(
+            html,
+            &target,
+            &headings,
+            HeadingAnchorPlacement::After,
+            None,
+        )
+        .expect("post process");
+        assert!(
+            after
+                .html
+                .starts_with("<h2 id=\"setup\">Setup<a class=\"heading-anchor\" href=\"#setup\"")
+        );
+        assert!(after.html.contains("href=\"#setup-2\""));
+        assert!(!after.html.contains("data-link-kind"));
+
+        let sections = after.sections.expect("sections");
+        assert_eq!(sections[1].anchor_slug, "setup-2");
+        assert!(sections[1].heading_html.contains("href=\"#setup-2\""));
+        assert_eq!(sections[1].heading_text, "Setup");
+
+        let before = post_process(
+            html,
+            &target,
+            &headings,
+            HeadingAnchorPlacement::Before,
+            None,
+        )
+        .expect("post process");
+        assert!(
+            before
+                .html
+                .starts_with("<h2 id=\"setup\"><a class=\"heading-anchor\" href=\"#setup\"")
+        );
+        assert!(before.html.contains("@copyHeadingLink()\">#</a>Setup</h2>"));
+    }
 }
@@ -1,11 +1,14 @@
 use std::{cell::RefCell, rc::Rc};
 
-use lol_html::{RewriteStrSettings, element, rewrite_str};
+use lol_html::{RewriteStrSettings, element, html_content::ContentType, rewrite_str};
 use uuid::Uuid;
 
-use crate::application::render::{
-    service::rewrite::HeadingInfo,
-    types::{RenderError, RenderedSection},
+use crate::{
+    application::render::{
+        service::rewrite::HeadingInfo,
+        types::{RenderError, RenderedSection},
+    },
+    config::HeadingAnchorPlacement,
 };
 
 #[derive(Clone)]
@@ -80,6 +83,40 @@ pub(super) fn apply_heading_ids(
     Ok(rewritten)
 }
 
+/// Add a `heading-anchor` deep link to every heading that has an id, on the
+/// configured side of its text. Clicking it also copies the section's URL.
+pub(super) fn apply_heading_anchors(
+    html: &str,
+    placement: HeadingAnchorPlacement,
+) -> Result<String, RenderError> {
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("h1, h2, h3, h4, h5, h6", move |el| {
+                let Some(id) = el.get_attribute("id") else {
+                    return Ok(());
+                };
+                let anchor = heading_anchor_html(&id);
+                match placement {
+                    HeadingAnchorPlacement::Before => el.prepend(&anchor, ContentType::Html),
+                    HeadingAnchorPlacement::After => el.append(&anchor, ContentType::Html),
+                }
+                Ok(())
+            })],
+            ..RewriteStrSettings::default()
+        },
+    )
+    .map_err(|err| RenderError::Document {
+        message: err.to_string(),
+    })
+}
+
+fn heading_anchor_html(id: &str) -> String {
+    format!(
+        "<a class=\"heading-anchor\" href=\"#{id}\" aria-label=\"Copy link to this section\" data-copy-state=\"idle\" data-on-click=\"@copyHeadingLink()\">#</a>"
+    )
+}
+
 pub(super) fn build_sections(
     html: &str,
     headings: &[HeadingInfo],
@@ -0,0 +1,113 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use lol_html::{RewriteStrSettings, element, rewrite_str};
+use url::Url;
+
+use crate::application::render::types::{ContentReferences, RenderError};
+
+use super::semantics::same_origin;
+
+#[derive(Default)]
+struct ReferenceState {
+    link_paths: BTreeSet<String>,
+    image_paths: BTreeSet<String>,
+    id_counts: BTreeMap<String, u32>,
+}
+
+/// Collect internal link targets, internal image sources and repeated element
+/// ids from the final HTML.
+pub(super) fn collect_references(
+    html: &str,
+    public_site_url: Option<&Url>,
+) -> Result<ContentReferences, RenderError> {
+    let state = Rc::new(RefCell::new(ReferenceState::default()));
+    let site_url = Rc::new(public_site_url.cloned());
+
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![
+                element!("a[href]", {
+                    let state = Rc::clone(&state);
+                    let site_url = Rc::clone(&site_url);
+                    move |el| {
+                        if let Some(path) = el
+                            .get_attribute("href")
+                            .and_then(|href| internal_path(&href, site_url.as_ref().as_ref()))
+                        {
+                            state.borrow_mut().link_paths.insert(path);
+                        }
+                        Ok(())
+                    }
+                }),
+                element!("img[src]", {
+                    let state = Rc::clone(&state);
+                    let site_url = Rc::clone(&site_url);
+                    move |el| {
+                        if let Some(path) = el
+                            .get_attribute("src")
+                            .and_then(|src| internal_path(&src, site_url.as_ref().as_ref()))
+                        {
+                            state.borrow_mut().image_paths.insert(path);
+                        }
+                        Ok(())
+                    }
+                }),
+                element!("[id]", {
+                    let state = Rc::clone(&state);
+                    move |el| {
+                        if let Some(id) = el.get_attribute("id") {
+                            let id = id.trim();
+                            if !id.is_empty() {
+                                *state
+                                    .borrow_mut()
+                                    .id_counts
+                                    .entry(id.to_string())
+                                    .or_default() += 1;
+                            }
+                        }
+                        Ok(())
+                    }
+                }),
+            ],
+            ..RewriteStrSettings::default()
+        },
+    )
+    .map_err(|err| RenderError::Document {
+        message: err.to_string(),
+    })?;
+
+    let state = state.borrow();
+    Ok(ContentReferences {
+        link_paths: state.link_paths.iter().cloned().collect(),
+        image_paths: state.image_paths.iter().cloned().collect(),
+        duplicate_ids: state
+            .id_counts
+            .iter()
+            .filter(|(_, count)| **count > 1)
+            .map(|(id, _)| id.clone())
+            .collect(),
+    })
+}
+
+/// Root-relative path of `value` when it targets this site. Relative paths
+/// are skipped because their meaning depends on where the content is served.
+fn internal_path(value: &str, site_url: Option<&Url>) -> Option<String> {
+    let value = value.trim();
+    let path = if value.starts_with('/') && !value.starts_with("//") {
+        value.to_string()
+    } else {
+        let url = Url::parse(value).ok()?;
+        if !site_url.is_some_and(|base| same_origin(&url, base)) {
+            return None;
+        }
+        url.path().to_string()
+    };
+
+    let end = path.find(['?', '#']).unwrap_or(path.len());
+    Some(path[..end].to_string())
+}
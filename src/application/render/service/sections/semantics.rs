@@ -436,7 +436,7 @@ fn extract_domain_from_url(url: &Url) -> Option<String> {
     })
 }
 
-fn same_origin(url: &Url, base: &Url) -> bool {
+pub(super) fn same_origin(url: &Url, base: &Url) -> bool {
     url.scheme() == base.scheme()
         && url.host_str() == base.host_str()
         && url.port_or_known_default() == base.port_or_known_default()
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::domain::routes::site_root;
+
 /// Identifies what is being rendered so callers can persist results appropriately.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RenderTarget {
@@ -25,6 +27,30 @@ impl RenderTarget {
     }
 }
 
+/// HTML sanitisation rules applied to rendered markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SanitizationPolicy {
+    /// Post bodies and summaries: structural markup, media and diagrams.
+    Post,
+    /// Static pages: post rules plus inline styles, media embeds and iframes.
+    Page,
+    /// Reader-submitted content: basic text formatting and links only.
+    Comment,
+}
+
+impl SanitizationPolicy {
+    /// Policy used when a request does not choose one explicitly.
+    pub fn for_target(target: &RenderTarget) -> Self {
+        match target {
+            RenderTarget::PageBody { .. } => SanitizationPolicy::Page,
+            RenderTarget::PostBody { .. } | RenderTarget::PostSummary { .. } => {
+                SanitizationPolicy::Post
+            }
+        }
+    }
+}
+
 /// Rendering request passed into the pipeline.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RenderRequest {
@@ -37,6 +63,13 @@ pub struct RenderRequest {
     /// Normalised public site URL used for same-origin checks during link classification.
     #[serde(default)]
     pub public_site_url: Option<String>,
+    /// Explicit sanitisation policy; derived from `target` when absent.
+    #[serde(default)]
+    pub sanitization: Option<SanitizationPolicy>,
+    /// Headings of the surrounding document that precede `markdown`, used to
+    /// give a slice of a post the anchors it has in the whole post.
+    #[serde(default)]
+    pub preceding_headings: Vec<String>,
 }
 
 impl RenderRequest {
@@ -46,6 +79,8 @@ impl RenderRequest {
             markdown: markdown.into(),
             context: None,
             public_site_url: None,
+            sanitization: None,
+            preceding_headings: Vec::new(),
         }
     }
 
@@ -54,23 +89,28 @@ impl RenderRequest {
         self
     }
 
+    /// Links to this site are told apart from external ones by this root;
+    /// an unset or unusable URL leaves every absolute link external.
     pub fn with_public_site_url(mut self, public_site_url: impl Into<String>) -> Self {
-        let normalized = normalize_public_site_url(public_site_url.into().as_str());
-        if !normalized.is_empty() {
-            self.public_site_url = Some(normalized);
-        }
+        self.public_site_url = site_root(&public_site_url.into());
         self
     }
-}
 
-fn normalize_public_site_url(url: &str) -> String {
-    let trimmed = url.trim();
-    if trimmed.is_empty() {
-        return String::new();
+    pub fn with_sanitization(mut self, policy: SanitizationPolicy) -> Self {
+        self.sanitization = Some(policy);
+        self
+    }
+
+    pub fn with_preceding_headings(mut self, headings: Vec<String>) -> Self {
+        self.preceding_headings = headings;
+        self
     }
 
-    let without_trailing = trimmed.trim_end_matches('/');
-    format!("{without_trailing}/")
+    /// Sanitisation policy in effect for this request.
+    pub fn sanitization_policy(&self) -> SanitizationPolicy {
+        self.sanitization
+            .unwrap_or_else(|| SanitizationPolicy::for_target(&self.target))
+    }
 }
 
 /// Section produced when rendering full posts. Pages and summaries omit sections.
@@ -113,6 +153,47 @@ pub struct ContentMetrics {
     pub mermaid_diagram_count: u32,
 }
 
+/// Same-site references found in rendered HTML. The pipeline cannot see
+/// stored content, so callers check these against the repositories.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ContentReferences {
+    /// Root-relative paths of internal links, without query or fragment.
+    pub link_paths: Vec<String>,
+    /// Root-relative paths of internal image sources.
+    pub image_paths: Vec<String>,
+    /// Element ids that occur more than once in the document.
+    pub duplicate_ids: Vec<String>,
+}
+
+/// Non-blocking problem found while checking rendered content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ContentWarning {
+    /// An internal link points at a post, page or upload that does not exist.
+    BrokenLink { href: String },
+    /// An image references an upload that does not exist.
+    MissingImage { src: String },
+    /// Several elements share the same id, so anchors to it are ambiguous.
+    DuplicateId { id: String },
+}
+
+impl ContentWarning {
+    /// Human readable description shown to editors.
+    pub fn message(&self) -> String {
+        match self {
+            ContentWarning::BrokenLink { href } => {
+                format!("Link `{href}` points to content that does not exist")
+            }
+            ContentWarning::MissingImage { src } => {
+                format!("Image `{src}` references an upload that does not exist")
+            }
+            ContentWarning::DuplicateId { id } => {
+                format!("Id `{id}` is used by more than one element")
+            }
+        }
+    }
+}
+
 /// Deterministic rendering result returned to callers.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RenderOutput {
@@ -132,6 +213,13 @@ pub struct RenderOutput {
     /// Basic content metrics that support editorial and SEO tooling.
     #[serde(default)]
     pub content_metrics: ContentMetrics,
+    /// Mermaid blocks rendered as plain code because the Mermaid CLI was
+    /// unavailable or failed.
+    #[serde(default)]
+    pub mermaid_fallbacks: u32,
+    /// Internal references to validate against stored content.
+    #[serde(default)]
+    pub references: ContentReferences,
 }
 
 impl RenderOutput {
@@ -150,6 +238,8 @@ impl RenderOutput {
             contains_mermaid,
             resource_hints: ResourceHints::default(),
             content_metrics: ContentMetrics::default(),
+            mermaid_fallbacks: 0,
+            references: ContentReferences::default(),
         }
     }
 
@@ -167,6 +257,8 @@ impl RenderOutput {
             contains_mermaid,
             resource_hints: ResourceHints::default(),
             content_metrics: ContentMetrics::default(),
+            mermaid_fallbacks: 0,
+            references: ContentReferences::default(),
         }
     }
 }
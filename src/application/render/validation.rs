@@ -0,0 +1,235 @@
+//! Checks rendered content against stored posts, pages and uploads.
+//!
+//! The render pipeline stays pure and only reports which same-site paths a
+//! document references. This module resolves those paths through a
+//! [`ContentReferenceResolver`] and turns the dangling ones into warnings.
+//! Warnings are advisory: they are stored next to the content and never stop
+//! a save or a render.
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::application::repos::RepoError;
+use crate::domain::routes::{decode_path_segment, reserved_page_slug_route};
+
+use super::types::{ContentReferences, ContentWarning};
+
+/// Looks up whether the content a same-site path points at exists.
+#[async_trait]
+pub trait ContentReferenceResolver: Send + Sync {
+    /// Whether a live post is stored under `slug`.
+    async fn post_exists(&self, slug: &str) -> Result<bool, RepoError>;
+    /// Whether a live page is stored under `slug`.
+    async fn page_exists(&self, slug: &str) -> Result<bool, RepoError>;
+    /// Whether an upload is stored at `path`, relative to `/uploads/`.
+    async fn upload_exists(&self, path: &str) -> Result<bool, RepoError>;
+}
+
+/// Content a root-relative path resolves to, when it is one we can check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReferenceTarget<'a> {
+    Post(&'a str),
+    Page(&'a str),
+    Upload(&'a str),
+}
+
+fn classify_path(path: &str) -> Option<ReferenceTarget<'_>> {
+    let trimmed = path.strip_prefix('/')?;
+
+    if let Some(upload) = trimmed.strip_prefix("uploads/") {
+        return (!upload.is_empty()).then_some(ReferenceTarget::Upload(upload));
+    }
+
+    let trimmed = trimmed.trim_end_matches('/');
+    if let Some(slug) = trimmed.strip_prefix("posts/") {
+        // `/posts/_preview/...` and other nested paths are not post slugs.
+        return (!slug.is_empty() && !slug.contains('/') && !slug.starts_with('_'))
+            .then_some(ReferenceTarget::Post(slug));
+    }
+
+    if trimmed.is_empty() || trimmed.contains('/') || reserved_page_slug_route(trimmed).is_some() {
+        return None;
+    }
+
+    Some(ReferenceTarget::Page(trimmed))
+}
+
+/// Resolve `references` and return one warning per dangling reference or
+/// repeated id. A lookup that fails is logged and skipped rather than
+/// reported, since it says nothing about the content itself.
+pub async fn check_content_references(
+    resolver: &dyn ContentReferenceResolver,
+    references: &ContentReferences,
+) -> Vec<ContentWarning> {
+    let mut warnings = Vec::new();
+
+    for href in &references.link_paths {
+        if let Some(target) = classify_path(href)
+            && !target_exists(resolver, target).await
+        {
+            warnings.push(ContentWarning::BrokenLink { href: href.clone() });
+        }
+    }
+
+    for src in &references.image_paths {
+        if let Some(target @ ReferenceTarget::Upload(_)) = classify_path(src)
+            && !target_exists(resolver, target).await
+        {
+            warnings.push(ContentWarning::MissingImage { src: src.clone() });
+        }
+    }
+
+    warnings.extend(
+        references
+            .duplicate_ids
+            .iter()
+            .map(|id| ContentWarning::DuplicateId { id: id.clone() }),
+    );
+
+    warnings
+}
+
+async fn target_exists(
+    resolver: &dyn ContentReferenceResolver,
+    target: ReferenceTarget<'_>,
+) -> bool {
+    let result = match target {
+        ReferenceTarget::Post(slug) => resolver.post_exists(&decode_path_segment(slug)).await,
+        ReferenceTarget::Page(slug) => resolver.page_exists(slug).await,
+        ReferenceTarget::Upload(path) => resolver.upload_exists(path).await,
+    };
+
+    result.unwrap_or_else(|err| {
+        warn!(
+            target = "application::render::check_content_references",
+            reference = ?target,
+            error = %err,
+            "failed to resolve content reference"
+        );
+        true
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct StubResolver {
+        posts: BTreeSet<&'static str>,
+        pages: BTreeSet<&'static str>,
+        uploads: BTreeSet<&'static str>,
+    }
+
+    #[async_trait]
+    impl ContentReferenceResolver for StubResolver {
+        async fn post_exists(&self, slug: &str) -> Result<bool, RepoError> {
+            Ok(self.posts.contains(slug))
+        }
+
+        async fn page_exists(&self, slug: &str) -> Result<bool, RepoError> {
+            Ok(self.pages.contains(slug))
+        }
+
+        async fn upload_exists(&self, path: &str) -> Result<bool, RepoError> {
+            Ok(self.uploads.contains(path))
+        }
+    }
+
+    fn resolver() -> StubResolver {
+        StubResolver {
+            posts: BTreeSet::from(["hello-world", "東京"]),
+            pages: BTreeSet::from(["about"]),
+            uploads: BTreeSet::from(["2026/10/cat.png"]),
+        }
+    }
+
+    fn references(links: &[&str], images: &[&str]) -> ContentReferences {
+        ContentReferences {
+            link_paths: links.iter().map(|path| path.to_string()).collect(),
+            image_paths: images.iter().map(|path| path.to_string()).collect(),
+            duplicate_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn classify_path_recognises_posts_pages_and_uploads() {
+        assert_eq!(
+            classify_path("/posts/hello"),
+            Some(ReferenceTarget::Post("hello"))
+        );
+        assert_eq!(
+            classify_path("/about/"),
+            Some(ReferenceTarget::Page("about"))
+        );
+        assert_eq!(
+            classify_path("/uploads/2026/10/cat.png"),
+            Some(ReferenceTarget::Upload("2026/10/cat.png"))
+        );
+        assert_eq!(classify_path("/"), None);
+        assert_eq!(classify_path("/tags/rust"), None);
+        assert_eq!(classify_path("/rss.xml"), None);
+        assert_eq!(classify_path("/posts/_preview/abc"), None);
+    }
+
+    #[tokio::test]
+    async fn broken_internal_link_produces_warning() {
+        let warnings =
+            check_content_references(&resolver(), &references(&["/posts/missing", "/gone"], &[]))
+                .await;
+
+        assert_eq!(
+            warnings,
+            vec![
+                ContentWarning::BrokenLink {
+                    href: "/posts/missing".to_string()
+                },
+                ContentWarning::BrokenLink {
+                    href: "/gone".to_string()
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn valid_references_produce_no_warnings() {
+        let warnings = check_content_references(
+            &resolver(),
+            &references(
+                &[
+                    "/posts/hello-world",
+                    "/posts/%E6%9D%B1%E4%BA%AC",
+                    "/about",
+                    "/tags/rust",
+                    "/",
+                ],
+                &["/uploads/2026/10/cat.png", "/static/public/logo.svg"],
+            ),
+        )
+        .await;
+
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+    }
+
+    #[tokio::test]
+    async fn missing_upload_and_duplicate_ids_are_reported() {
+        let mut refs = references(&[], &["/uploads/2026/10/deleted.png"]);
+        refs.duplicate_ids = vec!["intro".to_string()];
+
+        let warnings = check_content_references(&resolver(), &refs).await;
+
+        assert_eq!(
+            warnings,
+            vec![
+                ContentWarning::MissingImage {
+                    src: "/uploads/2026/10/deleted.png".to_string()
+                },
+                ContentWarning::DuplicateId {
+                    id: "intro".to_string()
+                },
+            ]
+        );
+    }
+}
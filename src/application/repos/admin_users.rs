@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::domain::entities::AdminUserRecord;
+
+use super::RepoError;
+
+#[async_trait]
+pub trait AdminUsersRepo: Send + Sync {
+    async fn insert_admin_user(&self, record: AdminUserRecord) -> Result<(), RepoError>;
+
+    async fn find_admin_user_by_username(
+        &self,
+        username: &str,
+    ) -> Result<Option<AdminUserRecord>, RepoError>;
+}
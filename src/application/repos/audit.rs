@@ -1,8 +1,9 @@
 use async_trait::async_trait;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::application::pagination::{AuditCursor, CursorPage, PageRequest};
-use crate::domain::entities::AuditLogRecord;
+use crate::domain::{entities::AuditLogRecord, types::ActorType};
 
 use super::RepoError;
 
@@ -12,6 +13,16 @@ pub struct AuditQueryFilter {
     pub action: Option<String>,
     pub entity_type: Option<String>,
     pub search: Option<String>,
+    /// Only entries whose recorded changes include this field.
+    pub field: Option<String>,
+    /// Only entries created at or after this instant.
+    pub from: Option<OffsetDateTime>,
+    /// Only entries created strictly before this instant.
+    pub to: Option<OffsetDateTime>,
+    /// Only entries recorded while serving the request with this id.
+    pub request_id: Option<String>,
+    /// Only entries whose actor has this origin.
+    pub actor_type: Option<ActorType>,
 }
 
 /// Count of audit logs by entity type.
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::application::pagination::{CommentCursor, CursorPage, PageRequest};
+use crate::domain::entities::CommentRecord;
+use crate::domain::types::CommentStatus;
+
+use super::RepoError;
+
+#[derive(Debug, Clone, Default)]
+pub struct CommentQueryFilter {
+    pub status: Option<CommentStatus>,
+    pub post_id: Option<Uuid>,
+    pub search: Option<String>,
+}
+
+#[async_trait]
+pub trait CommentsRepo: Send + Sync {
+    async fn insert_comment(&self, record: CommentRecord) -> Result<(), RepoError>;
+
+    async fn find_comment(&self, id: Uuid) -> Result<Option<CommentRecord>, RepoError>;
+
+    async fn list_comments(
+        &self,
+        filter: &CommentQueryFilter,
+        page: PageRequest<CommentCursor>,
+    ) -> Result<CursorPage<CommentRecord>, RepoError>;
+
+    async fn count_comments(&self, filter: &CommentQueryFilter) -> Result<u64, RepoError>;
+
+    async fn update_comment_status(
+        &self,
+        id: Uuid,
+        status: CommentStatus,
+    ) -> Result<CommentRecord, RepoError>;
+
+    /// Approved comments for a post, oldest first.
+    async fn list_approved_for_post(&self, post_id: Uuid) -> Result<Vec<CommentRecord>, RepoError>;
+}
@@ -16,6 +16,13 @@ pub enum RepoError {
     Integrity { message: String },
     #[error("database timeout")]
     Timeout,
+    /// The transaction lost a serialization or deadlock race with a
+    /// concurrent one; it rolled back and may be retried as a whole.
+    #[error("transaction conflicted with a concurrent write")]
+    Contention,
+    /// A conditional write found the record changed since the caller read it.
+    #[error("record was modified since it was read")]
+    Stale,
     #[error(transparent)]
     Pagination(#[from] PaginationError),
 }
@@ -24,4 +31,13 @@ impl RepoError {
     pub fn from_persistence(err: impl std::fmt::Display) -> Self {
         Self::Persistence(err.to_string())
     }
+
+    /// Whether the same call may succeed if retried (connection, timeout or
+    /// contention failures), as opposed to a problem with the request itself.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::Persistence(_) | Self::Timeout | Self::Contention
+        )
+    }
 }
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::RepoError;
+
+/// A finished response kept for replay under an `Idempotency-Key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub location: Option<String>,
+    pub etag: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// Result of claiming an idempotency key for a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyClaim {
+    /// The key was free, or held by a request whose lease ran out; the
+    /// caller runs the request and then completes or releases the key with
+    /// `token`.
+    Claimed { token: Uuid },
+    /// An unexpired earlier request used the key. `response` is `None`
+    /// while that request is still running.
+    Existing {
+        request_hash: String,
+        response: Option<StoredResponse>,
+    },
+}
+
+#[async_trait]
+pub trait IdempotencyRepo: Send + Sync {
+    /// Claim `key` for `api_key_id` for `ttl`. Expired claims of the same
+    /// API key are dropped first, so a key can be reused once it expires.
+    /// A claim still without a response after `lease` is taken over.
+    async fn claim_idempotency_key(
+        &self,
+        api_key_id: Uuid,
+        key: &str,
+        request_hash: &str,
+        ttl: Duration,
+        lease: Duration,
+    ) -> Result<IdempotencyClaim, RepoError>;
+
+    /// Store the response of a claimed request for replay. Does nothing
+    /// when the claim under `token` was taken over meanwhile.
+    async fn complete_idempotency_key(
+        &self,
+        api_key_id: Uuid,
+        key: &str,
+        token: Uuid,
+        response: &StoredResponse,
+    ) -> Result<(), RepoError>;
+
+    /// Forget a claim whose request failed, so a retry runs it again.
+    async fn release_idempotency_key(
+        &self,
+        api_key_id: Uuid,
+        key: &str,
+        token: Uuid,
+    ) -> Result<(), RepoError>;
+}
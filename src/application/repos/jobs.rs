@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use time::OffsetDateTime;
 
 use crate::application::pagination::{CursorPage, JobCursor, PageRequest};
-use crate::domain::entities::JobRecord;
+use crate::domain::entities::{JobAttemptRecord, JobRecord};
 use crate::domain::types::{JobState, JobType};
 
 use super::RepoError;
@@ -41,6 +41,23 @@ pub trait JobsRepo: Send + Sync {
 
     async fn find_job(&self, id: &str) -> Result<Option<JobRecord>, RepoError>;
 
+    /// Id of a job of `job_type` still waiting to run whose payload contains
+    /// every field of `payload`.
+    async fn find_pending_job(
+        &self,
+        job_type: JobType,
+        payload: &serde_json::Value,
+    ) -> Result<Option<String>, RepoError>;
+
+    /// The `limit` most recently enqueued jobs of `job_types` whose payload
+    /// names `slug`, newest first.
+    async fn latest_jobs_for_entity(
+        &self,
+        job_types: &[JobType],
+        slug: &str,
+        limit: u32,
+    ) -> Result<Vec<JobRecord>, RepoError>;
+
     async fn list_jobs(
         &self,
         filter: &JobQueryFilter,
@@ -48,4 +65,15 @@ pub trait JobsRepo: Send + Sync {
     ) -> Result<CursorPage<JobRecord>, RepoError>;
 
     async fn count_jobs(&self, filter: &JobQueryFilter) -> Result<u64, RepoError>;
+
+    /// Keep the error of a failed run of job `job_id`.
+    async fn record_job_attempt(
+        &self,
+        job_id: &str,
+        attempt: i32,
+        error: &str,
+    ) -> Result<(), RepoError>;
+
+    /// Failed runs of job `job_id`, oldest first.
+    async fn list_job_attempts(&self, job_id: &str) -> Result<Vec<JobAttemptRecord>, RepoError>;
 }
@@ -1,8 +1,11 @@
 //! Repository traits describing persistence adapters.
 
+mod admin_users;
 mod api_keys;
 mod audit;
+mod comments;
 mod error;
+mod idempotency;
 mod jobs;
 mod navigation;
 mod pages;
@@ -12,6 +15,7 @@ mod snapshots;
 mod tags;
 mod uploads;
 
+pub use admin_users::AdminUsersRepo;
 pub use api_keys::{
     ApiKeyListPage, ApiKeyPageRequest, ApiKeyQueryFilter, ApiKeyStatusFilter, ApiKeysRepo,
     CreateApiKeyParams, UpdateApiKeyMetadataParams, UpdateApiKeySecretParams,
@@ -19,7 +23,9 @@ pub use api_keys::{
 pub use audit::{
     AuditActionCount, AuditActorCount, AuditEntityTypeCount, AuditQueryFilter, AuditRepo,
 };
+pub use comments::{CommentQueryFilter, CommentsRepo};
 pub use error::RepoError;
+pub use idempotency::{IdempotencyClaim, IdempotencyRepo, StoredResponse};
 pub use jobs::{JobQueryFilter, JobsRepo, NewJobRecord, UpdateJobStateParams};
 pub use navigation::{
     CreateNavigationItemParams, NavigationQueryFilter, NavigationRepo, NavigationWriteRepo,
@@ -27,19 +33,22 @@ pub use navigation::{
 };
 pub use pages::{
     CreatePageParams, PageQueryFilter, PagesRepo, PagesWriteRepo, RestorePageSnapshotParams,
-    UpdatePageParams, UpdatePageStatusParams,
+    UpdatePageParams, UpdatePageStatusParams, UpdatePageTranslationParams,
 };
 pub use posts::{
-    CreatePostParams, PostListScope, PostQueryFilter, PostTagCount, PostsRepo, PostsWriteRepo,
-    RestorePostSnapshotParams, SectionsRepo, UpdatePostParams, UpdatePostPinnedParams,
-    UpdatePostStatusParams,
+    CreatePostParams, FeedAggregates, PostListScope, PostQueryFilter, PostTagCount, PostsRepo,
+    PostsWriteRepo, RestorePostSnapshotParams, SectionsRepo, UpdatePostParams,
+    UpdatePostPinnedParams, UpdatePostStatusParams, UpdatePostTranslationParams,
 };
 pub use settings::SettingsRepo;
 pub use snapshots::{
     SnapshotCursor, SnapshotFilter, SnapshotMonthCount, SnapshotRecord, SnapshotsRepo,
 };
 pub use tags::{
-    CreateTagParams, TagListRecord, TagQueryFilter, TagWithCount, TagsRepo, TagsWriteRepo,
-    UpdateTagParams,
+    CreateTagParams, TagAssignmentChanges, TagAssignmentMode, TagListRecord, TagQueryFilter,
+    TagWithCount, TagsRepo, TagsWriteRepo, UpdateTagParams,
+};
+pub use uploads::{
+    UploadContentTypeCount, UploadIntegritySummary, UploadMonthCount, UploadQueryFilter,
+    UploadsRepo,
 };
-pub use uploads::{UploadContentTypeCount, UploadMonthCount, UploadQueryFilter, UploadsRepo};
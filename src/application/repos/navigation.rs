@@ -54,6 +54,12 @@ pub trait NavigationRepo: Send + Sync {
         filter: &NavigationQueryFilter,
     ) -> Result<u64, RepoError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<NavigationItemRecord>, RepoError>;
+    /// Visible internal items linking to `page_id`, whatever the page's
+    /// status; the public listings above leave out links to hidden pages.
+    async fn list_visible_links_to_page(
+        &self,
+        page_id: Uuid,
+    ) -> Result<Vec<NavigationItemRecord>, RepoError>;
 }
 
 #[async_trait]
@@ -2,8 +2,10 @@ use async_trait::async_trait;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::application::pagination::{CursorPage, PageCursor};
-use crate::domain::entities::PageRecord;
+use crate::application::pagination::{CursorPage, PageCursor, PageRequest, TrashCursor};
+use crate::domain::entities::{
+    CalendarRecord, PageRecord, ScheduledRecord, TranslationRecord, TrashedRecord,
+};
 use crate::domain::types::PageStatus;
 
 use super::RepoError;
@@ -24,6 +26,7 @@ pub struct CreatePageParams {
     pub scheduled_at: Option<OffsetDateTime>,
     pub published_at: Option<OffsetDateTime>,
     pub archived_at: Option<OffsetDateTime>,
+    pub template: String,
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +36,10 @@ pub struct UpdatePageParams {
     pub title: String,
     pub body_markdown: String,
     pub rendered_html: String,
+    pub template: String,
+    /// Only update while `updated_at` still equals this value; otherwise fail
+    /// with `RepoError::Stale`.
+    pub expected_updated_at: Option<OffsetDateTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +51,13 @@ pub struct UpdatePageStatusParams {
     pub archived_at: Option<OffsetDateTime>,
 }
 
+#[derive(Debug, Clone)]
+pub struct UpdatePageTranslationParams {
+    pub id: Uuid,
+    pub lang: Option<String>,
+    pub translation_group: Option<Uuid>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RestorePageSnapshotParams {
     pub id: Uuid,
@@ -55,6 +69,7 @@ pub struct RestorePageSnapshotParams {
     pub scheduled_at: Option<OffsetDateTime>,
     pub published_at: Option<OffsetDateTime>,
     pub archived_at: Option<OffsetDateTime>,
+    pub template: String,
 }
 
 #[async_trait]
@@ -82,6 +97,23 @@ pub trait PagesRepo: Send + Sync {
         status: Option<PageStatus>,
         filter: &PageQueryFilter,
     ) -> Result<Vec<crate::domain::posts::MonthCount>, RepoError>;
+
+    /// Published pages in `translation_group` that carry a language, ordered by `lang`.
+    async fn list_page_translations(
+        &self,
+        translation_group: Uuid,
+    ) -> Result<Vec<TranslationRecord>, RepoError>;
+
+    /// Draft pages with a publication time, soonest first, at most `limit`.
+    async fn list_scheduled_pages(&self, limit: u32) -> Result<Vec<ScheduledRecord>, RepoError>;
+
+    /// Scheduled drafts and published pages dated in `[from, to)`, in date
+    /// order.
+    async fn list_calendar_pages(
+        &self,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> Result<Vec<CalendarRecord>, RepoError>;
 }
 
 #[async_trait]
@@ -95,14 +127,47 @@ pub trait PagesWriteRepo: Send + Sync {
         params: UpdatePageStatusParams,
     ) -> Result<PageRecord, RepoError>;
 
+    /// Set or clear the page's language and translation group. Fails with
+    /// [`RepoError::Duplicate`] when the group already has a page in `lang`.
+    async fn update_page_translation(
+        &self,
+        params: UpdatePageTranslationParams,
+    ) -> Result<PageRecord, RepoError>;
+
     async fn schedule_page_publication(
         &self,
         id: Uuid,
         publish_at: OffsetDateTime,
     ) -> Result<PageRecord, RepoError>;
 
+    /// Publish a scheduled page only while it is still a draft scheduled for
+    /// `expected_scheduled_at`. Returns `None` when another writer got there
+    /// first or the schedule changed.
+    async fn publish_scheduled_page(
+        &self,
+        id: Uuid,
+        expected_scheduled_at: Option<OffsetDateTime>,
+        published_at: OffsetDateTime,
+    ) -> Result<Option<PageRecord>, RepoError>;
+
+    /// Move the page to the trash, freeing its slug.
     async fn delete_page(&self, id: Uuid) -> Result<(), RepoError>;
 
+    async fn list_trashed_pages(
+        &self,
+        page: PageRequest<TrashCursor>,
+    ) -> Result<CursorPage<TrashedRecord>, RepoError>;
+
+    /// Take a trashed page out of the trash. Fails with
+    /// [`RepoError::Duplicate`] when its slug has been reused meanwhile.
+    async fn restore_page(&self, id: Uuid) -> Result<PageRecord, RepoError>;
+
+    /// Permanently delete a trashed page.
+    async fn purge_page(&self, id: Uuid) -> Result<(), RepoError>;
+
+    /// Permanently delete pages trashed before `cutoff`, returning how many.
+    async fn purge_trashed_pages(&self, cutoff: OffsetDateTime) -> Result<u64, RepoError>;
+
     async fn restore_page_snapshot(
         &self,
         params: RestorePageSnapshotParams,
@@ -1,12 +1,17 @@
 use async_trait::async_trait;
+use chrono_tz::Tz;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::application::pagination::{CursorPage, PageRequest, PostCursor};
-use crate::domain::entities::{PostRecord, PostSectionRecord};
+use crate::application::pagination::{CursorPage, PageRequest, PostCursor, TrashCursor};
+use crate::domain::entities::{
+    CalendarRecord, PostRecord, PostSectionRecord, ScheduledRecord, TranslationRecord,
+    TrashedRecord,
+};
 use crate::domain::types::PostStatus;
 
 use super::RepoError;
+use super::tags::TagWithCount;
 
 #[derive(Debug, Clone, Copy)]
 pub enum PostListScope {
@@ -18,14 +23,24 @@ pub enum PostListScope {
 pub struct PostQueryFilter {
     pub tag: Option<String>,
     pub month: Option<String>,
+    /// Calendar year of publication, for year archives.
+    pub year: Option<i32>,
     pub search: Option<String>,
 }
 
+/// Public tag and month counts, read together for the feed sidebars.
+#[derive(Clone, Default)]
+pub struct FeedAggregates {
+    pub tags: Vec<TagWithCount>,
+    pub months: Vec<crate::domain::posts::MonthCount>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreatePostParams {
     pub slug: String,
     pub title: String,
     pub excerpt: String,
+    pub excerpt_generated: bool,
     pub body_markdown: String,
     pub status: PostStatus,
     pub pinned: bool,
@@ -42,10 +57,14 @@ pub struct UpdatePostParams {
     pub slug: String,
     pub title: String,
     pub excerpt: String,
+    pub excerpt_generated: bool,
     pub body_markdown: String,
     pub pinned: bool,
     pub summary_markdown: Option<String>,
     pub summary_html: Option<String>,
+    /// Only update while `updated_at` still equals this value; otherwise fail
+    /// with `RepoError::Stale`.
+    pub expected_updated_at: Option<OffsetDateTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -54,6 +73,7 @@ pub struct RestorePostSnapshotParams {
     pub slug: String,
     pub title: String,
     pub excerpt: String,
+    pub excerpt_generated: bool,
     pub body_markdown: String,
     pub summary_markdown: Option<String>,
     pub summary_html: Option<String>,
@@ -79,6 +99,16 @@ pub struct UpdatePostStatusParams {
 pub struct UpdatePostPinnedParams {
     pub id: Uuid,
     pub pinned: bool,
+    /// New position among the pinned posts; `None` keeps the current one.
+    /// Ignored when unpinning, which resets it to 0.
+    pub pin_order: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdatePostTranslationParams {
+    pub id: Uuid,
+    pub lang: Option<String>,
+    pub translation_group: Option<Uuid>,
 }
 
 #[derive(Debug, Clone)]
@@ -116,6 +146,20 @@ pub trait PostsRepo: Send + Sync {
         filter: &PostQueryFilter,
     ) -> Result<Vec<crate::domain::posts::MonthCount>, RepoError>;
 
+    /// Like [`list_month_counts`](Self::list_month_counts), but bucketed by
+    /// the wall-clock month in `time_zone`.
+    async fn list_local_month_counts(
+        &self,
+        scope: PostListScope,
+        filter: &PostQueryFilter,
+        time_zone: Tz,
+    ) -> Result<Vec<crate::domain::posts::MonthCount>, RepoError>;
+
+    /// Public tag counts and month counts in one round trip, ordered like
+    /// [`TagsRepo::list_with_counts`](super::TagsRepo::list_with_counts) and
+    /// [`list_month_counts`](Self::list_month_counts).
+    async fn list_public_aggregates(&self) -> Result<FeedAggregates, RepoError>;
+
     async fn list_tag_counts(
         &self,
         scope: PostListScope,
@@ -125,6 +169,23 @@ pub trait PostsRepo: Send + Sync {
     async fn find_by_slug(&self, slug: &str) -> Result<Option<PostRecord>, RepoError>;
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<PostRecord>, RepoError>;
+
+    /// Published posts in `translation_group` that carry a language, ordered by `lang`.
+    async fn list_post_translations(
+        &self,
+        translation_group: Uuid,
+    ) -> Result<Vec<TranslationRecord>, RepoError>;
+
+    /// Draft posts with a publication time, soonest first, at most `limit`.
+    async fn list_scheduled_posts(&self, limit: u32) -> Result<Vec<ScheduledRecord>, RepoError>;
+
+    /// Scheduled drafts and published posts dated in `[from, to)`, in date
+    /// order.
+    async fn list_calendar_posts(
+        &self,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> Result<Vec<CalendarRecord>, RepoError>;
 }
 
 #[async_trait]
@@ -143,14 +204,47 @@ pub trait PostsWriteRepo: Send + Sync {
         params: UpdatePostPinnedParams,
     ) -> Result<PostRecord, RepoError>;
 
+    /// Set or clear the post's language and translation group. Fails with
+    /// [`RepoError::Duplicate`] when the group already has a post in `lang`.
+    async fn update_post_translation(
+        &self,
+        params: UpdatePostTranslationParams,
+    ) -> Result<PostRecord, RepoError>;
+
     async fn schedule_post_publication(
         &self,
         id: Uuid,
         publish_at: OffsetDateTime,
     ) -> Result<PostRecord, RepoError>;
 
+    /// Publish a scheduled post only while it is still a draft scheduled for
+    /// `expected_scheduled_at`. Returns `None` when another writer got there
+    /// first or the schedule changed.
+    async fn publish_scheduled_post(
+        &self,
+        id: Uuid,
+        expected_scheduled_at: Option<OffsetDateTime>,
+        published_at: OffsetDateTime,
+    ) -> Result<Option<PostRecord>, RepoError>;
+
+    /// Move the post to the trash, freeing its slug.
     async fn delete_post(&self, id: Uuid) -> Result<(), RepoError>;
 
+    async fn list_trashed_posts(
+        &self,
+        page: PageRequest<TrashCursor>,
+    ) -> Result<CursorPage<TrashedRecord>, RepoError>;
+
+    /// Take a trashed post out of the trash. Fails with
+    /// [`RepoError::Duplicate`] when its slug has been reused meanwhile.
+    async fn restore_post(&self, id: Uuid) -> Result<PostRecord, RepoError>;
+
+    /// Permanently delete a trashed post.
+    async fn purge_post(&self, id: Uuid) -> Result<(), RepoError>;
+
+    /// Permanently delete posts trashed before `cutoff`, returning how many.
+    async fn purge_trashed_posts(&self, cutoff: OffsetDateTime) -> Result<u64, RepoError>;
+
     async fn replace_post_tags(&self, post_id: Uuid, tag_ids: &[Uuid]) -> Result<(), RepoError>;
 
     async fn restore_post_snapshot(
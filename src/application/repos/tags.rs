@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use serde::Serialize;
 use time::OffsetDateTime;
@@ -53,10 +55,33 @@ pub struct UpdateTagParams {
     pub pinned: bool,
 }
 
+/// Direction of a bulk tag assignment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagAssignmentMode {
+    Add,
+    Remove,
+}
+
+/// What a bulk tag assignment touched.
+#[derive(Debug, Clone, Default)]
+pub struct TagAssignmentChanges {
+    /// Requested posts that exist and are not in the trash, with their slugs.
+    pub posts: Vec<(Uuid, String)>,
+    /// Posts whose association with the tag was added or removed.
+    pub changed: Vec<Uuid>,
+}
+
 #[async_trait]
 pub trait TagsRepo: Send + Sync {
     async fn list_all(&self) -> Result<Vec<TagRecord>, RepoError>;
     async fn list_for_post(&self, post_id: Uuid) -> Result<Vec<TagRecord>, RepoError>;
+    /// Tags of each of `post_ids` in one lookup, ordered as `list_for_post`
+    /// orders them. Posts without tags are left out of the map.
+    async fn tags_for_posts(
+        &self,
+        post_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<TagRecord>>, RepoError>;
     async fn list_with_counts(&self) -> Result<Vec<TagWithCount>, RepoError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<TagRecord>, RepoError>;
     async fn find_by_slug(&self, slug: &str) -> Result<Option<TagRecord>, RepoError>;
@@ -86,4 +111,13 @@ pub trait TagsWriteRepo: Send + Sync {
     async fn update_tag(&self, params: UpdateTagParams) -> Result<TagRecord, RepoError>;
 
     async fn delete_tag(&self, id: Uuid) -> Result<(), RepoError>;
+
+    /// Add or remove the tag on every live post in `post_ids` in one
+    /// transaction.
+    async fn assign_tag(
+        &self,
+        tag_id: Uuid,
+        post_ids: &[Uuid],
+        mode: TagAssignmentMode,
+    ) -> Result<TagAssignmentChanges, RepoError>;
 }
@@ -12,6 +12,10 @@ pub struct UploadQueryFilter {
     pub content_type: Option<String>,
     pub month: Option<String>,
     pub search: Option<String>,
+    /// Hex SHA-256 of the file contents. Uploads whose stored file failed
+    /// its integrity check never match, so a checksum lookup only finds
+    /// intact copies.
+    pub checksum: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +24,15 @@ pub struct UploadContentTypeCount {
     pub count: u64,
 }
 
+/// Outcome of the integrity checks recorded so far.
+#[derive(Debug, Clone, Default)]
+pub struct UploadIntegritySummary {
+    /// Uploads whose stored file failed its last check.
+    pub mismatched: u64,
+    /// Most recent check of any upload.
+    pub last_verified_at: Option<OffsetDateTime>,
+}
+
 #[derive(Debug, Clone)]
 pub struct UploadMonthCount {
     pub key: String,
@@ -31,6 +44,11 @@ pub struct UploadMonthCount {
 pub trait UploadsRepo: Send + Sync {
     async fn insert_upload(&self, record: UploadRecord) -> Result<(), RepoError>;
     async fn find_upload(&self, id: Uuid) -> Result<Option<UploadRecord>, RepoError>;
+    /// Upload served at `/uploads/{stored_path}`.
+    async fn find_upload_by_path(
+        &self,
+        stored_path: &str,
+    ) -> Result<Option<UploadRecord>, RepoError>;
     async fn list_recent(
         &self,
         limit: u32,
@@ -52,4 +70,14 @@ pub trait UploadsRepo: Send + Sync {
         filter: &UploadQueryFilter,
     ) -> Result<Vec<UploadContentTypeCount>, RepoError>;
     async fn delete_upload(&self, id: Uuid) -> Result<(), RepoError>;
+    /// Filenames of existing uploads that start with any of `prefixes`.
+    async fn filenames_with_prefixes(&self, prefixes: &[String]) -> Result<Vec<String>, RepoError>;
+    /// Store the outcome of re-hashing the upload's file.
+    async fn record_verification(
+        &self,
+        id: Uuid,
+        verified_at: OffsetDateTime,
+        matches: bool,
+    ) -> Result<(), RepoError>;
+    async fn integrity_summary(&self) -> Result<UploadIntegritySummary, RepoError>;
 }
@@ -0,0 +1,53 @@
+//! Correlation id of the HTTP request currently being served.
+//!
+//! The HTTP layer scopes the id to the request task so services can stamp it
+//! onto audit entries and job payloads without threading it through every
+//! signature. Outside a request (workers, CLI) no id is set.
+
+use std::future::Future;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Run `future` with `request_id` as the current request id.
+pub async fn scope<F>(request_id: String, future: F) -> F::Output
+where
+    F: Future,
+{
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// The id of the request being served, if any.
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Add the current request id to a job payload object as `request_id`.
+pub fn tag_job_payload(payload: &mut serde_json::Value) {
+    if let (Some(id), Some(object)) = (current(), payload.as_object_mut()) {
+        object
+            .entry("request_id")
+            .or_insert_with(|| serde_json::Value::String(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn request_id_is_visible_inside_scope_only() {
+        assert!(current().is_none());
+
+        let mut payload = serde_json::json!({ "slug": "hello" });
+        scope("req-1".to_string(), async {
+            assert_eq!(current().as_deref(), Some("req-1"));
+            tag_job_payload(&mut payload);
+        })
+        .await;
+
+        assert_eq!(payload["request_id"], "req-1");
+        assert!(current().is_none());
+    }
+}
@@ -4,21 +4,121 @@ use std::{fs, path::Path};
 
 use crate::{
     application::error::AppError,
-    infra::{db::PostgresRepositories, error::InfraError},
+    infra::{db::PostgresRepositories, error::InfraError, uploads::UploadStorage},
 };
 
 #[path = "site/export.rs"]
 mod export;
 #[path = "site/import.rs"]
 mod import;
+#[path = "site/markdown.rs"]
+mod markdown;
 #[path = "site/models.rs"]
 mod models;
+#[path = "site/uploads.rs"]
+mod uploads;
+
+pub use markdown::{
+    MarkdownImportReport, MarkdownPost, SkippedFile, import_markdown_posts, parse_markdown_post,
+};
 
 pub(super) const SETTINGS_ROW_ID: i16 = 1;
 
+/// How an import treats content already in the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Refuse to import if any posts, pages, tags or navigation items exist.
+    Fresh,
+    /// Keep existing rows when a slug (or navigation label) is already taken.
+    MergeSkip,
+    /// Replace existing rows with the archived ones on conflict.
+    MergeOverwrite,
+    /// Truncate content tables and API keys before importing.
+    Wipe,
+}
+
+impl ImportMode {
+    pub const ALL: [Self; 4] = [
+        Self::Fresh,
+        Self::MergeSkip,
+        Self::MergeOverwrite,
+        Self::Wipe,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fresh => "fresh",
+            Self::MergeSkip => "merge-skip",
+            Self::MergeOverwrite => "merge-overwrite",
+            Self::Wipe => "wipe",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
+/// Rows written to one table by an import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableCounts {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+/// Outcome of an import: row counts per table and the upload files restored.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Row counts keyed by table name, in import order.
+    pub tables: Vec<(&'static str, TableCounts)>,
+    /// Upload files written from the archive.
+    pub uploads_copied: usize,
+    /// Upload files already in storage with a matching checksum.
+    pub uploads_present: usize,
+    /// Uploads whose file could not be restored. Their rows are not imported.
+    pub upload_failures: Vec<UploadFailure>,
+}
+
+impl ImportReport {
+    /// Counts recorded for `table`, if the import touched it.
+    pub fn table(&self, table: &str) -> Option<&TableCounts> {
+        self.tables
+            .iter()
+            .find(|(name, _)| *name == table)
+            .map(|(_, counts)| counts)
+    }
+}
+
+/// An upload left out of an import.
+#[derive(Debug, Clone)]
+pub struct UploadFailure {
+    pub stored_path: String,
+    pub reason: String,
+}
+
 /// Export the current site data to the provided path as a TOML archive.
-pub async fn export_site(repositories: &PostgresRepositories, path: &Path) -> Result<(), AppError> {
-    let archive = export::gather_archive(repositories.pool()).await?;
+///
+/// With `upload_storage`, upload rows are added to the archive and their files are
+/// copied into a directory next to it named `<archive stem>-uploads`.
+pub async fn export_site(
+    repositories: &PostgresRepositories,
+    path: &Path,
+    upload_storage: Option<&UploadStorage>,
+) -> Result<(), AppError> {
+    let mut archive = export::gather_archive(repositories.pool()).await?;
+    if let Some(storage) = upload_storage {
+        let dir_name = uploads::uploads_dir_name(path);
+        let target_dir = path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(&dir_name);
+        archive.uploads = uploads::fetch_uploads(repositories.pool()).await?;
+        uploads::export_files(storage, &archive.uploads, &target_dir).await?;
+        archive.uploads_dir = Some(dir_name);
+    }
     let encoded = toml::to_string_pretty(&archive)
         .map_err(|err| AppError::unexpected(format!("failed to encode archive: {err}")))?;
     fs::write(path, encoded).map_err(|err| AppError::from(InfraError::Io(err)))?;
@@ -26,12 +126,44 @@ pub async fn export_site(repositories: &PostgresRepositories, path: &Path) -> Re
 }
 
 /// Import site data from the provided TOML archive path.
-pub async fn import_site(repositories: &PostgresRepositories, path: &Path) -> Result<(), AppError> {
+///
+/// `mode` decides what happens to rows already in the database; see
+/// [`ImportMode`]. Each entity class (posts with their tags, pages with
+/// navigation, API keys, uploads, settings) is written in its own
+/// transaction. API keys are never overwritten: archived keys whose id
+/// already exists are skipped in every mode.
+///
+/// Upload files listed in the archive are restored into `upload_storage` first.
+/// Files already present with a matching checksum are kept; missing or
+/// corrupt files are reported in the returned [`ImportReport`] and their rows
+/// are skipped.
+pub async fn import_site(
+    repositories: &PostgresRepositories,
+    upload_storage: &UploadStorage,
+    path: &Path,
+    mode: ImportMode,
+) -> Result<ImportReport, AppError> {
     let data = fs::read_to_string(path).map_err(|err| AppError::from(InfraError::Io(err)))?;
     let mut archive: models::SiteArchive = toml::from_str(&data)
         .map_err(|err| AppError::validation(format!("invalid archive: {err}")))?;
     archive.normalize();
-    import::import_archive(repositories, archive).await
+    import::prepare(repositories, &archive, mode).await?;
+
+    let mut report = ImportReport::default();
+    if !archive.uploads.is_empty() {
+        let source_dir = archive
+            .uploads_dir
+            .as_ref()
+            .map(|dir| path.parent().unwrap_or_else(|| Path::new("")).join(dir));
+        let restored =
+            uploads::import_files(upload_storage, &archive.uploads, source_dir, &mut report).await;
+        archive
+            .uploads
+            .retain(|upload| restored.contains(&upload.id));
+    }
+
+    import::import_archive(repositories, archive, mode, &mut report).await?;
+    Ok(report)
 }
 
 pub(super) fn map_sqlx_error(err: sqlx::Error) -> AppError {
@@ -28,6 +28,7 @@ pub(super) async fn gather_archive(pool: &PgPool) -> Result<SiteArchive, AppErro
     let api_keys = fetch_api_keys(pool).await?;
 
     Ok(SiteArchive {
+        uploads_dir: None,
         migrations,
         site_settings,
         posts,
@@ -36,6 +37,7 @@ pub(super) async fn gather_archive(pool: &PgPool) -> Result<SiteArchive, AppErro
         post_tags,
         navigation_items,
         api_keys,
+        uploads: Vec::new(),
     })
 }
 
@@ -110,6 +112,7 @@ async fn fetch_site_settings(pool: &PgPool) -> Result<SiteSettingsSnapshot, AppE
 }
 
 async fn fetch_posts(pool: &PgPool) -> Result<Vec<PostSnapshot>, AppError> {
+    // Trashed posts are left out; a restored archive starts with an empty trash.
     let rows = query!(
         r#"
         SELECT
@@ -124,6 +127,7 @@ async fn fetch_posts(pool: &PgPool) -> Result<Vec<PostSnapshot>, AppError> {
             published_at,
             archived_at
         FROM posts
+        WHERE deleted_at IS NULL
         ORDER BY slug
         "#
     )
@@ -158,8 +162,10 @@ async fn fetch_pages(pool: &PgPool) -> Result<Vec<PageSnapshot>, AppError> {
             status AS "status: PageStatus",
             scheduled_at,
             published_at,
-            archived_at
+            archived_at,
+            template
         FROM pages
+        WHERE deleted_at IS NULL
         ORDER BY slug
         "#
     )
@@ -177,6 +183,7 @@ async fn fetch_pages(pool: &PgPool) -> Result<Vec<PageSnapshot>, AppError> {
             scheduled_at: row.scheduled_at,
             published_at: row.published_at,
             archived_at: row.archived_at,
+            template: row.template,
         })
         .collect())
 }
@@ -211,7 +218,7 @@ async fn fetch_post_tags(pool: &PgPool) -> Result<Vec<PostTagLink>, AppError> {
             p.slug AS "post_slug!",
             t.slug AS "tag_slug!"
         FROM post_tags pt
-        INNER JOIN posts p ON p.id = pt.post_id
+        INNER JOIN posts p ON p.id = pt.post_id AND p.deleted_at IS NULL
         INNER JOIN tags t ON t.id = pt.tag_id
         ORDER BY p.slug, t.slug
         "#
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use sqlx::{Postgres, Transaction, query};
+use sqlx::{Postgres, Transaction, query, query_scalar};
 use uuid::Uuid;
 
 use crate::{
@@ -13,32 +13,469 @@ use crate::{
 };
 
 use super::{
-    SETTINGS_ROW_ID, map_sqlx_error,
+    ImportMode, ImportReport, SETTINGS_ROW_ID, TableCounts, map_sqlx_error,
     models::{MigrationEntry, MigrationSnapshot, SiteArchive},
 };
 
-pub(super) async fn import_archive(
+/// What an import does with one archived row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowAction {
+    Create,
+    Update(Uuid),
+    Skip(Uuid),
+}
+
+fn row_action(mode: ImportMode, existing: Option<Uuid>) -> RowAction {
+    match existing {
+        None => RowAction::Create,
+        Some(id) if mode == ImportMode::MergeSkip => RowAction::Skip(id),
+        Some(id) => RowAction::Update(id),
+    }
+}
+
+fn tally(counts: &mut TableCounts, action: RowAction) {
+    match action {
+        RowAction::Create => counts.created += 1,
+        RowAction::Update(_) => counts.updated += 1,
+        RowAction::Skip(_) => counts.skipped += 1,
+    }
+}
+
+/// Checks that run before anything is written: the migration history must
+/// match, archived references must resolve, and `fresh` requires an empty
+/// site. Under `wipe` the content tables are truncated here.
+pub(super) async fn prepare(
     repositories: &PostgresRepositories,
-    archive: SiteArchive,
+    archive: &SiteArchive,
+    mode: ImportMode,
 ) -> Result<(), AppError> {
     let mut tx = repositories.begin().await.map_err(map_sqlx_error)?;
 
     let db_migrations = fetch_migrations_from(&mut tx).await?;
     ensure_migrations_match(&db_migrations.entries, &archive.migrations.entries)?;
+    ensure_references_resolve(archive)?;
+
+    match mode {
+        ImportMode::Fresh => ensure_no_content(&mut tx).await?,
+        ImportMode::Wipe => {
+            query("SET CONSTRAINTS ALL DEFERRED")
+                .execute(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+
+            query(
+                "TRUNCATE post_tags, navigation_items, tags, pages, posts, api_keys RESTART IDENTITY CASCADE",
+            )
+            .execute(tx.as_mut())
+            .await
+            .map_err(map_sqlx_error)?;
+        }
+        ImportMode::MergeSkip | ImportMode::MergeOverwrite => {}
+    }
+
+    tx.commit().await.map_err(map_sqlx_error)?;
+    Ok(())
+}
+
+/// Write the archive, one transaction per entity class, so a failure never
+/// leaves post tags pointing at posts that were not imported.
+pub(super) async fn import_archive(
+    repositories: &PostgresRepositories,
+    archive: SiteArchive,
+    mode: ImportMode,
+    report: &mut ImportReport,
+) -> Result<(), AppError> {
+    let (tags, posts, post_tags) = import_posts(repositories, &archive, mode).await?;
+    report.tables.push(("tags", tags));
+    report.tables.push(("posts", posts));
+    report.tables.push(("post_tags", post_tags));
+
+    let (pages, navigation) = import_pages(repositories, &archive, mode).await?;
+    report.tables.push(("pages", pages));
+    report.tables.push(("navigation_items", navigation));
+
+    let api_keys = import_api_keys(repositories, &archive).await?;
+    report.tables.push(("api_keys", api_keys));
 
-    query("SET CONSTRAINTS ALL DEFERRED")
+    let uploads = import_uploads(repositories, &archive, mode).await?;
+    report.tables.push(("uploads", uploads));
+
+    let settings = import_settings(repositories, &archive, mode).await?;
+    report.tables.push(("site_settings", settings));
+
+    Ok(())
+}
+
+async fn import_posts(
+    repositories: &PostgresRepositories,
+    archive: &SiteArchive,
+    mode: ImportMode,
+) -> Result<(TableCounts, TableCounts, TableCounts), AppError> {
+    let mut tx = repositories.begin().await.map_err(map_sqlx_error)?;
+
+    let mut tag_counts = TableCounts::default();
+    let mut tag_ids = HashMap::new();
+    for tag in &archive.tags {
+        let existing = find_id(&mut tx, "SELECT id FROM tags WHERE slug = $1", &tag.slug).await?;
+        let action = row_action(mode, existing);
+        tally(&mut tag_counts, action);
+
+        let id = match action {
+            RowAction::Skip(id) => id,
+            RowAction::Update(id) => {
+                query!(
+                    r#"
+                    UPDATE tags
+                    SET name = $2, description = $3, pinned = $4, updated_at = now()
+                    WHERE id = $1
+                    "#,
+                    id,
+                    &tag.name,
+                    tag.description.as_deref(),
+                    tag.pinned
+                )
+                .execute(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+                id
+            }
+            RowAction::Create => {
+                let id = Uuid::new_v4();
+                query!(
+                    r#"
+                    INSERT INTO tags (id, slug, name, description, pinned)
+                    VALUES ($1, $2, $3, $4, $5)
+                    "#,
+                    id,
+                    &tag.slug,
+                    &tag.name,
+                    tag.description.as_deref(),
+                    tag.pinned
+                )
+                .execute(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+                id
+            }
+        };
+        tag_ids.insert(tag.slug.as_str(), id);
+    }
+
+    let mut post_counts = TableCounts::default();
+    // Posts whose tag links are written: skipped posts keep their own links.
+    let mut linked_posts = HashMap::new();
+    for post in &archive.posts {
+        let existing = find_id(
+            &mut tx,
+            "SELECT id FROM posts WHERE slug = $1 AND deleted_at IS NULL",
+            &post.slug,
+        )
+        .await?;
+        let action = row_action(mode, existing);
+        tally(&mut post_counts, action);
+
+        let id = match action {
+            RowAction::Skip(_) => continue,
+            RowAction::Update(id) => {
+                query!(
+                    r#"
+                    UPDATE posts
+                    SET
+                        title = $2,
+                        excerpt = $3,
+                        body_markdown = $4,
+                        summary_markdown = $5,
+                        status = $6,
+                        pinned = $7,
+                        pin_order = CASE WHEN $7 THEN pin_order ELSE 0 END,
+                        scheduled_at = $8,
+                        published_at = $9,
+                        archived_at = $10,
+                        updated_at = now()
+                    WHERE id = $1
+                    "#,
+                    id,
+                    &post.title,
+                    &post.excerpt,
+                    &post.body_markdown,
+                    post.summary_markdown.as_deref(),
+                    post.status as PostStatus,
+                    post.pinned,
+                    post.scheduled_at,
+                    post.published_at,
+                    post.archived_at
+                )
+                .execute(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+
+                // The archived tag set replaces the existing one.
+                query!("DELETE FROM post_tags WHERE post_id = $1", id)
+                    .execute(tx.as_mut())
+                    .await
+                    .map_err(map_sqlx_error)?;
+                id
+            }
+            RowAction::Create => {
+                let id = Uuid::new_v4();
+                query!(
+                    r#"
+                    INSERT INTO posts (
+                        id,
+                        slug,
+                        title,
+                        excerpt,
+                        body_markdown,
+                        summary_markdown,
+                        status,
+                        pinned,
+                        scheduled_at,
+                        published_at,
+                        archived_at
+                    )
+                    VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
+                    "#,
+                    id,
+                    &post.slug,
+                    &post.title,
+                    &post.excerpt,
+                    &post.body_markdown,
+                    post.summary_markdown.as_deref(),
+                    post.status as PostStatus,
+                    post.pinned,
+                    post.scheduled_at,
+                    post.published_at,
+                    post.archived_at
+                )
+                .execute(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+                id
+            }
+        };
+        linked_posts.insert(post.slug.as_str(), id);
+    }
+
+    let mut link_counts = TableCounts::default();
+    for link in &archive.post_tags {
+        let Some(post_id) = linked_posts.get(link.post_slug.as_str()) else {
+            link_counts.skipped += 1;
+            continue;
+        };
+        let tag_id = tag_ids
+            .get(link.tag_slug.as_str())
+            .ok_or_else(|| AppError::validation(format!("unknown tag slug `{}`", link.tag_slug)))?;
+
+        query!(
+            r#"
+            INSERT INTO post_tags (post_id, tag_id)
+            VALUES ($1, $2)
+            "#,
+            post_id,
+            tag_id
+        )
         .execute(tx.as_mut())
         .await
         .map_err(map_sqlx_error)?;
+        link_counts.created += 1;
+    }
 
-    query(
-        "TRUNCATE post_tags, navigation_items, tags, pages, posts, api_keys RESTART IDENTITY CASCADE",
-    )
-    .execute(tx.as_mut())
-    .await
-    .map_err(map_sqlx_error)?;
+    tx.commit().await.map_err(map_sqlx_error)?;
+    Ok((tag_counts, post_counts, link_counts))
+}
+
+async fn import_pages(
+    repositories: &PostgresRepositories,
+    archive: &SiteArchive,
+    mode: ImportMode,
+) -> Result<(TableCounts, TableCounts), AppError> {
+    let mut tx = repositories.begin().await.map_err(map_sqlx_error)?;
+
+    let mut page_counts = TableCounts::default();
+    let mut page_ids = HashMap::new();
+    for page in &archive.pages {
+        let existing = find_id(
+            &mut tx,
+            "SELECT id FROM pages WHERE slug = $1 AND deleted_at IS NULL",
+            &page.slug,
+        )
+        .await?;
+        let action = row_action(mode, existing);
+        tally(&mut page_counts, action);
+
+        let id = match action {
+            RowAction::Skip(id) => id,
+            RowAction::Update(id) => {
+                query!(
+                    r#"
+                    UPDATE pages
+                    SET
+                        title = $2,
+                        body_markdown = $3,
+                        status = $4,
+                        scheduled_at = $5,
+                        published_at = $6,
+                        archived_at = $7,
+                        template = $8,
+                        updated_at = now()
+                    WHERE id = $1
+                    "#,
+                    id,
+                    &page.title,
+                    &page.body_markdown,
+                    page.status as PageStatus,
+                    page.scheduled_at,
+                    page.published_at,
+                    page.archived_at,
+                    &page.template
+                )
+                .execute(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+                id
+            }
+            RowAction::Create => {
+                let id = Uuid::new_v4();
+                query!(
+                    r#"
+                    INSERT INTO pages (
+                        id,
+                        slug,
+                        title,
+                        body_markdown,
+                        rendered_html,
+                        status,
+                        scheduled_at,
+                        published_at,
+                        archived_at,
+                        template
+                    )
+                    VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10)
+                    "#,
+                    id,
+                    &page.slug,
+                    &page.title,
+                    &page.body_markdown,
+                    "", // rendered_html intentionally blank; renderall will repopulate
+                    page.status as PageStatus,
+                    page.scheduled_at,
+                    page.published_at,
+                    page.archived_at,
+                    &page.template
+                )
+                .execute(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+                id
+            }
+        };
+        page_ids.insert(page.slug.as_str(), id);
+    }
+
+    let mut navigation_counts = TableCounts::default();
+    for item in &archive.navigation_items {
+        let destination_page_id = match item.destination_type {
+            NavigationDestinationType::Internal => item
+                .destination_page_slug
+                .as_deref()
+                .and_then(|slug| page_ids.get(slug).copied()),
+            NavigationDestinationType::External => None,
+        };
+
+        let existing = find_id(
+            &mut tx,
+            "SELECT id FROM navigation_items WHERE label = $1 ORDER BY sort_order, id LIMIT 1",
+            &item.label,
+        )
+        .await?;
+        let action = row_action(mode, existing);
+        tally(&mut navigation_counts, action);
+
+        match action {
+            RowAction::Skip(_) => {}
+            RowAction::Update(id) => {
+                query!(
+                    r#"
+                    UPDATE navigation_items
+                    SET
+                        destination_type = $2,
+                        destination_url = $3,
+                        destination_page_id = $4,
+                        sort_order = $5,
+                        open_in_new_tab = $6,
+                        visible = $7,
+                        updated_at = now()
+                    WHERE id = $1
+                    "#,
+                    id,
+                    item.destination_type as NavigationDestinationType,
+                    item.destination_url.as_deref(),
+                    destination_page_id,
+                    item.sort_order,
+                    item.open_in_new_tab,
+                    item.visible
+                )
+                .execute(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+            }
+            RowAction::Create => {
+                query!(
+                    r#"
+                    INSERT INTO navigation_items (
+                        id,
+                        label,
+                        destination_type,
+                        destination_url,
+                        destination_page_id,
+                        sort_order,
+                        open_in_new_tab,
+                        visible
+                    )
+                    VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
+                    "#,
+                    Uuid::new_v4(),
+                    &item.label,
+                    item.destination_type as NavigationDestinationType,
+                    item.destination_url.as_deref(),
+                    destination_page_id,
+                    item.sort_order,
+                    item.open_in_new_tab,
+                    item.visible
+                )
+                .execute(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+            }
+        }
+    }
+
+    tx.commit().await.map_err(map_sqlx_error)?;
+    Ok((page_counts, navigation_counts))
+}
+
+/// API keys are credentials, so an archived key never replaces a live one:
+/// keys whose id already exists are skipped in every mode.
+async fn import_api_keys(
+    repositories: &PostgresRepositories,
+    archive: &SiteArchive,
+) -> Result<TableCounts, AppError> {
+    let mut tx = repositories.begin().await.map_err(map_sqlx_error)?;
+    let mut counts = TableCounts::default();
 
     for key in &archive.api_keys {
+        let existing: bool = query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM api_keys WHERE id = $1) AS "exists!""#,
+            key.id
+        )
+        .fetch_one(tx.as_mut())
+        .await
+        .map_err(map_sqlx_error)?;
+        if existing {
+            counts.skipped += 1;
+            continue;
+        }
+
         let expires_in_pg = key.expires_in.map(duration_to_pg_interval);
         query!(
             r#"
@@ -93,168 +530,89 @@ pub(super) async fn import_archive(
         .execute(tx.as_mut())
         .await
         .map_err(map_sqlx_error)?;
+        counts.created += 1;
     }
 
-    let mut tag_ids = HashMap::new();
-    for tag in &archive.tags {
-        let id = Uuid::new_v4();
-        query!(
-            r#"
-            INSERT INTO tags (id, slug, name, description, pinned)
-            VALUES ($1, $2, $3, $4, $5)
-            "#,
-            id,
-            tag.slug,
-            tag.name,
-            tag.description,
-            tag.pinned,
-        )
-        .execute(tx.as_mut())
-        .await
-        .map_err(map_sqlx_error)?;
-        tag_ids.insert(tag.slug.clone(), id);
-    }
+    tx.commit().await.map_err(map_sqlx_error)?;
+    Ok(counts)
+}
 
-    let mut post_ids = HashMap::new();
-    for post in &archive.posts {
-        let id = Uuid::new_v4();
-        query!(
-            r#"
-            INSERT INTO posts (
-                id,
-                slug,
-                title,
-                excerpt,
-                body_markdown,
-                summary_markdown,
-                status,
-                pinned,
-                scheduled_at,
-                published_at,
-                archived_at
-            )
-            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
-            "#,
-            id,
-            post.slug,
-            post.title,
-            post.excerpt,
-            post.body_markdown,
-            post.summary_markdown,
-            post.status as PostStatus,
-            post.pinned,
-            post.scheduled_at,
-            post.published_at,
-            post.archived_at,
+async fn import_uploads(
+    repositories: &PostgresRepositories,
+    archive: &SiteArchive,
+    mode: ImportMode,
+) -> Result<TableCounts, AppError> {
+    let mut tx = repositories.begin().await.map_err(map_sqlx_error)?;
+    let mut counts = TableCounts::default();
+
+    for upload in &archive.uploads {
+        let existing: bool = query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM uploads WHERE id = $1) AS "exists!""#,
+            upload.id
         )
-        .execute(tx.as_mut())
+        .fetch_one(tx.as_mut())
         .await
         .map_err(map_sqlx_error)?;
-        post_ids.insert(post.slug.clone(), id);
-    }
+        let action = row_action(mode, existing.then_some(upload.id));
+        tally(&mut counts, action);
+        if matches!(action, RowAction::Skip(_)) {
+            continue;
+        }
 
-    let mut page_ids = HashMap::new();
-    for page in &archive.pages {
-        let id = Uuid::new_v4();
+        let metadata = serde_json::to_value(&upload.metadata)
+            .map_err(|err| AppError::unexpected(format!("failed to encode metadata: {err}")))?;
         query!(
             r#"
-            INSERT INTO pages (
+            INSERT INTO uploads (
                 id,
-                slug,
-                title,
-                body_markdown,
-                rendered_html,
-                status,
-                scheduled_at,
-                published_at,
-                archived_at
+                filename,
+                content_type,
+                size_bytes,
+                checksum,
+                stored_path,
+                metadata,
+                created_at
             )
-            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9)
-            "#,
-            id,
-            page.slug,
-            page.title,
-            page.body_markdown,
-            "", // rendered_html intentionally blank; renderall will repopulate
-            page.status as PageStatus,
-            page.scheduled_at,
-            page.published_at,
-            page.archived_at,
-        )
-        .execute(tx.as_mut())
-        .await
-        .map_err(map_sqlx_error)?;
-        page_ids.insert(page.slug.clone(), id);
-    }
-
-    for link in &archive.post_tags {
-        let post_id = post_ids.get(&link.post_slug).ok_or_else(|| {
-            AppError::validation(format!("unknown post slug `{}`", link.post_slug))
-        })?;
-        let tag_id = tag_ids
-            .get(&link.tag_slug)
-            .ok_or_else(|| AppError::validation(format!("unknown tag slug `{}`", link.tag_slug)))?;
-
-        query!(
-            r#"
-            INSERT INTO post_tags (post_id, tag_id)
-            VALUES ($1, $2)
+            VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
+            ON CONFLICT (id) DO UPDATE SET
+                filename = EXCLUDED.filename,
+                content_type = EXCLUDED.content_type,
+                size_bytes = EXCLUDED.size_bytes,
+                checksum = EXCLUDED.checksum,
+                stored_path = EXCLUDED.stored_path,
+                metadata = EXCLUDED.metadata,
+                created_at = EXCLUDED.created_at
             "#,
-            post_id,
-            tag_id,
+            upload.id,
+            &upload.filename,
+            &upload.content_type,
+            upload.size_bytes,
+            &upload.checksum,
+            &upload.stored_path,
+            metadata,
+            upload.created_at
         )
         .execute(tx.as_mut())
         .await
         .map_err(map_sqlx_error)?;
     }
 
-    for item in &archive.navigation_items {
-        let destination_page_id = match item.destination_type {
-            NavigationDestinationType::Internal => {
-                let slug = item.destination_page_slug.as_ref().ok_or_else(|| {
-                    AppError::validation(format!(
-                        "navigation item `{}` missing destination_page_slug",
-                        item.label
-                    ))
-                })?;
-                Some(*page_ids.get(slug).ok_or_else(|| {
-                    AppError::validation(format!(
-                        "navigation item `{}` references unknown page slug `{slug}`",
-                        item.label
-                    ))
-                })?)
-            }
-            NavigationDestinationType::External => None,
-        };
+    tx.commit().await.map_err(map_sqlx_error)?;
+    Ok(counts)
+}
 
-        query!(
-            r#"
-            INSERT INTO navigation_items (
-                id,
-                label,
-                destination_type,
-                destination_url,
-                destination_page_id,
-                sort_order,
-                open_in_new_tab,
-                visible
-            )
-            VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
-            "#,
-            Uuid::new_v4(),
-            item.label,
-            item.destination_type as NavigationDestinationType,
-            item.destination_url,
-            destination_page_id,
-            item.sort_order,
-            item.open_in_new_tab,
-            item.visible,
-        )
-        .execute(tx.as_mut())
-        .await
-        .map_err(map_sqlx_error)?;
+async fn import_settings(
+    repositories: &PostgresRepositories,
+    archive: &SiteArchive,
+    mode: ImportMode,
+) -> Result<TableCounts, AppError> {
+    let mut counts = TableCounts::default();
+    if mode == ImportMode::MergeSkip {
+        counts.skipped += 1;
+        return Ok(counts);
     }
 
+    let mut tx = repositories.begin().await.map_err(map_sqlx_error)?;
     let settings = &archive.site_settings;
     query!(
         r#"
@@ -304,6 +662,77 @@ pub(super) async fn import_archive(
     .map_err(map_sqlx_error)?;
 
     tx.commit().await.map_err(map_sqlx_error)?;
+    counts.updated += 1;
+    Ok(counts)
+}
+
+async fn find_id(
+    tx: &mut Transaction<'_, Postgres>,
+    sql: &'static str,
+    key: &str,
+) -> Result<Option<Uuid>, AppError> {
+    query_scalar(sql)
+        .bind(key)
+        .fetch_optional(tx.as_mut())
+        .await
+        .map_err(map_sqlx_error)
+}
+
+async fn ensure_no_content(tx: &mut Transaction<'_, Postgres>) -> Result<(), AppError> {
+    let has_content: bool = query_scalar(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM posts)
+            OR EXISTS (SELECT 1 FROM pages)
+            OR EXISTS (SELECT 1 FROM tags)
+            OR EXISTS (SELECT 1 FROM navigation_items)
+        "#,
+    )
+    .fetch_one(tx.as_mut())
+    .await
+    .map_err(map_sqlx_error)?;
+
+    if has_content {
+        return Err(AppError::validation(
+            "database already has content; import with --mode merge-skip, merge-overwrite or wipe",
+        ));
+    }
+    Ok(())
+}
+
+fn ensure_references_resolve(archive: &SiteArchive) -> Result<(), AppError> {
+    for link in &archive.post_tags {
+        if !archive.posts.iter().any(|post| post.slug == link.post_slug) {
+            return Err(AppError::validation(format!(
+                "unknown post slug `{}`",
+                link.post_slug
+            )));
+        }
+        if !archive.tags.iter().any(|tag| tag.slug == link.tag_slug) {
+            return Err(AppError::validation(format!(
+                "unknown tag slug `{}`",
+                link.tag_slug
+            )));
+        }
+    }
+
+    for item in &archive.navigation_items {
+        if item.destination_type != NavigationDestinationType::Internal {
+            continue;
+        }
+        let slug = item.destination_page_slug.as_ref().ok_or_else(|| {
+            AppError::validation(format!(
+                "navigation item `{}` missing destination_page_slug",
+                item.label
+            ))
+        })?;
+        if !archive.pages.iter().any(|page| &page.slug == slug) {
+            return Err(AppError::validation(format!(
+                "navigation item `{}` references unknown page slug `{slug}`",
+                item.label
+            )));
+        }
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,426 @@
+//! Create posts from a directory of Markdown files with YAML front matter.
+//!
+//! ```markdown
+//! ---
+//! title: Hello
+//! slug: hello
+//! tags: [Rust, Web]
+//! date: 2024-03-01
+//! status: published
+//! ---
+//! Body in Markdown.
+//! ```
+//!
+//! Only `title` is required. `status` defaults to `draft`; `date` becomes the
+//! publication time and accepts RFC 3339 or `YYYY-MM-DD` (midnight UTC).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use time::{
+    Date, OffsetDateTime, format_description::well_known::Rfc3339, macros::format_description,
+};
+use uuid::Uuid;
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::{
+    application::{
+        admin::{
+            posts::{AdminPostError, AdminPostService, CreatePostCommand},
+            tags::{AdminTagError, AdminTagService, CreateTagCommand},
+        },
+        error::AppError,
+        repos::RepoError,
+    },
+    domain::{slug::derive_slug, types::PostStatus},
+    infra::error::InfraError,
+};
+
+/// Outcome of a Markdown import.
+#[derive(Debug, Default)]
+pub struct MarkdownImportReport {
+    /// Slugs of the created posts, in file name order.
+    pub created: Vec<String>,
+    /// Names of the tags that did not exist before the import.
+    pub tags_created: Vec<String>,
+    /// Files that were left out, with the reason.
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// A Markdown file that produced no post.
+#[derive(Debug, Clone)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+/// A post as described by one Markdown file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownPost {
+    pub title: String,
+    pub slug: Option<String>,
+    pub tags: Vec<String>,
+    pub date: Option<OffsetDateTime>,
+    pub status: PostStatus,
+    pub body: String,
+}
+
+/// Create a post for every `.md` / `.markdown` file directly inside `dir`,
+/// creating tags by name as needed.
+///
+/// Files that cannot be read, whose front matter is malformed, whose tags
+/// are unusable, or whose post is rejected (missing body, slug already
+/// taken), are skipped and listed in the report; only storage failures
+/// abort the import.
+pub async fn import_markdown_posts(
+    posts: &AdminPostService,
+    tags: &AdminTagService,
+    actor: &str,
+    dir: &Path,
+) -> Result<MarkdownImportReport, AppError> {
+    let files = markdown_files(dir).await?;
+    let mut tag_ids = TagLookup::load(tags).await?;
+    let mut report = MarkdownImportReport::default();
+
+    for path in files {
+        let text = match tokio::fs::read_to_string(&path).await {
+            Ok(text) => text,
+            Err(err) => {
+                report.skipped.push(SkippedFile {
+                    path,
+                    reason: format!("cannot read file: {err}"),
+                });
+                continue;
+            }
+        };
+        let post = match parse_markdown_post(&text) {
+            Ok(post) => post,
+            Err(reason) => {
+                report.skipped.push(SkippedFile { path, reason });
+                continue;
+            }
+        };
+
+        // Check every tag before creating any, so a bad name in the list
+        // leaves neither a post nor a stray tag behind.
+        if let Err(reason) = tag_ids.check(&post.tags) {
+            report.skipped.push(SkippedFile { path, reason });
+            continue;
+        }
+        let mut ids = Vec::with_capacity(post.tags.len());
+        let mut rejected = None;
+        for name in &post.tags {
+            match tag_ids.resolve(tags, actor, name, &mut report).await {
+                Ok(id) => ids.push(id),
+                Err(AdminTagError::Repo(err)) => return Err(repo_error(err)),
+                Err(err) => {
+                    rejected = Some(format!("tag `{name}`: {err}"));
+                    break;
+                }
+            }
+        }
+        if let Some(reason) = rejected {
+            report.skipped.push(SkippedFile { path, reason });
+            continue;
+        }
+
+        let record = match posts.create_post(actor, create_command(post)).await {
+            Ok(record) => record,
+            Err(AdminPostError::Repo(err)) => return Err(repo_error(err)),
+            // The file itself is at fault: no body, a bad or taken slug.
+            Err(err) => {
+                report.skipped.push(SkippedFile {
+                    path,
+                    reason: err.to_string(),
+                });
+                continue;
+            }
+        };
+
+        if !ids.is_empty() {
+            posts
+                .replace_tags(actor, &record, &ids)
+                .await
+                .map_err(post_error)?;
+        }
+        report.created.push(record.slug);
+    }
+
+    Ok(report)
+}
+
+/// Split `text` into front matter and body and read the known keys.
+pub fn parse_markdown_post(text: &str) -> Result<MarkdownPost, String> {
+    let (front_matter, body) = split_front_matter(text)?;
+    let docs = YamlLoader::load_from_str(front_matter)
+        .map_err(|err| format!("invalid front matter: {err}"))?;
+    let doc = match docs.as_slice() {
+        [doc @ Yaml::Hash(_)] => doc,
+        _ => return Err("front matter must be a single mapping".to_string()),
+    };
+
+    let title = optional_scalar(doc, "title")?
+        .filter(|title| !title.trim().is_empty())
+        .ok_or_else(|| "front matter has no `title`".to_string())?;
+    let slug = optional_scalar(doc, "slug")?;
+    let tags = match &doc["tags"] {
+        Yaml::BadValue | Yaml::Null => Vec::new(),
+        Yaml::Array(items) => items
+            .iter()
+            .map(|item| scalar(item).ok_or_else(|| "`tags` must be a list of names".to_string()))
+            .collect::<Result<Vec<_>, _>>()?,
+        other => vec![scalar(other).ok_or_else(|| "`tags` must be a list of names".to_string())?],
+    };
+    let tags = tags
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    let date = optional_scalar(doc, "date")?
+        .map(|date| parse_date(&date))
+        .transpose()?;
+    let status = match optional_scalar(doc, "status")?.as_deref() {
+        None | Some("draft") => PostStatus::Draft,
+        Some("published") => PostStatus::Published,
+        Some("archived") => PostStatus::Archived,
+        Some(other) => {
+            return Err(format!(
+                "unknown status `{other}`; expected draft, published or archived"
+            ));
+        }
+    };
+
+    Ok(MarkdownPost {
+        title: title.trim().to_string(),
+        slug,
+        tags,
+        date,
+        status,
+        body: body.trim_start_matches(['\r', '\n']).to_string(),
+    })
+}
+
+fn split_front_matter(text: &str) -> Result<(&str, &str), String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let rest = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+        .ok_or_else(|| "file does not start with `---` front matter".to_string())?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Ok((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    Err("front matter is not closed by `---`".to_string())
+}
+
+fn scalar(value: &Yaml) -> Option<String> {
+    match value {
+        Yaml::String(text) | Yaml::Real(text) => Some(text.clone()),
+        Yaml::Integer(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn optional_scalar(doc: &Yaml, key: &str) -> Result<Option<String>, String> {
+    match &doc[key] {
+        Yaml::BadValue | Yaml::Null => Ok(None),
+        value => scalar(value)
+            .map(Some)
+            .ok_or_else(|| format!("`{key}` must be a single value")),
+    }
+}
+
+fn parse_date(raw: &str) -> Result<OffsetDateTime, String> {
+    let raw = raw.trim();
+    if let Ok(at) = OffsetDateTime::parse(raw, &Rfc3339) {
+        return Ok(at);
+    }
+    Date::parse(raw, format_description!("[year]-[month]-[day]"))
+        .map(|date| date.midnight().assume_utc())
+        .map_err(|_| format!("`date` must be RFC 3339 or YYYY-MM-DD, got `{raw}`"))
+}
+
+fn create_command(post: MarkdownPost) -> CreatePostCommand {
+    CreatePostCommand {
+        slug: post.slug,
+        title: post.title,
+        excerpt: String::new(),
+        body_markdown: post.body,
+        summary_markdown: None,
+        status: post.status,
+        pinned: false,
+        scheduled_at: None,
+        published_at: post.date,
+        archived_at: None,
+    }
+}
+
+/// Markdown files directly inside `dir`, sorted by file name.
+async fn markdown_files(dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let mut entries = tokio::fs::read_dir(dir).await.map_err(|err| {
+        AppError::validation(format!("cannot read directory `{}`: {err}", dir.display()))
+    })?;
+    let mut files = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|err| AppError::from(InfraError::Io(err)))?
+    {
+        let path = entry.path();
+        let is_markdown = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown")
+            });
+        if is_markdown
+            && tokio::fs::metadata(&path)
+                .await
+                .is_ok_and(|metadata| metadata.is_file())
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Existing tags by lower-cased name and by slug, grown as tags are created.
+struct TagLookup {
+    by_name: HashMap<String, Uuid>,
+    by_slug: HashMap<String, Uuid>,
+}
+
+impl TagLookup {
+    async fn load(tags: &AdminTagService) -> Result<Self, AppError> {
+        let records = tags.list_all().await.map_err(tag_error)?;
+        let mut lookup = Self {
+            by_name: HashMap::new(),
+            by_slug: HashMap::new(),
+        };
+        for tag in records {
+            lookup.by_name.insert(tag.name.to_lowercase(), tag.id);
+            lookup.by_slug.insert(tag.slug, tag.id);
+        }
+        Ok(lookup)
+    }
+
+    /// Reject the file if a tag it would create cannot be given a slug.
+    fn check(&self, names: &[String]) -> Result<(), String> {
+        for name in names {
+            let key = name.to_lowercase();
+            if self.by_name.contains_key(&key) || self.by_slug.contains_key(&key) {
+                continue;
+            }
+            derive_slug(name).map_err(|err| format!("tag `{name}`: {err}"))?;
+        }
+        Ok(())
+    }
+
+    async fn resolve(
+        &mut self,
+        tags: &AdminTagService,
+        actor: &str,
+        name: &str,
+        report: &mut MarkdownImportReport,
+    ) -> Result<Uuid, AdminTagError> {
+        let key = name.to_lowercase();
+        if let Some(id) = self.by_name.get(&key).or_else(|| self.by_slug.get(&key)) {
+            return Ok(*id);
+        }
+
+        let tag = tags
+            .create_tag(
+                actor,
+                CreateTagCommand {
+                    name: name.to_string(),
+                    description: None,
+                    pinned: false,
+                },
+            )
+            .await?;
+        self.by_name.insert(key, tag.id);
+        self.by_slug.insert(tag.slug, tag.id);
+        report.tags_created.push(tag.name);
+        Ok(tag.id)
+    }
+}
+
+fn repo_error(err: RepoError) -> AppError {
+    AppError::from(InfraError::database(err.to_string()))
+}
+
+fn post_error(err: AdminPostError) -> AppError {
+    match err {
+        AdminPostError::Repo(err) => repo_error(err),
+        other => AppError::unexpected(other.to_string()),
+    }
+}
+
+fn tag_error(err: AdminTagError) -> AppError {
+    match err {
+        AdminTagError::Repo(err) => repo_error(err),
+        other => AppError::unexpected(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn front_matter_fields_map_onto_the_post() {
+        let post = parse_markdown_post(
+            "---\ntitle: Hello, world\nslug: hello\ntags: [Rust, \" Web \"]\ndate: 2024-03-01\nstatus: published\n---\n\n# Hi\n",
+        )
+        .expect("valid file");
+
+        assert_eq!(
+            post,
+            MarkdownPost {
+                title: "Hello, world".to_string(),
+                slug: Some("hello".to_string()),
+                tags: vec!["Rust".to_string(), "Web".to_string()],
+                date: Some(datetime!(2024-03-01 00:00 UTC)),
+                status: PostStatus::Published,
+                body: "# Hi\n".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn optional_fields_fall_back_to_defaults() {
+        let post = parse_markdown_post(
+            "---\r\ntitle: Draft\r\ntags: solo\r\ndate: 2024-03-01T08:30:00+02:00\r\n---\r\nBody",
+        )
+        .expect("valid file");
+
+        assert_eq!(post.slug, None);
+        assert_eq!(post.tags, vec!["solo".to_string()]);
+        assert_eq!(post.date, Some(datetime!(2024-03-01 06:30 UTC)));
+        assert_eq!(post.status, PostStatus::Draft);
+        assert_eq!(post.body, "Body");
+    }
+
+    #[test]
+    fn malformed_front_matter_is_rejected_with_a_reason() {
+        for (text, reason) in [
+            ("# No front matter\n", "does not start"),
+            ("---\ntitle: Open\n", "not closed"),
+            ("---\ntitle: [unclosed\n---\nBody\n", "invalid front matter"),
+            ("---\n- a list\n---\nBody\n", "single mapping"),
+            ("---\nslug: untitled\n---\nBody\n", "no `title`"),
+            ("---\ntitle: T\nstatus: live\n---\nBody\n", "unknown status"),
+            ("---\ntitle: T\ndate: yesterday\n---\nBody\n", "`date`"),
+            ("---\ntitle: T\ntags: [{a: b}]\n---\nBody\n", "`tags`"),
+        ] {
+            let err = parse_markdown_post(text).expect_err(text);
+            assert!(err.contains(reason), "{text:?}: {err}");
+        }
+    }
+}
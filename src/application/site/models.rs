@@ -5,10 +5,16 @@ use uuid::Uuid;
 use crate::domain::{
     api_keys::{ApiKeyStatus, ApiScope},
     types::{NavigationDestinationType, PageStatus, PostStatus},
+    uploads::UploadMetadata,
 };
+use crate::presentation::page_templates::DEFAULT_PAGE_TEMPLATE;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub(super) struct SiteArchive {
+    /// Directory holding the upload files, relative to the archive file.
+    /// Absent when the export did not include uploads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) uploads_dir: Option<String>,
     pub(super) migrations: MigrationSnapshot,
     pub(super) site_settings: SiteSettingsSnapshot,
     pub(super) posts: Vec<PostSnapshot>,
@@ -19,6 +25,8 @@ pub(super) struct SiteArchive {
     pub(super) navigation_items: Vec<NavigationSnapshot>,
     #[serde(default)]
     pub(super) api_keys: Vec<ApiKeySnapshot>,
+    #[serde(default)]
+    pub(super) uploads: Vec<UploadSnapshot>,
 }
 
 impl SiteArchive {
@@ -34,6 +42,8 @@ impl SiteArchive {
         self.navigation_items
             .sort_by(|a, b| a.sort_order.cmp(&b.sort_order).then(a.label.cmp(&b.label)));
         self.api_keys.sort_by(|a, b| a.prefix.cmp(&b.prefix));
+        self.uploads
+            .sort_by(|a, b| a.stored_path.cmp(&b.stored_path));
         self.migrations.entries.sort_by_key(|entry| entry.version);
     }
 }
@@ -93,6 +103,13 @@ pub(super) struct PageSnapshot {
     pub(super) scheduled_at: Option<OffsetDateTime>,
     pub(super) published_at: Option<OffsetDateTime>,
     pub(super) archived_at: Option<OffsetDateTime>,
+    /// Archives written before page templates existed render with the default.
+    #[serde(default = "default_page_template")]
+    pub(super) template: String,
+}
+
+fn default_page_template() -> String {
+    DEFAULT_PAGE_TEMPLATE.to_string()
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -142,3 +159,16 @@ pub(super) struct ApiKeySnapshot {
     pub(super) created_at: OffsetDateTime,
     pub(super) updated_at: OffsetDateTime,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct UploadSnapshot {
+    pub(super) id: Uuid,
+    pub(super) filename: String,
+    pub(super) content_type: String,
+    pub(super) size_bytes: i64,
+    pub(super) checksum: String,
+    pub(super) stored_path: String,
+    #[serde(default)]
+    pub(super) metadata: UploadMetadata,
+    pub(super) created_at: OffsetDateTime,
+}
@@ -0,0 +1,172 @@
+//! Upload rows and files carried alongside a site archive.
+//!
+//! Rows travel inside the TOML archive; files live in a sibling directory
+//! laid out like the upload storage itself, so each file is found at its
+//! `stored_path`. Files are streamed between directories and verified
+//! against the recorded checksum in both directions.
+
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value as JsonValue;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    application::error::AppError,
+    infra::{
+        error::InfraError,
+        uploads::{CopyOutcome, UploadStorage, UploadStorageError},
+    },
+};
+
+use super::{ImportReport, UploadFailure, map_sqlx_error, models::UploadSnapshot};
+
+struct UploadRow {
+    id: Uuid,
+    filename: String,
+    content_type: String,
+    size_bytes: i64,
+    checksum: String,
+    stored_path: String,
+    metadata: JsonValue,
+    created_at: OffsetDateTime,
+}
+
+/// Directory name, next to the archive file, that receives upload files.
+pub(super) fn uploads_dir_name(archive_path: &Path) -> String {
+    let stem = archive_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or("site");
+    format!("{stem}-uploads")
+}
+
+pub(super) async fn fetch_uploads(pool: &PgPool) -> Result<Vec<UploadSnapshot>, AppError> {
+    let rows = sqlx::query_as!(
+        UploadRow,
+        r#"
+        SELECT id, filename, content_type, size_bytes, checksum, stored_path, metadata, created_at
+        FROM uploads
+        ORDER BY stored_path
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(map_sqlx_error)?;
+
+    rows.into_iter()
+        .map(|row| {
+            let metadata = serde_json::from_value(row.metadata).map_err(|err| {
+                AppError::unexpected(format!(
+                    "invalid metadata stored for upload {}: {err}",
+                    row.id
+                ))
+            })?;
+            Ok(UploadSnapshot {
+                id: row.id,
+                filename: row.filename,
+                content_type: row.content_type,
+                size_bytes: row.size_bytes,
+                checksum: row.checksum,
+                stored_path: row.stored_path,
+                metadata,
+                created_at: row.created_at,
+            })
+        })
+        .collect()
+}
+
+/// Copy every upload file from `storage` into `target_dir`. A file that is
+/// missing or corrupt in storage is logged and left out; its row stays in the
+/// archive and is reported when the archive is imported.
+pub(super) async fn export_files(
+    storage: &UploadStorage,
+    uploads: &[UploadSnapshot],
+    target_dir: &Path,
+) -> Result<(), AppError> {
+    let target = UploadStorage::new(target_dir.to_path_buf())
+        .map_err(|err| AppError::from(InfraError::Io(err)))?;
+
+    for upload in uploads {
+        if let Err(err) = target
+            .copy_from(storage, &upload.stored_path, &upload.checksum)
+            .await
+        {
+            warn!(
+                target = "application::site::export_uploads",
+                stored_path = %upload.stored_path,
+                error = %err,
+                "upload file left out of export"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Restore upload files from `source_dir` into `storage`, returning the ids
+/// whose files are in place. Problems are recorded on `report` per upload and
+/// never abort the import.
+pub(super) async fn import_files(
+    storage: &UploadStorage,
+    uploads: &[UploadSnapshot],
+    source_dir: Option<PathBuf>,
+    report: &mut ImportReport,
+) -> HashSet<Uuid> {
+    let mut restored = HashSet::new();
+
+    let source = match source_dir {
+        Some(dir) if dir.is_dir() => match UploadStorage::new(dir) {
+            Ok(source) => Some(source),
+            Err(err) => {
+                warn!(
+                    target = "application::site::import_uploads",
+                    error = %err,
+                    "archive uploads directory is unreadable"
+                );
+                None
+            }
+        },
+        _ => None,
+    };
+
+    for upload in uploads {
+        let Some(source) = source.as_ref() else {
+            report.upload_failures.push(UploadFailure {
+                stored_path: upload.stored_path.clone(),
+                reason: "archive has no uploads directory".to_string(),
+            });
+            continue;
+        };
+
+        match storage
+            .copy_from(source, &upload.stored_path, &upload.checksum)
+            .await
+        {
+            Ok(CopyOutcome::Copied) => {
+                report.uploads_copied += 1;
+                restored.insert(upload.id);
+            }
+            Ok(CopyOutcome::AlreadyPresent) => {
+                report.uploads_present += 1;
+                restored.insert(upload.id);
+            }
+            Err(err) => report.upload_failures.push(UploadFailure {
+                stored_path: upload.stored_path.clone(),
+                reason: match err {
+                    UploadStorageError::Io(io) if io.kind() == ErrorKind::NotFound => {
+                        "file missing from archive".to_string()
+                    }
+                    other => other.to_string(),
+                },
+            }),
+        }
+    }
+
+    restored
+}
@@ -8,12 +8,13 @@ use std::sync::Arc;
 use thiserror::Error;
 use time::format_description::well_known::Rfc3339;
 
-use crate::application::pagination::{PageCursor, PageRequest, PostCursor};
+use crate::application::pagination::{CursorKeys, PageCursor, PageRequest, PostCursor};
 use crate::application::repos::{
     PageQueryFilter, PagesRepo, PostListScope, PostQueryFilter, PostsRepo, RepoError, SettingsRepo,
 };
 use crate::cache::L0Store;
-use crate::domain::types::{PageStatus, PostStatus};
+use crate::domain::routes::{BasePath, join_url, post_path};
+use crate::domain::types::{HomepageMode, PageStatus, PostStatus};
 
 /// Service for generating sitemap.xml and robots.txt.
 #[derive(Clone)]
@@ -22,6 +23,8 @@ pub struct SitemapService {
     pages: Arc<dyn PagesRepo>,
     settings: Arc<dyn SettingsRepo>,
     cache: Option<Arc<L0Store>>,
+    base_path: BasePath,
+    cursor_keys: CursorKeys,
 }
 
 #[derive(Debug, Error)]
@@ -34,6 +37,9 @@ pub enum SitemapError {
     Pages(String),
     #[error("failed to decode cursor: {0}")]
     Cursor(String),
+    /// The sitemap lists absolute URLs, so it needs `public_site_url`.
+    #[error("public_site_url is not set to an absolute http(s) URL")]
+    NoSiteUrl,
 }
 
 impl From<RepoError> for SitemapError {
@@ -54,9 +60,23 @@ impl SitemapService {
             pages,
             settings,
             cache,
+            base_path: BasePath::default(),
+            cursor_keys: CursorKeys::ephemeral(),
         }
     }
 
+    /// Build absolute URLs under `base_path`.
+    pub fn with_base_path(mut self, base_path: BasePath) -> Self {
+        self.base_path = base_path;
+        self
+    }
+
+    /// Read back cursors with the keys the repositories sign them with.
+    pub fn with_cursor_keys(mut self, cursor_keys: CursorKeys) -> Self {
+        self.cursor_keys = cursor_keys;
+        self
+    }
+
     /// Generate sitemap.xml content.
     ///
     /// Records cache dependencies: Sitemap, SiteSettings, PostsIndex.
@@ -85,11 +105,16 @@ impl SitemapService {
                 .map_err(|e| SitemapError::Settings(e.to_string()))?
         };
 
-        let base = normalize_public_site_url(&settings.public_site_url);
+        let base = self
+            .base_path
+            .site_url(&settings.public_site_url)
+            .ok_or(SitemapError::NoSiteUrl)?;
         let mut entries = Vec::new();
-
-        // Homepage entry
-        entries.push(sitemap_entry(&base, "/", Some(settings.updated_at)));
+        let homepage_page_id = match settings.homepage_mode {
+            HomepageMode::Page => settings.homepage_page_id,
+            HomepageMode::Feed => None,
+        };
+        let mut homepage_lastmod = None;
 
         // Posts
         let mut post_cursor: Option<PostCursor> = None;
@@ -108,16 +133,13 @@ impl SitemapService {
                     continue;
                 }
                 let lastmod = post.published_at.unwrap_or(post.updated_at);
-                entries.push(sitemap_entry(
-                    &base,
-                    &format!("/posts/{}", post.slug),
-                    Some(lastmod),
-                ));
+                entries.push(sitemap_entry(&base, &post_path(&post.slug), Some(lastmod)));
             }
 
             post_cursor = match page.next_cursor {
                 Some(next) => Some(
-                    PostCursor::decode(&next).map_err(|e| SitemapError::Cursor(e.to_string()))?,
+                    PostCursor::decode(&self.cursor_keys, &next)
+                        .map_err(|e| SitemapError::Cursor(e.to_string()))?,
                 ),
                 None => break,
             };
@@ -142,6 +164,11 @@ impl SitemapService {
                     continue;
                 }
                 let lastmod = record.published_at.unwrap_or(record.updated_at);
+                // Served at `/` instead of its own path.
+                if homepage_page_id == Some(record.id) {
+                    homepage_lastmod = Some(lastmod);
+                    continue;
+                }
                 entries.push(sitemap_entry(
                     &base,
                     &format!("/{}", record.slug),
@@ -151,7 +178,8 @@ impl SitemapService {
 
             page_cursor = match page.next_cursor {
                 Some(next) => Some(
-                    PageCursor::decode(&next).map_err(|e| SitemapError::Cursor(e.to_string()))?,
+                    PageCursor::decode(&self.cursor_keys, &next)
+                        .map_err(|e| SitemapError::Cursor(e.to_string()))?,
                 ),
                 None => break,
             };
@@ -160,6 +188,14 @@ impl SitemapService {
         let mut xml = String::from(
             "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
         );
+        // Homepage entry; the feed gets its own while `/` serves a page.
+        match homepage_lastmod {
+            Some(lastmod) => {
+                xml.push_str(&sitemap_entry(&base, "/", Some(lastmod)));
+                xml.push_str(&sitemap_entry(&base, "/posts", Some(settings.updated_at)));
+            }
+            None => xml.push_str(&sitemap_entry(&base, "/", Some(settings.updated_at))),
+        }
         for entry in entries {
             xml.push_str(&entry);
         }
@@ -193,20 +229,18 @@ impl SitemapService {
                 .map_err(|e| SitemapError::Settings(e.to_string()))?
         };
 
-        let base = normalize_public_site_url(&settings.public_site_url);
-        let sitemap_url = format!("{base}sitemap.xml");
-        let body = format!("User-agent: *\nAllow: /\nSitemap: {sitemap_url}\n");
+        let allow = self.base_path.join("/");
+        let mut body = format!("User-agent: *\nAllow: {allow}\n");
+        // Without an absolute site URL there is no sitemap to point at.
+        if let Some(base) = self.base_path.site_url(&settings.public_site_url) {
+            body.push_str(&format!("Sitemap: {}\n", join_url(&base, "sitemap.xml")));
+        }
         Ok(body)
     }
 }
 
-fn normalize_public_site_url(url: &str) -> String {
-    let trimmed = url.trim_end_matches('/');
-    format!("{trimmed}/")
-}
-
 fn sitemap_entry(base: &str, path: &str, lastmod: Option<time::OffsetDateTime>) -> String {
-    let loc = canonical_url(base, path);
+    let loc = join_url(base, path);
     let lastmod_str = lastmod
         .and_then(|dt| dt.format(&Rfc3339).ok())
         .unwrap_or_default();
@@ -216,12 +250,3 @@ fn sitemap_entry(base: &str, path: &str, lastmod: Option<time::OffsetDateTime>)
         format!("  <url><loc>{loc}</loc><lastmod>{lastmod_str}</lastmod></url>\n")
     }
 }
-
-fn canonical_url(base: &str, path: &str) -> String {
-    let base = base.trim_end_matches('/');
-    if path == "/" {
-        base.to_string()
-    } else {
-        format!("{base}{path}")
-    }
-}
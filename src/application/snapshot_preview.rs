@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use axum::http::StatusCode;
-use time::OffsetDateTime;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
 use crate::application::admin::snapshot_types::{
@@ -9,24 +12,106 @@ use crate::application::admin::snapshot_types::{
     PostSnapshotSource,
 };
 use crate::application::error::HttpError;
+use crate::application::format;
 use crate::application::repos::{RepoError, SettingsRepo, SnapshotRecord, SnapshotsRepo, TagsRepo};
 use crate::domain::entities::PostSectionRecord;
-use crate::domain::posts;
 use crate::domain::sections::{PostSectionNode, SectionTreeError, build_section_tree};
 use crate::domain::snapshots::Snapshotable;
 use crate::domain::types::SnapshotEntityType;
 use crate::presentation::views::{
     PageView, PostDetailContext, PostSectionEvent, PostTocEvent, TagBadge, build_tag_badges,
 };
-use crate::util::timezone;
+use crate::util::secret::random_secret;
 
 const SOURCE: &str = "application::snapshot_preview";
+const LINK_CONTEXT: &[u8] = b"snapshot-preview:";
+const DEFAULT_LINK_TTL: Duration = Duration::days(7);
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// A snapshot rendered the way the public site renders its post or page.
+pub enum SnapshotView {
+    Post(PostDetailContext),
+    Page(PageView),
+}
+
+/// HMAC key and lifetime of the tokens in shared snapshot preview links.
+///
+/// A token is `{expiry}.{signature}` and is bound to one snapshot id.
+#[derive(Clone)]
+pub struct PreviewLinkKeys {
+    secret: Arc<[u8]>,
+    ttl: Duration,
+}
+
+impl PreviewLinkKeys {
+    pub fn new(secret: impl Into<Vec<u8>>, ttl: Duration) -> Self {
+        Self {
+            secret: Arc::from(secret.into()),
+            ttl,
+        }
+    }
+
+    /// Keys backed by a random per-process secret; links stop working on restart.
+    pub fn ephemeral(ttl: Duration) -> Self {
+        Self::new(random_secret(), ttl)
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Token granting access to snapshot `id` until `now` plus the TTL.
+    ///
+    /// Should that instant be out of range, the token is issued already expired.
+    pub fn issue(&self, id: Uuid, now: OffsetDateTime) -> String {
+        let expires_at = now.checked_add(self.ttl).unwrap_or(now).unix_timestamp();
+        let signature = self.mac(id, expires_at).finalize().into_bytes();
+        format!("{expires_at}.{}", URL_SAFE_NO_PAD.encode(signature))
+    }
+
+    /// Whether `token` was issued for snapshot `id` and has not expired.
+    pub fn verify(&self, id: Uuid, token: &str, now: OffsetDateTime) -> bool {
+        let Some((expires_at, signature)) = token.trim().split_once('.') else {
+            return false;
+        };
+        let (Ok(expires_at), Ok(signature)) =
+            (expires_at.parse::<i64>(), URL_SAFE_NO_PAD.decode(signature))
+        else {
+            return false;
+        };
+        self.mac(id, expires_at).verify_slice(&signature).is_ok()
+            && expires_at > now.unix_timestamp()
+    }
+
+    fn mac(&self, id: Uuid, expires_at: i64) -> HmacSha256 {
+        let mut mac = <HmacSha256 as KeyInit>::new_from_slice(&self.secret)
+            .expect("hmac accepts keys of any length");
+        mac.update(LINK_CONTEXT);
+        mac.update(id.as_bytes());
+        mac.update(&expires_at.to_be_bytes());
+        mac
+    }
+}
+
+impl From<&crate::config::AdminAuthSettings> for PreviewLinkKeys {
+    /// Links reuse the admin session secret under their own HMAC context.
+    fn from(settings: &crate::config::AdminAuthSettings) -> Self {
+        let ttl = Duration::try_from(settings.preview_link_ttl)
+            .expect("admin_auth.preview_link_ttl_seconds is bounded at load");
+        match settings.session_secret.as_ref() {
+            Some(secret) => Self::new(secret.as_bytes().to_vec(), ttl),
+            None => Self::ephemeral(ttl),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct SnapshotPreviewService {
     snapshots: Arc<dyn SnapshotsRepo>,
     tags: Arc<dyn TagsRepo>,
     settings: Arc<dyn SettingsRepo>,
+    links: PreviewLinkKeys,
 }
 
 impl SnapshotPreviewService {
@@ -39,19 +124,83 @@ impl SnapshotPreviewService {
             snapshots,
             tags,
             settings,
+            links: PreviewLinkKeys::ephemeral(DEFAULT_LINK_TTL),
+        }
+    }
+
+    pub fn with_link_keys(mut self, links: PreviewLinkKeys) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Token for a shareable preview link to snapshot `id`.
+    pub fn link_token(&self, id: Uuid, now: OffsetDateTime) -> String {
+        self.links.issue(id, now)
+    }
+
+    /// The snapshot `token` grants access to, whether a post or a page.
+    ///
+    /// An invalid or expired token reads as a missing snapshot, so callers
+    /// cannot tell which snapshot ids exist.
+    pub async fn shared_snapshot_view(
+        &self,
+        id: Uuid,
+        token: &str,
+        now: OffsetDateTime,
+    ) -> Result<Option<SnapshotView>, HttpError> {
+        if !self.links.verify(id, token, now) {
+            return Ok(None);
         }
+        let Some(snapshot) = self.find_snapshot(id).await? else {
+            return Ok(None);
+        };
+        match snapshot.entity_type {
+            SnapshotEntityType::Post => self.post_view(snapshot).await.map(SnapshotView::Post),
+            SnapshotEntityType::Page => self.page_view(snapshot).map(SnapshotView::Page),
+        }
+        .map(Some)
     }
 
+    /// The post snapshot `token` grants access to; see [`Self::shared_snapshot_view`].
     pub async fn post_snapshot_view(
         &self,
         id: Uuid,
+        token: &str,
+        now: OffsetDateTime,
     ) -> Result<Option<PostDetailContext>, HttpError> {
-        let snapshot = match self.snapshots.find_snapshot(id).await {
-            Ok(Some(record)) => record,
-            Ok(None) => return Ok(None),
-            Err(err) => return Err(repo_error("find_snapshot", err)),
+        if !self.links.verify(id, token, now) {
+            return Ok(None);
+        }
+        let Some(snapshot) = self.find_snapshot(id).await? else {
+            return Ok(None);
         };
+        self.post_view(snapshot).await.map(Some)
+    }
 
+    /// The page snapshot `token` grants access to; see [`Self::shared_snapshot_view`].
+    pub async fn page_snapshot_view(
+        &self,
+        id: Uuid,
+        token: &str,
+        now: OffsetDateTime,
+    ) -> Result<Option<PageView>, HttpError> {
+        if !self.links.verify(id, token, now) {
+            return Ok(None);
+        }
+        let Some(snapshot) = self.find_snapshot(id).await? else {
+            return Ok(None);
+        };
+        self.page_view(snapshot).map(Some)
+    }
+
+    async fn find_snapshot(&self, id: Uuid) -> Result<Option<SnapshotRecord>, HttpError> {
+        self.snapshots
+            .find_snapshot(id)
+            .await
+            .map_err(|err| repo_error("find_snapshot", err))
+    }
+
+    async fn post_view(&self, snapshot: SnapshotRecord) -> Result<PostDetailContext, HttpError> {
         if snapshot.entity_type != SnapshotEntityType::Post {
             return Err(HttpError::new(
                 SOURCE,
@@ -100,14 +249,14 @@ impl SnapshotPreviewService {
         };
 
         let published_at = payload.published_at.unwrap_or(snapshot.created_at);
-        let localized = timezone::localized_datetime(published_at, settings.timezone);
-        let date = timezone::localized_date(published_at, settings.timezone);
+        let published =
+            format::local_timestamp(published_at, settings.timezone, &settings.date_format);
 
         let detail = PostDetailContext {
             slug: payload.slug,
             title: payload.title,
-            published: posts::format_human_date(date),
-            iso_date: localized.to_rfc3339(),
+            published: published.display,
+            iso_date: published.iso,
             tags: tag_badges,
             excerpt: payload.excerpt,
             summary_html: payload.summary_html,
@@ -117,18 +266,17 @@ impl SnapshotPreviewService {
             has_mermaid_diagrams,
             toc,
             is_pinned: payload.pinned,
+            comments: Vec::new(),
+            lang: None,
+            translations: Vec::new(),
+            // Snapshots are never indexed.
+            ld_json: None,
         };
 
-        Ok(Some(detail))
+        Ok(detail)
     }
 
-    pub async fn page_snapshot_view(&self, id: Uuid) -> Result<Option<PageView>, HttpError> {
-        let snapshot = match self.snapshots.find_snapshot(id).await {
-            Ok(Some(record)) => record,
-            Ok(None) => return Ok(None),
-            Err(err) => return Err(repo_error("find_snapshot", err)),
-        };
-
+    fn page_view(&self, snapshot: SnapshotRecord) -> Result<PageView, HttpError> {
         if snapshot.entity_type != SnapshotEntityType::Page {
             return Err(HttpError::new(
                 SOURCE,
@@ -151,13 +299,16 @@ impl SnapshotPreviewService {
         let (contains_code, contains_math, contains_mermaid) =
             render_feature_flags(&payload.rendered_html);
 
-        Ok(Some(PageView {
+        Ok(PageView {
             title: payload.title,
             content_html: payload.rendered_html,
             contains_code,
             contains_math,
             contains_mermaid,
-        }))
+            lang: None,
+            translations: Vec::new(),
+            template: payload.template,
+        })
     }
 
     async fn load_tag_badges(&self, tag_ids: &[Uuid]) -> Result<Vec<TagBadge>, HttpError> {
@@ -305,3 +456,48 @@ fn render_feature_flags(html: &str) -> (bool, bool, bool) {
     let contains_mermaid = html.contains("data-role=\"diagram-mermaid\"");
     (contains_code, contains_math, contains_mermaid)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_tokens_are_bound_to_their_snapshot_and_expire() {
+        let keys = PreviewLinkKeys::new(
+            b"0123456789abcdef0123456789abcdef".to_vec(),
+            Duration::hours(1),
+        );
+        let id = Uuid::new_v4();
+        let now = OffsetDateTime::now_utc();
+        let token = keys.issue(id, now);
+
+        assert!(keys.verify(id, &token, now));
+        assert!(keys.verify(id, &token, now + Duration::minutes(59)));
+        assert!(!keys.verify(id, &token, now + Duration::hours(1)));
+        assert!(!keys.verify(Uuid::new_v4(), &token, now));
+
+        let other_keys = PreviewLinkKeys::ephemeral(Duration::hours(1));
+        assert!(!other_keys.verify(id, &token, now));
+
+        let (expires_at, signature) = token.split_once('.').expect("token parts");
+        let extended = format!(
+            "{}.{signature}",
+            expires_at.parse::<i64>().expect("expiry") + 3600
+        );
+        assert!(!keys.verify(id, &extended, now + Duration::hours(1)));
+        for malformed in ["", ".", "abc", "123.", "x.y"] {
+            assert!(!keys.verify(id, malformed, now), "{malformed}");
+        }
+    }
+
+    #[test]
+    fn link_tokens_past_the_representable_range_are_already_expired() {
+        let keys =
+            PreviewLinkKeys::new(b"0123456789abcdef0123456789abcdef".to_vec(), Duration::MAX);
+        let id = Uuid::new_v4();
+        let now = OffsetDateTime::now_utc();
+        let token = keys.issue(id, now);
+
+        assert!(!keys.verify(id, &token, now));
+    }
+}
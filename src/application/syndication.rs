@@ -3,24 +3,32 @@
 //! This service encapsulates the business logic for generating syndication feeds,
 //! keeping the HTTP layer focused on request/response handling.
 
-use std::sync::Arc;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
+use lol_html::{RewriteStrSettings, element, rewrite_str};
+use percent_encoding::percent_decode_str;
 use thiserror::Error;
 use time::format_description::well_known::{Rfc2822, Rfc3339};
+use url::Url;
 
 use crate::application::pagination::PageRequest;
 use crate::application::repos::{
-    PostListScope, PostQueryFilter, PostsRepo, RepoError, SettingsRepo,
+    PostListScope, PostQueryFilter, PostsRepo, RepoError, SectionsRepo, SettingsRepo, UploadsRepo,
 };
 use crate::cache::{L0Store, hash_cursor_str, hash_post_list_key};
-use crate::domain::types::PostStatus;
+use crate::domain::entities::{PostRecord, SiteSettingsRecord};
+use crate::domain::routes::{BasePath, join_url, post_path};
+use crate::domain::types::{FeedContentMode, PostStatus};
 
 /// Service for generating RSS and Atom feeds.
 #[derive(Clone)]
 pub struct SyndicationService {
     posts: Arc<dyn PostsRepo>,
+    sections: Arc<dyn SectionsRepo>,
+    uploads: Arc<dyn UploadsRepo>,
     settings: Arc<dyn SettingsRepo>,
     cache: Option<Arc<L0Store>>,
+    base_path: BasePath,
 }
 
 #[derive(Debug, Error)]
@@ -29,6 +37,9 @@ pub enum SyndicationError {
     Settings(String),
     #[error("failed to list posts: {0}")]
     Posts(String),
+    /// Feeds carry absolute links, so they need `public_site_url`.
+    #[error("public_site_url is not set to an absolute http(s) URL")]
+    NoSiteUrl,
 }
 
 impl From<RepoError> for SyndicationError {
@@ -37,19 +48,47 @@ impl From<RepoError> for SyndicationError {
     }
 }
 
+/// What an item carries besides its title and link, per `feed_content_mode`.
+#[derive(Default)]
+struct ItemContent {
+    /// HTML shown instead of the excerpt.
+    summary_html: Option<String>,
+    /// The whole rendered post.
+    full_html: Option<String>,
+    enclosure: Option<Enclosure>,
+}
+
+/// First uploaded image of a post.
+struct Enclosure {
+    url: String,
+    content_type: String,
+    length: i64,
+}
+
 impl SyndicationService {
     pub fn new(
         posts: Arc<dyn PostsRepo>,
+        sections: Arc<dyn SectionsRepo>,
+        uploads: Arc<dyn UploadsRepo>,
         settings: Arc<dyn SettingsRepo>,
         cache: Option<Arc<L0Store>>,
     ) -> Self {
         Self {
             posts,
+            sections,
+            uploads,
             settings,
             cache,
+            base_path: BasePath::default(),
         }
     }
 
+    /// Build absolute URLs under `base_path`.
+    pub fn with_base_path(mut self, base_path: BasePath) -> Self {
+        self.base_path = base_path;
+        self
+    }
+
     /// Generate RSS 2.0 feed XML.
     ///
     /// Records cache dependencies: Feed, SiteSettings, PostsIndex.
@@ -59,79 +98,55 @@ impl SyndicationService {
         crate::cache::deps::record(crate::cache::EntityKey::SiteSettings);
         crate::cache::deps::record(crate::cache::EntityKey::PostsIndex);
 
-        let settings = if let Some(cache) = &self.cache {
-            if let Some(cached) = cache.get_site_settings() {
-                cached
-            } else {
-                let settings = self
-                    .settings
-                    .load_site_settings()
-                    .await
-                    .map_err(|e| SyndicationError::Settings(e.to_string()))?;
-                cache.set_site_settings(settings.clone());
-                settings
-            }
-        } else {
-            self.settings
-                .load_site_settings()
-                .await
-                .map_err(|e| SyndicationError::Settings(e.to_string()))?
-        };
-
-        let base = normalize_public_site_url(&settings.public_site_url);
-
-        let filter = PostQueryFilter::default();
-        let page_limit = 100u32;
-        let filter_hash = hash_post_list_key(&filter, page_limit);
-        let cursor_hash = hash_cursor_str(None);
-        let page = if let Some(cache) = &self.cache {
-            if let Some(cached) = cache.get_post_list(filter_hash, cursor_hash) {
-                cached
-            } else {
-                let page = self
-                    .posts
-                    .list_posts(
-                        PostListScope::Public,
-                        &filter,
-                        PageRequest::new(page_limit, None),
-                    )
-                    .await?;
-                cache.set_post_list(filter_hash, cursor_hash, page.clone());
-                page
-            }
-        } else {
-            self.posts
-                .list_posts(
-                    PostListScope::Public,
-                    &filter,
-                    PageRequest::new(page_limit, None),
-                )
-                .await?
-        };
+        let settings = self.load_settings().await?;
+        let base = self
+            .base_path
+            .site_url(&settings.public_site_url)
+            .ok_or(SyndicationError::NoSiteUrl)?;
+        let posts = self.list_posts(settings.feed_item_limit).await?;
 
         let mut items = String::new();
-        for post in page
-            .items
-            .into_iter()
-            .filter(|p| p.status == PostStatus::Published)
-        {
+        for post in posts {
             let published = post.published_at.unwrap_or(post.updated_at);
             let pub_date = published
                 .format(&Rfc2822)
                 .unwrap_or_else(|_| published.to_string());
-            let link = format!("{base}posts/{}", post.slug);
+            let link = join_url(&base, &post_path(&post.slug));
+            let content = self
+                .item_content(&post, settings.feed_content_mode, &base, &link)
+                .await?;
+
+            let description = content
+                .summary_html
+                .unwrap_or_else(|| xml_escape(&post.excerpt));
+            let mut extra = String::new();
+            if let Some(html) = &content.full_html {
+                extra.push_str(&format!(
+                    "      <content:encoded><![CDATA[{}]]></content:encoded>\n",
+                    cdata_escape(html)
+                ));
+            }
+            if let Some(enclosure) = &content.enclosure {
+                extra.push_str(&format!(
+                    "      <enclosure url=\"{}\" length=\"{}\" type=\"{}\"/>\n",
+                    xml_escape(&enclosure.url),
+                    enclosure.length,
+                    xml_escape(&enclosure.content_type),
+                ));
+            }
             items.push_str(&format!(
-                "    <item>\n      <title>{}</title>\n      <link>{}</link>\n      <guid>{}</guid>\n      <pubDate>{}</pubDate>\n      <description><![CDATA[{}]]></description>\n    </item>\n",
+                "    <item>\n      <title>{}</title>\n      <link>{}</link>\n      <guid>{}</guid>\n      <pubDate>{}</pubDate>\n      <description><![CDATA[{}]]></description>\n{}    </item>\n",
                 xml_escape(&post.title),
                 link,
                 link,
                 pub_date,
-                xml_escape(&post.excerpt),
+                cdata_escape(&description),
+                extra,
             ));
         }
 
         let channel = format!(
-            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\">\n  <channel>\n    <title>{}</title>\n    <link>{}</link>\n    <description>{}</description>\n{}  </channel>\n</rss>\n",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\">\n  <channel>\n    <title>{}</title>\n    <link>{}</link>\n    <description>{}</description>\n{}  </channel>\n</rss>\n",
             xml_escape(&settings.meta_title),
             base,
             xml_escape(&settings.meta_description),
@@ -150,29 +165,96 @@ impl SyndicationService {
         crate::cache::deps::record(crate::cache::EntityKey::SiteSettings);
         crate::cache::deps::record(crate::cache::EntityKey::PostsIndex);
 
-        let settings = if let Some(cache) = &self.cache {
-            if let Some(cached) = cache.get_site_settings() {
-                cached
-            } else {
-                let settings = self
-                    .settings
-                    .load_site_settings()
-                    .await
-                    .map_err(|e| SyndicationError::Settings(e.to_string()))?;
-                cache.set_site_settings(settings.clone());
-                settings
+        let settings = self.load_settings().await?;
+        let base = self
+            .base_path
+            .site_url(&settings.public_site_url)
+            .ok_or(SyndicationError::NoSiteUrl)?;
+        let posts = self.list_posts(settings.feed_item_limit).await?;
+
+        let updated = settings
+            .updated_at
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| settings.updated_at.to_string());
+
+        let mut entries = String::new();
+        for post in posts {
+            let published = post.published_at.unwrap_or(post.updated_at);
+            let published_str = published
+                .format(&Rfc3339)
+                .unwrap_or_else(|_| published.to_string());
+            let link = join_url(&base, &post_path(&post.slug));
+            let content = self
+                .item_content(&post, settings.feed_content_mode, &base, &link)
+                .await?;
+
+            let summary = match &content.summary_html {
+                Some(html) => format!("<summary type=\"html\">{}</summary>", xml_escape(html)),
+                None => format!(
+                    "<summary><![CDATA[{}]]></summary>",
+                    xml_escape(&post.excerpt)
+                ),
+            };
+            let mut extra = String::new();
+            if let Some(html) = &content.full_html {
+                extra.push_str(&format!(
+                    "    <content type=\"html\">{}</content>\n",
+                    xml_escape(html)
+                ));
             }
-        } else {
-            self.settings
-                .load_site_settings()
-                .await
-                .map_err(|e| SyndicationError::Settings(e.to_string()))?
-        };
+            if let Some(enclosure) = &content.enclosure {
+                extra.push_str(&format!(
+                    "    <link rel=\"enclosure\" href=\"{}\" type=\"{}\" length=\"{}\"/>\n",
+                    xml_escape(&enclosure.url),
+                    xml_escape(&enclosure.content_type),
+                    enclosure.length,
+                ));
+            }
+            entries.push_str(&format!(
+                "  <entry>\n    <title>{}</title>\n    <link href=\"{}\"/>\n    <id>{}</id>\n    <updated>{}</updated>\n    {}\n{}  </entry>\n",
+                xml_escape(&post.title),
+                link,
+                link,
+                published_str,
+                summary,
+                extra,
+            ));
+        }
 
-        let base = normalize_public_site_url(&settings.public_site_url);
+        let feed = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n  <title>{}</title>\n  <id>{}</id>\n  <updated>{}</updated>\n  <link href=\"{}\" rel=\"self\"/>\n{}\n</feed>\n",
+            xml_escape(&settings.meta_title),
+            base,
+            updated,
+            join_url(&base, "atom.xml"),
+            entries
+        );
 
+        Ok(feed)
+    }
+
+    async fn load_settings(&self) -> Result<SiteSettingsRecord, SyndicationError> {
+        if let Some(cache) = &self.cache
+            && let Some(cached) = cache.get_site_settings()
+        {
+            return Ok(cached);
+        }
+
+        let settings = self
+            .settings
+            .load_site_settings()
+            .await
+            .map_err(|e| SyndicationError::Settings(e.to_string()))?;
+        if let Some(cache) = &self.cache {
+            cache.set_site_settings(settings.clone());
+        }
+        Ok(settings)
+    }
+
+    /// The newest `limit` published posts.
+    async fn list_posts(&self, limit: i32) -> Result<Vec<PostRecord>, SyndicationError> {
         let filter = PostQueryFilter::default();
-        let page_limit = 100u32;
+        let page_limit = limit.max(1) as u32;
         let filter_hash = hash_post_list_key(&filter, page_limit);
         let cursor_hash = hash_cursor_str(None);
         let page = if let Some(cache) = &self.cache {
@@ -200,48 +282,185 @@ impl SyndicationService {
                 .await?
         };
 
-        let updated = settings
-            .updated_at
-            .format(&Rfc3339)
-            .unwrap_or_else(|_| settings.updated_at.to_string());
-
-        let mut entries = String::new();
-        for post in page
+        Ok(page
             .items
             .into_iter()
             .filter(|p| p.status == PostStatus::Published)
-        {
-            let published = post.published_at.unwrap_or(post.updated_at);
-            let published_str = published
-                .format(&Rfc3339)
-                .unwrap_or_else(|_| published.to_string());
-            let link = format!("{base}posts/{}", post.slug);
-            entries.push_str(&format!(
-                "  <entry>\n    <title>{}</title>\n    <link href=\"{}\"/>\n    <id>{}</id>\n    <updated>{}</updated>\n    <summary><![CDATA[{}]]></summary>\n  </entry>\n",
-                xml_escape(&post.title),
-                link,
-                link,
-                published_str,
-                xml_escape(&post.excerpt),
-            ));
+            .collect())
+    }
+
+    /// What `post`'s item carries under `mode`. Feed HTML has its links made
+    /// absolute against the item URL and is sanitized once more, since feed
+    /// readers apply their own (often laxer) policies.
+    async fn item_content(
+        &self,
+        post: &PostRecord,
+        mode: FeedContentMode,
+        base: &str,
+        link: &str,
+    ) -> Result<ItemContent, SyndicationError> {
+        // Stored values predating settings validation may not parse; the item
+        // keeps its HTML with links as rendered rather than failing the feed.
+        let item_url = Url::parse(link).ok();
+        let feed_html = |html: &str| {
+            let html = match &item_url {
+                Some(url) => absolutize_urls(html, url),
+                None => html.to_string(),
+            };
+            ammonia::clean(&html)
+        };
+
+        match mode {
+            FeedContentMode::Excerpt => Ok(ItemContent::default()),
+            FeedContentMode::Summary => Ok(ItemContent {
+                summary_html: post
+                    .summary_html
+                    .as_deref()
+                    .filter(|html| !html.trim().is_empty())
+                    .map(feed_html),
+                ..ItemContent::default()
+            }),
+            FeedContentMode::Full => {
+                let mut sections = self.sections.list_sections(post.id).await?;
+                sections.sort_by_key(|section| section.position);
+                let html: String = sections
+                    .iter()
+                    .flat_map(|section| [section.heading_html.as_str(), section.body_html.as_str()])
+                    .collect();
+
+                let enclosure = match (&item_url, Url::parse(base)) {
+                    (Some(item_url), Ok(site)) => {
+                        self.first_image_enclosure(&html, item_url, &site).await?
+                    }
+                    _ => None,
+                };
+
+                Ok(ItemContent {
+                    summary_html: None,
+                    full_html: Some(feed_html(&html)),
+                    enclosure,
+                })
+            }
         }
+    }
 
-        let feed = format!(
-            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n  <title>{}</title>\n  <id>{}</id>\n  <updated>{}</updated>\n  <link href=\"{}atom.xml\" rel=\"self\"/>\n{}\n</feed>\n",
-            xml_escape(&settings.meta_title),
-            base,
-            updated,
-            base,
-            entries
-        );
+    /// The first `<img>` in `html` served from the site's uploads.
+    async fn first_image_enclosure(
+        &self,
+        html: &str,
+        item_url: &Url,
+        site: &Url,
+    ) -> Result<Option<Enclosure>, SyndicationError> {
+        for src in image_sources(html) {
+            let Ok(image) = item_url.join(&src) else {
+                continue;
+            };
+            let Some(stored_path) = upload_stored_path(&image, site) else {
+                continue;
+            };
+            let Some(upload) = self.uploads.find_upload_by_path(&stored_path).await? else {
+                continue;
+            };
+            if !upload.content_type.starts_with("image/") {
+                continue;
+            }
+            return Ok(Some(Enclosure {
+                url: image.to_string(),
+                content_type: upload.content_type,
+                length: upload.size_bytes,
+            }));
+        }
+        Ok(None)
+    }
+}
 
-        Ok(feed)
+/// Rewrite relative `href` and `src` attributes in `html` to absolute URLs
+/// resolved against `base`, the URL of the page the HTML belongs to.
+///
+/// Root-relative paths, `#anchors` and protocol-relative `//host` URLs are
+/// resolved the way a browser viewing `base` would; values that already carry
+/// a scheme (`https:`, `mailto:`, `data:`) are left alone.
+pub fn absolutize_urls(html: &str, base: &Url) -> String {
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![
+                element!("[href]", |el| {
+                    absolutize_attribute(el, "href", base)?;
+                    Ok(())
+                }),
+                element!("[src]", |el| {
+                    absolutize_attribute(el, "src", base)?;
+                    Ok(())
+                }),
+            ],
+            ..RewriteStrSettings::default()
+        },
+    )
+    .unwrap_or_else(|_| html.to_string())
+}
+
+fn absolutize_attribute(
+    el: &mut lol_html::html_content::Element<'_, '_>,
+    attribute: &str,
+    base: &Url,
+) -> Result<(), lol_html::errors::AttributeNameError> {
+    let Some(value) = el.get_attribute(attribute) else {
+        return Ok(());
+    };
+    let value = value.trim();
+    if value.is_empty() || Url::parse(value).is_ok() {
+        return Ok(());
+    }
+    if let Ok(url) = base.join(value) {
+        el.set_attribute(attribute, url.as_str())?;
+    }
+    Ok(())
+}
+
+/// `src` of every `<img>` in `html`, in document order.
+fn image_sources(html: &str) -> Vec<String> {
+    let sources = Rc::new(RefCell::new(Vec::new()));
+
+    let result = rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("img[src]", {
+                let sources = Rc::clone(&sources);
+                move |el| {
+                    if let Some(src) = el.get_attribute("src") {
+                        sources.borrow_mut().push(src);
+                    }
+                    Ok(())
+                }
+            })],
+            ..RewriteStrSettings::default()
+        },
+    );
+    if result.is_err() {
+        return Vec::new();
+    }
+    sources.take()
+}
+
+/// Stored path of an upload URL on the site, with or without the base path.
+fn upload_stored_path(image: &Url, site: &Url) -> Option<String> {
+    if image.origin() != site.origin() {
+        return None;
     }
+    let path = image.path();
+    let relative = path
+        .strip_prefix(site.path())
+        .or_else(|| path.strip_prefix('/'))?;
+    let stored = percent_decode_str(relative.strip_prefix("uploads/")?)
+        .decode_utf8()
+        .ok()?;
+    (!stored.is_empty()).then(|| stored.into_owned())
 }
 
-fn normalize_public_site_url(url: &str) -> String {
-    let trimmed = url.trim_end_matches('/');
-    format!("{trimmed}/")
+/// Keep `]]>` in `text` from closing the surrounding CDATA section.
+fn cdata_escape(text: &str) -> String {
+    text.replace("]]>", "]]]]><![CDATA[>")
 }
 
 fn xml_escape(input: &str) -> String {
@@ -252,3 +471,80 @@ fn xml_escape(input: &str) -> String {
         .replace('"', "&quot;")
         .replace('\'', "&apos;")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(value: &str) -> Url {
+        Url::parse(value).expect("valid url")
+    }
+
+    #[test]
+    fn relative_links_resolve_against_the_item_url() {
+        let base = url("https://example.com/blog/posts/hello");
+        let html = r#"<a href="/about">About</a><a href="sibling">Sibling</a><img src="../uploads/cat.png">"#;
+
+        assert_eq!(
+            absolutize_urls(html, &base),
+            r#"<a href="https://example.com/about">About</a><a href="https://example.com/blog/posts/sibling">Sibling</a><img src="https://example.com/blog/uploads/cat.png">"#
+        );
+    }
+
+    #[test]
+    fn anchors_point_at_the_item_itself() {
+        let base = url("https://example.com/posts/hello");
+
+        assert_eq!(
+            absolutize_urls(r##"<a href="#setup">Setup</a>"##, &base),
+            r#"<a href="https://example.com/posts/hello#setup">Setup</a>"#
+        );
+    }
+
+    #[test]
+    fn protocol_relative_urls_take_the_site_scheme() {
+        let base = url("https://example.com/posts/hello");
+
+        assert_eq!(
+            absolutize_urls(r#"<img src="//cdn.example.net/a.png">"#, &base),
+            r#"<img src="https://cdn.example.net/a.png">"#
+        );
+    }
+
+    #[test]
+    fn absolute_urls_and_other_schemes_are_kept() {
+        let base = url("https://example.com/posts/hello");
+        let html = r#"<a href="https://other.example/x">x</a><a href="mailto:me@example.com">mail</a><img src="data:image/png;base64,AA"><a href="">empty</a>"#;
+
+        assert_eq!(absolutize_urls(html, &base), html);
+    }
+
+    #[test]
+    fn upload_paths_are_recognised_with_or_without_the_base_path() {
+        let site = url("https://example.com/blog/");
+
+        assert_eq!(
+            upload_stored_path(&url("https://example.com/uploads/2026/10/cat.png"), &site)
+                .as_deref(),
+            Some("2026/10/cat.png")
+        );
+        assert_eq!(
+            upload_stored_path(&url("https://example.com/blog/uploads/a%20b.png"), &site)
+                .as_deref(),
+            Some("a b.png")
+        );
+        assert_eq!(
+            upload_stored_path(&url("https://cdn.example.net/uploads/cat.png"), &site),
+            None
+        );
+        assert_eq!(
+            upload_stored_path(&url("https://example.com/static/cat.png"), &site),
+            None
+        );
+    }
+
+    #[test]
+    fn cdata_terminators_are_split() {
+        assert_eq!(cdata_escape("a ]]> b"), "a ]]]]><![CDATA[> b");
+    }
+}
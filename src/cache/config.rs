@@ -16,6 +16,8 @@ const DEFAULT_L1_RESPONSE_BODY_LIMIT_BYTES: usize = 1_048_576;
 const DEFAULT_AUTO_CONSUME_INTERVAL_MS: u64 = 5000;
 const DEFAULT_CONSUME_BATCH_LIMIT: usize = 100;
 const DEFAULT_MAX_EVENT_QUEUE_LEN: usize = 2048;
+const DEFAULT_WARMUP_HOT_PATHS: [&str; 3] = ["/", "/rss.xml", "/sitemap.xml"];
+const DEFAULT_WARMUP_CRON: &str = "0 */5 * * * *";
 
 /// Cache configuration from `soffio.toml`.
 #[derive(Debug, Clone, Deserialize)]
@@ -43,6 +45,10 @@ pub struct CacheConfig {
     pub consume_batch_limit: usize,
     /// Maximum event queue length before dropping oldest events.
     pub max_event_queue_len: usize,
+    /// Public paths re-warmed by the scheduled warm cycle.
+    pub warmup_hot_paths: Vec<String>,
+    /// Cron expression (with seconds) for the scheduled warm cycle.
+    pub warmup_cron: String,
 }
 
 impl Default for CacheConfig {
@@ -59,6 +65,8 @@ impl Default for CacheConfig {
             auto_consume_interval_ms: DEFAULT_AUTO_CONSUME_INTERVAL_MS,
             consume_batch_limit: DEFAULT_CONSUME_BATCH_LIMIT,
             max_event_queue_len: DEFAULT_MAX_EVENT_QUEUE_LEN,
+            warmup_hot_paths: DEFAULT_WARMUP_HOT_PATHS.map(str::to_string).to_vec(),
+            warmup_cron: DEFAULT_WARMUP_CRON.to_string(),
         }
     }
 }
@@ -77,6 +85,8 @@ impl From<&crate::config::CacheSettings> for CacheConfig {
             auto_consume_interval_ms: settings.auto_consume_interval_ms,
             consume_batch_limit: settings.consume_batch_limit,
             max_event_queue_len: settings.max_event_queue_len,
+            warmup_hot_paths: settings.warmup_hot_paths.clone(),
+            warmup_cron: settings.warmup_cron.clone(),
         }
     }
 }
@@ -136,6 +146,8 @@ mod tests {
         assert_eq!(config.auto_consume_interval_ms, 5000);
         assert_eq!(config.consume_batch_limit, 100);
         assert_eq!(config.max_event_queue_len, 2048);
+        assert_eq!(config.warmup_hot_paths, ["/", "/rss.xml", "/sitemap.xml"]);
+        assert_eq!(config.warmup_cron, "0 */5 * * * *");
     }
 
     #[test]
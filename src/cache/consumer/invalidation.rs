@@ -31,7 +31,13 @@ impl CacheConsumer {
                 EntityKey::ApiKey(prefix) => {
                     self.l0.invalidate_api_key(prefix);
                 }
-                EntityKey::PostsIndex => self.l0.invalidate_all_post_lists(),
+                // L0 list entries are keyed by filter hash, so any listing
+                // change drops them all; L1 stays scoped through the registry.
+                EntityKey::PostsIndex
+                | EntityKey::PostsRecent
+                | EntityKey::PostsPaged
+                | EntityKey::PostsTag(_)
+                | EntityKey::PostsMonth(_) => self.l0.invalidate_all_post_lists(),
                 EntityKey::PostAggTags => self.l0.invalidate_tag_counts(),
                 EntityKey::PostAggMonths => self.l0.invalidate_month_counts(),
                 EntityKey::Feed | EntityKey::Sitemap => {
@@ -53,4 +59,38 @@ impl CacheConsumer {
             }
         }
     }
+
+    /// Purge the cached responses for one public URL (path plus optional query).
+    ///
+    /// Drops every L1 variant of the URL and the L0 post or page record it
+    /// rendered. Shared L0 entries (settings, navigation, lists, counts) back
+    /// other URLs too and are left alone. Returns whether anything was cached.
+    pub fn purge_url(&self, url: &str) -> bool {
+        let mut existed = false;
+        for l1_key in L1Key::for_public_url(url) {
+            existed |= self.l1.get(&l1_key).is_some();
+            self.l1.invalidate(&l1_key);
+
+            let cache_key = CacheKey::L1(l1_key);
+            for entity in self.registry.entities_for_key(&cache_key) {
+                match entity {
+                    EntityKey::PostSlug(slug) => {
+                        if let Some(post) = self.l0.get_post_by_slug(&slug) {
+                            self.l0.invalidate_post(post.id, &slug);
+                        }
+                    }
+                    EntityKey::PageSlug(slug) => {
+                        if let Some(page) = self.l0.get_page_by_slug(&slug) {
+                            self.l0.invalidate_page(page.id, &slug);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            self.registry.unregister(&cache_key);
+        }
+
+        info!(url, existed, "Purged cached URL");
+        existed
+    }
 }
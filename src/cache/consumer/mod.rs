@@ -2,12 +2,14 @@
 //!
 //! Consumes events from the queue and executes invalidation/warming actions.
 
-use std::sync::Arc;
+use std::collections::BTreeSet;
 #[cfg(test)]
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use metrics::histogram;
+use tokio::sync::Notify;
 use tracing::{info, instrument};
 use uuid::Uuid;
 
@@ -20,8 +22,9 @@ use crate::infra::db::PostgresRepositories;
 
 use super::config::CacheConfig;
 use super::events::EventQueue;
-use super::keys::{CacheKey, EntityKey};
-use super::planner::ConsumptionPlan;
+use super::keys::{CacheKey, EntityKey, L1Key};
+use super::lock::mutex_lock;
+use super::planner::{ConsumptionPlan, WarmTarget};
 use super::registry::CacheRegistry;
 use super::store::{L0Store, L1Store};
 
@@ -32,6 +35,7 @@ mod warm;
 
 const METRIC_CACHE_CONSUME_MS: &str = "soffio_cache_consume_ms";
 const METRIC_CACHE_WARM_MS: &str = "soffio_cache_warm_ms";
+const SOURCE: &str = "cache::consumer";
 
 /// Cache consumer that processes events and maintains cache consistency.
 ///
@@ -46,6 +50,11 @@ pub struct CacheConsumer {
     registry: Arc<CacheRegistry>,
     queue: Arc<EventQueue>,
     repos: Option<Arc<PostgresRepositories>>,
+    /// L1 warm targets of consumed plans, rendered by the [`CacheWarmer`].
+    ///
+    /// [`CacheWarmer`]: super::CacheWarmer
+    warm_targets: Mutex<BTreeSet<WarmTarget>>,
+    warm_targets_queued: Notify,
     #[cfg(test)]
     warm_invocations: Arc<AtomicUsize>,
 }
@@ -67,6 +76,8 @@ impl CacheConsumer {
             registry,
             queue,
             repos: Some(repos),
+            warm_targets: Mutex::new(BTreeSet::new()),
+            warm_targets_queued: Notify::new(),
             #[cfg(test)]
             warm_invocations: Arc::new(AtomicUsize::new(0)),
         }
@@ -90,6 +101,8 @@ impl CacheConsumer {
             registry,
             queue,
             repos: None,
+            warm_targets: Mutex::new(BTreeSet::new()),
+            warm_targets_queued: Notify::new(),
             warm_invocations: Arc::new(AtomicUsize::new(0)),
         }
     }
@@ -151,6 +164,14 @@ impl CacheConsumer {
             self.warm(&plan).await;
         }
 
+        // Phase 4: Hand L1 targets to the warmer; rendering happens off this
+        // path, so write requests do not wait for it.
+        if self.config.enable_l1_cache && !plan.warm_targets.is_empty() {
+            mutex_lock(&self.warm_targets, SOURCE, "queue_warm_targets")
+                .extend(plan.warm_targets.iter().cloned());
+            self.warm_targets_queued.notify_one();
+        }
+
         // Observable: log consumption complete
         info!(
             event_count,
@@ -167,6 +188,22 @@ impl CacheConsumer {
         true
     }
 
+    /// Wait until a consumed plan queues L1 warm targets.
+    pub async fn warm_targets_queued(&self) {
+        self.warm_targets_queued.notified().await;
+    }
+
+    /// Take the queued L1 warm targets, highest priority first.
+    pub fn take_warm_targets(&self) -> Vec<WarmTarget> {
+        std::mem::take(&mut *mutex_lock(
+            &self.warm_targets,
+            SOURCE,
+            "take_warm_targets",
+        ))
+        .into_iter()
+        .collect()
+    }
+
     /// Get reference to the event queue.
     pub fn queue(&self) -> &Arc<EventQueue> {
         &self.queue
@@ -67,6 +67,24 @@ async fn consume_invalidate_only_skips_warm_phase() {
     assert_eq!(consumer.warm_invocation_count(), 1);
 }
 
+#[tokio::test]
+async fn consume_queues_l1_warm_targets_for_the_warmer() {
+    let consumer = create_consumer();
+
+    consumer.queue.publish(EventKind::PostUpserted {
+        post_id: Uuid::new_v4(),
+        slug: "hello".to_string(),
+        previous_slug: None,
+        listing: None,
+    });
+    assert!(consumer.consume_invalidate_only().await);
+
+    let targets = consumer.take_warm_targets();
+    assert_eq!(targets[0], WarmTarget::Post("hello".to_string()));
+    assert!(targets.contains(&WarmTarget::Feeds));
+    assert!(consumer.take_warm_targets().is_empty());
+}
+
 #[tokio::test]
 async fn invalidate_l0_site_settings() {
     let consumer = create_consumer();
@@ -88,11 +106,21 @@ async fn invalidate_l0_site_settings() {
         footer_copy: "© 2024".to_string(),
         public_site_url: "http://localhost".to_string(),
         favicon_svg: "".to_string(),
+        favicon_upload_id: None,
         timezone: chrono_tz::Tz::UTC,
+        date_format: "%B %-d, %Y".to_string(),
         meta_title: "Test".to_string(),
         meta_description: "Test".to_string(),
         og_title: "Test".to_string(),
         og_description: "Test".to_string(),
+        maintenance_enabled: false,
+        maintenance_html: "<p>Maintenance</p>".to_string(),
+        maintenance_retry_after_secs: 300,
+        feed_content_mode: crate::domain::types::FeedContentMode::Excerpt,
+        feed_item_limit: 20,
+        homepage_mode: crate::domain::types::HomepageMode::Feed,
+        homepage_page_id: None,
+        homepage_layout: crate::domain::types::HomepageLayout::List,
         updated_at: OffsetDateTime::now_utc(),
     };
     consumer.l0.set_site_settings(settings);
@@ -105,3 +133,156 @@ async fn invalidate_l0_site_settings() {
     // Should be invalidated
     assert!(consumer.l0.get_site_settings().is_none());
 }
+
+#[tokio::test]
+async fn scoped_post_edit_keeps_unrelated_tag_listing_cached() {
+    use std::collections::HashSet;
+
+    use crate::cache::events::PostListing;
+    use crate::cache::keys::{L1Key, OutputFormat, hash_query};
+    use crate::cache::store::CachedResponse;
+
+    let consumer = create_consumer();
+    let cache_response = |path: &str, deps: &[EntityKey]| {
+        let key = L1Key::Response {
+            format: OutputFormat::Html,
+            path: path.to_string(),
+            query_hash: hash_query(""),
+        };
+        consumer.l1.set(
+            key.clone(),
+            CachedResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: bytes::Bytes::from_static(b"ok"),
+            },
+        );
+        consumer.registry.register(
+            CacheKey::L1(key.clone()),
+            deps.iter().cloned().collect::<HashSet<_>>(),
+        );
+        key
+    };
+
+    let post_page = cache_response(
+        "/posts/old-post",
+        &[EntityKey::PostSlug("old-post".to_string())],
+    );
+    let tag_listing = cache_response(
+        "/tags/rust",
+        &[
+            EntityKey::PostsIndex,
+            EntityKey::PostsTag("rust".to_string()),
+            EntityKey::PostAggTags,
+            EntityKey::PostAggMonths,
+        ],
+    );
+
+    consumer.queue.publish(EventKind::PostUpserted {
+        post_id: Uuid::new_v4(),
+        slug: "old-post".to_string(),
+        previous_slug: None,
+        listing: Some(PostListing {
+            months: ["2019-03".to_string()].into(),
+            ..Default::default()
+        }),
+    });
+    consumer.consume_invalidate_only().await;
+
+    assert!(consumer.l1.get(&post_page).is_none());
+    assert!(consumer.l1.get(&tag_listing).is_some());
+}
+
+fn cache_html_response(consumer: &CacheConsumer, url: &str, deps: &[EntityKey]) -> L1Key {
+    use crate::cache::store::CachedResponse;
+
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let key = L1Key::for_request(path, query, false);
+    consumer.l1.set(
+        key.clone(),
+        CachedResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: bytes::Bytes::from_static(b"ok"),
+        },
+    );
+    consumer
+        .registry
+        .register(CacheKey::L1(key.clone()), deps.iter().cloned().collect());
+    key
+}
+
+#[tokio::test]
+async fn purge_url_drops_cached_response_and_its_post_record() {
+    use time::OffsetDateTime;
+
+    use crate::domain::entities::PostRecord;
+    use crate::domain::types::{PostStatus, RenderStatus};
+
+    let consumer = create_consumer();
+    let post_id = Uuid::new_v4();
+    consumer.l0.set_post(PostRecord {
+        id: post_id,
+        slug: "hello".to_string(),
+        title: "Hello".to_string(),
+        excerpt: String::new(),
+        excerpt_generated: false,
+        body_markdown: String::new(),
+        status: PostStatus::Published,
+        pinned: false,
+        pin_order: 0,
+        scheduled_at: None,
+        published_at: Some(OffsetDateTime::now_utc()),
+        archived_at: None,
+        summary_markdown: None,
+        summary_html: None,
+        lang: None,
+        translation_group: None,
+        render_status: RenderStatus::Ok,
+        render_error: None,
+        render_failed_at: None,
+        created_at: OffsetDateTime::now_utc(),
+        updated_at: OffsetDateTime::now_utc(),
+    });
+    let post_page = cache_html_response(
+        &consumer,
+        "/posts/hello",
+        &[
+            EntityKey::PostSlug("hello".to_string()),
+            EntityKey::SiteSettings,
+        ],
+    );
+    let other_page = cache_html_response(&consumer, "/posts/other", &[EntityKey::SiteSettings]);
+
+    assert!(consumer.purge_url("/posts/hello"));
+
+    assert!(consumer.l1.get(&post_page).is_none());
+    assert!(consumer.l0.get_post_by_id(post_id).is_none());
+    assert!(
+        consumer
+            .registry
+            .entities_for_key(&CacheKey::L1(post_page))
+            .is_empty()
+    );
+    assert!(consumer.l1.get(&other_page).is_some());
+}
+
+#[tokio::test]
+async fn purge_url_matches_query_string() {
+    let consumer = create_consumer();
+    let second_page = cache_html_response(&consumer, "/tags/rust?cursor=abc", &[]);
+
+    assert!(!consumer.purge_url("/tags/rust"));
+    assert!(consumer.l1.get(&second_page).is_some());
+
+    assert!(consumer.purge_url("/tags/rust?cursor=abc"));
+    assert!(consumer.l1.get(&second_page).is_none());
+}
+
+#[tokio::test]
+async fn purge_url_reports_never_cached_path() {
+    let consumer = create_consumer();
+
+    assert!(!consumer.purge_url("/posts/never-cached"));
+    assert!(consumer.l1.is_empty());
+}
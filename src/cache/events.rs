@@ -2,7 +2,7 @@
 //!
 //! Defines cache events and an in-memory queue for event-driven invalidation.
 
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
 use std::sync::Mutex;
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -63,11 +63,13 @@ pub enum EventKind {
     /// A post was created or updated.
     ///
     /// `previous_slug` is set when an existing post changes slug so old slug caches
-    /// can be invalidated in the same pass.
+    /// can be invalidated in the same pass. `listing` narrows invalidation to the
+    /// listings the post appears on; `None` invalidates every listing.
     PostUpserted {
         post_id: Uuid,
         slug: String,
         previous_slug: Option<String>,
+        listing: Option<PostListing>,
     },
     /// A post was deleted.
     PostDeleted { post_id: Uuid, slug: String },
@@ -91,9 +93,34 @@ pub enum EventKind {
     /// An API key was revoked.
     ApiKeyRevoked { prefix: String },
 
-    // Startup
+    // Warmup
     /// Warm the cache on application startup.
     WarmupOnStartup,
+    /// Periodic warm cycle for the configured hot paths.
+    WarmupScheduled,
+}
+
+/// Listings a post appeared on before a change or appears on after it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostListing {
+    /// Tag slugs whose listings include the post.
+    pub tags: BTreeSet<String>,
+    /// Month keys (`YYYY-MM`) whose listings include the post.
+    pub months: BTreeSet<String>,
+    /// Whether the post is on the first page of the homepage.
+    pub recent: bool,
+    /// Whether tag or month counts changed (publication, tags or month moved).
+    pub counts_changed: bool,
+}
+
+impl PostListing {
+    /// Widen this listing to also cover `other`.
+    pub fn merge(&mut self, other: &PostListing) {
+        self.tags.extend(other.tags.iter().cloned());
+        self.months.extend(other.months.iter().cloned());
+        self.recent |= other.recent;
+        self.counts_changed |= other.counts_changed;
+    }
 }
 
 /// In-memory event queue for cache invalidation.
@@ -241,6 +268,7 @@ mod tests {
             post_id: Uuid::nil(),
             slug: "test".to_string(),
             previous_slug: None,
+            listing: None,
         });
 
         assert_eq!(queue.len(), 3);
@@ -298,16 +326,19 @@ mod tests {
             post_id: Uuid::nil(),
             slug: "test".to_string(),
             previous_slug: None,
+            listing: None,
         };
         let kind2 = EventKind::PostUpserted {
             post_id: Uuid::nil(),
             slug: "test".to_string(),
             previous_slug: None,
+            listing: None,
         };
         let kind3 = EventKind::PostUpserted {
             post_id: Uuid::nil(),
             slug: "other".to_string(),
             previous_slug: None,
+            listing: None,
         };
 
         assert_eq!(kind1, kind2);
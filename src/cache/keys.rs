@@ -35,8 +35,16 @@ pub enum EntityKey {
     ApiKey(String),
 
     // Derived collections (invalidated when any post/page changes)
-    /// Homepage, archives, tag/month filtered lists
+    /// Every post listing: homepage, archives, tag/month filtered lists
     PostsIndex,
+    /// First page of the unfiltered listing (homepage)
+    PostsRecent,
+    /// Later pages of the unfiltered listing
+    PostsPaged,
+    /// Listing filtered by a tag slug
+    PostsTag(String),
+    /// Listing filtered by a month key (`YYYY-MM`)
+    PostsMonth(String),
     /// Tag counts for sidebar
     PostAggTags,
     /// Month counts for sidebar
@@ -88,6 +96,54 @@ pub enum L1Key {
     },
 }
 
+impl OutputFormat {
+    /// Format a public request is cached under.
+    ///
+    /// Feed, sitemap and icon paths have a fixed format; other paths are
+    /// cached as JSON when the client accepts it and as HTML otherwise.
+    pub fn for_request(path: &str, accepts_json: bool) -> Self {
+        if path.ends_with("/rss.xml") {
+            Self::Rss
+        } else if path.ends_with("/atom.xml") {
+            Self::Atom
+        } else if path.ends_with("/sitemap.xml") {
+            Self::Sitemap
+        } else if ["/favicon.ico", "/favicon.svg", "/apple-touch-icon.png"]
+            .iter()
+            .any(|icon| path.ends_with(icon))
+        {
+            Self::Favicon
+        } else if accepts_json {
+            Self::Json
+        } else {
+            Self::Html
+        }
+    }
+}
+
+impl L1Key {
+    /// Key the response cache layer uses for a GET to `path` with `query`.
+    pub fn for_request(path: &str, query: &str, accepts_json: bool) -> Self {
+        Self::Response {
+            format: OutputFormat::for_request(path, accepts_json),
+            path: path.to_string(),
+            query_hash: hash_query(query),
+        }
+    }
+
+    /// Every key a public URL (path plus optional `?query`) can be cached under.
+    pub fn for_public_url(url: &str) -> Vec<Self> {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let html = Self::for_request(path, query, false);
+        let json = Self::for_request(path, query, true);
+        if html == json {
+            vec![html]
+        } else {
+            vec![html, json]
+        }
+    }
+}
+
 /// Unified cache key.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CacheKey {
@@ -116,6 +172,7 @@ pub fn hash_post_list_key(filter: &PostQueryFilter, page_limit: u32) -> u64 {
     let mut hasher = DefaultHasher::new();
     filter.tag.hash(&mut hasher);
     filter.month.hash(&mut hasher);
+    filter.year.hash(&mut hasher);
     filter.search.hash(&mut hasher);
     page_limit.hash(&mut hasher);
     hasher.finish()
@@ -168,6 +225,31 @@ mod tests {
         assert_eq!(hash_query("page=2"), hash_query("page=2"));
     }
 
+    #[test]
+    fn public_url_keys_cover_html_and_json_variants() {
+        let keys = L1Key::for_public_url("/tags/rust?cursor=abc");
+        assert_eq!(
+            keys,
+            vec![
+                L1Key::Response {
+                    format: OutputFormat::Html,
+                    path: "/tags/rust".to_string(),
+                    query_hash: hash_query("cursor=abc"),
+                },
+                L1Key::Response {
+                    format: OutputFormat::Json,
+                    path: "/tags/rust".to_string(),
+                    query_hash: hash_query("cursor=abc"),
+                },
+            ]
+        );
+
+        assert_eq!(
+            L1Key::for_public_url("/rss.xml"),
+            vec![L1Key::for_request("/rss.xml", "", false)]
+        );
+    }
+
     #[test]
     fn different_queries_produce_different_hashes() {
         let hash1 = hash_query("page=1");
@@ -166,27 +166,16 @@ pub async fn response_cache_layer(
 }
 
 /// Detect output format from request.
+///
+/// Shares `OutputFormat::for_request` with `L1Key::for_public_url`, so manual
+/// purges derive the same keys this layer stores under.
 fn detect_format(request: &Request<Body>) -> OutputFormat {
-    let path = request.uri().path();
-
-    if path.ends_with("/rss.xml") || path == "/rss.xml" {
-        OutputFormat::Rss
-    } else if path.ends_with("/atom.xml") || path == "/atom.xml" {
-        OutputFormat::Atom
-    } else if path.ends_with("/sitemap.xml") || path == "/sitemap.xml" {
-        OutputFormat::Sitemap
-    } else if path.ends_with("/favicon.ico") || path == "/favicon.ico" {
-        OutputFormat::Favicon
-    } else if request
+    let accepts_json = request
         .headers()
         .get("Accept")
         .and_then(|v| v.to_str().ok())
-        .is_some_and(|v| v.contains("application/json"))
-    {
-        OutputFormat::Json
-    } else {
-        OutputFormat::Html
-    }
+        .is_some_and(|v| v.contains("application/json"));
+    OutputFormat::for_request(request.uri().path(), accepts_json)
 }
 
 fn output_format_label(format: &OutputFormat) -> &'static str {
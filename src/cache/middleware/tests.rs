@@ -63,11 +63,10 @@ fn detect_format_json_from_accept_header() {
 
 #[test]
 fn detect_format_favicon() {
-    let req = Request::builder()
-        .uri("/favicon.ico")
-        .body(Body::empty())
-        .unwrap();
-    assert_eq!(detect_format(&req), OutputFormat::Favicon);
+    for uri in ["/favicon.ico", "/favicon.svg", "/apple-touch-icon.png"] {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        assert_eq!(detect_format(&req), OutputFormat::Favicon, "{uri}");
+    }
 }
 
 fn build_cache_state(mut config: CacheConfig) -> (CacheState, Arc<L1Store>, Arc<CacheRegistry>) {
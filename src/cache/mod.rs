@@ -29,16 +29,18 @@ mod planner;
 mod registry;
 mod store;
 mod trigger;
+mod warmer;
 
 pub use config::CacheConfig;
 pub use consumer::CacheConsumer;
-pub use events::{CacheEvent, Epoch, EventKind, EventQueue};
+pub use events::{CacheEvent, Epoch, EventKind, EventQueue, PostListing};
 pub use keys::{
     CacheKey, EntityKey, L0Key, L1Key, OutputFormat, hash_cursor_str, hash_post_list_key,
     hash_query, hash_value,
 };
 pub use middleware::{CacheState, response_cache_layer};
-pub use planner::ConsumptionPlan;
+pub use planner::{ConsumptionPlan, WarmTarget};
 pub use registry::CacheRegistry;
 pub use store::{CachedResponse, L0Store, L1Store};
 pub use trigger::CacheTrigger;
+pub use warmer::CacheWarmer;
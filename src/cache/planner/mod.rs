@@ -2,17 +2,69 @@
 //!
 //! Merges multiple cache events into an optimized execution plan.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
 
 use uuid::Uuid;
 
-use super::events::{CacheEvent, EventKind};
+use crate::domain::routes::post_path;
+
+use super::events::{CacheEvent, EventKind, PostListing};
 use super::keys::EntityKey;
 
 #[cfg(test)]
 mod tests;
 
+/// A public response to render into L1 after invalidation.
+///
+/// Variants are declared in warm priority order: the entity that changed is
+/// rendered first, then the listings and feeds that show it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarmTarget {
+    /// A post page, by slug.
+    Post(String),
+    /// A page, by slug.
+    Page(String),
+    /// The homepage.
+    Home,
+    /// A tag listing, by tag slug.
+    Tag(String),
+    /// The RSS and Atom feeds.
+    Feeds,
+    /// The sitemap.
+    Sitemap,
+    /// Every path in `cache.warmup_hot_paths`.
+    HotPaths,
+}
+
+impl WarmTarget {
+    /// Label used in logs and metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Post(_) => "post",
+            Self::Page(_) => "page",
+            Self::Home => "home",
+            Self::Tag(_) => "tag",
+            Self::Feeds => "feeds",
+            Self::Sitemap => "sitemap",
+            Self::HotPaths => "hot_path",
+        }
+    }
+
+    /// Router paths rendered for this target.
+    pub fn paths(&self, hot_paths: &[String]) -> Vec<String> {
+        match self {
+            Self::Post(slug) => vec![post_path(slug)],
+            Self::Page(slug) => vec![format!("/{slug}")],
+            Self::Home => vec!["/".to_string()],
+            Self::Tag(slug) => vec![format!("/tags/{slug}")],
+            Self::Feeds => vec!["/rss.xml".to_string(), "/atom.xml".to_string()],
+            Self::Sitemap => vec!["/sitemap.xml".to_string()],
+            Self::HotPaths => hot_paths.to_vec(),
+        }
+    }
+}
+
 /// Actions to execute for cache consistency.
 ///
 /// The planner merges multiple events into a single plan, deduplicating
@@ -40,6 +92,8 @@ pub struct ConsumptionPlan {
     pub warm_feed: bool,
     /// Whether to warm the sitemap.
     pub warm_sitemap: bool,
+    /// Public responses to render into L1, highest priority first.
+    pub warm_targets: BTreeSet<WarmTarget>,
 }
 
 impl fmt::Display for ConsumptionPlan {
@@ -48,7 +102,7 @@ impl fmt::Display for ConsumptionPlan {
             f,
             "ConsumptionPlan {{ invalidate: {}, warm_settings: {}, warm_nav: {}, \
              warm_nav_pages: {}, warm_agg: {}, warm_posts: {}, warm_pages: {}, \
-             warm_homepage: {}, warm_feed: {}, warm_sitemap: {} }}",
+             warm_homepage: {}, warm_feed: {}, warm_sitemap: {}, warm_targets: {} }}",
             self.invalidate_entities.len(),
             self.warm_site_settings,
             self.warm_navigation,
@@ -59,6 +113,7 @@ impl fmt::Display for ConsumptionPlan {
             self.warm_homepage,
             self.warm_feed,
             self.warm_sitemap,
+            self.warm_targets.len(),
         )
     }
 }
@@ -82,20 +137,39 @@ impl ConsumptionPlan {
         // Track latest event per entity
         let mut post_epochs: HashMap<Uuid, (u64, EventKind)> = HashMap::new();
         let mut page_epochs: HashMap<Uuid, (u64, EventKind)> = HashMap::new();
+        // Listings touched by post events; any unscoped event widens this to all.
+        let mut post_listings = PostListing::default();
+        let mut all_post_listings = false;
 
         for event in events {
             match &event.kind {
                 EventKind::SiteSettingsUpdated => {
                     plan.invalidate_entities.insert(EntityKey::SiteSettings);
                     plan.warm_site_settings = true;
+                    // Settings show on every response.
+                    plan.warm_targets.extend([
+                        WarmTarget::Home,
+                        WarmTarget::Feeds,
+                        WarmTarget::Sitemap,
+                        WarmTarget::HotPaths,
+                    ]);
                 }
                 EventKind::NavigationUpdated => {
                     plan.invalidate_entities.insert(EntityKey::Navigation);
                     plan.warm_navigation = true;
                     plan.warm_navigation_pages = true;
+                    plan.warm_targets
+                        .extend([WarmTarget::Home, WarmTarget::HotPaths]);
                 }
                 EventKind::PostUpserted { post_id, .. }
                 | EventKind::PostDeleted { post_id, .. } => {
+                    match &event.kind {
+                        EventKind::PostUpserted {
+                            listing: Some(listing),
+                            ..
+                        } => post_listings.merge(listing),
+                        _ => all_post_listings = true,
+                    }
                     let entry = post_epochs.entry(*post_id);
                     entry
                         .and_modify(|(e, k)| {
@@ -122,12 +196,13 @@ impl ConsumptionPlan {
                     plan.invalidate_entities.insert(EntityKey::PostAggTags);
                     plan.invalidate_entities.insert(EntityKey::PostsIndex);
                     plan.warm_aggregations = true;
+                    plan.warm_targets.insert(WarmTarget::Home);
                 }
                 EventKind::ApiKeyUpserted { prefix } | EventKind::ApiKeyRevoked { prefix } => {
                     plan.invalidate_entities
                         .insert(EntityKey::ApiKey(prefix.clone()));
                 }
-                EventKind::WarmupOnStartup => {
+                EventKind::WarmupOnStartup | EventKind::WarmupScheduled => {
                     plan.warm_site_settings = true;
                     plan.warm_navigation = true;
                     plan.warm_navigation_pages = true;
@@ -135,6 +210,10 @@ impl ConsumptionPlan {
                     plan.warm_homepage = true;
                     plan.warm_feed = true;
                     plan.warm_sitemap = true;
+                    // A scheduled cycle renders the hot paths itself.
+                    if event.kind == EventKind::WarmupOnStartup {
+                        plan.warm_targets.insert(WarmTarget::HotPaths);
+                    }
                 }
             }
         }
@@ -164,6 +243,7 @@ impl ConsumptionPlan {
                             .insert(EntityKey::PostSlug(previous_slug.clone()));
                     }
                     plan.warm_posts.insert(post_id);
+                    plan.warm_targets.insert(WarmTarget::Post(slug));
                 }
                 _ => {}
             }
@@ -171,15 +251,25 @@ impl ConsumptionPlan {
 
         // If any post changed, invalidate derived collections
         if any_post_changed {
-            plan.invalidate_entities.insert(EntityKey::PostsIndex);
-            plan.invalidate_entities.insert(EntityKey::PostAggTags);
-            plan.invalidate_entities.insert(EntityKey::PostAggMonths);
+            // Later archive pages, feeds and the sitemap cannot be narrowed down.
+            plan.invalidate_entities.insert(EntityKey::PostsPaged);
             plan.invalidate_entities.insert(EntityKey::Feed);
             plan.invalidate_entities.insert(EntityKey::Sitemap);
-            plan.warm_aggregations = true;
-            plan.warm_homepage = true;
             plan.warm_feed = true;
             plan.warm_sitemap = true;
+            plan.warm_targets
+                .extend([WarmTarget::Feeds, WarmTarget::Sitemap]);
+
+            if all_post_listings {
+                plan.invalidate_entities.insert(EntityKey::PostsIndex);
+                plan.invalidate_entities.insert(EntityKey::PostAggTags);
+                plan.invalidate_entities.insert(EntityKey::PostAggMonths);
+                plan.warm_aggregations = true;
+                plan.warm_homepage = true;
+                plan.warm_targets.insert(WarmTarget::Home);
+            } else {
+                plan.scope_post_listings(post_listings);
+            }
         }
 
         // Process page events
@@ -205,16 +295,41 @@ impl ConsumptionPlan {
                             .insert(EntityKey::PageSlug(previous_slug.clone()));
                     }
                     plan.warm_pages.insert(page_id);
+                    plan.warm_targets.insert(WarmTarget::Page(slug));
                 }
                 _ => {}
             }
             plan.invalidate_entities.insert(EntityKey::Sitemap);
             plan.warm_sitemap = true;
+            plan.warm_targets.insert(WarmTarget::Sitemap);
         }
 
         plan
     }
 
+    /// Invalidate only the listings named by `listing`.
+    fn scope_post_listings(&mut self, listing: PostListing) {
+        for tag in listing.tags {
+            self.invalidate_entities
+                .insert(EntityKey::PostsTag(tag.clone()));
+            self.warm_targets.insert(WarmTarget::Tag(tag));
+        }
+        for month in listing.months {
+            self.invalidate_entities
+                .insert(EntityKey::PostsMonth(month));
+        }
+        if listing.recent {
+            self.invalidate_entities.insert(EntityKey::PostsRecent);
+            self.warm_homepage = true;
+            self.warm_targets.insert(WarmTarget::Home);
+        }
+        if listing.counts_changed {
+            self.invalidate_entities.insert(EntityKey::PostAggTags);
+            self.invalidate_entities.insert(EntityKey::PostAggMonths);
+            self.warm_aggregations = true;
+        }
+    }
+
     /// Check if the plan has any actions to execute.
     pub fn is_empty(&self) -> bool {
         self.invalidate_entities.is_empty()
@@ -227,6 +342,7 @@ impl ConsumptionPlan {
             && !self.warm_homepage
             && !self.warm_feed
             && !self.warm_sitemap
+            && self.warm_targets.is_empty()
     }
 
     /// Check if the plan has any warm actions to execute.
@@ -1,7 +1,7 @@
 use uuid::Uuid;
 
 use super::*;
-use crate::cache::events::CacheEvent;
+use crate::cache::events::{CacheEvent, PostListing};
 
 fn make_event(kind: EventKind, epoch: u64) -> CacheEvent {
     CacheEvent::new(kind, epoch)
@@ -34,6 +34,7 @@ fn post_upsert_triggers_derived_invalidation() {
             post_id,
             slug: "test".to_string(),
             previous_slug: None,
+            listing: None,
         },
         0,
     )];
@@ -110,6 +111,17 @@ fn warmup_on_startup() {
     assert!(plan.warm_sitemap);
 }
 
+#[test]
+fn warmup_scheduled_matches_startup_plan() {
+    let events = vec![make_event(EventKind::WarmupScheduled, 0)];
+    let plan = ConsumptionPlan::from_events(events);
+
+    assert!(plan.warm_site_settings);
+    assert!(plan.warm_aggregations);
+    assert!(plan.warm_homepage);
+    assert!(plan.invalidate_entities.is_empty());
+}
+
 #[test]
 fn dedupe_by_event_id() {
     let post_id = Uuid::new_v4();
@@ -118,6 +130,7 @@ fn dedupe_by_event_id() {
             post_id,
             slug: "test".to_string(),
             previous_slug: None,
+            listing: None,
         },
         0,
     );
@@ -141,6 +154,7 @@ fn keeps_latest_epoch() {
                 post_id,
                 slug: "test".to_string(),
                 previous_slug: None,
+                listing: None,
             },
             0,
         ),
@@ -205,6 +219,7 @@ fn post_slug_change_invalidates_previous_slug() {
             post_id,
             slug: "new-slug".to_string(),
             previous_slug: Some("old-slug".to_string()),
+            listing: None,
         },
         0,
     )];
@@ -260,3 +275,163 @@ fn is_empty() {
     let plan = ConsumptionPlan::from_events(events);
     assert!(!plan.is_empty());
 }
+
+fn scoped_upsert(post_id: Uuid, listing: PostListing) -> CacheEvent {
+    make_event(
+        EventKind::PostUpserted {
+            post_id,
+            slug: "old-post".to_string(),
+            previous_slug: None,
+            listing: Some(listing),
+        },
+        0,
+    )
+}
+
+#[test]
+fn scoped_edit_of_old_untagged_post_skips_unrelated_listings() {
+    let post_id = Uuid::new_v4();
+    let listing = PostListing {
+        months: ["2019-03".to_string()].into(),
+        ..Default::default()
+    };
+    let plan = ConsumptionPlan::from_events(vec![scoped_upsert(post_id, listing)]);
+
+    assert!(plan.invalidate_entities.contains(&EntityKey::Post(post_id)));
+    assert!(
+        plan.invalidate_entities
+            .contains(&EntityKey::PostSlug("old-post".to_string()))
+    );
+    assert!(
+        plan.invalidate_entities
+            .contains(&EntityKey::PostsMonth("2019-03".to_string()))
+    );
+    assert!(plan.invalidate_entities.contains(&EntityKey::PostsPaged));
+    assert!(!plan.invalidate_entities.contains(&EntityKey::PostsIndex));
+    assert!(!plan.invalidate_entities.contains(&EntityKey::PostsRecent));
+    assert!(
+        !plan
+            .invalidate_entities
+            .contains(&EntityKey::PostsTag("rust".to_string()))
+    );
+    assert!(!plan.invalidate_entities.contains(&EntityKey::PostAggTags));
+    assert!(!plan.warm_homepage);
+    assert!(!plan.warm_aggregations);
+}
+
+#[test]
+fn scoped_edit_of_recent_tagged_post_invalidates_its_listings() {
+    let listing = PostListing {
+        tags: ["rust".to_string()].into(),
+        months: ["2026-10".to_string()].into(),
+        recent: true,
+        counts_changed: true,
+    };
+    let plan = ConsumptionPlan::from_events(vec![scoped_upsert(Uuid::new_v4(), listing)]);
+
+    assert!(
+        plan.invalidate_entities
+            .contains(&EntityKey::PostsTag("rust".to_string()))
+    );
+    assert!(plan.invalidate_entities.contains(&EntityKey::PostsRecent));
+    assert!(plan.invalidate_entities.contains(&EntityKey::PostAggTags));
+    assert!(plan.invalidate_entities.contains(&EntityKey::PostAggMonths));
+    assert!(plan.warm_homepage);
+    assert!(plan.warm_aggregations);
+}
+
+#[test]
+fn unscoped_post_event_widens_scoped_batch_to_all_listings() {
+    let post_id = Uuid::new_v4();
+    let events = vec![
+        scoped_upsert(post_id, PostListing::default()),
+        make_event(
+            EventKind::PostDeleted {
+                post_id: Uuid::new_v4(),
+                slug: "gone".to_string(),
+            },
+            1,
+        ),
+    ];
+    let plan = ConsumptionPlan::from_events(events);
+
+    assert!(plan.invalidate_entities.contains(&EntityKey::PostsIndex));
+    assert!(plan.invalidate_entities.contains(&EntityKey::PostAggTags));
+    assert!(plan.warm_homepage);
+}
+
+#[test]
+fn publishing_a_tagged_post_warms_its_page_listings_and_feeds_in_priority_order() {
+    let post_id = Uuid::new_v4();
+    let listing = PostListing {
+        tags: ["rust".to_string(), "web".to_string()].into(),
+        months: ["2026-10".to_string()].into(),
+        recent: true,
+        counts_changed: true,
+    };
+    let plan = ConsumptionPlan::from_events(vec![make_event(
+        EventKind::PostUpserted {
+            post_id,
+            slug: "hello".to_string(),
+            previous_slug: None,
+            listing: Some(listing),
+        },
+        0,
+    )]);
+
+    assert_eq!(
+        plan.warm_targets.into_iter().collect::<Vec<_>>(),
+        [
+            WarmTarget::Post("hello".to_string()),
+            WarmTarget::Home,
+            WarmTarget::Tag("rust".to_string()),
+            WarmTarget::Tag("web".to_string()),
+            WarmTarget::Feeds,
+            WarmTarget::Sitemap,
+        ]
+    );
+}
+
+#[test]
+fn settings_warm_everything_and_deleted_posts_warm_no_page() {
+    let plan = ConsumptionPlan::from_events(vec![make_event(EventKind::SiteSettingsUpdated, 0)]);
+    assert_eq!(
+        plan.warm_targets.into_iter().collect::<Vec<_>>(),
+        [
+            WarmTarget::Home,
+            WarmTarget::Feeds,
+            WarmTarget::Sitemap,
+            WarmTarget::HotPaths,
+        ]
+    );
+
+    let plan = ConsumptionPlan::from_events(vec![make_event(
+        EventKind::PostDeleted {
+            post_id: Uuid::new_v4(),
+            slug: "gone".to_string(),
+        },
+        0,
+    )]);
+    assert!(
+        !plan
+            .warm_targets
+            .contains(&WarmTarget::Post("gone".to_string()))
+    );
+    assert!(plan.warm_targets.contains(&WarmTarget::Feeds));
+}
+
+#[test]
+fn warm_target_paths() {
+    let hot = vec!["/".to_string(), "/tags/rust".to_string()];
+
+    assert_eq!(
+        WarmTarget::Post("hello".to_string()).paths(&hot),
+        ["/posts/hello"]
+    );
+    assert_eq!(
+        WarmTarget::Page("about".to_string()).paths(&hot),
+        ["/about"]
+    );
+    assert_eq!(WarmTarget::Feeds.paths(&hot), ["/rss.xml", "/atom.xml"]);
+    assert_eq!(WarmTarget::HotPaths.paths(&hot), hot);
+}
@@ -1,3 +1,6 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::application::pagination::CursorPage;
@@ -33,6 +36,7 @@ impl L0Store {
             post_lists: std::sync::RwLock::new(lru::LruCache::new(
                 config.l0_post_list_limit_non_zero(),
             )),
+            chrome_version: AtomicU64::new(0),
         }
     }
 
@@ -48,6 +52,7 @@ impl L0Store {
 
     pub fn invalidate_site_settings(&self) {
         *rw_write(&self.site_settings, SOURCE, "invalidate_site_settings") = None;
+        self.bump_chrome_version();
     }
 
     pub fn get_navigation(&self) -> Option<Vec<NavigationItemRecord>> {
@@ -62,6 +67,7 @@ impl L0Store {
 
     pub fn invalidate_navigation(&self) {
         *rw_write(&self.navigation, SOURCE, "invalidate_navigation") = None;
+        self.bump_chrome_version();
     }
 
     pub fn get_tag_counts(&self) -> Option<Vec<crate::application::repos::TagWithCount>> {
@@ -162,6 +168,17 @@ impl L0Store {
     pub fn invalidate_page(&self, id: Uuid, slug: &str) {
         rw_write(&self.pages_by_id, SOURCE, "invalidate_page.by_id").pop(&id);
         rw_write(&self.pages_by_slug, SOURCE, "invalidate_page.by_slug").pop(slug);
+        self.bump_chrome_version();
+    }
+
+    /// Version of the data the site chrome is built from. Changes whenever
+    /// settings, navigation or a page is invalidated.
+    pub fn chrome_version(&self) -> u64 {
+        self.chrome_version.load(Ordering::Acquire)
+    }
+
+    fn bump_chrome_version(&self) {
+        self.chrome_version.fetch_add(1, Ordering::AcqRel);
     }
 
     pub fn get_api_key_by_prefix(&self, prefix: &str) -> Option<ApiKeyRecord> {
@@ -185,6 +202,21 @@ impl L0Store {
         rw_write(&self.api_keys_by_prefix, SOURCE, "invalidate_api_key").pop(prefix);
     }
 
+    /// Drop cached API keys that are no longer active at `now`; returns how
+    /// many were removed.
+    pub fn evict_inactive_api_keys(&self, now: OffsetDateTime) -> usize {
+        let mut keys = rw_write(&self.api_keys_by_prefix, SOURCE, "evict_inactive_api_keys");
+        let inactive: Vec<String> = keys
+            .iter()
+            .filter(|(_, key)| !key.is_active_at(now))
+            .map(|(prefix, _)| prefix.clone())
+            .collect();
+        for prefix in &inactive {
+            keys.pop(prefix);
+        }
+        inactive.len()
+    }
+
     pub fn get_post_list(
         &self,
         filter_hash: u64,
@@ -222,5 +254,6 @@ impl L0Store {
         rw_write(&self.pages_by_slug, SOURCE, "clear.pages_by_slug").clear();
         rw_write(&self.api_keys_by_prefix, SOURCE, "clear.api_keys_by_prefix").clear();
         rw_write(&self.post_lists, SOURCE, "clear.post_lists").clear();
+        self.bump_chrome_version();
     }
 }
@@ -4,6 +4,7 @@
 //! L1: HTTP response cache for rendered pages.
 
 use std::sync::RwLock;
+use std::sync::atomic::AtomicU64;
 
 use bytes::Bytes;
 use lru::LruCache;
@@ -65,6 +66,10 @@ pub struct L0Store {
     // List cache (high cardinality, strict LRU)
     // Key: (filter_hash, cursor_hash)
     post_lists: RwLock<LruCache<(u64, u64), CursorPage<PostRecord>>>,
+
+    // Bumped whenever settings, navigation or a page is invalidated, so
+    // views built from them (the site chrome) know to rebuild.
+    chrome_version: AtomicU64,
 }
 
 /// Cached HTTP response.
@@ -6,20 +6,27 @@ use uuid::Uuid;
 use super::*;
 
 fn sample_post(id: Uuid, slug: &str) -> PostRecord {
-    use crate::domain::types::PostStatus;
+    use crate::domain::types::{PostStatus, RenderStatus};
     PostRecord {
         id,
         slug: slug.to_string(),
         title: "Test Post".to_string(),
         excerpt: "".to_string(),
+        excerpt_generated: false,
         body_markdown: "".to_string(),
         status: PostStatus::Published,
         pinned: false,
+        pin_order: 0,
         scheduled_at: None,
         published_at: Some(OffsetDateTime::now_utc()),
         archived_at: None,
         summary_markdown: None,
         summary_html: None,
+        lang: None,
+        translation_group: None,
+        render_status: RenderStatus::Ok,
+        render_error: None,
+        render_failed_at: None,
         created_at: OffsetDateTime::now_utc(),
         updated_at: OffsetDateTime::now_utc(),
     }
@@ -39,11 +46,21 @@ fn sample_settings() -> SiteSettingsRecord {
         footer_copy: "© 2024".to_string(),
         public_site_url: "http://localhost".to_string(),
         favicon_svg: "".to_string(),
+        favicon_upload_id: None,
         timezone: chrono_tz::Tz::UTC,
+        date_format: "%B %-d, %Y".to_string(),
         meta_title: "Test Site".to_string(),
         meta_description: "Test description".to_string(),
         og_title: "Test Site".to_string(),
         og_description: "Test OG description".to_string(),
+        maintenance_enabled: false,
+        maintenance_html: "<p>Maintenance</p>".to_string(),
+        maintenance_retry_after_secs: 300,
+        feed_content_mode: crate::domain::types::FeedContentMode::Excerpt,
+        feed_item_limit: 20,
+        homepage_mode: crate::domain::types::HomepageMode::Feed,
+        homepage_page_id: None,
+        homepage_layout: crate::domain::types::HomepageLayout::List,
         updated_at: OffsetDateTime::now_utc(),
     }
 }
@@ -90,6 +107,28 @@ fn l0_singleton_cache() {
     assert!(store.get_site_settings().is_none());
 }
 
+#[test]
+fn l0_chrome_version_moves_on_chrome_invalidations() {
+    let store = L0Store::new(&CacheConfig::default());
+    let start = store.chrome_version();
+
+    store.set_site_settings(sample_settings());
+    store.invalidate_tag_counts();
+    store.invalidate_post(Uuid::new_v4(), "post");
+    assert_eq!(store.chrome_version(), start);
+
+    store.invalidate_site_settings();
+    let after_settings = store.chrome_version();
+    assert!(after_settings > start);
+
+    store.invalidate_navigation();
+    assert!(store.chrome_version() > after_settings);
+
+    let after_navigation = store.chrome_version();
+    store.invalidate_page(Uuid::new_v4(), "about");
+    assert!(store.chrome_version() > after_navigation);
+}
+
 #[test]
 fn l1_response_cache_roundtrip() {
     let config = CacheConfig::default();
@@ -165,3 +204,45 @@ fn l0_store_recovers_from_poisoned_lock() {
     store.set_site_settings(sample_settings());
     assert!(store.get_site_settings().is_some());
 }
+
+fn sample_api_key(prefix: &str, expires_at: Option<OffsetDateTime>) -> ApiKeyRecord {
+    ApiKeyRecord {
+        id: Uuid::new_v4(),
+        name: prefix.to_string(),
+        description: None,
+        prefix: prefix.to_string(),
+        hashed_secret: Vec::new(),
+        scopes: Vec::new(),
+        status: crate::domain::api_keys::ApiKeyStatus::Active,
+        expires_in: None,
+        expires_at,
+        revoked_at: None,
+        last_used_at: None,
+        created_by: "test".to_string(),
+        created_at: OffsetDateTime::now_utc(),
+        updated_at: OffsetDateTime::now_utc(),
+    }
+}
+
+#[test]
+fn l0_evicts_only_inactive_api_keys() {
+    let config = CacheConfig::default();
+    let store = L0Store::new(&config);
+    let now = OffsetDateTime::now_utc();
+
+    store.set_api_key(sample_api_key("forever", None));
+    store.set_api_key(sample_api_key(
+        "later",
+        Some(now + time::Duration::hours(1)),
+    ));
+    store.set_api_key(sample_api_key(
+        "expired",
+        Some(now - time::Duration::minutes(1)),
+    ));
+
+    assert_eq!(store.evict_inactive_api_keys(now), 1);
+    assert!(store.get_api_key_by_prefix("expired").is_none());
+    assert!(store.get_api_key_by_prefix("forever").is_some());
+    assert!(store.get_api_key_by_prefix("later").is_some());
+    assert_eq!(store.evict_inactive_api_keys(now), 0);
+}
@@ -10,7 +10,7 @@ use uuid::Uuid;
 
 use super::config::CacheConfig;
 use super::consumer::CacheConsumer;
-use super::events::{EventKind, EventQueue};
+use super::events::{EventKind, EventQueue, PostListing};
 
 /// Cache trigger for publishing cache events.
 ///
@@ -75,6 +75,27 @@ impl CacheTrigger {
                 post_id,
                 slug: slug.to_string(),
                 previous_slug: previous_slug.map(str::to_string),
+                listing: None,
+            },
+            true,
+        )
+        .await;
+    }
+
+    /// Trigger a post upsert event scoped to the listings the post appears on.
+    pub async fn post_upserted_in_listing(
+        &self,
+        post_id: Uuid,
+        slug: &str,
+        previous_slug: Option<&str>,
+        listing: PostListing,
+    ) {
+        self.trigger(
+            EventKind::PostUpserted {
+                post_id,
+                slug: slug.to_string(),
+                previous_slug: previous_slug.map(str::to_string),
+                listing: Some(listing),
             },
             true,
         )
@@ -174,6 +195,15 @@ impl CacheTrigger {
         self.trigger(EventKind::WarmupOnStartup, false).await;
     }
 
+    /// Trigger the L0 part of a scheduled warm cycle and consume it right away,
+    /// so responses rendered afterwards read from a warm object cache.
+    pub async fn warmup_scheduled(&self) {
+        self.trigger(EventKind::WarmupScheduled, false).await;
+        if self.config.is_enabled() {
+            self.consumer.consume_full().await;
+        }
+    }
+
     /// Get the underlying config.
     pub fn config(&self) -> &CacheConfig {
         &self.config
@@ -276,6 +306,9 @@ mod tests {
         trigger
             .post_upserted_with_previous_slug(Uuid::nil(), "new-post-slug", Some("old-post-slug"))
             .await;
+        trigger
+            .post_upserted_in_listing(Uuid::nil(), "post-slug", None, PostListing::default())
+            .await;
         trigger.post_deleted(Uuid::nil(), "post-slug").await;
         trigger.page_upserted(Uuid::nil(), "page-slug").await;
         trigger
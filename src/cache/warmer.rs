@@ -0,0 +1,293 @@
+//! Cache warmer.
+//!
+//! Re-runs public paths through the router so the L1 response cache holds
+//! them before the first visitor arrives: the configured hot paths on a
+//! schedule, and the targets of consumed plans (a published post, its tags,
+//! the homepage and the feeds) as soon as they are invalidated.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Method, Request, StatusCode},
+};
+use futures::{StreamExt, stream};
+use metrics::histogram;
+use tower::ServiceExt;
+use tracing::{debug, warn};
+
+use super::trigger::CacheTrigger;
+
+/// Paths rendered at once when warming queued targets.
+const TARGET_WARM_CONCURRENCY: usize = 4;
+/// A path that renders slower than this is left to its first visitor.
+const WARM_PATH_TIMEOUT: Duration = Duration::from_secs(10);
+const METRIC_CACHE_WARM_PATH_MS: &str = "soffio_cache_warm_path_ms";
+
+/// Warms the L0 object cache and renders public paths into L1.
+#[derive(Clone)]
+pub struct CacheWarmer {
+    trigger: Arc<CacheTrigger>,
+    router: Router,
+}
+
+impl CacheWarmer {
+    /// Create a warmer that renders paths through `router`.
+    ///
+    /// The router must include the response cache layer so that each
+    /// request lands in L1.
+    pub fn new(trigger: Arc<CacheTrigger>, router: Router) -> Self {
+        Self { trigger, router }
+    }
+
+    /// Run one warm cycle and return the number of paths that rendered.
+    ///
+    /// Does nothing when the cache is disabled. Paths already held by L1
+    /// are served from it and count as warmed.
+    pub async fn warm_cycle(&self) -> usize {
+        let config = self.trigger.config();
+        if !config.is_enabled() {
+            debug!("Scheduled warmup skipped: cache disabled");
+            return 0;
+        }
+
+        self.trigger.warmup_scheduled().await;
+
+        if !config.enable_l1_cache {
+            return 0;
+        }
+
+        let mut warmed = 0;
+        for path in &config.warmup_hot_paths {
+            match self.render("hot_path", path).await {
+                Some(status) if status.is_success() => warmed += 1,
+                Some(status) => {
+                    warn!(path = %path, status = status.as_u16(), "Hot path did not render");
+                }
+                None => {}
+            }
+        }
+
+        debug!(
+            warmed,
+            total = config.warmup_hot_paths.len(),
+            "Warmed: hot paths"
+        );
+        warmed
+    }
+
+    /// Render the targets queued by consumed plans, highest priority first
+    /// and a few at a time, and return the number of paths that rendered.
+    pub async fn warm_queued_targets(&self) -> usize {
+        let config = self.trigger.config();
+        let targets = self.trigger.consumer().take_warm_targets();
+        if !config.enable_l1_cache || targets.is_empty() {
+            return 0;
+        }
+
+        let mut seen = HashSet::new();
+        let paths: Vec<(&'static str, String)> = targets
+            .iter()
+            .flat_map(|target| {
+                target
+                    .paths(&config.warmup_hot_paths)
+                    .into_iter()
+                    .map(move |path| (target.kind(), path))
+            })
+            .filter(|(_, path)| seen.insert(path.clone()))
+            .collect();
+        let total = paths.len();
+
+        let renders: Vec<_> = paths
+            .into_iter()
+            .map(|(kind, path)| self.warm_target(kind, path))
+            .collect();
+        let warmed = stream::iter(renders)
+            .buffer_unordered(TARGET_WARM_CONCURRENCY)
+            .fold(0, |warmed, rendered| {
+                std::future::ready(warmed + usize::from(rendered))
+            })
+            .await;
+
+        debug!(warmed, total, "Warmed: queued targets");
+        warmed
+    }
+
+    /// Warm queued targets as consumed plans queue them; runs until the task
+    /// is aborted.
+    pub async fn run_target_warming(&self) {
+        loop {
+            self.trigger.consumer().warm_targets_queued().await;
+            self.warm_queued_targets().await;
+        }
+    }
+
+    /// Render one queued target, reporting whether it is now cached.
+    async fn warm_target(&self, kind: &'static str, path: String) -> bool {
+        match self.render(kind, &path).await {
+            Some(status) if status.is_success() => true,
+            Some(status) => {
+                // Drafts and deleted entries answer 404; nothing to warm.
+                debug!(path = %path, status = status.as_u16(), "Warm target did not render");
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Request `path` through the router and record how long it took.
+    ///
+    /// Returns the response status, or `None` when the path is invalid or
+    /// did not render in time.
+    async fn render(&self, target: &'static str, path: &str) -> Option<StatusCode> {
+        let request = match Request::builder()
+            .method(Method::GET)
+            .uri(path)
+            .body(Body::empty())
+        {
+            Ok(request) => request,
+            Err(err) => {
+                warn!(path = %path, error = %err, "Skipping invalid warm path");
+                return None;
+            }
+        };
+
+        let started_at = Instant::now();
+        let rendered = tokio::time::timeout(WARM_PATH_TIMEOUT, async {
+            let Ok(response) = self.router.clone().oneshot(request).await;
+            let status = response.status();
+            // Drain the body so streaming handlers finish like a real visit.
+            let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+            status
+        })
+        .await;
+        histogram!(METRIC_CACHE_WARM_PATH_MS, "target" => target)
+            .record(started_at.elapsed().as_secs_f64() * 1000.0);
+
+        match rendered {
+            Ok(status) => Some(status),
+            Err(_) => {
+                warn!(
+                    path = %path,
+                    target,
+                    timeout_ms = WARM_PATH_TIMEOUT.as_millis() as u64,
+                    "Warm path timed out"
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{middleware, routing::get};
+
+    use super::*;
+    use crate::cache::{
+        CacheConfig, CacheConsumer, CacheRegistry, CacheState, EventQueue, L0Store, L1Store,
+        PostListing,
+        keys::{L1Key, OutputFormat, hash_query},
+        response_cache_layer,
+    };
+
+    fn build_warmer(config: CacheConfig, hits: Arc<AtomicUsize>) -> (CacheWarmer, Arc<L1Store>) {
+        let l0 = Arc::new(L0Store::new(&config));
+        let l1 = Arc::new(L1Store::new(&config));
+        let registry = Arc::new(CacheRegistry::new());
+        let queue = Arc::new(EventQueue::new());
+        let consumer = Arc::new(CacheConsumer::new_without_repos(
+            config.clone(),
+            l0,
+            l1.clone(),
+            registry.clone(),
+            queue.clone(),
+        ));
+        let trigger = Arc::new(CacheTrigger::new(config.clone(), queue, consumer));
+
+        let state = CacheState {
+            config,
+            l1: l1.clone(),
+            registry,
+        };
+        let handler = move || {
+            let hits = hits.clone();
+            async move {
+                hits.fetch_add(1, Ordering::Relaxed);
+                "warm"
+            }
+        };
+        let router = Router::new()
+            .route("/", get(handler.clone()))
+            .route("/tags/{tag}", get(handler))
+            .layer(middleware::from_fn_with_state(state, response_cache_layer));
+
+        (CacheWarmer::new(trigger, router), l1)
+    }
+
+    fn html_key(path: &str) -> L1Key {
+        L1Key::Response {
+            format: OutputFormat::Html,
+            path: path.to_string(),
+            query_hash: hash_query(""),
+        }
+    }
+
+    #[tokio::test]
+    async fn warm_cycle_populates_hot_paths() {
+        let config = CacheConfig {
+            warmup_hot_paths: vec!["/".to_string(), "/tags/rust".to_string()],
+            ..Default::default()
+        };
+        let hits = Arc::new(AtomicUsize::new(0));
+        let (warmer, l1) = build_warmer(config, hits.clone());
+
+        assert_eq!(warmer.warm_cycle().await, 2);
+
+        assert!(l1.get(&html_key("/")).is_some());
+        assert!(l1.get(&html_key("/tags/rust")).is_some());
+        assert_eq!(hits.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn queued_targets_of_a_published_post_land_in_l1() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let (warmer, l1) = build_warmer(CacheConfig::default(), hits.clone());
+        let listing = PostListing {
+            tags: ["rust".to_string()].into(),
+            recent: true,
+            ..Default::default()
+        };
+
+        warmer
+            .trigger
+            .post_upserted_in_listing(uuid::Uuid::new_v4(), "hello", None, listing)
+            .await;
+
+        // The test router has no post, feed or sitemap routes.
+        assert_eq!(warmer.warm_queued_targets().await, 2);
+        assert!(l1.get(&html_key("/")).is_some());
+        assert!(l1.get(&html_key("/tags/rust")).is_some());
+        assert_eq!(warmer.warm_queued_targets().await, 0);
+    }
+
+    #[tokio::test]
+    async fn warm_cycle_is_skipped_when_cache_disabled() {
+        let config = CacheConfig {
+            enable_l0_cache: false,
+            enable_l1_cache: false,
+            warmup_hot_paths: vec!["/".to_string()],
+            ..Default::default()
+        };
+        let hits = Arc::new(AtomicUsize::new(0));
+        let (warmer, _) = build_warmer(config, hits.clone());
+
+        assert_eq!(warmer.warm_cycle().await, 0);
+        assert_eq!(hits.load(Ordering::Relaxed), 0);
+    }
+}
@@ -1,9 +1,10 @@
 use std::path::PathBuf;
 
 use clap::{Args, Parser, Subcommand, ValueHint, builder::BoolishValueParser};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 
 /// Command-line arguments for the Soffio binary.
-#[derive(Debug, Parser)]
+#[derive(Debug, Clone, Parser)]
 #[command(name = "soffio", version, about = "Soffio Blog server")]
 pub struct CliArgs {
     /// Optional path to a configuration file.
@@ -24,12 +25,27 @@ pub enum Command {
     /// Export site content and configuration to a TOML archive.
     #[command(name = "export")]
     ExportSite(ExportArgs),
+    /// Write the published site as static HTML, feeds and assets.
+    #[command(name = "export-static")]
+    ExportStatic(ExportStaticArgs),
     /// Import site content and configuration from a TOML archive.
     #[command(name = "import")]
     ImportSite(ImportArgs),
+    /// Create posts from a directory of Markdown files with YAML front matter.
+    #[command(name = "import-markdown")]
+    ImportMarkdown(ImportMarkdownArgs),
     /// Migration utilities.
     #[command(name = "migrations")]
     Migrations(MigrationsArgs),
+    /// Admin account management.
+    #[command(name = "admin")]
+    Admin(AdminArgs),
+    /// Upload maintenance.
+    #[command(name = "uploads")]
+    Uploads(UploadsArgs),
+    /// Check configuration and the environment without starting listeners.
+    #[command(name = "doctor")]
+    Doctor(Box<DoctorArgs>),
 }
 
 #[derive(Debug, Args, Clone)]
@@ -48,6 +64,29 @@ pub struct RenderAllArgs {
     /// Maximum number of concurrent render tasks.
     #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(usize))]
     pub concurrency: usize,
+
+    /// Report how many posts and pages would be rendered, and which have stale
+    /// output, without rendering or writing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Log progress after every N rendered items; 0 disables progress logs.
+    #[arg(long, value_name = "N", default_value_t = 100)]
+    pub progress_every: usize,
+
+    /// Only render the post or page with this slug; repeat for several.
+    #[arg(long = "slug", value_name = "SLUG")]
+    pub slugs: Vec<String>,
+
+    /// Only render content updated after this RFC 3339 timestamp.
+    #[arg(long, value_name = "RFC3339", value_parser = parse_rfc3339)]
+    pub since: Option<OffsetDateTime>,
+}
+
+fn parse_rfc3339(raw: &str) -> Result<OffsetDateTime, String> {
+    OffsetDateTime::parse(raw, &Rfc3339).map_err(|err| {
+        format!("expected an RFC 3339 timestamp such as 2026-01-31T12:00:00Z: {err}")
+    })
 }
 
 #[derive(Debug, Args, Default, Clone)]
@@ -63,6 +102,21 @@ pub struct ServeArgs {
     pub overrides: ServeOverrides,
 }
 
+#[derive(Debug, Args, Default, Clone)]
+pub struct DoctorArgs {
+    #[command(flatten)]
+    pub overrides: ServeOverrides,
+
+    /// Report format.
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        value_parser = ["text", "json"]
+    )]
+    pub output: String,
+}
+
 #[derive(Debug, Args, Default, Clone)]
 pub struct RenderOverrides {
     /// Override the Mermaid CLI executable path used for diagram rendering.
@@ -72,6 +126,37 @@ pub struct RenderOverrides {
     /// Override the directory used to cache rendered Mermaid diagrams.
     #[arg(long = "render-mermaid-cache-dir", value_name = "PATH")]
     pub mermaid_cache_dir: Option<PathBuf>,
+
+    /// Override the comma-separated list of providers embedded from bare links.
+    #[arg(long = "render-embed-providers", value_name = "LIST")]
+    pub embed_providers: Option<String>,
+
+    /// Override which side of post headings carries the deep-link anchor.
+    #[arg(long = "render-heading-anchors", value_name = "PLACEMENT")]
+    pub heading_anchors: Option<String>,
+
+    /// Override the maximum length of excerpts derived from post bodies.
+    #[arg(long = "render-excerpt-length", value_name = "CHARS")]
+    pub excerpt_length: Option<u32>,
+
+    /// Override the largest markdown body accepted by render previews.
+    #[arg(long = "render-preview-max-bytes", value_name = "BYTES")]
+    pub preview_max_bytes: Option<u64>,
+
+    /// Override the per-caller render preview rate limit window size.
+    #[arg(
+        long = "render-preview-rate-limit-window-seconds",
+        value_name = "SECONDS"
+    )]
+    pub preview_rate_limit_window_seconds: Option<u64>,
+
+    /// Override the per-caller render preview request ceiling.
+    #[arg(long = "render-preview-rate-limit-max-requests", value_name = "COUNT")]
+    pub preview_rate_limit_max_requests: Option<u64>,
+
+    /// Override the admin editor's live preview debounce (0 disables it).
+    #[arg(long = "render-preview-debounce-ms", value_name = "MILLISECONDS")]
+    pub preview_debounce_ms: Option<u64>,
 }
 
 #[derive(Debug, Args, Default, Clone)]
@@ -79,14 +164,42 @@ pub struct ServeOverrides {
     #[command(flatten)]
     pub render: RenderOverrides,
 
-    /// Override the public listener host.
+    /// Override the public listener host, or `unix:/path` for a unix socket.
     #[arg(long = "server-host", value_name = "HOST")]
     pub server_host: Option<String>,
 
-    /// Override the administrative listener host.
+    /// Override the administrative listener host, or `unix:/path` for a unix socket.
     #[arg(long = "server-admin-host", value_name = "HOST")]
     pub server_admin_host: Option<String>,
 
+    /// Override the permissions of unix socket files (octal, e.g. `660`).
+    #[arg(long = "server-socket-mode", value_name = "MODE")]
+    pub server_socket_mode: Option<String>,
+
+    /// Serve the public listener over TLS with this PEM certificate chain.
+    #[arg(long = "tls-cert-path", value_name = "PATH")]
+    pub tls_cert_path: Option<String>,
+
+    /// Private key (PEM) for `--tls-cert-path`.
+    #[arg(long = "tls-key-path", value_name = "PATH")]
+    pub tls_key_path: Option<String>,
+
+    /// Serve the admin listener over TLS with this PEM certificate chain.
+    #[arg(long = "admin-tls-cert-path", value_name = "PATH")]
+    pub admin_tls_cert_path: Option<String>,
+
+    /// Private key (PEM) for `--admin-tls-cert-path`.
+    #[arg(long = "admin-tls-key-path", value_name = "PATH")]
+    pub admin_tls_key_path: Option<String>,
+
+    /// Redirect plain HTTP on this port to the TLS public listener.
+    #[arg(long = "http-redirect-port", value_name = "PORT")]
+    pub http_redirect_port: Option<u16>,
+
+    /// Take the listeners from systemd socket activation (`LISTEN_FDS`).
+    #[arg(long = "systemd-socket", action = clap::ArgAction::SetTrue)]
+    pub systemd_socket: bool,
+
     /// Override the public listener port.
     #[arg(long = "server-public-port", value_name = "PORT")]
     pub public_port: Option<u16>,
@@ -99,6 +212,30 @@ pub struct ServeOverrides {
     #[arg(long = "server-graceful-shutdown-seconds", value_name = "SECONDS")]
     pub server_graceful_shutdown_seconds: Option<u64>,
 
+    /// Override the trailing-slash policy for public URLs (preserve|always|never).
+    #[arg(long = "server-trailing-slash", value_name = "POLICY")]
+    pub server_trailing_slash: Option<String>,
+
+    /// Override the path prefix the public site is served under (e.g. `/blog`).
+    #[arg(long = "server-base-path", value_name = "PATH")]
+    pub server_base_path: Option<String>,
+
+    /// Override the base URL API error codes are appended to for `docs_url`.
+    #[arg(long = "server-api-error-docs-url", value_name = "URL")]
+    pub server_api_error_docs_url: Option<String>,
+
+    /// Override the reverse proxies trusted for client addresses (comma-separated CIDRs).
+    #[arg(long = "server-trusted-proxies", value_name = "CIDRS")]
+    pub server_trusted_proxies: Option<String>,
+
+    /// Send Content-Security-Policy violation reports to this URI.
+    #[arg(long = "csp-report-uri", value_name = "URI")]
+    pub csp_report_uri: Option<String>,
+
+    /// Send the policy as Content-Security-Policy-Report-Only, to trial it.
+    #[arg(long = "csp-report-only", action = clap::ArgAction::SetTrue)]
+    pub csp_report_only: bool,
+
     /// Override the base log level (trace|debug|info|warn|error).
     #[arg(long = "log-level", value_name = "LEVEL")]
     pub log_level: Option<String>,
@@ -111,10 +248,18 @@ pub struct ServeOverrides {
     )]
     pub log_json: Option<bool>,
 
+    /// Export traces to this OTLP/HTTP collector endpoint.
+    #[arg(long = "log-otlp-endpoint", value_name = "URL")]
+    pub log_otlp_endpoint: Option<String>,
+
     /// Override the database connection URL.
     #[arg(long = "database-url", value_name = "URL")]
     pub database_url: Option<String>,
 
+    /// Override the read replica URL used for public page reads.
+    #[arg(long = "database-read-url", value_name = "URL")]
+    pub database_read_url: Option<String>,
+
     /// Override the HTTP database pool size.
     #[arg(long = "database-http-max-connections", value_name = "COUNT")]
     pub database_http_max_connections: Option<u32>,
@@ -123,6 +268,18 @@ pub struct ServeOverrides {
     #[arg(long = "database-jobs-max-connections", value_name = "COUNT")]
     pub database_jobs_max_connections: Option<u32>,
 
+    /// Override how long a query waits for a pooled connection, in milliseconds.
+    #[arg(long = "database-acquire-timeout-ms", value_name = "MS")]
+    pub database_acquire_timeout_ms: Option<u64>,
+
+    /// Override the per-statement timeout in milliseconds (0 disables it).
+    #[arg(long = "database-statement-timeout-ms", value_name = "MS")]
+    pub database_statement_timeout_ms: Option<u64>,
+
+    /// Override the slow query warning threshold in milliseconds (0 disables it).
+    #[arg(long = "database-slow-query-log-ms", value_name = "MS")]
+    pub database_slow_query_log_ms: Option<u64>,
+
     /// Override the render-post worker concurrency.
     #[arg(long = "jobs-render-post-concurrency", value_name = "COUNT")]
     pub jobs_render_post_concurrency: Option<u32>,
@@ -143,6 +300,10 @@ pub struct ServeOverrides {
     #[arg(long = "jobs-publish-page-concurrency", value_name = "COUNT")]
     pub jobs_publish_page_concurrency: Option<u32>,
 
+    /// Override how many days trashed posts and pages are kept (0 disables purging).
+    #[arg(long = "jobs-trash-retention-days", value_name = "DAYS")]
+    pub jobs_trash_retention_days: Option<u32>,
+
     /// Override the uploads directory.
     #[arg(long = "uploads-directory", value_name = "PATH")]
     pub uploads_directory: Option<PathBuf>,
@@ -151,6 +312,15 @@ pub struct ServeOverrides {
     #[arg(long = "uploads-max-request-bytes", value_name = "BYTES")]
     pub uploads_max_request_bytes: Option<u64>,
 
+    /// Override the largest single file of a multi-file admin upload in bytes.
+    #[arg(long = "uploads-max-file-bytes", value_name = "BYTES")]
+    pub uploads_max_file_bytes: Option<u64>,
+
+    /// Override how uploads whose contents contradict their declared type
+    /// are handled (reject|detected|declared).
+    #[arg(long = "uploads-content-type-policy", value_name = "POLICY")]
+    pub uploads_content_type_policy: Option<String>,
+
     /// Override the rate limit window size.
     #[arg(long = "rate-limit-window-seconds", value_name = "SECONDS")]
     pub rate_limit_window_seconds: Option<u64>,
@@ -167,10 +337,58 @@ pub struct ServeOverrides {
     #[arg(long = "api-rate-limit-max-requests", value_name = "COUNT")]
     pub api_rate_limit_max_requests: Option<u64>,
 
+    /// Override where API rate limit buckets are kept (memory|postgres).
+    #[arg(long = "api-rate-limit-backend", value_name = "BACKEND")]
+    pub api_rate_limit_backend: Option<String>,
+
+    /// Override the maximum comment body length in characters.
+    #[arg(long = "comments-max-body-chars", value_name = "CHARS")]
+    pub comments_max_body_chars: Option<u64>,
+
+    /// Override the per-IP comment ingest rate limit window size.
+    #[arg(long = "comments-rate-limit-window-seconds", value_name = "SECONDS")]
+    pub comments_rate_limit_window_seconds: Option<u64>,
+
+    /// Override the per-IP comment ingest request ceiling.
+    #[arg(long = "comments-rate-limit-max-requests", value_name = "COUNT")]
+    pub comments_rate_limit_max_requests: Option<u64>,
+
+    /// Override the comma-separated search engine endpoints pinged with the sitemap URL.
+    #[arg(long = "sitemap-ping-urls", value_name = "LIST")]
+    pub sitemap_ping_urls: Option<String>,
+
+    /// Override the admin session lifetime.
+    #[arg(long = "admin-auth-session-ttl-seconds", value_name = "SECONDS")]
+    pub admin_auth_session_ttl_seconds: Option<u64>,
+
+    /// Mark the admin session cookie as `Secure`.
+    #[arg(
+        long = "admin-auth-cookie-secure",
+        value_name = "BOOL",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub admin_auth_cookie_secure: Option<bool>,
+
     /// Override the background scheduler cadence.
     #[arg(long = "scheduler-cadence-seconds", value_name = "SECONDS")]
     pub scheduler_cadence_seconds: Option<u64>,
 
+    /// Evict idle rate limit buckets on each maintenance tick.
+    #[arg(
+        long = "scheduler-evict-rate-limit-buckets",
+        value_name = "BOOL",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub scheduler_evict_rate_limit_buckets: Option<bool>,
+
+    /// Drop expired in-memory cache entries on each maintenance tick.
+    #[arg(
+        long = "scheduler-sweep-expired-cache-entries",
+        value_name = "BOOL",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub scheduler_sweep_expired_cache_entries: Option<bool>,
+
     /// Enable L0 object cache.
     #[arg(
         long = "cache-enable-l0-cache",
@@ -222,6 +440,14 @@ pub struct ServeOverrides {
     /// Override the maximum cache event queue length.
     #[arg(long = "cache-max-event-queue-len", value_name = "COUNT")]
     pub cache_max_event_queue_len: Option<usize>,
+
+    /// Override the comma-separated list of paths warmed on schedule.
+    #[arg(long = "cache-warmup-hot-paths", value_name = "LIST")]
+    pub cache_warmup_hot_paths: Option<String>,
+
+    /// Override the cron expression for the hot path warm cycle.
+    #[arg(long = "cache-warmup-cron", value_name = "CRON")]
+    pub cache_warmup_cron: Option<String>,
 }
 
 #[derive(Debug, Args, Default, Clone)]
@@ -241,6 +467,11 @@ pub struct ExportArgs {
     /// Path to the export file to write.
     #[arg(value_name = "FILE", value_hint = ValueHint::FilePath)]
     pub file: PathBuf,
+
+    /// Also export upload rows, copying their files into `<FILE stem>-uploads`
+    /// next to the archive.
+    #[arg(long)]
+    pub include_uploads: bool,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -251,6 +482,41 @@ pub struct ImportArgs {
     /// Path to the archive to import.
     #[arg(value_name = "FILE", value_hint = ValueHint::FilePath)]
     pub file: PathBuf,
+
+    /// How to treat existing content: `fresh` refuses a non-empty site,
+    /// `merge-skip` keeps existing rows on slug conflicts, `merge-overwrite`
+    /// replaces them, and `wipe` deletes all content first (requires --yes).
+    #[arg(
+        long,
+        value_name = "MODE",
+        default_value = "fresh",
+        value_parser = ["fresh", "merge-skip", "merge-overwrite", "wipe"]
+    )]
+    pub mode: String,
+
+    /// Confirm `--mode wipe`.
+    #[arg(long, required_if_eq("mode", "wipe"))]
+    pub yes: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ExportStaticArgs {
+    #[command(flatten)]
+    pub database: DatabaseOverride,
+
+    /// Directory to write the snapshot into; created if missing.
+    #[arg(value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub dir: PathBuf,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ImportMarkdownArgs {
+    #[command(flatten)]
+    pub database: DatabaseOverride,
+
+    /// Directory whose `.md` files become posts; subdirectories are ignored.
+    #[arg(value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub dir: PathBuf,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -264,6 +530,16 @@ pub enum MigrationsCommand {
     /// Reconcile archive migration entries with the live database.
     #[command(name = "reconcile")]
     Reconcile(MigrationsReconcileArgs),
+    /// List embedded and applied migrations and flag checksum divergence;
+    /// exits non-zero while migrations are pending.
+    #[command(name = "status")]
+    Status(MigrationsStatusArgs),
+    /// List only the embedded migrations not yet applied.
+    #[command(name = "pending")]
+    Pending(MigrationsStatusArgs),
+    /// Exit non-zero when applied migrations diverge from the embedded ones.
+    #[command(name = "verify")]
+    Verify(MigrationsStatusArgs),
 }
 
 #[derive(Debug, Args, Clone)]
@@ -275,3 +551,74 @@ pub struct MigrationsReconcileArgs {
     #[arg(value_name = "ARCHIVE", value_hint = ValueHint::FilePath)]
     pub file: PathBuf,
 }
+
+#[derive(Debug, Args, Clone)]
+pub struct MigrationsStatusArgs {
+    #[command(flatten)]
+    pub database: DatabaseOverride,
+
+    /// Report format.
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        value_parser = ["text", "json"]
+    )]
+    pub output: String,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct AdminArgs {
+    #[command(subcommand)]
+    pub command: AdminCommand,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum AdminCommand {
+    /// Create an account that can sign in to the admin listener.
+    #[command(name = "create-user")]
+    CreateUser(AdminCreateUserArgs),
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct AdminCreateUserArgs {
+    #[command(flatten)]
+    pub database: DatabaseOverride,
+
+    /// Login name for the new account.
+    #[arg(value_name = "USERNAME")]
+    pub username: String,
+
+    /// Password for the new account; read from stdin when omitted.
+    #[arg(
+        long = "password",
+        env = "SOFFIO_ADMIN_PASSWORD",
+        value_name = "PASSWORD",
+        hide_env_values = true
+    )]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct UploadsArgs {
+    #[command(subcommand)]
+    pub command: UploadsCommand,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum UploadsCommand {
+    /// Re-hash every stored upload and flag those that no longer match their
+    /// checksum; exits non-zero when any do.
+    #[command(name = "verify-all")]
+    VerifyAll(UploadsVerifyAllArgs),
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct UploadsVerifyAllArgs {
+    #[command(flatten)]
+    pub database: DatabaseOverride,
+
+    /// Maximum number of files hashed at once.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(usize))]
+    pub concurrency: usize,
+}
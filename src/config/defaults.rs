@@ -5,22 +5,46 @@ pub(super) const DEFAULT_ADMIN_HOST: &str = "127.0.0.1";
 pub(super) const DEFAULT_PUBLIC_PORT: u16 = 3000;
 pub(super) const DEFAULT_ADMIN_PORT: u16 = 3001;
 pub(super) const DEFAULT_GRACEFUL_SHUTDOWN_SECS: u64 = 30;
+pub(super) const DEFAULT_HSTS_MAX_AGE_SECS: u64 = 365 * 24 * 60 * 60;
+pub(super) const DEFAULT_REFERRER_POLICY: &str = "strict-origin-when-cross-origin";
+pub(super) const DEFAULT_API_ERROR_DOCS_URL: &str =
+    crate::infra::http::api::error::DEFAULT_ERROR_DOCS_URL;
 pub(super) const DEFAULT_UPLOAD_DIR: &str = "uploads";
 pub(super) const DEFAULT_RATE_LIMIT_WINDOW_SECS: u64 = 60;
 pub(super) const DEFAULT_RATE_LIMIT_MAX_REQUESTS: u64 = 180;
 pub(super) const DEFAULT_API_RATE_LIMIT_WINDOW_SECS: u64 = 60;
 pub(super) const DEFAULT_API_RATE_LIMIT_MAX_REQUESTS: u64 = 120;
 pub(super) const DEFAULT_SCHEDULER_CADENCE_SECS: u64 = 300;
+pub(super) const DEFAULT_COMMENT_MAX_BODY_CHARS: u64 = 4000;
+pub(super) const DEFAULT_COMMENT_RATE_LIMIT_WINDOW_SECS: u64 = 60;
+pub(super) const DEFAULT_COMMENT_RATE_LIMIT_MAX_REQUESTS: u64 = 10;
+pub(super) const DEFAULT_ADMIN_SESSION_TTL_SECS: u64 = 12 * 60 * 60;
+pub(super) const MAX_ADMIN_SESSION_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+pub(super) const MIN_ADMIN_SESSION_SECRET_LEN: usize = 32;
+pub(super) const DEFAULT_PREVIEW_LINK_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+pub(super) const MAX_PREVIEW_LINK_TTL_SECS: u64 = 365 * 24 * 60 * 60;
 pub(super) const DEFAULT_UPLOAD_REQUEST_LIMIT_BYTES: u64 = 10 * 1024 * 1024;
+pub(super) const DEFAULT_OTLP_SERVICE_NAME: &str = "soffio";
+pub(super) const DEFAULT_OTLP_SAMPLE_RATIO: f64 = 1.0;
 pub(super) const DEFAULT_DB_HTTP_MAX_CONNECTIONS: u32 = 8;
 pub(super) const DEFAULT_DB_JOBS_MAX_CONNECTIONS: u32 = 8;
+pub(super) const DEFAULT_DB_ACQUIRE_TIMEOUT_MS: u64 = 5_000;
+pub(super) const DEFAULT_DB_STATEMENT_TIMEOUT_MS: u64 = 30_000;
+pub(super) const DEFAULT_DB_SLOW_QUERY_LOG_MS: u64 = 500;
 pub(super) const DEFAULT_JOB_RENDER_POST_CONCURRENCY: u32 = 2;
 pub(super) const DEFAULT_JOB_RENDER_SUMMARY_CONCURRENCY: u32 = 2;
 pub(super) const DEFAULT_JOB_RENDER_PAGE_CONCURRENCY: u32 = 1;
 pub(super) const DEFAULT_JOB_PUBLISH_POST_CONCURRENCY: u32 = 1;
 pub(super) const DEFAULT_JOB_PUBLISH_PAGE_CONCURRENCY: u32 = 1;
+pub(super) const DEFAULT_JOB_TRASH_RETENTION_DAYS: u32 = 30;
 pub(crate) const DEFAULT_MERMAID_CLI_PATH: &str = "mmdc";
 pub(crate) const DEFAULT_MERMAID_CACHE_DIR: &str = "/tmp/soffio-mermaid";
+pub(super) const DEFAULT_RENDER_EMBED_PROVIDERS: &str = "youtube,vimeo";
+pub(super) const DEFAULT_RENDER_EXCERPT_LENGTH: u32 = 200;
+pub(super) const DEFAULT_RENDER_PREVIEW_MAX_BYTES: u64 = 256 * 1024;
+pub(super) const DEFAULT_RENDER_PREVIEW_RATE_LIMIT_WINDOW_SECS: u64 = 60;
+pub(super) const DEFAULT_RENDER_PREVIEW_RATE_LIMIT_MAX_REQUESTS: u64 = 30;
+pub(super) const DEFAULT_RENDER_PREVIEW_DEBOUNCE_MS: u64 = 300;
 
 pub(super) const DEFAULT_CACHE_L0_POST_LIMIT: usize = 500;
 pub(super) const DEFAULT_CACHE_L0_PAGE_LIMIT: usize = 100;
@@ -31,3 +55,5 @@ pub(super) const DEFAULT_CACHE_L1_RESPONSE_BODY_LIMIT_BYTES: usize = 1_048_576;
 pub(super) const DEFAULT_CACHE_AUTO_CONSUME_INTERVAL_MS: u64 = 5000;
 pub(super) const DEFAULT_CACHE_CONSUME_BATCH_LIMIT: usize = 100;
 pub(super) const DEFAULT_CACHE_MAX_EVENT_QUEUE_LEN: usize = 2048;
+pub(super) const DEFAULT_CACHE_WARMUP_HOT_PATHS: &str = "/,/rss.xml,/sitemap.xml";
+pub(super) const DEFAULT_CACHE_WARMUP_CRON: &str = "0 */5 * * * *";
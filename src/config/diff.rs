@@ -0,0 +1,310 @@
+//! Setting-by-setting comparison of two resolved configurations, used when
+//! `serve` reloads its configuration at runtime.
+
+use std::fmt::Debug;
+
+use serde::Serialize;
+
+use super::types::Settings;
+
+/// Settings a running `serve` applies on reload; every other change waits
+/// for a restart.
+pub const RELOADABLE_KEYS: &[&str] = &[
+    "logging.level",
+    "uploads.max_request_bytes",
+    "api_rate_limit.window_seconds",
+    "api_rate_limit.max_requests",
+    "comments.rate_limit_window_seconds",
+    "comments.rate_limit_max_requests",
+    "render.preview_rate_limit_window_seconds",
+    "render.preview_rate_limit_max_requests",
+    "cache.auto_consume_interval_ms",
+];
+
+/// Settings that are accepted but read by nothing at runtime, so changing
+/// them has no effect with or without a restart.
+pub const INERT_KEYS: &[&str] = &["rate_limit.window_seconds", "rate_limit.max_requests"];
+
+/// Settings whose values never leave the process.
+const SECRET_KEYS: &[&str] = &[
+    "database.url",
+    "database.read_url",
+    "admin_auth.session_secret",
+];
+
+const REDACTED: &str = "<redacted>";
+const UNSET: &str = "None";
+
+/// Old and new value of one setting, rendered for logs and audit entries.
+/// Secrets are shown as `<redacted>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettingChange {
+    pub key: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// Changed settings, split by whether a reload can apply them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SettingsDiff {
+    pub applied: Vec<SettingChange>,
+    /// Changes that take effect only after a restart.
+    pub deferred: Vec<SettingChange>,
+    /// Changes to [`INERT_KEYS`], which take effect neither now nor after a
+    /// restart.
+    pub inert: Vec<SettingChange>,
+}
+
+impl SettingsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.deferred.is_empty() && self.inert.is_empty()
+    }
+}
+
+/// Compare every setting of `old` and `new`.
+pub fn diff_settings(old: &Settings, new: &Settings) -> SettingsDiff {
+    let mut diff = SettingsDiff::default();
+    for ((key, old), (_, new)) in setting_values(old).into_iter().zip(setting_values(new)) {
+        if old == new {
+            continue;
+        }
+        let change = if SECRET_KEYS.contains(&key) {
+            SettingChange {
+                key,
+                old: redact(old),
+                new: redact(new),
+            }
+        } else {
+            SettingChange { key, old, new }
+        };
+        if RELOADABLE_KEYS.contains(&key) {
+            diff.applied.push(change);
+        } else if INERT_KEYS.contains(&key) {
+            diff.inert.push(change);
+        } else {
+            diff.deferred.push(change);
+        }
+    }
+    diff
+}
+
+fn shown<T: Debug>(value: T) -> String {
+    format!("{value:?}")
+}
+
+fn redact(value: String) -> String {
+    if value == UNSET {
+        value
+    } else {
+        REDACTED.to_string()
+    }
+}
+
+/// Every setting as `(key, value)`, in a fixed order so two calls zip.
+fn setting_values(settings: &Settings) -> Vec<(&'static str, String)> {
+    let Settings {
+        server,
+        logging,
+        database,
+        jobs,
+        render,
+        uploads,
+        rate_limit,
+        api_rate_limit,
+        scheduler,
+        cache,
+        comments,
+        sitemap,
+        admin_auth,
+        slugs,
+    } = settings;
+
+    vec![
+        ("server.public_addr", server.public_addr.to_string()),
+        ("server.admin_addr", server.admin_addr.to_string()),
+        ("server.socket_mode", shown(server.socket_mode)),
+        ("server.systemd_socket", server.systemd_socket.to_string()),
+        ("server.tls", shown(&server.tls)),
+        ("server.admin_tls", shown(&server.admin_tls)),
+        (
+            "server.http_redirect_port",
+            shown(server.http_redirect_port),
+        ),
+        ("server.graceful_shutdown", shown(server.graceful_shutdown)),
+        (
+            "server.trailing_slash",
+            server.trailing_slash.as_str().to_string(),
+        ),
+        ("server.base_path", server.base_path.as_str().to_string()),
+        ("server.trusted_proxies", shown(&server.trusted_proxies)),
+        ("server.security_headers", shown(&server.security_headers)),
+        (
+            "server.api_error_docs_url",
+            server.api_error_docs_url.clone(),
+        ),
+        ("logging.level", logging.level.to_string()),
+        ("logging.format", shown(logging.format)),
+        (
+            "logging.otlp",
+            shown(logging.otlp.as_ref().map(|otlp| &otlp.endpoint)),
+        ),
+        ("database.url", shown(database.url.as_deref())),
+        ("database.read_url", shown(database.read_url.as_deref())),
+        (
+            "database.http_max_connections",
+            database.http_max_connections.to_string(),
+        ),
+        (
+            "database.jobs_max_connections",
+            database.jobs_max_connections.to_string(),
+        ),
+        ("database.acquire_timeout", shown(database.acquire_timeout)),
+        (
+            "database.statement_timeout",
+            shown(database.statement_timeout),
+        ),
+        ("database.slow_query_log", shown(database.slow_query_log)),
+        (
+            "jobs.render_post_concurrency",
+            jobs.render_post_concurrency.to_string(),
+        ),
+        (
+            "jobs.render_summary_concurrency",
+            jobs.render_summary_concurrency.to_string(),
+        ),
+        (
+            "jobs.render_page_concurrency",
+            jobs.render_page_concurrency.to_string(),
+        ),
+        (
+            "jobs.publish_post_concurrency",
+            jobs.publish_post_concurrency.to_string(),
+        ),
+        (
+            "jobs.publish_page_concurrency",
+            jobs.publish_page_concurrency.to_string(),
+        ),
+        ("jobs.trash_retention", shown(jobs.trash_retention)),
+        ("render.mermaid_cli_path", shown(&render.mermaid_cli_path)),
+        ("render.mermaid_cache_dir", shown(&render.mermaid_cache_dir)),
+        ("render.embed_providers", shown(&render.embed_providers)),
+        (
+            "render.heading_anchors",
+            render.heading_anchors.as_str().to_string(),
+        ),
+        ("render.excerpt_length", render.excerpt_length.to_string()),
+        (
+            "render.preview_max_bytes",
+            render.preview_max_bytes.to_string(),
+        ),
+        (
+            "render.preview_rate_limit_window_seconds",
+            render.preview_rate_limit_window_seconds.to_string(),
+        ),
+        (
+            "render.preview_rate_limit_max_requests",
+            render.preview_rate_limit_max_requests.to_string(),
+        ),
+        ("render.preview_debounce", shown(render.preview_debounce)),
+        ("uploads.directory", shown(&uploads.directory)),
+        (
+            "uploads.max_request_bytes",
+            uploads.max_request_bytes.to_string(),
+        ),
+        ("uploads.max_file_bytes", shown(uploads.max_file_bytes)),
+        (
+            "uploads.content_type_policy",
+            uploads.content_type_policy.as_str().to_string(),
+        ),
+        (
+            "rate_limit.window_seconds",
+            rate_limit.window_seconds.to_string(),
+        ),
+        (
+            "rate_limit.max_requests",
+            rate_limit.max_requests.to_string(),
+        ),
+        (
+            "api_rate_limit.window_seconds",
+            api_rate_limit.window_seconds.to_string(),
+        ),
+        (
+            "api_rate_limit.max_requests",
+            api_rate_limit.max_requests.to_string(),
+        ),
+        (
+            "api_rate_limit.backend",
+            api_rate_limit.backend.as_str().to_string(),
+        ),
+        ("scheduler.cadence", shown(scheduler.cadence)),
+        (
+            "scheduler.evict_rate_limit_buckets",
+            scheduler.evict_rate_limit_buckets.to_string(),
+        ),
+        (
+            "scheduler.sweep_expired_cache_entries",
+            scheduler.sweep_expired_cache_entries.to_string(),
+        ),
+        ("cache.enable_l0_cache", cache.enable_l0_cache.to_string()),
+        ("cache.enable_l1_cache", cache.enable_l1_cache.to_string()),
+        ("cache.l0_post_limit", cache.l0_post_limit.to_string()),
+        ("cache.l0_page_limit", cache.l0_page_limit.to_string()),
+        ("cache.l0_api_key_limit", cache.l0_api_key_limit.to_string()),
+        (
+            "cache.l0_post_list_limit",
+            cache.l0_post_list_limit.to_string(),
+        ),
+        (
+            "cache.l1_response_limit",
+            cache.l1_response_limit.to_string(),
+        ),
+        (
+            "cache.l1_response_body_limit_bytes",
+            cache.l1_response_body_limit_bytes.to_string(),
+        ),
+        (
+            "cache.auto_consume_interval_ms",
+            cache.auto_consume_interval_ms.to_string(),
+        ),
+        (
+            "cache.consume_batch_limit",
+            cache.consume_batch_limit.to_string(),
+        ),
+        (
+            "cache.max_event_queue_len",
+            cache.max_event_queue_len.to_string(),
+        ),
+        ("cache.warmup_hot_paths", shown(&cache.warmup_hot_paths)),
+        ("cache.warmup_cron", cache.warmup_cron.clone()),
+        (
+            "comments.max_body_chars",
+            comments.max_body_chars.to_string(),
+        ),
+        (
+            "comments.rate_limit_window_seconds",
+            comments.rate_limit_window_seconds.to_string(),
+        ),
+        (
+            "comments.rate_limit_max_requests",
+            comments.rate_limit_max_requests.to_string(),
+        ),
+        ("sitemap.ping_urls", shown(&sitemap.ping_urls)),
+        (
+            "admin_auth.session_secret",
+            shown(admin_auth.session_secret.as_deref()),
+        ),
+        ("admin_auth.session_ttl", shown(admin_auth.session_ttl)),
+        (
+            "admin_auth.preview_link_ttl",
+            shown(admin_auth.preview_link_ttl),
+        ),
+        (
+            "admin_auth.cookie_secure",
+            admin_auth.cookie_secure.to_string(),
+        ),
+        ("slugs.transliterate", slugs.transliterate.to_string()),
+        ("slugs.allow_unicode", slugs.allow_unicode.to_string()),
+        ("slugs.date_prefix", slugs.date_prefix.to_string()),
+        ("slugs.hash_suffix", slugs.hash_suffix.to_string()),
+    ]
+}
@@ -1,36 +1,55 @@
 use std::{
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     num::{NonZeroU32, NonZeroU64},
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
     time::Duration,
 };
 
 use clap::Parser;
-use config::{Config, Environment, File};
+use config::{Config, Environment, File, Source};
 use serde::Deserialize;
 use tracing::level_filters::LevelFilter;
+use url::Url;
 
-use super::cli::{CliArgs, Command, MigrationsCommand, ServeOverrides};
+use crate::domain::routes::BasePath;
+use crate::domain::slug::SlugStrategy;
+use crate::domain::uploads::ContentTypePolicy;
+
+use super::cli::{
+    AdminCommand, CliArgs, Command, MigrationsCommand, ServeOverrides, UploadsCommand,
+};
 use super::defaults::{
-    DEFAULT_ADMIN_HOST, DEFAULT_ADMIN_PORT, DEFAULT_API_RATE_LIMIT_MAX_REQUESTS,
+    DEFAULT_ADMIN_HOST, DEFAULT_ADMIN_PORT, DEFAULT_ADMIN_SESSION_TTL_SECS,
+    DEFAULT_API_ERROR_DOCS_URL, DEFAULT_API_RATE_LIMIT_MAX_REQUESTS,
     DEFAULT_API_RATE_LIMIT_WINDOW_SECS, DEFAULT_CACHE_AUTO_CONSUME_INTERVAL_MS,
     DEFAULT_CACHE_CONSUME_BATCH_LIMIT, DEFAULT_CACHE_L0_API_KEY_LIMIT, DEFAULT_CACHE_L0_PAGE_LIMIT,
     DEFAULT_CACHE_L0_POST_LIMIT, DEFAULT_CACHE_L0_POST_LIST_LIMIT,
     DEFAULT_CACHE_L1_RESPONSE_BODY_LIMIT_BYTES, DEFAULT_CACHE_L1_RESPONSE_LIMIT,
-    DEFAULT_CACHE_MAX_EVENT_QUEUE_LEN, DEFAULT_CONFIG_BASENAME, DEFAULT_DB_HTTP_MAX_CONNECTIONS,
-    DEFAULT_DB_JOBS_MAX_CONNECTIONS, DEFAULT_GRACEFUL_SHUTDOWN_SECS, DEFAULT_HOST,
-    DEFAULT_JOB_PUBLISH_PAGE_CONCURRENCY, DEFAULT_JOB_PUBLISH_POST_CONCURRENCY,
-    DEFAULT_JOB_RENDER_PAGE_CONCURRENCY, DEFAULT_JOB_RENDER_POST_CONCURRENCY,
-    DEFAULT_JOB_RENDER_SUMMARY_CONCURRENCY, DEFAULT_MERMAID_CACHE_DIR, DEFAULT_MERMAID_CLI_PATH,
+    DEFAULT_CACHE_MAX_EVENT_QUEUE_LEN, DEFAULT_CACHE_WARMUP_CRON, DEFAULT_CACHE_WARMUP_HOT_PATHS,
+    DEFAULT_COMMENT_MAX_BODY_CHARS, DEFAULT_COMMENT_RATE_LIMIT_MAX_REQUESTS,
+    DEFAULT_COMMENT_RATE_LIMIT_WINDOW_SECS, DEFAULT_CONFIG_BASENAME, DEFAULT_DB_ACQUIRE_TIMEOUT_MS,
+    DEFAULT_DB_HTTP_MAX_CONNECTIONS, DEFAULT_DB_JOBS_MAX_CONNECTIONS, DEFAULT_DB_SLOW_QUERY_LOG_MS,
+    DEFAULT_DB_STATEMENT_TIMEOUT_MS, DEFAULT_GRACEFUL_SHUTDOWN_SECS, DEFAULT_HOST,
+    DEFAULT_HSTS_MAX_AGE_SECS, DEFAULT_JOB_PUBLISH_PAGE_CONCURRENCY,
+    DEFAULT_JOB_PUBLISH_POST_CONCURRENCY, DEFAULT_JOB_RENDER_PAGE_CONCURRENCY,
+    DEFAULT_JOB_RENDER_POST_CONCURRENCY, DEFAULT_JOB_RENDER_SUMMARY_CONCURRENCY,
+    DEFAULT_JOB_TRASH_RETENTION_DAYS, DEFAULT_MERMAID_CACHE_DIR, DEFAULT_MERMAID_CLI_PATH,
+    DEFAULT_OTLP_SAMPLE_RATIO, DEFAULT_OTLP_SERVICE_NAME, DEFAULT_PREVIEW_LINK_TTL_SECS,
     DEFAULT_PUBLIC_PORT, DEFAULT_RATE_LIMIT_MAX_REQUESTS, DEFAULT_RATE_LIMIT_WINDOW_SECS,
+    DEFAULT_REFERRER_POLICY, DEFAULT_RENDER_EMBED_PROVIDERS, DEFAULT_RENDER_EXCERPT_LENGTH,
+    DEFAULT_RENDER_PREVIEW_DEBOUNCE_MS, DEFAULT_RENDER_PREVIEW_MAX_BYTES,
+    DEFAULT_RENDER_PREVIEW_RATE_LIMIT_MAX_REQUESTS, DEFAULT_RENDER_PREVIEW_RATE_LIMIT_WINDOW_SECS,
     DEFAULT_SCHEDULER_CADENCE_SECS, DEFAULT_UPLOAD_DIR, DEFAULT_UPLOAD_REQUEST_LIMIT_BYTES,
-    LOCAL_CONFIG_BASENAME,
+    LOCAL_CONFIG_BASENAME, MAX_ADMIN_SESSION_TTL_SECS, MAX_PREVIEW_LINK_TTL_SECS,
+    MIN_ADMIN_SESSION_SECRET_LEN,
 };
 use super::types::{
-    ApiRateLimitSettings, CacheSettings, DatabaseSettings, JobsSettings, LoadError, LogFormat,
-    LoggingSettings, RateLimitSettings, RenderSettings, SchedulerSettings, ServerSettings,
-    Settings, UploadSettings,
+    AdminAuthSettings, ApiRateLimitSettings, CacheSettings, CommentSettings, DatabaseSettings,
+    EmbedProvider, HeadingAnchorPlacement, IpCidr, JobsSettings, ListenAddr, LoadError, LogFormat,
+    LoggingSettings, OtlpSettings, RateLimitBackend, RateLimitSettings, RenderSettings,
+    SchedulerSettings, SecurityHeadersSettings, ServerSettings, Settings, SitemapSettings,
+    TlsSettings, TrailingSlashPolicy, UploadSettings,
 };
 
 /// Load settings using the configured precedence (file → environment → CLI).
@@ -43,19 +62,31 @@ pub fn load(cli: &CliArgs) -> Result<Settings, LoadError> {
         builder = builder.add_source(File::from(path.as_path()).required(true));
     }
 
-    builder = builder.add_source(Environment::with_prefix("SOFFIO").separator("__"));
+    builder = builder.add_source(environment_source());
 
     let mut raw: RawSettings = builder.build()?.try_deserialize()?;
 
     match cli.command.as_ref() {
         Some(Command::Serve(args)) => raw.apply_serve_overrides(&args.overrides),
+        Some(Command::Doctor(args)) => raw.apply_serve_overrides(&args.overrides),
         Some(Command::RenderAll(args)) => raw.apply_renderall_overrides(&args.overrides),
         Some(Command::ExportSite(args)) => raw.apply_database_override(&args.database),
+        Some(Command::ExportStatic(args)) => raw.apply_database_override(&args.database),
         Some(Command::ImportSite(args)) => raw.apply_database_override(&args.database),
+        Some(Command::ImportMarkdown(args)) => raw.apply_database_override(&args.database),
         Some(Command::Migrations(args)) => match &args.command {
             MigrationsCommand::Reconcile(reconcile) => {
                 raw.apply_database_override(&reconcile.database)
             }
+            MigrationsCommand::Status(status)
+            | MigrationsCommand::Pending(status)
+            | MigrationsCommand::Verify(status) => raw.apply_database_override(&status.database),
+        },
+        Some(Command::Admin(args)) => match &args.command {
+            AdminCommand::CreateUser(create) => raw.apply_database_override(&create.database),
+        },
+        Some(Command::Uploads(args)) => match &args.command {
+            UploadsCommand::VerifyAll(verify) => raw.apply_database_override(&verify.database),
         },
         None => raw.apply_serve_overrides(&ServeOverrides::default()),
     }
@@ -63,6 +94,68 @@ pub fn load(cli: &CliArgs) -> Result<Settings, LoadError> {
     Settings::from_raw(raw)
 }
 
+/// A configuration source and the keys it sets, in dotted form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigSource {
+    pub name: String,
+    pub keys: Vec<String>,
+}
+
+/// The sources `load` merges, in the order it applies them, each read on its
+/// own so callers can tell which one supplied a value: a later source wins,
+/// and CLI flags override them all.
+pub fn config_sources(config_file: Option<&Path>) -> Result<Vec<ConfigSource>, LoadError> {
+    let mut sources = vec![
+        read_source(
+            format!("{DEFAULT_CONFIG_BASENAME}.*"),
+            File::with_name(DEFAULT_CONFIG_BASENAME).required(false),
+        )?,
+        read_source(
+            format!("{LOCAL_CONFIG_BASENAME}.*"),
+            File::with_name(LOCAL_CONFIG_BASENAME).required(false),
+        )?,
+    ];
+    if let Some(path) = config_file {
+        sources.push(read_source(
+            path.display().to_string(),
+            File::from(path).required(true),
+        )?);
+    }
+    sources.push(read_source(
+        "environment (SOFFIO__*)".to_string(),
+        environment_source(),
+    )?);
+    Ok(sources)
+}
+
+fn environment_source() -> Environment {
+    Environment::with_prefix("SOFFIO").separator("__")
+}
+
+fn read_source(
+    name: String,
+    source: impl Source + Send + Sync + 'static,
+) -> Result<ConfigSource, LoadError> {
+    let values = Config::builder().add_source(source).build()?.collect()?;
+    let mut keys = Vec::new();
+    for (key, value) in values {
+        flatten_keys(key, value, &mut keys);
+    }
+    keys.sort();
+    Ok(ConfigSource { name, keys })
+}
+
+fn flatten_keys(prefix: String, value: config::Value, keys: &mut Vec<String>) {
+    match value.clone().into_table() {
+        Ok(table) => {
+            for (key, nested) in table {
+                flatten_keys(format!("{prefix}.{key}"), nested, keys);
+            }
+        }
+        Err(_) => keys.push(prefix),
+    }
+}
+
 /// Resolve configuration using the supplied CLI arguments, returning both for downstream use.
 pub fn load_with_cli() -> Result<(CliArgs, Settings), LoadError> {
     let args = CliArgs::parse();
@@ -83,6 +176,10 @@ pub(super) struct RawSettings {
     pub(super) api_rate_limit: RawApiRateLimitSettings,
     pub(super) scheduler: RawSchedulerSettings,
     pub(super) cache: RawCacheSettings,
+    pub(super) comments: RawCommentSettings,
+    pub(super) sitemap: RawSitemapSettings,
+    pub(super) admin_auth: RawAdminAuthSettings,
+    pub(super) slugs: RawSlugSettings,
 }
 
 impl Settings {
@@ -98,6 +195,10 @@ impl Settings {
             api_rate_limit,
             scheduler,
             cache,
+            comments,
+            sitemap,
+            admin_auth,
+            slugs,
         } = raw;
 
         let server = build_server_settings(server)?;
@@ -110,6 +211,10 @@ impl Settings {
         let api_rate_limit = build_api_rate_limit_settings(api_rate_limit)?;
         let scheduler = build_scheduler_settings(scheduler)?;
         let cache = build_cache_settings(cache)?;
+        let comments = build_comment_settings(comments)?;
+        let sitemap = build_sitemap_settings(sitemap)?;
+        let admin_auth = build_admin_auth_settings(admin_auth)?;
+        let slugs = build_slug_strategy(slugs);
 
         Ok(Self {
             server,
@@ -122,6 +227,10 @@ impl Settings {
             api_rate_limit,
             scheduler,
             cache,
+            comments,
+            sitemap,
+            admin_auth,
+            slugs,
         })
     }
 }
@@ -148,10 +257,53 @@ fn build_server_settings(server: RawServerSettings) -> Result<ServerSettings, Lo
         ));
     }
 
-    let public_addr = parse_socket_addr(&host, public_port)
+    let public_addr = parse_listen_addr(&host, public_port)
         .map_err(|reason| LoadError::invalid("server.public_addr", reason))?;
-    let admin_addr = parse_socket_addr(&admin_host, admin_port)
+    let admin_addr = parse_listen_addr(&admin_host, admin_port)
         .map_err(|reason| LoadError::invalid("server.admin_addr", reason))?;
+    if public_addr == admin_addr && matches!(public_addr, ListenAddr::Unix(_)) {
+        return Err(LoadError::invalid(
+            "server.admin_addr",
+            "the public and admin listeners need different socket paths",
+        ));
+    }
+
+    let socket_mode = server
+        .socket_mode
+        .as_deref()
+        .map(parse_socket_mode)
+        .transpose()
+        .map_err(|reason| LoadError::invalid("server.socket_mode", reason))?;
+
+    let tls = build_tls_settings("server.tls", server.tls)?;
+    let admin_tls = build_tls_settings("server.admin_tls", server.admin_tls)?;
+    if tls.is_some() && matches!(public_addr, ListenAddr::Unix(_)) {
+        return Err(LoadError::invalid(
+            "server.tls",
+            "TLS needs a TCP listener; terminate TLS in front of unix sockets",
+        ));
+    }
+    if admin_tls.is_some() && matches!(admin_addr, ListenAddr::Unix(_)) {
+        return Err(LoadError::invalid(
+            "server.admin_tls",
+            "TLS needs a TCP listener; terminate TLS in front of unix sockets",
+        ));
+    }
+    let http_redirect_port = server.http_redirect_port.filter(|port| *port != 0);
+    if let Some(port) = http_redirect_port {
+        if tls.is_none() {
+            return Err(LoadError::invalid(
+                "server.http_redirect_port",
+                "requires server.tls to be configured",
+            ));
+        }
+        if port == public_port || port == admin_port {
+            return Err(LoadError::invalid(
+                "server.http_redirect_port",
+                format!("port {port} is already used by another listener"),
+            ));
+        }
+    }
 
     let graceful_secs = server
         .graceful_shutdown_seconds
@@ -164,13 +316,180 @@ fn build_server_settings(server: RawServerSettings) -> Result<ServerSettings, Lo
     }
     let graceful_shutdown = Duration::from_secs(graceful_secs);
 
+    let trailing_slash = match server.trailing_slash.as_deref() {
+        Some(value) => TrailingSlashPolicy::parse(value).ok_or_else(|| {
+            LoadError::invalid(
+                "server.trailing_slash",
+                format!("unknown policy `{value}`; expected preserve, always or never"),
+            )
+        })?,
+        None => TrailingSlashPolicy::default(),
+    };
+
+    let base_path_value = server.base_path.as_deref().unwrap_or_default();
+    let base_path = BasePath::parse(base_path_value).ok_or_else(|| {
+        LoadError::invalid(
+            "server.base_path",
+            format!(
+                "invalid base path `{base_path_value}`; expected segments of letters, digits, `-`, `_`, `.` or `~`"
+            ),
+        )
+    })?;
+
+    let trusted_proxies = server
+        .trusted_proxies
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            parse_cidr(entry).ok_or_else(|| {
+                LoadError::invalid(
+                    "server.trusted_proxies",
+                    format!("`{entry}` is not an IP address or CIDR block"),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let security_headers = build_security_headers_settings(server.security_headers)?;
+
+    let api_error_docs_url = server
+        .api_error_docs_url
+        .unwrap_or_else(|| DEFAULT_API_ERROR_DOCS_URL.to_string());
+    if !(api_error_docs_url.starts_with("https://") || api_error_docs_url.starts_with("http://")) {
+        return Err(LoadError::invalid(
+            "server.api_error_docs_url",
+            "must be an http:// or https:// URL",
+        ));
+    }
+
     Ok(ServerSettings {
         public_addr,
         admin_addr,
+        socket_mode,
+        systemd_socket: server.systemd_socket.unwrap_or(false),
+        tls,
+        admin_tls,
+        http_redirect_port,
         graceful_shutdown,
+        trailing_slash,
+        base_path,
+        trusted_proxies,
+        security_headers,
+        api_error_docs_url,
     })
 }
 
+/// Text that can be sent as an HTTP header value as is.
+fn is_header_text(value: &str) -> bool {
+    value.bytes().all(|b| b == b' ' || b.is_ascii_graphic())
+}
+
+/// Values accepted by `Referrer-Policy`.
+const REFERRER_POLICIES: &[&str] = &[
+    "no-referrer",
+    "no-referrer-when-downgrade",
+    "origin",
+    "origin-when-cross-origin",
+    "same-origin",
+    "strict-origin",
+    "strict-origin-when-cross-origin",
+    "unsafe-url",
+];
+
+fn build_security_headers_settings(
+    headers: RawSecurityHeadersSettings,
+) -> Result<SecurityHeadersSettings, LoadError> {
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let content_security_policy = non_empty(headers.content_security_policy);
+    if let Some(policy) = &content_security_policy
+        && !is_header_text(policy)
+    {
+        return Err(LoadError::invalid(
+            "server.security_headers.content_security_policy",
+            "must be a single line of printable ASCII",
+        ));
+    }
+
+    let report_uri = non_empty(headers.report_uri);
+    if let Some(uri) = &report_uri
+        && (!is_header_text(uri) || (!uri.starts_with('/') && Url::parse(uri).is_err()))
+    {
+        return Err(LoadError::invalid(
+            "server.security_headers.report_uri",
+            format!("`{uri}` is neither an absolute URL nor a path starting with `/`"),
+        ));
+    }
+
+    let referrer_policy = non_empty(headers.referrer_policy)
+        .unwrap_or_else(|| DEFAULT_REFERRER_POLICY.to_string())
+        .to_ascii_lowercase();
+    if !REFERRER_POLICIES.contains(&referrer_policy.as_str()) {
+        return Err(LoadError::invalid(
+            "server.security_headers.referrer_policy",
+            format!("unknown policy `{referrer_policy}`"),
+        ));
+    }
+
+    let hsts_max_age = match headers
+        .hsts_max_age_seconds
+        .unwrap_or(DEFAULT_HSTS_MAX_AGE_SECS)
+    {
+        0 => None,
+        seconds => Some(Duration::from_secs(seconds)),
+    };
+
+    Ok(SecurityHeadersSettings {
+        enabled: headers.enabled.unwrap_or(true),
+        content_security_policy,
+        report_uri,
+        report_only: headers.report_only.unwrap_or(false),
+        hsts_max_age,
+        referrer_policy,
+    })
+}
+
+/// `addr/prefix`, or a bare address for a single host.
+fn parse_cidr(value: &str) -> Option<IpCidr> {
+    let (addr, prefix_len) = match value.split_once('/') {
+        Some((addr, prefix_len)) => (addr, Some(prefix_len.parse::<u8>().ok()?)),
+        None => (value, None),
+    };
+    let addr = IpAddr::from_str(addr).ok()?;
+    let prefix_len = prefix_len.unwrap_or(if addr.is_ipv4() { 32 } else { 128 });
+    IpCidr::new(addr, prefix_len)
+}
+
+/// `None` unless a certificate is configured; the key is then required.
+fn build_tls_settings(
+    key: &'static str,
+    tls: RawTlsSettings,
+) -> Result<Option<TlsSettings>, LoadError> {
+    let path = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    let Some(cert_path) = path(tls.cert_path) else {
+        return Ok(None);
+    };
+    let key_path = path(tls.key_path)
+        .ok_or_else(|| LoadError::invalid(key, "key_path is required when cert_path is set"))?;
+    Ok(Some(TlsSettings {
+        cert_path,
+        key_path,
+        client_ca_path: path(tls.client_ca_path),
+    }))
+}
+
 fn build_logging_settings(logging: RawLoggingSettings) -> Result<LoggingSettings, LoadError> {
     let level = match logging.level {
         Some(level) => LevelFilter::from_str(level.as_str()).map_err(|err| {
@@ -185,7 +504,71 @@ fn build_logging_settings(logging: RawLoggingSettings) -> Result<LoggingSettings
         LogFormat::Compact
     };
 
-    Ok(LoggingSettings { level, format })
+    let otlp = build_otlp_settings(logging.otlp)?;
+
+    Ok(LoggingSettings {
+        level,
+        format,
+        otlp,
+    })
+}
+
+fn build_otlp_settings(otlp: RawOtlpSettings) -> Result<Option<OtlpSettings>, LoadError> {
+    let Some(endpoint) = otlp
+        .endpoint
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(None);
+    };
+    match Url::parse(&endpoint) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+        _ => {
+            return Err(LoadError::invalid(
+                "logging.otlp.endpoint",
+                "must be an http or https URL",
+            ));
+        }
+    }
+
+    let service_name = otlp
+        .service_name
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| DEFAULT_OTLP_SERVICE_NAME.to_string());
+
+    let sample_ratio = otlp.sample_ratio.unwrap_or(DEFAULT_OTLP_SAMPLE_RATIO);
+    if !(0.0..=1.0).contains(&sample_ratio) {
+        return Err(LoadError::invalid(
+            "logging.otlp.sample_ratio",
+            "must be between 0.0 and 1.0",
+        ));
+    }
+
+    let headers = otlp
+        .headers
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => {
+                Ok((name.trim().to_string(), value.trim().to_string()))
+            }
+            _ => Err(LoadError::invalid(
+                "logging.otlp.headers",
+                "expected comma-separated `name=value` pairs",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(OtlpSettings {
+        endpoint,
+        service_name,
+        sample_ratio,
+        headers,
+    }))
 }
 
 fn build_database_settings(database: RawDatabaseSettings) -> Result<DatabaseSettings, LoadError> {
@@ -193,6 +576,10 @@ fn build_database_settings(database: RawDatabaseSettings) -> Result<DatabaseSett
         let trimmed = value.trim();
         (!trimmed.is_empty()).then(|| trimmed.to_string())
     });
+    let read_url = database.read_url.and_then(|value| {
+        let trimmed = value.trim();
+        (!trimmed.is_empty()).then(|| trimmed.to_string())
+    });
 
     let http_value = database
         .http_max_connections
@@ -204,10 +591,39 @@ fn build_database_settings(database: RawDatabaseSettings) -> Result<DatabaseSett
     let http_max_connections = non_zero_u32(http_value.into(), "database.http_max_connections")?;
     let jobs_max_connections = non_zero_u32(jobs_value.into(), "database.jobs_max_connections")?;
 
+    let acquire_timeout_ms = database
+        .acquire_timeout_ms
+        .unwrap_or(DEFAULT_DB_ACQUIRE_TIMEOUT_MS);
+    if acquire_timeout_ms == 0 {
+        return Err(LoadError::invalid(
+            "database.acquire_timeout_ms",
+            "must be greater than zero",
+        ));
+    }
+
+    // Zero turns either limit off.
+    let statement_timeout_ms = database
+        .statement_timeout_ms
+        .unwrap_or(DEFAULT_DB_STATEMENT_TIMEOUT_MS);
+    let slow_query_log_ms = database
+        .slow_query_log_ms
+        .unwrap_or(DEFAULT_DB_SLOW_QUERY_LOG_MS);
+    if i32::try_from(statement_timeout_ms).is_err() {
+        return Err(LoadError::invalid(
+            "database.statement_timeout_ms",
+            "value exceeds the Postgres limit of 2147483647",
+        ));
+    }
+
     Ok(DatabaseSettings {
         url,
+        read_url,
         http_max_connections,
         jobs_max_connections,
+        acquire_timeout: Duration::from_millis(acquire_timeout_ms),
+        statement_timeout: (statement_timeout_ms > 0)
+            .then(|| Duration::from_millis(statement_timeout_ms)),
+        slow_query_log: (slow_query_log_ms > 0).then(|| Duration::from_millis(slow_query_log_ms)),
     })
 }
 
@@ -229,9 +645,37 @@ fn build_upload_settings(uploads: RawUploadSettings) -> Result<UploadSettings, L
         )
     })?;
 
+    let max_file_bytes = match uploads.max_file_bytes {
+        Some(value) => {
+            let limit = NonZeroU64::new(value).ok_or_else(|| {
+                LoadError::invalid("uploads.max_file_bytes", "must be greater than zero")
+            })?;
+            if limit > max_request_bytes {
+                return Err(LoadError::invalid(
+                    "uploads.max_file_bytes",
+                    "must not exceed uploads.max_request_bytes",
+                ));
+            }
+            Some(limit)
+        }
+        None => None,
+    };
+
+    let content_type_policy = match uploads.content_type_policy.as_deref() {
+        Some(value) => ContentTypePolicy::parse(value).ok_or_else(|| {
+            LoadError::invalid(
+                "uploads.content_type_policy",
+                format!("unknown policy `{value}`; expected reject, detected or declared"),
+            )
+        })?,
+        None => ContentTypePolicy::default(),
+    };
+
     Ok(UploadSettings {
         directory,
         max_request_bytes,
+        max_file_bytes,
+        content_type_policy,
     })
 }
 
@@ -251,6 +695,9 @@ fn build_jobs_settings(jobs: RawJobsSettings) -> Result<JobsSettings, LoadError>
     let publish_page = jobs
         .publish_page_concurrency
         .unwrap_or(DEFAULT_JOB_PUBLISH_PAGE_CONCURRENCY);
+    let trash_retention_days = jobs
+        .trash_retention_days
+        .unwrap_or(DEFAULT_JOB_TRASH_RETENTION_DAYS);
 
     Ok(JobsSettings {
         render_post_concurrency: non_zero_u32(render_post.into(), "jobs.render_post_concurrency")?,
@@ -267,6 +714,8 @@ fn build_jobs_settings(jobs: RawJobsSettings) -> Result<JobsSettings, LoadError>
             publish_page.into(),
             "jobs.publish_page_concurrency",
         )?,
+        trash_retention: (trash_retention_days > 0)
+            .then(|| Duration::from_secs(u64::from(trash_retention_days) * 86_400)),
     })
 }
 
@@ -291,9 +740,66 @@ fn build_render_settings(render: RawRenderSettings) -> Result<RenderSettings, Lo
         ));
     }
 
+    let embed_providers = render
+        .embed_providers
+        .as_deref()
+        .unwrap_or(DEFAULT_RENDER_EMBED_PROVIDERS)
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            EmbedProvider::parse(name).ok_or_else(|| {
+                LoadError::invalid(
+                    "render.embed_providers",
+                    format!("unknown provider `{name}`; expected youtube or vimeo"),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let heading_anchors = match render.heading_anchors.as_deref() {
+        Some(value) => HeadingAnchorPlacement::parse(value).ok_or_else(|| {
+            LoadError::invalid(
+                "render.heading_anchors",
+                format!("unknown placement `{value}`; expected before or after"),
+            )
+        })?,
+        None => HeadingAnchorPlacement::default(),
+    };
+
+    let excerpt_length = render
+        .excerpt_length
+        .unwrap_or(DEFAULT_RENDER_EXCERPT_LENGTH);
+
     Ok(RenderSettings {
         mermaid_cli_path: cli_path,
         mermaid_cache_dir: cache_dir,
+        embed_providers,
+        heading_anchors,
+        excerpt_length: non_zero_u32(excerpt_length.into(), "render.excerpt_length")?,
+        preview_max_bytes: non_zero_u32(
+            render
+                .preview_max_bytes
+                .unwrap_or(DEFAULT_RENDER_PREVIEW_MAX_BYTES),
+            "render.preview_max_bytes",
+        )?,
+        preview_rate_limit_window_seconds: non_zero_u32(
+            render
+                .preview_rate_limit_window_seconds
+                .unwrap_or(DEFAULT_RENDER_PREVIEW_RATE_LIMIT_WINDOW_SECS),
+            "render.preview_rate_limit_window_seconds",
+        )?,
+        preview_rate_limit_max_requests: non_zero_u32(
+            render
+                .preview_rate_limit_max_requests
+                .unwrap_or(DEFAULT_RENDER_PREVIEW_RATE_LIMIT_MAX_REQUESTS),
+            "render.preview_rate_limit_max_requests",
+        )?,
+        preview_debounce: Duration::from_millis(
+            render
+                .preview_debounce_ms
+                .unwrap_or(DEFAULT_RENDER_PREVIEW_DEBOUNCE_MS),
+        ),
     })
 }
 
@@ -329,9 +835,123 @@ fn build_api_rate_limit_settings(
         .unwrap_or(DEFAULT_API_RATE_LIMIT_MAX_REQUESTS);
     let max_requests = non_zero_u32(max_requests_val, "api_rate_limit.max_requests")?;
 
+    let backend = match rate_limit.backend.as_deref() {
+        Some(value) => RateLimitBackend::parse(value).ok_or_else(|| {
+            LoadError::invalid(
+                "api_rate_limit.backend",
+                format!("unknown backend `{value}`; expected memory or postgres"),
+            )
+        })?,
+        None => RateLimitBackend::default(),
+    };
+
     Ok(ApiRateLimitSettings {
         window_seconds,
         max_requests,
+        backend,
+    })
+}
+
+fn build_comment_settings(comments: RawCommentSettings) -> Result<CommentSettings, LoadError> {
+    let max_body_chars = non_zero_u32(
+        comments
+            .max_body_chars
+            .unwrap_or(DEFAULT_COMMENT_MAX_BODY_CHARS),
+        "comments.max_body_chars",
+    )?;
+    let rate_limit_window_seconds = non_zero_u32(
+        comments
+            .rate_limit_window_seconds
+            .unwrap_or(DEFAULT_COMMENT_RATE_LIMIT_WINDOW_SECS),
+        "comments.rate_limit_window_seconds",
+    )?;
+    let rate_limit_max_requests = non_zero_u32(
+        comments
+            .rate_limit_max_requests
+            .unwrap_or(DEFAULT_COMMENT_RATE_LIMIT_MAX_REQUESTS),
+        "comments.rate_limit_max_requests",
+    )?;
+
+    Ok(CommentSettings {
+        max_body_chars,
+        rate_limit_window_seconds,
+        rate_limit_max_requests,
+    })
+}
+
+fn build_sitemap_settings(sitemap: RawSitemapSettings) -> Result<SitemapSettings, LoadError> {
+    let ping_urls = sitemap
+        .ping_urls
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(|url| match url::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(url.to_string()),
+            _ => Err(LoadError::invalid(
+                "sitemap.ping_urls",
+                format!("`{url}` must be an absolute http(s) URL"),
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SitemapSettings { ping_urls })
+}
+
+fn build_admin_auth_settings(
+    admin_auth: RawAdminAuthSettings,
+) -> Result<AdminAuthSettings, LoadError> {
+    let session_secret = admin_auth
+        .session_secret
+        .map(|secret| secret.trim().to_string())
+        .filter(|secret| !secret.is_empty());
+    if let Some(secret) = session_secret.as_ref()
+        && secret.len() < MIN_ADMIN_SESSION_SECRET_LEN
+    {
+        return Err(LoadError::invalid(
+            "admin_auth.session_secret",
+            "must be at least 32 bytes",
+        ));
+    }
+
+    let ttl_seconds = admin_auth
+        .session_ttl_seconds
+        .unwrap_or(DEFAULT_ADMIN_SESSION_TTL_SECS);
+    if ttl_seconds == 0 {
+        return Err(LoadError::invalid(
+            "admin_auth.session_ttl_seconds",
+            "must be greater than zero",
+        ));
+    }
+    if ttl_seconds > MAX_ADMIN_SESSION_TTL_SECS {
+        return Err(LoadError::invalid(
+            "admin_auth.session_ttl_seconds",
+            "must be at most 2592000 (30 days)",
+        ));
+    }
+
+    let preview_link_ttl_seconds = admin_auth
+        .preview_link_ttl_seconds
+        .unwrap_or(DEFAULT_PREVIEW_LINK_TTL_SECS);
+    if preview_link_ttl_seconds == 0 {
+        return Err(LoadError::invalid(
+            "admin_auth.preview_link_ttl_seconds",
+            "must be greater than zero",
+        ));
+    }
+    if preview_link_ttl_seconds > MAX_PREVIEW_LINK_TTL_SECS {
+        return Err(LoadError::invalid(
+            "admin_auth.preview_link_ttl_seconds",
+            "must be at most 31536000 (365 days)",
+        ));
+    }
+
+    Ok(AdminAuthSettings {
+        session_secret,
+        session_ttl: Duration::from_secs(ttl_seconds),
+        preview_link_ttl: Duration::from_secs(preview_link_ttl_seconds),
+        cookie_secure: admin_auth.cookie_secure.unwrap_or(false),
     })
 }
 
@@ -350,10 +970,51 @@ fn build_scheduler_settings(
 
     Ok(SchedulerSettings {
         cadence: Duration::from_secs(cadence_seconds),
+        evict_rate_limit_buckets: scheduler.evict_rate_limit_buckets.unwrap_or(true),
+        sweep_expired_cache_entries: scheduler.sweep_expired_cache_entries.unwrap_or(true),
     })
 }
 
+fn build_slug_strategy(slugs: RawSlugSettings) -> SlugStrategy {
+    let defaults = SlugStrategy::default();
+    SlugStrategy {
+        transliterate: slugs.transliterate.unwrap_or(defaults.transliterate),
+        allow_unicode: slugs.allow_unicode.unwrap_or(defaults.allow_unicode),
+        date_prefix: slugs.date_prefix.unwrap_or(defaults.date_prefix),
+        hash_suffix: slugs.hash_suffix.unwrap_or(defaults.hash_suffix),
+    }
+}
+
 fn build_cache_settings(cache: RawCacheSettings) -> Result<CacheSettings, LoadError> {
+    let warmup_hot_paths = cache
+        .warmup_hot_paths
+        .as_deref()
+        .unwrap_or(DEFAULT_CACHE_WARMUP_HOT_PATHS)
+        .split(',')
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(|path| {
+            if path.starts_with('/') && !path.starts_with("//") {
+                Ok(path.to_string())
+            } else {
+                Err(LoadError::invalid(
+                    "cache.warmup_hot_paths",
+                    format!("`{path}` must be an absolute path starting with `/`"),
+                ))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let warmup_cron = cache
+        .warmup_cron
+        .unwrap_or_else(|| DEFAULT_CACHE_WARMUP_CRON.to_string());
+    if let Err(err) = apalis_cron::Schedule::from_str(&warmup_cron) {
+        return Err(LoadError::invalid(
+            "cache.warmup_cron",
+            format!("invalid cron expression `{warmup_cron}`: {err}"),
+        ));
+    }
+
     Ok(CacheSettings {
         enable_l0_cache: cache.enable_l0_cache.unwrap_or(true),
         enable_l1_cache: cache.enable_l1_cache.unwrap_or(true),
@@ -380,9 +1041,43 @@ fn build_cache_settings(cache: RawCacheSettings) -> Result<CacheSettings, LoadEr
         max_event_queue_len: cache
             .max_event_queue_len
             .unwrap_or(DEFAULT_CACHE_MAX_EVENT_QUEUE_LEN),
+        warmup_hot_paths,
+        warmup_cron,
     })
 }
 
+/// `host` is an IP address combined with `port`, or `unix:/absolute/path`
+/// for a unix domain socket, in which case `port` is ignored.
+fn parse_listen_addr(host: &str, port: u16) -> Result<ListenAddr, String> {
+    let Some(path) = host.strip_prefix(ListenAddr::UNIX_PREFIX) else {
+        return parse_socket_addr(host, port).map(ListenAddr::Tcp);
+    };
+    let path = Path::new(path);
+    if !path.is_absolute() {
+        return Err(format!(
+            "invalid unix socket `{host}`: the path must be absolute, e.g. `unix:/run/soffio/public.sock`"
+        ));
+    }
+    if path.file_name().is_none() {
+        return Err(format!(
+            "invalid unix socket `{host}`: the path names no file"
+        ));
+    }
+    Ok(ListenAddr::Unix(path.to_path_buf()))
+}
+
+/// Octal permission bits such as `660`, `0660` or `0o660`.
+fn parse_socket_mode(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+    let digits = trimmed.strip_prefix("0o").unwrap_or(trimmed);
+    match u32::from_str_radix(digits, 8) {
+        Ok(mode) if mode <= 0o777 && !digits.is_empty() => Ok(mode),
+        _ => Err(format!(
+            "invalid mode `{value}`; expected octal permission bits such as `660`"
+        )),
+    }
+}
+
 fn parse_socket_addr(host: &str, port: u16) -> Result<SocketAddr, String> {
     let candidate = format!("{host}:{port}");
     candidate
@@ -407,7 +1102,28 @@ pub(super) struct RawServerSettings {
     pub(super) admin_host: Option<String>,
     pub(super) public_port: Option<u16>,
     pub(super) admin_port: Option<u16>,
+    pub(super) socket_mode: Option<String>,
+    pub(super) systemd_socket: Option<bool>,
+    pub(super) tls: RawTlsSettings,
+    pub(super) admin_tls: RawTlsSettings,
+    pub(super) http_redirect_port: Option<u16>,
     pub(super) graceful_shutdown_seconds: Option<u64>,
+    pub(super) trailing_slash: Option<String>,
+    pub(super) base_path: Option<String>,
+    pub(super) trusted_proxies: Option<String>,
+    pub(super) security_headers: RawSecurityHeadersSettings,
+    pub(super) api_error_docs_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub(super) struct RawSecurityHeadersSettings {
+    pub(super) enabled: Option<bool>,
+    pub(super) content_security_policy: Option<String>,
+    pub(super) report_uri: Option<String>,
+    pub(super) report_only: Option<bool>,
+    pub(super) hsts_max_age_seconds: Option<u64>,
+    pub(super) referrer_policy: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -415,14 +1131,37 @@ pub(super) struct RawServerSettings {
 pub(super) struct RawLoggingSettings {
     pub(super) level: Option<String>,
     pub(super) json: Option<bool>,
+    pub(super) otlp: RawOtlpSettings,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub(super) struct RawTlsSettings {
+    pub(super) cert_path: Option<String>,
+    pub(super) key_path: Option<String>,
+    pub(super) client_ca_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub(super) struct RawOtlpSettings {
+    pub(super) endpoint: Option<String>,
+    pub(super) service_name: Option<String>,
+    pub(super) sample_ratio: Option<f64>,
+    pub(super) headers: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(default)]
 pub(super) struct RawDatabaseSettings {
     pub(super) url: Option<String>,
+    #[serde(alias = "replica_url")]
+    pub(super) read_url: Option<String>,
     pub(super) http_max_connections: Option<u32>,
     pub(super) jobs_max_connections: Option<u32>,
+    pub(super) acquire_timeout_ms: Option<u64>,
+    pub(super) statement_timeout_ms: Option<u64>,
+    pub(super) slow_query_log_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -430,6 +1169,8 @@ pub(super) struct RawDatabaseSettings {
 pub(super) struct RawUploadSettings {
     pub(super) directory: Option<PathBuf>,
     pub(super) max_request_bytes: Option<u64>,
+    pub(super) max_file_bytes: Option<u64>,
+    pub(super) content_type_policy: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -440,6 +1181,7 @@ pub(super) struct RawJobsSettings {
     pub(super) render_page_concurrency: Option<u32>,
     pub(super) publish_post_concurrency: Option<u32>,
     pub(super) publish_page_concurrency: Option<u32>,
+    pub(super) trash_retention_days: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -447,6 +1189,13 @@ pub(super) struct RawJobsSettings {
 pub(super) struct RawRenderSettings {
     pub(super) mermaid_cli_path: Option<PathBuf>,
     pub(super) mermaid_cache_dir: Option<PathBuf>,
+    pub(super) embed_providers: Option<String>,
+    pub(super) heading_anchors: Option<String>,
+    pub(super) excerpt_length: Option<u32>,
+    pub(super) preview_max_bytes: Option<u64>,
+    pub(super) preview_rate_limit_window_seconds: Option<u64>,
+    pub(super) preview_rate_limit_max_requests: Option<u64>,
+    pub(super) preview_debounce_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -461,12 +1210,61 @@ pub(super) struct RawRateLimitSettings {
 pub(super) struct RawApiRateLimitSettings {
     pub(super) window_seconds: Option<u64>,
     pub(super) max_requests: Option<u64>,
+    pub(super) backend: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub(super) struct RawCommentSettings {
+    pub(super) max_body_chars: Option<u64>,
+    pub(super) rate_limit_window_seconds: Option<u64>,
+    pub(super) rate_limit_max_requests: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub(super) struct RawSitemapSettings {
+    pub(super) ping_urls: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(default)]
+pub(super) struct RawAdminAuthSettings {
+    pub(super) session_secret: Option<String>,
+    pub(super) session_ttl_seconds: Option<u64>,
+    pub(super) preview_link_ttl_seconds: Option<u64>,
+    pub(super) cookie_secure: Option<bool>,
+}
+
+impl std::fmt::Debug for RawAdminAuthSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawAdminAuthSettings")
+            .field(
+                "session_secret",
+                &self.session_secret.as_ref().map(|_| "<redacted>"),
+            )
+            .field("session_ttl_seconds", &self.session_ttl_seconds)
+            .field("preview_link_ttl_seconds", &self.preview_link_ttl_seconds)
+            .field("cookie_secure", &self.cookie_secure)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub(super) struct RawSlugSettings {
+    pub(super) transliterate: Option<bool>,
+    pub(super) allow_unicode: Option<bool>,
+    pub(super) date_prefix: Option<bool>,
+    pub(super) hash_suffix: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(default)]
 pub(super) struct RawSchedulerSettings {
     pub(super) cadence_seconds: Option<u64>,
+    pub(super) evict_rate_limit_buckets: Option<bool>,
+    pub(super) sweep_expired_cache_entries: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -483,4 +1281,6 @@ pub(super) struct RawCacheSettings {
     pub(super) auto_consume_interval_ms: Option<u64>,
     pub(super) consume_batch_limit: Option<usize>,
     pub(super) max_event_queue_len: Option<usize>,
+    pub(super) warmup_hot_paths: Option<String>,
+    pub(super) warmup_cron: Option<String>,
 }
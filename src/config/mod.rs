@@ -2,21 +2,27 @@
 
 mod cli;
 mod defaults;
+mod diff;
 mod loading;
 mod overrides;
 mod types;
 
 pub use cli::{
-    CliArgs, Command, DatabaseOverride, ExportArgs, ImportArgs, MigrationsArgs, MigrationsCommand,
-    MigrationsReconcileArgs, RenderAllArgs, RenderAllOverrides, RenderOverrides, ServeArgs,
-    ServeOverrides,
+    AdminArgs, AdminCommand, AdminCreateUserArgs, CliArgs, Command, DatabaseOverride, DoctorArgs,
+    ExportArgs, ExportStaticArgs, ImportArgs, ImportMarkdownArgs, MigrationsArgs,
+    MigrationsCommand, MigrationsReconcileArgs, MigrationsStatusArgs, RenderAllArgs,
+    RenderAllOverrides, RenderOverrides, ServeArgs, ServeOverrides, UploadsArgs, UploadsCommand,
+    UploadsVerifyAllArgs,
 };
 pub(crate) use defaults::{DEFAULT_MERMAID_CACHE_DIR, DEFAULT_MERMAID_CLI_PATH};
-pub use loading::{load, load_with_cli};
+pub use diff::{INERT_KEYS, RELOADABLE_KEYS, SettingChange, SettingsDiff, diff_settings};
+pub use loading::{ConfigSource, config_sources, load, load_with_cli};
 pub use types::{
-    ApiRateLimitSettings, CacheSettings, DatabaseSettings, JobsSettings, LoadError, LogFormat,
-    LoggingSettings, RateLimitSettings, RenderSettings, SchedulerSettings, ServerSettings,
-    Settings, UploadSettings,
+    AdminAuthSettings, ApiRateLimitSettings, CacheSettings, CommentSettings, DatabaseSettings,
+    EmbedProvider, HeadingAnchorPlacement, IpCidr, JobsSettings, ListenAddr, LoadError, LogFormat,
+    LoggingSettings, OtlpSettings, RateLimitBackend, RateLimitSettings, RenderSettings,
+    SchedulerSettings, SecurityHeadersSettings, ServerSettings, Settings, SitemapSettings,
+    TlsSettings, TrailingSlashPolicy, UploadSettings,
 };
 
 #[cfg(test)]
@@ -9,6 +9,27 @@ impl RawSettings {
         if let Some(host) = overrides.server_admin_host.as_ref() {
             self.server.admin_host = Some(host.clone());
         }
+        if let Some(mode) = overrides.server_socket_mode.as_ref() {
+            self.server.socket_mode = Some(mode.clone());
+        }
+        if let Some(path) = overrides.tls_cert_path.as_ref() {
+            self.server.tls.cert_path = Some(path.clone());
+        }
+        if let Some(path) = overrides.tls_key_path.as_ref() {
+            self.server.tls.key_path = Some(path.clone());
+        }
+        if let Some(path) = overrides.admin_tls_cert_path.as_ref() {
+            self.server.admin_tls.cert_path = Some(path.clone());
+        }
+        if let Some(path) = overrides.admin_tls_key_path.as_ref() {
+            self.server.admin_tls.key_path = Some(path.clone());
+        }
+        if let Some(port) = overrides.http_redirect_port {
+            self.server.http_redirect_port = Some(port);
+        }
+        if overrides.systemd_socket {
+            self.server.systemd_socket = Some(true);
+        }
         if let Some(port) = overrides.public_port {
             self.server.public_port = Some(port);
         }
@@ -18,27 +39,66 @@ impl RawSettings {
         if let Some(seconds) = overrides.server_graceful_shutdown_seconds {
             self.server.graceful_shutdown_seconds = Some(seconds);
         }
+        if let Some(policy) = overrides.server_trailing_slash.as_ref() {
+            self.server.trailing_slash = Some(policy.clone());
+        }
+        if let Some(path) = overrides.server_base_path.as_ref() {
+            self.server.base_path = Some(path.clone());
+        }
+        if let Some(proxies) = overrides.server_trusted_proxies.as_ref() {
+            self.server.trusted_proxies = Some(proxies.clone());
+        }
+        if let Some(url) = overrides.server_api_error_docs_url.as_ref() {
+            self.server.api_error_docs_url = Some(url.clone());
+        }
+        if let Some(uri) = overrides.csp_report_uri.as_ref() {
+            self.server.security_headers.report_uri = Some(uri.clone());
+        }
+        if overrides.csp_report_only {
+            self.server.security_headers.report_only = Some(true);
+        }
         if let Some(level) = overrides.log_level.as_ref() {
             self.logging.level = Some(level.clone());
         }
         if let Some(json) = overrides.log_json {
             self.logging.json = Some(json);
         }
+        if let Some(endpoint) = overrides.log_otlp_endpoint.as_ref() {
+            self.logging.otlp.endpoint = Some(endpoint.clone());
+        }
         if let Some(url) = overrides.database_url.as_ref() {
             self.database.url = Some(url.clone());
         }
+        if let Some(url) = overrides.database_read_url.as_ref() {
+            self.database.read_url = Some(url.clone());
+        }
         if let Some(max) = overrides.database_http_max_connections {
             self.database.http_max_connections = Some(max);
         }
         if let Some(max) = overrides.database_jobs_max_connections {
             self.database.jobs_max_connections = Some(max);
         }
+        if let Some(ms) = overrides.database_acquire_timeout_ms {
+            self.database.acquire_timeout_ms = Some(ms);
+        }
+        if let Some(ms) = overrides.database_statement_timeout_ms {
+            self.database.statement_timeout_ms = Some(ms);
+        }
+        if let Some(ms) = overrides.database_slow_query_log_ms {
+            self.database.slow_query_log_ms = Some(ms);
+        }
         if let Some(directory) = overrides.uploads_directory.as_ref() {
             self.uploads.directory = Some(directory.clone());
         }
         if let Some(limit) = overrides.uploads_max_request_bytes {
             self.uploads.max_request_bytes = Some(limit);
         }
+        if let Some(limit) = overrides.uploads_max_file_bytes {
+            self.uploads.max_file_bytes = Some(limit);
+        }
+        if let Some(policy) = overrides.uploads_content_type_policy.as_ref() {
+            self.uploads.content_type_policy = Some(policy.clone());
+        }
         if let Some(window) = overrides.rate_limit_window_seconds {
             self.rate_limit.window_seconds = Some(window);
         }
@@ -51,9 +111,36 @@ impl RawSettings {
         if let Some(max) = overrides.api_rate_limit_max_requests {
             self.api_rate_limit.max_requests = Some(max);
         }
+        if let Some(backend) = overrides.api_rate_limit_backend.as_ref() {
+            self.api_rate_limit.backend = Some(backend.clone());
+        }
+        if let Some(max) = overrides.comments_max_body_chars {
+            self.comments.max_body_chars = Some(max);
+        }
+        if let Some(window) = overrides.comments_rate_limit_window_seconds {
+            self.comments.rate_limit_window_seconds = Some(window);
+        }
+        if let Some(max) = overrides.comments_rate_limit_max_requests {
+            self.comments.rate_limit_max_requests = Some(max);
+        }
+        if let Some(urls) = overrides.sitemap_ping_urls.as_ref() {
+            self.sitemap.ping_urls = Some(urls.clone());
+        }
+        if let Some(ttl) = overrides.admin_auth_session_ttl_seconds {
+            self.admin_auth.session_ttl_seconds = Some(ttl);
+        }
+        if let Some(secure) = overrides.admin_auth_cookie_secure {
+            self.admin_auth.cookie_secure = Some(secure);
+        }
         if let Some(cadence) = overrides.scheduler_cadence_seconds {
             self.scheduler.cadence_seconds = Some(cadence);
         }
+        if let Some(enabled) = overrides.scheduler_evict_rate_limit_buckets {
+            self.scheduler.evict_rate_limit_buckets = Some(enabled);
+        }
+        if let Some(enabled) = overrides.scheduler_sweep_expired_cache_entries {
+            self.scheduler.sweep_expired_cache_entries = Some(enabled);
+        }
         if let Some(value) = overrides.jobs_render_post_concurrency {
             self.jobs.render_post_concurrency = Some(value);
         }
@@ -69,6 +156,9 @@ impl RawSettings {
         if let Some(value) = overrides.jobs_publish_page_concurrency {
             self.jobs.publish_page_concurrency = Some(value);
         }
+        if let Some(days) = overrides.jobs_trash_retention_days {
+            self.jobs.trash_retention_days = Some(days);
+        }
 
         self.apply_render_overrides(&overrides.render);
         self.apply_cache_overrides(overrides);
@@ -108,6 +198,12 @@ impl RawSettings {
         if let Some(v) = overrides.cache_max_event_queue_len {
             self.cache.max_event_queue_len = Some(v);
         }
+        if let Some(paths) = overrides.cache_warmup_hot_paths.as_ref() {
+            self.cache.warmup_hot_paths = Some(paths.clone());
+        }
+        if let Some(cron) = overrides.cache_warmup_cron.as_ref() {
+            self.cache.warmup_cron = Some(cron.clone());
+        }
     }
 
     pub(super) fn apply_renderall_overrides(&mut self, overrides: &RenderAllOverrides) {
@@ -128,5 +224,26 @@ impl RawSettings {
         if let Some(dir) = overrides.mermaid_cache_dir.as_ref() {
             self.render.mermaid_cache_dir = Some(dir.clone());
         }
+        if let Some(providers) = overrides.embed_providers.as_ref() {
+            self.render.embed_providers = Some(providers.clone());
+        }
+        if let Some(placement) = overrides.heading_anchors.as_ref() {
+            self.render.heading_anchors = Some(placement.clone());
+        }
+        if let Some(length) = overrides.excerpt_length {
+            self.render.excerpt_length = Some(length);
+        }
+        if let Some(max) = overrides.preview_max_bytes {
+            self.render.preview_max_bytes = Some(max);
+        }
+        if let Some(window) = overrides.preview_rate_limit_window_seconds {
+            self.render.preview_rate_limit_window_seconds = Some(window);
+        }
+        if let Some(max) = overrides.preview_rate_limit_max_requests {
+            self.render.preview_rate_limit_max_requests = Some(max);
+        }
+        if let Some(ms) = overrides.preview_debounce_ms {
+            self.render.preview_debounce_ms = Some(ms);
+        }
     }
 }
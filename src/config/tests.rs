@@ -1,6 +1,8 @@
 use super::defaults::DEFAULT_UPLOAD_REQUEST_LIMIT_BYTES;
 use super::loading::RawSettings;
 use super::*;
+use crate::domain::slug::SlugStrategy;
+use crate::domain::uploads::ContentTypePolicy;
 use clap::Parser;
 use tracing::level_filters::LevelFilter;
 
@@ -19,7 +21,7 @@ fn cli_overrides_take_highest_precedence() {
     raw.apply_serve_overrides(&overrides);
     let settings = Settings::from_raw(raw).expect("valid settings");
 
-    assert_eq!(settings.server.public_addr.port(), 4321);
+    assert_eq!(settings.server.public_addr.port(), Some(4321));
     assert_eq!(settings.logging.level, LevelFilter::DEBUG);
 }
 
@@ -95,6 +97,55 @@ fn parse_renderall_arguments() {
     }
 }
 
+#[test]
+fn parse_renderall_dry_run_and_progress() {
+    let args = CliArgs::parse_from(["soffio", "renderall", "--dry-run", "--progress-every", "25"]);
+
+    match args.command.expect("renderall command") {
+        Command::RenderAll(render) => {
+            assert!(render.dry_run);
+            assert_eq!(render.progress_every, 25);
+        }
+        _ => panic!("wrong command parsed"),
+    }
+
+    let args = CliArgs::parse_from(["soffio", "renderall"]);
+    match args.command.expect("renderall command") {
+        Command::RenderAll(render) => {
+            assert!(!render.dry_run);
+            assert_eq!(render.progress_every, 100);
+        }
+        _ => panic!("wrong command parsed"),
+    }
+}
+
+#[test]
+fn parse_renderall_slug_and_since_filters() {
+    let args = CliArgs::parse_from([
+        "soffio",
+        "renderall",
+        "--slug",
+        "hello",
+        "--slug",
+        "about",
+        "--since",
+        "2026-05-01T08:30:00+02:00",
+    ]);
+
+    match args.command.expect("renderall command") {
+        Command::RenderAll(render) => {
+            assert_eq!(render.slugs, vec!["hello".to_string(), "about".to_string()]);
+            let since = render.since.expect("since parsed");
+            assert_eq!(since.unix_timestamp(), 1_777_617_000);
+        }
+        _ => panic!("wrong command parsed"),
+    }
+
+    let err = CliArgs::try_parse_from(["soffio", "renderall", "--since", "yesterday"])
+        .expect_err("invalid timestamp rejected");
+    assert!(err.to_string().contains("RFC 3339"));
+}
+
 #[test]
 fn parse_export_arguments() {
     let args = CliArgs::parse_from([
@@ -112,11 +163,22 @@ fn parse_export_arguments() {
                 Some("postgres://example")
             );
             assert_eq!(export.file, std::path::Path::new("/tmp/site.toml"));
+            assert!(!export.include_uploads);
         }
         _ => panic!("wrong command parsed"),
     }
 }
 
+#[test]
+fn parse_export_include_uploads_flag() {
+    let args = CliArgs::parse_from(["soffio", "export", "--include-uploads", "/tmp/site.toml"]);
+
+    match args.command.expect("export command") {
+        Command::ExportSite(export) => assert!(export.include_uploads),
+        _ => panic!("wrong command parsed"),
+    }
+}
+
 #[test]
 fn parse_import_arguments() {
     let args = CliArgs::parse_from([
@@ -134,11 +196,75 @@ fn parse_import_arguments() {
                 Some("postgres://example")
             );
             assert_eq!(import.file, std::path::Path::new("/tmp/site.toml"));
+            assert_eq!(import.mode, "fresh");
+            assert!(!import.yes);
+        }
+        _ => panic!("wrong command parsed"),
+    }
+}
+
+#[test]
+fn parse_export_static_arguments() {
+    let args = CliArgs::parse_from(["soffio", "export-static", "/tmp/site"]);
+
+    match args.command.expect("export-static command") {
+        Command::ExportStatic(export) => {
+            assert!(export.database.database_url.is_none());
+            assert_eq!(export.dir, std::path::Path::new("/tmp/site"));
         }
         _ => panic!("wrong command parsed"),
     }
 }
 
+#[test]
+fn parse_import_markdown_arguments() {
+    let args = CliArgs::parse_from([
+        "soffio",
+        "import-markdown",
+        "--database-url",
+        "postgres://example",
+        "/tmp/posts",
+    ]);
+
+    match args.command.expect("import-markdown command") {
+        Command::ImportMarkdown(import) => {
+            assert_eq!(
+                import.database.database_url.as_deref(),
+                Some("postgres://example")
+            );
+            assert_eq!(import.dir, std::path::Path::new("/tmp/posts"));
+        }
+        _ => panic!("wrong command parsed"),
+    }
+}
+
+#[test]
+fn import_wipe_mode_requires_confirmation() {
+    let unconfirmed =
+        CliArgs::try_parse_from(["soffio", "import", "--mode", "wipe", "/tmp/site.toml"]);
+    assert!(unconfirmed.is_err());
+
+    let args = CliArgs::parse_from([
+        "soffio",
+        "import",
+        "--mode",
+        "wipe",
+        "--yes",
+        "/tmp/site.toml",
+    ]);
+    match args.command.expect("import command") {
+        Command::ImportSite(import) => {
+            assert_eq!(import.mode, "wipe");
+            assert!(import.yes);
+        }
+        _ => panic!("wrong command parsed"),
+    }
+
+    let unknown =
+        CliArgs::try_parse_from(["soffio", "import", "--mode", "replace", "/tmp/site.toml"]);
+    assert!(unknown.is_err());
+}
+
 #[test]
 fn parse_migrations_reconcile_arguments() {
     let args = CliArgs::parse_from([
@@ -159,6 +285,135 @@ fn parse_migrations_reconcile_arguments() {
                 );
                 assert_eq!(rec.file, std::path::Path::new("/tmp/archive.toml"));
             }
+            _ => panic!("wrong migrations command parsed"),
+        },
+        _ => panic!("wrong command parsed"),
+    }
+}
+
+#[test]
+fn parse_migrations_status_pending_and_verify_arguments() {
+    let args = CliArgs::parse_from(["soffio", "migrations", "status"]);
+    match args.command.expect("migrations command") {
+        Command::Migrations(mig) => match mig.command {
+            MigrationsCommand::Status(status) => assert_eq!(status.output, "text"),
+            _ => panic!("wrong migrations command parsed"),
+        },
+        _ => panic!("wrong command parsed"),
+    }
+
+    let args = CliArgs::parse_from(["soffio", "migrations", "verify", "--output", "json"]);
+    match args.command.expect("migrations command") {
+        Command::Migrations(mig) => match mig.command {
+            MigrationsCommand::Verify(verify) => assert_eq!(verify.output, "json"),
+            _ => panic!("wrong migrations command parsed"),
+        },
+        _ => panic!("wrong command parsed"),
+    }
+
+    let args = CliArgs::parse_from(["soffio", "migrations", "pending", "--output", "json"]);
+    match args.command.expect("migrations command") {
+        Command::Migrations(mig) => match mig.command {
+            MigrationsCommand::Pending(pending) => assert_eq!(pending.output, "json"),
+            _ => panic!("wrong migrations command parsed"),
+        },
+        _ => panic!("wrong command parsed"),
+    }
+
+    let unknown = CliArgs::try_parse_from(["soffio", "migrations", "verify", "--output", "yaml"]);
+    assert!(unknown.is_err());
+}
+
+#[test]
+fn parse_doctor_arguments() {
+    let args = CliArgs::parse_from([
+        "soffio",
+        "doctor",
+        "--output",
+        "json",
+        "--server-public-port",
+        "4321",
+    ]);
+
+    match args.command.expect("doctor command") {
+        Command::Doctor(doctor) => {
+            assert_eq!(doctor.output, "json");
+            assert_eq!(doctor.overrides.public_port, Some(4321));
+        }
+        _ => panic!("wrong command parsed"),
+    }
+}
+
+#[test]
+fn config_sources_list_the_keys_each_file_sets() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("doctor.toml");
+    std::fs::write(
+        &path,
+        "[server]\npublic_port = 4000\n\n[uploads]\ndirectory = \"/srv/uploads\"\n",
+    )
+    .expect("write config");
+
+    let sources = config_sources(Some(&path)).expect("sources");
+    let file = sources
+        .iter()
+        .find(|source| source.name == path.display().to_string())
+        .expect("explicit file listed");
+    assert_eq!(file.keys, ["server.public_port", "uploads.directory"]);
+    assert!(sources.last().expect("env").name.starts_with("environment"));
+
+    assert!(config_sources(Some(&dir.path().join("missing.toml"))).is_err());
+}
+
+#[test]
+fn parse_admin_create_user_arguments() {
+    let args = CliArgs::parse_from([
+        "soffio",
+        "admin",
+        "create-user",
+        "--database-url",
+        "postgres://example",
+        "--password",
+        "correct horse battery",
+        "editor",
+    ]);
+
+    match args.command.expect("admin command") {
+        Command::Admin(admin) => match admin.command {
+            AdminCommand::CreateUser(create) => {
+                assert_eq!(
+                    create.database.database_url.as_deref(),
+                    Some("postgres://example")
+                );
+                assert_eq!(create.username, "editor");
+                assert_eq!(create.password.as_deref(), Some("correct horse battery"));
+            }
+        },
+        _ => panic!("wrong command parsed"),
+    }
+}
+
+#[test]
+fn parse_uploads_verify_all_arguments() {
+    let args = CliArgs::parse_from([
+        "soffio",
+        "uploads",
+        "verify-all",
+        "--database-url",
+        "postgres://example",
+        "--concurrency",
+        "8",
+    ]);
+
+    match args.command.expect("uploads command") {
+        Command::Uploads(uploads) => match uploads.command {
+            UploadsCommand::VerifyAll(verify) => {
+                assert_eq!(
+                    verify.database.database_url.as_deref(),
+                    Some("postgres://example")
+                );
+                assert_eq!(verify.concurrency, 8);
+            }
         },
         _ => panic!("wrong command parsed"),
     }
@@ -187,6 +442,230 @@ fn parse_serve_overrides() {
     }
 }
 
+#[test]
+fn comment_settings_use_defaults_and_accept_overrides() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(settings.comments.max_body_chars.get(), 4000);
+    assert_eq!(settings.comments.rate_limit_window_seconds.get(), 60);
+    assert_eq!(settings.comments.rate_limit_max_requests.get(), 10);
+
+    let mut raw = RawSettings::default();
+    let overrides = ServeOverrides {
+        comments_max_body_chars: Some(280),
+        comments_rate_limit_max_requests: Some(3),
+        ..Default::default()
+    };
+    raw.apply_serve_overrides(&overrides);
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(settings.comments.max_body_chars.get(), 280);
+    assert_eq!(settings.comments.rate_limit_max_requests.get(), 3);
+}
+
+#[test]
+fn comment_settings_reject_zero_body_limit() {
+    let mut raw = RawSettings::default();
+    raw.comments.max_body_chars = Some(0);
+    let err = Settings::from_raw(raw).expect_err("zero body limit rejected");
+    assert!(err.to_string().contains("comments.max_body_chars"));
+}
+
+#[test]
+fn api_error_docs_url_defaults_to_the_error_reference_and_must_be_http() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(
+        settings.server.api_error_docs_url,
+        "https://github.com/xfyyzy/soffio/blob/main/docs/api/errors.md#"
+    );
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        server_api_error_docs_url: Some("https://docs.example.com/errors/".to_string()),
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(
+        settings.server.api_error_docs_url,
+        "https://docs.example.com/errors/"
+    );
+
+    let mut raw = RawSettings::default();
+    raw.server.api_error_docs_url = Some("docs/errors".to_string());
+    let err = Settings::from_raw(raw).expect_err("relative URL rejected");
+    assert!(err.to_string().contains("server.api_error_docs_url"));
+}
+
+#[test]
+fn scheduler_maintenance_tasks_default_on_and_can_be_disabled() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(
+        settings.scheduler.cadence,
+        std::time::Duration::from_secs(300)
+    );
+    assert!(settings.scheduler.evict_rate_limit_buckets);
+    assert!(settings.scheduler.sweep_expired_cache_entries);
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        scheduler_evict_rate_limit_buckets: Some(false),
+        scheduler_sweep_expired_cache_entries: Some(false),
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert!(!settings.scheduler.evict_rate_limit_buckets);
+    assert!(!settings.scheduler.sweep_expired_cache_entries);
+}
+
+#[test]
+fn slug_strategy_defaults_to_plain_transliterated_slugs() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(settings.slugs, SlugStrategy::default());
+    assert!(settings.slugs.transliterate);
+
+    let mut raw = RawSettings::default();
+    raw.slugs.date_prefix = Some(true);
+    raw.slugs.hash_suffix = Some(true);
+    raw.slugs.transliterate = Some(false);
+    raw.slugs.allow_unicode = Some(true);
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(
+        settings.slugs,
+        SlugStrategy {
+            transliterate: false,
+            allow_unicode: true,
+            date_prefix: true,
+            hash_suffix: true,
+        }
+    );
+}
+
+#[test]
+fn admin_auth_settings_use_defaults_and_accept_overrides() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert!(settings.admin_auth.session_secret.is_none());
+    assert_eq!(
+        settings.admin_auth.session_ttl,
+        std::time::Duration::from_secs(43_200)
+    );
+    assert_eq!(
+        settings.admin_auth.preview_link_ttl,
+        std::time::Duration::from_secs(7 * 24 * 60 * 60)
+    );
+    assert!(!settings.admin_auth.cookie_secure);
+
+    let mut raw = RawSettings::default();
+    raw.admin_auth.session_secret = Some("0123456789abcdef0123456789abcdef".to_string());
+    raw.admin_auth.preview_link_ttl_seconds = Some(3600);
+    let overrides = ServeOverrides {
+        admin_auth_session_ttl_seconds: Some(600),
+        admin_auth_cookie_secure: Some(true),
+        ..Default::default()
+    };
+    raw.apply_serve_overrides(&overrides);
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(
+        settings.admin_auth.session_ttl,
+        std::time::Duration::from_secs(600)
+    );
+    assert_eq!(
+        settings.admin_auth.preview_link_ttl,
+        std::time::Duration::from_secs(3600)
+    );
+    assert!(settings.admin_auth.cookie_secure);
+    assert!(!format!("{:?}", settings.admin_auth).contains("0123456789abcdef"));
+}
+
+#[test]
+fn admin_auth_settings_reject_short_secret() {
+    let mut raw = RawSettings::default();
+    raw.admin_auth.session_secret = Some("too-short".to_string());
+    let err = Settings::from_raw(raw).expect_err("short secret rejected");
+    assert!(err.to_string().contains("admin_auth.session_secret"));
+
+    let mut raw = RawSettings::default();
+    raw.admin_auth.preview_link_ttl_seconds = Some(0);
+    let err = Settings::from_raw(raw).expect_err("zero ttl rejected");
+    assert!(
+        err.to_string()
+            .contains("admin_auth.preview_link_ttl_seconds")
+    );
+
+    let mut raw = RawSettings::default();
+    raw.admin_auth.preview_link_ttl_seconds = Some(u64::MAX);
+    let err = Settings::from_raw(raw).expect_err("unbounded ttl rejected");
+    assert!(
+        err.to_string()
+            .contains("admin_auth.preview_link_ttl_seconds")
+    );
+
+    let mut raw = RawSettings::default();
+    raw.admin_auth.session_ttl_seconds = Some(u64::MAX);
+    let err = Settings::from_raw(raw).expect_err("unbounded session ttl rejected");
+    assert!(err.to_string().contains("admin_auth.session_ttl_seconds"));
+}
+
+#[test]
+fn render_embed_providers_default_to_all_and_accept_overrides() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(
+        settings.render.embed_providers,
+        vec![EmbedProvider::YouTube, EmbedProvider::Vimeo]
+    );
+
+    let mut raw = RawSettings::default();
+    let overrides = ServeOverrides {
+        render: RenderOverrides {
+            embed_providers: Some(" Vimeo ".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    raw.apply_serve_overrides(&overrides);
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(settings.render.embed_providers, vec![EmbedProvider::Vimeo]);
+
+    let mut raw = RawSettings::default();
+    raw.render.embed_providers = Some(String::new());
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert!(settings.render.embed_providers.is_empty());
+}
+
+#[test]
+fn render_embed_providers_reject_unknown_names() {
+    let mut raw = RawSettings::default();
+    raw.render.embed_providers = Some("youtube,myspace".to_string());
+    let err = Settings::from_raw(raw).expect_err("unknown provider rejected");
+    assert!(err.to_string().contains("render.embed_providers"));
+}
+
+#[test]
+fn render_heading_anchors_default_after_and_accept_overrides() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(
+        settings.render.heading_anchors,
+        HeadingAnchorPlacement::After
+    );
+
+    let mut raw = RawSettings::default();
+    let overrides = ServeOverrides {
+        render: RenderOverrides {
+            heading_anchors: Some(" Before ".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    raw.apply_serve_overrides(&overrides);
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(
+        settings.render.heading_anchors,
+        HeadingAnchorPlacement::Before
+    );
+
+    let mut raw = RawSettings::default();
+    raw.render.heading_anchors = Some("inline".to_string());
+    let err = Settings::from_raw(raw).expect_err("unknown placement rejected");
+    assert!(err.to_string().contains("render.heading_anchors"));
+}
+
 #[test]
 fn cache_settings_use_correct_defaults() {
     let raw = RawSettings::default();
@@ -203,6 +682,54 @@ fn cache_settings_use_correct_defaults() {
     assert_eq!(settings.cache.auto_consume_interval_ms, 5000);
     assert_eq!(settings.cache.consume_batch_limit, 100);
     assert_eq!(settings.cache.max_event_queue_len, 2048);
+    assert_eq!(
+        settings.cache.warmup_hot_paths,
+        vec!["/", "/rss.xml", "/sitemap.xml"]
+    );
+    assert_eq!(settings.cache.warmup_cron, "0 */5 * * * *");
+}
+
+#[test]
+fn cache_warmup_settings_are_validated() {
+    let mut raw = RawSettings::default();
+    raw.cache.warmup_hot_paths = Some(" /, /tags/rust ,".to_string());
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(settings.cache.warmup_hot_paths, vec!["/", "/tags/rust"]);
+
+    let mut raw = RawSettings::default();
+    raw.cache.warmup_hot_paths = Some("/,posts".to_string());
+    let err = Settings::from_raw(raw).expect_err("relative path rejected");
+    assert!(err.to_string().contains("cache.warmup_hot_paths"));
+
+    let mut raw = RawSettings::default();
+    raw.cache.warmup_cron = Some("every five minutes".to_string());
+    let err = Settings::from_raw(raw).expect_err("invalid cron rejected");
+    assert!(err.to_string().contains("cache.warmup_cron"));
+}
+
+#[test]
+fn sitemap_ping_urls_are_validated() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert!(settings.sitemap.ping_urls.is_empty());
+
+    let mut raw = RawSettings::default();
+    raw.sitemap.ping_urls =
+        Some(" https://www.bing.com/ping , http://search.test/ping,".to_string());
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(
+        settings.sitemap.ping_urls,
+        vec!["https://www.bing.com/ping", "http://search.test/ping"]
+    );
+
+    for bad in ["www.bing.com/ping", "ftp://search.test/ping"] {
+        let mut raw = RawSettings::default();
+        raw.sitemap.ping_urls = Some(bad.to_string());
+        let err = Settings::from_raw(raw).expect_err("non-http ping url rejected");
+        assert!(
+            err.to_string().contains("sitemap.ping_urls"),
+            "{bad}: {err}"
+        );
+    }
 }
 
 #[test]
@@ -258,3 +785,513 @@ fn parse_cache_cli_arguments() {
         _ => panic!("wrong command parsed"),
     }
 }
+
+#[test]
+fn render_excerpt_length_defaults_and_rejects_zero() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(settings.render.excerpt_length.get(), 200);
+
+    let mut raw = RawSettings::default();
+    raw.render.excerpt_length = Some(0);
+    let err = Settings::from_raw(raw).expect_err("zero length rejected");
+    assert!(err.to_string().contains("render.excerpt_length"));
+}
+
+#[test]
+fn render_preview_limits_default_and_reject_zero() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(settings.render.preview_max_bytes.get(), 262_144);
+    assert_eq!(settings.render.preview_rate_limit_window_seconds.get(), 60);
+    assert_eq!(settings.render.preview_rate_limit_max_requests.get(), 30);
+    assert_eq!(
+        settings.render.preview_debounce,
+        std::time::Duration::from_millis(300)
+    );
+
+    let mut raw = RawSettings::default();
+    raw.render.preview_max_bytes = Some(0);
+    let err = Settings::from_raw(raw).expect_err("zero cap rejected");
+    assert!(err.to_string().contains("render.preview_max_bytes"));
+}
+
+#[test]
+fn logging_otlp_is_disabled_without_endpoint() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert!(settings.logging.otlp.is_none());
+}
+
+#[test]
+fn logging_otlp_parses_settings_and_redacts_headers() {
+    let mut raw = RawSettings::default();
+    raw.logging.otlp.sample_ratio = Some(0.25);
+    raw.logging.otlp.headers = Some("authorization=Bearer secret, x-tenant=blog".to_string());
+    let overrides = ServeOverrides {
+        log_otlp_endpoint: Some("http://collector:4318/v1/traces".to_string()),
+        ..Default::default()
+    };
+    raw.apply_serve_overrides(&overrides);
+
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    let otlp = settings.logging.otlp.expect("otlp configured");
+    assert_eq!(otlp.endpoint, "http://collector:4318/v1/traces");
+    assert_eq!(otlp.service_name, "soffio");
+    assert_eq!(otlp.sample_ratio, 0.25);
+    assert_eq!(
+        otlp.headers,
+        vec![
+            ("authorization".to_string(), "Bearer secret".to_string()),
+            ("x-tenant".to_string(), "blog".to_string()),
+        ]
+    );
+    assert!(!format!("{otlp:?}").contains("secret"));
+}
+
+#[test]
+fn logging_otlp_rejects_invalid_values() {
+    let mut raw = RawSettings::default();
+    raw.logging.otlp.endpoint = Some("collector:4318".to_string());
+    let err = Settings::from_raw(raw).expect_err("endpoint without scheme rejected");
+    assert!(err.to_string().contains("logging.otlp.endpoint"));
+
+    let mut raw = RawSettings::default();
+    raw.logging.otlp.endpoint = Some("http://collector:4318".to_string());
+    raw.logging.otlp.sample_ratio = Some(1.5);
+    let err = Settings::from_raw(raw).expect_err("ratio above one rejected");
+    assert!(err.to_string().contains("logging.otlp.sample_ratio"));
+
+    let mut raw = RawSettings::default();
+    raw.logging.otlp.endpoint = Some("http://collector:4318".to_string());
+    raw.logging.otlp.headers = Some("missing-separator".to_string());
+    let err = Settings::from_raw(raw).expect_err("malformed header rejected");
+    assert!(err.to_string().contains("logging.otlp.headers"));
+}
+
+#[test]
+fn database_timeouts_default_and_zero_disables() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(
+        settings.database.acquire_timeout,
+        std::time::Duration::from_secs(5)
+    );
+    assert_eq!(
+        settings.database.statement_timeout,
+        Some(std::time::Duration::from_secs(30))
+    );
+    assert_eq!(
+        settings.database.slow_query_log,
+        Some(std::time::Duration::from_millis(500))
+    );
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        database_statement_timeout_ms: Some(0),
+        database_slow_query_log_ms: Some(0),
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert!(settings.database.statement_timeout.is_none());
+    assert!(settings.database.slow_query_log.is_none());
+}
+
+#[test]
+fn database_statement_timeout_rejects_values_beyond_postgres_range() {
+    let mut raw = RawSettings::default();
+    raw.database.statement_timeout_ms = Some(u64::from(u32::MAX));
+    let err = Settings::from_raw(raw).expect_err("oversized timeout rejected");
+    assert!(err.to_string().contains("database.statement_timeout_ms"));
+}
+
+#[test]
+fn database_acquire_timeout_must_be_positive() {
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        database_acquire_timeout_ms: Some(0),
+        ..Default::default()
+    });
+    let err = Settings::from_raw(raw).expect_err("zero acquire timeout rejected");
+    assert!(err.to_string().contains("database.acquire_timeout_ms"));
+}
+
+#[test]
+fn database_read_url_is_optional_and_overridable() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert!(settings.database.read_url.is_none());
+
+    let mut raw = RawSettings::default();
+    raw.database.read_url = Some("   ".to_string());
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert!(settings.database.read_url.is_none());
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        database_read_url: Some("postgres://replica/soffio".to_string()),
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(
+        settings.database.read_url.as_deref(),
+        Some("postgres://replica/soffio")
+    );
+}
+
+#[test]
+fn trash_retention_defaults_to_30_days_and_zero_disables() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(
+        settings.jobs.trash_retention,
+        Some(std::time::Duration::from_secs(30 * 86_400))
+    );
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        jobs_trash_retention_days: Some(0),
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert!(settings.jobs.trash_retention.is_none());
+}
+
+#[test]
+fn trailing_slash_policy_defaults_to_preserve() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(
+        settings.server.trailing_slash,
+        TrailingSlashPolicy::Preserve
+    );
+}
+
+#[test]
+fn trailing_slash_policy_can_be_overridden_via_cli() {
+    let mut raw = RawSettings::default();
+    let overrides = ServeOverrides {
+        server_trailing_slash: Some("Never".to_string()),
+        ..Default::default()
+    };
+
+    raw.apply_serve_overrides(&overrides);
+    let settings = Settings::from_raw(raw).expect("valid settings");
+
+    assert_eq!(settings.server.trailing_slash, TrailingSlashPolicy::Never);
+}
+
+#[test]
+fn unknown_trailing_slash_policy_is_rejected() {
+    let mut raw = RawSettings::default();
+    raw.server.trailing_slash = Some("sometimes".to_string());
+
+    let err = Settings::from_raw(raw).expect_err("unknown policy");
+    assert!(err.to_string().contains("server.trailing_slash"));
+}
+
+#[test]
+fn base_path_is_normalised_and_validated() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert!(settings.server.base_path.is_root());
+
+    let mut raw = RawSettings::default();
+    let overrides = ServeOverrides {
+        server_base_path: Some("blog/".to_string()),
+        ..Default::default()
+    };
+    raw.apply_serve_overrides(&overrides);
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(settings.server.base_path.as_str(), "/blog");
+
+    let mut raw = RawSettings::default();
+    raw.server.base_path = Some("/my blog".to_string());
+    let err = Settings::from_raw(raw).expect_err("invalid base path");
+    assert!(err.to_string().contains("server.base_path"));
+}
+
+#[test]
+fn settings_diff_splits_reloadable_changes_and_redacts_secrets() {
+    let old = Settings::from_raw(RawSettings::default()).expect("valid settings");
+
+    let mut raw = RawSettings::default();
+    raw.api_rate_limit.max_requests = Some(5);
+    raw.rate_limit.max_requests = Some(90);
+    raw.server.public_port = Some(4100);
+    raw.database.url = Some("postgres://soffio:hunter2@db/soffio".to_string());
+    let new = Settings::from_raw(raw).expect("valid settings");
+
+    let diff = diff_settings(&old, &new);
+    let applied: Vec<_> = diff.applied.iter().map(|change| change.key).collect();
+    let deferred: Vec<_> = diff.deferred.iter().map(|change| change.key).collect();
+    assert_eq!(applied, ["api_rate_limit.max_requests"]);
+    assert_eq!(deferred, ["server.public_addr", "database.url"]);
+    let inert: Vec<_> = diff.inert.iter().map(|change| change.key).collect();
+    assert_eq!(inert, ["rate_limit.max_requests"]);
+
+    let url = &diff.deferred[1];
+    assert_eq!(url.old, "None");
+    assert_eq!(url.new, "<redacted>");
+    assert!(diff_settings(&new, &new).is_empty());
+}
+
+#[test]
+fn api_rate_limit_backend_defaults_to_memory_and_accepts_overrides() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(settings.api_rate_limit.backend, RateLimitBackend::Memory);
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        api_rate_limit_backend: Some("Postgres".to_string()),
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(settings.api_rate_limit.backend, RateLimitBackend::Postgres);
+
+    let mut raw = RawSettings::default();
+    raw.api_rate_limit.backend = Some("redis".to_string());
+    let err = Settings::from_raw(raw).expect_err("unknown backend");
+    assert!(err.to_string().contains("api_rate_limit.backend"));
+}
+
+#[test]
+fn upload_file_limit_is_optional_and_bounded_by_the_request_limit() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(settings.uploads.max_file_bytes, None);
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        uploads_max_request_bytes: Some(4096),
+        uploads_max_file_bytes: Some(1024),
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(
+        settings.uploads.max_file_bytes.map(|limit| limit.get()),
+        Some(1024)
+    );
+
+    for limit in [0, 8192] {
+        let mut raw = RawSettings::default();
+        raw.uploads.max_request_bytes = Some(4096);
+        raw.uploads.max_file_bytes = Some(limit);
+        let err = Settings::from_raw(raw).expect_err("invalid file limit");
+        assert!(err.to_string().contains("uploads.max_file_bytes"), "{err}");
+    }
+}
+
+#[test]
+fn upload_content_type_policy_defaults_to_reject_and_accepts_overrides() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert_eq!(
+        settings.uploads.content_type_policy,
+        ContentTypePolicy::Reject
+    );
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        uploads_content_type_policy: Some("Detected".to_string()),
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(
+        settings.uploads.content_type_policy,
+        ContentTypePolicy::Detected
+    );
+
+    let mut raw = RawSettings::default();
+    raw.uploads.content_type_policy = Some("trust".to_string());
+    let err = Settings::from_raw(raw).expect_err("unknown policy");
+    assert!(err.to_string().contains("uploads.content_type_policy"));
+}
+
+#[test]
+fn listener_addresses_accept_tcp_and_unix_forms() {
+    let mut raw = RawSettings::default();
+    raw.server.host = Some("0.0.0.0".to_string());
+    raw.server.public_port = Some(4000);
+    raw.server.admin_host = Some("unix:/run/soffio/admin.sock".to_string());
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(settings.server.public_addr.to_string(), "0.0.0.0:4000");
+    assert_eq!(
+        settings.server.admin_addr,
+        ListenAddr::Unix("/run/soffio/admin.sock".into())
+    );
+    assert_eq!(settings.server.admin_addr.port(), None);
+    assert_eq!(
+        settings.server.admin_addr.to_string(),
+        "unix:/run/soffio/admin.sock"
+    );
+    assert!(!settings.server.systemd_socket);
+
+    let mut raw = RawSettings::default();
+    raw.server.host = Some("unix:run/public.sock".to_string());
+    let err = Settings::from_raw(raw).expect_err("relative socket path");
+    assert!(err.to_string().contains("server.public_addr"));
+    assert!(err.to_string().contains("absolute"));
+
+    let mut raw = RawSettings::default();
+    raw.server.host = Some("not-an-ip".to_string());
+    let err = Settings::from_raw(raw).expect_err("invalid host");
+    assert!(err.to_string().contains("server.public_addr"));
+
+    let mut raw = RawSettings::default();
+    raw.server.host = Some("unix:/run/soffio.sock".to_string());
+    raw.server.admin_host = Some("unix:/run/soffio.sock".to_string());
+    let err = Settings::from_raw(raw).expect_err("shared socket path");
+    assert!(err.to_string().contains("server.admin_addr"));
+}
+
+#[test]
+fn socket_mode_and_systemd_socket_parse_from_cli() {
+    let args = CliArgs::parse_from([
+        "soffio",
+        "serve",
+        "--server-socket-mode",
+        "0o660",
+        "--systemd-socket",
+    ]);
+    let Some(Command::Serve(serve)) = args.command else {
+        panic!("wrong command parsed");
+    };
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&serve.overrides);
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    assert_eq!(settings.server.socket_mode, Some(0o660));
+    assert!(settings.server.systemd_socket);
+
+    for (value, expected) in [("660", Some(0o660)), ("0600", Some(0o600)), ("888", None)] {
+        let mut raw = RawSettings::default();
+        raw.server.socket_mode = Some(value.to_string());
+        match expected {
+            Some(mode) => assert_eq!(
+                Settings::from_raw(raw).expect(value).server.socket_mode,
+                Some(mode)
+            ),
+            None => assert!(
+                Settings::from_raw(raw)
+                    .expect_err(value)
+                    .to_string()
+                    .contains("server.socket_mode")
+            ),
+        }
+    }
+}
+
+#[test]
+fn tls_settings_require_a_key_and_a_tcp_listener() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert!(settings.server.tls.is_none());
+    assert!(settings.server.admin_tls.is_none());
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        tls_cert_path: Some("/etc/soffio/fullchain.pem".to_string()),
+        tls_key_path: Some("/etc/soffio/privkey.pem".to_string()),
+        http_redirect_port: Some(8080),
+        ..Default::default()
+    });
+    raw.server.tls.client_ca_path = Some(" ".to_string());
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    let tls = settings.server.tls.expect("public TLS");
+    assert_eq!(
+        tls.key_path,
+        std::path::PathBuf::from("/etc/soffio/privkey.pem")
+    );
+    assert_eq!(tls.client_ca_path, None);
+    assert!(settings.server.admin_tls.is_none());
+    assert_eq!(settings.server.http_redirect_port, Some(8080));
+
+    let mut raw = RawSettings::default();
+    raw.server.admin_tls.cert_path = Some("/etc/soffio/admin.pem".to_string());
+    let err = Settings::from_raw(raw).expect_err("missing key");
+    assert!(err.to_string().contains("server.admin_tls"));
+
+    let mut raw = RawSettings::default();
+    raw.server.http_redirect_port = Some(8080);
+    let err = Settings::from_raw(raw).expect_err("redirect without TLS");
+    assert!(err.to_string().contains("server.http_redirect_port"));
+
+    let mut raw = RawSettings::default();
+    raw.server.host = Some("unix:/run/soffio/public.sock".to_string());
+    raw.server.tls.cert_path = Some("/etc/soffio/fullchain.pem".to_string());
+    raw.server.tls.key_path = Some("/etc/soffio/privkey.pem".to_string());
+    let err = Settings::from_raw(raw).expect_err("TLS on a unix socket");
+    assert!(err.to_string().contains("server.tls"));
+}
+
+#[test]
+fn trusted_proxies_parse_addresses_and_cidr_blocks() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    assert!(settings.server.trusted_proxies.is_empty());
+
+    let mut raw = RawSettings::default();
+    raw.apply_serve_overrides(&ServeOverrides {
+        server_trusted_proxies: Some("127.0.0.1, 10.1.2.3/8,fd00::/8".to_string()),
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    let shown: Vec<String> = settings
+        .server
+        .trusted_proxies
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(shown, ["127.0.0.1/32", "10.0.0.0/8", "fd00::/8"]);
+
+    let proxies = &settings.server.trusted_proxies;
+    assert!(proxies[1].contains("10.200.0.1".parse().expect("ip")));
+    assert!(!proxies[1].contains("11.0.0.1".parse().expect("ip")));
+    assert!(proxies[0].contains("::ffff:127.0.0.1".parse().expect("ip")));
+    assert!(proxies[2].contains("fd12::1".parse().expect("ip")));
+
+    for invalid in ["10.0.0.0/33", "proxy.internal", "::/129"] {
+        let mut raw = RawSettings::default();
+        raw.server.trusted_proxies = Some(invalid.to_string());
+        let err = Settings::from_raw(raw).expect_err("invalid proxy");
+        assert!(
+            err.to_string().contains("server.trusted_proxies"),
+            "{invalid}"
+        );
+    }
+}
+
+#[test]
+fn security_headers_default_on_and_validate_their_values() {
+    let settings = Settings::from_raw(RawSettings::default()).expect("valid settings");
+    let headers = &settings.server.security_headers;
+    assert!(headers.enabled);
+    assert_eq!(headers.content_security_policy, None);
+    assert_eq!(headers.referrer_policy, "strict-origin-when-cross-origin");
+    assert_eq!(
+        headers.hsts_max_age,
+        Some(std::time::Duration::from_secs(365 * 24 * 60 * 60))
+    );
+
+    let mut raw = RawSettings::default();
+    raw.server.security_headers.hsts_max_age_seconds = Some(0);
+    raw.server.security_headers.referrer_policy = Some("No-Referrer".to_string());
+    raw.apply_serve_overrides(&ServeOverrides {
+        csp_report_uri: Some("https://reports.example/csp".to_string()),
+        csp_report_only: true,
+        ..Default::default()
+    });
+    let settings = Settings::from_raw(raw).expect("valid settings");
+    let headers = &settings.server.security_headers;
+    assert_eq!(headers.hsts_max_age, None);
+    assert_eq!(headers.referrer_policy, "no-referrer");
+    assert_eq!(
+        headers.report_uri.as_deref(),
+        Some("https://reports.example/csp")
+    );
+    assert!(headers.report_only);
+
+    let mut raw = RawSettings::default();
+    raw.server.security_headers.referrer_policy = Some("sometimes".to_string());
+    let err = Settings::from_raw(raw).expect_err("unknown referrer policy");
+    assert!(err.to_string().contains("referrer_policy"));
+
+    let mut raw = RawSettings::default();
+    raw.server.security_headers.content_security_policy =
+        Some("default-src 'self';\nscript-src 'none'".to_string());
+    let err = Settings::from_raw(raw).expect_err("multi-line policy");
+    assert!(err.to_string().contains("content_security_policy"));
+
+    let mut raw = RawSettings::default();
+    raw.server.security_headers.report_uri = Some("reports".to_string());
+    let err = Settings::from_raw(raw).expect_err("relative report uri");
+    assert!(err.to_string().contains("report_uri"));
+}
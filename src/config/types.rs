@@ -1,5 +1,6 @@
 use std::{
-    net::SocketAddr,
+    fmt,
+    net::{IpAddr, SocketAddr},
     num::{NonZeroU32, NonZeroU64},
     path::PathBuf,
     time::Duration,
@@ -8,6 +9,10 @@ use std::{
 use thiserror::Error;
 use tracing::level_filters::LevelFilter;
 
+use crate::domain::routes::BasePath;
+use crate::domain::slug::SlugStrategy;
+use crate::domain::uploads::ContentTypePolicy;
+
 /// Fully-resolved deployment settings after precedence resolution and validation.
 #[derive(Debug, Clone)]
 pub struct Settings {
@@ -21,19 +26,216 @@ pub struct Settings {
     pub api_rate_limit: ApiRateLimitSettings,
     pub scheduler: SchedulerSettings,
     pub cache: CacheSettings,
+    pub comments: CommentSettings,
+    pub sitemap: SitemapSettings,
+    pub admin_auth: AdminAuthSettings,
+    /// How slugs derived from post titles are shaped.
+    pub slugs: SlugStrategy,
 }
 
 #[derive(Debug, Clone)]
 pub struct ServerSettings {
-    pub public_addr: SocketAddr,
-    pub admin_addr: SocketAddr,
+    pub public_addr: ListenAddr,
+    pub admin_addr: ListenAddr,
+    /// Permissions for unix socket files; `None` leaves them to the umask.
+    pub socket_mode: Option<u32>,
+    /// Take both listeners from systemd socket activation (`LISTEN_FDS`)
+    /// instead of binding `public_addr` and `admin_addr`.
+    pub systemd_socket: bool,
+    /// TLS termination for the public listener.
+    pub tls: Option<TlsSettings>,
+    /// TLS termination for the admin listener, configured independently.
+    pub admin_tls: Option<TlsSettings>,
+    /// Plain-HTTP port on the public host that redirects to HTTPS.
+    pub http_redirect_port: Option<u16>,
     pub graceful_shutdown: Duration,
+    /// Canonical trailing-slash form for public URLs.
+    pub trailing_slash: TrailingSlashPolicy,
+    /// Prefix the public listener is mounted under behind a reverse proxy.
+    pub base_path: BasePath,
+    /// Peers whose `X-Forwarded-For`/`Forwarded` headers name the client.
+    pub trusted_proxies: Vec<IpCidr>,
+    pub security_headers: SecurityHeadersSettings,
+    /// Base URL API error codes are appended to for `docs_url`.
+    pub api_error_docs_url: String,
+}
+
+/// `Content-Security-Policy` and related response headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityHeadersSettings {
+    pub enabled: bool,
+    /// Replaces the built-in policy of the public site when set.
+    pub content_security_policy: Option<String>,
+    /// Where browsers send violation reports.
+    pub report_uri: Option<String>,
+    /// Send the policy as `Content-Security-Policy-Report-Only`.
+    pub report_only: bool,
+    /// `None` omits `Strict-Transport-Security`.
+    pub hsts_max_age: Option<Duration>,
+    pub referrer_policy: String,
+}
+
+/// An address block such as `10.0.0.0/8` or `::1/128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    /// `None` when `prefix_len` is longer than the address. Host bits of
+    /// `addr` are cleared.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Option<Self> {
+        let network = match addr {
+            IpAddr::V4(addr) if prefix_len <= 32 => {
+                IpAddr::from((u32::from(addr) & v4_mask(prefix_len)).to_be_bytes())
+            }
+            IpAddr::V6(addr) if prefix_len <= 128 => {
+                IpAddr::from((u128::from(addr) & v6_mask(prefix_len)).to_be_bytes())
+            }
+            _ => return None,
+        };
+        Some(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    /// Whether `addr` falls inside the block. IPv4-mapped IPv6 addresses
+    /// match IPv4 blocks.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match (self.network, addr.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(addr)) => {
+                u32::from(addr) & v4_mask(self.prefix_len) == u32::from(network)
+            }
+            (IpAddr::V6(network), IpAddr::V6(addr)) => {
+                u128::from(addr) & v6_mask(self.prefix_len) == u128::from(network)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn v4_mask(prefix_len: u8) -> u32 {
+    u32::MAX
+        .checked_shl(32 - u32::from(prefix_len))
+        .unwrap_or(0)
+}
+
+fn v6_mask(prefix_len: u8) -> u128 {
+    u128::MAX
+        .checked_shl(128 - u32::from(prefix_len))
+        .unwrap_or(0)
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+/// PEM files for a TLS listener. They are read at startup and again on every
+/// configuration reload, so renewed certificates apply without a restart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsSettings {
+    /// Certificate chain, leaf first.
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    /// When set, clients must present a certificate signed by this CA.
+    pub client_ca_path: Option<PathBuf>,
+}
+
+/// Where a listener accepts connections: a TCP socket address, or a unix
+/// domain socket written as `unix:/path/to.sock`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListenAddr {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl ListenAddr {
+    pub const UNIX_PREFIX: &'static str = "unix:";
+
+    /// The TCP port; `None` for a unix socket.
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            Self::Tcp(addr) => Some(addr.port()),
+            Self::Unix(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            Self::Unix(path) => write!(f, "{}{}", Self::UNIX_PREFIX, path.display()),
+        }
+    }
+}
+
+/// How public URLs treat a trailing slash. Non-canonical requests are
+/// redirected so `/posts/foo/` and `/posts/foo` share one cache entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingSlashPolicy {
+    /// Serve both forms as routed; no redirects.
+    #[default]
+    Preserve,
+    /// Redirect `/posts/foo` to `/posts/foo/`.
+    Always,
+    /// Redirect `/posts/foo/` to `/posts/foo`.
+    Never,
+}
+
+impl TrailingSlashPolicy {
+    pub const ALL: [Self; 3] = [Self::Preserve, Self::Always, Self::Never];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Preserve => "preserve",
+            Self::Always => "always",
+            Self::Never => "never",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|policy| policy.as_str().eq_ignore_ascii_case(value.trim()))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct LoggingSettings {
     pub level: LevelFilter,
     pub format: LogFormat,
+    /// OpenTelemetry trace export; `None` unless an endpoint is configured.
+    pub otlp: Option<OtlpSettings>,
+}
+
+/// OTLP/HTTP span exporter installed alongside the log output.
+#[derive(Clone)]
+pub struct OtlpSettings {
+    /// Collector traces endpoint, e.g. `http://localhost:4318/v1/traces`.
+    pub endpoint: String,
+    /// `service.name` resource attribute reported with every span.
+    pub service_name: String,
+    /// Fraction of root traces sampled, between 0.0 and 1.0.
+    pub sample_ratio: f64,
+    /// Extra request headers, typically for collector authentication.
+    pub headers: Vec<(String, String)>,
+}
+
+impl std::fmt::Debug for OtlpSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header_names: Vec<&str> = self.headers.iter().map(|(name, _)| name.as_str()).collect();
+        f.debug_struct("OtlpSettings")
+            .field("endpoint", &self.endpoint)
+            .field("service_name", &self.service_name)
+            .field("sample_ratio", &self.sample_ratio)
+            .field("headers", &header_names)
+            .finish()
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -45,20 +247,96 @@ pub enum LogFormat {
 #[derive(Debug, Clone)]
 pub struct DatabaseSettings {
     pub url: Option<String>,
+    /// Replica serving public page reads; `None` sends them to `url`.
+    pub read_url: Option<String>,
     pub http_max_connections: NonZeroU32,
     pub jobs_max_connections: NonZeroU32,
+    /// How long a query waits for a free pooled connection before failing.
+    pub acquire_timeout: Duration,
+    /// Per-statement limit applied to every pooled connection; `None` disables it.
+    pub statement_timeout: Option<Duration>,
+    /// Statements slower than this are logged as warnings; `None` disables it.
+    pub slow_query_log: Option<Duration>,
 }
 
 #[derive(Debug, Clone)]
 pub struct RenderSettings {
     pub mermaid_cli_path: PathBuf,
     pub mermaid_cache_dir: PathBuf,
+    /// Providers whose bare links are turned into embedded players.
+    pub embed_providers: Vec<EmbedProvider>,
+    /// Where post headings carry their `heading-anchor` link.
+    pub heading_anchors: HeadingAnchorPlacement,
+    /// Maximum characters of an excerpt derived from the post body.
+    pub excerpt_length: NonZeroU32,
+    /// Largest markdown body accepted by the render preview endpoints, in bytes.
+    pub preview_max_bytes: NonZeroU32,
+    /// Window size (seconds) for per-caller render preview rate limiting.
+    pub preview_rate_limit_window_seconds: NonZeroU32,
+    /// Maximum render previews per caller within the window.
+    pub preview_rate_limit_max_requests: NonZeroU32,
+    /// Quiet period before the admin editor's live preview renders; a newer
+    /// keystroke within it supersedes the pending render. Zero disables it.
+    pub preview_debounce: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbedProvider {
+    YouTube,
+    Vimeo,
+}
+
+impl EmbedProvider {
+    pub const ALL: [Self; 2] = [Self::YouTube, Self::Vimeo];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::YouTube => "youtube",
+            Self::Vimeo => "vimeo",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|provider| provider.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
+/// Side of the heading text the `heading-anchor` deep link is placed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeadingAnchorPlacement {
+    Before,
+    #[default]
+    After,
+}
+
+impl HeadingAnchorPlacement {
+    pub const ALL: [Self; 2] = [Self::Before, Self::After];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Before => "before",
+            Self::After => "after",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|placement| placement.as_str().eq_ignore_ascii_case(value.trim()))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct UploadSettings {
     pub directory: PathBuf,
     pub max_request_bytes: NonZeroU64,
+    /// Largest single file in a multi-file admin upload; `None` leaves each
+    /// file bounded only by `max_request_bytes`.
+    pub max_file_bytes: Option<NonZeroU64>,
+    /// Handling of uploads whose contents contradict their declared type.
+    pub content_type_policy: ContentTypePolicy,
 }
 
 #[derive(Debug, Clone)]
@@ -68,6 +346,9 @@ pub struct JobsSettings {
     pub render_page_concurrency: NonZeroU32,
     pub publish_post_concurrency: NonZeroU32,
     pub publish_page_concurrency: NonZeroU32,
+    /// How long trashed posts and pages are kept before being purged;
+    /// `None` keeps them until purged by hand.
+    pub trash_retention: Option<Duration>,
 }
 
 #[derive(Debug, Clone)]
@@ -80,11 +361,91 @@ pub struct RateLimitSettings {
 pub struct ApiRateLimitSettings {
     pub window_seconds: NonZeroU32,
     pub max_requests: NonZeroU32,
+    /// Where request counts are kept.
+    pub backend: RateLimitBackend,
+}
+
+/// Storage for API rate limit buckets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitBackend {
+    /// Per-process buckets; each node enforces the limit on its own.
+    #[default]
+    Memory,
+    /// Buckets in Postgres, shared by every node using the database.
+    Postgres,
+}
+
+impl RateLimitBackend {
+    pub const ALL: [Self; 2] = [Self::Memory, Self::Postgres];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Postgres => "postgres",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|backend| backend.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
+/// Limits applied to the comment ingest endpoint.
+#[derive(Debug, Clone)]
+pub struct CommentSettings {
+    /// Maximum comment body length in characters.
+    pub max_body_chars: NonZeroU32,
+    /// Window size (seconds) for per-IP ingest rate limiting.
+    pub rate_limit_window_seconds: NonZeroU32,
+    /// Maximum ingest requests per client IP within the window.
+    pub rate_limit_max_requests: NonZeroU32,
+}
+
+/// Search engines told about sitemap changes.
+#[derive(Debug, Clone, Default)]
+pub struct SitemapSettings {
+    /// Endpoints requested with the sitemap URL as their `sitemap` query
+    /// parameter when a page is published or unpublished; empty disables pings.
+    pub ping_urls: Vec<String>,
+}
+
+/// Session handling for the admin listener.
+#[derive(Clone)]
+pub struct AdminAuthSettings {
+    /// Key used to sign session cookies; a random per-process key is used when unset.
+    pub session_secret: Option<String>,
+    /// Lifetime of a signed-in session.
+    pub session_ttl: Duration,
+    /// Lifetime of a shared snapshot preview link.
+    pub preview_link_ttl: Duration,
+    /// Mark the session cookie `Secure` (requires serving the admin over HTTPS).
+    pub cookie_secure: bool,
+}
+
+impl std::fmt::Debug for AdminAuthSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminAuthSettings")
+            .field(
+                "session_secret",
+                &self.session_secret.as_ref().map(|_| "<redacted>"),
+            )
+            .field("session_ttl", &self.session_ttl)
+            .field("preview_link_ttl", &self.preview_link_ttl)
+            .field("cookie_secure", &self.cookie_secure)
+            .finish()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct SchedulerSettings {
+    /// Interval between maintenance ticks.
     pub cadence: Duration,
+    /// Evict idle rate limit buckets on each tick.
+    pub evict_rate_limit_buckets: bool,
+    /// Drop expired entries from the in-memory caches on each tick.
+    pub sweep_expired_cache_entries: bool,
 }
 
 /// Fully-resolved cache settings.
@@ -112,6 +473,10 @@ pub struct CacheSettings {
     pub consume_batch_limit: usize,
     /// Maximum queue length for cache events.
     pub max_event_queue_len: usize,
+    /// Public paths re-warmed on every scheduled warm cycle.
+    pub warmup_hot_paths: Vec<String>,
+    /// Cron expression (with seconds) for the hot path warm cycle.
+    pub warmup_cron: String,
 }
 
 #[derive(Debug, Error)]
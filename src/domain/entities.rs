@@ -6,7 +6,10 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::domain::{
-    types::{JobState, JobType, NavigationDestinationType, PageStatus, PostStatus},
+    types::{
+        ActorType, CommentStatus, FeedContentMode, HomepageLayout, HomepageMode, JobState, JobType,
+        NavigationDestinationType, PageStatus, PostStatus, RenderStatus,
+    },
     uploads::UploadMetadata,
 };
 
@@ -16,14 +19,27 @@ pub struct PostRecord {
     pub slug: String,
     pub title: String,
     pub excerpt: String,
+    /// Whether `excerpt` was derived from the body rather than written by an editor.
+    pub excerpt_generated: bool,
     pub body_markdown: String,
     pub status: PostStatus,
     pub pinned: bool,
+    /// Position among the pinned posts, lowest first; 0 for unpinned posts.
+    pub pin_order: i32,
     pub scheduled_at: Option<OffsetDateTime>,
     pub published_at: Option<OffsetDateTime>,
     pub archived_at: Option<OffsetDateTime>,
     pub summary_markdown: Option<String>,
     pub summary_html: Option<String>,
+    /// BCP 47 language tag of this variant; `None` for untranslated content.
+    pub lang: Option<String>,
+    /// Shared id linking this post to its translations.
+    pub translation_group: Option<Uuid>,
+    /// Outcome of the latest render job.
+    pub render_status: RenderStatus,
+    /// Error reported by the latest failed render; cleared once a render succeeds.
+    pub render_error: Option<String>,
+    pub render_failed_at: Option<OffsetDateTime>,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
 }
@@ -56,10 +72,61 @@ pub struct PageRecord {
     pub scheduled_at: Option<OffsetDateTime>,
     pub published_at: Option<OffsetDateTime>,
     pub archived_at: Option<OffsetDateTime>,
+    /// BCP 47 language tag of this variant; `None` for untranslated content.
+    pub lang: Option<String>,
+    /// Shared id linking this page to its translations.
+    pub translation_group: Option<Uuid>,
+    /// Name of the registered page template the page renders with.
+    pub template: String,
+    /// Outcome of the latest render job.
+    pub render_status: RenderStatus,
+    /// Error reported by the latest failed render; cleared once a render succeeds.
+    pub render_error: Option<String>,
+    pub render_failed_at: Option<OffsetDateTime>,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
 }
 
+/// A published member of a translation group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TranslationRecord {
+    pub id: Uuid,
+    pub slug: String,
+    pub lang: String,
+}
+
+/// A trashed post or page awaiting restore or purge.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrashedRecord {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub deleted_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+/// A draft post or page with a publication time, whether or not that time
+/// has passed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledRecord {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub scheduled_at: OffsetDateTime,
+}
+
+/// A post or page on the content calendar: a draft at its publication
+/// time, or a published item at the time it went live.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarRecord {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    /// Still a draft waiting for `at`; otherwise published at `at`.
+    pub scheduled: bool,
+    pub at: OffsetDateTime,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct TagRecord {
     pub id: Uuid,
@@ -100,11 +167,26 @@ pub struct SiteSettingsRecord {
     pub footer_copy: String,
     pub public_site_url: String,
     pub favicon_svg: String,
+    /// PNG or ICO upload served at `/favicon.ico`; the SVG is used without one.
+    pub favicon_upload_id: Option<Uuid>,
     pub timezone: Tz,
+    /// strftime pattern for dates shown to readers.
+    pub date_format: String,
     pub meta_title: String,
     pub meta_description: String,
     pub og_title: String,
     pub og_description: String,
+    pub maintenance_enabled: bool,
+    pub maintenance_html: String,
+    pub maintenance_retry_after_secs: i32,
+    pub feed_content_mode: FeedContentMode,
+    /// Most items an RSS or Atom feed lists.
+    pub feed_item_limit: i32,
+    pub homepage_mode: HomepageMode,
+    /// Page served at `/` in page mode. The feed is served instead while it
+    /// is unset, unpublished or deleted.
+    pub homepage_page_id: Option<Uuid>,
+    pub homepage_layout: HomepageLayout,
     pub updated_at: OffsetDateTime,
 }
 
@@ -118,16 +200,52 @@ pub struct UploadRecord {
     pub stored_path: String,
     pub metadata: UploadMetadata,
     pub created_at: OffsetDateTime,
+    /// When the stored file was last re-hashed; `None` until first checked.
+    pub last_verified_at: Option<OffsetDateTime>,
+    /// The stored file no longer hashes to `checksum`, or is missing.
+    pub checksum_mismatch: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommentRecord {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub author: String,
+    pub body: String,
+    pub status: CommentStatus,
+    pub source_ip: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+/// Account allowed to sign in to the admin listener.
+///
+/// Deliberately not `Serialize`: the password hash must never leak into
+/// audit snapshots or exports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdminUserRecord {
+    pub id: Uuid,
+    pub username: String,
+    pub password_hash: String,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct AuditLogRecord {
     pub id: Uuid,
     pub actor: String,
+    pub actor_type: ActorType,
+    /// API key behind the entry; kept after the key is revoked or deleted.
+    pub actor_key_id: Option<Uuid>,
     pub action: String,
     pub entity_type: String,
     pub entity_id: Option<String>,
     pub payload_text: Option<String>,
+    /// Shallow `{field: {old, new}}` diff recorded for updates.
+    pub changes: Option<serde_json::Value>,
+    /// Correlation id of the HTTP request that produced the entry.
+    pub request_id: Option<String>,
     pub created_at: OffsetDateTime,
 }
 
@@ -146,3 +264,14 @@ pub struct JobRecord {
     pub last_error: Option<String>,
     pub priority: i32,
 }
+
+/// One failed run of a queued job, kept after later attempts overwrite
+/// `last_error`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JobAttemptRecord {
+    pub job_id: String,
+    /// 1-based number of the run that failed.
+    pub attempt: i32,
+    pub error: String,
+    pub failed_at: OffsetDateTime,
+}
@@ -6,8 +6,10 @@ pub mod error;
 pub mod navigation;
 pub mod pages;
 pub mod posts;
+pub mod routes;
 pub mod sections;
 pub mod slug;
 pub mod snapshots;
+pub mod translations;
 pub mod types;
 pub mod uploads;
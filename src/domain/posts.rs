@@ -7,8 +7,6 @@ use time::{Date, format_description::FormatItem, macros::format_description};
 
 pub use data::POSTS;
 
-pub const HUMAN_DATE_FORMAT: &[FormatItem<'static>] =
-    format_description!("[month repr:long] [day padding:none], [year]");
 pub const MONTH_KEY_FORMAT: &[FormatItem<'static>] =
     format_description!("[year]-[month padding:zero]");
 pub const MONTH_LABEL_FORMAT: &[FormatItem<'static>] =
@@ -143,10 +141,6 @@ pub fn month_label_for(date: Date) -> String {
     date.format(MONTH_LABEL_FORMAT).expect("valid month label")
 }
 
-pub fn format_human_date(date: Date) -> String {
-    date.format(HUMAN_DATE_FORMAT).expect("valid calendar date")
-}
-
 pub fn post_has_code_blocks(post: &Post) -> bool {
     post.sections.iter().any(|section| {
         section
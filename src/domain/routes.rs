@@ -0,0 +1,365 @@
+//! Public route table and the page slugs it reserves.
+//!
+//! Pages are served by the public router's fallback at `/{slug}`, so a page
+//! whose slug equals the first segment of a built-in route is shadowed by that
+//! route. The router's paths are listed here once; a test next to the router
+//! fails when it registers a path missing from this table.
+//!
+//! Absolute URLs are built here too: [`site_root`] checks the configured
+//! `public_site_url` and [`join_url`] appends paths to it.
+//!
+//! Post slugs may hold non-ASCII letters when unicode slugs are enabled.
+//! Links carry them percent-encoded, built by [`post_path`], and the router
+//! hands handlers the decoded slug, see [`decode_path_segment`].
+
+use std::borrow::Cow;
+
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+use url::Url;
+
+/// Bytes escaped in a path segment: everything but unreserved URL characters.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Paths registered by the public router.
+pub const PUBLIC_ROUTES: &[&str] = &[
+    "/",
+    "/tags/{tag}",
+    "/months/{month}",
+    "/archive/{year}",
+    "/archive/{year}/{month}",
+    "/posts/{slug}",
+    "/posts",
+    "/ui/posts",
+    "/sitemap.xml",
+    "/rss.xml",
+    "/atom.xml",
+    "/favicon.ico",
+    "/favicon.svg",
+    "/apple-touch-icon.png",
+    "/posts/_preview/{id}",
+    "/pages/_preview/{id}",
+    "/posts/_preview/snapshot/{id}",
+    "/pages/_preview/snapshot/{id}",
+    "/preview/snapshots/{id}",
+    "/_health/db",
+    "/readyz",
+    "/robots.txt",
+    "/uploads/{*path}",
+    "/static/public/{*path}",
+    "/static/common/{*path}",
+];
+
+/// Prefixes of routers merged into the public listener.
+pub const MERGED_ROUTE_PREFIXES: &[&str] = &["/api/v1"];
+
+/// The built-in route a page slug would be shadowed by, if any.
+pub fn reserved_page_slug_route(slug: &str) -> Option<&'static str> {
+    PUBLIC_ROUTES
+        .iter()
+        .chain(MERGED_ROUTE_PREFIXES)
+        .copied()
+        .find(|route| {
+            first_segment(route).is_some_and(|segment| segment.eq_ignore_ascii_case(slug))
+        })
+}
+
+/// Every slug a page may not use, in route table order without duplicates.
+pub fn reserved_page_slugs() -> Vec<&'static str> {
+    let mut slugs: Vec<&'static str> = Vec::new();
+    for segment in PUBLIC_ROUTES
+        .iter()
+        .chain(MERGED_ROUTE_PREFIXES)
+        .filter_map(|route| first_segment(route))
+    {
+        if !slugs.contains(&segment) {
+            slugs.push(segment);
+        }
+    }
+    slugs
+}
+
+/// Prefix the public site is mounted under, such as `/blog`; empty at the root.
+///
+/// Every generated link goes through [`BasePath::join`] or
+/// [`BasePath::site_url`], so relative and absolute URLs agree on the prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BasePath(String);
+
+impl BasePath {
+    /// Normalise `value` to `/segment[/segment…]` without a trailing slash.
+    /// Empty input and `/` mean the root. Segments are limited to unreserved
+    /// URL characters.
+    pub fn parse(value: &str) -> Option<Self> {
+        let trimmed = value.trim().trim_matches('/');
+        if trimmed.is_empty() {
+            return Some(Self::default());
+        }
+        let valid = trimmed.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.' | '~'))
+        });
+        valid.then(|| Self(format!("/{trimmed}")))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Root-relative URL of an application path: `/posts/a` → `/blog/posts/a`.
+    pub fn join(&self, path: &str) -> String {
+        format!("{}/{}", self.0, path.trim_start_matches('/'))
+    }
+
+    /// Absolute site root, with a trailing slash: `https://example.com/blog/`.
+    /// `None` when `public_site_url` is unset or not usable, see [`site_root`].
+    pub fn site_url(&self, public_site_url: &str) -> Option<String> {
+        site_root(public_site_url).map(|root| join_url(&root, &self.join("/")))
+    }
+
+    /// [`BasePath::site_url`], or the root-relative `/blog/` when there is no
+    /// absolute one, for links that still work on the current host.
+    pub fn site_url_or_relative(&self, public_site_url: &str) -> String {
+        self.site_url(public_site_url)
+            .unwrap_or_else(|| self.join("/"))
+    }
+
+    /// The application path of a request path under this prefix, if it is one.
+    pub fn strip<'a>(&self, path: &'a str) -> Option<&'a str> {
+        if self.is_root() {
+            return Some(path);
+        }
+        match path.strip_prefix(self.0.as_str())? {
+            "" => Some("/"),
+            rest if rest.starts_with('/') => Some(rest),
+            _ => None,
+        }
+    }
+}
+
+/// `public_site_url` with exactly one trailing slash, ready for [`join_url`].
+///
+/// `None` when the value is empty or not an absolute http(s) URL that paths
+/// can be appended to (one with a query or fragment, say), so callers can
+/// leave out what needs an absolute URL.
+pub fn site_root(public_site_url: &str) -> Option<String> {
+    let trimmed = public_site_url.trim();
+    let url = Url::parse(trimmed).ok()?;
+    let usable = matches!(url.scheme(), "http" | "https")
+        && url.host().is_some()
+        && url.query().is_none()
+        && url.fragment().is_none();
+    usable.then(|| format!("{}/", trimmed.trim_end_matches('/')))
+}
+
+/// Append `path` to `root` with exactly one `/` between them, whichever side
+/// already has one: `("https://example.com/blog", "/posts/a")` and
+/// `("https://example.com/blog/", "posts/a")` both give
+/// `https://example.com/blog/posts/a`. An empty path gives `root` with a
+/// trailing slash. `root` may also be root-relative, or empty for `/`.
+pub fn join_url(root: &str, path: &str) -> String {
+    format!(
+        "{}/{}",
+        root.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    )
+}
+
+/// Root-relative path of the post `slug`, `/posts/{slug}`, with the slug
+/// percent-encoded. ASCII slugs come back unchanged.
+pub fn post_path(slug: &str) -> String {
+    format!("/posts/{}", encode_path_segment(slug))
+}
+
+/// Percent-encode `segment` for use as one URL path segment.
+pub fn encode_path_segment(segment: &str) -> Cow<'_, str> {
+    utf8_percent_encode(segment, PATH_SEGMENT).into()
+}
+
+/// Decode a percent-encoded path segment, such as one taken from a raw
+/// request path. Input that does not decode to UTF-8 is returned as is.
+pub fn decode_path_segment(segment: &str) -> Cow<'_, str> {
+    percent_decode_str(segment)
+        .decode_utf8()
+        .unwrap_or(Cow::Borrowed(segment))
+}
+
+fn first_segment(route: &'static str) -> Option<&'static str> {
+    route
+        .trim_start_matches('/')
+        .split('/')
+        .next()
+        .filter(|segment| !segment.is_empty() && !segment.starts_with('{'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_reserved_slug_names_its_route() {
+        let slugs = reserved_page_slugs();
+        for expected in [
+            "tags",
+            "months",
+            "archive",
+            "posts",
+            "ui",
+            "sitemap.xml",
+            "rss.xml",
+            "atom.xml",
+            "favicon.ico",
+            "favicon.svg",
+            "apple-touch-icon.png",
+            "pages",
+            "preview",
+            "_health",
+            "readyz",
+            "robots.txt",
+            "uploads",
+            "static",
+            "api",
+        ] {
+            assert!(slugs.contains(&expected), "{expected} missing");
+            assert!(reserved_page_slug_route(expected).is_some(), "{expected}");
+        }
+        assert_eq!(reserved_page_slug_route("posts"), Some("/posts/{slug}"));
+        assert_eq!(reserved_page_slug_route("API"), Some("/api/v1"));
+    }
+
+    #[test]
+    fn base_path_normalises_and_rejects_unsafe_segments() {
+        assert!(BasePath::parse("").expect("empty").is_root());
+        assert!(BasePath::parse("/").expect("slash").is_root());
+        assert_eq!(BasePath::parse("blog/").expect("blog").as_str(), "/blog");
+        assert_eq!(
+            BasePath::parse("/sites/blog").expect("nested").as_str(),
+            "/sites/blog"
+        );
+        for invalid in ["/a//b", "/../etc", "/blog?x", "/my blog"] {
+            assert_eq!(BasePath::parse(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn base_path_prefixes_relative_and_absolute_urls() {
+        let root = BasePath::default();
+        assert_eq!(root.join("/posts/a"), "/posts/a");
+        assert_eq!(root.join("/"), "/");
+        assert_eq!(
+            root.site_url("https://example.com/").as_deref(),
+            Some("https://example.com/")
+        );
+
+        let blog = BasePath::parse("/blog").expect("blog");
+        assert_eq!(blog.join("/posts/a"), "/blog/posts/a");
+        assert_eq!(blog.join("/"), "/blog/");
+        assert_eq!(
+            blog.site_url("https://example.com").as_deref(),
+            Some("https://example.com/blog/")
+        );
+    }
+
+    #[test]
+    fn site_url_degrades_to_relative_without_a_public_site_url() {
+        let blog = BasePath::parse("/blog").expect("blog");
+        assert_eq!(blog.site_url(""), None);
+        assert_eq!(blog.site_url_or_relative(""), "/blog/");
+        assert_eq!(BasePath::default().site_url_or_relative("  "), "/");
+    }
+
+    #[test]
+    fn site_root_accepts_only_absolute_http_urls() {
+        for (input, root) in [
+            ("https://example.com", "https://example.com/"),
+            ("https://example.com/", "https://example.com/"),
+            ("https://example.com//", "https://example.com/"),
+            (" http://localhost:3000 ", "http://localhost:3000/"),
+            ("https://example.com/blog", "https://example.com/blog/"),
+        ] {
+            assert_eq!(site_root(input).as_deref(), Some(root), "{input}");
+        }
+        for input in [
+            "",
+            "example.com",
+            "/blog",
+            "ftp://example.com",
+            "https://example.com/?lang=en",
+            "https://example.com/#top",
+        ] {
+            assert_eq!(site_root(input), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn join_url_keeps_exactly_one_slash() {
+        for root in ["https://example.com", "https://example.com/"] {
+            for path in ["posts/a", "/posts/a"] {
+                assert_eq!(
+                    join_url(root, path),
+                    "https://example.com/posts/a",
+                    "{root} + {path}"
+                );
+            }
+            assert_eq!(join_url(root, ""), "https://example.com/");
+            assert_eq!(join_url(root, "/"), "https://example.com/");
+        }
+        assert_eq!(
+            join_url("https://example.com/blog/", "/sitemap.xml"),
+            "https://example.com/blog/sitemap.xml"
+        );
+        assert_eq!(join_url("", "/posts/a"), "/posts/a");
+        assert_eq!(join_url("/blog/", "posts/a"), "/blog/posts/a");
+    }
+
+    #[test]
+    fn base_path_strips_only_its_own_prefix() {
+        let blog = BasePath::parse("/blog").expect("blog");
+        assert_eq!(blog.strip("/blog"), Some("/"));
+        assert_eq!(blog.strip("/blog/posts/a"), Some("/posts/a"));
+        assert_eq!(blog.strip("/blogroll"), None);
+        assert_eq!(blog.strip("/posts/a"), None);
+        assert_eq!(BasePath::default().strip("/posts/a"), Some("/posts/a"));
+    }
+
+    #[test]
+    fn post_paths_percent_encode_unicode_slugs() {
+        assert_eq!(post_path("hello-world"), "/posts/hello-world");
+        assert_eq!(
+            post_path("こんにちは-世界"),
+            "/posts/%E3%81%93%E3%82%93%E3%81%AB%E3%81%A1%E3%81%AF-%E4%B8%96%E7%95%8C"
+        );
+        assert_eq!(encode_path_segment("a/b?c"), "a%2Fb%3Fc");
+
+        for slug in ["hello-world", "привет-мир", "こんにちは-世界"] {
+            let encoded = encode_path_segment(slug);
+            assert_eq!(decode_path_segment(&encoded), slug);
+        }
+        assert_eq!(decode_path_segment("%FF"), "%FF");
+    }
+
+    #[test]
+    fn near_misses_are_allowed() {
+        for slug in [
+            "post",
+            "posts-archive",
+            "my-tags",
+            "apis",
+            "static-site",
+            "about",
+        ] {
+            assert_eq!(reserved_page_slug_route(slug), None, "{slug}");
+        }
+    }
+}
@@ -1,19 +1,37 @@
 //! Utilities for generating deterministic, human-friendly slugs.
 //!
 //! The helpers here bridge ASCII slugification (`slug` crate) with Chinese
-//! transliteration (`pinyin` crate) so inputs like “基线对齐” become
-//! `ji-xian-dui-qi`. Consumers can provide their own uniqueness predicate to
-//! avoid persistence conflicts while keeping the slug generation logic pure.
-
-use std::collections::HashMap;
+//! transliteration (`pinyin` crate) and transliteration of other scripts
+//! (`deunicode` crate), so inputs like “基线对齐” become `ji-xian-dui-qi` and
+//! “Привет мир” becomes `privet-mir`. A [`SlugStrategy`] may instead keep
+//! letters from any script, leaving URLs to percent-encode them. Consumers can provide their own
+//! uniqueness predicate to avoid persistence conflicts while keeping the slug
+//! generation logic pure.
+
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 
+use deunicode::deunicode_char;
 use pinyin::{Pinyin, ToPinyin};
+use sha2::{Digest, Sha256};
 use slug::slugify;
 use thiserror::Error;
+use time::Date;
+use unicode_normalization::{UnicodeNormalization, is_nfc};
+use unicode_properties::{GeneralCategory, UnicodeGeneralCategory};
 
 const MAX_SUFFIX_ATTEMPTS: usize = 32;
 
+/// Longest slug, in characters, accepted from callers or produced by
+/// derivation.
+pub const MAX_SLUG_LEN: usize = 120;
+
+/// Room kept free on derived bases so a `-NN` suffix still fits.
+const SUFFIX_RESERVE: usize = 4;
+
+/// Hex digits of the hash a [`SlugStrategy`] with `hash_suffix` appends.
+const HASH_SUFFIX_LEN: usize = 6;
+
 /// Errors that can occur while generating a slug.
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum SlugError {
@@ -25,6 +43,17 @@ pub enum SlugError {
     Exhausted { base: String },
 }
 
+/// Reasons an explicitly supplied slug is rejected.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum SlugValidationError {
+    #[error("slug must not be empty")]
+    Empty,
+    #[error("slug must be at most {max} characters")]
+    TooLong { max: usize },
+    #[error("slug may only contain lowercase letters, digits and single hyphens")]
+    InvalidCharacters,
+}
+
 /// Errors that can occur while generating a slug via an async uniqueness check.
 #[derive(Debug, Error)]
 pub enum SlugAsyncError<E>
@@ -39,12 +68,144 @@ where
 
 /// Derive a base slug from the provided human-readable text.
 pub fn derive_slug(input: &str) -> Result<String, SlugError> {
+    let words = slug_words(input, true)?;
+    Ok(truncate_base(words, MAX_SLUG_LEN - SUFFIX_RESERVE))
+}
+
+/// How slugs derived from post titles are shaped.
+///
+/// The default transliterates and adds nothing else, which is what
+/// [`derive_slug`] produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlugStrategy {
+    /// Spell non-ASCII text in ASCII (pinyin for Chinese, `deunicode` for
+    /// other scripts). When off, non-ASCII characters are dropped.
+    pub transliterate: bool,
+    /// Keep letters and digits from any script, lowercased and in NFC,
+    /// instead of reducing the slug to ASCII: `東京の夜` stays `東京の夜` and
+    /// `हिन्दी` keeps its vowel signs. Takes precedence over `transliterate`.
+    pub allow_unicode: bool,
+    /// Start the slug with the date, e.g. `2026-10-16-hello-world`.
+    pub date_prefix: bool,
+    /// End the slug with a short hash of the title and date, e.g.
+    /// `hello-world-3f9a1c`, so equal titles on different days never collide.
+    pub hash_suffix: bool,
+}
+
+impl Default for SlugStrategy {
+    fn default() -> Self {
+        Self {
+            transliterate: true,
+            allow_unicode: false,
+            date_prefix: false,
+            hash_suffix: false,
+        }
+    }
+}
+
+impl SlugStrategy {
+    /// Derive a base slug for `input` dated `date`. The result depends only
+    /// on its arguments; collisions are left to [`unique_slug_from_base_async`].
+    pub fn derive(&self, input: &str, date: Date) -> Result<String, SlugError> {
+        let words = if self.allow_unicode {
+            unicode_slug_words(input)?
+        } else {
+            slug_words(input, self.transliterate)?
+        };
+
+        let prefix = if self.date_prefix {
+            format!(
+                "{:04}-{:02}-{:02}-",
+                date.year(),
+                u8::from(date.month()),
+                date.day()
+            )
+        } else {
+            String::new()
+        };
+        let suffix = if self.hash_suffix {
+            format!("-{}", short_hash(input, date))
+        } else {
+            String::new()
+        };
+
+        let limit = MAX_SLUG_LEN - SUFFIX_RESERVE - prefix.len() - suffix.len();
+        Ok(format!("{prefix}{}{suffix}", truncate_base(words, limit)))
+    }
+
+    /// Check a caller-supplied slug: [`validate_slug`], with lowercase
+    /// letters from any script, and the combining marks that follow them,
+    /// allowed when `allow_unicode` is set. Unicode slugs must be in NFC, the
+    /// form derived slugs take.
+    pub fn validate(&self, slug: &str) -> Result<(), SlugValidationError> {
+        if !self.allow_unicode {
+            return validate_slug(slug);
+        }
+        check_slug(slug, |ch| {
+            (ch.is_alphanumeric() && !ch.is_uppercase()) || is_combining_mark(ch)
+        })?;
+        let mut previous = None;
+        for ch in slug.chars() {
+            if is_combining_mark(ch) && !previous.is_some_and(continues_word) {
+                return Err(SlugValidationError::InvalidCharacters);
+            }
+            previous = Some(ch);
+        }
+        if !is_nfc(slug) {
+            return Err(SlugValidationError::InvalidCharacters);
+        }
+        Ok(())
+    }
+}
+
+/// Slugified words of `input`, transliterated or stripped down to ASCII.
+fn slug_words(input: &str, transliterate: bool) -> Result<String, SlugError> {
+    if input.trim().is_empty() {
+        return Err(SlugError::EmptyInput);
+    }
+
+    let ascii = if transliterate {
+        transliterate_to_ascii(input)
+    } else {
+        strip_non_ascii(input)
+    };
+    let candidate = slugify(&ascii);
+
+    if candidate.is_empty() {
+        return Err(SlugError::Unrepresentable {
+            input: input.to_string(),
+        });
+    }
+
+    Ok(candidate)
+}
+
+/// Lowercased runs of letters and digits from any script, joined by hyphens.
+///
+/// Input is normalized to NFC first, and combining marks are kept when they
+/// follow a letter, so vowel signs and viramas (`हिन्दी`) stay in the word.
+fn unicode_slug_words(input: &str) -> Result<String, SlugError> {
     if input.trim().is_empty() {
         return Err(SlugError::EmptyInput);
     }
 
-    let transliterated = transliterate_to_ascii(input);
-    let candidate = slugify(&transliterated);
+    let mut candidate = String::with_capacity(input.len());
+    for ch in input.nfc() {
+        if is_combining_mark(ch) && candidate.chars().next_back().is_some_and(continues_word) {
+            candidate.push(ch);
+            continue;
+        }
+        let lowered = ch
+            .is_alphanumeric()
+            .then(|| ch.to_lowercase().filter(|lower| lower.is_alphanumeric()));
+        match lowered {
+            Some(lowered) => candidate.extend(lowered),
+            None if !candidate.is_empty() && !candidate.ends_with('-') => candidate.push('-'),
+            None => {}
+        }
+    }
+    let trimmed = candidate.trim_end_matches('-').len();
+    candidate.truncate(trimmed);
 
     if candidate.is_empty() {
         return Err(SlugError::Unrepresentable {
@@ -55,6 +216,59 @@ pub fn derive_slug(input: &str) -> Result<String, SlugError> {
     Ok(candidate)
 }
 
+/// Nonspacing (Mn) and spacing (Mc) combining marks.
+fn is_combining_mark(ch: char) -> bool {
+    matches!(
+        ch.general_category(),
+        GeneralCategory::NonspacingMark | GeneralCategory::SpacingMark
+    )
+}
+
+/// Whether a combining mark may follow `previous`: a letter, or a mark
+/// already attached to one.
+fn continues_word(previous: char) -> bool {
+    previous.is_alphabetic() || is_combining_mark(previous)
+}
+
+fn short_hash(input: &str, date: Date) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(date.to_julian_day().to_be_bytes());
+    hasher.update(input.trim().as_bytes());
+    let mut hash = hex::encode(hasher.finalize());
+    hash.truncate(HASH_SUFFIX_LEN);
+    hash
+}
+
+/// Check a caller-supplied slug against the rules derived slugs follow:
+/// lowercase ASCII letters and digits separated by single hyphens, no
+/// leading or trailing hyphen, at most [`MAX_SLUG_LEN`] characters.
+pub fn validate_slug(slug: &str) -> Result<(), SlugValidationError> {
+    check_slug(slug, |ch| ch.is_ascii_lowercase() || ch.is_ascii_digit())
+}
+
+fn check_slug(slug: &str, is_word_char: impl Fn(char) -> bool) -> Result<(), SlugValidationError> {
+    if slug.is_empty() {
+        return Err(SlugValidationError::Empty);
+    }
+    if slug.chars().count() > MAX_SLUG_LEN {
+        return Err(SlugValidationError::TooLong { max: MAX_SLUG_LEN });
+    }
+    let charset_ok = slug.chars().all(|ch| ch == '-' || is_word_char(ch));
+    if !charset_ok || slug.starts_with('-') || slug.ends_with('-') || slug.contains("--") {
+        return Err(SlugValidationError::InvalidCharacters);
+    }
+    Ok(())
+}
+
+fn truncate_base(mut candidate: String, limit: usize) -> String {
+    if let Some((end, _)) = candidate.char_indices().nth(limit) {
+        candidate.truncate(end);
+        let trimmed = candidate.trim_end_matches('-').len();
+        candidate.truncate(trimmed);
+    }
+    candidate
+}
+
 /// Produce a slug that does not collide according to the supplied predicate.
 ///
 /// The `is_unique` closure must return `true` when the provided slug does not
@@ -83,7 +297,7 @@ where
 /// Async variant of [`generate_unique_slug`] that awaits the uniqueness predicate.
 pub async fn generate_unique_slug_async<F, Fut, E>(
     input: &str,
-    mut is_unique: F,
+    is_unique: F,
 ) -> Result<String, SlugAsyncError<E>>
 where
     F: FnMut(&str) -> Fut,
@@ -91,7 +305,19 @@ where
     E: std::error::Error + Send + Sync + 'static,
 {
     let base = derive_slug(input)?;
+    unique_slug_from_base_async(base, is_unique).await
+}
 
+/// Return `base`, or the first of `base-2`, `base-3`, … the predicate accepts.
+pub async fn unique_slug_from_base_async<F, Fut, E>(
+    base: String,
+    mut is_unique: F,
+) -> Result<String, SlugAsyncError<E>>
+where
+    F: FnMut(&str) -> Fut,
+    Fut: Future<Output = Result<bool, E>>,
+    E: std::error::Error + Send + Sync + 'static,
+{
     if is_unique(&base).await.map_err(SlugAsyncError::Predicate)? {
         return Ok(base);
     }
@@ -112,18 +338,20 @@ where
 /// Deterministically generate unique anchor slugs within a single document.
 ///
 /// Headings processed in order will receive monotonic suffixes when duplicates
-/// occur (e.g. `section`, `section-2`, `section-3`).
+/// occur (e.g. `section`, `section-2`, `section-3`). A suffix is skipped when
+/// an earlier heading already produced it naturally (a heading titled
+/// `Section 2`), so every anchor stays unique. Slugs depend only on the
+/// heading texts, which keeps deep links stable across re-renders.
 #[derive(Default, Debug)]
 pub struct AnchorSlugger {
     occurrences: HashMap<String, usize>,
+    used: HashSet<String>,
 }
 
 impl AnchorSlugger {
     /// Create a new slugger instance.
     pub fn new() -> Self {
-        Self {
-            occurrences: HashMap::new(),
-        }
+        Self::default()
     }
 
     /// Generate a slug for the provided heading text, ensuring uniqueness
@@ -134,11 +362,17 @@ impl AnchorSlugger {
         let count = self.occurrences.entry(base.clone()).or_insert(0);
         *count += 1;
 
-        if *count == 1 {
-            Ok(base)
+        let mut candidate = if *count == 1 {
+            base.clone()
         } else {
-            Ok(format!("{base}-{}", *count))
+            format!("{base}-{}", *count)
+        };
+        while self.used.contains(&candidate) {
+            *count += 1;
+            candidate = format!("{base}-{}", *count);
         }
+        self.used.insert(candidate.clone());
+        Ok(candidate)
     }
 }
 
@@ -154,16 +388,28 @@ fn transliterate_to_ascii(input: &str) -> String {
         match ch.to_pinyin() {
             Some(py) => append_pinyin(&mut output, py),
             None if ch.is_whitespace() => output.push(' '),
-            None => {
-                // Preserve unhandled characters so slugify can decide how to filter them.
-                output.push(ch);
-            }
+            None => match deunicode_char(ch) {
+                Some(ascii) => output.push_str(ascii),
+                // Unknown characters separate words rather than vanish.
+                None => output.push(' '),
+            },
         }
     }
 
     output
 }
 
+fn strip_non_ascii(input: &str) -> String {
+    input
+        .chars()
+        .filter_map(|ch| match ch {
+            ch if ch.is_ascii() => Some(ch),
+            ch if ch.is_whitespace() => Some(' '),
+            _ => None,
+        })
+        .collect()
+}
+
 fn append_pinyin(buffer: &mut String, pinyin: Pinyin) {
     if !buffer.is_empty() && !buffer.ends_with(' ') {
         buffer.push(' ');
@@ -181,6 +427,210 @@ mod tests {
         assert_eq!(slug, "rust-ji-chu-jiao-cheng");
     }
 
+    #[test]
+    fn derive_slug_transliterates_cyrillic() {
+        let slug = derive_slug("Привет мир").expect("slug");
+        assert_eq!(slug, "privet-mir");
+        assert_eq!(validate_slug(&slug), Ok(()));
+    }
+
+    #[test]
+    fn slug_strategy_default_matches_derive_slug() {
+        let date = time::macros::date!(2026 - 10 - 16);
+        for title in ["Rust 基础教程", "Привет мир", "Crème brûlée"] {
+            assert_eq!(
+                SlugStrategy::default().derive(title, date),
+                derive_slug(title),
+                "{title}"
+            );
+        }
+    }
+
+    #[test]
+    fn slug_strategy_prefixes_the_date() {
+        let strategy = SlugStrategy {
+            date_prefix: true,
+            ..SlugStrategy::default()
+        };
+        let slug = strategy
+            .derive("Hello World", time::macros::date!(2026 - 03 - 07))
+            .expect("slug");
+        assert_eq!(slug, "2026-03-07-hello-world");
+    }
+
+    #[test]
+    fn slug_strategy_appends_a_deterministic_hash() {
+        let strategy = SlugStrategy {
+            hash_suffix: true,
+            ..SlugStrategy::default()
+        };
+        let today = time::macros::date!(2026 - 10 - 16);
+        let tomorrow = time::macros::date!(2026 - 10 - 17);
+
+        let slug = strategy.derive("Hello World", today).expect("slug");
+        let (words, hash) = slug.rsplit_once('-').expect("hash suffix");
+        assert_eq!(words, "hello-world");
+        assert_eq!(hash.len(), HASH_SUFFIX_LEN);
+        assert!(hash.bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_eq!(strategy.derive("Hello World", today), Ok(slug.clone()));
+        assert_ne!(strategy.derive("Hello World", tomorrow), Ok(slug.clone()));
+        assert_ne!(strategy.derive("Hello Worlds", today), Ok(slug));
+    }
+
+    #[test]
+    fn slug_strategy_without_transliteration_drops_non_ascii() {
+        let strategy = SlugStrategy {
+            transliterate: false,
+            ..SlugStrategy::default()
+        };
+        let date = time::macros::date!(2026 - 10 - 16);
+        assert_eq!(
+            strategy.derive("Rust 基础教程 naïve", date),
+            Ok("rust-nave".to_string())
+        );
+        assert_eq!(
+            strategy.derive("Привет мир", date),
+            Err(SlugError::Unrepresentable {
+                input: "Привет мир".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn slug_strategy_keeps_non_latin_scripts_in_unicode_mode() {
+        let unicode = SlugStrategy {
+            allow_unicode: true,
+            ..SlugStrategy::default()
+        };
+        let date = time::macros::date!(2026 - 10 - 16);
+        let title = "東京の夜、ラーメン！ Guide";
+
+        let ascii = SlugStrategy::default().derive(title, date).expect("slug");
+        assert!(ascii.is_ascii(), "{ascii}");
+        assert_eq!(validate_slug(&ascii), Ok(()));
+
+        let slug = unicode.derive(title, date).expect("slug");
+        assert_eq!(slug, "東京の夜-ラーメン-guide");
+        assert_eq!(unicode.validate(&slug), Ok(()));
+        assert_eq!(
+            validate_slug(&slug),
+            Err(SlugValidationError::InvalidCharacters)
+        );
+        assert_eq!(
+            unicode.derive("Привет, Мир!", date),
+            Ok("привет-мир".to_string())
+        );
+        assert_eq!(
+            unicode.derive("!!! ???", date),
+            Err(SlugError::Unrepresentable {
+                input: "!!! ???".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn unicode_slugs_keep_combining_marks() {
+        let unicode = SlugStrategy {
+            allow_unicode: true,
+            ..SlugStrategy::default()
+        };
+        let date = time::macros::date!(2026 - 10 - 16);
+
+        assert_eq!(
+            unicode.derive("हिन्दी भाषा", date),
+            Ok("हिन्दी-भाषा".to_string())
+        );
+        assert_eq!(unicode.validate("हिन्दी"), Ok(()));
+        assert_eq!(
+            unicode.validate("\u{93f}हिन्दी"),
+            Err(SlugValidationError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    fn unicode_slugs_are_normalized_to_nfc() {
+        let unicode = SlugStrategy {
+            allow_unicode: true,
+            ..SlugStrategy::default()
+        };
+        let decomposed = "Cafe\u{301} Noir";
+
+        let slug = unicode
+            .derive(decomposed, time::macros::date!(2026 - 10 - 16))
+            .expect("slug");
+        assert_eq!(slug, "caf\u{e9}-noir");
+        assert_eq!(unicode.validate(&slug), Ok(()));
+        assert_eq!(
+            unicode.validate("cafe\u{301}"),
+            Err(SlugValidationError::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    fn unicode_slugs_are_truncated_on_characters() {
+        let unicode = SlugStrategy {
+            allow_unicode: true,
+            ..SlugStrategy::default()
+        };
+        let slug = unicode
+            .derive(&"東京 ".repeat(100), time::macros::date!(2026 - 10 - 16))
+            .expect("slug");
+        assert!(slug.chars().count() <= MAX_SLUG_LEN - SUFFIX_RESERVE);
+        assert!(!slug.ends_with('-'));
+        assert_eq!(unicode.validate(&slug), Ok(()));
+
+        for bad in ["Привет", "東京 夜", "-東京", "東京--夜"] {
+            assert_eq!(
+                unicode.validate(bad),
+                Err(SlugValidationError::InvalidCharacters),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn slug_strategy_keeps_long_slugs_within_the_limit() {
+        let strategy = SlugStrategy {
+            transliterate: true,
+            allow_unicode: false,
+            date_prefix: true,
+            hash_suffix: true,
+        };
+        let title = "word ".repeat(60);
+        let slug = strategy
+            .derive(&title, time::macros::date!(2026 - 10 - 16))
+            .expect("slug");
+        assert!(slug.starts_with("2026-10-16-word-"), "{slug}");
+        assert!(slug.len() <= MAX_SLUG_LEN - SUFFIX_RESERVE);
+        assert_eq!(validate_slug(&slug), Ok(()));
+    }
+
+    #[test]
+    fn derive_slug_truncates_long_titles() {
+        let title = "word ".repeat(60);
+        let slug = derive_slug(&title).expect("slug");
+        assert!(slug.len() <= MAX_SLUG_LEN - SUFFIX_RESERVE);
+        assert!(!slug.ends_with('-'));
+        assert_eq!(validate_slug(&slug), Ok(()));
+    }
+
+    #[test]
+    fn validate_slug_rejects_malformed_input() {
+        assert_eq!(validate_slug("hello-world-2"), Ok(()));
+        assert_eq!(validate_slug(""), Err(SlugValidationError::Empty));
+        for bad in ["Hello", "hello world", "-hello", "hello-", "a--b", "héllo"] {
+            assert_eq!(
+                validate_slug(bad),
+                Err(SlugValidationError::InvalidCharacters),
+                "{bad}"
+            );
+        }
+        assert_eq!(
+            validate_slug(&"a".repeat(MAX_SLUG_LEN + 1)),
+            Err(SlugValidationError::TooLong { max: MAX_SLUG_LEN })
+        );
+    }
+
     #[test]
     fn generate_unique_slug_appends_counter() {
         let mut existing = vec!["pattern-library".to_string()];
@@ -223,6 +673,42 @@ mod tests {
         assert_eq!(third, "shen-ru-li-jie");
     }
 
+    #[test]
+    fn anchor_slugger_skips_suffixes_taken_by_earlier_headings() {
+        let mut slugger = AnchorSlugger::new();
+
+        let anchors: Vec<String> = ["Overview 2", "Overview", "Overview", "Overview 2"]
+            .into_iter()
+            .map(|heading| slugger.anchor_for(heading).expect("slug"))
+            .collect();
+
+        assert_eq!(
+            anchors,
+            ["overview-2", "overview", "overview-3", "overview-2-2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn strategy_slugs_resolve_collisions_with_a_counter() {
+        let strategy = SlugStrategy {
+            date_prefix: true,
+            ..SlugStrategy::default()
+        };
+        let base = strategy
+            .derive("Hello World", time::macros::date!(2026 - 10 - 16))
+            .expect("slug");
+        let taken = ["2026-10-16-hello-world", "2026-10-16-hello-world-2"];
+
+        let slug = unique_slug_from_base_async(base, |candidate| {
+            let free = !taken.contains(&candidate);
+            async move { Ok::<bool, std::convert::Infallible>(free) }
+        })
+        .await
+        .expect("unique slug");
+
+        assert_eq!(slug, "2026-10-16-hello-world-3");
+    }
+
     #[tokio::test]
     async fn generate_unique_slug_async_works() {
         use std::sync::Arc;
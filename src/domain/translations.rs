@@ -0,0 +1,127 @@
+//! Language tags for translated posts and pages.
+//!
+//! Translations of the same content share a `translation_group` id and differ
+//! by `lang`. Tags follow the subset of BCP 47 the database accepts: a two or
+//! three letter primary subtag followed by alphanumeric subtags of two to eight
+//! characters (`en`, `pt-BR`, `zh-Hans`).
+
+use thiserror::Error;
+use uuid::Uuid;
+
+const MIN_SUBTAG_LEN: usize = 2;
+const MAX_SUBTAG_LEN: usize = 8;
+
+/// Normalize a language tag, lowercasing the primary subtag.
+///
+/// Underscores are accepted as separators (`pt_BR`). Returns `None` when the
+/// input is not a tag the database would accept.
+pub fn normalize_lang(input: &str) -> Option<String> {
+    let trimmed = input.trim();
+    let mut subtags = trimmed.split(['-', '_']);
+
+    let primary = subtags.next()?;
+    if !(2..=3).contains(&primary.len()) || !primary.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut normalized = primary.to_ascii_lowercase();
+    for subtag in subtags {
+        if !(MIN_SUBTAG_LEN..=MAX_SUBTAG_LEN).contains(&subtag.len())
+            || !subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return None;
+        }
+        normalized.push('-');
+        normalized.push_str(subtag);
+    }
+
+    Some(normalized)
+}
+
+/// Reasons a translation assignment is rejected.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TranslationError {
+    #[error("lang must be a language tag such as `en` or `pt-BR`")]
+    InvalidLang,
+    #[error("translation_group requires lang")]
+    GroupWithoutLang,
+}
+
+impl TranslationError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::InvalidLang => "lang must be a language tag such as `en` or `pt-BR`",
+            Self::GroupWithoutLang => "translation_group requires lang",
+        }
+    }
+}
+
+/// Validate a `lang`/`translation_group` pair before it is stored.
+///
+/// A blank `lang` clears the language. A group is only meaningful when its
+/// members say which language they are, so a group without `lang` is rejected.
+pub fn validate_translation(
+    lang: Option<&str>,
+    translation_group: Option<Uuid>,
+) -> Result<Option<String>, TranslationError> {
+    let lang = match lang.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => Some(normalize_lang(value).ok_or(TranslationError::InvalidLang)?),
+        None => None,
+    };
+
+    if translation_group.is_some() && lang.is_none() {
+        return Err(TranslationError::GroupWithoutLang);
+    }
+
+    Ok(lang)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_lang_accepts_common_tags() {
+        assert_eq!(normalize_lang("en").as_deref(), Some("en"));
+        assert_eq!(normalize_lang(" EN ").as_deref(), Some("en"));
+        assert_eq!(normalize_lang("pt_BR").as_deref(), Some("pt-BR"));
+        assert_eq!(normalize_lang("zh-Hans").as_deref(), Some("zh-Hans"));
+        assert_eq!(normalize_lang("yue").as_deref(), Some("yue"));
+    }
+
+    #[test]
+    fn normalize_lang_rejects_malformed_tags() {
+        for input in [
+            "",
+            "e",
+            "engl",
+            "en-",
+            "en-x",
+            "en-toolongtag",
+            "e1",
+            "en US",
+        ] {
+            assert_eq!(normalize_lang(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn validate_translation_requires_lang_for_groups() {
+        let group = Some(Uuid::new_v4());
+
+        assert_eq!(validate_translation(None, None), Ok(None));
+        assert_eq!(validate_translation(Some("  "), None), Ok(None));
+        assert_eq!(
+            validate_translation(Some("DE"), group),
+            Ok(Some("de".to_string()))
+        );
+        assert_eq!(
+            validate_translation(None, group),
+            Err(TranslationError::GroupWithoutLang)
+        );
+        assert_eq!(
+            validate_translation(Some("german"), None),
+            Err(TranslationError::InvalidLang)
+        );
+    }
+}
@@ -1,7 +1,10 @@
 //! Shared domain enumerations aligned with persisted database enums.
 
 use serde::{Deserialize, Serialize};
-pub use soffio_api_types::{NavigationDestinationType, PageStatus, PostStatus, SnapshotEntityType};
+pub use soffio_api_types::{
+    CommentStatus, FeedContentMode, HomepageLayout, HomepageMode, NavigationDestinationType,
+    PageStatus, PostStatus, RenderStatus, SnapshotEntityType,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -27,6 +30,41 @@ impl JobState {
     }
 }
 
+/// Origin of the actor an audit entry is attributed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorType {
+    /// A signed-in admin user.
+    Admin,
+    /// A request authenticated with an API key.
+    ApiKey,
+    /// Cron jobs, signals and other work nobody triggered directly.
+    System,
+}
+
+impl ActorType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ActorType::Admin => "admin",
+            ActorType::ApiKey => "api_key",
+            ActorType::System => "system",
+        }
+    }
+}
+
+impl TryFrom<&str> for ActorType {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "admin" => Ok(ActorType::Admin),
+            "api_key" => Ok(ActorType::ApiKey),
+            "system" => Ok(ActorType::System),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum JobType {
@@ -37,6 +75,7 @@ pub enum JobType {
     RenderSummary,
     PublishPost,
     PublishPage,
+    PingSitemap,
 }
 
 impl JobType {
@@ -49,6 +88,7 @@ impl JobType {
             JobType::RenderSummary => "render_summary",
             JobType::PublishPost => "publish_post",
             JobType::PublishPage => "publish_page",
+            JobType::PingSitemap => "ping_sitemap",
         }
     }
 }
@@ -65,6 +105,7 @@ impl TryFrom<&str> for JobType {
             "render_summary" => Ok(JobType::RenderSummary),
             "publish_post" => Ok(JobType::PublishPost),
             "publish_page" => Ok(JobType::PublishPage),
+            "ping_sitemap" => Ok(JobType::PingSitemap),
             _ => Err(()),
         }
     }
@@ -43,6 +43,156 @@ pub fn supports_inline_preview(content_type: &str) -> bool {
         || INLINE_PREVIEW_EXACT.contains(&content_type)
 }
 
+/// Content type stored when nothing better is known.
+pub const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Bytes of an upload [`sniff_content_type`] needs to see.
+pub const SNIFF_LEN: usize = 12;
+
+/// A content type and the `(offset, bytes)` runs that must all match for it.
+type Signature = (&'static str, &'static [(usize, &'static [u8])]);
+
+/// Magic numbers of the types uploads are checked against.
+const SIGNATURES: &[Signature] = &[
+    ("image/png", &[(0, b"\x89PNG\r\n\x1a\n")]),
+    ("image/jpeg", &[(0, b"\xff\xd8\xff")]),
+    ("image/gif", &[(0, b"GIF8")]),
+    ("image/webp", &[(0, b"RIFF"), (8, b"WEBP")]),
+    ("audio/wav", &[(0, b"RIFF"), (8, b"WAVE")]),
+    ("audio/flac", &[(0, b"fLaC")]),
+    ("application/pdf", &[(0, b"%PDF-")]),
+    ("font/woff", &[(0, b"wOFF")]),
+    ("font/woff2", &[(0, b"wOF2")]),
+];
+
+/// Other names clients send for the types in [`SIGNATURES`].
+const ALIASES: &[(&str, &str)] = &[
+    ("image/jpg", "image/jpeg"),
+    ("image/pjpeg", "image/jpeg"),
+    ("audio/x-wav", "audio/wav"),
+    ("audio/wave", "audio/wav"),
+    ("audio/vnd.wave", "audio/wav"),
+    ("audio/x-flac", "audio/flac"),
+    ("application/x-pdf", "application/pdf"),
+    ("application/font-woff", "font/woff"),
+    ("application/x-font-woff", "font/woff"),
+];
+
+/// What to do with an upload whose contents contradict its declared type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentTypePolicy {
+    /// Refuse the upload.
+    #[default]
+    Reject,
+    /// Store it under the type its contents show.
+    Detected,
+    /// Store it under the declared type.
+    Declared,
+}
+
+impl ContentTypePolicy {
+    pub const ALL: [Self; 3] = [Self::Reject, Self::Detected, Self::Declared];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reject => "reject",
+            Self::Detected => "detected",
+            Self::Declared => "declared",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|policy| policy.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
+/// An upload's contents are of a different type than declared.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("declared as {declared} but the contents are {detected}")]
+pub struct ContentTypeMismatch {
+    pub declared: String,
+    pub detected: &'static str,
+}
+
+/// Recognise a file type from its first [`SNIFF_LEN`] bytes.
+///
+/// Only formats with an unambiguous signature are recognised; text formats
+/// and containers shared by several types (ZIP, ISO media) yield `None`.
+pub fn sniff_content_type(head: &[u8]) -> Option<&'static str> {
+    SIGNATURES
+        .iter()
+        .find(|(_, runs)| {
+            runs.iter()
+                .all(|(offset, magic)| head.get(*offset..offset + magic.len()) == Some(*magic))
+        })
+        .map(|(content_type, _)| *content_type)
+}
+
+/// Content type to store for an upload declared as `declared` that starts
+/// with `head`.
+///
+/// A missing or `application/octet-stream` declaration takes the sniffed
+/// type. Unrecognised contents keep the declaration unless it names a type
+/// with a known signature, in which case they count as `application/octet-stream`.
+/// Only a specific declaration that contradicts the contents is subject to
+/// `policy`.
+pub fn resolve_content_type(
+    declared: Option<&str>,
+    head: &[u8],
+    policy: ContentTypePolicy,
+) -> Result<String, ContentTypeMismatch> {
+    let declared = declared.map(str::trim).filter(|value| !value.is_empty());
+    let detected = sniff_content_type(head);
+
+    let Some(declared) = declared else {
+        return Ok(detected.unwrap_or(OCTET_STREAM).to_string());
+    };
+    let essence = canonical_content_type(declared);
+    let detected = match detected {
+        Some(detected) => detected,
+        // A type we can recognise must look like one.
+        None if has_signature(&essence) => OCTET_STREAM,
+        None => return Ok(declared.to_string()),
+    };
+
+    if essence == OCTET_STREAM {
+        return Ok(detected.to_string());
+    }
+    if essence == detected {
+        return Ok(declared.to_string());
+    }
+    match policy {
+        ContentTypePolicy::Reject => Err(ContentTypeMismatch {
+            declared: declared.to_string(),
+            detected,
+        }),
+        ContentTypePolicy::Detected => Ok(detected.to_string()),
+        ContentTypePolicy::Declared => Ok(declared.to_string()),
+    }
+}
+
+fn has_signature(essence: &str) -> bool {
+    SIGNATURES
+        .iter()
+        .any(|(content_type, _)| *content_type == essence)
+}
+
+/// Lowercase type and subtype without parameters, with aliases resolved.
+fn canonical_content_type(content_type: &str) -> String {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == essence)
+        .map_or(essence, |(_, canonical)| (*canonical).to_string())
+}
+
 /// Structured metadata stored alongside an uploaded asset.
 ///
 /// Internally represented as a flat map so the same keys can be reused verbatim
@@ -166,3 +316,82 @@ fn validate_key(key: &str) -> Result<(), MetadataValidationError> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    const PDF: &[u8] = b"%PDF-1.7\n%\xe2\xe3";
+
+    #[test]
+    fn sniffs_signatures_at_their_offsets() {
+        assert_eq!(sniff_content_type(PNG), Some("image/png"));
+        assert_eq!(
+            sniff_content_type(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(
+            sniff_content_type(b"RIFF\0\0\0\0WAVEfmt "),
+            Some("audio/wav")
+        );
+        assert_eq!(sniff_content_type(b"RIFF"), None);
+        assert_eq!(sniff_content_type(b"hello world"), None);
+    }
+
+    #[test]
+    fn matching_or_generic_declarations_are_accepted() {
+        let resolve = |declared| resolve_content_type(declared, PNG, ContentTypePolicy::Reject);
+        assert_eq!(resolve(Some("image/png")).unwrap(), "image/png");
+        assert_eq!(resolve(Some("IMAGE/PNG; q=1")).unwrap(), "IMAGE/PNG; q=1");
+        assert_eq!(
+            resolve(Some("application/octet-stream")).unwrap(),
+            "image/png"
+        );
+        assert_eq!(resolve(None).unwrap(), "image/png");
+        assert_eq!(
+            resolve_content_type(Some("text/plain"), b"notes", ContentTypePolicy::Reject).unwrap(),
+            "text/plain"
+        );
+        assert_eq!(
+            resolve_content_type(
+                Some("image/jpg"),
+                b"\xff\xd8\xff\xe0",
+                ContentTypePolicy::Reject
+            )
+            .unwrap(),
+            "image/jpg"
+        );
+    }
+
+    #[test]
+    fn mismatches_follow_the_policy() {
+        let resolve = |policy| resolve_content_type(Some("image/png"), PDF, policy);
+        assert_eq!(
+            resolve(ContentTypePolicy::Reject).unwrap_err(),
+            ContentTypeMismatch {
+                declared: "image/png".to_string(),
+                detected: "application/pdf",
+            }
+        );
+        assert_eq!(
+            resolve(ContentTypePolicy::Detected).unwrap(),
+            "application/pdf"
+        );
+        assert_eq!(resolve(ContentTypePolicy::Declared).unwrap(), "image/png");
+    }
+
+    #[test]
+    fn unrecognised_contents_of_a_signed_type_are_a_mismatch() {
+        let resolve = |policy| resolve_content_type(Some("image/jpg"), b"<html>", policy);
+        assert_eq!(
+            resolve(ContentTypePolicy::Reject).unwrap_err(),
+            ContentTypeMismatch {
+                declared: "image/jpg".to_string(),
+                detected: OCTET_STREAM,
+            }
+        );
+        assert_eq!(resolve(ContentTypePolicy::Detected).unwrap(), OCTET_STREAM);
+        assert_eq!(resolve(ContentTypePolicy::Declared).unwrap(), "image/jpg");
+    }
+}
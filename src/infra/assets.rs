@@ -1,6 +1,14 @@
 //! Embedded static asset serving utilities.
+//!
+//! Every asset is served at its plain path (`/static/common/tokens.css`) and
+//! at a content-addressed one carrying a short hash of its bytes
+//! (`/static/common/tokens.3f9ab2c1.css`). Layouts link the hashed form via
+//! [`hashed_asset_path`], which browsers may cache forever; plain paths are
+//! only cached briefly since their contents change between releases.
 
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::LazyLock;
 
 use axum::{
     body::Body,
@@ -11,6 +19,7 @@ use axum::{
 use bytes::Bytes;
 use include_dir::{Dir, include_dir};
 use mime_guess::{Mime, MimeGuess};
+use sha2::{Digest, Sha256};
 
 use crate::application::error::ErrorReport;
 
@@ -18,28 +27,143 @@ static STATIC_PUBLIC_ASSETS: Dir<'_> = include_dir!("$OUT_DIR/static_public");
 static STATIC_ADMIN_ASSETS: Dir<'_> = include_dir!("$OUT_DIR/static_admin");
 static STATIC_SHARED_ASSETS: Dir<'_> = include_dir!("$OUT_DIR/static_common");
 
+/// Content hash of every embedded asset, keyed by its path under `/static/`
+/// (`common/tokens.css`).
+static ASSET_MANIFEST: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
+    let mut manifest = HashMap::new();
+    for (prefix, bundle) in [
+        ("public", &STATIC_PUBLIC_ASSETS),
+        ("admin", &STATIC_ADMIN_ASSETS),
+        ("common", &STATIC_SHARED_ASSETS),
+    ] {
+        collect_hashes(prefix, bundle, &mut manifest);
+    }
+    manifest
+});
+
+/// Hex digits of the content hash embedded in hashed asset URLs.
+const ASSET_HASH_LEN: usize = 8;
+
+/// `Cache-Control` for assets requested by their current content hash.
+const HASHED_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// `Cache-Control` for plain asset paths and for hashes of replaced
+/// versions, whose contents change with the next deploy.
+const UNHASHED_CACHE_CONTROL: &str = "public, max-age=300";
+
 /// Serve embedded public static assets.
 pub async fn serve_public(path: Option<Path<String>>) -> Response {
-    serve_static(&STATIC_PUBLIC_ASSETS, path, "infra::assets::serve_public")
+    serve_static(
+        "public",
+        &STATIC_PUBLIC_ASSETS,
+        path,
+        "infra::assets::serve_public",
+    )
 }
 
 /// Serve embedded admin static assets.
 pub async fn serve_admin(path: Option<Path<String>>) -> Response {
-    serve_static(&STATIC_ADMIN_ASSETS, path, "infra::assets::serve_admin")
+    serve_static(
+        "admin",
+        &STATIC_ADMIN_ASSETS,
+        path,
+        "infra::assets::serve_admin",
+    )
 }
 
 /// Serve embedded shared static assets.
 pub async fn serve_common(path: Option<Path<String>>) -> Response {
-    serve_static(&STATIC_SHARED_ASSETS, path, "infra::assets::serve_common")
+    serve_static(
+        "common",
+        &STATIC_SHARED_ASSETS,
+        path,
+        "infra::assets::serve_common",
+    )
+}
+
+/// URL path of the content-addressed version of the asset at `path` under
+/// `/static/` (`common/tokens.css` becomes `/static/common/tokens.3f9ab2c1.css`).
+/// Paths without an embedded asset are returned unhashed.
+pub fn hashed_asset_path(path: &str) -> String {
+    match ASSET_MANIFEST.get(path) {
+        Some(hash) => format!("/static/{}", with_hash(path, hash)),
+        None => format!("/static/{path}"),
+    }
+}
+
+fn collect_hashes(
+    prefix: &str,
+    dir: &'static Dir<'static>,
+    manifest: &mut HashMap<String, String>,
+) {
+    for file in dir.files() {
+        let path = format!("{prefix}/{}", file.path().to_string_lossy());
+        manifest.insert(path, content_hash(file.contents()));
+    }
+    for child in dir.dirs() {
+        collect_hashes(prefix, child, manifest);
+    }
+}
+
+fn content_hash(contents: &[u8]) -> String {
+    let mut hash = hex::encode(Sha256::digest(contents));
+    hash.truncate(ASSET_HASH_LEN);
+    hash
+}
+
+/// Insert `hash` before the extension of the file name in `path`.
+fn with_hash(path: &str, hash: &str) -> String {
+    let (dir, name) = match path.rsplit_once('/') {
+        Some((dir, name)) => (Some(dir), name),
+        None => (None, path),
+    };
+    let name = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => format!("{stem}.{hash}.{extension}"),
+        _ => format!("{name}.{hash}"),
+    };
+    match dir {
+        Some(dir) => format!("{dir}/{name}"),
+        None => name,
+    }
+}
+
+/// Split a hashed asset path into the plain path and the hash it carries,
+/// the inverse of [`with_hash`]. Returns `None` for paths without a hash.
+fn strip_hash(path: &str) -> Option<(String, &str)> {
+    let (dir, name) = match path.rsplit_once('/') {
+        Some((dir, name)) => (Some(dir), name),
+        None => (None, path),
+    };
+    let mut parts = name.rsplitn(3, '.');
+    let last = parts.next()?;
+    let second = parts.next()?;
+    let (plain, hash) = match parts.next() {
+        Some(stem) if is_asset_hash(second) => (format!("{stem}.{last}"), second),
+        None if is_asset_hash(last) => (second.to_string(), last),
+        _ => return None,
+    };
+    let plain = match dir {
+        Some(dir) => format!("{dir}/{plain}"),
+        None => plain,
+    };
+    Some((plain, hash))
+}
+
+fn is_asset_hash(value: &str) -> bool {
+    value.len() == ASSET_HASH_LEN
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
 }
 
 fn serve_static(
+    prefix: &str,
     bundle: &'static Dir<'static>,
     path: Option<Path<String>>,
     source: &'static str,
 ) -> Response {
     let captured = path.map(|Path(value)| value);
-    match resolve_asset(bundle, captured) {
+    match resolve_asset(prefix, bundle, captured) {
         Ok(Some(asset)) => asset.into_response(),
         Ok(None) => not_found_response(source),
         Err(status) => rejected_response(source, status),
@@ -63,9 +187,11 @@ fn rejected_response(source: &'static str, status: StatusCode) -> Response {
 struct Asset<'a> {
     contents: Cow<'a, [u8]>,
     mime: MimeGuess,
+    cache_control: &'static str,
 }
 
 fn resolve_asset(
+    prefix: &str,
     bundle: &'static Dir<'static>,
     path: Option<String>,
 ) -> Result<Option<Asset<'static>>, StatusCode> {
@@ -79,26 +205,49 @@ fn resolve_asset(
         return Ok(None);
     }
 
-    let Some(file) = bundle.get_file(&candidate) else {
+    if let Some(file) = bundle.get_file(&candidate) {
+        return Ok(Some(Asset {
+            contents: Cow::Borrowed(file.contents()),
+            mime: mime_guess::from_path(&candidate),
+            cache_control: UNHASHED_CACHE_CONTROL,
+        }));
+    }
+
+    let Some((plain, hash)) = strip_hash(&candidate) else {
+        return Ok(None);
+    };
+    let Some(file) = bundle.get_file(&plain) else {
         return Ok(None);
     };
 
-    let mime = mime_guess::from_path(&candidate);
-    let contents = Cow::Borrowed(file.contents());
-    Ok(Some(Asset { contents, mime }))
+    // A hash from before the last deploy still gets the current version,
+    // so pages cached across a deploy keep their styles, but only briefly.
+    let current = ASSET_MANIFEST
+        .get(&format!("{prefix}/{plain}"))
+        .is_some_and(|current| current == hash);
+    Ok(Some(Asset {
+        contents: Cow::Borrowed(file.contents()),
+        mime: mime_guess::from_path(&plain),
+        cache_control: if current {
+            HASHED_CACHE_CONTROL
+        } else {
+            UNHASHED_CACHE_CONTROL
+        },
+    }))
 }
 
 impl IntoResponse for Asset<'static> {
     fn into_response(self) -> Response {
         let mime = self.mime.first_or_octet_stream();
-        match self.contents {
-            Cow::Borrowed(slice) => build_response(Bytes::from_static(slice), mime),
-            Cow::Owned(bytes) => build_response(Bytes::from(bytes), mime),
-        }
+        let bytes = match self.contents {
+            Cow::Borrowed(slice) => Bytes::from_static(slice),
+            Cow::Owned(bytes) => Bytes::from(bytes),
+        };
+        build_response(bytes, mime, self.cache_control)
     }
 }
 
-fn build_response(bytes: Bytes, mime: Mime) -> Response {
+fn build_response(bytes: Bytes, mime: Mime, cache_control: &'static str) -> Response {
     let len = bytes.len();
     let mut response = Response::new(Body::from(bytes));
     *response.status_mut() = StatusCode::OK;
@@ -112,8 +261,103 @@ fn build_response(bytes: Bytes, mime: Mime) -> Response {
     }
     headers.insert(
         header::CACHE_CONTROL,
-        HeaderValue::from_static("public, max-age=31536000, immutable"),
+        HeaderValue::from_static(cache_control),
     );
 
     response
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn get_common(path: &str) -> Response {
+        serve_common(Some(Path(path.to_string()))).await
+    }
+
+    fn cache_control(response: &Response) -> &str {
+        response.headers()[header::CACHE_CONTROL]
+            .to_str()
+            .expect("ascii header")
+    }
+
+    #[test]
+    fn manifest_maps_assets_to_hashed_paths() {
+        let tokens = STATIC_SHARED_ASSETS
+            .get_file("tokens.css")
+            .expect("tokens.css is embedded");
+        let hash = content_hash(tokens.contents());
+
+        assert_eq!(hash.len(), ASSET_HASH_LEN);
+        assert_eq!(
+            hashed_asset_path("common/tokens.css"),
+            format!("/static/common/tokens.{hash}.css")
+        );
+        assert_eq!(
+            hashed_asset_path("common/missing.css"),
+            "/static/common/missing.css"
+        );
+    }
+
+    #[test]
+    fn hashes_round_trip_through_asset_paths() {
+        for (path, hashed) in [
+            ("app.css", "app.0123abcd.css"),
+            ("katex/katex.min.css", "katex/katex.min.0123abcd.css"),
+            ("LICENSE", "LICENSE.0123abcd"),
+        ] {
+            assert_eq!(with_hash(path, "0123abcd"), hashed);
+            assert_eq!(
+                strip_hash(hashed),
+                Some((path.to_string(), "0123abcd")),
+                "{hashed}"
+            );
+        }
+        for plain in [
+            "app.css",
+            "katex/katex.min.css",
+            "app.0123ABCD.css",
+            "app.0123.css",
+        ] {
+            assert_eq!(strip_hash(plain), None, "{plain}");
+        }
+    }
+
+    #[tokio::test]
+    async fn hashed_urls_are_immutable_and_plain_paths_short_lived() {
+        let hashed = hashed_asset_path("common/tokens.css");
+        let hashed = hashed.trim_start_matches("/static/common/");
+
+        let response = get_common(hashed).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(cache_control(&response), HASHED_CACHE_CONTROL);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/css");
+
+        let response = get_common("tokens.css").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(cache_control(&response), UNHASHED_CACHE_CONTROL);
+    }
+
+    #[tokio::test]
+    async fn unknown_hashes_fall_back_to_the_current_asset() {
+        let current = STATIC_SHARED_ASSETS
+            .get_file("tokens.css")
+            .expect("tokens.css is embedded");
+        let stale = with_hash("tokens.css", "00000000");
+        assert_ne!(
+            hashed_asset_path("common/tokens.css"),
+            format!("/static/common/{stale}")
+        );
+
+        let response = get_common(&stale).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(cache_control(&response), UNHASHED_CACHE_CONTROL);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        assert_eq!(body.as_ref(), current.contents());
+
+        let response = get_common("missing.00000000.css").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
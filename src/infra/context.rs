@@ -0,0 +1,527 @@
+//! The services `serve` runs, wired from settings and repositories.
+
+use std::sync::{Arc, atomic::AtomicU64};
+
+use crate::{
+    application::{
+        admin::{
+            audit::AdminAuditService,
+            auth::{AdminAuthService, AdminSessionKeys},
+            chrome::AdminChromeService,
+            comments::AdminCommentService,
+            dashboard::{AdminDashboardDeps, AdminDashboardService},
+            jobs::AdminJobService,
+            navigation::AdminNavigationService,
+            pages::AdminPageService,
+            posts::AdminPostService,
+            schedule::AdminScheduleService,
+            settings::AdminSettingsService,
+            snapshots::AdminSnapshotService,
+            tags::AdminTagService,
+            uploads::AdminUploadService,
+        },
+        api_keys::ApiKeyService,
+        chrome::ChromeService,
+        error::AppError,
+        feed::FeedService,
+        jobs::JobWorkerContext,
+        page::PageService,
+        render::{
+            InFlightRenders, PreviewDebouncer, RenderMailbox, RenderPreviewService, render_service,
+        },
+        repos::{
+            AdminUsersRepo, ApiKeysRepo, AuditRepo, CommentsRepo, JobsRepo, NavigationRepo,
+            NavigationWriteRepo, PagesRepo, PagesWriteRepo, PostsRepo, PostsWriteRepo,
+            SectionsRepo, SettingsRepo, SnapshotsRepo, TagsRepo, TagsWriteRepo, UploadsRepo,
+        },
+        sitemap::SitemapService,
+        snapshot_preview::{PreviewLinkKeys, SnapshotPreviewService},
+        syndication::SyndicationService,
+    },
+    cache::{
+        CacheConfig, CacheConsumer, CacheRegistry, CacheState, CacheTrigger, EventQueue, L0Store,
+        L1Store,
+    },
+    config,
+    domain::routes::BasePath,
+    infra::{
+        db::PostgresRepositories,
+        error::InfraError,
+        http::{self, AdminState, ApiState, HttpState},
+        reload::{ConfigReloader, ReloadTargets},
+        telemetry::LogLevelHandle,
+        tls::TlsConfigHandle,
+        uploads::UploadStorage,
+    },
+};
+
+/// Repository handles for each kind of traffic.
+#[derive(Clone)]
+pub struct Repositories {
+    /// Primary pool for admin, API and write traffic.
+    pub http: Arc<PostgresRepositories>,
+    /// Shares the HTTP primary pool, with site reads routed to the read
+    /// replica when `database.read_url` is set.
+    pub public: Arc<PostgresRepositories>,
+    /// Primary pool for background jobs.
+    pub jobs: Arc<PostgresRepositories>,
+}
+
+/// What a running `serve` needs to reload its configuration.
+pub struct ReloadSource {
+    /// Arguments the process was started with; reloads resolve them again.
+    pub cli: config::CliArgs,
+    pub log_level: Option<LogLevelHandle>,
+    /// Listener certificates; `serve` fills these in before building the
+    /// application context.
+    pub tls: Vec<TlsConfigHandle>,
+}
+
+/// Every service `serve` runs, wired over one set of repositories.
+pub struct ApplicationContext {
+    pub http_state: HttpState,
+    pub admin_state: AdminState,
+    pub api_state: ApiState,
+    pub job_context: JobWorkerContext,
+    pub api_keys: Arc<ApiKeyService>,
+    pub cache_trigger: Option<Arc<CacheTrigger>>,
+    /// Auto-consume interval, shared with configuration reloads.
+    pub cache_consume_interval_ms: Arc<AtomicU64>,
+}
+
+/// Sign-in attempts allowed per client address within the window.
+const LOGIN_RATE_LIMIT_MAX_ATTEMPTS: u32 = 10;
+const LOGIN_RATE_LIMIT_WINDOW: std::time::Duration = std::time::Duration::from_secs(300);
+
+fn build_site_services(
+    repositories: &Arc<PostgresRepositories>,
+    cache: Option<Arc<L0Store>>,
+    base_path: &BasePath,
+) -> (Arc<FeedService>, Arc<PageService>, Arc<ChromeService>) {
+    let posts_repo: Arc<dyn PostsRepo> = repositories.clone();
+    let sections_repo: Arc<dyn SectionsRepo> = repositories.clone();
+    let tags_repo: Arc<dyn TagsRepo> = repositories.clone();
+    let settings_repo: Arc<dyn SettingsRepo> = repositories.clone();
+    let navigation_repo: Arc<dyn NavigationRepo> = repositories.clone();
+    let pages_repo: Arc<dyn PagesRepo> = repositories.clone();
+    let comments_repo: Arc<dyn CommentsRepo> = repositories.clone();
+
+    let feed = Arc::new(
+        FeedService::new(
+            posts_repo,
+            sections_repo,
+            tags_repo,
+            settings_repo.clone(),
+            cache.clone(),
+        )
+        .with_comments(comments_repo)
+        .with_base_path(base_path.clone())
+        .with_cursor_keys(repositories.cursor_keys().clone()),
+    );
+    let page = Arc::new(PageService::new(pages_repo.clone(), cache.clone()));
+    let chrome = Arc::new(
+        ChromeService::new(navigation_repo, settings_repo, pages_repo, cache)
+            .with_base_path(base_path.clone())
+            .with_cursor_keys(repositories.cursor_keys().clone()),
+    );
+
+    (feed, page, chrome)
+}
+
+/// Build the public, admin, API and job services from `settings`.
+pub fn build_application_context(
+    repositories: Repositories,
+    settings: &config::Settings,
+    reload: ReloadSource,
+) -> Result<ApplicationContext, AppError> {
+    let Repositories {
+        http: http_repositories,
+        public: public_repositories,
+        jobs: job_repositories,
+    } = repositories;
+
+    let posts_repo: Arc<dyn PostsRepo> = http_repositories.clone();
+    let posts_write_repo: Arc<dyn PostsWriteRepo> = http_repositories.clone();
+    let sections_repo: Arc<dyn SectionsRepo> = http_repositories.clone();
+    let tags_repo: Arc<dyn TagsRepo> = http_repositories.clone();
+    let tags_write_repo: Arc<dyn TagsWriteRepo> = http_repositories.clone();
+    let settings_repo: Arc<dyn SettingsRepo> = http_repositories.clone();
+    let navigation_repo: Arc<dyn NavigationRepo> = http_repositories.clone();
+    let navigation_write_repo: Arc<dyn NavigationWriteRepo> = http_repositories.clone();
+    let pages_repo: Arc<dyn PagesRepo> = http_repositories.clone();
+    let pages_write_repo: Arc<dyn PagesWriteRepo> = http_repositories.clone();
+    let uploads_repo: Arc<dyn UploadsRepo> = http_repositories.clone();
+    let api_keys_repo: Arc<dyn ApiKeysRepo> = http_repositories.clone();
+    let audit_repo: Arc<dyn AuditRepo> = http_repositories.clone();
+    let jobs_repo: Arc<dyn JobsRepo> = http_repositories.clone();
+    let snapshots_repo: Arc<dyn SnapshotsRepo> = http_repositories.clone();
+    let comments_repo: Arc<dyn CommentsRepo> = http_repositories.clone();
+    let admin_users_repo: Arc<dyn AdminUsersRepo> = http_repositories.clone();
+
+    let job_posts_repo: Arc<dyn PostsRepo> = job_repositories.clone();
+    let job_posts_write_repo: Arc<dyn PostsWriteRepo> = job_repositories.clone();
+    let job_sections_repo: Arc<dyn SectionsRepo> = job_repositories.clone();
+    let job_tags_repo: Arc<dyn TagsRepo> = job_repositories.clone();
+    let job_settings_repo: Arc<dyn SettingsRepo> = job_repositories.clone();
+    let job_pages_repo: Arc<dyn PagesRepo> = job_repositories.clone();
+    let job_pages_write_repo: Arc<dyn PagesWriteRepo> = job_repositories.clone();
+    let job_jobs_repo: Arc<dyn JobsRepo> = job_repositories.clone();
+    let job_navigation_repo: Arc<dyn NavigationRepo> = job_repositories.clone();
+
+    let upload_storage = Arc::new(
+        UploadStorage::new(settings.uploads.directory.clone())
+            .map_err(|err| AppError::from(InfraError::Io(err)))?,
+    );
+
+    let cache_config = CacheConfig::from(&settings.cache);
+    let (cache_trigger, cache_state, l0_cache) = if cache_config.is_enabled() {
+        let l0 = Arc::new(L0Store::new(&cache_config));
+        let l1 = Arc::new(L1Store::new(&cache_config));
+        let registry = Arc::new(CacheRegistry::new());
+        let queue = Arc::new(EventQueue::new_with_limit(cache_config.max_event_queue_len));
+        let consumer = Arc::new(CacheConsumer::new(
+            cache_config.clone(),
+            l0,
+            l1.clone(),
+            registry.clone(),
+            queue.clone(),
+            http_repositories.clone(),
+        ));
+        let l0_cache = if cache_config.enable_l0_cache {
+            Some(consumer.l0().clone())
+        } else {
+            None
+        };
+        let trigger = Some(Arc::new(CacheTrigger::new(
+            cache_config.clone(),
+            queue,
+            consumer.clone(),
+        )));
+        let state = Some(CacheState {
+            config: cache_config.clone(),
+            l1,
+            registry,
+        });
+        (trigger, state, l0_cache)
+    } else {
+        (None, None, None)
+    };
+
+    let (feed_service_http, page_service_http, chrome_service_http) = build_site_services(
+        &public_repositories,
+        l0_cache.clone(),
+        &settings.server.base_path,
+    );
+    let (feed_service_jobs, page_service_jobs, chrome_service_jobs) =
+        build_site_services(&job_repositories, None, &settings.server.base_path);
+
+    let audit_service = AdminAuditService::new(audit_repo.clone())
+        .with_cursor_keys(http_repositories.cursor_keys().clone());
+    let admin_post_service = Arc::new(
+        AdminPostService::new(
+            posts_repo.clone(),
+            posts_write_repo.clone(),
+            sections_repo.clone(),
+            jobs_repo.clone(),
+            tags_repo.clone(),
+            audit_service.clone(),
+        )
+        .with_cache_trigger_opt(cache_trigger.clone())
+        .with_excerpt_length(settings.render.excerpt_length.get() as usize)
+        .with_slug_strategy(settings.slugs),
+    );
+    let admin_page_service = Arc::new(
+        AdminPageService::new(
+            pages_repo.clone(),
+            pages_write_repo.clone(),
+            jobs_repo.clone(),
+            audit_service.clone(),
+            settings_repo.clone(),
+            navigation_repo.clone(),
+        )
+        .with_cache_trigger_opt(cache_trigger.clone())
+        .with_sitemap_ping(
+            settings.sitemap.ping_urls.clone(),
+            settings.server.base_path.clone(),
+        ),
+    );
+    let admin_tag_service = Arc::new(
+        AdminTagService::new(
+            tags_repo.clone(),
+            tags_write_repo.clone(),
+            audit_service.clone(),
+        )
+        .with_cache_trigger_opt(cache_trigger.clone()),
+    );
+    let admin_navigation_service = Arc::new(
+        AdminNavigationService::new(
+            navigation_repo.clone(),
+            navigation_write_repo.clone(),
+            pages_repo.clone(),
+            audit_service.clone(),
+        )
+        .with_cache_trigger_opt(cache_trigger.clone()),
+    );
+    let admin_settings_service = Arc::new(
+        AdminSettingsService::new(
+            settings_repo.clone(),
+            uploads_repo.clone(),
+            pages_repo.clone(),
+            audit_service.clone(),
+        )
+        .with_cache_trigger_opt(cache_trigger.clone()),
+    );
+    let admin_upload_service = Arc::new(
+        AdminUploadService::new(uploads_repo.clone(), audit_service.clone())
+            .with_cursor_keys(http_repositories.cursor_keys().clone()),
+    );
+    let admin_job_service = Arc::new(AdminJobService::new(
+        jobs_repo.clone(),
+        audit_service.clone(),
+    ));
+    let admin_comment_service = Arc::new(
+        AdminCommentService::new(
+            comments_repo.clone(),
+            posts_repo.clone(),
+            audit_service.clone(),
+            settings.comments.max_body_chars.get() as usize,
+        )
+        .with_cache_trigger_opt(cache_trigger.clone()),
+    );
+    let admin_auth_service = Arc::new(AdminAuthService::new(
+        admin_users_repo,
+        AdminSessionKeys::from(&settings.admin_auth),
+    ));
+    let admin_snapshot_service = Arc::new(AdminSnapshotService::new(snapshots_repo.clone()));
+    let snapshot_preview_service = Arc::new(
+        SnapshotPreviewService::new(
+            snapshots_repo.clone(),
+            tags_repo.clone(),
+            settings_repo.clone(),
+        )
+        .with_link_keys(PreviewLinkKeys::from(&settings.admin_auth)),
+    );
+    let admin_audit_service = Arc::new(audit_service);
+    let api_key_service = Arc::new(
+        ApiKeyService::new(api_keys_repo.clone()).with_cache_trigger_opt(cache_trigger.clone()),
+    );
+
+    let job_audit_service = AdminAuditService::new(job_repositories.clone())
+        .with_cursor_keys(job_repositories.cursor_keys().clone());
+    let job_admin_post_service = Arc::new(
+        AdminPostService::new(
+            job_posts_repo.clone(),
+            job_posts_write_repo.clone(),
+            job_sections_repo.clone(),
+            job_jobs_repo.clone(),
+            job_tags_repo.clone(),
+            job_audit_service.clone(),
+        )
+        .with_cache_trigger_opt(cache_trigger.clone())
+        .with_excerpt_length(settings.render.excerpt_length.get() as usize)
+        .with_slug_strategy(settings.slugs),
+    );
+    let job_admin_page_service = Arc::new(
+        AdminPageService::new(
+            job_pages_repo.clone(),
+            job_pages_write_repo.clone(),
+            job_jobs_repo.clone(),
+            job_audit_service.clone(),
+            job_settings_repo.clone(),
+            job_navigation_repo,
+        )
+        .with_cache_trigger_opt(cache_trigger.clone())
+        .with_sitemap_ping(
+            settings.sitemap.ping_urls.clone(),
+            settings.server.base_path.clone(),
+        ),
+    );
+
+    // Public site services read through the replica when one is configured;
+    // admin and API services above stay on the primary to read their writes.
+    let public_posts_repo: Arc<dyn PostsRepo> = public_repositories.clone();
+    let public_pages_repo: Arc<dyn PagesRepo> = public_repositories.clone();
+    let public_settings_repo: Arc<dyn SettingsRepo> = public_repositories.clone();
+    let syndication_service = Arc::new(
+        SyndicationService::new(
+            public_posts_repo.clone(),
+            public_repositories.clone(),
+            public_repositories.clone(),
+            public_settings_repo.clone(),
+            l0_cache.clone(),
+        )
+        .with_base_path(settings.server.base_path.clone()),
+    );
+    let sitemap_service = Arc::new(
+        SitemapService::new(
+            public_posts_repo,
+            public_pages_repo,
+            public_settings_repo,
+            l0_cache.clone(),
+        )
+        .with_base_path(settings.server.base_path.clone())
+        .with_cursor_keys(public_repositories.cursor_keys().clone()),
+    );
+
+    let http_state = HttpState {
+        feed: feed_service_http.clone(),
+        pages: page_service_http.clone(),
+        chrome: chrome_service_http.clone(),
+        syndication: syndication_service,
+        sitemap: sitemap_service,
+        db: http_repositories.clone(),
+        upload_storage: upload_storage.clone(),
+        snapshot_preview: snapshot_preview_service.clone(),
+        cache: cache_state,
+        trailing_slash: settings.server.trailing_slash,
+        base_path: settings.server.base_path.clone(),
+        security_headers: http::SecurityHeaders::public(&settings.server.security_headers),
+    };
+
+    let render_preview_service = Arc::new(RenderPreviewService::new(
+        render_service(),
+        settings.render.preview_max_bytes.get() as usize,
+    ));
+    let render_preview_rate_limiter = Arc::new(http::ApiRateLimiter::new(
+        std::time::Duration::from_secs(
+            settings.render.preview_rate_limit_window_seconds.get() as u64
+        ),
+        settings.render.preview_rate_limit_max_requests.get(),
+    ));
+
+    let api_rate_window =
+        std::time::Duration::from_secs(settings.api_rate_limit.window_seconds.get() as u64);
+    let rate_limiter: Arc<dyn http::RateLimiter> = match settings.api_rate_limit.backend {
+        config::RateLimitBackend::Memory => Arc::new(http::ApiRateLimiter::new(
+            api_rate_window,
+            settings.api_rate_limit.max_requests.get(),
+        )),
+        config::RateLimitBackend::Postgres => Arc::new(http::PostgresRateLimiter::new(
+            http_repositories.pool().clone(),
+            api_rate_window,
+            settings.api_rate_limit.max_requests.get(),
+        )),
+    };
+    let comment_rate_limiter = Arc::new(http::ApiRateLimiter::new(
+        std::time::Duration::from_secs(settings.comments.rate_limit_window_seconds.get() as u64),
+        settings.comments.rate_limit_max_requests.get(),
+    ));
+
+    let upload_limit = Arc::new(AtomicU64::new(settings.uploads.max_request_bytes.get()));
+    let cache_consume_interval_ms =
+        Arc::new(AtomicU64::new(settings.cache.auto_consume_interval_ms));
+    let config_reload = Arc::new(ConfigReloader::new(
+        reload.cli,
+        settings.clone(),
+        ReloadTargets {
+            log_level: reload.log_level,
+            api_rate_limiter: rate_limiter.clone(),
+            comment_rate_limiter: comment_rate_limiter.clone(),
+            render_preview_rate_limiter: render_preview_rate_limiter.clone(),
+            upload_limit_bytes: upload_limit.clone(),
+            cache_consume_interval_ms: cache_consume_interval_ms.clone(),
+            tls: reload.tls,
+        },
+        admin_audit_service.clone(),
+    ));
+
+    let admin_state = AdminState {
+        db: http_repositories.clone(),
+        chrome: Arc::new(
+            AdminChromeService::new(settings_repo.clone())
+                .with_cache(l0_cache.clone())
+                .with_base_path(settings.server.base_path.clone()),
+        ),
+        dashboard: Arc::new(AdminDashboardService::new(AdminDashboardDeps {
+            posts: posts_repo.clone(),
+            pages: pages_repo.clone(),
+            tags: tags_repo.clone(),
+            navigation: navigation_repo.clone(),
+            uploads: uploads_repo.clone(),
+            api_keys: api_keys_repo.clone(),
+        })),
+        posts: admin_post_service,
+        pages: admin_page_service,
+        schedule: Arc::new(AdminScheduleService::new(
+            posts_repo.clone(),
+            pages_repo.clone(),
+        )),
+        tags: admin_tag_service,
+        navigation: admin_navigation_service,
+        settings: admin_settings_service,
+        uploads: admin_upload_service,
+        upload_storage: upload_storage.clone(),
+        upload_limit: upload_limit.clone(),
+        upload_file_limit: settings.uploads.max_file_bytes,
+        upload_content_type_policy: settings.uploads.content_type_policy,
+        jobs: admin_job_service,
+        audit: admin_audit_service,
+        api_keys: api_key_service.clone(),
+        snapshots: admin_snapshot_service.clone(),
+        snapshot_preview: snapshot_preview_service.clone(),
+        comments: admin_comment_service,
+        auth: admin_auth_service,
+        cache: cache_trigger.clone(),
+        render_preview: render_preview_service.clone(),
+        render_preview_rate_limiter: render_preview_rate_limiter.clone(),
+        render_preview_debouncer: Arc::new(PreviewDebouncer::new(settings.render.preview_debounce)),
+        login_rate_limiter: Arc::new(http::ApiRateLimiter::new(
+            LOGIN_RATE_LIMIT_WINDOW,
+            LOGIN_RATE_LIMIT_MAX_ATTEMPTS,
+        )),
+        session_cookie_secure: settings.admin_auth.cookie_secure,
+        config_reload,
+        security_headers: http::SecurityHeaders::admin(&settings.server.security_headers),
+        base_path: settings.server.base_path.clone(),
+    };
+
+    let api_state = ApiState {
+        api_keys: admin_state.api_keys.clone(),
+        posts: admin_state.posts.clone(),
+        pages: admin_state.pages.clone(),
+        schedule: admin_state.schedule.clone(),
+        tags: admin_state.tags.clone(),
+        navigation: admin_state.navigation.clone(),
+        uploads: admin_state.uploads.clone(),
+        settings: admin_state.settings.clone(),
+        jobs: admin_state.jobs.clone(),
+        audit: admin_state.audit.clone(),
+        snapshots: admin_snapshot_service.clone(),
+        comments: admin_state.comments.clone(),
+        db: http_repositories.clone(),
+        upload_storage: upload_storage.clone(),
+        upload_limit,
+        upload_content_type_policy: settings.uploads.content_type_policy,
+        rate_limiter,
+        comment_rate_limiter,
+        render_preview: render_preview_service,
+        render_preview_rate_limiter,
+    };
+
+    let render_mailbox = RenderMailbox::new();
+    let inflight_renders = InFlightRenders::new();
+
+    let job_context = JobWorkerContext {
+        repositories: job_repositories,
+        renderer: render_service(),
+        feed: feed_service_jobs,
+        pages: page_service_jobs,
+        snapshot_preview: snapshot_preview_service.clone(),
+        chrome: chrome_service_jobs,
+        upload_storage,
+        render_mailbox,
+        inflight_renders,
+        admin_posts: job_admin_post_service,
+        admin_pages: job_admin_page_service,
+    };
+
+    Ok(ApplicationContext {
+        http_state,
+        admin_state,
+        api_state,
+        job_context,
+        api_keys: api_key_service,
+        cache_trigger,
+        cache_consume_interval_ms,
+    })
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use time::OffsetDateTime;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    application::repos::{AdminUsersRepo, RepoError},
+    domain::entities::AdminUserRecord,
+};
+
+use super::{PostgresRepositories, map_sqlx_error};
+
+struct AdminUserRow {
+    id: Uuid,
+    username: String,
+    password_hash: String,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl From<AdminUserRow> for AdminUserRecord {
+    fn from(row: AdminUserRow) -> Self {
+        Self {
+            id: row.id,
+            username: row.username,
+            password_hash: row.password_hash,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[async_trait]
+impl AdminUsersRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn insert_admin_user(&self, record: AdminUserRecord) -> Result<(), RepoError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO admin_users (id, username, password_hash, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            record.id,
+            record.username,
+            record.password_hash,
+            record.created_at,
+            record.updated_at
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn find_admin_user_by_username(
+        &self,
+        username: &str,
+    ) -> Result<Option<AdminUserRecord>, RepoError> {
+        let row = sqlx::query_as!(
+            AdminUserRow,
+            r#"
+            SELECT id, username, password_hash, created_at, updated_at
+            FROM admin_users
+            WHERE username = $1
+            "#,
+            username
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(row.map(AdminUserRecord::from))
+    }
+}
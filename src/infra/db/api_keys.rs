@@ -1,6 +1,7 @@
 use sqlx::postgres::types::PgInterval;
 use sqlx::query;
 use time::OffsetDateTime;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::application::pagination::ApiKeyCursor;
@@ -74,6 +75,7 @@ impl TryFrom<ApiKeyRow> for ApiKeyRecord {
 
 #[async_trait::async_trait]
 impl ApiKeysRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_key(&self, params: CreateApiKeyParams) -> Result<ApiKeyRecord, RepoError> {
         let now = OffsetDateTime::now_utc();
         let expires_in_pg = params.expires_in.map(duration_to_pg_interval);
@@ -116,6 +118,7 @@ impl ApiKeysRepo for PostgresRepositories {
         ApiKeyRecord::try_from(row)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_keys(
         &self,
         filter: &ApiKeyQueryFilter,
@@ -254,6 +257,7 @@ impl ApiKeysRepo for PostgresRepositories {
         })
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_prefix(&self, prefix: &str) -> Result<Option<ApiKeyRecord>, RepoError> {
         let row = sqlx::query_as!(
             ApiKeyRow,
@@ -274,6 +278,7 @@ impl ApiKeysRepo for PostgresRepositories {
         row.map(ApiKeyRecord::try_from).transpose()
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_id(&self, id: Uuid) -> Result<Option<ApiKeyRecord>, RepoError> {
         let row = sqlx::query_as!(
             ApiKeyRow,
@@ -294,6 +299,7 @@ impl ApiKeysRepo for PostgresRepositories {
         row.map(ApiKeyRecord::try_from).transpose()
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn revoke_key(&self, id: Uuid, revoked_at: OffsetDateTime) -> Result<(), RepoError> {
         query!(
             r#"
@@ -313,6 +319,7 @@ impl ApiKeysRepo for PostgresRepositories {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_key(&self, id: Uuid) -> Result<bool, RepoError> {
         let result = query!(
             r#"
@@ -328,6 +335,7 @@ impl ApiKeysRepo for PostgresRepositories {
         Ok(result.rows_affected() > 0)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn expire_keys(&self) -> Result<u64, RepoError> {
         let result = query!(
             r#"
@@ -346,6 +354,7 @@ impl ApiKeysRepo for PostgresRepositories {
         Ok(result.rows_affected())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_secret(
         &self,
         params: UpdateApiKeySecretParams,
@@ -379,6 +388,7 @@ impl ApiKeysRepo for PostgresRepositories {
         ApiKeyRecord::try_from(row)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_metadata(
         &self,
         params: UpdateApiKeyMetadataParams,
@@ -409,6 +419,7 @@ impl ApiKeysRepo for PostgresRepositories {
         ApiKeyRecord::try_from(row)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_last_used(
         &self,
         id: Uuid,
@@ -1,12 +1,13 @@
 use async_trait::async_trait;
-use sqlx::QueryBuilder;
+use sqlx::{Postgres, QueryBuilder};
 use time::OffsetDateTime;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::{
     application::pagination::{AuditCursor, CursorPage, PageRequest},
     application::repos::{AuditQueryFilter, AuditRepo, RepoError},
-    domain::entities::AuditLogRecord,
+    domain::{entities::AuditLogRecord, types::ActorType},
 };
 
 use super::{PostgresRepositories, map_sqlx_error};
@@ -15,10 +16,14 @@ use super::{PostgresRepositories, map_sqlx_error};
 struct AuditRow {
     id: Uuid,
     actor: String,
+    actor_type: String,
+    actor_key_id: Option<Uuid>,
     action: String,
     entity_type: String,
     entity_id: Option<String>,
     payload_text: Option<String>,
+    changes: Option<serde_json::Value>,
+    request_id: Option<String>,
     created_at: OffsetDateTime,
 }
 
@@ -27,29 +32,70 @@ impl From<AuditRow> for AuditLogRecord {
         Self {
             id: row.id,
             actor: row.actor,
+            // The column's CHECK constraint admits only known origins.
+            actor_type: ActorType::try_from(row.actor_type.as_str()).unwrap_or(ActorType::Admin),
+            actor_key_id: row.actor_key_id,
             action: row.action,
             entity_type: row.entity_type,
             entity_id: row.entity_id,
             payload_text: row.payload_text,
+            changes: row.changes,
+            request_id: row.request_id,
             created_at: row.created_at,
         }
     }
 }
 
+/// `created_at` within `[from, to)`.
+fn push_time_range(qb: &mut QueryBuilder<'_, Postgres>, filter: &AuditQueryFilter) {
+    if let Some(from) = filter.from {
+        qb.push(" AND created_at >= ");
+        qb.push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        qb.push(" AND created_at < ");
+        qb.push_bind(to);
+    }
+}
+
+/// Entries recorded while serving the request with this id.
+fn push_request_id(qb: &mut QueryBuilder<'_, Postgres>, filter: &AuditQueryFilter) {
+    if let Some(request_id) = filter.request_id.as_ref() {
+        qb.push(" AND request_id = ");
+        qb.push_bind(request_id.clone());
+    }
+}
+
+/// Entries whose actor has this origin.
+fn push_actor_type(qb: &mut QueryBuilder<'_, Postgres>, filter: &AuditQueryFilter) {
+    if let Some(actor_type) = filter.actor_type {
+        qb.push(" AND actor_type = ");
+        qb.push_bind(actor_type.as_str());
+    }
+}
+
 #[async_trait]
 impl AuditRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn append_log(&self, record: AuditLogRecord) -> Result<(), RepoError> {
         sqlx::query!(
             r#"
-            INSERT INTO audit_logs (id, actor, action, entity_type, entity_id, payload_text, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO audit_logs (
+                id, actor, actor_type, actor_key_id, action, entity_type, entity_id,
+                payload_text, changes, request_id, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
             record.id,
             record.actor,
+            record.actor_type.as_str(),
+            record.actor_key_id,
             record.action,
             record.entity_type,
             record.entity_id,
             record.payload_text,
+            record.changes,
+            record.request_id,
             record.created_at
         )
         .execute(self.pool())
@@ -59,6 +105,7 @@ impl AuditRepo for PostgresRepositories {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_recent(&self, limit: u32) -> Result<Vec<AuditLogRecord>, RepoError> {
         let filter = AuditQueryFilter::default();
         let page = PageRequest::new(limit, None);
@@ -66,6 +113,7 @@ impl AuditRepo for PostgresRepositories {
         Ok(result.items)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_filtered(
         &self,
         page: PageRequest<AuditCursor>,
@@ -73,7 +121,8 @@ impl AuditRepo for PostgresRepositories {
     ) -> Result<CursorPage<AuditLogRecord>, RepoError> {
         let limit = page.limit.clamp(1, 200);
         let mut qb = QueryBuilder::new(
-            "SELECT id, actor, action, entity_type, entity_id, payload_text, created_at \
+            "SELECT id, actor, actor_type, actor_key_id, action, entity_type, entity_id, payload_text, \
+             changes, request_id, created_at \
              FROM audit_logs WHERE 1=1 ",
         );
 
@@ -104,6 +153,15 @@ impl AuditRepo for PostgresRepositories {
             qb.push(")");
         }
 
+        if let Some(field) = filter.field.as_ref() {
+            qb.push(" AND changes ? ");
+            qb.push_bind(field);
+        }
+
+        push_time_range(&mut qb, filter);
+        push_request_id(&mut qb, filter);
+        push_actor_type(&mut qb, filter);
+
         if let Some(cursor) = page.cursor {
             qb.push(" AND (");
             qb.push("created_at < ");
@@ -126,9 +184,9 @@ impl AuditRepo for PostgresRepositories {
 
         let records: Vec<AuditLogRecord> = rows.into_iter().map(AuditLogRecord::from).collect();
         let next_cursor = if records.len() as u32 == limit {
-            records
-                .last()
-                .map(|entry| AuditCursor::new(entry.created_at, entry.id).encode())
+            records.last().map(|entry| {
+                AuditCursor::new(entry.created_at, entry.id).encode(self.cursor_keys())
+            })
         } else {
             None
         };
@@ -136,6 +194,7 @@ impl AuditRepo for PostgresRepositories {
         Ok(CursorPage::new(records, next_cursor))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_filtered(&self, filter: &AuditQueryFilter) -> Result<u64, RepoError> {
         let mut qb = QueryBuilder::new("SELECT COUNT(*) FROM audit_logs WHERE 1=1 ");
 
@@ -163,6 +222,15 @@ impl AuditRepo for PostgresRepositories {
             qb.push(")");
         }
 
+        if let Some(field) = filter.field.as_ref() {
+            qb.push(" AND changes ? ");
+            qb.push_bind(field);
+        }
+
+        push_time_range(&mut qb, filter);
+        push_request_id(&mut qb, filter);
+        push_actor_type(&mut qb, filter);
+
         let count: i64 = qb
             .build_query_scalar()
             .fetch_one(self.pool())
@@ -172,6 +240,7 @@ impl AuditRepo for PostgresRepositories {
         Ok(count as u64)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_entity_type_counts(
         &self,
         filter: &AuditQueryFilter,
@@ -204,6 +273,15 @@ impl AuditRepo for PostgresRepositories {
             qb.push(")");
         }
 
+        if let Some(field) = filter.field.as_ref() {
+            qb.push(" AND changes ? ");
+            qb.push_bind(field);
+        }
+
+        push_time_range(&mut qb, filter);
+        push_request_id(&mut qb, filter);
+        push_actor_type(&mut qb, filter);
+
         qb.push(" GROUP BY entity_type ORDER BY count DESC");
 
         let rows: Vec<CountRow> = qb
@@ -221,6 +299,7 @@ impl AuditRepo for PostgresRepositories {
             .collect())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_distinct_actors(
         &self,
         filter: &AuditQueryFilter,
@@ -254,6 +333,15 @@ impl AuditRepo for PostgresRepositories {
             qb.push(")");
         }
 
+        if let Some(field) = filter.field.as_ref() {
+            qb.push(" AND changes ? ");
+            qb.push_bind(field);
+        }
+
+        push_time_range(&mut qb, filter);
+        push_request_id(&mut qb, filter);
+        push_actor_type(&mut qb, filter);
+
         qb.push(" GROUP BY actor ORDER BY count DESC");
 
         let rows: Vec<CountRow> = qb
@@ -271,6 +359,7 @@ impl AuditRepo for PostgresRepositories {
             .collect())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_distinct_actions(
         &self,
         filter: &AuditQueryFilter,
@@ -304,6 +393,15 @@ impl AuditRepo for PostgresRepositories {
             qb.push(")");
         }
 
+        if let Some(field) = filter.field.as_ref() {
+            qb.push(" AND changes ? ");
+            qb.push_bind(field);
+        }
+
+        push_time_range(&mut qb, filter);
+        push_request_id(&mut qb, filter);
+        push_actor_type(&mut qb, filter);
+
         qb.push(" GROUP BY action ORDER BY count DESC");
 
         let rows: Vec<CountRow> = qb
@@ -321,11 +419,13 @@ impl AuditRepo for PostgresRepositories {
             .collect())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_id(&self, id: Uuid) -> Result<Option<AuditLogRecord>, RepoError> {
         let row = sqlx::query_as!(
             AuditRow,
             r#"
-            SELECT id, actor, action, entity_type, entity_id, payload_text, created_at
+            SELECT id, actor, actor_type, actor_key_id, action, entity_type, entity_id, payload_text,
+                   changes, request_id, created_at
             FROM audit_logs
             WHERE id = $1
             "#,
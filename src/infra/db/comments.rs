@@ -0,0 +1,210 @@
+use async_trait::async_trait;
+use sqlx::{Postgres, QueryBuilder};
+use time::OffsetDateTime;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    application::pagination::{CommentCursor, CursorPage, PageRequest},
+    application::repos::{CommentQueryFilter, CommentsRepo, RepoError},
+    domain::{entities::CommentRecord, types::CommentStatus},
+};
+
+use super::{PostgresRepositories, map_sqlx_error};
+
+const COMMENT_COLUMNS: &str =
+    "id, post_id, author, body, status, source_ip, created_at, updated_at";
+
+#[derive(sqlx::FromRow)]
+struct CommentRow {
+    id: Uuid,
+    post_id: Uuid,
+    author: String,
+    body: String,
+    status: CommentStatus,
+    source_ip: Option<String>,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl From<CommentRow> for CommentRecord {
+    fn from(row: CommentRow) -> Self {
+        Self {
+            id: row.id,
+            post_id: row.post_id,
+            author: row.author,
+            body: row.body,
+            status: row.status,
+            source_ip: row.source_ip,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+fn push_comment_filter<'q>(qb: &mut QueryBuilder<'q, Postgres>, filter: &'q CommentQueryFilter) {
+    if let Some(status) = filter.status {
+        qb.push(" AND status = ");
+        qb.push_bind(status);
+    }
+
+    if let Some(post_id) = filter.post_id {
+        qb.push(" AND post_id = ");
+        qb.push_bind(post_id);
+    }
+
+    if let Some(search) = filter.search.as_ref() {
+        let pattern = format!("%{}%", search);
+        qb.push(" AND (author ILIKE ");
+        qb.push_bind(pattern.clone());
+        qb.push(" OR body ILIKE ");
+        qb.push_bind(pattern);
+        qb.push(")");
+    }
+}
+
+#[async_trait]
+impl CommentsRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn insert_comment(&self, record: CommentRecord) -> Result<(), RepoError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO comments (id, post_id, author, body, status, source_ip, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            record.id,
+            record.post_id,
+            record.author,
+            record.body,
+            record.status as CommentStatus,
+            record.source_ip,
+            record.created_at,
+            record.updated_at
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn find_comment(&self, id: Uuid) -> Result<Option<CommentRecord>, RepoError> {
+        let row = sqlx::query_as!(
+            CommentRow,
+            r#"
+            SELECT id, post_id, author, body, status AS "status: CommentStatus", source_ip,
+                   created_at, updated_at
+            FROM comments
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(row.map(CommentRecord::from))
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_comments(
+        &self,
+        filter: &CommentQueryFilter,
+        page: PageRequest<CommentCursor>,
+    ) -> Result<CursorPage<CommentRecord>, RepoError> {
+        let limit = page.limit.clamp(1, 200);
+        let mut qb =
+            QueryBuilder::new(format!("SELECT {COMMENT_COLUMNS} FROM comments WHERE 1=1 "));
+        push_comment_filter(&mut qb, filter);
+
+        if let Some(cursor) = page.cursor {
+            qb.push(" AND (created_at < ");
+            qb.push_bind(cursor.created_at());
+            qb.push(" OR (created_at = ");
+            qb.push_bind(cursor.created_at());
+            qb.push(" AND id < ");
+            qb.push_bind(cursor.id());
+            qb.push("))");
+        }
+
+        qb.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+        qb.push_bind(limit as i64);
+
+        let rows = qb
+            .build_query_as::<CommentRow>()
+            .fetch_all(self.pool())
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let records: Vec<CommentRecord> = rows.into_iter().map(CommentRecord::from).collect();
+        let next_cursor = if records.len() as u32 == limit {
+            records.last().map(|comment| {
+                CommentCursor::new(comment.created_at, comment.id).encode(self.cursor_keys())
+            })
+        } else {
+            None
+        };
+
+        Ok(CursorPage::new(records, next_cursor))
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn count_comments(&self, filter: &CommentQueryFilter) -> Result<u64, RepoError> {
+        let mut qb = QueryBuilder::new("SELECT COUNT(*) FROM comments WHERE 1=1 ");
+        push_comment_filter(&mut qb, filter);
+
+        let count: i64 = qb
+            .build_query_scalar()
+            .fetch_one(self.pool())
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(count as u64)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn update_comment_status(
+        &self,
+        id: Uuid,
+        status: CommentStatus,
+    ) -> Result<CommentRecord, RepoError> {
+        let row = sqlx::query_as!(
+            CommentRow,
+            r#"
+            UPDATE comments
+            SET status = $1, updated_at = now()
+            WHERE id = $2
+            RETURNING id, post_id, author, body, status AS "status: CommentStatus", source_ip,
+                      created_at, updated_at
+            "#,
+            status as CommentStatus,
+            id
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(row.into())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_approved_for_post(&self, post_id: Uuid) -> Result<Vec<CommentRecord>, RepoError> {
+        let rows = sqlx::query_as!(
+            CommentRow,
+            r#"
+            SELECT id, post_id, author, body, status AS "status: CommentStatus", source_ip,
+                   created_at, updated_at
+            FROM comments
+            WHERE status = 'approved' AND post_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+            post_id
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(rows.into_iter().map(CommentRecord::from).collect())
+    }
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::application::repos::{IdempotencyClaim, IdempotencyRepo, RepoError, StoredResponse};
+
+use super::{PostgresRepositories, map_sqlx_error};
+
+struct IdempotencyRow {
+    request_hash: String,
+    response_status: Option<i16>,
+    response_content_type: Option<String>,
+    response_location: Option<String>,
+    response_etag: Option<String>,
+    response_body: Option<Vec<u8>>,
+}
+
+impl IdempotencyRow {
+    fn into_claim(self) -> IdempotencyClaim {
+        let response = match (self.response_status, self.response_body) {
+            (Some(status), Some(body)) => u16::try_from(status).ok().map(|status| StoredResponse {
+                status,
+                content_type: self.response_content_type,
+                location: self.response_location,
+                etag: self.response_etag,
+                body,
+            }),
+            _ => None,
+        };
+        IdempotencyClaim::Existing {
+            request_hash: self.request_hash,
+            response,
+        }
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn claim_idempotency_key(
+        &self,
+        api_key_id: Uuid,
+        key: &str,
+        request_hash: &str,
+        ttl: Duration,
+        lease: Duration,
+    ) -> Result<IdempotencyClaim, RepoError> {
+        sqlx::query!(
+            "DELETE FROM api_idempotency_keys WHERE api_key_id = $1::uuid AND expires_at <= now()",
+            api_key_id
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        let token = Uuid::new_v4();
+        loop {
+            // Takes a free key, an expired one, or one whose request stopped
+            // answering; the row only comes back when this call won it.
+            let claimed = sqlx::query_scalar!(
+                r#"
+                INSERT INTO api_idempotency_keys (
+                    api_key_id, idempotency_key, request_hash, expires_at, claim_token
+                )
+                VALUES (
+                    $1::uuid, $2::text, $3::text,
+                    now() + make_interval(secs => $4::float8), $6::uuid
+                )
+                ON CONFLICT (api_key_id, idempotency_key) DO UPDATE
+                SET request_hash = EXCLUDED.request_hash,
+                    response_status = NULL,
+                    response_content_type = NULL,
+                    response_location = NULL,
+                    response_etag = NULL,
+                    response_body = NULL,
+                    created_at = now(),
+                    expires_at = EXCLUDED.expires_at,
+                    claim_token = EXCLUDED.claim_token,
+                    claimed_at = now()
+                WHERE api_idempotency_keys.expires_at <= now()
+                   OR (api_idempotency_keys.response_status IS NULL
+                       AND api_idempotency_keys.claimed_at
+                           <= now() - make_interval(secs => $5::float8))
+                RETURNING claim_token
+                "#,
+                api_key_id,
+                key,
+                request_hash,
+                ttl.as_secs_f64(),
+                lease.as_secs_f64(),
+                token
+            )
+            .fetch_optional(self.pool())
+            .await
+            .map_err(map_sqlx_error)?;
+            if claimed.is_some() {
+                return Ok(IdempotencyClaim::Claimed { token });
+            }
+
+            let row = sqlx::query_as!(
+                IdempotencyRow,
+                r#"
+                SELECT request_hash, response_status, response_content_type,
+                       response_location, response_etag, response_body
+                FROM api_idempotency_keys
+                WHERE api_key_id = $1::uuid AND idempotency_key = $2::text
+                "#,
+                api_key_id,
+                key
+            )
+            .fetch_optional(self.pool())
+            .await
+            .map_err(map_sqlx_error)?;
+
+            // The holder can release the key between the two statements;
+            // claim again rather than run the request without owning it.
+            if let Some(row) = row {
+                return Ok(row.into_claim());
+            }
+        }
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn complete_idempotency_key(
+        &self,
+        api_key_id: Uuid,
+        key: &str,
+        token: Uuid,
+        response: &StoredResponse,
+    ) -> Result<(), RepoError> {
+        let status = i16::try_from(response.status)
+            .map_err(|_| RepoError::from_persistence("response status out of range"))?;
+        sqlx::query!(
+            r#"
+            UPDATE api_idempotency_keys
+            SET response_status = $3::smallint,
+                response_content_type = $4::text,
+                response_body = $5::bytea,
+                response_location = $7::text,
+                response_etag = $8::text
+            WHERE api_key_id = $1::uuid AND idempotency_key = $2::text
+              AND claim_token = $6::uuid
+            "#,
+            api_key_id,
+            key,
+            status,
+            response.content_type.as_deref(),
+            response.body.as_slice(),
+            token,
+            response.location.as_deref(),
+            response.etag.as_deref(),
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn release_idempotency_key(
+        &self,
+        api_key_id: Uuid,
+        key: &str,
+        token: Uuid,
+    ) -> Result<(), RepoError> {
+        sqlx::query!(
+            r#"
+            DELETE FROM api_idempotency_keys
+            WHERE api_key_id = $1::uuid AND idempotency_key = $2::text
+              AND claim_token = $3::uuid
+            "#,
+            api_key_id,
+            key,
+            token
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+}
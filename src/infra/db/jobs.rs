@@ -3,12 +3,14 @@ use std::convert::TryFrom;
 use async_trait::async_trait;
 use sqlx::QueryBuilder;
 use time::OffsetDateTime;
+use tracing::instrument;
+use uuid::Uuid;
 
 use crate::{
     application::pagination::{CursorPage, JobCursor, PageRequest},
     application::repos::{JobQueryFilter, JobsRepo, NewJobRecord, RepoError, UpdateJobStateParams},
     domain::{
-        entities::JobRecord,
+        entities::{JobAttemptRecord, JobRecord},
         types::{JobState, JobType},
     },
 };
@@ -60,8 +62,27 @@ impl TryFrom<JobRow> for JobRecord {
     }
 }
 
+struct JobAttemptRow {
+    job_id: String,
+    attempt: i32,
+    error: String,
+    failed_at: OffsetDateTime,
+}
+
+impl From<JobAttemptRow> for JobAttemptRecord {
+    fn from(row: JobAttemptRow) -> Self {
+        Self {
+            job_id: row.job_id,
+            attempt: row.attempt,
+            error: row.error,
+            failed_at: row.failed_at,
+        }
+    }
+}
+
 #[async_trait]
 impl JobsRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn enqueue_job(&self, job: NewJobRecord) -> Result<String, RepoError> {
         let record = sqlx::query!(
             r#"
@@ -81,6 +102,7 @@ impl JobsRepo for PostgresRepositories {
         Ok(record.id)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_job_state(&self, params: UpdateJobStateParams) -> Result<(), RepoError> {
         let UpdateJobStateParams {
             id,
@@ -120,6 +142,7 @@ impl JobsRepo for PostgresRepositories {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_job(&self, id: &str) -> Result<Option<JobRecord>, RepoError> {
         let row = sqlx::query_as!(
             JobRow,
@@ -151,6 +174,74 @@ impl JobsRepo for PostgresRepositories {
         }
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn find_pending_job(
+        &self,
+        job_type: JobType,
+        payload: &serde_json::Value,
+    ) -> Result<Option<String>, RepoError> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT id
+              FROM apalis.jobs
+             WHERE job_type = $1
+               AND status = 'Pending'
+               AND job @> $2
+             ORDER BY run_at, id
+             LIMIT 1
+            "#,
+            job_type.as_str(),
+            payload
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn latest_jobs_for_entity(
+        &self,
+        job_types: &[JobType],
+        slug: &str,
+        limit: u32,
+    ) -> Result<Vec<JobRecord>, RepoError> {
+        let job_types: Vec<String> = job_types
+            .iter()
+            .map(|job_type| job_type.as_str().to_string())
+            .collect();
+        let rows = sqlx::query_as!(
+            JobRow,
+            r#"
+            SELECT id,
+                   job_type,
+                   job,
+                   status,
+                   attempts,
+                   max_attempts,
+                   run_at,
+                   last_error,
+                   lock_at,
+                   lock_by,
+                   done_at,
+                   priority
+              FROM apalis.jobs
+             WHERE job ->> 'slug' = $1
+               AND job_type = ANY($2)
+             ORDER BY id DESC
+             LIMIT $3
+            "#,
+            slug,
+            job_types.as_slice(),
+            i64::from(limit)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        rows.into_iter().map(JobRecord::try_from).collect()
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_jobs(
         &self,
         filter: &JobQueryFilter,
@@ -230,7 +321,7 @@ impl JobsRepo for PostgresRepositories {
         let next_cursor = if records.len() as u32 == limit {
             records
                 .last()
-                .map(|job| JobCursor::new(job.run_at, job.id.clone()).encode())
+                .map(|job| JobCursor::new(job.run_at, job.id.clone()).encode(self.cursor_keys()))
         } else {
             None
         };
@@ -238,6 +329,7 @@ impl JobsRepo for PostgresRepositories {
         Ok(CursorPage::new(records, next_cursor))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_jobs(&self, filter: &JobQueryFilter) -> Result<u64, RepoError> {
         let mut qb = QueryBuilder::new("SELECT COUNT(*) FROM apalis.jobs WHERE 1=1 ");
 
@@ -275,4 +367,47 @@ impl JobsRepo for PostgresRepositories {
 
         Ok(count as u64)
     }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_job_attempt(
+        &self,
+        job_id: &str,
+        attempt: i32,
+        error: &str,
+    ) -> Result<(), RepoError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO job_attempts (id, job_id, attempt, error)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            Uuid::new_v4(),
+            job_id,
+            attempt,
+            error
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_job_attempts(&self, job_id: &str) -> Result<Vec<JobAttemptRecord>, RepoError> {
+        let rows = sqlx::query_as!(
+            JobAttemptRow,
+            r#"
+            SELECT job_id, attempt, error, failed_at
+              FROM job_attempts
+             WHERE job_id = $1
+             ORDER BY failed_at, attempt
+            "#,
+            job_id
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(rows.into_iter().map(JobAttemptRecord::from).collect())
+    }
 }
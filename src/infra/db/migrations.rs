@@ -0,0 +1,139 @@
+//! Comparison of the live `_sqlx_migrations` table with the migrations
+//! embedded in the binary.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use sqlx::{PgPool, Row};
+use time::OffsetDateTime;
+
+/// How one migration version compares between the binary and the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    /// Applied, and the recorded checksum matches the embedded file.
+    Applied,
+    /// Embedded but not yet applied.
+    Pending,
+    /// Applied with a checksum that differs from the embedded file.
+    ChecksumMismatch,
+    /// Recorded as attempted but not completed.
+    Failed,
+    /// Applied but not embedded in this binary.
+    Unknown,
+}
+
+impl MigrationState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Applied => "applied",
+            Self::Pending => "pending",
+            Self::ChecksumMismatch => "checksum_mismatch",
+            Self::Failed => "failed",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// Whether the live schema has drifted from the binary for this version.
+    /// Pending migrations are not divergence: `serve` applies them.
+    pub fn is_divergent(self) -> bool {
+        matches!(self, Self::ChecksumMismatch | Self::Failed | Self::Unknown)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub applied_at: Option<OffsetDateTime>,
+    pub state: MigrationState,
+}
+
+struct AppliedMigration {
+    description: String,
+    installed_on: OffsetDateTime,
+    success: bool,
+    checksum: Vec<u8>,
+}
+
+/// Every embedded or applied migration, ordered by version.
+pub async fn migration_status(pool: &PgPool) -> Result<Vec<MigrationStatus>, sqlx::Error> {
+    let applied = fetch_applied(pool).await?;
+    let mut statuses = BTreeMap::new();
+
+    for migration in sqlx::migrate!("./migrations").iter() {
+        if migration.migration_type.is_down_migration() {
+            continue;
+        }
+        let status = match applied.get(&migration.version) {
+            None => MigrationStatus {
+                version: migration.version,
+                description: migration.description.to_string(),
+                applied_at: None,
+                state: MigrationState::Pending,
+            },
+            Some(row) => MigrationStatus {
+                version: migration.version,
+                description: migration.description.to_string(),
+                applied_at: Some(row.installed_on),
+                state: if !row.success {
+                    MigrationState::Failed
+                } else if row.checksum.as_slice() != migration.checksum.as_ref() {
+                    MigrationState::ChecksumMismatch
+                } else {
+                    MigrationState::Applied
+                },
+            },
+        };
+        statuses.insert(migration.version, status);
+    }
+
+    for (version, row) in applied {
+        statuses.entry(version).or_insert_with(|| MigrationStatus {
+            version,
+            description: row.description,
+            applied_at: Some(row.installed_on),
+            state: MigrationState::Unknown,
+        });
+    }
+
+    Ok(statuses.into_values().collect())
+}
+
+/// Embedded migrations not yet applied, ordered by version.
+pub async fn pending_migrations(pool: &PgPool) -> Result<Vec<MigrationStatus>, sqlx::Error> {
+    let mut statuses = migration_status(pool).await?;
+    statuses.retain(|status| status.state == MigrationState::Pending);
+    Ok(statuses)
+}
+
+async fn fetch_applied(pool: &PgPool) -> Result<BTreeMap<i64, AppliedMigration>, sqlx::Error> {
+    let exists: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+    if !exists {
+        return Ok(BTreeMap::new());
+    }
+
+    let rows = sqlx::query(
+        "SELECT version, description, installed_on, success, checksum \
+         FROM _sqlx_migrations ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut applied = BTreeMap::new();
+    for row in rows {
+        applied.insert(
+            row.try_get("version")?,
+            AppliedMigration {
+                description: row.try_get("description")?,
+                installed_on: row.try_get("installed_on")?,
+                success: row.try_get("success")?,
+                checksum: row.try_get("checksum")?,
+            },
+        );
+    }
+    Ok(applied)
+}
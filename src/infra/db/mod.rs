@@ -1,11 +1,19 @@
 //! Postgres-backed repository implementations.
 
+mod admin_users;
 pub(crate) mod api_keys;
 mod audit;
+mod comments;
+mod idempotency;
 mod jobs;
+mod migrations;
 mod navigation;
 mod pages;
+mod post_drafts;
 mod posts;
+mod read_routing;
+mod references;
+mod render_plan;
 mod settings;
 mod snapshots;
 mod tags;
@@ -13,19 +21,27 @@ mod timezone;
 mod uploads;
 mod util;
 
+pub use migrations::{MigrationState, MigrationStatus, migration_status, pending_migrations};
+pub use post_drafts::{PostDraft, PostDraftContent, PostDraftKey};
 pub use posts::{PersistedPostSection, PersistedPostSectionOwned};
+pub use read_routing::on_primary;
+pub use render_plan::{RenderPlan, RenderSelection};
 pub use timezone::DbTimeZone;
 pub use util::map_sqlx_error;
 
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc, time::Duration};
 
 use sqlx::{
-    Postgres, QueryBuilder, Transaction,
-    postgres::{PgPool, PgPoolOptions},
+    ConnectOptions, Executor, Postgres, QueryBuilder, Transaction,
+    postgres::{PgConnectOptions, PgPool, PgPoolOptions},
     query,
 };
 
+use tracing::warn;
+
+use crate::application::pagination::CursorKeys;
 use crate::application::repos::{PostListScope, PostQueryFilter, RepoError};
+use crate::domain::routes::{reserved_page_slug_route, reserved_page_slugs};
 use crate::domain::types::PostStatus;
 
 const POSTS_PRIMARY_TIME_EXPR: &str = "CASE \
@@ -35,38 +51,155 @@ const POSTS_PRIMARY_TIME_EXPR: &str = "CASE \
         COALESCE(p.updated_at, p.created_at) \
 END";
 
+/// Per-connection limits applied when a pool is opened.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConnectionLimits {
+    /// How long a query waits for a free connection; `None` keeps the sqlx
+    /// default of 30 seconds.
+    pub acquire_timeout: Option<Duration>,
+    /// `SET statement_timeout` issued on every new connection.
+    pub statement_timeout: Option<Duration>,
+    /// Statements slower than this are logged at warn level by sqlx, inside
+    /// the span of the repository method that issued them.
+    pub slow_query_threshold: Option<Duration>,
+}
+
+/// Postgres-backed repositories.
+///
+/// Writes, transactions and admin-facing reads use the primary pool. The
+/// read-only site traits (posts, sections, pages, tags, settings and
+/// navigation lookups) use the read pool when one is attached, except inside
+/// [`on_primary`].
 #[derive(Clone)]
 pub struct PostgresRepositories {
     pool: Arc<PgPool>,
+    read_pool: Option<Arc<PgPool>>,
+    cursor_keys: CursorKeys,
+}
+
+/// Log live pages whose slug is shadowed by a built-in public route. Such
+/// pages predate reserved-slug validation and are unreachable until renamed.
+async fn warn_reserved_page_slugs(pool: &PgPool) {
+    let reserved: Vec<String> = reserved_page_slugs()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let rows = sqlx::query!(
+        "SELECT id, slug FROM pages WHERE deleted_at IS NULL AND lower(slug) = ANY($1)",
+        &reserved
+    )
+    .fetch_all(pool)
+    .await;
+
+    match rows {
+        Ok(rows) => {
+            for row in rows {
+                warn!(
+                    target = "infra::db::migrations",
+                    page_id = %row.id,
+                    slug = %row.slug,
+                    route = reserved_page_slug_route(&row.slug).unwrap_or_default(),
+                    "page slug is shadowed by a built-in route; rename the page"
+                );
+            }
+        }
+        Err(err) => warn!(
+            target = "infra::db::migrations",
+            error = %err,
+            "failed to check page slugs against reserved routes"
+        ),
+    }
 }
 
 impl PostgresRepositories {
     pub fn new(pool: PgPool) -> Self {
         Self {
             pool: Arc::new(pool),
+            read_pool: None,
+            cursor_keys: CursorKeys::ephemeral(),
+        }
+    }
+
+    /// Share this primary pool and route read-only site queries to `read_pool`.
+    pub fn with_read_pool(&self, read_pool: PgPool) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            read_pool: Some(Arc::new(read_pool)),
+            cursor_keys: self.cursor_keys.clone(),
         }
     }
 
+    /// Sign the cursors of listed pages with `keys`.
+    pub fn with_cursor_keys(mut self, keys: CursorKeys) -> Self {
+        self.cursor_keys = keys;
+        self
+    }
+
+    /// Keys the cursors issued by these repositories verify against.
+    pub fn cursor_keys(&self) -> &CursorKeys {
+        &self.cursor_keys
+    }
+
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
 
+    /// Pool for read-only site queries: the replica if attached and the
+    /// current request has not forced the primary, else the primary.
+    pub fn read_pool(&self) -> &PgPool {
+        match self.read_pool.as_deref() {
+            Some(replica) if !read_routing::primary_forced() => replica,
+            _ => &self.pool,
+        }
+    }
+
     pub async fn begin(&self) -> Result<Transaction<'_, Postgres>, sqlx::Error> {
         self.pool.begin().await
     }
 
-    pub async fn connect(url: &str, max_connections: u32) -> Result<PgPool, sqlx::Error> {
-        PgPoolOptions::new()
-            .max_connections(max_connections)
-            .connect(url)
+    pub async fn connect(
+        url: &str,
+        max_connections: u32,
+        limits: ConnectionLimits,
+    ) -> Result<PgPool, sqlx::Error> {
+        let options = PgConnectOptions::from_str(url)?;
+        Self::connect_with(options, max_connections, limits).await
+    }
+
+    pub async fn connect_with(
+        options: PgConnectOptions,
+        max_connections: u32,
+        limits: ConnectionLimits,
+    ) -> Result<PgPool, sqlx::Error> {
+        let options = match limits.slow_query_threshold {
+            Some(threshold) => options.log_slow_statements(log::LevelFilter::Warn, threshold),
+            None => options.log_slow_statements(log::LevelFilter::Off, Duration::MAX),
+        };
+        let statement_timeout_ms = limits.statement_timeout.map(|timeout| timeout.as_millis());
+
+        let mut pool_options = PgPoolOptions::new().max_connections(max_connections);
+        if let Some(timeout) = limits.acquire_timeout {
+            pool_options = pool_options.acquire_timeout(timeout);
+        }
+
+        pool_options
+            .after_connect(move |conn, _meta| {
+                Box::pin(async move {
+                    if let Some(ms) = statement_timeout_ms {
+                        conn.execute(format!("SET statement_timeout = {ms}").as_str())
+                            .await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect_with(options)
             .await
     }
 
     pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::Error> {
-        sqlx::migrate!("./migrations")
-            .run(pool)
-            .await
-            .map_err(Into::into)
+        sqlx::migrate!("./migrations").run(pool).await?;
+        warn_reserved_page_slugs(pool).await;
+        Ok(())
     }
 
     pub async fn health_check(&self) -> Result<(), sqlx::Error> {
@@ -78,6 +211,7 @@ impl PostgresRepositories {
     }
 
     fn apply_scope_conditions<'q>(qb: &mut QueryBuilder<'q, Postgres>, scope: PostListScope) {
+        qb.push(" AND p.deleted_at IS NULL ");
         match scope {
             PostListScope::Public => {
                 qb.push(" AND p.status = ");
@@ -110,6 +244,14 @@ impl PostgresRepositories {
             qb.push(" ");
         }
 
+        if let Some(year) = filter.year {
+            qb.push(" AND to_char(");
+            Self::push_primary_time_expr(qb);
+            qb.push(", 'YYYY') = ");
+            qb.push_bind(format!("{year:04}"));
+            qb.push(" ");
+        }
+
         if let Some(search) = filter.search.as_ref() {
             qb.push(" AND (");
             qb.push("p.title ILIKE ");
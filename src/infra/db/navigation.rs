@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use sqlx::QueryBuilder;
 use time::OffsetDateTime;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::{
@@ -35,13 +36,9 @@ fn map_sqlx_error(err: sqlx::Error) -> RepoError {
         sqlx::Error::Database(db) if db.message().contains("violates") => RepoError::Integrity {
             message: db.message().to_string(),
         },
-        sqlx::Error::Database(db)
-            if db
-                .message()
-                .contains("canceling statement due to user request") =>
-        {
-            RepoError::Timeout
-        }
+        // 57014 query_canceled: statement_timeout or an explicit cancel.
+        sqlx::Error::Database(db) if db.code().as_deref() == Some("57014") => RepoError::Timeout,
+        sqlx::Error::PoolTimedOut => RepoError::Timeout,
         other => RepoError::from_persistence(other),
     }
 }
@@ -82,6 +79,7 @@ impl From<NavigationItemRow> for NavigationItemRecord {
 
 #[async_trait]
 impl NavigationRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_navigation(
         &self,
         visibility: Option<bool>,
@@ -151,7 +149,7 @@ impl NavigationRepo for PostgresRepositories {
 
         let mut rows = qb
             .build_query_as::<NavigationItemRow>()
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
@@ -161,7 +159,7 @@ impl NavigationRepo for PostgresRepositories {
                 .pop()
                 .expect("navigation query should have at least one extra row when truncated");
             let cursor = NavigationCursor::new(extra.sort_order, extra.primary_time, extra.id);
-            Some(cursor.encode())
+            Some(cursor.encode(self.cursor_keys()))
         } else {
             None
         };
@@ -171,6 +169,7 @@ impl NavigationRepo for PostgresRepositories {
         Ok(CursorPage::new(records, next_cursor))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_navigation(
         &self,
         visibility: Option<bool>,
@@ -215,7 +214,7 @@ impl NavigationRepo for PostgresRepositories {
 
         let count: i64 = qb
             .build_query_scalar()
-            .fetch_one(self.pool())
+            .fetch_one(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
@@ -224,6 +223,7 @@ impl NavigationRepo for PostgresRepositories {
         })?)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_external_navigation(
         &self,
         visibility: Option<bool>,
@@ -269,7 +269,7 @@ impl NavigationRepo for PostgresRepositories {
 
         let count: i64 = qb
             .build_query_scalar()
-            .fetch_one(self.pool())
+            .fetch_one(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
@@ -278,6 +278,7 @@ impl NavigationRepo for PostgresRepositories {
         })?)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_id(&self, id: Uuid) -> Result<Option<NavigationItemRecord>, RepoError> {
         let row = sqlx::query_as!(
             NavigationItemRow,
@@ -300,16 +301,53 @@ impl NavigationRepo for PostgresRepositories {
             "#,
             id
         )
-        .fetch_optional(self.pool())
+        .fetch_optional(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(row.map(NavigationItemRecord::from))
     }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_visible_links_to_page(
+        &self,
+        page_id: Uuid,
+    ) -> Result<Vec<NavigationItemRecord>, RepoError> {
+        let rows = sqlx::query_as!(
+            NavigationItemRow,
+            r#"
+            SELECT ni.id,
+                   ni.label,
+                   ni.destination_type AS "destination_type: NavigationDestinationType",
+                   ni.destination_page_id,
+                   p.slug AS "destination_page_slug?",
+                   ni.destination_url,
+                   ni.sort_order,
+                   ni.visible,
+                   ni.open_in_new_tab,
+                   ni.created_at,
+                   COALESCE(ni.updated_at, ni.created_at) AS "primary_time!",
+                   ni.updated_at
+            FROM navigation_items ni
+            LEFT JOIN pages p ON p.id = ni.destination_page_id
+            WHERE ni.destination_type = 'internal'::navigation_destination_type
+              AND ni.destination_page_id = $1
+              AND ni.visible
+            ORDER BY ni.sort_order ASC, ni.created_at ASC, ni.id ASC
+            "#,
+            page_id
+        )
+        .fetch_all(self.read_pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(rows.into_iter().map(NavigationItemRecord::from).collect())
+    }
 }
 
 #[async_trait]
 impl NavigationWriteRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_navigation_item(
         &self,
         params: CreateNavigationItemParams,
@@ -360,6 +398,7 @@ impl NavigationWriteRepo for PostgresRepositories {
         Ok(NavigationItemRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_navigation_item(
         &self,
         params: UpdateNavigationItemParams,
@@ -412,6 +451,7 @@ impl NavigationWriteRepo for PostgresRepositories {
         Ok(NavigationItemRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_navigation_item(&self, id: Uuid) -> Result<(), RepoError> {
         sqlx::query!(
             r#"
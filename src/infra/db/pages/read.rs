@@ -2,30 +2,43 @@ use async_trait::async_trait;
 use futures::{StreamExt, stream::BoxStream};
 use sqlx::QueryBuilder;
 use time::OffsetDateTime;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::{
     application::pagination::{CursorPage, PageCursor},
     application::repos::{PageQueryFilter, PagesRepo, RepoError},
-    domain::{entities::PageRecord, types::PageStatus},
+    domain::{
+        entities::{CalendarRecord, PageRecord, ScheduledRecord, TranslationRecord},
+        types::{PageStatus, RenderStatus},
+    },
 };
 
 use super::PostgresRepositories;
 use super::types::{PAGE_PRIMARY_TIME_EXPR, PageListRow, PageRow};
-use crate::infra::db::map_sqlx_error;
+use crate::infra::db::{RenderSelection, map_sqlx_error};
 
 impl PostgresRepositories {
-    pub fn stream_all_pages(&self) -> BoxStream<'_, Result<PageRecord, RepoError>> {
+    pub fn stream_all_pages<'a>(
+        &'a self,
+        selection: &'a RenderSelection,
+    ) -> BoxStream<'a, Result<PageRecord, RepoError>> {
         let stream = sqlx::query_as!(
             PageRow,
             r#"
             SELECT id, slug, title, body_markdown, rendered_html,
                    status AS "status: PageStatus",
-                   scheduled_at, published_at, archived_at,
+                   scheduled_at, published_at, archived_at, lang, translation_group, template,
+                   render_status AS "render_status: RenderStatus", render_error, render_failed_at,
                    created_at, updated_at
             FROM pages
+            WHERE deleted_at IS NULL
+              AND ($1::text[] IS NULL OR slug = ANY($1))
+              AND ($2::timestamptz IS NULL OR updated_at > $2)
             ORDER BY slug
-            "#
+            "#,
+            selection.slug_filter(),
+            selection.updated_since
         )
         .fetch(self.pool())
         .map(|row| match row {
@@ -35,10 +48,57 @@ impl PostgresRepositories {
 
         Box::pin(stream)
     }
+
+    /// Ids of the admin pages matching `status` and `filter`, in admin list
+    /// order, at most `limit` of them. Read from the primary, since callers
+    /// act on them straight away.
+    pub async fn list_admin_page_ids(
+        &self,
+        status: Option<PageStatus>,
+        filter: &PageQueryFilter,
+        limit: u64,
+    ) -> Result<Vec<Uuid>, RepoError> {
+        let mut qb = QueryBuilder::new("SELECT id FROM pages WHERE deleted_at IS NULL ");
+
+        if let Some(status) = status {
+            qb.push("AND status = ");
+            qb.push_bind(status);
+        }
+
+        if let Some(search) = filter.search.as_ref() {
+            qb.push(" AND (");
+            qb.push("title ILIKE ");
+            qb.push_bind(format!("%{}%", search));
+            qb.push(" OR slug ILIKE ");
+            qb.push_bind(format!("%{}%", search));
+            qb.push(" OR rendered_html ILIKE ");
+            qb.push_bind(format!("%{}%", search));
+            qb.push(")");
+        }
+
+        if let Some(month) = filter.month.as_ref() {
+            qb.push(" AND to_char(");
+            qb.push(PAGE_PRIMARY_TIME_EXPR);
+            qb.push(", 'YYYY-MM') = ");
+            qb.push_bind(month);
+            qb.push(" ");
+        }
+
+        qb.push(" ORDER BY ");
+        qb.push(PAGE_PRIMARY_TIME_EXPR);
+        qb.push(" DESC, id DESC LIMIT ");
+        qb.push_bind(i64::try_from(limit).unwrap_or(i64::MAX));
+
+        qb.build_query_scalar()
+            .fetch_all(self.pool())
+            .await
+            .map_err(map_sqlx_error)
+    }
 }
 
 #[async_trait]
 impl PagesRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_pages(
         &self,
         status: Option<PageStatus>,
@@ -49,10 +109,12 @@ impl PagesRepo for PostgresRepositories {
         let limit = limit.clamp(1, 100) as i64;
         let mut qb = QueryBuilder::new(
             "SELECT id, slug, title, body_markdown, rendered_html, status, \
-             scheduled_at, published_at, archived_at, created_at, updated_at, ",
+             scheduled_at, published_at, archived_at, lang, translation_group, template, \
+             render_status, render_error, render_failed_at, \
+             created_at, updated_at, ",
         );
         qb.push(PAGE_PRIMARY_TIME_EXPR);
-        qb.push(" AS primary_time FROM pages WHERE 1=1 ");
+        qb.push(" AS primary_time FROM pages WHERE deleted_at IS NULL ");
 
         if let Some(status) = status {
             qb.push("AND status = ");
@@ -104,13 +166,13 @@ impl PagesRepo for PostgresRepositories {
 
         let mut rows = qb
             .build_query_as::<PageListRow>()
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
         let next_cursor = if (rows.len() as i64) > limit {
             let overflow = rows.pop().expect("overflow row exists when len > limit");
-            Some(PageCursor::new(overflow.primary_time, overflow.id).encode())
+            Some(PageCursor::new(overflow.primary_time, overflow.id).encode(self.cursor_keys()))
         } else {
             None
         };
@@ -120,31 +182,36 @@ impl PagesRepo for PostgresRepositories {
         Ok(CursorPage::new(records, next_cursor))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_slug(&self, slug: &str) -> Result<Option<PageRecord>, RepoError> {
         let row = sqlx::query_as!(
             PageRow,
             r#"
             SELECT id, slug, title, body_markdown, rendered_html,
                    status AS "status: PageStatus",
-                   scheduled_at, published_at, archived_at, created_at, updated_at
+                   scheduled_at, published_at, archived_at, lang, translation_group, template,
+                   render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                   created_at, updated_at
             FROM pages
             WHERE slug = $1
+              AND deleted_at IS NULL
             "#,
             slug
         )
-        .fetch_optional(self.pool())
+        .fetch_optional(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(row.map(PageRecord::from))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_pages(
         &self,
         status: Option<PageStatus>,
         filter: &PageQueryFilter,
     ) -> Result<u64, RepoError> {
-        let mut qb = QueryBuilder::new("SELECT COUNT(*) FROM pages WHERE 1=1 ");
+        let mut qb = QueryBuilder::new("SELECT COUNT(*) FROM pages WHERE deleted_at IS NULL ");
 
         if let Some(status) = status {
             qb.push("AND status = ");
@@ -172,32 +239,37 @@ impl PagesRepo for PostgresRepositories {
 
         let count: i64 = qb
             .build_query_scalar()
-            .fetch_one(self.pool())
+            .fetch_one(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
         Ok(count as u64)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_id(&self, id: Uuid) -> Result<Option<PageRecord>, RepoError> {
         let row = sqlx::query_as!(
             PageRow,
             r#"
             SELECT id, slug, title, body_markdown, rendered_html,
                    status AS "status: PageStatus",
-                   scheduled_at, published_at, archived_at, created_at, updated_at
+                   scheduled_at, published_at, archived_at, lang, translation_group, template,
+                   render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                   created_at, updated_at
             FROM pages
             WHERE id = $1
+              AND deleted_at IS NULL
             "#,
             id
         )
-        .fetch_optional(self.pool())
+        .fetch_optional(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(row.map(PageRecord::from))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_month_counts(
         &self,
         status: Option<PageStatus>,
@@ -211,7 +283,7 @@ impl PagesRepo for PostgresRepositories {
 
         let mut qb = QueryBuilder::new("SELECT date_trunc('month', ");
         qb.push(PAGE_PRIMARY_TIME_EXPR);
-        qb.push(") AS bucket, COUNT(*) AS count FROM pages WHERE 1=1 ");
+        qb.push(") AS bucket, COUNT(*) AS count FROM pages WHERE deleted_at IS NULL ");
 
         if let Some(status) = status {
             qb.push("AND status = ");
@@ -237,7 +309,7 @@ impl PagesRepo for PostgresRepositories {
 
         let rows: Vec<MonthRow> = qb
             .build_query_as::<MonthRow>()
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
@@ -255,6 +327,80 @@ impl PagesRepo for PostgresRepositories {
 
         Ok(counts)
     }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_page_translations(
+        &self,
+        translation_group: Uuid,
+    ) -> Result<Vec<TranslationRecord>, RepoError> {
+        sqlx::query_as!(
+            TranslationRecord,
+            r#"
+            SELECT id, slug, lang AS "lang!"
+            FROM pages
+            WHERE translation_group = $1
+              AND lang IS NOT NULL
+              AND status = 'published'::page_status
+              AND published_at IS NOT NULL
+              AND deleted_at IS NULL
+            ORDER BY lang
+            "#,
+            translation_group
+        )
+        .fetch_all(self.read_pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_scheduled_pages(&self, limit: u32) -> Result<Vec<ScheduledRecord>, RepoError> {
+        sqlx::query_as!(
+            ScheduledRecord,
+            r#"
+            SELECT id, slug, title, scheduled_at AS "scheduled_at!"
+            FROM pages
+            WHERE status = 'draft'::page_status
+              AND scheduled_at IS NOT NULL
+              AND deleted_at IS NULL
+            ORDER BY scheduled_at, id
+            LIMIT $1
+            "#,
+            i64::from(limit)
+        )
+        .fetch_all(self.read_pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_calendar_pages(
+        &self,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> Result<Vec<CalendarRecord>, RepoError> {
+        sqlx::query_as!(
+            CalendarRecord,
+            r#"
+            SELECT id, slug, title, scheduled AS "scheduled!", at AS "at!"
+            FROM (
+                SELECT id, slug, title,
+                       status = 'draft'::page_status AS scheduled,
+                       CASE WHEN status = 'draft'::page_status
+                            THEN scheduled_at ELSE published_at END AS at
+                FROM pages
+                WHERE deleted_at IS NULL
+                  AND status IN ('draft'::page_status, 'published'::page_status)
+            ) dated
+            WHERE at >= $1 AND at < $2
+            ORDER BY at, id
+            "#,
+            from,
+            to
+        )
+        .fetch_all(self.read_pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
 }
 
 #[cfg(test)]
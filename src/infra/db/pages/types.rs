@@ -1,7 +1,10 @@
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::domain::{entities::PageRecord, types::PageStatus};
+use crate::domain::{
+    entities::PageRecord,
+    types::{PageStatus, RenderStatus},
+};
 
 pub(super) const PAGE_PRIMARY_TIME_EXPR: &str = "CASE \
     WHEN status = 'published'::page_status THEN \
@@ -21,6 +24,12 @@ pub(super) struct PageRow {
     pub(super) scheduled_at: Option<OffsetDateTime>,
     pub(super) published_at: Option<OffsetDateTime>,
     pub(super) archived_at: Option<OffsetDateTime>,
+    pub(super) lang: Option<String>,
+    pub(super) translation_group: Option<Uuid>,
+    pub(super) template: String,
+    pub(super) render_status: RenderStatus,
+    pub(super) render_error: Option<String>,
+    pub(super) render_failed_at: Option<OffsetDateTime>,
     pub(super) created_at: OffsetDateTime,
     pub(super) updated_at: OffsetDateTime,
 }
@@ -37,6 +46,12 @@ impl From<PageRow> for PageRecord {
             scheduled_at: row.scheduled_at,
             published_at: row.published_at,
             archived_at: row.archived_at,
+            lang: row.lang,
+            translation_group: row.translation_group,
+            template: row.template,
+            render_status: row.render_status,
+            render_error: row.render_error,
+            render_failed_at: row.render_failed_at,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
@@ -54,6 +69,12 @@ pub(super) struct PageListRow {
     pub(super) scheduled_at: Option<OffsetDateTime>,
     pub(super) published_at: Option<OffsetDateTime>,
     pub(super) archived_at: Option<OffsetDateTime>,
+    pub(super) lang: Option<String>,
+    pub(super) translation_group: Option<Uuid>,
+    pub(super) template: String,
+    pub(super) render_status: RenderStatus,
+    pub(super) render_error: Option<String>,
+    pub(super) render_failed_at: Option<OffsetDateTime>,
     pub(super) created_at: OffsetDateTime,
     pub(super) updated_at: OffsetDateTime,
     pub(super) primary_time: OffsetDateTime,
@@ -71,6 +92,12 @@ impl From<PageListRow> for PageRecord {
             scheduled_at: row.scheduled_at,
             published_at: row.published_at,
             archived_at: row.archived_at,
+            lang: row.lang,
+            translation_group: row.translation_group,
+            template: row.template,
+            render_status: row.render_status,
+            render_error: row.render_error,
+            render_failed_at: row.render_failed_at,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
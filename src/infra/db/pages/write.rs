@@ -1,22 +1,30 @@
 use async_trait::async_trait;
 use sqlx::{Postgres, Transaction};
 use time::OffsetDateTime;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::{
+    application::pagination::{CursorPage, PageRequest, TrashCursor},
+    application::render::ContentWarning,
     application::repos::{
         CreatePageParams, PagesWriteRepo, RepoError, RestorePageSnapshotParams, UpdatePageParams,
-        UpdatePageStatusParams,
+        UpdatePageStatusParams, UpdatePageTranslationParams,
+    },
+    domain::{
+        entities::{PageRecord, TrashedRecord},
+        types::{PageStatus, RenderStatus},
     },
-    domain::{entities::PageRecord, types::PageStatus},
 };
 
 use super::PostgresRepositories;
 use super::types::PageRow;
 use crate::infra::db::map_sqlx_error;
+use crate::infra::db::util::{ContentTable, TrashedRow, missing_or_stale, trashed_page};
 
 #[async_trait]
 impl PagesWriteRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_page(&self, params: CreatePageParams) -> Result<PageRecord, RepoError> {
         let CreatePageParams {
             slug,
@@ -27,6 +35,7 @@ impl PagesWriteRepo for PostgresRepositories {
             scheduled_at,
             published_at,
             archived_at,
+            template,
         } = params;
 
         let id = Uuid::new_v4();
@@ -37,17 +46,19 @@ impl PagesWriteRepo for PostgresRepositories {
             r#"
             INSERT INTO pages (
                 id, slug, title, body_markdown, rendered_html, status,
-                scheduled_at, published_at, archived_at,
+                scheduled_at, published_at, archived_at, template,
                 created_at, updated_at
             )
             VALUES (
                 $1, $2, $3, $4, $5, $6,
-                $7, $8, $9,
-                $10, $10
+                $7, $8, $9, $10,
+                $11, $11
             )
             RETURNING id, slug, title, body_markdown, rendered_html,
                      status AS "status: PageStatus",
-                     scheduled_at, published_at, archived_at, created_at, updated_at
+                     scheduled_at, published_at, archived_at, lang, translation_group, template,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at
             "#,
             id,
             slug,
@@ -58,6 +69,7 @@ impl PagesWriteRepo for PostgresRepositories {
             scheduled_at,
             published_at,
             archived_at,
+            template,
             now
         )
         .fetch_one(self.pool())
@@ -67,6 +79,7 @@ impl PagesWriteRepo for PostgresRepositories {
         Ok(PageRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_page(&self, params: UpdatePageParams) -> Result<PageRecord, RepoError> {
         let UpdatePageParams {
             id,
@@ -74,6 +87,8 @@ impl PagesWriteRepo for PostgresRepositories {
             title,
             body_markdown,
             rendered_html,
+            template,
+            expected_updated_at,
         } = params;
 
         let now = OffsetDateTime::now_utc();
@@ -86,26 +101,36 @@ impl PagesWriteRepo for PostgresRepositories {
                 title = $3,
                 body_markdown = $4,
                 rendered_html = $5,
-                updated_at = $6
+                template = $6,
+                updated_at = $7
             WHERE id = $1
+              AND ($8::timestamptz IS NULL OR updated_at = $8)
             RETURNING id, slug, title, body_markdown, rendered_html,
                      status AS "status: PageStatus",
-                     scheduled_at, published_at, archived_at, created_at, updated_at
+                     scheduled_at, published_at, archived_at, lang, translation_group, template,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at
             "#,
             id,
             slug,
             title,
             body_markdown,
             rendered_html,
-            now
+            template,
+            now,
+            expected_updated_at
         )
-        .fetch_one(self.pool())
+        .fetch_optional(self.pool())
         .await
         .map_err(map_sqlx_error)?;
 
-        Ok(PageRecord::from(row))
+        match row {
+            Some(row) => Ok(PageRecord::from(row)),
+            None => Err(missing_or_stale(self.pool(), ContentTable::Pages, id).await),
+        }
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_page_status(
         &self,
         params: UpdatePageStatusParams,
@@ -132,7 +157,9 @@ impl PagesWriteRepo for PostgresRepositories {
             WHERE id = $1
             RETURNING id, slug, title, body_markdown, rendered_html,
                      status AS "status: PageStatus",
-                     scheduled_at, published_at, archived_at, created_at, updated_at
+                     scheduled_at, published_at, archived_at, lang, translation_group, template,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at
             "#,
             id,
             status as PageStatus,
@@ -148,6 +175,44 @@ impl PagesWriteRepo for PostgresRepositories {
         Ok(PageRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn update_page_translation(
+        &self,
+        params: UpdatePageTranslationParams,
+    ) -> Result<PageRecord, RepoError> {
+        let UpdatePageTranslationParams {
+            id,
+            lang,
+            translation_group,
+        } = params;
+
+        let row = sqlx::query_as!(
+            PageRow,
+            r#"
+            UPDATE pages
+               SET lang = $2,
+                   translation_group = $3,
+                   updated_at = now()
+             WHERE id = $1
+               AND deleted_at IS NULL
+            RETURNING id, slug, title, body_markdown, rendered_html,
+                     status AS "status: PageStatus",
+                     scheduled_at, published_at, archived_at, lang, translation_group, template,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at
+            "#,
+            id,
+            lang,
+            translation_group
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        row.map(PageRecord::from).ok_or(RepoError::NotFound)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn schedule_page_publication(
         &self,
         id: Uuid,
@@ -164,7 +229,9 @@ impl PagesWriteRepo for PostgresRepositories {
              WHERE id = $1
             RETURNING id, slug, title, body_markdown, rendered_html,
                      status AS "status: PageStatus",
-                     scheduled_at, published_at, archived_at, created_at, updated_at
+                     scheduled_at, published_at, archived_at, lang, translation_group, template,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at
             "#,
             id,
             publish_at,
@@ -177,11 +244,67 @@ impl PagesWriteRepo for PostgresRepositories {
         Ok(PageRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn publish_scheduled_page(
+        &self,
+        id: Uuid,
+        expected_scheduled_at: Option<OffsetDateTime>,
+        published_at: OffsetDateTime,
+    ) -> Result<Option<PageRecord>, RepoError> {
+        let row = sqlx::query_as!(
+            PageRow,
+            r#"
+            UPDATE pages
+               SET status = $4,
+                   scheduled_at = NULL,
+                   published_at = $3,
+                   archived_at = NULL,
+                   updated_at = now()
+             WHERE id = $1
+               AND status = $5
+               AND scheduled_at IS NOT DISTINCT FROM $2
+            RETURNING id, slug, title, body_markdown, rendered_html,
+                     status AS "status: PageStatus",
+                     scheduled_at, published_at, archived_at, lang, translation_group, template,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at
+            "#,
+            id,
+            expected_scheduled_at,
+            published_at,
+            PageStatus::Published as PageStatus,
+            PageStatus::Draft as PageStatus
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(row.map(PageRecord::from))
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_page(&self, id: Uuid) -> Result<(), RepoError> {
+        // Navigation links keep a page from being deleted; trashing one would
+        // leave them pointing at a page that no longer renders.
+        let linked: bool = sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM navigation_items WHERE destination_page_id = $1) AS "linked!""#,
+            id
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+        if linked {
+            return Err(RepoError::InvalidInput {
+                message: "page is referenced by a navigation item".to_string(),
+            });
+        }
+
         sqlx::query!(
             r#"
-            DELETE FROM pages
-            WHERE id = $1
+            UPDATE pages
+               SET deleted_at = now()
+             WHERE id = $1
+               AND deleted_at IS NULL
             "#,
             id
         )
@@ -192,6 +315,87 @@ impl PagesWriteRepo for PostgresRepositories {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_trashed_pages(
+        &self,
+        page: PageRequest<TrashCursor>,
+    ) -> Result<CursorPage<TrashedRecord>, RepoError> {
+        let limit = page.limit.clamp(1, 100) as i64;
+        let (cursor_deleted_at, cursor_id) = page
+            .cursor
+            .map(|cursor| (cursor.deleted_at(), cursor.id()))
+            .unzip();
+        let rows = sqlx::query_as!(
+            TrashedRow,
+            r#"
+            SELECT id, slug, title, deleted_at AS "deleted_at!", updated_at
+            FROM pages
+            WHERE deleted_at IS NOT NULL
+              AND ($1::timestamptz IS NULL OR (deleted_at, id) < ($1, $2::uuid))
+            ORDER BY deleted_at DESC, id DESC
+            LIMIT $3
+            "#,
+            cursor_deleted_at,
+            cursor_id,
+            limit + 1
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(trashed_page(rows, limit, self.cursor_keys()))
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn restore_page(&self, id: Uuid) -> Result<PageRecord, RepoError> {
+        let row = sqlx::query_as!(
+            PageRow,
+            r#"
+            UPDATE pages
+               SET deleted_at = NULL,
+                   updated_at = now()
+             WHERE id = $1
+               AND deleted_at IS NOT NULL
+            RETURNING id, slug, title, body_markdown, rendered_html,
+                     status AS "status: PageStatus",
+                     scheduled_at, published_at, archived_at, lang, translation_group, template,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at
+            "#,
+            id
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(PageRecord::from(row))
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn purge_page(&self, id: Uuid) -> Result<(), RepoError> {
+        let result = sqlx::query!(
+            "DELETE FROM pages WHERE id = $1 AND deleted_at IS NOT NULL",
+            id
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+        if result.rows_affected() == 0 {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn purge_trashed_pages(&self, cutoff: OffsetDateTime) -> Result<u64, RepoError> {
+        let result = sqlx::query!("DELETE FROM pages WHERE deleted_at < $1", cutoff)
+            .execute(self.pool())
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn restore_page_snapshot(
         &self,
         params: RestorePageSnapshotParams,
@@ -207,6 +411,7 @@ impl PagesWriteRepo for PostgresRepositories {
             scheduled_at,
             published_at,
             archived_at,
+            template,
         } = params;
 
         let row = sqlx::query_as!(
@@ -221,11 +426,14 @@ impl PagesWriteRepo for PostgresRepositories {
                 scheduled_at = $7,
                 published_at = $8,
                 archived_at = $9,
-                updated_at = $10
+                template = $10,
+                updated_at = $11
             WHERE id = $1
             RETURNING id, slug, title, body_markdown, rendered_html,
                      status AS "status: PageStatus",
-                     scheduled_at, published_at, archived_at, created_at, updated_at
+                     scheduled_at, published_at, archived_at, lang, translation_group, template,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at
             "#,
             id,
             slug,
@@ -236,6 +444,7 @@ impl PagesWriteRepo for PostgresRepositories {
             scheduled_at,
             published_at,
             archived_at,
+            template,
             now
         )
         .fetch_one(self.pool())
@@ -251,11 +460,12 @@ impl PostgresRepositories {
         &self,
         slug: &str,
     ) -> Result<Option<Uuid>, RepoError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_scalar!(
             r#"
             SELECT id
             FROM pages
             WHERE slug = $1
+              AND deleted_at IS NULL
             "#,
             slug
         )
@@ -263,7 +473,7 @@ impl PostgresRepositories {
         .await
         .map_err(map_sqlx_error)?;
 
-        Ok(row.map(|record| record.id))
+        Ok(row)
     }
 
     pub async fn find_page_id_by_slug(
@@ -271,11 +481,12 @@ impl PostgresRepositories {
         tx: &mut Transaction<'_, Postgres>,
         slug: &str,
     ) -> Result<Option<Uuid>, RepoError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_scalar!(
             r#"
             SELECT id
             FROM pages
             WHERE slug = $1
+              AND deleted_at IS NULL
             "#,
             slug
         )
@@ -283,9 +494,11 @@ impl PostgresRepositories {
         .await
         .map_err(map_sqlx_error)?;
 
-        Ok(row.map(|record| record.id))
+        Ok(row)
     }
 
+    /// Store freshly rendered HTML. Rendering is derived output, so `updated_at`
+    /// (and with it the page's version) stays put.
     pub async fn update_page_rendered_html(
         &self,
         tx: &mut Transaction<'_, Postgres>,
@@ -295,8 +508,7 @@ impl PostgresRepositories {
         sqlx::query!(
             r#"
             UPDATE pages
-            SET rendered_html = $2,
-                updated_at = now()
+            SET rendered_html = $2
             WHERE id = $1
             "#,
             page_id,
@@ -308,4 +520,89 @@ impl PostgresRepositories {
 
         Ok(())
     }
+
+    /// Mark the live page at `slug` as failed to render, leaving `updated_at`
+    /// alone.
+    pub async fn record_page_render_failure(
+        &self,
+        slug: &str,
+        message: &str,
+    ) -> Result<(), RepoError> {
+        sqlx::query!(
+            r#"
+            UPDATE pages
+            SET render_status = 'failed',
+                render_error = $2,
+                render_failed_at = now()
+            WHERE slug = $1
+              AND deleted_at IS NULL
+            "#,
+            slug,
+            message
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    pub async fn clear_page_render_failure(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        page_id: Uuid,
+    ) -> Result<(), RepoError> {
+        sqlx::query!(
+            r#"
+            UPDATE pages
+            SET render_status = 'ok',
+                render_error = NULL,
+                render_failed_at = NULL
+            WHERE id = $1
+            "#,
+            page_id
+        )
+        .execute(tx.as_mut())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    pub async fn replace_page_render_warnings(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        page_id: Uuid,
+        warnings: &[ContentWarning],
+    ) -> Result<(), RepoError> {
+        let warnings = serde_json::to_value(warnings).map_err(RepoError::from_persistence)?;
+        sqlx::query!(
+            "UPDATE pages SET render_warnings = $2 WHERE id = $1",
+            page_id,
+            warnings
+        )
+        .execute(tx.as_mut())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    /// Content warnings recorded by the latest successful render of `page_id`.
+    pub async fn load_page_render_warnings(
+        &self,
+        page_id: Uuid,
+    ) -> Result<Vec<ContentWarning>, RepoError> {
+        let value = sqlx::query_scalar!("SELECT render_warnings FROM pages WHERE id = $1", page_id)
+            .fetch_optional(self.pool())
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let Some(value) = value else {
+            return Ok(Vec::new());
+        };
+        serde_json::from_value(value).map_err(|err| RepoError::Integrity {
+            message: format!("invalid render warnings stored for page {page_id}: {err}"),
+        })
+    }
 }
@@ -0,0 +1,203 @@
+//! Editor autosaves, kept in `post_drafts` beside the live posts.
+//!
+//! An autosave never touches the post itself: the editor stores whatever the
+//! form holds and offers it back when it is reopened.
+
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::application::repos::RepoError;
+
+use super::{PostgresRepositories, map_sqlx_error};
+
+/// Which editor an autosave belongs to. An existing post has one autosave
+/// shared by every admin; the new-post editor has one per admin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostDraftKey<'a> {
+    Post(Uuid),
+    NewPost(&'a str),
+}
+
+/// Editor fields captured by an autosave.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostDraftContent {
+    pub title: String,
+    pub slug: String,
+    pub excerpt: String,
+    pub body_markdown: String,
+    pub summary_markdown: Option<String>,
+}
+
+impl PostDraftContent {
+    /// Nothing has been typed into any field.
+    pub fn is_blank(&self) -> bool {
+        self.title.is_empty()
+            && self.slug.is_empty()
+            && self.excerpt.is_empty()
+            && self.body_markdown.is_empty()
+            && self.summary_markdown.is_none()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostDraft {
+    pub content: PostDraftContent,
+    /// Admin who saved it last.
+    pub author: String,
+    pub saved_at: OffsetDateTime,
+}
+
+struct PostDraftRow {
+    author: String,
+    title: String,
+    slug: String,
+    excerpt: String,
+    body_markdown: String,
+    summary_markdown: Option<String>,
+    saved_at: OffsetDateTime,
+}
+
+impl From<PostDraftRow> for PostDraft {
+    fn from(row: PostDraftRow) -> Self {
+        Self {
+            content: PostDraftContent {
+                title: row.title,
+                slug: row.slug,
+                excerpt: row.excerpt,
+                body_markdown: row.body_markdown,
+                summary_markdown: row.summary_markdown,
+            },
+            author: row.author,
+            saved_at: row.saved_at,
+        }
+    }
+}
+
+impl PostgresRepositories {
+    /// Store `content` as the autosave for `key`, replacing any earlier one,
+    /// and return when it was saved. `author` is recorded as the last saver of
+    /// a post's autosave; a new-post autosave always belongs to its key's admin.
+    pub async fn save_post_draft(
+        &self,
+        key: PostDraftKey<'_>,
+        author: &str,
+        content: &PostDraftContent,
+    ) -> Result<OffsetDateTime, RepoError> {
+        let saved_at = match key {
+            PostDraftKey::Post(post_id) => {
+                sqlx::query_scalar!(
+                    r#"
+                    INSERT INTO post_drafts (
+                        id, post_id, author, title, slug, excerpt, body_markdown, summary_markdown
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                    ON CONFLICT (post_id) WHERE post_id IS NOT NULL DO UPDATE
+                       SET author = EXCLUDED.author,
+                           title = EXCLUDED.title,
+                           slug = EXCLUDED.slug,
+                           excerpt = EXCLUDED.excerpt,
+                           body_markdown = EXCLUDED.body_markdown,
+                           summary_markdown = EXCLUDED.summary_markdown,
+                           saved_at = now()
+                    RETURNING saved_at
+                    "#,
+                    Uuid::new_v4(),
+                    post_id,
+                    author,
+                    content.title,
+                    content.slug,
+                    content.excerpt,
+                    content.body_markdown,
+                    content.summary_markdown
+                )
+                .fetch_one(self.pool())
+                .await
+            }
+            PostDraftKey::NewPost(owner) => {
+                sqlx::query_scalar!(
+                    r#"
+                    INSERT INTO post_drafts (
+                        id, post_id, author, title, slug, excerpt, body_markdown, summary_markdown
+                    ) VALUES ($1, NULL, $2, $3, $4, $5, $6, $7)
+                    ON CONFLICT (author) WHERE post_id IS NULL DO UPDATE
+                       SET author = EXCLUDED.author,
+                           title = EXCLUDED.title,
+                           slug = EXCLUDED.slug,
+                           excerpt = EXCLUDED.excerpt,
+                           body_markdown = EXCLUDED.body_markdown,
+                           summary_markdown = EXCLUDED.summary_markdown,
+                           saved_at = now()
+                    RETURNING saved_at
+                    "#,
+                    Uuid::new_v4(),
+                    owner,
+                    content.title,
+                    content.slug,
+                    content.excerpt,
+                    content.body_markdown,
+                    content.summary_markdown
+                )
+                .fetch_one(self.pool())
+                .await
+            }
+        };
+        saved_at.map_err(map_sqlx_error)
+    }
+
+    /// The autosave stored for `key`, if any.
+    pub async fn load_post_draft(
+        &self,
+        key: PostDraftKey<'_>,
+    ) -> Result<Option<PostDraft>, RepoError> {
+        let row = match key {
+            PostDraftKey::Post(post_id) => {
+                sqlx::query_as!(
+                    PostDraftRow,
+                    r#"
+                    SELECT author, title, slug, excerpt, body_markdown, summary_markdown, saved_at
+                    FROM post_drafts
+                    WHERE post_id = $1
+                    "#,
+                    post_id
+                )
+                .fetch_optional(self.pool())
+                .await
+            }
+            PostDraftKey::NewPost(author) => {
+                sqlx::query_as!(
+                    PostDraftRow,
+                    r#"
+                    SELECT author, title, slug, excerpt, body_markdown, summary_markdown, saved_at
+                    FROM post_drafts
+                    WHERE post_id IS NULL AND author = $1
+                    "#,
+                    author
+                )
+                .fetch_optional(self.pool())
+                .await
+            }
+        }
+        .map_err(map_sqlx_error)?;
+        Ok(row.map(PostDraft::from))
+    }
+
+    /// Drop the autosave for `key`; a no-op when there is none.
+    pub async fn clear_post_draft(&self, key: PostDraftKey<'_>) -> Result<(), RepoError> {
+        match key {
+            PostDraftKey::Post(post_id) => {
+                sqlx::query!("DELETE FROM post_drafts WHERE post_id = $1", post_id)
+                    .execute(self.pool())
+                    .await
+            }
+            PostDraftKey::NewPost(author) => {
+                sqlx::query!(
+                    "DELETE FROM post_drafts WHERE post_id IS NULL AND author = $1",
+                    author
+                )
+                .execute(self.pool())
+                .await
+            }
+        }
+        .map_err(map_sqlx_error)?;
+        Ok(())
+    }
+}
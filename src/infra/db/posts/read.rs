@@ -1,35 +1,49 @@
 use async_trait::async_trait;
+use chrono_tz::Tz;
 use futures::{StreamExt, stream::BoxStream};
 use sqlx::QueryBuilder;
-use time::OffsetDateTime;
+use time::{Date, OffsetDateTime};
+use tracing::instrument;
+use uuid::Uuid;
 
 use crate::application::pagination::{CursorPage, PageRequest, PaginationError, PostCursor};
 use crate::application::repos::{
-    PostListScope, PostQueryFilter, PostTagCount, PostsRepo, RepoError,
+    FeedAggregates, PostListScope, PostQueryFilter, PostTagCount, PostsRepo, RepoError,
+    TagWithCount,
 };
-use crate::domain::entities::PostRecord;
-use crate::domain::types::PostStatus;
+use crate::domain::entities::{CalendarRecord, PostRecord, ScheduledRecord, TranslationRecord};
+use crate::domain::types::{PostStatus, RenderStatus};
 
 use super::PostgresRepositories;
 use super::types::PostRow;
-use crate::infra::db::map_sqlx_error;
+use crate::infra::db::{RenderSelection, map_sqlx_error};
 
 impl PostgresRepositories {
-    pub fn stream_all_posts(&self) -> BoxStream<'_, Result<PostRecord, RepoError>> {
+    pub fn stream_all_posts<'a>(
+        &'a self,
+        selection: &'a RenderSelection,
+    ) -> BoxStream<'a, Result<PostRecord, RepoError>> {
         let stream = sqlx::query_as!(
             PostRow,
             r#"
-            SELECT id, slug, title, excerpt, body_markdown,
-                   status AS "status: PostStatus", pinned, scheduled_at,
+            SELECT id, slug, title, excerpt, excerpt_generated, body_markdown,
+                   status AS "status: PostStatus", pinned, pin_order, scheduled_at,
                    published_at, archived_at, summary_markdown, summary_html,
+                   lang, translation_group,
+                   render_status AS "render_status: RenderStatus", render_error, render_failed_at,
                    created_at, updated_at,
                    CASE
                        WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
                        ELSE COALESCE(updated_at, created_at)
                    END AS "primary_time!"
             FROM posts
+            WHERE deleted_at IS NULL
+              AND ($1::text[] IS NULL OR slug = ANY($1))
+              AND ($2::timestamptz IS NULL OR updated_at > $2)
             ORDER BY slug
-            "#
+            "#,
+            selection.slug_filter(),
+            selection.updated_since
         )
         .fetch(self.pool())
         .map(|row| match row {
@@ -39,10 +53,34 @@ impl PostgresRepositories {
 
         Box::pin(stream)
     }
+
+    /// Ids of the admin posts matching `status` and `filter`, in admin list
+    /// order, at most `limit` of them. Read from the primary, since callers
+    /// act on them straight away.
+    pub async fn list_admin_post_ids(
+        &self,
+        status: Option<PostStatus>,
+        filter: &PostQueryFilter,
+        limit: u64,
+    ) -> Result<Vec<Uuid>, RepoError> {
+        let mut qb = QueryBuilder::new("SELECT p.id FROM posts p WHERE 1=1 ");
+        Self::apply_scope_conditions(&mut qb, PostListScope::Admin { status });
+        Self::apply_feed_filter(&mut qb, filter);
+        qb.push(" ORDER BY p.pinned DESC, p.pin_order ASC, ");
+        Self::push_primary_time_expr(&mut qb);
+        qb.push(" DESC, p.id DESC LIMIT ");
+        qb.push_bind(i64::try_from(limit).unwrap_or(i64::MAX));
+
+        qb.build_query_scalar()
+            .fetch_all(self.pool())
+            .await
+            .map_err(map_sqlx_error)
+    }
 }
 
 #[async_trait]
 impl PostsRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_posts(
         &self,
         scope: PostListScope,
@@ -52,9 +90,10 @@ impl PostsRepo for PostgresRepositories {
         let limit = page.limit.clamp(1, 100) as i64;
 
         let mut qb = QueryBuilder::new(
-            "SELECT p.id, p.slug, p.title, p.excerpt, p.body_markdown, p.status, \
-             p.pinned, p.scheduled_at, p.published_at, p.archived_at, p.summary_markdown, \
-             p.summary_html, p.created_at, p.updated_at, ",
+            "SELECT p.id, p.slug, p.title, p.excerpt, p.excerpt_generated, p.body_markdown, p.status, \
+             p.pinned, p.pin_order, p.scheduled_at, p.published_at, p.archived_at, p.summary_markdown, \
+             p.summary_html, p.lang, p.translation_group, p.render_status, p.render_error, \
+             p.render_failed_at, p.created_at, p.updated_at, ",
         );
         Self::push_primary_time_expr(&mut qb);
         qb.push(" AS primary_time FROM posts p WHERE 1=1 ");
@@ -65,9 +104,11 @@ impl PostsRepo for PostgresRepositories {
         if let Some(cursor) = page.cursor {
             match scope {
                 PostListScope::Public => {
-                    qb.push(" AND (p.pinned, p.published_at, p.id) < (");
+                    qb.push(" AND (p.pinned, -p.pin_order, p.published_at, p.id) < (");
                     qb.push_bind(cursor.pinned());
                     qb.push(", ");
+                    qb.push_bind(-cursor.pin_order());
+                    qb.push(", ");
                     qb.push_bind(cursor.sort_key());
                     qb.push(", ");
                     qb.push_bind(cursor.id());
@@ -79,11 +120,13 @@ impl PostsRepo for PostgresRepositories {
                             "cursor missing status for admin scope".to_string(),
                         ))
                     })?;
-                    qb.push(" AND (p.pinned, ");
+                    qb.push(" AND (p.pinned, -p.pin_order, ");
                     Self::push_primary_time_expr(&mut qb);
                     qb.push(", p.id) < (");
                     qb.push_bind(cursor.pinned());
                     qb.push(", ");
+                    qb.push_bind(-cursor.pin_order());
+                    qb.push(", ");
                     qb.push_bind(cursor.sort_key());
                     qb.push(", ");
                     qb.push_bind(cursor.id());
@@ -94,10 +137,12 @@ impl PostsRepo for PostgresRepositories {
 
         match scope {
             PostListScope::Public => {
-                qb.push(" ORDER BY p.pinned DESC, p.published_at DESC NULLS LAST, p.id DESC ");
+                qb.push(
+                    " ORDER BY p.pinned DESC, p.pin_order ASC, p.published_at DESC NULLS LAST, p.id DESC ",
+                );
             }
             PostListScope::Admin { .. } => {
-                qb.push(" ORDER BY p.pinned DESC, primary_time DESC, p.id DESC ");
+                qb.push(" ORDER BY p.pinned DESC, p.pin_order ASC, primary_time DESC, p.id DESC ");
             }
         }
 
@@ -106,7 +151,7 @@ impl PostsRepo for PostgresRepositories {
 
         let mut rows = qb
             .build_query_as::<PostRow>()
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
@@ -122,15 +167,24 @@ impl PostsRepo for PostgresRepositories {
                 .expect("page should contain at least one row when truncated");
             let sort_key = last_row.primary_time;
             let cursor = match scope {
-                PostListScope::Public => {
-                    PostCursor::published(sort_key, last_row.id, last_row.pinned)
-                }
+                PostListScope::Public => PostCursor::published(
+                    sort_key,
+                    last_row.id,
+                    last_row.pinned,
+                    last_row.pin_order,
+                ),
                 PostListScope::Admin { status } => {
                     let status = status.unwrap_or(last_row.status);
-                    PostCursor::admin(status, sort_key, last_row.id, last_row.pinned)
+                    PostCursor::admin(
+                        status,
+                        sort_key,
+                        last_row.id,
+                        last_row.pinned,
+                        last_row.pin_order,
+                    )
                 }
             };
-            Some(cursor.encode())
+            Some(cursor.encode(self.cursor_keys()))
         } else {
             None
         };
@@ -142,6 +196,7 @@ impl PostsRepo for PostgresRepositories {
         Ok(CursorPage::new(records, next_cursor))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_posts(
         &self,
         scope: PostListScope,
@@ -153,13 +208,14 @@ impl PostsRepo for PostgresRepositories {
 
         let count: i64 = qb
             .build_query_scalar()
-            .fetch_one(self.pool())
+            .fetch_one(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
         Self::convert_count(count)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_posts_before(
         &self,
         scope: PostListScope,
@@ -172,9 +228,11 @@ impl PostsRepo for PostgresRepositories {
 
         match scope {
             PostListScope::Public => {
-                qb.push(" AND (p.pinned, p.published_at, p.id) >= (");
+                qb.push(" AND (p.pinned, -p.pin_order, p.published_at, p.id) >= (");
                 qb.push_bind(cursor.pinned());
                 qb.push(", ");
+                qb.push_bind(-cursor.pin_order());
+                qb.push(", ");
                 qb.push_bind(cursor.sort_key());
                 qb.push(", ");
                 qb.push_bind(cursor.id());
@@ -186,11 +244,13 @@ impl PostsRepo for PostgresRepositories {
                         "cursor missing status for admin scope".to_string(),
                     ))
                 })?;
-                qb.push(" AND (p.pinned, ");
+                qb.push(" AND (p.pinned, -p.pin_order, ");
                 Self::push_primary_time_expr(&mut qb);
                 qb.push(", p.id) >= (");
                 qb.push_bind(cursor.pinned());
                 qb.push(", ");
+                qb.push_bind(-cursor.pin_order());
+                qb.push(", ");
                 qb.push_bind(cursor.sort_key());
                 qb.push(", ");
                 qb.push_bind(cursor.id());
@@ -200,13 +260,14 @@ impl PostsRepo for PostgresRepositories {
 
         let count: i64 = qb
             .build_query_scalar()
-            .fetch_one(self.pool())
+            .fetch_one(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
         Self::convert_count(count)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_month_counts(
         &self,
         scope: PostListScope,
@@ -231,7 +292,7 @@ impl PostsRepo for PostgresRepositories {
 
         let rows: Vec<MonthRow> = qb
             .build_query_as::<MonthRow>()
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
@@ -250,6 +311,119 @@ impl PostsRepo for PostgresRepositories {
         Ok(counts)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_local_month_counts(
+        &self,
+        scope: PostListScope,
+        filter: &PostQueryFilter,
+        time_zone: Tz,
+    ) -> Result<Vec<crate::domain::posts::MonthCount>, RepoError> {
+        #[derive(sqlx::FromRow)]
+        struct MonthRow {
+            bucket: Date,
+            count: i64,
+        }
+
+        let mut qb = QueryBuilder::new("SELECT date_trunc('month', ");
+        Self::push_primary_time_expr(&mut qb);
+        qb.push(" AT TIME ZONE ");
+        qb.push_bind(time_zone.name());
+        qb.push(")::date AS bucket, COUNT(*) AS count FROM posts p WHERE 1=1 ");
+        Self::apply_scope_conditions(&mut qb, scope);
+        Self::apply_feed_filter(&mut qb, filter);
+
+        qb.push(" AND ");
+        Self::push_primary_time_expr(&mut qb);
+        qb.push(" IS NOT NULL ");
+        qb.push(" GROUP BY bucket ORDER BY bucket DESC ");
+
+        let rows: Vec<MonthRow> = qb
+            .build_query_as::<MonthRow>()
+            .fetch_all(self.read_pool())
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| crate::domain::posts::MonthCount {
+                key: crate::domain::posts::month_key_for(row.bucket),
+                label: crate::domain::posts::month_label_for(row.bucket),
+                count: row.count as usize,
+            })
+            .collect())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_public_aggregates(&self) -> Result<FeedAggregates, RepoError> {
+        // Tag rows come first (kind 0), then month buckets (kind 1).
+        let rows = sqlx::query!(
+            r#"
+            WITH public_posts AS (
+                SELECT p.id, date_trunc('month', p.published_at) AS bucket
+                FROM posts p
+                WHERE p.deleted_at IS NULL
+                  AND p.status = 'published'
+                  AND p.published_at IS NOT NULL
+            )
+            SELECT
+                counts.tag_id AS "tag_id?",
+                counts.slug AS "slug?",
+                counts.name AS "name?",
+                counts.pinned AS "pinned?",
+                counts.bucket AS "bucket?",
+                counts.count AS "count!"
+            FROM (
+                SELECT
+                    0 AS kind,
+                    t.id AS tag_id,
+                    t.slug,
+                    t.name,
+                    t.pinned,
+                    NULL::timestamptz AS bucket,
+                    COUNT(pp.id) AS count
+                FROM tags t
+                LEFT JOIN post_tags pt ON pt.tag_id = t.id
+                LEFT JOIN public_posts pp ON pp.id = pt.post_id
+                GROUP BY t.id, t.slug, t.name, t.pinned
+                UNION ALL
+                SELECT 1, NULL, NULL, NULL, NULL, pp.bucket, COUNT(*)
+                FROM public_posts pp
+                GROUP BY pp.bucket
+            ) counts
+            ORDER BY counts.kind, counts.pinned DESC, LOWER(counts.name), counts.slug,
+                     counts.bucket DESC
+            "#
+        )
+        .fetch_all(self.read_pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        let mut aggregates = FeedAggregates::default();
+        for row in rows {
+            if let Some(bucket) = row.bucket {
+                let date = bucket.date();
+                aggregates.months.push(crate::domain::posts::MonthCount {
+                    key: crate::domain::posts::month_key_for(date),
+                    label: crate::domain::posts::month_label_for(date),
+                    count: row.count as usize,
+                });
+            } else if let (Some(id), Some(slug), Some(name), Some(pinned)) =
+                (row.tag_id, row.slug, row.name, row.pinned)
+            {
+                aggregates.tags.push(TagWithCount {
+                    id,
+                    slug,
+                    name,
+                    pinned,
+                    count: row.count,
+                });
+            }
+        }
+
+        Ok(aggregates)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_tag_counts(
         &self,
         scope: PostListScope,
@@ -281,7 +455,7 @@ impl PostsRepo for PostgresRepositories {
 
         let rows: Vec<TagRow> = qb
             .build_query_as::<TagRow>()
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
@@ -298,13 +472,16 @@ impl PostsRepo for PostgresRepositories {
         Ok(counts)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_slug(&self, slug: &str) -> Result<Option<PostRecord>, RepoError> {
         let row = sqlx::query_as!(
             PostRow,
             r#"
-            SELECT id, slug, title, excerpt, body_markdown,
-                   status AS "status: PostStatus", pinned, scheduled_at,
+            SELECT id, slug, title, excerpt, excerpt_generated, body_markdown,
+                   status AS "status: PostStatus", pinned, pin_order, scheduled_at,
                    published_at, archived_at, summary_markdown, summary_html,
+                    lang, translation_group,
+                    render_status AS "render_status: RenderStatus", render_error, render_failed_at,
                     created_at, updated_at,
                    CASE
                        WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
@@ -312,23 +489,27 @@ impl PostsRepo for PostgresRepositories {
                    END AS "primary_time!"
             FROM posts
             WHERE slug = $1
+              AND deleted_at IS NULL
             "#,
             slug
         )
-        .fetch_optional(self.pool())
+        .fetch_optional(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(row.map(PostRecord::from))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_id(&self, id: uuid::Uuid) -> Result<Option<PostRecord>, RepoError> {
         let row = sqlx::query_as!(
             PostRow,
             r#"
-            SELECT id, slug, title, excerpt, body_markdown,
-                   status AS "status: PostStatus", pinned, scheduled_at,
+            SELECT id, slug, title, excerpt, excerpt_generated, body_markdown,
+                   status AS "status: PostStatus", pinned, pin_order, scheduled_at,
                    published_at, archived_at, summary_markdown, summary_html,
+                   lang, translation_group,
+                   render_status AS "render_status: RenderStatus", render_error, render_failed_at,
                    created_at, updated_at,
                    CASE
                        WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
@@ -336,15 +517,90 @@ impl PostsRepo for PostgresRepositories {
                    END AS "primary_time!"
             FROM posts
             WHERE id = $1
+              AND deleted_at IS NULL
             "#,
             id
         )
-        .fetch_optional(self.pool())
+        .fetch_optional(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(row.map(PostRecord::from))
     }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_post_translations(
+        &self,
+        translation_group: uuid::Uuid,
+    ) -> Result<Vec<TranslationRecord>, RepoError> {
+        sqlx::query_as!(
+            TranslationRecord,
+            r#"
+            SELECT id, slug, lang AS "lang!"
+            FROM posts
+            WHERE translation_group = $1
+              AND lang IS NOT NULL
+              AND status = 'published'::post_status
+              AND published_at IS NOT NULL
+              AND deleted_at IS NULL
+            ORDER BY lang
+            "#,
+            translation_group
+        )
+        .fetch_all(self.read_pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_scheduled_posts(&self, limit: u32) -> Result<Vec<ScheduledRecord>, RepoError> {
+        sqlx::query_as!(
+            ScheduledRecord,
+            r#"
+            SELECT id, slug, title, scheduled_at AS "scheduled_at!"
+            FROM posts
+            WHERE status = 'draft'::post_status
+              AND scheduled_at IS NOT NULL
+              AND deleted_at IS NULL
+            ORDER BY scheduled_at, id
+            LIMIT $1
+            "#,
+            i64::from(limit)
+        )
+        .fetch_all(self.read_pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_calendar_posts(
+        &self,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> Result<Vec<CalendarRecord>, RepoError> {
+        sqlx::query_as!(
+            CalendarRecord,
+            r#"
+            SELECT id, slug, title, scheduled AS "scheduled!", at AS "at!"
+            FROM (
+                SELECT id, slug, title,
+                       status = 'draft'::post_status AS scheduled,
+                       CASE WHEN status = 'draft'::post_status
+                            THEN scheduled_at ELSE published_at END AS at
+                FROM posts
+                WHERE deleted_at IS NULL
+                  AND status IN ('draft'::post_status, 'published'::post_status)
+            ) dated
+            WHERE at >= $1 AND at < $2
+            ORDER BY at, id
+            "#,
+            from,
+            to
+        )
+        .fetch_all(self.read_pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,9 @@
 use async_trait::async_trait;
 use sqlx::{Postgres, Transaction};
+use tracing::instrument;
 use uuid::Uuid;
 
+use crate::application::render::ContentWarning;
 use crate::application::repos::{RepoError, SectionsRepo};
 use crate::domain::entities::PostSectionRecord;
 
@@ -10,14 +12,16 @@ use super::types::{PersistedPostSection, PersistedPostSectionOwned, PostSectionR
 use crate::infra::db::map_sqlx_error;
 
 impl PostgresRepositories {
+    /// Lock the post row for the rest of `tx`, returning its current body and
+    /// summary markdown.
     pub async fn lock_post_for_update(
         &self,
         tx: &mut Transaction<'_, Postgres>,
         post_id: Uuid,
-    ) -> Result<(), RepoError> {
-        sqlx::query!(
+    ) -> Result<(String, Option<String>), RepoError> {
+        let row = sqlx::query!(
             r#"
-            SELECT id
+            SELECT body_markdown, summary_markdown
             FROM posts
             WHERE id = $1
             FOR UPDATE
@@ -28,18 +32,19 @@ impl PostgresRepositories {
         .await
         .map_err(map_sqlx_error)?;
 
-        Ok(())
+        Ok((row.body_markdown, row.summary_markdown))
     }
 
     pub async fn find_post_id_by_slug_immediate(
         &self,
         slug: &str,
     ) -> Result<Option<Uuid>, RepoError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_scalar!(
             r#"
             SELECT id
             FROM posts
             WHERE slug = $1
+              AND deleted_at IS NULL
             "#,
             slug
         )
@@ -47,7 +52,7 @@ impl PostgresRepositories {
         .await
         .map_err(map_sqlx_error)?;
 
-        Ok(row.map(|record| record.id))
+        Ok(row)
     }
 
     pub async fn find_post_id_by_slug(
@@ -55,11 +60,12 @@ impl PostgresRepositories {
         tx: &mut Transaction<'_, Postgres>,
         slug: &str,
     ) -> Result<Option<Uuid>, RepoError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_scalar!(
             r#"
             SELECT id
             FROM posts
             WHERE slug = $1
+              AND deleted_at IS NULL
             "#,
             slug
         )
@@ -67,7 +73,7 @@ impl PostgresRepositories {
         .await
         .map_err(map_sqlx_error)?;
 
-        Ok(row.map(|record| record.id))
+        Ok(row)
     }
 
     pub async fn replace_post_sections(
@@ -133,6 +139,8 @@ impl PostgresRepositories {
         Ok(())
     }
 
+    /// Store the rendered summary, leaving `updated_at` alone like the other
+    /// render outputs.
     pub async fn update_post_summary_html(
         &self,
         tx: &mut Transaction<'_, Postgres>,
@@ -142,8 +150,7 @@ impl PostgresRepositories {
         sqlx::query!(
             r#"
             UPDATE posts
-            SET summary_html = $2,
-                updated_at = now()
+            SET summary_html = $2
             WHERE id = $1
             "#,
             post_id,
@@ -156,7 +163,33 @@ impl PostgresRepositories {
         Ok(())
     }
 
-    pub async fn update_post_updated_at(
+    /// Mark the live post at `slug` as failed to render. Leaves `updated_at`
+    /// alone so editors holding the post are not told it changed under them.
+    pub async fn record_post_render_failure(
+        &self,
+        slug: &str,
+        message: &str,
+    ) -> Result<(), RepoError> {
+        sqlx::query!(
+            r#"
+            UPDATE posts
+            SET render_status = 'failed',
+                render_error = $2,
+                render_failed_at = now()
+            WHERE slug = $1
+              AND deleted_at IS NULL
+            "#,
+            slug,
+            message
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    pub async fn clear_post_render_failure(
         &self,
         tx: &mut Transaction<'_, Postgres>,
         post_id: Uuid,
@@ -164,7 +197,9 @@ impl PostgresRepositories {
         sqlx::query!(
             r#"
             UPDATE posts
-            SET updated_at = now()
+            SET render_status = 'ok',
+                render_error = NULL,
+                render_failed_at = NULL
             WHERE id = $1
             "#,
             post_id
@@ -175,10 +210,121 @@ impl PostgresRepositories {
 
         Ok(())
     }
+
+    /// Replace the content warnings stored for `post_id`. Like render
+    /// failures, warnings leave `updated_at` alone.
+    pub async fn replace_post_render_warnings(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+        warnings: &[ContentWarning],
+    ) -> Result<(), RepoError> {
+        let warnings = serde_json::to_value(warnings).map_err(RepoError::from_persistence)?;
+        sqlx::query!(
+            "UPDATE posts SET render_warnings = $2 WHERE id = $1",
+            post_id,
+            warnings
+        )
+        .execute(tx.as_mut())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    /// Hash of what the stored sections of `post_id` were rendered from, or
+    /// `None` when unknown.
+    pub async fn post_sections_hash(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+    ) -> Result<Option<String>, RepoError> {
+        sqlx::query_scalar!("SELECT sections_hash FROM posts WHERE id = $1", post_id)
+            .fetch_one(tx.as_mut())
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    /// Record what the sections just stored for `post_id` were rendered from.
+    /// Like render failures, this leaves `updated_at` alone.
+    pub async fn update_post_sections_hash(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+        sections_hash: &str,
+    ) -> Result<(), RepoError> {
+        sqlx::query!(
+            "UPDATE posts SET sections_hash = $2 WHERE id = $1",
+            post_id,
+            sections_hash
+        )
+        .execute(tx.as_mut())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    /// Sections stored for `post_id` together with their sections hash, read
+    /// from one snapshot so the two always agree.
+    pub async fn load_post_sections_with_hash(
+        &self,
+        post_id: Uuid,
+    ) -> Result<(Option<String>, Vec<PostSectionRecord>), RepoError> {
+        let mut tx = self.begin().await.map_err(map_sqlx_error)?;
+        sqlx::query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(tx.as_mut())
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let sections_hash =
+            sqlx::query_scalar!("SELECT sections_hash FROM posts WHERE id = $1", post_id)
+                .fetch_optional(tx.as_mut())
+                .await
+                .map_err(map_sqlx_error)?;
+        let rows = sqlx::query_as!(
+            PostSectionRow,
+            r#"
+            SELECT id, post_id, parent_id, position, level, heading_html, heading_text, body_html,
+                   contains_code, contains_math, contains_mermaid, anchor_slug, created_at
+            FROM post_sections
+            WHERE post_id = $1
+            "#,
+            post_id
+        )
+        .fetch_all(tx.as_mut())
+        .await
+        .map_err(map_sqlx_error)?;
+        tx.commit().await.map_err(map_sqlx_error)?;
+
+        Ok((
+            sections_hash.flatten(),
+            rows.into_iter().map(PostSectionRecord::from).collect(),
+        ))
+    }
+
+    /// Content warnings recorded by the latest successful render of `post_id`.
+    pub async fn load_post_render_warnings(
+        &self,
+        post_id: Uuid,
+    ) -> Result<Vec<ContentWarning>, RepoError> {
+        let value = sqlx::query_scalar!("SELECT render_warnings FROM posts WHERE id = $1", post_id)
+            .fetch_optional(self.pool())
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let Some(value) = value else {
+            return Ok(Vec::new());
+        };
+        serde_json::from_value(value).map_err(|err| RepoError::Integrity {
+            message: format!("invalid render warnings stored for post {post_id}: {err}"),
+        })
+    }
 }
 
 #[async_trait]
 impl SectionsRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_sections(&self, post_id: Uuid) -> Result<Vec<PostSectionRecord>, RepoError> {
         let rows = sqlx::query_as!(
             PostSectionRow,
@@ -191,7 +337,7 @@ impl SectionsRepo for PostgresRepositories {
             "#,
             post_id
         )
-        .fetch_all(self.pool())
+        .fetch_all(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
@@ -2,7 +2,7 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::domain::entities::{PostRecord, PostSectionRecord};
-use crate::domain::types::PostStatus;
+use crate::domain::types::{PostStatus, RenderStatus};
 
 #[derive(sqlx::FromRow)]
 pub(crate) struct PostRow {
@@ -10,14 +10,21 @@ pub(crate) struct PostRow {
     pub(crate) slug: String,
     pub(crate) title: String,
     pub(crate) excerpt: String,
+    pub(crate) excerpt_generated: bool,
     pub(crate) body_markdown: String,
     pub(crate) status: PostStatus,
     pub(crate) pinned: bool,
+    pub(crate) pin_order: i32,
     pub(crate) scheduled_at: Option<OffsetDateTime>,
     pub(crate) published_at: Option<OffsetDateTime>,
     pub(crate) archived_at: Option<OffsetDateTime>,
     pub(crate) summary_markdown: Option<String>,
     pub(crate) summary_html: Option<String>,
+    pub(crate) lang: Option<String>,
+    pub(crate) translation_group: Option<Uuid>,
+    pub(crate) render_status: RenderStatus,
+    pub(crate) render_error: Option<String>,
+    pub(crate) render_failed_at: Option<OffsetDateTime>,
     pub(crate) created_at: OffsetDateTime,
     pub(crate) updated_at: OffsetDateTime,
     pub(crate) primary_time: OffsetDateTime,
@@ -30,14 +37,21 @@ impl From<PostRow> for PostRecord {
             slug: row.slug,
             title: row.title,
             excerpt: row.excerpt,
+            excerpt_generated: row.excerpt_generated,
             body_markdown: row.body_markdown,
             status: row.status,
             pinned: row.pinned,
+            pin_order: row.pin_order,
             scheduled_at: row.scheduled_at,
             published_at: row.published_at,
             archived_at: row.archived_at,
             summary_markdown: row.summary_markdown,
             summary_html: row.summary_html,
+            lang: row.lang,
+            translation_group: row.translation_group,
+            render_status: row.render_status,
+            render_error: row.render_error,
+            render_failed_at: row.render_failed_at,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
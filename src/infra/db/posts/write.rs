@@ -1,16 +1,19 @@
 use async_trait::async_trait;
 use time::OffsetDateTime;
+use tracing::instrument;
 use uuid::Uuid;
 
+use crate::application::pagination::{CursorPage, PageRequest, TrashCursor};
 use crate::application::repos::{
     CreatePostParams, PostsWriteRepo, RepoError, RestorePostSnapshotParams, UpdatePostParams,
-    UpdatePostPinnedParams, UpdatePostStatusParams,
+    UpdatePostPinnedParams, UpdatePostStatusParams, UpdatePostTranslationParams,
 };
-use crate::domain::entities::PostRecord;
-use crate::domain::types::PostStatus;
+use crate::domain::entities::{PostRecord, TrashedRecord};
+use crate::domain::types::{PostStatus, RenderStatus};
 
 use super::PostgresRepositories;
 use super::types::{PersistedPostSectionOwned, PostRow};
+use crate::infra::db::util::{ContentTable, TrashedRow, missing_or_stale, trashed_page};
 
 fn map_sqlx_error(err: sqlx::Error) -> RepoError {
     match err {
@@ -31,24 +34,22 @@ fn map_sqlx_error(err: sqlx::Error) -> RepoError {
         sqlx::Error::Database(db) if db.message().contains("violates") => RepoError::Integrity {
             message: db.message().to_string(),
         },
-        sqlx::Error::Database(db)
-            if db
-                .message()
-                .contains("canceling statement due to user request") =>
-        {
-            RepoError::Timeout
-        }
+        // 57014 query_canceled: statement_timeout or an explicit cancel.
+        sqlx::Error::Database(db) if db.code().as_deref() == Some("57014") => RepoError::Timeout,
+        sqlx::Error::PoolTimedOut => RepoError::Timeout,
         other => RepoError::from_persistence(other),
     }
 }
 
 #[async_trait]
 impl PostsWriteRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_post(&self, params: CreatePostParams) -> Result<PostRecord, RepoError> {
         let CreatePostParams {
             slug,
             title,
             excerpt,
+            excerpt_generated,
             body_markdown,
             status,
             pinned,
@@ -65,18 +66,20 @@ impl PostsWriteRepo for PostgresRepositories {
             PostRow,
             r#"
             INSERT INTO posts (
-                id, slug, title, excerpt, body_markdown, status, pinned,
+                id, slug, title, excerpt, excerpt_generated, body_markdown, status, pinned,
                 scheduled_at, published_at, archived_at, summary_markdown, summary_html,
                 created_at, updated_at
             )
             VALUES (
-                $1, $2, $3, $4, $5, $6, $7,
-                $8, $9, $10, $11, $12,
-                $13, $13
+                $1, $2, $3, $4, $5, $6, $7, $8,
+                $9, $10, $11, $12, $13,
+                $14, $14
             )
-            RETURNING id, slug, title, excerpt, body_markdown,
-                     status AS "status: PostStatus", pinned, scheduled_at, published_at, archived_at,
-                     summary_markdown, summary_html, created_at, updated_at,
+            RETURNING id, slug, title, excerpt, excerpt_generated, body_markdown,
+                     status AS "status: PostStatus", pinned, pin_order, scheduled_at, published_at, archived_at,
+                     summary_markdown, summary_html, lang, translation_group,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at,
                      CASE
                          WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
                          ELSE COALESCE(updated_at, created_at)
@@ -86,6 +89,7 @@ impl PostsWriteRepo for PostgresRepositories {
             slug,
             title,
             excerpt,
+            excerpt_generated,
             body_markdown,
             status as PostStatus,
             pinned,
@@ -103,16 +107,19 @@ impl PostsWriteRepo for PostgresRepositories {
         Ok(PostRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_post(&self, params: UpdatePostParams) -> Result<PostRecord, RepoError> {
         let UpdatePostParams {
             id,
             slug,
             title,
             excerpt,
+            excerpt_generated,
             body_markdown,
             pinned,
             summary_markdown,
             summary_html,
+            expected_updated_at,
         } = params;
 
         let now = OffsetDateTime::now_utc();
@@ -123,15 +130,20 @@ impl PostsWriteRepo for PostgresRepositories {
             SET slug = $2,
                 title = $3,
                 excerpt = $4,
-                body_markdown = $5,
-                pinned = $6,
-                summary_markdown = $7,
-                summary_html = $8,
-                updated_at = $9
+                excerpt_generated = $5,
+                body_markdown = $6,
+                pinned = $7,
+                pin_order = CASE WHEN $7 THEN pin_order ELSE 0 END,
+                summary_markdown = $8,
+                summary_html = $9,
+                updated_at = $10
             WHERE id = $1
-            RETURNING id, slug, title, excerpt, body_markdown,
-                     status AS "status: PostStatus", pinned, scheduled_at, published_at, archived_at,
-                     summary_markdown, summary_html, created_at, updated_at,
+              AND ($11::timestamptz IS NULL OR updated_at = $11)
+            RETURNING id, slug, title, excerpt, excerpt_generated, body_markdown,
+                     status AS "status: PostStatus", pinned, pin_order, scheduled_at, published_at, archived_at,
+                     summary_markdown, summary_html, lang, translation_group,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at,
                      CASE
                          WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
                          ELSE COALESCE(updated_at, created_at)
@@ -141,19 +153,25 @@ impl PostsWriteRepo for PostgresRepositories {
             slug,
             title,
             excerpt,
+            excerpt_generated,
             body_markdown,
             pinned,
             summary_markdown,
             summary_html,
-            now
+            now,
+            expected_updated_at
         )
-        .fetch_one(self.pool())
+        .fetch_optional(self.pool())
         .await
         .map_err(map_sqlx_error)?;
 
-        Ok(PostRecord::from(row))
+        match row {
+            Some(row) => Ok(PostRecord::from(row)),
+            None => Err(missing_or_stale(self.pool(), ContentTable::Posts, id).await),
+        }
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_post_status(
         &self,
         params: UpdatePostStatusParams,
@@ -178,9 +196,11 @@ impl PostsWriteRepo for PostgresRepositories {
                 archived_at = $5,
                 updated_at = $6
             WHERE id = $1
-            RETURNING id, slug, title, excerpt, body_markdown,
-                     status AS "status: PostStatus", pinned, scheduled_at, published_at, archived_at,
-                     summary_markdown, summary_html, created_at, updated_at,
+            RETURNING id, slug, title, excerpt, excerpt_generated, body_markdown,
+                     status AS "status: PostStatus", pinned, pin_order, scheduled_at, published_at, archived_at,
+                     summary_markdown, summary_html, lang, translation_group,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at,
                      CASE
                          WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
                          ELSE COALESCE(updated_at, created_at)
@@ -200,29 +220,38 @@ impl PostsWriteRepo for PostgresRepositories {
         Ok(PostRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_post_pinned(
         &self,
         params: UpdatePostPinnedParams,
     ) -> Result<PostRecord, RepoError> {
-        let UpdatePostPinnedParams { id, pinned } = params;
+        let UpdatePostPinnedParams {
+            id,
+            pinned,
+            pin_order,
+        } = params;
 
         let row = sqlx::query_as!(
             PostRow,
             r#"
             UPDATE posts
                SET pinned = $2,
+                   pin_order = CASE WHEN $2 THEN COALESCE($3, pin_order) ELSE 0 END,
                    updated_at = now()
              WHERE id = $1
-            RETURNING id, slug, title, excerpt, body_markdown,
-                     status AS "status: PostStatus", pinned, scheduled_at, published_at, archived_at,
-                     summary_markdown, summary_html, created_at, updated_at,
+            RETURNING id, slug, title, excerpt, excerpt_generated, body_markdown,
+                     status AS "status: PostStatus", pinned, pin_order, scheduled_at, published_at, archived_at,
+                     summary_markdown, summary_html, lang, translation_group,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at,
                      CASE
                          WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
                          ELSE COALESCE(updated_at, created_at)
                      END AS "primary_time!"
             "#,
             id,
-            pinned
+            pinned,
+            pin_order
         )
         .fetch_one(self.pool())
         .await
@@ -231,6 +260,48 @@ impl PostsWriteRepo for PostgresRepositories {
         Ok(PostRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn update_post_translation(
+        &self,
+        params: UpdatePostTranslationParams,
+    ) -> Result<PostRecord, RepoError> {
+        let UpdatePostTranslationParams {
+            id,
+            lang,
+            translation_group,
+        } = params;
+
+        let row = sqlx::query_as!(
+            PostRow,
+            r#"
+            UPDATE posts
+               SET lang = $2,
+                   translation_group = $3,
+                   updated_at = now()
+             WHERE id = $1
+               AND deleted_at IS NULL
+            RETURNING id, slug, title, excerpt, excerpt_generated, body_markdown,
+                     status AS "status: PostStatus", pinned, pin_order, scheduled_at, published_at, archived_at,
+                     summary_markdown, summary_html, lang, translation_group,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at,
+                     CASE
+                         WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
+                         ELSE COALESCE(updated_at, created_at)
+                     END AS "primary_time!"
+            "#,
+            id,
+            lang,
+            translation_group
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        row.map(PostRecord::from).ok_or(RepoError::NotFound)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn schedule_post_publication(
         &self,
         id: Uuid,
@@ -245,9 +316,11 @@ impl PostsWriteRepo for PostgresRepositories {
                    status = $3,
                    updated_at = now()
              WHERE id = $1
-            RETURNING id, slug, title, excerpt, body_markdown,
-                     status AS "status: PostStatus", pinned, scheduled_at, published_at, archived_at,
-                     summary_markdown, summary_html, created_at, updated_at,
+            RETURNING id, slug, title, excerpt, excerpt_generated, body_markdown,
+                     status AS "status: PostStatus", pinned, pin_order, scheduled_at, published_at, archived_at,
+                     summary_markdown, summary_html, lang, translation_group,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at,
                      CASE
                          WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
                          ELSE COALESCE(updated_at, created_at)
@@ -264,11 +337,56 @@ impl PostsWriteRepo for PostgresRepositories {
         Ok(PostRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn publish_scheduled_post(
+        &self,
+        id: Uuid,
+        expected_scheduled_at: Option<OffsetDateTime>,
+        published_at: OffsetDateTime,
+    ) -> Result<Option<PostRecord>, RepoError> {
+        let row = sqlx::query_as!(
+            PostRow,
+            r#"
+            UPDATE posts
+               SET status = $4,
+                   scheduled_at = NULL,
+                   published_at = $3,
+                   archived_at = NULL,
+                   updated_at = now()
+             WHERE id = $1
+               AND status = $5
+               AND scheduled_at IS NOT DISTINCT FROM $2
+            RETURNING id, slug, title, excerpt, excerpt_generated, body_markdown,
+                     status AS "status: PostStatus", pinned, pin_order, scheduled_at, published_at, archived_at,
+                     summary_markdown, summary_html, lang, translation_group,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at,
+                     CASE
+                         WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
+                         ELSE COALESCE(updated_at, created_at)
+                     END AS "primary_time!"
+            "#,
+            id,
+            expected_scheduled_at,
+            published_at,
+            PostStatus::Published as PostStatus,
+            PostStatus::Draft as PostStatus
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(row.map(PostRecord::from))
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_post(&self, id: Uuid) -> Result<(), RepoError> {
         sqlx::query!(
             r#"
-            DELETE FROM posts
-            WHERE id = $1
+            UPDATE posts
+               SET deleted_at = now()
+             WHERE id = $1
+               AND deleted_at IS NULL
             "#,
             id
         )
@@ -279,6 +397,91 @@ impl PostsWriteRepo for PostgresRepositories {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn list_trashed_posts(
+        &self,
+        page: PageRequest<TrashCursor>,
+    ) -> Result<CursorPage<TrashedRecord>, RepoError> {
+        let limit = page.limit.clamp(1, 100) as i64;
+        let (cursor_deleted_at, cursor_id) = page
+            .cursor
+            .map(|cursor| (cursor.deleted_at(), cursor.id()))
+            .unzip();
+        let rows = sqlx::query_as!(
+            TrashedRow,
+            r#"
+            SELECT id, slug, title, deleted_at AS "deleted_at!", updated_at
+            FROM posts
+            WHERE deleted_at IS NOT NULL
+              AND ($1::timestamptz IS NULL OR (deleted_at, id) < ($1, $2::uuid))
+            ORDER BY deleted_at DESC, id DESC
+            LIMIT $3
+            "#,
+            cursor_deleted_at,
+            cursor_id,
+            limit + 1
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(trashed_page(rows, limit, self.cursor_keys()))
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn restore_post(&self, id: Uuid) -> Result<PostRecord, RepoError> {
+        let row = sqlx::query_as!(
+            PostRow,
+            r#"
+            UPDATE posts
+               SET deleted_at = NULL,
+                   updated_at = now()
+             WHERE id = $1
+               AND deleted_at IS NOT NULL
+            RETURNING id, slug, title, excerpt, excerpt_generated, body_markdown,
+                     status AS "status: PostStatus", pinned, pin_order, scheduled_at, published_at, archived_at,
+                     summary_markdown, summary_html, lang, translation_group,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at,
+                     CASE
+                         WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
+                         ELSE COALESCE(updated_at, created_at)
+                     END AS "primary_time!"
+            "#,
+            id
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(PostRecord::from(row))
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn purge_post(&self, id: Uuid) -> Result<(), RepoError> {
+        let result = sqlx::query!(
+            "DELETE FROM posts WHERE id = $1 AND deleted_at IS NOT NULL",
+            id
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+        if result.rows_affected() == 0 {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn purge_trashed_posts(&self, cutoff: OffsetDateTime) -> Result<u64, RepoError> {
+        let result = sqlx::query!("DELETE FROM posts WHERE deleted_at < $1", cutoff)
+            .execute(self.pool())
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn replace_post_tags(&self, post_id: Uuid, tag_ids: &[Uuid]) -> Result<(), RepoError> {
         let mut tx = self.pool().begin().await.map_err(map_sqlx_error)?;
 
@@ -313,6 +516,7 @@ impl PostsWriteRepo for PostgresRepositories {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn restore_post_snapshot(
         &self,
         params: RestorePostSnapshotParams,
@@ -325,6 +529,7 @@ impl PostsWriteRepo for PostgresRepositories {
             slug,
             title,
             excerpt,
+            excerpt_generated,
             body_markdown,
             summary_markdown,
             summary_html,
@@ -344,19 +549,25 @@ impl PostsWriteRepo for PostgresRepositories {
             SET slug = $2,
                 title = $3,
                 excerpt = $4,
-                body_markdown = $5,
-                summary_markdown = $6,
-                summary_html = $7,
-                status = $8,
-                pinned = $9,
-                scheduled_at = $10,
-                published_at = $11,
-                archived_at = $12,
-                updated_at = $13
+                excerpt_generated = $5,
+                body_markdown = $6,
+                summary_markdown = $7,
+                summary_html = $8,
+                status = $9,
+                pinned = $10,
+                pin_order = CASE WHEN $10 THEN pin_order ELSE 0 END,
+                scheduled_at = $11,
+                published_at = $12,
+                archived_at = $13,
+                updated_at = $14,
+                -- Restored sections cannot seed an incremental render.
+                sections_hash = NULL
             WHERE id = $1
-            RETURNING id, slug, title, excerpt, body_markdown,
-                     status AS "status: PostStatus", pinned, scheduled_at, published_at, archived_at,
-                     summary_markdown, summary_html, created_at, updated_at,
+            RETURNING id, slug, title, excerpt, excerpt_generated, body_markdown,
+                     status AS "status: PostStatus", pinned, pin_order, scheduled_at, published_at, archived_at,
+                     summary_markdown, summary_html, lang, translation_group,
+                     render_status AS "render_status: RenderStatus", render_error, render_failed_at,
+                     created_at, updated_at,
                      CASE
                          WHEN status = 'published'::post_status THEN COALESCE(published_at, updated_at, created_at)
                          ELSE COALESCE(updated_at, created_at)
@@ -366,6 +577,7 @@ impl PostsWriteRepo for PostgresRepositories {
             slug,
             title,
             excerpt,
+            excerpt_generated,
             body_markdown,
             summary_markdown,
             summary_html,
@@ -0,0 +1,24 @@
+//! Per-request override of read-replica routing.
+//!
+//! Replicas lag the primary, so a request that must see a write made just
+//! before it (such as a preview opened right after saving in the admin) runs
+//! inside [`on_primary`]. Repository reads inside the scope skip the replica.
+
+use std::future::Future;
+
+tokio::task_local! {
+    static FORCE_PRIMARY: bool;
+}
+
+/// Run `future` with every repository read routed to the primary pool.
+pub async fn on_primary<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    FORCE_PRIMARY.scope(true, future).await
+}
+
+/// Whether the current task must read from the primary.
+pub(super) fn primary_forced() -> bool {
+    FORCE_PRIMARY.try_with(|forced| *forced).unwrap_or(false)
+}
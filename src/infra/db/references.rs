@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+
+use crate::application::render::ContentReferenceResolver;
+use crate::application::repos::RepoError;
+
+use super::{PostgresRepositories, map_sqlx_error};
+
+#[async_trait]
+impl ContentReferenceResolver for PostgresRepositories {
+    async fn post_exists(&self, slug: &str) -> Result<bool, RepoError> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM posts WHERE slug = $1 AND deleted_at IS NULL) AS "exists!""#,
+            slug
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    async fn page_exists(&self, slug: &str) -> Result<bool, RepoError> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM pages WHERE slug = $1 AND deleted_at IS NULL) AS "exists!""#,
+            slug
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    async fn upload_exists(&self, path: &str) -> Result<bool, RepoError> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM uploads WHERE stored_path = $1) AS "exists!""#,
+            path
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+}
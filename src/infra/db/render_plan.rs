@@ -0,0 +1,127 @@
+//! Read-only estimate of what `renderall` would re-render.
+
+use time::OffsetDateTime;
+
+use crate::application::repos::RepoError;
+
+use super::{PostgresRepositories, map_sqlx_error};
+
+/// Narrows `renderall` to named slugs and/or content edited after a cutoff.
+/// The default selects everything.
+#[derive(Debug, Clone, Default)]
+pub struct RenderSelection {
+    /// Only these slugs; empty means every slug.
+    pub slugs: Vec<String>,
+    /// Only content whose `updated_at` is later than this.
+    pub updated_since: Option<OffsetDateTime>,
+}
+
+impl RenderSelection {
+    /// The slug list as a query parameter; `NULL` when unrestricted.
+    pub(crate) fn slug_filter(&self) -> Option<&[String]> {
+        (!self.slugs.is_empty()).then_some(self.slugs.as_slice())
+    }
+}
+
+/// Content a full re-render of one kind (posts or pages) would touch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderPlan {
+    /// Items that would be rendered.
+    pub total: usize,
+    /// Slugs whose stored output is known to be out of date: the last render
+    /// failed, or the body was never rendered (as after an import).
+    pub stale: Vec<String>,
+}
+
+impl RenderPlan {
+    fn from_rows(rows: impl ExactSizeIterator<Item = (String, bool)>) -> Self {
+        let total = rows.len();
+        let stale = rows
+            .filter_map(|(slug, stale)| stale.then_some(slug))
+            .collect();
+        Self { total, stale }
+    }
+}
+
+impl PostgresRepositories {
+    /// Plan a re-render of the selected posts without writing anything.
+    pub async fn post_render_plan(
+        &self,
+        selection: &RenderSelection,
+    ) -> Result<RenderPlan, RepoError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT p.slug,
+                   p.render_status = 'failed'::render_status
+                   OR (
+                       p.body_markdown <> ''
+                       AND NOT EXISTS (SELECT 1 FROM post_sections s WHERE s.post_id = p.id)
+                   ) AS "stale!"
+            FROM posts p
+            WHERE p.deleted_at IS NULL
+              AND ($1::text[] IS NULL OR p.slug = ANY($1))
+              AND ($2::timestamptz IS NULL OR p.updated_at > $2)
+            ORDER BY p.slug
+            "#,
+            selection.slug_filter(),
+            selection.updated_since
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(RenderPlan::from_rows(
+            rows.into_iter().map(|row| (row.slug, row.stale)),
+        ))
+    }
+
+    /// Plan a re-render of the selected pages without writing anything.
+    pub async fn page_render_plan(
+        &self,
+        selection: &RenderSelection,
+    ) -> Result<RenderPlan, RepoError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT slug,
+                   render_status = 'failed'::render_status
+                   OR (body_markdown <> '' AND rendered_html = '') AS "stale!"
+            FROM pages
+            WHERE deleted_at IS NULL
+              AND ($1::text[] IS NULL OR slug = ANY($1))
+              AND ($2::timestamptz IS NULL OR updated_at > $2)
+            ORDER BY slug
+            "#,
+            selection.slug_filter(),
+            selection.updated_since
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(RenderPlan::from_rows(
+            rows.into_iter().map(|row| (row.slug, row.stale)),
+        ))
+    }
+
+    /// Which of `slugs` name a post that is not in the trash.
+    pub async fn existing_post_slugs(&self, slugs: &[String]) -> Result<Vec<String>, RepoError> {
+        sqlx::query_scalar!(
+            "SELECT slug FROM posts WHERE deleted_at IS NULL AND slug = ANY($1) ORDER BY slug",
+            slugs
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    /// Which of `slugs` name a page that is not in the trash.
+    pub async fn existing_page_slugs(&self, slugs: &[String]) -> Result<Vec<String>, RepoError> {
+        sqlx::query_scalar!(
+            "SELECT slug FROM pages WHERE deleted_at IS NULL AND slug = ANY($1) ORDER BY slug",
+            slugs
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+}
@@ -1,9 +1,14 @@
 use async_trait::async_trait;
 use time::OffsetDateTime;
+use tracing::instrument;
+use uuid::Uuid;
 
 use crate::{
     application::repos::{RepoError, SettingsRepo},
-    domain::entities::SiteSettingsRecord,
+    domain::{
+        entities::SiteSettingsRecord,
+        types::{FeedContentMode, HomepageLayout, HomepageMode},
+    },
 };
 
 use super::{DbTimeZone, PostgresRepositories, map_sqlx_error};
@@ -22,11 +27,21 @@ struct SiteSettingsRow {
     footer_copy: String,
     public_site_url: String,
     favicon_svg: String,
+    favicon_upload_id: Option<Uuid>,
     timezone: DbTimeZone,
+    date_format: String,
     meta_title: String,
     meta_description: String,
     og_title: String,
     og_description: String,
+    maintenance_enabled: bool,
+    maintenance_html: String,
+    maintenance_retry_after_secs: i32,
+    feed_content_mode: FeedContentMode,
+    feed_item_limit: i32,
+    homepage_mode: HomepageMode,
+    homepage_page_id: Option<Uuid>,
+    homepage_layout: HomepageLayout,
     updated_at: OffsetDateTime,
 }
 
@@ -45,11 +60,21 @@ impl From<SiteSettingsRow> for SiteSettingsRecord {
             footer_copy: row.footer_copy,
             public_site_url: row.public_site_url,
             favicon_svg: row.favicon_svg,
+            favicon_upload_id: row.favicon_upload_id,
             timezone: row.timezone.into(),
+            date_format: row.date_format,
             meta_title: row.meta_title,
             meta_description: row.meta_description,
             og_title: row.og_title,
             og_description: row.og_description,
+            maintenance_enabled: row.maintenance_enabled,
+            maintenance_html: row.maintenance_html,
+            maintenance_retry_after_secs: row.maintenance_retry_after_secs,
+            feed_content_mode: row.feed_content_mode,
+            feed_item_limit: row.feed_item_limit,
+            homepage_mode: row.homepage_mode,
+            homepage_page_id: row.homepage_page_id,
+            homepage_layout: row.homepage_layout,
             updated_at: row.updated_at,
         }
     }
@@ -57,6 +82,7 @@ impl From<SiteSettingsRow> for SiteSettingsRecord {
 
 #[async_trait]
 impl SettingsRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn load_site_settings(&self) -> Result<SiteSettingsRecord, RepoError> {
         let row = sqlx::query_as!(
             SiteSettingsRow,
@@ -73,17 +99,27 @@ impl SettingsRepo for PostgresRepositories {
                    footer_copy,
                    public_site_url,
                    favicon_svg,
+                   favicon_upload_id,
                    timezone AS "timezone: DbTimeZone",
+                   date_format,
                    meta_title,
                    meta_description,
                    og_title,
                    og_description,
+                   maintenance_enabled,
+                   maintenance_html,
+                   maintenance_retry_after_secs,
+                   feed_content_mode AS "feed_content_mode: FeedContentMode",
+                   feed_item_limit,
+                   homepage_mode AS "homepage_mode: HomepageMode",
+                   homepage_page_id,
+                   homepage_layout AS "homepage_layout: HomepageLayout",
                    updated_at
             FROM site_settings
             WHERE id = 1
             "#
         )
-        .fetch_optional(self.pool())
+        .fetch_optional(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
@@ -92,6 +128,7 @@ impl SettingsRepo for PostgresRepositories {
         Ok(SiteSettingsRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn upsert_site_settings(&self, settings: SiteSettingsRecord) -> Result<(), RepoError> {
         sqlx::query!(
             r#"
@@ -114,8 +151,21 @@ impl SettingsRepo for PostgresRepositories {
                 meta_description,
                 og_title,
                 og_description,
+                maintenance_enabled,
+                maintenance_html,
+                maintenance_retry_after_secs,
+                date_format,
+                feed_content_mode,
+                feed_item_limit,
+                favicon_upload_id,
+                homepage_mode,
+                homepage_page_id,
+                homepage_layout,
                 updated_at
-            ) VALUES (1, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
+            ) VALUES (
+                1, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17,
+                $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28
+            )
             ON CONFLICT (id) DO UPDATE SET
                 homepage_size = EXCLUDED.homepage_size,
                 admin_page_size = EXCLUDED.admin_page_size,
@@ -134,6 +184,16 @@ impl SettingsRepo for PostgresRepositories {
                 meta_description = EXCLUDED.meta_description,
                 og_title = EXCLUDED.og_title,
                 og_description = EXCLUDED.og_description,
+                maintenance_enabled = EXCLUDED.maintenance_enabled,
+                maintenance_html = EXCLUDED.maintenance_html,
+                maintenance_retry_after_secs = EXCLUDED.maintenance_retry_after_secs,
+                date_format = EXCLUDED.date_format,
+                feed_content_mode = EXCLUDED.feed_content_mode,
+                feed_item_limit = EXCLUDED.feed_item_limit,
+                favicon_upload_id = EXCLUDED.favicon_upload_id,
+                homepage_mode = EXCLUDED.homepage_mode,
+                homepage_page_id = EXCLUDED.homepage_page_id,
+                homepage_layout = EXCLUDED.homepage_layout,
                 updated_at = EXCLUDED.updated_at
             "#,
             settings.homepage_size,
@@ -153,6 +213,16 @@ impl SettingsRepo for PostgresRepositories {
             settings.meta_description,
             settings.og_title,
             settings.og_description,
+            settings.maintenance_enabled,
+            settings.maintenance_html,
+            settings.maintenance_retry_after_secs,
+            settings.date_format,
+            settings.feed_content_mode as FeedContentMode,
+            settings.feed_item_limit,
+            settings.favicon_upload_id,
+            settings.homepage_mode as HomepageMode,
+            settings.homepage_page_id,
+            settings.homepage_layout as HomepageLayout,
             settings.updated_at
         )
         .execute(self.pool())
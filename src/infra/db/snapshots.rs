@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use sqlx::QueryBuilder;
 use time::OffsetDateTime;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::application::pagination::{CursorPage, PageRequest, SnapshotCursor};
@@ -38,6 +39,7 @@ impl From<SnapshotRow> for SnapshotRecord {
 
 #[async_trait]
 impl SnapshotsRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create(&self, record: SnapshotRecord) -> Result<(), RepoError> {
         sqlx::query!(
             r#"
@@ -61,6 +63,7 @@ impl SnapshotsRepo for PostgresRepositories {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_snapshots(
         &self,
         filter: &SnapshotFilter,
@@ -111,7 +114,7 @@ impl SnapshotsRepo for PostgresRepositories {
 
         let next_cursor = if (rows.len() as i64) > limit {
             let overflow = rows.pop().expect("overflow row exists when len > limit");
-            Some(SnapshotCursor::new(overflow.created_at, overflow.id).encode())
+            Some(SnapshotCursor::new(overflow.created_at, overflow.id).encode(self.cursor_keys()))
         } else {
             None
         };
@@ -120,6 +123,7 @@ impl SnapshotsRepo for PostgresRepositories {
         Ok(CursorPage::new(records, next_cursor))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_snapshots(&self, filter: &SnapshotFilter) -> Result<u64, RepoError> {
         let mut qb = QueryBuilder::new("SELECT COUNT(*) FROM snapshots WHERE 1=1 ");
 
@@ -153,6 +157,7 @@ impl SnapshotsRepo for PostgresRepositories {
         Ok(count as u64)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_snapshot(&self, id: Uuid) -> Result<Option<SnapshotRecord>, RepoError> {
         let row: Option<SnapshotRow> = sqlx::query_as!(
             SnapshotRow,
@@ -169,6 +174,7 @@ impl SnapshotsRepo for PostgresRepositories {
         Ok(row.map(SnapshotRecord::from))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn latest_snapshot(
         &self,
         entity_type: SnapshotEntityType,
@@ -193,6 +199,7 @@ impl SnapshotsRepo for PostgresRepositories {
         Ok(row.map(SnapshotRecord::from))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn current_schema_version(&self) -> Result<i64, RepoError> {
         let version: Option<i64> =
             sqlx::query_scalar(r#"SELECT MAX(version) FROM _sqlx_migrations"#)
@@ -203,6 +210,7 @@ impl SnapshotsRepo for PostgresRepositories {
         Ok(version.unwrap_or(0))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn month_counts(
         &self,
         filter: &SnapshotFilter,
@@ -256,6 +264,7 @@ impl SnapshotsRepo for PostgresRepositories {
         Ok(counts)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_description(
         &self,
         id: Uuid,
@@ -279,6 +288,7 @@ impl SnapshotsRepo for PostgresRepositories {
         Ok(row.map(SnapshotRecord::from))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_snapshot(&self, id: Uuid) -> Result<Option<SnapshotRecord>, RepoError> {
         let row: Option<SnapshotRow> = sqlx::query_as!(
             SnapshotRow,
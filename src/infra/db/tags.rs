@@ -1,13 +1,16 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use sqlx::{Postgres, QueryBuilder};
 use time::OffsetDateTime;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::{
     application::pagination::{CursorPage, PageRequest, TagCursor},
     application::repos::{
-        CreateTagParams, RepoError, TagListRecord, TagQueryFilter, TagWithCount, TagsRepo,
-        TagsWriteRepo, UpdateTagParams,
+        CreateTagParams, RepoError, TagAssignmentChanges, TagAssignmentMode, TagListRecord,
+        TagQueryFilter, TagWithCount, TagsRepo, TagsWriteRepo, UpdateTagParams,
     },
     domain::entities::TagRecord,
 };
@@ -56,6 +59,7 @@ struct TagListRow {
 
 #[async_trait]
 impl TagsRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_all(&self) -> Result<Vec<TagRecord>, RepoError> {
         let rows = sqlx::query_as!(
             TagRow,
@@ -65,13 +69,14 @@ impl TagsRepo for PostgresRepositories {
             ORDER BY pinned DESC, LOWER(name), slug
             "#
         )
-        .fetch_all(self.pool())
+        .fetch_all(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(rows.into_iter().map(TagRecord::from).collect())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_for_post(&self, post_id: Uuid) -> Result<Vec<TagRecord>, RepoError> {
         let rows = sqlx::query_as!(
             TagRow,
@@ -84,13 +89,66 @@ impl TagsRepo for PostgresRepositories {
             "#,
             post_id
         )
-        .fetch_all(self.pool())
+        .fetch_all(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(rows.into_iter().map(TagRecord::from).collect())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn tags_for_posts(
+        &self,
+        post_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<TagRecord>>, RepoError> {
+        struct PostTagRow {
+            post_id: Uuid,
+            id: Uuid,
+            slug: String,
+            name: String,
+            description: Option<String>,
+            pinned: bool,
+            created_at: OffsetDateTime,
+            updated_at: OffsetDateTime,
+        }
+
+        if post_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows = sqlx::query_as!(
+            PostTagRow,
+            r#"
+            SELECT pt.post_id, t.id, t.slug, t.name, t.description, t.pinned, t.created_at, t.updated_at
+            FROM tags t
+            INNER JOIN post_tags pt ON pt.tag_id = t.id
+            WHERE pt.post_id = ANY($1)
+            ORDER BY t.name ASC
+            "#,
+            post_ids
+        )
+        .fetch_all(self.read_pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        let mut tags: HashMap<Uuid, Vec<TagRecord>> = HashMap::new();
+        for row in rows {
+            tags.entry(row.post_id)
+                .or_default()
+                .push(TagRecord::from(TagRow {
+                    id: row.id,
+                    slug: row.slug,
+                    name: row.name,
+                    description: row.description,
+                    pinned: row.pinned,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                }));
+        }
+        Ok(tags)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_with_counts(&self) -> Result<Vec<TagWithCount>, RepoError> {
         let rows = sqlx::query!(
             r#"
@@ -106,11 +164,12 @@ impl TagsRepo for PostgresRepositories {
                 ON p.id = pt.post_id
                 AND p.status = 'published'
                 AND p.published_at IS NOT NULL
+                AND p.deleted_at IS NULL
             GROUP BY t.id, t.slug, t.name, t.pinned
             ORDER BY t.pinned DESC, LOWER(t.name), t.slug
             "#
         )
-        .fetch_all(self.pool())
+        .fetch_all(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
@@ -126,6 +185,7 @@ impl TagsRepo for PostgresRepositories {
             .collect())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_id(&self, id: Uuid) -> Result<Option<TagRecord>, RepoError> {
         let row = sqlx::query_as!(
             TagRow,
@@ -136,13 +196,14 @@ impl TagsRepo for PostgresRepositories {
             "#,
             id
         )
-        .fetch_optional(self.pool())
+        .fetch_optional(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(row.map(TagRecord::from))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_by_slug(&self, slug: &str) -> Result<Option<TagRecord>, RepoError> {
         let row = sqlx::query_as!(
             TagRow,
@@ -153,13 +214,14 @@ impl TagsRepo for PostgresRepositories {
             "#,
             slug
         )
-        .fetch_optional(self.pool())
+        .fetch_optional(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(row.map(TagRecord::from))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_usage(&self, id: Uuid) -> Result<u64, RepoError> {
         let row = sqlx::query!(
             r#"
@@ -169,13 +231,14 @@ impl TagsRepo for PostgresRepositories {
             "#,
             id
         )
-        .fetch_one(self.pool())
+        .fetch_one(self.read_pool())
         .await
         .map_err(map_sqlx_error)?;
 
         Ok(u64::try_from(row.count).unwrap_or(u64::MAX))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_admin_tags(
         &self,
         pinned: Option<bool>,
@@ -197,6 +260,7 @@ impl TagsRepo for PostgresRepositories {
                         ON p.id = pt.post_id \
                         AND p.status = 'published'::post_status \
                         AND p.published_at IS NOT NULL \
+                        AND p.deleted_at IS NULL \
                      WHERE pt.tag_id = t.id) AS usage_count \
              FROM tags t \
              WHERE 1=1 ",
@@ -228,7 +292,7 @@ impl TagsRepo for PostgresRepositories {
 
         let mut rows = qb
             .build_query_as::<TagListRow>()
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
@@ -242,7 +306,7 @@ impl TagsRepo for PostgresRepositories {
                 .last()
                 .expect("list_admin_tags rows should be non-empty when truncated");
             let cursor = TagCursor::new(last_row.pinned, last_row.primary_time, last_row.id);
-            Some(cursor.encode())
+            Some(cursor.encode(self.cursor_keys()))
         } else {
             None
         };
@@ -266,6 +330,7 @@ impl TagsRepo for PostgresRepositories {
         Ok(CursorPage::new(records, next_cursor))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_tags(
         &self,
         pinned: Option<bool>,
@@ -283,13 +348,14 @@ impl TagsRepo for PostgresRepositories {
 
         let count: i64 = qb
             .build_query_scalar()
-            .fetch_one(self.pool())
+            .fetch_one(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
         Self::convert_count(count)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn month_counts(
         &self,
         pinned: Option<bool>,
@@ -316,7 +382,7 @@ impl TagsRepo for PostgresRepositories {
 
         let rows: Vec<MonthRow> = qb
             .build_query_as::<MonthRow>()
-            .fetch_all(self.pool())
+            .fetch_all(self.read_pool())
             .await
             .map_err(map_sqlx_error)?;
 
@@ -338,6 +404,7 @@ impl TagsRepo for PostgresRepositories {
 
 #[async_trait]
 impl TagsWriteRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn create_tag(&self, params: CreateTagParams) -> Result<TagRecord, RepoError> {
         let CreateTagParams {
             slug,
@@ -373,6 +440,7 @@ impl TagsWriteRepo for PostgresRepositories {
         Ok(TagRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn update_tag(&self, params: UpdateTagParams) -> Result<TagRecord, RepoError> {
         let UpdateTagParams {
             id,
@@ -407,6 +475,7 @@ impl TagsWriteRepo for PostgresRepositories {
         Ok(TagRecord::from(row))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_tag(&self, id: Uuid) -> Result<(), RepoError> {
         sqlx::query!(
             r#"
@@ -421,6 +490,69 @@ impl TagsWriteRepo for PostgresRepositories {
 
         Ok(())
     }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn assign_tag(
+        &self,
+        tag_id: Uuid,
+        post_ids: &[Uuid],
+        mode: TagAssignmentMode,
+    ) -> Result<TagAssignmentChanges, RepoError> {
+        let mut tx = self.pool().begin().await.map_err(map_sqlx_error)?;
+
+        let posts: Vec<(Uuid, String)> = sqlx::query!(
+            r#"
+            SELECT id, slug
+            FROM posts
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            FOR SHARE
+            "#,
+            post_ids
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(map_sqlx_error)?
+        .into_iter()
+        .map(|row| (row.id, row.slug))
+        .collect();
+
+        let live: Vec<Uuid> = posts.iter().map(|(id, _)| *id).collect();
+        let changed = match mode {
+            TagAssignmentMode::Add => {
+                sqlx::query_scalar!(
+                    r#"
+                    INSERT INTO post_tags (post_id, tag_id)
+                    SELECT post_id, $2
+                    FROM UNNEST($1::uuid[]) AS post_id
+                    ON CONFLICT DO NOTHING
+                    RETURNING post_id
+                    "#,
+                    &live,
+                    tag_id
+                )
+                .fetch_all(&mut *tx)
+                .await
+            }
+            TagAssignmentMode::Remove => {
+                sqlx::query_scalar!(
+                    r#"
+                    DELETE FROM post_tags
+                    WHERE post_id = ANY($1) AND tag_id = $2
+                    RETURNING post_id
+                    "#,
+                    &live,
+                    tag_id
+                )
+                .fetch_all(&mut *tx)
+                .await
+            }
+        }
+        .map_err(map_sqlx_error)?;
+
+        tx.commit().await.map_err(map_sqlx_error)?;
+
+        Ok(TagAssignmentChanges { posts, changed })
+    }
 }
 
 impl PostgresRepositories {
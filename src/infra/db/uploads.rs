@@ -2,13 +2,15 @@ use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 use sqlx::{Postgres, QueryBuilder};
 use time::OffsetDateTime;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::{
     application::{
         pagination::{CursorPage, PageRequest, UploadCursor},
         repos::{
-            RepoError, UploadContentTypeCount, UploadMonthCount, UploadQueryFilter, UploadsRepo,
+            RepoError, UploadContentTypeCount, UploadIntegritySummary, UploadMonthCount,
+            UploadQueryFilter, UploadsRepo,
         },
     },
     domain::{
@@ -29,6 +31,8 @@ struct UploadRow {
     stored_path: String,
     metadata: JsonValue,
     created_at: OffsetDateTime,
+    last_verified_at: Option<OffsetDateTime>,
+    checksum_mismatch: bool,
 }
 
 impl From<UploadRow> for UploadRecord {
@@ -43,12 +47,15 @@ impl From<UploadRow> for UploadRecord {
             stored_path: row.stored_path,
             metadata,
             created_at: row.created_at,
+            last_verified_at: row.last_verified_at,
+            checksum_mismatch: row.checksum_mismatch,
         }
     }
 }
 
 #[async_trait]
 impl UploadsRepo for PostgresRepositories {
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn insert_upload(&self, record: UploadRecord) -> Result<(), RepoError> {
         let metadata_json = serde_json::to_value(&record.metadata).expect("metadata serializable");
 
@@ -84,11 +91,13 @@ impl UploadsRepo for PostgresRepositories {
         Ok(())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn find_upload(&self, id: Uuid) -> Result<Option<UploadRecord>, RepoError> {
         let row = sqlx::query_as!(
             UploadRow,
             r#"
-            SELECT id, filename, content_type, size_bytes, checksum, stored_path, metadata, created_at
+            SELECT id, filename, content_type, size_bytes, checksum, stored_path, metadata, created_at,
+                   last_verified_at, checksum_mismatch
             FROM uploads
             WHERE id = $1
             "#,
@@ -101,6 +110,29 @@ impl UploadsRepo for PostgresRepositories {
         Ok(row.map(UploadRecord::from))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn find_upload_by_path(
+        &self,
+        stored_path: &str,
+    ) -> Result<Option<UploadRecord>, RepoError> {
+        let row = sqlx::query_as!(
+            UploadRow,
+            r#"
+            SELECT id, filename, content_type, size_bytes, checksum, stored_path, metadata, created_at,
+                   last_verified_at, checksum_mismatch
+            FROM uploads
+            WHERE stored_path = $1
+            "#,
+            stored_path
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(row.map(UploadRecord::from))
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_recent(
         &self,
         limit: u32,
@@ -108,8 +140,8 @@ impl UploadsRepo for PostgresRepositories {
     ) -> Result<Vec<UploadRecord>, RepoError> {
         let limit = limit.clamp(1, 200) as i64;
         let mut qb = QueryBuilder::new(
-            "SELECT id, filename, content_type, size_bytes, checksum, stored_path, metadata, created_at \
-             FROM uploads",
+            "SELECT id, filename, content_type, size_bytes, checksum, stored_path, metadata, created_at, \
+             last_verified_at, checksum_mismatch FROM uploads",
         );
 
         if let Some(before) = before {
@@ -130,6 +162,7 @@ impl UploadsRepo for PostgresRepositories {
         Ok(rows.into_iter().map(UploadRecord::from).collect())
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn list_uploads(
         &self,
         filter: &UploadQueryFilter,
@@ -137,8 +170,8 @@ impl UploadsRepo for PostgresRepositories {
     ) -> Result<CursorPage<UploadRecord>, RepoError> {
         let limit = page.limit.clamp(1, 200) as i64;
         let mut qb = QueryBuilder::new(
-            "SELECT id, filename, content_type, size_bytes, checksum, stored_path, metadata, created_at \
-             FROM uploads WHERE 1=1 ",
+            "SELECT id, filename, content_type, size_bytes, checksum, stored_path, metadata, created_at, \
+             last_verified_at, checksum_mismatch FROM uploads WHERE 1=1 ",
         );
 
         apply_filter(&mut qb, filter);
@@ -171,7 +204,7 @@ impl UploadsRepo for PostgresRepositories {
                 .last()
                 .expect("cursor computation requires at least one row");
             let cursor = UploadCursor::new(row.created_at, row.id);
-            Some(cursor.encode())
+            Some(cursor.encode(self.cursor_keys()))
         } else {
             None
         };
@@ -181,6 +214,7 @@ impl UploadsRepo for PostgresRepositories {
         Ok(CursorPage::new(records, next_cursor))
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn count_uploads(&self, filter: &UploadQueryFilter) -> Result<u64, RepoError> {
         let mut qb = QueryBuilder::new("SELECT COUNT(*) FROM uploads WHERE 1=1 ");
         apply_filter(&mut qb, filter);
@@ -194,6 +228,7 @@ impl UploadsRepo for PostgresRepositories {
         PostgresRepositories::convert_count(count)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn sum_upload_sizes(&self, filter: &UploadQueryFilter) -> Result<u64, RepoError> {
         let mut qb = QueryBuilder::new(
             "SELECT COALESCE(SUM(size_bytes), 0)::BIGINT FROM uploads WHERE 1=1 ",
@@ -209,6 +244,7 @@ impl UploadsRepo for PostgresRepositories {
         PostgresRepositories::convert_count(total)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn month_counts(
         &self,
         filter: &UploadQueryFilter,
@@ -246,6 +282,7 @@ impl UploadsRepo for PostgresRepositories {
         Ok(counts)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn content_type_counts(
         &self,
         filter: &UploadQueryFilter,
@@ -281,6 +318,7 @@ impl UploadsRepo for PostgresRepositories {
         Ok(counts)
     }
 
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
     async fn delete_upload(&self, id: Uuid) -> Result<(), RepoError> {
         sqlx::query!(
             r#"
@@ -295,6 +333,80 @@ impl UploadsRepo for PostgresRepositories {
 
         Ok(())
     }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn filenames_with_prefixes(&self, prefixes: &[String]) -> Result<Vec<String>, RepoError> {
+        if prefixes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let patterns: Vec<String> = prefixes
+            .iter()
+            .map(|prefix| format!("{}%", escape_like(prefix)))
+            .collect();
+
+        sqlx::query_scalar!(
+            r#"
+            SELECT filename
+            FROM uploads
+            WHERE filename LIKE ANY($1)
+            "#,
+            &patterns
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(map_sqlx_error)
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn record_verification(
+        &self,
+        id: Uuid,
+        verified_at: OffsetDateTime,
+        matches: bool,
+    ) -> Result<(), RepoError> {
+        // A file that hashes correctly again stays flagged while another
+        // intact upload holds its checksum, so the dedupe index still holds.
+        sqlx::query!(
+            r#"
+            UPDATE uploads
+            SET last_verified_at = $2,
+                checksum_mismatch = $3 OR EXISTS (
+                    SELECT 1 FROM uploads other
+                    WHERE other.checksum = uploads.checksum
+                      AND other.id <> uploads.id
+                      AND NOT other.checksum_mismatch
+                )
+            WHERE id = $1
+            "#,
+            id,
+            verified_at,
+            !matches
+        )
+        .execute(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(db.system = "postgresql"))]
+    async fn integrity_summary(&self) -> Result<UploadIntegritySummary, RepoError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE checksum_mismatch) AS "mismatched!",
+                   MAX(last_verified_at) AS last_verified_at
+            FROM uploads
+            "#
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(map_sqlx_error)?;
+
+        Ok(UploadIntegritySummary {
+            mismatched: PostgresRepositories::convert_count(row.mismatched)?,
+            last_verified_at: row.last_verified_at,
+        })
+    }
 }
 
 fn apply_filter<'q>(qb: &mut QueryBuilder<'q, Postgres>, filter: &'q UploadQueryFilter) {
@@ -315,4 +427,22 @@ fn apply_filter<'q>(qb: &mut QueryBuilder<'q, Postgres>, filter: &'q UploadQuery
         qb.push_bind(format!("%{search}%"));
         qb.push(' ');
     }
+
+    if let Some(checksum) = filter.checksum.as_ref() {
+        qb.push(" AND checksum = ");
+        qb.push_bind(checksum);
+        qb.push(" AND NOT checksum_mismatch ");
+    }
+}
+
+/// `value` with the `LIKE` wildcards and the escape character quoted.
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
 }
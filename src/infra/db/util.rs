@@ -1,4 +1,10 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::application::pagination::{CursorKeys, CursorPage, TrashCursor};
 use crate::application::repos::RepoError;
+use crate::domain::entities::TrashedRecord;
 
 pub fn map_sqlx_error(err: sqlx::Error) -> RepoError {
     match err {
@@ -19,13 +25,85 @@ pub fn map_sqlx_error(err: sqlx::Error) -> RepoError {
         sqlx::Error::Database(db) if db.message().contains("violates") => RepoError::Integrity {
             message: db.message().to_string(),
         },
-        sqlx::Error::Database(db)
-            if db
-                .message()
-                .contains("canceling statement due to user request") =>
-        {
-            RepoError::Timeout
+        // 57014 query_canceled: statement_timeout or an explicit cancel.
+        sqlx::Error::Database(db) if db.code().as_deref() == Some("57014") => RepoError::Timeout,
+        sqlx::Error::PoolTimedOut => RepoError::Timeout,
+        // 40001 serialization_failure, 40P01 deadlock_detected.
+        sqlx::Error::Database(db) if matches!(db.code().as_deref(), Some("40001" | "40P01")) => {
+            RepoError::Contention
         }
         other => RepoError::from_persistence(other),
     }
 }
+
+/// Tables whose conditional updates report `Stale` through [`missing_or_stale`].
+#[derive(Debug, Clone, Copy)]
+pub(super) enum ContentTable {
+    Posts,
+    Pages,
+}
+
+/// Explain why a conditional `UPDATE ... WHERE id = $1 AND updated_at = ...`
+/// matched no row: `Stale` if the row still exists, `NotFound` otherwise.
+pub(super) async fn missing_or_stale(pool: &PgPool, table: ContentTable, id: Uuid) -> RepoError {
+    let exists = match table {
+        ContentTable::Posts => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS (SELECT 1 FROM posts WHERE id = $1) AS "exists!""#,
+                id
+            )
+            .fetch_one(pool)
+            .await
+        }
+        ContentTable::Pages => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS (SELECT 1 FROM pages WHERE id = $1) AS "exists!""#,
+                id
+            )
+            .fetch_one(pool)
+            .await
+        }
+    };
+    match exists {
+        Ok(true) => RepoError::Stale,
+        Ok(false) => RepoError::NotFound,
+        Err(err) => map_sqlx_error(err),
+    }
+}
+
+pub(super) struct TrashedRow {
+    pub(super) id: Uuid,
+    pub(super) slug: String,
+    pub(super) title: String,
+    pub(super) deleted_at: OffsetDateTime,
+    pub(super) updated_at: OffsetDateTime,
+}
+
+/// Turn up to `limit + 1` trashed rows, most recently trashed first, into a
+/// page whose cursor resumes after the last row kept.
+pub(super) fn trashed_page(
+    mut rows: Vec<TrashedRow>,
+    limit: i64,
+    keys: &CursorKeys,
+) -> CursorPage<TrashedRecord> {
+    let next_cursor = if (rows.len() as i64) > limit {
+        rows.pop();
+        rows.last()
+            .map(|row| TrashCursor::new(row.deleted_at, row.id).encode(keys))
+    } else {
+        None
+    };
+
+    let items = rows
+        .into_iter()
+        .map(|row| TrashedRecord {
+            id: row.id,
+            slug: row.slug,
+            title: row.title,
+            deleted_at: row.deleted_at,
+            updated_at: row.updated_at,
+        })
+        .collect();
+
+    CursorPage::new(items, next_cursor)
+}
@@ -0,0 +1,403 @@
+//! Deployment self-checks shared by `soffio doctor` and `/readyz`.
+//!
+//! Each check returns a [`CheckResult`] instead of failing, so callers can run
+//! them all and report the worst level.
+
+use std::{
+    net::SocketAddr,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::config::{CacheSettings, ListenAddr};
+use crate::infra::db::{MigrationState, migration_status};
+
+/// Leave this much of the upload volume free before warning.
+const MIN_FREE_UPLOAD_BYTES: u64 = 512 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckLevel {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub level: CheckLevel,
+    pub detail: String,
+    /// What to change when the check does not pass.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    pub fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            level: CheckLevel::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    pub fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            level: CheckLevel::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            level: CheckLevel::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// The most severe level among `results`; `Pass` when there are none.
+pub fn worst_level(results: &[CheckResult]) -> CheckLevel {
+    results
+        .iter()
+        .map(|result| result.level)
+        .max()
+        .unwrap_or(CheckLevel::Pass)
+}
+
+pub async fn check_database(pool: &PgPool) -> CheckResult {
+    match sqlx::query_scalar!("SELECT 1 AS ok").fetch_one(pool).await {
+        Ok(_) => CheckResult::pass("database", "connected"),
+        Err(err) => CheckResult::fail(
+            "database",
+            format!("cannot reach the database: {err}"),
+            "check database.url and that Postgres accepts connections from this host",
+        ),
+    }
+}
+
+pub async fn check_migrations(pool: &PgPool) -> CheckResult {
+    let statuses = match migration_status(pool).await {
+        Ok(statuses) => statuses,
+        Err(err) => {
+            return CheckResult::fail(
+                "migrations",
+                format!("cannot read migration status: {err}"),
+                "check that the database user can read _sqlx_migrations",
+            );
+        }
+    };
+
+    let divergent: Vec<String> = statuses
+        .iter()
+        .filter(|status| status.state.is_divergent())
+        .map(|status| format!("{} ({})", status.version, status.state.as_str()))
+        .collect();
+    if !divergent.is_empty() {
+        return CheckResult::fail(
+            "migrations",
+            format!("diverged from this binary: {}", divergent.join(", ")),
+            "run `soffio migrations status` and deploy the binary that matches the schema",
+        );
+    }
+
+    let pending = statuses
+        .iter()
+        .filter(|status| status.state == MigrationState::Pending)
+        .count();
+    if pending > 0 {
+        return CheckResult::warn(
+            "migrations",
+            format!("{pending} pending"),
+            "`soffio serve` applies pending migrations on startup",
+        );
+    }
+
+    CheckResult::pass("migrations", format!("{} applied", statuses.len()))
+}
+
+/// The uploads directory exists, accepts a probe file, and has free space.
+pub async fn check_uploads(directory: &Path) -> CheckResult {
+    let directory = directory.to_path_buf();
+    match tokio::task::spawn_blocking(move || uploads_result(&directory)).await {
+        Ok(result) => result,
+        Err(err) => CheckResult::fail("uploads", format!("check task failed: {err}"), "retry"),
+    }
+}
+
+fn uploads_result(directory: &Path) -> CheckResult {
+    const NAME: &str = "uploads";
+    let shown = directory.display();
+
+    if !directory.is_dir() {
+        return CheckResult::fail(
+            NAME,
+            format!("{shown} does not exist"),
+            "create the directory or point uploads.directory at an existing one",
+        );
+    }
+    if let Err(err) = tempfile::tempfile_in(directory) {
+        return CheckResult::fail(
+            NAME,
+            format!("{shown} is not writable: {err}"),
+            "grant the service user write access to uploads.directory",
+        );
+    }
+
+    match available_bytes(directory) {
+        Some(free) if free < MIN_FREE_UPLOAD_BYTES => CheckResult::warn(
+            NAME,
+            format!("{shown} is writable; {} free", format_bytes(free)),
+            "free disk space or move uploads.directory to a larger volume",
+        ),
+        Some(free) => CheckResult::pass(
+            NAME,
+            format!("{shown} is writable; {} free", format_bytes(free)),
+        ),
+        None => CheckResult::warn(
+            NAME,
+            format!("{shown} is writable; free space unknown"),
+            "make `df` available to report free space",
+        ),
+    }
+}
+
+/// Free bytes on the volume holding `path`, read from POSIX `df`.
+fn available_bytes(path: &Path) -> Option<u64> {
+    let output = Command::new("df")
+        .arg("-Pk")
+        .arg(path)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let kib: u64 = stdout
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// The Mermaid CLI runs and reports a version. Mermaid is optional, so a
+/// missing CLI only warns.
+pub async fn check_mermaid(cli_path: &Path) -> CheckResult {
+    let cli_path = cli_path.to_path_buf();
+    match tokio::task::spawn_blocking(move || mermaid_result(&cli_path)).await {
+        Ok(result) => result,
+        Err(err) => CheckResult::fail("mermaid", format!("check task failed: {err}"), "retry"),
+    }
+}
+
+fn mermaid_result(cli_path: &Path) -> CheckResult {
+    const NAME: &str = "mermaid";
+    let shown = cli_path.display();
+
+    match Command::new(cli_path)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            CheckResult::pass(NAME, format!("{shown} {version}"))
+        }
+        Ok(output) => CheckResult::warn(
+            NAME,
+            format!("{shown} --version exited with {}", output.status),
+            "reinstall @mermaid-js/mermaid-cli; diagrams render as code blocks until then",
+        ),
+        Err(err) => CheckResult::warn(
+            NAME,
+            format!("{shown} is not runnable: {err}"),
+            "install @mermaid-js/mermaid-cli or set render.mermaid_cli_path; diagrams render as code blocks until then",
+        ),
+    }
+}
+
+/// The response cache's worst-case size against available memory.
+pub fn check_cache(cache: &CacheSettings) -> CheckResult {
+    if !cache.enable_l1_cache {
+        let detail = if cache.enable_l0_cache {
+            "L0 enabled, L1 disabled"
+        } else {
+            "disabled"
+        };
+        return CheckResult::pass("cache", detail);
+    }
+    l1_result(
+        cache.l1_response_limit,
+        cache.l1_response_body_limit_bytes,
+        available_memory_bytes(),
+    )
+}
+
+/// Warns when a full L1 cache would take more than half of `available`.
+fn l1_result(limit: usize, body_limit: usize, available: Option<u64>) -> CheckResult {
+    const NAME: &str = "cache";
+
+    let worst_case = (limit as u64).saturating_mul(body_limit as u64);
+    let detail = format!(
+        "L1 holds up to {limit} responses of {} ({} worst case)",
+        format_bytes(body_limit as u64),
+        format_bytes(worst_case)
+    );
+
+    match available {
+        Some(available) if worst_case > available / 2 => CheckResult::warn(
+            NAME,
+            format!("{detail}, {} available", format_bytes(available)),
+            "lower cache.l1_response_limit or cache.l1_response_body_limit_bytes",
+        ),
+        _ => CheckResult::pass(NAME, detail),
+    }
+}
+
+/// `MemAvailable` from `/proc/meminfo`; unknown elsewhere.
+fn available_memory_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo
+        .lines()
+        .find(|line| line.starts_with("MemAvailable:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// The listener at `addr` can be created: a TCP port is free, or a unix
+/// socket's directory exists and no live process owns the socket.
+pub async fn check_listener(name: &'static str, addr: &ListenAddr) -> CheckResult {
+    let path = match addr {
+        ListenAddr::Tcp(addr) => return check_port(name, *addr).await,
+        ListenAddr::Unix(path) => path,
+    };
+    if !path.parent().is_some_and(Path::is_dir) {
+        return CheckResult::fail(
+            name,
+            format!("the directory of {addr} does not exist"),
+            "create the socket directory or change the listener address",
+        );
+    }
+    if tokio::net::UnixStream::connect(path).await.is_ok() {
+        return CheckResult::fail(
+            name,
+            format!("{addr} is in use by another process"),
+            "stop the process holding the socket or change the listener address",
+        );
+    }
+    CheckResult::pass(name, format!("{addr} is available"))
+}
+
+/// `addr` can be bound right now. The listener is dropped immediately.
+pub async fn check_port(name: &'static str, addr: SocketAddr) -> CheckResult {
+    match tokio::net::TcpListener::bind(addr).await {
+        Ok(_) => CheckResult::pass(name, format!("{addr} is available")),
+        Err(err) => CheckResult::fail(
+            name,
+            format!("cannot bind {addr}: {err}"),
+            "stop the process holding the port or change the listener address",
+        ),
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worst_level_picks_the_most_severe() {
+        let results = [
+            CheckResult::pass("a", "ok"),
+            CheckResult::warn("b", "meh", "fix"),
+            CheckResult::pass("c", "ok"),
+        ];
+        assert_eq!(worst_level(&results), CheckLevel::Warn);
+        assert_eq!(worst_level(&[]), CheckLevel::Pass);
+    }
+
+    #[test]
+    fn cache_warns_when_worst_case_exceeds_half_of_memory() {
+        let gib = 1024 * 1024 * 1024;
+        let result = l1_result(1_000, 1024 * 1024, Some(gib));
+        assert_eq!(result.level, CheckLevel::Warn);
+
+        let result = l1_result(100, 1024 * 1024, Some(gib));
+        assert_eq!(result.level, CheckLevel::Pass);
+
+        let result = l1_result(1_000_000, 1024 * 1024, None);
+        assert_eq!(result.level, CheckLevel::Pass);
+    }
+
+    #[test]
+    fn uploads_fail_for_a_missing_directory() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let missing = dir.path().join("missing");
+        assert_eq!(uploads_result(&missing).level, CheckLevel::Fail);
+        assert_ne!(uploads_result(dir.path()).level, CheckLevel::Fail);
+    }
+
+    #[test]
+    fn missing_mermaid_cli_only_warns() {
+        let result = mermaid_result(Path::new("/nonexistent/soffio-mmdc"));
+        assert_eq!(result.level, CheckLevel::Warn);
+        assert!(result.hint.is_some());
+    }
+
+    #[tokio::test]
+    async fn bound_port_fails() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        assert_eq!(check_port("public", addr).await.level, CheckLevel::Fail);
+    }
+
+    #[tokio::test]
+    async fn unix_socket_in_missing_directory_fails() {
+        let addr = ListenAddr::Unix("/nonexistent/soffio/public.sock".into());
+        assert_eq!(
+            check_listener("public", &addr).await.level,
+            CheckLevel::Fail
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::infra::http::admin::shared::ListLimit;
+
 #[derive(Debug, Deserialize, Default, Clone)]
 pub struct ApiKeyFilters {
     pub status: Option<String>,
@@ -7,6 +9,8 @@ pub struct ApiKeyFilters {
     pub scope: Option<String>,
     pub cursor: Option<String>,
     pub trail: Option<String>,
+    #[serde(flatten)]
+    pub limit: ListLimit,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,6 +38,8 @@ pub struct ApiKeyPanelForm {
     pub scope: Option<String>,
     pub cursor: Option<String>,
     pub trail: Option<String>,
+    #[serde(flatten)]
+    pub limit: ListLimit,
     pub clear: Option<String>,
 }
 
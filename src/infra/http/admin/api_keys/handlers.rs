@@ -1,5 +1,6 @@
 use askama::Template;
 use axum::{
+    Extension,
     extract::{Form, Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
@@ -7,6 +8,7 @@ use axum::{
 use uuid::Uuid;
 
 use crate::{
+    application::admin::auth::AdminSession,
     application::{
         api_keys::{ApiKeyIssued, IssueApiKeyCommand, UpdateApiKeyCommand},
         stream::StreamBuilder,
@@ -16,7 +18,7 @@ use crate::{
         AdminState,
         pagination::CursorState,
         selectors::{API_KEY_EDITOR_PANEL, PANEL, SCOPE_PICKER, SCOPE_SELECTION_STORE},
-        shared::{Toast, push_toasts},
+        shared::{ListLimit, Toast, push_toasts},
     },
     presentation::{admin::views as admin_views, views::render_template_response},
 };
@@ -49,7 +51,10 @@ pub async fn admin_api_keys_panel(
     Form(form): Form<ApiKeyPanelForm>,
 ) -> Response {
     let filters = if form.clear.is_some() {
-        ApiKeyFilters::default()
+        ApiKeyFilters {
+            limit: form.limit,
+            ..ApiKeyFilters::default()
+        }
     } else {
         ApiKeyFilters {
             status: form.status,
@@ -57,6 +62,7 @@ pub async fn admin_api_keys_panel(
             scope: form.scope,
             cursor: form.cursor,
             trail: form.trail,
+            limit: form.limit,
         }
     };
 
@@ -113,6 +119,7 @@ pub async fn admin_api_key_update(
 
 pub async fn admin_api_key_create(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Form(form): Form<CreateApiKeyForm>,
 ) -> Response {
     let scope_values = parse_scope_state(&form.scope_state);
@@ -125,7 +132,7 @@ pub async fn admin_api_key_create(
 
     let expires_in = parse_expires_in(form.expires_in.as_deref());
 
-    let actor = "admin:api-keys";
+    let actor = session.username.as_str();
     let issued = match state
         .api_keys
         .issue(IssueApiKeyCommand {
@@ -168,6 +175,7 @@ pub async fn admin_api_key_revoke(
         scope: form.filter_scope,
         cursor: form.cursor,
         trail: form.trail,
+        limit: ListLimit::default(),
     };
 
     match build_stream(&state, &filters, &[Toast::success("Key revoked")]).await {
@@ -215,6 +223,7 @@ pub async fn admin_api_key_delete(
         scope: form.filter_scope,
         cursor: form.cursor,
         trail: form.trail,
+        limit: ListLimit::default(),
     };
 
     match build_stream(&state, &filters, &[Toast::success("Key deleted")]).await {
@@ -277,7 +286,8 @@ async fn build_page(
 
     let query_filter = build_api_key_filter(filters.scope.as_deref(), filters.search.as_deref());
 
-    let cursor_state = CursorState::new(filters.cursor.clone(), filters.trail.clone());
+    let cursor_state =
+        CursorState::new(filters.cursor.clone(), filters.trail.clone()).with_limit(&filters.limit);
 
     let panel = build_panel_view(state, status_filter, &query_filter, &cursor_state).await?;
     let chrome = load_chrome(state).await?;
@@ -364,7 +374,8 @@ async fn build_stream(
 
     let query_filter = build_api_key_filter(filters.scope.as_deref(), filters.search.as_deref());
 
-    let cursor_state = CursorState::new(filters.cursor.clone(), filters.trail.clone());
+    let cursor_state =
+        CursorState::new(filters.cursor.clone(), filters.trail.clone()).with_limit(&filters.limit);
 
     let panel = build_panel_view(state, status_filter, &query_filter, &cursor_state).await?;
     let panel_html = render_panel_html(&panel)?;
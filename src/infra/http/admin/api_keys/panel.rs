@@ -34,11 +34,15 @@ pub async fn build_panel_view(
     let timezone = settings.timezone;
 
     let cursor = cursor_state
-        .decode_with(ApiKeyCursor::decode, "admin_api_keys")
+        .decode_with(
+            state.db.cursor_keys(),
+            ApiKeyCursor::decode,
+            "admin_api_keys",
+        )
         .map_err(ApiKeyHttpError::from_http)?;
 
     let page_req = ApiKeyPageRequest {
-        limit: settings.admin_page_size as u32,
+        limit: pagination::page_size(settings.admin_page_size, cursor_state.limit()),
         cursor,
     };
 
@@ -121,7 +125,7 @@ pub async fn build_panel_view(
         ));
         let next_trail = pagination::join_cursor_history(&next_history);
         admin_views::AdminApiKeyPaginationState {
-            cursor: Some(c.encode()),
+            cursor: Some(c.encode(state.db.cursor_keys())),
             trail: next_trail,
         }
     });
@@ -162,15 +166,19 @@ pub async fn build_panel_view(
         previous_page_state,
         next_page_state,
         available_scopes: scope_options(),
-        custom_hidden_fields: build_api_key_hidden_fields(filter),
+        custom_hidden_fields: build_api_key_hidden_fields(filter, cursor_state.limit()),
     })
 }
 
-fn build_api_key_hidden_fields(filter: &ApiKeyQueryFilter) -> Vec<admin_views::AdminHiddenField> {
+fn build_api_key_hidden_fields(
+    filter: &ApiKeyQueryFilter,
+    limit: Option<u32>,
+) -> Vec<admin_views::AdminHiddenField> {
     let mut fields = Vec::new();
     if let Some(scope) = filter.scope {
         fields.push(admin_views::AdminHiddenField::new("scope", scope.as_str()));
     }
+    fields.extend(pagination::limit_hidden_field(limit));
     fields
 }
 
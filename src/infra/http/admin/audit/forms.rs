@@ -2,15 +2,27 @@
 
 use serde::Deserialize;
 
-/// Form for panel refresh requests.
-#[derive(Debug, Deserialize)]
+use crate::infra::http::admin::shared::ListLimit;
+
+/// Form for panel refresh requests. The CSV export reads the same fields
+/// from its query string.
+#[derive(Debug, Default, Deserialize)]
 pub(crate) struct AdminAuditPanelForm {
     /// Entity type from status tabs (mapped to entity_type filter)
     pub(crate) status: Option<String>,
     pub(crate) actor: Option<String>,
+    /// Actor origin: `admin`, `api_key` or `system`.
+    pub(crate) actor_type: Option<String>,
     pub(crate) action: Option<String>,
     pub(crate) search: Option<String>,
+    pub(crate) field: Option<String>,
+    /// First day included, `YYYY-MM-DD` in the site timezone.
+    pub(crate) from: Option<String>,
+    /// Last day included, `YYYY-MM-DD` in the site timezone.
+    pub(crate) to: Option<String>,
     pub(crate) cursor: Option<String>,
     pub(crate) trail: Option<String>,
+    #[serde(flatten)]
+    pub(crate) limit: ListLimit,
     pub(crate) clear: Option<String>,
 }
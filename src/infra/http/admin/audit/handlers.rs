@@ -2,29 +2,37 @@
 
 use askama::Template;
 use axum::{
-    extract::{Form, State},
+    extract::{Form, Query, State},
+    http::header,
     response::{IntoResponse, Response},
 };
+use chrono_tz::Tz;
+use sqlx::types::chrono::NaiveDate;
 
 use crate::{
     application::{pagination::AuditCursor, repos::AuditQueryFilter},
+    domain::types::ActorType,
     infra::http::admin::{
         AdminState, pagination::CursorState, selectors::AUDIT_PANEL, shared::datastar_replace,
     },
     presentation::admin::views as admin_views,
+    util::timezone,
 };
 
 use super::{
     errors::admin_audit_error,
     forms::AdminAuditPanelForm,
-    panel::{apply_pagination_links, build_audit_list_view, render_audit_panel_html},
+    panel::{
+        apply_pagination_links, build_audit_list_view, format_changes, render_audit_panel_html,
+    },
+    status::actor_type_label,
 };
 
 /// GET /audit - Render audit log list page.
 pub(crate) async fn admin_audit(State(state): State<AdminState>) -> Response {
     let filter = AuditQueryFilter::default();
 
-    let mut content = match build_audit_list_view(&state, &filter, None).await {
+    let mut content = match build_audit_list_view(&state, &filter, None, None).await {
         Ok(content) => content,
         Err(err) => {
             return admin_audit_error("infra::http::admin::audit::admin_audit", err)
@@ -56,26 +64,20 @@ pub(crate) async fn admin_audit_panel(
     State(state): State<AdminState>,
     Form(form): Form<AdminAuditPanelForm>,
 ) -> Response {
-    let (actor, action, entity_type, search) = if form.clear.is_some() {
-        (None, None, None, None)
-    } else {
-        (
-            form.actor.filter(|s| !s.is_empty()),
-            form.action.filter(|s| !s.is_empty()),
-            form.status.filter(|s| !s.is_empty()), // from status tabs
-            form.search.filter(|s| !s.is_empty()),
-        )
+    let tz = match site_timezone(&state).await {
+        Ok(tz) => tz,
+        Err(response) => return response,
     };
-
-    let filter = AuditQueryFilter {
-        actor,
-        action,
-        entity_type,
-        search,
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
+    let filter = if form.clear.is_some() {
+        AuditQueryFilter::default()
+    } else {
+        filter_from_form(form, tz)
     };
 
-    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
     let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
         AuditCursor::decode,
         "infra::http::admin::audit::admin_audit_panel",
     ) {
@@ -83,13 +85,14 @@ pub(crate) async fn admin_audit_panel(
         Err(err) => return err.into_response(),
     };
 
-    let mut content = match build_audit_list_view(&state, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => {
-            return admin_audit_error("infra::http::admin::audit::admin_audit_panel", err)
-                .into_response();
-        }
-    };
+    let mut content =
+        match build_audit_list_view(&state, &filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_audit_error("infra::http::admin::audit::admin_audit_panel", err)
+                    .into_response();
+            }
+        };
 
     apply_pagination_links(&mut content, &cursor_state);
 
@@ -140,6 +143,23 @@ pub(crate) async fn admin_audit_detail(
                 badge_status: None,
                 is_multiline: false,
             },
+            admin_views::AdminAuditDetailField {
+                label: "Actor Type".to_string(),
+                value: actor_type_label(record.actor_type).to_string(),
+                is_badge: false,
+                badge_status: None,
+                is_multiline: false,
+            },
+            admin_views::AdminAuditDetailField {
+                label: "API Key".to_string(),
+                value: record
+                    .actor_key_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| "—".to_string()),
+                is_badge: false,
+                badge_status: None,
+                is_multiline: false,
+            },
             admin_views::AdminAuditDetailField {
                 label: "Action".to_string(),
                 value: record.action.clone(),
@@ -171,6 +191,24 @@ pub(crate) async fn admin_audit_detail(
                 badge_status: None,
                 is_multiline: true,
             },
+            admin_views::AdminAuditDetailField {
+                label: "Changes".to_string(),
+                value: record
+                    .changes
+                    .as_ref()
+                    .and_then(format_changes)
+                    .unwrap_or_else(|| "—".to_string()),
+                is_badge: false,
+                badge_status: None,
+                is_multiline: true,
+            },
+            admin_views::AdminAuditDetailField {
+                label: "Request ID".to_string(),
+                value: record.request_id.clone().unwrap_or_else(|| "—".to_string()),
+                is_badge: false,
+                badge_status: None,
+                is_multiline: false,
+            },
             admin_views::AdminAuditDetailField {
                 label: "Created At".to_string(),
                 value: admin_views::format_timestamp(record.created_at, settings.timezone),
@@ -197,3 +235,67 @@ pub(crate) async fn admin_audit_detail(
         }
     }
 }
+
+/// GET /audit/export.csv - Download the entries matching the current filters.
+pub(crate) async fn admin_audit_export(
+    State(state): State<AdminState>,
+    Query(form): Query<AdminAuditPanelForm>,
+) -> Response {
+    let tz = match site_timezone(&state).await {
+        Ok(tz) => tz,
+        Err(response) => return response,
+    };
+    let filter = filter_from_form(form, tz);
+
+    match state.audit.export_csv(&filter).await {
+        Ok(csv) => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"audit-log.csv\"",
+                ),
+            ],
+            csv,
+        )
+            .into_response(),
+        Err(err) => {
+            admin_audit_error("infra::http::admin::audit::admin_audit_export", err).into_response()
+        }
+    }
+}
+
+async fn site_timezone(state: &AdminState) -> Result<Tz, Response> {
+    state
+        .settings
+        .load()
+        .await
+        .map(|settings| settings.timezone)
+        .map_err(|err| {
+            tracing::error!(error = %err, "Failed to load settings for audit filters");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        })
+}
+
+/// Filter from submitted form fields. Empty fields are ignored, and the date
+/// range covers whole days in the site timezone, `to` included.
+pub(super) fn filter_from_form(form: AdminAuditPanelForm, tz: Tz) -> AuditQueryFilter {
+    let date = |value: Option<String>| {
+        value.and_then(|value| NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok())
+    };
+    AuditQueryFilter {
+        actor: form.actor.filter(|s| !s.is_empty()),
+        actor_type: form
+            .actor_type
+            .and_then(|value| ActorType::try_from(value.as_str()).ok()),
+        action: form.action.filter(|s| !s.is_empty()),
+        entity_type: form.status.filter(|s| !s.is_empty()), // from status tabs
+        search: form.search.filter(|s| !s.is_empty()),
+        field: form.field.filter(|s| !s.is_empty()),
+        from: date(form.from).and_then(|day| timezone::local_day_start(day, tz)),
+        to: date(form.to)
+            .and_then(|day| day.succ_opt())
+            .and_then(|day| timezone::local_day_start(day, tz)),
+        request_id: None,
+    }
+}
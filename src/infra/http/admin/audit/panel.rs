@@ -1,6 +1,9 @@
 //! Panel building for audit list.
 
 use askama::Template;
+use chrono_tz::Tz;
+use time::Duration;
+use url::form_urlencoded::Serializer;
 
 use crate::{
     application::{
@@ -8,31 +11,41 @@ use crate::{
         pagination::{AuditCursor, PageRequest},
         repos::{AuditQueryFilter, SettingsRepo},
     },
+    domain::types::ActorType,
     infra::http::admin::{
         AdminState,
         pagination::{self, CursorState},
         shared::template_render_http_error,
     },
     presentation::admin::views as admin_views,
+    util::timezone,
 };
 
-use super::status::{action_options, actor_options, entity_type_filters};
+use super::status::{
+    action_options, actor_options, actor_type_label, actor_type_options, entity_type_filters,
+};
 
 /// Build the complete audit list view for rendering.
 pub(super) async fn build_audit_list_view(
     state: &AdminState,
     filter: &AuditQueryFilter,
     cursor: Option<AuditCursor>,
+    limit: Option<u32>,
 ) -> Result<admin_views::AdminAuditListView, crate::application::repos::RepoError> {
     let settings = state.db.load_site_settings().await?;
-    let admin_page_size = settings.admin_page_size.clamp(1, 100).max(1) as u32;
+    let admin_page_size = pagination::page_size(settings.admin_page_size, limit);
 
     // Build filters for counts (without entity_type to get all entity types)
     let count_filter = AuditQueryFilter {
         actor: filter.actor.clone(),
+        actor_type: filter.actor_type,
         action: filter.action.clone(),
         entity_type: None, // Don't filter for entity type counts
         search: filter.search.clone(),
+        field: filter.field.clone(),
+        from: filter.from,
+        to: filter.to,
+        request_id: filter.request_id.clone(),
     };
 
     // Parallel queries
@@ -52,10 +65,12 @@ pub(super) async fn build_audit_list_view(
             id: entry.id.to_string(),
             detail_href: format!("/audit/{}", entry.id),
             actor: entry.actor,
+            actor_type: actor_type_label(entry.actor_type),
             action: entry.action,
             entity_type: entry.entity_type,
             entity_id: entry.entity_id,
             payload_text: entry.payload_text,
+            changed_fields: entry.changes.as_ref().and_then(changed_field_names),
             created_at: admin_views::format_timestamp(entry.created_at, settings.timezone),
         })
         .collect();
@@ -67,18 +82,33 @@ pub(super) async fn build_audit_list_view(
     );
     let actor_opts = actor_options(&actor_counts, filter.actor.as_deref());
     let action_opts = action_options(&action_counts, filter.action.as_deref());
+    let (filter_from, filter_to) = date_range_values(filter, settings.timezone);
+    let filter_query = build_filter_query(filter, filter_from.as_deref(), filter_to.as_deref());
+    let export_href = if filter_query.is_empty() {
+        "/audit/export.csv".to_string()
+    } else {
+        format!("/audit/export.csv?{filter_query}")
+    };
 
     Ok(admin_views::AdminAuditListView {
         heading: "Audit Log".to_string(),
         filters,
         entries,
         actor_options: actor_opts,
+        actor_type_options: actor_type_options(),
         action_options: action_opts,
         filter_actor: filter.actor.clone(),
+        filter_actor_type: filter
+            .actor_type
+            .map(|actor_type| actor_type.as_str().to_string()),
         filter_action: filter.action.clone(),
         filter_entity_type: filter.entity_type.clone(),
         filter_search: filter.search.clone(),
-        filter_query: String::new(),
+        filter_field: filter.field.clone(),
+        filter_from: filter_from.clone(),
+        filter_to: filter_to.clone(),
+        filter_query,
+        export_href,
         active_status_key: filter.entity_type.clone(),
         next_cursor: page.next_cursor,
         cursor_param: None,
@@ -86,18 +116,110 @@ pub(super) async fn build_audit_list_view(
         previous_page_state: None,
         next_page_state: None,
         panel_action: "/audit/panel".to_string(),
-        custom_hidden_fields: build_audit_hidden_fields(filter),
+        custom_hidden_fields: build_audit_hidden_fields(filter, filter_from, filter_to, limit),
     })
 }
 
-fn build_audit_hidden_fields(filter: &AuditQueryFilter) -> Vec<admin_views::AdminHiddenField> {
+/// The filter's date range as the `YYYY-MM-DD` values the form submitted.
+fn date_range_values(filter: &AuditQueryFilter, tz: Tz) -> (Option<String>, Option<String>) {
+    let from = filter
+        .from
+        .map(|from| timezone::localized_date(from, tz).to_string());
+    // `to` is the start of the day after the last included one.
+    let to = filter
+        .to
+        .map(|to| timezone::localized_date(to - Duration::nanoseconds(1), tz).to_string());
+    (from, to)
+}
+
+fn build_filter_query(filter: &AuditQueryFilter, from: Option<&str>, to: Option<&str>) -> String {
+    let mut serializer = Serializer::new(String::new());
+    let pairs = [
+        ("status", filter.entity_type.as_deref()),
+        ("actor", filter.actor.as_deref()),
+        ("actor_type", filter.actor_type.map(ActorType::as_str)),
+        ("action", filter.action.as_deref()),
+        ("search", filter.search.as_deref()),
+        ("field", filter.field.as_deref()),
+        ("from", from),
+        ("to", to),
+    ];
+    for (key, value) in pairs {
+        if let Some(value) = value {
+            serializer.append_pair(key, value);
+        }
+    }
+    serializer.finish()
+}
+
+/// Comma-separated names of the fields recorded in an entry's changes.
+pub(super) fn changed_field_names(changes: &serde_json::Value) -> Option<String> {
+    let fields = changes.as_object()?;
+    if fields.is_empty() {
+        return None;
+    }
+    Some(
+        fields
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// One `field: old → new` line per recorded change.
+pub(super) fn format_changes(changes: &serde_json::Value) -> Option<String> {
+    let fields = changes.as_object()?;
+    let lines: Vec<String> = fields
+        .iter()
+        .map(|(field, change)| {
+            format!(
+                "{field}: {} → {}",
+                format_change_value(change.get("old")),
+                format_change_value(change.get("new"))
+            )
+        })
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn format_change_value(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None | Some(serde_json::Value::Null) => "—".to_string(),
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn build_audit_hidden_fields(
+    filter: &AuditQueryFilter,
+    from: Option<String>,
+    to: Option<String>,
+    limit: Option<u32>,
+) -> Vec<admin_views::AdminHiddenField> {
     let mut fields = Vec::new();
     if let Some(ref actor) = filter.actor {
         fields.push(admin_views::AdminHiddenField::new("actor", actor.clone()));
     }
+    if let Some(actor_type) = filter.actor_type {
+        fields.push(admin_views::AdminHiddenField::new(
+            "actor_type",
+            actor_type.as_str(),
+        ));
+    }
     if let Some(ref action) = filter.action {
         fields.push(admin_views::AdminHiddenField::new("action", action.clone()));
     }
+    if let Some(ref field) = filter.field {
+        fields.push(admin_views::AdminHiddenField::new("field", field.clone()));
+    }
+    if let Some(from) = from {
+        fields.push(admin_views::AdminHiddenField::new("from", from));
+    }
+    if let Some(to) = to {
+        fields.push(admin_views::AdminHiddenField::new("to", to));
+    }
+    fields.extend(pagination::limit_hidden_field(limit));
     fields
 }
 
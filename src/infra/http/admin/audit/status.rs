@@ -1,8 +1,10 @@
 //! Status helpers for audit admin.
 
 use crate::application::repos::{AuditActionCount, AuditActorCount, AuditEntityTypeCount};
+use crate::domain::types::ActorType;
 use crate::presentation::admin::views::{
-    AdminAuditActionOption, AdminAuditActorOption, AdminAuditStatusFilterView,
+    AdminAuditActionOption, AdminAuditActorOption, AdminAuditActorTypeOption,
+    AdminAuditStatusFilterView,
 };
 use std::collections::HashMap;
 
@@ -16,6 +18,7 @@ const ALL_ENTITY_TYPES: &[&str] = &[
     "api_key",
     "settings",
     "job",
+    "comment",
 ];
 
 /// Build entity type status filters (tabs).
@@ -70,6 +73,28 @@ pub(super) fn actor_options(
         .collect()
 }
 
+/// Actor origins offered by the origin dropdown.
+const ACTOR_TYPES: [ActorType; 3] = [ActorType::Admin, ActorType::ApiKey, ActorType::System];
+
+pub(super) fn actor_type_label(actor_type: ActorType) -> &'static str {
+    match actor_type {
+        ActorType::Admin => "Admin",
+        ActorType::ApiKey => "API key",
+        ActorType::System => "System",
+    }
+}
+
+/// Build actor origin dropdown options.
+pub(super) fn actor_type_options() -> Vec<AdminAuditActorTypeOption> {
+    ACTOR_TYPES
+        .into_iter()
+        .map(|actor_type| AdminAuditActorTypeOption {
+            value: actor_type.as_str(),
+            label: actor_type_label(actor_type),
+        })
+        .collect()
+}
+
 /// Build action dropdown options.
 pub(super) fn action_options(
     action_counts: &[AuditActionCount],
@@ -0,0 +1,66 @@
+//! Session cookie encoding for the admin listener.
+
+use axum::http::{HeaderMap, HeaderValue, header::COOKIE};
+
+pub(super) const SESSION_COOKIE: &str = "soffio_admin_session";
+
+/// Return the raw session cookie value from the request headers, if present.
+pub(super) fn read_session_cookie(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            (name == SESSION_COOKIE).then_some(value)
+        })
+}
+
+/// `Set-Cookie` value that stores a freshly issued session.
+pub(super) fn session_cookie(value: &str, max_age_seconds: i64, secure: bool) -> HeaderValue {
+    build_cookie(value, max_age_seconds, secure)
+}
+
+/// `Set-Cookie` value that removes the session cookie.
+pub(super) fn cleared_session_cookie(secure: bool) -> HeaderValue {
+    build_cookie("", 0, secure)
+}
+
+fn build_cookie(value: &str, max_age_seconds: i64, secure: bool) -> HeaderValue {
+    let mut cookie = format!(
+        "{SESSION_COOKIE}={value}; Path=/; Max-Age={max_age_seconds}; HttpOnly; SameSite=Lax"
+    );
+    if secure {
+        cookie.push_str("; Secure");
+    }
+    // Session values are base64url with a `.` separator, so the header is always valid.
+    HeaderValue::from_str(&cookie).unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_session_cookie_among_others() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_static("theme=dark; soffio_admin_session=abc.def; other=1"),
+        );
+        assert_eq!(read_session_cookie(&headers), Some("abc.def"));
+    }
+
+    #[test]
+    fn session_cookie_sets_security_attributes() {
+        let value = session_cookie("abc.def", 600, true);
+        let value = value.to_str().expect("ascii cookie");
+        assert!(value.contains("HttpOnly"));
+        assert!(value.contains("SameSite=Lax"));
+        assert!(value.ends_with("; Secure"));
+
+        let insecure = session_cookie("abc.def", 600, false);
+        assert!(!insecure.to_str().expect("ascii cookie").contains("Secure"));
+    }
+}
@@ -0,0 +1,249 @@
+//! CSRF token extraction and form injection.
+//!
+//! Every admin form posts through `<form method="post">`, either natively or
+//! via datastar's `contentType: 'form'`. Rather than threading the token
+//! through each view, authenticated HTML and SSE responses are rewritten so
+//! every such form carries a hidden `csrf_token` field. The rewrite runs
+//! chunk by chunk, so SSE streams keep flowing and nothing is buffered
+//! beyond the tag being parsed.
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_stream::stream;
+use axum::{
+    body::{Body, Bytes, to_bytes},
+    extract::FromRequest,
+    http::{
+        HeaderMap, Request, StatusCode,
+        header::{CONTENT_LENGTH, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::Multipart;
+use futures::StreamExt;
+use lol_html::{element, html_content::ContentType, send};
+use tracing::warn;
+
+use crate::application::admin::auth::{AdminSession, AdminSessionKeys};
+
+pub(super) const CSRF_FIELD: &str = "csrf_token";
+pub(super) const CSRF_HEADER: &str = "x-csrf-token";
+
+/// Check the CSRF token on a state-changing request and hand the request back intact.
+///
+/// The token is read from the `x-csrf-token` header or, failing that, from
+/// the `csrf_token` field of a urlencoded or multipart body. The body is
+/// buffered up to `body_limit` bytes and restored for the downstream handler.
+pub(super) async fn verify_request(
+    keys: &AdminSessionKeys,
+    session: &AdminSession,
+    request: Request<Body>,
+    body_limit: usize,
+) -> Result<Request<Body>, Response> {
+    if let Some(token) = request
+        .headers()
+        .get(CSRF_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        return if keys.verify_csrf(session, token) {
+            Ok(request)
+        } else {
+            Err(forbidden())
+        };
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = to_bytes(body, body_limit)
+        .await
+        .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE.into_response())?;
+
+    let token = token_from_body(&parts.headers, &bytes).await;
+    match token {
+        Some(token) if keys.verify_csrf(session, &token) => {
+            Ok(Request::from_parts(parts, Body::from(bytes)))
+        }
+        _ => Err(forbidden()),
+    }
+}
+
+async fn token_from_body(headers: &HeaderMap, bytes: &Bytes) -> Option<String> {
+    let content_type = headers.get(CONTENT_TYPE)?.to_str().ok()?;
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    match mime.as_str() {
+        "application/x-www-form-urlencoded" => url::form_urlencoded::parse(bytes)
+            .find(|(name, _)| name == CSRF_FIELD)
+            .map(|(_, value)| value.into_owned()),
+        "multipart/form-data" => token_from_multipart(content_type, bytes.clone()).await,
+        _ => None,
+    }
+}
+
+async fn token_from_multipart(content_type: &str, bytes: Bytes) -> Option<String> {
+    let request = Request::builder()
+        .header(CONTENT_TYPE, content_type)
+        .body(Body::from(bytes))
+        .ok()?;
+    let mut multipart = Multipart::from_request(request, &()).await.ok()?;
+
+    while let Ok(Some(field)) = multipart.next_field().await {
+        if field.name() == Some(CSRF_FIELD) {
+            return field.text().await.ok();
+        }
+    }
+    None
+}
+
+fn forbidden() -> Response {
+    (StatusCode::FORBIDDEN, "Invalid or missing CSRF token").into_response()
+}
+
+/// Add a hidden CSRF field to every POST form in HTML and datastar SSE responses.
+pub(super) fn inject_form_tokens(response: Response, token: &str) -> Response {
+    let rewritable = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value.starts_with("text/html") || value.starts_with("text/event-stream")
+        });
+    if !rewritable {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, add_token_fields(body, token))
+}
+
+fn add_token_fields(body: Body, token: &str) -> Body {
+    let field = format!(r#"<input type="hidden" name="{CSRF_FIELD}" value="{token}">"#);
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&output);
+    let mut rewriter = send::HtmlRewriter::new(
+        send::Settings {
+            element_content_handlers: vec![element!("form[method=post]", move |el| {
+                el.prepend(&field, ContentType::Html);
+                Ok(())
+            })],
+            ..send::Settings::new_send()
+        },
+        move |chunk: &[u8]| {
+            sink.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .extend_from_slice(chunk);
+        },
+    );
+    let take_output = move || {
+        let mut output = output.lock().unwrap_or_else(PoisonError::into_inner);
+        Bytes::from(std::mem::take(&mut *output))
+    };
+
+    let mut input = body.into_data_stream();
+    Body::from_stream(stream! {
+        while let Some(chunk) = input.next().await {
+            let written = chunk.and_then(|chunk| rewriter.write(&chunk).map_err(rewrite_failed));
+            if let Err(err) = written {
+                yield Err(err);
+                return;
+            }
+            let rewritten = take_output();
+            if !rewritten.is_empty() {
+                yield Ok(rewritten);
+            }
+        }
+        if let Err(err) = rewriter.end() {
+            yield Err(rewrite_failed(err));
+            return;
+        }
+        let rewritten = take_output();
+        if !rewritten.is_empty() {
+            yield Ok(rewritten);
+        }
+    })
+}
+
+fn rewrite_failed(err: lol_html::errors::RewritingError) -> axum::Error {
+    warn!(
+        target = "soffio::http::admin::auth",
+        error = %err,
+        "failed to inject CSRF tokens into response"
+    );
+    axum::Error::new(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use futures::channel::mpsc;
+
+    use super::*;
+
+    async fn rewrite(chunks: Vec<&'static str>) -> String {
+        let input = futures::stream::iter(chunks.into_iter().map(Ok::<_, Infallible>));
+        let body = add_token_fields(Body::from_stream(input), "tok");
+        let bytes = to_bytes(body, usize::MAX).await.expect("rewritten body");
+        String::from_utf8(bytes.to_vec()).expect("utf-8 body")
+    }
+
+    #[tokio::test]
+    async fn hidden_field_is_added_to_post_forms_only() {
+        let html = r#"<form method="post" action="/x"><button>Go</button></form><form method="get"></form>"#;
+        assert_eq!(
+            rewrite(vec![html]).await,
+            r#"<form method="post" action="/x"><input type="hidden" name="csrf_token" value="tok"><button>Go</button></form><form method="get"></form>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn hidden_field_is_added_inside_sse_patches() {
+        let sse = "event: datastar-patch-elements\ndata: selector #panel\ndata: elements <form data-role=\"inline-form\" method=\"post\"\ndata: elements   action=\"/posts/1/pin\">\ndata: elements </form>\n\n";
+        assert!(rewrite(vec![sse]).await.contains(
+            "action=\"/posts/1/pin\"><input type=\"hidden\" name=\"csrf_token\" value=\"tok\">\n"
+        ));
+    }
+
+    #[tokio::test]
+    async fn forms_split_across_chunks_are_rewritten() {
+        let rewritten = rewrite(vec![
+            "<p>a</p><form met",
+            "hod=\"post\" action=\"/x\">",
+            "</form>",
+        ])
+        .await;
+        assert_eq!(
+            rewritten,
+            r#"<p>a</p><form method="post" action="/x"><input type="hidden" name="csrf_token" value="tok"></form>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn events_are_forwarded_before_the_stream_ends() {
+        let (sender, receiver) = mpsc::unbounded::<Result<&'static str, Infallible>>();
+        let body = add_token_fields(Body::from_stream(receiver), "tok");
+        let mut output = body.into_data_stream();
+
+        sender
+            .unbounded_send(Ok(
+                "event: datastar-patch-elements\ndata: elements <form method=\"post\"></form>\n\n",
+            ))
+            .expect("send first event");
+        let first = output
+            .next()
+            .await
+            .expect("first chunk")
+            .expect("chunk body");
+        assert!(String::from_utf8_lossy(&first).contains("csrf_token"));
+
+        drop(sender);
+        to_bytes(Body::from_stream(output), usize::MAX)
+            .await
+            .expect("stream ends once the input does");
+    }
+}
@@ -0,0 +1,151 @@
+//! HTTP handlers for admin sign-in and sign-out.
+
+use axum::{
+    Extension,
+    extract::{Form, State},
+    http::{
+        HeaderValue, StatusCode,
+        header::{RETRY_AFTER, SET_COOKIE},
+    },
+    response::{IntoResponse, Redirect, Response},
+};
+use serde::Deserialize;
+use time::OffsetDateTime;
+use tracing::{error, info, warn};
+
+use crate::{
+    application::admin::auth::{AdminAuthError, AdminSession},
+    infra::http::{admin::AdminState, client_ip::ClientIp},
+    presentation::{admin::views as admin_views, views::render_template_response},
+};
+
+use super::{
+    cookie::{cleared_session_cookie, session_cookie},
+    middleware::LOGIN_PATH,
+};
+
+const LOGIN_ROUTE: &str = "admin.login";
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminLoginForm {
+    pub(crate) username: String,
+    pub(crate) password: String,
+}
+
+/// GET /login - Render the sign-in form.
+pub(crate) async fn admin_login(State(state): State<AdminState>) -> Response {
+    render_login(&state, String::new(), None, StatusCode::OK).await
+}
+
+/// POST /login - Check credentials and issue a session cookie.
+pub(crate) async fn admin_login_submit(
+    State(state): State<AdminState>,
+    client_ip: Option<Extension<ClientIp>>,
+    Form(form): Form<AdminLoginForm>,
+) -> Response {
+    let client = client_ip.map_or_else(
+        || "unknown".to_string(),
+        |Extension(ClientIp(ip))| ip.to_string(),
+    );
+    let (allowed, _) = state.login_rate_limiter.allow(&client, LOGIN_ROUTE);
+    if !allowed {
+        warn!(
+            target = "soffio::http::admin::auth",
+            client = %client,
+            "admin sign-in attempts throttled"
+        );
+        let retry_after = state.login_rate_limiter.retry_after_secs();
+        let mut response = render_login(
+            &state,
+            form.username,
+            Some("Too many sign-in attempts. Try again later."),
+            StatusCode::TOO_MANY_REQUESTS,
+        )
+        .await;
+        if let Ok(value) = HeaderValue::from_str(&retry_after.to_string()) {
+            response.headers_mut().insert(RETRY_AFTER, value);
+        }
+        return response;
+    }
+
+    let user = match state
+        .auth
+        .authenticate(&form.username, &form.password)
+        .await
+    {
+        Ok(user) => user,
+        Err(AdminAuthError::InvalidCredentials) => {
+            return render_login(
+                &state,
+                form.username,
+                Some("Invalid username or password."),
+                StatusCode::UNAUTHORIZED,
+            )
+            .await;
+        }
+        Err(err) => {
+            error!(
+                target = "soffio::http::admin::auth",
+                error = %err,
+                "failed to authenticate admin user"
+            );
+            return render_login(
+                &state,
+                form.username,
+                Some("Sign-in is temporarily unavailable."),
+                StatusCode::SERVICE_UNAVAILABLE,
+            )
+            .await;
+        }
+    };
+
+    let keys = state.auth.keys();
+    let (session, value) = keys.issue(&user.username, OffsetDateTime::now_utc());
+    info!(
+        target = "soffio::http::admin::auth",
+        username = %session.username,
+        "admin signed in"
+    );
+
+    let cookie = session_cookie(
+        &value,
+        keys.ttl().whole_seconds(),
+        state.session_cookie_secure,
+    );
+    ([(SET_COOKIE, cookie)], Redirect::to("/")).into_response()
+}
+
+/// POST /logout - Drop the session cookie.
+pub(crate) async fn admin_logout(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+) -> Response {
+    info!(
+        target = "soffio::http::admin::auth",
+        username = %session.username,
+        "admin signed out"
+    );
+    let cookie = cleared_session_cookie(state.session_cookie_secure);
+    ([(SET_COOKIE, cookie)], Redirect::to(LOGIN_PATH)).into_response()
+}
+
+async fn render_login(
+    state: &AdminState,
+    username: String,
+    error: Option<&str>,
+    status: StatusCode,
+) -> Response {
+    let chrome = match state.chrome.load(LOGIN_PATH).await {
+        Ok(chrome) => chrome,
+        Err(err) => return err.into_response(),
+    };
+
+    let content = admin_views::AdminLoginView {
+        heading: chrome.brand.title.clone(),
+        form_action: LOGIN_PATH.to_string(),
+        username,
+        error: error.map(str::to_string),
+    };
+    let view = admin_views::AdminLayout::new(chrome, content);
+    render_template_response(admin_views::AdminLoginTemplate { view }, status)
+}
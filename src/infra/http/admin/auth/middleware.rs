@@ -0,0 +1,76 @@
+//! Session gate applied to the whole admin router.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Method, Request},
+    middleware::Next,
+    response::{IntoResponse, Redirect, Response},
+};
+use time::OffsetDateTime;
+
+use crate::{
+    application::{
+        actor::{self, Actor},
+        stream::StreamBuilder,
+    },
+    infra::http::admin::AdminState,
+};
+
+use super::{cookie::read_session_cookie, csrf};
+
+pub(super) const LOGIN_PATH: &str = "/login";
+
+/// Paths reachable without a session: the login form, health probe and static assets.
+fn is_public_path(path: &str) -> bool {
+    matches!(path, LOGIN_PATH | "/_health/db" | "/favicon.ico") || path.starts_with("/static/")
+}
+
+fn is_safe_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// Require a valid session cookie, enforce CSRF on writes, and expose the
+/// [`AdminSession`](crate::application::admin::auth::AdminSession) to handlers.
+pub(crate) async fn require_session(
+    State(state): State<AdminState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if is_public_path(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let keys = state.auth.keys();
+    let session = read_session_cookie(request.headers())
+        .and_then(|value| keys.verify(value, OffsetDateTime::now_utc()));
+    let Some(session) = session else {
+        return redirect_to_login(&request);
+    };
+
+    let mut request = if is_safe_method(request.method()) {
+        request
+    } else {
+        let body_limit = usize::try_from(state.upload_limit_bytes()).unwrap_or(usize::MAX);
+        match csrf::verify_request(keys, &session, request, body_limit).await {
+            Ok(request) => request,
+            Err(response) => return response,
+        }
+    };
+
+    let token = keys.csrf_token(&session);
+    let actor = Actor::admin(&session.username);
+    request.extensions_mut().insert(session);
+    let response = actor::scope(actor, next.run(request)).await;
+    csrf::inject_form_tokens(response, &token)
+}
+
+fn redirect_to_login(request: &Request<Body>) -> Response {
+    // Datastar requests consume SSE, so a plain redirect would be patched into the page.
+    if request.headers().contains_key("datastar-request") {
+        let mut stream = StreamBuilder::new();
+        stream.push_script(format!("window.location.assign('{LOGIN_PATH}');"));
+        return stream.into_response();
+    }
+    Redirect::to(LOGIN_PATH).into_response()
+}
@@ -0,0 +1,17 @@
+//! Admin authentication module.
+//!
+//! This module gates the admin listener behind signed session cookies and
+//! enforces CSRF tokens on every state-changing request.
+//!
+//! - `cookie`: Session cookie encoding
+//! - `csrf`: Token extraction and form injection
+//! - `handlers`: Login and logout endpoints
+//! - `middleware`: Session gate applied to the whole admin router
+
+mod cookie;
+mod csrf;
+mod handlers;
+mod middleware;
+
+pub(super) use handlers::{admin_login, admin_login_submit, admin_logout};
+pub(super) use middleware::require_session;
@@ -0,0 +1,215 @@
+//! Pieces shared by the posts and pages bulk actions.
+
+use std::future::Future;
+
+use futures::future::join_all;
+use uuid::Uuid;
+
+use super::shared::Toast;
+
+/// Most items a "select all matching" bulk action may touch.
+pub(super) const SELECT_ALL_CAP: u64 = 500;
+
+/// Items acted on concurrently; the next batch starts once this one is done.
+pub(super) const BATCH_SIZE: usize = 25;
+
+/// Value the `confirm` field must hold before deleting everything that matches.
+pub(super) const DELETE_CONFIRMATION: &str = "delete";
+
+/// Per-item failure toasts shown before the rest are summarised.
+const FAILURE_TOAST_LIMIT: usize = 5;
+
+/// Actions the posts and pages bulk forms can apply.
+#[derive(Clone, Copy)]
+pub(super) enum BulkAction {
+    Publish,
+    Draft,
+    Archive,
+    Delete,
+}
+
+impl BulkAction {
+    pub(super) fn from_str(action: &str) -> Option<Self> {
+        match action {
+            "publish" => Some(Self::Publish),
+            "draft" => Some(Self::Draft),
+            "archive" => Some(Self::Archive),
+            "delete" => Some(Self::Delete),
+            _ => None,
+        }
+    }
+
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            BulkAction::Publish => "Publish",
+            BulkAction::Draft => "Move to Draft",
+            BulkAction::Archive => "Archive",
+            BulkAction::Delete => "Delete",
+        }
+    }
+}
+
+/// Whether the form asked to act on every row matching the current filters.
+pub(super) fn select_all_requested(raw: Option<&str>) -> bool {
+    matches!(
+        raw.map(str::trim),
+        Some("1") | Some("true") | Some("on") | Some("yes")
+    )
+}
+
+/// Reject a "select all matching" request that covers more than `cap` items.
+pub(super) fn check_select_all_cap(matched: u64, cap: u64, noun: &str) -> Result<(), Toast> {
+    if matched > cap {
+        return Err(Toast::error(format!(
+            "{matched} {noun}s match these filters; bulk actions apply to at most {cap}. \
+             Narrow the filters and try again."
+        )));
+    }
+    Ok(())
+}
+
+/// Deleting every match needs `confirm` typed out as [`DELETE_CONFIRMATION`].
+pub(super) fn check_delete_confirmation(confirm: Option<&str>, noun: &str) -> Result<(), Toast> {
+    let typed = confirm.map(str::trim).unwrap_or_default();
+    if typed.eq_ignore_ascii_case(DELETE_CONFIRMATION) {
+        Ok(())
+    } else {
+        Err(Toast::error(format!(
+            "Type \"{DELETE_CONFIRMATION}\" to confirm deleting every matching {noun}"
+        )))
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct BulkOutcome {
+    pub succeeded: usize,
+    pub failures: Vec<String>,
+}
+
+/// Run `apply` for every id, `batch_size` at a time, collecting failures in
+/// id order instead of stopping at the first one.
+pub(super) async fn apply_in_batches<F, Fut>(
+    ids: &[Uuid],
+    batch_size: usize,
+    apply: F,
+) -> BulkOutcome
+where
+    F: Fn(Uuid) -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    let mut outcome = BulkOutcome::default();
+    for batch in ids.chunks(batch_size.max(1)) {
+        for result in join_all(batch.iter().copied().map(&apply)).await {
+            match result {
+                Ok(()) => outcome.succeeded += 1,
+                Err(failure) => outcome.failures.push(failure),
+            }
+        }
+    }
+    outcome
+}
+
+/// A summary toast followed by one toast per failure, up to a handful.
+pub(super) fn outcome_toasts(label: &str, noun: &str, outcome: &BulkOutcome) -> Vec<Toast> {
+    let plural = |count: usize| if count == 1 { "" } else { "s" };
+
+    if outcome.failures.is_empty() {
+        return vec![Toast::success(format!(
+            "{label} applied to {} {noun}{}",
+            outcome.succeeded,
+            plural(outcome.succeeded)
+        ))];
+    }
+
+    let mut toasts = vec![Toast::error(format!(
+        "{label}: {} {noun}{} succeeded, {} failed",
+        outcome.succeeded,
+        plural(outcome.succeeded),
+        outcome.failures.len()
+    ))];
+    toasts.extend(
+        outcome
+            .failures
+            .iter()
+            .take(FAILURE_TOAST_LIMIT)
+            .map(|failure| Toast::error(failure.clone())),
+    );
+    let hidden = outcome.failures.len().saturating_sub(FAILURE_TOAST_LIMIT);
+    if hidden > 0 {
+        toasts.push(Toast::error(format!(
+            "…and {hidden} more failure{}",
+            plural(hidden)
+        )));
+    }
+    toasts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::shared::ToastKind;
+    use super::*;
+
+    #[test]
+    fn select_all_cap_rejects_only_above_the_limit() {
+        assert!(check_select_all_cap(500, 500, "post").is_ok());
+        let toast = check_select_all_cap(501, 500, "post").expect_err("over the cap");
+        assert!(toast.text.starts_with("501 posts match"));
+        assert!(toast.text.contains("at most 500"));
+    }
+
+    #[test]
+    fn delete_needs_the_typed_confirmation() {
+        assert!(check_delete_confirmation(Some(" Delete "), "page").is_ok());
+        assert!(check_delete_confirmation(Some("yes"), "page").is_err());
+        assert!(check_delete_confirmation(None, "page").is_err());
+    }
+
+    #[test]
+    fn select_all_flag_accepts_checkbox_values() {
+        assert!(select_all_requested(Some("on")));
+        assert!(select_all_requested(Some("true")));
+        assert!(!select_all_requested(Some("")));
+        assert!(!select_all_requested(None));
+    }
+
+    #[tokio::test]
+    async fn partial_failures_are_collected_per_item() {
+        let ids: Vec<Uuid> = (0..12).map(|_| Uuid::new_v4()).collect();
+        let failing = [ids[1], ids[4], ids[5], ids[7], ids[9], ids[10], ids[11]];
+
+        let outcome = apply_in_batches(&ids, 5, |id| async move {
+            if failing.contains(&id) {
+                Err(format!("`{id}` failed"))
+            } else {
+                Ok(())
+            }
+        })
+        .await;
+
+        assert_eq!(outcome.succeeded, 5);
+        let expected: Vec<String> = failing.iter().map(|id| format!("`{id}` failed")).collect();
+        assert_eq!(outcome.failures, expected);
+
+        let toasts = outcome_toasts("Archive", "post", &outcome);
+        assert_eq!(toasts[0].text, "Archive: 5 posts succeeded, 7 failed");
+        assert_eq!(toasts.len(), 1 + FAILURE_TOAST_LIMIT + 1);
+        assert_eq!(toasts[1].text, expected[0]);
+        assert_eq!(toasts.last().unwrap().text, "…and 2 more failures");
+        assert!(
+            toasts
+                .iter()
+                .all(|toast| matches!(toast.kind, ToastKind::Error))
+        );
+    }
+
+    #[tokio::test]
+    async fn clean_runs_report_a_single_success() {
+        let ids = [Uuid::new_v4()];
+        let outcome = apply_in_batches(&ids, BATCH_SIZE, |_| async { Ok(()) }).await;
+
+        let toasts = outcome_toasts("Publish", "page", &outcome);
+        assert_eq!(toasts.len(), 1);
+        assert_eq!(toasts[0].text, "Publish applied to 1 page");
+        assert!(matches!(toasts[0].kind, ToastKind::Success));
+    }
+}
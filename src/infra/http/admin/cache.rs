@@ -0,0 +1,66 @@
+//! Manual cache purge for a single public URL.
+
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::application::admin::auth::AdminSession;
+
+use super::AdminState;
+
+#[derive(Debug, Deserialize)]
+pub(super) struct PurgeRequest {
+    path: String,
+}
+
+#[derive(Debug, Serialize)]
+struct PurgeResponse<'a> {
+    path: &'a str,
+    existed: bool,
+}
+
+pub(super) async fn admin_cache_purge(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Json(request): Json<PurgeRequest>,
+) -> Response {
+    let path = request.path.trim();
+    if !path.starts_with('/') || path.starts_with("//") {
+        return (
+            StatusCode::BAD_REQUEST,
+            "path must be a site-relative URL such as /posts/hello",
+        )
+            .into_response();
+    }
+
+    let existed = state
+        .cache
+        .as_ref()
+        .is_some_and(|trigger| trigger.consumer().purge_url(path));
+    let response = PurgeResponse { path, existed };
+
+    if let Err(err) = state
+        .audit
+        .record(
+            session.username.as_str(),
+            "cache.purge",
+            "cache",
+            Some(path),
+            Some(&response),
+        )
+        .await
+    {
+        error!(
+            target = "soffio::http::admin::cache",
+            error = %err,
+            "failed to record cache purge audit entry"
+        );
+    }
+
+    Json(response).into_response()
+}
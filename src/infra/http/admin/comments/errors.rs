@@ -0,0 +1,31 @@
+//! Error conversion utilities for comments admin handlers.
+
+use axum::http::StatusCode;
+
+use crate::application::{admin::comments::AdminCommentError, error::HttpError};
+use crate::infra::http::repo_error_to_http;
+
+/// Convert AdminCommentError to HttpError for HTTP responses.
+pub(super) fn admin_comment_error(source: &'static str, err: AdminCommentError) -> HttpError {
+    match err {
+        AdminCommentError::NotFound => HttpError::new(
+            source,
+            StatusCode::NOT_FOUND,
+            "Comment not found",
+            "The requested comment does not exist".to_string(),
+        ),
+        AdminCommentError::PostNotFound => HttpError::new(
+            source,
+            StatusCode::NOT_FOUND,
+            "Post not found",
+            "The comment's post does not exist".to_string(),
+        ),
+        AdminCommentError::ConstraintViolation(field) => HttpError::new(
+            source,
+            StatusCode::BAD_REQUEST,
+            "Invalid comment",
+            format!("Field `{field}` is invalid"),
+        ),
+        AdminCommentError::Repo(repo) => repo_error_to_http(source, repo),
+    }
+}
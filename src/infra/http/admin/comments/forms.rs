@@ -0,0 +1,26 @@
+//! Form structs for comments admin handlers.
+
+use serde::Deserialize;
+
+use crate::infra::http::admin::shared::ListLimit;
+
+/// Form for moderation action requests with filter context.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminCommentActionForm {
+    pub(crate) status_filter: Option<String>,
+    pub(crate) filter_search: Option<String>,
+    pub(crate) cursor: Option<String>,
+    pub(crate) trail: Option<String>,
+}
+
+/// Form for panel refresh requests.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminCommentsPanelForm {
+    pub(crate) status: Option<String>,
+    pub(crate) search: Option<String>,
+    pub(crate) cursor: Option<String>,
+    pub(crate) trail: Option<String>,
+    pub(crate) clear: Option<String>,
+    #[serde(flatten)]
+    pub(crate) limit: ListLimit,
+}
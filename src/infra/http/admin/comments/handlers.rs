@@ -0,0 +1,217 @@
+//! HTTP handlers for comments admin - moderation queue and actions.
+
+use askama::Template;
+use axum::{
+    Extension,
+    extract::{Form, Path, State},
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::{
+    application::admin::auth::AdminSession,
+    application::{pagination::CommentCursor, repos::CommentQueryFilter},
+    infra::http::admin::{
+        AdminState,
+        pagination::CursorState,
+        selectors::COMMENTS_PANEL,
+        shared::{Toast, datastar_replace, push_toasts},
+    },
+    presentation::admin::views as admin_views,
+};
+
+use super::{
+    errors::admin_comment_error,
+    forms::{AdminCommentActionForm, AdminCommentsPanelForm},
+    panel::{apply_pagination_links, build_comment_list_view, render_comment_panel_html},
+    status::parse_comment_status,
+};
+
+/// GET /comments - Render the moderation queue.
+pub(crate) async fn admin_comments(State(state): State<AdminState>) -> Response {
+    let filter = CommentQueryFilter::default();
+
+    let mut content = match build_comment_list_view(&state, None, &filter, None, None).await {
+        Ok(content) => content,
+        Err(err) => {
+            return admin_comment_error("infra::http::admin::comments::admin_comments", err)
+                .into_response();
+        }
+    };
+
+    let cursor_state = CursorState::default();
+    apply_pagination_links(&mut content, &cursor_state);
+
+    let chrome = match state.chrome.load("/comments").await {
+        Ok(chrome) => chrome,
+        Err(err) => return err.into_response(),
+    };
+    let view = admin_views::AdminLayout::new(chrome, content);
+    let template = admin_views::AdminCommentsTemplate { view };
+
+    match template.render() {
+        Ok(html) => axum::response::Html(html).into_response(),
+        Err(err) => {
+            tracing::error!(error = %err, "Failed to render comments template");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// POST /comments/panel - AJAX panel refresh via datastar SSE.
+pub(crate) async fn admin_comments_panel(
+    State(state): State<AdminState>,
+    Form(form): Form<AdminCommentsPanelForm>,
+) -> Response {
+    let status = match parse_comment_status(form.status.as_deref()) {
+        Ok(status) => status,
+        Err(err) => return err.into_response(),
+    };
+
+    let search = if form.clear.is_some() {
+        None
+    } else {
+        form.search.clone().filter(|s| !s.is_empty())
+    };
+
+    let filter = CommentQueryFilter {
+        status,
+        post_id: None,
+        search,
+    };
+
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        CommentCursor::decode,
+        "infra::http::admin::comments::admin_comments_panel",
+    ) {
+        Ok(cursor) => cursor,
+        Err(err) => return err.into_response(),
+    };
+
+    let mut content = match build_comment_list_view(
+        &state,
+        status,
+        &filter,
+        cursor,
+        cursor_state.limit(),
+    )
+    .await
+    {
+        Ok(content) => content,
+        Err(err) => {
+            return admin_comment_error("infra::http::admin::comments::admin_comments_panel", err)
+                .into_response();
+        }
+    };
+
+    apply_pagination_links(&mut content, &cursor_state);
+
+    match render_comment_panel_html(
+        &content,
+        "infra::http::admin::comments::admin_comments_panel",
+    ) {
+        Ok(html) => datastar_replace(COMMENTS_PANEL, html).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+/// POST /comments/{id}/approve - Publish a comment on its post page.
+pub(crate) async fn admin_comment_approve(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Path(id): Path<Uuid>,
+    Form(form): Form<AdminCommentActionForm>,
+) -> Response {
+    match state.comments.approve(&session.username, id).await {
+        Ok(comment) => {
+            respond_with_panel_and_toast(
+                &state,
+                &form,
+                Toast::success(format!("Comment by {} approved", comment.author)),
+            )
+            .await
+        }
+        Err(err) => admin_comment_error("infra::http::admin::comments::admin_comment_approve", err)
+            .into_response(),
+    }
+}
+
+/// POST /comments/{id}/spam - Reject a comment as spam.
+pub(crate) async fn admin_comment_spam(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Path(id): Path<Uuid>,
+    Form(form): Form<AdminCommentActionForm>,
+) -> Response {
+    match state.comments.mark_spam(&session.username, id).await {
+        Ok(comment) => {
+            respond_with_panel_and_toast(
+                &state,
+                &form,
+                Toast::success(format!("Comment by {} marked as spam", comment.author)),
+            )
+            .await
+        }
+        Err(err) => admin_comment_error("infra::http::admin::comments::admin_comment_spam", err)
+            .into_response(),
+    }
+}
+
+/// Helper to respond with refreshed panel and toast message.
+async fn respond_with_panel_and_toast(
+    state: &AdminState,
+    form: &AdminCommentActionForm,
+    toast: Toast,
+) -> Response {
+    let status = match parse_comment_status(form.status_filter.as_deref()) {
+        Ok(status) => status,
+        Err(err) => return err.into_response(),
+    };
+
+    let filter = CommentQueryFilter {
+        status,
+        post_id: None,
+        search: form.filter_search.clone().filter(|s| !s.is_empty()),
+    };
+
+    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        CommentCursor::decode,
+        "infra::http::admin::comments::respond_with_panel_and_toast",
+    ) {
+        Ok(cursor) => cursor,
+        Err(err) => return err.into_response(),
+    };
+
+    let mut content =
+        match build_comment_list_view(state, status, &filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_comment_error(
+                    "infra::http::admin::comments::respond_with_panel_and_toast",
+                    err,
+                )
+                .into_response();
+            }
+        };
+
+    apply_pagination_links(&mut content, &cursor_state);
+
+    match render_comment_panel_html(
+        &content,
+        "infra::http::admin::comments::respond_with_panel_and_toast",
+    ) {
+        Ok(html) => {
+            let mut stream = datastar_replace(COMMENTS_PANEL, html);
+            if let Err(err) = push_toasts(&mut stream, &[toast]) {
+                return err.into_response();
+            }
+            stream.into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
@@ -0,0 +1,13 @@
+//! Comments admin module.
+//!
+//! This module handles the moderation queue for externally ingested comments.
+
+mod errors;
+mod forms;
+mod handlers;
+mod panel;
+mod status;
+
+pub(super) use handlers::{
+    admin_comment_approve, admin_comment_spam, admin_comments, admin_comments_panel,
+};
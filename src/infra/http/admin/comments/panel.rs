@@ -0,0 +1,155 @@
+//! Panel building for the comment moderation queue.
+
+use std::collections::{HashMap, hash_map::Entry};
+
+use askama::Template;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        admin::comments::AdminCommentError,
+        error::HttpError,
+        pagination::{CommentCursor, PageRequest},
+        repos::{CommentQueryFilter, PostsRepo, SettingsRepo},
+    },
+    domain::{entities::PostRecord, types::CommentStatus},
+    infra::http::admin::{
+        AdminState,
+        pagination::{self, CursorState},
+        shared::template_render_http_error,
+    },
+    presentation::admin::views as admin_views,
+};
+
+use super::status::{comment_actions_for_status, status_filters};
+
+/// Build the complete comment list view for rendering.
+pub(super) async fn build_comment_list_view(
+    state: &AdminState,
+    status: Option<CommentStatus>,
+    filter: &CommentQueryFilter,
+    cursor: Option<CommentCursor>,
+    limit: Option<u32>,
+) -> Result<admin_views::AdminCommentListView, AdminCommentError> {
+    let settings = state.db.load_site_settings().await?;
+    let admin_page_size = pagination::page_size(settings.admin_page_size, limit);
+
+    let page_request = PageRequest::new(admin_page_size, cursor);
+
+    let base_filter = CommentQueryFilter {
+        status: None,
+        ..filter.clone()
+    };
+    let list_filter = CommentQueryFilter {
+        status,
+        ..filter.clone()
+    };
+
+    let (page, status_counts) = tokio::try_join!(
+        state.comments.list(&list_filter, page_request),
+        state.comments.status_counts(&base_filter),
+    )?;
+
+    let mut posts: HashMap<Uuid, Option<PostRecord>> = HashMap::new();
+    for comment in &page.items {
+        if let Entry::Vacant(entry) = posts.entry(comment.post_id) {
+            entry.insert(PostsRepo::find_by_id(state.db.as_ref(), comment.post_id).await?);
+        }
+    }
+
+    let comments = page
+        .items
+        .into_iter()
+        .map(|comment| {
+            let post = posts.get(&comment.post_id).and_then(Option::as_ref);
+            admin_views::AdminCommentRowView {
+                id: comment.id.to_string(),
+                author: comment.author,
+                body: comment.body,
+                post_title: post
+                    .map(|post| post.title.clone())
+                    .unwrap_or_else(|| comment.post_id.to_string()),
+                post_href: post.map(|post| format!("/posts/{}/edit", post.id)),
+                status_key: comment.status.as_str().to_string(),
+                status_label: comment.status.display_name().to_string(),
+                created_at: admin_views::format_timestamp(comment.created_at, settings.timezone),
+                source_ip: comment.source_ip,
+                actions: comment_actions_for_status(comment.status),
+            }
+        })
+        .collect();
+
+    Ok(admin_views::AdminCommentListView {
+        heading: "Comments".to_string(),
+        filters: status_filters(&status_counts, status),
+        comments,
+        filter_search: filter.search.clone(),
+        filter_tag: None,
+        filter_month: None,
+        active_status_key: status.map(|s| s.as_str().to_string()),
+        tag_filter_enabled: false,
+        month_filter_enabled: false,
+        tag_filter_label: String::new(),
+        tag_filter_all_label: String::new(),
+        tag_filter_field: String::new(),
+        tag_options: Vec::new(),
+        month_options: Vec::new(),
+        next_cursor: page.next_cursor,
+        cursor_param: None,
+        trail: None,
+        previous_page_state: None,
+        next_page_state: None,
+        panel_action: "/comments/panel".to_string(),
+        row_action_prefix: "/comments".to_string(),
+        custom_hidden_fields: pagination::limit_hidden_field(limit).into_iter().collect(),
+    })
+}
+
+/// Render comment panel HTML from view.
+pub(super) fn render_comment_panel_html(
+    content: &admin_views::AdminCommentListView,
+    template_source: &'static str,
+) -> Result<String, HttpError> {
+    let template = admin_views::AdminCommentsPanelTemplate {
+        content: content.clone(),
+    };
+
+    template.render().map_err(|err| {
+        template_render_http_error(template_source, "Template rendering failed", err)
+    })
+}
+
+/// Apply pagination links to comment list view.
+pub(super) fn apply_pagination_links(
+    content: &mut admin_views::AdminCommentListView,
+    cursor_state: &CursorState,
+) {
+    content.cursor_param = cursor_state.current_token();
+    content.trail = pagination::join_cursor_history(cursor_state.history_tokens());
+
+    let mut previous_history = cursor_state.clone_history();
+    let previous_token = previous_history.pop();
+
+    content.previous_page_state = previous_token.map(|token| {
+        let previous_cursor_value = pagination::decode_cursor_token(&token);
+        let previous_trail = pagination::join_cursor_history(&previous_history);
+        admin_views::AdminPostPaginationState {
+            cursor: previous_cursor_value,
+            trail: previous_trail,
+        }
+    });
+
+    if let Some(next_cursor) = content.next_cursor.clone() {
+        let mut next_history = cursor_state.clone_history();
+        next_history.push(pagination::encode_cursor_token(
+            cursor_state.current_token_ref(),
+        ));
+        let next_trail = pagination::join_cursor_history(&next_history);
+        content.next_page_state = Some(admin_views::AdminPostPaginationState {
+            cursor: Some(next_cursor),
+            trail: next_trail,
+        });
+    } else {
+        content.next_page_state = None;
+    }
+}
@@ -0,0 +1,105 @@
+//! Comment status parsing utilities.
+
+use axum::http::StatusCode;
+
+use crate::{
+    application::{admin::comments::AdminCommentStatusCounts, error::HttpError},
+    domain::types::CommentStatus,
+    presentation::admin::views as admin_views,
+};
+
+/// Parse comment status from optional string parameter.
+pub(super) fn parse_comment_status(
+    value: Option<&str>,
+) -> Result<Option<CommentStatus>, HttpError> {
+    let Some(raw) = value else {
+        return Ok(None);
+    };
+
+    if raw.is_empty() {
+        return Ok(None);
+    }
+
+    raw.parse::<CommentStatus>().map(Some).map_err(|_| {
+        HttpError::new(
+            "infra::http::admin::comments::parse_comment_status",
+            StatusCode::BAD_REQUEST,
+            "Unknown comment status filter",
+            format!("Status `{raw}` is not recognised"),
+        )
+    })
+}
+
+/// Build status filter tabs from counts.
+pub(super) fn status_filters(
+    counts: &AdminCommentStatusCounts,
+    active: Option<CommentStatus>,
+) -> Vec<admin_views::AdminCommentStatusFilterView> {
+    let mut filters = vec![admin_views::AdminCommentStatusFilterView {
+        status_key: None,
+        label: "All".to_string(),
+        count: counts.total,
+        is_active: active.is_none(),
+    }];
+
+    for (status, count) in [
+        (CommentStatus::Pending, counts.pending),
+        (CommentStatus::Approved, counts.approved),
+        (CommentStatus::Spam, counts.spam),
+    ] {
+        filters.push(admin_views::AdminCommentStatusFilterView {
+            status_key: Some(status.as_str().to_string()),
+            label: status.display_name().to_string(),
+            count,
+            is_active: active == Some(status),
+        });
+    }
+
+    filters
+}
+
+/// Determine available moderation actions for a comment status.
+pub(super) fn comment_actions_for_status(
+    status: CommentStatus,
+) -> Vec<admin_views::AdminCommentRowActionView> {
+    let approve = admin_views::AdminCommentRowActionView {
+        value: "approve",
+        label: "Approve",
+        is_danger: false,
+    };
+    let spam = admin_views::AdminCommentRowActionView {
+        value: "spam",
+        label: "Reject",
+        is_danger: true,
+    };
+
+    match status {
+        CommentStatus::Pending => vec![approve, spam],
+        CommentStatus::Approved => vec![spam],
+        CommentStatus::Spam => vec![approve],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_comments_offer_both_moderation_actions() {
+        let values: Vec<&str> = comment_actions_for_status(CommentStatus::Pending)
+            .iter()
+            .map(|action| action.value)
+            .collect();
+        assert_eq!(values, vec!["approve", "spam"]);
+    }
+
+    #[test]
+    fn parse_comment_status_rejects_unknown_values() {
+        assert_eq!(
+            parse_comment_status(Some("approved")).expect("valid"),
+            Some(CommentStatus::Approved)
+        );
+        assert_eq!(parse_comment_status(Some("")).expect("empty"), None);
+        assert!(parse_comment_status(Some("deleted")).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+//! Runtime configuration reload, the HTTP counterpart of `SIGHUP`.
+
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::application::admin::auth::AdminSession;
+
+use super::AdminState;
+
+/// Reload the configuration and report which changes were applied and which
+/// require a restart. Invalid configuration is rejected and nothing changes.
+pub(super) async fn admin_config_reload(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+) -> Response {
+    match state.config_reload.reload(session.username.as_str()).await {
+        Ok(diff) => Json(diff).into_response(),
+        Err(err) => (StatusCode::UNPROCESSABLE_ENTITY, err.to_string()).into_response(),
+    }
+}
@@ -4,17 +4,34 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
+use crate::application::error::HttpError;
+use crate::application::repos::SettingsRepo;
 use crate::presentation::{admin::views as admin_views, views::render_template_response};
 
 use super::AdminState;
 
+const SOURCE: &str = "infra::http::admin::dashboard";
+
 pub(super) async fn admin_dashboard(State(state): State<AdminState>) -> Response {
     let chrome = match state.chrome.load("/").await {
         Ok(chrome) => chrome,
         Err(err) => return err.into_response(),
     };
 
-    let content = match state.dashboard.overview().await {
+    let timezone = match state.db.load_site_settings().await {
+        Ok(settings) => settings.timezone,
+        Err(err) => {
+            return HttpError::new(
+                SOURCE,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to load settings",
+                err.to_string(),
+            )
+            .into_response();
+        }
+    };
+
+    let content = match state.dashboard.overview(timezone).await {
         Ok(view) => view,
         Err(err) => return err.into_response(),
     };
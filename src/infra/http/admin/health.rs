@@ -1,4 +1,11 @@
-use axum::{extract::State, response::Response};
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::application::render::{render_service, run_render_self_test};
 
 use super::super::db_health_response;
 use super::AdminState;
@@ -6,3 +13,26 @@ use super::AdminState;
 pub(super) async fn admin_health(State(state): State<AdminState>) -> Response {
     db_health_response(state.db.health_check().await)
 }
+
+/// Render a fixed document and report per-feature status; 503 when a
+/// feature failed (a disabled Mermaid CLI is reported but still 200).
+pub(super) async fn admin_render_health() -> Response {
+    let report = match tokio::task::spawn_blocking(|| run_render_self_test(&render_service())).await
+    {
+        Ok(report) => report,
+        Err(err) => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("render self-test task failed: {err}"),
+            )
+                .into_response();
+        }
+    };
+
+    let status = if report.healthy() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
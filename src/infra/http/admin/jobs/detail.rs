@@ -0,0 +1,277 @@
+//! Job detail view: fields, timeline, payload, attempts and related audit entries.
+
+use serde_json::Value;
+
+use crate::{
+    application::{
+        admin::jobs::{AdminJobError, JobDetail, JobEntity, JobEntityKind},
+        repos::SettingsRepo,
+    },
+    domain::types::JobState,
+    infra::http::admin::AdminState,
+    presentation::admin::views as admin_views,
+};
+
+use super::status::{job_type_key, job_type_label, state_key, state_label};
+
+/// Build the job detail view for a single job.
+pub(super) async fn build_job_detail_view(
+    state: &AdminState,
+    detail: JobDetail,
+) -> Result<admin_views::AdminJobDetailView, AdminJobError> {
+    let settings = state.db.load_site_settings().await?;
+    let tz = settings.timezone;
+    let JobDetail {
+        job,
+        timeline,
+        attempt_history,
+        entity,
+        request_id,
+        related_audit,
+    } = detail;
+
+    let field = |label: &str, value: String| admin_views::AdminJobDetailField {
+        label: label.to_string(),
+        value,
+        is_badge: false,
+        badge_status: None,
+        is_multiline: false,
+    };
+    let badge = |label: &str, value: &str, status: &str| admin_views::AdminJobDetailField {
+        label: label.to_string(),
+        value: value.to_string(),
+        is_badge: true,
+        badge_status: Some(status.to_string()),
+        is_multiline: false,
+    };
+
+    let mut fields = vec![
+        field("ID", job.id.clone()),
+        badge(
+            "Type",
+            job_type_label(job.job_type),
+            job_type_key(job.job_type),
+        ),
+        badge("State", state_label(job.state), state_key(job.state)),
+        field("Attempts", format!("{}/{}", job.attempts, job.max_attempts)),
+        field("Priority", job.priority.to_string()),
+    ];
+    if let Some(worker) = &job.lock_by {
+        fields.push(field("Worker", worker.clone()));
+    }
+
+    let mut steps = vec![admin_views::AdminJobTimelineStep {
+        label: "Due",
+        at: admin_views::format_timestamp(timeline.queued_at, tz),
+        elapsed: None,
+    }];
+    if let Some(locked_at) = timeline.locked_at {
+        steps.push(admin_views::AdminJobTimelineStep {
+            label: "Picked up",
+            at: admin_views::format_timestamp(locked_at, tz),
+            elapsed: timeline
+                .wait_ms
+                .map(|ms| format!("waited {}", format_elapsed(ms))),
+        });
+    }
+    if let Some(finished_at) = timeline.finished_at {
+        steps.push(admin_views::AdminJobTimelineStep {
+            label: finish_label(job.state),
+            at: admin_views::format_timestamp(finished_at, tz),
+            elapsed: timeline
+                .run_ms
+                .map(|ms| format!("ran {}", format_elapsed(ms))),
+        });
+    }
+
+    let entity = match entity {
+        Some(entity) => Some(entity_view(state, entity).await?),
+        None => None,
+    };
+
+    let attempts = attempt_history
+        .into_iter()
+        .map(|attempt| admin_views::AdminJobAttemptView {
+            attempt: attempt.attempt,
+            failed_at: admin_views::format_timestamp(attempt.failed_at, tz),
+            error: attempt.error,
+        })
+        .collect();
+
+    let related_audit = related_audit
+        .into_iter()
+        .map(|entry| admin_views::AdminJobAuditView {
+            href: format!("/audit/{}", entry.id),
+            entity: match entry.entity_id {
+                Some(id) => format!("{} {id}", entry.entity_type),
+                None => entry.entity_type,
+            },
+            action: entry.action,
+            actor: entry.actor,
+            created_at: admin_views::format_timestamp(entry.created_at, tz),
+        })
+        .collect();
+
+    Ok(admin_views::AdminJobDetailView {
+        heading: format!("Job: {}", job.id),
+        fields,
+        timeline: steps,
+        entity,
+        payload: json_tokens(&job.payload),
+        last_error: job.last_error,
+        attempts,
+        request_id,
+        related_audit,
+    })
+}
+
+fn finish_label(state: JobState) -> &'static str {
+    match state {
+        JobState::Failed => "Failed",
+        JobState::Killed => "Killed",
+        _ => "Finished",
+    }
+}
+
+/// Link the job's post or page to its editor while it still exists.
+async fn entity_view(
+    state: &AdminState,
+    entity: JobEntity,
+) -> Result<admin_views::AdminJobEntityView, AdminJobError> {
+    let (kind_label, href) = match entity.kind {
+        JobEntityKind::Post => (
+            "Post",
+            state
+                .db
+                .find_post_id_by_slug_immediate(&entity.slug)
+                .await?
+                .map(|id| format!("/posts/{id}/edit")),
+        ),
+        JobEntityKind::Page => (
+            "Page",
+            state
+                .db
+                .find_page_id_by_slug_immediate(&entity.slug)
+                .await?
+                .map(|id| format!("/pages/{id}/edit")),
+        ),
+    };
+    Ok(admin_views::AdminJobEntityView {
+        kind_label,
+        slug: entity.slug,
+        href,
+    })
+}
+
+/// `850 ms`, `2.4 s`, `3 min 5 s` or `1 h 12 min`.
+fn format_elapsed(ms: i64) -> String {
+    if ms < 1_000 {
+        format!("{ms} ms")
+    } else if ms < 60_000 {
+        format!("{:.1} s", ms as f64 / 1_000.0)
+    } else if ms < 3_600_000 {
+        format!("{} min {} s", ms / 60_000, ms % 60_000 / 1_000)
+    } else {
+        format!("{} h {} min", ms / 3_600_000, ms % 3_600_000 / 60_000)
+    }
+}
+
+/// `value` pretty-printed as `serde_json::to_string_pretty` would, split into
+/// tokens the template colours by kind.
+fn json_tokens(value: &Value) -> Vec<admin_views::AdminJsonToken> {
+    let mut tokens = Vec::new();
+    push_value(&mut tokens, value, 0);
+    tokens
+}
+
+fn push_value(tokens: &mut Vec<admin_views::AdminJsonToken>, value: &Value, depth: usize) {
+    match value {
+        Value::Null | Value::Bool(_) => push_token(tokens, "literal", value.to_string()),
+        Value::Number(_) => push_token(tokens, "number", value.to_string()),
+        Value::String(_) => push_token(tokens, "string", value.to_string()),
+        Value::Array(items) if items.is_empty() => push_token(tokens, "punct", "[]".into()),
+        Value::Object(fields) if fields.is_empty() => push_token(tokens, "punct", "{}".into()),
+        Value::Array(items) => {
+            push_token(tokens, "punct", "[".into());
+            for (index, item) in items.iter().enumerate() {
+                let separator = if index == 0 { "" } else { "," };
+                push_token(tokens, "punct", format!("{separator}{}", indent(depth + 1)));
+                push_value(tokens, item, depth + 1);
+            }
+            push_token(tokens, "punct", format!("{}]", indent(depth)));
+        }
+        Value::Object(fields) => {
+            push_token(tokens, "punct", "{".into());
+            for (index, (key, item)) in fields.iter().enumerate() {
+                let separator = if index == 0 { "" } else { "," };
+                push_token(tokens, "punct", format!("{separator}{}", indent(depth + 1)));
+                push_token(tokens, "key", Value::String(key.clone()).to_string());
+                push_token(tokens, "punct", ": ".into());
+                push_value(tokens, item, depth + 1);
+            }
+            push_token(tokens, "punct", format!("{}}}", indent(depth)));
+        }
+    }
+}
+
+/// Append a token, merging runs of punctuation into one.
+fn push_token(tokens: &mut Vec<admin_views::AdminJsonToken>, kind: &'static str, text: String) {
+    if kind == "punct"
+        && let Some(last) = tokens.last_mut()
+        && last.kind == "punct"
+    {
+        last.text.push_str(&text);
+        return;
+    }
+    tokens.push(admin_views::AdminJsonToken { kind, text });
+}
+
+fn indent(depth: usize) -> String {
+    format!("\n{}", "  ".repeat(depth))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_tokens_reproduce_pretty_printing() {
+        let payload = serde_json::json!({
+            "slug": "hello \"world\"",
+            "attempt": 3,
+            "publish_at": null,
+            "tags": ["a", true],
+            "empty": {},
+        });
+
+        let tokens = json_tokens(&payload);
+        let text: String = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(text, serde_json::to_string_pretty(&payload).unwrap());
+
+        assert!(tokens.contains(&admin_views::AdminJsonToken {
+            kind: "key",
+            text: "\"slug\"".to_string(),
+        }));
+        assert!(tokens.contains(&admin_views::AdminJsonToken {
+            kind: "string",
+            text: "\"hello \\\"world\\\"\"".to_string(),
+        }));
+        assert!(tokens.contains(&admin_views::AdminJsonToken {
+            kind: "number",
+            text: "3".to_string(),
+        }));
+        assert!(
+            tokens
+                .windows(2)
+                .all(|pair| !(pair[0].kind == "punct" && pair[1].kind == "punct"))
+        );
+    }
+
+    #[test]
+    fn elapsed_times_pick_a_readable_unit() {
+        assert_eq!(format_elapsed(850), "850 ms");
+        assert_eq!(format_elapsed(2_400), "2.4 s");
+        assert_eq!(format_elapsed(185_000), "3 min 5 s");
+        assert_eq!(format_elapsed(4_320_000), "1 h 12 min");
+    }
+}
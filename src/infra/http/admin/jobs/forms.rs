@@ -2,6 +2,8 @@
 
 use serde::Deserialize;
 
+use crate::infra::http::admin::shared::ListLimit;
+
 /// Form for status/type action requests with filter context.
 #[derive(Debug, Deserialize)]
 pub(crate) struct AdminJobActionForm {
@@ -20,5 +22,7 @@ pub(crate) struct AdminJobsPanelForm {
     pub(crate) search: Option<String>,
     pub(crate) cursor: Option<String>,
     pub(crate) trail: Option<String>,
+    #[serde(flatten)]
+    pub(crate) limit: ListLimit,
     pub(crate) clear: Option<String>,
 }
@@ -2,11 +2,13 @@
 
 use askama::Template;
 use axum::{
+    Extension,
     extract::{Form, Path, State},
     response::{IntoResponse, Response},
 };
 
 use crate::{
+    application::admin::auth::AdminSession,
     application::{pagination::JobCursor, repos::JobQueryFilter},
     infra::http::admin::{
         AdminState,
@@ -18,11 +20,10 @@ use crate::{
 };
 
 use super::{
+    detail::build_job_detail_view,
     errors::admin_job_error,
     forms::{AdminJobActionForm, AdminJobsPanelForm},
-    panel::{
-        apply_pagination_links, build_job_detail_view, build_job_list_view, render_job_panel_html,
-    },
+    panel::{apply_pagination_links, build_job_list_view, render_job_panel_html},
     status::{parse_job_state, parse_job_type},
 };
 
@@ -34,7 +35,7 @@ pub(crate) async fn admin_jobs(State(state): State<AdminState>) -> Response {
         search: None,
     };
 
-    let mut content = match build_job_list_view(&state, None, &filter, None).await {
+    let mut content = match build_job_list_view(&state, None, &filter, None, None).await {
         Ok(content) => content,
         Err(err) => {
             return admin_job_error("infra::http::admin::jobs::admin_jobs", err).into_response();
@@ -65,15 +66,15 @@ pub(crate) async fn admin_job_detail(
     State(state): State<AdminState>,
     Path(id): Path<String>,
 ) -> Response {
-    let job = match state.jobs.load_job(&id).await {
-        Ok(job) => job,
+    let detail = match state.jobs.load_job_detail(&id).await {
+        Ok(detail) => detail,
         Err(err) => {
             return admin_job_error("infra::http::admin::jobs::admin_job_detail", err)
                 .into_response();
         }
     };
 
-    let content = match build_job_detail_view(&state, job).await {
+    let content = match build_job_detail_view(&state, detail).await {
         Ok(content) => content,
         Err(err) => {
             return admin_job_error("infra::http::admin::jobs::admin_job_detail", err)
@@ -124,8 +125,10 @@ pub(crate) async fn admin_jobs_panel(
         search,
     };
 
-    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
     let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
         JobCursor::decode,
         "infra::http::admin::jobs::admin_jobs_panel",
     ) {
@@ -133,13 +136,14 @@ pub(crate) async fn admin_jobs_panel(
         Err(err) => return err.into_response(),
     };
 
-    let mut content = match build_job_list_view(&state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => {
-            return admin_job_error("infra::http::admin::jobs::admin_jobs_panel", err)
-                .into_response();
-        }
-    };
+    let mut content =
+        match build_job_list_view(&state, status, &filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_job_error("infra::http::admin::jobs::admin_jobs_panel", err)
+                    .into_response();
+            }
+        };
 
     apply_pagination_links(&mut content, &cursor_state);
 
@@ -152,10 +156,11 @@ pub(crate) async fn admin_jobs_panel(
 /// POST /jobs/{id}/retry - Retry a failed/killed job.
 pub(crate) async fn admin_job_retry(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<String>,
     Form(form): Form<AdminJobActionForm>,
 ) -> Response {
-    match state.jobs.retry_job("admin", &id).await {
+    match state.jobs.retry_job(&session.username, &id).await {
         Ok(_job) => {
             respond_with_panel_and_toast(
                 &state,
@@ -173,12 +178,13 @@ pub(crate) async fn admin_job_retry(
 /// POST /jobs/{id}/cancel - Cancel a pending/scheduled job.
 pub(crate) async fn admin_job_cancel(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<String>,
     Form(form): Form<AdminJobActionForm>,
 ) -> Response {
     match state
         .jobs
-        .cancel_job("admin", &id, Some("Cancelled by admin"))
+        .cancel_job(&session.username, &id, Some("Cancelled by admin"))
         .await
     {
         Ok(_job) => {
@@ -221,6 +227,7 @@ async fn respond_with_panel_and_toast(
 
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
     let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
         JobCursor::decode,
         "infra::http::admin::jobs::respond_with_panel_and_toast",
     ) {
@@ -228,16 +235,17 @@ async fn respond_with_panel_and_toast(
         Err(err) => return err.into_response(),
     };
 
-    let mut content = match build_job_list_view(state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => {
-            return admin_job_error(
-                "infra::http::admin::jobs::respond_with_panel_and_toast",
-                err,
-            )
-            .into_response();
-        }
-    };
+    let mut content =
+        match build_job_list_view(state, status, &filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_job_error(
+                    "infra::http::admin::jobs::respond_with_panel_and_toast",
+                    err,
+                )
+                .into_response();
+            }
+        };
 
     apply_pagination_links(&mut content, &cursor_state);
 
@@ -2,6 +2,7 @@
 //!
 //! This module handles the admin interface for viewing and managing background jobs.
 
+mod detail;
 mod errors;
 mod forms;
 mod handlers;
@@ -9,8 +9,12 @@ use crate::{
         pagination::{JobCursor, PageRequest},
         repos::{JobQueryFilter, SettingsRepo},
     },
-    domain::{entities::JobRecord, types::JobState},
-    infra::http::admin::{AdminState, pagination::CursorState, shared::template_render_http_error},
+    domain::types::JobState,
+    infra::http::admin::{
+        AdminState,
+        pagination::{self, CursorState},
+        shared::template_render_http_error,
+    },
     presentation::admin::views as admin_views,
 };
 
@@ -24,9 +28,10 @@ pub(super) async fn build_job_list_view(
     status: Option<JobState>,
     filter: &JobQueryFilter,
     cursor: Option<JobCursor>,
+    limit: Option<u32>,
 ) -> Result<admin_views::AdminJobListView, AdminJobError> {
     let settings = state.db.load_site_settings().await?;
-    let admin_page_size = settings.admin_page_size.clamp(1, 100).max(1) as u32;
+    let admin_page_size = pagination::page_size(settings.admin_page_size, limit);
 
     let page_request = PageRequest::new(admin_page_size, cursor);
 
@@ -93,11 +98,14 @@ pub(super) async fn build_job_list_view(
         next_page_state: None,
         panel_action: "/jobs/panel".to_string(),
         row_action_prefix: "/jobs".to_string(),
-        custom_hidden_fields: build_job_hidden_fields(filter),
+        custom_hidden_fields: build_job_hidden_fields(filter, limit),
     })
 }
 
-fn build_job_hidden_fields(filter: &JobQueryFilter) -> Vec<admin_views::AdminHiddenField> {
+fn build_job_hidden_fields(
+    filter: &JobQueryFilter,
+    limit: Option<u32>,
+) -> Vec<admin_views::AdminHiddenField> {
     let mut fields = Vec::new();
     if let Some(jt) = filter.job_type {
         fields.push(admin_views::AdminHiddenField::new(
@@ -105,111 +113,10 @@ fn build_job_hidden_fields(filter: &JobQueryFilter) -> Vec<admin_views::AdminHid
             job_type_key(jt),
         ));
     }
+    fields.extend(pagination::limit_hidden_field(limit));
     fields
 }
 
-/// Build the job detail view for a single job.
-pub(super) async fn build_job_detail_view(
-    state: &AdminState,
-    job: JobRecord,
-) -> Result<admin_views::AdminJobDetailView, AdminJobError> {
-    let settings = state.db.load_site_settings().await?;
-
-    let mut fields = Vec::new();
-
-    // ID field
-    fields.push(admin_views::AdminJobDetailField {
-        label: "ID".to_string(),
-        value: job.id.clone(),
-        is_badge: false,
-        badge_status: None,
-        is_multiline: false,
-    });
-
-    // Type field with badge
-    fields.push(admin_views::AdminJobDetailField {
-        label: "Type".to_string(),
-        value: job_type_label(job.job_type).to_string(),
-        is_badge: true,
-        badge_status: Some(job_type_key(job.job_type).to_string()),
-        is_multiline: false,
-    });
-
-    // State field with badge
-    fields.push(admin_views::AdminJobDetailField {
-        label: "State".to_string(),
-        value: state_label(job.state).to_string(),
-        is_badge: true,
-        badge_status: Some(state_key(job.state).to_string()),
-        is_multiline: false,
-    });
-
-    // Attempts field
-    fields.push(admin_views::AdminJobDetailField {
-        label: "Attempts".to_string(),
-        value: format!("{}/{}", job.attempts, job.max_attempts),
-        is_badge: false,
-        badge_status: None,
-        is_multiline: false,
-    });
-
-    // Priority field
-    fields.push(admin_views::AdminJobDetailField {
-        label: "Priority".to_string(),
-        value: job.priority.to_string(),
-        is_badge: false,
-        badge_status: None,
-        is_multiline: false,
-    });
-
-    // Run at field
-    fields.push(admin_views::AdminJobDetailField {
-        label: "Run At".to_string(),
-        value: admin_views::format_timestamp(job.run_at, settings.timezone),
-        is_badge: false,
-        badge_status: None,
-        is_multiline: false,
-    });
-
-    // Done at field (if applicable)
-    if let Some(done_at) = job.done_at {
-        fields.push(admin_views::AdminJobDetailField {
-            label: "Done At".to_string(),
-            value: admin_views::format_timestamp(done_at, settings.timezone),
-            is_badge: false,
-            badge_status: None,
-            is_multiline: false,
-        });
-    }
-
-    // Payload field (JSON formatted)
-    let payload_str =
-        serde_json::to_string_pretty(&job.payload).unwrap_or_else(|_| job.payload.to_string());
-    fields.push(admin_views::AdminJobDetailField {
-        label: "Payload".to_string(),
-        value: payload_str,
-        is_badge: false,
-        badge_status: None,
-        is_multiline: true,
-    });
-
-    // Last error field (if any)
-    if let Some(last_error) = &job.last_error {
-        fields.push(admin_views::AdminJobDetailField {
-            label: "Last Error".to_string(),
-            value: last_error.clone(),
-            is_badge: false,
-            badge_status: None,
-            is_multiline: true,
-        });
-    }
-
-    Ok(admin_views::AdminJobDetailView {
-        heading: format!("Job: {}", job.id),
-        fields,
-    })
-}
-
 /// Determine available actions based on job state.
 fn job_actions_for_state(state: JobState) -> Vec<admin_views::AdminJobRowActionView> {
     match state {
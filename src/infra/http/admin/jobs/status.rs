@@ -128,6 +128,7 @@ pub(super) fn job_type_label(job_type: JobType) -> &'static str {
         JobType::RenderSummary => "Render Summary",
         JobType::PublishPost => "Publish Post",
         JobType::PublishPage => "Publish Page",
+        JobType::PingSitemap => "Ping Sitemap",
     }
 }
 
@@ -1,5 +1,10 @@
 mod api_keys;
 mod audit;
+mod auth;
+mod bulk;
+mod cache;
+mod comments;
+mod config_reload;
 mod dashboard;
 mod health;
 mod jobs;
@@ -7,6 +12,8 @@ mod navigation;
 mod pages;
 mod pagination;
 mod posts;
+mod render;
+mod schedule;
 mod selectors;
 mod settings;
 mod shared;
@@ -23,20 +30,23 @@ use axum::{
     body::Body,
     extract::{DefaultBodyLimit, State},
     http::{
-        StatusCode,
+        Request, StatusCode,
         header::{CACHE_CONTROL, CONTENT_TYPE},
     },
-    middleware,
+    middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::{get, post},
 };
+use tower::{Layer, ServiceExt};
 
 use crate::{application::repos::SettingsRepo, infra::assets};
 
 use super::middleware::{log_responses, set_request_context};
+use super::security_headers::apply_security_headers;
 use tracing::error;
 
-pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router {
+pub fn build_admin_router(state: AdminState) -> Router {
+    let security_headers = state.security_headers.clone();
     Router::new()
         .route("/", get(dashboard::admin_dashboard))
         .route("/posts", get(posts::admin_posts))
@@ -44,10 +54,25 @@ pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router
         .route("/posts/bulk", post(posts::admin_posts_bulk_action))
         .route("/posts/create", post(posts::admin_post_create))
         .route("/posts/new", get(posts::admin_post_new))
+        .route("/posts/preview", post(posts::admin_post_preview))
+        .route(
+            "/posts/preview/close",
+            post(posts::admin_post_preview_close),
+        )
         .route(
             "/posts/{id}/edit",
             get(posts::admin_post_edit).post(posts::admin_post_update),
         )
+        .route("/posts/new/autosave", post(posts::admin_post_autosave_new))
+        .route(
+            "/posts/new/autosave/discard",
+            post(posts::admin_post_autosave_discard_new),
+        )
+        .route("/posts/{id}/autosave", post(posts::admin_post_autosave))
+        .route(
+            "/posts/{id}/autosave/discard",
+            post(posts::admin_post_autosave_discard),
+        )
         .route(
             "/posts/new/tags/toggle",
             post(posts::admin_post_tags_toggle_new),
@@ -60,6 +85,12 @@ pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router
         .route("/posts/{id}/publish", post(posts::admin_post_publish))
         .route("/posts/{id}/draft", post(posts::admin_post_move_to_draft))
         .route("/posts/{id}/archive", post(posts::admin_post_archive))
+        .route("/posts/{id}/rerender", post(posts::admin_post_rerender))
+        .route("/posts/{id}/duplicate", post(posts::admin_post_duplicate))
+        .route(
+            "/posts/{id}/render-progress",
+            get(posts::admin_post_render_progress),
+        )
         .route("/posts/{id}/pin", post(posts::admin_post_pin))
         .route("/posts/{id}/unpin", post(posts::admin_post_unpin))
         .route(
@@ -72,6 +103,7 @@ pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router
         )
         .route("/pages", get(pages::admin_pages))
         .route("/pages/panel", post(pages::admin_page_panel))
+        .route("/pages/bulk", post(pages::admin_pages_bulk_action))
         .route("/pages/create", post(pages::admin_page_create))
         .route("/pages/new", get(pages::admin_page_new))
         .route(
@@ -82,6 +114,8 @@ pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router
         .route("/pages/{id}/publish", post(pages::admin_page_publish))
         .route("/pages/{id}/draft", post(pages::admin_page_move_to_draft))
         .route("/pages/{id}/archive", post(pages::admin_page_archive))
+        .route("/pages/{id}/rerender", post(pages::admin_page_rerender))
+        .route("/pages/{id}/duplicate", post(pages::admin_page_duplicate))
         .route(
             "/pages/{id}/snapshots",
             get(snapshots::admin_page_snapshots).post(snapshots::admin_page_snapshots_panel),
@@ -102,6 +136,23 @@ pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router
             "/snapshots/{id}/edit",
             get(snapshots::admin_snapshot_edit).post(snapshots::admin_snapshot_update),
         )
+        .route("/schedule", get(schedule::admin_schedule))
+        .route("/schedule/panel", post(schedule::admin_schedule_panel))
+        .route(
+            "/schedule/{kind}/{id}/publish",
+            post(schedule::admin_schedule_publish),
+        )
+        .route(
+            "/schedule/{kind}/{id}/reschedule",
+            post(schedule::admin_schedule_reschedule),
+        )
+        .route("/comments", get(comments::admin_comments))
+        .route("/comments/panel", post(comments::admin_comments_panel))
+        .route(
+            "/comments/{id}/approve",
+            post(comments::admin_comment_approve),
+        )
+        .route("/comments/{id}/spam", post(comments::admin_comment_spam))
         .route("/jobs", get(jobs::admin_jobs))
         .route("/jobs/panel", post(jobs::admin_jobs_panel))
         .route("/jobs/{id}", get(jobs::admin_job_detail))
@@ -109,6 +160,7 @@ pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router
         .route("/jobs/{id}/cancel", post(jobs::admin_job_cancel))
         .route("/audit", get(audit::admin_audit))
         .route("/audit/panel", post(audit::admin_audit_panel))
+        .route("/audit/export.csv", get(audit::admin_audit_export))
         .route("/audit/{id}", get(audit::admin_audit_detail))
         .route("/tags", get(tags::admin_tags))
         .route("/tags/panel", post(tags::admin_tags_panel))
@@ -160,7 +212,10 @@ pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router
             "/uploads",
             get(uploads::admin_uploads)
                 .post(uploads::admin_upload_store)
-                .layer(DefaultBodyLimit::max(upload_body_limit)),
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    upload_body_limit,
+                )),
         )
         .route("/uploads/panel", post(uploads::admin_uploads_panel))
         .route(
@@ -170,6 +225,9 @@ pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router
         .route("/uploads/new", get(uploads::admin_upload_new))
         .route("/uploads/{id}", get(uploads::admin_upload_download))
         .route("/uploads/{id}/delete", post(uploads::admin_upload_delete))
+        .route("/cache/purge", post(cache::admin_cache_purge))
+        .route("/config/reload", post(config_reload::admin_config_reload))
+        .route("/render/preview", post(render::admin_render_preview))
         .route("/toasts", post(toasts::admin_toast))
         .route("/api-keys", get(api_keys::admin_api_keys))
         .route("/api-keys/create", post(api_keys::admin_api_key_create))
@@ -195,15 +253,47 @@ pub fn build_admin_router(state: AdminState, upload_body_limit: usize) -> Router
             "/api-keys/{id}/delete",
             post(api_keys::admin_api_key_delete),
         )
+        .route(
+            "/login",
+            get(auth::admin_login).post(auth::admin_login_submit),
+        )
+        .route("/logout", post(auth::admin_logout))
         .route("/_health/db", get(health::admin_health))
+        .route("/_health/render", get(health::admin_render_health))
         .route("/static/admin/{*path}", get(assets::serve_admin))
         .route("/static/common/{*path}", get(assets::serve_common))
+        .route("/static/public/{*path}", get(assets::serve_public))
         .route("/favicon.ico", get(favicon))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_session,
+        ))
         .with_state(state)
+        .layer(middleware::from_fn_with_state(
+            security_headers,
+            apply_security_headers,
+        ))
         .layer(middleware::from_fn(log_responses))
         .layer(middleware::from_fn(set_request_context))
 }
 
+/// Apply the upload limit per request, since configuration reloads change it.
+async fn upload_body_limit(
+    State(state): State<AdminState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let limit = usize::try_from(state.upload_limit_bytes()).unwrap_or(usize::MAX);
+    match DefaultBodyLimit::max(limit)
+        .layer(next)
+        .oneshot(request)
+        .await
+    {
+        Ok(response) => response,
+        Err(never) => match never {},
+    }
+}
+
 async fn favicon(State(state): State<AdminState>) -> Response {
     match state.db.load_site_settings().await {
         Ok(settings) => Response::builder()
@@ -2,12 +2,16 @@
 
 use serde::Deserialize;
 
+use crate::infra::http::admin::shared::ListLimit;
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct AdminNavigationQuery {
     pub(super) status: Option<String>,
     pub(super) cursor: Option<String>,
     pub(super) trail: Option<String>,
     pub(super) search: Option<String>,
+    #[serde(flatten)]
+    pub(super) limit: ListLimit,
 }
 
 #[derive(Debug, Deserialize)]
@@ -16,6 +20,8 @@ pub(crate) struct AdminNavigationPanelForm {
     pub(super) search: Option<String>,
     pub(super) cursor: Option<String>,
     pub(super) trail: Option<String>,
+    #[serde(flatten)]
+    pub(super) limit: ListLimit,
     pub(super) clear: Option<String>,
 }
 
@@ -1,6 +1,7 @@
 //! Navigation admin HTTP handlers.
 
 use axum::{
+    Extension,
     extract::{Form, Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Redirect, Response},
@@ -8,6 +9,7 @@ use axum::{
 use uuid::Uuid;
 
 use crate::{
+    application::admin::auth::AdminSession,
     application::{
         admin::navigation::{CreateNavigationItemCommand, UpdateNavigationItemCommand},
         pagination::NavigationCursor,
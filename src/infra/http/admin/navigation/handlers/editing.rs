@@ -50,6 +50,7 @@ pub(crate) async fn admin_navigation_edit(
 
 pub(crate) async fn admin_navigation_create(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Form(form): Form<AdminNavigationForm>,
 ) -> Response {
     let destination_type = match parse_navigation_type(&form.destination_type) {
@@ -69,7 +70,7 @@ pub(crate) async fn admin_navigation_create(
         open_in_new_tab: form.open_in_new_tab.is_some(),
     };
 
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     match state.navigation.create_item(actor, command).await {
         Ok(item) => {
@@ -109,6 +110,7 @@ pub(crate) async fn admin_navigation_create(
 
 pub(crate) async fn admin_navigation_update(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminNavigationForm>,
 ) -> Response {
@@ -130,7 +132,7 @@ pub(crate) async fn admin_navigation_update(
         open_in_new_tab: form.open_in_new_tab.is_some(),
     };
 
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     match state.navigation.update_item(actor, command).await {
         Ok(item) => {
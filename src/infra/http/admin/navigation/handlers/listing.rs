@@ -9,27 +9,35 @@ pub(crate) async fn admin_navigation(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(query.cursor.clone(), query.trail.clone());
+    let cursor_state =
+        CursorState::new(query.cursor.clone(), query.trail.clone()).with_limit(&query.limit);
 
     let status = match parse_navigation_status(query.status.as_deref()) {
         Ok(status) => status,
         Err(err) => return err.into_response(),
     };
 
-    let cursor =
-        match cursor_state.decode_with(NavigationCursor::decode, "infra::http::admin_navigation") {
-            Ok(cursor) => cursor,
-            Err(err) => return err.into_response(),
-        };
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        NavigationCursor::decode,
+        "infra::http::admin_navigation",
+    ) {
+        Ok(cursor) => cursor,
+        Err(err) => return err.into_response(),
+    };
 
     let filter = build_navigation_filter(query.search.as_deref());
 
-    let mut content = match build_navigation_list_view(&state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => {
-            return admin_navigation_error("infra::http::admin_navigation", err).into_response();
-        }
-    };
+    let mut content =
+        match build_navigation_list_view(&state, status, &filter, cursor, cursor_state.limit())
+            .await
+        {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_navigation_error("infra::http::admin_navigation", err)
+                    .into_response();
+            }
+        };
 
     apply_navigation_pagination_links(&mut content, &cursor_state);
 
@@ -49,9 +57,11 @@ pub(crate) async fn admin_navigation_panel(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
 
     let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
         NavigationCursor::decode,
         "infra::http::admin_navigation_panel",
     ) {
@@ -66,13 +76,16 @@ pub(crate) async fn admin_navigation_panel(
     };
     let filter = build_navigation_filter(search);
 
-    let mut content = match build_navigation_list_view(&state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => {
-            return admin_navigation_error("infra::http::admin_navigation_panel", err)
-                .into_response();
-        }
-    };
+    let mut content =
+        match build_navigation_list_view(&state, status, &filter, cursor, cursor_state.limit())
+            .await
+        {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_navigation_error("infra::http::admin_navigation_panel", err)
+                    .into_response();
+            }
+        };
 
     apply_navigation_pagination_links(&mut content, &cursor_state);
 
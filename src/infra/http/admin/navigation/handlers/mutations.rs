@@ -2,6 +2,7 @@ use super::*;
 
 pub(crate) async fn admin_navigation_toggle_visibility(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminNavigationVisibilityForm>,
 ) -> Response {
@@ -12,6 +13,7 @@ pub(crate) async fn admin_navigation_toggle_visibility(
 
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
     let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
         NavigationCursor::decode,
         "infra::http::admin_navigation_toggle_visibility",
     ) {
@@ -74,17 +76,23 @@ pub(crate) async fn admin_navigation_toggle_visibility(
         open_in_new_tab: item.open_in_new_tab,
     };
 
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     let result = state.navigation.update_item(actor, command).await;
 
-    let mut content = match build_navigation_list_view(&state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => {
-            return admin_navigation_error("infra::http::admin_navigation_toggle_visibility", err)
+    let mut content =
+        match build_navigation_list_view(&state, status, &filter, cursor, cursor_state.limit())
+            .await
+        {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_navigation_error(
+                    "infra::http::admin_navigation_toggle_visibility",
+                    err,
+                )
                 .into_response();
-        }
-    };
+            }
+        };
 
     apply_navigation_pagination_links(&mut content, &cursor_state);
 
@@ -127,6 +135,7 @@ pub(crate) async fn admin_navigation_toggle_visibility(
 
 pub(crate) async fn admin_navigation_delete(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminNavigationDeleteForm>,
 ) -> Response {
@@ -138,6 +147,7 @@ pub(crate) async fn admin_navigation_delete(
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
 
     let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
         NavigationCursor::decode,
         "infra::http::admin_navigation_delete",
     ) {
@@ -178,17 +188,20 @@ pub(crate) async fn admin_navigation_delete(
         }
     };
 
-    if let Err(err) = state.navigation.delete_item("admin", id).await {
+    if let Err(err) = state.navigation.delete_item(&session.username, id).await {
         return admin_navigation_error("infra::http::admin_navigation_delete", err).into_response();
     }
 
-    let mut content = match build_navigation_list_view(&state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => {
-            return admin_navigation_error("infra::http::admin_navigation_delete", err)
-                .into_response();
-        }
-    };
+    let mut content =
+        match build_navigation_list_view(&state, status, &filter, cursor, cursor_state.limit())
+            .await
+        {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_navigation_error("infra::http::admin_navigation_delete", err)
+                    .into_response();
+            }
+        };
 
     apply_navigation_pagination_links(&mut content, &cursor_state);
 
@@ -220,13 +233,15 @@ async fn render_navigation_error_panel(
     cursor_state: &CursorState,
     message: impl Into<String>,
 ) -> Response {
-    let mut content = match build_navigation_list_view(state, status, filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => {
-            return admin_navigation_error("infra::http::admin_navigation_error_panel", err)
-                .into_response();
-        }
-    };
+    let mut content =
+        match build_navigation_list_view(state, status, filter, cursor, cursor_state.limit()).await
+        {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_navigation_error("infra::http::admin_navigation_error_panel", err)
+                    .into_response();
+            }
+        };
 
     apply_navigation_pagination_links(&mut content, cursor_state);
 
@@ -9,6 +9,7 @@ use crate::application::error::HttpError;
 use crate::application::pagination::{NavigationCursor, PageRequest};
 use crate::application::repos::{NavigationQueryFilter, SettingsRepo};
 use crate::domain::entities::NavigationItemRecord;
+use crate::domain::routes::{join_url, reserved_page_slug_route};
 use crate::domain::types::NavigationDestinationType;
 use crate::infra::http::admin::AdminState;
 use crate::infra::http::admin::pagination::{self, CursorState};
@@ -17,8 +18,7 @@ use crate::infra::http::repo_error_to_http;
 use crate::presentation::admin::views as admin_views;
 
 use super::status::{
-    NavigationListStatus, navigation_type_key, navigation_type_label, normalize_public_site_url,
-    status_filters, status_key,
+    NavigationListStatus, navigation_type_key, navigation_type_label, status_filters, status_key,
 };
 
 pub(super) fn apply_navigation_pagination_links(
@@ -60,10 +60,13 @@ pub(super) async fn build_navigation_list_view(
     status: NavigationListStatus,
     filter: &NavigationQueryFilter,
     cursor: Option<NavigationCursor>,
+    limit: Option<u32>,
 ) -> Result<admin_views::AdminNavigationListView, AdminNavigationError> {
     let settings = state.db.load_site_settings().await?;
-    let admin_page_size = settings.admin_page_size.clamp(1, 100).max(1) as u32;
-    let public_site_url = normalize_public_site_url(&settings.public_site_url);
+    let admin_page_size = pagination::page_size(settings.admin_page_size, limit);
+    let public_site_url = state
+        .base_path
+        .site_url_or_relative(&settings.public_site_url);
 
     let counts_future = state.navigation.status_counts(filter);
     let list_future = state.navigation.list(
@@ -111,7 +114,8 @@ pub(super) async fn build_navigation_list_view(
         tag_filter_label: "Tag".to_string(),
         tag_filter_all_label: "All tags".to_string(),
         tag_filter_field: "tag".to_string(),
-        custom_hidden_fields: Vec::new(), // Navigation has no special filters
+        // Navigation has no special filters; only a page size override is carried.
+        custom_hidden_fields: pagination::limit_hidden_field(limit).into_iter().collect(),
     })
 }
 
@@ -135,7 +139,7 @@ pub(super) async fn build_navigation_panel_html(
     error_source: &'static str,
     template_source: &'static str,
 ) -> Result<String, HttpError> {
-    let content = build_navigation_list_view(state, status, filter, None)
+    let content = build_navigation_list_view(state, status, filter, None, None)
         .await
         .map_err(|err| admin_navigation_error(error_source, err))?;
 
@@ -150,7 +154,7 @@ fn map_navigation_row(
         NavigationDestinationType::Internal => item
             .destination_page_slug
             .as_deref()
-            .map(|slug| format!("{}{}", public_site_url, slug))
+            .map(|slug| join_url(public_site_url, slug))
             .unwrap_or_else(|| public_site_url.to_string()),
         NavigationDestinationType::External => item
             .destination_url
@@ -173,6 +177,18 @@ fn map_navigation_row(
             .unwrap_or_else(|| "—".to_string()),
     };
 
+    let destination_warning = match item.destination_type {
+        NavigationDestinationType::Internal => item
+            .destination_page_slug
+            .as_deref()
+            .and_then(|slug| {
+                reserved_page_slug_route(slug).map(|route| {
+                    format!("Shadowed by the built-in route {route}; rename the page to make it reachable")
+                })
+            }),
+        NavigationDestinationType::External => None,
+    };
+
     let destination_type_status = navigation_type_key(item.destination_type).to_string();
     let toggle_label = if item.visible { "Hide" } else { "Show" };
 
@@ -183,6 +199,7 @@ fn map_navigation_row(
         destination_type_label,
         destination_type_status,
         destination_display,
+        destination_warning,
         sort_order: item.sort_order,
         visible: item.visible,
         toggle_action: format!("/navigation/{}/visibility", item.id),
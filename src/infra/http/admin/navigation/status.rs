@@ -136,11 +136,3 @@ pub(super) fn navigation_type_key(destination: NavigationDestinationType) -> &'s
         NavigationDestinationType::External => "external",
     }
 }
-
-pub(super) fn normalize_public_site_url(url: &str) -> String {
-    if url.ends_with('/') {
-        url.to_string()
-    } else {
-        format!("{url}/")
-    }
-}
@@ -1,11 +1,15 @@
+use crate::application::render::ContentWarning;
 use crate::domain::{entities::PageRecord, types::PageStatus};
+use crate::infra::http::api::etag::version_token;
 use crate::presentation::admin::views as admin_views;
+use crate::presentation::page_templates::DEFAULT_PAGE_TEMPLATE;
 use chrono_tz::Tz;
 
-use super::status::page_status_options;
+use super::status::{page_status_options, page_template_options};
 
 pub(crate) fn build_page_editor_view(
     page: &PageRecord,
+    render_warnings: &[ContentWarning],
     tz: Tz,
 ) -> admin_views::AdminPageEditorView {
     admin_views::AdminPageEditorView {
@@ -14,12 +18,24 @@ pub(crate) fn build_page_editor_view(
         body_markdown: page.body_markdown.clone(),
         status: page.status,
         status_options: page_status_options(page.status),
+        template_options: page_template_options(&page.template),
         published_at: page
             .published_at
             .map(|time| admin_views::format_timestamp(time, tz)),
         form_action: format!("/pages/{}/edit", page.id),
+        version: Some(version_token(page.updated_at)),
         submit_label: "Save Changes".to_string(),
         enable_live_submit: true,
+        render_failure: admin_views::AdminRenderFailureView::from_status(
+            page.render_status,
+            page.render_error.as_deref(),
+            page.render_failed_at,
+            tz,
+        ),
+        render_warnings: render_warnings
+            .iter()
+            .map(ContentWarning::message)
+            .collect(),
     }
 }
 
@@ -30,9 +46,13 @@ pub(crate) fn build_new_page_editor_view() -> admin_views::AdminPageEditorView {
         body_markdown: String::new(),
         status: PageStatus::Draft,
         status_options: page_status_options(PageStatus::Draft),
+        template_options: page_template_options(DEFAULT_PAGE_TEMPLATE),
         published_at: None,
         form_action: "/pages/create".to_string(),
+        version: None,
         submit_label: "Create Page".to_string(),
         enable_live_submit: true,
+        render_failure: None,
+        render_warnings: Vec::new(),
     }
 }
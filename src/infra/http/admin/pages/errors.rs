@@ -11,6 +11,12 @@ pub(crate) fn admin_page_error(source: &'static str, err: AdminPageError) -> Htt
             "Page request could not be processed",
             format!("Invalid field `{field}`"),
         ),
+        err @ AdminPageError::ReservedSlug { .. } => HttpError::new(
+            source,
+            StatusCode::BAD_REQUEST,
+            "Page request could not be processed",
+            err.to_string(),
+        ),
         AdminPageError::Render(render) => HttpError::new(
             source,
             StatusCode::BAD_REQUEST,
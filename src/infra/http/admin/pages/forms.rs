@@ -1,13 +1,20 @@
 use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::infra::http::admin::shared::ListLimit;
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct AdminPageForm {
     pub(crate) title: String,
     pub(crate) body_markdown: String,
     pub(crate) status: String,
+    pub(crate) template: Option<String>,
     pub(crate) status_filter: Option<String>,
     pub(crate) filter_search: Option<String>,
     pub(crate) filter_month: Option<String>,
+    /// The editor's `version` of the page; absent when creating one.
+    #[serde(default)]
+    pub(crate) version: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -26,6 +33,8 @@ pub(crate) struct AdminPagePanelForm {
     pub(crate) month: Option<String>,
     pub(crate) cursor: Option<String>,
     pub(crate) trail: Option<String>,
+    #[serde(flatten)]
+    pub(crate) limit: ListLimit,
     pub(crate) clear: Option<String>,
 }
 
@@ -37,3 +46,17 @@ pub(crate) struct AdminPageStatusActionForm {
     pub(crate) cursor: Option<String>,
     pub(crate) trail: Option<String>,
 }
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminPageBulkActionForm {
+    pub(crate) action: String,
+    #[serde(default)]
+    pub(crate) ids: Vec<Uuid>,
+    pub(crate) status_filter: Option<String>,
+    pub(crate) filter_search: Option<String>,
+    pub(crate) filter_month: Option<String>,
+    /// Apply the action to every page matching the filters instead of `ids`.
+    pub(crate) select_all_matching: Option<String>,
+    /// Typed confirmation required to delete every matching page.
+    pub(crate) confirm: Option<String>,
+}
@@ -1,12 +1,15 @@
 use askama::Template;
 use axum::{
+    Extension,
     extract::{Form, Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use tracing::warn;
 use uuid::Uuid;
 
 use crate::{
+    application::admin::auth::AdminSession,
     application::{
         admin::pages::{CreatePageCommand, UpdatePageContentCommand, UpdatePageStatusCommand},
         error::HttpError,
@@ -19,8 +22,8 @@ use crate::{
         pagination::{self, CursorState},
         selectors::{PAGES_PANEL, PANEL},
         shared::{
-            AdminPostQuery, EditorSuccessRender, Toast, datastar_replace, push_toasts,
-            stream_editor_success, template_render_http_error,
+            AdminPostQuery, EditorSuccessRender, Toast, datastar_replace, form_version,
+            push_toasts, stream_editor_success, template_render_http_error,
         },
     },
     presentation::{admin::views as admin_views, views::render_template_response},
@@ -29,11 +32,16 @@ use crate::{
 use super::{
     editor::{build_new_page_editor_view, build_page_editor_view},
     errors::admin_page_error,
-    forms::{AdminPageDeleteForm, AdminPageForm, AdminPagePanelForm, AdminPageStatusActionForm},
+    forms::{
+        AdminPageBulkActionForm, AdminPageDeleteForm, AdminPageForm, AdminPagePanelForm,
+        AdminPageStatusActionForm,
+    },
     panel::{build_page_list_view, build_page_panel_html, render_page_panel_html},
     status::{page_status_label, parse_page_status},
 };
 
+#[path = "handlers/bulk.rs"]
+mod bulk;
 #[path = "handlers/editing.rs"]
 mod editing;
 #[path = "handlers/listing.rs"]
@@ -43,8 +51,10 @@ mod mutations;
 #[path = "handlers/shared.rs"]
 mod shared;
 
+pub(crate) use bulk::admin_pages_bulk_action;
 pub(crate) use editing::{admin_page_create, admin_page_edit, admin_page_new, admin_page_update};
 pub(crate) use listing::{admin_page_panel, admin_pages};
 pub(crate) use mutations::{
-    admin_page_archive, admin_page_delete, admin_page_move_to_draft, admin_page_publish,
+    admin_page_archive, admin_page_delete, admin_page_duplicate, admin_page_move_to_draft,
+    admin_page_publish, admin_page_rerender,
 };
@@ -0,0 +1,152 @@
+//! Bulk action handlers for pages.
+
+use super::*;
+use crate::application::repos::PagesRepo;
+use crate::infra::http::admin::bulk::{self, BATCH_SIZE, BulkAction, SELECT_ALL_CAP};
+
+const SOURCE: &str = "infra::http::admin_pages_bulk_action";
+
+pub(crate) async fn admin_pages_bulk_action(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Form(form): Form<AdminPageBulkActionForm>,
+) -> Response {
+    let status_filter = match parse_page_status(form.status_filter.as_deref()) {
+        Ok(status) => status,
+        Err(err) => return err.into_response(),
+    };
+
+    let filter =
+        shared::build_page_filter(form.filter_search.as_deref(), form.filter_month.as_deref());
+
+    let Some(action) = BulkAction::from_str(form.action.trim()) else {
+        return respond(
+            &state,
+            status_filter,
+            &filter,
+            &[Toast::error("Select a valid bulk action")],
+        )
+        .await;
+    };
+
+    let ids = if bulk::select_all_requested(form.select_all_matching.as_deref()) {
+        match select_all_matching(&state, status_filter, &filter, action, &form).await {
+            Ok(ids) if ids.is_empty() => {
+                return respond(
+                    &state,
+                    status_filter,
+                    &filter,
+                    &[Toast::error("No pages match these filters")],
+                )
+                .await;
+            }
+            Ok(ids) => ids,
+            Err(toast) => return respond(&state, status_filter, &filter, &[toast]).await,
+        }
+    } else if form.ids.is_empty() {
+        return respond(
+            &state,
+            status_filter,
+            &filter,
+            &[Toast::error("Select at least one page")],
+        )
+        .await;
+    } else {
+        form.ids.clone()
+    };
+
+    let actor = session.username.as_str();
+    let outcome = bulk::apply_in_batches(&ids, BATCH_SIZE, |id| {
+        apply_to_page(&state, actor, action, id)
+    })
+    .await;
+
+    let messages = bulk::outcome_toasts(action.label(), "page", &outcome);
+    respond(&state, status_filter, &filter, &messages).await
+}
+
+async fn respond(
+    state: &AdminState,
+    status_filter: Option<PageStatus>,
+    filter: &PageQueryFilter,
+    messages: &[Toast],
+) -> Response {
+    shared::respond_with_pages_panel_with_state(
+        state,
+        status_filter,
+        filter,
+        &CursorState::default(),
+        messages,
+        SOURCE,
+        SOURCE,
+    )
+    .await
+}
+
+/// Every page matching the panel filters, refusing more than [`SELECT_ALL_CAP`]
+/// and an unconfirmed delete.
+async fn select_all_matching(
+    state: &AdminState,
+    status: Option<PageStatus>,
+    filter: &PageQueryFilter,
+    action: BulkAction,
+    form: &AdminPageBulkActionForm,
+) -> Result<Vec<Uuid>, Toast> {
+    if matches!(action, BulkAction::Delete) {
+        bulk::check_delete_confirmation(form.confirm.as_deref(), "page")?;
+    }
+
+    let matched = state
+        .db
+        .count_pages(status, filter)
+        .await
+        .map_err(|err| Toast::error(format!("Failed to count matching pages: {err}")))?;
+    bulk::check_select_all_cap(matched, SELECT_ALL_CAP, "page")?;
+
+    state
+        .db
+        .list_admin_page_ids(status, filter, SELECT_ALL_CAP)
+        .await
+        .map_err(|err| Toast::error(format!("Failed to load matching pages: {err}")))
+}
+
+async fn apply_to_page(
+    state: &AdminState,
+    actor: &str,
+    action: BulkAction,
+    id: Uuid,
+) -> Result<(), String> {
+    let page = match state.pages.find_by_id(id).await {
+        Ok(Some(page)) => page,
+        Ok(None) => return Err(format!("Page `{id}` not found")),
+        Err(err) => return Err(format!("Failed to load `{id}`: {err}")),
+    };
+
+    let target_status = match action {
+        BulkAction::Delete => {
+            return state
+                .pages
+                .delete_page(actor, page.id, &page.slug)
+                .await
+                .map(|_| ())
+                .map_err(|err| format!("{} ({})", page.title, err));
+        }
+        BulkAction::Publish => PageStatus::Published,
+        BulkAction::Draft => PageStatus::Draft,
+        BulkAction::Archive => PageStatus::Archived,
+    };
+
+    let command = UpdatePageStatusCommand {
+        id: page.id,
+        status: target_status,
+        scheduled_at: page.scheduled_at,
+        published_at: page.published_at,
+        archived_at: page.archived_at,
+    };
+    state
+        .pages
+        .update_status(actor, command)
+        .await
+        .map(|_| ())
+        .map_err(|err| format!("{} ({})", page.title, err))
+}
@@ -41,7 +41,14 @@ pub(crate) async fn admin_page_edit(
         }
     };
 
-    let content = build_page_editor_view(&page, timezone);
+    let render_warnings = match state.db.load_page_render_warnings(page.id).await {
+        Ok(warnings) => warnings,
+        Err(err) => {
+            return admin_page_error("infra::http::admin_page_edit", err.into()).into_response();
+        }
+    };
+
+    let content = build_page_editor_view(&page, &render_warnings, timezone);
 
     let view = admin_views::AdminLayout::new(chrome, content);
     render_template_response(admin_views::AdminPageEditTemplate { view }, StatusCode::OK)
@@ -49,6 +56,7 @@ pub(crate) async fn admin_page_edit(
 
 pub(crate) async fn admin_page_update(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPageForm>,
 ) -> Response {
@@ -71,20 +79,29 @@ pub(crate) async fn admin_page_update(
         Err(err) => return err.into_response(),
     };
 
+    let expected_updated_at =
+        match form_version("infra::http::admin_page_update", form.version.as_deref()) {
+            Ok(version) => version,
+            Err(err) => return err.into_response(),
+        };
+
     let command = UpdatePageContentCommand {
         id: page.id,
         slug: page.slug.clone(),
         title: form.title.trim().to_string(),
         body_markdown: form.body_markdown.trim().to_string(),
+        template: form.template,
+        expected_updated_at,
     };
 
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     let updated = match state.pages.update_page(actor, command).await {
         Ok(page) => page,
         Err(err) => return admin_page_error("infra::http::admin_page_update", err).into_response(),
     };
 
+    let mut navigation_warning = None;
     let final_record = if updated.status != status {
         let status_command = UpdatePageStatusCommand {
             id: updated.id,
@@ -94,7 +111,12 @@ pub(crate) async fn admin_page_update(
             archived_at: page.archived_at,
         };
         match state.pages.update_status(actor, status_command).await {
-            Ok(page) => page,
+            Ok(page) => {
+                if updated.status == PageStatus::Published {
+                    navigation_warning = shared::hidden_navigation_toast(&state, &page).await;
+                }
+                page
+            }
             Err(err) => {
                 return admin_page_error("infra::http::admin_page_update", err).into_response();
             }
@@ -110,7 +132,14 @@ pub(crate) async fn admin_page_update(
         }
     };
 
-    let content = build_page_editor_view(&final_record, timezone);
+    let render_warnings = match state.db.load_page_render_warnings(final_record.id).await {
+        Ok(warnings) => warnings,
+        Err(err) => {
+            return admin_page_error("infra::http::admin_page_update", err.into()).into_response();
+        }
+    };
+
+    let content = build_page_editor_view(&final_record, &render_warnings, timezone);
 
     let template = admin_views::AdminPageEditPanelTemplate {
         content: content.clone(),
@@ -130,13 +159,12 @@ pub(crate) async fn admin_page_update(
 
     let mut stream = datastar_replace(PANEL, panel_html);
 
-    if let Err(err) = push_toasts(
-        &mut stream,
-        &[Toast::success(format!(
-            "Saved page \"{}\"",
-            final_record.title
-        ))],
-    ) {
+    let mut toasts = vec![Toast::success(format!(
+        "Saved page \"{}\"",
+        final_record.title
+    ))];
+    toasts.extend(navigation_warning);
+    if let Err(err) = push_toasts(&mut stream, &toasts) {
         return err.into_response();
     }
 
@@ -145,6 +173,7 @@ pub(crate) async fn admin_page_update(
 
 pub(crate) async fn admin_page_create(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Form(form): Form<AdminPageForm>,
 ) -> Response {
     let status_filter = match parse_page_status(form.status_filter.as_deref()) {
@@ -172,9 +201,10 @@ pub(crate) async fn admin_page_create(
         scheduled_at: None,
         published_at: None,
         archived_at: None,
+        template: form.template,
     };
 
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     match state.pages.create_page(actor, command).await {
         Ok(page) => {
@@ -235,7 +265,12 @@ async fn respond_with_page_editor_success(
         Err(err) => return admin_page_error(template_source, err.into()).into_response(),
     };
 
-    let content = build_page_editor_view(page, timezone);
+    let render_warnings = match state.db.load_page_render_warnings(page.id).await {
+        Ok(warnings) => warnings,
+        Err(err) => return admin_page_error(template_source, err.into()).into_response(),
+    };
+
+    let content = build_page_editor_view(page, &render_warnings, timezone);
 
     let editor_template = admin_views::AdminPageEditPanelTemplate {
         content: content.clone(),
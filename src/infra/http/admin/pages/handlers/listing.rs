@@ -9,7 +9,8 @@ pub(crate) async fn admin_pages(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(query.cursor.clone(), query.trail.clone());
+    let cursor_state =
+        CursorState::new(query.cursor.clone(), query.trail.clone()).with_limit(&query.limit);
 
     let status = match parse_page_status(query.status.as_deref()) {
         Ok(status) => status,
@@ -18,15 +19,20 @@ pub(crate) async fn admin_pages(
 
     let filter = shared::build_page_filter(query.search.as_deref(), query.month.as_deref());
 
-    let cursor = match cursor_state.decode_with(PageCursor::decode, "infra::http::admin_pages") {
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        PageCursor::decode,
+        "infra::http::admin_pages",
+    ) {
         Ok(cursor) => cursor,
         Err(err) => return err.into_response(),
     };
 
-    let mut content = match build_page_list_view(&state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_page_error("infra::http::admin_pages", err).into_response(),
-    };
+    let mut content =
+        match build_page_list_view(&state, status, &filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => return admin_page_error("infra::http::admin_pages", err).into_response(),
+        };
 
     shared::apply_pagination_links(&mut content, &cursor_state);
 
@@ -43,10 +49,14 @@ pub(crate) async fn admin_page_panel(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
 
-    let cursor = match cursor_state.decode_with(PageCursor::decode, "infra::http::admin_page_panel")
-    {
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        PageCursor::decode,
+        "infra::http::admin_page_panel",
+    ) {
         Ok(cursor) => cursor,
         Err(err) => return err.into_response(),
     };
@@ -57,10 +67,13 @@ pub(crate) async fn admin_page_panel(
         shared::build_page_filter(form.search.as_deref(), form.month.as_deref())
     };
 
-    let mut content = match build_page_list_view(&state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_page_error("infra::http::admin_page_panel", err).into_response(),
-    };
+    let mut content =
+        match build_page_list_view(&state, status, &filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_page_error("infra::http::admin_page_panel", err).into_response();
+            }
+        };
 
     shared::apply_pagination_links(&mut content, &cursor_state);
 
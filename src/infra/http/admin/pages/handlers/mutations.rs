@@ -2,6 +2,7 @@ use super::*;
 
 pub(crate) async fn admin_page_delete(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPageDeleteForm>,
 ) -> Response {
@@ -14,7 +15,7 @@ pub(crate) async fn admin_page_delete(
         shared::build_page_filter(form.filter_search.as_deref(), form.filter_month.as_deref());
 
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     let page = match state.pages.find_by_id(id).await {
         Ok(Some(page)) => page,
@@ -75,11 +76,13 @@ pub(crate) async fn admin_page_delete(
 
 pub(crate) async fn admin_page_publish(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPageStatusActionForm>,
 ) -> Response {
     handle_page_status_action(
         &state,
+        &session.username,
         id,
         form,
         PageStatus::Published,
@@ -91,11 +94,13 @@ pub(crate) async fn admin_page_publish(
 
 pub(crate) async fn admin_page_move_to_draft(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPageStatusActionForm>,
 ) -> Response {
     handle_page_status_action(
         &state,
+        &session.username,
         id,
         form,
         PageStatus::Draft,
@@ -107,11 +112,13 @@ pub(crate) async fn admin_page_move_to_draft(
 
 pub(crate) async fn admin_page_archive(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPageStatusActionForm>,
 ) -> Response {
     handle_page_status_action(
         &state,
+        &session.username,
         id,
         form,
         PageStatus::Archived,
@@ -123,6 +130,7 @@ pub(crate) async fn admin_page_archive(
 
 async fn handle_page_status_action(
     state: &AdminState,
+    actor: &str,
     id: Uuid,
     form: AdminPageStatusActionForm,
     target_status: PageStatus,
@@ -168,7 +176,6 @@ async fn handle_page_status_action(
         }
     };
 
-    let actor = "admin";
     let command = UpdatePageStatusCommand {
         id: page.id,
         status: target_status,
@@ -177,23 +184,106 @@ async fn handle_page_status_action(
         archived_at: page.archived_at,
     };
 
-    let message = match state.pages.update_status(actor, command).await {
-        Ok(updated) => Toast::success(format!(
-            "Updated page \"{}\" to {}",
-            updated.title,
-            page_status_label(target_status),
+    let mut messages = Vec::with_capacity(2);
+    match state.pages.update_status(actor, command).await {
+        Ok(updated) => {
+            messages.push(Toast::success(format!(
+                "Updated page \"{}\" to {}",
+                updated.title,
+                page_status_label(target_status),
+            )));
+            if page.status == PageStatus::Published {
+                messages.extend(shared::hidden_navigation_toast(state, &updated).await);
+            }
+        }
+        Err(err) => messages.push(Toast::error(format!(
+            "Failed to update page status: {}",
+            err
+        ))),
+    }
+
+    shared::respond_with_pages_panel_with_state(
+        state,
+        status_filter,
+        &filter,
+        &cursor_state,
+        &messages,
+        error_source,
+        template_source,
+    )
+    .await
+}
+
+pub(crate) async fn admin_page_rerender(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Path(id): Path<Uuid>,
+    Form(form): Form<AdminPageStatusActionForm>,
+) -> Response {
+    let status_filter = match parse_page_status(form.status_filter.as_deref()) {
+        Ok(status) => status,
+        Err(err) => return err.into_response(),
+    };
+
+    let filter =
+        shared::build_page_filter(form.filter_search.as_deref(), form.filter_month.as_deref());
+    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
+
+    let message = match state.pages.find_by_id(id).await {
+        Ok(Some(page)) => match state.pages.rerender_page(&session.username, &page).await {
+            Ok(job_id) => Toast::success(format!(
+                "Queued re-render of \"{}\" (job {job_id})",
+                page.title
+            )),
+            Err(err) => Toast::error(format!("Failed to queue re-render: {}", err)),
+        },
+        Ok(None) => Toast::error("Page not found"),
+        Err(err) => Toast::error(format!("Failed to load page: {}", err)),
+    };
+
+    shared::respond_with_pages_panel_message(
+        &state,
+        status_filter,
+        &filter,
+        &cursor_state,
+        message,
+        "infra::http::admin_page_rerender",
+        "infra::http::admin_page_rerender",
+    )
+    .await
+}
+
+pub(crate) async fn admin_page_duplicate(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Path(id): Path<Uuid>,
+    Form(form): Form<AdminPageStatusActionForm>,
+) -> Response {
+    let status_filter = match parse_page_status(form.status_filter.as_deref()) {
+        Ok(status) => status,
+        Err(err) => return err.into_response(),
+    };
+
+    let filter =
+        shared::build_page_filter(form.filter_search.as_deref(), form.filter_month.as_deref());
+    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
+
+    let message = match state.pages.duplicate_page(&session.username, id).await {
+        Ok(page) => Toast::success(format!(
+            "Created draft \"{}\" at /{}",
+            page.title, page.slug
         )),
-        Err(err) => Toast::error(format!("Failed to update page status: {}", err)),
+        Err(err) => Toast::error(format!("Failed to duplicate page: {}", err)),
     };
 
     shared::respond_with_pages_panel_message(
-        state,
+        &state,
         status_filter,
         &filter,
         &cursor_state,
         message,
-        error_source,
-        template_source,
+        "infra::http::admin_page_duplicate",
+        "infra::http::admin_page_duplicate",
     )
     .await
 }
@@ -14,6 +14,38 @@ pub(super) fn build_page_filter(search: Option<&str>, month: Option<&str>) -> Pa
     }
 }
 
+/// Warning for visible navigation items left linking to `page` after it was
+/// unpublished; the public menu drops them until it is published again.
+pub(super) async fn hidden_navigation_toast(
+    state: &AdminState,
+    page: &PageRecord,
+) -> Option<Toast> {
+    if page.status == PageStatus::Published {
+        return None;
+    }
+    let links = match state.pages.navigation_links(page.id).await {
+        Ok(links) => links,
+        Err(err) => {
+            warn!(
+                target = "infra::http::admin::pages",
+                slug = page.slug,
+                error = %err,
+                "failed to check navigation links to page"
+            );
+            return None;
+        }
+    };
+    if links.is_empty() {
+        return None;
+    }
+    let labels: Vec<&str> = links.iter().map(|item| item.label.as_str()).collect();
+    Some(Toast::warning(format!(
+        "Navigation still links to \"{}\" ({}); these items are hidden from the menu until the page is published again",
+        page.title,
+        labels.join(", "),
+    )))
+}
+
 pub(super) async fn respond_with_pages_panel_message(
     state: &AdminState,
     status_filter: Option<PageStatus>,
@@ -36,7 +68,7 @@ pub(super) async fn respond_with_pages_panel_message(
     .await
 }
 
-async fn respond_with_pages_panel_with_state(
+pub(super) async fn respond_with_pages_panel_with_state(
     state: &AdminState,
     status_filter: Option<PageStatus>,
     filter: &PageQueryFilter,
@@ -45,12 +77,21 @@ async fn respond_with_pages_panel_with_state(
     error_source: &'static str,
     template_source: &'static str,
 ) -> Response {
-    let cursor = match cursor_state.decode_with(PageCursor::decode, error_source) {
-        Ok(cursor) => cursor,
-        Err(err) => return err.into_response(),
-    };
+    let cursor =
+        match cursor_state.decode_with(state.db.cursor_keys(), PageCursor::decode, error_source) {
+            Ok(cursor) => cursor,
+            Err(err) => return err.into_response(),
+        };
 
-    let mut content = match build_page_list_view(state, status_filter, filter, cursor).await {
+    let mut content = match build_page_list_view(
+        state,
+        status_filter,
+        filter,
+        cursor,
+        cursor_state.limit(),
+    )
+    .await
+    {
         Ok(content) => content,
         Err(err) => return admin_page_error(error_source, err).into_response(),
     };
@@ -6,8 +6,8 @@ use crate::{
         pagination::PageCursor,
         repos::{PageQueryFilter, SettingsRepo},
     },
-    domain::types::PageStatus,
-    infra::http::admin::{AdminState, shared::template_render_http_error},
+    domain::{routes::join_url, types::PageStatus},
+    infra::http::admin::{AdminState, pagination, shared::template_render_http_error},
     presentation::admin::views as admin_views,
 };
 use url::form_urlencoded::Serializer;
@@ -22,10 +22,13 @@ pub(crate) async fn build_page_list_view(
     status: Option<PageStatus>,
     filter: &PageQueryFilter,
     cursor: Option<PageCursor>,
+    limit: Option<u32>,
 ) -> Result<admin_views::AdminPageListView, AdminPageError> {
     let settings = state.db.load_site_settings().await?;
-    let admin_page_size = settings.admin_page_size.clamp(1, 100).max(1) as u32;
-    let public_site_url = normalize_public_site_url(&settings.public_site_url);
+    let admin_page_size = pagination::page_size(settings.admin_page_size, limit);
+    let public_site_url = state
+        .base_path
+        .site_url_or_relative(&settings.public_site_url);
 
     let counts_filter = filter.clone();
     let mut month_filter = filter.clone();
@@ -65,8 +68,9 @@ pub(crate) async fn build_page_list_view(
                 display_time,
                 display_time_kind,
                 actions: page_actions_for_status(record.status),
-                preview_href: format!("{}pages/_preview/{}", public_site_url, record.id),
+                preview_href: join_url(&public_site_url, &format!("pages/_preview/{}", record.id)),
                 edit_href: format!("/pages/{}/edit", record.id),
+                pin_order: 0,
                 snapshots_href: Some(format!("/pages/{}/snapshots", record.id)),
             }
         })
@@ -118,15 +122,19 @@ pub(crate) async fn build_page_list_view(
         tag_filter_enabled: false,
         month_filter_enabled: true,
         row_action_prefix: "/pages".to_string(),
-        custom_hidden_fields: build_page_hidden_fields(filter),
+        custom_hidden_fields: build_page_hidden_fields(filter, limit),
     })
 }
 
-fn build_page_hidden_fields(filter: &PageQueryFilter) -> Vec<admin_views::AdminHiddenField> {
+fn build_page_hidden_fields(
+    filter: &PageQueryFilter,
+    limit: Option<u32>,
+) -> Vec<admin_views::AdminHiddenField> {
     let mut fields = Vec::new();
     if let Some(ref month) = filter.month {
         fields.push(admin_views::AdminHiddenField::new("month", month.clone()));
     }
+    fields.extend(pagination::limit_hidden_field(limit));
     fields
 }
 
@@ -150,7 +158,7 @@ pub(crate) async fn build_page_panel_html(
     error_source: &'static str,
     template_source: &'static str,
 ) -> Result<String, crate::application::error::HttpError> {
-    let content = build_page_list_view(state, status, filter, None)
+    let content = build_page_list_view(state, status, filter, None, None)
         .await
         .map_err(|err| admin_page_error(error_source, err))?;
 
@@ -181,18 +189,20 @@ fn page_actions_for_status(status: PageStatus) -> Vec<admin_views::AdminPostRowA
             is_danger: false,
         });
     }
+    actions.push(admin_views::AdminPostRowActionView {
+        value: "rerender",
+        label: "Re-render",
+        is_danger: false,
+    });
+    actions.push(admin_views::AdminPostRowActionView {
+        value: "duplicate",
+        label: "Duplicate",
+        is_danger: false,
+    });
 
     actions
 }
 
-fn normalize_public_site_url(url: &str) -> String {
-    if url.ends_with('/') {
-        url.to_string()
-    } else {
-        format!("{url}/")
-    }
-}
-
 fn time_column_label(status: Option<PageStatus>) -> String {
     match status {
         Some(PageStatus::Published) => "Published".to_string(),
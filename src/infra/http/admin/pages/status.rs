@@ -3,7 +3,7 @@ use axum::http::StatusCode;
 use crate::{
     application::{admin::pages::AdminPageStatusCounts, error::HttpError},
     domain::types::PageStatus,
-    presentation::admin::views as admin_views,
+    presentation::{admin::views as admin_views, page_templates::PAGE_TEMPLATES},
 };
 
 pub(crate) fn parse_page_status(value: Option<&str>) -> Result<Option<PageStatus>, HttpError> {
@@ -97,3 +97,14 @@ pub(crate) fn page_status_options(selected: PageStatus) -> Vec<admin_views::Admi
     )
     .collect()
 }
+
+pub(crate) fn page_template_options(selected: &str) -> Vec<admin_views::AdminPageTemplateOption> {
+    PAGE_TEMPLATES
+        .iter()
+        .map(|&(value, label)| admin_views::AdminPageTemplateOption {
+            value,
+            label,
+            selected: value == selected,
+        })
+        .collect()
+}
@@ -1,9 +1,30 @@
 use axum::http::StatusCode;
 
+use crate::application::admin::settings::{MAX_ADMIN_PAGE_SIZE, MIN_ADMIN_PAGE_SIZE};
 use crate::application::error::HttpError;
+use crate::application::pagination::CursorKeys;
+use crate::infra::http::admin::shared::ListLimit;
+use crate::presentation::admin::views::AdminHiddenField;
 
 pub(crate) const CURSOR_ROOT_TOKEN: &str = "~";
 
+/// Rows per admin list page: the request's `limit` when one was sent,
+/// otherwise the `admin_page_size` setting, clamped to the range the settings
+/// editor accepts.
+pub(crate) fn page_size(admin_page_size: i32, limit: Option<u32>) -> u32 {
+    let size = limit.map_or(i64::from(admin_page_size), i64::from);
+    size.clamp(
+        i64::from(MIN_ADMIN_PAGE_SIZE),
+        i64::from(MAX_ADMIN_PAGE_SIZE),
+    ) as u32
+}
+
+/// Hidden `limit` input carried by the pagination, status and filter forms so
+/// a per-request page size survives paging through the cursor trail.
+pub(crate) fn limit_hidden_field(limit: Option<u32>) -> Option<AdminHiddenField> {
+    limit.map(|limit| AdminHiddenField::new("limit", limit.to_string()))
+}
+
 pub(crate) fn parse_cursor_history(raw: Option<&str>) -> Vec<String> {
     raw.unwrap_or("")
         .split('.')
@@ -37,15 +58,16 @@ pub(crate) fn join_cursor_history(tokens: &[String]) -> Option<String> {
 
 pub(crate) fn decode_cursor_param<T, F, E>(
     raw: Option<&str>,
+    keys: &CursorKeys,
     decoder: F,
     source: &'static str,
 ) -> Result<Option<T>, HttpError>
 where
-    F: Fn(&str) -> Result<T, E>,
+    F: Fn(&CursorKeys, &str) -> Result<T, E>,
     E: std::fmt::Display,
 {
     match raw {
-        Some(value) if !value.is_empty() => decoder(value).map(Some).map_err(|err| {
+        Some(value) if !value.is_empty() => decoder(keys, value).map(Some).map_err(|err| {
             HttpError::new(
                 source,
                 StatusCode::BAD_REQUEST,
@@ -61,6 +83,7 @@ where
 pub(crate) struct CursorState {
     history: Vec<String>,
     current: Option<String>,
+    limit: Option<u32>,
 }
 
 impl CursorState {
@@ -68,9 +91,20 @@ impl CursorState {
         Self {
             history: parse_cursor_history(trail.as_deref()),
             current,
+            limit: None,
         }
     }
 
+    /// Attach the request's `limit` override, if it sent a usable one.
+    pub(crate) fn with_limit(mut self, limit: &ListLimit) -> Self {
+        self.limit = limit.rows();
+        self
+    }
+
+    pub(crate) fn limit(&self) -> Option<u32> {
+        self.limit
+    }
+
     pub(crate) fn history_tokens(&self) -> &[String] {
         &self.history
     }
@@ -89,13 +123,72 @@ impl CursorState {
 
     pub(crate) fn decode_with<T, F, E>(
         &self,
+        keys: &CursorKeys,
         decoder: F,
         source: &'static str,
     ) -> Result<Option<T>, HttpError>
     where
-        F: Fn(&str) -> Result<T, E>,
+        F: Fn(&CursorKeys, &str) -> Result<T, E>,
         E: std::fmt::Display,
     {
-        decode_cursor_param(self.current.as_deref(), decoder, source)
+        decode_cursor_param(self.current.as_deref(), keys, decoder, source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::Query;
+    use axum::response::IntoResponse;
+
+    use super::*;
+    use crate::application::pagination::PostCursor;
+    use crate::infra::http::admin::shared::AdminPostQuery;
+
+    #[test]
+    fn page_size_prefers_request_limit_and_clamps() {
+        assert_eq!(page_size(20, None), 20);
+        assert_eq!(page_size(20, Some(50)), 50);
+        assert_eq!(page_size(1, None), 5);
+        assert_eq!(page_size(20, Some(1_000)), 200);
+        assert_eq!(page_size(-3, None), 5);
+    }
+
+    #[test]
+    fn limit_is_parsed_leniently_and_kept_clamped() {
+        let limit = |raw: &str| {
+            let uri = format!("/posts?status=draft&limit={raw}")
+                .parse()
+                .expect("uri");
+            let Query(query) = Query::<AdminPostQuery>::try_from_uri(&uri).expect("list query");
+            CursorState::default().with_limit(&query.limit).limit()
+        };
+        assert_eq!(limit("+25+"), Some(25));
+        assert_eq!(limit(""), None);
+        assert_eq!(limit("ten"), None);
+        assert_eq!(limit("500"), Some(200));
+        assert_eq!(limit("1"), Some(5));
+        assert_eq!(
+            CursorState::default()
+                .with_limit(&ListLimit::default())
+                .limit(),
+            None
+        );
+
+        let field = limit_hidden_field(Some(200)).expect("hidden field");
+        assert_eq!(field.name, "limit");
+        assert_eq!(field.value, "200");
+        assert!(limit_hidden_field(None).is_none());
+    }
+
+    #[test]
+    fn undecodable_cursors_are_bad_requests() {
+        let keys = CursorKeys::ephemeral();
+        let err = decode_cursor_param(Some("tampered"), &keys, PostCursor::decode, "test")
+            .expect_err("tampered cursor rejected");
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+
+        let none =
+            decode_cursor_param(Some(""), &keys, PostCursor::decode, "test").expect("no cursor");
+        assert!(none.is_none());
     }
 }
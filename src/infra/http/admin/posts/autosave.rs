@@ -0,0 +1,299 @@
+//! Editor autosave: keeps the form in `post_drafts` without touching the post.
+
+use askama::Template;
+use axum::{
+    Extension,
+    extract::{Form, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use chrono_tz::Tz;
+use time::OffsetDateTime;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        admin::{auth::AdminSession, posts::editable_excerpt},
+        error::HttpError,
+        repos::SettingsRepo,
+    },
+    domain::entities::PostRecord,
+    infra::{
+        db::{PostDraft, PostDraftContent, PostDraftKey},
+        http::admin::{
+            AdminState,
+            selectors::{POST_AUTOSAVE_RESTORE, POST_AUTOSAVE_STATUS},
+            shared::{blank_to_none_opt, datastar_replace, template_render_http_error},
+        },
+    },
+    presentation::admin::views as admin_views,
+};
+
+use super::errors::admin_post_error;
+use super::forms::AdminPostAutosaveForm;
+
+const SOURCE: &str = "infra::http::admin_post_autosave";
+
+pub(crate) async fn admin_post_autosave(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Path(id): Path<Uuid>,
+    Form(form): Form<AdminPostAutosaveForm>,
+) -> Response {
+    match state.posts.load_post(id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpError::new(
+                SOURCE,
+                StatusCode::NOT_FOUND,
+                "Post not found",
+                format!("Post `{id}` could not be found"),
+            )
+            .into_response();
+        }
+        Err(err) => return admin_post_error(SOURCE, err).into_response(),
+    }
+
+    store_autosave(&state, PostDraftKey::Post(id), &session.username, form).await
+}
+
+pub(crate) async fn admin_post_autosave_new(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Form(form): Form<AdminPostAutosaveForm>,
+) -> Response {
+    let author = session.username.as_str();
+    store_autosave(&state, PostDraftKey::NewPost(author), author, form).await
+}
+
+pub(crate) async fn admin_post_autosave_discard(
+    State(state): State<AdminState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    discard_autosave(&state, PostDraftKey::Post(id)).await
+}
+
+pub(crate) async fn admin_post_autosave_discard_new(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+) -> Response {
+    discard_autosave(&state, PostDraftKey::NewPost(&session.username)).await
+}
+
+async fn store_autosave(
+    state: &AdminState,
+    key: PostDraftKey<'_>,
+    author: &str,
+    form: AdminPostAutosaveForm,
+) -> Response {
+    let content = form.into_content();
+    if matches!(key, PostDraftKey::NewPost(_)) && content.is_blank() {
+        return StatusCode::NO_CONTENT.into_response();
+    }
+
+    let saved_at = match state.db.save_post_draft(key, author, &content).await {
+        Ok(saved_at) => saved_at,
+        Err(err) => return admin_post_error(SOURCE, err.into()).into_response(),
+    };
+    let tz = match state.db.load_site_settings().await {
+        Ok(settings) => settings.timezone,
+        Err(err) => return admin_post_error(SOURCE, err.into()).into_response(),
+    };
+
+    let template = admin_views::AdminPostAutosaveStatusTemplate {
+        saved_at: admin_views::format_timestamp(saved_at, tz),
+    };
+    match template.render() {
+        Ok(html) => datastar_replace(POST_AUTOSAVE_STATUS, html).into_response(),
+        Err(err) => {
+            template_render_http_error(SOURCE, "Template rendering failed", err).into_response()
+        }
+    }
+}
+
+async fn discard_autosave(state: &AdminState, key: PostDraftKey<'_>) -> Response {
+    if let Err(err) = state.db.clear_post_draft(key).await {
+        return admin_post_error(SOURCE, err.into()).into_response();
+    }
+
+    let template = admin_views::AdminPostAutosaveTemplate { autosave: None };
+    match template.render() {
+        Ok(html) => datastar_replace(POST_AUTOSAVE_RESTORE, html).into_response(),
+        Err(err) => {
+            template_render_http_error(SOURCE, "Template rendering failed", err).into_response()
+        }
+    }
+}
+
+/// Drop the autosave once the editor's content has really been saved. A
+/// failure only leaves a stale offer behind, so it is logged, not returned.
+pub(super) async fn clear_autosave(state: &AdminState, key: PostDraftKey<'_>) {
+    if let Err(err) = state.db.clear_post_draft(key).await {
+        warn!(target = SOURCE, error = %err, "failed to clear post autosave");
+    }
+}
+
+/// Offer the autosave for `post` (or the new-post editor when `None`) above
+/// the editor, and load it into the form when `restore` is set.
+pub(super) async fn apply_autosave(
+    state: &AdminState,
+    view: &mut admin_views::AdminPostEditorView,
+    key: PostDraftKey<'_>,
+    post: Option<&PostRecord>,
+    restore: bool,
+    tz: Tz,
+) -> Result<(), HttpError> {
+    let draft = state
+        .db
+        .load_post_draft(key)
+        .await
+        .map_err(|err| admin_post_error(SOURCE, err.into()))?;
+    let Some(draft) = draft else {
+        return Ok(());
+    };
+    let saved = post.map(|post| (saved_content(post), post.updated_at));
+    if !offers_restore(&draft, saved.as_ref().map(|(content, at)| (content, *at))) {
+        return Ok(());
+    }
+
+    let editor_href = match post {
+        Some(post) => format!("/posts/{}/edit", post.id),
+        None => "/posts/new".to_string(),
+    };
+    view.autosave = Some(admin_views::AdminPostAutosaveView {
+        saved_at: admin_views::format_timestamp(draft.saved_at, tz),
+        restore_href: format!("{editor_href}?restore=true"),
+        discard_action: format!("{}/discard", view.autosave_action),
+        restored: restore,
+    });
+
+    if restore {
+        let content = draft.content;
+        view.title = content.title;
+        view.slug = content.slug;
+        view.excerpt = content.excerpt;
+        view.body_markdown = content.body_markdown;
+        view.summary_markdown = content.summary_markdown;
+    }
+    Ok(())
+}
+
+/// The editor fields as `post` fills them when opened.
+fn saved_content(post: &PostRecord) -> PostDraftContent {
+    PostDraftContent {
+        title: post.title.clone(),
+        slug: post.slug.clone(),
+        excerpt: editable_excerpt(post),
+        body_markdown: post.body_markdown.clone(),
+        summary_markdown: post.summary_markdown.clone(),
+    }
+}
+
+/// An autosave is worth offering when it differs from the saved post and
+/// was taken after the post was last saved; for a new post, whenever
+/// anything was typed.
+fn offers_restore(draft: &PostDraft, saved: Option<(&PostDraftContent, OffsetDateTime)>) -> bool {
+    match saved {
+        Some((content, updated_at)) => draft.saved_at > updated_at && draft.content != *content,
+        None => !draft.content.is_blank(),
+    }
+}
+
+impl AdminPostAutosaveForm {
+    /// Normalised the way a real save stores the fields.
+    fn into_content(self) -> PostDraftContent {
+        PostDraftContent {
+            title: self.title.trim().to_string(),
+            slug: self.slug.unwrap_or_default().trim().to_string(),
+            excerpt: self.excerpt.trim().to_string(),
+            body_markdown: self.body_markdown.trim().to_string(),
+            summary_markdown: blank_to_none_opt(self.summary_markdown),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    fn content(body: &str) -> PostDraftContent {
+        PostDraftContent {
+            title: "Title".into(),
+            slug: "title".into(),
+            excerpt: String::new(),
+            body_markdown: body.into(),
+            summary_markdown: None,
+        }
+    }
+
+    fn draft(body: &str, saved_at: OffsetDateTime) -> PostDraft {
+        PostDraft {
+            content: content(body),
+            author: "admin".into(),
+            saved_at,
+        }
+    }
+
+    #[test]
+    fn offers_a_newer_autosave_that_differs_from_the_post() {
+        let saved_at = OffsetDateTime::now_utc();
+        let post = content("saved body");
+
+        assert!(offers_restore(
+            &draft("edited body", saved_at),
+            Some((&post, saved_at - Duration::minutes(5)))
+        ));
+    }
+
+    #[test]
+    fn skips_an_autosave_older_than_the_post_or_matching_it() {
+        let saved_at = OffsetDateTime::now_utc();
+        let post = content("saved body");
+
+        assert!(!offers_restore(
+            &draft("edited body", saved_at),
+            Some((&post, saved_at + Duration::minutes(5)))
+        ));
+        assert!(!offers_restore(
+            &draft("saved body", saved_at),
+            Some((&post, saved_at - Duration::minutes(5)))
+        ));
+    }
+
+    #[test]
+    fn offers_a_new_post_autosave_unless_blank() {
+        let saved_at = OffsetDateTime::now_utc();
+
+        assert!(offers_restore(&draft("typed", saved_at), None));
+        let blank = PostDraft {
+            content: PostDraftContent::default(),
+            author: "admin".into(),
+            saved_at,
+        };
+        assert!(!offers_restore(&blank, None));
+    }
+
+    #[test]
+    fn autosave_form_is_normalised_like_a_save() {
+        let form = AdminPostAutosaveForm {
+            title: "  Title ".into(),
+            slug: None,
+            excerpt: " ".into(),
+            body_markdown: "Body\n\n".into(),
+            summary_markdown: Some("   ".into()),
+        };
+
+        assert_eq!(
+            form.into_content(),
+            PostDraftContent {
+                title: "Title".into(),
+                slug: String::new(),
+                excerpt: String::new(),
+                body_markdown: "Body".into(),
+                summary_markdown: None,
+            }
+        );
+    }
+}
@@ -1,14 +1,21 @@
 //! Bulk action handlers for posts.
 
 use axum::{
+    Extension,
     extract::{Form, State},
     response::{IntoResponse, Response},
 };
 use time::OffsetDateTime;
+use uuid::Uuid;
 
 use crate::{
-    application::admin::posts::UpdatePostStatusCommand, domain::types::PostStatus,
-    infra::http::admin::AdminState, infra::http::admin::shared::Toast,
+    application::admin::auth::AdminSession,
+    application::admin::posts::UpdatePostStatusCommand,
+    application::repos::{PostListScope, PostQueryFilter, PostsRepo},
+    domain::types::PostStatus,
+    infra::http::admin::AdminState,
+    infra::http::admin::bulk::{self, BATCH_SIZE, BulkAction, SELECT_ALL_CAP},
+    infra::http::admin::shared::Toast,
 };
 
 use super::forms::AdminPostBulkActionForm;
@@ -16,37 +23,11 @@ use super::response::respond_with_posts_panel;
 use super::status::parse_post_status;
 use super::utils::build_post_filter;
 
-#[derive(Clone, Copy)]
-pub(super) enum BulkAction {
-    Publish,
-    Draft,
-    Archive,
-    Delete,
-}
-
-impl BulkAction {
-    pub(super) fn from_str(action: &str) -> Option<Self> {
-        match action {
-            "publish" => Some(Self::Publish),
-            "draft" => Some(Self::Draft),
-            "archive" => Some(Self::Archive),
-            "delete" => Some(Self::Delete),
-            _ => None,
-        }
-    }
-
-    pub(super) fn label(self) -> &'static str {
-        match self {
-            BulkAction::Publish => "Publish",
-            BulkAction::Draft => "Move to Draft",
-            BulkAction::Archive => "Archive",
-            BulkAction::Delete => "Delete",
-        }
-    }
-}
+const SOURCE: &str = "infra::http::admin_posts_bulk_action";
 
 pub(crate) async fn admin_posts_bulk_action(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Form(form): Form<AdminPostBulkActionForm>,
 ) -> Response {
     let status_filter = match parse_post_status(form.status_filter.as_deref()) {
@@ -61,121 +42,136 @@ pub(crate) async fn admin_posts_bulk_action(
     );
 
     let Some(action) = BulkAction::from_str(form.action.trim()) else {
-        let messages = [Toast::error("Select a valid bulk action")];
-        return respond_with_posts_panel(
+        return respond(
             &state,
             status_filter,
             &filter,
-            &messages,
-            "infra::http::admin_posts_bulk_action",
-            "infra::http::admin_posts_bulk_action",
+            &[Toast::error("Select a valid bulk action")],
         )
         .await;
     };
 
-    if form.ids.is_empty() {
-        let messages = [Toast::error("Select at least one post")];
-        return respond_with_posts_panel(
+    let ids = if bulk::select_all_requested(form.select_all_matching.as_deref()) {
+        match select_all_matching(&state, status_filter, &filter, action, &form).await {
+            Ok(ids) if ids.is_empty() => {
+                return respond(
+                    &state,
+                    status_filter,
+                    &filter,
+                    &[Toast::error("No posts match these filters")],
+                )
+                .await;
+            }
+            Ok(ids) => ids,
+            Err(toast) => return respond(&state, status_filter, &filter, &[toast]).await,
+        }
+    } else if form.ids.is_empty() {
+        return respond(
             &state,
             status_filter,
             &filter,
-            &messages,
-            "infra::http::admin_posts_bulk_action",
-            "infra::http::admin_posts_bulk_action",
+            &[Toast::error("Select at least one post")],
         )
         .await;
-    }
+    } else {
+        form.ids.clone()
+    };
 
-    let actor = "admin";
-    let mut successes = 0usize;
-    let mut failures: Vec<String> = Vec::new();
+    let actor = session.username.as_str();
+    let outcome = bulk::apply_in_batches(&ids, BATCH_SIZE, |id| {
+        apply_to_post(&state, actor, action, id)
+    })
+    .await;
 
-    for id in form.ids.iter().copied() {
-        let post = match state.posts.load_post(id).await {
-            Ok(Some(post)) => post,
-            Ok(None) => {
-                failures.push(format!("Post `{id}` not found"));
-                continue;
-            }
-            Err(err) => {
-                failures.push(format!("Failed to load `{id}`: {err}"));
-                continue;
-            }
-        };
-
-        let result = match action {
-            BulkAction::Delete => state
-                .posts
-                .delete_post(actor, post.id, &post.slug)
-                .await
-                .map(|_| ()),
-            BulkAction::Publish => {
-                let command = UpdatePostStatusCommand {
-                    id: post.id,
-                    status: PostStatus::Published,
-                    scheduled_at: post.scheduled_at,
-                    published_at: Some(OffsetDateTime::now_utc()),
-                    archived_at: post.archived_at,
-                };
-                state.posts.update_status(actor, command).await.map(|_| ())
-            }
-            BulkAction::Draft => {
-                let command = UpdatePostStatusCommand {
-                    id: post.id,
-                    status: PostStatus::Draft,
-                    scheduled_at: None,
-                    published_at: None,
-                    archived_at: None,
-                };
-                state.posts.update_status(actor, command).await.map(|_| ())
-            }
-            BulkAction::Archive => {
-                let command = UpdatePostStatusCommand {
-                    id: post.id,
-                    status: PostStatus::Archived,
-                    scheduled_at: post.scheduled_at,
-                    published_at: post.published_at,
-                    archived_at: Some(OffsetDateTime::now_utc()),
-                };
-                state.posts.update_status(actor, command).await.map(|_| ())
-            }
-        };
+    let messages = bulk::outcome_toasts(action.label(), "post", &outcome);
+    respond(&state, status_filter, &filter, &messages).await
+}
 
-        match result {
-            Ok(_) => successes += 1,
-            Err(err) => failures.push(format!("{} ({})", post.title, err)),
-        }
+async fn respond(
+    state: &AdminState,
+    status_filter: Option<PostStatus>,
+    filter: &PostQueryFilter,
+    messages: &[Toast],
+) -> Response {
+    respond_with_posts_panel(state, status_filter, filter, messages, SOURCE, SOURCE).await
+}
+
+/// Every post matching the panel filters, refusing more than [`SELECT_ALL_CAP`]
+/// and an unconfirmed delete.
+async fn select_all_matching(
+    state: &AdminState,
+    status: Option<PostStatus>,
+    filter: &PostQueryFilter,
+    action: BulkAction,
+    form: &AdminPostBulkActionForm,
+) -> Result<Vec<Uuid>, Toast> {
+    if matches!(action, BulkAction::Delete) {
+        bulk::check_delete_confirmation(form.confirm.as_deref(), "post")?;
     }
 
-    let message = if failures.is_empty() {
-        Toast::success(format!(
-            "{} applied to {} post{}",
-            action.label(),
-            successes,
-            if successes == 1 { "" } else { "s" }
-        ))
-    } else {
-        let sample = failures
-            .first()
-            .cloned()
-            .unwrap_or_else(|| "unknown error".to_string());
-        Toast::error(format!(
-            "{} succeeded, {} failed (e.g. {})",
-            successes,
-            failures.len(),
-            sample
-        ))
+    let matched = state
+        .db
+        .count_posts(PostListScope::Admin { status }, filter)
+        .await
+        .map_err(|err| Toast::error(format!("Failed to count matching posts: {err}")))?;
+    bulk::check_select_all_cap(matched, SELECT_ALL_CAP, "post")?;
+
+    state
+        .db
+        .list_admin_post_ids(status, filter, SELECT_ALL_CAP)
+        .await
+        .map_err(|err| Toast::error(format!("Failed to load matching posts: {err}")))
+}
+
+async fn apply_to_post(
+    state: &AdminState,
+    actor: &str,
+    action: BulkAction,
+    id: Uuid,
+) -> Result<(), String> {
+    let post = match state.posts.load_post(id).await {
+        Ok(Some(post)) => post,
+        Ok(None) => return Err(format!("Post `{id}` not found")),
+        Err(err) => return Err(format!("Failed to load `{id}`: {err}")),
+    };
+
+    let result = match action {
+        BulkAction::Delete => state
+            .posts
+            .delete_post(actor, post.id, &post.slug)
+            .await
+            .map(|_| ()),
+        BulkAction::Publish => {
+            let command = UpdatePostStatusCommand {
+                id: post.id,
+                status: PostStatus::Published,
+                scheduled_at: post.scheduled_at,
+                published_at: Some(OffsetDateTime::now_utc()),
+                archived_at: post.archived_at,
+            };
+            state.posts.update_status(actor, command).await.map(|_| ())
+        }
+        BulkAction::Draft => {
+            let command = UpdatePostStatusCommand {
+                id: post.id,
+                status: PostStatus::Draft,
+                scheduled_at: None,
+                published_at: None,
+                archived_at: None,
+            };
+            state.posts.update_status(actor, command).await.map(|_| ())
+        }
+        BulkAction::Archive => {
+            let command = UpdatePostStatusCommand {
+                id: post.id,
+                status: PostStatus::Archived,
+                scheduled_at: post.scheduled_at,
+                published_at: post.published_at,
+                archived_at: Some(OffsetDateTime::now_utc()),
+            };
+            state.posts.update_status(actor, command).await.map(|_| ())
+        }
     };
 
-    let messages = [message];
-
-    respond_with_posts_panel(
-        &state,
-        status_filter,
-        &filter,
-        &messages,
-        "infra::http::admin_posts_bulk_action",
-        "infra::http::admin_posts_bulk_action",
-    )
-    .await
+    result.map_err(|err| format!("{} ({})", post.title, err))
 }
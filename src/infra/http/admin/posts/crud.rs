@@ -2,13 +2,16 @@
 
 use askama::Template;
 use axum::{
+    Extension,
     extract::{Form, Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
+    application::admin::auth::AdminSession,
     application::{
         admin::posts::{CreatePostCommand, UpdatePostContentCommand, UpdatePostStatusCommand},
         error::HttpError,
@@ -16,18 +19,21 @@ use crate::{
         repos::SettingsRepo,
     },
     domain::types::PostStatus,
+    infra::db::PostDraftKey,
     infra::http::admin::{
         AdminState,
         pagination::CursorState,
         selectors::POSTS_PANEL,
         shared::{
-            Toast, blank_to_none_opt, datastar_replace, push_toasts, template_render_http_error,
+            Toast, blank_to_none_opt, datastar_replace, form_version, push_toasts,
+            template_render_http_error,
         },
     },
     presentation::{admin::views as admin_views, views::render_template_response},
 };
 
-use super::errors::admin_post_error;
+use super::autosave::{apply_autosave, clear_autosave};
+use super::errors::{admin_post_error, slug_field_error_response};
 use super::forms::{AdminPostDeleteForm, AdminPostForm, AdminPostPanelForm};
 use super::pagination::apply_pagination_links;
 use super::panel::{build_post_list_view, render_post_panel_html};
@@ -50,14 +56,19 @@ pub(crate) async fn admin_posts(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(query.cursor.clone(), query.trail.clone());
+    let cursor_state =
+        CursorState::new(query.cursor.clone(), query.trail.clone()).with_limit(&query.limit);
 
     let status = match parse_post_status(query.status.as_deref()) {
         Ok(status) => status,
         Err(err) => return err.into_response(),
     };
 
-    let cursor = match cursor_state.decode_with(PostCursor::decode, "infra::http::admin_posts") {
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        PostCursor::decode,
+        "infra::http::admin_posts",
+    ) {
         Ok(cursor) => cursor,
         Err(err) => return err.into_response(),
     };
@@ -68,10 +79,11 @@ pub(crate) async fn admin_posts(
         query.month.as_deref(),
     );
 
-    let mut content = match build_post_list_view(&state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_post_error("infra::http::admin_posts", err).into_response(),
-    };
+    let mut content =
+        match build_post_list_view(&state, status, &filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => return admin_post_error("infra::http::admin_posts", err).into_response(),
+        };
 
     apply_pagination_links(&mut content, &cursor_state);
 
@@ -88,13 +100,17 @@ pub(crate) async fn admin_posts_panel(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
 
-    let cursor =
-        match cursor_state.decode_with(PostCursor::decode, "infra::http::admin_posts_panel") {
-            Ok(cursor) => cursor,
-            Err(err) => return err.into_response(),
-        };
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        PostCursor::decode,
+        "infra::http::admin_posts_panel",
+    ) {
+        Ok(cursor) => cursor,
+        Err(err) => return err.into_response(),
+    };
 
     let filter = if form.clear.is_some() {
         build_post_filter(None, None, None)
@@ -106,10 +122,13 @@ pub(crate) async fn admin_posts_panel(
         )
     };
 
-    let mut content = match build_post_list_view(&state, status, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_post_error("infra::http::admin_posts_panel", err).into_response(),
-    };
+    let mut content =
+        match build_post_list_view(&state, status, &filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_post_error("infra::http::admin_posts_panel", err).into_response();
+            }
+        };
 
     apply_pagination_links(&mut content, &cursor_state);
 
@@ -121,16 +140,46 @@ pub(crate) async fn admin_posts_panel(
     datastar_replace(POSTS_PANEL, panel_html).into_response()
 }
 
-pub(crate) async fn admin_post_new(State(state): State<AdminState>) -> Response {
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct AdminPostEditQuery {
+    /// Open the editor with its autosave instead of the saved post.
+    pub(crate) restore: bool,
+}
+
+pub(crate) async fn admin_post_new(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Query(query): Query<AdminPostEditQuery>,
+) -> Response {
     let chrome = match state.chrome.load("/posts").await {
         Ok(chrome) => chrome,
         Err(err) => return err.into_response(),
     };
 
-    let content = match build_new_post_editor_view(&state).await {
+    let timezone = match state.db.load_site_settings().await {
+        Ok(settings) => settings.timezone,
+        Err(err) => {
+            return admin_post_error("infra::http::admin_post_new", err.into()).into_response();
+        }
+    };
+
+    let mut content = match build_new_post_editor_view(&state).await {
         Ok(view) => view,
         Err(err) => return err.into_response(),
     };
+    if let Err(err) = apply_autosave(
+        &state,
+        &mut content,
+        PostDraftKey::NewPost(&session.username),
+        None,
+        query.restore,
+        timezone,
+    )
+    .await
+    {
+        return err.into_response();
+    }
     let view = admin_views::AdminLayout::new(chrome, content);
 
     render_template_response(admin_views::AdminPostEditTemplate { view }, StatusCode::OK)
@@ -139,6 +188,7 @@ pub(crate) async fn admin_post_new(State(state): State<AdminState>) -> Response
 pub(crate) async fn admin_post_edit(
     State(state): State<AdminState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<AdminPostEditQuery>,
 ) -> Response {
     let chrome = match state.chrome.load("/posts").await {
         Ok(chrome) => chrome,
@@ -166,10 +216,22 @@ pub(crate) async fn admin_post_edit(
         }
     };
 
-    let content = match build_post_editor_view(&state, &post, timezone).await {
+    let mut content = match build_post_editor_view(&state, &post, timezone).await {
         Ok(view) => view,
         Err(err) => return err.into_response(),
     };
+    if let Err(err) = apply_autosave(
+        &state,
+        &mut content,
+        PostDraftKey::Post(post.id),
+        Some(&post),
+        query.restore,
+        timezone,
+    )
+    .await
+    {
+        return err.into_response();
+    }
 
     let view = admin_views::AdminLayout::new(chrome, content);
     render_template_response(admin_views::AdminPostEditTemplate { view }, StatusCode::OK)
@@ -177,6 +239,7 @@ pub(crate) async fn admin_post_edit(
 
 pub(crate) async fn admin_post_update(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPostForm>,
 ) -> Response {
@@ -203,21 +266,34 @@ pub(crate) async fn admin_post_update(
     let summary_markdown = blank_to_none_opt(form.summary_markdown);
     let pinned = parse_checkbox_flag(&form.pinned);
 
+    let slug = blank_to_none_opt(form.slug).unwrap_or_else(|| post.slug.clone());
+    let expected_updated_at =
+        match form_version("infra::http::admin_post_update", form.version.as_deref()) {
+            Ok(version) => version,
+            Err(err) => return err.into_response(),
+        };
+
     let command = UpdatePostContentCommand {
         id: post.id,
-        slug: post.slug.clone(),
+        slug,
         title: form.title.trim().to_string(),
         excerpt: form.excerpt.trim().to_string(),
         body_markdown: form.body_markdown.trim().to_string(),
         pinned,
         summary_markdown,
+        expected_updated_at,
     };
 
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     let updated = match state.posts.update_post(actor, command).await {
         Ok(post) => post,
-        Err(err) => return admin_post_error("infra::http::admin_post_update", err).into_response(),
+        Err(err) => {
+            if let Some(response) = slug_field_error_response(&err) {
+                return response;
+            }
+            return admin_post_error("infra::http::admin_post_update", err).into_response();
+        }
     };
 
     let final_record = if updated.status != status {
@@ -252,6 +328,7 @@ pub(crate) async fn admin_post_update(
     {
         return admin_post_error("infra::http::admin_post_update", err).into_response();
     }
+    clear_autosave(&state, PostDraftKey::Post(final_record.id)).await;
 
     let content = match build_post_editor_view(&state, &final_record, timezone).await {
         Ok(view) => view,
@@ -296,6 +373,7 @@ pub(crate) async fn admin_post_update(
 
 pub(crate) async fn admin_post_create(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Form(form): Form<AdminPostForm>,
 ) -> Response {
     let status_filter = match parse_post_status(form.status_filter.as_deref()) {
@@ -324,6 +402,7 @@ pub(crate) async fn admin_post_create(
     let tag_ids = parse_tag_state(&form.tag_state);
 
     let command = CreatePostCommand {
+        slug: blank_to_none_opt(form.slug),
         title: title.clone(),
         excerpt: excerpt.clone(),
         body_markdown: body_markdown.clone(),
@@ -335,10 +414,11 @@ pub(crate) async fn admin_post_create(
         archived_at: None,
     };
 
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     match state.posts.create_post(actor, command).await {
         Ok(post) => {
+            clear_autosave(&state, PostDraftKey::NewPost(actor)).await;
             let mut toasts = Vec::new();
             toasts.push(Toast::success(format!("Created post \"{}\"", post.title)));
 
@@ -362,6 +442,9 @@ pub(crate) async fn admin_post_create(
             .await
         }
         Err(err) => {
+            if let Some(response) = slug_field_error_response(&err) {
+                return response;
+            }
             let message = Toast::error(format!("Failed to create post: {}", err));
             respond_with_posts_panel(
                 &state,
@@ -378,6 +461,7 @@ pub(crate) async fn admin_post_create(
 
 pub(crate) async fn admin_post_delete(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPostDeleteForm>,
 ) -> Response {
@@ -394,7 +478,7 @@ pub(crate) async fn admin_post_delete(
 
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
 
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     let post = match state.posts.load_post(id).await {
         Ok(Some(post)) => post,
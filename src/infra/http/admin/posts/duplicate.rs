@@ -0,0 +1,57 @@
+//! Copying a post into a new draft.
+
+use axum::{
+    Extension,
+    extract::{Form, Path, State},
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::application::admin::auth::AdminSession;
+use crate::infra::http::admin::{AdminState, pagination::CursorState, shared::Toast};
+
+use super::forms::AdminPostStatusActionForm;
+use super::response::respond_with_posts_panel_message;
+use super::status::parse_post_status;
+use super::utils::build_post_filter;
+
+const SOURCE: &str = "infra::http::admin_post_duplicate";
+
+pub(crate) async fn admin_post_duplicate(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Path(id): Path<Uuid>,
+    Form(form): Form<AdminPostStatusActionForm>,
+) -> Response {
+    let status_filter = match parse_post_status(form.status_filter.as_deref()) {
+        Ok(status) => status,
+        Err(err) => return err.into_response(),
+    };
+
+    let filter = build_post_filter(
+        form.filter_search.as_deref(),
+        form.filter_tag.as_deref(),
+        form.filter_month.as_deref(),
+    );
+
+    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
+
+    let message = match state.posts.duplicate_post(&session.username, id).await {
+        Ok(post) => Toast::success(format!(
+            "Created draft \"{}\" at /posts/{}",
+            post.title, post.slug
+        )),
+        Err(err) => Toast::error(format!("Failed to duplicate post: {}", err)),
+    };
+
+    respond_with_posts_panel_message(
+        &state,
+        status_filter,
+        &filter,
+        &cursor_state,
+        message,
+        SOURCE,
+        SOURCE,
+    )
+    .await
+}
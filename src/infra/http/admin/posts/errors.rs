@@ -1,7 +1,16 @@
-use axum::http::StatusCode;
+use askama::Template;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
 
 use crate::application::{admin::posts::AdminPostError, error::HttpError};
+use crate::infra::http::admin::{
+    selectors::POST_SLUG_ERROR,
+    shared::{Toast, datastar_replace, push_toasts, template_render_http_error},
+};
 use crate::infra::http::repo_error_to_http;
+use crate::presentation::admin::views as admin_views;
 
 pub(super) fn admin_post_error(source: &'static str, err: AdminPostError) -> HttpError {
     match err {
@@ -11,6 +20,53 @@ pub(super) fn admin_post_error(source: &'static str, err: AdminPostError) -> Htt
             "Post request could not be processed",
             format!("Invalid field `{field}`"),
         ),
+        AdminPostError::InvalidSlug(err) => HttpError::new(
+            source,
+            StatusCode::BAD_REQUEST,
+            "Post request could not be processed",
+            err.to_string(),
+        ),
+        AdminPostError::SlugConflict { slug } => HttpError::new(
+            source,
+            StatusCode::CONFLICT,
+            "Slug already in use",
+            format!("Slug `{slug}` is already used by another post"),
+        ),
         AdminPostError::Repo(repo) => repo_error_to_http(source, repo),
     }
 }
+
+/// Editor response for slug rejections: fills the inline field error and
+/// raises a toast. Other errors return `None` for the caller to handle.
+pub(super) fn slug_field_error_response(err: &AdminPostError) -> Option<Response> {
+    let message = match err {
+        AdminPostError::InvalidSlug(err) => format!("Invalid slug: {err}"),
+        AdminPostError::SlugConflict { slug } => {
+            format!("Slug `{slug}` is already used by another post")
+        }
+        _ => return None,
+    };
+
+    let template = admin_views::AdminPostSlugErrorTemplate {
+        message: Some(message.clone()),
+    };
+    let html = match template.render() {
+        Ok(html) => html,
+        Err(err) => {
+            return Some(
+                template_render_http_error(
+                    "infra::http::admin_post_slug_error",
+                    "Template rendering failed",
+                    err,
+                )
+                .into_response(),
+            );
+        }
+    };
+
+    let mut stream = datastar_replace(POST_SLUG_ERROR, html);
+    if let Err(err) = push_toasts(&mut stream, &[Toast::error(message)]) {
+        return Some(err.into_response());
+    }
+    Some(stream.into_response())
+}
@@ -1,6 +1,8 @@
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::infra::http::admin::shared::ListLimit;
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct AdminPostStatusActionForm {
     pub(crate) status_filter: Option<String>,
@@ -14,6 +16,8 @@ pub(crate) struct AdminPostStatusActionForm {
 #[derive(Debug, Deserialize)]
 pub(crate) struct AdminPostForm {
     pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) slug: Option<String>,
     pub(crate) excerpt: String,
     pub(crate) body_markdown: String,
     pub(crate) summary_markdown: Option<String>,
@@ -25,6 +29,27 @@ pub(crate) struct AdminPostForm {
     pub(crate) tag_state: Option<String>,
     #[serde(default)]
     pub(crate) pinned: Option<String>,
+    /// The editor's `version` of the post; absent when creating one.
+    #[serde(default)]
+    pub(crate) version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminPostPreviewForm {
+    pub(crate) body_markdown: String,
+    #[serde(default)]
+    pub(crate) preview_open: Option<String>,
+}
+
+/// The editor fields an autosave keeps; the rest of the form is ignored.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminPostAutosaveForm {
+    pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) slug: Option<String>,
+    pub(crate) excerpt: String,
+    pub(crate) body_markdown: String,
+    pub(crate) summary_markdown: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,6 +64,8 @@ pub(crate) struct AdminPostDeleteForm {
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct AdminPostPinForm {
+    /// Position among the pinned posts; blank keeps the current one.
+    pub(crate) pin_order: Option<String>,
     pub(crate) status_filter: Option<String>,
     pub(crate) filter_search: Option<String>,
     pub(crate) filter_tag: Option<String>,
@@ -56,6 +83,10 @@ pub(crate) struct AdminPostBulkActionForm {
     pub(crate) filter_search: Option<String>,
     pub(crate) filter_tag: Option<String>,
     pub(crate) filter_month: Option<String>,
+    /// Apply the action to every post matching the filters instead of `ids`.
+    pub(crate) select_all_matching: Option<String>,
+    /// Typed confirmation required to delete every matching post.
+    pub(crate) confirm: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -66,6 +97,8 @@ pub(crate) struct AdminPostPanelForm {
     pub(crate) month: Option<String>,
     pub(crate) cursor: Option<String>,
     pub(crate) trail: Option<String>,
+    #[serde(flatten)]
+    pub(crate) limit: ListLimit,
     pub(crate) clear: Option<String>,
 }
 
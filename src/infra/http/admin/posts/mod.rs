@@ -2,10 +2,15 @@
 //!
 //! This module handles the admin interface for posts with split responsibilities:
 //! - `crud`: List, create, edit, update, delete operations
+//! - `autosave`: Editor autosaves kept apart from the post
 //! - `bulk`: Bulk actions on multiple posts
 //! - `status_actions`: Publish, draft, archive status changes
 //! - `pin`: Pin/unpin operations
 //! - `tags`: Tag selection and toggle
+//! - `preview`: Live preview pane for the editor
+//! - `rerender`: On-demand re-render of a single post
+//! - `duplicate`: Copying a post into a new draft
+//! - `render_progress`: Render-progress badge polling
 //! - `response`: Shared response helper functions
 //! - `pagination`: Cursor pagination helpers
 //! - `utils`: Common utility functions
@@ -15,13 +20,18 @@
 //! - `status`: Status filtering and parsing
 //! - `errors`: Error conversion utilities
 
+mod autosave;
 mod bulk;
 mod crud;
+mod duplicate;
 mod errors;
 mod forms;
 mod pagination;
 mod panel;
 mod pin;
+mod preview;
+mod render_progress;
+mod rerender;
 mod response;
 mod sections;
 mod status;
@@ -30,11 +40,19 @@ mod tags;
 mod utils;
 
 // Re-export all public handlers
+pub(super) use autosave::{
+    admin_post_autosave, admin_post_autosave_discard, admin_post_autosave_discard_new,
+    admin_post_autosave_new,
+};
 pub(super) use bulk::admin_posts_bulk_action;
 pub(super) use crud::{
     admin_post_create, admin_post_delete, admin_post_edit, admin_post_new, admin_post_update,
     admin_posts, admin_posts_panel,
 };
+pub(super) use duplicate::admin_post_duplicate;
 pub(super) use pin::{admin_post_pin, admin_post_unpin};
+pub(super) use preview::{admin_post_preview, admin_post_preview_close};
+pub(super) use render_progress::admin_post_render_progress;
+pub(super) use rerender::admin_post_rerender;
 pub(super) use status_actions::{admin_post_archive, admin_post_move_to_draft, admin_post_publish};
 pub(super) use tags::{admin_post_tags_toggle, admin_post_tags_toggle_new};
@@ -1,4 +1,5 @@
 use askama::Template;
+use futures::future::try_join_all;
 
 use crate::{
     application::{
@@ -7,14 +8,19 @@ use crate::{
         pagination::{PageRequest, PostCursor},
         repos::{PostQueryFilter, SettingsRepo},
     },
-    domain::types::PostStatus,
-    infra::http::admin::{AdminState, pagination::CursorState, shared::template_render_http_error},
+    domain::{routes::join_url, types::PostStatus},
+    infra::http::admin::{
+        AdminState,
+        pagination::{self, CursorState},
+        shared::template_render_http_error,
+    },
     presentation::admin::views as admin_views,
 };
 use url::form_urlencoded::Serializer;
 
 use super::{
     errors::admin_post_error,
+    sections::render_progress_view,
     status::{status_filters, status_key, status_label},
 };
 
@@ -23,16 +29,20 @@ pub(super) async fn build_post_list_view(
     status: Option<PostStatus>,
     filter: &PostQueryFilter,
     cursor: Option<PostCursor>,
+    limit: Option<u32>,
 ) -> Result<admin_views::AdminPostListView, AdminPostError> {
     let settings = state.db.load_site_settings().await?;
-    let admin_page_size = settings.admin_page_size.clamp(1, 100).max(1) as u32;
-    let public_site_url = normalize_public_site_url(&settings.public_site_url);
+    let admin_page_size = pagination::page_size(settings.admin_page_size, limit);
+    let public_site_url = state
+        .base_path
+        .site_url_or_relative(&settings.public_site_url);
 
     let counts_filter = filter.clone();
     let list_filter = filter.clone();
     let month_filter = PostQueryFilter {
         tag: filter.tag.clone(),
         month: None,
+        year: None,
         search: filter.search.clone(),
     };
     let mut tag_count_filter = filter.clone();
@@ -46,11 +56,19 @@ pub(super) async fn build_post_list_view(
         state.posts.tag_counts(status, &tag_count_filter)
     )?;
 
+    let progress = try_join_all(
+        page.items
+            .iter()
+            .map(|post| state.posts.render_progress(post)),
+    )
+    .await?;
+
     let posts = page
         .items
         .into_iter()
-        .map(|post| {
-            let preview_href = format!("{}posts/_preview/{}", public_site_url, post.id);
+        .zip(progress)
+        .map(|(post, progress)| {
+            let preview_href = join_url(&public_site_url, &format!("posts/_preview/{}", post.id));
             let edit_href = format!("/posts/{}/edit", post.id);
 
             let (display_time, display_time_kind) = match post.status {
@@ -79,7 +97,9 @@ pub(super) async fn build_post_list_view(
                 preview_href,
                 edit_href,
                 is_pinned: post.pinned,
+                pin_order: post.pin_order,
                 snapshots_href: Some(format!("/posts/{}/snapshots", post.id)),
+                render_progress: render_progress_view(post.id, &progress),
             }
         })
         .collect();
@@ -142,11 +162,14 @@ pub(super) async fn build_post_list_view(
         tag_filter_enabled: true,
         month_filter_enabled: true,
         row_action_prefix: "/posts".to_string(),
-        custom_hidden_fields: build_hidden_fields(filter),
+        custom_hidden_fields: build_hidden_fields(filter, limit),
     })
 }
 
-fn build_hidden_fields(filter: &PostQueryFilter) -> Vec<admin_views::AdminHiddenField> {
+fn build_hidden_fields(
+    filter: &PostQueryFilter,
+    limit: Option<u32>,
+) -> Vec<admin_views::AdminHiddenField> {
     let mut fields = Vec::new();
     if let Some(ref tag) = filter.tag {
         fields.push(admin_views::AdminHiddenField::new("tag", tag.clone()));
@@ -154,6 +177,7 @@ fn build_hidden_fields(filter: &PostQueryFilter) -> Vec<admin_views::AdminHidden
     if let Some(ref month) = filter.month {
         fields.push(admin_views::AdminHiddenField::new("month", month.clone()));
     }
+    fields.extend(pagination::limit_hidden_field(limit));
     fields
 }
 
@@ -185,7 +209,7 @@ pub(super) async fn build_post_panel_html(
     error_source: &'static str,
     template_source: &'static str,
 ) -> Result<String, HttpError> {
-    let mut content = build_post_list_view(state, status, filter, None)
+    let mut content = build_post_list_view(state, status, filter, None, None)
         .await
         .map_err(|err| admin_post_error(error_source, err))?;
 
@@ -236,14 +260,16 @@ fn post_actions_for_status(
             is_danger: false,
         });
     }
+    actions.push(admin_views::AdminPostRowActionView {
+        value: "rerender",
+        label: "Re-render",
+        is_danger: false,
+    });
+    actions.push(admin_views::AdminPostRowActionView {
+        value: "duplicate",
+        label: "Duplicate",
+        is_danger: false,
+    });
 
     actions
 }
-
-fn normalize_public_site_url(url: &str) -> String {
-    if url.ends_with('/') {
-        url.to_string()
-    } else {
-        format!("{}/", url)
-    }
-}
@@ -1,11 +1,13 @@
 //! Pin action handlers for posts.
 
 use axum::{
+    Extension,
     extract::{Form, Path, State},
     response::{IntoResponse, Response},
 };
 use uuid::Uuid;
 
+use crate::application::admin::auth::AdminSession;
 use crate::infra::http::admin::{AdminState, pagination::CursorState, shared::Toast};
 
 use super::forms::AdminPostPinForm;
@@ -15,11 +17,13 @@ use super::utils::build_post_filter;
 
 pub(crate) async fn admin_post_pin(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPostPinForm>,
 ) -> Response {
     handle_post_pin_action(
         &state,
+        &session.username,
         id,
         form,
         true,
@@ -31,11 +35,13 @@ pub(crate) async fn admin_post_pin(
 
 pub(crate) async fn admin_post_unpin(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPostPinForm>,
 ) -> Response {
     handle_post_pin_action(
         &state,
+        &session.username,
         id,
         form,
         false,
@@ -47,6 +53,7 @@ pub(crate) async fn admin_post_unpin(
 
 async fn handle_post_pin_action(
     state: &AdminState,
+    actor: &str,
     id: Uuid,
     form: AdminPostPinForm,
     should_pin: bool,
@@ -65,8 +72,6 @@ async fn handle_post_pin_action(
     );
 
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
-    let actor = "admin";
-
     let post = match state.posts.load_post(id).await {
         Ok(Some(post)) => post,
         Ok(None) => {
@@ -97,15 +102,36 @@ async fn handle_post_pin_action(
         }
     };
 
-    let message = if post.pinned == should_pin {
+    let pin_order = match parse_pin_order(form.pin_order.as_deref()) {
+        Ok(order) => order,
+        Err(raw) => {
+            let message = Toast::error(format!("`{}` is not a valid pin order", raw));
+            return respond_with_posts_panel_message(
+                state,
+                status_filter,
+                &filter,
+                &cursor_state,
+                message,
+                error_source,
+                template_source,
+            )
+            .await;
+        }
+    };
+
+    let message = if post.pinned == should_pin && pin_order.is_none() {
         let verb = if should_pin { "Pinned" } else { "Unpinned" };
         Toast::success(format!("{} post \"{}\"", verb, post.title))
     } else {
         match state
             .posts
-            .update_pin_state(actor, post.id, should_pin)
+            .update_pin_state(actor, post.id, should_pin, pin_order)
             .await
         {
+            Ok(updated) if post.pinned && updated.pinned => Toast::success(format!(
+                "Moved post \"{}\" to pin position {}",
+                updated.title, updated.pin_order
+            )),
             Ok(updated) => {
                 let verb = if updated.pinned { "Pinned" } else { "Unpinned" };
                 Toast::success(format!("{} post \"{}\"", verb, updated.title))
@@ -125,3 +151,12 @@ async fn handle_post_pin_action(
     )
     .await
 }
+
+/// Parse the submitted pin order: blank means keep the current one, anything
+/// that is not a whole number is returned as the error.
+fn parse_pin_order(raw: Option<&str>) -> Result<Option<i32>, &str> {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        None => Ok(None),
+        Some(value) => value.parse().map(Some).map_err(|_| value),
+    }
+}
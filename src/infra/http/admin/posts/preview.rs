@@ -0,0 +1,107 @@
+//! Live preview pane for the post editor.
+
+use askama::Template;
+use axum::{
+    Extension,
+    extract::{Form, Query, State},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::{
+    application::{
+        admin::auth::AdminSession,
+        render::{PreviewTarget, RenderPreviewCommand, RenderPreviewError},
+        stream::StreamBuilder,
+    },
+    infra::http::admin::{
+        AdminState,
+        selectors::POST_PREVIEW,
+        shared::{Toast, datastar_replace, push_toasts, template_render_http_error},
+    },
+    presentation::admin::views as admin_views,
+};
+
+use super::forms::AdminPostPreviewForm;
+
+/// Shares its rate limit bucket with `POST /render/preview`.
+const RENDER_PREVIEW_ROUTE: &str = "render.preview";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct AdminPostPreviewQuery {
+    /// Sent by keystrokes; only re-renders a pane that is already open.
+    live: bool,
+}
+
+pub(crate) async fn admin_post_preview(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Query(query): Query<AdminPostPreviewQuery>,
+    Form(form): Form<AdminPostPreviewForm>,
+) -> Response {
+    if query.live && form.preview_open.as_deref() != Some("true") {
+        return StreamBuilder::new().into_response();
+    }
+
+    let caller = format!("admin:{}", session.username);
+    if !state.render_preview_debouncer.settle(&caller).await {
+        return StreamBuilder::new().into_response();
+    }
+
+    let (allowed, _) = state
+        .render_preview_rate_limiter
+        .allow(&caller, RENDER_PREVIEW_ROUTE);
+    if !allowed {
+        return toast_response(Toast::error(
+            "Preview rate limit reached; try again in a moment",
+        ));
+    }
+
+    let command = RenderPreviewCommand {
+        markdown: form.body_markdown,
+        target: PreviewTarget::Post,
+        sanitized: true,
+    };
+    let preview = match state.render_preview.preview(command).await {
+        Ok(preview) => preview,
+        Err(RenderPreviewError::TooLarge { limit }) => {
+            return toast_response(Toast::error(format!(
+                "Body is too large to preview (limit {limit} bytes)"
+            )));
+        }
+        Err(RenderPreviewError::Render(err)) => {
+            return toast_response(Toast::error(format!("Preview failed: {err}")));
+        }
+    };
+
+    render_preview_pane(Some(admin_views::AdminPostPreviewView {
+        html: preview.html,
+        mermaid_fallbacks: preview.mermaid_fallbacks,
+    }))
+}
+
+pub(crate) async fn admin_post_preview_close() -> Response {
+    render_preview_pane(None)
+}
+
+fn render_preview_pane(preview: Option<admin_views::AdminPostPreviewView>) -> Response {
+    let template = admin_views::AdminPostPreviewTemplate { preview };
+    match template.render() {
+        Ok(html) => datastar_replace(POST_PREVIEW, html).into_response(),
+        Err(err) => template_render_http_error(
+            "infra::http::admin_post_preview",
+            "Template rendering failed",
+            err,
+        )
+        .into_response(),
+    }
+}
+
+fn toast_response(toast: Toast) -> Response {
+    let mut stream = StreamBuilder::new();
+    if let Err(err) = push_toasts(&mut stream, &[toast]) {
+        return err.into_response();
+    }
+    stream.into_response()
+}
@@ -0,0 +1,55 @@
+//! Render-progress badge, polled by the editor and post list while a job runs.
+
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::application::error::HttpError;
+use crate::infra::http::admin::{
+    AdminState,
+    shared::{datastar_replace, template_render_http_error},
+};
+use crate::presentation::admin::views as admin_views;
+
+use super::errors::admin_post_error;
+use super::sections::render_progress_view;
+
+const SOURCE: &str = "infra::http::admin_post_render_progress";
+
+pub(crate) async fn admin_post_render_progress(
+    State(state): State<AdminState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    let post = match state.posts.load_post(id).await {
+        Ok(Some(post)) => post,
+        Ok(None) => {
+            return HttpError::new(
+                SOURCE,
+                StatusCode::NOT_FOUND,
+                "Post not found",
+                format!("Post `{id}` could not be found"),
+            )
+            .into_response();
+        }
+        Err(err) => return admin_post_error(SOURCE, err).into_response(),
+    };
+
+    let progress = match state.posts.render_progress(&post).await {
+        Ok(progress) => progress,
+        Err(err) => return admin_post_error(SOURCE, err).into_response(),
+    };
+
+    let template = admin_views::AdminRenderProgressTemplate {
+        progress: render_progress_view(post.id, &progress),
+    };
+    match template.render() {
+        Ok(html) => datastar_replace(&format!("#render-progress-{id}"), html).into_response(),
+        Err(err) => {
+            template_render_http_error(SOURCE, "Template rendering failed", err).into_response()
+        }
+    }
+}
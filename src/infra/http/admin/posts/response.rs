@@ -78,12 +78,21 @@ pub(super) async fn respond_with_posts_panel_with_state(
     error_source: &'static str,
     template_source: &'static str,
 ) -> Response {
-    let cursor = match cursor_state.decode_with(PostCursor::decode, error_source) {
-        Ok(cursor) => cursor,
-        Err(err) => return err.into_response(),
-    };
+    let cursor =
+        match cursor_state.decode_with(state.db.cursor_keys(), PostCursor::decode, error_source) {
+            Ok(cursor) => cursor,
+            Err(err) => return err.into_response(),
+        };
 
-    let mut content = match build_post_list_view(state, status_filter, filter, cursor).await {
+    let mut content = match build_post_list_view(
+        state,
+        status_filter,
+        filter,
+        cursor,
+        cursor_state.limit(),
+    )
+    .await
+    {
         Ok(content) => content,
         Err(err) => return admin_post_error(error_source, err).into_response(),
     };
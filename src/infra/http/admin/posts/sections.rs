@@ -6,15 +6,21 @@ use uuid::Uuid;
 
 use crate::{
     application::{
-        admin::tags::AdminTagError, error::HttpError, feed::order_tags_with_pins,
+        admin::{posts::editable_excerpt, tags::AdminTagError},
+        error::HttpError,
+        feed::order_tags_with_pins,
+        jobs::{RenderProgress, RenderProgressState},
+        render::ContentWarning,
         repos::TagWithCount,
     },
     domain::{entities::PostRecord, types::PostStatus},
-    infra::http::admin::AdminState,
+    infra::http::{admin::AdminState, api::etag::version_token},
     presentation::admin::views as admin_views,
 };
 
-use super::status::post_status_options;
+use super::{errors::admin_post_error, status::post_status_options};
+
+const EXCERPT_PLACEHOLDER: &str = "Leave blank to generate from the body";
 
 pub(super) async fn build_post_editor_view(
     state: &AdminState,
@@ -26,11 +32,25 @@ pub(super) async fn build_post_editor_view(
     let selected_ids: Vec<Uuid> = selected_records.iter().map(|tag| tag.id).collect();
 
     let tag_picker = build_tag_picker_view(Some(post.id), &tags_with_counts, &selected_ids);
+    let render_warnings = load_render_warnings(state, post.id).await?;
+    let render_progress = state.posts.render_progress(post).await.map_err(|err| {
+        admin_post_error(
+            "infra::http::admin::posts::sections::build_post_editor_view",
+            err,
+        )
+    })?;
 
     Ok(admin_views::AdminPostEditorView {
         title: post.title.clone(),
+        slug: post.slug.clone(),
+        slug_placeholder: String::new(),
         heading: format!("Edit Post: {}", post.title),
-        excerpt: post.excerpt.clone(),
+        excerpt: editable_excerpt(post),
+        excerpt_placeholder: if post.excerpt_generated {
+            post.excerpt.clone()
+        } else {
+            EXCERPT_PLACEHOLDER.to_string()
+        },
         body_markdown: post.body_markdown.clone(),
         summary_markdown: post.summary_markdown.clone(),
         status: post.status,
@@ -39,10 +59,21 @@ pub(super) async fn build_post_editor_view(
             .published_at
             .map(|time| admin_views::format_timestamp(time, tz)),
         form_action: format!("/posts/{}/edit", post.id),
+        version: Some(version_token(post.updated_at)),
         submit_label: "Save Changes".to_string(),
         enable_live_submit: true,
         tag_picker,
         pinned: post.pinned,
+        render_progress: Some(render_progress_view(post.id, &render_progress)),
+        render_failure: admin_views::AdminRenderFailureView::from_status(
+            post.render_status,
+            post.render_error.as_deref(),
+            post.render_failed_at,
+            tz,
+        ),
+        render_warnings,
+        autosave_action: format!("/posts/{}/autosave", post.id),
+        autosave: None,
     })
 }
 
@@ -56,18 +87,27 @@ pub(super) async fn build_new_post_editor_view(
 
     Ok(admin_views::AdminPostEditorView {
         title: String::new(),
+        slug: String::new(),
+        slug_placeholder: "Derived from the title".to_string(),
         heading: "Create Post".to_string(),
         excerpt: String::new(),
+        excerpt_placeholder: EXCERPT_PLACEHOLDER.to_string(),
         body_markdown: String::new(),
         summary_markdown: None,
         status: PostStatus::Draft,
         status_options: post_status_options(PostStatus::Draft),
         published_at: None,
         form_action: "/posts/create".to_string(),
+        version: None,
         submit_label: "Create Post".to_string(),
         enable_live_submit: true,
         tag_picker,
         pinned: false,
+        render_progress: None,
+        render_failure: None,
+        render_warnings: Vec::new(),
+        autosave_action: "/posts/new/autosave".to_string(),
+        autosave: None,
     })
 }
 
@@ -122,6 +162,24 @@ pub(super) fn build_tag_picker_view(
     }
 }
 
+pub(super) fn render_progress_view(
+    post_id: Uuid,
+    progress: &RenderProgress,
+) -> admin_views::AdminRenderProgressView {
+    admin_views::AdminRenderProgressView {
+        post_id: post_id.to_string(),
+        state_key: progress.state.as_str(),
+        label: match progress.state {
+            RenderProgressState::Pending => "Queued",
+            RenderProgressState::Rendering => "Rendering",
+            RenderProgressState::Rendered => "Rendered",
+            RenderProgressState::Failed => "Render failed",
+        },
+        error: progress.error.clone(),
+        in_flight: progress.state.is_in_flight(),
+    }
+}
+
 pub(super) async fn load_tag_counts(state: &AdminState) -> Result<Vec<TagWithCount>, HttpError> {
     state
         .tags
@@ -130,6 +188,22 @@ pub(super) async fn load_tag_counts(state: &AdminState) -> Result<Vec<TagWithCou
         .map_err(|err| map_tag_error("infra::http::admin::posts::sections::load_tag_counts", err))
 }
 
+async fn load_render_warnings(state: &AdminState, post_id: Uuid) -> Result<Vec<String>, HttpError> {
+    let warnings = state
+        .db
+        .load_post_render_warnings(post_id)
+        .await
+        .map_err(|err| {
+            HttpError::new(
+                "infra::http::admin::posts::sections::load_render_warnings",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to load content warnings",
+                err.to_string(),
+            )
+        })?;
+    Ok(warnings.iter().map(ContentWarning::message).collect())
+}
+
 pub(super) async fn load_selected_tags(
     state: &AdminState,
     post_id: Uuid,
@@ -1,6 +1,7 @@
 //! Status action handlers for posts (publish, draft, archive).
 
 use axum::{
+    Extension,
     extract::{Form, Path, State},
     response::{IntoResponse, Response},
 };
@@ -8,6 +9,7 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::{
+    application::admin::auth::AdminSession,
     application::admin::posts::{AdminPostError, UpdatePostStatusCommand},
     domain::{entities::PostRecord, types::PostStatus},
     infra::http::admin::{AdminState, pagination::CursorState, shared::Toast},
@@ -20,11 +22,13 @@ use super::utils::build_post_filter;
 
 pub(crate) async fn admin_post_publish(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPostStatusActionForm>,
 ) -> Response {
     handle_post_status_action(
         &state,
+        &session.username,
         id,
         form,
         PostStatus::Published,
@@ -36,11 +40,13 @@ pub(crate) async fn admin_post_publish(
 
 pub(crate) async fn admin_post_move_to_draft(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPostStatusActionForm>,
 ) -> Response {
     handle_post_status_action(
         &state,
+        &session.username,
         id,
         form,
         PostStatus::Draft,
@@ -52,11 +58,13 @@ pub(crate) async fn admin_post_move_to_draft(
 
 pub(crate) async fn admin_post_archive(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminPostStatusActionForm>,
 ) -> Response {
     handle_post_status_action(
         &state,
+        &session.username,
         id,
         form,
         PostStatus::Archived,
@@ -68,6 +76,7 @@ pub(crate) async fn admin_post_archive(
 
 async fn handle_post_status_action(
     state: &AdminState,
+    actor: &str,
     id: Uuid,
     form: AdminPostStatusActionForm,
     target_status: PostStatus,
@@ -87,8 +96,6 @@ async fn handle_post_status_action(
 
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
 
-    let actor = "admin";
-
     let post = match state.posts.load_post(id).await {
         Ok(Some(post)) => post,
         Ok(None) => {
@@ -18,6 +18,7 @@ pub(super) fn build_post_filter(
         search: normalize_filter_value(search),
         tag: normalize_filter_value(tag),
         month: normalize_filter_value(month),
+        year: None,
     }
 }
 
@@ -0,0 +1,67 @@
+//! Render preview for the editor, sharing the API endpoint's limits.
+
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::application::admin::auth::AdminSession;
+use crate::application::render::{PreviewTarget, RenderPreviewCommand, RenderPreviewError};
+
+use super::AdminState;
+
+const RENDER_PREVIEW_ROUTE: &str = "render.preview";
+
+#[derive(Debug, Deserialize)]
+pub(super) struct PreviewRequest {
+    markdown: String,
+    target: PreviewTarget,
+    #[serde(default = "default_sanitized")]
+    sanitized: bool,
+}
+
+fn default_sanitized() -> bool {
+    true
+}
+
+pub(super) async fn admin_render_preview(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Json(request): Json<PreviewRequest>,
+) -> Response {
+    let limiter_key = format!("admin:{}", session.username);
+    let (allowed, _) = state
+        .render_preview_rate_limiter
+        .allow(&limiter_key, RENDER_PREVIEW_ROUTE);
+    if !allowed {
+        let retry_after = state.render_preview_rate_limiter.retry_after_secs();
+        let mut response = (
+            StatusCode::TOO_MANY_REQUESTS,
+            "render preview rate limit exceeded",
+        )
+            .into_response();
+        if let Ok(value) = HeaderValue::from_str(&retry_after.to_string()) {
+            response.headers_mut().insert(RETRY_AFTER, value);
+        }
+        return response;
+    }
+
+    let command = RenderPreviewCommand {
+        markdown: request.markdown,
+        target: request.target,
+        sanitized: request.sanitized,
+    };
+
+    match state.render_preview.preview(command).await {
+        Ok(preview) => Json(preview).into_response(),
+        Err(err @ RenderPreviewError::TooLarge { .. }) => {
+            (StatusCode::PAYLOAD_TOO_LARGE, err.to_string()).into_response()
+        }
+        Err(err @ RenderPreviewError::Render(_)) => {
+            (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+        }
+    }
+}
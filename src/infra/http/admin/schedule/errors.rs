@@ -0,0 +1,19 @@
+//! Error conversion utilities for calendar admin handlers.
+
+use axum::http::StatusCode;
+
+use crate::application::{admin::schedule::AdminScheduleError, error::HttpError};
+use crate::infra::http::repo_error_to_http;
+
+/// Convert AdminScheduleError to HttpError for HTTP responses.
+pub(super) fn admin_schedule_error(source: &'static str, err: AdminScheduleError) -> HttpError {
+    match err {
+        AdminScheduleError::InvalidRange | AdminScheduleError::RangeTooLong => HttpError::new(
+            source,
+            StatusCode::BAD_REQUEST,
+            "Invalid calendar range",
+            err.to_string(),
+        ),
+        AdminScheduleError::Repo(repo) => repo_error_to_http(source, repo),
+    }
+}
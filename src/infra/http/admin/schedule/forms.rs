@@ -0,0 +1,32 @@
+//! Form structs for calendar admin handlers.
+
+use serde::Deserialize;
+
+/// Query string of the calendar page.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminScheduleQuery {
+    /// Month to show as `YYYY-MM`; the current month when absent.
+    pub(crate) month: Option<String>,
+}
+
+/// Form for month navigation and for actions that re-render the panel.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminScheduleMonthForm {
+    pub(crate) month: Option<String>,
+}
+
+/// Form for moving a scheduled item to a new publication time.
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminScheduleRescheduleForm {
+    pub(crate) month: Option<String>,
+    /// Local time in the site timezone, as sent by a `datetime-local` input.
+    pub(crate) scheduled_at: String,
+}
+
+/// Content type segment of calendar action paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ScheduleTarget {
+    Posts,
+    Pages,
+}
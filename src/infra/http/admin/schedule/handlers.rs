@@ -0,0 +1,265 @@
+//! HTTP handlers for the content calendar - month grid and quick actions.
+
+use askama::Template;
+use axum::{
+    Extension,
+    extract::{Form, Path, Query, State},
+    response::{IntoResponse, Response},
+};
+use sqlx::types::chrono::NaiveDateTime;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        admin::{
+            auth::AdminSession, pages::UpdatePageStatusCommand, posts::UpdatePostStatusCommand,
+        },
+        repos::SettingsRepo,
+    },
+    domain::types::{PageStatus, PostStatus},
+    infra::http::admin::{
+        AdminState,
+        selectors::SCHEDULE_PANEL,
+        shared::{Toast, datastar_replace, push_toasts},
+    },
+    presentation::admin::views as admin_views,
+    util::timezone,
+};
+
+use super::{
+    errors::admin_schedule_error,
+    forms::{
+        AdminScheduleMonthForm, AdminScheduleQuery, AdminScheduleRescheduleForm, ScheduleTarget,
+    },
+    panel::{
+        LOCAL_INPUT_FORMAT, ScheduleViewError, build_schedule_view, render_schedule_panel_html,
+    },
+};
+
+/// GET /schedule - Render the content calendar.
+pub(crate) async fn admin_schedule(
+    State(state): State<AdminState>,
+    Query(query): Query<AdminScheduleQuery>,
+) -> Response {
+    let content = match build_schedule_view(&state, query.month.as_deref()).await {
+        Ok(content) => content,
+        Err(err) => {
+            return view_error_response("infra::http::admin::schedule::admin_schedule", err);
+        }
+    };
+
+    let chrome = match state.chrome.load("/schedule").await {
+        Ok(chrome) => chrome,
+        Err(err) => return err.into_response(),
+    };
+    let view = admin_views::AdminLayout::new(chrome, content);
+    let template = admin_views::AdminScheduleTemplate { view };
+
+    match template.render() {
+        Ok(html) => axum::response::Html(html).into_response(),
+        Err(err) => {
+            tracing::error!(error = %err, "Failed to render schedule template");
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// POST /schedule/panel - Switch the calendar to another month.
+pub(crate) async fn admin_schedule_panel(
+    State(state): State<AdminState>,
+    Form(form): Form<AdminScheduleMonthForm>,
+) -> Response {
+    respond_with_panel(
+        &state,
+        form.month.as_deref(),
+        None,
+        "infra::http::admin::schedule::admin_schedule_panel",
+    )
+    .await
+}
+
+/// POST /schedule/{kind}/{id}/publish - Publish a scheduled item right away.
+pub(crate) async fn admin_schedule_publish(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Path((target, id)): Path<(ScheduleTarget, Uuid)>,
+    Form(form): Form<AdminScheduleMonthForm>,
+) -> Response {
+    let toast = match publish_at(
+        &state,
+        &session.username,
+        target,
+        id,
+        OffsetDateTime::now_utc(),
+    )
+    .await
+    {
+        Ok(title) => Toast::success(format!("Published {} \"{title}\"", noun(target))),
+        Err(message) => Toast::error(message),
+    };
+
+    respond_with_panel(
+        &state,
+        form.month.as_deref(),
+        Some(toast),
+        "infra::http::admin::schedule::admin_schedule_publish",
+    )
+    .await
+}
+
+/// POST /schedule/{kind}/{id}/reschedule - Move a scheduled item to a new
+/// publication time in the site timezone.
+pub(crate) async fn admin_schedule_reschedule(
+    State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
+    Path((target, id)): Path<(ScheduleTarget, Uuid)>,
+    Form(form): Form<AdminScheduleRescheduleForm>,
+) -> Response {
+    let toast = match reschedule(&state, &session.username, target, id, &form.scheduled_at).await {
+        Ok((title, at)) => {
+            Toast::success(format!("Rescheduled {} \"{title}\" for {at}", noun(target)))
+        }
+        Err(message) => Toast::error(message),
+    };
+
+    respond_with_panel(
+        &state,
+        form.month.as_deref(),
+        Some(toast),
+        "infra::http::admin::schedule::admin_schedule_reschedule",
+    )
+    .await
+}
+
+/// Parse the submitted local time and schedule the item for it. Returns the
+/// item title and the formatted new time.
+async fn reschedule(
+    state: &AdminState,
+    actor: &str,
+    target: ScheduleTarget,
+    id: Uuid,
+    raw: &str,
+) -> Result<(String, String), String> {
+    let tz = state
+        .db
+        .load_site_settings()
+        .await
+        .map_err(|err| format!("Failed to load site settings: {err}"))?
+        .timezone;
+
+    let raw = raw.trim();
+    let at = NaiveDateTime::parse_from_str(raw, LOCAL_INPUT_FORMAT)
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+        .and_then(|local| timezone::local_instant(local, tz))
+        .ok_or_else(|| format!("`{raw}` is not a valid date and time"))?;
+    if at <= OffsetDateTime::now_utc() {
+        return Err("Pick a time in the future, or use Publish now".to_string());
+    }
+
+    let title = publish_at(state, actor, target, id, at).await?;
+    Ok((title, admin_views::format_timestamp(at, tz)))
+}
+
+/// Schedule a draft post or page to publish at `at`; a time that has passed
+/// publishes it now. Returns the item title, or the message for an error
+/// toast.
+async fn publish_at(
+    state: &AdminState,
+    actor: &str,
+    target: ScheduleTarget,
+    id: Uuid,
+    at: OffsetDateTime,
+) -> Result<String, String> {
+    match target {
+        ScheduleTarget::Posts => {
+            let post = state
+                .posts
+                .load_post(id)
+                .await
+                .map_err(|err| format!("Failed to load post: {err}"))?
+                .ok_or_else(|| "Post not found".to_string())?;
+            if post.status != PostStatus::Draft {
+                return Err(format!("Post \"{}\" is not a draft", post.title));
+            }
+            let command = UpdatePostStatusCommand {
+                id,
+                status: PostStatus::Published,
+                scheduled_at: None,
+                published_at: Some(at),
+                archived_at: None,
+            };
+            state
+                .posts
+                .update_status(actor, command)
+                .await
+                .map_err(|err| format!("Failed to schedule post \"{}\": {err}", post.title))?;
+            Ok(post.title)
+        }
+        ScheduleTarget::Pages => {
+            let page = state
+                .pages
+                .find_by_id(id)
+                .await
+                .map_err(|err| format!("Failed to load page: {err}"))?
+                .ok_or_else(|| "Page not found".to_string())?;
+            if page.status != PageStatus::Draft {
+                return Err(format!("Page \"{}\" is not a draft", page.title));
+            }
+            let command = UpdatePageStatusCommand {
+                id,
+                status: PageStatus::Published,
+                scheduled_at: None,
+                published_at: Some(at),
+                archived_at: None,
+            };
+            state
+                .pages
+                .update_status(actor, command)
+                .await
+                .map_err(|err| format!("Failed to schedule page \"{}\": {err}", page.title))?;
+            Ok(page.title)
+        }
+    }
+}
+
+fn noun(target: ScheduleTarget) -> &'static str {
+    match target {
+        ScheduleTarget::Posts => "post",
+        ScheduleTarget::Pages => "page",
+    }
+}
+
+/// Helper to respond with the refreshed panel and an optional toast.
+async fn respond_with_panel(
+    state: &AdminState,
+    month: Option<&str>,
+    toast: Option<Toast>,
+    source: &'static str,
+) -> Response {
+    let content = match build_schedule_view(state, month).await {
+        Ok(content) => content,
+        Err(err) => return view_error_response(source, err),
+    };
+
+    match render_schedule_panel_html(&content, source) {
+        Ok(html) => {
+            let mut stream = datastar_replace(SCHEDULE_PANEL, html);
+            if let Some(toast) = toast
+                && let Err(err) = push_toasts(&mut stream, &[toast])
+            {
+                return err.into_response();
+            }
+            stream.into_response()
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+fn view_error_response(source: &'static str, err: ScheduleViewError) -> Response {
+    match err {
+        ScheduleViewError::Month(err) => err.into_response(),
+        ScheduleViewError::Schedule(err) => admin_schedule_error(source, err).into_response(),
+    }
+}
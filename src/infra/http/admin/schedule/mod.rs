@@ -0,0 +1,13 @@
+//! Content calendar admin module.
+//!
+//! This module renders scheduled and published posts and pages on a month
+//! grid and handles the calendar's publish and reschedule actions.
+
+mod errors;
+mod forms;
+mod handlers;
+mod panel;
+
+pub(super) use handlers::{
+    admin_schedule, admin_schedule_panel, admin_schedule_publish, admin_schedule_reschedule,
+};
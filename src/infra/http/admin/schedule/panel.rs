@@ -0,0 +1,231 @@
+//! Panel building for the content calendar.
+
+use std::collections::HashMap;
+
+use askama::Template;
+use axum::http::StatusCode;
+use chrono_tz::Tz;
+use time::{Date, Duration, Month, OffsetDateTime};
+
+use crate::{
+    application::{
+        admin::{
+            dashboard::ScheduledKind,
+            schedule::{AdminScheduleError, ScheduleItem, ScheduleItemState},
+        },
+        error::HttpError,
+        format,
+        repos::SettingsRepo,
+    },
+    infra::http::admin::{AdminState, shared::template_render_http_error},
+    presentation::admin::views as admin_views,
+    util::timezone,
+};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Format of `datetime-local` input values.
+pub(super) const LOCAL_INPUT_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// Build the month grid for the month given as `YYYY-MM`, or for the current
+/// month in the site timezone when `month` is empty.
+pub(super) async fn build_schedule_view(
+    state: &AdminState,
+    month: Option<&str>,
+) -> Result<admin_views::AdminScheduleView, ScheduleViewError> {
+    let settings = state
+        .db
+        .load_site_settings()
+        .await
+        .map_err(AdminScheduleError::from)?;
+    let tz = settings.timezone;
+    let today = timezone::localized_date(OffsetDateTime::now_utc(), tz);
+    let first = parse_month(month, today)?;
+    let (start, end) = grid_bounds(first);
+
+    let days = state.schedule.calendar(start, end, tz).await?;
+    let mut items: HashMap<Date, Vec<ScheduleItem>> =
+        days.into_iter().map(|day| (day.date, day.items)).collect();
+
+    let mut weeks = Vec::new();
+    let mut day = start;
+    while day <= end {
+        let mut week = Vec::with_capacity(WEEKDAYS.len());
+        for _ in 0..WEEKDAYS.len() {
+            week.push(admin_views::AdminScheduleDayView {
+                date: day.to_string(),
+                day_number: day.day(),
+                in_month: day.month() == first.month(),
+                is_today: day == today,
+                items: items
+                    .remove(&day)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|item| item_view(item, tz))
+                    .collect(),
+            });
+            day += Duration::days(1);
+        }
+        weeks.push(admin_views::AdminScheduleWeekView { days: week });
+    }
+
+    Ok(admin_views::AdminScheduleView {
+        heading: "Calendar".to_string(),
+        month_label: format!("{} {}", first.month(), first.year()),
+        month: month_key(first),
+        month_links: vec![
+            month_link("Previous", shift_month(first, false)),
+            month_link("Today", today.replace_day(1).unwrap_or(today)),
+            month_link("Next", shift_month(first, true)),
+        ],
+        timezone: tz.name().to_string(),
+        weekdays: WEEKDAYS.to_vec(),
+        weeks,
+        panel_action: "/schedule/panel".to_string(),
+    })
+}
+
+/// Render calendar panel HTML from view.
+pub(super) fn render_schedule_panel_html(
+    content: &admin_views::AdminScheduleView,
+    template_source: &'static str,
+) -> Result<String, HttpError> {
+    let template = admin_views::AdminSchedulePanelTemplate {
+        content: content.clone(),
+    };
+
+    template.render().map_err(|err| {
+        template_render_http_error(template_source, "Template rendering failed", err)
+    })
+}
+
+/// Failure to build the calendar: a bad month parameter or a service error.
+pub(super) enum ScheduleViewError {
+    Month(HttpError),
+    Schedule(AdminScheduleError),
+}
+
+impl From<AdminScheduleError> for ScheduleViewError {
+    fn from(err: AdminScheduleError) -> Self {
+        Self::Schedule(err)
+    }
+}
+
+impl From<HttpError> for ScheduleViewError {
+    fn from(err: HttpError) -> Self {
+        Self::Month(err)
+    }
+}
+
+fn item_view(item: ScheduleItem, tz: Tz) -> admin_views::AdminScheduleItemView {
+    let (kind, segment) = match item.kind {
+        ScheduledKind::Post => ("Post", "posts"),
+        ScheduledKind::Page => ("Page", "pages"),
+    };
+    admin_views::AdminScheduleItemView {
+        kind,
+        title: item.title,
+        time: format::format_local(item.at, tz, "%H:%M"),
+        edit_href: format!("/{segment}/{}/edit", item.id),
+        scheduled: item.state == ScheduleItemState::Scheduled,
+        publish_action: format!("/schedule/{segment}/{}/publish", item.id),
+        reschedule_action: format!("/schedule/{segment}/{}/reschedule", item.id),
+        reschedule_value: format::format_local(item.at, tz, LOCAL_INPUT_FORMAT),
+    }
+}
+
+/// First day of the month given as `YYYY-MM`, or of the month of `today`.
+fn parse_month(value: Option<&str>, today: Date) -> Result<Date, HttpError> {
+    let Some(raw) = value.map(str::trim).filter(|raw| !raw.is_empty()) else {
+        return Ok(today.replace_day(1).unwrap_or(today));
+    };
+
+    raw.split_once('-')
+        .and_then(|(year, month)| {
+            let year = year.parse::<i32>().ok()?;
+            let month = Month::try_from(month.parse::<u8>().ok()?).ok()?;
+            Date::from_calendar_date(year, month, 1).ok()
+        })
+        .ok_or_else(|| {
+            HttpError::new(
+                "infra::http::admin::schedule::parse_month",
+                StatusCode::BAD_REQUEST,
+                "Invalid calendar month",
+                format!("Month `{raw}` is not in YYYY-MM form"),
+            )
+        })
+}
+
+/// The Monday on or before `first` through the Sunday on or after the last
+/// day of its month.
+fn grid_bounds(first: Date) -> (Date, Date) {
+    let last = shift_month(first, true).previous_day().unwrap_or(first);
+    let start = first - Duration::days(i64::from(first.weekday().number_days_from_monday()));
+    let end = last + Duration::days(6 - i64::from(last.weekday().number_days_from_monday()));
+    (start, end)
+}
+
+fn shift_month(first: Date, forward: bool) -> Date {
+    let (year, month) = match (forward, first.month()) {
+        (true, Month::December) => (first.year() + 1, Month::January),
+        (true, month) => (first.year(), month.next()),
+        (false, Month::January) => (first.year() - 1, Month::December),
+        (false, month) => (first.year(), month.previous()),
+    };
+    Date::from_calendar_date(year, month, 1).unwrap_or(first)
+}
+
+fn month_link(label: &'static str, first: Date) -> admin_views::AdminScheduleMonthLink {
+    admin_views::AdminScheduleMonthLink {
+        label,
+        month: month_key(first),
+    }
+}
+
+fn month_key(first: Date) -> String {
+    format!("{:04}-{:02}", first.year(), u8::from(first.month()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn grid_spans_whole_weeks_around_the_month() {
+        assert_eq!(
+            grid_bounds(date!(2026 - 11 - 01)),
+            (date!(2026 - 10 - 26), date!(2026 - 12 - 06))
+        );
+        assert_eq!(
+            grid_bounds(date!(2027 - 02 - 01)),
+            (date!(2027 - 02 - 01), date!(2027 - 02 - 28))
+        );
+    }
+
+    #[test]
+    fn months_shift_across_year_boundaries() {
+        assert_eq!(
+            shift_month(date!(2026 - 12 - 01), true),
+            date!(2027 - 01 - 01)
+        );
+        assert_eq!(
+            shift_month(date!(2027 - 01 - 01), false),
+            date!(2026 - 12 - 01)
+        );
+        assert_eq!(month_key(date!(2027 - 01 - 01)), "2027-01");
+    }
+
+    #[test]
+    fn parse_month_defaults_to_today_and_rejects_garbage() {
+        let today = date!(2026 - 10 - 16);
+        assert_eq!(parse_month(None, today).unwrap(), date!(2026 - 10 - 01));
+        assert_eq!(parse_month(Some(""), today).unwrap(), date!(2026 - 10 - 01));
+        assert_eq!(
+            parse_month(Some("2027-03"), today).unwrap(),
+            date!(2027 - 03 - 01)
+        );
+        assert!(parse_month(Some("2027-13"), today).is_err());
+        assert!(parse_month(Some("march"), today).is_err());
+    }
+}
@@ -11,11 +11,17 @@ pub const NAVIGATION_PANEL: &str = "[data-admin-panel=\"navigation\"]";
 pub const TAGS_PANEL: &str = "[data-admin-panel=\"tags\"]";
 pub const UPLOADS_PANEL: &str = "[data-admin-panel=\"uploads\"]";
 pub const JOBS_PANEL: &str = "[data-admin-panel=\"jobs\"]";
+pub const COMMENTS_PANEL: &str = "[data-admin-panel=\"comments\"]";
+pub const SCHEDULE_PANEL: &str = "[data-admin-panel=\"schedule\"]";
 pub const AUDIT_PANEL: &str = "[data-admin-panel=\"audit\"]";
 pub const API_KEY_EDITOR_PANEL: &str = "[data-admin-panel=\"api-key-editor\"]";
 
 pub const TAG_PICKER: &str = "[data-role=\"picker\"][data-picker-type=\"tag\"]";
 pub const TAG_SELECTION_STORE: &str = "[data-role=\"tag-selection-store\"]";
+pub const POST_PREVIEW: &str = "[data-role=\"post-preview\"]";
+pub const POST_SLUG_ERROR: &str = "[data-role=\"field-error\"][data-field=\"slug\"]";
+pub const POST_AUTOSAVE_RESTORE: &str = "[data-role=\"autosave-restore\"]";
+pub const POST_AUTOSAVE_STATUS: &str = "[data-role=\"autosave-status\"]";
 
 pub const SCOPE_PICKER: &str = "[data-role=\"picker\"][data-picker-type=\"scope\"]";
 pub const SCOPE_SELECTION_STORE: &str = "[data-role=\"scope-selection-store\"]";
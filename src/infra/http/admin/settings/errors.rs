@@ -9,11 +9,11 @@ use crate::{
 
 pub(super) fn admin_settings_error(source: &'static str, err: AdminSettingsError) -> HttpError {
     match err {
-        AdminSettingsError::ConstraintViolation(field) => HttpError::new(
+        AdminSettingsError::Invalid(invalid) => HttpError::new(
             source,
             StatusCode::BAD_REQUEST,
             "Settings request could not be processed",
-            format!("Invalid field `{field}`"),
+            invalid.to_string(),
         ),
         AdminSettingsError::Repo(repo) => repo_error_to_http(source, repo),
     }
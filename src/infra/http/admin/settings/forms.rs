@@ -1,13 +1,14 @@
 //! Form definitions for settings admin handlers.
 
-use chrono_tz::Tz;
 use serde::Deserialize;
 use thiserror::Error;
+use uuid::Uuid;
 
-use crate::application::admin::settings::UpdateSettingsCommand;
+use crate::application::admin::settings::{UpdateSettingsCommand, parse_timezone};
+use crate::domain::types::{FeedContentMode, HomepageLayout, HomepageMode};
 use crate::presentation::admin::views as admin_views;
 
-use super::validators::{parse_positive_i32, validate_favicon_svg};
+use super::validators::parse_i32;
 use super::views::{EditFieldValues, build_edit_view};
 
 #[derive(Debug, Clone, Deserialize)]
@@ -24,47 +25,99 @@ pub(crate) struct AdminSettingsForm {
     pub(super) footer_copy: String,
     pub(super) public_site_url: String,
     pub(super) favicon_svg: String,
+    pub(super) favicon_upload_id: String,
     pub(super) timezone: String,
+    pub(super) date_format: String,
     pub(super) meta_title: String,
     pub(super) meta_description: String,
     pub(super) og_title: String,
     pub(super) og_description: String,
+    pub(super) maintenance_enabled: Option<String>,
+    pub(super) maintenance_html: String,
+    pub(super) maintenance_retry_after_secs: String,
+    pub(super) feed_content_mode: String,
+    pub(super) feed_item_limit: String,
+    pub(super) homepage_mode: String,
+    pub(super) homepage_page_id: String,
+    pub(super) homepage_layout: String,
 }
 
 #[derive(Debug, Error)]
 pub(super) enum AdminSettingsFormError {
-    #[error("`{field}` must be a positive integer")]
-    InvalidInteger { field: &'static str },
-    #[error("`{field}` must be greater than zero")]
-    NonPositive { field: &'static str },
+    #[error("`{field}` must be an integer")]
+    Integer { field: &'static str },
     #[error("`{value}` is not a recognised timezone")]
-    InvalidTimezone { value: String },
-    #[error("`{field}` must be an SVG document")]
-    InvalidFavicon {
-        field: &'static str,
-        reason: &'static str,
-    },
+    Timezone { value: String },
+    #[error("`{value}` is not a feed content mode")]
+    FeedContentMode { value: String },
+    #[error("`{value}` is not an upload ID")]
+    UploadId { value: String },
+    #[error("`{value}` is not a homepage mode")]
+    HomepageMode { value: String },
+    #[error("`{value}` is not a page ID")]
+    PageId { value: String },
+    #[error("`{value}` is not a homepage layout")]
+    HomepageLayout { value: String },
 }
 
 impl AdminSettingsForm {
-    pub(super) const MAX_FAVICON_SVG_LENGTH: usize = 8 * 1024;
-
     pub(super) fn to_command(&self) -> Result<UpdateSettingsCommand, AdminSettingsFormError> {
-        let homepage_size = parse_positive_i32(self.homepage_size.trim(), "homepage_size")?;
-        let admin_page_size = parse_positive_i32(self.admin_page_size.trim(), "admin_page_size")?;
-        let tag_filter_limit =
-            parse_positive_i32(self.tag_filter_limit.trim(), "tag_filter_limit")?;
-        let month_filter_limit =
-            parse_positive_i32(self.month_filter_limit.trim(), "month_filter_limit")?;
+        let homepage_size = parse_i32(self.homepage_size.trim(), "homepage_size")?;
+        let admin_page_size = parse_i32(self.admin_page_size.trim(), "admin_page_size")?;
+        let tag_filter_limit = parse_i32(self.tag_filter_limit.trim(), "tag_filter_limit")?;
+        let month_filter_limit = parse_i32(self.month_filter_limit.trim(), "month_filter_limit")?;
+        let maintenance_retry_after_secs = parse_i32(
+            self.maintenance_retry_after_secs.trim(),
+            "maintenance_retry_after_secs",
+        )?;
+        let feed_item_limit = parse_i32(self.feed_item_limit.trim(), "feed_item_limit")?;
 
-        let timezone = self.timezone.trim().parse::<Tz>().map_err(|_| {
-            AdminSettingsFormError::InvalidTimezone {
+        let timezone =
+            parse_timezone(&self.timezone).ok_or_else(|| AdminSettingsFormError::Timezone {
                 value: self.timezone.trim().to_string(),
-            }
-        })?;
+            })?;
+        let feed_content_mode = self
+            .feed_content_mode
+            .trim()
+            .parse::<FeedContentMode>()
+            .map_err(|()| AdminSettingsFormError::FeedContentMode {
+                value: self.feed_content_mode.trim().to_string(),
+            })?;
+        let favicon_upload_id = match self.favicon_upload_id.trim() {
+            "" => None,
+            value => Some(
+                value
+                    .parse::<Uuid>()
+                    .map_err(|_| AdminSettingsFormError::UploadId {
+                        value: value.to_string(),
+                    })?,
+            ),
+        };
 
-        let favicon_svg = self.favicon_svg.trim();
-        validate_favicon_svg(favicon_svg)?;
+        let homepage_mode = self
+            .homepage_mode
+            .trim()
+            .parse::<HomepageMode>()
+            .map_err(|()| AdminSettingsFormError::HomepageMode {
+                value: self.homepage_mode.trim().to_string(),
+            })?;
+        let homepage_page_id = match self.homepage_page_id.trim() {
+            "" => None,
+            value => Some(
+                value
+                    .parse::<Uuid>()
+                    .map_err(|_| AdminSettingsFormError::PageId {
+                        value: value.to_string(),
+                    })?,
+            ),
+        };
+        let homepage_layout = self
+            .homepage_layout
+            .trim()
+            .parse::<HomepageLayout>()
+            .map_err(|()| AdminSettingsFormError::HomepageLayout {
+                value: self.homepage_layout.trim().to_string(),
+            })?;
 
         Ok(UpdateSettingsCommand {
             homepage_size,
@@ -78,12 +131,22 @@ impl AdminSettingsForm {
             brand_href: self.brand_href.trim().to_string(),
             footer_copy: self.footer_copy.trim().to_string(),
             public_site_url: self.public_site_url.trim().to_string(),
-            favicon_svg: favicon_svg.to_string(),
+            favicon_svg: self.favicon_svg.trim().to_string(),
+            favicon_upload_id,
             timezone,
+            date_format: self.date_format.trim().to_string(),
             meta_title: self.meta_title.trim().to_string(),
             meta_description: self.meta_description.trim().to_string(),
             og_title: self.og_title.trim().to_string(),
             og_description: self.og_description.trim().to_string(),
+            maintenance_enabled: self.maintenance_enabled.is_some(),
+            maintenance_html: self.maintenance_html.trim().to_string(),
+            maintenance_retry_after_secs,
+            feed_content_mode,
+            feed_item_limit,
+            homepage_mode,
+            homepage_page_id,
+            homepage_layout,
         })
     }
 
@@ -94,6 +157,7 @@ impl AdminSettingsForm {
             tag_filter_limit: self.tag_filter_limit.trim().to_string(),
             month_filter_limit: self.month_filter_limit.trim().to_string(),
             timezone: self.timezone.trim().to_string(),
+            date_format: self.date_format.trim().to_string(),
             show_tag_aggregations: self.show_tag_aggregations.is_some(),
             show_month_aggregations: self.show_month_aggregations.is_some(),
             global_toc_enabled: self.global_toc_enabled.is_some(),
@@ -102,10 +166,19 @@ impl AdminSettingsForm {
             footer_copy: self.footer_copy.trim().to_string(),
             public_site_url: self.public_site_url.trim().to_string(),
             favicon_svg: self.favicon_svg.trim().to_string(),
+            favicon_upload_id: self.favicon_upload_id.trim().to_string(),
             meta_title: self.meta_title.trim().to_string(),
             meta_description: self.meta_description.trim().to_string(),
             og_title: self.og_title.trim().to_string(),
             og_description: self.og_description.trim().to_string(),
+            maintenance_enabled: self.maintenance_enabled.is_some(),
+            maintenance_html: self.maintenance_html.trim().to_string(),
+            maintenance_retry_after_secs: self.maintenance_retry_after_secs.trim().to_string(),
+            feed_content_mode: self.feed_content_mode.trim().to_string(),
+            feed_item_limit: self.feed_item_limit.trim().to_string(),
+            homepage_mode: self.homepage_mode.trim().to_string(),
+            homepage_page_id: self.homepage_page_id.trim().to_string(),
+            homepage_layout: self.homepage_layout.trim().to_string(),
             updated_at,
         })
     }
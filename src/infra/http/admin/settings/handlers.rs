@@ -2,11 +2,13 @@
 
 use askama::Template;
 use axum::{
+    Extension,
     extract::{Form, State},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
 
+use crate::application::admin::auth::AdminSession;
 use crate::application::admin::settings::AdminSettingsError;
 use crate::application::error::HttpError;
 use crate::presentation::{admin::views as admin_views, views::render_template_response};
@@ -62,6 +64,7 @@ pub(crate) async fn admin_settings_edit(State(state): State<AdminState>) -> Resp
 
 pub(crate) async fn admin_settings_update(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Form(form): Form<AdminSettingsForm>,
 ) -> Response {
     let original = match state.settings.load().await {
@@ -87,7 +90,7 @@ pub(crate) async fn admin_settings_update(
         }
     };
 
-    let actor = "admin";
+    let actor = session.username.as_str();
     match state.settings.update(actor, command).await {
         Ok(updated) => {
             let content = edit_view_from_record(&updated);
@@ -99,14 +102,15 @@ pub(crate) async fn admin_settings_update(
             .into_response()
         }
         Err(err) => match err {
-            AdminSettingsError::ConstraintViolation(field) => {
+            AdminSettingsError::Invalid(invalid) => {
                 let content = form.to_edit_view(original_updated);
-                render_editor_with_toast(
-                    content,
-                    &[Toast::error(format!("Field `{field}` cannot be empty"))],
-                    "infra::http::admin_settings_update",
-                )
-                .into_response()
+                let toasts: Vec<Toast> = invalid
+                    .errors
+                    .iter()
+                    .map(|error| Toast::error(format!("`{}` {}", error.field, error.message)))
+                    .collect();
+                render_editor_with_toast(content, &toasts, "infra::http::admin_settings_update")
+                    .into_response()
             }
             AdminSettingsError::Repo(repo) => admin_settings_error(
                 "infra::http::admin_settings_update",
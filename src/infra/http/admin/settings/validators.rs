@@ -1,44 +1,12 @@
-//! Validation helpers for settings form.
+//! Parsing helpers for settings form.
+//!
+//! Only the form's string-to-value conversion lives here; value rules are
+//! enforced by `SettingsValidator` in the settings service.
 
-use super::forms::{AdminSettingsForm, AdminSettingsFormError};
+use super::forms::AdminSettingsFormError;
 
-pub(super) fn parse_positive_i32(
-    value: &str,
-    field: &'static str,
-) -> Result<i32, AdminSettingsFormError> {
-    let parsed = value
+pub(super) fn parse_i32(value: &str, field: &'static str) -> Result<i32, AdminSettingsFormError> {
+    value
         .parse::<i32>()
-        .map_err(|_| AdminSettingsFormError::InvalidInteger { field })?;
-    if parsed <= 0 {
-        return Err(AdminSettingsFormError::NonPositive { field });
-    }
-    Ok(parsed)
-}
-
-pub(super) fn validate_favicon_svg(value: &str) -> Result<(), AdminSettingsFormError> {
-    if value.is_empty() {
-        return Err(AdminSettingsFormError::InvalidFavicon {
-            field: "favicon_svg",
-            reason: "cannot be empty",
-        });
-    }
-    if value.len() > AdminSettingsForm::MAX_FAVICON_SVG_LENGTH {
-        return Err(AdminSettingsFormError::InvalidFavicon {
-            field: "favicon_svg",
-            reason: "exceeds maximum length",
-        });
-    }
-    if !value.to_ascii_lowercase().contains("<svg") {
-        return Err(AdminSettingsFormError::InvalidFavicon {
-            field: "favicon_svg",
-            reason: "missing <svg> element",
-        });
-    }
-    if value.to_ascii_lowercase().contains("<script") {
-        return Err(AdminSettingsFormError::InvalidFavicon {
-            field: "favicon_svg",
-            reason: "scripts are not allowed",
-        });
-    }
-    Ok(())
+        .map_err(|_| AdminSettingsFormError::Integer { field })
 }
@@ -1,6 +1,9 @@
 //! View building functions for settings.
 
+use crate::application::admin::settings::MIN_ADMIN_PAGE_SIZE;
+use crate::application::format;
 use crate::domain::entities::SiteSettingsRecord;
+use crate::domain::types::{FeedContentMode, HomepageLayout, HomepageMode};
 use crate::presentation::admin::views as admin_views;
 
 pub(super) const SETTINGS_FORM_ACTION: &str = "/settings/edit";
@@ -11,6 +14,7 @@ pub(super) struct EditFieldValues {
     pub(super) tag_filter_limit: String,
     pub(super) month_filter_limit: String,
     pub(super) timezone: String,
+    pub(super) date_format: String,
     pub(super) show_tag_aggregations: bool,
     pub(super) show_month_aggregations: bool,
     pub(super) global_toc_enabled: bool,
@@ -19,10 +23,19 @@ pub(super) struct EditFieldValues {
     pub(super) footer_copy: String,
     pub(super) public_site_url: String,
     pub(super) favicon_svg: String,
+    pub(super) favicon_upload_id: String,
     pub(super) meta_title: String,
     pub(super) meta_description: String,
     pub(super) og_title: String,
     pub(super) og_description: String,
+    pub(super) maintenance_enabled: bool,
+    pub(super) maintenance_html: String,
+    pub(super) maintenance_retry_after_secs: String,
+    pub(super) feed_content_mode: String,
+    pub(super) feed_item_limit: String,
+    pub(super) homepage_mode: String,
+    pub(super) homepage_page_id: String,
+    pub(super) homepage_layout: String,
     pub(super) updated_at: String,
 }
 
@@ -50,6 +63,7 @@ pub(super) fn edit_view_from_record(
         tag_filter_limit: record.tag_filter_limit.to_string(),
         month_filter_limit: record.month_filter_limit.to_string(),
         timezone: timezone.name().to_string(),
+        date_format: record.date_format.clone(),
         show_tag_aggregations: record.show_tag_aggregations,
         show_month_aggregations: record.show_month_aggregations,
         global_toc_enabled: record.global_toc_enabled,
@@ -58,10 +72,25 @@ pub(super) fn edit_view_from_record(
         footer_copy: record.footer_copy.clone(),
         public_site_url: record.public_site_url.clone(),
         favicon_svg: record.favicon_svg.clone(),
+        favicon_upload_id: record
+            .favicon_upload_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
         meta_title: record.meta_title.clone(),
         meta_description: record.meta_description.clone(),
         og_title: record.og_title.clone(),
         og_description: record.og_description.clone(),
+        maintenance_enabled: record.maintenance_enabled,
+        maintenance_html: record.maintenance_html.clone(),
+        maintenance_retry_after_secs: record.maintenance_retry_after_secs.to_string(),
+        feed_content_mode: record.feed_content_mode.as_str().to_string(),
+        feed_item_limit: record.feed_item_limit.to_string(),
+        homepage_mode: record.homepage_mode.as_str().to_string(),
+        homepage_page_id: record
+            .homepage_page_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        homepage_layout: record.homepage_layout.as_str().to_string(),
         updated_at: admin_views::format_timestamp(record.updated_at, timezone),
     })
 }
@@ -79,6 +108,20 @@ fn summary_fields(
         "Homepage Size",
         record.homepage_size.to_string(),
     ));
+    simple.push(summary_text_field(
+        "Homepage",
+        homepage_mode_label(record.homepage_mode).to_string(),
+    ));
+    simple.push(summary_text_field(
+        "Homepage Page",
+        record
+            .homepage_page_id
+            .map_or_else(|| "None".to_string(), |id| id.to_string()),
+    ));
+    simple.push(summary_text_field(
+        "Homepage Layout",
+        homepage_layout_label(record.homepage_layout).to_string(),
+    ));
     simple.push(summary_text_field(
         "Admin Page Size",
         record.admin_page_size.to_string(),
@@ -95,6 +138,10 @@ fn summary_fields(
         "Timezone",
         record.timezone.name().to_string(),
     ));
+    simple.push(summary_text_field(
+        "Date Format",
+        record.date_format.clone(),
+    ));
     simple.push(summary_badge_field(
         "Show Tag Aggregations",
         record.show_tag_aggregations,
@@ -116,8 +163,30 @@ fn summary_fields(
         "Public Site URL",
         record.public_site_url.clone(),
     ));
+    simple.push(summary_text_field(
+        "Favicon Upload",
+        record
+            .favicon_upload_id
+            .map_or_else(|| "None (SVG)".to_string(), |id| id.to_string()),
+    ));
     simple.push(summary_text_field("Meta Title", record.meta_title.clone()));
     simple.push(summary_text_field("OG Title", record.og_title.clone()));
+    simple.push(summary_badge_field(
+        "Maintenance Mode",
+        record.maintenance_enabled,
+    ));
+    simple.push(summary_text_field(
+        "Maintenance Retry-After (s)",
+        record.maintenance_retry_after_secs.to_string(),
+    ));
+    simple.push(summary_text_field(
+        "Feed Content",
+        feed_content_mode_label(record.feed_content_mode).to_string(),
+    ));
+    simple.push(summary_text_field(
+        "Feed Item Limit",
+        record.feed_item_limit.to_string(),
+    ));
 
     multiline.push(summary_multiline_field(
         "Footer Copy",
@@ -135,6 +204,10 @@ fn summary_fields(
         "OG Description",
         record.og_description.clone(),
     ));
+    multiline.push(summary_multiline_field(
+        "Maintenance Page HTML",
+        record.maintenance_html.clone(),
+    ));
 
     (simple, multiline)
 }
@@ -146,6 +219,7 @@ pub(super) fn build_edit_view(values: EditFieldValues) -> admin_views::AdminSett
         tag_filter_limit,
         month_filter_limit,
         timezone,
+        date_format,
         show_tag_aggregations,
         show_month_aggregations,
         global_toc_enabled,
@@ -154,10 +228,19 @@ pub(super) fn build_edit_view(values: EditFieldValues) -> admin_views::AdminSett
         footer_copy,
         public_site_url,
         favicon_svg,
+        favicon_upload_id,
         meta_title,
         meta_description,
         og_title,
         og_description,
+        maintenance_enabled,
+        maintenance_html,
+        maintenance_retry_after_secs,
+        feed_content_mode,
+        feed_item_limit,
+        homepage_mode,
+        homepage_page_id,
+        homepage_layout,
         updated_at,
     } = values;
 
@@ -170,12 +253,48 @@ pub(super) fn build_edit_view(values: EditFieldValues) -> admin_views::AdminSett
                 min: Some("1".to_string()),
             },
         },
+        admin_views::AdminSettingsEditSimpleField {
+            label: "Homepage".to_string(),
+            input: admin_views::AdminSettingsEditInputKind::Select {
+                name: "homepage_mode".to_string(),
+                options: HOMEPAGE_MODES
+                    .into_iter()
+                    .map(|mode| admin_views::AdminSettingsEditSelectOption {
+                        value: mode.as_str(),
+                        label: homepage_mode_label(mode),
+                        selected: mode.as_str() == homepage_mode,
+                    })
+                    .collect(),
+            },
+        },
+        admin_views::AdminSettingsEditSimpleField {
+            label: "Homepage Page".to_string(),
+            input: admin_views::AdminSettingsEditInputKind::Text {
+                name: "homepage_page_id".to_string(),
+                value: homepage_page_id,
+                placeholder: Some("ID of a published page, served at / in page mode".to_string()),
+            },
+        },
+        admin_views::AdminSettingsEditSimpleField {
+            label: "Homepage Layout".to_string(),
+            input: admin_views::AdminSettingsEditInputKind::Select {
+                name: "homepage_layout".to_string(),
+                options: HOMEPAGE_LAYOUTS
+                    .into_iter()
+                    .map(|layout| admin_views::AdminSettingsEditSelectOption {
+                        value: layout.as_str(),
+                        label: homepage_layout_label(layout),
+                        selected: layout.as_str() == homepage_layout,
+                    })
+                    .collect(),
+            },
+        },
         admin_views::AdminSettingsEditSimpleField {
             label: "Admin Page Size".to_string(),
             input: admin_views::AdminSettingsEditInputKind::Number {
                 name: "admin_page_size".to_string(),
                 value: admin_page_size,
-                min: Some("1".to_string()),
+                min: Some(MIN_ADMIN_PAGE_SIZE.to_string()),
             },
         },
         admin_views::AdminSettingsEditSimpleField {
@@ -202,6 +321,14 @@ pub(super) fn build_edit_view(values: EditFieldValues) -> admin_views::AdminSett
                 placeholder: Some("Asia/Shanghai".to_string()),
             },
         },
+        admin_views::AdminSettingsEditSimpleField {
+            label: "Date Format".to_string(),
+            input: admin_views::AdminSettingsEditInputKind::Text {
+                name: "date_format".to_string(),
+                value: date_format,
+                placeholder: Some(format::DEFAULT_DATE_FORMAT.to_string()),
+            },
+        },
         admin_views::AdminSettingsEditSimpleField {
             label: "Show Tag Aggregations".to_string(),
             input: admin_views::AdminSettingsEditInputKind::Checkbox {
@@ -250,6 +377,14 @@ pub(super) fn build_edit_view(values: EditFieldValues) -> admin_views::AdminSett
                 placeholder: Some("https://example.com".to_string()),
             },
         },
+        admin_views::AdminSettingsEditSimpleField {
+            label: "Favicon Upload".to_string(),
+            input: admin_views::AdminSettingsEditInputKind::Text {
+                name: "favicon_upload_id".to_string(),
+                value: favicon_upload_id,
+                placeholder: Some("ID of a PNG or ICO upload; blank uses the SVG".to_string()),
+            },
+        },
         admin_views::AdminSettingsEditSimpleField {
             label: "Meta Title".to_string(),
             input: admin_views::AdminSettingsEditInputKind::Text {
@@ -266,6 +401,44 @@ pub(super) fn build_edit_view(values: EditFieldValues) -> admin_views::AdminSett
                 placeholder: None,
             },
         },
+        admin_views::AdminSettingsEditSimpleField {
+            label: "Maintenance Mode".to_string(),
+            input: admin_views::AdminSettingsEditInputKind::Checkbox {
+                name: "maintenance_enabled".to_string(),
+                checked: maintenance_enabled,
+                toggle_id: settings_toggle_id("maintenance"),
+            },
+        },
+        admin_views::AdminSettingsEditSimpleField {
+            label: "Maintenance Retry-After (s)".to_string(),
+            input: admin_views::AdminSettingsEditInputKind::Number {
+                name: "maintenance_retry_after_secs".to_string(),
+                value: maintenance_retry_after_secs,
+                min: Some("1".to_string()),
+            },
+        },
+        admin_views::AdminSettingsEditSimpleField {
+            label: "Feed Content".to_string(),
+            input: admin_views::AdminSettingsEditInputKind::Select {
+                name: "feed_content_mode".to_string(),
+                options: FEED_CONTENT_MODES
+                    .into_iter()
+                    .map(|mode| admin_views::AdminSettingsEditSelectOption {
+                        value: mode.as_str(),
+                        label: feed_content_mode_label(mode),
+                        selected: mode.as_str() == feed_content_mode,
+                    })
+                    .collect(),
+            },
+        },
+        admin_views::AdminSettingsEditSimpleField {
+            label: "Feed Item Limit".to_string(),
+            input: admin_views::AdminSettingsEditInputKind::Number {
+                name: "feed_item_limit".to_string(),
+                value: feed_item_limit,
+                min: Some("1".to_string()),
+            },
+        },
     ];
 
     let multiline_fields = vec![
@@ -293,6 +466,12 @@ pub(super) fn build_edit_view(values: EditFieldValues) -> admin_views::AdminSett
             value: og_description,
             rows: 3,
         },
+        admin_views::AdminSettingsEditMultilineField {
+            label: "Maintenance Page HTML".to_string(),
+            name: "maintenance_html".to_string(),
+            value: maintenance_html,
+            rows: 6,
+        },
     ];
 
     admin_views::AdminSettingsEditView {
@@ -306,6 +485,43 @@ pub(super) fn build_edit_view(values: EditFieldValues) -> admin_views::AdminSett
     }
 }
 
+const FEED_CONTENT_MODES: [FeedContentMode; 3] = [
+    FeedContentMode::Excerpt,
+    FeedContentMode::Summary,
+    FeedContentMode::Full,
+];
+
+fn feed_content_mode_label(mode: FeedContentMode) -> &'static str {
+    match mode {
+        FeedContentMode::Excerpt => "Excerpt",
+        FeedContentMode::Summary => "Summary",
+        FeedContentMode::Full => "Full post",
+    }
+}
+
+const HOMEPAGE_MODES: [HomepageMode; 2] = [HomepageMode::Feed, HomepageMode::Page];
+
+fn homepage_mode_label(mode: HomepageMode) -> &'static str {
+    match mode {
+        HomepageMode::Feed => "Post feed",
+        HomepageMode::Page => "Page (feed at /posts)",
+    }
+}
+
+const HOMEPAGE_LAYOUTS: [HomepageLayout; 3] = [
+    HomepageLayout::List,
+    HomepageLayout::Grid,
+    HomepageLayout::Magazine,
+];
+
+fn homepage_layout_label(layout: HomepageLayout) -> &'static str {
+    match layout {
+        HomepageLayout::List => "List",
+        HomepageLayout::Grid => "Card grid",
+        HomepageLayout::Magazine => "Magazine (featured post first)",
+    }
+}
+
 fn summary_text_field(label: &str, value: String) -> admin_views::AdminSettingsSummaryField {
     admin_views::AdminSettingsSummaryField {
         label: label.to_string(),
@@ -360,18 +576,27 @@ mod tests {
 
         for expected in [
             "Homepage Size",
+            "Homepage",
+            "Homepage Page",
+            "Homepage Layout",
             "Admin Page Size",
             "Tag Filter Limit",
             "Month Filter Limit",
             "Timezone",
+            "Date Format",
             "Show Tag Aggregations",
             "Show Month Aggregations",
             "Global Table of Contents",
             "Brand Title",
             "Brand Link",
             "Public Site URL",
+            "Favicon Upload",
             "Meta Title",
             "OG Title",
+            "Maintenance Mode",
+            "Maintenance Retry-After (s)",
+            "Feed Content",
+            "Feed Item Limit",
         ] {
             assert!(
                 simple_labels.contains(&expected),
@@ -384,6 +609,7 @@ mod tests {
             "Favicon SVG",
             "Meta Description",
             "OG Description",
+            "Maintenance Page HTML",
         ] {
             assert!(
                 multiline_labels.contains(&expected),
@@ -404,10 +630,22 @@ mod tests {
                 admin_views::AdminSettingsEditInputKind::Number { name, .. } => name.as_str(),
                 admin_views::AdminSettingsEditInputKind::Text { name, .. } => name.as_str(),
                 admin_views::AdminSettingsEditInputKind::Checkbox { name, .. } => name.as_str(),
+                admin_views::AdminSettingsEditInputKind::Select { name, .. } => name.as_str(),
             })
             .collect();
 
-        for expected in ["homepage_size", "admin_page_size", "public_site_url"] {
+        for expected in [
+            "homepage_size",
+            "admin_page_size",
+            "public_site_url",
+            "favicon_upload_id",
+            "date_format",
+            "feed_content_mode",
+            "feed_item_limit",
+            "homepage_mode",
+            "homepage_page_id",
+            "homepage_layout",
+        ] {
             assert!(
                 input_names.contains(&expected),
                 "missing edit input `{expected}`"
@@ -429,11 +667,21 @@ mod tests {
             footer_copy: "Copyright Soffio".to_string(),
             public_site_url: "https://example.com".to_string(),
             favicon_svg: "<svg></svg>".to_string(),
+            favicon_upload_id: None,
             timezone: UTC,
+            date_format: "%B %-d, %Y".to_string(),
             meta_title: "Meta".to_string(),
             meta_description: "Meta description".to_string(),
             og_title: "OG".to_string(),
             og_description: "OG description".to_string(),
+            maintenance_enabled: false,
+            maintenance_html: "<p>Maintenance</p>".to_string(),
+            maintenance_retry_after_secs: 300,
+            feed_content_mode: FeedContentMode::Full,
+            feed_item_limit: 20,
+            homepage_mode: HomepageMode::Feed,
+            homepage_page_id: None,
+            homepage_layout: HomepageLayout::Magazine,
             updated_at: OffsetDateTime::UNIX_EPOCH,
         }
     }
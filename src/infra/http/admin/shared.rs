@@ -1,13 +1,20 @@
 use askama::{Error as AskamaError, Template};
+use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use datastar::prelude::ElementPatchMode;
 use serde::Deserialize;
 use std::time::Duration;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use super::selectors::{PANEL, TOAST_STACK};
 use crate::{
-    application::{error::HttpError, stream::StreamBuilder},
+    application::{
+        admin::settings::{MAX_ADMIN_PAGE_SIZE, MIN_ADMIN_PAGE_SIZE},
+        error::HttpError,
+        stream::StreamBuilder,
+    },
+    infra::http::api::etag::parse_version_token,
     presentation::{admin::views as admin_views, views::TemplateRenderError},
 };
 
@@ -20,6 +27,28 @@ pub(super) struct AdminPostQuery {
     pub(super) search: Option<String>,
     pub(super) tag: Option<String>,
     pub(super) month: Option<String>,
+    #[serde(flatten)]
+    pub(super) limit: ListLimit,
+}
+
+/// The `limit` an admin list form or query may send, flattened into each of
+/// them so the field is parsed and clamped in one place.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub(crate) struct ListLimit {
+    /// Rows per page for this request, overriding `admin_page_size`.
+    limit: Option<String>,
+}
+
+impl ListLimit {
+    /// The requested rows per page, clamped to the range the settings editor
+    /// accepts; `None` for a blank or malformed value, so the setting applies.
+    pub(crate) fn rows(&self) -> Option<u32> {
+        self.limit
+            .as_deref()
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .map(|rows| rows.clamp(MIN_ADMIN_PAGE_SIZE as u32, MAX_ADMIN_PAGE_SIZE as u32))
+    }
 }
 
 pub(super) fn blank_to_none_opt(value: Option<String>) -> Option<String> {
@@ -33,6 +62,26 @@ pub(super) fn blank_to_none_opt(value: Option<String>) -> Option<String> {
     })
 }
 
+/// The `updated_at` an editor form was loaded at, read from its `version`
+/// field. A form without one saves unconditionally.
+pub(super) fn form_version(
+    source: &'static str,
+    version: Option<&str>,
+) -> Result<Option<OffsetDateTime>, HttpError> {
+    version
+        .map(|token| {
+            parse_version_token(token).ok_or_else(|| {
+                HttpError::new(
+                    source,
+                    StatusCode::BAD_REQUEST,
+                    "Invalid form",
+                    "The editor version is malformed",
+                )
+            })
+        })
+        .transpose()
+}
+
 #[derive(Clone)]
 pub(super) struct Toast {
     pub id: Uuid,
@@ -44,6 +93,7 @@ pub(super) struct Toast {
 #[derive(Clone, Copy)]
 pub(super) enum ToastKind {
     Success,
+    Warning,
     Error,
 }
 
@@ -51,6 +101,7 @@ impl ToastKind {
     fn as_variant(self) -> &'static str {
         match self {
             ToastKind::Success => "success",
+            ToastKind::Warning => "warning",
             ToastKind::Error => "error",
         }
     }
@@ -63,6 +114,16 @@ impl Toast {
         Self::success_with_ttl(text, DEFAULT_TOAST_TTL)
     }
 
+    /// Shown for a change that went through but left something to fix.
+    pub fn warning(text: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind: ToastKind::Warning,
+            text: text.into(),
+            ttl: DEFAULT_TOAST_TTL,
+        }
+    }
+
     pub fn error(text: impl Into<String>) -> Self {
         Self::error_with_ttl(text, DEFAULT_TOAST_TTL)
     }
@@ -161,3 +222,15 @@ pub(super) fn template_render_http_error(
 ) -> HttpError {
     HttpError::from(TemplateRenderError::new(source, message, err))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversized_form_version_is_a_bad_request() {
+        let token = i128::MAX.to_string();
+        let error = form_version("test", Some(&token)).expect_err("oversized version");
+        assert_eq!(error.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,10 +1,12 @@
 use axum::{
+    Extension,
     extract::{Form, Path, State},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
 use uuid::Uuid;
 
+use crate::application::admin::auth::AdminSession;
 use crate::application::admin::snapshots::SnapshotServiceError;
 use crate::application::error::HttpError;
 use crate::application::pagination::{PageRequest, SnapshotCursor};
@@ -14,7 +16,10 @@ use crate::infra::http::admin::{
     AdminState,
     pagination::CursorState,
     selectors::PANEL,
-    shared::{Toast, blank_to_none_opt, datastar_replace, push_toasts, template_render_http_error},
+    shared::{
+        ListLimit, Toast, blank_to_none_opt, datastar_replace, push_toasts,
+        template_render_http_error,
+    },
 };
 use crate::presentation::admin::views as admin_views;
 use askama::Template;
@@ -25,22 +30,26 @@ pub struct SnapshotActionForm {
     pub trail: Option<String>,
     pub search: Option<String>,
     pub month: Option<String>,
+    #[serde(flatten)]
+    pub limit: ListLimit,
 }
 
 pub async fn admin_snapshot_rollback(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<SnapshotActionForm>,
 ) -> Response {
-    handle_action(state, id, form, Action::Rollback).await
+    handle_action(state, &session.username, id, form, Action::Rollback).await
 }
 
 pub async fn admin_snapshot_delete(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<SnapshotActionForm>,
 ) -> Response {
-    handle_action(state, id, form, Action::Delete).await
+    handle_action(state, &session.username, id, form, Action::Delete).await
 }
 
 #[derive(Clone, Copy)]
@@ -51,17 +60,19 @@ enum Action {
 
 async fn handle_action(
     state: AdminState,
+    actor: &str,
     id: Uuid,
     form: SnapshotActionForm,
     action: Action,
 ) -> Response {
-    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
-    let cursor = match cursor_state.decode_with(SnapshotCursor::decode, SOURCE) {
-        Ok(cursor) => cursor,
-        Err(err) => return err.into_response(),
-    };
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
+    let cursor =
+        match cursor_state.decode_with(state.db.cursor_keys(), SnapshotCursor::decode, SOURCE) {
+            Ok(cursor) => cursor,
+            Err(err) => return err.into_response(),
+        };
 
-    let actor = "admin:snapshots";
     let snapshot = match state.snapshots.find(id).await {
         Ok(Some(s)) => s,
         Ok(None) => {
@@ -96,7 +107,10 @@ async fn handle_action(
         Action::Delete => format!("Deleted snapshot v{}", snapshot.version),
     };
 
-    let page_request = PageRequest::new(admin_page_size(&state).await, cursor);
+    let page_request = PageRequest::new(
+        super::panel::admin_page_size(&state, cursor_state.limit()).await,
+        cursor,
+    );
     let panel_result = match action {
         Action::Rollback => {
             do_rollback(&state, actor, id, snapshot.entity_type, snapshot.entity_id).await
@@ -185,6 +199,11 @@ fn map_post_error(
         AdminPostError::ConstraintViolation(field) => SnapshotServiceError::Snapshot(
             crate::domain::snapshots::SnapshotError::Validation(field.to_string()),
         ),
+        err @ (AdminPostError::InvalidSlug(_) | AdminPostError::SlugConflict { .. }) => {
+            SnapshotServiceError::Snapshot(crate::domain::snapshots::SnapshotError::Validation(
+                err.to_string(),
+            ))
+        }
         AdminPostError::Repo(repo) => SnapshotServiceError::Repo(repo),
     }
 }
@@ -195,6 +214,9 @@ fn map_page_error(err: crate::application::admin::pages::AdminPageError) -> Snap
         AdminPageError::ConstraintViolation(field) => SnapshotServiceError::Snapshot(
             crate::domain::snapshots::SnapshotError::Validation(field.to_string()),
         ),
+        err @ AdminPageError::ReservedSlug { .. } => SnapshotServiceError::Snapshot(
+            crate::domain::snapshots::SnapshotError::Validation(err.to_string()),
+        ),
         AdminPageError::Render(render_err) => SnapshotServiceError::App(
             crate::application::error::AppError::unexpected(render_err.to_string()),
         ),
@@ -229,7 +251,9 @@ async fn load_panel(
     };
 
     let timezone = settings.timezone;
-    let public_site_url = super::panel::normalize_public_site_url(&settings.public_site_url);
+    let public_site_url = state
+        .base_path
+        .site_url_or_relative(&settings.public_site_url);
     let filter_meta = match validated_snapshot_filter_meta(filter) {
         Ok(meta) => meta,
         Err(err) => return Err(err.into_response()),
@@ -242,6 +266,7 @@ async fn load_panel(
         entity_id: filter_meta.entity_id,
         timezone,
         public_site_url: &public_site_url,
+        preview: &state.snapshot_preview,
     };
 
     let mut content = super::panel::build_content(meta, page, month_counts);
@@ -275,13 +300,6 @@ fn error_to_message(err: &SnapshotServiceError) -> String {
     }
 }
 
-async fn admin_page_size(state: &AdminState) -> u32 {
-    match state.db.load_site_settings().await {
-        Ok(settings) => settings.admin_page_size.clamp(1, 100).max(1) as u32,
-        Err(_) => 20,
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 struct SnapshotFilterMeta {
     entity_type: SnapshotEntityType,
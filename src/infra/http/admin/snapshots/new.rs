@@ -1,10 +1,12 @@
 use axum::{
+    Extension,
     extract::{Form, Path, State},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
 use uuid::Uuid;
 
+use crate::application::admin::auth::AdminSession;
 use crate::domain::types::SnapshotEntityType;
 use crate::{
     application::{
@@ -43,18 +45,20 @@ pub async fn admin_page_snapshot_new(
 
 pub async fn admin_post_snapshot_create(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<SnapshotCreateForm>,
 ) -> Response {
-    create_snapshot(&state, Entity::Post, id, form).await
+    create_snapshot(&state, &session.username, Entity::Post, id, form).await
 }
 
 pub async fn admin_page_snapshot_create(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<SnapshotCreateForm>,
 ) -> Response {
-    create_snapshot(&state, Entity::Page, id, form).await
+    create_snapshot(&state, &session.username, Entity::Page, id, form).await
 }
 
 #[derive(Clone, Copy)]
@@ -125,12 +129,12 @@ async fn render_form(state: &AdminState, entity: Entity, id: Uuid) -> Response {
 
 async fn create_snapshot(
     state: &AdminState,
+    actor: &str,
     entity: Entity,
     id: Uuid,
     form: SnapshotCreateForm,
 ) -> Response {
     let description = blank_to_none_opt(form.description);
-    let actor = "admin:snapshots";
 
     let result = match entity {
         Entity::Post => match state.posts.snapshot_source(id).await {
@@ -12,13 +12,17 @@ use crate::{
         error::HttpError,
         pagination::{CursorPage, PageRequest, SnapshotCursor},
         repos::{SettingsRepo, SnapshotFilter, SnapshotMonthCount, SnapshotRecord},
+        snapshot_preview::SnapshotPreviewService,
     },
-    domain::types::SnapshotEntityType,
+    domain::{routes::join_url, types::SnapshotEntityType},
     infra::http::admin::{
         AdminState,
         pagination::{self, CursorState},
         selectors::PANEL,
-        shared::{AdminPostQuery, blank_to_none_opt, datastar_replace, template_render_http_error},
+        shared::{
+            AdminPostQuery, ListLimit, blank_to_none_opt, datastar_replace,
+            template_render_http_error,
+        },
     },
     presentation::admin::views as admin_views,
     presentation::views::render_template_response,
@@ -39,6 +43,8 @@ pub struct SnapshotPanelForm {
     pub cursor: Option<String>,
     pub trail: Option<String>,
     pub clear: Option<String>,
+    #[serde(flatten)]
+    pub limit: ListLimit,
 }
 
 #[derive(Clone, Copy)]
@@ -111,11 +117,13 @@ async fn render_snapshots(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(query.cursor.clone(), query.trail.clone());
-    let cursor = match cursor_state.decode_with(SnapshotCursor::decode, SOURCE) {
-        Ok(cursor) => cursor,
-        Err(err) => return err.into_response(),
-    };
+    let cursor_state =
+        CursorState::new(query.cursor.clone(), query.trail.clone()).with_limit(&query.limit);
+    let cursor =
+        match cursor_state.decode_with(state.db.cursor_keys(), SnapshotCursor::decode, SOURCE) {
+            Ok(cursor) => cursor,
+            Err(err) => return err.into_response(),
+        };
 
     let filter = SnapshotFilter {
         entity_type: Some(entity.kind()),
@@ -142,11 +150,13 @@ async fn render_snapshots_panel(
     id: Uuid,
     form: SnapshotPanelForm,
 ) -> Response {
-    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
-    let cursor = match cursor_state.decode_with(SnapshotCursor::decode, SOURCE) {
-        Ok(cursor) => cursor,
-        Err(err) => return err.into_response(),
-    };
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
+    let cursor =
+        match cursor_state.decode_with(state.db.cursor_keys(), SnapshotCursor::decode, SOURCE) {
+            Ok(cursor) => cursor,
+            Err(err) => return err.into_response(),
+        };
 
     let mut filter = SnapshotFilter {
         entity_type: Some(entity.kind()),
@@ -179,7 +189,7 @@ pub(super) async fn build_snapshot_view(
     cursor_state: CursorState,
     cursor: Option<SnapshotCursor>,
 ) -> Result<admin_views::AdminSnapshotListView, Response> {
-    let page_request = PageRequest::new(admin_page_size(state).await, cursor);
+    let page_request = PageRequest::new(admin_page_size(state, cursor_state.limit()).await, cursor);
 
     let settings = match state.db.load_site_settings().await {
         Ok(settings) => settings,
@@ -194,7 +204,9 @@ pub(super) async fn build_snapshot_view(
         }
     };
     let timezone = settings.timezone;
-    let public_site_url = normalize_public_site_url(&settings.public_site_url);
+    let public_site_url = state
+        .base_path
+        .site_url_or_relative(&settings.public_site_url);
 
     let (page, month_counts) = match load_snapshots(state, &filter, page_request).await {
         Ok(res) => res,
@@ -208,6 +220,7 @@ pub(super) async fn build_snapshot_view(
         entity_id: id,
         timezone,
         public_site_url: &public_site_url,
+        preview: &state.snapshot_preview,
     };
 
     let mut content = build_content(meta, page, month_counts);
@@ -216,10 +229,10 @@ pub(super) async fn build_snapshot_view(
     Ok(content)
 }
 
-async fn admin_page_size(state: &AdminState) -> u32 {
+pub(super) async fn admin_page_size(state: &AdminState, limit: Option<u32>) -> u32 {
     match state.db.load_site_settings().await {
-        Ok(settings) => settings.admin_page_size.clamp(1, 100).max(1) as u32,
-        Err(_) => 20,
+        Ok(settings) => pagination::page_size(settings.admin_page_size, limit),
+        Err(_) => pagination::page_size(20, limit),
     }
 }
 
@@ -248,6 +261,7 @@ pub(super) struct SnapshotContentMeta<'a> {
     pub entity_id: Uuid,
     pub timezone: chrono_tz::Tz,
     pub public_site_url: &'a str,
+    pub preview: &'a SnapshotPreviewService,
 }
 
 pub(super) fn build_content(
@@ -255,21 +269,29 @@ pub(super) fn build_content(
     page: CursorPage<SnapshotRecord>,
     month_counts: Vec<SnapshotMonthCount>,
 ) -> admin_views::AdminSnapshotListView {
+    let now = time::OffsetDateTime::now_utc();
     let rows = page
         .items
         .into_iter()
-        .map(|record| admin_views::AdminSnapshotRowView {
-            id: record.id.to_string(),
-            version: record.version,
-            description: record.description.clone(),
-            created_at: admin_views::format_timestamp(record.created_at, meta.timezone),
-            preview_href: format!(
-                "{}{}/_preview/snapshot/{}",
-                meta.public_site_url, meta.entity_slug, record.id
-            ),
-            edit_href: format!("/snapshots/{}/edit", record.id),
-            rollback_action: format!("/snapshots/{}/rollback", record.id),
-            delete_action: format!("/snapshots/{}/delete", record.id),
+        .map(|record| {
+            let token = meta.preview.link_token(record.id, now);
+            admin_views::AdminSnapshotRowView {
+                id: record.id.to_string(),
+                version: record.version,
+                description: record.description.clone(),
+                created_at: admin_views::format_timestamp(record.created_at, meta.timezone),
+                preview_href: format!(
+                    "{}{}/_preview/snapshot/{}?token={token}",
+                    meta.public_site_url, meta.entity_slug, record.id
+                ),
+                share_href: join_url(
+                    meta.public_site_url,
+                    &format!("preview/snapshots/{}?token={token}", record.id),
+                ),
+                edit_href: format!("/snapshots/{}/edit", record.id),
+                rollback_action: format!("/snapshots/{}/rollback", record.id),
+                delete_action: format!("/snapshots/{}/delete", record.id),
+            }
         })
         .collect();
 
@@ -305,14 +327,7 @@ pub(super) fn build_content(
         next_page_state: None,
         custom_hidden_fields: Vec::new(),
         active_status_key: None,
-    }
-}
-
-pub(super) fn normalize_public_site_url(url: &str) -> String {
-    if url.ends_with('/') {
-        url.to_string()
-    } else {
-        format!("{url}/")
+        copy_toast_action: "/toasts".to_string(),
     }
 }
 
@@ -322,6 +337,9 @@ pub(super) fn apply_pagination_links(
 ) {
     content.cursor_param = cursor_state.current_token();
     content.trail = pagination::join_cursor_history(cursor_state.history_tokens());
+    content
+        .custom_hidden_fields
+        .extend(pagination::limit_hidden_field(cursor_state.limit()));
 
     let mut previous_history = cursor_state.clone_history();
     let previous_token = previous_history.pop();
@@ -1,13 +1,25 @@
-use std::sync::Arc;
+use std::num::NonZeroU64;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
 
 use crate::application::admin::{
-    audit::AdminAuditService, chrome::AdminChromeService, dashboard::AdminDashboardService,
-    jobs::AdminJobService, navigation::AdminNavigationService, pages::AdminPageService,
-    posts::AdminPostService, settings::AdminSettingsService, snapshots::AdminSnapshotService,
-    tags::AdminTagService, uploads::AdminUploadService,
+    audit::AdminAuditService, auth::AdminAuthService, chrome::AdminChromeService,
+    comments::AdminCommentService, dashboard::AdminDashboardService, jobs::AdminJobService,
+    navigation::AdminNavigationService, pages::AdminPageService, posts::AdminPostService,
+    schedule::AdminScheduleService, settings::AdminSettingsService,
+    snapshots::AdminSnapshotService, tags::AdminTagService, uploads::AdminUploadService,
 };
 use crate::application::api_keys::ApiKeyService;
-use crate::infra::{db::PostgresRepositories, uploads::UploadStorage};
+use crate::application::render::{PreviewDebouncer, RenderPreviewService};
+use crate::application::snapshot_preview::SnapshotPreviewService;
+use crate::cache::CacheTrigger;
+use crate::domain::routes::BasePath;
+use crate::domain::uploads::ContentTypePolicy;
+use crate::infra::http::api::rate_limit::ApiRateLimiter;
+use crate::infra::http::security_headers::SecurityHeaders;
+use crate::infra::{db::PostgresRepositories, reload::ConfigReloader, uploads::UploadStorage};
 
 #[derive(Clone)]
 pub struct AdminState {
@@ -16,14 +28,52 @@ pub struct AdminState {
     pub dashboard: Arc<AdminDashboardService>,
     pub posts: Arc<AdminPostService>,
     pub pages: Arc<AdminPageService>,
+    pub schedule: Arc<AdminScheduleService>,
     pub tags: Arc<AdminTagService>,
     pub navigation: Arc<AdminNavigationService>,
     pub settings: Arc<AdminSettingsService>,
     pub uploads: Arc<AdminUploadService>,
     pub upload_storage: Arc<UploadStorage>,
-    pub upload_limit_bytes: u64,
+    /// Largest accepted upload request; changed by configuration reloads.
+    pub upload_limit: Arc<AtomicU64>,
+    /// Largest single file of a multi-file upload; `None` leaves each file
+    /// bounded only by `upload_limit`.
+    pub upload_file_limit: Option<NonZeroU64>,
+    /// Handling of uploads whose contents contradict their declared type.
+    pub upload_content_type_policy: ContentTypePolicy,
     pub jobs: Arc<AdminJobService>,
     pub audit: Arc<AdminAuditService>,
     pub api_keys: Arc<ApiKeyService>,
     pub snapshots: Arc<AdminSnapshotService>,
+    /// Issues the tokens of shared snapshot preview links.
+    pub snapshot_preview: Arc<SnapshotPreviewService>,
+    pub comments: Arc<AdminCommentService>,
+    pub auth: Arc<AdminAuthService>,
+    /// Cache handle for manual purges; `None` when caching is disabled.
+    pub cache: Option<Arc<CacheTrigger>>,
+    pub render_preview: Arc<RenderPreviewService>,
+    /// Per-user limiter for render previews, shared with the API listener.
+    pub render_preview_rate_limiter: Arc<ApiRateLimiter>,
+    /// Collapses bursts of editor keystrokes into one live preview render.
+    pub render_preview_debouncer: Arc<PreviewDebouncer>,
+    /// Per-client limiter for sign-in attempts.
+    pub login_rate_limiter: Arc<ApiRateLimiter>,
+    pub session_cookie_secure: bool,
+    pub config_reload: Arc<ConfigReloader>,
+    pub security_headers: SecurityHeaders,
+    /// Prefix of the public site, for links from the admin to it.
+    pub base_path: BasePath,
+}
+
+impl AdminState {
+    pub fn upload_limit_bytes(&self) -> u64 {
+        self.upload_limit.load(Ordering::Relaxed)
+    }
+
+    /// Largest accepted single file, never above the request limit.
+    pub fn upload_file_limit_bytes(&self) -> u64 {
+        let request = self.upload_limit_bytes();
+        self.upload_file_limit
+            .map_or(request, |limit| limit.get().min(request))
+    }
 }
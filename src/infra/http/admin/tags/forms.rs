@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::infra::http::admin::shared::ListLimit;
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct AdminTagForm {
     pub(super) name: String,
@@ -20,6 +22,8 @@ pub(crate) struct AdminTagPanelForm {
     pub(super) month: Option<String>,
     pub(super) cursor: Option<String>,
     pub(super) trail: Option<String>,
+    #[serde(flatten)]
+    pub(super) limit: ListLimit,
     pub(super) clear: Option<String>,
 }
 
@@ -1,5 +1,6 @@
 use askama::Template;
 use axum::{
+    Extension,
     extract::{Form, Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Redirect, Response},
@@ -7,6 +8,7 @@ use axum::{
 use uuid::Uuid;
 
 use crate::{
+    application::admin::auth::AdminSession,
     application::{
         admin::tags::{CreateTagCommand, UpdateTagCommand},
         pagination::TagCursor,
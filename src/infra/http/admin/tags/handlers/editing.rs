@@ -34,10 +34,11 @@ pub(crate) async fn admin_tag_edit(
 
 pub(crate) async fn admin_tag_update(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminTagForm>,
 ) -> Response {
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     let command = UpdateTagCommand {
         id,
@@ -82,6 +83,7 @@ pub(crate) async fn admin_tag_update(
 
 pub(crate) async fn admin_tag_create(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Form(form): Form<AdminTagForm>,
 ) -> Response {
     let pinned_filter = match parse_tag_status(form.status_filter.as_deref()) {
@@ -92,7 +94,7 @@ pub(crate) async fn admin_tag_create(
     let filter =
         shared::build_tag_filter(form.filter_search.as_deref(), form.filter_month.as_deref());
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
-    let actor = "admin";
+    let actor = session.username.as_str();
     let pinned = shared::parse_checkbox_flag(&form.pinned);
 
     let command = CreateTagCommand {
@@ -169,15 +171,21 @@ async fn respond_with_tag_editor_success(
         }
     };
 
-    let cursor = match cursor_state.decode_with(TagCursor::decode, template_source) {
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        TagCursor::decode,
+        template_source,
+    ) {
         Ok(cursor) => cursor,
         Err(err) => return err.into_response(),
     };
 
-    let mut list_content = match build_tag_list_view(state, pinned_filter, filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_tag_error(template_source, err).into_response(),
-    };
+    let mut list_content =
+        match build_tag_list_view(state, pinned_filter, filter, cursor, cursor_state.limit()).await
+        {
+            Ok(content) => content,
+            Err(err) => return admin_tag_error(template_source, err).into_response(),
+        };
 
     apply_pagination_links(&mut list_content, &cursor_state);
 
@@ -9,7 +9,8 @@ pub(crate) async fn admin_tags(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(query.cursor.clone(), query.trail.clone());
+    let cursor_state =
+        CursorState::new(query.cursor.clone(), query.trail.clone()).with_limit(&query.limit);
 
     let pinned_filter = match parse_tag_status(query.status.as_deref()) {
         Ok(status) => status,
@@ -18,15 +19,22 @@ pub(crate) async fn admin_tags(
 
     let filter = shared::build_tag_filter(query.search.as_deref(), query.month.as_deref());
 
-    let cursor = match cursor_state.decode_with(TagCursor::decode, "infra::http::admin_tags") {
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        TagCursor::decode,
+        "infra::http::admin_tags",
+    ) {
         Ok(cursor) => cursor,
         Err(err) => return err.into_response(),
     };
 
-    let mut content = match build_tag_list_view(&state, pinned_filter, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_tag_error("infra::http::admin_tags", err).into_response(),
-    };
+    let mut content =
+        match build_tag_list_view(&state, pinned_filter, &filter, cursor, cursor_state.limit())
+            .await
+        {
+            Ok(content) => content,
+            Err(err) => return admin_tag_error("infra::http::admin_tags", err).into_response(),
+        };
 
     apply_pagination_links(&mut content, &cursor_state);
 
@@ -43,10 +51,14 @@ pub(crate) async fn admin_tags_panel(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
 
-    let cursor = match cursor_state.decode_with(TagCursor::decode, "infra::http::admin_tags_panel")
-    {
+    let cursor = match cursor_state.decode_with(
+        state.db.cursor_keys(),
+        TagCursor::decode,
+        "infra::http::admin_tags_panel",
+    ) {
         Ok(cursor) => cursor,
         Err(err) => return err.into_response(),
     };
@@ -57,10 +69,15 @@ pub(crate) async fn admin_tags_panel(
         shared::build_tag_filter(form.search.as_deref(), form.month.as_deref())
     };
 
-    let mut content = match build_tag_list_view(&state, pinned_filter, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_tag_error("infra::http::admin_tags_panel", err).into_response(),
-    };
+    let mut content =
+        match build_tag_list_view(&state, pinned_filter, &filter, cursor, cursor_state.limit())
+            .await
+        {
+            Ok(content) => content,
+            Err(err) => {
+                return admin_tag_error("infra::http::admin_tags_panel", err).into_response();
+            }
+        };
 
     apply_pagination_links(&mut content, &cursor_state);
 
@@ -3,6 +3,7 @@ use super::*;
 
 pub(crate) async fn admin_tag_delete(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminTagDeleteForm>,
 ) -> Response {
@@ -33,7 +34,7 @@ pub(crate) async fn admin_tag_delete(
         Err(err) => return admin_tag_error("infra::http::admin_tag_delete", err).into_response(),
     };
 
-    let actor = "admin";
+    let actor = session.username.as_str();
 
     let message = match state.tags.delete_tag(actor, id).await {
         Ok(()) => Toast::success(format!(
@@ -65,11 +66,13 @@ pub(crate) async fn admin_tag_delete(
 
 pub(crate) async fn admin_tag_pin(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminTagPinForm>,
 ) -> Response {
     handle_tag_pin_action(
         &state,
+        &session.username,
         id,
         form,
         true,
@@ -81,11 +84,13 @@ pub(crate) async fn admin_tag_pin(
 
 pub(crate) async fn admin_tag_unpin(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminTagPinForm>,
 ) -> Response {
     handle_tag_pin_action(
         &state,
+        &session.username,
         id,
         form,
         false,
@@ -97,6 +102,7 @@ pub(crate) async fn admin_tag_unpin(
 
 async fn handle_tag_pin_action(
     state: &AdminState,
+    actor: &str,
     id: Uuid,
     form: AdminTagPinForm,
     pinned: bool,
@@ -112,8 +118,6 @@ async fn handle_tag_pin_action(
         shared::build_tag_filter(form.filter_search.as_deref(), form.filter_month.as_deref());
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
 
-    let actor = "admin";
-
     let message = match state.tags.update_tag_pinned(actor, id, pinned).await {
         Ok(tag) => {
             let verb = tag_status_label(pinned);
@@ -41,15 +41,18 @@ pub(super) async fn respond_with_tags_panel_internal(
         template_source,
     } = params;
 
-    let cursor = match cursor_state.decode_with(TagCursor::decode, error_source) {
-        Ok(cursor) => cursor,
-        Err(err) => return err.into_response(),
-    };
+    let cursor =
+        match cursor_state.decode_with(state.db.cursor_keys(), TagCursor::decode, error_source) {
+            Ok(cursor) => cursor,
+            Err(err) => return err.into_response(),
+        };
 
-    let mut content = match build_tag_list_view(state, pinned_filter, filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_tag_error(error_source, err).into_response(),
-    };
+    let mut content =
+        match build_tag_list_view(state, pinned_filter, filter, cursor, cursor_state.limit()).await
+        {
+            Ok(content) => content,
+            Err(err) => return admin_tag_error(error_source, err).into_response(),
+        };
 
     apply_pagination_links(&mut content, &cursor_state);
 
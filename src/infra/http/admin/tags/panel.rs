@@ -7,6 +7,7 @@ use crate::{
         pagination::{PageRequest, TagCursor},
         repos::{SettingsRepo, TagQueryFilter},
     },
+    domain::routes::join_url,
     infra::http::admin::{
         AdminState,
         pagination::{self, CursorState},
@@ -22,10 +23,13 @@ pub(super) async fn build_tag_list_view(
     pinned_filter: Option<bool>,
     filter: &TagQueryFilter,
     cursor: Option<TagCursor>,
+    limit: Option<u32>,
 ) -> Result<admin_views::AdminTagListView, AdminTagError> {
     let settings = state.db.load_site_settings().await?;
-    let admin_page_size = settings.admin_page_size.clamp(1, 100).max(1) as u32;
-    let public_site_url = normalize_public_site_url(&settings.public_site_url);
+    let admin_page_size = pagination::page_size(settings.admin_page_size, limit);
+    let public_site_url = state
+        .base_path
+        .site_url_or_relative(&settings.public_site_url);
 
     let counts_filter = filter.clone();
     let list_filter = filter.clone();
@@ -49,7 +53,7 @@ pub(super) async fn build_tag_list_view(
             let display_time = admin_views::format_timestamp(display_time_source, timezone);
             let id_str = record.id.to_string();
             let slug = record.slug.clone();
-            let public_href = format!("{}tags/{}", public_site_url, slug);
+            let public_href = join_url(&public_site_url, &format!("tags/{slug}"));
             admin_views::AdminTagRowView {
                 id: id_str.clone(),
                 name: record.name,
@@ -114,15 +118,19 @@ pub(super) async fn build_tag_list_view(
         tag_filter_label: "Tag".to_string(),
         tag_filter_all_label: "All tags".to_string(),
         tag_filter_field: "tag".to_string(),
-        custom_hidden_fields: build_tag_hidden_fields(filter),
+        custom_hidden_fields: build_tag_hidden_fields(filter, limit),
     })
 }
 
-fn build_tag_hidden_fields(filter: &TagQueryFilter) -> Vec<admin_views::AdminHiddenField> {
+fn build_tag_hidden_fields(
+    filter: &TagQueryFilter,
+    limit: Option<u32>,
+) -> Vec<admin_views::AdminHiddenField> {
     let mut fields = Vec::new();
     if let Some(ref month) = filter.month {
         fields.push(admin_views::AdminHiddenField::new("month", month.clone()));
     }
+    fields.extend(pagination::limit_hidden_field(limit));
     fields
 }
 
@@ -172,11 +180,3 @@ pub(super) fn apply_pagination_links(
         content.next_page_state = None;
     }
 }
-
-fn normalize_public_site_url(url: &str) -> String {
-    if url.ends_with('/') {
-        url.to_string()
-    } else {
-        format!("{}/", url)
-    }
-}
@@ -9,6 +9,7 @@ use crate::{
 
 use super::super::shared::Toast;
 
+#[derive(Debug)]
 pub(super) enum UploadPayloadError {
     Missing,
     PayloadTooLarge,
@@ -22,7 +23,9 @@ impl UploadPayloadError {
             UploadPayloadError::Missing => Toast::error("Please choose a file to upload"),
             UploadPayloadError::PayloadTooLarge => {
                 let limit_mib = limit_bytes.div_ceil(1_048_576);
-                Toast::error(format!("File is too large (limit is {limit_mib} MiB)"))
+                Toast::error(format!(
+                    "Upload is too large (limit is {limit_mib} MiB per request)"
+                ))
             }
             UploadPayloadError::InvalidFormData => Toast::error("Upload form data was invalid"),
             UploadPayloadError::Read { .. } => Toast::error("Upload failed, please try again"),
@@ -39,5 +42,11 @@ pub(super) fn admin_upload_error(source: &'static str, err: AdminUploadError) ->
             "The requested upload does not exist",
         ),
         AdminUploadError::Repo(repo) => repo_error_to_http(source, repo),
+        AdminUploadError::Storage(err) => HttpError::new(
+            source,
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to read stored upload",
+            err.to_string(),
+        ),
     }
 }
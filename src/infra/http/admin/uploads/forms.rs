@@ -2,6 +2,8 @@
 
 use serde::Deserialize;
 
+use crate::infra::http::admin::shared::ListLimit;
+
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
 pub(crate) struct AdminUploadQuery {
@@ -11,6 +13,8 @@ pub(crate) struct AdminUploadQuery {
     #[serde(rename = "content_type")]
     pub(super) content_type: Option<String>,
     pub(super) month: Option<String>,
+    #[serde(flatten)]
+    pub(super) limit: ListLimit,
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,6 +25,8 @@ pub(crate) struct AdminUploadPanelForm {
     pub(super) month: Option<String>,
     pub(super) cursor: Option<String>,
     pub(super) trail: Option<String>,
+    #[serde(flatten)]
+    pub(super) limit: ListLimit,
     pub(super) clear: Option<String>,
 }
 
@@ -1,6 +1,7 @@
 //! HTTP handlers for upload admin.
 
 use axum::{
+    Extension,
     extract::{Form, Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
@@ -10,6 +11,7 @@ use tracing::{error, warn};
 use uuid::Uuid;
 
 use crate::{
+    application::admin::auth::AdminSession,
     application::{
         admin::uploads::AdminUploadError, error::HttpError, pagination::UploadCursor,
         repos::UploadQueryFilter,
@@ -28,14 +30,14 @@ use super::errors::admin_upload_error;
 use super::forms::{
     AdminUploadDeleteForm, AdminUploadPanelForm, AdminUploadQuery, UploadQueuePreviewForm,
 };
-use super::multipart::read_upload_payload;
+use super::multipart::{UploadBatch, UploadLimits, read_upload_batch};
 use super::panel::{
     apply_upload_pagination_links, build_upload_filter, build_upload_list_view,
     render_upload_queue_html,
 };
 use super::queue::parse_queue_manifest;
-use super::response::{build_download_response, respond_with_upload_panel};
-use super::storage::{handle_upload_payload, upload_payload_error};
+use super::response::{build_download_response, respond_with_upload_panel, upload_form_view};
+use super::storage::{respond_with_upload_outcomes, store_upload_parts, upload_payload_error};
 
 const SOURCE_BASE: &str = "infra::http::admin_uploads";
 
@@ -48,11 +50,13 @@ pub(crate) async fn admin_uploads(
         Err(err) => return err.into_response(),
     };
 
-    let cursor_state = CursorState::new(query.cursor.clone(), query.trail.clone());
-    let cursor = match cursor_state.decode_with(UploadCursor::decode, SOURCE_BASE) {
-        Ok(cursor) => cursor,
-        Err(err) => return err.into_response(),
-    };
+    let cursor_state =
+        CursorState::new(query.cursor.clone(), query.trail.clone()).with_limit(&query.limit);
+    let cursor =
+        match cursor_state.decode_with(state.db.cursor_keys(), UploadCursor::decode, SOURCE_BASE) {
+            Ok(cursor) => cursor,
+            Err(err) => return err.into_response(),
+        };
 
     let filter = build_upload_filter(
         query.search.as_deref(),
@@ -60,10 +64,11 @@ pub(crate) async fn admin_uploads(
         query.month.as_deref(),
     );
 
-    let mut content = match build_upload_list_view(&state, &filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_upload_error(SOURCE_BASE, err).into_response(),
-    };
+    let mut content =
+        match build_upload_list_view(&state, &filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => return admin_upload_error(SOURCE_BASE, err).into_response(),
+        };
 
     apply_upload_pagination_links(&mut content, &cursor_state);
 
@@ -75,11 +80,13 @@ pub(crate) async fn admin_uploads_panel(
     State(state): State<AdminState>,
     Form(form): Form<AdminUploadPanelForm>,
 ) -> Response {
-    let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
-    let cursor = match cursor_state.decode_with(UploadCursor::decode, SOURCE_BASE) {
-        Ok(cursor) => cursor,
-        Err(err) => return err.into_response(),
-    };
+    let cursor_state =
+        CursorState::new(form.cursor.clone(), form.trail.clone()).with_limit(&form.limit);
+    let cursor =
+        match cursor_state.decode_with(state.db.cursor_keys(), UploadCursor::decode, SOURCE_BASE) {
+            Ok(cursor) => cursor,
+            Err(err) => return err.into_response(),
+        };
 
     let filter = if form.clear.is_some() {
         UploadQueryFilter::default()
@@ -110,7 +117,7 @@ pub(crate) async fn admin_upload_queue_preview(
     let queue = match parse_queue_manifest(&form.queue_manifest) {
         Ok(entries) => admin_views::AdminUploadQueueView {
             entries,
-            limit_mib: state.upload_limit_bytes.div_ceil(1_048_576),
+            limit_mib: state.upload_file_limit_bytes().div_ceil(1_048_576),
         },
         Err(err) => return err.into_response(),
     };
@@ -130,19 +137,7 @@ pub(crate) async fn admin_upload_new(State(state): State<AdminState>) -> Respons
         Err(err) => return err.into_response(),
     };
 
-    let content = admin_views::AdminUploadFormView {
-        heading: "Upload file".to_string(),
-        upload_action: "/uploads".to_string(),
-        queue_sync_action: "/uploads/queue/preview".to_string(),
-        back_href: "/uploads".to_string(),
-        toast_action: "/toasts".to_string(),
-        upload_limit_bytes: state.upload_limit_bytes,
-        upload_limit_mib: state.upload_limit_bytes.div_ceil(1_048_576),
-        queue: admin_views::AdminUploadQueueView {
-            entries: Vec::new(),
-            limit_mib: state.upload_limit_bytes.div_ceil(1_048_576),
-        },
-    };
+    let content = upload_form_view(&state);
 
     let view = admin_views::AdminLayout::new(chrome, content);
     render_template_response(admin_views::AdminUploadNewTemplate { view }, StatusCode::OK)
@@ -150,24 +145,46 @@ pub(crate) async fn admin_upload_new(State(state): State<AdminState>) -> Respons
 
 pub(crate) async fn admin_upload_store(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     mut multipart: Multipart,
 ) -> Response {
-    match read_upload_payload(&mut multipart).await {
-        Ok(payload) => handle_upload_payload(&state, payload).await,
-        Err(err) => upload_payload_error(&state, err).await,
+    let limits = UploadLimits {
+        file_bytes: state.upload_file_limit_bytes(),
+        content_type_policy: state.upload_content_type_policy,
+    };
+    let UploadBatch {
+        parts,
+        suppress_panel_patch,
+    } = match read_upload_batch(&mut multipart, &limits).await {
+        Ok(batch) => batch,
+        Err(err) => return upload_payload_error(&state, err).await,
+    };
+
+    match store_upload_parts(
+        &state.uploads,
+        &state.upload_storage,
+        &session.username,
+        parts,
+    )
+    .await
+    {
+        Ok(outcomes) => respond_with_upload_outcomes(&state, &outcomes, suppress_panel_patch).await,
+        Err(err) => admin_upload_error("infra::http::admin_upload_store", err).into_response(),
     }
 }
 
 pub(crate) async fn admin_upload_delete(
     State(state): State<AdminState>,
+    Extension(session): Extension<AdminSession>,
     Path(id): Path<Uuid>,
     Form(form): Form<AdminUploadDeleteForm>,
 ) -> Response {
     let cursor_state = CursorState::new(form.cursor.clone(), form.trail.clone());
-    let cursor = match cursor_state.decode_with(UploadCursor::decode, SOURCE_BASE) {
-        Ok(cursor) => cursor,
-        Err(err) => return err.into_response(),
-    };
+    let cursor =
+        match cursor_state.decode_with(state.db.cursor_keys(), UploadCursor::decode, SOURCE_BASE) {
+            Ok(cursor) => cursor,
+            Err(err) => return err.into_response(),
+        };
 
     let filter = build_upload_filter(
         form.search.as_deref(),
@@ -175,7 +192,7 @@ pub(crate) async fn admin_upload_delete(
         form.month.as_deref(),
     );
 
-    let actor = "admin";
+    let actor = session.username.as_str();
     let result = state.uploads.delete_upload(actor, id).await;
 
     match result {
@@ -214,11 +231,7 @@ pub(crate) async fn admin_upload_delete(
             )
             .await
         }
-        Err(AdminUploadError::Repo(err)) => admin_upload_error(
-            "infra::http::admin_upload_delete",
-            AdminUploadError::Repo(err),
-        )
-        .into_response(),
+        Err(err) => admin_upload_error("infra::http::admin_upload_delete", err).into_response(),
     }
 }
 
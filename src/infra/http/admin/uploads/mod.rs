@@ -7,7 +7,7 @@
 //! - `panel`: Panel building and view construction
 //! - `response`: Response helper functions
 //! - `storage`: Upload storage processing logic
-//! - `multipart`: Multipart payload parsing
+//! - `multipart`: Multipart payload parsing, one batch of files per request
 //! - `queue`: Queue-related functions
 
 mod errors;
@@ -18,6 +18,8 @@ mod panel;
 mod queue;
 mod response;
 mod storage;
+#[cfg(test)]
+mod tests;
 
 pub(super) use handlers::{
     admin_upload_delete, admin_upload_download, admin_upload_new, admin_upload_queue_preview,
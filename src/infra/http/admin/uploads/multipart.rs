@@ -2,95 +2,158 @@
 
 use axum::http::StatusCode;
 use axum_extra::extract::Multipart;
-use axum_extra::extract::multipart::Field;
+use axum_extra::extract::multipart::{Field, MultipartError};
+use bytes::{Bytes, BytesMut};
 use tracing::error;
 
+use crate::domain::uploads::{ContentTypePolicy, SNIFF_LEN, resolve_content_type};
+
 use super::errors::UploadPayloadError;
 
 const SOURCE_BASE: &str = "infra::http::admin_uploads";
 
-pub(super) struct UploadPayload {
+/// One `file` part of an upload request, read into memory and checked.
+pub(super) struct UploadPart {
+    /// Row of the upload queue the file was sent from.
+    pub(super) queue_entry_id: Option<String>,
     pub(super) filename: String,
     pub(super) content_type: String,
-    pub(super) field: Field,
-    pub(super) queue_entry_id: Option<String>,
+    /// The file contents, or why the file was rejected before anything was
+    /// stored.
+    pub(super) contents: Result<Bytes, String>,
+}
+
+/// Every file of an upload request.
+pub(super) struct UploadBatch {
+    pub(super) parts: Vec<UploadPart>,
     pub(super) suppress_panel_patch: bool,
 }
 
-pub(super) async fn read_upload_payload(
+/// Checks each file must pass before it is stored.
+pub(super) struct UploadLimits {
+    pub(super) file_bytes: u64,
+    pub(super) content_type_policy: ContentTypePolicy,
+}
+
+/// Read every part of an upload request.
+///
+/// A `queue_entry_id` field applies to the next `file` part. Files are
+/// buffered whole, bounded by the request limit, so all of them are checked
+/// before the first is stored; a rejected file is kept with its reason and
+/// the rest of the request is still read.
+pub(super) async fn read_upload_batch(
     multipart: &mut Multipart,
-) -> Result<UploadPayload, UploadPayloadError> {
+    limits: &UploadLimits,
+) -> Result<UploadBatch, UploadPayloadError> {
+    let mut parts = Vec::new();
     let mut queue_entry_id: Option<String> = None;
     let mut suppress_panel_patch = false;
-    loop {
-        match multipart.next_field().await {
-            Ok(Some(field)) => {
-                match field.name() {
-                    Some("queue_entry_id") => {
-                        let value = field
-                            .text()
-                            .await
-                            .map_err(|_| UploadPayloadError::InvalidFormData)?
-                            .trim()
-                            .to_string();
-                        if !value.is_empty() {
-                            queue_entry_id = Some(value);
-                        }
-                        continue;
-                    }
-                    Some("suppress_panel_patch") => {
-                        let value = field
-                            .text()
-                            .await
-                            .map_err(|_| UploadPayloadError::InvalidFormData)?
-                            .trim()
-                            .to_ascii_lowercase();
-                        suppress_panel_patch =
-                            matches!(value.as_str(), "true" | "1" | "yes" | "on");
-                        continue;
-                    }
-                    Some("file") => {}
-                    _ => continue,
-                }
-
-                let filename = field
-                    .file_name()
-                    .map(|value| value.to_string())
-                    .filter(|value| !value.trim().is_empty())
-                    .unwrap_or_else(|| "upload.bin".to_string());
-
-                let content_type = field
-                    .content_type()
-                    .map(|mime| mime.to_string())
-                    .unwrap_or_else(|| "application/octet-stream".to_string());
-
-                return Ok(UploadPayload {
-                    filename,
-                    content_type,
-                    field,
-                    queue_entry_id,
-                    suppress_panel_patch,
-                });
+
+    while let Some(field) = multipart.next_field().await.map_err(payload_error)? {
+        match field.name() {
+            Some("queue_entry_id") => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|_| UploadPayloadError::InvalidFormData)?
+                    .trim()
+                    .to_string();
+                queue_entry_id = (!value.is_empty()).then_some(value);
+            }
+            Some("suppress_panel_patch") => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|_| UploadPayloadError::InvalidFormData)?
+                    .trim()
+                    .to_ascii_lowercase();
+                suppress_panel_patch = matches!(value.as_str(), "true" | "1" | "yes" | "on");
             }
-            Ok(None) => break,
-            Err(err) => {
-                let status = err.status();
-                error!(
-                    target = SOURCE_BASE,
-                    status = status.as_u16(),
-                    error = %err,
-                    "failed to read multipart payload"
-                );
-                return Err(match status {
-                    StatusCode::PAYLOAD_TOO_LARGE => UploadPayloadError::PayloadTooLarge,
-                    StatusCode::BAD_REQUEST => UploadPayloadError::InvalidFormData,
-                    _ => UploadPayloadError::Read {
-                        _detail: err.to_string(),
-                    },
-                });
+            Some("file") => {
+                let part = read_file_part(field, queue_entry_id.take(), limits).await?;
+                parts.push(part);
             }
+            _ => {}
         }
     }
 
-    Err(UploadPayloadError::Missing)
+    if parts.is_empty() {
+        return Err(UploadPayloadError::Missing);
+    }
+
+    Ok(UploadBatch {
+        parts,
+        suppress_panel_patch,
+    })
+}
+
+async fn read_file_part(
+    mut field: Field,
+    queue_entry_id: Option<String>,
+    limits: &UploadLimits,
+) -> Result<UploadPart, UploadPayloadError> {
+    let filename = field
+        .file_name()
+        .map(|value| value.to_string())
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "upload.bin".to_string());
+    let declared = field.content_type().map(|mime| mime.to_string());
+
+    // An oversized file is drained rather than kept so the parts after it
+    // can still be read.
+    let mut buffer = BytesMut::new();
+    let mut oversized = false;
+    while let Some(chunk) = field.chunk().await.map_err(payload_error)? {
+        if oversized {
+            continue;
+        }
+        if (buffer.len() + chunk.len()) as u64 > limits.file_bytes {
+            oversized = true;
+            buffer = BytesMut::new();
+            continue;
+        }
+        buffer.extend_from_slice(&chunk);
+    }
+
+    let head = &buffer[..buffer.len().min(SNIFF_LEN)];
+    let resolved = resolve_content_type(declared.as_deref(), head, limits.content_type_policy);
+    let content_type = match &resolved {
+        Ok(content_type) => content_type.clone(),
+        Err(_) => declared.unwrap_or_default(),
+    };
+
+    let contents = if oversized {
+        let limit_mib = limits.file_bytes.div_ceil(1_048_576);
+        Err(format!("File is too large (limit is {limit_mib} MiB)"))
+    } else if buffer.is_empty() {
+        Err("Uploaded file is empty".to_string())
+    } else if let Err(mismatch) = resolved {
+        Err(format!("File type does not match its contents: {mismatch}"))
+    } else {
+        Ok(buffer.freeze())
+    };
+
+    Ok(UploadPart {
+        queue_entry_id,
+        filename,
+        content_type,
+        contents,
+    })
+}
+
+fn payload_error(err: MultipartError) -> UploadPayloadError {
+    let status = err.status();
+    error!(
+        target = SOURCE_BASE,
+        status = status.as_u16(),
+        error = %err,
+        "failed to read multipart payload"
+    );
+    match status {
+        StatusCode::PAYLOAD_TOO_LARGE => UploadPayloadError::PayloadTooLarge,
+        StatusCode::BAD_REQUEST => UploadPayloadError::InvalidFormData,
+        _ => UploadPayloadError::Read {
+            _detail: err.to_string(),
+        },
+    }
 }
@@ -11,7 +11,7 @@ use crate::{
         pagination::UploadCursor,
         repos::{SettingsRepo, UploadQueryFilter},
     },
-    domain::{entities::UploadRecord, uploads},
+    domain::{entities::UploadRecord, routes::join_url, uploads},
     presentation::admin::views as admin_views,
     util::bytes::format_bytes,
 };
@@ -35,6 +35,7 @@ pub(super) fn build_upload_filter(
         search: blank_to_none_opt(search.map(str::to_string)),
         content_type: blank_to_none_opt(content_type.map(str::to_string)),
         month: blank_to_none_opt(month.map(str::to_string)),
+        checksum: None,
     }
 }
 
@@ -42,11 +43,14 @@ pub(super) async fn build_upload_list_view(
     state: &AdminState,
     filter: &UploadQueryFilter,
     cursor: Option<UploadCursor>,
+    limit: Option<u32>,
 ) -> Result<admin_views::AdminUploadListView, AdminUploadError> {
     let settings = state.db.load_site_settings().await?;
-    let admin_page_size = settings.admin_page_size.clamp(1, 100) as u32;
+    let admin_page_size = pagination::page_size(settings.admin_page_size, limit);
     let timezone = settings.timezone;
-    let public_site_url = normalize_public_site_url(&settings.public_site_url);
+    let public_site_url = state
+        .base_path
+        .site_url_or_relative(&settings.public_site_url);
 
     let page_request = crate::application::pagination::PageRequest::new(admin_page_size, cursor);
 
@@ -121,11 +125,14 @@ pub(super) async fn build_upload_list_view(
         tag_filter_enabled: true,
         month_filter_enabled: true,
         copy_toast_action: "/toasts".to_string(),
-        custom_hidden_fields: build_upload_hidden_fields(filter),
+        custom_hidden_fields: build_upload_hidden_fields(filter, limit),
     })
 }
 
-fn build_upload_hidden_fields(filter: &UploadQueryFilter) -> Vec<admin_views::AdminHiddenField> {
+fn build_upload_hidden_fields(
+    filter: &UploadQueryFilter,
+    limit: Option<u32>,
+) -> Vec<admin_views::AdminHiddenField> {
     let mut fields = Vec::new();
     if let Some(ref ct) = filter.content_type {
         fields.push(admin_views::AdminHiddenField::new(
@@ -136,6 +143,7 @@ fn build_upload_hidden_fields(filter: &UploadQueryFilter) -> Vec<admin_views::Ad
     if let Some(ref month) = filter.month {
         fields.push(admin_views::AdminHiddenField::new("month", month.clone()));
     }
+    fields.extend(pagination::limit_hidden_field(limit));
     fields
 }
 
@@ -188,7 +196,7 @@ pub(super) fn map_upload_row(
         Ok(value) => format_bytes(value),
         Err(_) => format_bytes(0),
     };
-    let mut public_href = format!("{}uploads/{}", public_site_url, record.stored_path);
+    let mut public_href = join_url(public_site_url, &format!("uploads/{}", record.stored_path));
     let mut query = form_urlencoded::Serializer::new(String::new());
     for (key, value) in record.metadata.query_pairs() {
         query.append_pair(&key, &value);
@@ -215,6 +223,7 @@ pub(super) fn map_upload_row(
         delete_action: format!("/uploads/{}/delete", record.id),
         preview_href,
         public_href,
+        checksum_mismatch: record.checksum_mismatch,
     }
 }
 
@@ -251,11 +260,3 @@ pub(super) fn apply_upload_pagination_links(
         content.next_page_state = None;
     }
 }
-
-pub(super) fn normalize_public_site_url(url: &str) -> String {
-    if url.ends_with('/') {
-        url.to_string()
-    } else {
-        format!("{url}/")
-    }
-}
@@ -5,13 +5,9 @@ use serde_json::json;
 
 use crate::{application::error::HttpError, util::bytes::format_bytes};
 
-use super::super::AdminState;
-use super::super::shared::Toast;
 use super::forms::UploadQueueManifestEntry;
-use super::response::respond_with_upload_form;
 use crate::application::stream::StreamBuilder;
 use crate::presentation::admin::views as admin_views;
-use axum::response::Response;
 
 pub(super) const UPLOAD_QUEUE_EVENT: &str = "admin:upload-entry";
 
@@ -91,28 +87,6 @@ pub(super) fn queue_status_label(status: &str) -> String {
     .to_string()
 }
 
-pub(super) async fn respond_with_queue_error_or_form(
-    state: &AdminState,
-    entry_id: Option<&str>,
-    message: String,
-    suppress_panel_patch: bool,
-) -> Response {
-    if let Some(id) = entry_id {
-        let mut stream = StreamBuilder::new();
-        push_queue_event(
-            &mut stream,
-            Some(id),
-            "error",
-            Some(&message),
-            None,
-            suppress_panel_patch,
-        );
-        return stream.into_response();
-    }
-
-    respond_with_upload_form(state, Toast::error(message)).await
-}
-
 pub(super) fn push_queue_event(
     stream: &mut StreamBuilder,
     entry_id: Option<&str>,
@@ -26,20 +26,26 @@ use super::panel::{
     render_upload_panel_html, wrap_content,
 };
 
-pub(super) async fn respond_with_upload_form(state: &AdminState, toast: Toast) -> Response {
-    let content = admin_views::AdminUploadFormView {
+pub(super) fn upload_form_view(state: &AdminState) -> admin_views::AdminUploadFormView {
+    let file_limit_bytes = state.upload_file_limit_bytes();
+    admin_views::AdminUploadFormView {
         heading: "Upload file".to_string(),
         upload_action: "/uploads".to_string(),
         queue_sync_action: "/uploads/queue/preview".to_string(),
         back_href: "/uploads".to_string(),
         toast_action: "/toasts".to_string(),
-        upload_limit_bytes: state.upload_limit_bytes,
-        upload_limit_mib: state.upload_limit_bytes.div_ceil(1_048_576),
+        upload_limit_bytes: file_limit_bytes,
+        upload_limit_mib: file_limit_bytes.div_ceil(1_048_576),
+        request_limit_bytes: state.upload_limit_bytes(),
         queue: admin_views::AdminUploadQueueView {
             entries: Vec::new(),
-            limit_mib: state.upload_limit_bytes.div_ceil(1_048_576),
+            limit_mib: file_limit_bytes.div_ceil(1_048_576),
         },
-    };
+    }
+}
+
+pub(super) async fn respond_with_upload_form(state: &AdminState, toasts: &[Toast]) -> Response {
+    let content = upload_form_view(state);
 
     let panel_html =
         match render_upload_form_panel_html(&content, "infra::http::admin_upload_store") {
@@ -48,7 +54,7 @@ pub(super) async fn respond_with_upload_form(state: &AdminState, toast: Toast) -
         };
 
     let mut stream = datastar_replace(ADMIN_CONTENT, wrap_content(panel_html));
-    if let Err(err) = push_toasts(&mut stream, &[toast]) {
+    if let Err(err) = push_toasts(&mut stream, toasts) {
         return err.into_response();
     }
     stream.into_response()
@@ -63,10 +69,11 @@ pub(super) async fn respond_with_upload_panel(
     error_source: &'static str,
     template_source: &'static str,
 ) -> Response {
-    let mut content = match build_upload_list_view(state, filter, cursor).await {
-        Ok(content) => content,
-        Err(err) => return admin_upload_error(error_source, err).into_response(),
-    };
+    let mut content =
+        match build_upload_list_view(state, filter, cursor, cursor_state.limit()).await {
+            Ok(content) => content,
+            Err(err) => return admin_upload_error(error_source, err).into_response(),
+        };
 
     apply_upload_pagination_links(&mut content, cursor_state);
 
@@ -109,7 +116,7 @@ pub(super) async fn render_full_upload_panel(state: &AdminState) -> Result<Strin
     let filter = UploadQueryFilter::default();
     let cursor_state = CursorState::default();
 
-    let mut content = match build_upload_list_view(state, &filter, None).await {
+    let mut content = match build_upload_list_view(state, &filter, None, None).await {
         Ok(content) => content,
         Err(err) => return Err(admin_upload_error("infra::http::admin_upload_store", err)),
     };
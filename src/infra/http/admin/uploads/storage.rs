@@ -1,12 +1,8 @@
 //! Upload storage processing logic.
 
-use axum::{
-    http::StatusCode,
-    response::{IntoResponse, Response},
-};
-use axum_extra::extract::multipart::MultipartError;
+use axum::response::{IntoResponse, Response};
+use bytes::Bytes;
 use datastar::prelude::ElementPatchMode;
-use futures::StreamExt;
 use std::convert::TryFrom;
 use time::OffsetDateTime;
 use tracing::{error, warn};
@@ -14,144 +10,117 @@ use uuid::Uuid;
 
 use crate::{
     application::{
-        admin::uploads::AdminUploadError,
+        admin::uploads::{AdminUploadError, AdminUploadService},
         metadata::metadata_registry,
         repos::{RepoError, UploadQueryFilter},
         stream::StreamBuilder,
     },
     domain::{entities::UploadRecord, uploads::UploadMetadata},
-    infra::uploads::UploadStorageError,
+    infra::uploads::UploadStorage,
 };
 
-use super::super::{AdminState, pagination::CursorState, selectors::UPLOADS_PANEL, shared::Toast};
+use super::super::{
+    AdminState,
+    pagination::CursorState,
+    selectors::UPLOADS_PANEL,
+    shared::{Toast, push_toasts},
+};
 
 use super::errors::{UploadPayloadError, admin_upload_error};
-use super::multipart::UploadPayload;
+use super::multipart::UploadPart;
 use super::panel::{apply_upload_pagination_links, build_upload_list_view};
-use super::queue::{push_queue_event, respond_with_queue_error_or_form};
+use super::queue::push_queue_event;
 use super::response::{
     render_full_upload_panel, respond_with_upload_form, respond_with_upload_page,
 };
 
 const SOURCE_BASE: &str = "infra::http::admin_uploads";
 
-pub(super) async fn handle_upload_payload(state: &AdminState, payload: UploadPayload) -> Response {
-    let UploadPayload {
-        filename,
-        content_type,
-        field,
-        queue_entry_id,
-        suppress_panel_patch,
-    } = payload;
+/// What became of one file of an upload request.
+pub(super) struct UploadOutcome {
+    pub(super) queue_entry_id: Option<String>,
+    /// Name the file was sent with.
+    pub(super) filename: String,
+    /// The stored upload, whose filename may carry a suffix, or why the
+    /// file was not stored.
+    pub(super) result: Result<UploadRecord, String>,
+}
 
-    let entry_id = queue_entry_id.as_deref();
+impl UploadOutcome {
+    fn renamed_to(&self) -> Option<&str> {
+        match &self.result {
+            Ok(record) if record.filename != self.filename => Some(&record.filename),
+            _ => None,
+        }
+    }
+}
 
-    let stream = field.map(|result| {
-        result.map_err(|err| {
-            if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
-                UploadStorageError::PayloadTooLarge {
-                    source: Box::new(err),
-                }
-            } else {
-                UploadStorageError::PayloadStream {
-                    source: Box::new(err),
-                }
+/// Store the accepted files of a batch one after another.
+///
+/// Names that collide with an existing upload or an earlier file of the
+/// batch are given a numeric suffix first. A file that fails to store does
+/// not stop the ones after it.
+pub(super) async fn store_upload_parts(
+    uploads: &AdminUploadService,
+    storage: &UploadStorage,
+    actor: &str,
+    parts: Vec<UploadPart>,
+) -> Result<Vec<UploadOutcome>, AdminUploadError> {
+    let accepted: Vec<String> = parts
+        .iter()
+        .filter(|part| part.contents.is_ok())
+        .map(|part| part.filename.clone())
+        .collect();
+    let mut unique_names = uploads.unique_filenames(&accepted).await?.into_iter();
+
+    let mut outcomes = Vec::with_capacity(parts.len());
+    for part in parts {
+        let UploadPart {
+            queue_entry_id,
+            filename,
+            content_type,
+            contents,
+        } = part;
+
+        let result = match contents {
+            Ok(contents) => {
+                let name = unique_names.next().unwrap_or_else(|| filename.clone());
+                store_part(uploads, storage, actor, name, content_type, contents).await
             }
-        })
-    });
+            Err(message) => Err(message),
+        };
 
-    let limit_bytes = state.upload_limit_bytes;
+        outcomes.push(UploadOutcome {
+            queue_entry_id,
+            filename,
+            result,
+        });
+    }
 
-    let stored = match state.upload_storage.store_stream(&filename, stream).await {
-        Ok(stored) => stored,
-        Err(UploadStorageError::EmptyPayload) => {
-            return respond_with_queue_error_or_form(
-                state,
-                entry_id,
-                "Uploaded file is empty".to_string(),
-                suppress_panel_patch,
-            )
-            .await;
-        }
-        Err(UploadStorageError::PayloadTooLarge { source }) => {
-            let limit_mib = limit_bytes.div_ceil(1_048_576);
-            error!(
-                target = SOURCE_BASE,
-                error = %source,
-                limit_bytes = limit_bytes,
-                limit_mib = limit_mib,
-                "upload request exceeded configured body limit"
-            );
-            return respond_with_queue_error_or_form(
-                state,
-                entry_id,
-                format!("File is too large (limit is {limit_mib} MiB)"),
-                suppress_panel_patch,
-            )
-            .await;
-        }
-        Err(UploadStorageError::SizeOverflow) => {
-            let limit_mib = limit_bytes.div_ceil(1_048_576);
-            error!(
-                target = SOURCE_BASE,
-                limit_bytes = limit_bytes,
-                limit_mib = limit_mib,
-                "upload stream size exceeded supported range"
-            );
-            return respond_with_queue_error_or_form(
-                state,
-                entry_id,
-                format!("File is too large (limit is {limit_mib} MiB)"),
-                suppress_panel_patch,
-            )
-            .await;
-        }
-        Err(UploadStorageError::PayloadStream { source }) => {
-            let message = if let Some(multipart_err) = source.downcast_ref::<MultipartError>() {
-                match multipart_err.status() {
-                    StatusCode::PAYLOAD_TOO_LARGE => {
-                        let limit_mib = limit_bytes.div_ceil(1_048_576);
-                        format!("File is too large (limit is {limit_mib} MiB)")
-                    }
-                    StatusCode::BAD_REQUEST => "Upload form data was invalid".to_string(),
-                    _ => "Could not store uploaded file, please retry later".to_string(),
-                }
-            } else {
-                "Could not store uploaded file, please retry later".to_string()
-            };
+    Ok(outcomes)
+}
 
-            error!(
-                target = SOURCE_BASE,
-                error = %source,
-                "failed to persist upload payload"
-            );
-            return respond_with_queue_error_or_form(
-                state,
-                entry_id,
-                message,
-                suppress_panel_patch,
-            )
-            .await;
-        }
+async fn store_part(
+    uploads: &AdminUploadService,
+    storage: &UploadStorage,
+    actor: &str,
+    filename: String,
+    content_type: String,
+    contents: Bytes,
+) -> Result<UploadRecord, String> {
+    let stored = match storage.store(&filename, contents).await {
+        Ok(stored) => stored,
         Err(err) => {
             error!(
                 target = SOURCE_BASE,
                 error = %err,
                 "failed to persist upload payload"
             );
-            return respond_with_queue_error_or_form(
-                state,
-                entry_id,
-                "Could not store uploaded file, please retry later".to_string(),
-                suppress_panel_patch,
-            )
-            .await;
+            return Err("Could not store uploaded file, please retry later".to_string());
         }
     };
 
-    let stored_size = u64::try_from(stored.size_bytes).unwrap_or(0);
-
-    let metadata = match state.upload_storage.absolute_path(&stored.stored_path) {
+    let metadata = match storage.absolute_path(&stored.stored_path) {
         Ok(path) => match metadata_registry().extract_from_file(&content_type, path.as_path()) {
             Ok(metadata) => metadata,
             Err(err) => {
@@ -175,54 +144,19 @@ pub(super) async fn handle_upload_payload(state: &AdminState, payload: UploadPay
 
     let record = UploadRecord {
         id: Uuid::new_v4(),
-        filename: filename.clone(),
+        filename,
         content_type,
         size_bytes: stored.size_bytes,
-        checksum: stored.checksum.clone(),
-        stored_path: stored.stored_path.clone(),
+        checksum: stored.checksum,
+        stored_path: stored.stored_path,
         metadata,
         created_at: OffsetDateTime::now_utc(),
+        last_verified_at: None,
+        checksum_mismatch: false,
     };
 
-    let actor = "admin";
-    match state.uploads.register_upload(actor, record.clone()).await {
-        Ok(_) => {
-            if let Some(id) = entry_id {
-                let mut stream = StreamBuilder::new();
-                push_queue_event(
-                    &mut stream,
-                    Some(id),
-                    "success",
-                    None,
-                    Some(stored_size),
-                    suppress_panel_patch,
-                );
-
-                if !suppress_panel_patch && let Ok(html) = render_full_upload_panel(state).await {
-                    stream.push_patch(html, UPLOADS_PANEL, ElementPatchMode::Replace);
-                }
-
-                return stream.into_response();
-            }
-
-            let filter = UploadQueryFilter::default();
-            let cursor_state = CursorState::default();
-            match build_upload_list_view(state, &filter, None).await {
-                Ok(mut content) => {
-                    apply_upload_pagination_links(&mut content, &cursor_state);
-                    let toasts = [Toast::success("File uploaded successfully")];
-                    respond_with_upload_page(
-                        content,
-                        &toasts,
-                        "infra::http::admin_upload_store",
-                        "infra::http::admin_upload_store",
-                    )
-                }
-                Err(err) => {
-                    admin_upload_error("infra::http::admin_upload_store", err).into_response()
-                }
-            }
-        }
+    match uploads.register_upload(actor, record.clone()).await {
+        Ok(()) => Ok(record),
         Err(AdminUploadError::Repo(repo_err)) => {
             let message = match &repo_err {
                 RepoError::Duplicate { constraint } => {
@@ -244,7 +178,7 @@ pub(super) async fn handle_upload_payload(state: &AdminState, payload: UploadPay
                 }
             };
 
-            if let Err(remove_err) = state.upload_storage.delete(&record.stored_path).await {
+            if let Err(remove_err) = storage.delete(&record.stored_path).await {
                 warn!(
                     target = SOURCE_BASE,
                     error = %remove_err,
@@ -252,27 +186,105 @@ pub(super) async fn handle_upload_payload(state: &AdminState, payload: UploadPay
                 );
             }
 
-            if let Some(id) = entry_id {
-                let mut stream = StreamBuilder::new();
-                push_queue_event(
-                    &mut stream,
-                    Some(id),
-                    "error",
-                    Some(&message),
-                    Some(stored_size),
-                    suppress_panel_patch,
-                );
-                return stream.into_response();
-            }
+            Err(message)
+        }
+        Err(AdminUploadError::NotFound | AdminUploadError::Storage(_)) => {
+            unreachable!("register_upload only fails with repository errors")
+        }
+    }
+}
+
+/// Answer an upload request with the result of each of its files.
+///
+/// Files sent from the upload queue update their queue rows; each failed
+/// file also gets its own error toast, so one bad file does not hide the
+/// others that were stored.
+pub(super) async fn respond_with_upload_outcomes(
+    state: &AdminState,
+    outcomes: &[UploadOutcome],
+    suppress_panel_patch: bool,
+) -> Response {
+    let stored = outcomes
+        .iter()
+        .filter(|outcome| outcome.result.is_ok())
+        .count();
+    let failures: Vec<Toast> = outcomes
+        .iter()
+        .filter_map(|outcome| {
+            outcome
+                .result
+                .as_ref()
+                .err()
+                .map(|message| Toast::error(format!("{}: {message}", outcome.filename)))
+        })
+        .collect();
 
-            respond_with_upload_form(state, Toast::error(message)).await
+    if outcomes
+        .iter()
+        .any(|outcome| outcome.queue_entry_id.is_some())
+    {
+        let mut stream = StreamBuilder::new();
+        for outcome in outcomes {
+            let (status, message, size_bytes) = match &outcome.result {
+                Ok(record) => (
+                    "success",
+                    outcome.renamed_to().map(|name| format!("Saved as {name}")),
+                    Some(u64::try_from(record.size_bytes).unwrap_or(0)),
+                ),
+                Err(message) => ("error", Some(message.clone()), None),
+            };
+            push_queue_event(
+                &mut stream,
+                outcome.queue_entry_id.as_deref(),
+                status,
+                message.as_deref(),
+                size_bytes,
+                suppress_panel_patch,
+            );
         }
-        Err(AdminUploadError::NotFound) => {
-            unreachable!("register_upload cannot yield NotFound")
+
+        if stored > 0
+            && !suppress_panel_patch
+            && let Ok(html) = render_full_upload_panel(state).await
+        {
+            stream.push_patch(html, UPLOADS_PANEL, ElementPatchMode::Replace);
+        }
+
+        if !failures.is_empty()
+            && let Err(err) = push_toasts(&mut stream, &failures)
+        {
+            return err.into_response();
+        }
+        return stream.into_response();
+    }
+
+    if stored == 0 {
+        return respond_with_upload_form(state, &failures).await;
+    }
+
+    let mut toasts = vec![if stored == 1 {
+        Toast::success("File uploaded successfully")
+    } else {
+        Toast::success(format!("{stored} files uploaded successfully"))
+    }];
+    toasts.extend(failures);
+
+    let filter = UploadQueryFilter::default();
+    let cursor_state = CursorState::default();
+    match build_upload_list_view(state, &filter, None, None).await {
+        Ok(mut content) => {
+            apply_upload_pagination_links(&mut content, &cursor_state);
+            respond_with_upload_page(
+                content,
+                &toasts,
+                "infra::http::admin_upload_store",
+                "infra::http::admin_upload_store",
+            )
         }
+        Err(err) => admin_upload_error("infra::http::admin_upload_store", err).into_response(),
     }
 }
 
 pub(super) async fn upload_payload_error(state: &AdminState, err: UploadPayloadError) -> Response {
-    respond_with_upload_form(state, err.into_toast(state.upload_limit_bytes)).await
+    respond_with_upload_form(state, &[err.into_toast(state.upload_limit_bytes())]).await
 }
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::FromRequest;
+use axum::http::{Request, header};
+use axum_extra::extract::Multipart;
+use sqlx::PgPool;
+
+use crate::application::admin::audit::AdminAuditService;
+use crate::application::admin::uploads::AdminUploadService;
+use crate::application::repos::UploadQueryFilter;
+use crate::domain::uploads::ContentTypePolicy;
+use crate::infra::db::PostgresRepositories;
+use crate::infra::uploads::UploadStorage;
+
+use super::multipart::{UploadLimits, read_upload_batch};
+use super::storage::store_upload_parts;
+
+const BOUNDARY: &str = "soffio-batch-boundary";
+
+/// A multipart upload of `(queue id, filename, content type, contents)`
+/// files, each preceded by its queue id the way the upload queue sends them.
+async fn multipart(files: &[(&str, &str, &str, &[u8])]) -> Multipart {
+    let mut body = Vec::new();
+    for (queue_id, filename, content_type, contents) in files {
+        body.extend_from_slice(
+            format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"queue_entry_id\"\r\n\r\n{queue_id}\r\n"
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(
+            format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\nContent-Type: {content_type}\r\n\r\n"
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(contents);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{BOUNDARY}--\r\n").as_bytes());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/uploads")
+        .header(
+            header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(body))
+        .expect("build request");
+    Multipart::from_request(request, &())
+        .await
+        .expect("multipart request")
+}
+
+fn upload_service(pool: PgPool) -> AdminUploadService {
+    let repos = Arc::new(PostgresRepositories::new(pool));
+    let keys = repos.cursor_keys().clone();
+    AdminUploadService::new(repos.clone(), AdminAuditService::new(repos)).with_cursor_keys(keys)
+}
+
+fn limits(file_bytes: u64) -> UploadLimits {
+    UploadLimits {
+        file_bytes,
+        content_type_policy: ContentTypePolicy::Reject,
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn oversized_file_fails_alone_and_the_rest_of_the_batch_is_stored(pool: PgPool) {
+    let uploads = upload_service(pool);
+    let workdir = tempfile::tempdir().expect("tempdir");
+    let storage = UploadStorage::new(workdir.path().to_path_buf()).expect("storage");
+
+    let oversized = vec![b'x'; 2048];
+    let mut form = multipart(&[
+        ("q1", "notes.txt", "text/plain", b"first file".as_slice()),
+        (
+            "q2",
+            "huge.bin",
+            "application/octet-stream",
+            oversized.as_slice(),
+        ),
+        ("q3", "notes.txt", "text/plain", b"second file".as_slice()),
+    ])
+    .await;
+
+    let batch = read_upload_batch(&mut form, &limits(1024))
+        .await
+        .expect("read batch");
+    let outcomes = store_upload_parts(&uploads, &storage, "tester", batch.parts)
+        .await
+        .expect("store batch");
+
+    assert_eq!(outcomes.len(), 3);
+    let ids: Vec<_> = outcomes
+        .iter()
+        .map(|outcome| outcome.queue_entry_id.as_deref())
+        .collect();
+    assert_eq!(ids, [Some("q1"), Some("q2"), Some("q3")]);
+
+    let first = outcomes[0].result.as_ref().expect("first file stored");
+    assert_eq!(first.filename, "notes.txt");
+    let message = outcomes[1].result.as_ref().expect_err("oversized file");
+    assert!(message.contains("too large"), "{message}");
+    let third = outcomes[2].result.as_ref().expect("third file stored");
+    assert_eq!(third.filename, "notes-1.txt");
+
+    let stored = uploads
+        .count(&UploadQueryFilter::default())
+        .await
+        .expect("count uploads");
+    assert_eq!(stored, 2);
+    for record in [first, third] {
+        let bytes = storage
+            .read(&record.stored_path)
+            .await
+            .expect("stored file");
+        assert_eq!(bytes.len() as i64, record.size_bytes);
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn rejected_files_are_checked_before_anything_is_stored(pool: PgPool) {
+    let uploads = upload_service(pool);
+    let workdir = tempfile::tempdir().expect("tempdir");
+    let storage = UploadStorage::new(workdir.path().to_path_buf()).expect("storage");
+
+    let mut form = multipart(&[("q1", "existing.txt", "text/plain", b"original".as_slice())]).await;
+    let batch = read_upload_batch(&mut form, &limits(1024))
+        .await
+        .expect("read batch");
+    store_upload_parts(&uploads, &storage, "tester", batch.parts)
+        .await
+        .expect("store first batch");
+
+    let mut form = multipart(&[
+        (
+            "q1",
+            "photo.png",
+            "image/png",
+            b"%PDF-1.7 not a png".as_slice(),
+        ),
+        ("q2", "empty.txt", "text/plain", b"".as_slice()),
+        (
+            "q3",
+            "existing.txt",
+            "text/plain",
+            b"replacement".as_slice(),
+        ),
+    ])
+    .await;
+    let batch = read_upload_batch(&mut form, &limits(1024))
+        .await
+        .expect("read batch");
+    let outcomes = store_upload_parts(&uploads, &storage, "tester", batch.parts)
+        .await
+        .expect("store second batch");
+
+    let mismatch = outcomes[0].result.as_ref().expect_err("mismatched type");
+    assert!(mismatch.contains("application/pdf"), "{mismatch}");
+    let empty = outcomes[1].result.as_ref().expect_err("empty file");
+    assert!(empty.contains("empty"), "{empty}");
+    let renamed = outcomes[2].result.as_ref().expect("renamed file stored");
+    assert_eq!(renamed.filename, "existing-1.txt");
+
+    let stored = uploads
+        .count(&UploadQueryFilter::default())
+        .await
+        .expect("count uploads");
+    assert_eq!(stored, 2);
+}
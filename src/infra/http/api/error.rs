@@ -1,8 +1,31 @@
 use crate::application::error::ErrorReport;
+use crate::application::request_id;
 use axum::Json;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
+use std::sync::OnceLock;
+
+/// Page documenting every error code, used unless
+/// `server.api_error_docs_url` points somewhere else.
+pub const DEFAULT_ERROR_DOCS_URL: &str =
+    "https://github.com/xfyyzy/soffio/blob/main/docs/api/errors.md#";
+
+static ERROR_DOCS_URL: OnceLock<String> = OnceLock::new();
+
+/// Set the base URL error codes are appended to for `docs_url`. Only the
+/// first call takes effect; `serve` makes it at startup.
+pub fn set_error_docs_url(base: &str) {
+    let _ = ERROR_DOCS_URL.set(base.to_string());
+}
+
+/// Documentation link for `code`: the configured base followed by the code.
+pub fn docs_url(code: &str) -> String {
+    let base = ERROR_DOCS_URL
+        .get()
+        .map_or(DEFAULT_ERROR_DOCS_URL, String::as_str);
+    format!("{base}{code}")
+}
 
 #[derive(Debug, Serialize)]
 pub struct ApiErrorBody {
@@ -20,6 +43,7 @@ pub mod codes {
     pub const INVALID_INPUT: &str = "invalid_input";
     pub const INTEGRITY: &str = "integrity_error";
     pub const DB_TIMEOUT: &str = "db_timeout";
+    pub const DB_CONTENTION: &str = "db_contention";
     pub const REPO: &str = "repo_error";
     pub const RENDER: &str = "render_error";
     pub const NAVIGATION: &str = "navigation_error";
@@ -27,6 +51,50 @@ pub mod codes {
     pub const SETTINGS: &str = "settings_error";
     pub const JOBS: &str = "jobs_error";
     pub const TAG_IN_USE: &str = "tag_in_use";
+    pub const PRECONDITION_REQUIRED: &str = "precondition_required";
+    pub const PRECONDITION_FAILED: &str = "precondition_failed";
+    pub const PAYLOAD_TOO_LARGE: &str = "payload_too_large";
+    pub const IDEMPOTENCY_CONFLICT: &str = "idempotency_conflict";
+    pub const KEY_EXPIRED: &str = "expired";
+    pub const KEY_REVOKED: &str = "revoked";
+
+    /// Every code the API answers with.
+    pub const ALL: [&str; 24] = [
+        BAD_REQUEST,
+        UNAUTHORIZED,
+        FORBIDDEN,
+        NOT_FOUND,
+        RATE_LIMITED,
+        DUPLICATE,
+        INVALID_CURSOR,
+        INVALID_INPUT,
+        INTEGRITY,
+        DB_TIMEOUT,
+        DB_CONTENTION,
+        REPO,
+        RENDER,
+        NAVIGATION,
+        UPLOAD,
+        SETTINGS,
+        JOBS,
+        TAG_IN_USE,
+        PRECONDITION_REQUIRED,
+        PRECONDITION_FAILED,
+        PAYLOAD_TOO_LARGE,
+        IDEMPOTENCY_CONFLICT,
+        KEY_EXPIRED,
+        KEY_REVOKED,
+    ];
+
+    /// Whether the same request may succeed later: rate limiting, database
+    /// timeouts and contention, and server-side failures. Rejections of the
+    /// request itself (validation, auth, conflicts) are not.
+    pub fn is_retryable(code: &str) -> bool {
+        matches!(
+            code,
+            RATE_LIMITED | DB_TIMEOUT | DB_CONTENTION | REPO | JOBS
+        )
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -35,6 +103,35 @@ pub struct ApiErrorMessage {
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hint: Option<String>,
+    /// Per-field problems, for requests rejected by validation.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<ApiFieldError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Where `code` is documented.
+    pub docs_url: String,
+    /// Whether repeating the same request later may succeed.
+    pub retryable: bool,
+}
+
+impl ApiErrorMessage {
+    fn new(status: StatusCode, code: &str, message: String, hint: Option<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message,
+            hint,
+            fields: Vec::new(),
+            request_id: request_id::current(),
+            docs_url: docs_url(code),
+            retryable: codes::is_retryable(code) || status.is_server_error(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiFieldError {
+    pub field: String,
+    pub message: String,
 }
 
 #[derive(Debug)]
@@ -43,6 +140,7 @@ pub struct ApiError {
     code: &'static str,
     message: &'static str,
     hint: Option<String>,
+    fields: Vec<ApiFieldError>,
 }
 
 impl ApiError {
@@ -57,9 +155,15 @@ impl ApiError {
             code,
             message,
             hint,
+            fields: Vec::new(),
         }
     }
 
+    pub fn with_fields(mut self, fields: Vec<ApiFieldError>) -> Self {
+        self.fields = fields;
+        self
+    }
+
     pub fn bad_request(message: &'static str, hint: Option<String>) -> Self {
         Self::new(StatusCode::BAD_REQUEST, codes::BAD_REQUEST, message, hint)
     }
@@ -86,13 +190,32 @@ impl ApiError {
         Self::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, message, None)
     }
 
+    pub fn precondition_required() -> Self {
+        Self::new(
+            StatusCode::PRECONDITION_REQUIRED,
+            codes::PRECONDITION_REQUIRED,
+            "If-Match header required",
+            Some("Send the ETag from a previous read, or * to overwrite".to_string()),
+        )
+    }
+
+    pub fn precondition_failed() -> Self {
+        Self::new(
+            StatusCode::PRECONDITION_FAILED,
+            codes::PRECONDITION_FAILED,
+            "Resource was modified since it was read",
+            Some("Fetch the latest version and retry".to_string()),
+        )
+    }
+
     pub fn rate_limited(retry_after: u64) -> Response {
         let body = ApiErrorBody {
-            error: ApiErrorMessage {
-                code: codes::RATE_LIMITED.to_string(),
-                message: "Rate limit exceeded".to_string(),
-                hint: Some(format!("Retry after {retry_after} seconds")),
-            },
+            error: ApiErrorMessage::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                codes::RATE_LIMITED,
+                "Rate limit exceeded".to_string(),
+                Some(format!("Retry after {retry_after} seconds")),
+            ),
         };
         let mut response = (StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response();
         if let Ok(value) = axum::http::HeaderValue::from_str(&retry_after.to_string()) {
@@ -115,9 +238,8 @@ impl IntoResponse for ApiError {
         let hint = self.hint.clone();
         let body = ApiErrorBody {
             error: ApiErrorMessage {
-                code: self.code.to_string(),
-                message: self.message.to_string(),
-                hint: self.hint,
+                fields: self.fields,
+                ..ApiErrorMessage::new(self.status, self.code, self.message.to_string(), self.hint)
             },
         };
         let mut response = (self.status, Json(body)).into_response();
@@ -131,3 +253,70 @@ impl IntoResponse for ApiError {
         response
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_code_has_a_docs_url_and_a_fixed_retryability() {
+        let retryable = [
+            codes::RATE_LIMITED,
+            codes::DB_TIMEOUT,
+            codes::DB_CONTENTION,
+            codes::REPO,
+            codes::JOBS,
+        ];
+        let mut seen = std::collections::HashSet::new();
+        for code in codes::ALL {
+            assert!(seen.insert(code), "{code} listed twice");
+            assert_eq!(docs_url(code), format!("{DEFAULT_ERROR_DOCS_URL}{code}"));
+            assert_eq!(docs_url(code), docs_url(code));
+            assert_eq!(
+                codes::is_retryable(code),
+                retryable.contains(&code),
+                "{code}"
+            );
+        }
+        for code in retryable {
+            assert!(codes::ALL.contains(&code));
+        }
+    }
+
+    #[test]
+    fn messages_mark_server_failures_retryable() {
+        let validation = ApiErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            codes::INVALID_INPUT,
+            "bad".into(),
+            None,
+        );
+        assert!(!validation.retryable);
+        assert_eq!(validation.docs_url, docs_url(codes::INVALID_INPUT));
+
+        let render = ApiErrorMessage::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            codes::RENDER,
+            "render failed".into(),
+            None,
+        );
+        assert!(render.retryable);
+
+        let json = serde_json::to_value(ApiErrorBody {
+            error: ApiErrorMessage::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                codes::DB_TIMEOUT,
+                "timeout".into(),
+                None,
+            ),
+        })
+        .expect("serialize");
+        assert_eq!(json["error"]["retryable"], true);
+        assert_eq!(json["error"]["code"], "db_timeout");
+        assert!(
+            json["error"]["docs_url"]
+                .as_str()
+                .is_some_and(|url| url.ends_with("#db_timeout"))
+        );
+    }
+}
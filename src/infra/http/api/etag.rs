@@ -0,0 +1,119 @@
+//! ETags and `If-Match` preconditions for optimistic concurrency.
+//!
+//! A record's ETag is its `updated_at` in unix microseconds, the precision
+//! Postgres stores, so a tag read back from any response matches the row
+//! exactly until the next write.
+
+use axum::http::{
+    HeaderMap, HeaderValue,
+    header::{ETAG, IF_MATCH},
+};
+use axum::response::{IntoResponse, Response};
+use time::OffsetDateTime;
+
+use super::error::ApiError;
+
+/// Strong ETag for a record last written at `updated_at`.
+pub fn etag_for(updated_at: OffsetDateTime) -> String {
+    format!("\"{}\"", version_token(updated_at))
+}
+
+/// The ETag without its quotes, for forms that carry a record's version.
+pub fn version_token(updated_at: OffsetDateTime) -> String {
+    (updated_at.unix_timestamp_nanos() / 1_000).to_string()
+}
+
+/// The `updated_at` named by a [`version_token`], if the token is one.
+pub fn parse_version_token(token: &str) -> Option<OffsetDateTime> {
+    let micros = token.parse::<i128>().ok()?;
+    OffsetDateTime::from_unix_timestamp_nanos(micros.checked_mul(1_000)?).ok()
+}
+
+/// Attach the ETag for `updated_at` to `response`.
+pub fn with_etag(response: impl IntoResponse, updated_at: OffsetDateTime) -> Response {
+    let mut response = response.into_response();
+    if let Ok(value) = HeaderValue::from_str(&etag_for(updated_at)) {
+        response.headers_mut().insert(ETAG, value);
+    }
+    response
+}
+
+/// Read the mandatory `If-Match` precondition of an update.
+///
+/// Returns the `updated_at` the client expects, or `None` for `If-Match: *`,
+/// which overwrites unconditionally. A missing header is rejected with 428;
+/// a tag that can never match (weak or malformed) with 412.
+pub fn require_if_match(headers: &HeaderMap) -> Result<Option<OffsetDateTime>, ApiError> {
+    let value = headers
+        .get(IF_MATCH)
+        .ok_or_else(ApiError::precondition_required)?
+        .to_str()
+        .map_err(|_| ApiError::precondition_failed())?
+        .trim();
+
+    if value == "*" {
+        return Ok(None);
+    }
+    if value.contains(',') {
+        return Err(ApiError::bad_request(
+            "If-Match accepts a single ETag",
+            Some(value.to_string()),
+        ));
+    }
+
+    value
+        .strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .and_then(parse_version_token)
+        .map(Some)
+        .ok_or_else(ApiError::precondition_failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(if_match: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_MATCH, HeaderValue::from_str(if_match).expect("header"));
+        headers
+    }
+
+    #[test]
+    fn etag_round_trips_through_if_match() {
+        let updated_at = OffsetDateTime::from_unix_timestamp_nanos(1_760_616_000_123_456_000)
+            .expect("timestamp");
+        let tag = etag_for(updated_at);
+
+        assert_eq!(tag, "\"1760616000123456\"");
+        assert_eq!(
+            require_if_match(&headers(&tag)).expect("valid tag"),
+            Some(updated_at)
+        );
+    }
+
+    #[test]
+    fn wildcard_matches_any_version() {
+        assert_eq!(require_if_match(&headers("*")).expect("wildcard"), None);
+    }
+
+    #[test]
+    fn missing_or_weak_tags_are_rejected() {
+        assert!(require_if_match(&HeaderMap::new()).is_err());
+        assert!(require_if_match(&headers("W/\"1760616000123456\"")).is_err());
+        assert!(require_if_match(&headers("\"yesterday\"")).is_err());
+    }
+
+    #[test]
+    fn oversized_tokens_fail_the_precondition() {
+        let token = i128::MAX.to_string();
+        assert_eq!(parse_version_token(&token), None);
+
+        let error = require_if_match(&headers(&format!("\"{token}\"")))
+            .expect_err("oversized tag cannot match");
+        assert_eq!(
+            error.into_response().status(),
+            axum::http::StatusCode::PRECONDITION_FAILED
+        );
+    }
+}
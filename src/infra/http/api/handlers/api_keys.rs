@@ -4,10 +4,13 @@ use axum::Json;
 use axum::extract::{Extension, State};
 
 use crate::application::api_keys::ApiPrincipal;
+use crate::domain::api_keys::ApiScope;
 
-use super::api_key_to_api;
+use super::{api_key_to_api, require_scope};
 use crate::infra::http::api::error::ApiError;
-use crate::infra::http::api::models::ApiKeyInfoResponse;
+use crate::infra::http::api::models::{
+    ApiKeyInfoResponse, ApiKeyRotateAllResponse, FailedApiKeyRotation, RotatedApiKey,
+};
 use crate::infra::http::api::state::ApiState;
 
 pub async fn get_api_key_info(
@@ -33,3 +36,43 @@ pub async fn get_api_key_info(
 
     Ok(Json(body))
 }
+
+/// Rotate every active key, the caller's own included.
+///
+/// The response hands out a token for every key, so only a key holding every
+/// scope may ask for it: it gains nothing it could not already do.
+pub async fn rotate_all_api_keys(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+) -> Result<Json<ApiKeyRotateAllResponse>, ApiError> {
+    for scope in ApiScope::all() {
+        require_scope(&principal, *scope)?;
+    }
+
+    let outcome = state.api_keys.rotate_all().await.map_err(api_key_to_api)?;
+
+    let body = ApiKeyRotateAllResponse {
+        rotated: outcome
+            .rotated
+            .into_iter()
+            .map(|issued| RotatedApiKey {
+                id: issued.record.id,
+                name: issued.record.name,
+                prefix: issued.record.prefix,
+                token: issued.token,
+            })
+            .collect(),
+        failed: outcome
+            .failed
+            .into_iter()
+            .map(|(record, err)| FailedApiKeyRotation {
+                id: record.id,
+                name: record.name,
+                prefix: record.prefix,
+                error: err.to_string(),
+            })
+            .collect(),
+    };
+
+    Ok(Json(body))
+}
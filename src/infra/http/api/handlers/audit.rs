@@ -3,13 +3,15 @@
 use axum::Json;
 use axum::extract::{Extension, Query, State};
 use axum::response::IntoResponse;
+use time::OffsetDateTime;
 
 use crate::application::api_keys::ApiPrincipal;
 use crate::application::pagination::{AuditCursor, PageRequest};
 use crate::application::repos::AuditQueryFilter;
 use crate::domain::api_keys::ApiScope;
+use crate::util::timezone;
 
-use super::{AuditListQuery, repo_to_api};
+use super::{AuditListQuery, cursor_to_api, repo_to_api, require_scope};
 use crate::infra::http::api::error::ApiError;
 use crate::infra::http::api::state::ApiState;
 
@@ -18,26 +20,29 @@ pub async fn list_audit_logs(
     Extension(principal): Extension<ApiPrincipal>,
     Query(query): Query<AuditListQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::AuditRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::AuditRead)?;
 
     let limit = query.limit.unwrap_or(50).clamp(1, 200);
-    let cursor = match query.cursor.as_deref().map(AuditCursor::decode).transpose() {
-        Ok(cursor) => cursor,
-        Err(err) => {
-            return Err(ApiError::bad_request(
-                "invalid cursor",
-                Some(err.to_string()),
-            ));
-        }
-    };
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(|cursor| AuditCursor::decode(state.db.cursor_keys(), cursor))
+        .transpose()
+        .map_err(cursor_to_api)?;
+
+    let from = parse_bound("from", query.from.as_deref())?;
+    let to = parse_bound("to", query.to.as_deref())?;
 
     let filter = AuditQueryFilter {
         actor: query.actor,
+        actor_type: query.actor_type,
         action: query.action,
         entity_type: query.entity_type,
         search: query.search,
+        field: query.field,
+        from,
+        to,
+        request_id: query.request_id,
     };
 
     let page = state
@@ -48,3 +53,16 @@ pub async fn list_audit_logs(
 
     Ok(Json(page))
 }
+
+fn parse_bound(name: &str, value: Option<&str>) -> Result<Option<OffsetDateTime>, ApiError> {
+    value
+        .map(|value| {
+            timezone::parse_rfc3339(value).ok_or_else(|| {
+                ApiError::bad_request(
+                    "invalid timestamp",
+                    Some(format!("`{name}` must be an RFC 3339 timestamp")),
+                )
+            })
+        })
+        .transpose()
+}
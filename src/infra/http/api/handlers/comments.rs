@@ -0,0 +1,65 @@
+//! Comments handlers
+
+use axum::Json;
+use axum::extract::{Extension, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+
+use crate::application::admin::comments::IngestCommentCommand;
+use crate::application::api_keys::ApiPrincipal;
+use crate::domain::api_keys::ApiScope;
+use crate::infra::http::client_ip::ClientIp;
+
+use super::{comment_to_api, require_scope};
+use crate::infra::http::api::error::ApiError;
+use crate::infra::http::api::models::*;
+use crate::infra::http::api::state::ApiState;
+
+const COMMENT_INGEST_ROUTE: &str = "comments.ingest";
+
+pub async fn create_comment(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    client_ip: Option<Extension<ClientIp>>,
+    Json(payload): Json<CommentCreateRequest>,
+) -> Result<Response, ApiError> {
+    require_scope(&principal, ApiScope::CommentWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    // Webhook ingest reaches us from the commenting service, so the caller
+    // address is shared by every commenter. Limit per key and commenter
+    // address, falling back to the caller address, then to the key alone.
+    // The author name is never part of the key: it is free text a caller can
+    // change on every request.
+    let source_ip = payload
+        .author_ip
+        .or(client_ip.map(|Extension(ClientIp(ip))| ip))
+        .map(|ip| ip.to_string());
+    let limiter_key = match &source_ip {
+        Some(ip) => format!("{}:{ip}", principal.key_id),
+        None => principal.key_id.to_string(),
+    };
+    let (allowed, _) = state
+        .comment_rate_limiter
+        .allow(&limiter_key, COMMENT_INGEST_ROUTE);
+    if !allowed {
+        return Ok(ApiError::rate_limited(
+            state.comment_rate_limiter.retry_after_secs(),
+        ));
+    }
+
+    let command = IngestCommentCommand {
+        post_id: payload.post_id,
+        author: payload.author,
+        body: payload.body,
+        source_ip,
+    };
+
+    let comment = state
+        .comments
+        .ingest(&actor, command)
+        .await
+        .map_err(comment_to_api)?;
+
+    Ok((StatusCode::CREATED, Json(comment)).into_response())
+}
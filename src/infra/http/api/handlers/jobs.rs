@@ -1,16 +1,17 @@
 //! Jobs handlers
 
 use axum::Json;
-use axum::extract::{Extension, Query, State};
+use axum::extract::{Extension, Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 
+use crate::application::admin::jobs::AdminJobError;
 use crate::application::api_keys::ApiPrincipal;
 use crate::application::pagination::{JobCursor, PageRequest};
 use crate::application::repos::JobQueryFilter;
 use crate::domain::api_keys::ApiScope;
 
-use super::JobsListQuery;
+use super::{JobsListQuery, cursor_to_api, require_scope};
 use crate::infra::http::api::error::{ApiError, codes};
 use crate::infra::http::api::state::ApiState;
 
@@ -19,20 +20,15 @@ pub async fn list_jobs(
     Extension(principal): Extension<ApiPrincipal>,
     Query(query): Query<JobsListQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::JobRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::JobRead)?;
 
     let limit = query.limit.unwrap_or(50).clamp(1, 200);
-    let cursor = match query.cursor.as_deref().map(JobCursor::decode).transpose() {
-        Ok(cursor) => cursor,
-        Err(err) => {
-            return Err(ApiError::bad_request(
-                "invalid cursor",
-                Some(err.to_string()),
-            ));
-        }
-    };
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(|cursor| JobCursor::decode(state.db.cursor_keys(), cursor))
+        .transpose()
+        .map_err(cursor_to_api)?;
 
     let filter = JobQueryFilter {
         state: query.state,
@@ -55,3 +51,27 @@ pub async fn list_jobs(
 
     Ok(Json(page))
 }
+
+pub async fn get_job(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::JobRead)?;
+
+    let detail = state
+        .jobs
+        .load_job_detail(&id)
+        .await
+        .map_err(|err| match err {
+            AdminJobError::NotFound => ApiError::not_found("job not found"),
+            AdminJobError::Repo(err) => ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                codes::JOBS,
+                "Failed to load job",
+                Some(err.to_string()),
+            ),
+        })?;
+
+    Ok(Json(detail))
+}
@@ -5,10 +5,13 @@
 
 mod api_keys;
 mod audit;
+mod comments;
 mod jobs;
 mod navigation;
 mod pages;
 mod posts;
+mod render;
+mod schedule;
 mod settings;
 mod snapshots;
 mod tags;
@@ -17,10 +20,13 @@ mod uploads;
 // Re-export all handlers for external use
 pub use api_keys::*;
 pub use audit::*;
+pub use comments::*;
 pub use jobs::*;
 pub use navigation::*;
 pub use pages::*;
 pub use posts::*;
+pub use render::*;
+pub use schedule::*;
 pub use settings::*;
 pub use snapshots::*;
 pub use tags::*;
@@ -30,7 +36,7 @@ pub use uploads::*;
 
 use serde::Deserialize;
 
-use crate::domain::types::{JobState, JobType, PageStatus, PostStatus};
+use crate::domain::types::{ActorType, JobState, JobType, PageStatus, PostStatus};
 
 #[derive(Debug, Deserialize)]
 pub struct PostListQuery {
@@ -42,6 +48,14 @@ pub struct PostListQuery {
     pub limit: Option<u32>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PostStatusQuery {
+    /// Hold the response until the post's render and publish jobs settle,
+    /// for at most the publish wait timeout.
+    #[serde(default)]
+    pub wait_render: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PageListQuery {
     pub status: Option<PageStatus>,
@@ -73,6 +87,8 @@ pub struct UploadListQuery {
     pub search: Option<String>,
     pub content_type: Option<String>,
     pub month: Option<String>,
+    /// Hex SHA-256 of the contents, to find an already uploaded file.
+    pub checksum: Option<String>,
     pub cursor: Option<String>,
     pub limit: Option<u32>,
 }
@@ -89,29 +105,82 @@ pub struct JobsListQuery {
 #[derive(Debug, Deserialize)]
 pub struct AuditListQuery {
     pub actor: Option<String>,
+    /// Only entries whose actor has this origin.
+    pub actor_type: Option<ActorType>,
     pub action: Option<String>,
     pub entity_type: Option<String>,
     pub search: Option<String>,
+    /// Only entries whose recorded changes touch this field.
+    pub field: Option<String>,
+    /// RFC 3339 timestamp; entries created at or after it.
+    pub from: Option<String>,
+    /// RFC 3339 timestamp; entries created before it.
+    pub to: Option<String>,
+    /// Only entries recorded while serving this request.
+    pub request_id: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScheduleQuery {
+    /// First day of the range, `YYYY-MM-DD` in the site timezone.
+    pub from: String,
+    /// Last day of the range, inclusive.
+    pub to: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrashListQuery {
     pub cursor: Option<String>,
     pub limit: Option<u32>,
 }
 
+// ----- Access policy -----
+
+use crate::application::api_keys::ApiPrincipal;
+use crate::domain::api_keys::ApiScope;
+
+/// Gate a handler on `scope`.
+///
+/// Every handler calls this before it touches storage, so a key without the
+/// scope gets 403 whether or not the resource exists. Past this point a
+/// missing resource answers 404, and so must one the key is not allowed to
+/// see: filter it out as if it did not exist rather than answering 403.
+pub(crate) fn require_scope(principal: &ApiPrincipal, scope: ApiScope) -> Result<(), ApiError> {
+    principal.requires(scope).map_err(|_| ApiError::forbidden())
+}
+
 // ----- Shared error conversions -----
 
 use axum::http::StatusCode;
 
+use crate::application::admin::comments::AdminCommentError;
 use crate::application::admin::navigation::AdminNavigationError;
 use crate::application::admin::pages::AdminPageError;
 use crate::application::admin::posts::AdminPostError;
-use crate::application::admin::settings::AdminSettingsError;
+use crate::application::admin::schedule::AdminScheduleError;
+use crate::application::admin::settings::{AdminSettingsError, SettingsValidationError};
 use crate::application::admin::snapshots::SnapshotServiceError;
 use crate::application::admin::tags::AdminTagError;
 use crate::application::admin::uploads::AdminUploadError;
 use crate::application::api_keys::ApiKeyError;
+use crate::application::pagination::PaginationError;
+use crate::application::render::RenderPreviewError;
 use crate::application::repos::RepoError;
 use crate::infra::uploads::UploadStorageError;
 
-use super::error::{ApiError, codes};
+use super::error::{ApiError, ApiFieldError, codes};
+
+/// Every cursor that fails to verify or decode answers `invalid_cursor`.
+pub(crate) fn cursor_to_api(err: PaginationError) -> ApiError {
+    ApiError::new(
+        StatusCode::BAD_REQUEST,
+        codes::INVALID_CURSOR,
+        "Invalid cursor",
+        Some(err.to_string()),
+    )
+}
 
 pub(crate) fn repo_to_api(err: RepoError) -> ApiError {
     match err {
@@ -121,12 +190,7 @@ pub(crate) fn repo_to_api(err: RepoError) -> ApiError {
             "Duplicate record",
             Some(constraint),
         ),
-        RepoError::Pagination(p) => ApiError::new(
-            StatusCode::BAD_REQUEST,
-            codes::INVALID_CURSOR,
-            "Invalid cursor",
-            Some(p.to_string()),
-        ),
+        RepoError::Pagination(p) => cursor_to_api(p),
         RepoError::NotFound => ApiError::not_found("resource not found"),
         RepoError::InvalidInput { message } => ApiError::new(
             StatusCode::BAD_REQUEST,
@@ -146,6 +210,13 @@ pub(crate) fn repo_to_api(err: RepoError) -> ApiError {
             "Database timeout",
             None,
         ),
+        RepoError::Contention => ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            codes::DB_CONTENTION,
+            "Database contention",
+            None,
+        ),
+        RepoError::Stale => ApiError::precondition_failed(),
         RepoError::Persistence(msg) => ApiError::new(
             StatusCode::INTERNAL_SERVER_ERROR,
             codes::REPO,
@@ -163,6 +234,18 @@ pub(crate) fn post_to_api(err: AdminPostError) -> ApiError {
             "Invalid post",
             Some(field.to_string()),
         ),
+        AdminPostError::InvalidSlug(err) => ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::INVALID_INPUT,
+            "Invalid slug",
+            Some(err.to_string()),
+        ),
+        AdminPostError::SlugConflict { slug } => ApiError::new(
+            StatusCode::CONFLICT,
+            codes::DUPLICATE,
+            "Slug already in use",
+            Some(slug),
+        ),
         AdminPostError::Repo(repo) => repo_to_api(repo),
     }
 }
@@ -175,6 +258,12 @@ pub(crate) fn page_to_api(err: AdminPageError) -> ApiError {
             "Invalid page",
             Some(field.to_string()),
         ),
+        err @ AdminPageError::ReservedSlug { .. } => ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::INVALID_INPUT,
+            "Reserved slug",
+            Some(err.to_string()),
+        ),
         AdminPageError::Render(render_err) => ApiError::new(
             StatusCode::BAD_REQUEST,
             codes::RENDER,
@@ -203,29 +292,94 @@ pub(crate) fn tag_to_api(err: AdminTagError) -> ApiError {
     }
 }
 
+pub(crate) fn schedule_to_api(err: AdminScheduleError) -> ApiError {
+    match err {
+        AdminScheduleError::InvalidRange | AdminScheduleError::RangeTooLong => ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::INVALID_INPUT,
+            "Invalid calendar range",
+            Some(err.to_string()),
+        ),
+        AdminScheduleError::Repo(repo) => repo_to_api(repo),
+    }
+}
+
+pub(crate) fn comment_to_api(err: AdminCommentError) -> ApiError {
+    match err {
+        AdminCommentError::NotFound => ApiError::not_found("comment not found"),
+        AdminCommentError::PostNotFound => ApiError::not_found("post not found"),
+        AdminCommentError::ConstraintViolation(field) => ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::INVALID_INPUT,
+            "Invalid comment",
+            Some(field.to_string()),
+        ),
+        AdminCommentError::Repo(repo) => repo_to_api(repo),
+    }
+}
+
+pub(crate) fn render_preview_to_api(err: RenderPreviewError) -> ApiError {
+    match err {
+        RenderPreviewError::TooLarge { .. } => ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            codes::PAYLOAD_TOO_LARGE,
+            "Markdown too large to preview",
+            Some(err.to_string()),
+        ),
+        RenderPreviewError::Render(render_err) => ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::RENDER,
+            "Rendering failed",
+            Some(render_err.to_string()),
+        ),
+    }
+}
+
 pub(crate) fn nav_to_api(err: AdminNavigationError) -> ApiError {
-    ApiError::new(
-        StatusCode::BAD_REQUEST,
-        codes::NAVIGATION,
-        "Navigation update failed",
-        Some(err.to_string()),
-    )
+    match err {
+        AdminNavigationError::Repo(repo) => repo_to_api(repo),
+        err @ AdminNavigationError::ConstraintViolation(_) => ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::NAVIGATION,
+            "Navigation update failed",
+            Some(err.to_string()),
+        ),
+    }
 }
 
 pub(crate) fn upload_to_api(err: AdminUploadError) -> ApiError {
     match err {
         AdminUploadError::NotFound => ApiError::not_found("upload not found"),
         AdminUploadError::Repo(repo) => repo_to_api(repo),
+        AdminUploadError::Storage(err) => ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            codes::UPLOAD,
+            "Failed to read stored upload",
+            Some(err.to_string()),
+        ),
     }
 }
 
 pub(crate) fn upload_storage_to_api(err: UploadStorageError) -> ApiError {
-    ApiError::new(
-        StatusCode::BAD_REQUEST,
-        codes::UPLOAD,
-        "Failed to store upload",
-        Some(err.to_string()),
-    )
+    match err {
+        UploadStorageError::PayloadTooLarge { .. } | UploadStorageError::SizeOverflow => {
+            ApiError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                codes::PAYLOAD_TOO_LARGE,
+                "Upload exceeds the configured size limit",
+                Some(err.to_string()),
+            )
+        }
+        UploadStorageError::PayloadStream { source } => {
+            ApiError::bad_request("failed to read upload", Some(source.to_string()))
+        }
+        _ => ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::UPLOAD,
+            "Failed to store upload",
+            Some(err.to_string()),
+        ),
+    }
 }
 
 pub(crate) fn snapshot_to_api(err: SnapshotServiceError) -> ApiError {
@@ -248,12 +402,26 @@ pub(crate) fn snapshot_to_api(err: SnapshotServiceError) -> ApiError {
 }
 
 pub(crate) fn settings_to_api(err: AdminSettingsError) -> ApiError {
-    ApiError::new(
-        StatusCode::BAD_REQUEST,
-        codes::SETTINGS,
-        "Settings update failed",
-        Some(err.to_string()),
-    )
+    match err {
+        AdminSettingsError::Invalid(invalid) => ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::SETTINGS,
+            "Invalid settings",
+            Some(invalid.to_string()),
+        )
+        .with_fields(settings_field_errors(&invalid)),
+        AdminSettingsError::Repo(repo) => repo_to_api(repo),
+    }
+}
+
+pub(crate) fn settings_field_errors(err: &SettingsValidationError) -> Vec<ApiFieldError> {
+    err.errors
+        .iter()
+        .map(|error| ApiFieldError {
+            field: error.field.to_string(),
+            message: error.message.clone(),
+        })
+        .collect()
 }
 
 pub(crate) fn api_key_to_api(err: ApiKeyError) -> ApiError {
@@ -14,7 +14,7 @@ use crate::application::pagination::{NavigationCursor, PageRequest};
 use crate::application::repos::NavigationQueryFilter;
 use crate::domain::api_keys::ApiScope;
 
-use super::{NavigationListQuery, nav_to_api, settings_to_api};
+use super::{NavigationListQuery, cursor_to_api, nav_to_api, require_scope, settings_to_api};
 use crate::infra::http::api::error::ApiError;
 use crate::infra::http::api::models::*;
 use crate::infra::http::api::state::ApiState;
@@ -24,29 +24,19 @@ pub async fn list_navigation(
     Extension(principal): Extension<ApiPrincipal>,
     Query(query): Query<NavigationListQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationRead)?;
     let settings = state.settings.load().await.map_err(settings_to_api)?;
     let limit = query
         .limit
         .unwrap_or_else(|| settings.admin_page_size.max(1) as u32)
         .clamp(1, 100);
 
-    let cursor = match query
+    let cursor = query
         .cursor
         .as_deref()
-        .map(NavigationCursor::decode)
+        .map(|cursor| NavigationCursor::decode(state.db.cursor_keys(), cursor))
         .transpose()
-    {
-        Ok(cursor) => cursor,
-        Err(err) => {
-            return Err(ApiError::bad_request(
-                "invalid cursor",
-                Some(err.to_string()),
-            ));
-        }
-    };
+        .map_err(cursor_to_api)?;
 
     let filter = NavigationQueryFilter {
         search: query.search,
@@ -66,9 +56,7 @@ pub async fn get_navigation_item(
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationRead)?;
 
     let item = state.navigation.find_by_id(id).await.map_err(nav_to_api)?;
 
@@ -83,9 +71,7 @@ pub async fn create_navigation(
     Extension(principal): Extension<ApiPrincipal>,
     Json(payload): Json<NavigationCreateRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let command = CreateNavigationItemCommand {
@@ -113,9 +99,7 @@ pub async fn update_navigation(
     Path(id): Path<Uuid>,
     Json(payload): Json<NavigationUpdateRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let command = UpdateNavigationItemCommand {
@@ -144,9 +128,7 @@ pub async fn update_navigation_label(
     Path(id): Path<Uuid>,
     Json(payload): Json<NavigationLabelRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let existing = state
@@ -182,9 +164,7 @@ pub async fn update_navigation_destination(
     Path(id): Path<Uuid>,
     Json(payload): Json<NavigationDestinationRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let existing = state
@@ -220,9 +200,7 @@ pub async fn update_navigation_sort_order(
     Path(id): Path<Uuid>,
     Json(payload): Json<NavigationSortOrderRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let existing = state
@@ -258,9 +236,7 @@ pub async fn update_navigation_visibility(
     Path(id): Path<Uuid>,
     Json(payload): Json<NavigationVisibilityRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let existing = state
@@ -296,9 +272,7 @@ pub async fn update_navigation_open_in_new_tab(
     Path(id): Path<Uuid>,
     Json(payload): Json<NavigationOpenInNewTabRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let existing = state
@@ -333,11 +307,16 @@ pub async fn delete_navigation(
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::NavigationWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::NavigationWrite)?;
     let actor = ApiState::actor_label(&principal);
 
+    state
+        .navigation
+        .find_by_id(id)
+        .await
+        .map_err(nav_to_api)?
+        .ok_or_else(|| ApiError::not_found("navigation not found"))?;
+
     state
         .navigation
         .delete_item(&actor, id)
@@ -2,7 +2,7 @@
 
 use axum::Json;
 use axum::extract::{Extension, Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode, header::LOCATION};
 use axum::response::IntoResponse;
 use uuid::Uuid;
 
@@ -10,23 +10,25 @@ use crate::application::admin::pages::{
     CreatePageCommand, UpdatePageContentCommand, UpdatePageStatusCommand,
 };
 use crate::application::api_keys::ApiPrincipal;
-use crate::application::pagination::PageCursor;
+use crate::application::pagination::{PageCursor, PageRequest, TrashCursor};
 use crate::application::repos::PageQueryFilter;
 use crate::domain::api_keys::ApiScope;
 
-use super::{PageListQuery, page_to_api, settings_to_api};
+use super::{
+    PageListQuery, TrashListQuery, cursor_to_api, page_to_api, require_scope, settings_to_api,
+};
 use crate::infra::http::api::error::ApiError;
+use crate::infra::http::api::etag::{require_if_match, with_etag};
 use crate::infra::http::api::models::*;
 use crate::infra::http::api::state::ApiState;
+use crate::infra::http::api::strict_json::StrictJson;
 
 pub async fn list_pages(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Query(query): Query<PageListQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PageRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PageRead)?;
 
     let settings = state.settings.load().await.map_err(settings_to_api)?;
     let limit = query
@@ -34,15 +36,12 @@ pub async fn list_pages(
         .unwrap_or_else(|| settings.admin_page_size.max(1) as u32)
         .clamp(1, 100);
 
-    let cursor = match query.cursor.as_deref().map(PageCursor::decode).transpose() {
-        Ok(cursor) => cursor,
-        Err(err) => {
-            return Err(ApiError::bad_request(
-                "invalid cursor",
-                Some(err.to_string()),
-            ));
-        }
-    };
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(|cursor| PageCursor::decode(state.db.cursor_keys(), cursor))
+        .transpose()
+        .map_err(cursor_to_api)?;
 
     let filter = PageQueryFilter {
         search: query.search,
@@ -63,14 +62,15 @@ pub async fn get_page(
     Extension(principal): Extension<ApiPrincipal>,
     Path(slug): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PageRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PageRead)?;
 
     let page = state.pages.find_by_slug(&slug).await.map_err(page_to_api)?;
 
     match page {
-        Some(page) => Ok(Json(page)),
+        Some(page) => {
+            let updated_at = page.updated_at;
+            Ok(with_etag(Json(page), updated_at))
+        }
         None => Err(ApiError::not_found("page not found")),
     }
 }
@@ -80,14 +80,15 @@ pub async fn get_page_by_id(
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PageRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PageRead)?;
 
     let page = state.pages.find_by_id(id).await.map_err(page_to_api)?;
 
     match page {
-        Some(page) => Ok(Json(page)),
+        Some(page) => {
+            let updated_at = page.updated_at;
+            Ok(with_etag(Json(page), updated_at))
+        }
         None => Err(ApiError::not_found("page not found")),
     }
 }
@@ -95,11 +96,9 @@ pub async fn get_page_by_id(
 pub async fn create_page(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
-    Json(payload): Json<PageCreateRequest>,
+    StrictJson(payload): StrictJson<PageCreateRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PageWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PageWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let command = CreatePageCommand {
@@ -110,6 +109,7 @@ pub async fn create_page(
         scheduled_at: payload.scheduled_at,
         published_at: payload.published_at,
         archived_at: payload.archived_at,
+        template: payload.template,
     };
 
     let page = state
@@ -118,18 +118,45 @@ pub async fn create_page(
         .await
         .map_err(page_to_api)?;
 
-    Ok((StatusCode::CREATED, Json(page)))
+    let location = format!("/api/v1/pages/{}", page.id);
+    let updated_at = page.updated_at;
+    Ok(with_etag(
+        (StatusCode::CREATED, [(LOCATION, location)], Json(page)),
+        updated_at,
+    ))
+}
+
+pub async fn duplicate_page(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PageWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    let page = state
+        .pages
+        .duplicate_page(&actor, id)
+        .await
+        .map_err(page_to_api)?;
+
+    let location = format!("/api/v1/pages/{}", page.id);
+    let updated_at = page.updated_at;
+    Ok(with_etag(
+        (StatusCode::CREATED, [(LOCATION, location)], Json(page)),
+        updated_at,
+    ))
 }
 
 pub async fn update_page(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<PageUpdateRequest>,
+    headers: HeaderMap,
+    StrictJson(payload): StrictJson<PageUpdateRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PageWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PageWrite)?;
+    let expected_updated_at = require_if_match(&headers)?;
     let actor = ApiState::actor_label(&principal);
 
     let command = UpdatePageContentCommand {
@@ -137,6 +164,8 @@ pub async fn update_page(
         slug: payload.slug,
         title: payload.title,
         body_markdown: payload.body_markdown,
+        template: payload.template,
+        expected_updated_at,
     };
 
     let page = state
@@ -145,18 +174,19 @@ pub async fn update_page(
         .await
         .map_err(page_to_api)?;
 
-    Ok(Json(page))
+    let updated_at = page.updated_at;
+    Ok(with_etag(Json(page), updated_at))
 }
 
 pub async fn update_page_title(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<PageTitleRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PageWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PageWrite)?;
+    let expected_updated_at = require_if_match(&headers)?;
     let actor = ApiState::actor_label(&principal);
 
     let page = state
@@ -175,6 +205,8 @@ pub async fn update_page_title(
         slug: page.slug.clone(),
         title: payload.title,
         body_markdown: page.body_markdown.clone(),
+        template: None,
+        expected_updated_at,
     };
 
     let updated = state
@@ -183,18 +215,37 @@ pub async fn update_page_title(
         .await
         .map_err(page_to_api)?;
 
-    Ok(Json(updated))
+    let updated_at = updated.updated_at;
+    Ok(with_etag(Json(updated), updated_at))
+}
+
+pub async fn update_page_translation(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<PageTranslationRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PageWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    let page = state
+        .pages
+        .update_translation(&actor, id, payload.lang, payload.translation_group)
+        .await
+        .map_err(page_to_api)?;
+
+    Ok(Json(page))
 }
 
 pub async fn update_page_body(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<PageBodyRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PageWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PageWrite)?;
+    let expected_updated_at = require_if_match(&headers)?;
     let actor = ApiState::actor_label(&principal);
 
     let page = state
@@ -209,6 +260,8 @@ pub async fn update_page_body(
         slug: page.slug.clone(),
         title: page.title.clone(),
         body_markdown: payload.body_markdown,
+        template: None,
+        expected_updated_at,
     };
 
     let updated = state
@@ -217,7 +270,8 @@ pub async fn update_page_body(
         .await
         .map_err(page_to_api)?;
 
-    Ok(Json(updated))
+    let updated_at = updated.updated_at;
+    Ok(with_etag(Json(updated), updated_at))
 }
 
 pub async fn update_page_status(
@@ -226,9 +280,7 @@ pub async fn update_page_status(
     Path(id): Path<Uuid>,
     Json(payload): Json<PageStatusRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PageWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PageWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let command = UpdatePageStatusCommand {
@@ -253,9 +305,7 @@ pub async fn delete_page(
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PageWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PageWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let page = state
@@ -273,3 +323,62 @@ pub async fn delete_page(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+pub async fn list_trashed_pages(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Query(query): Query<TrashListQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PageRead)?;
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(|cursor| TrashCursor::decode(state.db.cursor_keys(), cursor))
+        .transpose()
+        .map_err(cursor_to_api)?;
+
+    let page = state
+        .pages
+        .list_trash(PageRequest::new(limit, cursor))
+        .await
+        .map_err(page_to_api)?;
+
+    Ok(Json(page))
+}
+
+pub async fn restore_page(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PageWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    let page = state
+        .pages
+        .restore_page(&actor, id)
+        .await
+        .map_err(page_to_api)?;
+
+    let updated_at = page.updated_at;
+    Ok(with_etag(Json(page), updated_at))
+}
+
+pub async fn purge_page(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PageWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    state
+        .pages
+        .purge_page(&actor, id)
+        .await
+        .map_err(page_to_api)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
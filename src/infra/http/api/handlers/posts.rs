@@ -2,31 +2,36 @@
 
 use axum::Json;
 use axum::extract::{Extension, Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode, header::LOCATION};
 use axum::response::IntoResponse;
+use serde::Serialize;
 use uuid::Uuid;
 
 use crate::application::admin::posts::{
-    CreatePostCommand, UpdatePostContentCommand, UpdatePostStatusCommand,
+    CreatePostCommand, UpdatePostContentCommand, UpdatePostStatusCommand, editable_excerpt,
 };
 use crate::application::api_keys::ApiPrincipal;
-use crate::application::pagination::{PageRequest, PostCursor};
+use crate::application::jobs::PUBLISH_JOB_WAIT_TIMEOUT;
+use crate::application::pagination::{PageRequest, PostCursor, TrashCursor};
 use crate::application::repos::{PostListScope, PostQueryFilter};
 use crate::domain::api_keys::ApiScope;
 
-use super::{PostListQuery, post_to_api, repo_to_api, settings_to_api};
+use super::{
+    PostListQuery, PostStatusQuery, TrashListQuery, cursor_to_api, post_to_api, repo_to_api,
+    require_scope, settings_to_api,
+};
 use crate::infra::http::api::error::ApiError;
+use crate::infra::http::api::etag::{require_if_match, with_etag};
 use crate::infra::http::api::models::*;
 use crate::infra::http::api::state::ApiState;
+use crate::infra::http::api::strict_json::StrictJson;
 
 pub async fn list_posts(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Query(query): Query<PostListQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostRead)?;
 
     let settings = state.settings.load().await.map_err(settings_to_api)?;
     let limit = query
@@ -34,19 +39,17 @@ pub async fn list_posts(
         .unwrap_or_else(|| settings.admin_page_size.max(1) as u32)
         .clamp(1, 100);
 
-    let cursor = match query.cursor.as_deref().map(PostCursor::decode).transpose() {
-        Ok(cursor) => cursor,
-        Err(err) => {
-            return Err(ApiError::bad_request(
-                "invalid cursor",
-                Some(err.to_string()),
-            ));
-        }
-    };
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(|cursor| PostCursor::decode(state.db.cursor_keys(), cursor))
+        .transpose()
+        .map_err(cursor_to_api)?;
 
     let filter = PostQueryFilter {
         tag: query.tag,
         month: query.month,
+        year: None,
         search: query.search,
     };
 
@@ -66,14 +69,38 @@ pub async fn list_posts(
     Ok(Json(page))
 }
 
+pub async fn post_month_aggregations(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PostRead)?;
+    let settings = state.settings.load().await.map_err(settings_to_api)?;
+
+    let months = state
+        .posts
+        .month_aggregations(settings.timezone, settings.month_filter_limit)
+        .await
+        .map_err(post_to_api)?;
+
+    Ok(Json(MonthAggregationsResponse {
+        month_filter_limit: settings.month_filter_limit,
+        timezone: settings.timezone.name().to_string(),
+        months: months
+            .into_iter()
+            .map(|month| MonthAggregation {
+                month: month.key,
+                count: month.count,
+            })
+            .collect(),
+    }))
+}
+
 pub async fn get_post(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(slug): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostRead)?;
 
     let post = state
         .posts
@@ -83,7 +110,15 @@ pub async fn get_post(
         .map_err(repo_to_api)?;
 
     match post {
-        Some(post) => Ok(Json(post)),
+        Some(post) => {
+            let updated_at = post.updated_at;
+            let post = state
+                .posts
+                .with_render_progress(post, None)
+                .await
+                .map_err(post_to_api)?;
+            Ok(with_etag(Json(post), updated_at))
+        }
         None => Err(ApiError::not_found("post not found")),
     }
 }
@@ -93,9 +128,7 @@ pub async fn get_post_by_id(
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostRead)?;
 
     let post = state
         .posts
@@ -105,7 +138,15 @@ pub async fn get_post_by_id(
         .map_err(repo_to_api)?;
 
     match post {
-        Some(post) => Ok(Json(post)),
+        Some(post) => {
+            let updated_at = post.updated_at;
+            let post = state
+                .posts
+                .with_render_progress(post, None)
+                .await
+                .map_err(post_to_api)?;
+            Ok(with_etag(Json(post), updated_at))
+        }
         None => Err(ApiError::not_found("post not found")),
     }
 }
@@ -113,15 +154,14 @@ pub async fn get_post_by_id(
 pub async fn create_post(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
-    Json(payload): Json<PostCreateRequest>,
+    StrictJson(payload): StrictJson<PostCreateRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
 
     let actor = ApiState::actor_label(&principal);
 
     let command = CreatePostCommand {
+        slug: payload.slug,
         title: payload.title,
         excerpt: payload.excerpt,
         body_markdown: payload.body_markdown,
@@ -139,18 +179,45 @@ pub async fn create_post(
         .await
         .map_err(post_to_api)?;
 
-    Ok((StatusCode::CREATED, Json(post)))
+    let location = format!("/api/v1/posts/{}", post.id);
+    let updated_at = post.updated_at;
+    Ok(with_etag(
+        (StatusCode::CREATED, [(LOCATION, location)], Json(post)),
+        updated_at,
+    ))
+}
+
+pub async fn duplicate_post(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PostWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    let post = state
+        .posts
+        .duplicate_post(&actor, id)
+        .await
+        .map_err(post_to_api)?;
+
+    let location = format!("/api/v1/posts/{}", post.id);
+    let updated_at = post.updated_at;
+    Ok(with_etag(
+        (StatusCode::CREATED, [(LOCATION, location)], Json(post)),
+        updated_at,
+    ))
 }
 
 pub async fn update_post(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<PostUpdateRequest>,
+    headers: HeaderMap,
+    StrictJson(payload): StrictJson<PostUpdateRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
+    let expected_updated_at = require_if_match(&headers)?;
     let actor = ApiState::actor_label(&principal);
 
     let command = UpdatePostContentCommand {
@@ -161,6 +228,7 @@ pub async fn update_post(
         body_markdown: payload.body_markdown,
         pinned: payload.pinned,
         summary_markdown: payload.summary_markdown,
+        expected_updated_at,
     };
 
     let post = state
@@ -169,18 +237,18 @@ pub async fn update_post(
         .await
         .map_err(post_to_api)?;
 
-    Ok(Json(post))
+    let updated_at = post.updated_at;
+    Ok(with_etag(Json(post), updated_at))
 }
 
 pub async fn update_post_status(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
+    Query(query): Query<PostStatusQuery>,
     Json(payload): Json<PostStatusRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let command = UpdatePostStatusCommand {
@@ -196,6 +264,12 @@ pub async fn update_post_status(
         .update_status(&actor, command)
         .await
         .map_err(post_to_api)?;
+    let wait = query.wait_render.then_some(PUBLISH_JOB_WAIT_TIMEOUT);
+    let post = state
+        .posts
+        .with_render_progress(post, wait)
+        .await
+        .map_err(post_to_api)?;
 
     Ok(Json(post))
 }
@@ -206,14 +280,30 @@ pub async fn update_post_pin(
     Path(id): Path<Uuid>,
     Json(payload): Json<PostPinRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    let post = state
+        .posts
+        .update_pin_state(&actor, id, payload.pinned, payload.pin_order)
+        .await
+        .map_err(post_to_api)?;
+
+    Ok(Json(post))
+}
+
+pub async fn update_post_translation(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<PostTranslationRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PostWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let post = state
         .posts
-        .update_pin_state(&actor, id, payload.pinned)
+        .update_translation(&actor, id, payload.lang, payload.translation_group)
         .await
         .map_err(post_to_api)?;
 
@@ -224,11 +314,11 @@ pub async fn update_post_title(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<PostTitleRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
+    let expected_updated_at = require_if_match(&headers)?;
     let actor = ApiState::actor_label(&principal);
 
     let post = state
@@ -246,10 +336,11 @@ pub async fn update_post_title(
         id,
         slug: post.slug.clone(),
         title: payload.title,
-        excerpt: post.excerpt.clone(),
+        excerpt: editable_excerpt(&post),
         body_markdown: post.body_markdown.clone(),
         pinned: post.pinned,
         summary_markdown: post.summary_markdown.clone(),
+        expected_updated_at,
     };
 
     let updated = state
@@ -258,18 +349,19 @@ pub async fn update_post_title(
         .await
         .map_err(post_to_api)?;
 
-    Ok(Json(updated))
+    let updated_at = updated.updated_at;
+    Ok(with_etag(Json(updated), updated_at))
 }
 
 pub async fn update_post_excerpt(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<PostExcerptRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
+    let expected_updated_at = require_if_match(&headers)?;
     let actor = ApiState::actor_label(&principal);
 
     let post = state
@@ -287,6 +379,7 @@ pub async fn update_post_excerpt(
         body_markdown: post.body_markdown.clone(),
         pinned: post.pinned,
         summary_markdown: post.summary_markdown.clone(),
+        expected_updated_at,
     };
 
     let updated = state
@@ -295,18 +388,20 @@ pub async fn update_post_excerpt(
         .await
         .map_err(post_to_api)?;
 
-    Ok(Json(updated))
+    let updated_at = updated.updated_at;
+    Ok(with_etag(Json(updated), updated_at))
 }
 
 pub async fn update_post_body(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
+    Query(query): Query<PostStatusQuery>,
+    headers: HeaderMap,
     Json(payload): Json<PostBodyRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
+    let expected_updated_at = require_if_match(&headers)?;
     let actor = ApiState::actor_label(&principal);
 
     let post = state
@@ -320,10 +415,11 @@ pub async fn update_post_body(
         id,
         slug: post.slug.clone(),
         title: post.title.clone(),
-        excerpt: post.excerpt.clone(),
+        excerpt: editable_excerpt(&post),
         body_markdown: payload.body_markdown,
         pinned: post.pinned,
         summary_markdown: post.summary_markdown.clone(),
+        expected_updated_at,
     };
 
     let updated = state
@@ -331,19 +427,26 @@ pub async fn update_post_body(
         .update_post(&actor, command)
         .await
         .map_err(post_to_api)?;
+    let wait = query.wait_render.then_some(PUBLISH_JOB_WAIT_TIMEOUT);
+    let updated = state
+        .posts
+        .with_render_progress(updated, wait)
+        .await
+        .map_err(post_to_api)?;
 
-    Ok(Json(updated))
+    let updated_at = updated.post.updated_at;
+    Ok(with_etag(Json(updated), updated_at))
 }
 
 pub async fn update_post_summary(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<PostSummaryRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
+    let expected_updated_at = require_if_match(&headers)?;
     let actor = ApiState::actor_label(&principal);
 
     let post = state
@@ -357,10 +460,11 @@ pub async fn update_post_summary(
         id,
         slug: post.slug.clone(),
         title: post.title.clone(),
-        excerpt: post.excerpt.clone(),
+        excerpt: editable_excerpt(&post),
         body_markdown: post.body_markdown.clone(),
         pinned: post.pinned,
         summary_markdown: payload.summary_markdown,
+        expected_updated_at,
     };
 
     let updated = state
@@ -369,7 +473,8 @@ pub async fn update_post_summary(
         .await
         .map_err(post_to_api)?;
 
-    Ok(Json(updated))
+    let updated_at = updated.updated_at;
+    Ok(with_etag(Json(updated), updated_at))
 }
 
 pub async fn replace_post_tags(
@@ -378,9 +483,7 @@ pub async fn replace_post_tags(
     Path(id): Path<Uuid>,
     Json(payload): Json<PostTagsRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let post = state
@@ -405,9 +508,7 @@ pub async fn delete_post(
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::PostWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::PostWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let post = state
@@ -426,3 +527,75 @@ pub async fn delete_post(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+pub async fn list_trashed_posts(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Query(query): Query<TrashListQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PostRead)?;
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 100);
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(|cursor| TrashCursor::decode(state.db.cursor_keys(), cursor))
+        .transpose()
+        .map_err(cursor_to_api)?;
+
+    let page = state
+        .posts
+        .list_trash(PageRequest::new(limit, cursor))
+        .await
+        .map_err(post_to_api)?;
+
+    Ok(Json(page))
+}
+
+pub async fn restore_post(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PostWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    let post = state
+        .posts
+        .restore_post(&actor, id)
+        .await
+        .map_err(post_to_api)?;
+
+    let updated_at = post.updated_at;
+    Ok(with_etag(Json(post), updated_at))
+}
+
+pub async fn purge_post(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PostWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    state
+        .posts
+        .purge_post(&actor, id)
+        .await
+        .map_err(post_to_api)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Serialize)]
+struct MonthAggregationsResponse {
+    month_filter_limit: i32,
+    timezone: String,
+    months: Vec<MonthAggregation>,
+}
+
+#[derive(Debug, Serialize)]
+struct MonthAggregation {
+    month: String,
+    count: usize,
+}
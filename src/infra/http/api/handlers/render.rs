@@ -0,0 +1,51 @@
+//! Render preview handlers
+
+use axum::Json;
+use axum::extract::{Extension, State};
+use axum::response::{IntoResponse, Response};
+
+use crate::application::api_keys::ApiPrincipal;
+use crate::application::render::{PreviewTarget, RenderPreviewCommand};
+use crate::domain::api_keys::ApiScope;
+
+use super::{render_preview_to_api, require_scope};
+use crate::infra::http::api::error::ApiError;
+use crate::infra::http::api::models::*;
+use crate::infra::http::api::state::ApiState;
+
+const RENDER_PREVIEW_ROUTE: &str = "render.preview";
+
+pub async fn render_preview(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Json(payload): Json<RenderPreviewRequest>,
+) -> Result<Response, ApiError> {
+    require_scope(&principal, ApiScope::RenderPreview)?;
+
+    let limiter_key = principal.key_id.to_string();
+    let (allowed, _) = state
+        .render_preview_rate_limiter
+        .allow(&limiter_key, RENDER_PREVIEW_ROUTE);
+    if !allowed {
+        return Ok(ApiError::rate_limited(
+            state.render_preview_rate_limiter.retry_after_secs(),
+        ));
+    }
+
+    let command = RenderPreviewCommand {
+        markdown: payload.markdown,
+        target: match payload.target {
+            RenderPreviewTarget::Post => PreviewTarget::Post,
+            RenderPreviewTarget::Page => PreviewTarget::Page,
+        },
+        sanitized: payload.sanitized,
+    };
+
+    let preview = state
+        .render_preview
+        .preview(command)
+        .await
+        .map_err(render_preview_to_api)?;
+
+    Ok(Json(preview).into_response())
+}
@@ -0,0 +1,43 @@
+//! Content calendar handlers
+
+use axum::Json;
+use axum::extract::{Extension, Query, State};
+use axum::response::IntoResponse;
+use time::{Date, macros::format_description};
+
+use crate::application::api_keys::ApiPrincipal;
+use crate::domain::api_keys::ApiScope;
+
+use super::{ScheduleQuery, require_scope, schedule_to_api, settings_to_api};
+use crate::infra::http::api::error::ApiError;
+use crate::infra::http::api::state::ApiState;
+
+pub async fn get_schedule(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Query(query): Query<ScheduleQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::PostRead)?;
+    require_scope(&principal, ApiScope::PageRead)?;
+
+    let from = parse_day("from", &query.from)?;
+    let to = parse_day("to", &query.to)?;
+    let settings = state.settings.load().await.map_err(settings_to_api)?;
+
+    let days = state
+        .schedule
+        .calendar(from, to, settings.timezone)
+        .await
+        .map_err(schedule_to_api)?;
+
+    Ok(Json(days))
+}
+
+fn parse_day(name: &str, value: &str) -> Result<Date, ApiError> {
+    Date::parse(value.trim(), format_description!("[year]-[month]-[day]")).map_err(|_| {
+        ApiError::bad_request(
+            "invalid date",
+            Some(format!("`{name}` must be a YYYY-MM-DD date")),
+        )
+    })
+}
@@ -3,23 +3,23 @@
 use axum::Json;
 use axum::extract::{Extension, State};
 use axum::response::IntoResponse;
+use serde::Serialize;
 
-use crate::application::admin::settings::UpdateSettingsCommand;
+use crate::application::admin::settings::SettingsValidator;
 use crate::application::api_keys::ApiPrincipal;
 use crate::domain::api_keys::ApiScope;
 
-use super::settings_to_api;
-use crate::infra::http::api::error::ApiError;
+use super::{require_scope, settings_field_errors, settings_to_api};
+use crate::infra::http::api::error::{ApiError, ApiFieldError};
 use crate::infra::http::api::models::SettingsPatchRequest;
 use crate::infra::http::api::state::ApiState;
+use crate::infra::http::api::strict_json::StrictJson;
 
 pub async fn get_settings(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::SettingsRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::SettingsRead)?;
 
     let settings = state.settings.load().await.map_err(settings_to_api)?;
     Ok(Json(settings))
@@ -28,94 +28,41 @@ pub async fn get_settings(
 pub async fn patch_settings(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
-    Json(payload): Json<SettingsPatchRequest>,
+    StrictJson(payload): StrictJson<SettingsPatchRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::SettingsWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::SettingsWrite)?;
     let actor = ApiState::actor_label(&principal);
 
-    let mut current = state.settings.load().await.map_err(settings_to_api)?;
-
-    if let Some(val) = payload.brand_title {
-        current.brand_title = val;
-    }
-    if let Some(val) = payload.brand_href {
-        current.brand_href = val;
-    }
-    if let Some(val) = payload.footer_copy {
-        current.footer_copy = val;
-    }
-    if let Some(val) = payload.homepage_size {
-        current.homepage_size = val;
-    }
-    if let Some(val) = payload.admin_page_size {
-        current.admin_page_size = val;
-    }
-    if let Some(val) = payload.show_tag_aggregations {
-        current.show_tag_aggregations = val;
-    }
-    if let Some(val) = payload.show_month_aggregations {
-        current.show_month_aggregations = val;
-    }
-    if let Some(val) = payload.tag_filter_limit {
-        current.tag_filter_limit = val;
-    }
-    if let Some(val) = payload.month_filter_limit {
-        current.month_filter_limit = val;
-    }
-    if let Some(val) = payload.timezone {
-        current.timezone = val
-            .parse::<chrono_tz::Tz>()
-            .map_err(|err| ApiError::bad_request("invalid timezone", Some(err.to_string())))?;
-    }
-    if let Some(val) = payload.meta_title {
-        current.meta_title = val;
-    }
-    if let Some(val) = payload.meta_description {
-        current.meta_description = val;
-    }
-    if let Some(val) = payload.og_title {
-        current.og_title = val;
-    }
-    if let Some(val) = payload.og_description {
-        current.og_description = val;
-    }
-    if let Some(val) = payload.public_site_url {
-        current.public_site_url = val;
-    }
-    if let Some(val) = payload.global_toc_enabled {
-        current.global_toc_enabled = val;
-    }
-    if let Some(val) = payload.favicon_svg {
-        current.favicon_svg = val;
-    }
-
-    let command = UpdateSettingsCommand {
-        homepage_size: current.homepage_size,
-        admin_page_size: current.admin_page_size,
-        show_tag_aggregations: current.show_tag_aggregations,
-        show_month_aggregations: current.show_month_aggregations,
-        tag_filter_limit: current.tag_filter_limit,
-        month_filter_limit: current.month_filter_limit,
-        global_toc_enabled: current.global_toc_enabled,
-        brand_title: current.brand_title.clone(),
-        brand_href: current.brand_href.clone(),
-        footer_copy: current.footer_copy.clone(),
-        public_site_url: current.public_site_url.clone(),
-        favicon_svg: current.favicon_svg.clone(),
-        timezone: current.timezone,
-        meta_title: current.meta_title.clone(),
-        meta_description: current.meta_description.clone(),
-        og_title: current.og_title.clone(),
-        og_description: current.og_description.clone(),
-    };
-
     let updated = state
         .settings
-        .update(&actor, command)
+        .patch(&actor, payload)
         .await
         .map_err(settings_to_api)?;
 
     Ok(Json(updated))
 }
+
+/// Dry run of `patch_settings`: report what the patch would be rejected for
+/// without storing anything.
+pub async fn validate_settings(
+    Extension(principal): Extension<ApiPrincipal>,
+    StrictJson(payload): StrictJson<SettingsPatchRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::SettingsWrite)?;
+
+    let fields = match SettingsValidator::validate_patch(&payload) {
+        Ok(()) => Vec::new(),
+        Err(err) => settings_field_errors(&err),
+    };
+
+    Ok(Json(SettingsValidationResponse {
+        valid: fields.is_empty(),
+        errors: fields,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+struct SettingsValidationResponse {
+    valid: bool,
+    errors: Vec<ApiFieldError>,
+}
@@ -10,11 +10,12 @@ use crate::application::admin::snapshots::SnapshotServiceError;
 use crate::application::api_keys::ApiPrincipal;
 use crate::application::pagination::{CursorPage, PageRequest, SnapshotCursor};
 use crate::application::repos::{SnapshotFilter, SnapshotRecord};
+use crate::domain::api_keys::ApiScope;
 use crate::domain::types::SnapshotEntityType;
 
 use super::super::error::ApiError;
 use super::super::models::{SnapshotCreateRequest, SnapshotListQuery, SnapshotResponse};
-use super::{page_to_api, post_to_api, snapshot_to_api};
+use super::{cursor_to_api, page_to_api, post_to_api, require_scope, snapshot_to_api};
 use crate::application::error::AppError;
 use crate::infra::http::api::state::ApiState;
 
@@ -36,24 +37,14 @@ pub async fn list_snapshots(
     Query(query): Query<SnapshotListQuery>,
     axum::extract::Extension(principal): axum::extract::Extension<ApiPrincipal>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(crate::domain::api_keys::ApiScope::SnapshotRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::SnapshotRead)?;
 
-    let cursor = match query
+    let cursor = query
         .cursor
         .as_deref()
-        .map(SnapshotCursor::decode)
+        .map(|cursor| SnapshotCursor::decode(state.db.cursor_keys(), cursor))
         .transpose()
-    {
-        Ok(c) => c,
-        Err(err) => {
-            return Err(ApiError::bad_request(
-                "invalid cursor",
-                Some(err.to_string()),
-            ));
-        }
-    };
+        .map_err(cursor_to_api)?;
 
     let filter = SnapshotFilter {
         entity_type: query.entity_type,
@@ -82,9 +73,7 @@ pub async fn get_snapshot(
     Path(id): Path<Uuid>,
     axum::extract::Extension(principal): axum::extract::Extension<ApiPrincipal>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(crate::domain::api_keys::ApiScope::SnapshotRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::SnapshotRead)?;
 
     let snapshot = state
         .snapshots
@@ -101,9 +90,7 @@ pub async fn create_snapshot(
     axum::extract::Extension(principal): axum::extract::Extension<ApiPrincipal>,
     Json(payload): Json<SnapshotCreateRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(crate::domain::api_keys::ApiScope::SnapshotWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::SnapshotWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let record = match payload.entity_type {
@@ -143,9 +130,7 @@ pub async fn rollback_snapshot(
     axum::extract::Extension(principal): axum::extract::Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(crate::domain::api_keys::ApiScope::SnapshotWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::SnapshotWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     // Decide entity type by reading snapshot first
@@ -176,6 +161,9 @@ pub async fn rollback_snapshot(
                             crate::application::admin::posts::types::AdminPostError::Repo(repo) => {
                                 SnapshotServiceError::Repo(repo)
                             }
+                            other => SnapshotServiceError::Snapshot(
+                                crate::domain::snapshots::SnapshotError::Validation(other.to_string()),
+                            ),
                         })
                 })
                 .await
@@ -202,6 +190,11 @@ pub async fn rollback_snapshot(
                             crate::application::admin::pages::AdminPageError::Repo(repo) => {
                                 SnapshotServiceError::Repo(repo)
                             }
+                            other @ crate::application::admin::pages::AdminPageError::ReservedSlug { .. } => {
+                                SnapshotServiceError::Snapshot(
+                                    crate::domain::snapshots::SnapshotError::Validation(other.to_string()),
+                                )
+                            }
                         })
                 })
                 .await
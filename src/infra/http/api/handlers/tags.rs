@@ -4,15 +4,16 @@ use axum::Json;
 use axum::extract::{Extension, Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use serde::Serialize;
 use uuid::Uuid;
 
 use crate::application::admin::tags::{CreateTagCommand, UpdateTagCommand};
 use crate::application::api_keys::ApiPrincipal;
 use crate::application::pagination::{PageRequest, TagCursor};
-use crate::application::repos::TagQueryFilter;
+use crate::application::repos::{TagAssignmentMode, TagQueryFilter, TagWithCount};
 use crate::domain::api_keys::ApiScope;
 
-use super::{TagListQuery, settings_to_api, tag_to_api};
+use super::{TagListQuery, cursor_to_api, require_scope, settings_to_api, tag_to_api};
 use crate::infra::http::api::error::ApiError;
 use crate::infra::http::api::models::*;
 use crate::infra::http::api::state::ApiState;
@@ -22,24 +23,19 @@ pub async fn list_tags(
     Extension(principal): Extension<ApiPrincipal>,
     Query(query): Query<TagListQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::TagRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::TagRead)?;
     let settings = state.settings.load().await.map_err(settings_to_api)?;
     let limit = query
         .limit
         .unwrap_or_else(|| settings.admin_page_size.max(1) as u32)
         .clamp(1, 100);
 
-    let cursor = match query.cursor.as_deref().map(TagCursor::decode).transpose() {
-        Ok(cursor) => cursor,
-        Err(err) => {
-            return Err(ApiError::bad_request(
-                "invalid cursor",
-                Some(err.to_string()),
-            ));
-        }
-    };
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(|cursor| TagCursor::decode(state.db.cursor_keys(), cursor))
+        .transpose()
+        .map_err(cursor_to_api)?;
 
     let filter = TagQueryFilter {
         search: query.search,
@@ -55,14 +51,31 @@ pub async fn list_tags(
     Ok(Json(page))
 }
 
+pub async fn tag_aggregations(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::TagRead)?;
+    let settings = state.settings.load().await.map_err(settings_to_api)?;
+
+    let tags = state
+        .tags
+        .aggregations(settings.tag_filter_limit)
+        .await
+        .map_err(tag_to_api)?;
+
+    Ok(Json(TagAggregationsResponse {
+        tag_filter_limit: settings.tag_filter_limit,
+        tags,
+    }))
+}
+
 pub async fn get_tag_by_id(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::TagRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::TagRead)?;
 
     let tag = state.tags.find_by_id(id).await.map_err(tag_to_api)?;
 
@@ -77,9 +90,7 @@ pub async fn get_tag_by_slug(
     Extension(principal): Extension<ApiPrincipal>,
     Path(slug): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::TagRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::TagRead)?;
 
     let tag = state.tags.find_by_slug(&slug).await.map_err(tag_to_api)?;
 
@@ -94,9 +105,7 @@ pub async fn create_tag(
     Extension(principal): Extension<ApiPrincipal>,
     Json(payload): Json<TagCreateRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::TagWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::TagWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let command = CreateTagCommand {
@@ -120,9 +129,7 @@ pub async fn update_tag(
     Path(id): Path<Uuid>,
     Json(payload): Json<TagUpdateRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::TagWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::TagWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let command = UpdateTagCommand {
@@ -147,9 +154,7 @@ pub async fn update_tag_pin(
     Path(id): Path<Uuid>,
     Json(payload): Json<TagPinRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::TagWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::TagWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let tag = state
@@ -167,9 +172,7 @@ pub async fn update_tag_name(
     Path(id): Path<Uuid>,
     Json(payload): Json<TagNameRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::TagWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::TagWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let existing = state
@@ -201,9 +204,7 @@ pub async fn update_tag_description(
     Path(id): Path<Uuid>,
     Json(payload): Json<TagDescriptionRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::TagWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::TagWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let existing = state
@@ -234,11 +235,16 @@ pub async fn delete_tag(
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::TagWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::TagWrite)?;
     let actor = ApiState::actor_label(&principal);
 
+    state
+        .tags
+        .find_by_id(id)
+        .await
+        .map_err(tag_to_api)?
+        .ok_or_else(|| ApiError::not_found("tag not found"))?;
+
     state
         .tags
         .delete_tag(&actor, id)
@@ -247,3 +253,32 @@ pub async fn delete_tag(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+pub async fn assign_tag(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<TagAssignRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::TagWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    let mode = match payload.mode {
+        TagAssignMode::Add => TagAssignmentMode::Add,
+        TagAssignMode::Remove => TagAssignmentMode::Remove,
+    };
+
+    let assignment = state
+        .tags
+        .assign_tag(&actor, id, &payload.post_ids, mode)
+        .await
+        .map_err(tag_to_api)?;
+
+    Ok(Json(assignment))
+}
+
+#[derive(Debug, Serialize)]
+struct TagAggregationsResponse {
+    tag_filter_limit: i32,
+    tags: Vec<TagWithCount>,
+}
@@ -1,9 +1,11 @@
 //! Uploads handlers
 
 use axum::Json;
+use axum::extract::multipart::MultipartError;
 use axum::extract::{Extension, Multipart, Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use futures::{StreamExt, stream};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
@@ -12,9 +14,14 @@ use crate::application::pagination::{PageRequest, UploadCursor};
 use crate::application::repos::UploadQueryFilter;
 use crate::domain::api_keys::ApiScope;
 use crate::domain::entities::UploadRecord;
-
-use super::{UploadListQuery, settings_to_api, upload_storage_to_api, upload_to_api};
-use crate::infra::http::api::error::ApiError;
+use crate::domain::uploads::{SNIFF_LEN, resolve_content_type};
+use crate::infra::uploads::UploadStorageError;
+
+use super::{
+    UploadListQuery, cursor_to_api, require_scope, settings_to_api, upload_storage_to_api,
+    upload_to_api,
+};
+use crate::infra::http::api::error::{ApiError, codes};
 use crate::infra::http::api::models::UploadResponse;
 use crate::infra::http::api::state::ApiState;
 
@@ -23,34 +30,25 @@ pub async fn list_uploads(
     Extension(principal): Extension<ApiPrincipal>,
     Query(query): Query<UploadListQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::UploadRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::UploadRead)?;
     let settings = state.settings.load().await.map_err(settings_to_api)?;
     let limit = query
         .limit
         .unwrap_or_else(|| settings.admin_page_size.max(1) as u32)
         .clamp(1, 100);
 
-    let cursor = match query
+    let cursor = query
         .cursor
         .as_deref()
-        .map(UploadCursor::decode)
+        .map(|cursor| UploadCursor::decode(state.db.cursor_keys(), cursor))
         .transpose()
-    {
-        Ok(cursor) => cursor,
-        Err(err) => {
-            return Err(ApiError::bad_request(
-                "invalid cursor",
-                Some(err.to_string()),
-            ));
-        }
-    };
+        .map_err(cursor_to_api)?;
 
     let filter = UploadQueryFilter {
         content_type: query.content_type,
         month: query.month,
         search: query.search,
+        checksum: query.checksum.map(|checksum| checksum.to_ascii_lowercase()),
     };
 
     let page = state
@@ -67,9 +65,7 @@ pub async fn get_upload(
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::UploadRead)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::UploadRead)?;
 
     let upload = state.uploads.find_upload(id).await.map_err(upload_to_api)?;
 
@@ -84,56 +80,78 @@ pub async fn upload_file(
     Extension(principal): Extension<ApiPrincipal>,
     mut multipart: Multipart,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::UploadWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::UploadWrite)?;
     let actor = ApiState::actor_label(&principal);
 
-    let mut filename = None;
-    let mut content_type = None;
-    let mut data: Option<bytes::Bytes> = None;
+    let mut field = loop {
+        match multipart.next_field().await.map_err(multipart_to_api)? {
+            Some(field) if field.name() == Some("file") => break field,
+            Some(_) => continue,
+            None => return Err(ApiError::bad_request("missing file", None)),
+        }
+    };
 
-    while let Some(field) = multipart
-        .next_field()
-        .await
-        .map_err(|err| ApiError::bad_request("invalid multipart payload", Some(err.to_string())))?
-    {
-        if field.name() == Some("file") {
-            filename = field.file_name().map(|s| s.to_string());
-            content_type = field.content_type().map(|s| s.to_string());
-            data = Some(field.bytes().await.map_err(|err| {
-                ApiError::bad_request("failed to read upload", Some(err.to_string()))
-            })?);
-            break;
+    let filename = field
+        .file_name()
+        .map(|s| s.to_string())
+        .ok_or_else(|| ApiError::bad_request("missing file", None))?;
+    let declared = field.content_type().map(|s| s.to_string());
+
+    // Buffer just enough of the part to sniff its type; the rest streams
+    // straight to storage.
+    let mut head = Vec::new();
+    let mut head_len = 0;
+    while head_len < SNIFF_LEN {
+        match field.chunk().await.map_err(multipart_to_api)? {
+            Some(chunk) => {
+                head_len += chunk.len();
+                head.push(chunk);
+            }
+            None => break,
         }
     }
-
-    let filename = filename.ok_or_else(|| ApiError::bad_request("missing file", None))?;
-    let data = data.ok_or_else(|| ApiError::bad_request("missing file", None))?;
-    let content_type = content_type.unwrap_or_else(|| "application/octet-stream".to_string());
+    let sniffed: Vec<u8> = head.iter().flatten().copied().take(SNIFF_LEN).collect();
+
+    let content_type = resolve_content_type(
+        declared.as_deref(),
+        &sniffed,
+        state.upload_content_type_policy,
+    )
+    .map_err(|err| {
+        ApiError::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            codes::UPLOAD,
+            "Content type does not match file contents",
+            Some(err.to_string()),
+        )
+    })?;
+
+    let rest = field.map(|result| result.map_err(multipart_to_storage));
+    let stream = stream::iter(head.into_iter().map(Ok)).chain(rest);
 
     let stored = state
         .upload_storage
-        .store(&filename, data)
+        .store_stream(&filename, stream)
         .await
         .map_err(upload_storage_to_api)?;
 
     let record = UploadRecord {
         id: Uuid::new_v4(),
         filename: filename.clone(),
-        content_type: content_type.clone(),
+        content_type,
         size_bytes: stored.size_bytes,
         checksum: stored.checksum.clone(),
         stored_path: stored.stored_path.clone(),
         metadata: crate::domain::uploads::UploadMetadata::default(),
         created_at: OffsetDateTime::now_utc(),
+        last_verified_at: None,
+        checksum_mismatch: false,
     };
 
-    state
-        .uploads
-        .register_upload(&actor, record.clone())
-        .await
-        .map_err(upload_to_api)?;
+    if let Err(err) = state.uploads.register_upload(&actor, record.clone()).await {
+        let _ = state.upload_storage.delete(&record.stored_path).await;
+        return Err(upload_to_api(err));
+    }
 
     let response = UploadResponse {
         id: record.id,
@@ -148,14 +166,34 @@ pub async fn upload_file(
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+/// Multipart failures before the file part starts; a body over the upload
+/// limit surfaces here as a 413.
+fn multipart_to_api(err: MultipartError) -> ApiError {
+    if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        upload_storage_to_api(multipart_to_storage(err))
+    } else {
+        ApiError::bad_request("invalid multipart payload", Some(err.to_string()))
+    }
+}
+
+fn multipart_to_storage(err: MultipartError) -> UploadStorageError {
+    if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        UploadStorageError::PayloadTooLarge {
+            source: Box::new(err),
+        }
+    } else {
+        UploadStorageError::PayloadStream {
+            source: Box::new(err),
+        }
+    }
+}
+
 pub async fn delete_upload(
     State(state): State<ApiState>,
     Extension(principal): Extension<ApiPrincipal>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    principal
-        .requires(ApiScope::UploadWrite)
-        .map_err(|_| ApiError::forbidden())?;
+    require_scope(&principal, ApiScope::UploadWrite)?;
     let actor = ApiState::actor_label(&principal);
 
     let record = state
@@ -168,3 +206,20 @@ pub async fn delete_upload(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+pub async fn verify_upload(
+    State(state): State<ApiState>,
+    Extension(principal): Extension<ApiPrincipal>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_scope(&principal, ApiScope::UploadWrite)?;
+    let actor = ApiState::actor_label(&principal);
+
+    let verification = state
+        .uploads
+        .verify_upload(&actor, id, &state.upload_storage)
+        .await
+        .map_err(upload_to_api)?;
+
+    Ok(Json(verification))
+}
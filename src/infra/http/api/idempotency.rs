@@ -0,0 +1,300 @@
+//! `Idempotency-Key` support for API create endpoints.
+//!
+//! The first request under a key is run and its response stored; a retry
+//! with the same key and the same request gets the stored response back
+//! instead of creating a second resource: the same status and body, with
+//! the `Content-Type`, `Location` and `ETag` the first answer carried.
+//! Answers that may change on a retry (server errors, timeouts, rate limits
+//! and other retryable errors) are not stored, so the retry runs again.
+//! Keys are scoped to the API key that sent them and expire after
+//! [`IDEMPOTENCY_TTL`]. A request that never answers, for instance because
+//! the client went away mid-flight, holds its key for [`IDEMPOTENCY_LEASE`]
+//! at most; a retry after that runs again.
+
+use std::time::Duration;
+
+use axum::body::{Body, Bytes};
+use axum::extract::{MatchedPath, State};
+use axum::http::{
+    HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode,
+    header::{CONTENT_TYPE, ETAG, LOCATION},
+};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::application::api_keys::ApiPrincipal;
+use crate::application::repos::{IdempotencyClaim, IdempotencyRepo, StoredResponse};
+
+use super::error::{ApiError, codes};
+use super::handlers::repo_to_api;
+use super::state::ApiState;
+
+pub const IDEMPOTENCY_KEY_HEADER: HeaderName = HeaderName::from_static("idempotency-key");
+/// Set on responses replayed from an earlier request.
+pub const IDEMPOTENT_REPLAYED_HEADER: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// How long a key and its response are kept.
+pub const IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// How long a request may hold its key without answering before a retry
+/// takes the key over.
+pub const IDEMPOTENCY_LEASE: Duration = Duration::from_secs(60);
+
+/// Create routes that honour the header. Uploads are left out: their bodies
+/// are too large to buffer for a fingerprint.
+const IDEMPOTENT_ROUTES: &[&str] = &[
+    "/api/v1/posts",
+    "/api/v1/pages",
+    "/api/v1/tags",
+    "/api/v1/navigation",
+    "/api/v1/comments",
+    "/api/v1/snapshots",
+];
+
+const MAX_KEY_LEN: usize = 255;
+/// Largest request body fingerprinted; matches axum's default body limit.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+pub async fn api_idempotency(
+    State(state): State<ApiState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(key) = request.headers().get(&IDEMPOTENCY_KEY_HEADER).cloned() else {
+        return next.run(request).await;
+    };
+    let idempotent_route = request.method() == Method::POST
+        && request
+            .extensions()
+            .get::<MatchedPath>()
+            .is_some_and(|matched| IDEMPOTENT_ROUTES.contains(&matched.as_str()));
+    if !idempotent_route {
+        return next.run(request).await;
+    }
+    let Some(key) = key.to_str().ok().filter(|key| is_valid_key(key)) else {
+        return ApiError::bad_request(
+            "Invalid Idempotency-Key header",
+            Some(format!(
+                "Use up to {MAX_KEY_LEN} visible ASCII characters, such as a UUID"
+            )),
+        )
+        .into_response();
+    };
+    let key = key.to_string();
+    let Some(api_key_id) = request
+        .extensions()
+        .get::<ApiPrincipal>()
+        .map(|principal| principal.key_id)
+    else {
+        return ApiError::unauthorized().into_response();
+    };
+
+    let (parts, body) = request.into_parts();
+    let Ok(body) = axum::body::to_bytes(body, MAX_BODY_BYTES).await else {
+        return ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            codes::PAYLOAD_TOO_LARGE,
+            "Request body too large",
+            None,
+        )
+        .into_response();
+    };
+    let request_hash = fingerprint(&parts.method, parts.uri.path(), &body);
+
+    let claim = state
+        .db
+        .claim_idempotency_key(
+            api_key_id,
+            &key,
+            &request_hash,
+            IDEMPOTENCY_TTL,
+            IDEMPOTENCY_LEASE,
+        )
+        .await;
+    let token = match claim {
+        Ok(IdempotencyClaim::Claimed { token }) => token,
+        Ok(IdempotencyClaim::Existing {
+            request_hash: stored,
+            ..
+        }) if stored != request_hash => {
+            return conflict("Idempotency-Key was already used for a different request");
+        }
+        Ok(IdempotencyClaim::Existing { response: None, .. }) => {
+            return conflict("A request with this Idempotency-Key is still in progress");
+        }
+        Ok(IdempotencyClaim::Existing {
+            response: Some(stored),
+            ..
+        }) => return replay(stored),
+        Err(err) => return repo_to_api(err).into_response(),
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    store_response(&state, api_key_id, &key, token, response).await
+}
+
+/// Keep the response of a claimed request, or give the key back when the
+/// request did not reach a final result so a retry runs it again.
+async fn store_response(
+    state: &ApiState,
+    api_key_id: uuid::Uuid,
+    key: &str,
+    token: uuid::Uuid,
+    response: Response,
+) -> Response {
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(err) => {
+            warn!(
+                target = "soffio::api::idempotency",
+                error = %err,
+                "failed to buffer response; releasing idempotency key"
+            );
+            release(state, api_key_id, key, token).await;
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    if is_retryable(parts.status, &body) {
+        release(state, api_key_id, key, token).await;
+    } else {
+        let stored = StoredResponse {
+            status: parts.status.as_u16(),
+            content_type: header_text(&parts.headers, &CONTENT_TYPE),
+            location: header_text(&parts.headers, &LOCATION),
+            etag: header_text(&parts.headers, &ETAG),
+            body: body.to_vec(),
+        };
+        if let Err(err) = state
+            .db
+            .complete_idempotency_key(api_key_id, key, token, &stored)
+            .await
+        {
+            warn!(
+                target = "soffio::api::idempotency",
+                error = %err,
+                "failed to store response for idempotency key"
+            );
+            release(state, api_key_id, key, token).await;
+        }
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+async fn release(state: &ApiState, api_key_id: uuid::Uuid, key: &str, token: uuid::Uuid) {
+    if let Err(err) = state
+        .db
+        .release_idempotency_key(api_key_id, key, token)
+        .await
+    {
+        warn!(
+            target = "soffio::api::idempotency",
+            error = %err,
+            "failed to release idempotency key"
+        );
+    }
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut response = (status, Bytes::from(stored.body)).into_response();
+    let headers = response.headers_mut();
+    for (name, value) in [
+        (CONTENT_TYPE, stored.content_type),
+        (LOCATION, stored.location),
+        (ETAG, stored.etag),
+    ] {
+        if let Some(value) = value.and_then(|value| HeaderValue::from_str(&value).ok()) {
+            headers.insert(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+fn header_text(headers: &HeaderMap, name: &HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Whether `status` and `body` answer a request that may succeed if sent
+/// again, as opposed to a final result worth replaying.
+fn is_retryable(status: StatusCode, body: &[u8]) -> bool {
+    if status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+    {
+        return true;
+    }
+    status.is_client_error()
+        && serde_json::from_slice::<serde_json::Value>(body)
+            .is_ok_and(|body| body["error"]["retryable"] == true)
+}
+
+fn conflict(message: &'static str) -> Response {
+    ApiError::new(
+        StatusCode::CONFLICT,
+        codes::IDEMPOTENCY_CONFLICT,
+        message,
+        None,
+    )
+    .into_response()
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_KEY_LEN && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Hash of everything that defines the request, so a reused key with a
+/// different payload is detected.
+fn fingerprint(method: &Method, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_must_be_short_visible_ascii() {
+        assert!(is_valid_key("3f1c2a9e-8d3b-4c55-a0b1-0f6f1b9d2c7e"));
+        assert!(!is_valid_key(""));
+        assert!(!is_valid_key("has space"));
+        assert!(!is_valid_key(&"k".repeat(MAX_KEY_LEN + 1)));
+    }
+
+    #[test]
+    fn only_final_answers_are_kept() {
+        assert!(!is_retryable(StatusCode::CREATED, b"{}"));
+        assert!(!is_retryable(
+            StatusCode::CONFLICT,
+            br#"{"error":{"code":"duplicate","retryable":false}}"#
+        ));
+        assert!(is_retryable(
+            StatusCode::CONFLICT,
+            br#"{"error":{"code":"db_contention","retryable":true}}"#
+        ));
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS, b""));
+        assert!(is_retryable(StatusCode::REQUEST_TIMEOUT, b""));
+        assert!(is_retryable(StatusCode::SERVICE_UNAVAILABLE, b""));
+    }
+
+    #[test]
+    fn fingerprint_covers_path_and_body() {
+        let base = fingerprint(&Method::POST, "/api/v1/posts", b"{}");
+        assert_eq!(base, fingerprint(&Method::POST, "/api/v1/posts", b"{}"));
+        assert_ne!(base, fingerprint(&Method::POST, "/api/v1/pages", b"{}"));
+        assert_ne!(base, fingerprint(&Method::POST, "/api/v1/posts", b"{ }"));
+    }
+}
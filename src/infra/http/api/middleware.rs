@@ -1,14 +1,16 @@
 use axum::body::Body;
 use axum::extract::MatchedPath;
-use axum::extract::State;
-use axum::http::{Request, StatusCode};
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::{Request, StatusCode, header};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
+use tower::{Layer, ServiceExt};
 use tracing::warn;
 
+use crate::application::actor::{self, Actor};
 use crate::application::api_keys::ApiAuthError;
 
-use super::error::ApiError;
+use super::error::{ApiError, codes};
 use super::state::ApiState;
 
 pub async fn api_auth(
@@ -35,20 +37,31 @@ pub async fn api_auth(
             return ApiError::unauthorized().into_response();
         }
         Err(ApiAuthError::Expired) => {
-            return ApiError::new(StatusCode::UNAUTHORIZED, "expired", "API key expired", None)
-                .into_response();
+            return ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                codes::KEY_EXPIRED,
+                "API key expired",
+                None,
+            )
+            .into_response();
         }
         Err(ApiAuthError::Revoked) => {
-            return ApiError::new(StatusCode::UNAUTHORIZED, "revoked", "API key revoked", None)
-                .into_response();
+            return ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                codes::KEY_REVOKED,
+                "API key revoked",
+                None,
+            )
+            .into_response();
         }
     };
 
     let principal_clone = principal.clone();
+    let actor = Actor::from(&principal);
     request.extensions_mut().insert(principal);
     request.extensions_mut().insert(state.clone());
 
-    let mut response = next.run(request).await;
+    let mut response = actor::scope(actor, next.run(request)).await;
     // expose principal to outer middleware/logging
     response.extensions_mut().insert(principal_clone);
     response
@@ -80,7 +93,7 @@ pub async fn api_rate_limit(
 
     let key = principal.key_id.to_string();
 
-    let (allowed, remaining) = state.rate_limiter.allow(&key, &route_key);
+    let (allowed, remaining) = state.rate_limiter.allow(&key, &route_key).await;
     let retry_after = state.rate_limiter.retry_after_secs();
 
     let mut response = if allowed {
@@ -104,6 +117,43 @@ pub async fn api_rate_limit(
     response
 }
 
+/// Apply the upload limit per request, since configuration reloads change it.
+///
+/// A declared `Content-Length` over the limit is refused before the body is
+/// read; bodies without one are cut off by the multipart extractor, which the
+/// handler reports as the same 413.
+pub async fn api_upload_body_limit(
+    State(state): State<ApiState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let limit = state.upload_limit_bytes();
+    let declared = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if declared.is_some_and(|length| length > limit) {
+        return ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            codes::PAYLOAD_TOO_LARGE,
+            "Upload exceeds the configured size limit",
+            Some(format!("limit is {limit} bytes")),
+        )
+        .into_response();
+    }
+
+    let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+    match DefaultBodyLimit::max(limit)
+        .layer(next)
+        .oneshot(request)
+        .await
+    {
+        Ok(response) => response,
+        Err(never) => match never {},
+    }
+}
+
 fn extract_token(header: Option<&axum::http::HeaderValue>) -> Option<String> {
     let raw = header?.to_str().ok()?;
     let bearer = raw.strip_prefix("Bearer ")?;
@@ -1,15 +1,18 @@
 pub mod error;
+pub mod etag;
 pub mod handlers;
+pub mod idempotency;
 pub mod middleware;
 pub mod models;
 pub mod rate_limit;
 pub mod state;
+pub mod strict_json;
 
 pub use state::ApiState;
 
 use axum::{
     Router, middleware as axum_middleware,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 
 use crate::infra::http::RouterState;
@@ -18,9 +21,14 @@ use crate::infra::http::middleware::log_responses;
 pub fn build_api_router(state: RouterState) -> Router<RouterState> {
     let auth_state = state.clone();
     let rate_state = state.clone();
+    let idempotency_state = state.clone();
 
     Router::new()
         .route("/api/v1/api-keys/me", get(handlers::get_api_key_info))
+        .route(
+            "/api/v1/api-keys/rotate-all",
+            post(handlers::rotate_all_api_keys),
+        )
         .route(
             "/api/v1/posts",
             get(handlers::list_posts).post(handlers::create_post),
@@ -31,6 +39,10 @@ pub fn build_api_router(state: RouterState) -> Router<RouterState> {
                 .patch(handlers::update_post)
                 .delete(handlers::delete_post),
         )
+        .route(
+            "/api/v1/posts/{id}/duplicate",
+            post(handlers::duplicate_post),
+        )
         .route("/api/v1/posts/{id}/pin", post(handlers::update_post_pin))
         .route(
             "/api/v1/posts/{id}/title",
@@ -50,7 +62,21 @@ pub fn build_api_router(state: RouterState) -> Router<RouterState> {
             post(handlers::update_post_status),
         )
         .route("/api/v1/posts/{id}/tags", post(handlers::replace_post_tags))
+        .route(
+            "/api/v1/posts/{id}/translation",
+            post(handlers::update_post_translation),
+        )
         .route("/api/v1/posts/slug/{slug}", get(handlers::get_post))
+        .route(
+            "/api/v1/posts/months",
+            get(handlers::post_month_aggregations),
+        )
+        .route("/api/v1/posts/trash", get(handlers::list_trashed_posts))
+        .route("/api/v1/posts/trash/{id}", delete(handlers::purge_post))
+        .route(
+            "/api/v1/posts/trash/{id}/restore",
+            post(handlers::restore_post),
+        )
         .route(
             "/api/v1/pages",
             get(handlers::list_pages).post(handlers::create_page),
@@ -61,16 +87,30 @@ pub fn build_api_router(state: RouterState) -> Router<RouterState> {
                 .patch(handlers::update_page)
                 .delete(handlers::delete_page),
         )
+        .route(
+            "/api/v1/pages/{id}/duplicate",
+            post(handlers::duplicate_page),
+        )
         .route(
             "/api/v1/pages/{id}/title",
             post(handlers::update_page_title),
         )
         .route("/api/v1/pages/{id}/body", post(handlers::update_page_body))
+        .route(
+            "/api/v1/pages/{id}/translation",
+            post(handlers::update_page_translation),
+        )
         .route(
             "/api/v1/pages/{id}/status",
             post(handlers::update_page_status),
         )
         .route("/api/v1/pages/slug/{slug}", get(handlers::get_page))
+        .route("/api/v1/pages/trash", get(handlers::list_trashed_pages))
+        .route("/api/v1/pages/trash/{id}", delete(handlers::purge_page))
+        .route(
+            "/api/v1/pages/trash/{id}/restore",
+            post(handlers::restore_page),
+        )
         .route(
             "/api/v1/tags",
             get(handlers::list_tags).post(handlers::create_tag),
@@ -81,7 +121,9 @@ pub fn build_api_router(state: RouterState) -> Router<RouterState> {
                 .patch(handlers::update_tag)
                 .delete(handlers::delete_tag),
         )
+        .route("/api/v1/tags/aggregations", get(handlers::tag_aggregations))
         .route("/api/v1/tags/slug/{slug}", get(handlers::get_tag_by_slug))
+        .route("/api/v1/tags/{id}/assign", post(handlers::assign_tag))
         .route("/api/v1/tags/{id}/pin", post(handlers::update_tag_pin))
         .route("/api/v1/tags/{id}/name", post(handlers::update_tag_name))
         .route(
@@ -120,18 +162,32 @@ pub fn build_api_router(state: RouterState) -> Router<RouterState> {
         )
         .route(
             "/api/v1/uploads",
-            get(handlers::list_uploads).post(handlers::upload_file),
+            get(handlers::list_uploads)
+                .post(handlers::upload_file)
+                .layer(axum_middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware::api_upload_body_limit,
+                )),
         )
         .route(
             "/api/v1/uploads/{id}",
             get(handlers::get_upload).delete(handlers::delete_upload),
         )
+        .route("/api/v1/uploads/{id}/verify", post(handlers::verify_upload))
         .route(
             "/api/v1/site/settings",
             get(handlers::get_settings).patch(handlers::patch_settings),
         )
+        .route(
+            "/api/v1/site/settings/validate",
+            post(handlers::validate_settings),
+        )
+        .route("/api/v1/comments", post(handlers::create_comment))
+        .route("/api/v1/render/preview", post(handlers::render_preview))
         .route("/api/v1/jobs", get(handlers::list_jobs))
+        .route("/api/v1/jobs/{id}", get(handlers::get_job))
         .route("/api/v1/audit", get(handlers::list_audit_logs))
+        .route("/api/v1/schedule", get(handlers::get_schedule))
         .route(
             "/api/v1/snapshots",
             get(handlers::list_snapshots).post(handlers::create_snapshot),
@@ -142,6 +198,10 @@ pub fn build_api_router(state: RouterState) -> Router<RouterState> {
             post(handlers::rollback_snapshot),
         )
         .with_state(state)
+        .layer(axum_middleware::from_fn_with_state(
+            idempotency_state,
+            idempotency::api_idempotency,
+        ))
         .layer(axum_middleware::from_fn(log_responses))
         .layer(axum_middleware::from_fn_with_state(
             rate_state,
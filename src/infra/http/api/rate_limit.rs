@@ -1,61 +1,149 @@
+use async_trait::async_trait;
 use dashmap::DashMap;
-use std::sync::Arc;
+use metrics::counter;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
 use std::time::{Duration, Instant};
+use tracing::debug;
+
+mod postgres;
+
+pub use postgres::PostgresRateLimiter;
+
+use crate::application::jobs::{MaintenanceError, MaintenanceTask};
+use crate::infra::error::InfraError;
 
 const CLEANUP_INTERVAL_CALLS: u64 = 256;
+/// Buckets tracked by one in-memory limiter before the oldest are evicted.
+pub const DEFAULT_MAX_BUCKETS: usize = 100_000;
+/// Share of the cap evicted at once when it is hit, so a flood of new keys
+/// pays for a scan once per batch rather than once per request.
+const EVICTION_BATCH_DIVISOR: usize = 16;
+const METRIC_BUCKET_EVICTED_TOTAL: &str = "soffio_api_rate_limit_bucket_evicted_total";
 
-#[derive(Debug, Clone)]
+/// Limits requests per caller and route, either in this process or shared
+/// between nodes, so the API middleware does not depend on the backend.
+#[async_trait]
+pub trait RateLimiter: Send + Sync {
+    /// Spend one request for `key` on `route`. Returns whether it is allowed
+    /// and how many requests remain.
+    async fn allow(&self, key: &str, route: &str) -> (bool, u32);
+
+    /// Seconds a denied caller is told to wait.
+    fn retry_after_secs(&self) -> u64;
+
+    fn limit(&self) -> u32;
+
+    /// Replace the window and request limit for every bucket.
+    fn reconfigure(&self, window: Duration, max_requests: u32);
+
+    /// Drop buckets idle long enough to have refilled; returns how many.
+    async fn evict_idle(&self) -> Result<u64, InfraError>;
+}
+
+/// Tokens left and when they were last refilled, which is also the last
+/// time the bucket was used.
+#[derive(Debug, Clone, Copy)]
 struct BucketState {
     tokens: f64,
-    last_refill: Instant,
-    last_seen: Instant,
+    refilled_at: Instant,
 }
 
-#[derive(Debug, Clone)]
-pub struct ApiRateLimiter {
+#[derive(Debug, Clone, Copy)]
+struct Limits {
     window: Duration,
     max_requests: u32,
     refill_per_sec: f64,
+}
+
+impl Limits {
+    fn new(window: Duration, max_requests: u32) -> Self {
+        let window_secs = window.as_secs_f64().max(0.001);
+        Self {
+            window,
+            max_requests,
+            refill_per_sec: max_requests as f64 / window_secs,
+        }
+    }
+
+    /// How long an idle bucket is kept. An idle bucket refills completely
+    /// within one window, so evicting it then changes nothing.
+    fn stale_after(&self) -> Duration {
+        self.window
+    }
+}
+
+/// Token-bucket limiter keyed by caller and route.
+///
+/// Each bucket holds up to `max_requests` tokens and refills continuously at
+/// `max_requests / window`, so unlike a fixed window a client cannot spend a
+/// full allowance on both sides of a window boundary. Buckets idle for a
+/// whole window are evicted, and at most `max_buckets` are tracked: once the
+/// cap is hit the least recently used are dropped first, so a caller cycling
+/// through distinct keys cannot grow memory without bound. The map is
+/// sharded, so requests for different buckets rarely contend. Clones share
+/// buckets and limits, so [`ApiRateLimiter::reconfigure`] applies to every
+/// clone.
+#[derive(Debug, Clone)]
+pub struct ApiRateLimiter {
+    limits: Arc<RwLock<Limits>>,
     buckets: Arc<DashMap<String, BucketState>>,
     cleanup_tick: Arc<AtomicU64>,
     cleanup_interval_calls: u64,
+    max_buckets: usize,
 }
 
 impl ApiRateLimiter {
     pub fn new(window: Duration, max_requests: u32) -> Self {
-        let window_secs = window.as_secs_f64().max(0.001);
         Self {
-            window,
-            max_requests,
-            refill_per_sec: max_requests as f64 / window_secs,
+            limits: Arc::new(RwLock::new(Limits::new(window, max_requests))),
             buckets: Arc::new(DashMap::new()),
             cleanup_tick: Arc::new(AtomicU64::new(0)),
             cleanup_interval_calls: CLEANUP_INTERVAL_CALLS,
+            max_buckets: DEFAULT_MAX_BUCKETS,
         }
     }
 
+    /// Cap the number of tracked buckets (at least one).
+    pub fn with_max_buckets(mut self, max_buckets: usize) -> Self {
+        self.max_buckets = max_buckets.max(1);
+        self
+    }
+
+    /// Replace the window and request limit. Existing buckets keep their
+    /// tokens, capped at the new limit on their next refill.
+    pub fn reconfigure(&self, window: Duration, max_requests: u32) {
+        *self.limits.write().unwrap_or_else(PoisonError::into_inner) =
+            Limits::new(window, max_requests);
+    }
+
     pub fn allow(&self, key: &str, route: &str) -> (bool, u32) {
-        if self.max_requests == 0 {
+        self.allow_at(key, route, Instant::now())
+    }
+
+    fn allow_at(&self, key: &str, route: &str, now: Instant) -> (bool, u32) {
+        let limits = self.limits();
+        if limits.max_requests == 0 {
             return (false, 0);
         }
 
         let bucket_key = format!("{key}:{route}");
-        let now = Instant::now();
         self.maybe_cleanup(now);
+        if self.buckets.len() >= self.max_buckets && !self.buckets.contains_key(&bucket_key) {
+            self.make_room(now);
+        }
 
         let mut entry = self
             .buckets
             .entry(bucket_key)
-            .or_insert_with(|| BucketState::new(self.max_requests, now));
+            .or_insert_with(|| BucketState::new(limits.max_requests, now));
 
-        let elapsed_secs = now.duration_since(entry.last_refill).as_secs_f64();
+        let elapsed_secs = now.duration_since(entry.refilled_at).as_secs_f64();
         if elapsed_secs > 0.0 {
-            let refill = elapsed_secs * self.refill_per_sec;
-            entry.tokens = (entry.tokens + refill).min(self.max_requests as f64);
-            entry.last_refill = now;
+            let refill = elapsed_secs * limits.refill_per_sec;
+            entry.tokens = (entry.tokens + refill).min(limits.max_requests as f64);
+            entry.refilled_at = now;
         }
-        entry.last_seen = now;
 
         if entry.tokens < 1.0 {
             return (false, 0);
@@ -66,11 +154,15 @@ impl ApiRateLimiter {
     }
 
     pub fn retry_after_secs(&self) -> u64 {
-        self.window.as_secs().max(1)
+        self.limits().window.as_secs().max(1)
     }
 
     pub fn limit(&self) -> u32 {
-        self.max_requests
+        self.limits().max_requests
+    }
+
+    fn limits(&self) -> Limits {
+        *self.limits.read().unwrap_or_else(PoisonError::into_inner)
     }
 
     fn maybe_cleanup(&self, now: Instant) {
@@ -79,15 +171,95 @@ impl ApiRateLimiter {
             return;
         }
 
-        let stale_after = self.stale_after();
+        self.evict_idle_at(now);
+    }
+
+    fn evict_idle_at(&self, now: Instant) -> usize {
+        let stale_after = self.limits().stale_after();
+        let before = self.buckets.len();
         self.buckets
-            .retain(|_, state| now.duration_since(state.last_seen) < stale_after);
+            .retain(|_, state| now.duration_since(state.refilled_at) < stale_after);
+        before.saturating_sub(self.buckets.len())
     }
 
-    fn stale_after(&self) -> Duration {
-        match self.window.checked_mul(4) {
-            Some(value) => value,
-            None => self.window,
+    /// Free at least one slot under the cap: idle buckets first, then the
+    /// least recently used, a batch at a time.
+    fn make_room(&self, now: Instant) {
+        self.evict_idle_at(now);
+        let len = self.buckets.len();
+        if len < self.max_buckets {
+            return;
+        }
+
+        let batch = (len + 1 - self.max_buckets)
+            .max(self.max_buckets / EVICTION_BATCH_DIVISOR)
+            .min(len);
+        let mut by_age: Vec<(Instant, String)> = self
+            .buckets
+            .iter()
+            .map(|entry| (entry.refilled_at, entry.key().clone()))
+            .collect();
+        by_age.select_nth_unstable_by_key(batch - 1, |(refilled_at, _)| *refilled_at);
+        for (_, key) in by_age.into_iter().take(batch) {
+            self.buckets.remove(&key);
+        }
+        counter!(METRIC_BUCKET_EVICTED_TOTAL).increment(batch as u64);
+    }
+}
+
+#[async_trait]
+impl RateLimiter for ApiRateLimiter {
+    async fn allow(&self, key: &str, route: &str) -> (bool, u32) {
+        ApiRateLimiter::allow(self, key, route)
+    }
+
+    fn retry_after_secs(&self) -> u64 {
+        ApiRateLimiter::retry_after_secs(self)
+    }
+
+    fn limit(&self) -> u32 {
+        ApiRateLimiter::limit(self)
+    }
+
+    fn reconfigure(&self, window: Duration, max_requests: u32) {
+        ApiRateLimiter::reconfigure(self, window, max_requests)
+    }
+
+    async fn evict_idle(&self) -> Result<u64, InfraError> {
+        Ok(self.evict_idle_at(Instant::now()) as u64)
+    }
+}
+
+/// Maintenance step that evicts idle buckets from every limiter, instead of
+/// waiting for enough requests to trigger the in-line cleanup.
+pub struct IdleRateLimitBuckets {
+    pub limiters: Vec<Arc<dyn RateLimiter>>,
+}
+
+#[async_trait]
+impl MaintenanceTask for IdleRateLimitBuckets {
+    fn name(&self) -> &'static str {
+        "idle_rate_limit_buckets"
+    }
+
+    async fn run(&self) -> Result<u64, MaintenanceError> {
+        let mut removed = 0;
+        let mut first_error = None;
+        for limiter in &self.limiters {
+            match limiter.evict_idle().await {
+                Ok(count) => removed += count,
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        debug!(
+            target = "soffio::api::ratelimit",
+            removed, "evicted idle rate limit buckets"
+        );
+        match first_error {
+            Some(err) => Err(err.into()),
+            None => Ok(removed),
         }
     }
 }
@@ -96,8 +268,7 @@ impl BucketState {
     fn new(max_requests: u32, now: Instant) -> Self {
         Self {
             tokens: max_requests as f64,
-            last_refill: now,
-            last_seen: now,
+            refilled_at: now,
         }
     }
 }
@@ -106,6 +277,25 @@ impl BucketState {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn in_memory_limiter_honours_the_trait_contract() {
+        let limiter: Arc<dyn RateLimiter> =
+            Arc::new(ApiRateLimiter::new(Duration::from_secs(60), 3));
+        assert_eq!(limiter.limit(), 3);
+        assert_eq!(limiter.retry_after_secs(), 60);
+
+        assert_eq!(limiter.allow("key", "route").await, (true, 2));
+        assert_eq!(limiter.allow("key", "route").await, (true, 1));
+        assert_eq!(limiter.allow("key", "route").await, (true, 0));
+        assert_eq!(limiter.allow("key", "route").await, (false, 0));
+        assert_eq!(limiter.allow("key", "other").await, (true, 2));
+        assert_eq!(limiter.allow("other", "route").await, (true, 2));
+
+        limiter.reconfigure(Duration::from_secs(30), 5);
+        assert_eq!(limiter.limit(), 5);
+        assert_eq!(limiter.retry_after_secs(), 30);
+    }
+
     #[test]
     fn allow_up_to_limit_then_deny() {
         let limiter = ApiRateLimiter::new(Duration::from_secs(60), 2);
@@ -142,4 +332,110 @@ mod tests {
 
         assert_eq!(limiter.buckets.len(), 3);
     }
+
+    fn allowed_at(limiter: &ApiRateLimiter, now: Instant, attempts: u32) -> u32 {
+        (0..attempts)
+            .filter(|_| limiter.allow_at("key", "route", now).0)
+            .count() as u32
+    }
+
+    #[test]
+    fn burst_across_a_window_boundary_is_capped() {
+        let limiter = ApiRateLimiter::new(Duration::from_secs(60), 10);
+        let start = Instant::now();
+        assert_eq!(allowed_at(&limiter, start, 1), 1);
+
+        // A fixed window would allow 10 just before the seam and 10 more
+        // just after it.
+        let before_seam = allowed_at(&limiter, start + Duration::from_millis(59_900), 20);
+        let after_seam = allowed_at(&limiter, start + Duration::from_millis(60_100), 20);
+        assert_eq!(before_seam, 10);
+        assert_eq!(after_seam, 0);
+    }
+
+    #[test]
+    fn steady_state_refills_at_the_configured_rate() {
+        let limiter = ApiRateLimiter::new(Duration::from_secs(10), 10);
+        let start = Instant::now();
+        assert_eq!(allowed_at(&limiter, start, 20), 10);
+
+        assert_eq!(allowed_at(&limiter, start + Duration::from_secs(1), 5), 1);
+        assert_eq!(allowed_at(&limiter, start + Duration::from_secs(5), 10), 4);
+        for second in 6..16 {
+            assert_eq!(
+                allowed_at(&limiter, start + Duration::from_secs(second), 5),
+                1
+            );
+        }
+
+        // Refill never exceeds the bucket size, however long the key idles.
+        assert_eq!(
+            allowed_at(&limiter, start + Duration::from_secs(1_000), 20),
+            10
+        );
+    }
+
+    #[tokio::test]
+    async fn evict_idle_drops_only_stale_buckets() {
+        let limiter = ApiRateLimiter::new(Duration::from_secs(60), 3);
+        let start = Instant::now();
+        let _ = limiter.allow_at("idle", "route", start);
+        let _ = limiter.allow_at("busy", "route", start + Duration::from_secs(200));
+
+        assert_eq!(limiter.evict_idle_at(start + Duration::from_secs(230)), 1);
+        assert_eq!(limiter.buckets.len(), 1);
+        assert!(limiter.buckets.contains_key("busy:route"));
+
+        let task = IdleRateLimitBuckets {
+            limiters: vec![Arc::new(limiter.clone())],
+        };
+        assert_eq!(task.run().await.expect("sweep"), 0);
+    }
+
+    #[test]
+    fn distinct_keys_beyond_the_cap_evict_the_oldest_buckets() {
+        let limiter = ApiRateLimiter::new(Duration::from_secs(60), 2).with_max_buckets(1_000);
+        let start = Instant::now();
+        assert_eq!(allowed_at(&limiter, start, 3), 2);
+
+        for n in 0..10_000_u64 {
+            let now = start + Duration::from_millis(n);
+            let (allowed, remaining) = limiter.allow_at(&format!("scanner-{n}"), "route", now);
+            assert!(allowed);
+            assert_eq!(remaining, 1);
+            assert!(limiter.buckets.len() <= 1_000);
+            if n % 250 == 0 {
+                // A bucket in active use survives the flood and stays limited.
+                assert!(!limiter.allow_at("key", "route", now).0);
+            }
+        }
+
+        let end = start + Duration::from_millis(10_000);
+        assert!(!limiter.buckets.contains_key("scanner-0:route"));
+        assert!(limiter.buckets.contains_key("scanner-9999:route"));
+        assert_eq!(
+            allowed_at_key(&limiter, "scanner-9999", end, 3),
+            1,
+            "the last scanner key keeps its spent token"
+        );
+    }
+
+    fn allowed_at_key(limiter: &ApiRateLimiter, key: &str, now: Instant, attempts: u32) -> u32 {
+        (0..attempts)
+            .filter(|_| limiter.allow_at(key, "route", now).0)
+            .count() as u32
+    }
+
+    #[test]
+    fn reconfigure_applies_to_clones() {
+        let limiter = ApiRateLimiter::new(Duration::from_secs(60), 1);
+        let shared = limiter.clone();
+        assert!(shared.allow("key", "route").0);
+        assert!(!shared.allow("key", "route").0);
+
+        limiter.reconfigure(Duration::from_secs(30), 5);
+        assert_eq!(shared.limit(), 5);
+        assert_eq!(shared.retry_after_secs(), 30);
+        assert!(shared.allow("other", "route").0);
+    }
 }
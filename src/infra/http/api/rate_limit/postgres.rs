@@ -0,0 +1,144 @@
+//! Rate limit buckets kept in Postgres, so every node sharing the database
+//! enforces one limit.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use tracing::warn;
+
+use super::{CLEANUP_INTERVAL_CALLS, Limits, RateLimiter};
+use crate::infra::error::InfraError;
+
+/// Token-bucket limiter with the same behaviour as
+/// [`ApiRateLimiter`](super::ApiRateLimiter), shared through the
+/// `rate_limit_buckets` table. Bucket refills use the database clock, so
+/// nodes with skewed clocks still agree.
+///
+/// A request is allowed when the database is unreachable: the API handlers
+/// would fail anyway, and a limiter outage should not lock out every key.
+#[derive(Debug)]
+pub struct PostgresRateLimiter {
+    pool: PgPool,
+    limits: RwLock<Limits>,
+    cleanup_tick: AtomicU64,
+}
+
+impl PostgresRateLimiter {
+    pub fn new(pool: PgPool, window: Duration, max_requests: u32) -> Self {
+        Self {
+            pool,
+            limits: RwLock::new(Limits::new(window, max_requests)),
+            cleanup_tick: AtomicU64::new(0),
+        }
+    }
+
+    fn limits(&self) -> Limits {
+        *self.limits.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn maybe_cleanup(&self, limits: &Limits) {
+        let tick = self.cleanup_tick.fetch_add(1, Ordering::Relaxed) + 1;
+        if !tick.is_multiple_of(CLEANUP_INTERVAL_CALLS) {
+            return;
+        }
+
+        if let Err(err) = self.delete_idle(limits).await {
+            warn!(
+                target = "soffio::api::ratelimit",
+                error = %err,
+                "failed to evict idle rate limit buckets"
+            );
+        }
+    }
+
+    async fn delete_idle(&self, limits: &Limits) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM rate_limit_buckets
+            WHERE refilled_at < clock_timestamp() - make_interval(secs => $1::float8)
+            "#,
+            limits.stale_after().as_secs_f64()
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl RateLimiter for PostgresRateLimiter {
+    async fn allow(&self, key: &str, route: &str) -> (bool, u32) {
+        let limits = self.limits();
+        if limits.max_requests == 0 {
+            return (false, 0);
+        }
+        self.maybe_cleanup(&limits).await;
+
+        // Refill and spend one token in a single statement. The row lock taken
+        // by `ON CONFLICT DO UPDATE` serialises concurrent requests for a
+        // bucket, and the `WHERE` clause leaves an empty bucket untouched, so a
+        // denied request consumes nothing and returns no row. The refill is
+        // clamped at zero in case the clock reading precedes the last refill.
+        let tokens = sqlx::query_scalar!(
+            r#"
+            INSERT INTO rate_limit_buckets AS bucket (bucket_key, tokens, refilled_at)
+            VALUES ($1::text, $2::float8 - 1, clock_timestamp())
+            ON CONFLICT (bucket_key) DO UPDATE SET
+                tokens = LEAST(
+                    $2::float8,
+                    bucket.tokens + GREATEST(
+                        EXTRACT(EPOCH FROM clock_timestamp() - bucket.refilled_at)::float8, 0
+                    ) * $3::float8
+                ) - 1,
+                refilled_at = clock_timestamp()
+            WHERE LEAST(
+                $2::float8,
+                bucket.tokens + GREATEST(
+                    EXTRACT(EPOCH FROM clock_timestamp() - bucket.refilled_at)::float8, 0
+                ) * $3::float8
+            ) >= 1
+            RETURNING tokens
+            "#,
+            format!("{key}:{route}"),
+            f64::from(limits.max_requests),
+            limits.refill_per_sec
+        )
+        .fetch_optional(&self.pool)
+        .await;
+
+        match tokens {
+            Ok(Some(tokens)) => (true, tokens.max(0.0).floor() as u32),
+            Ok(None) => (false, 0),
+            Err(err) => {
+                warn!(
+                    target = "soffio::api::ratelimit",
+                    error = %err,
+                    "rate limit backend unavailable; allowing request"
+                );
+                (true, 0)
+            }
+        }
+    }
+
+    fn retry_after_secs(&self) -> u64 {
+        self.limits().window.as_secs().max(1)
+    }
+
+    fn limit(&self) -> u32 {
+        self.limits().max_requests
+    }
+
+    fn reconfigure(&self, window: Duration, max_requests: u32) {
+        *self.limits.write().unwrap_or_else(PoisonError::into_inner) =
+            Limits::new(window, max_requests);
+    }
+
+    async fn evict_idle(&self) -> Result<u64, InfraError> {
+        self.delete_idle(&self.limits())
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))
+    }
+}
@@ -1,25 +1,32 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
+use crate::application::actor::Actor;
 use crate::application::admin::audit::AdminAuditService;
+use crate::application::admin::comments::AdminCommentService;
 use crate::application::admin::jobs::AdminJobService;
 use crate::application::admin::navigation::AdminNavigationService;
 use crate::application::admin::pages::AdminPageService;
 use crate::application::admin::posts::AdminPostService;
+use crate::application::admin::schedule::AdminScheduleService;
 use crate::application::admin::settings::AdminSettingsService;
 use crate::application::admin::snapshots::AdminSnapshotService;
 use crate::application::admin::tags::AdminTagService;
 use crate::application::admin::uploads::AdminUploadService;
 use crate::application::api_keys::{ApiKeyService, ApiPrincipal};
+use crate::application::render::RenderPreviewService;
+use crate::domain::uploads::ContentTypePolicy;
 use crate::infra::db::PostgresRepositories;
 use crate::infra::uploads::UploadStorage;
 
-use super::rate_limit::ApiRateLimiter;
+use super::rate_limit::{ApiRateLimiter, RateLimiter};
 
 #[derive(Clone)]
 pub struct ApiState {
     pub api_keys: Arc<ApiKeyService>,
     pub posts: Arc<AdminPostService>,
     pub pages: Arc<AdminPageService>,
+    pub schedule: Arc<AdminScheduleService>,
     pub tags: Arc<AdminTagService>,
     pub navigation: Arc<AdminNavigationService>,
     pub uploads: Arc<AdminUploadService>,
@@ -27,13 +34,29 @@ pub struct ApiState {
     pub jobs: Arc<AdminJobService>,
     pub audit: Arc<AdminAuditService>,
     pub snapshots: Arc<AdminSnapshotService>,
+    pub comments: Arc<AdminCommentService>,
     pub db: Arc<PostgresRepositories>,
     pub upload_storage: Arc<UploadStorage>,
-    pub rate_limiter: Arc<ApiRateLimiter>,
+    /// Largest accepted upload request, shared with the admin listener.
+    pub upload_limit: Arc<AtomicU64>,
+    pub upload_content_type_policy: ContentTypePolicy,
+    /// Per-key limiter for the API, local or shared between nodes.
+    pub rate_limiter: Arc<dyn RateLimiter>,
+    /// Per-client-IP limiter for the comment ingest endpoint.
+    pub comment_rate_limiter: Arc<ApiRateLimiter>,
+    pub render_preview: Arc<RenderPreviewService>,
+    /// Per-caller limiter for render previews, shared with the admin listener.
+    pub render_preview_rate_limiter: Arc<ApiRateLimiter>,
 }
 
 impl ApiState {
+    /// Audit actor name for writes made with `principal`:
+    /// `api-key:{prefix}:{name}`.
     pub fn actor_label(principal: &ApiPrincipal) -> String {
-        format!("api_key:{}:{}", principal.prefix, principal.name)
+        Actor::from(principal).name
+    }
+
+    pub fn upload_limit_bytes(&self) -> u64 {
+        self.upload_limit.load(Ordering::Relaxed)
     }
 }
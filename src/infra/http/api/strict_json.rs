@@ -0,0 +1,123 @@
+//! JSON request bodies that reject fields the target type does not declare.
+//!
+//! serde skips unknown fields by default, so a misspelt field in a partial
+//! update is dropped and the request still succeeds. [`StrictJson`] answers
+//! 400 instead and names every field it did not recognise.
+
+use axum::Json;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+
+use super::error::{ApiError, ApiFieldError, codes};
+
+/// Like [`axum::Json`], but unknown top-level fields are an error.
+#[derive(Debug, Clone)]
+pub struct StrictJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for StrictJson<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<serde_json::Value>::from_request(req, state)
+            .await
+            .map_err(|rejection| {
+                let status = rejection.status();
+                let code = if status == StatusCode::PAYLOAD_TOO_LARGE {
+                    codes::PAYLOAD_TOO_LARGE
+                } else {
+                    codes::BAD_REQUEST
+                };
+                ApiError::new(
+                    status,
+                    code,
+                    "Invalid JSON body",
+                    Some(rejection.body_text()),
+                )
+            })?;
+
+        let unknown = unknown_fields::<T>(&value);
+        if !unknown.is_empty() {
+            let hint = format!("unknown fields: {}", unknown.join(", "));
+            let fields = unknown
+                .into_iter()
+                .map(|field| ApiFieldError {
+                    field,
+                    message: "unknown field".to_string(),
+                })
+                .collect();
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                codes::INVALID_INPUT,
+                "Unknown fields in request body",
+                Some(hint),
+            )
+            .with_fields(fields));
+        }
+
+        serde_json::from_value(value)
+            .map(StrictJson)
+            .map_err(|err| {
+                ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    codes::INVALID_INPUT,
+                    "Invalid request body",
+                    Some(err.to_string()),
+                )
+            })
+    }
+}
+
+/// Top-level keys of `value` that `T` does not declare, in key order.
+///
+/// Empty when `value` is not an object or `T` is not a plain struct; the
+/// normal deserialization reports those.
+fn unknown_fields<T: DeserializeOwned>(value: &serde_json::Value) -> Vec<String> {
+    let (Some(object), Some(known)) = (value.as_object(), struct_fields::<T>()) else {
+        return Vec::new();
+    };
+    object
+        .keys()
+        .filter(|key| !known.contains(&key.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Field names a derived `Deserialize` struct asks for.
+fn struct_fields<T: DeserializeOwned>() -> Option<&'static [&'static str]> {
+    let mut fields = None;
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// Deserializer that records the field list of the struct asked of it and
+/// then fails.
+struct FieldNames<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = Some(fields);
+        Err(de::Error::custom("field names recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
@@ -0,0 +1,96 @@
+//! Serving the public listener under `server.base_path`.
+//!
+//! Routers are built for the root; when a base path is configured the whole
+//! listener is wrapped so requests under the prefix are routed with it
+//! stripped, and anything outside it answers 404.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+
+use crate::domain::routes::BasePath;
+
+use super::middleware::with_path;
+
+/// Mount `router` under `base_path`; the root mount returns it unchanged.
+pub fn mount_at_base_path(router: Router, base_path: &BasePath) -> Router {
+    if base_path.is_root() {
+        return router;
+    }
+    Router::new()
+        .fallback_service(router)
+        .layer(middleware::from_fn_with_state(
+            base_path.clone(),
+            strip_base_path,
+        ))
+}
+
+async fn strip_base_path(
+    State(base_path): State<BasePath>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(uri) = base_path
+        .strip(request.uri().path())
+        .and_then(|path| with_path(request.uri(), path))
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    *request.uri_mut() = uri;
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn app(base_path: &str) -> Router {
+        let router = Router::new()
+            .route("/", get(|| async { "home" }))
+            .route("/posts/{slug}", get(|| async { "post" }));
+        mount_at_base_path(router, &BasePath::parse(base_path).expect("base path"))
+    }
+
+    async fn status_of(app: Router, uri: &str) -> StatusCode {
+        let request = Request::builder()
+            .uri(uri)
+            .body(Body::empty())
+            .expect("request");
+        app.oneshot(request).await.expect("response").status()
+    }
+
+    #[tokio::test]
+    async fn routes_only_under_the_prefix() {
+        assert_eq!(status_of(app("/blog"), "/blog").await, StatusCode::OK);
+        assert_eq!(status_of(app("/blog"), "/blog/").await, StatusCode::OK);
+        assert_eq!(
+            status_of(app("/blog"), "/blog/posts/a?lang=fr").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status_of(app("/blog"), "/posts/a").await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status_of(app("/blog"), "/blogroll").await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn root_mount_is_unchanged() {
+        assert_eq!(status_of(app(""), "/posts/a").await, StatusCode::OK);
+        assert_eq!(
+            status_of(app(""), "/blog/posts/a").await,
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Client addresses for requests that arrive through reverse proxies.
+//!
+//! Forwarding headers are only read when the connecting peer is one of the
+//! configured `server.trusted_proxies`; anyone else could write them.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, Request, header::FORWARDED},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::config::IpCidr;
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Address of the client that sent a request, after forwarding headers from
+/// trusted proxies have been applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// Reverse proxies whose forwarding headers are believed.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Arc<[IpCidr]>);
+
+impl TrustedProxies {
+    pub fn new(blocks: Vec<IpCidr>) -> Self {
+        Self(blocks.into())
+    }
+
+    fn trusts(&self, addr: IpAddr) -> bool {
+        self.0.iter().any(|block| block.contains(addr))
+    }
+
+    /// Client address of a request received from `peer`.
+    ///
+    /// Hops are walked from the right, since each proxy appends the address
+    /// it received the request from; the first hop that is not a trusted
+    /// proxy is the client. An unparseable hop stops the walk at the last
+    /// trusted address, so nothing to its left is believed.
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        if !self.trusts(peer) {
+            return peer;
+        }
+        let mut client = peer;
+        for hop in forwarded_hops(headers).into_iter().rev() {
+            let Some(hop) = hop else {
+                break;
+            };
+            client = hop;
+            if !self.trusts(hop) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+/// Record the [`ClientIp`] of TCP requests. Must wrap the routers, outside
+/// [`set_request_context`](super::middleware::set_request_context).
+pub async fn resolve_client_ip(
+    State(proxies): State<TrustedProxies>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    if let Some(ConnectInfo(peer)) = request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        let client = proxies.client_ip(peer.ip(), request.headers());
+        request.extensions_mut().insert(ClientIp(client));
+    }
+    next.run(request).await
+}
+
+/// Hops listed by `Forwarded` (preferred) or `X-Forwarded-For`, leftmost
+/// first. `None` marks a hop without a usable address, such as `unknown` or
+/// an obfuscated identifier.
+fn forwarded_hops(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    let forwarded = list_elements(headers, FORWARDED.as_str());
+    if !forwarded.is_empty() {
+        return forwarded
+            .iter()
+            .map(|element| forwarded_for(element).and_then(parse_node))
+            .collect();
+    }
+    list_elements(headers, X_FORWARDED_FOR)
+        .iter()
+        .map(|element| parse_node(element))
+        .collect()
+}
+
+/// Comma-separated elements across every instance of header `name`, in order.
+fn list_elements(headers: &HeaderMap, name: &str) -> Vec<String> {
+    headers
+        .get_all(name)
+        .iter()
+        .map(|value| value.to_str().unwrap_or(""))
+        .flat_map(|value| value.split(','))
+        .map(|element| element.trim().to_string())
+        .filter(|element| !element.is_empty())
+        .collect()
+}
+
+/// The `for=` parameter of one `Forwarded` element (RFC 7239).
+fn forwarded_for(element: &str) -> Option<&str> {
+    element.split(';').find_map(|pair| {
+        let (name, value) = pair.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("for")
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// An address as proxies write it: bare, with a port, or bracketed IPv6.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim();
+    if let Ok(addr) = node.parse::<IpAddr>() {
+        return Some(addr);
+    }
+    if let Ok(addr) = node.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    node.strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .and_then(|(addr, _)| addr.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn proxies(blocks: &[(&str, u8)]) -> TrustedProxies {
+        TrustedProxies::new(
+            blocks
+                .iter()
+                .map(|(addr, len)| IpCidr::new(addr.parse().expect("addr"), *len).expect("cidr"))
+                .collect(),
+        )
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().expect("ip")
+    }
+
+    #[test]
+    fn untrusted_peers_cannot_spoof_their_address() {
+        let trusted = proxies(&[("10.0.0.0", 8)]);
+        let spoofed = headers(&[
+            ("x-forwarded-for", "198.51.100.1"),
+            ("forwarded", "for=198.51.100.2"),
+        ]);
+
+        assert_eq!(
+            trusted.client_ip(ip("203.0.113.9"), &spoofed),
+            ip("203.0.113.9")
+        );
+        assert_eq!(
+            TrustedProxies::default().client_ip(ip("10.0.0.1"), &spoofed),
+            ip("10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn chained_proxies_yield_the_rightmost_untrusted_hop() {
+        let trusted = proxies(&[("10.0.0.0", 8), ("127.0.0.1", 32)]);
+        // The client forged the leftmost entry; 203.0.113.5 reached the
+        // outer proxy, which forwarded through 10.0.0.2.
+        let chained = headers(&[
+            ("x-forwarded-for", "198.51.100.1, 203.0.113.5"),
+            ("x-forwarded-for", "10.0.0.2"),
+        ]);
+
+        assert_eq!(
+            trusted.client_ip(ip("127.0.0.1"), &chained),
+            ip("203.0.113.5")
+        );
+    }
+
+    #[test]
+    fn forwarded_header_wins_and_accepts_ports_and_ipv6() {
+        let trusted = proxies(&[("10.0.0.0", 8)]);
+        let both = headers(&[
+            ("x-forwarded-for", "198.51.100.1"),
+            (
+                "forwarded",
+                "for=\"[2001:db8::7]:4711\";proto=https, For=\"10.1.2.3:80\"",
+            ),
+        ]);
+
+        assert_eq!(trusted.client_ip(ip("10.0.0.1"), &both), ip("2001:db8::7"));
+    }
+
+    #[test]
+    fn unusable_hops_stop_at_the_last_trusted_address() {
+        let trusted = proxies(&[("10.0.0.0", 8)]);
+
+        let unknown = headers(&[("forwarded", "for=198.51.100.1, for=unknown, for=10.0.0.3")]);
+        assert_eq!(trusted.client_ip(ip("10.0.0.1"), &unknown), ip("10.0.0.3"));
+
+        let garbage = headers(&[("x-forwarded-for", "198.51.100.1, not-an-ip")]);
+        assert_eq!(trusted.client_ip(ip("10.0.0.1"), &garbage), ip("10.0.0.1"));
+
+        let all_trusted = headers(&[("x-forwarded-for", "10.0.0.9, 10.0.0.8")]);
+        assert_eq!(
+            trusted.client_ip(ip("10.0.0.1"), &all_trusted),
+            ip("10.0.0.9")
+        );
+    }
+
+    #[test]
+    fn ipv4_mapped_peers_match_ipv4_blocks() {
+        let trusted = proxies(&[("127.0.0.1", 32)]);
+        let forwarded = headers(&[("x-forwarded-for", "203.0.113.5")]);
+
+        assert_eq!(
+            trusted.client_ip(ip("::ffff:127.0.0.1"), &forwarded),
+            ip("203.0.113.5")
+        );
+    }
+}
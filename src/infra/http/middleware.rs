@@ -1,28 +1,100 @@
+use std::net::{IpAddr, SocketAddr};
 use std::time::Instant;
 
-use axum::{body::Body, http::Request, middleware::Next, response::Response};
-use tracing::{error, warn};
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{HeaderName, HeaderValue, Request, Uri},
+    middleware::Next,
+    response::Response,
+};
+use tracing::{Instrument, error, info_span, warn};
 use uuid::Uuid;
 
-use crate::{application::api_keys::ApiPrincipal, application::error::ErrorReport};
+use super::client_ip::ClientIp;
+use crate::{
+    application::api_keys::ApiPrincipal, application::error::ErrorReport, application::request_id,
+};
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+const MAX_REQUEST_ID_LEN: usize = 128;
 
 #[derive(Clone)]
 pub struct RequestContext {
     pub request_id: String,
+    /// `None` for requests that arrive over a unix socket.
+    pub client_ip: Option<IpAddr>,
 }
 
 pub async fn set_request_context(mut request: Request<Body>, next: Next) -> Response {
-    let request_id = Uuid::new_v4().to_string();
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let client_ip = client_ip(&request);
+    if let Some(ip) = client_ip {
+        request.extensions_mut().insert(ClientIp(ip));
+    }
     let ctx = RequestContext {
         request_id: request_id.clone(),
+        client_ip,
     };
     request.extensions_mut().insert(ctx.clone());
 
-    let mut response = next.run(request).await;
+    let span = info_span!(
+        "request",
+        request_id = %request_id,
+        client_ip = client_ip.map(tracing::field::display),
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    let mut response =
+        request_id::scope(request_id.clone(), next.run(request).instrument(span)).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
     response.extensions_mut().insert(ctx);
     response
 }
 
+/// The address resolved from trusted proxy headers, else the TCP peer.
+fn client_ip(request: &Request<Body>) -> Option<IpAddr> {
+    let extensions = request.extensions();
+    extensions
+        .get::<ClientIp>()
+        .map(|ClientIp(ip)| *ip)
+        .or_else(|| {
+            extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(peer)| peer.ip())
+        })
+}
+
+/// Incoming ids are honoured only when short and made of token characters,
+/// so they are safe to echo back and to write into logs.
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.' | ':'))
+}
+
+/// `uri` with its path replaced by `path`, keeping the query string.
+pub(crate) fn with_path(uri: &Uri, path: &str) -> Option<Uri> {
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(path_and_query.parse().ok()?);
+    Uri::from_parts(parts).ok()
+}
+
 pub async fn log_responses(request: Request<Body>, next: Next) -> Response {
     let method = request.method().clone();
     let uri = request.uri().clone();
@@ -1,12 +1,20 @@
 mod admin;
 pub mod api;
-mod middleware;
+mod base_path;
+pub mod client_ip;
+pub mod middleware;
 pub(crate) mod public;
+pub mod security_headers;
+mod static_export;
 
 pub use admin::{AdminState, build_admin_router};
-pub use api::rate_limit::ApiRateLimiter;
+pub use api::rate_limit::{ApiRateLimiter, IdleRateLimitBuckets, PostgresRateLimiter, RateLimiter};
 pub use api::{ApiState, build_api_router as build_api_v1_router};
+pub use base_path::mount_at_base_path;
+pub use client_ip::{ClientIp, TrustedProxies, resolve_client_ip};
 pub use public::{HttpState, build_router};
+pub use security_headers::{ContentFeatures, SecurityHeaders, apply_security_headers};
+pub use static_export::{StaticExportReport, export_static_site};
 
 use crate::application::error::ErrorReport;
 use crate::application::error::HttpError;
@@ -67,6 +75,18 @@ pub fn repo_error_to_http(source: &'static str, err: RepoError) -> HttpError {
             "Database timeout",
             "Database timeout",
         ),
+        RepoError::Contention => HttpError::new(
+            source,
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Database contention",
+            "The transaction conflicted with a concurrent write",
+        ),
+        RepoError::Stale => HttpError::new(
+            source,
+            StatusCode::PRECONDITION_FAILED,
+            "Stale update",
+            "The record was modified since it was read",
+        ),
         RepoError::Persistence(message) => HttpError::new(
             source,
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -93,3 +113,18 @@ impl FromRef<RouterState> for ApiState {
         state.api.clone()
     }
 }
+
+/// Public site plus the headless API, as served on the public listener,
+/// mounted under the configured base path.
+pub fn build_public_router(http_state: HttpState, api_state: ApiState) -> axum::Router {
+    let base_path = http_state.base_path.clone();
+    let router_state = RouterState {
+        http: http_state,
+        api: api_state,
+    };
+    let public_router = build_router(router_state.clone());
+    let api_router = build_api_v1_router(router_state.clone());
+
+    let router = public_router.merge(api_router).with_state(router_state);
+    mount_at_base_path(router, &base_path)
+}
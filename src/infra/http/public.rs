@@ -8,18 +8,23 @@ use crate::{
         snapshot_preview::SnapshotPreviewService, syndication::SyndicationService,
     },
     cache::{CacheState, response_cache_layer},
+    config::TrailingSlashPolicy,
+    domain::routes::BasePath,
     infra::{db::PostgresRepositories, uploads::UploadStorage},
 };
 
 use super::{
     RouterState,
     middleware::{log_responses, set_request_context},
+    security_headers::{SecurityHeaders, apply_security_headers},
 };
 
 #[path = "public/assets.rs"]
 mod assets;
 #[path = "public/feed.rs"]
 mod feed;
+#[path = "public/maintenance.rs"]
+mod maintenance;
 #[path = "public/meta.rs"]
 mod meta;
 #[path = "public/pages.rs"]
@@ -28,12 +33,22 @@ mod pages;
 mod previews;
 #[path = "public/syndication.rs"]
 mod syndication;
+#[path = "public/trailing_slash.rs"]
+mod trailing_slash;
 
-use assets::{favicon, public_health, serve_upload};
-use feed::{index, month_index, post_detail, posts_partial, tag_index};
+use assets::{apple_touch_icon, favicon, favicon_svg, public_health, readiness, serve_upload};
+use feed::{
+    index, month_archive, month_index, post_detail, posts_index, posts_partial, tag_index,
+    year_archive,
+};
+use maintenance::maintenance_gate;
 use pages::fallback_router;
-use previews::{page_preview, page_snapshot_preview, post_preview, post_snapshot_preview};
+use previews::{
+    page_preview, page_snapshot_preview, post_preview, post_snapshot_preview, read_from_primary,
+    shared_snapshot_preview,
+};
 use syndication::{atom_feed, robots_txt, rss_feed, sitemap};
+use trailing_slash::canonical_trailing_slash;
 
 #[derive(Clone)]
 pub struct HttpState {
@@ -46,6 +61,9 @@ pub struct HttpState {
     pub upload_storage: Arc<UploadStorage>,
     pub snapshot_preview: Arc<SnapshotPreviewService>,
     pub cache: Option<CacheState>,
+    pub trailing_slash: TrailingSlashPolicy,
+    pub base_path: BasePath,
+    pub security_headers: SecurityHeaders,
 }
 
 pub fn build_router(state: RouterState) -> Router<RouterState> {
@@ -53,14 +71,19 @@ pub fn build_router(state: RouterState) -> Router<RouterState> {
     // Middleware skips datastar-request headers, so streaming requests are not cached
     let cached_routes = Router::new()
         .route("/", get(index))
+        .route("/posts", get(posts_index))
         .route("/tags/{tag}", get(tag_index))
         .route("/months/{month}", get(month_index))
+        .route("/archive/{year}", get(year_archive))
+        .route("/archive/{year}/{month}", get(month_archive))
         .route("/posts/{slug}", get(post_detail))
         .route("/ui/posts", get(posts_partial))
         .route("/sitemap.xml", get(sitemap))
         .route("/rss.xml", get(rss_feed))
         .route("/atom.xml", get(atom_feed))
         .route("/favicon.ico", get(favicon))
+        .route("/favicon.svg", get(favicon_svg))
+        .route("/apple-touch-icon.png", get(apple_touch_icon))
         .fallback(fallback_router);
 
     // Apply L1 cache layer conditionally
@@ -73,13 +96,18 @@ pub fn build_router(state: RouterState) -> Router<RouterState> {
         cached_routes
     };
 
-    // Routes that should NOT be cached (previews, health, static assets)
-    let static_routes = Router::new()
+    let preview_routes = Router::new()
         .route("/posts/_preview/{id}", get(post_preview))
         .route("/pages/_preview/{id}", get(page_preview))
         .route("/posts/_preview/snapshot/{id}", get(post_snapshot_preview))
         .route("/pages/_preview/snapshot/{id}", get(page_snapshot_preview))
+        .route("/preview/snapshots/{id}", get(shared_snapshot_preview))
+        .route_layer(middleware::from_fn(read_from_primary));
+
+    // Routes that should NOT be cached (previews, health, static assets)
+    let static_routes = preview_routes
         .route("/_health/db", get(public_health))
+        .route("/readyz", get(readiness))
         .route("/robots.txt", get(robots_txt))
         .route("/uploads/{*path}", get(serve_upload))
         .route(
@@ -91,9 +119,72 @@ pub fn build_router(state: RouterState) -> Router<RouterState> {
             get(crate::infra::assets::serve_common),
         );
 
-    cached_routes
+    // The maintenance gate wraps the cache layer so cached pages are not
+    // served while the site is down; the API router is merged in separately.
+    let maintenance_state = state.http.clone();
+    let trailing_slash = (state.http.trailing_slash, state.http.base_path.clone());
+    let security_headers = state.http.security_headers.clone();
+    let site: Router = cached_routes
         .merge(static_routes)
-        .with_state(state)
+        .layer(middleware::from_fn_with_state(
+            maintenance_state,
+            maintenance_gate,
+        ))
+        .with_state(state);
+
+    // Route layers run after matching, so the slash policy wraps the site as
+    // a fallback service: it must redirect or rewrite before routing and
+    // before the cache sees the path.
+    Router::new()
+        .fallback_service(site)
+        .layer(middleware::from_fn_with_state(
+            trailing_slash,
+            canonical_trailing_slash,
+        ))
+        .layer(middleware::from_fn_with_state(
+            security_headers,
+            apply_security_headers,
+        ))
         .layer(middleware::from_fn(log_responses))
         .layer(middleware::from_fn(set_request_context))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::routes::{MERGED_ROUTE_PREFIXES, PUBLIC_ROUTES};
+
+    /// Paths passed to `.route(` in a router source file.
+    fn registered_paths(source: &str) -> Vec<&str> {
+        source
+            .split(".route(")
+            .skip(1)
+            .filter_map(|rest| rest.trim_start().strip_prefix('"'))
+            .filter_map(|rest| rest.split('"').next())
+            .filter(|path| path.starts_with('/'))
+            .collect()
+    }
+
+    #[test]
+    fn route_table_lists_every_public_route() {
+        let paths = registered_paths(include_str!("public.rs"));
+        assert!(!paths.is_empty());
+        for path in paths {
+            assert!(
+                PUBLIC_ROUTES.contains(&path),
+                "add `{path}` to domain::routes::PUBLIC_ROUTES so pages cannot shadow it"
+            );
+        }
+    }
+
+    #[test]
+    fn api_routes_sit_under_a_merged_prefix() {
+        for path in registered_paths(include_str!("api/mod.rs")) {
+            assert!(
+                MERGED_ROUTE_PREFIXES
+                    .iter()
+                    .any(|prefix| path.starts_with(prefix)),
+                "`{path}` is outside domain::routes::MERGED_ROUTE_PREFIXES"
+            );
+        }
+    }
+}
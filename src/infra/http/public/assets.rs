@@ -7,14 +7,22 @@ use axum::{
         HeaderValue, StatusCode,
         header::{CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE},
     },
-    response::{IntoResponse, Response},
+    response::{IntoResponse, Json, Response},
 };
 use bytes::Bytes;
-use tracing::error;
+use serde_json::json;
+use tracing::{error, warn};
 
 use crate::{
-    application::{error::HttpError, repos::SettingsRepo},
-    infra::uploads::UploadStorageError,
+    application::{
+        error::HttpError,
+        repos::{RepoError, SettingsRepo, UploadsRepo},
+    },
+    domain::entities::SiteSettingsRecord,
+    infra::{
+        doctor::{self, CheckLevel},
+        uploads::UploadStorageError,
+    },
 };
 
 use super::HttpState;
@@ -63,30 +71,137 @@ pub(super) async fn public_health(State(state): State<HttpState>) -> Response {
     super::super::db_health_response(state.db.health_check().await)
 }
 
+/// Readiness runs the `soffio doctor` checks that matter while serving and
+/// reports only their names and levels; details stay in `soffio doctor`.
+pub(super) async fn readiness(State(state): State<HttpState>) -> Response {
+    let pool = state.db.pool();
+    let checks = [
+        doctor::check_database(pool).await,
+        doctor::check_migrations(pool).await,
+        doctor::check_uploads(state.upload_storage.root()).await,
+    ];
+    let level = doctor::worst_level(&checks);
+    let status = if level == CheckLevel::Fail {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    let checks: Vec<_> = checks
+        .iter()
+        .map(|check| json!({ "name": check.name, "level": check.level }))
+        .collect();
+    (status, Json(json!({ "level": level, "checks": checks }))).into_response()
+}
+
+/// `/favicon.ico`: the PNG or ICO upload picked in settings, or the SVG
+/// favicon when none is set or it cannot be read.
 pub(super) async fn favicon(State(state): State<HttpState>) -> Response {
     crate::cache::deps::record(crate::cache::EntityKey::SiteSettings);
 
+    let settings = match state.db.load_site_settings().await {
+        Ok(settings) => settings,
+        Err(err) => return favicon_unavailable(&err),
+    };
+    match raster_favicon(&state, &settings).await {
+        Some((content_type, bytes)) => icon_response(&content_type, Body::from(bytes)),
+        None => svg_favicon_response(settings),
+    }
+}
+
+/// `/favicon.svg`: always the inline SVG favicon.
+pub(super) async fn favicon_svg(State(state): State<HttpState>) -> Response {
+    crate::cache::deps::record(crate::cache::EntityKey::SiteSettings);
+
     match state.db.load_site_settings().await {
-        Ok(settings) => Response::builder()
-            .status(StatusCode::OK)
-            .header(CONTENT_TYPE, "image/svg+xml; charset=utf-8")
-            .header(CACHE_CONTROL, "public, max-age=3600")
-            .body(Body::from(settings.favicon_svg))
-            .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response()),
+        Ok(settings) => svg_favicon_response(settings),
+        Err(err) => favicon_unavailable(&err),
+    }
+}
+
+/// `/apple-touch-icon.png`: the favicon upload when it is a PNG; iOS does
+/// not take SVG or ICO icons, so there is nothing to fall back to.
+pub(super) async fn apple_touch_icon(State(state): State<HttpState>) -> Response {
+    crate::cache::deps::record(crate::cache::EntityKey::SiteSettings);
+
+    let settings = match state.db.load_site_settings().await {
+        Ok(settings) => settings,
+        Err(err) => return favicon_unavailable(&err),
+    };
+    match raster_favicon(&state, &settings).await {
+        Some((content_type, bytes)) if content_type == "image/png" => {
+            icon_response(&content_type, Body::from(bytes))
+        }
+        _ => HttpError::new(
+            "infra::http::public::apple_touch_icon",
+            StatusCode::NOT_FOUND,
+            "Icon not found",
+            "No PNG favicon is set",
+        )
+        .into_response(),
+    }
+}
+
+/// Content type and contents of the favicon upload, if one is set and its
+/// file can be read.
+async fn raster_favicon(
+    state: &HttpState,
+    settings: &SiteSettingsRecord,
+) -> Option<(String, Bytes)> {
+    let id = settings.favicon_upload_id?;
+    let upload = match state.db.find_upload(id).await {
+        Ok(upload) => upload?,
         Err(err) => {
-            error!(target = "soffio::http::favicon", error = %err, "failed to load favicon from settings");
-            let mut response = StatusCode::SERVICE_UNAVAILABLE.into_response();
-            crate::application::error::ErrorReport::from_error(
-                "infra::http::public::favicon",
-                StatusCode::SERVICE_UNAVAILABLE,
-                &err,
-            )
-            .attach(&mut response);
-            response
+            warn!(
+                target = "soffio::http::favicon",
+                upload_id = %id,
+                error = %err,
+                "failed to load favicon upload"
+            );
+            return None;
+        }
+    };
+    match state.upload_storage.read(&upload.stored_path).await {
+        Ok(bytes) => Some((upload.content_type, bytes)),
+        Err(err) => {
+            warn!(
+                target = "soffio::http::favicon",
+                upload_id = %id,
+                error = %err,
+                "failed to read favicon upload"
+            );
+            None
         }
     }
 }
 
+fn svg_favicon_response(settings: SiteSettingsRecord) -> Response {
+    icon_response(
+        "image/svg+xml; charset=utf-8",
+        Body::from(settings.favicon_svg),
+    )
+}
+
+fn icon_response(content_type: &str, body: Body) -> Response {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, content_type)
+        .header(CACHE_CONTROL, "public, max-age=3600")
+        .body(body)
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+fn favicon_unavailable(err: &RepoError) -> Response {
+    error!(target = "soffio::http::favicon", error = %err, "failed to load favicon from settings");
+    let mut response = StatusCode::SERVICE_UNAVAILABLE.into_response();
+    crate::application::error::ErrorReport::from_error(
+        "infra::http::public::favicon",
+        StatusCode::SERVICE_UNAVAILABLE,
+        err,
+    )
+    .attach(&mut response);
+    response
+}
+
 fn build_upload_response(path: &str, bytes: Bytes) -> Response {
     let mut response = Response::new(Body::from(bytes.clone()));
     *response.status_mut() = StatusCode::OK;
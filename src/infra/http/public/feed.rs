@@ -1,24 +1,29 @@
 use axum::{
     extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
-    response::{IntoResponse, Response},
+    response::{IntoResponse, Redirect, Response},
 };
 use serde::Deserialize;
 
 use crate::{
     application::{
         error::{ErrorReport, HttpError},
-        feed::{self, FeedError, FeedFilter},
+        feed::{self, ArchivePeriod, FeedError, FeedFilter},
     },
-    presentation::views::{
-        IndexTemplate, LayoutChrome, LayoutContext, PostTemplate, PostsPartial,
-        render_not_found_response, render_template_response,
+    domain::routes::post_path,
+    infra::http::security_headers::ContentFeatures,
+    presentation::{
+        page_templates::render_page_response,
+        views::{
+            IndexTemplate, LayoutChrome, LayoutContext, PostTemplate, PostsPartial,
+            render_not_found_response, render_template_response,
+        },
     },
 };
 
 use super::{
     HttpState,
-    meta::{canonical_url, post_meta},
+    meta::{canonical_url, page_meta, post_meta},
 };
 
 #[derive(Debug, Default, Deserialize)]
@@ -27,14 +32,23 @@ pub(super) struct CursorQuery {
     cursor: Option<String>,
 }
 
+/// `?lang=` selects a language variant of translated content.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(super) struct LangQuery {
+    pub(super) lang: Option<String>,
+}
+
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
 pub(super) struct PartialQuery {
     cursor: Option<String>,
     tag: Option<String>,
     month: Option<String>,
+    year: Option<String>,
 }
 
+/// `/` serves the homepage page in page mode and the feed otherwise.
 pub(super) async fn index(
     State(state): State<HttpState>,
     Query(query): Query<CursorQuery>,
@@ -44,13 +58,54 @@ pub(super) async fn index(
         Err(err) => return err.into_response(),
     };
 
+    if let Some(slug) = chrome.homepage_slug.clone() {
+        match state.pages.page_view(&slug).await {
+            Ok(Some(page_view)) => {
+                let canonical = canonical_url(&chrome.meta.canonical, "/");
+                let meta = page_meta(&chrome, &page_view, canonical);
+                let view = LayoutContext::new(chrome.clone().with_meta(meta), page_view);
+                return render_page_response(view, StatusCode::OK);
+            }
+            Ok(None) => {}
+            Err(err) => return err.into_response(),
+        }
+    }
+
+    feed_index(&state, chrome, query, "/").await
+}
+
+/// `/posts` always serves the feed; it is canonical only while `/` serves a
+/// page.
+pub(super) async fn posts_index(
+    State(state): State<HttpState>,
+    Query(query): Query<CursorQuery>,
+) -> Response {
+    let chrome = match state.chrome.load().await {
+        Ok(chrome) => chrome,
+        Err(err) => return err.into_response(),
+    };
+
+    let path = if chrome.homepage_slug.is_some() {
+        "/posts"
+    } else {
+        "/"
+    };
+    feed_index(&state, chrome, query, path).await
+}
+
+async fn feed_index(
+    state: &HttpState,
+    chrome: LayoutChrome,
+    query: CursorQuery,
+    canonical_path: &str,
+) -> Response {
     match state
         .feed
         .page_context(FeedFilter::All, query.cursor.as_deref())
         .await
     {
         Ok(content) => {
-            let canonical = canonical_url(&chrome.meta.canonical, "/");
+            let canonical = canonical_url(&chrome.meta.canonical, canonical_path);
             let view = LayoutContext::new(chrome.clone().with_canonical(canonical), content);
             render_template_response(IndexTemplate { view }, StatusCode::OK)
         }
@@ -116,17 +171,65 @@ pub(super) async fn month_index(
     }
 }
 
+/// `/archive/{year}` lists the posts published that year.
+pub(super) async fn year_archive(
+    State(state): State<HttpState>,
+    Path(year): Path<String>,
+    Query(query): Query<CursorQuery>,
+) -> Response {
+    archive_index(&state, ArchivePeriod::parse(&year, None), query).await
+}
+
+/// `/archive/{year}/{month}` lists the posts published that month.
+pub(super) async fn month_archive(
+    State(state): State<HttpState>,
+    Path((year, month)): Path<(String, String)>,
+    Query(query): Query<CursorQuery>,
+) -> Response {
+    archive_index(&state, ArchivePeriod::parse(&year, Some(&month)), query).await
+}
+
+/// Malformed periods are not found; a period without posts renders the
+/// empty state.
+async fn archive_index(
+    state: &HttpState,
+    period: Option<ArchivePeriod>,
+    query: CursorQuery,
+) -> Response {
+    let chrome = match state.chrome.load().await {
+        Ok(chrome) => chrome,
+        Err(err) => return err.into_response(),
+    };
+    let Some(period) = period else {
+        return render_not_found_response(chrome);
+    };
+
+    match state
+        .feed
+        .page_context(FeedFilter::Archive(period), query.cursor.as_deref())
+        .await
+    {
+        Ok(content) => {
+            let canonical = canonical_url(&chrome.meta.canonical, &period.path());
+            let view = LayoutContext::new(chrome.clone().with_canonical(canonical), content);
+            render_template_response(IndexTemplate { view }, StatusCode::OK)
+        }
+        Err(err) => feed_error_to_response(err, chrome),
+    }
+}
+
 pub(super) async fn posts_partial(
     State(state): State<HttpState>,
     headers: HeaderMap,
     Query(params): Query<PartialQuery>,
 ) -> Result<Response, HttpError> {
-    if params.tag.is_some() && params.month.is_some() {
+    let filters = [&params.tag, &params.month, &params.year];
+    if filters.iter().filter(|value| value.is_some()).count() > 1 {
         return Err(HttpError::new(
             "infra::http::posts_partial",
             StatusCode::BAD_REQUEST,
             "Conflicting filters",
-            "Received both tag and month filters",
+            "Received more than one of the tag, month and year filters",
         ));
     }
 
@@ -156,6 +259,18 @@ pub(super) async fn posts_partial(
             }
             Err(err) => return Err(err.into()),
         }
+    } else if let Some(year) = params.year.as_deref() {
+        match ArchivePeriod::parse(year, None) {
+            Some(period) => FeedFilter::Archive(period),
+            None => {
+                return Err(HttpError::new(
+                    "infra::http::posts_partial",
+                    StatusCode::NOT_FOUND,
+                    "Unknown year",
+                    "Requested year archive could not be found",
+                ));
+            }
+        }
     } else {
         FeedFilter::All
     };
@@ -181,18 +296,37 @@ pub(super) async fn posts_partial(
 pub(super) async fn post_detail(
     State(state): State<HttpState>,
     Path(slug): Path<String>,
+    Query(query): Query<LangQuery>,
 ) -> Response {
     let chrome = match state.chrome.load().await {
         Ok(chrome) => chrome,
         Err(err) => return err.into_response(),
     };
 
+    if let Some(lang) = query.lang.as_deref() {
+        match state.feed.translation_slug(&slug, lang).await {
+            Ok(Some(target)) if target != slug => {
+                return Redirect::temporary(&state.base_path.join(&post_path(&target)))
+                    .into_response();
+            }
+            Ok(_) => {}
+            Err(err) => return feed_error_to_response(err, chrome),
+        }
+    }
+
     match state.feed.post_detail(&slug).await {
         Ok(Some(content)) => {
-            let canonical = canonical_url(&chrome.meta.canonical, &format!("/posts/{slug}"));
+            let features = ContentFeatures {
+                inline_style_elements: content.has_mermaid_diagrams,
+            };
+            let canonical = canonical_url(&chrome.meta.canonical, &post_path(&slug));
             let meta = post_meta(&chrome, &content, canonical);
             let view = LayoutContext::new(chrome.clone().with_meta(meta), content);
-            render_template_response(PostTemplate { view }, StatusCode::OK)
+            let mut response = render_template_response(PostTemplate { view }, StatusCode::OK);
+            if let Some((name, value)) = state.security_headers.content_security_policy(features) {
+                response.headers_mut().insert(name, value);
+            }
+            response
         }
         Ok(None) => render_not_found_response(chrome),
         Err(err) => feed_error_to_response(err, chrome),
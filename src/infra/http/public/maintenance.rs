@@ -0,0 +1,80 @@
+//! Maintenance mode gate for the public site.
+//!
+//! While enabled in site settings, public routes answer 503 with the
+//! configured page. The gate wraps only the public router, so the admin
+//! listener and `/api/v1` keep serving, and health and static asset paths
+//! pass through so probes and the maintenance page's own assets still work.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{
+        HeaderValue, Request, StatusCode,
+        header::{CACHE_CONTROL, CONTENT_TYPE, RETRY_AFTER},
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::warn;
+
+use super::HttpState;
+
+const EXEMPT_PREFIXES: &[&str] = &["/_health/", "/readyz", "/static/"];
+
+pub(super) async fn maintenance_gate(
+    State(state): State<HttpState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if EXEMPT_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+    {
+        return next.run(request).await;
+    }
+
+    match state.chrome.site_settings().await {
+        Ok(settings) if settings.maintenance_enabled => maintenance_response(
+            settings.maintenance_html,
+            settings.maintenance_retry_after_secs,
+        ),
+        Ok(_) => next.run(request).await,
+        Err(err) => {
+            // Fail open: a settings outage should not take the site down too.
+            warn!(
+                target = "soffio::http::maintenance",
+                error = ?err,
+                "failed to load site settings; skipping maintenance check"
+            );
+            next.run(request).await
+        }
+    }
+}
+
+fn maintenance_response(html: String, retry_after_secs: i32) -> Response {
+    let mut response = (StatusCode::SERVICE_UNAVAILABLE, html).into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("text/html; charset=utf-8"),
+    );
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(RETRY_AFTER, HeaderValue::from(retry_after_secs.max(1)));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maintenance_response_is_an_uncacheable_503() {
+        let response = maintenance_response("<p>Back soon</p>".to_string(), 120);
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[RETRY_AFTER], "120");
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-store");
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html; charset=utf-8");
+    }
+}
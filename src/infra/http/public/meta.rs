@@ -1,4 +1,7 @@
-use crate::presentation::views::{LayoutChrome, PageMetaView, PageView, PostDetailContext};
+use crate::domain::routes::{join_url, post_path};
+use crate::presentation::views::{
+    AlternateLinkView, LayoutChrome, PageMetaView, PageView, PostDetailContext, TranslationLinkView,
+};
 
 pub(super) fn post_meta(
     chrome: &LayoutChrome,
@@ -7,32 +10,50 @@ pub(super) fn post_meta(
 ) -> PageMetaView {
     let description = fallback_description(&content.excerpt, &chrome.meta.description);
 
+    let alternates = alternate_links(&chrome.meta.canonical, &content.translations, post_path);
+
     chrome
         .meta
         .clone()
         .with_canonical(canonical)
         .with_content(content.title.clone(), description)
+        .with_translations(content.lang.clone(), alternates)
 }
 
 pub(super) fn page_meta(chrome: &LayoutChrome, page: &PageView, canonical: String) -> PageMetaView {
     let derived = summarize_html(&page.content_html, 180);
     let description = fallback_description(&derived, &chrome.meta.description);
 
+    let alternates = alternate_links(&chrome.meta.canonical, &page.translations, |slug| {
+        format!("/{slug}")
+    });
+
     chrome
         .meta
         .clone()
         .with_canonical(canonical)
         .with_content(page.title.clone(), description)
+        .with_translations(page.lang.clone(), alternates)
 }
 
+fn alternate_links(
+    base: &str,
+    translations: &[TranslationLinkView],
+    path_for: impl Fn(&str) -> String,
+) -> Vec<AlternateLinkView> {
+    translations
+        .iter()
+        .map(|translation| AlternateLinkView {
+            hreflang: translation.lang.clone(),
+            href: canonical_url(base, &path_for(&translation.slug)),
+        })
+        .collect()
+}
+
+/// `path` under the chrome's canonical root, which is absolute when
+/// `public_site_url` is set and root-relative otherwise.
 pub(super) fn canonical_url(base: &str, path: &str) -> String {
-    let root = normalize_public_site_url(base);
-    let trimmed = path.trim_start_matches('/');
-    if trimmed.is_empty() {
-        root.clone()
-    } else {
-        format!("{root}{trimmed}")
-    }
+    join_url(base, path)
 }
 
 fn fallback_description(candidate: &str, fallback: &str) -> String {
@@ -80,8 +101,3 @@ fn summarize_html(html: &str, max_len: usize) -> String {
 
     text.trim().to_string()
 }
-
-fn normalize_public_site_url(url: &str) -> String {
-    let trimmed = url.trim_end_matches('/');
-    format!("{trimmed}/")
-}
@@ -1,16 +1,24 @@
 use axum::{
     body::Body,
-    extract::State,
+    extract::{Query, State},
     http::{Request, StatusCode},
-    response::{IntoResponse, Response},
+    response::{IntoResponse, Redirect, Response},
 };
 
-use crate::presentation::views::{
-    LayoutContext, PageTemplate, render_not_found_response, render_template_response,
+use crate::{
+    domain::{
+        routes::{decode_path_segment, post_path},
+        translations::normalize_lang,
+    },
+    presentation::{
+        page_templates::render_page_response,
+        views::{LayoutChrome, LayoutContext, render_not_found_response},
+    },
 };
 
 use super::{
     HttpState,
+    feed::{LangQuery, feed_error_to_response},
     meta::{canonical_url, page_meta},
 };
 
@@ -30,14 +38,72 @@ pub(super) async fn fallback_router(
         return render_not_found_response(chrome);
     }
 
+    if let Some((prefix, rest)) = slug.split_once('/') {
+        return language_prefix_redirect(&state, chrome, prefix, rest).await;
+    }
+
+    let lang = Query::<LangQuery>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(query)| query.lang);
+    if let Some(lang) = lang.as_deref() {
+        match state.pages.translation_slug(slug, lang).await {
+            Ok(Some(target)) if target != slug => {
+                return Redirect::temporary(&state.base_path.join(&target)).into_response();
+            }
+            Ok(_) => {}
+            Err(err) => return err.into_response(),
+        }
+    }
+
     match state.pages.page_view(slug).await {
         Ok(Some(page_view)) => {
-            let canonical = canonical_url(&chrome.meta.canonical, &format!("/{slug}"));
+            let path = if chrome.homepage_slug.as_deref() == Some(slug) {
+                "/".to_string()
+            } else {
+                format!("/{slug}")
+            };
+            let canonical = canonical_url(&chrome.meta.canonical, &path);
             let meta = page_meta(&chrome, &page_view, canonical);
             let view = LayoutContext::new(chrome.clone().with_meta(meta), page_view);
-            render_template_response(PageTemplate { view }, StatusCode::OK)
+            render_page_response(view, StatusCode::OK)
         }
         Ok(None) => render_not_found_response(chrome),
         Err(err) => err.into_response(),
     }
 }
+
+/// `/{lang}/posts/{slug}` and `/{lang}/{slug}` redirect to the canonical URL
+/// of the variant in `lang`, or of the content itself when it has none.
+async fn language_prefix_redirect(
+    state: &HttpState,
+    chrome: LayoutChrome,
+    lang: &str,
+    rest: &str,
+) -> Response {
+    if normalize_lang(lang).is_none() {
+        return render_not_found_response(chrome);
+    }
+
+    let location = match rest.split_once('/') {
+        Some(("posts", post_slug)) if !post_slug.contains('/') => {
+            match state
+                .feed
+                .translation_slug(&decode_path_segment(post_slug), lang)
+                .await
+            {
+                Ok(target) => target.map(|slug| post_path(&slug)),
+                Err(err) => return feed_error_to_response(err, chrome),
+            }
+        }
+        None => match state.pages.translation_slug(rest, lang).await {
+            Ok(target) => target.map(|slug| format!("/{slug}")),
+            Err(err) => return err.into_response(),
+        },
+        Some(_) => None,
+    };
+
+    match location {
+        Some(location) => Redirect::temporary(&state.base_path.join(&location)).into_response(),
+        None => render_not_found_response(chrome),
+    }
+}
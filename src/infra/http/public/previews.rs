@@ -1,12 +1,19 @@
 use axum::{
-    extract::{Path, State},
-    http::{HeaderValue, StatusCode, header::CACHE_CONTROL},
+    body::Body,
+    extract::{Path, Query, State},
+    http::{HeaderName, HeaderValue, Request, StatusCode, header::CACHE_CONTROL},
+    middleware::Next,
     response::{IntoResponse, Response},
 };
+use serde::Deserialize;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
+use crate::application::snapshot_preview::SnapshotView;
+use crate::infra::db::on_primary;
+use crate::presentation::page_templates::render_page_response;
 use crate::presentation::views::{
-    LayoutContext, PageTemplate, PostTemplate, render_not_found_response, render_template_response,
+    LayoutContext, PostTemplate, render_not_found_response, render_template_response,
 };
 
 use super::{
@@ -15,6 +22,12 @@ use super::{
     meta::{canonical_url, page_meta, post_meta},
 };
 
+/// Previews are opened right after saving in the admin, so they read from the
+/// primary rather than a replica that may not have the save yet.
+pub(super) async fn read_from_primary(request: Request<Body>, next: Next) -> Response {
+    on_primary(next.run(request)).await
+}
+
 pub(super) async fn post_preview(State(state): State<HttpState>, Path(id): Path<Uuid>) -> Response {
     let chrome = match state.chrome.load().await {
         Ok(chrome) => chrome,
@@ -44,7 +57,7 @@ pub(super) async fn page_preview(State(state): State<HttpState>, Path(id): Path<
             let canonical = canonical_url(&chrome.meta.canonical, &format!("/pages/_preview/{id}"));
             let meta = page_meta(&chrome, &content, canonical);
             let view = LayoutContext::new(chrome.clone().with_meta(meta), content);
-            render_template_response(PageTemplate { view }, StatusCode::OK)
+            render_page_response(view, StatusCode::OK)
         }
         Ok(None) => render_not_found_response(chrome),
         Err(err) => err.into_response(),
@@ -54,13 +67,18 @@ pub(super) async fn page_preview(State(state): State<HttpState>, Path(id): Path<
 pub(super) async fn post_snapshot_preview(
     State(state): State<HttpState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<SharedPreviewQuery>,
 ) -> Response {
     let chrome = match state.chrome.load().await {
         Ok(chrome) => chrome,
         Err(err) => return err.into_response(),
     };
 
-    match state.snapshot_preview.post_snapshot_view(id).await {
+    match state
+        .snapshot_preview
+        .post_snapshot_view(id, &query.token, OffsetDateTime::now_utc())
+        .await
+    {
         Ok(Some(content)) => {
             let canonical = canonical_url(
                 &chrome.meta.canonical,
@@ -84,23 +102,26 @@ pub(super) async fn post_snapshot_preview(
 pub(super) async fn page_snapshot_preview(
     State(state): State<HttpState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<SharedPreviewQuery>,
 ) -> Response {
     let chrome = match state.chrome.load().await {
         Ok(chrome) => chrome,
         Err(err) => return err.into_response(),
     };
 
-    match state.snapshot_preview.page_snapshot_view(id).await {
+    match state
+        .snapshot_preview
+        .page_snapshot_view(id, &query.token, OffsetDateTime::now_utc())
+        .await
+    {
         Ok(Some(content)) => {
             let canonical = canonical_url(
                 &chrome.meta.canonical,
                 &format!("/pages/_preview/snapshot/{id}"),
             );
             let meta = page_meta(&chrome, &content, canonical);
-            let mut response = render_template_response(
-                PageTemplate {
-                    view: LayoutContext::new(chrome.clone().with_meta(meta), content),
-                },
+            let mut response = render_page_response(
+                LayoutContext::new(chrome.clone().with_meta(meta), content),
                 StatusCode::OK,
             );
             set_no_store(&mut response);
@@ -111,6 +132,59 @@ pub(super) async fn page_snapshot_preview(
     }
 }
 
+const X_ROBOTS_TAG: HeaderName = HeaderName::from_static("x-robots-tag");
+
+/// `?token=` of a shared snapshot preview link.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(super) struct SharedPreviewQuery {
+    token: String,
+}
+
+/// A snapshot shared from the admin, rendered as the public site would render
+/// its post or page. A bad or expired token is a plain 404, like an unknown id.
+pub(super) async fn shared_snapshot_preview(
+    State(state): State<HttpState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SharedPreviewQuery>,
+) -> Response {
+    let chrome = match state.chrome.load().await {
+        Ok(chrome) => chrome,
+        Err(err) => return err.into_response(),
+    };
+
+    let view = state
+        .snapshot_preview
+        .shared_snapshot_view(id, &query.token, OffsetDateTime::now_utc())
+        .await;
+    let canonical = canonical_url(&chrome.meta.canonical, &format!("/preview/snapshots/{id}"));
+    let mut response = match view {
+        Ok(Some(SnapshotView::Post(content))) => {
+            let meta = post_meta(&chrome, &content, canonical);
+            render_template_response(
+                PostTemplate {
+                    view: LayoutContext::new(chrome.clone().with_meta(meta), content),
+                },
+                StatusCode::OK,
+            )
+        }
+        Ok(Some(SnapshotView::Page(content))) => {
+            let meta = page_meta(&chrome, &content, canonical);
+            render_page_response(
+                LayoutContext::new(chrome.clone().with_meta(meta), content),
+                StatusCode::OK,
+            )
+        }
+        Ok(None) => render_not_found_response(chrome),
+        Err(err) => return err.into_response(),
+    };
+    set_no_store(&mut response);
+    response
+        .headers_mut()
+        .insert(X_ROBOTS_TAG, HeaderValue::from_static("noindex, nofollow"));
+    response
+}
+
 fn set_no_store(response: &mut Response) {
     let value = HeaderValue::from_static("no-store");
     response.headers_mut().insert(CACHE_CONTROL, value);
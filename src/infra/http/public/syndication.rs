@@ -6,6 +6,8 @@ use axum::{
 };
 
 use crate::application::error::HttpError;
+use crate::application::sitemap::SitemapError;
+use crate::application::syndication::SyndicationError;
 
 use super::HttpState;
 
@@ -14,7 +16,10 @@ pub(super) async fn sitemap(State(state): State<HttpState>) -> Response {
         Ok(body) => xml_response(body, "application/xml"),
         Err(err) => HttpError::new(
             "infra::http::public::sitemap",
-            StatusCode::INTERNAL_SERVER_ERROR,
+            match err {
+                SitemapError::NoSiteUrl => StatusCode::NOT_FOUND,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            },
             "Failed to generate sitemap",
             err.to_string(),
         )
@@ -27,7 +32,7 @@ pub(super) async fn rss_feed(State(state): State<HttpState>) -> Response {
         Ok(body) => xml_response(body, "application/rss+xml"),
         Err(err) => HttpError::new(
             "infra::http::public::rss",
-            StatusCode::INTERNAL_SERVER_ERROR,
+            syndication_status(&err),
             "Failed to generate RSS feed",
             err.to_string(),
         )
@@ -40,7 +45,7 @@ pub(super) async fn atom_feed(State(state): State<HttpState>) -> Response {
         Ok(body) => xml_response(body, "application/atom+xml"),
         Err(err) => HttpError::new(
             "infra::http::public::atom",
-            StatusCode::INTERNAL_SERVER_ERROR,
+            syndication_status(&err),
             "Failed to generate Atom feed",
             err.to_string(),
         )
@@ -61,6 +66,14 @@ pub(super) async fn robots_txt(State(state): State<HttpState>) -> Response {
     }
 }
 
+/// Feeds without a `public_site_url` are missing rather than broken.
+fn syndication_status(err: &SyndicationError) -> StatusCode {
+    match err {
+        SyndicationError::NoSiteUrl => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
 fn xml_response(body: String, content_type: &str) -> Response {
     Response::builder()
         .status(StatusCode::OK)
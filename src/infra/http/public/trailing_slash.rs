@@ -0,0 +1,233 @@
+//! Trailing-slash canonicalisation for the public site.
+//!
+//! Under `always` or `never`, GET and HEAD requests for the non-canonical
+//! form answer 301 with the canonical one, so `/posts/foo/` and `/posts/foo`
+//! never become two response cache entries. Routes are registered without a
+//! trailing slash; under `always` the canonical form is rewritten to the
+//! routed path before dispatch, which is why this runs ahead of routing.
+//! The root and static asset paths are left untouched. Paths seen here are
+//! already relative to the base path, so redirects put it back in front.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderValue, Method, Request, StatusCode, header::LOCATION},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::config::TrailingSlashPolicy;
+use crate::domain::routes::BasePath;
+use crate::infra::http::middleware::with_path;
+
+const EXEMPT_PREFIXES: &[&str] = &["/_health/", "/readyz", "/static/", "/uploads/", "/api/"];
+
+#[derive(Debug, PartialEq, Eq)]
+enum SlashAction {
+    Pass,
+    Redirect(String),
+    Rewrite(String),
+}
+
+pub(super) async fn canonical_trailing_slash(
+    State((policy, base_path)): State<(TrailingSlashPolicy, BasePath)>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+
+    match slash_action(request.uri().path(), policy) {
+        SlashAction::Pass => next.run(request).await,
+        SlashAction::Redirect(path) => redirect(&base_path.join(&path), request.uri().query()),
+        SlashAction::Rewrite(path) => {
+            if let Some(uri) = with_path(request.uri(), &path) {
+                *request.uri_mut() = uri;
+            }
+            next.run(request).await
+        }
+    }
+}
+
+fn slash_action(path: &str, policy: TrailingSlashPolicy) -> SlashAction {
+    if is_exempt(path) {
+        return SlashAction::Pass;
+    }
+
+    let trimmed = path.trim_end_matches('/');
+    let has_slash = trimmed.len() != path.len();
+    match (policy, has_slash) {
+        (TrailingSlashPolicy::Preserve, _) => SlashAction::Pass,
+        (TrailingSlashPolicy::Never, true) => SlashAction::Redirect(trimmed.to_string()),
+        (TrailingSlashPolicy::Never, false) => SlashAction::Pass,
+        (TrailingSlashPolicy::Always, false) => SlashAction::Redirect(format!("{path}/")),
+        (TrailingSlashPolicy::Always, true) if path.len() - trimmed.len() > 1 => {
+            SlashAction::Redirect(format!("{trimmed}/"))
+        }
+        (TrailingSlashPolicy::Always, true) => SlashAction::Rewrite(trimmed.to_string()),
+    }
+}
+
+/// The root, static prefixes, and file-like paths (`/rss.xml`) keep their form.
+fn is_exempt(path: &str) -> bool {
+    if path.trim_end_matches('/').is_empty() {
+        return true;
+    }
+    if EXEMPT_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+    {
+        return true;
+    }
+    path.rsplit('/')
+        .next()
+        .is_some_and(|segment| segment.contains('.'))
+}
+
+fn redirect(path: &str, query: Option<&str>) -> Response {
+    let location = match query {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+    match HeaderValue::from_str(&location) {
+        Ok(value) => (StatusCode::MOVED_PERMANENTLY, [(LOCATION, value)]).into_response(),
+        Err(_) => StatusCode::BAD_REQUEST.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn app(policy: TrailingSlashPolicy, base_path: BasePath) -> Router {
+        let site = Router::new()
+            .route("/", get(|| async { "home" }))
+            .route("/posts/{slug}", get(|| async { "post" }))
+            .route("/rss.xml", get(|| async { "feed" }));
+        Router::new()
+            .fallback_service(site)
+            .layer(middleware::from_fn_with_state(
+                (policy, base_path),
+                canonical_trailing_slash,
+            ))
+    }
+
+    async fn get_path(policy: TrailingSlashPolicy, uri: &str) -> Response {
+        let request = Request::builder()
+            .uri(uri)
+            .body(Body::empty())
+            .expect("request");
+        app(policy, BasePath::default())
+            .oneshot(request)
+            .await
+            .expect("response")
+    }
+
+    #[test]
+    fn never_strips_trailing_slashes() {
+        assert_eq!(
+            slash_action("/posts/foo/", TrailingSlashPolicy::Never),
+            SlashAction::Redirect("/posts/foo".to_string())
+        );
+        assert_eq!(
+            slash_action("/posts/foo//", TrailingSlashPolicy::Never),
+            SlashAction::Redirect("/posts/foo".to_string())
+        );
+        assert_eq!(
+            slash_action("/posts/foo", TrailingSlashPolicy::Never),
+            SlashAction::Pass
+        );
+    }
+
+    #[test]
+    fn always_appends_a_slash_and_routes_the_canonical_form() {
+        assert_eq!(
+            slash_action("/posts/foo", TrailingSlashPolicy::Always),
+            SlashAction::Redirect("/posts/foo/".to_string())
+        );
+        assert_eq!(
+            slash_action("/posts/foo//", TrailingSlashPolicy::Always),
+            SlashAction::Redirect("/posts/foo/".to_string())
+        );
+        assert_eq!(
+            slash_action("/posts/foo/", TrailingSlashPolicy::Always),
+            SlashAction::Rewrite("/posts/foo".to_string())
+        );
+    }
+
+    #[test]
+    fn root_and_static_paths_are_exempt() {
+        for policy in TrailingSlashPolicy::ALL {
+            for path in [
+                "/",
+                "/static/public/site.css",
+                "/static/common/",
+                "/uploads/2026/photo.png",
+                "/_health/db",
+                "/api/v1/posts",
+                "/rss.xml",
+            ] {
+                assert_eq!(slash_action(path, policy), SlashAction::Pass, "{path}");
+            }
+        }
+    }
+
+    #[test]
+    fn preserve_never_redirects() {
+        for path in ["/posts/foo", "/posts/foo/"] {
+            assert_eq!(
+                slash_action(path, TrailingSlashPolicy::Preserve),
+                SlashAction::Pass
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn redirect_keeps_the_query_string() {
+        let response = get_path(TrailingSlashPolicy::Never, "/posts/foo/?page=2").await;
+
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(response.headers()[LOCATION], "/posts/foo?page=2");
+    }
+
+    #[tokio::test]
+    async fn redirect_keeps_the_base_path() {
+        let base_path = BasePath::parse("/blog").expect("base path");
+        let request = Request::builder()
+            .uri("/posts/foo/")
+            .body(Body::empty())
+            .expect("request");
+        let response = app(TrailingSlashPolicy::Never, base_path)
+            .oneshot(request)
+            .await
+            .expect("response");
+
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(response.headers()[LOCATION], "/blog/posts/foo");
+    }
+
+    #[tokio::test]
+    async fn always_serves_the_slashed_form() {
+        let response = get_path(TrailingSlashPolicy::Always, "/posts/foo").await;
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(response.headers()[LOCATION], "/posts/foo/");
+
+        let response = get_path(TrailingSlashPolicy::Always, "/posts/foo/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn root_is_served_under_either_policy() {
+        for policy in [TrailingSlashPolicy::Always, TrailingSlashPolicy::Never] {
+            let response = get_path(policy, "/").await;
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = get_path(policy, "/rss.xml").await;
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Security headers for the public and admin listeners.
+//!
+//! The built-in policies follow what rendered pages load: scripts and
+//! stylesheets from `/static`, datastar expressions (which need
+//! `'unsafe-eval'`), KaTeX output with inline `style` attributes and fonts
+//! under `/static/public/katex`, and remote images, media and embeds. Mermaid
+//! diagrams are inline SVG carrying their own `<style>` element, so pages that
+//! contain them widen the policy per response (see [`ContentFeatures`]).
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{
+        HeaderMap, HeaderName, HeaderValue, Request,
+        header::{
+            CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY_REPORT_ONLY, REFERRER_POLICY,
+            STRICT_TRANSPORT_SECURITY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+        },
+    },
+    middleware::Next,
+    response::Response,
+};
+
+use crate::config::SecurityHeadersSettings;
+
+const PUBLIC_POLICY: &[&str] = &[
+    "default-src 'self'",
+    "script-src 'self' 'unsafe-eval'",
+    "style-src 'self'",
+    "style-src-attr 'unsafe-inline'",
+    "img-src 'self' data: https:",
+    "font-src 'self'",
+    "media-src 'self' https:",
+    "frame-src https:",
+    "connect-src 'self'",
+    "object-src 'none'",
+    "base-uri 'self'",
+    "form-action 'self'",
+    "frame-ancestors 'self'",
+];
+
+/// Admin pages preview arbitrary post content, so inline styles are allowed
+/// throughout; framing is not allowed at all.
+const ADMIN_POLICY: &[&str] = &[
+    "default-src 'self'",
+    "script-src 'self' 'unsafe-eval'",
+    "style-src 'self' 'unsafe-inline'",
+    "img-src 'self' data: https:",
+    "font-src 'self'",
+    "media-src 'self' https:",
+    "frame-src 'self' https:",
+    "connect-src 'self'",
+    "object-src 'none'",
+    "base-uri 'self'",
+    "form-action 'self'",
+    "frame-ancestors 'none'",
+];
+
+/// Directive added for pages with inline `<style>` elements.
+const INLINE_STYLE_ELEMENTS: &str = "style-src-elem 'self' 'unsafe-inline'";
+
+/// What a rendered page contains beyond the baseline policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentFeatures {
+    /// The body embeds `<style>` elements, as Mermaid SVGs do.
+    pub inline_style_elements: bool,
+}
+
+/// Headers added to every response of one listener.
+#[derive(Debug, Clone, Default)]
+pub struct SecurityHeaders(Option<Arc<Inner>>);
+
+#[derive(Debug)]
+struct Inner {
+    csp_header: HeaderName,
+    /// Directives of the policy, without the report URI.
+    policy: String,
+    /// Only the built-in public policy is widened per response; a configured
+    /// policy is sent as written.
+    adjustable: bool,
+    report_uri: Option<String>,
+    hsts: Option<HeaderValue>,
+    referrer_policy: HeaderValue,
+    frame_options: HeaderValue,
+}
+
+impl SecurityHeaders {
+    /// Headers for the public site.
+    pub fn public(settings: &SecurityHeadersSettings) -> Self {
+        let (policy, adjustable) = match &settings.content_security_policy {
+            Some(policy) => (policy.trim_end_matches(';').to_string(), false),
+            None => (PUBLIC_POLICY.join("; "), true),
+        };
+        Self::build(
+            settings,
+            policy,
+            adjustable,
+            &settings.referrer_policy,
+            "SAMEORIGIN",
+        )
+    }
+
+    /// Stricter headers for the admin listener.
+    pub fn admin(settings: &SecurityHeadersSettings) -> Self {
+        Self::build(
+            settings,
+            ADMIN_POLICY.join("; "),
+            false,
+            "same-origin",
+            "DENY",
+        )
+    }
+
+    fn build(
+        settings: &SecurityHeadersSettings,
+        policy: String,
+        adjustable: bool,
+        referrer_policy: &str,
+        frame_options: &'static str,
+    ) -> Self {
+        if !settings.enabled {
+            return Self(None);
+        }
+        let csp_header = if settings.report_only {
+            CONTENT_SECURITY_POLICY_REPORT_ONLY
+        } else {
+            CONTENT_SECURITY_POLICY
+        };
+        let hsts = settings.hsts_max_age.map(|max_age| {
+            HeaderValue::from_str(&format!("max-age={}", max_age.as_secs()))
+                .expect("max-age is a valid header value")
+        });
+        Self(Some(Arc::new(Inner {
+            csp_header,
+            policy,
+            adjustable,
+            report_uri: settings.report_uri.clone(),
+            hsts,
+            referrer_policy: HeaderValue::from_str(referrer_policy)
+                .expect("referrer policy is validated when settings load"),
+            frame_options: HeaderValue::from_static(frame_options),
+        })))
+    }
+
+    /// The policy header for a page with `features`, or `None` when the
+    /// headers are disabled. Handlers insert it themselves to widen the
+    /// policy; [`apply_security_headers`] leaves an existing header alone.
+    pub fn content_security_policy(
+        &self,
+        features: ContentFeatures,
+    ) -> Option<(HeaderName, HeaderValue)> {
+        let inner = self.0.as_ref()?;
+        let mut policy = inner.policy.clone();
+        if inner.adjustable && features.inline_style_elements {
+            policy.push_str("; ");
+            policy.push_str(INLINE_STYLE_ELEMENTS);
+        }
+        if let Some(uri) = &inner.report_uri {
+            policy.push_str("; report-uri ");
+            policy.push_str(uri);
+        }
+        let value = HeaderValue::from_str(&policy)
+            .expect("policy and report URI are validated when settings load");
+        Some((inner.csp_header.clone(), value))
+    }
+
+    fn apply(&self, headers: &mut HeaderMap) {
+        let Some(inner) = self.0.as_ref() else {
+            return;
+        };
+        if !headers.contains_key(&inner.csp_header)
+            && let Some((name, value)) = self.content_security_policy(ContentFeatures::default())
+        {
+            headers.insert(name, value);
+        }
+        if let Some(hsts) = &inner.hsts {
+            headers
+                .entry(STRICT_TRANSPORT_SECURITY)
+                .or_insert_with(|| hsts.clone());
+        }
+        headers
+            .entry(X_CONTENT_TYPE_OPTIONS)
+            .or_insert_with(|| HeaderValue::from_static("nosniff"));
+        headers
+            .entry(REFERRER_POLICY)
+            .or_insert_with(|| inner.referrer_policy.clone());
+        headers
+            .entry(X_FRAME_OPTIONS)
+            .or_insert_with(|| inner.frame_options.clone());
+    }
+}
+
+/// Add the listener's security headers to responses that do not set them.
+pub async fn apply_security_headers(
+    State(headers): State<SecurityHeaders>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    headers.apply(response.headers_mut());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn settings() -> SecurityHeadersSettings {
+        SecurityHeadersSettings {
+            enabled: true,
+            content_security_policy: None,
+            report_uri: None,
+            report_only: false,
+            hsts_max_age: Some(Duration::from_secs(600)),
+            referrer_policy: "strict-origin-when-cross-origin".to_string(),
+        }
+    }
+
+    fn policy(headers: &SecurityHeaders, features: ContentFeatures) -> String {
+        let (_, value) = headers
+            .content_security_policy(features)
+            .expect("headers are enabled");
+        value.to_str().expect("ascii").to_string()
+    }
+
+    #[test]
+    fn mermaid_pages_allow_inline_style_elements() {
+        let headers = SecurityHeaders::public(&settings());
+        let mermaid = ContentFeatures {
+            inline_style_elements: true,
+        };
+
+        assert!(!policy(&headers, ContentFeatures::default()).contains("style-src-elem"));
+        assert!(policy(&headers, mermaid).contains(INLINE_STYLE_ELEMENTS));
+    }
+
+    #[test]
+    fn configured_policy_is_sent_as_written_with_report_uri() {
+        let headers = SecurityHeaders::public(&SecurityHeadersSettings {
+            content_security_policy: Some("default-src 'self';".to_string()),
+            report_uri: Some("/csp-reports".to_string()),
+            report_only: true,
+            ..settings()
+        });
+
+        let (name, value) = headers
+            .content_security_policy(ContentFeatures {
+                inline_style_elements: true,
+            })
+            .expect("headers are enabled");
+        assert_eq!(name, CONTENT_SECURITY_POLICY_REPORT_ONLY);
+        assert_eq!(value, "default-src 'self'; report-uri /csp-reports");
+    }
+
+    #[test]
+    fn existing_headers_are_kept_and_disabled_headers_add_nothing() {
+        let mut response = HeaderMap::new();
+        response.insert(REFERRER_POLICY, HeaderValue::from_static("no-referrer"));
+        SecurityHeaders::admin(&settings()).apply(&mut response);
+
+        assert_eq!(response[REFERRER_POLICY], "no-referrer");
+        assert_eq!(response[X_FRAME_OPTIONS], "DENY");
+        assert_eq!(response[STRICT_TRANSPORT_SECURITY], "max-age=600");
+        assert!(
+            response[CONTENT_SECURITY_POLICY]
+                .to_str()
+                .expect("ascii")
+                .contains("frame-ancestors 'none'")
+        );
+
+        let mut untouched = HeaderMap::new();
+        SecurityHeaders::public(&SecurityHeadersSettings {
+            enabled: false,
+            ..settings()
+        })
+        .apply(&mut untouched);
+        assert!(untouched.is_empty());
+    }
+}
@@ -0,0 +1,322 @@
+//! Static snapshot of the public site.
+//!
+//! The exporter crawls the public router in-process: it starts from the home
+//! page, the sitemap and the feeds, follows every same-site link, stylesheet,
+//! script and image it finds, and writes each successful response to disk.
+//! Pages are rendered by the regular public handlers, so the snapshot matches
+//! what the live site serves; unpublished content is never linked and answers
+//! `404`, so it is not exported.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, VecDeque},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode, header},
+};
+use lol_html::{RewriteStrSettings, element, rewrite_str};
+use percent_encoding::percent_decode_str;
+use tower::ServiceExt;
+use url::Url;
+
+use crate::{application::error::AppError, infra::error::InfraError};
+
+/// Crawled besides the home page; posts and pages are listed in the sitemap.
+const SEED_PATHS: [&str; 5] = [
+    "sitemap.xml",
+    "rss.xml",
+    "atom.xml",
+    "robots.txt",
+    "favicon.ico",
+];
+
+/// Dynamic, private or operational routes that have no place in a snapshot.
+const SKIPPED_PREFIXES: [&str; 7] = [
+    "api/",
+    "ui/",
+    "_health/",
+    "readyz",
+    "posts/_preview/",
+    "pages/_preview/",
+    "preview/",
+];
+
+/// Largest response body written; uploads are capped well below this.
+const MAX_BODY_BYTES: usize = 256 * 1024 * 1024;
+
+#[derive(Debug, Default)]
+pub struct StaticExportReport {
+    /// Files written, relative to the output directory, in crawl order.
+    pub written: Vec<PathBuf>,
+    /// Linked site paths that did not answer with a success.
+    pub failed: Vec<(String, StatusCode)>,
+}
+
+/// Crawl `router` from `site_url` (the absolute site root, base path
+/// included) and write every page and asset it links to under `dir`.
+///
+/// HTML responses are written as `{path}/index.html` so the snapshot can be
+/// served by any static file server with the same URLs; other responses keep
+/// their path. Links that fail are reported, not fatal, but the home page
+/// must render.
+pub async fn export_static_site(
+    router: Router,
+    site_url: &Url,
+    dir: &Path,
+) -> Result<StaticExportReport, AppError> {
+    let mut report = StaticExportReport::default();
+    let mut seen = BTreeSet::new();
+    let mut queue = VecDeque::new();
+
+    for path in std::iter::once("").chain(SEED_PATHS) {
+        let url = site_url
+            .join(path)
+            .map_err(|err| AppError::validation(format!("invalid site url: {err}")))?;
+        enqueue(site_url, url, &mut seen, &mut queue);
+    }
+
+    while let Some((url, relative)) = queue.pop_front() {
+        let request = Request::get(url.path())
+            .body(Body::empty())
+            .map_err(|err| AppError::unexpected(format!("invalid request path: {err}")))?;
+        let response = router
+            .clone()
+            .oneshot(request)
+            .await
+            .map_err(|err| AppError::unexpected(err.to_string()))?;
+        let status = response.status();
+
+        if status.is_redirection() {
+            let location = response
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|location| url.join(location).ok());
+            if let Some(location) = location {
+                enqueue(site_url, location, &mut seen, &mut queue);
+            }
+            continue;
+        }
+        if !status.is_success() {
+            if relative.is_empty() {
+                return Err(AppError::unexpected(format!(
+                    "the home page answered {status}; is maintenance mode on?"
+                )));
+            }
+            report.failed.push((relative, status));
+            continue;
+        }
+
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let body = to_bytes(response.into_body(), MAX_BODY_BYTES)
+            .await
+            .map_err(|err| AppError::unexpected(format!("failed to read {relative}: {err}")))?;
+
+        let is_html = content_type.starts_with("text/html");
+        let links = match std::str::from_utf8(&body) {
+            Ok(text) if is_html => html_links(text),
+            Ok(text) if content_type.starts_with("text/css") => css_links(text),
+            Ok(text) if content_type.contains("xml") => sitemap_links(text),
+            _ => Vec::new(),
+        };
+        for link in links {
+            if let Ok(target) = url.join(&link) {
+                enqueue(site_url, target, &mut seen, &mut queue);
+            }
+        }
+
+        let Some(file) = output_path(&relative, is_html) else {
+            continue;
+        };
+        let target = dir.join(&file);
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(InfraError::from)?;
+        }
+        tokio::fs::write(&target, &body)
+            .await
+            .map_err(InfraError::from)?;
+        report.written.push(file);
+    }
+
+    Ok(report)
+}
+
+/// Queue `url` once if it is an exportable page of the site.
+fn enqueue(
+    site_url: &Url,
+    mut url: Url,
+    seen: &mut BTreeSet<String>,
+    queue: &mut VecDeque<(Url, String)>,
+) {
+    url.set_query(None);
+    url.set_fragment(None);
+    let Some(relative) = site_path(site_url, &url) else {
+        return;
+    };
+    if SKIPPED_PREFIXES
+        .iter()
+        .any(|prefix| relative.starts_with(prefix))
+    {
+        return;
+    }
+    if seen.insert(relative.clone()) {
+        queue.push_back((url, relative));
+    }
+}
+
+/// Path of `url` relative to the site root, without surrounding slashes;
+/// `None` for other origins and for paths outside the base path.
+fn site_path(site_url: &Url, url: &Url) -> Option<String> {
+    if url.origin() != site_url.origin() {
+        return None;
+    }
+    let root = site_url.path();
+    let path = url.path();
+    if path == root.trim_end_matches('/') {
+        return Some(String::new());
+    }
+    let relative = path.strip_prefix(root)?;
+    Some(relative.trim_end_matches('/').to_string())
+}
+
+/// File for a site path: pages become directory indexes. `None` when a
+/// segment would escape the output directory.
+fn output_path(relative: &str, is_html: bool) -> Option<PathBuf> {
+    let mut file = PathBuf::new();
+    for segment in relative.split('/').filter(|segment| !segment.is_empty()) {
+        let segment = percent_decode_str(segment).decode_utf8().ok()?;
+        if matches!(segment.as_ref(), "." | "..") || segment.contains(['/', '\\']) {
+            return None;
+        }
+        file.push(segment.as_ref());
+    }
+    if is_html {
+        file.push("index.html");
+    }
+    (!file.as_os_str().is_empty()).then_some(file)
+}
+
+/// `href` and `src` targets in an HTML document.
+fn html_links(html: &str) -> Vec<String> {
+    let links = Rc::new(RefCell::new(Vec::new()));
+
+    let result = rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![
+                element!("[href]", {
+                    let links = Rc::clone(&links);
+                    move |el| {
+                        if let Some(href) = el.get_attribute("href") {
+                            links.borrow_mut().push(href);
+                        }
+                        Ok(())
+                    }
+                }),
+                element!("[src]", {
+                    let links = Rc::clone(&links);
+                    move |el| {
+                        if let Some(src) = el.get_attribute("src") {
+                            links.borrow_mut().push(src);
+                        }
+                        Ok(())
+                    }
+                }),
+            ],
+            ..RewriteStrSettings::default()
+        },
+    );
+    if result.is_err() {
+        return Vec::new();
+    }
+    links.take()
+}
+
+/// `url(...)` targets in a stylesheet.
+fn css_links(css: &str) -> Vec<String> {
+    css.split("url(")
+        .skip(1)
+        .filter_map(|rest| rest.split_once(')'))
+        .map(|(target, _)| target.trim().trim_matches(['"', '\'']).to_string())
+        .filter(|target| !target.is_empty() && !target.starts_with("data:"))
+        .collect()
+}
+
+/// `<loc>` entries of a sitemap; other XML documents have none.
+fn sitemap_links(xml: &str) -> Vec<String> {
+    xml.split("<loc>")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("</loc>"))
+        .map(|(loc, _)| loc.trim().replace("&amp;", "&"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(value: &str) -> Url {
+        Url::parse(value).expect("valid url")
+    }
+
+    #[test]
+    fn site_paths_are_relative_to_the_base_path() {
+        let site = url("https://example.com/blog/");
+
+        assert_eq!(
+            site_path(&site, &url("https://example.com/blog")).as_deref(),
+            Some("")
+        );
+        assert_eq!(
+            site_path(&site, &url("https://example.com/blog/posts/hello/")).as_deref(),
+            Some("posts/hello")
+        );
+        assert_eq!(site_path(&site, &url("https://example.com/other")), None);
+        assert_eq!(
+            site_path(&site, &url("https://cdn.example.com/blog/x")),
+            None
+        );
+    }
+
+    #[test]
+    fn pages_become_directory_indexes_and_escapes_are_refused() {
+        assert_eq!(output_path("", true), Some(PathBuf::from("index.html")));
+        assert_eq!(
+            output_path("posts/hello", true),
+            Some(PathBuf::from("posts/hello/index.html"))
+        );
+        assert_eq!(
+            output_path("uploads/a%20b.png", false),
+            Some(PathBuf::from("uploads/a b.png"))
+        );
+        assert_eq!(output_path("uploads/%2E%2E/secret", false), None);
+        assert_eq!(output_path("", false), None);
+    }
+
+    #[test]
+    fn links_are_collected_from_html_css_and_sitemaps() {
+        let html = r#"<link rel="stylesheet" href="/static/public/site.css"><a href="/posts/a#top">A</a><img src="/uploads/x.png">"#;
+        assert_eq!(
+            html_links(html),
+            vec!["/static/public/site.css", "/posts/a#top", "/uploads/x.png"]
+        );
+
+        let css = r#"@font-face { src: url("fonts/a.woff2") } body { background: url(data:image/png;base64,AA) }"#;
+        assert_eq!(css_links(css), vec!["fonts/a.woff2"]);
+
+        let xml = "<urlset><url><loc>https://example.com/posts/a</loc></url></urlset>";
+        assert_eq!(sitemap_links(xml), vec!["https://example.com/posts/a"]);
+    }
+}
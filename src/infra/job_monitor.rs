@@ -0,0 +1,201 @@
+//! Background job workers for `serve`.
+
+use std::sync::Arc;
+
+use crate::{
+    application::{
+        api_keys::ApiKeyService,
+        jobs::{
+            ExpireApiKeysContext, JobWorkerContext, PingSitemapJobPayload, PublishPageJobPayload,
+            PublishPostJobPayload, PurgeTrashContext, WarmCacheContext, expire_api_keys_schedule,
+            process_expire_api_keys_job, process_ping_sitemap_job, process_publish_page_job,
+            process_publish_post_job, process_purge_trash_job, process_warm_cache_job,
+            purge_trash_schedule, record_failed_attempt, warm_cache_schedule,
+        },
+        render::{
+            RenderPageJobPayload, RenderPostJobPayload, process_render_page_job,
+            process_render_post_job,
+        },
+    },
+    cache::CacheWarmer,
+    config,
+    domain::types::JobType,
+    infra::db::PostgresRepositories,
+};
+use apalis::{
+    layers::WorkerBuilderExt,
+    prelude::{
+        Attempt, Data, Error as ApalisError, Monitor, TaskId, WorkerBuilder, WorkerFactoryFn,
+    },
+};
+use apalis_cron::CronStream;
+use apalis_sql::{Config as ApalisSqlConfig, postgres::PostgresStorage};
+use tracing::{error, warn};
+
+/// Run the queued and scheduled job workers until the returned task is
+/// aborted.
+pub fn spawn_job_monitor(
+    repositories: Arc<PostgresRepositories>,
+    context: JobWorkerContext,
+    api_keys: Arc<ApiKeyService>,
+    cache_warmer: Option<Arc<CacheWarmer>>,
+    purge_trash: Option<PurgeTrashContext>,
+    jobs: &config::JobsSettings,
+    cache: &config::CacheSettings,
+) -> tokio::task::JoinHandle<()> {
+    let render_storage = PostgresStorage::new_with_config(
+        repositories.pool().clone(),
+        ApalisSqlConfig::new(JobType::RenderPost.as_str()),
+    );
+    let render_page_storage = PostgresStorage::new_with_config(
+        repositories.pool().clone(),
+        ApalisSqlConfig::new(JobType::RenderPage.as_str()),
+    );
+    let publish_post_storage = PostgresStorage::new_with_config(
+        repositories.pool().clone(),
+        ApalisSqlConfig::new(JobType::PublishPost.as_str()),
+    );
+    let publish_page_storage = PostgresStorage::new_with_config(
+        repositories.pool().clone(),
+        ApalisSqlConfig::new(JobType::PublishPage.as_str()),
+    );
+    let ping_sitemap_storage = PostgresStorage::new_with_config(
+        repositories.pool().clone(),
+        ApalisSqlConfig::new(JobType::PingSitemap.as_str()),
+    );
+
+    let render_post_concurrency = jobs.render_post_concurrency.get() as usize;
+    let render_page_concurrency = jobs.render_page_concurrency.get() as usize;
+    let publish_post_concurrency = jobs.publish_post_concurrency.get() as usize;
+    let publish_page_concurrency = jobs.publish_page_concurrency.get() as usize;
+
+    let render_post_worker = WorkerBuilder::new("render-post-worker")
+        .concurrency(render_post_concurrency)
+        .data(context.clone())
+        .backend(render_storage)
+        .build_fn(run_render_post_job);
+    let render_page_worker = WorkerBuilder::new("render-page-worker")
+        .concurrency(render_page_concurrency)
+        .data(context.clone())
+        .backend(render_page_storage)
+        .build_fn(run_render_page_job);
+    let publish_post_worker = WorkerBuilder::new("publish-post-worker")
+        .concurrency(publish_post_concurrency)
+        .data(context.clone())
+        .backend(publish_post_storage)
+        .build_fn(run_publish_post_job);
+    let publish_page_worker = WorkerBuilder::new("publish-page-worker")
+        .concurrency(publish_page_concurrency)
+        .data(context.clone())
+        .backend(publish_page_storage)
+        .build_fn(run_publish_page_job);
+    let ping_sitemap_worker = WorkerBuilder::new("ping-sitemap-worker")
+        .data(context.clone())
+        .backend(ping_sitemap_storage)
+        .build_fn(run_ping_sitemap_job);
+
+    let expire_api_keys_ctx = ExpireApiKeysContext { api_keys };
+    let expire_api_keys_worker = WorkerBuilder::new("expire-api-keys-worker")
+        .data(expire_api_keys_ctx)
+        .backend(CronStream::new(expire_api_keys_schedule()))
+        .build_fn(process_expire_api_keys_job);
+
+    let monitor = Monitor::new()
+        .register(render_post_worker)
+        .register(render_page_worker)
+        .register(publish_post_worker)
+        .register(publish_page_worker)
+        .register(ping_sitemap_worker)
+        .register(expire_api_keys_worker);
+
+    let monitor = match cache_warmer {
+        Some(warmer) => match warm_cache_schedule(&cache.warmup_cron) {
+            Ok(schedule) => {
+                let warm_cache_worker = WorkerBuilder::new("warm-cache-worker")
+                    .data(WarmCacheContext { warmer })
+                    .backend(CronStream::new(schedule))
+                    .build_fn(process_warm_cache_job);
+                monitor.register(warm_cache_worker)
+            }
+            Err(err) => {
+                warn!(error = %err, "scheduled cache warming disabled");
+                monitor
+            }
+        },
+        None => monitor,
+    };
+
+    let monitor = match purge_trash {
+        Some(purge_trash_ctx) => {
+            let purge_trash_worker = WorkerBuilder::new("purge-trash-worker")
+                .data(purge_trash_ctx)
+                .backend(CronStream::new(purge_trash_schedule()))
+                .build_fn(process_purge_trash_job);
+            monitor.register(purge_trash_worker)
+        }
+        None => monitor,
+    };
+
+    tokio::spawn(async move {
+        if let Err(err) = monitor.run().await {
+            error!(error = %err, "job monitor stopped");
+        }
+    })
+}
+
+// Queued jobs run through these wrappers so every failed run lands in
+// `job_attempts`; cron jobs have no job row to attach attempts to.
+
+async fn run_render_post_job(
+    payload: RenderPostJobPayload,
+    context: Data<JobWorkerContext>,
+    task_id: TaskId,
+    attempt: Attempt,
+) -> Result<(), ApalisError> {
+    let repositories = context.repositories.clone();
+    let result = process_render_post_job(payload, context).await;
+    record_failed_attempt(repositories.as_ref(), &task_id, &attempt, result).await
+}
+
+async fn run_render_page_job(
+    payload: RenderPageJobPayload,
+    context: Data<JobWorkerContext>,
+    task_id: TaskId,
+    attempt: Attempt,
+) -> Result<(), ApalisError> {
+    let repositories = context.repositories.clone();
+    let result = process_render_page_job(payload, context).await;
+    record_failed_attempt(repositories.as_ref(), &task_id, &attempt, result).await
+}
+
+async fn run_publish_post_job(
+    payload: PublishPostJobPayload,
+    context: Data<JobWorkerContext>,
+    task_id: TaskId,
+    attempt: Attempt,
+) -> Result<(), ApalisError> {
+    let repositories = context.repositories.clone();
+    let result = process_publish_post_job(payload, context).await;
+    record_failed_attempt(repositories.as_ref(), &task_id, &attempt, result).await
+}
+
+async fn run_publish_page_job(
+    payload: PublishPageJobPayload,
+    context: Data<JobWorkerContext>,
+    task_id: TaskId,
+    attempt: Attempt,
+) -> Result<(), ApalisError> {
+    let repositories = context.repositories.clone();
+    let result = process_publish_page_job(payload, context).await;
+    record_failed_attempt(repositories.as_ref(), &task_id, &attempt, result).await
+}
+
+async fn run_ping_sitemap_job(
+    payload: PingSitemapJobPayload,
+    context: Data<JobWorkerContext>,
+    task_id: TaskId,
+    attempt: Attempt,
+) -> Result<(), ApalisError> {
+    let result = process_ping_sitemap_job(payload).await;
+    record_failed_attempt(context.repositories.as_ref(), &task_id, &attempt, result).await
+}
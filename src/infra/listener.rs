@@ -0,0 +1,229 @@
+//! Listener sockets for `serve`: TCP, unix domain sockets, and sockets
+//! inherited through systemd socket activation.
+
+use std::io;
+use std::os::fd::{FromRawFd, IntoRawFd, RawFd};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::Path;
+
+use tokio::net::{TcpListener, UnixListener, UnixStream};
+
+use crate::config::ListenAddr;
+use crate::infra::tls::{TlsConfigHandle, TlsListener};
+
+/// First file descriptor systemd passes (`SD_LISTEN_FDS_START`).
+const LISTEN_FDS_START: RawFd = 3;
+
+/// A bound listener, ready for `axum::serve`.
+#[derive(Debug)]
+pub enum Listener {
+    Tcp(TcpListener),
+    Unix(UnixListener),
+    Tls(TlsListener),
+}
+
+impl Listener {
+    /// Bind `addr`. A unix socket file left behind by a previous run is
+    /// removed first, and `socket_mode` (if any) is applied to the new file.
+    pub async fn bind(addr: &ListenAddr, socket_mode: Option<u32>) -> io::Result<Self> {
+        match addr {
+            ListenAddr::Tcp(addr) => TcpListener::bind(addr).await.map(Self::Tcp),
+            ListenAddr::Unix(path) => {
+                remove_stale_socket(path).await?;
+                let listener = UnixListener::bind(path)?;
+                if let Some(mode) = socket_mode {
+                    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+                }
+                Ok(Self::Unix(listener))
+            }
+        }
+    }
+
+    /// Terminate TLS on this listener when `tls` is set. Only TCP listeners
+    /// can be wrapped.
+    pub fn with_tls(self, tls: Option<TlsConfigHandle>) -> io::Result<Self> {
+        match (self, tls) {
+            (listener, None) => Ok(listener),
+            (Self::Tcp(listener), Some(tls)) => TlsListener::new(listener, tls).map(Self::Tls),
+            (_, Some(tls)) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "TLS with {} needs a TCP listener",
+                    tls.cert_path().display()
+                ),
+            )),
+        }
+    }
+
+    /// Human-readable local address, for logs.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Tcp(listener) => listener
+                .local_addr()
+                .map(|addr| addr.to_string())
+                .unwrap_or_else(|_| "tcp".to_string()),
+            Self::Tls(listener) => axum::serve::Listener::local_addr(listener)
+                .map(|addr| format!("{addr} (TLS)"))
+                .unwrap_or_else(|_| "tls".to_string()),
+            Self::Unix(listener) => listener
+                .local_addr()
+                .ok()
+                .and_then(|addr| addr.as_pathname().map(|path| path.display().to_string()))
+                .map(|path| format!("{}{path}", ListenAddr::UNIX_PREFIX))
+                .unwrap_or_else(|| ListenAddr::UNIX_PREFIX.to_string()),
+        }
+    }
+}
+
+/// Refuse to replace a live socket or a regular file; remove anything else
+/// that is a socket nobody accepts on.
+async fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    if !metadata.file_type().is_socket() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} exists and is not a socket", path.display()),
+        ));
+    }
+    if UnixStream::connect(path).await.is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            format!("{} is in use by another process", path.display()),
+        ));
+    }
+    std::fs::remove_file(path)
+}
+
+/// The public and admin listeners handed over by systemd.
+///
+/// Sockets named `public` and `admin` (`FileDescriptorName=`) are matched by
+/// name; otherwise the first passed socket is public and the second admin.
+/// Call this at most once: the descriptors are owned by the returned
+/// listeners.
+pub fn systemd_listeners() -> io::Result<(Listener, Listener)> {
+    let fds = listen_fds(
+        std::env::var("LISTEN_PID").ok().as_deref(),
+        std::env::var("LISTEN_FDS").ok().as_deref(),
+        std::process::id(),
+    )?;
+    let names = std::env::var("LISTEN_FDNAMES").unwrap_or_default();
+    let (public, admin) = pick_fds(fds, &names)?;
+    Ok((from_fd(public)?, from_fd(admin)?))
+}
+
+/// Number of descriptors passed to this process, validated against
+/// `LISTEN_PID` so variables inherited from a parent are ignored.
+fn listen_fds(pid: Option<&str>, fds: Option<&str>, own_pid: u32) -> io::Result<usize> {
+    let (Some(pid), Some(fds)) = (pid, fds) else {
+        return Err(not_activated("LISTEN_PID and LISTEN_FDS are not set"));
+    };
+    if pid.trim().parse::<u32>().ok() != Some(own_pid) {
+        return Err(not_activated("LISTEN_PID names another process"));
+    }
+    fds.trim()
+        .parse::<usize>()
+        .map_err(|_| not_activated("LISTEN_FDS is not a number"))
+}
+
+/// File descriptors of the public and admin sockets.
+fn pick_fds(count: usize, names: &str) -> io::Result<(RawFd, RawFd)> {
+    if count < 2 {
+        return Err(not_activated(
+            "systemd passed fewer than two sockets; soffio needs a public and an admin socket",
+        ));
+    }
+    let names: Vec<&str> = names.split(':').collect();
+    let position = |name: &str| names.iter().take(count).position(|entry| *entry == name);
+    let (public, admin) = match (position("public"), position("admin")) {
+        (Some(public), Some(admin)) => (public, admin),
+        _ => (0, 1),
+    };
+    let fd = |index: usize| {
+        RawFd::try_from(index)
+            .ok()
+            .and_then(|index| index.checked_add(LISTEN_FDS_START))
+            .ok_or_else(|| not_activated("socket index out of range"))
+    };
+    Ok((fd(public)?, fd(admin)?))
+}
+
+fn from_fd(fd: RawFd) -> io::Result<Listener> {
+    // SAFETY: systemd passes these descriptors to this process only, and
+    // `systemd_listeners` takes ownership of each one exactly once.
+    let unix = unsafe { std::os::unix::net::UnixListener::from_raw_fd(fd) };
+    if unix.local_addr().is_ok() {
+        unix.set_nonblocking(true)?;
+        return UnixListener::from_std(unix).map(Listener::Unix);
+    }
+    // SAFETY: ownership moves from the rejected unix listener to this one.
+    let tcp = unsafe { std::net::TcpListener::from_raw_fd(unix.into_raw_fd()) };
+    tcp.local_addr()?;
+    tcp.set_nonblocking(true)?;
+    TcpListener::from_std(tcp).map(Listener::Tcp)
+}
+
+fn not_activated(reason: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("systemd socket activation unavailable: {reason}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listen_fds_requires_matching_pid() {
+        assert_eq!(listen_fds(Some("42"), Some("2"), 42).expect("fds"), 2);
+        assert!(listen_fds(Some("41"), Some("2"), 42).is_err());
+        assert!(listen_fds(None, Some("2"), 42).is_err());
+        assert!(listen_fds(Some("42"), Some("two"), 42).is_err());
+    }
+
+    #[test]
+    fn pick_fds_prefers_names_then_order() {
+        assert_eq!(pick_fds(2, "").expect("fds"), (3, 4));
+        assert_eq!(pick_fds(2, "admin:public").expect("fds"), (4, 3));
+        assert_eq!(pick_fds(3, "x:admin:public").expect("fds"), (5, 4));
+        assert!(pick_fds(1, "public").is_err());
+    }
+
+    #[tokio::test]
+    async fn binds_unix_socket_and_replaces_stale_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("public.sock");
+        let addr = ListenAddr::Unix(path.clone());
+
+        let listener = Listener::bind(&addr, Some(0o660)).await.expect("bind");
+        let mode = std::fs::metadata(&path)
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o660);
+        assert_eq!(listener.describe(), format!("unix:{}", path.display()));
+
+        let accept = async {
+            let Listener::Unix(listener) = &listener else {
+                panic!("expected a unix listener");
+            };
+            listener.accept().await.expect("accept");
+        };
+        let (_, client) = tokio::join!(accept, UnixStream::connect(&path));
+        client.expect("connect");
+
+        let error = Listener::bind(&addr, None)
+            .await
+            .expect_err("socket is live");
+        assert_eq!(error.kind(), io::ErrorKind::AddrInUse);
+
+        drop(listener);
+        Listener::bind(&addr, None)
+            .await
+            .expect("stale socket replaced");
+    }
+}
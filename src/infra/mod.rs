@@ -1,8 +1,14 @@
 //! Infrastructure adapters and runtime bootstrap.
 
 pub mod assets;
+pub mod context;
 pub mod db;
+pub mod doctor;
 pub mod error;
 pub mod http;
+pub mod job_monitor;
+pub mod listener;
+pub mod reload;
 pub mod telemetry;
+pub mod tls;
 pub mod uploads;
@@ -0,0 +1,204 @@
+//! Runtime configuration reload for `serve`.
+//!
+//! A reload re-runs [`config::load`] with the original CLI arguments, applies
+//! the settings listed in [`config::RELOADABLE_KEYS`] to the running services,
+//! reports changes to [`config::INERT_KEYS`] as having no effect, and every
+//! other change as requiring a restart.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+use crate::application::admin::audit::AdminAuditService;
+use crate::config::{self, CliArgs, LoadError, Settings, SettingsDiff};
+use crate::infra::http::{ApiRateLimiter, RateLimiter};
+use crate::infra::telemetry::LogLevelHandle;
+use crate::infra::tls::TlsConfigHandle;
+
+const TARGET: &str = "soffio::config::reload";
+
+/// Runtime handles a reload updates.
+#[derive(Clone)]
+pub struct ReloadTargets {
+    /// `None` when no reloadable subscriber is installed.
+    pub log_level: Option<LogLevelHandle>,
+    pub api_rate_limiter: Arc<dyn RateLimiter>,
+    pub comment_rate_limiter: Arc<ApiRateLimiter>,
+    pub render_preview_rate_limiter: Arc<ApiRateLimiter>,
+    pub upload_limit_bytes: Arc<AtomicU64>,
+    pub cache_consume_interval_ms: Arc<AtomicU64>,
+    /// Listener certificates, read again on every reload.
+    pub tls: Vec<TlsConfigHandle>,
+}
+
+pub struct ConfigReloader {
+    cli: CliArgs,
+    /// Settings in effect: startup values plus every change applied since.
+    running: Mutex<Settings>,
+    targets: ReloadTargets,
+    audit: Arc<AdminAuditService>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        cli: CliArgs,
+        settings: Settings,
+        targets: ReloadTargets,
+        audit: Arc<AdminAuditService>,
+    ) -> Self {
+        Self {
+            cli,
+            running: Mutex::new(settings),
+            targets,
+            audit,
+        }
+    }
+
+    /// Reload the configuration on behalf of `actor`.
+    ///
+    /// Invalid configuration leaves the running settings untouched. Deferred
+    /// changes are reported again on every reload until the process restarts.
+    /// TLS certificates are re-read from their files even when nothing else
+    /// changed, so renewed certificates apply without a restart.
+    pub async fn reload(&self, actor: &str) -> Result<SettingsDiff, LoadError> {
+        let mut running = self.running.lock().await;
+        let loaded = config::load(&self.cli)?;
+        let diff = config::diff_settings(&running, &loaded);
+
+        if !diff.applied.is_empty() {
+            self.apply(&loaded);
+        }
+        if !diff.applied.is_empty() || !diff.inert.is_empty() {
+            apply_reloadable(&mut running, &loaded);
+        }
+        drop(running);
+
+        log_diff(actor, &diff);
+        self.reload_certificates(actor);
+        if let Err(err) = self
+            .audit
+            .record(actor, "config.reload", "config", None, Some(&diff))
+            .await
+        {
+            error!(
+                target = TARGET,
+                error = %err,
+                "failed to record config reload audit entry"
+            );
+        }
+
+        Ok(diff)
+    }
+
+    fn reload_certificates(&self, actor: &str) {
+        for tls in &self.targets.tls {
+            match tls.reload() {
+                Ok(()) => info!(
+                    target = TARGET,
+                    actor,
+                    cert = %tls.cert_path().display(),
+                    "reloaded TLS certificate"
+                ),
+                Err(err) => error!(
+                    target = TARGET,
+                    actor,
+                    error = %err,
+                    "TLS certificate not reloaded; the previous one stays in use"
+                ),
+            }
+        }
+    }
+
+    fn apply(&self, settings: &Settings) {
+        let targets = &self.targets;
+
+        if let Some(handle) = &targets.log_level
+            && let Err(err) = handle.set_level(settings.logging.level)
+        {
+            warn!(target = TARGET, error = %err, "log level was not changed");
+        }
+
+        targets.api_rate_limiter.reconfigure(
+            seconds(settings.api_rate_limit.window_seconds.get()),
+            settings.api_rate_limit.max_requests.get(),
+        );
+        targets.comment_rate_limiter.reconfigure(
+            seconds(settings.comments.rate_limit_window_seconds.get()),
+            settings.comments.rate_limit_max_requests.get(),
+        );
+        targets.render_preview_rate_limiter.reconfigure(
+            seconds(settings.render.preview_rate_limit_window_seconds.get()),
+            settings.render.preview_rate_limit_max_requests.get(),
+        );
+        targets
+            .upload_limit_bytes
+            .store(settings.uploads.max_request_bytes.get(), Ordering::Relaxed);
+        targets
+            .cache_consume_interval_ms
+            .store(settings.cache.auto_consume_interval_ms, Ordering::Relaxed);
+    }
+}
+
+fn seconds(value: u32) -> Duration {
+    Duration::from_secs(u64::from(value))
+}
+
+/// Copy the reloadable and inert settings of `loaded` into `running`.
+fn apply_reloadable(running: &mut Settings, loaded: &Settings) {
+    running.logging.level = loaded.logging.level;
+    running.uploads.max_request_bytes = loaded.uploads.max_request_bytes;
+    running.rate_limit = loaded.rate_limit.clone();
+    running.api_rate_limit.window_seconds = loaded.api_rate_limit.window_seconds;
+    running.api_rate_limit.max_requests = loaded.api_rate_limit.max_requests;
+    running.comments.rate_limit_window_seconds = loaded.comments.rate_limit_window_seconds;
+    running.comments.rate_limit_max_requests = loaded.comments.rate_limit_max_requests;
+    running.render.preview_rate_limit_window_seconds =
+        loaded.render.preview_rate_limit_window_seconds;
+    running.render.preview_rate_limit_max_requests = loaded.render.preview_rate_limit_max_requests;
+    running.cache.auto_consume_interval_ms = loaded.cache.auto_consume_interval_ms;
+}
+
+fn log_diff(actor: &str, diff: &SettingsDiff) {
+    if diff.is_empty() {
+        info!(
+            target = TARGET,
+            actor, "configuration reloaded; nothing changed"
+        );
+        return;
+    }
+    for change in &diff.applied {
+        info!(
+            target = TARGET,
+            actor,
+            key = change.key,
+            old = %change.old,
+            new = %change.new,
+            "applied configuration change"
+        );
+    }
+    for change in &diff.deferred {
+        warn!(
+            target = TARGET,
+            actor,
+            key = change.key,
+            old = %change.old,
+            new = %change.new,
+            "configuration change requires a restart"
+        );
+    }
+    for change in &diff.inert {
+        warn!(
+            target = TARGET,
+            actor,
+            key = change.key,
+            old = %change.old,
+            new = %change.new,
+            "configuration setting has no runtime effect"
+        );
+    }
+}
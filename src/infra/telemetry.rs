@@ -1,26 +1,105 @@
-use std::sync::Once;
+use std::{
+    collections::HashMap,
+    net::{TcpStream, ToSocketAddrs},
+    sync::Once,
+    thread,
+    time::Duration,
+};
 
 use metrics::{Unit, describe_counter, describe_gauge, describe_histogram};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig, WithHttpConfig};
+use opentelemetry_sdk::{
+    Resource,
+    trace::{Sampler, SdkTracerProvider},
+};
+use tracing::{Subscriber, level_filters::LevelFilter, warn};
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{
-    EnvFilter, fmt,
+    EnvFilter, Registry, fmt,
     layer::{Layer, SubscriberExt},
+    registry::LookupSpan,
+    reload,
     util::SubscriberInitExt,
 };
+use url::Url;
 
-use crate::config::{LogFormat, LoggingSettings};
+use crate::config::{LogFormat, LoggingSettings, OtlpSettings};
 
 use super::error::InfraError;
 
 static METRIC_DESCRIPTIONS: Once = Once::new();
 
+const TRACER_NAME: &str = "soffio";
+const OTLP_EXPORT_TIMEOUT: Duration = Duration::from_secs(5);
+const OTLP_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Keeps the trace exporter alive; call [`TelemetryGuard::shutdown`] before exiting.
+#[must_use = "call `shutdown` to flush pending spans"]
+pub struct TelemetryGuard {
+    tracer_provider: Option<SdkTracerProvider>,
+    log_level: LogLevelHandle,
+}
+
+impl TelemetryGuard {
+    /// Handle for changing the log level of the installed subscriber.
+    pub fn log_level(&self) -> LogLevelHandle {
+        self.log_level.clone()
+    }
+
+    /// Flush pending spans and stop the exporter, waiting at most `timeout`.
+    ///
+    /// Blocks the calling thread; run it off the async runtime.
+    pub fn shutdown(self, timeout: Duration) {
+        let Some(provider) = self.tracer_provider else {
+            return;
+        };
+        if let Err(err) = provider.shutdown_with_timeout(timeout) {
+            warn!(
+                target = "soffio::telemetry",
+                error = %err,
+                "failed to flush pending spans"
+            );
+        }
+    }
+}
+
+/// Changes the default log level at runtime. Directives from `RUST_LOG` keep
+/// taking precedence, as they do at startup.
+#[derive(Clone)]
+pub struct LogLevelHandle(reload::Handle<EnvFilter, Registry>);
+
+impl LogLevelHandle {
+    pub fn set_level(&self, level: LevelFilter) -> Result<(), InfraError> {
+        self.0
+            .reload(env_filter(level))
+            .map_err(|err| InfraError::telemetry(format!("failed to change log level: {err}")))
+    }
+}
+
+/// The level filter layer [`init`] installs, with a handle to change it.
+pub fn reloadable_filter(
+    level: LevelFilter,
+) -> (reload::Layer<EnvFilter, Registry>, LogLevelHandle) {
+    let (layer, handle) = reload::Layer::new(env_filter(level));
+    (layer, LogLevelHandle(handle))
+}
+
+fn env_filter(level: LevelFilter) -> EnvFilter {
+    EnvFilter::builder()
+        .with_default_directive(level.into())
+        .from_env_lossy()
+}
+
 /// Install a global tracing subscriber using the provided logging settings.
-pub fn init(logging: &LoggingSettings) -> Result<(), InfraError> {
+///
+/// When OTLP export is configured an OpenTelemetry layer is added next to the
+/// log output. Exporter problems are reported as warnings and never abort
+/// startup.
+pub fn init(logging: &LoggingSettings) -> Result<TelemetryGuard, InfraError> {
     describe_metrics();
 
-    let env_filter = EnvFilter::builder()
-        .with_default_directive(logging.level.into())
-        .from_env_lossy();
+    let (env_filter, log_level) = reloadable_filter(logging.level);
 
     let fmt_layer = match logging.format {
         LogFormat::Json => fmt::layer()
@@ -32,14 +111,93 @@ pub fn init(logging: &LoggingSettings) -> Result<(), InfraError> {
         LogFormat::Compact => fmt::layer().compact().with_target(true).boxed(),
     };
 
+    let (tracer_provider, otlp_error) = match logging.otlp.as_ref().map(build_tracer_provider) {
+        Some(Ok(provider)) => (Some(provider), None),
+        Some(Err(err)) => (None, Some(err)),
+        None => (None, None),
+    };
+
     tracing_subscriber::registry()
         .with(env_filter)
         .with(ErrorLayer::default())
         .with(fmt_layer)
+        .with(tracer_provider.as_ref().map(otel_layer))
         .try_init()
         .map_err(|err| {
             InfraError::telemetry(format!("failed to install tracing subscriber: {err}"))
-        })
+        })?;
+
+    if let Some(err) = otlp_error {
+        warn!(
+            target = "soffio::telemetry",
+            error = %err,
+            "OTLP trace export disabled"
+        );
+    } else if let Some(otlp) = logging.otlp.as_ref() {
+        spawn_collector_probe(otlp.endpoint.clone());
+    }
+
+    Ok(TelemetryGuard {
+        tracer_provider,
+        log_level,
+    })
+}
+
+fn build_tracer_provider(otlp: &OtlpSettings) -> Result<SdkTracerProvider, InfraError> {
+    let headers: HashMap<String, String> = otlp.headers.iter().cloned().collect();
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(otlp.endpoint.clone())
+        .with_headers(headers)
+        .with_timeout(OTLP_EXPORT_TIMEOUT)
+        .build()
+        .map_err(|err| InfraError::telemetry(format!("failed to build OTLP exporter: {err}")))?;
+
+    let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(otlp.sample_ratio)));
+    let resource = Resource::builder()
+        .with_service_name(otlp.service_name.clone())
+        .build();
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(sampler)
+        .with_resource(resource)
+        .build())
+}
+
+fn otel_layer<S>(provider: &SdkTracerProvider) -> impl Layer<S> + Send + Sync + use<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span> + Send + Sync,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(TRACER_NAME))
+}
+
+/// Warn once if the collector cannot be reached; spans are still buffered and
+/// exported if it comes up later.
+fn spawn_collector_probe(endpoint: String) {
+    thread::spawn(move || {
+        let Some(target) = Url::parse(&endpoint).ok().and_then(|url| {
+            let host = url.host_str()?.to_string();
+            let port = url.port_or_known_default()?;
+            Some((host, port))
+        }) else {
+            return;
+        };
+
+        let reachable = target
+            .to_socket_addrs()
+            .map(|mut addrs| {
+                addrs.any(|addr| TcpStream::connect_timeout(&addr, OTLP_PROBE_TIMEOUT).is_ok())
+            })
+            .unwrap_or(false);
+        if !reachable {
+            warn!(
+                target = "soffio::telemetry",
+                endpoint = %endpoint,
+                "OTLP collector is unreachable; spans will be dropped until it is available"
+            );
+        }
+    });
 }
 
 fn describe_metrics() {
@@ -96,3 +254,32 @@ fn describe_metrics() {
         );
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn otlp_layer_installs_when_collector_is_unreachable() {
+        let settings = OtlpSettings {
+            endpoint: "http://127.0.0.1:9/v1/traces".to_string(),
+            service_name: "soffio-test".to_string(),
+            sample_ratio: 1.0,
+            headers: vec![("authorization".to_string(), "Bearer test".to_string())],
+        };
+        let provider = build_tracer_provider(&settings).expect("exporter builds offline");
+
+        let subscriber = tracing_subscriber::registry().with(otel_layer(&provider));
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("smoke");
+            let _entered = span.enter();
+            tracing::info!("inside span");
+        });
+
+        TelemetryGuard {
+            tracer_provider: Some(provider),
+            log_level: reloadable_filter(LevelFilter::INFO).1,
+        }
+        .shutdown(Duration::from_millis(500));
+    }
+}
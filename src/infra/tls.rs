@@ -0,0 +1,450 @@
+//! TLS termination for the HTTP listeners.
+//!
+//! Certificates are loaded into a [`TlsConfigHandle`], which a configuration
+//! reload swaps in place; connections accepted afterwards use the new
+//! certificate while established ones keep theirs.
+
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use axum::{
+    Router,
+    extract::Request,
+    http::{StatusCode, header},
+    response::{IntoResponse, Redirect, Response},
+};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::{
+    RootCertStore, ServerConfig,
+    crypto::{CryptoProvider, ring},
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+    server::WebPkiClientVerifier,
+};
+use tokio_rustls::server::TlsStream;
+use tracing::{debug, warn};
+
+use crate::config::TlsSettings;
+
+/// Slow or stalled handshakes are dropped after this long.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Completed handshakes waiting for the server to pick them up.
+const ACCEPT_BACKLOG: usize = 64;
+
+/// A TLS file that could not be used. The message names the file.
+#[derive(Debug, thiserror::Error)]
+#[error("TLS {what} {}: {reason}", path.display())]
+pub struct TlsError {
+    what: &'static str,
+    path: PathBuf,
+    reason: String,
+}
+
+impl TlsError {
+    fn new(what: &'static str, path: &Path, reason: impl ToString) -> Self {
+        Self {
+            what,
+            path: path.to_path_buf(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// The server configuration a TLS listener uses, reloadable from its files.
+#[derive(Clone)]
+pub struct TlsConfigHandle {
+    settings: TlsSettings,
+    current: Arc<RwLock<Arc<ServerConfig>>>,
+}
+
+impl TlsConfigHandle {
+    /// Read and validate the files named by `settings`.
+    pub fn load(settings: &TlsSettings) -> Result<Self, TlsError> {
+        let config = server_config(settings, OffsetDateTime::now_utc())?;
+        Ok(Self {
+            settings: settings.clone(),
+            current: Arc::new(RwLock::new(Arc::new(config))),
+        })
+    }
+
+    /// Read the files again. On error the previous configuration stays.
+    pub fn reload(&self) -> Result<(), TlsError> {
+        let config = server_config(&self.settings, OffsetDateTime::now_utc())?;
+        let mut current = self
+            .current
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *current = Arc::new(config);
+        Ok(())
+    }
+
+    pub fn cert_path(&self) -> &Path {
+        &self.settings.cert_path
+    }
+
+    pub fn current(&self) -> Arc<ServerConfig> {
+        self.current
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(ring::default_provider())
+}
+
+fn server_config(settings: &TlsSettings, now: OffsetDateTime) -> Result<ServerConfig, TlsError> {
+    let cert_path = settings.cert_path.as_path();
+    let certs = read_certs("certificate", cert_path)?;
+    check_validity(cert_path, &certs[0], now)?;
+
+    let key_path = settings.key_path.as_path();
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|err| TlsError::new("private key", key_path, err))?;
+
+    let builder = ServerConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(|err| TlsError::new("certificate", cert_path, err))?;
+    let builder = match settings.client_ca_path.as_deref() {
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in read_certs("client CA", ca_path)? {
+                roots
+                    .add(cert)
+                    .map_err(|err| TlsError::new("client CA", ca_path, err))?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider())
+                .build()
+                .map_err(|err| TlsError::new("client CA", ca_path, err))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config = builder.with_single_cert(certs, key).map_err(|err| {
+        TlsError::new(
+            "private key",
+            key_path,
+            format!("does not match {}: {err}", cert_path.display()),
+        )
+    })?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// At least one certificate from a PEM file.
+fn read_certs(what: &'static str, path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .map_err(|err| TlsError::new(what, path, err))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| TlsError::new(what, path, err))?;
+    if certs.is_empty() {
+        return Err(TlsError::new(what, path, "no PEM certificates found"));
+    }
+    Ok(certs)
+}
+
+fn check_validity(
+    path: &Path,
+    cert: &CertificateDer<'_>,
+    now: OffsetDateTime,
+) -> Result<(), TlsError> {
+    let Some((not_before, not_after)) = validity(cert) else {
+        return Err(TlsError::new(
+            "certificate",
+            path,
+            "cannot read the validity period",
+        ));
+    };
+    if now < not_before {
+        return Err(TlsError::new(
+            "certificate",
+            path,
+            format!("not valid before {not_before}"),
+        ));
+    }
+    if now > not_after {
+        return Err(TlsError::new(
+            "certificate",
+            path,
+            format!("expired at {not_after}"),
+        ));
+    }
+    Ok(())
+}
+
+/// `notBefore` and `notAfter` of a DER-encoded X.509 certificate.
+fn validity(cert: &[u8]) -> Option<(OffsetDateTime, OffsetDateTime)> {
+    let (_, certificate, _) = der_element(cert)?;
+    let (_, tbs, _) = der_element(certificate)?;
+    // An explicit `[0]` version precedes the serial number in v3 certificates.
+    let (tag, _, mut rest) = der_element(tbs)?;
+    if tag == 0xa0 {
+        (_, _, rest) = der_element(rest)?;
+    }
+    let (_, _, rest) = der_element(rest)?; // signature algorithm
+    let (_, _, rest) = der_element(rest)?; // issuer
+    let (_, validity, _) = der_element(rest)?;
+    let (before_tag, not_before, rest) = der_element(validity)?;
+    let (after_tag, not_after, _) = der_element(rest)?;
+    Some((
+        der_time(before_tag, not_before)?,
+        der_time(after_tag, not_after)?,
+    ))
+}
+
+/// Split one DER element off `input`: tag, contents and the remainder.
+fn der_element(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = if first < 0x80 {
+        (usize::from(first), rest)
+    } else {
+        let count = usize::from(first & 0x7f);
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
+        }
+        let (bytes, rest) = rest.split_at(count);
+        let len = bytes
+            .iter()
+            .fold(0usize, |len, byte| (len << 8) | usize::from(*byte));
+        (len, rest)
+    };
+    if rest.len() < len {
+        return None;
+    }
+    let (contents, rest) = rest.split_at(len);
+    Some((tag, contents, rest))
+}
+
+/// `UTCTime` (`YYMMDDHHMMSSZ`) or `GeneralizedTime` (`YYYYMMDDHHMMSSZ`).
+fn der_time(tag: u8, value: &[u8]) -> Option<OffsetDateTime> {
+    let text = std::str::from_utf8(value).ok()?.strip_suffix('Z')?;
+    let (year, rest) = match tag {
+        0x17 => {
+            let year: i32 = text.get(..2)?.parse().ok()?;
+            let year = if year < 50 { 2000 + year } else { 1900 + year };
+            (year, text.get(2..)?)
+        }
+        0x18 => (text.get(..4)?.parse().ok()?, text.get(4..)?),
+        _ => return None,
+    };
+    let field = |start: usize| rest.get(start..start + 2)?.parse::<u8>().ok();
+    let date = Date::from_calendar_date(year, Month::try_from(field(0)?).ok()?, field(2)?).ok()?;
+    let time = Time::from_hms(field(4)?, field(6)?, field(8)?).ok()?;
+    Some(PrimitiveDateTime::new(date, time).assume_utc())
+}
+
+/// A TCP listener that completes TLS handshakes before handing connections
+/// to `axum::serve`. Handshakes run concurrently, so a slow client does not
+/// hold up the others.
+pub struct TlsListener {
+    local_addr: SocketAddr,
+    accepted: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+    acceptor: JoinHandle<()>,
+}
+
+impl TlsListener {
+    pub fn new(listener: TcpListener, tls: TlsConfigHandle) -> io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let (sender, accepted) = mpsc::channel(ACCEPT_BACKLOG);
+        let acceptor = tokio::spawn(accept_loop(listener, tls, sender));
+        Ok(Self {
+            local_addr,
+            accepted,
+            acceptor,
+        })
+    }
+}
+
+impl Drop for TlsListener {
+    fn drop(&mut self) {
+        self.acceptor.abort();
+    }
+}
+
+impl std::fmt::Debug for TlsListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsListener")
+            .field("local_addr", &self.local_addr)
+            .finish_non_exhaustive()
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.accepted.recv().await {
+            Some(connection) => connection,
+            // The accept loop only ends when the listener is dropped.
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    tls: TlsConfigHandle,
+    sender: mpsc::Sender<(TlsStream<TcpStream>, SocketAddr)>,
+) {
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(err) => {
+                // Usually out of file descriptors; back off like axum does.
+                warn!(target = "soffio::tls", error = %err, "accept failed");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+        let acceptor = TlsAcceptor::from(tls.current());
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(stream)) => {
+                    let _ = sender.send((stream, peer)).await;
+                }
+                Ok(Err(err)) => {
+                    debug!(target = "soffio::tls", %peer, error = %err, "TLS handshake failed");
+                }
+                Err(_) => {
+                    debug!(target = "soffio::tls", %peer, "TLS handshake timed out");
+                }
+            }
+        });
+    }
+}
+
+/// Router for the plain-HTTP redirect listener: every request gets a
+/// permanent redirect to the same host and path over HTTPS on `https_port`.
+pub fn https_redirect_router(https_port: u16) -> Router {
+    Router::new()
+        .fallback(move |request: Request| async move { redirect_to_https(&request, https_port) })
+}
+
+fn redirect_to_https(request: &Request, https_port: u16) -> Response {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok());
+    let Some(host) = host else {
+        return (StatusCode::BAD_REQUEST, "missing Host header").into_response();
+    };
+    let path = request
+        .uri()
+        .path_and_query()
+        .map_or("/", |path| path.as_str());
+    Redirect::permanent(&https_location(host, path, https_port)).into_response()
+}
+
+fn https_location(host: &str, path: &str, https_port: u16) -> String {
+    // Drop any port from the Host header, minding bracketed IPv6 literals.
+    let hostname = match host.rfind(':') {
+        Some(index) if !host[index..].contains(']') => &host[..index],
+        _ => host,
+    };
+    if https_port == 443 {
+        format!("https://{hostname}{path}")
+    } else {
+        format!("https://{hostname}:{https_port}{path}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    fn settings(cert: &str, key: &str) -> TlsSettings {
+        TlsSettings {
+            cert_path: fixture(cert),
+            key_path: fixture(key),
+            client_ca_path: None,
+        }
+    }
+
+    #[test]
+    fn validity_is_read_from_utc_and_generalized_times() {
+        let cert = read_certs("certificate", &fixture("cert.pem")).expect("cert");
+        let (not_before, not_after) = validity(&cert[0]).expect("validity");
+        assert!(not_before.year() >= 2026);
+        assert_eq!(not_after.year(), not_before.year() + 100);
+
+        let expired = read_certs("certificate", &fixture("expired-cert.pem")).expect("cert");
+        let (_, not_after) = validity(&expired[0]).expect("validity");
+        assert_eq!(not_after, time::macros::datetime!(2020-02-01 00:00:00 UTC));
+    }
+
+    #[test]
+    fn load_errors_name_the_offending_file() {
+        let err = TlsConfigHandle::load(&settings("expired-cert.pem", "expired-key.pem"))
+            .err()
+            .expect("expired certificate");
+        let message = err.to_string();
+        assert!(message.contains("expired-cert.pem"), "{message}");
+        assert!(message.contains("expired at"), "{message}");
+
+        let err = TlsConfigHandle::load(&settings("cert.pem", "missing-key.pem"))
+            .err()
+            .expect("missing key");
+        assert!(err.to_string().contains("missing-key.pem"));
+
+        let err = TlsConfigHandle::load(&settings("cert.pem", "expired-key.pem"))
+            .err()
+            .expect("mismatched key");
+        assert!(err.to_string().contains("does not match"));
+    }
+
+    #[test]
+    fn failed_reload_keeps_the_previous_certificate() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let tls = TlsSettings {
+            cert_path: dir.path().join("cert.pem"),
+            key_path: dir.path().join("key.pem"),
+            client_ca_path: None,
+        };
+        std::fs::copy(fixture("cert.pem"), &tls.cert_path).expect("copy cert");
+        std::fs::copy(fixture("key.pem"), &tls.key_path).expect("copy key");
+        let handle = TlsConfigHandle::load(&tls).expect("load");
+        let before = handle.current();
+
+        std::fs::copy(fixture("expired-cert.pem"), &tls.cert_path).expect("copy cert");
+        assert!(handle.reload().is_err());
+        assert!(Arc::ptr_eq(&before, &handle.current()));
+
+        std::fs::copy(fixture("cert.pem"), &tls.cert_path).expect("copy cert");
+        handle.reload().expect("reload");
+        assert!(!Arc::ptr_eq(&before, &handle.current()));
+    }
+
+    #[test]
+    fn redirect_location_keeps_host_and_path() {
+        assert_eq!(
+            https_location("example.com:8080", "/posts?page=2", 443),
+            "https://example.com/posts?page=2"
+        );
+        assert_eq!(
+            https_location("[::1]:8080", "/", 8443),
+            "https://[::1]:8443/"
+        );
+        assert_eq!(https_location("[::1]", "/", 443), "https://[::1]/");
+    }
+}
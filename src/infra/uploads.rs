@@ -9,7 +9,10 @@ use futures::{StreamExt, pin_mut, stream};
 use sha2::{Digest, Sha256};
 use slug::slugify;
 use thiserror::Error;
-use tokio::{fs, io::AsyncWriteExt};
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncWriteExt},
+};
 use uuid::Uuid;
 
 /// Errors that can occur while interacting with the upload storage backend.
@@ -33,6 +36,8 @@ pub enum UploadStorageError {
     EmptyPayload,
     #[error("uploaded file size exceeds supported range")]
     SizeOverflow,
+    #[error("checksum mismatch: expected {expected}, found {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
 }
 
 /// Result of storing an upload payload.
@@ -43,6 +48,18 @@ pub struct StoredUpload {
     pub size_bytes: i64,
 }
 
+/// Outcome of copying a stored upload between storages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyOutcome {
+    /// The file was written and its checksum verified.
+    Copied,
+    /// The destination already held the file with a matching checksum.
+    AlreadyPresent,
+}
+
+/// Buffer size used when streaming files between storages.
+const COPY_CHUNK_BYTES: usize = 64 * 1024;
+
 /// Filesystem-backed upload storage.
 #[derive(Debug)]
 pub struct UploadStorage {
@@ -56,9 +73,15 @@ impl UploadStorage {
         Ok(Self { root })
     }
 
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
     /// Store the provided payload and return metadata describing the stored asset.
     ///
-    /// The payload is streamed to disk to avoid buffering large files in memory.
+    /// The payload is streamed to a temporary file, hashed as it arrives, and
+    /// moved into place only once the stream has ended; on any error the
+    /// temporary file is removed and nothing is left behind.
     pub async fn store_stream<S>(
         &self,
         original_name: &str,
@@ -74,47 +97,20 @@ impl UploadStorage {
             fs::create_dir_all(parent).await?;
         }
 
-        let mut file = fs::File::create(&absolute).await?;
-        let mut hasher = Sha256::new();
-        let mut total_bytes: u64 = 0;
-        let mut saw_payload = false;
-
-        pin_mut!(stream);
-        while let Some(chunk_result) = stream.next().await {
-            let chunk = match chunk_result {
-                Ok(chunk) => chunk,
-                Err(err) => {
-                    drop(file);
-                    let _ = fs::remove_file(&absolute).await;
-                    return Err(err);
-                }
-            };
-
-            if chunk.is_empty() {
-                continue;
+        let partial = partial_path(&absolute);
+        let (checksum, size_bytes) = match stream_to_file(stream, &partial).await {
+            Ok(written) => written,
+            Err(err) => {
+                let _ = fs::remove_file(&partial).await;
+                return Err(err);
             }
+        };
 
-            saw_payload = true;
-            total_bytes = total_bytes
-                .checked_add(chunk.len() as u64)
-                .ok_or(UploadStorageError::SizeOverflow)?;
-            file.write_all(&chunk).await?;
-            hasher.update(&chunk);
+        if let Err(err) = fs::rename(&partial, &absolute).await {
+            let _ = fs::remove_file(&partial).await;
+            return Err(err.into());
         }
 
-        file.flush().await?;
-
-        if !saw_payload {
-            drop(file);
-            let _ = fs::remove_file(&absolute).await;
-            return Err(UploadStorageError::EmptyPayload);
-        }
-
-        let digest = hasher.finalize();
-        let checksum = hex_from_bytes(&digest);
-        let size_bytes =
-            i64::try_from(total_bytes).map_err(|_| UploadStorageError::SizeOverflow)?;
-
         Ok(StoredUpload {
             stored_path,
             checksum,
@@ -139,6 +135,12 @@ impl UploadStorage {
         Ok(Bytes::from(data))
     }
 
+    /// Hex SHA-256 of the stored payload, read in fixed-size chunks.
+    pub async fn checksum(&self, stored_path: &str) -> Result<String, UploadStorageError> {
+        let absolute = self.resolve(stored_path)?;
+        file_checksum(&absolute).await
+    }
+
     /// Remove the stored payload. Missing files are treated as success.
     pub async fn delete(&self, stored_path: &str) -> Result<(), UploadStorageError> {
         let absolute = self.resolve(stored_path)?;
@@ -149,6 +151,50 @@ impl UploadStorage {
         }
     }
 
+    /// Copy `stored_path` from `source` into this storage, streaming the bytes
+    /// and verifying them against `expected_checksum`.
+    ///
+    /// A destination file whose checksum already matches is left untouched.
+    /// Otherwise the copy goes to a temporary file that replaces the
+    /// destination only once its checksum matches.
+    pub async fn copy_from(
+        &self,
+        source: &UploadStorage,
+        stored_path: &str,
+        expected_checksum: &str,
+    ) -> Result<CopyOutcome, UploadStorageError> {
+        let from = source.resolve(stored_path)?;
+        let to = self.resolve(stored_path)?;
+
+        if fs::try_exists(&to).await? && file_checksum(&to).await? == expected_checksum {
+            return Ok(CopyOutcome::AlreadyPresent);
+        }
+
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let partial = partial_path(&to);
+
+        let actual = match stream_copy(&from, &partial).await {
+            Ok(actual) => actual,
+            Err(err) => {
+                let _ = fs::remove_file(&partial).await;
+                return Err(err);
+            }
+        };
+        if actual != expected_checksum {
+            let _ = fs::remove_file(&partial).await;
+            return Err(UploadStorageError::ChecksumMismatch {
+                expected: expected_checksum.to_string(),
+                actual,
+            });
+        }
+
+        fs::rename(&partial, &to).await?;
+        Ok(CopyOutcome::Copied)
+    }
+
     /// Obtain the absolute filesystem path for a stored upload.
     pub fn absolute_path(&self, stored_path: &str) -> Result<PathBuf, UploadStorageError> {
         self.resolve(stored_path)
@@ -200,6 +246,84 @@ fn sanitize_filename(original: &str) -> String {
     }
 }
 
+/// Sibling of `path` that receives bytes until they are complete.
+fn partial_path(path: &Path) -> PathBuf {
+    let mut partial = path.as_os_str().to_os_string();
+    partial.push(".partial");
+    PathBuf::from(partial)
+}
+
+/// Write `stream` to `to`, returning the hex SHA-256 and size of the bytes
+/// written. Leaves `to` in place on error; the caller removes it.
+async fn stream_to_file<S>(stream: S, to: &Path) -> Result<(String, i64), UploadStorageError>
+where
+    S: futures::Stream<Item = Result<Bytes, UploadStorageError>>,
+{
+    let mut file = fs::File::create(to).await?;
+    let mut hasher = Sha256::new();
+    let mut total_bytes: u64 = 0;
+
+    pin_mut!(stream);
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        if chunk.is_empty() {
+            continue;
+        }
+
+        total_bytes = total_bytes
+            .checked_add(chunk.len() as u64)
+            .ok_or(UploadStorageError::SizeOverflow)?;
+        file.write_all(&chunk).await?;
+        hasher.update(&chunk);
+    }
+
+    file.flush().await?;
+
+    if total_bytes == 0 {
+        return Err(UploadStorageError::EmptyPayload);
+    }
+
+    let size_bytes = i64::try_from(total_bytes).map_err(|_| UploadStorageError::SizeOverflow)?;
+    Ok((hex_from_bytes(&hasher.finalize()), size_bytes))
+}
+
+/// Copy `from` to `to` in fixed-size chunks, returning the hex SHA-256 of the
+/// bytes written.
+async fn stream_copy(from: &Path, to: &Path) -> Result<String, UploadStorageError> {
+    let mut reader = fs::File::open(from).await?;
+    let mut writer = fs::File::create(to).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; COPY_CHUNK_BYTES];
+
+    loop {
+        let read = reader.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..read]).await?;
+        hasher.update(&buffer[..read]);
+    }
+
+    writer.flush().await?;
+    Ok(hex_from_bytes(&hasher.finalize()))
+}
+
+async fn file_checksum(path: &Path) -> Result<String, UploadStorageError> {
+    let mut reader = fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; COPY_CHUNK_BYTES];
+
+    loop {
+        let read = reader.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hex_from_bytes(&hasher.finalize()))
+}
+
 fn hex_from_bytes(bytes: &[u8]) -> String {
     let mut output = String::with_capacity(bytes.len() * 2);
     for byte in bytes {
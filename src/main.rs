@@ -9,6 +9,10 @@ use soffio::{
 use tracing::{Dispatch, Level, dispatcher, error};
 use tracing_subscriber::fmt as tracing_fmt;
 
+#[path = "main/admin.rs"]
+mod admin;
+#[path = "main/doctor.rs"]
+mod doctor;
 #[path = "main/import_export.rs"]
 mod import_export;
 mod migrations_tool;
@@ -16,12 +20,18 @@ mod migrations_tool;
 mod renderall;
 #[path = "main/serve.rs"]
 mod serve;
+#[path = "main/uploads.rs"]
+mod uploads;
 
 #[tokio::main]
 async fn main() {
-    if let Err(error) = run().await {
-        report_application_error(&error);
-        process::exit(1);
+    match run().await {
+        Ok(0) => {}
+        Ok(code) => process::exit(code),
+        Err(error) => {
+            report_application_error(&error);
+            process::exit(1);
+        }
     }
 }
 
@@ -38,25 +48,56 @@ fn report_application_error(error: &AppError) {
     });
 }
 
-async fn run() -> Result<(), AppError> {
+/// Returns the process exit code; only `doctor` reports a non-zero one
+/// without an error.
+async fn run() -> Result<i32, AppError> {
     let (cli_args, settings) = config::load_with_cli()
         .map_err(|err| AppError::unexpected(format!("failed to load configuration: {err}")))?;
+    let config_file = cli_args.config_file.clone();
+    let reload_cli = cli_args.clone();
 
     let command = cli_args
         .command
         .unwrap_or(config::Command::Serve(Box::<config::ServeArgs>::default()));
 
-    telemetry::init(&settings.logging).map_err(AppError::from)?;
+    let telemetry = telemetry::init(&settings.logging).map_err(AppError::from)?;
+    let reload = serve::ReloadSource {
+        cli: reload_cli,
+        log_level: Some(telemetry.log_level()),
+        tls: Vec::new(),
+    };
+    let flush_timeout = settings.server.graceful_shutdown;
     configure_render_service(RenderPipelineConfig::from(&settings.render))
         .map_err(|err| AppError::unexpected(err.to_string()))?;
 
-    match command {
-        config::Command::Serve(_) => serve::run_serve(settings).await,
-        config::Command::RenderAll(args) => renderall::run_renderall(settings, args).await,
+    let mut exit_code = 0;
+    let result = match command {
+        config::Command::Serve(_) => serve::run_serve(settings, reload).await,
+        config::Command::RenderAll(args) => renderall::run_renderall(settings, args, reload).await,
         config::Command::ExportSite(args) => import_export::run_export_site(settings, args).await,
+        config::Command::ExportStatic(args) => {
+            import_export::run_export_static(settings, args, reload).await
+        }
         config::Command::ImportSite(args) => import_export::run_import_site(settings, args).await,
+        config::Command::ImportMarkdown(args) => {
+            import_export::run_import_markdown(settings, args, reload).await
+        }
         config::Command::Migrations(args) => import_export::run_migrations(settings, args).await,
+        config::Command::Admin(args) => admin::run_admin(settings, args).await,
+        config::Command::Uploads(args) => uploads::run_uploads(settings, args).await,
+        config::Command::Doctor(args) => {
+            doctor::run_doctor(settings, config_file.as_deref(), *args)
+                .await
+                .map(|code| exit_code = code)
+        }
+    };
+
+    // Flush buffered spans off the runtime so a slow collector cannot stall it.
+    if let Err(err) = tokio::task::spawn_blocking(move || telemetry.shutdown(flush_timeout)).await {
+        error!(error = %err, "telemetry shutdown task failed");
     }
+
+    result.map(|()| exit_code)
 }
 
 #[cfg(test)]
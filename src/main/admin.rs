@@ -0,0 +1,66 @@
+use std::io::{self, BufRead};
+use std::sync::Arc;
+
+use soffio::{
+    application::admin::auth::{
+        AdminAuthError, AdminAuthService, AdminSessionKeys, CreateAdminUserCommand,
+    },
+    application::error::AppError,
+    application::repos::AdminUsersRepo,
+    config,
+};
+use tracing::info;
+
+use crate::serve::init_repositories;
+
+pub(super) async fn run_admin(
+    settings: config::Settings,
+    args: config::AdminArgs,
+) -> Result<(), AppError> {
+    match args.command {
+        config::AdminCommand::CreateUser(cmd) => create_user(settings, cmd).await,
+    }
+}
+
+async fn create_user(
+    settings: config::Settings,
+    args: config::AdminCreateUserArgs,
+) -> Result<(), AppError> {
+    let password = match args.password {
+        Some(password) => password,
+        None => read_password_from_stdin()?,
+    };
+
+    let http_repositories = init_repositories(&settings).await?.http;
+    let users: Arc<dyn AdminUsersRepo> = http_repositories;
+    let service = AdminAuthService::new(users, AdminSessionKeys::from(&settings.admin_auth));
+
+    let user = service
+        .create_user(CreateAdminUserCommand {
+            username: args.username,
+            password,
+        })
+        .await
+        .map_err(|err| match err {
+            AdminAuthError::ConstraintViolation(_) | AdminAuthError::DuplicateUsername(_) => {
+                AppError::validation(err.to_string())
+            }
+            other => AppError::unexpected(other.to_string()),
+        })?;
+
+    info!(
+        target = "soffio::admin",
+        username = %user.username,
+        "Admin user created"
+    );
+    Ok(())
+}
+
+fn read_password_from_stdin() -> Result<String, AppError> {
+    let mut line = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(|err| AppError::unexpected(format!("failed to read password: {err}")))?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
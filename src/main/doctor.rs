@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_json::json;
+use soffio::{
+    application::error::AppError,
+    config::{self, ConfigSource},
+    infra::{
+        db::{ConnectionLimits, PostgresRepositories},
+        doctor::{self, CheckLevel, CheckResult},
+    },
+};
+
+/// Run every check and print the report. The returned exit code is 0 when
+/// all checks pass, 2 when the worst result is a warning and 1 on failure.
+pub(super) async fn run_doctor(
+    settings: config::Settings,
+    config_file: Option<&Path>,
+    args: config::DoctorArgs,
+) -> Result<i32, AppError> {
+    let mut checks = Vec::new();
+
+    let sources = match config::config_sources(config_file) {
+        Ok(sources) => {
+            checks.push(config_result(&sources));
+            sources
+        }
+        Err(err) => {
+            checks.push(CheckResult::fail(
+                "config",
+                err.to_string(),
+                "fix the configuration file named in the error",
+            ));
+            Vec::new()
+        }
+    };
+
+    checks.extend(database_results(&settings).await);
+    checks.push(doctor::check_uploads(&settings.uploads.directory).await);
+    checks.push(doctor::check_mermaid(&settings.render.mermaid_cli_path).await);
+    checks.push(doctor::check_cache(&settings.cache));
+    if settings.server.systemd_socket {
+        checks.push(CheckResult::pass(
+            "listeners",
+            "provided by systemd socket activation",
+        ));
+    } else {
+        checks.push(doctor::check_listener("public_listener", &settings.server.public_addr).await);
+        checks.push(doctor::check_listener("admin_listener", &settings.server.admin_addr).await);
+    }
+
+    let level = doctor::worst_level(&checks);
+    if args.output == "json" {
+        print_json(&sources, &checks, level);
+    } else {
+        print_text(&sources, &checks, level);
+    }
+
+    Ok(match level {
+        CheckLevel::Pass => 0,
+        CheckLevel::Warn => 2,
+        CheckLevel::Fail => 1,
+    })
+}
+
+fn config_result(sources: &[ConfigSource]) -> CheckResult {
+    let files = sources
+        .iter()
+        .filter(|source| !source.name.starts_with("environment") && !source.keys.is_empty())
+        .count();
+    let keys = effective_sources(sources).len();
+    if files == 0 {
+        CheckResult::warn(
+            "config",
+            format!("no configuration file found; {keys} keys from the environment"),
+            "create soffio.toml or pass --config-file",
+        )
+    } else {
+        CheckResult::pass("config", format!("{keys} keys from {files} file(s)"))
+    }
+}
+
+/// Each configured key and the source that supplies its value.
+fn effective_sources(sources: &[ConfigSource]) -> BTreeMap<&str, &str> {
+    let mut effective = BTreeMap::new();
+    for source in sources {
+        for key in &source.keys {
+            effective.insert(key.as_str(), source.name.as_str());
+        }
+    }
+    effective
+}
+
+/// Connect the way `serve` does, without running migrations.
+async fn database_results(settings: &config::Settings) -> Vec<CheckResult> {
+    let Some(url) = settings.database.url.as_deref() else {
+        return vec![CheckResult::fail(
+            "database",
+            "database url is not configured",
+            "set database.url, SOFFIO__DATABASE__URL or DATABASE_URL",
+        )];
+    };
+    let limits = ConnectionLimits {
+        acquire_timeout: Some(settings.database.acquire_timeout),
+        statement_timeout: settings.database.statement_timeout,
+        slow_query_threshold: settings.database.slow_query_log,
+    };
+
+    let pool = match PostgresRepositories::connect(url, 1, limits).await {
+        Ok(pool) => pool,
+        Err(err) => {
+            return vec![CheckResult::fail(
+                "database",
+                format!("cannot connect: {err}"),
+                "check database.url and that Postgres accepts connections from this host",
+            )];
+        }
+    };
+    let mut results = vec![
+        doctor::check_database(&pool).await,
+        doctor::check_migrations(&pool).await,
+    ];
+    pool.close().await;
+
+    if let Some(read_url) = settings.database.read_url.as_deref() {
+        results.push(
+            match PostgresRepositories::connect(read_url, 1, limits).await {
+                Ok(read_pool) => {
+                    let mut result = doctor::check_database(&read_pool).await;
+                    result.name = "database_read_replica";
+                    read_pool.close().await;
+                    result
+                }
+                Err(err) => CheckResult::fail(
+                    "database_read_replica",
+                    format!("cannot connect: {err}"),
+                    "check database.read_url or unset it to read from the primary",
+                ),
+            },
+        );
+    }
+    results
+}
+
+fn print_text(sources: &[ConfigSource], checks: &[CheckResult], level: CheckLevel) {
+    println!("Configuration sources (later sources win, CLI flags override all):");
+    for source in sources {
+        let found = if source.keys.is_empty() {
+            "nothing set".to_string()
+        } else {
+            format!("{} keys", source.keys.len())
+        };
+        println!("  {:<28} {found}", source.name);
+    }
+    let effective = effective_sources(sources);
+    if !effective.is_empty() {
+        println!();
+        println!("Source of each configured key:");
+        for (key, source) in effective {
+            println!("  {key:<44} {source}");
+        }
+    }
+
+    println!();
+    for check in checks {
+        println!(
+            "[{}] {:<22} {}",
+            check.level.as_str(),
+            check.name,
+            check.detail
+        );
+        if let Some(hint) = &check.hint {
+            println!("       hint: {hint}");
+        }
+    }
+    println!();
+    println!("Result: {}", level.as_str());
+}
+
+fn print_json(sources: &[ConfigSource], checks: &[CheckResult], level: CheckLevel) {
+    let source_list: Vec<_> = sources
+        .iter()
+        .map(|source| json!({ "name": source.name, "keys": source.keys }))
+        .collect();
+    let report = json!({
+        "level": level,
+        "sources": source_list,
+        "effective": effective_sources(sources),
+        "checks": checks,
+    });
+    println!("{report:#}");
+}
@@ -1,41 +1,143 @@
-use soffio::{application::error::AppError, application::site, config};
-use tracing::info;
+use soffio::{
+    application::error::AppError,
+    application::repos::SettingsRepo,
+    application::site,
+    config,
+    infra::{error::InfraError, http, uploads::UploadStorage},
+};
+use tracing::{info, warn};
+use url::Url;
 
 use crate::migrations_tool;
-use crate::serve::init_repositories;
+use crate::serve::{ReloadSource, build_application_context, init_repositories};
+
+/// Audit actor for posts and tags created by `import-markdown`.
+const MARKDOWN_IMPORT_ACTOR: &str = "import-markdown";
 
 pub(super) async fn run_export_site(
     settings: config::Settings,
     args: config::ExportArgs,
 ) -> Result<(), AppError> {
-    let (http_repositories, _) = init_repositories(&settings).await?;
+    let http_repositories = init_repositories(&settings).await?.http;
     let path = args.file;
+    let upload_storage = if args.include_uploads {
+        Some(open_upload_storage(&settings)?)
+    } else {
+        None
+    };
 
     info!(
         target = "soffio::export",
         path = %path.display(),
+        include_uploads = args.include_uploads,
         "Starting export"
     );
 
-    site::export_site(&http_repositories, &path).await?;
+    site::export_site(&http_repositories, &path, upload_storage.as_ref()).await?;
     info!(target = "soffio::export", "Export completed");
     Ok(())
 }
 
+pub(super) async fn run_export_static(
+    settings: config::Settings,
+    args: config::ExportStaticArgs,
+    reload: ReloadSource,
+) -> Result<(), AppError> {
+    let repositories = init_repositories(&settings).await?;
+    let app = build_application_context(repositories, &settings, reload)?;
+    let site_settings = app
+        .http_state
+        .db
+        .load_site_settings()
+        .await
+        .map_err(|err| AppError::unexpected(format!("failed to load site settings: {err}")))?;
+    let site_url = app
+        .http_state
+        .base_path
+        .site_url(&site_settings.public_site_url)
+        .ok_or_else(|| {
+            AppError::validation(format!(
+                "export-static needs public_site_url to be an absolute http(s) URL, got `{}`",
+                site_settings.public_site_url
+            ))
+        })?;
+    let site_url = Url::parse(&site_url).map_err(|err| {
+        AppError::validation(format!("invalid public site url `{site_url}`: {err}"))
+    })?;
+
+    // Render every page fresh rather than from (or into) the response cache.
+    let mut http_state = app.http_state;
+    http_state.cache = None;
+    let router = http::build_public_router(http_state, app.api_state);
+
+    info!(
+        target = "soffio::export_static",
+        dir = %args.dir.display(),
+        site_url = %site_url,
+        "Starting static export"
+    );
+
+    let report = http::export_static_site(router, &site_url, &args.dir).await?;
+    for (path, status) in &report.failed {
+        warn!(
+            target = "soffio::export_static",
+            path = %path,
+            status = status.as_u16(),
+            "Linked path not exported"
+        );
+    }
+    info!(
+        target = "soffio::export_static",
+        files = report.written.len(),
+        failed = report.failed.len(),
+        "Static export completed"
+    );
+    Ok(())
+}
+
 pub(super) async fn run_import_site(
     settings: config::Settings,
     args: config::ImportArgs,
 ) -> Result<(), AppError> {
-    let (http_repositories, _) = init_repositories(&settings).await?;
+    let mode = site::ImportMode::parse(&args.mode)
+        .ok_or_else(|| AppError::validation(format!("unknown import mode `{}`", args.mode)))?;
+    let http_repositories = init_repositories(&settings).await?.http;
     let path = args.file;
+    let upload_storage = open_upload_storage(&settings)?;
 
     info!(
         target = "soffio::import",
         path = %path.display(),
+        mode = mode.as_str(),
         "Starting import"
     );
 
-    site::import_site(&http_repositories, &path).await?;
+    let report = site::import_site(&http_repositories, &upload_storage, &path, mode).await?;
+    for (table, counts) in &report.tables {
+        info!(
+            target = "soffio::import",
+            table = *table,
+            created = counts.created,
+            updated = counts.updated,
+            skipped = counts.skipped,
+            "Rows imported"
+        );
+    }
+    for failure in &report.upload_failures {
+        warn!(
+            target = "soffio::import",
+            stored_path = %failure.stored_path,
+            reason = %failure.reason,
+            "Upload not restored"
+        );
+    }
+    info!(
+        target = "soffio::import",
+        copied = report.uploads_copied,
+        already_present = report.uploads_present,
+        failed = report.upload_failures.len(),
+        "Uploads restored"
+    );
     info!(
         target = "soffio::import",
         "Import completed. Re-run renderall to regenerate derived content."
@@ -43,6 +145,47 @@ pub(super) async fn run_import_site(
     Ok(())
 }
 
+pub(super) async fn run_import_markdown(
+    settings: config::Settings,
+    args: config::ImportMarkdownArgs,
+    reload: ReloadSource,
+) -> Result<(), AppError> {
+    let repositories = init_repositories(&settings).await?;
+    let app = build_application_context(repositories, &settings, reload)?;
+    let admin = app.admin_state;
+
+    info!(
+        target = "soffio::import_markdown",
+        dir = %args.dir.display(),
+        "Starting Markdown import"
+    );
+
+    let report =
+        site::import_markdown_posts(&admin.posts, &admin.tags, MARKDOWN_IMPORT_ACTOR, &args.dir)
+            .await?;
+    for skipped in &report.skipped {
+        warn!(
+            target = "soffio::import_markdown",
+            path = %skipped.path.display(),
+            reason = %skipped.reason,
+            "File skipped"
+        );
+    }
+    info!(
+        target = "soffio::import_markdown",
+        posts_created = report.created.len(),
+        tags_created = report.tags_created.len(),
+        files_skipped = report.skipped.len(),
+        "Markdown import completed. Posts render once `serve` runs its job workers."
+    );
+    Ok(())
+}
+
+fn open_upload_storage(settings: &config::Settings) -> Result<UploadStorage, AppError> {
+    UploadStorage::new(settings.uploads.directory.clone())
+        .map_err(|err| AppError::from(InfraError::Io(err)))
+}
+
 pub(super) async fn run_migrations(
     settings: config::Settings,
     args: config::MigrationsArgs,
@@ -51,6 +194,15 @@ pub(super) async fn run_migrations(
         config::MigrationsCommand::Reconcile(cmd) => {
             migrations_tool::reconcile_archive(&settings.database, &cmd).await?
         }
+        config::MigrationsCommand::Status(cmd) => {
+            migrations_tool::report_status(&settings.database, &cmd).await?
+        }
+        config::MigrationsCommand::Pending(cmd) => {
+            migrations_tool::report_pending(&settings.database, &cmd).await?
+        }
+        config::MigrationsCommand::Verify(cmd) => {
+            migrations_tool::verify(&settings.database, &cmd).await?
+        }
     }
     Ok(())
 }
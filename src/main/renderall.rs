@@ -14,17 +14,19 @@ use soffio::{
     },
     config,
     domain::entities::{PageRecord, PostRecord},
+    infra::db::{RenderPlan, RenderSelection},
 };
 use tracing::info;
 
-use crate::serve::{build_application_context, init_repositories};
+use crate::serve::{ReloadSource, build_application_context, init_repositories};
 
 pub(super) async fn run_renderall(
     settings: config::Settings,
     args: config::RenderAllArgs,
+    reload: ReloadSource,
 ) -> Result<(), AppError> {
-    let (http_repositories, job_repositories) = init_repositories(&settings).await?;
-    let app = build_application_context(http_repositories, job_repositories, &settings)?;
+    let repositories = init_repositories(&settings).await?;
+    let app = build_application_context(repositories, &settings, reload)?;
     let job_context = app.job_context;
 
     let filter_specified = args.posts || args.pages;
@@ -37,41 +39,162 @@ pub(super) async fn run_renderall(
         ));
     }
 
+    let selection = RenderSelection {
+        slugs: args.slugs.clone(),
+        updated_since: args.since,
+    };
+    check_slugs_exist(&job_context, &selection, render_posts, render_pages).await?;
+
+    if args.dry_run {
+        return report_dry_run(&job_context, &selection, render_posts, render_pages).await;
+    }
+
     let concurrency = args.concurrency.clamp(1, 32);
+    let progress_every = args.progress_every;
 
     info!(
         target = "soffio::renderall",
         concurrency,
         posts = render_posts,
         pages = render_pages,
+        slugs = selection.slugs.len(),
+        since = ?selection.updated_since,
         "Starting renderall"
     );
 
     if render_posts {
-        render_all_posts(&job_context, concurrency).await?;
+        render_all_posts(&job_context, &selection, concurrency, progress_every).await?;
     }
 
     if render_pages {
-        render_all_pages(&job_context, concurrency).await?;
+        render_all_pages(&job_context, &selection, concurrency, progress_every).await?;
     }
 
     Ok(())
 }
 
-async fn render_all_posts(ctx: &JobWorkerContext, concurrency: usize) -> Result<(), AppError> {
+/// Fail before rendering anything when a `--slug` names no post or page of
+/// the kinds being rendered.
+async fn check_slugs_exist(
+    ctx: &JobWorkerContext,
+    selection: &RenderSelection,
+    render_posts: bool,
+    render_pages: bool,
+) -> Result<(), AppError> {
+    if selection.slugs.is_empty() {
+        return Ok(());
+    }
+
+    let mut found = Vec::new();
+    if render_posts {
+        found.extend(
+            ctx.repositories
+                .existing_post_slugs(&selection.slugs)
+                .await
+                .map_err(|err| AppError::unexpected(err.to_string()))?,
+        );
+    }
+    if render_pages {
+        found.extend(
+            ctx.repositories
+                .existing_page_slugs(&selection.slugs)
+                .await
+                .map_err(|err| AppError::unexpected(err.to_string()))?,
+        );
+    }
+
+    let missing: Vec<&str> = selection
+        .slugs
+        .iter()
+        .filter(|slug| !found.contains(slug))
+        .map(String::as_str)
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let kind = match (render_posts, render_pages) {
+        (true, true) => "post or page",
+        (true, false) => "post",
+        _ => "page",
+    };
+    Err(AppError::validation(format!(
+        "renderall: no {kind} with slug `{}`",
+        missing.join("`, `")
+    )))
+}
+
+async fn report_dry_run(
+    ctx: &JobWorkerContext,
+    selection: &RenderSelection,
+    render_posts: bool,
+    render_pages: bool,
+) -> Result<(), AppError> {
+    if render_posts {
+        let plan = ctx
+            .repositories
+            .post_render_plan(selection)
+            .await
+            .map_err(|err| AppError::unexpected(err.to_string()))?;
+        log_plan("posts", &plan);
+    }
+
+    if render_pages {
+        let plan = ctx
+            .repositories
+            .page_render_plan(selection)
+            .await
+            .map_err(|err| AppError::unexpected(err.to_string()))?;
+        log_plan("pages", &plan);
+    }
+
+    Ok(())
+}
+
+fn log_plan(kind: &'static str, plan: &RenderPlan) {
+    info!(
+        target = "soffio::renderall",
+        kind,
+        total = plan.total,
+        stale = plan.stale.len(),
+        "Dry run: nothing rendered"
+    );
+    if !plan.stale.is_empty() {
+        info!(
+            target = "soffio::renderall",
+            kind,
+            slugs = %plan.stale.join(", "),
+            "Dry run: stale output would change"
+        );
+    }
+}
+
+fn log_progress(kind: &'static str, done: usize, progress_every: usize) {
+    if progress_every > 0 && done.is_multiple_of(progress_every) {
+        info!(target = "soffio::renderall", kind, done, "Render progress");
+    }
+}
+
+async fn render_all_posts(
+    ctx: &JobWorkerContext,
+    selection: &RenderSelection,
+    concurrency: usize,
+    progress_every: usize,
+) -> Result<(), AppError> {
     let total = Arc::new(AtomicUsize::new(0));
     let worker_ctx = ctx.clone();
     let total_handle = total.clone();
 
     ctx.repositories
-        .stream_all_posts()
+        .stream_all_posts(selection)
         .map_err(|err| AppError::unexpected(err.to_string()))
         .try_for_each_concurrent(Some(concurrency), move |post| {
             let ctx = worker_ctx.clone();
             let counter = total_handle.clone();
             async move {
                 render_post(&ctx, post).await?;
-                counter.fetch_add(1, Ordering::Relaxed);
+                let done = counter.fetch_add(1, Ordering::Relaxed) + 1;
+                log_progress("posts", done, progress_every);
                 Ok(())
             }
         })
@@ -86,20 +209,26 @@ async fn render_all_posts(ctx: &JobWorkerContext, concurrency: usize) -> Result<
     Ok(())
 }
 
-async fn render_all_pages(ctx: &JobWorkerContext, concurrency: usize) -> Result<(), AppError> {
+async fn render_all_pages(
+    ctx: &JobWorkerContext,
+    selection: &RenderSelection,
+    concurrency: usize,
+    progress_every: usize,
+) -> Result<(), AppError> {
     let total = Arc::new(AtomicUsize::new(0));
     let worker_ctx = ctx.clone();
     let total_handle = total.clone();
 
     ctx.repositories
-        .stream_all_pages()
+        .stream_all_pages(selection)
         .map_err(|err| AppError::unexpected(err.to_string()))
         .try_for_each_concurrent(Some(concurrency), move |page| {
             let ctx = worker_ctx.clone();
             let counter = total_handle.clone();
             async move {
                 render_page(&ctx, page).await?;
-                counter.fetch_add(1, Ordering::Relaxed);
+                let done = counter.fetch_add(1, Ordering::Relaxed) + 1;
+                log_progress("pages", done, progress_every);
                 Ok(())
             }
         })
@@ -116,11 +245,11 @@ async fn render_all_pages(ctx: &JobWorkerContext, concurrency: usize) -> Result<
 
 async fn render_post(ctx: &JobWorkerContext, post: PostRecord) -> Result<(), AppError> {
     process_render_post_job(
-        RenderPostJobPayload {
-            slug: post.slug.clone(),
-            body_markdown: post.body_markdown.clone(),
-            summary_markdown: post.summary_markdown.clone(),
-        },
+        RenderPostJobPayload::new(
+            post.slug.clone(),
+            post.body_markdown.clone(),
+            post.summary_markdown.clone(),
+        ),
         Data::new(ctx.clone()),
     )
     .await
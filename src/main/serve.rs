@@ -1,55 +1,66 @@
-use std::sync::Arc;
+use std::sync::{Arc, atomic::Ordering};
+use std::time::Duration;
 
 use soffio::{
-    application::{api_keys::ApiKeyService, error::AppError, jobs::JobWorkerContext},
-    cache::CacheTrigger,
+    application::{
+        actor,
+        error::AppError,
+        jobs::{ExpiredCacheEntries, MaintenanceTask, MaintenanceTick, PurgeTrashContext},
+    },
+    cache::CacheWarmer,
     config,
     infra::{
-        db::PostgresRepositories,
-        http::{AdminState, ApiState, HttpState},
+        http::{self, IdleRateLimitBuckets, RateLimiter},
+        job_monitor::spawn_job_monitor,
+        reload::ConfigReloader,
     },
 };
+use tracing::{error, info, warn};
 
-#[path = "serve/context.rs"]
-mod context;
 #[path = "serve/http_server.rs"]
 mod http_server;
-#[path = "serve/job_monitor.rs"]
-mod job_monitor;
 #[path = "serve/repositories.rs"]
 mod repositories;
 
-pub(super) struct ApplicationContext {
-    pub(super) http_state: HttpState,
-    pub(super) admin_state: AdminState,
-    pub(super) api_state: ApiState,
-    pub(super) job_context: JobWorkerContext,
-    pub(super) api_keys: Arc<ApiKeyService>,
-    pub(super) cache_trigger: Option<Arc<CacheTrigger>>,
-}
+pub(super) use soffio::infra::context::{
+    ApplicationContext, ReloadSource, Repositories, build_application_context,
+};
 
-use http_server::serve_http;
-use job_monitor::spawn_job_monitor;
+use http_server::{ListenerTls, serve_http};
 
-pub(super) async fn run_serve(settings: config::Settings) -> Result<(), AppError> {
-    let (http_repositories, job_repositories) = init_repositories(&settings).await?;
-    let app = build_application_context(
-        http_repositories.clone(),
-        job_repositories.clone(),
-        &settings,
-    )?;
+pub(super) async fn run_serve(
+    settings: config::Settings,
+    mut reload: ReloadSource,
+) -> Result<(), AppError> {
+    // Unusable certificates fail startup before anything else is set up.
+    let tls = ListenerTls::load(&settings.server)?;
+    reload.tls = tls.handles();
+    http::api::error::set_error_docs_url(&settings.server.api_error_docs_url);
+    // Shared rate limit buckets mean several nodes; without a common secret
+    // each signs sessions, cursors and preview links with its own key.
+    if settings.admin_auth.session_secret.is_none()
+        && settings.api_rate_limit.backend == config::RateLimitBackend::Postgres
+    {
+        warn!(
+            target = "soffio::serve",
+            "admin_auth.session_secret is not set but rate limits are shared; sessions, list cursors and preview links will only work on the node that issued them"
+        );
+    }
+
+    let repositories = init_repositories(&settings).await?;
+    let app = build_application_context(repositories.clone(), &settings, reload)?;
 
     if let Some(trigger) = &app.cache_trigger {
         trigger.warmup_on_startup().await;
     }
 
+    // The interval is read each cycle so configuration reloads take effect.
     let cache_handle = if let Some(trigger) = app.cache_trigger.clone() {
-        let interval_ms = trigger.config().auto_consume_interval_ms;
+        let interval_ms = app.cache_consume_interval_ms.clone();
         Some(tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
-            interval.tick().await;
             loop {
-                interval.tick().await;
+                tokio::time::sleep(Duration::from_millis(interval_ms.load(Ordering::Relaxed)))
+                    .await;
                 trigger.consumer().consume_full().await;
             }
         }))
@@ -57,14 +68,57 @@ pub(super) async fn run_serve(settings: config::Settings) -> Result<(), AppError
         None
     };
 
+    let reload_handle = spawn_reload_on_sighup(app.admin_state.config_reload.clone());
+
+    // The warmer renders paths through its own copy of the public router,
+    // sharing the response cache with the listener: targets of consumed plans
+    // right away, hot paths on the warm schedule.
+    let cache_warmer = app
+        .cache_trigger
+        .clone()
+        .filter(|trigger| trigger.config().enable_l1_cache)
+        .map(|trigger| {
+            let router = http::build_public_router(app.http_state.clone(), app.api_state.clone());
+            Arc::new(CacheWarmer::new(trigger, router))
+        });
+    let target_warm_handle = cache_warmer.clone().map(|warmer| {
+        tokio::spawn(async move {
+            warmer.run_target_warming().await;
+        })
+    });
+    let scheduled_warmer = cache_warmer.filter(|_| !settings.cache.warmup_hot_paths.is_empty());
+
+    let purge_trash = settings
+        .jobs
+        .trash_retention
+        .map(|retention| PurgeTrashContext {
+            posts: app.api_state.posts.clone(),
+            pages: app.api_state.pages.clone(),
+            retention,
+        });
+
+    let maintenance = build_maintenance_tick(&settings.scheduler, &app);
+    let maintenance_handle = (!maintenance.is_empty())
+        .then(|| tokio::spawn(Arc::new(maintenance).run(settings.scheduler.cadence)));
+
     let monitor_handle = spawn_job_monitor(
-        job_repositories,
+        repositories.jobs,
         app.job_context.clone(),
         app.api_keys.clone(),
+        scheduled_warmer,
+        purge_trash,
         &settings.jobs,
+        &settings.cache,
     );
 
-    let result = serve_http(&settings, app.http_state, app.admin_state, app.api_state).await;
+    let result = serve_http(
+        &settings,
+        tls,
+        app.http_state,
+        app.admin_state,
+        app.api_state,
+    )
+    .await;
 
     monitor_handle.abort();
     let _ = monitor_handle.await;
@@ -74,19 +128,86 @@ pub(super) async fn run_serve(settings: config::Settings) -> Result<(), AppError
         let _ = h.await;
     }
 
+    if let Some(h) = target_warm_handle {
+        h.abort();
+        let _ = h.await;
+    }
+
+    if let Some(h) = maintenance_handle {
+        h.abort();
+        let _ = h.await;
+    }
+
+    reload_handle.abort();
+    let _ = reload_handle.await;
+
     result
 }
 
-pub(super) async fn init_repositories(
-    settings: &config::Settings,
-) -> Result<(Arc<PostgresRepositories>, Arc<PostgresRepositories>), AppError> {
-    repositories::init_repositories(settings).await
+/// The housekeeping steps run every `scheduler.cadence`. Scheduled posts and
+/// pages are published by their own job workers, so the tick leaves them out.
+fn build_maintenance_tick(
+    scheduler: &config::SchedulerSettings,
+    app: &ApplicationContext,
+) -> MaintenanceTick {
+    let mut tasks: Vec<Arc<dyn MaintenanceTask>> = Vec::new();
+    if scheduler.evict_rate_limit_buckets {
+        let limiters: Vec<Arc<dyn RateLimiter>> = vec![
+            app.api_state.rate_limiter.clone(),
+            app.api_state.comment_rate_limiter.clone(),
+            app.api_state.render_preview_rate_limiter.clone(),
+            app.admin_state.login_rate_limiter.clone(),
+        ];
+        tasks.push(Arc::new(IdleRateLimitBuckets { limiters }));
+    }
+    let l0 = app
+        .cache_trigger
+        .as_ref()
+        .filter(|trigger| trigger.config().enable_l0_cache)
+        .map(|trigger| trigger.consumer().l0().clone());
+    if let Some(l0) = l0.filter(|_| scheduler.sweep_expired_cache_entries) {
+        tasks.push(Arc::new(ExpiredCacheEntries { l0 }));
+    }
+    MaintenanceTick::new(tasks)
 }
 
-pub(super) fn build_application_context(
-    http_repositories: Arc<PostgresRepositories>,
-    job_repositories: Arc<PostgresRepositories>,
+/// Reload the configuration on every `SIGHUP`.
+#[cfg(unix)]
+fn spawn_reload_on_sighup(reloader: Arc<ConfigReloader>) -> tokio::task::JoinHandle<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    tokio::spawn(async move {
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(stream) => stream,
+            Err(err) => {
+                error!(
+                    target = "soffio::config::reload",
+                    error = %err,
+                    "failed to listen for SIGHUP; use POST /config/reload instead"
+                );
+                return;
+            }
+        };
+        while hangups.recv().await.is_some() {
+            info!(target = "soffio::config::reload", "SIGHUP received");
+            if let Err(err) = reloader.reload(actor::SYSTEM_ACTOR).await {
+                error!(
+                    target = "soffio::config::reload",
+                    error = %err,
+                    "configuration reload rejected; running settings unchanged"
+                );
+            }
+        }
+    })
+}
+
+#[cfg(not(unix))]
+fn spawn_reload_on_sighup(_reloader: Arc<ConfigReloader>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async {})
+}
+
+pub(super) async fn init_repositories(
     settings: &config::Settings,
-) -> Result<ApplicationContext, AppError> {
-    context::build_application_context(http_repositories, job_repositories, settings)
+) -> Result<Repositories, AppError> {
+    repositories::init_repositories(settings).await
 }
@@ -1,44 +1,159 @@
+use std::net::SocketAddr;
+
+use axum::{Router, middleware, serve::ListenerExt};
 use soffio::{
     application::error::AppError,
     config,
     infra::{
         error::InfraError,
-        http::{self, AdminState, ApiState, HttpState, RouterState},
+        http::{self, AdminState, ApiState, HttpState},
+        listener::{self, Listener},
+        tls::{self, TlsConfigHandle},
     },
 };
 use tokio::try_join;
+use tracing::info;
+
+/// Certificates for the listeners that terminate TLS themselves.
+pub(super) struct ListenerTls {
+    public: Option<TlsConfigHandle>,
+    admin: Option<TlsConfigHandle>,
+}
+
+impl ListenerTls {
+    pub(super) fn load(server: &config::ServerSettings) -> Result<Self, AppError> {
+        let load = |settings: Option<&config::TlsSettings>| {
+            settings
+                .map(TlsConfigHandle::load)
+                .transpose()
+                .map_err(|err| AppError::from(InfraError::configuration(err.to_string())))
+        };
+        Ok(Self {
+            public: load(server.tls.as_ref())?,
+            admin: load(server.admin_tls.as_ref())?,
+        })
+    }
+
+    /// Every loaded certificate, for configuration reloads.
+    pub(super) fn handles(&self) -> Vec<TlsConfigHandle> {
+        self.public.iter().chain(&self.admin).cloned().collect()
+    }
+}
 
 pub(super) async fn serve_http(
     settings: &config::Settings,
+    tls: ListenerTls,
     http_state: HttpState,
     admin_state: AdminState,
     api_state: ApiState,
 ) -> Result<(), AppError> {
-    let router_state = RouterState {
-        http: http_state,
-        api: api_state,
-    };
-    let public_router = http::build_router(router_state.clone());
-    let upload_body_limit = settings.uploads.max_request_bytes.get() as usize;
-    let admin_router = http::build_admin_router(admin_state, upload_body_limit);
-    let api_router = http::build_api_v1_router(router_state.clone());
-
-    let public_router = public_router
-        .merge(api_router)
-        .with_state(router_state.clone());
+    let trusted_proxies = http::TrustedProxies::new(settings.server.trusted_proxies.clone());
+    let public_router = http::build_public_router(http_state, api_state).layer(
+        middleware::from_fn_with_state(trusted_proxies.clone(), http::resolve_client_ip),
+    );
+    let admin_router = http::build_admin_router(admin_state).layer(middleware::from_fn_with_state(
+        trusted_proxies,
+        http::resolve_client_ip,
+    ));
 
-    let public_listener = tokio::net::TcpListener::bind(settings.server.public_addr)
+    let (public_listener, admin_listener) = bind_listeners(&settings.server)
         .await
+        .and_then(|(public, admin)| Ok((public.with_tls(tls.public)?, admin.with_tls(tls.admin)?)))
         .map_err(|err| AppError::from(InfraError::from(err)))?;
-    let admin_listener = tokio::net::TcpListener::bind(settings.server.admin_addr)
+    let redirect = bind_https_redirect(&settings.server)
         .await
         .map_err(|err| AppError::from(InfraError::from(err)))?;
+    info!(
+        target = "soffio::serve",
+        public = %public_listener.describe(),
+        admin = %admin_listener.describe(),
+        "listeners ready"
+    );
 
-    let public_server = axum::serve(public_listener, public_router.into_make_service());
-    let admin_server = axum::serve(admin_listener, admin_router.into_make_service());
-
-    try_join!(public_server, admin_server)
-        .map_err(|err| AppError::unexpected(format!("server error: {err}")))?;
+    try_join!(
+        serve_public(public_listener, public_router),
+        serve_admin(admin_listener, admin_router),
+        serve_https_redirect(redirect)
+    )
+    .map_err(|err| AppError::unexpected(format!("server error: {err}")))?;
 
     Ok(())
 }
+
+async fn bind_listeners(server: &config::ServerSettings) -> std::io::Result<(Listener, Listener)> {
+    if server.systemd_socket {
+        return listener::systemd_listeners();
+    }
+    let public = Listener::bind(&server.public_addr, server.socket_mode).await?;
+    let admin = Listener::bind(&server.admin_addr, server.socket_mode).await?;
+    Ok((public, admin))
+}
+
+/// Client addresses are only known on TCP; over a unix socket the reverse
+/// proxy in front is the peer.
+async fn serve_public(listener: Listener, router: Router) -> std::io::Result<()> {
+    match listener {
+        Listener::Tcp(listener) => {
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        }
+        Listener::Unix(listener) => axum::serve(listener, router.into_make_service()).await,
+        // axum only reads peer addresses off its own listeners; tapping the
+        // TLS one wraps it in a type it does.
+        Listener::Tls(listener) => {
+            axum::serve(
+                listener.tap_io(|_| {}),
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        }
+    }
+}
+
+async fn serve_admin(listener: Listener, router: Router) -> std::io::Result<()> {
+    match listener {
+        Listener::Tcp(listener) => {
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        }
+        Listener::Unix(listener) => axum::serve(listener, router.into_make_service()).await,
+        Listener::Tls(listener) => {
+            axum::serve(
+                listener.tap_io(|_| {}),
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+        }
+    }
+}
+
+/// The plain-HTTP listener that redirects to the TLS public listener, with
+/// the HTTPS port to redirect to.
+async fn bind_https_redirect(
+    server: &config::ServerSettings,
+) -> std::io::Result<Option<(tokio::net::TcpListener, u16)>> {
+    let (Some(port), config::ListenAddr::Tcp(public)) =
+        (server.http_redirect_port, &server.public_addr)
+    else {
+        return Ok(None);
+    };
+    let listener = tokio::net::TcpListener::bind(SocketAddr::new(public.ip(), port)).await?;
+    Ok(Some((listener, public.port())))
+}
+
+async fn serve_https_redirect(
+    redirect: Option<(tokio::net::TcpListener, u16)>,
+) -> std::io::Result<()> {
+    match redirect {
+        Some((listener, https_port)) => {
+            axum::serve(listener, tls::https_redirect_router(https_port)).await
+        }
+        None => Ok(()),
+    }
+}
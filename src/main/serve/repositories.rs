@@ -1,14 +1,18 @@
 use std::sync::Arc;
 
 use soffio::{
-    application::error::AppError,
+    application::{error::AppError, pagination::CursorKeys},
     config,
-    infra::{db::PostgresRepositories, error::InfraError},
+    infra::{
+        context::Repositories,
+        db::{ConnectionLimits, PostgresRepositories},
+        error::InfraError,
+    },
 };
 
 pub(super) async fn init_repositories(
     settings: &config::Settings,
-) -> Result<(Arc<PostgresRepositories>, Arc<PostgresRepositories>), AppError> {
+) -> Result<Repositories, AppError> {
     let database_url = settings
         .database
         .url
@@ -16,22 +20,58 @@ pub(super) async fn init_repositories(
         .ok_or_else(|| InfraError::configuration("database url is not configured"))
         .map_err(AppError::from)?;
 
-    let http_pool =
-        PostgresRepositories::connect(database_url, settings.database.http_max_connections.get())
+    // Migrations may legitimately outlast the statement timeout, so they run on
+    // a dedicated connection without limits.
+    let migration_pool =
+        PostgresRepositories::connect(database_url, 1, ConnectionLimits::default())
             .await
             .map_err(|err| AppError::from(InfraError::database(err.to_string())))?;
-
-    PostgresRepositories::run_migrations(&http_pool)
+    PostgresRepositories::run_migrations(&migration_pool)
         .await
         .map_err(|err| AppError::from(InfraError::database(err.to_string())))?;
+    migration_pool.close().await;
+
+    let limits = ConnectionLimits {
+        acquire_timeout: Some(settings.database.acquire_timeout),
+        statement_timeout: settings.database.statement_timeout,
+        slow_query_threshold: settings.database.slow_query_log,
+    };
+
+    let http_pool = PostgresRepositories::connect(
+        database_url,
+        settings.database.http_max_connections.get(),
+        limits,
+    )
+    .await
+    .map_err(|err| AppError::from(InfraError::database(err.to_string())))?;
+
+    let jobs_pool = PostgresRepositories::connect(
+        database_url,
+        settings.database.jobs_max_connections.get(),
+        limits,
+    )
+    .await
+    .map_err(|err| AppError::from(InfraError::database(err.to_string())))?;
 
-    let jobs_pool =
-        PostgresRepositories::connect(database_url, settings.database.jobs_max_connections.get())
+    let cursor_keys = CursorKeys::from(&settings.admin_auth);
+    let http = PostgresRepositories::new(http_pool).with_cursor_keys(cursor_keys.clone());
+    let public = match settings.database.read_url.as_deref() {
+        Some(read_url) => {
+            let read_pool = PostgresRepositories::connect(
+                read_url,
+                settings.database.http_max_connections.get(),
+                limits,
+            )
             .await
             .map_err(|err| AppError::from(InfraError::database(err.to_string())))?;
+            http.with_read_pool(read_pool)
+        }
+        None => http.clone(),
+    };
 
-    Ok((
-        Arc::new(PostgresRepositories::new(http_pool)),
-        Arc::new(PostgresRepositories::new(jobs_pool)),
-    ))
+    Ok(Repositories {
+        http: Arc::new(http),
+        public: Arc::new(public),
+        jobs: Arc::new(PostgresRepositories::new(jobs_pool).with_cursor_keys(cursor_keys)),
+    })
 }
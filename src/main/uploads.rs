@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use soffio::{
+    application::admin::audit::AdminAuditService,
+    application::admin::uploads::AdminUploadService,
+    application::error::AppError,
+    application::repos::{AuditRepo, UploadsRepo},
+    config,
+    infra::{error::InfraError, uploads::UploadStorage},
+};
+use tracing::{info, warn};
+
+use crate::serve::init_repositories;
+
+pub(super) async fn run_uploads(
+    settings: config::Settings,
+    args: config::UploadsArgs,
+) -> Result<(), AppError> {
+    match args.command {
+        config::UploadsCommand::VerifyAll(cmd) => verify_all(settings, cmd).await,
+    }
+}
+
+async fn verify_all(
+    settings: config::Settings,
+    args: config::UploadsVerifyAllArgs,
+) -> Result<(), AppError> {
+    let storage = UploadStorage::new(settings.uploads.directory.clone())
+        .map_err(|err| AppError::from(InfraError::Io(err)))?;
+
+    let http_repositories = init_repositories(&settings).await?.http;
+    let cursor_keys = http_repositories.cursor_keys().clone();
+    let uploads_repo: Arc<dyn UploadsRepo> = http_repositories.clone();
+    let audit_repo: Arc<dyn AuditRepo> = http_repositories;
+    let service = AdminUploadService::new(uploads_repo, AdminAuditService::new(audit_repo))
+        .with_cursor_keys(cursor_keys);
+
+    let concurrency = args.concurrency.clamp(1, 32);
+    info!(
+        target = "soffio::uploads",
+        concurrency, "Verifying stored uploads"
+    );
+
+    let report = service
+        .verify_all("cli", &storage, concurrency)
+        .await
+        .map_err(|err| AppError::unexpected(err.to_string()))?;
+
+    for id in &report.mismatched_ids {
+        warn!(target = "soffio::uploads", upload_id = %id, "Checksum mismatch");
+    }
+    info!(
+        target = "soffio::uploads",
+        checked = report.checked,
+        matched = report.matched,
+        mismatched = report.mismatched,
+        missing = report.missing,
+        "Upload verification completed"
+    );
+
+    if report.mismatched > 0 {
+        return Err(AppError::validation(format!(
+            "{} of {} uploads failed checksum verification",
+            report.mismatched, report.checked
+        )));
+    }
+    Ok(())
+}
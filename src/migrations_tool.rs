@@ -3,10 +3,13 @@ use std::{
     io::{BufRead, BufReader, BufWriter, Write},
 };
 
-use sqlx::postgres::PgPoolOptions;
+use serde_json::json;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use time::format_description::well_known::Rfc3339;
 
 use soffio::application::error::AppError;
-use soffio::config::{DatabaseSettings, MigrationsReconcileArgs};
+use soffio::config::{DatabaseSettings, MigrationsReconcileArgs, MigrationsStatusArgs};
+use soffio::infra::db::{MigrationState, MigrationStatus, migration_status, pending_migrations};
 
 #[derive(Debug, Clone)]
 struct MigrationEntry {
@@ -18,13 +21,8 @@ pub async fn reconcile_archive(
     database: &DatabaseSettings,
     args: &MigrationsReconcileArgs,
 ) -> Result<(), AppError> {
-    let database_url = database.url.as_deref().ok_or_else(|| {
-        AppError::validation(
-            "database url is required (provide --database-url or set SOFFIO__DATABASE__URL/DATABASE_URL)",
-        )
-    })?;
-
-    let migrations = fetch_migrations(database_url).await?;
+    let pool = connect(database).await?;
+    let migrations = fetch_migrations(&pool).await?;
     rewrite_archive(&args.file, &migrations)
         .map_err(|e| AppError::unexpected(format!("failed to rewrite archive: {e}")))?;
 
@@ -37,17 +35,134 @@ pub async fn reconcile_archive(
     Ok(())
 }
 
-async fn fetch_migrations(database_url: &str) -> Result<Vec<MigrationEntry>, AppError> {
-    let pool = PgPoolOptions::new()
+/// Print every embedded and applied migration and fail while any is
+/// pending, so deploys can wait for the schema; divergence is flagged but
+/// left to `verify`.
+pub async fn report_status(
+    database: &DatabaseSettings,
+    args: &MigrationsStatusArgs,
+) -> Result<(), AppError> {
+    let statuses = load_statuses(database).await?;
+    print_report(&statuses, &args.output);
+
+    let pending = count_pending(&statuses);
+    if pending > 0 {
+        return Err(AppError::validation(format!(
+            "{pending} migration(s) embedded in this binary are not applied"
+        )));
+    }
+    Ok(())
+}
+
+/// Print only the migrations that are embedded but not applied.
+pub async fn report_pending(
+    database: &DatabaseSettings,
+    args: &MigrationsStatusArgs,
+) -> Result<(), AppError> {
+    let pool = connect(database).await?;
+    let pending = pending_migrations(&pool)
+        .await
+        .map_err(|e| AppError::unexpected(e.to_string()))?;
+
+    if args.output == "json" {
+        println!("{:#}", json!({ "pending": pending }));
+        return Ok(());
+    }
+    for status in &pending {
+        println!("{:<14} {}", status.version, status.description);
+    }
+    println!("{} pending", pending.len());
+    Ok(())
+}
+
+/// Fail when any applied migration diverges from the embedded migrations,
+/// so deploy pipelines can gate on the exit code.
+pub async fn verify(
+    database: &DatabaseSettings,
+    args: &MigrationsStatusArgs,
+) -> Result<(), AppError> {
+    let statuses = load_statuses(database).await?;
+    print_report(&statuses, &args.output);
+
+    let divergent = statuses
+        .iter()
+        .filter(|status| status.state.is_divergent())
+        .count();
+    if divergent > 0 {
+        return Err(AppError::validation(format!(
+            "{divergent} applied migration(s) diverge from the migrations embedded in this binary"
+        )));
+    }
+    Ok(())
+}
+
+async fn load_statuses(database: &DatabaseSettings) -> Result<Vec<MigrationStatus>, AppError> {
+    let pool = connect(database).await?;
+    migration_status(&pool)
+        .await
+        .map_err(|e| AppError::unexpected(e.to_string()))
+}
+
+fn print_report(statuses: &[MigrationStatus], output: &str) {
+    let divergent = statuses.iter().any(|status| status.state.is_divergent());
+    if output == "json" {
+        let report = json!({ "divergent": divergent, "migrations": statuses });
+        println!("{report:#}");
+        return;
+    }
+
+    for status in statuses {
+        let applied_at = status
+            .applied_at
+            .and_then(|at| at.format(&Rfc3339).ok())
+            .unwrap_or_else(|| "-".to_string());
+        let flag = if status.state.is_divergent() {
+            "!"
+        } else {
+            " "
+        };
+        println!(
+            "{flag} {:<14} {:<17} {:<25} {}",
+            status.version,
+            status.state.as_str(),
+            applied_at,
+            status.description
+        );
+    }
+    let pending = count_pending(statuses);
+    println!(
+        "{} migrations, {pending} pending{}",
+        statuses.len(),
+        if divergent { ", DIVERGED" } else { "" }
+    );
+}
+
+fn count_pending(statuses: &[MigrationStatus]) -> usize {
+    statuses
+        .iter()
+        .filter(|status| status.state == MigrationState::Pending)
+        .count()
+}
+
+async fn connect(database: &DatabaseSettings) -> Result<PgPool, AppError> {
+    let database_url = database.url.as_deref().ok_or_else(|| {
+        AppError::validation(
+            "database url is required (provide --database-url or set SOFFIO__DATABASE__URL/DATABASE_URL)",
+        )
+    })?;
+
+    PgPoolOptions::new()
         .max_connections(1)
         .connect(database_url)
         .await
-        .map_err(|e| AppError::unexpected(e.to_string()))?;
+        .map_err(|e| AppError::unexpected(e.to_string()))
+}
 
+async fn fetch_migrations(pool: &PgPool) -> Result<Vec<MigrationEntry>, AppError> {
     let rows = sqlx::query!(
         r#"SELECT version, encode(checksum, 'hex') AS "checksum!" FROM _sqlx_migrations ORDER BY version"#
     )
-    .fetch_all(&pool)
+    .fetch_all(pool)
     .await
     .map_err(|e| AppError::unexpected(e.to_string()))?;
 
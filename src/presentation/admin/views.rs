@@ -1,15 +1,19 @@
-use crate::util::timezone;
+use crate::application::format;
+use crate::infra::assets::hashed_asset_path;
 use chrono_tz::Tz;
 use time::OffsetDateTime;
 
 mod api_keys;
 mod audit;
+mod auth;
+mod comments;
 mod dashboard;
 mod editors;
 mod jobs;
 mod navigation;
 mod pages;
 mod posts;
+mod schedule;
 mod settings;
 mod snapshots;
 mod tags;
@@ -25,23 +29,33 @@ pub use api_keys::{
     AdminApiKeysTemplate, AdminApiScopeDisplay, AdminApiScopeOption,
 };
 pub use audit::{
-    AdminAuditActionOption, AdminAuditActorOption, AdminAuditDetailField, AdminAuditDetailTemplate,
-    AdminAuditDetailView, AdminAuditListView, AdminAuditPanelTemplate, AdminAuditRowView,
-    AdminAuditStatusFilterView, AdminAuditTemplate,
+    AdminAuditActionOption, AdminAuditActorOption, AdminAuditActorTypeOption,
+    AdminAuditDetailField, AdminAuditDetailTemplate, AdminAuditDetailView, AdminAuditListView,
+    AdminAuditPanelTemplate, AdminAuditRowView, AdminAuditStatusFilterView, AdminAuditTemplate,
+};
+pub use auth::{AdminLoginTemplate, AdminLoginView};
+pub use comments::{
+    AdminCommentListView, AdminCommentRowActionView, AdminCommentRowView,
+    AdminCommentStatusFilterView, AdminCommentsPanelTemplate, AdminCommentsTemplate,
 };
 pub use dashboard::{
     AdminDashboardPanelView, AdminDashboardTemplate, AdminDashboardView, AdminMetricView,
+    AdminUpcomingItemView,
 };
 pub use editors::{
     AdminPageEditPanelTemplate, AdminPageEditTemplate, AdminPageEditorView, AdminPageStatusOption,
-    AdminPostEditPanelTemplate, AdminPostEditTemplate, AdminPostEditorView,
-    AdminPostSelectedTagView, AdminPostStatusOption, AdminPostTagPickerOptionView,
+    AdminPageTemplateOption, AdminPostAutosaveStatusTemplate, AdminPostAutosaveTemplate,
+    AdminPostAutosaveView, AdminPostEditPanelTemplate, AdminPostEditTemplate, AdminPostEditorView,
+    AdminPostPreviewTemplate, AdminPostPreviewView, AdminPostSelectedTagView,
+    AdminPostSlugErrorTemplate, AdminPostStatusOption, AdminPostTagPickerOptionView,
     AdminPostTagPickerTemplate, AdminPostTagPickerView, AdminPostTagSelectionStoreTemplate,
+    AdminRenderFailureView,
 };
 pub use jobs::{
-    AdminJobDetailField, AdminJobDetailPanelTemplate, AdminJobDetailTemplate, AdminJobDetailView,
-    AdminJobListView, AdminJobRowActionView, AdminJobRowView, AdminJobStatusFilterView,
-    AdminJobTypeOption, AdminJobsPanelTemplate, AdminJobsTemplate,
+    AdminJobAttemptView, AdminJobAuditView, AdminJobDetailField, AdminJobDetailPanelTemplate,
+    AdminJobDetailTemplate, AdminJobDetailView, AdminJobEntityView, AdminJobListView,
+    AdminJobRowActionView, AdminJobRowView, AdminJobStatusFilterView, AdminJobTimelineStep,
+    AdminJobTypeOption, AdminJobsPanelTemplate, AdminJobsTemplate, AdminJsonToken,
 };
 pub use navigation::{
     AdminNavigationDestinationTypeOption, AdminNavigationEditPanelTemplate,
@@ -56,13 +70,18 @@ pub use pages::{
 pub use posts::{
     AdminPostListView, AdminPostMonthOption, AdminPostPaginationState, AdminPostRowActionView,
     AdminPostRowView, AdminPostStatusFilterView, AdminPostTagOption, AdminPostTimeKind,
-    AdminPostsPanelTemplate, AdminPostsTemplate,
+    AdminPostsPanelTemplate, AdminPostsTemplate, AdminRenderProgressTemplate,
+    AdminRenderProgressView,
+};
+pub use schedule::{
+    AdminScheduleDayView, AdminScheduleItemView, AdminScheduleMonthLink,
+    AdminSchedulePanelTemplate, AdminScheduleTemplate, AdminScheduleView, AdminScheduleWeekView,
 };
 pub use settings::{
     AdminSettingsEditInputKind, AdminSettingsEditMultilineField, AdminSettingsEditPanelTemplate,
-    AdminSettingsEditSimpleField, AdminSettingsEditTemplate, AdminSettingsEditView,
-    AdminSettingsPanelTemplate, AdminSettingsSummaryField, AdminSettingsSummaryValueKind,
-    AdminSettingsSummaryView, AdminSettingsTemplate,
+    AdminSettingsEditSelectOption, AdminSettingsEditSimpleField, AdminSettingsEditTemplate,
+    AdminSettingsEditView, AdminSettingsPanelTemplate, AdminSettingsSummaryField,
+    AdminSettingsSummaryValueKind, AdminSettingsSummaryView, AdminSettingsTemplate,
 };
 pub use snapshots::{
     AdminSnapshotEditTemplate, AdminSnapshotEditorPanelTemplate, AdminSnapshotEditorView,
@@ -114,22 +133,19 @@ pub struct AdminChrome {
 #[derive(Clone)]
 pub struct AdminLayout<T> {
     pub chrome: AdminChrome,
-    pub asset_version: String,
     pub content: T,
 }
 
 impl<T> AdminLayout<T> {
     pub fn new(chrome: AdminChrome, content: T) -> Self {
-        Self {
-            chrome,
-            asset_version: asset_version(),
-            content,
-        }
+        Self { chrome, content }
     }
-}
 
-fn asset_version() -> String {
-    env!("CARGO_PKG_VERSION").to_string()
+    /// Content-addressed URL of the static asset at `path` under `/static/`,
+    /// e.g. `view.asset("admin/app.css")`.
+    pub fn asset(&self, path: &str) -> String {
+        hashed_asset_path(path)
+    }
 }
 
 /// Generic hidden field for form submissions.
@@ -158,6 +174,5 @@ impl AdminHiddenField {
 }
 
 pub fn format_timestamp(time: OffsetDateTime, tz: Tz) -> String {
-    let localized = timezone::localized_datetime(time, tz);
-    localized.format("%Y/%m/%d %H:%M:%S").to_string()
+    format::format_local(time, tz, format::ADMIN_TIMESTAMP_FORMAT)
 }
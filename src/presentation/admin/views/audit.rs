@@ -7,10 +7,13 @@ use super::{AdminHiddenField, AdminLayout, AdminPostPaginationState};
 pub struct AdminAuditRowView {
     pub id: String,
     pub actor: String,
+    pub actor_type: &'static str,
     pub action: String,
     pub entity_type: String,
     pub entity_id: Option<String>,
     pub payload_text: Option<String>,
+    /// Names of the fields an update changed, comma separated.
+    pub changed_fields: Option<String>,
     pub created_at: String,
     pub detail_href: String,
 }
@@ -32,6 +35,13 @@ pub struct AdminAuditActorOption {
     pub count: usize,
 }
 
+/// Actor origin option for dropdown filter.
+#[derive(Clone)]
+pub struct AdminAuditActorTypeOption {
+    pub value: &'static str,
+    pub label: &'static str,
+}
+
 /// Action option for dropdown filter.
 #[derive(Clone)]
 pub struct AdminAuditActionOption {
@@ -49,14 +59,22 @@ pub struct AdminAuditListView {
 
     // Audit-specific filter options
     pub actor_options: Vec<AdminAuditActorOption>,
+    pub actor_type_options: Vec<AdminAuditActorTypeOption>,
     pub action_options: Vec<AdminAuditActionOption>,
 
     // Current filter values
     pub filter_actor: Option<String>,
+    pub filter_actor_type: Option<String>,
     pub filter_action: Option<String>,
     pub filter_entity_type: Option<String>,
     pub filter_search: Option<String>,
+    pub filter_field: Option<String>,
+    /// First and last day of the date range, `YYYY-MM-DD`.
+    pub filter_from: Option<String>,
+    pub filter_to: Option<String>,
     pub filter_query: String,
+    /// CSV download of the entries matching the current filters.
+    pub export_href: String,
 
     // Status tabs
     pub active_status_key: Option<String>,
@@ -0,0 +1,18 @@
+use askama::Template;
+
+use super::AdminLayout;
+
+/// Sign-in form for the admin listener.
+#[derive(Clone)]
+pub struct AdminLoginView {
+    pub heading: String,
+    pub form_action: String,
+    pub username: String,
+    pub error: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "admin/login.html")]
+pub struct AdminLoginTemplate {
+    pub view: AdminLayout<AdminLoginView>,
+}
@@ -0,0 +1,87 @@
+use askama::Template;
+
+use super::{
+    AdminHiddenField, AdminLayout, AdminPostMonthOption, AdminPostPaginationState,
+    AdminPostTagOption,
+};
+
+/// Status filter tab view for comments.
+#[derive(Clone)]
+pub struct AdminCommentStatusFilterView {
+    pub status_key: Option<String>,
+    pub label: String,
+    pub count: u64,
+    pub is_active: bool,
+}
+
+/// Row action button for comment moderation.
+#[derive(Clone)]
+pub struct AdminCommentRowActionView {
+    pub value: &'static str,
+    pub label: &'static str,
+    pub is_danger: bool,
+}
+
+/// Single comment row in the moderation queue.
+#[derive(Clone)]
+pub struct AdminCommentRowView {
+    pub id: String,
+    pub author: String,
+    pub body: String,
+    pub post_title: String,
+    pub post_href: Option<String>,
+    pub status_key: String,
+    pub status_label: String,
+    pub created_at: String,
+    pub source_ip: Option<String>,
+    pub actions: Vec<AdminCommentRowActionView>,
+}
+
+/// Moderation queue list view aligned with AdminJobListView pattern.
+#[derive(Clone)]
+pub struct AdminCommentListView {
+    pub heading: String,
+    pub filters: Vec<AdminCommentStatusFilterView>,
+    pub comments: Vec<AdminCommentRowView>,
+
+    // Filter state
+    pub filter_search: Option<String>,
+    pub filter_tag: Option<String>,
+    pub filter_month: Option<String>,
+    pub active_status_key: Option<String>,
+
+    // Shared filter form (tag/month filters are disabled for comments)
+    pub tag_filter_enabled: bool,
+    pub month_filter_enabled: bool,
+    pub tag_filter_label: String,
+    pub tag_filter_all_label: String,
+    pub tag_filter_field: String,
+    pub tag_options: Vec<AdminPostTagOption>,
+    pub month_options: Vec<AdminPostMonthOption>,
+
+    // Pagination - reuse AdminPostPaginationState
+    pub next_cursor: Option<String>,
+    pub cursor_param: Option<String>,
+    pub trail: Option<String>,
+    pub previous_page_state: Option<AdminPostPaginationState>,
+    pub next_page_state: Option<AdminPostPaginationState>,
+
+    // Action paths
+    pub panel_action: String,
+    pub row_action_prefix: String,
+
+    /// Generic hidden fields for filter state retention
+    pub custom_hidden_fields: Vec<AdminHiddenField>,
+}
+
+#[derive(Template)]
+#[template(path = "admin/comments.html")]
+pub struct AdminCommentsTemplate {
+    pub view: AdminLayout<AdminCommentListView>,
+}
+
+#[derive(Template)]
+#[template(path = "admin/comments_panel.html")]
+pub struct AdminCommentsPanelTemplate {
+    pub content: AdminCommentListView,
+}
@@ -23,17 +23,33 @@ impl AdminDashboardPanelView {
     }
 }
 
+#[derive(Clone)]
+pub struct AdminUpcomingItemView {
+    pub kind: &'static str,
+    pub title: String,
+    pub edit_href: String,
+    pub scheduled_at: String,
+    /// Still a draft although its publication time has passed.
+    pub stuck: bool,
+}
+
 #[derive(Clone)]
 pub struct AdminDashboardView {
     pub title: String,
     pub panels: Vec<AdminDashboardPanelView>,
     pub empty_message: String,
+    pub upcoming: Vec<AdminUpcomingItemView>,
+    pub upcoming_empty_message: String,
 }
 
 impl AdminDashboardView {
     pub fn has_panels(&self) -> bool {
         !self.panels.is_empty()
     }
+
+    pub fn has_upcoming(&self) -> bool {
+        !self.upcoming.is_empty()
+    }
 }
 
 #[derive(Template)]
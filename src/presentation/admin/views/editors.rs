@@ -1,23 +1,52 @@
-use crate::domain::types::{PageStatus, PostStatus};
+use crate::domain::types::{PageStatus, PostStatus, RenderStatus};
 use askama::Template;
+use chrono_tz::Tz;
+use time::OffsetDateTime;
 
-use super::AdminLayout;
+use super::{AdminLayout, AdminRenderProgressView, format_timestamp};
 
 #[derive(Clone)]
 pub struct AdminPostEditorView {
     pub title: String,
+    pub slug: String,
+    /// Shown while the slug is blank on new posts.
+    pub slug_placeholder: String,
     pub heading: String,
     pub excerpt: String,
+    /// Shown while the excerpt is blank: the derived excerpt, or a hint.
+    pub excerpt_placeholder: String,
     pub body_markdown: String,
     pub summary_markdown: Option<String>,
     pub status: PostStatus,
     pub status_options: Vec<AdminPostStatusOption>,
     pub published_at: Option<String>,
     pub form_action: String,
+    /// Version of the saved post, sent back so a save over a newer edit is
+    /// refused; `None` for new posts.
+    pub version: Option<String>,
     pub submit_label: String,
     pub enable_live_submit: bool,
     pub tag_picker: AdminPostTagPickerView,
     pub pinned: bool,
+    /// Progress of the post's render and publish jobs; `None` for new posts.
+    pub render_progress: Option<AdminRenderProgressView>,
+    pub render_failure: Option<AdminRenderFailureView>,
+    /// Content warnings from the latest successful render.
+    pub render_warnings: Vec<String>,
+    /// Receives the form while typing; see `AdminPostAutosaveStatusTemplate`.
+    pub autosave_action: String,
+    /// A newer autosave the editor offers to restore.
+    pub autosave: Option<AdminPostAutosaveView>,
+}
+
+#[derive(Clone)]
+pub struct AdminPostAutosaveView {
+    pub saved_at: String,
+    /// Reopens the editor with the autosave's content in the form.
+    pub restore_href: String,
+    pub discard_action: String,
+    /// The form already holds the autosave rather than the saved post.
+    pub restored: bool,
 }
 
 #[derive(Clone)]
@@ -44,6 +73,13 @@ pub struct AdminPostSelectedTagView {
     pub slug: String,
 }
 
+/// Rendered body shown in the editor's live preview pane.
+#[derive(Clone)]
+pub struct AdminPostPreviewView {
+    pub html: String,
+    pub mermaid_fallbacks: u32,
+}
+
 #[derive(Clone)]
 pub struct AdminPageEditorView {
     pub title: String,
@@ -51,10 +87,38 @@ pub struct AdminPageEditorView {
     pub body_markdown: String,
     pub status: PageStatus,
     pub status_options: Vec<AdminPageStatusOption>,
+    pub template_options: Vec<AdminPageTemplateOption>,
     pub published_at: Option<String>,
     pub form_action: String,
+    /// Version of the saved page, sent back so a save over a newer edit is
+    /// refused; `None` for new pages.
+    pub version: Option<String>,
     pub submit_label: String,
     pub enable_live_submit: bool,
+    pub render_failure: Option<AdminRenderFailureView>,
+    /// Content warnings from the latest successful render.
+    pub render_warnings: Vec<String>,
+}
+
+/// Error left by the latest render job, shown until a render succeeds.
+#[derive(Clone)]
+pub struct AdminRenderFailureView {
+    pub message: String,
+    pub failed_at: Option<String>,
+}
+
+impl AdminRenderFailureView {
+    pub fn from_status(
+        status: RenderStatus,
+        error: Option<&str>,
+        failed_at: Option<OffsetDateTime>,
+        tz: Tz,
+    ) -> Option<Self> {
+        (status == RenderStatus::Failed).then(|| Self {
+            message: error.unwrap_or("Render failed").to_string(),
+            failed_at: failed_at.map(|time| format_timestamp(time, tz)),
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -64,6 +128,13 @@ pub struct AdminPageStatusOption {
     pub selected: bool,
 }
 
+#[derive(Clone)]
+pub struct AdminPageTemplateOption {
+    pub value: &'static str,
+    pub label: &'static str,
+    pub selected: bool,
+}
+
 #[derive(Clone)]
 pub struct AdminPostStatusOption {
     pub value: &'static str,
@@ -83,6 +154,34 @@ pub struct AdminPostEditPanelTemplate {
     pub content: AdminPostEditorView,
 }
 
+/// Preview pane; `None` renders it closed.
+#[derive(Template)]
+#[template(path = "admin/post_preview.html")]
+pub struct AdminPostPreviewTemplate {
+    pub preview: Option<AdminPostPreviewView>,
+}
+
+/// Restore offer above the editor; `None` renders it empty.
+#[derive(Template)]
+#[template(path = "admin/post_autosave.html")]
+pub struct AdminPostAutosaveTemplate {
+    pub autosave: Option<AdminPostAutosaveView>,
+}
+
+/// Line under the editor confirming the latest autosave.
+#[derive(Template)]
+#[template(path = "admin/post_autosave_status.html")]
+pub struct AdminPostAutosaveStatusTemplate {
+    pub saved_at: String,
+}
+
+/// Inline error under the editor's slug field; `None` renders it empty.
+#[derive(Template)]
+#[template(path = "admin/post_slug_error.html")]
+pub struct AdminPostSlugErrorTemplate {
+    pub message: Option<String>,
+}
+
 #[derive(Template)]
 #[template(path = "admin/post_tag_picker.html")]
 pub struct AdminPostTagPickerTemplate {
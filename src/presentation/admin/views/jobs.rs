@@ -97,11 +97,61 @@ pub struct AdminJobDetailField {
     pub is_multiline: bool,
 }
 
+/// One piece of the highlighted payload; `kind` is `key`, `string`,
+/// `number`, `literal` or `punct`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdminJsonToken {
+    pub kind: &'static str,
+    pub text: String,
+}
+
+/// A point in the job's life and how long it took to get there.
+#[derive(Clone)]
+pub struct AdminJobTimelineStep {
+    pub label: &'static str,
+    pub at: String,
+    pub elapsed: Option<String>,
+}
+
+/// A failed run kept in the attempts history.
+#[derive(Clone)]
+pub struct AdminJobAttemptView {
+    pub attempt: i32,
+    pub failed_at: String,
+    pub error: String,
+}
+
+/// The post or page the job acts on.
+#[derive(Clone)]
+pub struct AdminJobEntityView {
+    pub kind_label: &'static str,
+    pub slug: String,
+    /// Editor link; absent once the content is gone.
+    pub href: Option<String>,
+}
+
+/// Audit entry recorded by the request that queued the job.
+#[derive(Clone)]
+pub struct AdminJobAuditView {
+    pub href: String,
+    pub action: String,
+    pub actor: String,
+    pub entity: String,
+    pub created_at: String,
+}
+
 /// Job detail view for single job page.
 #[derive(Clone)]
 pub struct AdminJobDetailView {
     pub heading: String,
     pub fields: Vec<AdminJobDetailField>,
+    pub timeline: Vec<AdminJobTimelineStep>,
+    pub entity: Option<AdminJobEntityView>,
+    pub payload: Vec<AdminJsonToken>,
+    pub last_error: Option<String>,
+    pub attempts: Vec<AdminJobAttemptView>,
+    pub request_id: Option<String>,
+    pub related_audit: Vec<AdminJobAuditView>,
 }
 
 #[derive(Template)]
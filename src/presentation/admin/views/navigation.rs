@@ -21,6 +21,8 @@ pub struct AdminNavigationRowView {
     pub destination_type_label: String,
     pub destination_type_status: String,
     pub destination_display: String,
+    /// Set when an internal destination's page slug is shadowed by a built-in route.
+    pub destination_warning: Option<String>,
     pub sort_order: i32,
     pub visible: bool,
     pub toggle_action: String,
@@ -17,6 +17,9 @@ pub struct AdminPageRowView {
     pub actions: Vec<AdminPostRowActionView>,
     pub preview_href: String,
     pub edit_href: String,
+    /// Always zero; pages cannot be pinned, but the shared row actions
+    /// partial renders the pin order input.
+    pub pin_order: i32,
     pub snapshots_href: Option<String>,
 }
 
@@ -14,7 +14,9 @@ pub struct AdminPostRowView {
     pub preview_href: String,
     pub edit_href: String,
     pub is_pinned: bool,
+    pub pin_order: i32,
     pub snapshots_href: Option<String>,
+    pub render_progress: AdminRenderProgressView,
 }
 
 #[derive(Clone, Copy)]
@@ -100,3 +102,19 @@ pub struct AdminPostsTemplate {
 pub struct AdminPostsPanelTemplate {
     pub content: AdminPostListView,
 }
+
+/// Render-progress badge of a post; polls for updates while a job is in flight.
+#[derive(Clone)]
+pub struct AdminRenderProgressView {
+    pub post_id: String,
+    pub state_key: &'static str,
+    pub label: &'static str,
+    pub error: Option<String>,
+    pub in_flight: bool,
+}
+
+#[derive(Template)]
+#[template(path = "admin/partials/render_progress.html")]
+pub struct AdminRenderProgressTemplate {
+    pub progress: AdminRenderProgressView,
+}
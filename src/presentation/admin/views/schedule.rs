@@ -0,0 +1,68 @@
+use askama::Template;
+
+use super::AdminLayout;
+
+/// A post or page shown on a calendar day.
+#[derive(Clone)]
+pub struct AdminScheduleItemView {
+    pub kind: &'static str,
+    pub title: String,
+    /// Local time of day the item publishes or was published at.
+    pub time: String,
+    pub edit_href: String,
+    /// Still a draft waiting for its publication time.
+    pub scheduled: bool,
+    pub publish_action: String,
+    pub reschedule_action: String,
+    /// Current publication time as a `datetime-local` input value.
+    pub reschedule_value: String,
+}
+
+#[derive(Clone)]
+pub struct AdminScheduleDayView {
+    pub date: String,
+    pub day_number: u8,
+    /// Whether the day belongs to the displayed month rather than padding
+    /// the first or last week.
+    pub in_month: bool,
+    pub is_today: bool,
+    pub items: Vec<AdminScheduleItemView>,
+}
+
+#[derive(Clone)]
+pub struct AdminScheduleWeekView {
+    pub days: Vec<AdminScheduleDayView>,
+}
+
+/// Toolbar button switching the calendar to another month.
+#[derive(Clone)]
+pub struct AdminScheduleMonthLink {
+    pub label: &'static str,
+    /// Target month as `YYYY-MM`.
+    pub month: String,
+}
+
+#[derive(Clone)]
+pub struct AdminScheduleView {
+    pub heading: String,
+    pub month_label: String,
+    /// Displayed month as `YYYY-MM`.
+    pub month: String,
+    pub month_links: Vec<AdminScheduleMonthLink>,
+    pub timezone: String,
+    pub weekdays: Vec<&'static str>,
+    pub weeks: Vec<AdminScheduleWeekView>,
+    pub panel_action: String,
+}
+
+#[derive(Template)]
+#[template(path = "admin/schedule.html")]
+pub struct AdminScheduleTemplate {
+    pub view: AdminLayout<AdminScheduleView>,
+}
+
+#[derive(Template)]
+#[template(path = "admin/schedule_panel.html")]
+pub struct AdminSchedulePanelTemplate {
+    pub content: AdminScheduleView,
+}
@@ -74,6 +74,17 @@ pub enum AdminSettingsEditInputKind {
         checked: bool,
         toggle_id: String,
     },
+    Select {
+        name: String,
+        options: Vec<AdminSettingsEditSelectOption>,
+    },
+}
+
+#[derive(Clone)]
+pub struct AdminSettingsEditSelectOption {
+    pub value: &'static str,
+    pub label: &'static str,
+    pub selected: bool,
 }
 
 #[derive(Clone)]
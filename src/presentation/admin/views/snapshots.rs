@@ -12,6 +12,7 @@ pub struct AdminSnapshotRowView {
     pub description: Option<String>,
     pub created_at: String,
     pub preview_href: String,
+    pub share_href: String,
     pub edit_href: String,
     pub rollback_action: String,
     pub delete_action: String,
@@ -41,6 +42,7 @@ pub struct AdminSnapshotListView {
     pub next_page_state: Option<AdminPostPaginationState>,
     pub custom_hidden_fields: Vec<AdminHiddenField>,
     pub active_status_key: Option<String>,
+    pub copy_toast_action: String,
 }
 
 #[derive(Template)]
@@ -18,6 +18,8 @@ pub struct AdminUploadRowView {
     pub delete_action: String,
     pub preview_href: Option<String>,
     pub public_href: String,
+    /// The stored file failed its last checksum verification.
+    pub checksum_mismatch: bool,
 }
 
 #[derive(Clone)]
@@ -120,8 +122,11 @@ pub struct AdminUploadFormView {
     pub queue_sync_action: String,
     pub back_href: String,
     pub toast_action: String,
+    /// Largest single file.
     pub upload_limit_bytes: u64,
     pub upload_limit_mib: u64,
+    /// Largest request; the queue sends files in batches that fit it.
+    pub request_limit_bytes: u64,
     pub queue: AdminUploadQueueView,
 }
 
@@ -1,4 +1,5 @@
 //! Presentation-layer view models and render adapters.
 
 pub mod admin;
+pub mod page_templates;
 pub mod views;
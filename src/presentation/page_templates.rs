@@ -0,0 +1,62 @@
+//! Layouts a page can be rendered with.
+//!
+//! Pages store their layout by name. Saving a page checks the name against
+//! [`PAGE_TEMPLATES`]; rendering falls back to the default layout for any
+//! name it does not know, so a row written behind the registry's back still
+//! serves.
+
+use askama::Template;
+use axum::{http::StatusCode, response::Response};
+use tracing::warn;
+
+use super::views::{LayoutContext, PageTemplate, PageView, render_template_response};
+
+const SOURCE: &str = "presentation::page_templates";
+
+pub const DEFAULT_PAGE_TEMPLATE: &str = "default";
+
+/// Registered page templates as `(name, label)`, in the order the admin
+/// editor offers them.
+pub const PAGE_TEMPLATES: &[(&str, &str)] = &[
+    (DEFAULT_PAGE_TEMPLATE, "Default"),
+    ("wide", "Wide"),
+    ("landing", "Landing"),
+];
+
+pub fn is_page_template(name: &str) -> bool {
+    PAGE_TEMPLATES
+        .iter()
+        .any(|(registered, _)| *registered == name)
+}
+
+/// Body spanning the full content width, without the sidebar columns.
+#[derive(Template)]
+#[template(path = "page_wide.html")]
+pub struct WidePageTemplate {
+    pub view: LayoutContext<PageView>,
+}
+
+/// Full-bleed body outside the split frame, for front pages.
+#[derive(Template)]
+#[template(path = "page_landing.html")]
+pub struct LandingPageTemplate {
+    pub view: LayoutContext<PageView>,
+}
+
+/// Render a page with the template it names.
+pub fn render_page_response(view: LayoutContext<PageView>, status: StatusCode) -> Response {
+    match view.content.template.as_str() {
+        "wide" => render_template_response(WidePageTemplate { view }, status),
+        "landing" => render_template_response(LandingPageTemplate { view }, status),
+        name => {
+            if name != DEFAULT_PAGE_TEMPLATE {
+                warn!(
+                    target = SOURCE,
+                    template = name,
+                    "unknown page template, rendering with the default"
+                );
+            }
+            render_template_response(PageTemplate { view }, status)
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::application::error::{ErrorReport, HttpError};
+use crate::infra::assets::hashed_asset_path;
 use askama::{Error as AskamaError, Template};
 use axum::{
     http::StatusCode,
@@ -61,7 +62,10 @@ pub fn render_template_response<T: Template>(template: T, status: StatusCode) ->
 }
 
 pub fn render_not_found_response(chrome: LayoutChrome) -> Response {
-    let content = ErrorPageView::not_found();
+    let mut content = ErrorPageView::not_found();
+    if let Some(action) = content.primary_action.as_mut() {
+        action.href = format!("{}{}", chrome.base_path, action.href);
+    }
     let view = LayoutContext::new(chrome, content);
     let mut response = render_template_response(ErrorTemplate { view }, StatusCode::NOT_FOUND);
     ErrorReport::from_message(
@@ -103,6 +107,13 @@ pub struct LayoutChrome {
     pub navigation: NavigationView,
     pub footer: FooterView,
     pub meta: PageMetaView,
+    /// Prefix for root-relative links such as static assets; empty at the root.
+    pub base_path: String,
+    /// A PNG or ICO favicon is set, so the icon link points at it instead of
+    /// the SVG.
+    pub raster_favicon: bool,
+    /// Slug of the page served at `/`; `None` while `/` serves the feed.
+    pub homepage_slug: Option<String>,
 }
 
 impl LayoutChrome {
@@ -124,7 +135,8 @@ pub struct LayoutContext<T> {
     pub navigation: NavigationView,
     pub footer: FooterView,
     pub meta: PageMetaView,
-    pub asset_version: String,
+    pub base_path: String,
+    pub raster_favicon: bool,
     pub content: T,
 }
 
@@ -135,14 +147,17 @@ impl<T> LayoutContext<T> {
             navigation: chrome.navigation,
             footer: chrome.footer,
             meta: chrome.meta,
-            asset_version: asset_version(),
+            base_path: chrome.base_path,
+            raster_favicon: chrome.raster_favicon,
             content,
         }
     }
-}
 
-fn asset_version() -> String {
-    env!("CARGO_PKG_VERSION").to_string()
+    /// Content-addressed URL of the static asset at `path` under `/static/`,
+    /// e.g. `view.asset("common/tokens.css")`.
+    pub fn asset(&self, path: &str) -> String {
+        format!("{}{}", self.base_path, hashed_asset_path(path))
+    }
 }
 
 #[derive(Clone)]
@@ -154,6 +169,7 @@ pub struct TagBadge {
 #[derive(Clone)]
 pub struct PostCard {
     pub slug: String,
+    pub path: String,
     pub title: String,
     pub excerpt: String,
     pub iso_date: String,
@@ -178,6 +194,20 @@ pub struct MonthSummary {
     pub is_active: bool,
 }
 
+/// Heading and neighbouring-period links of a year or month archive.
+#[derive(Clone)]
+pub struct ArchiveView {
+    pub title: String,
+    pub previous: Option<ArchiveLinkView>,
+    pub next: Option<ArchiveLinkView>,
+}
+
+#[derive(Clone)]
+pub struct ArchiveLinkView {
+    pub label: String,
+    pub path: String,
+}
+
 pub struct PageContext {
     pub posts: Vec<PostCard>,
     pub post_count: usize,
@@ -189,7 +219,15 @@ pub struct PageContext {
     pub show_month_filters: bool,
     pub next_cursor: Option<String>,
     pub load_more_query: String,
-    pub posts_ld_json: Option<String>,
+    /// Serialized JSON-LD, already escaped for a `<script>` element.
+    pub ld_json: Option<String>,
+    pub base_path: String,
+    /// `homepage_layout` of the post feed; tag and month archives are `list`.
+    pub layout: &'static str,
+    /// Post shown above the others on the first page of a magazine feed.
+    pub featured: Option<PostCard>,
+    /// Set on `/archive` pages.
+    pub archive: Option<ArchiveView>,
 }
 
 #[derive(Template)]
@@ -208,6 +246,7 @@ pub struct FeedLoaderContext {
     pub has_results: bool,
     pub next_cursor: Option<String>,
     pub load_more_query: String,
+    pub base_path: String,
 }
 
 #[derive(Template)]
@@ -221,6 +260,7 @@ pub struct FeedLoaderTemplate {
 pub struct PostCardsAppendTemplate {
     pub posts: Vec<PostCard>,
     pub offset: usize,
+    pub base_path: String,
 }
 
 pub struct PostDetailContext {
@@ -237,6 +277,27 @@ pub struct PostDetailContext {
     pub has_mermaid_diagrams: bool,
     pub toc: Option<PostTocView>,
     pub is_pinned: bool,
+    pub comments: Vec<PostCommentView>,
+    pub lang: Option<String>,
+    /// Published variants of this post, itself included; empty when untranslated.
+    pub translations: Vec<TranslationLinkView>,
+    /// Serialized JSON-LD, already escaped for a `<script>` element.
+    pub ld_json: Option<String>,
+}
+
+/// One language variant of a translated post or page.
+#[derive(Clone)]
+pub struct TranslationLinkView {
+    pub lang: String,
+    pub slug: String,
+}
+
+#[derive(Clone)]
+pub struct PostCommentView {
+    pub author: String,
+    pub body: String,
+    pub published: String,
+    pub iso_date: String,
 }
 
 #[derive(Clone)]
@@ -281,6 +342,11 @@ pub struct PageView {
     pub contains_code: bool,
     pub contains_math: bool,
     pub contains_mermaid: bool,
+    pub lang: Option<String>,
+    /// Published variants of this page, itself included; empty when untranslated.
+    pub translations: Vec<TranslationLinkView>,
+    /// Name of the registered page template to render with.
+    pub template: String,
 }
 
 #[derive(Template)]
@@ -337,7 +403,18 @@ pub struct PageMetaView {
     pub description: String,
     pub og_title: String,
     pub og_description: String,
+    /// Absolute when `public_site_url` is set, root-relative otherwise.
     pub canonical: String,
+    /// Value of the document's `lang` attribute.
+    pub lang: String,
+    /// `hreflang` alternates for translated content.
+    pub alternates: Vec<AlternateLinkView>,
+}
+
+#[derive(Clone)]
+pub struct AlternateLinkView {
+    pub hreflang: String,
+    pub href: String,
 }
 
 impl PageMetaView {
@@ -345,6 +422,25 @@ impl PageMetaView {
         Self { canonical, ..self }
     }
 
+    /// `og:url` must be absolute, so it is left out with a relative canonical.
+    pub fn og_url(&self) -> Option<&str> {
+        let absolute =
+            self.canonical.starts_with("https://") || self.canonical.starts_with("http://");
+        absolute.then_some(self.canonical.as_str())
+    }
+
+    pub fn with_translations(
+        self,
+        lang: Option<String>,
+        alternates: Vec<AlternateLinkView>,
+    ) -> Self {
+        Self {
+            lang: lang.unwrap_or(self.lang),
+            alternates,
+            ..self
+        }
+    }
+
     pub fn with_content(self, title: String, description: String) -> Self {
         Self {
             title: title.clone(),
@@ -368,6 +464,25 @@ where
         .collect()
 }
 
+/// Build the language links of a translation group from `(lang, slug)` pairs.
+///
+/// A group with a single published member has nothing to link to, so it
+/// yields no links and the page renders as if it were untranslated.
+pub fn build_translation_links<'a, T>(translations: T) -> Vec<TranslationLinkView>
+where
+    T: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let links: Vec<TranslationLinkView> = translations
+        .into_iter()
+        .map(|(lang, slug)| TranslationLinkView {
+            lang: lang.to_string(),
+            slug: slug.to_string(),
+        })
+        .collect();
+
+    if links.len() < 2 { Vec::new() } else { links }
+}
+
 pub fn title_case(tag: &str) -> String {
     if tag.eq_ignore_ascii_case("ai") {
         return "AI".to_string();
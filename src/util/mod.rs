@@ -1,2 +1,3 @@
 pub mod bytes;
+pub mod secret;
 pub mod timezone;
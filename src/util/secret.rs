@@ -0,0 +1,11 @@
+//! Random key material for signing keys that have no configured secret.
+
+use uuid::Uuid;
+
+/// 32 random bytes, built from two v4 UUIDs.
+pub fn random_secret() -> Vec<u8> {
+    let mut secret = Vec::with_capacity(32);
+    secret.extend_from_slice(Uuid::new_v4().as_bytes());
+    secret.extend_from_slice(Uuid::new_v4().as_bytes());
+    secret
+}
@@ -1,6 +1,6 @@
 use chrono::Datelike;
 use chrono_tz::Tz;
-use sqlx::types::chrono::{DateTime, TimeZone, Utc};
+use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use time::{Date, Month, OffsetDateTime, UtcOffset};
 
 pub fn localized_datetime(time: OffsetDateTime, tz: Tz) -> DateTime<Tz> {
@@ -21,3 +21,40 @@ pub fn localized_date(time: OffsetDateTime, tz: Tz) -> Date {
         u8::try_from(localized.day()).expect("valid day value from chrono to time conversion");
     Date::from_calendar_date(localized.year(), month, day).expect("valid calendar date")
 }
+
+/// Parse an RFC 3339 timestamp such as `2026-10-16T08:00:00Z`.
+pub fn parse_rfc3339(value: &str) -> Option<OffsetDateTime> {
+    let parsed = DateTime::parse_from_rfc3339(value.trim()).ok()?;
+    from_chrono(parsed.with_timezone(&Utc))
+}
+
+/// The first instant of `date` in `tz`. On days that start inside a DST gap
+/// this is the earliest valid local time.
+pub fn local_day_start(date: NaiveDate, tz: Tz) -> Option<OffsetDateTime> {
+    local_instant(date.and_hms_opt(0, 0, 0)?, tz)
+}
+
+/// The instant a wall-clock time in `tz` names. A time skipped by a DST
+/// change moves an hour later, and a repeated one takes its first instant.
+pub fn local_instant(local: NaiveDateTime, tz: Tz) -> Option<OffsetDateTime> {
+    let resolved = tz.from_local_datetime(&local).earliest().or_else(|| {
+        tz.from_local_datetime(&(local + chrono::Duration::hours(1)))
+            .earliest()
+    })?;
+    from_chrono(resolved.with_timezone(&Utc))
+}
+
+/// [`local_day_start`] for a `time` date.
+pub fn local_date_start(date: Date, tz: Tz) -> Option<OffsetDateTime> {
+    let date = NaiveDate::from_ymd_opt(
+        date.year(),
+        u32::from(u8::from(date.month())),
+        u32::from(date.day()),
+    )?;
+    local_day_start(date, tz)
+}
+
+fn from_chrono(datetime: DateTime<Utc>) -> Option<OffsetDateTime> {
+    let nanos = datetime.timestamp_nanos_opt()?;
+    OffsetDateTime::from_unix_timestamp_nanos(i128::from(nanos)).ok()
+}
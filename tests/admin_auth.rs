@@ -0,0 +1,174 @@
+mod support;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::header::{CONTENT_TYPE, COOKIE, LOCATION};
+use axum::http::{Request, StatusCode};
+use soffio::application::admin::auth::{
+    AdminAuthError, AdminAuthService, AdminSessionKeys, CreateAdminUserCommand,
+};
+use soffio::infra::db::PostgresRepositories;
+use sqlx::PgPool;
+use time::{Duration, OffsetDateTime};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use support::test_app::TestApp;
+
+fn service(pool: PgPool) -> AdminAuthService {
+    let repos = Arc::new(PostgresRepositories::new(pool));
+    AdminAuthService::new(repos, AdminSessionKeys::ephemeral(Duration::hours(1)))
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn created_user_can_authenticate(pool: PgPool) {
+    let auth = service(pool);
+
+    let user = auth
+        .create_user(CreateAdminUserCommand {
+            username: "Editor".to_string(),
+            password: "correct horse battery".to_string(),
+        })
+        .await
+        .expect("create admin user");
+    assert_eq!(user.username, "editor");
+    assert!(user.password_hash.starts_with("$argon2id$"));
+
+    let signed_in = auth
+        .authenticate("editor", "correct horse battery")
+        .await
+        .expect("valid credentials");
+    assert_eq!(signed_in.id, user.id);
+
+    let err = auth
+        .authenticate("editor", "wrong password!")
+        .await
+        .expect_err("wrong password rejected");
+    assert!(matches!(err, AdminAuthError::InvalidCredentials));
+
+    let err = auth
+        .authenticate("nobody", "correct horse battery")
+        .await
+        .expect_err("unknown user rejected");
+    assert!(matches!(err, AdminAuthError::InvalidCredentials));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_user_rejects_duplicates_and_short_passwords(pool: PgPool) {
+    let auth = service(pool);
+
+    let err = auth
+        .create_user(CreateAdminUserCommand {
+            username: "editor".to_string(),
+            password: "short".to_string(),
+        })
+        .await
+        .expect_err("short password rejected");
+    assert!(matches!(err, AdminAuthError::ConstraintViolation(_)));
+
+    auth.create_user(CreateAdminUserCommand {
+        username: "editor".to_string(),
+        password: "correct horse battery".to_string(),
+    })
+    .await
+    .expect("create admin user");
+
+    let err = auth
+        .create_user(CreateAdminUserCommand {
+            username: "EDITOR".to_string(),
+            password: "another long password".to_string(),
+        })
+        .await
+        .expect_err("duplicate username rejected");
+    assert!(matches!(err, AdminAuthError::DuplicateUsername(_)));
+}
+
+/// A session cookie for `editor` and the CSRF token that goes with it.
+fn signed_in(app: &TestApp) -> (String, String) {
+    let keys = app.admin.auth.keys();
+    let (session, value) = keys.issue("editor", OffsetDateTime::now_utc());
+    (
+        format!("soffio_admin_session={value}"),
+        keys.csrf_token(&session),
+    )
+}
+
+fn pin_request(cookie: &str) -> axum::http::request::Builder {
+    Request::builder()
+        .method("POST")
+        .uri(format!("/posts/{}/pin", Uuid::new_v4()))
+        .header(COOKIE, cookie)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn admin_pages_redirect_to_login_without_a_session(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+
+    for cookie in [None, Some("soffio_admin_session=forged.value")] {
+        let mut request = Request::builder().uri("/posts");
+        if let Some(cookie) = cookie {
+            request = request.header(COOKIE, cookie);
+        }
+        let response = app
+            .admin_router()
+            .oneshot(request.body(Body::empty()).expect("build request"))
+            .await
+            .expect("send request");
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER, "{cookie:?}");
+        assert_eq!(response.headers()[LOCATION], "/login", "{cookie:?}");
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn admin_writes_require_a_matching_csrf_token(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let (cookie, token) = signed_in(&app);
+
+    let missing = pin_request(&cookie)
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::empty())
+        .expect("build request");
+    let wrong_header = pin_request(&cookie)
+        .header("x-csrf-token", "not-the-token")
+        .body(Body::empty())
+        .expect("build request");
+    let wrong_field = pin_request(&cookie)
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from("csrf_token=not-the-token"))
+        .expect("build request");
+    for request in [missing, wrong_header, wrong_field] {
+        let response = app
+            .admin_router()
+            .oneshot(request)
+            .await
+            .expect("send request");
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    let response = app
+        .admin_router()
+        .oneshot(
+            pin_request(&cookie)
+                .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(format!("csrf_token={token}")))
+                .expect("build request"),
+        )
+        .await
+        .expect("send request");
+    assert_ne!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .admin_router()
+        .oneshot(
+            pin_request(&cookie)
+                .header("x-csrf-token", token)
+                .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::empty())
+                .expect("build request"),
+        )
+        .await
+        .expect("send request");
+    assert_ne!(response.status(), StatusCode::FORBIDDEN);
+}
@@ -1,13 +1,19 @@
+use std::sync::Arc;
+
+use soffio::application::admin::dashboard::{
+    AdminDashboardDeps, AdminDashboardService, ScheduledKind,
+};
 use soffio::application::repos::{
-    CreateNavigationItemParams, NavigationQueryFilter, NavigationRepo, NavigationWriteRepo,
-    UploadQueryFilter, UploadsRepo,
+    CreateNavigationItemParams, CreatePageParams, CreatePostParams, NavigationQueryFilter,
+    NavigationRepo, NavigationWriteRepo, PagesWriteRepo, PostsWriteRepo, UploadQueryFilter,
+    UploadsRepo,
 };
 use soffio::domain::entities::UploadRecord;
-use soffio::domain::types::NavigationDestinationType;
+use soffio::domain::types::{NavigationDestinationType, PageStatus, PostStatus};
 use soffio::domain::uploads::UploadMetadata;
 use soffio::infra::db::PostgresRepositories;
 use sqlx::PgPool;
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
 #[sqlx::test(migrations = "./migrations")]
@@ -87,6 +93,8 @@ async fn dashboard_upload_total_bytes_matches_seed(pool: PgPool) {
             stored_path: "uploads/first.png".to_string(),
             metadata: UploadMetadata::default(),
             created_at,
+            last_verified_at: None,
+            checksum_mismatch: false,
         },
     )
     .await
@@ -103,6 +111,8 @@ async fn dashboard_upload_total_bytes_matches_seed(pool: PgPool) {
             stored_path: "uploads/second.pdf".to_string(),
             metadata: UploadMetadata::default(),
             created_at,
+            last_verified_at: None,
+            checksum_mismatch: false,
         },
     )
     .await
@@ -124,3 +134,108 @@ async fn dashboard_upload_total_bytes_matches_seed(pool: PgPool) {
     .expect("sum image upload bytes");
     assert_eq!(image_bytes, 10);
 }
+
+async fn create_post(
+    repos: &PostgresRepositories,
+    slug: &str,
+    status: PostStatus,
+    scheduled_at: Option<OffsetDateTime>,
+) {
+    PostsWriteRepo::create_post(
+        repos,
+        CreatePostParams {
+            slug: slug.to_string(),
+            title: slug.to_string(),
+            excerpt: String::new(),
+            excerpt_generated: false,
+            body_markdown: "Body".to_string(),
+            status,
+            pinned: false,
+            scheduled_at,
+            published_at: (status == PostStatus::Published).then(OffsetDateTime::now_utc),
+            archived_at: None,
+            summary_markdown: None,
+            summary_html: None,
+        },
+    )
+    .await
+    .expect("create post");
+}
+
+async fn create_page(repos: &PostgresRepositories, slug: &str, scheduled_at: OffsetDateTime) {
+    PagesWriteRepo::create_page(
+        repos,
+        CreatePageParams {
+            slug: slug.to_string(),
+            title: slug.to_string(),
+            body_markdown: "Body".to_string(),
+            rendered_html: "<p>Body</p>".to_string(),
+            status: PageStatus::Draft,
+            scheduled_at: Some(scheduled_at),
+            published_at: None,
+            archived_at: None,
+            template: "default".to_string(),
+        },
+    )
+    .await
+    .expect("create page");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn dashboard_upcoming_lists_scheduled_items_soonest_first(pool: PgPool) {
+    let repos = Arc::new(PostgresRepositories::new(pool));
+    let now = OffsetDateTime::now_utc();
+
+    create_post(
+        &repos,
+        "next-week",
+        PostStatus::Draft,
+        Some(now + Duration::days(7)),
+    )
+    .await;
+    create_post(
+        &repos,
+        "overdue",
+        PostStatus::Draft,
+        Some(now - Duration::hours(2)),
+    )
+    .await;
+    create_post(&repos, "unscheduled", PostStatus::Draft, None).await;
+    create_post(
+        &repos,
+        "already-live",
+        PostStatus::Published,
+        Some(now + Duration::days(1)),
+    )
+    .await;
+    create_page(&repos, "tomorrow", now + Duration::days(1)).await;
+    create_page(&repos, "next-month", now + Duration::days(30)).await;
+
+    let dashboard = AdminDashboardService::new(AdminDashboardDeps {
+        posts: repos.clone(),
+        pages: repos.clone(),
+        tags: repos.clone(),
+        navigation: repos.clone(),
+        uploads: repos.clone(),
+        api_keys: repos,
+    });
+
+    let upcoming = dashboard.upcoming(10, now).await.expect("list upcoming");
+    let listed: Vec<_> = upcoming
+        .iter()
+        .map(|item| (item.slug.as_str(), item.kind, item.stuck))
+        .collect();
+    assert_eq!(
+        listed,
+        [
+            ("overdue", ScheduledKind::Post, true),
+            ("tomorrow", ScheduledKind::Page, false),
+            ("next-week", ScheduledKind::Post, false),
+            ("next-month", ScheduledKind::Page, false),
+        ]
+    );
+
+    let first_two = dashboard.upcoming(2, now).await.expect("list upcoming");
+    let slugs: Vec<_> = first_two.iter().map(|item| item.slug.as_str()).collect();
+    assert_eq!(slugs, ["overdue", "tomorrow"]);
+}
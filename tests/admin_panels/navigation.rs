@@ -1,8 +1,7 @@
 use super::*;
 
-#[test]
-fn snapshot_admin_navigation_panel() {
-    let content = AdminNavigationListView {
+fn navigation_list(items: Vec<AdminNavigationRowView>) -> AdminNavigationListView {
+    AdminNavigationListView {
         heading: "Navigation".into(),
         filters: vec![
             AdminNavigationStatusFilterView {
@@ -24,20 +23,7 @@ fn snapshot_admin_navigation_panel() {
                 is_active: false,
             },
         ],
-        items: vec![AdminNavigationRowView {
-            id: "nav1".into(),
-            label: "About".into(),
-            preview_href: "https://example.com/about".into(),
-            destination_type_label: "Internal".into(),
-            destination_type_status: "internal".into(),
-            destination_display: "/about".into(),
-            sort_order: 1,
-            visible: true,
-            toggle_action: "/navigation/nav1/visibility".into(),
-            toggle_label: "Hide",
-            edit_href: "/navigation/nav1/edit".into(),
-            delete_action: "/navigation/nav1/delete".into(),
-        }],
+        items,
         filter_search: Some("about".into()),
         filter_tag: None,
         filter_month: None,
@@ -61,9 +47,45 @@ fn snapshot_admin_navigation_panel() {
         tag_filter_all_label: "All tags".into(),
         tag_filter_field: "tag".into(),
         custom_hidden_fields: Vec::new(),
-    };
+    }
+}
+
+fn internal_row(slug: &str, destination_warning: Option<String>) -> AdminNavigationRowView {
+    AdminNavigationRowView {
+        id: "nav1".into(),
+        label: "About".into(),
+        preview_href: format!("https://example.com/{slug}"),
+        destination_type_label: "Internal".into(),
+        destination_type_status: "internal".into(),
+        destination_display: format!("/{slug}"),
+        destination_warning,
+        sort_order: 1,
+        visible: true,
+        toggle_action: "/navigation/nav1/visibility".into(),
+        toggle_label: "Hide",
+        edit_href: "/navigation/nav1/edit".into(),
+        delete_action: "/navigation/nav1/delete".into(),
+    }
+}
+
+#[test]
+fn snapshot_admin_navigation_panel() {
+    let content = navigation_list(vec![internal_row("about", None)]);
 
     let template = AdminNavigationPanelTemplate { content };
     let rendered = template.render().unwrap();
     assert_admin_snapshot!("admin_navigation_panel", rendered);
 }
+
+#[test]
+fn navigation_panel_warns_about_shadowed_page_destinations() {
+    let content = navigation_list(vec![internal_row(
+        "posts",
+        Some("Shadowed by the built-in route /posts/{slug}".into()),
+    )]);
+
+    let rendered = AdminNavigationPanelTemplate { content }.render().unwrap();
+    assert!(rendered.contains(
+        r#"<span data-role="destination-warning" role="note">Shadowed by the built-in route /posts/{slug}</span>"#
+    ));
+}
@@ -25,6 +25,7 @@ fn snapshot_admin_pages_panel() {
             }],
             preview_href: "http://localhost:3000/pages/_preview/321".into(),
             edit_href: "/pages/about/edit".into(),
+            pin_order: 0,
             snapshots_href: Some("/pages/321/snapshots".into()),
         }],
         filter_search: Some("about".into()),
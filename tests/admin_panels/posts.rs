@@ -1,8 +1,7 @@
 use super::*;
 
-#[test]
-fn snapshot_admin_posts_panel() {
-    let content = AdminPostListView {
+fn sample_posts_panel() -> AdminPostListView {
+    AdminPostListView {
         heading: "Posts".into(),
         filters: vec![AdminPostStatusFilterView {
             status_key: None,
@@ -37,7 +36,15 @@ fn snapshot_admin_posts_panel() {
             preview_href: "http://localhost:3000/posts/_preview/123".into(),
             edit_href: "/posts/hello-world/edit".into(),
             is_pinned: false,
+            pin_order: 0,
             snapshots_href: Some("/posts/123/snapshots".into()),
+            render_progress: AdminRenderProgressView {
+                post_id: "123".into(),
+                state_key: "rendering",
+                label: "Rendering",
+                error: None,
+                in_flight: true,
+            },
         }],
         tag_options: vec![AdminPostTagOption {
             slug: "rust".into(),
@@ -76,9 +83,71 @@ fn snapshot_admin_posts_panel() {
         month_filter_enabled: true,
         row_action_prefix: "/posts".into(),
         custom_hidden_fields: Vec::new(),
-    };
+    }
+}
 
-    let template = AdminPostsPanelTemplate { content };
+#[test]
+fn snapshot_admin_posts_panel() {
+    let template = AdminPostsPanelTemplate {
+        content: sample_posts_panel(),
+    };
     let rendered = template.render().unwrap();
     assert_admin_snapshot!("admin_posts_panel", rendered);
 }
+
+#[test]
+fn posts_panel_carries_page_size_override_through_every_form() {
+    let mut content = sample_posts_panel();
+    content.custom_hidden_fields = vec![AdminHiddenField::new("limit", "50")];
+
+    let rendered = AdminPostsPanelTemplate { content }.render().unwrap();
+
+    // One status tab, the filter form, and the previous/next pagination forms.
+    let limit_input = r#"<input type="hidden" name="limit" value="50">"#;
+    assert_eq!(rendered.matches(limit_input).count(), 4);
+}
+
+#[test]
+fn post_preview_pane_renders_open_and_closed() {
+    let open = AdminPostPreviewTemplate {
+        preview: Some(AdminPostPreviewView {
+            html: "<p>Hello <strong>world</strong></p>".into(),
+            mermaid_fallbacks: 2,
+        }),
+    }
+    .render()
+    .unwrap();
+    assert!(open.contains(r#"data-role="post-preview" data-state="open""#));
+    assert!(open.contains(r#"name="preview_open" value="true" form="post-editor-form""#));
+    assert!(open.contains("<p>Hello <strong>world</strong></p>"));
+    assert!(open.contains("2 diagrams are shown as code"));
+
+    let closed = AdminPostPreviewTemplate { preview: None }.render().unwrap();
+    assert!(closed.contains(r#"data-role="post-preview" data-state="closed""#));
+    assert!(!closed.contains("preview_open"));
+}
+
+#[test]
+fn render_progress_badge_polls_only_while_in_flight() {
+    let badge = |state_key, in_flight, error: Option<&str>| {
+        AdminRenderProgressTemplate {
+            progress: AdminRenderProgressView {
+                post_id: "123".into(),
+                state_key,
+                label: "Label",
+                error: error.map(str::to_string),
+                in_flight,
+            },
+        }
+        .render()
+        .unwrap()
+    };
+
+    let pending = badge("pending", true, None);
+    assert!(pending.contains(r#"id="render-progress-123""#));
+    assert!(pending.contains("@get(`/posts/123/render-progress`)"));
+
+    let failed = badge("failed", false, Some("bad math"));
+    assert!(failed.contains(r#"data-status="failed" title="bad math">"#));
+    assert!(!failed.contains("data-on-interval"));
+}
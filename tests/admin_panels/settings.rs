@@ -92,6 +92,24 @@ fn render_admin_settings_edit_template() {
                     placeholder: Some("Asia/Shanghai".into()),
                 },
             },
+            AdminSettingsEditSimpleField {
+                label: "Feed Content".into(),
+                input: AdminSettingsEditInputKind::Select {
+                    name: "feed_content_mode".into(),
+                    options: vec![
+                        AdminSettingsEditSelectOption {
+                            value: "excerpt",
+                            label: "Excerpt",
+                            selected: false,
+                        },
+                        AdminSettingsEditSelectOption {
+                            value: "full",
+                            label: "Full post",
+                            selected: true,
+                        },
+                    ],
+                },
+            },
         ],
         multiline_fields: vec![AdminSettingsEditMultilineField {
             label: "Footer Copy".into(),
@@ -111,4 +129,6 @@ fn render_admin_settings_edit_template() {
 
     let rendered = template.render().unwrap();
     assert!(rendered.contains("Last updated"));
+    assert!(rendered.contains(r#"<select name="feed_content_mode">"#));
+    assert!(rendered.contains(r#"<option value="full" selected>Full post</option>"#));
 }
@@ -24,6 +24,7 @@ fn render_admin_uploads_template() {
         delete_action: "/uploads/00000000-0000-0000-0000-000000000000/delete".into(),
         preview_href: Some("https://example.com/uploads/diagram.png?height=480&width=640".into()),
         public_href: "https://example.com/uploads/diagram.png?height=480&width=640".into(),
+        checksum_mismatch: false,
     }];
 
     let content = AdminUploadListView {
@@ -67,4 +68,5 @@ fn render_admin_uploads_template() {
     let rendered = template.render().unwrap();
     assert!(rendered.contains("Upload File"));
     assert!(rendered.contains("diagram.png"));
+    assert!(!rendered.contains("upload-integrity-warning"));
 }
@@ -15,13 +15,16 @@ use soffio::domain::entities::UploadRecord;
 use soffio::infra::http::api::handlers;
 use soffio::infra::http::api::models::*;
 use soffio::infra::http::api::state::ApiState;
+use soffio::infra::http::api::strict_json::StrictJson;
 use tower::ServiceExt;
 use uuid::Uuid;
 
 #[path = "support/mod.rs"]
 mod support;
 
-use support::api_harness::{build_state, response_json, string_field, uuid_field};
+use support::api_harness::{build_state, if_match, response_json, string_field, uuid_field};
+use support::fixtures::{FixturePage, FixturePost};
+use support::test_app::TestApp;
 
 #[path = "api/rate_limit.rs"]
 mod rate_limit;
@@ -52,3 +55,27 @@ mod audit;
 
 #[path = "api/api_keys.rs"]
 mod api_keys;
+
+#[path = "api/access_policy.rs"]
+mod access_policy;
+
+#[path = "api/comments.rs"]
+mod comments;
+
+#[path = "api/request_id.rs"]
+mod request_id;
+
+#[path = "api/render.rs"]
+mod render;
+
+#[path = "api/idempotency.rs"]
+mod idempotency;
+
+#[path = "api/static_export.rs"]
+mod static_export;
+
+#[path = "api/schedule.rs"]
+mod schedule;
+
+#[path = "api/cursors.rs"]
+mod cursors;
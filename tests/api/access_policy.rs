@@ -0,0 +1,171 @@
+use super::*;
+
+use soffio::application::api_keys::ApiPrincipal;
+
+// ============ Scope Before Existence ============
+//
+// A key without the resource's scope gets 403 whether or not the resource
+// exists; a key with it gets 404 for an id that is not there.
+
+#[derive(Debug, Clone, Copy)]
+enum Resource {
+    Post,
+    Page,
+    Tag,
+    Navigation,
+    Upload,
+    Snapshot,
+    Job,
+}
+
+impl Resource {
+    const ALL: [Resource; 7] = [
+        Resource::Post,
+        Resource::Page,
+        Resource::Tag,
+        Resource::Navigation,
+        Resource::Upload,
+        Resource::Snapshot,
+        Resource::Job,
+    ];
+
+    fn read_scope(self) -> ApiScope {
+        match self {
+            Resource::Post => ApiScope::PostRead,
+            Resource::Page => ApiScope::PageRead,
+            Resource::Tag => ApiScope::TagRead,
+            Resource::Navigation => ApiScope::NavigationRead,
+            Resource::Upload => ApiScope::UploadRead,
+            Resource::Snapshot => ApiScope::SnapshotRead,
+            Resource::Job => ApiScope::JobRead,
+        }
+    }
+
+    /// Jobs are read-only through the API.
+    fn write_scope(self) -> Option<ApiScope> {
+        match self {
+            Resource::Post => Some(ApiScope::PostWrite),
+            Resource::Page => Some(ApiScope::PageWrite),
+            Resource::Tag => Some(ApiScope::TagWrite),
+            Resource::Navigation => Some(ApiScope::NavigationWrite),
+            Resource::Upload => Some(ApiScope::UploadWrite),
+            Resource::Snapshot => Some(ApiScope::SnapshotWrite),
+            Resource::Job => None,
+        }
+    }
+}
+
+fn status_of<T: IntoResponse, E: IntoResponse>(result: Result<T, E>) -> StatusCode {
+    match result {
+        Ok(resp) => resp.into_response().status(),
+        Err(err) => err.into_response().status(),
+    }
+}
+
+async fn read_missing(state: &ApiState, principal: ApiPrincipal, resource: Resource) -> StatusCode {
+    let id = Uuid::new_v4();
+    match resource {
+        Resource::Post => status_of(
+            handlers::get_post_by_id(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Page => status_of(
+            handlers::get_page_by_id(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Tag => status_of(
+            handlers::get_tag_by_id(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Navigation => status_of(
+            handlers::get_navigation_item(State(state.clone()), Extension(principal), Path(id))
+                .await,
+        ),
+        Resource::Upload => status_of(
+            handlers::get_upload(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Snapshot => status_of(
+            handlers::get_snapshot(State(state.clone()), Path(id), Extension(principal)).await,
+        ),
+        Resource::Job => status_of(
+            handlers::get_job(
+                State(state.clone()),
+                Extension(principal),
+                Path(id.to_string()),
+            )
+            .await,
+        ),
+    }
+}
+
+/// `None` for resources the API cannot write.
+async fn write_missing(
+    state: &ApiState,
+    principal: ApiPrincipal,
+    resource: Resource,
+) -> Option<StatusCode> {
+    let id = Uuid::new_v4();
+    let status = match resource {
+        Resource::Post => status_of(
+            handlers::delete_post(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Page => status_of(
+            handlers::delete_page(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Tag => status_of(
+            handlers::delete_tag(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Navigation => status_of(
+            handlers::delete_navigation(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Upload => status_of(
+            handlers::delete_upload(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Snapshot => status_of(
+            handlers::rollback_snapshot(State(state.clone()), Extension(principal), Path(id)).await,
+        ),
+        Resource::Job => return None,
+    };
+    Some(status)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn scope_is_checked_before_existence_for_every_resource(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+
+    for resource in Resource::ALL {
+        // Keys need at least one scope; "no scope" holds one no resource uses.
+        let mut classes = vec![
+            (
+                "no scope",
+                ApiScope::RenderPreview,
+                StatusCode::FORBIDDEN,
+                StatusCode::FORBIDDEN,
+            ),
+            (
+                "read scope",
+                resource.read_scope(),
+                StatusCode::NOT_FOUND,
+                StatusCode::FORBIDDEN,
+            ),
+        ];
+        if let Some(scope) = resource.write_scope() {
+            classes.push((
+                "write scope",
+                scope,
+                StatusCode::FORBIDDEN,
+                StatusCode::NOT_FOUND,
+            ));
+        }
+
+        for (class, scope, read_status, write_status) in classes {
+            let principal = app.principal_with(&[scope]).await;
+
+            assert_eq!(
+                read_missing(&app.api, principal.clone(), resource).await,
+                read_status,
+                "{resource:?} read with {class}"
+            );
+            if let Some(status) = write_missing(&app.api, principal, resource).await {
+                assert_eq!(status, write_status, "{resource:?} write with {class}");
+            }
+        }
+    }
+}
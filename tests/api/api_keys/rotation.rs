@@ -109,3 +109,90 @@ async fn api_rotate_recalculates_expiration_preserves_created_at(pool: PgPool) {
     let new_auth = state.api_keys.authenticate(&rotated.token).await;
     assert!(new_auth.is_ok(), "new token should authenticate");
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_rotate_all_replaces_every_active_token(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+
+    let mut old_tokens = vec![token];
+    for name in ["ci", "deploy", "backup"] {
+        let issued = state
+            .api_keys
+            .issue(IssueApiKeyCommand {
+                name: name.to_string(),
+                description: None,
+                scopes: vec![ApiScope::PostRead],
+                expires_in: None,
+                created_by: "tests".to_string(),
+            })
+            .await
+            .unwrap();
+        old_tokens.push(issued.token);
+    }
+    let revoked = state
+        .api_keys
+        .issue(IssueApiKeyCommand {
+            name: "revoked".to_string(),
+            description: None,
+            scopes: vec![ApiScope::PostRead],
+            expires_in: None,
+            created_by: "tests".to_string(),
+        })
+        .await
+        .unwrap();
+    state.api_keys.revoke(revoked.record.id).await.unwrap();
+
+    let Json(outcome) = handlers::rotate_all_api_keys(State(state.clone()), Extension(principal))
+        .await
+        .expect("rotate all keys");
+
+    assert!(outcome.failed.is_empty(), "{:?}", outcome.failed);
+    assert_eq!(outcome.rotated.len(), old_tokens.len());
+    assert!(
+        outcome
+            .rotated
+            .iter()
+            .all(|key| key.id != revoked.record.id),
+        "revoked keys stay revoked"
+    );
+    for old in &old_tokens {
+        assert!(
+            state.api_keys.authenticate(old).await.is_err(),
+            "old token still authenticates"
+        );
+    }
+    for key in &outcome.rotated {
+        let principal = state
+            .api_keys
+            .authenticate(&key.token)
+            .await
+            .expect("new token authenticates");
+        assert_eq!(principal.key_id, key.id);
+        assert_eq!(principal.prefix, key.prefix);
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_rotate_all_requires_every_scope(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let limited = state
+        .api_keys
+        .issue(IssueApiKeyCommand {
+            name: "limited".to_string(),
+            description: None,
+            scopes: vec![ApiScope::PostRead, ApiScope::PostWrite],
+            expires_in: None,
+            created_by: "tests".to_string(),
+        })
+        .await
+        .unwrap();
+    let principal = state.api_keys.authenticate(&limited.token).await.unwrap();
+
+    let err = handlers::rotate_all_api_keys(State(state.clone()), Extension(principal))
+        .await
+        .expect_err("a partial key cannot rotate every key");
+
+    assert_eq!(err.into_response().status(), StatusCode::FORBIDDEN);
+    assert!(state.api_keys.authenticate(&token).await.is_ok());
+}
@@ -12,8 +12,9 @@ async fn api_snapshots_cover_create_get_list_and_rollback(pool: PgPool) {
         handlers::create_post(
             State(state.clone()),
             Extension(principal.clone()),
-            Json(PostCreateRequest {
+            StrictJson(PostCreateRequest {
                 title: "snap-post".into(),
+                slug: None,
                 excerpt: "excerpt".into(),
                 body_markdown: "# body".into(),
                 summary_markdown: None,
@@ -86,7 +87,8 @@ async fn api_snapshots_cover_create_get_list_and_rollback(pool: PgPool) {
             State(state.clone()),
             Extension(principal.clone()),
             Path(post_id),
-            Json(PostUpdateRequest {
+            if_match("*"),
+            StrictJson(PostUpdateRequest {
                 slug: post_slug.clone(),
                 title: "changed".into(),
                 excerpt: "changed excerpt".into(),
@@ -184,3 +186,142 @@ async fn api_snapshots_cover_create_get_list_and_rollback(pool: PgPool) {
     assert_eq!(status, StatusCode::OK);
     assert_eq!(uuid_field(&get_json, "id"), snapshot_id);
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn shared_snapshot_links_render_the_snapshot_until_they_expire(pool: PgPool) {
+    use axum::http::header::CACHE_CONTROL;
+
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    let principal = test_app.principal();
+    let previews = test_app.http.snapshot_preview.clone();
+    let app = test_app.router();
+
+    let (status, post_json) = response_json(
+        handlers::create_post(
+            State(state.clone()),
+            Extension(principal.clone()),
+            StrictJson(PostCreateRequest {
+                title: "Snapshot title".into(),
+                slug: None,
+                excerpt: "excerpt".into(),
+                body_markdown: "# body".into(),
+                summary_markdown: None,
+                status: soffio::domain::types::PostStatus::Draft,
+                pinned: false,
+                scheduled_at: None,
+                published_at: None,
+                archived_at: None,
+            }),
+        )
+        .await
+        .expect("create post"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let post_id = uuid_field(&post_json, "id");
+    let post_slug = string_field(&post_json, "slug").to_string();
+
+    let (status, snap_json) = response_json(
+        handlers::create_snapshot(
+            State(state.clone()),
+            Extension(principal.clone()),
+            Json(SnapshotCreateRequest {
+                entity_type: soffio::domain::types::SnapshotEntityType::Post,
+                entity_id: post_id,
+                description: None,
+            }),
+        )
+        .await
+        .expect("create snapshot"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let snapshot_id = uuid_field(&snap_json, "id");
+
+    let (status, _) = response_json(
+        handlers::update_post(
+            State(state.clone()),
+            Extension(principal),
+            Path(post_id),
+            if_match("*"),
+            StrictJson(PostUpdateRequest {
+                slug: post_slug,
+                title: "Edited title".into(),
+                excerpt: "changed excerpt".into(),
+                body_markdown: "changed body".into(),
+                summary_markdown: None,
+                pinned: false,
+            }),
+        )
+        .await
+        .expect("update post"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let now = OffsetDateTime::now_utc();
+    let get = |uri: String| {
+        let app = app.clone();
+        async move {
+            let request = Request::builder()
+                .uri(&uri)
+                .body(Body::empty())
+                .expect("build request");
+            let response = app.oneshot(request).await.expect("public request");
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+                .await
+                .expect("read body");
+            (status, headers, String::from_utf8_lossy(&body).into_owned())
+        }
+    };
+
+    let link = format!(
+        "/preview/snapshots/{snapshot_id}?token={}",
+        previews.link_token(snapshot_id, now)
+    );
+    let (status, headers, html) = get(link).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains("Snapshot title"));
+    assert!(!html.contains("Edited title"));
+    assert_eq!(headers[CACHE_CONTROL], "no-store");
+    assert_eq!(headers["x-robots-tag"], "noindex, nofollow");
+
+    let admin_link = format!(
+        "/posts/_preview/snapshot/{snapshot_id}?token={}",
+        previews.link_token(snapshot_id, now)
+    );
+    let (status, _, html) = get(admin_link).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains("Snapshot title"));
+
+    // The id in a shared link must not open the per-type previews on its own.
+    for uri in [
+        format!("/posts/_preview/snapshot/{snapshot_id}"),
+        format!("/pages/_preview/snapshot/{snapshot_id}"),
+        format!("/posts/_preview/snapshot/{snapshot_id}?token=garbage"),
+    ] {
+        let (status, _, html) = get(uri.clone()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{uri}");
+        assert!(!html.contains("Snapshot title"), "{uri}");
+    }
+
+    let expired = previews.link_token(snapshot_id, now - time::Duration::days(30));
+    let other = previews.link_token(Uuid::new_v4(), now);
+    let missing = Uuid::new_v4();
+    let missing_token = previews.link_token(missing, now);
+    for uri in [
+        format!("/preview/snapshots/{snapshot_id}?token={expired}"),
+        format!("/preview/snapshots/{snapshot_id}?token={other}"),
+        format!("/preview/snapshots/{snapshot_id}?token=garbage"),
+        format!("/preview/snapshots/{snapshot_id}"),
+        format!("/preview/snapshots/{missing}?token={missing_token}"),
+    ] {
+        let (status, headers, html) = get(uri.clone()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{uri}");
+        assert_eq!(headers[CACHE_CONTROL], "no-store", "{uri}");
+        assert!(!html.contains("Snapshot title"), "{uri}");
+    }
+}
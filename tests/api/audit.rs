@@ -2,3 +2,9 @@ use super::*;
 
 #[path = "audit_cases/list.rs"]
 mod list;
+
+#[path = "audit_cases/changes.rs"]
+mod changes;
+
+#[path = "audit_cases/actors.rs"]
+mod actors;
@@ -0,0 +1,151 @@
+use super::*;
+
+use apalis::prelude::Data;
+use soffio::application::actor::SYSTEM_ACTOR;
+use soffio::application::jobs::{PublishPostJobPayload, process_publish_post_job};
+use soffio::application::pagination::PageRequest;
+use soffio::application::repos::{AuditQueryFilter, JobQueryFilter};
+use soffio::domain::types::{ActorType, JobType, PostStatus};
+
+fn api_request(token: &str, uri: &str, payload: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("authorization", format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .expect("build request")
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_writes_and_their_jobs_are_attributed_to_the_api_key(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let (state, token) = (test_app.api.clone(), test_app.token().to_string());
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = test_app.router();
+    let label = format!("api-key:{}:{}", principal.prefix, principal.name);
+
+    let (status, created) = response_json(
+        app.clone()
+            .oneshot(api_request(
+                &token,
+                "/api/v1/posts",
+                serde_json::json!({
+                    "title": "Attributed",
+                    "excerpt": "excerpt",
+                    "body_markdown": "# body",
+                    "status": "draft",
+                }),
+            ))
+            .await
+            .expect("create post"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{created}");
+    let post_id = uuid_field(&created, "id");
+
+    let publish_at = OffsetDateTime::now_utc() + time::Duration::hours(1);
+    let (status, scheduled) = response_json(
+        app.clone()
+            .oneshot(api_request(
+                &token,
+                &format!("/api/v1/posts/{post_id}/status"),
+                serde_json::json!({
+                    "status": "published",
+                    "published_at": publish_at,
+                }),
+            ))
+            .await
+            .expect("schedule post"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{scheduled}");
+
+    // Every job the writes enqueued carries the key as its actor.
+    let jobs = state
+        .jobs
+        .list_jobs(&JobQueryFilter::default(), PageRequest::new(50, None))
+        .await
+        .expect("list jobs")
+        .items;
+    assert!(!jobs.is_empty());
+    for job in &jobs {
+        assert_eq!(job.payload["actor"]["name"], label.as_str(), "{job:?}");
+        assert_eq!(job.payload["actor"]["type"], "api_key", "{job:?}");
+    }
+
+    // The publish job runs later, outside the request, as the key.
+    let publish = jobs
+        .iter()
+        .find(|job| job.job_type == JobType::PublishPost)
+        .expect("publish job");
+    let payload: PublishPostJobPayload =
+        serde_json::from_value(publish.payload.clone()).expect("publish payload");
+    process_publish_post_job(payload, Data::new(test_app.job_context()))
+        .await
+        .expect("run publish job");
+
+    let entries = state
+        .audit
+        .list_filtered(
+            PageRequest::new(50, None),
+            &AuditQueryFilter {
+                actor_type: Some(ActorType::ApiKey),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list audit entries")
+        .items;
+    let mut actions: Vec<&str> = entries
+        .iter()
+        .filter(|entry| entry.entity_id.as_deref() == Some(&post_id.to_string()))
+        .map(|entry| entry.action.as_str())
+        .collect();
+    actions.sort_unstable();
+    assert_eq!(actions, ["post.create", "post.status", "post.status"]);
+    for entry in &entries {
+        assert_eq!(entry.actor, label);
+        assert_eq!(entry.actor_key_id, Some(principal.key_id));
+    }
+
+    let published = state.posts.load_post(post_id).await.unwrap().unwrap();
+    assert_eq!(published.status, PostStatus::Published);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn writes_outside_a_request_are_attributed_to_the_system(pool: PgPool) {
+    let (state, _token) = build_state(pool).await;
+
+    state
+        .tags
+        .create_tag(
+            SYSTEM_ACTOR,
+            soffio::application::admin::tags::CreateTagCommand {
+                name: "cron".into(),
+                description: None,
+                pinned: false,
+            },
+        )
+        .await
+        .expect("create tag");
+
+    let entries = state.audit.list_recent(10).await.expect("list audit");
+    let entry = entries
+        .iter()
+        .find(|entry| entry.action == "tag.create")
+        .expect("tag audit entry");
+    assert_eq!(entry.actor, SYSTEM_ACTOR);
+    assert_eq!(entry.actor_type, ActorType::System);
+    assert_eq!(entry.actor_key_id, None);
+
+    let api_entries = state
+        .audit
+        .count_filtered(&AuditQueryFilter {
+            actor_type: Some(ActorType::ApiKey),
+            ..Default::default()
+        })
+        .await
+        .expect("count audit");
+    assert_eq!(api_entries, 0);
+}
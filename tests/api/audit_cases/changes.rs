@@ -0,0 +1,67 @@
+use super::*;
+
+use serde_json::json;
+use soffio::application::admin::posts::{CreatePostCommand, UpdatePostContentCommand};
+use soffio::application::pagination::PageRequest;
+use soffio::application::repos::AuditQueryFilter;
+use soffio::domain::types::PostStatus;
+
+#[sqlx::test(migrations = "./migrations")]
+async fn post_update_records_only_the_changed_fields(pool: PgPool) {
+    let (state, _token) = build_state(pool).await;
+    let post = state
+        .posts
+        .create_post(
+            "tests",
+            CreatePostCommand {
+                slug: None,
+                title: "Before".into(),
+                excerpt: "excerpt".into(),
+                body_markdown: "# body".into(),
+                summary_markdown: None,
+                status: PostStatus::Draft,
+                pinned: false,
+                scheduled_at: None,
+                published_at: None,
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("create post");
+
+    state
+        .posts
+        .update_post(
+            "tests",
+            UpdatePostContentCommand {
+                id: post.id,
+                slug: post.slug.clone(),
+                title: "After".into(),
+                excerpt: post.excerpt.clone(),
+                body_markdown: post.body_markdown.clone(),
+                pinned: post.pinned,
+                summary_markdown: post.summary_markdown.clone(),
+                expected_updated_at: None,
+            },
+        )
+        .await
+        .expect("update post");
+
+    let entries = state
+        .audit
+        .list_filtered(
+            PageRequest::new(10, None),
+            &AuditQueryFilter {
+                action: Some("post.update".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("list audit entries")
+        .items;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        entries[0].changes,
+        Some(json!({ "title": { "old": "Before", "new": "After" } }))
+    );
+}
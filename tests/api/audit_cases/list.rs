@@ -26,9 +26,14 @@ async fn api_can_list_audit_logs(pool: PgPool) {
         Extension(principal.clone()),
         Query(handlers::AuditListQuery {
             actor: None,
+            actor_type: None,
             action: None,
             entity_type: None,
             search: None,
+            field: None,
+            from: None,
+            to: None,
+            request_id: None,
             cursor: None,
             limit: Some(10),
         }),
@@ -36,3 +41,98 @@ async fn api_can_list_audit_logs(pool: PgPool) {
     .await
     .expect("list audit logs via handler");
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_filters_audit_logs_by_date_range(pool: PgPool) {
+    let (state, _token) = build_state(pool.clone()).await;
+    let issued = state
+        .api_keys
+        .issue(IssueApiKeyCommand {
+            name: "audit-range".to_string(),
+            description: None,
+            scopes: vec![ApiScope::AuditRead],
+            expires_in: None,
+            created_by: "tests".to_string(),
+        })
+        .await
+        .unwrap();
+    let principal = state.api_keys.authenticate(&issued.token).await.unwrap();
+
+    for (entity_id, created_at) in [
+        ("before", "2026-10-01T23:59:59.999999Z"),
+        ("first", "2026-10-02T00:00:00Z"),
+        ("last", "2026-10-02T23:59:59.999999Z"),
+        ("after", "2026-10-03T00:00:00Z"),
+    ] {
+        sqlx::query(
+            "INSERT INTO audit_logs (id, actor, action, entity_type, entity_id, created_at) \
+             VALUES ($1, 'tests', 'range.check', 'post', $2, $3::timestamptz)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(entity_id)
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .expect("insert audit log");
+    }
+
+    let list = |from: Option<&str>, to: Option<&str>| {
+        handlers::list_audit_logs(
+            State(state.clone()),
+            Extension(principal.clone()),
+            Query(handlers::AuditListQuery {
+                actor: None,
+                actor_type: None,
+                action: Some("range.check".to_string()),
+                entity_type: None,
+                search: None,
+                field: None,
+                from: from.map(str::to_string),
+                to: to.map(str::to_string),
+                request_id: None,
+                cursor: None,
+                limit: Some(10),
+            }),
+        )
+    };
+    let entity_ids = |body: serde_json::Value| -> Vec<String> {
+        body["items"]
+            .as_array()
+            .expect("items")
+            .iter()
+            .map(|item| string_field(item, "entity_id").to_string())
+            .collect()
+    };
+
+    // `from` is inclusive and `to` exclusive.
+    let (status, body) = response_json(
+        list(Some("2026-10-02T00:00:00Z"), Some("2026-10-03T00:00:00Z"))
+            .await
+            .expect("range"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(entity_ids(body), ["last", "first"]);
+
+    let (_, body) = response_json(
+        list(Some("2026-10-02T00:00:00+00:00"), None)
+            .await
+            .expect("from"),
+    )
+    .await;
+    assert_eq!(entity_ids(body), ["after", "last", "first"]);
+
+    let (_, body) = response_json(
+        list(None, Some("2026-10-02T02:00:00+02:00"))
+            .await
+            .expect("to"),
+    )
+    .await;
+    assert_eq!(entity_ids(body), ["before"]);
+
+    let err = list(Some("yesterday"), None)
+        .await
+        .err()
+        .expect("invalid from");
+    assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,4 @@
+use super::*;
+
+#[path = "comments_cases/moderation.rs"]
+mod moderation;
@@ -0,0 +1,250 @@
+use super::*;
+
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::repos::CommentsRepo;
+use soffio::domain::types::PostStatus;
+
+async fn create_post(state: &ApiState, status: PostStatus) -> Uuid {
+    state
+        .posts
+        .create_post(
+            "test",
+            CreatePostCommand {
+                slug: None,
+                title: format!("comments-{}", Uuid::new_v4()),
+                excerpt: "excerpt".into(),
+                body_markdown: "# body".into(),
+                summary_markdown: None,
+                status,
+                pinned: false,
+                scheduled_at: None,
+                published_at: (status == PostStatus::Published).then(OffsetDateTime::now_utc),
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("create post via service")
+        .id
+}
+
+fn comment_payload(post_id: Uuid, body: &str) -> CommentCreateRequest {
+    CommentCreateRequest {
+        post_id,
+        author: "Reader".into(),
+        body: body.into(),
+        author_ip: None,
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_comment_ingest_moderate_and_publish(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let post_id = create_post(&state, PostStatus::Published).await;
+
+    let (status, created) = response_json(
+        handlers::create_comment(
+            State(state.clone()),
+            Extension(principal.clone()),
+            None,
+            Json(comment_payload(post_id, "Nice write-up")),
+        )
+        .await
+        .expect("ingest comment via handler"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(string_field(&created, "status"), "pending");
+    let comment_id = uuid_field(&created, "id");
+
+    let visible = state
+        .db
+        .list_approved_for_post(post_id)
+        .await
+        .expect("list approved comments");
+    assert!(visible.is_empty(), "pending comments must stay hidden");
+
+    let approved = state
+        .comments
+        .approve("test", comment_id)
+        .await
+        .expect("approve comment");
+    assert_eq!(approved.status, CommentStatus::Approved);
+
+    let visible = state
+        .db
+        .list_approved_for_post(post_id)
+        .await
+        .expect("list approved comments");
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].body, "Nice write-up");
+
+    state
+        .comments
+        .mark_spam("test", comment_id)
+        .await
+        .expect("mark comment as spam");
+
+    let visible = state
+        .db
+        .list_approved_for_post(post_id)
+        .await
+        .expect("list approved comments");
+    assert!(visible.is_empty(), "spam comments must be hidden");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_comment_ingest_rejects_invalid_input(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let post_id = create_post(&state, PostStatus::Published).await;
+    let draft_id = create_post(&state, PostStatus::Draft).await;
+
+    let oversized = "x".repeat(4001);
+    let err = handlers::create_comment(
+        State(state.clone()),
+        Extension(principal.clone()),
+        None,
+        Json(comment_payload(post_id, &oversized)),
+    )
+    .await
+    .expect_err("oversized body rejected");
+    let (status, _) = response_json(err).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let err = handlers::create_comment(
+        State(state.clone()),
+        Extension(principal.clone()),
+        None,
+        Json(comment_payload(draft_id, "Too early")),
+    )
+    .await
+    .expect_err("draft post rejected");
+    let (status, _) = response_json(err).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_comment_ingest_is_rate_limited_per_commenter(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let post_id = create_post(&state, PostStatus::Published).await;
+
+    let state = ApiState {
+        comment_rate_limiter: Arc::new(soffio::infra::http::api::rate_limit::ApiRateLimiter::new(
+            std::time::Duration::from_secs(60),
+            1,
+        )),
+        ..state
+    };
+    // Every request arrives from the commenting service's webhook address.
+    let webhook = || {
+        Some(Extension(soffio::infra::http::ClientIp(
+            std::net::IpAddr::from([203, 0, 113, 7]),
+        )))
+    };
+    let from = |author_ip: [u8; 4], body: &str| CommentCreateRequest {
+        author_ip: Some(std::net::IpAddr::from(author_ip)),
+        ..comment_payload(post_id, body)
+    };
+
+    let first = handlers::create_comment(
+        State(state.clone()),
+        Extension(principal.clone()),
+        webhook(),
+        Json(from([198, 51, 100, 4], "First")),
+    )
+    .await
+    .expect("first comment accepted");
+    assert_eq!(first.status(), StatusCode::CREATED);
+    let (_, created) = response_json(first).await;
+    let stored = state
+        .db
+        .find_comment(uuid_field(&created, "id"))
+        .await
+        .expect("load comment")
+        .expect("comment stored");
+    assert_eq!(stored.source_ip.as_deref(), Some("198.51.100.4"));
+
+    let second = handlers::create_comment(
+        State(state.clone()),
+        Extension(principal.clone()),
+        webhook(),
+        Json(from([198, 51, 100, 4], "Second")),
+    )
+    .await
+    .expect("rate limited response");
+    assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let other_commenter = handlers::create_comment(
+        State(state.clone()),
+        Extension(principal.clone()),
+        webhook(),
+        Json(from([192, 0, 2, 9], "Another reader")),
+    )
+    .await
+    .expect("other commenter accepted");
+    assert_eq!(other_commenter.status(), StatusCode::CREATED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_comment_ingest_limit_ignores_author_names(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let post_id = create_post(&state, PostStatus::Published).await;
+
+    let state = ApiState {
+        comment_rate_limiter: Arc::new(soffio::infra::http::api::rate_limit::ApiRateLimiter::new(
+            std::time::Duration::from_secs(60),
+            1,
+        )),
+        ..state
+    };
+    let renamed = |author: &str| CommentCreateRequest {
+        author: author.into(),
+        ..comment_payload(post_id, "Hello")
+    };
+
+    // Without `author_ip`, the caller address keys the limit.
+    let client = || {
+        Some(Extension(soffio::infra::http::ClientIp(
+            std::net::IpAddr::from([203, 0, 113, 7]),
+        )))
+    };
+    let first = handlers::create_comment(
+        State(state.clone()),
+        Extension(principal.clone()),
+        client(),
+        Json(renamed("Reader")),
+    )
+    .await
+    .expect("first comment accepted");
+    assert_eq!(first.status(), StatusCode::CREATED);
+    let renamed_again = handlers::create_comment(
+        State(state.clone()),
+        Extension(principal.clone()),
+        client(),
+        Json(renamed("Another Name")),
+    )
+    .await
+    .expect("rate limited response");
+    assert_eq!(renamed_again.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // Without any address, the key alone does.
+    let mut statuses = Vec::new();
+    for author in ["Alice", "Bob"] {
+        let response = handlers::create_comment(
+            State(state.clone()),
+            Extension(principal.clone()),
+            None,
+            Json(renamed(author)),
+        )
+        .await
+        .expect("comment response");
+        statuses.push(response.status());
+    }
+    assert_eq!(
+        statuses,
+        [StatusCode::CREATED, StatusCode::TOO_MANY_REQUESTS]
+    );
+}
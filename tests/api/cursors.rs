@@ -0,0 +1,205 @@
+use super::*;
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::api_keys::ApiPrincipal;
+use soffio::application::pagination::{
+    AuditCursor, CursorKeys, JobCursor, NavigationCursor, PageCursor, PostCursor, SnapshotCursor,
+    TagCursor, TrashCursor, UploadCursor,
+};
+use soffio::domain::types::PostStatus;
+use time::Duration;
+
+/// A small xorshift generator, so failures reproduce.
+struct Noise(u64);
+
+impl Noise {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
+
+fn list_app(state: &ApiState) -> Router {
+    Router::new()
+        .route("/api/v1/posts", get(handlers::list_posts))
+        .route("/api/v1/posts/trash", get(handlers::list_trashed_posts))
+        .route("/api/v1/pages", get(handlers::list_pages))
+        .route("/api/v1/pages/trash", get(handlers::list_trashed_pages))
+        .route("/api/v1/tags", get(handlers::list_tags))
+        .route("/api/v1/navigation", get(handlers::list_navigation))
+        .route("/api/v1/uploads", get(handlers::list_uploads))
+        .route("/api/v1/jobs", get(handlers::list_jobs))
+        .route("/api/v1/audit", get(handlers::list_audit_logs))
+        .route("/api/v1/snapshots", get(handlers::list_snapshots))
+        .with_state(state.clone())
+}
+
+/// Every cursor-paginated listing with a well-formed cursor of its own kind.
+fn listings(keys: &CursorKeys) -> Vec<(&'static str, String)> {
+    let when = OffsetDateTime::now_utc();
+    let id = Uuid::new_v4();
+    vec![
+        (
+            "/api/v1/posts",
+            PostCursor::admin(PostStatus::Published, when, id, false, 0).encode(keys),
+        ),
+        (
+            "/api/v1/posts/trash",
+            TrashCursor::new(when, id).encode(keys),
+        ),
+        ("/api/v1/pages", PageCursor::new(when, id).encode(keys)),
+        (
+            "/api/v1/pages/trash",
+            TrashCursor::new(when, id).encode(keys),
+        ),
+        ("/api/v1/tags", TagCursor::new(false, when, id).encode(keys)),
+        (
+            "/api/v1/navigation",
+            NavigationCursor::new(0, when, id).encode(keys),
+        ),
+        ("/api/v1/uploads", UploadCursor::new(when, id).encode(keys)),
+        ("/api/v1/jobs", JobCursor::new(when, "job").encode(keys)),
+        ("/api/v1/audit", AuditCursor::new(when, id).encode(keys)),
+        (
+            "/api/v1/snapshots",
+            SnapshotCursor::new(when, id).encode(keys),
+        ),
+    ]
+}
+
+async fn get_json(
+    app: &Router,
+    principal: &ApiPrincipal,
+    uri: &str,
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    request.extensions_mut().insert(principal.clone());
+    let response = app.clone().oneshot(request).await.expect("send request");
+    response_json(response).await
+}
+
+async fn assert_invalid_cursor(app: &Router, principal: &ApiPrincipal, path: &str, cursor: &str) {
+    let (status, body) = get_json(app, principal, &format!("{path}?cursor={cursor}")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{path} accepted {cursor}");
+    assert_eq!(body["error"]["code"], "invalid_cursor", "{path}: {body}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn list_endpoints_reject_random_and_bit_flipped_cursors(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = list_app(&state);
+    let mut noise = Noise(0x9e37_79b9_7f4a_7c15);
+
+    for (path, valid) in listings(state.db.cursor_keys()) {
+        let (status, body) = get_json(&app, &principal, &format!("{path}?cursor={valid}")).await;
+        assert_eq!(status, StatusCode::OK, "{path}: {body}");
+
+        let bytes = URL_SAFE_NO_PAD.decode(&valid).expect("base64 cursor");
+        for index in 0..bytes.len() {
+            let mut flipped = bytes.clone();
+            flipped[index] ^= 1 << (index % 8);
+            assert_invalid_cursor(&app, &principal, path, &URL_SAFE_NO_PAD.encode(flipped)).await;
+        }
+
+        for _ in 0..32 {
+            let len = (noise.next() % 96) as usize + 1;
+            let random = URL_SAFE_NO_PAD.encode(noise.bytes(len));
+            assert_invalid_cursor(&app, &principal, path, &random).await;
+        }
+        for garbage in ["not-a-cursor", "~", "%00", "eyJ9"] {
+            assert_invalid_cursor(&app, &principal, path, garbage).await;
+        }
+    }
+
+    // A well-formed cursor is only accepted by its own listing.
+    let keys = state.db.cursor_keys();
+    let tag_cursor = TagCursor::new(false, OffsetDateTime::now_utc(), Uuid::new_v4()).encode(keys);
+    assert_invalid_cursor(&app, &principal, "/api/v1/posts", &tag_cursor).await;
+    let upload_cursor = UploadCursor::new(OffsetDateTime::now_utc(), Uuid::new_v4()).encode(keys);
+    assert_invalid_cursor(&app, &principal, "/api/v1/snapshots", &upload_cursor).await;
+
+    // And only by the site whose keys signed it.
+    for (path, foreign) in listings(&CursorKeys::new(b"another-node".to_vec())) {
+        assert_invalid_cursor(&app, &principal, path, &foreign).await;
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn cursors_to_removed_rows_continue_from_the_next_row(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = list_app(&state);
+
+    let now = OffsetDateTime::now_utc();
+    let mut ids = Vec::new();
+    for (index, slug) in ["newest", "middle", "oldest"].into_iter().enumerate() {
+        let post = state
+            .posts
+            .create_post(
+                "test",
+                CreatePostCommand {
+                    slug: Some(slug.into()),
+                    title: slug.into(),
+                    excerpt: "excerpt".into(),
+                    body_markdown: "# body".into(),
+                    summary_markdown: None,
+                    status: PostStatus::Published,
+                    pinned: false,
+                    scheduled_at: None,
+                    published_at: Some(now - Duration::hours(index as i64)),
+                    archived_at: None,
+                },
+            )
+            .await
+            .expect("create post");
+        ids.push(post.id);
+    }
+
+    let (status, first) = get_json(&app, &principal, "/api/v1/posts?limit=1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["items"][0]["slug"], "newest");
+    let cursor = first["next_cursor"]
+        .as_str()
+        .expect("next cursor")
+        .to_string();
+
+    // The row the cursor was taken from, and the one after it, are gone.
+    for (id, slug) in [(ids[0], "newest"), (ids[1], "middle")] {
+        state
+            .posts
+            .delete_post("test", id, slug)
+            .await
+            .expect("trash post");
+        state
+            .posts
+            .purge_post("test", id)
+            .await
+            .expect("purge post");
+    }
+
+    let (status, next) = get_json(
+        &app,
+        &principal,
+        &format!("/api/v1/posts?limit=1&cursor={cursor}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{next}");
+    let slugs: Vec<&str> = next["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .map(|item| string_field(item, "slug"))
+        .collect();
+    assert_eq!(slugs, ["oldest"]);
+}
@@ -0,0 +1,235 @@
+use super::*;
+
+use soffio::application::pagination::PageRequest;
+
+fn create_post_request(token: &str, key: &str, title: &str) -> Request<Body> {
+    let payload = serde_json::json!({
+        "title": title,
+        "excerpt": "excerpt",
+        "body_markdown": "# body",
+        "status": "draft",
+        "pinned": false,
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/api/v1/posts")
+        .header("authorization", format!("Bearer {token}"))
+        .header("content-type", "application/json")
+        .header("idempotency-key", key)
+        .body(Body::from(payload.to_string()))
+        .expect("build request")
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn replayed_create_returns_the_original_post(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let (state, token) = (test_app.api.clone(), test_app.token().to_string());
+    let app = test_app.router();
+
+    let first = app
+        .clone()
+        .oneshot(create_post_request(&token, "retry-1", "once"))
+        .await
+        .expect("send first request");
+    let first_headers = first.headers().clone();
+    let (status, first) = response_json(first).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let location = format!("/api/v1/posts/{}", uuid_field(&first, "id"));
+    assert_eq!(
+        first_headers
+            .get("location")
+            .and_then(|value| value.to_str().ok()),
+        Some(location.as_str())
+    );
+    assert!(first_headers.contains_key("etag"));
+
+    let replay = app
+        .oneshot(create_post_request(&token, "retry-1", "once"))
+        .await
+        .expect("send retry");
+    assert_eq!(
+        replay
+            .headers()
+            .get("idempotent-replayed")
+            .and_then(|value| value.to_str().ok()),
+        Some("true")
+    );
+    for header in ["location", "etag", "content-type"] {
+        assert_eq!(
+            replay.headers().get(header),
+            first_headers.get(header),
+            "{header} must be replayed"
+        );
+    }
+    let (status, second) = response_json(replay).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(uuid_field(&second, "id"), uuid_field(&first, "id"));
+
+    let total = state
+        .posts
+        .list(None, &Default::default(), PageRequest::new(50, None))
+        .await
+        .expect("list posts")
+        .items
+        .iter()
+        .filter(|post| post.title == "once")
+        .count();
+    assert_eq!(total, 1, "the retry must not create a second post");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn reusing_a_key_for_a_different_payload_conflicts(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let token = test_app.token().to_string();
+    let app = test_app.router();
+
+    let first = app
+        .clone()
+        .oneshot(create_post_request(&token, "retry-2", "original"))
+        .await
+        .expect("send first request");
+    assert_eq!(first.status(), StatusCode::CREATED);
+
+    let (status, body) = response_json(
+        app.clone()
+            .oneshot(create_post_request(&token, "retry-2", "changed"))
+            .await
+            .expect("send conflicting request"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "idempotency_conflict");
+
+    let fresh = app
+        .oneshot(create_post_request(&token, "retry-3", "changed"))
+        .await
+        .expect("send request with a new key");
+    assert_eq!(fresh.status(), StatusCode::CREATED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn an_abandoned_claim_is_taken_over_after_its_lease(pool: PgPool) {
+    use std::time::Duration;
+
+    use soffio::application::repos::{IdempotencyClaim, IdempotencyRepo, StoredResponse};
+
+    let app = TestApp::new(pool).await;
+    let api_key_id = app.principal().key_id;
+    let ttl = Duration::from_secs(60);
+    let lease = Duration::from_secs(60);
+
+    let IdempotencyClaim::Claimed { token: abandoned } = app
+        .api
+        .db
+        .claim_idempotency_key(api_key_id, "lease-1", "hash", ttl, lease)
+        .await
+        .expect("claim key")
+    else {
+        panic!("a fresh key must be claimed");
+    };
+
+    let busy = app
+        .api
+        .db
+        .claim_idempotency_key(api_key_id, "lease-1", "hash", ttl, lease)
+        .await
+        .expect("claim held key");
+    assert!(
+        matches!(busy, IdempotencyClaim::Existing { response: None, .. }),
+        "a claim within its lease is still in progress"
+    );
+
+    let IdempotencyClaim::Claimed { token: retry } = app
+        .api
+        .db
+        .claim_idempotency_key(api_key_id, "lease-1", "hash", ttl, Duration::ZERO)
+        .await
+        .expect("take over key")
+    else {
+        panic!("a claim past its lease must be taken over");
+    };
+    assert_ne!(retry, abandoned);
+
+    let stored = |status| StoredResponse {
+        status,
+        content_type: None,
+        location: None,
+        etag: None,
+        body: Vec::new(),
+    };
+    app.api
+        .db
+        .complete_idempotency_key(api_key_id, "lease-1", abandoned, &stored(500))
+        .await
+        .expect("complete with stale token");
+    app.api
+        .db
+        .complete_idempotency_key(api_key_id, "lease-1", retry, &stored(201))
+        .await
+        .expect("complete with current token");
+
+    let replay = app
+        .api
+        .db
+        .claim_idempotency_key(api_key_id, "lease-1", "hash", ttl, lease)
+        .await
+        .expect("read completed key");
+    assert_eq!(
+        replay,
+        IdempotencyClaim::Existing {
+            request_hash: "hash".to_string(),
+            response: Some(stored(201)),
+        }
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn a_rate_limited_request_is_run_again_on_retry(pool: PgPool) {
+    use std::time::Duration;
+
+    let test_app = TestApp::new(pool).await;
+    let token = test_app.token().to_string();
+    let post = FixturePost::published().create(&test_app).await;
+    let window = Duration::from_millis(300);
+    test_app.api.comment_rate_limiter.reconfigure(window, 1);
+    let app = test_app.router();
+
+    let comment = |key: &str, body: &str| {
+        let payload = serde_json::json!({
+            "post_id": post.id,
+            "author": "Reader",
+            "body": body,
+        });
+        Request::builder()
+            .method("POST")
+            .uri("/api/v1/comments")
+            .header("authorization", format!("Bearer {token}"))
+            .header("content-type", "application/json")
+            .header("idempotency-key", key)
+            .body(Body::from(payload.to_string()))
+            .expect("build request")
+    };
+
+    let first = app
+        .clone()
+        .oneshot(comment("comment-1", "First"))
+        .await
+        .expect("send first comment");
+    assert_eq!(first.status(), StatusCode::CREATED);
+
+    let limited = app
+        .clone()
+        .oneshot(comment("comment-2", "Second"))
+        .await
+        .expect("send rate limited comment");
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    tokio::time::sleep(window + Duration::from_millis(100)).await;
+
+    let retry = app
+        .oneshot(comment("comment-2", "Second"))
+        .await
+        .expect("retry comment");
+    assert!(retry.headers().get("idempotent-replayed").is_none());
+    assert_eq!(retry.status(), StatusCode::CREATED);
+}
@@ -2,3 +2,6 @@ use super::*;
 
 #[path = "jobs_cases/list.rs"]
 mod list;
+
+#[path = "jobs_cases/get.rs"]
+mod get;
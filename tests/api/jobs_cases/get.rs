@@ -0,0 +1,86 @@
+use super::*;
+
+use soffio::application::pagination::PageRequest;
+use soffio::application::repos::JobQueryFilter;
+use soffio::application::request_id;
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_job_detail_links_the_post_and_its_request_audit(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+
+    let payload = PostCreateRequest {
+        title: "Traced".into(),
+        slug: None,
+        excerpt: "excerpt".into(),
+        body_markdown: "# body".into(),
+        summary_markdown: None,
+        status: soffio::domain::types::PostStatus::Draft,
+        pinned: false,
+        scheduled_at: None,
+        published_at: None,
+        archived_at: None,
+    };
+    let (status, created) = response_json(
+        request_id::scope(
+            "req-job-detail".to_string(),
+            handlers::create_post(
+                State(state.clone()),
+                Extension(principal.clone()),
+                StrictJson(payload),
+            ),
+        )
+        .await
+        .expect("create post"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let slug = string_field(&created, "slug").to_string();
+
+    let jobs = state
+        .jobs
+        .list_jobs(&JobQueryFilter::default(), PageRequest::new(50, None))
+        .await
+        .expect("list jobs");
+    let job = jobs
+        .items
+        .iter()
+        .find(|job| job.job_type == soffio::domain::types::JobType::RenderPost)
+        .expect("creating a post enqueues a render job");
+
+    let (status, body) = response_json(
+        handlers::get_job(
+            State(state.clone()),
+            Extension(principal.clone()),
+            Path(job.id.clone()),
+        )
+        .await
+        .expect("get job via handler"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_eq!(body["id"], job.id.as_str());
+    assert_eq!(body["entity"]["kind"], "post");
+    assert_eq!(body["entity"]["slug"], slug.as_str());
+    assert_eq!(body["request_id"], "req-job-detail");
+    assert!(body["timeline"]["queued_at"].is_string());
+    let actions: Vec<&str> = body["related_audit"]
+        .as_array()
+        .expect("related audit entries")
+        .iter()
+        .filter_map(|entry| entry["action"].as_str())
+        .collect();
+    assert!(actions.contains(&"post.create"), "got {actions:?}");
+
+    let missing = handlers::get_job(
+        State(state),
+        Extension(principal),
+        Path("01NOSUCHJOB".to_string()),
+    )
+    .await
+    .err()
+    .expect("unknown job is an error")
+    .into_response();
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+}
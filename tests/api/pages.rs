@@ -14,3 +14,15 @@ mod update_status;
 
 #[path = "pages_cases/partial.rs"]
 mod partial;
+
+#[path = "pages_cases/publish_job.rs"]
+mod publish_job;
+
+#[path = "pages_cases/trash.rs"]
+mod trash;
+
+#[path = "pages_cases/templates.rs"]
+mod templates;
+
+#[path = "pages_cases/visibility.rs"]
+mod visibility;
@@ -4,8 +4,8 @@ use super::*;
 
 #[sqlx::test(migrations = "./migrations")]
 async fn api_can_create_and_list_pages(pool: PgPool) {
-    let (state, token) = build_state(pool).await;
-    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
 
     let page_payload = PageCreateRequest {
         slug: None,
@@ -15,13 +15,14 @@ async fn api_can_create_and_list_pages(pool: PgPool) {
         scheduled_at: None,
         published_at: None,
         archived_at: None,
+        template: None,
     };
 
     let (status, created_page) = response_json(
         handlers::create_page(
-            State(state.clone()),
+            app.state(),
             Extension(principal.clone()),
-            Json(page_payload),
+            StrictJson(page_payload),
         )
         .await
         .expect("create page via handler"),
@@ -34,7 +35,7 @@ async fn api_can_create_and_list_pages(pool: PgPool) {
 
     let (status, found_by_id) = response_json(
         handlers::get_page_by_id(
-            State(state.clone()),
+            app.state(),
             Extension(principal.clone()),
             Path(created_page_id.parse().unwrap()),
         )
@@ -47,7 +48,7 @@ async fn api_can_create_and_list_pages(pool: PgPool) {
 
     let (status, found_by_slug) = response_json(
         handlers::get_page(
-            State(state.clone()),
+            app.state(),
             Extension(principal.clone()),
             Path(created_page_slug.clone()),
         )
@@ -62,7 +63,7 @@ async fn api_can_create_and_list_pages(pool: PgPool) {
     );
 
     let _list = handlers::list_pages(
-        State(state.clone()),
+        app.state(),
         Extension(principal.clone()),
         Query(handlers::PageListQuery {
             status: None,
@@ -2,14 +2,14 @@ use super::*;
 
 #[sqlx::test(migrations = "./migrations")]
 async fn api_page_create_honors_slug_field(pool: PgPool) {
-    let (state, token) = build_state(pool).await;
-    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
 
     let (status, custom_page) = response_json(
         handlers::create_page(
-            State(state.clone()),
+            app.state(),
             Extension(principal.clone()),
-            Json(PageCreateRequest {
+            StrictJson(PageCreateRequest {
                 slug: Some("custom-page-slug".into()),
                 title: "ignored-title-for-slug".into(),
                 body_markdown: "# Page content".into(),
@@ -17,6 +17,7 @@ async fn api_page_create_honors_slug_field(pool: PgPool) {
                 scheduled_at: None,
                 published_at: None,
                 archived_at: None,
+                template: None,
             }),
         )
         .await
@@ -28,9 +29,9 @@ async fn api_page_create_honors_slug_field(pool: PgPool) {
 
     let (status, auto_page) = response_json(
         handlers::create_page(
-            State(state.clone()),
+            app.state(),
             Extension(principal),
-            Json(PageCreateRequest {
+            StrictJson(PageCreateRequest {
                 slug: None,
                 title: "Auto Slug Page".into(),
                 body_markdown: "# Page content".into(),
@@ -38,6 +39,7 @@ async fn api_page_create_honors_slug_field(pool: PgPool) {
                 scheduled_at: None,
                 published_at: None,
                 archived_at: None,
+                template: None,
             }),
         )
         .await
@@ -47,3 +49,61 @@ async fn api_page_create_honors_slug_field(pool: PgPool) {
     assert_eq!(status, StatusCode::CREATED);
     assert_eq!(string_field(&auto_page, "slug"), "auto-slug-page");
 }
+
+fn page_request(slug: Option<&str>, title: &str) -> PageCreateRequest {
+    PageCreateRequest {
+        slug: slug.map(str::to_string),
+        title: title.into(),
+        body_markdown: "# Page content".into(),
+        status: soffio::domain::types::PageStatus::Draft,
+        scheduled_at: None,
+        published_at: None,
+        archived_at: None,
+        template: None,
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_page_create_rejects_reserved_slugs(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    for slug in soffio::domain::routes::reserved_page_slugs() {
+        let rejected = handlers::create_page(
+            app.state(),
+            Extension(principal.clone()),
+            StrictJson(page_request(Some(slug), "Reserved")),
+        )
+        .await
+        .err()
+        .unwrap_or_else(|| panic!("`{slug}` should be rejected"))
+        .into_response();
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST, "{slug}");
+    }
+
+    let (status, near_miss) = response_json(
+        handlers::create_page(
+            app.state(),
+            Extension(principal.clone()),
+            StrictJson(page_request(Some("posts-archive"), "Archive")),
+        )
+        .await
+        .expect("near miss is allowed"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(string_field(&near_miss, "slug"), "posts-archive");
+
+    let (status, derived) = response_json(
+        handlers::create_page(
+            app.state(),
+            Extension(principal),
+            StrictJson(page_request(None, "Posts")),
+        )
+        .await
+        .expect("derived slug steps past the reserved word"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(string_field(&derived, "slug"), "posts-2");
+}
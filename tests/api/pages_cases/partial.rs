@@ -2,30 +2,20 @@ use super::*;
 
 #[sqlx::test(migrations = "./migrations")]
 async fn api_can_partial_update_page(pool: PgPool) {
-    let (state, token) = build_state(pool).await;
-    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
 
-    let page = state
-        .pages
-        .create_page(
-            "test",
-            soffio::application::admin::pages::CreatePageCommand {
-                slug: None,
-                title: "page".into(),
-                body_markdown: "hello".into(),
-                status: soffio::domain::types::PageStatus::Draft,
-                scheduled_at: None,
-                published_at: None,
-                archived_at: None,
-            },
-        )
-        .await
-        .expect("create page");
+    let page = FixturePage::draft()
+        .title("page")
+        .body("hello")
+        .create(&app)
+        .await;
 
     handlers::update_page_title(
-        State(state.clone()),
+        app.state(),
         Extension(principal.clone()),
         axum::extract::Path(page.id),
+        if_match("*"),
         Json(PageTitleRequest {
             title: "new page".into(),
         }),
@@ -33,13 +23,14 @@ async fn api_can_partial_update_page(pool: PgPool) {
     .await
     .expect("update page title");
 
-    let mut latest = state.pages.find_by_id(page.id).await.unwrap().unwrap();
+    let mut latest = app.api.pages.find_by_id(page.id).await.unwrap().unwrap();
     assert_eq!(latest.title, "new page");
 
     handlers::update_page_body(
-        State(state.clone()),
+        app.state(),
         Extension(principal),
         axum::extract::Path(page.id),
+        if_match("*"),
         Json(PageBodyRequest {
             body_markdown: "updated body".into(),
         }),
@@ -47,6 +38,6 @@ async fn api_can_partial_update_page(pool: PgPool) {
     .await
     .expect("update page body");
 
-    latest = state.pages.find_by_id(page.id).await.unwrap().unwrap();
+    latest = app.api.pages.find_by_id(page.id).await.unwrap().unwrap();
     assert_eq!(latest.body_markdown, "updated body");
 }
@@ -0,0 +1,54 @@
+use super::*;
+
+use soffio::application::actor::SYSTEM_ACTOR;
+use soffio::application::repos::PagesWriteRepo;
+use soffio::domain::types::PageStatus;
+
+#[sqlx::test(migrations = "./migrations")]
+async fn publish_page_job_runs_once_per_payload(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let page = FixturePage::draft()
+        .title("publish-twice")
+        .create(&app)
+        .await;
+    let scheduled = PagesWriteRepo::schedule_page_publication(
+        app.api.db.as_ref(),
+        page.id,
+        OffsetDateTime::now_utc() - time::Duration::minutes(1),
+    )
+    .await
+    .expect("schedule page");
+    let publish_at = scheduled.scheduled_at;
+
+    let first = app
+        .api
+        .pages
+        .publish_scheduled_by_slug(SYSTEM_ACTOR, &page.slug, publish_at)
+        .await
+        .expect("first run")
+        .expect("first run publishes");
+    assert_eq!(first.status, PageStatus::Published);
+    assert_eq!(first.published_at, publish_at);
+
+    let second = app
+        .api
+        .pages
+        .publish_scheduled_by_slug(SYSTEM_ACTOR, &page.slug, publish_at)
+        .await
+        .expect("second run");
+    assert!(second.is_none(), "retry should be a no-op");
+
+    let audits = app
+        .api
+        .audit
+        .list_recent(100)
+        .await
+        .expect("list audit")
+        .iter()
+        .filter(|entry| {
+            entry.action == "page.status"
+                && entry.entity_id.as_deref() == Some(&page.id.to_string())
+        })
+        .count();
+    assert_eq!(audits, 1);
+}
@@ -0,0 +1,173 @@
+use super::*;
+
+use soffio::application::api_keys::ApiPrincipal;
+use soffio::domain::types::PageStatus;
+
+async fn create_published_page(
+    app: &TestApp,
+    principal: &ApiPrincipal,
+    slug: &str,
+    template: Option<&str>,
+) -> axum::response::Response {
+    let result = handlers::create_page(
+        app.state(),
+        Extension(principal.clone()),
+        StrictJson(PageCreateRequest {
+            slug: Some(slug.into()),
+            title: format!("{slug} page"),
+            body_markdown: "# Heading\n\nBody text.".into(),
+            status: PageStatus::Published,
+            scheduled_at: None,
+            published_at: None,
+            archived_at: None,
+            template: template.map(str::to_string),
+        }),
+    )
+    .await;
+    match result {
+        Ok(response) => response.into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+async fn get_html(app: &Router, uri: &str) -> String {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    String::from_utf8(body.to_vec()).expect("utf-8 body")
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn each_page_template_renders_its_own_layout(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    for (slug, template) in [
+        ("plain", None),
+        ("wide", Some("wide")),
+        ("landing", Some("landing")),
+    ] {
+        let (status, page) =
+            response_json(create_published_page(&app, &principal, slug, template).await).await;
+        assert_eq!(status, StatusCode::CREATED, "{page}");
+        assert_eq!(page["template"], template.unwrap_or("default"));
+    }
+
+    let site = app.router();
+
+    let plain = get_html(&site, "/plain").await;
+    assert!(plain.contains(r#"<layout-frame data-layout="split""#));
+    assert!(plain.contains("Body text."));
+
+    let wide = get_html(&site, "/wide").await;
+    assert!(wide.contains(r#"<layout-frame data-layout="wide""#));
+    assert!(!wide.contains(r#"data-layout="split""#));
+    assert!(wide.contains("Body text."));
+
+    let landing = get_html(&site, "/landing").await;
+    assert!(landing.contains(r#"<main data-role="content" data-layout="landing">"#));
+    assert!(!landing.contains("<layout-frame"));
+    assert!(landing.contains("Body text."));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn unknown_page_template_is_rejected_on_save(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let (status, body) =
+        response_json(create_published_page(&app, &principal, "about", Some("sidebar")).await)
+            .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert_eq!(body["error"]["hint"], "template");
+
+    let (status, page) =
+        response_json(create_published_page(&app, &principal, "about", Some("wide")).await).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let id = uuid_field(&page, "id");
+
+    let update = |template: Option<&str>| PageUpdateRequest {
+        slug: "about".into(),
+        title: "About".into(),
+        body_markdown: "# About".into(),
+        template: template.map(str::to_string),
+    };
+    let response = handlers::update_page(
+        app.state(),
+        Extension(principal.clone()),
+        Path(id),
+        if_match("*"),
+        StrictJson(update(Some("sidebar"))),
+    )
+    .await
+    .err()
+    .expect("unknown template is rejected")
+    .into_response();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let (status, updated) = response_json(
+        handlers::update_page(
+            app.state(),
+            Extension(principal.clone()),
+            Path(id),
+            if_match("*"),
+            StrictJson(update(None)),
+        )
+        .await
+        .expect("update without a template")
+        .into_response(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{updated}");
+    assert_eq!(updated["template"], "wide");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn unregistered_stored_template_renders_with_the_default(pool: PgPool) {
+    let app = TestApp::new(pool.clone()).await;
+    let page = FixturePage::published()
+        .slug("legacy")
+        .body("# Heading\n\nBody text.")
+        .create(&app)
+        .await;
+    sqlx::query("UPDATE pages SET template = 'retired' WHERE id = $1")
+        .bind(page.id)
+        .execute(&pool)
+        .await
+        .expect("store an unregistered template");
+
+    let html = get_html(&app.router(), "/legacy").await;
+    assert!(html.contains(r#"<layout-frame data-layout="split""#));
+    assert!(html.contains("Body text."));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn duplicated_pages_keep_the_template_as_a_draft(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let page = FixturePage::published()
+        .slug("pricing")
+        .title("pricing page")
+        .template("landing")
+        .create(&app)
+        .await;
+    let (status, copy) = response_json(
+        handlers::duplicate_page(app.state(), Extension(principal), Path(page.id))
+            .await
+            .expect("duplicate page"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{copy}");
+    assert_eq!(string_field(&copy, "slug"), "pricing-copy");
+    assert_eq!(string_field(&copy, "title"), "pricing page (Copy)");
+    assert_eq!(string_field(&copy, "status"), "draft");
+    assert_eq!(copy["template"], "landing");
+    assert_eq!(copy["body_markdown"], page.body_markdown);
+}
@@ -0,0 +1,116 @@
+use super::*;
+
+use soffio::application::api_keys::ApiPrincipal;
+
+async fn create_page(app: &TestApp, principal: &ApiPrincipal, slug: &str) -> Uuid {
+    let (status, created) = response_json(
+        handlers::create_page(
+            app.state(),
+            Extension(principal.clone()),
+            StrictJson(PageCreateRequest {
+                slug: Some(slug.into()),
+                title: "Trash page".into(),
+                body_markdown: "# Page content".into(),
+                status: soffio::domain::types::PageStatus::Draft,
+                scheduled_at: None,
+                published_at: None,
+                archived_at: None,
+                template: None,
+            }),
+        )
+        .await
+        .expect("create page via handler"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    uuid_field(&created, "id")
+}
+
+async fn trashed_ids(app: &TestApp, principal: &ApiPrincipal) -> Vec<Uuid> {
+    let (status, trash) = response_json(
+        handlers::list_trashed_pages(
+            app.state(),
+            Extension(principal.clone()),
+            Query(handlers::TrashListQuery {
+                cursor: None,
+                limit: None,
+            }),
+        )
+        .await
+        .expect("list trash"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    trash["items"]
+        .as_array()
+        .expect("items array")
+        .iter()
+        .map(|item| uuid_field(item, "id"))
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_page_delete_moves_to_trash_and_restores(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+    let id = create_page(&app, &principal, "about").await;
+
+    handlers::delete_page(app.state(), Extension(principal.clone()), Path(id))
+        .await
+        .expect("delete page");
+
+    let hidden = handlers::get_page(
+        app.state(),
+        Extension(principal.clone()),
+        Path("about".to_string()),
+    )
+    .await
+    .err()
+    .expect("trashed page is hidden")
+    .into_response();
+    assert_eq!(hidden.status(), StatusCode::NOT_FOUND);
+    assert_eq!(trashed_ids(&app, &principal).await, vec![id]);
+
+    let (status, restored) = response_json(
+        handlers::restore_page(app.state(), Extension(principal.clone()), Path(id))
+            .await
+            .expect("restore page"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(string_field(&restored, "slug"), "about");
+    assert!(trashed_ids(&app, &principal).await.is_empty());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_page_restore_conflicts_when_slug_was_reused(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+    let original = create_page(&app, &principal, "contact").await;
+
+    handlers::delete_page(app.state(), Extension(principal.clone()), Path(original))
+        .await
+        .expect("delete page");
+    let replacement = create_page(&app, &principal, "contact").await;
+
+    let conflict =
+        handlers::restore_page(app.state(), Extension(principal.clone()), Path(original))
+            .await
+            .err()
+            .expect("restore conflicts with the new page")
+            .into_response();
+    assert_eq!(conflict.status(), StatusCode::CONFLICT);
+
+    let (_, current) = response_json(
+        handlers::get_page(
+            app.state(),
+            Extension(principal.clone()),
+            Path("contact".to_string()),
+        )
+        .await
+        .expect("replacement keeps the slug"),
+    )
+    .await;
+    assert_eq!(uuid_field(&current, "id"), replacement);
+    assert_eq!(trashed_ids(&app, &principal).await, vec![original]);
+}
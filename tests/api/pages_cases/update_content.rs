@@ -1,41 +1,79 @@
 use super::*;
 
+use soffio::infra::http::api::etag::etag_for;
+
 #[sqlx::test(migrations = "./migrations")]
 async fn api_can_update_page_content(pool: PgPool) {
-    let (state, token) = build_state(pool).await;
-    let principal = state.api_keys.authenticate(&token).await.unwrap();
-
-    // Create a page via service to get the ID
-    let page = state
-        .pages
-        .create_page(
-            "test",
-            soffio::application::admin::pages::CreatePageCommand {
-                slug: None,
-                title: "original-page".into(),
-                body_markdown: "# original".into(),
-                status: soffio::domain::types::PageStatus::Draft,
-                scheduled_at: None,
-                published_at: None,
-                archived_at: None,
-            },
-        )
-        .await
-        .expect("create page via service");
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let page = FixturePage::draft()
+        .title("original-page")
+        .body("# original")
+        .create(&app)
+        .await;
 
     // Update the page via handler
     let update_payload = PageUpdateRequest {
         slug: page.slug.clone(),
         title: "updated-page".into(),
         body_markdown: "# updated".into(),
+        template: None,
     };
 
     let _updated = handlers::update_page(
-        State(state.clone()),
+        app.state(),
         Extension(principal.clone()),
         axum::extract::Path(page.id),
-        Json(update_payload),
+        if_match("*"),
+        StrictJson(update_payload),
     )
     .await
     .expect("update page via handler");
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_page_update_rejects_stale_if_match(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let page = FixturePage::draft()
+        .title("versioned-page")
+        .body("# v1")
+        .create(&app)
+        .await;
+    let current = etag_for(page.updated_at);
+
+    let payload = |body: &str| PageUpdateRequest {
+        slug: page.slug.clone(),
+        title: "versioned-page".into(),
+        body_markdown: body.into(),
+        template: None,
+    };
+
+    let response = handlers::update_page(
+        app.state(),
+        Extension(principal.clone()),
+        axum::extract::Path(page.id),
+        if_match(&current),
+        StrictJson(payload("# v2")),
+    )
+    .await
+    .expect("conditional update")
+    .into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key(axum::http::header::ETAG));
+
+    let stale = handlers::update_page(
+        app.state(),
+        Extension(principal.clone()),
+        axum::extract::Path(page.id),
+        if_match(&current),
+        StrictJson(payload("# v3")),
+    )
+    .await
+    .err()
+    .expect("stale update rejected")
+    .into_response();
+    assert_eq!(stale.status(), StatusCode::PRECONDITION_FAILED);
+}
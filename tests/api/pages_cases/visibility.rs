@@ -0,0 +1,148 @@
+use super::*;
+
+use soffio::application::actor::SYSTEM_ACTOR;
+use soffio::application::admin::navigation::CreateNavigationItemCommand;
+use soffio::application::admin::pages::{AdminPageService, UpdatePageStatusCommand};
+use soffio::application::jobs::PingSitemapJobPayload;
+use soffio::application::pagination::PageRequest;
+use soffio::application::repos::{JobQueryFilter, PagesWriteRepo};
+use soffio::domain::routes::BasePath;
+use soffio::domain::types::{JobType, NavigationDestinationType, PageStatus};
+
+#[sqlx::test(migrations = "./migrations")]
+async fn unpublishing_a_linked_page_records_the_hidden_navigation(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let page = FixturePage::published().slug("about").create(&app).await;
+    app.api
+        .navigation
+        .create_item(
+            "test",
+            CreateNavigationItemCommand {
+                label: "About".into(),
+                destination_type: NavigationDestinationType::Internal,
+                destination_page_id: Some(page.id),
+                destination_url: None,
+                sort_order: 0,
+                visible: true,
+                open_in_new_tab: false,
+            },
+        )
+        .await
+        .expect("create navigation item");
+
+    let draft = app
+        .api
+        .pages
+        .update_status(
+            "test",
+            UpdatePageStatusCommand {
+                id: page.id,
+                status: PageStatus::Draft,
+                scheduled_at: None,
+                published_at: None,
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("unpublish page");
+    assert_eq!(draft.status, PageStatus::Draft);
+
+    let links = app
+        .api
+        .pages
+        .navigation_links(page.id)
+        .await
+        .expect("navigation links");
+    let labels: Vec<&str> = links.iter().map(|item| item.label.as_str()).collect();
+    assert_eq!(labels, ["About"]);
+
+    let hidden: Vec<_> = app
+        .api
+        .audit
+        .list_recent(100)
+        .await
+        .expect("list audit")
+        .into_iter()
+        .filter(|entry| {
+            entry.action == "page.navigation_hidden"
+                && entry.entity_id.as_deref() == Some(&page.id.to_string())
+        })
+        .collect();
+    assert_eq!(hidden.len(), 1);
+    let payload = hidden[0].payload_text.as_deref().expect("audit payload");
+    assert!(payload.contains("About"), "{payload}");
+}
+
+/// The page service with sitemap pings configured against a public site URL.
+async fn pinging_pages(app: &TestApp) -> AdminPageService {
+    sqlx::query("UPDATE site_settings SET public_site_url = 'https://example.com/'")
+        .execute(app.api.db.pool())
+        .await
+        .expect("set public site url");
+    (*app.api.pages).clone().with_sitemap_ping(
+        vec!["https://search.test/ping".into()],
+        BasePath::parse("/blog").expect("base path"),
+    )
+}
+
+async fn sitemap_pings(app: &TestApp) -> Vec<PingSitemapJobPayload> {
+    app.api
+        .jobs
+        .list_jobs(&JobQueryFilter::default(), PageRequest::new(100, None))
+        .await
+        .expect("list jobs")
+        .items
+        .into_iter()
+        .filter(|job| job.job_type == JobType::PingSitemap)
+        .map(|job| serde_json::from_value(job.payload).expect("ping payload"))
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn publishing_a_page_enqueues_a_sitemap_ping(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let pages = pinging_pages(&app).await;
+
+    let page = FixturePage::draft().slug("pinged").create(&app).await;
+    let scheduled = PagesWriteRepo::schedule_page_publication(
+        app.api.db.as_ref(),
+        page.id,
+        OffsetDateTime::now_utc() - time::Duration::minutes(1),
+    )
+    .await
+    .expect("schedule page");
+    pages
+        .publish_scheduled_by_slug(SYSTEM_ACTOR, &page.slug, scheduled.scheduled_at)
+        .await
+        .expect("publish page")
+        .expect("page published");
+
+    let pings = sitemap_pings(&app).await;
+    assert_eq!(pings.len(), 1);
+    assert_eq!(pings[0].slug, "pinged");
+    assert_eq!(pings[0].sitemap_url, "https://example.com/blog/sitemap.xml");
+    assert_eq!(pings[0].ping_urls, ["https://search.test/ping"]);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn trashing_a_published_page_enqueues_a_sitemap_ping(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let pages = pinging_pages(&app).await;
+
+    let draft = FixturePage::draft().slug("draft").create(&app).await;
+    pages
+        .delete_page("test", draft.id, &draft.slug)
+        .await
+        .expect("trash draft page");
+    assert!(sitemap_pings(&app).await.is_empty());
+
+    let page = FixturePage::published().slug("gone").create(&app).await;
+    pages
+        .delete_page("test", page.id, &page.slug)
+        .await
+        .expect("trash published page");
+
+    let pings = sitemap_pings(&app).await;
+    assert_eq!(pings.len(), 1);
+    assert_eq!(pings[0].slug, "gone");
+}
@@ -11,3 +11,36 @@ mod update_status;
 
 #[path = "posts_cases/partial.rs"]
 mod partial;
+
+#[path = "posts_cases/excerpt.rs"]
+mod excerpt;
+
+#[path = "posts_cases/publish_job.rs"]
+mod publish_job;
+
+#[path = "posts_cases/trash.rs"]
+mod trash;
+
+#[path = "posts_cases/render_job.rs"]
+mod render_job;
+
+#[path = "posts_cases/translations.rs"]
+mod translations;
+
+#[path = "posts_cases/slugs.rs"]
+mod slugs;
+
+#[path = "posts_cases/structured_data.rs"]
+mod structured_data;
+
+#[path = "posts_cases/duplicate.rs"]
+mod duplicate;
+
+#[path = "posts_cases/pin_order.rs"]
+mod pin_order;
+
+#[path = "posts_cases/archive.rs"]
+mod archive;
+
+#[path = "posts_cases/months.rs"]
+mod months;
@@ -0,0 +1,110 @@
+use super::*;
+
+use time::macros::datetime;
+
+async fn seed_posts(app: &TestApp) {
+    for (slug, published_at) in [
+        ("march-early", datetime!(2024-03-02 09:00 UTC)),
+        ("march-late", datetime!(2024-03-28 18:30 UTC)),
+        ("january", datetime!(2024-01-15 12:00 UTC)),
+        ("november", datetime!(2023-11-20 08:00 UTC)),
+    ] {
+        FixturePost::published()
+            .slug(slug)
+            .published_at(published_at)
+            .create(app)
+            .await;
+    }
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, String) {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    (
+        status,
+        String::from_utf8(body.to_vec()).expect("utf-8 body"),
+    )
+}
+
+fn lists(html: &str, slug: &str) -> bool {
+    html.contains(&format!(r#"data-entry="{slug}""#))
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn month_and_year_archives_list_their_posts(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    seed_posts(&app).await;
+    let site = app.router();
+
+    let (status, html) = get(&site, "/archive/2024/03").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains(r#"data-role="archive-title">March 2024</h1>"#));
+    assert!(lists(&html, "march-early") && lists(&html, "march-late"));
+    assert!(!lists(&html, "january") && !lists(&html, "november"));
+    // The nearest earlier month with posts, and nothing later.
+    assert!(html.contains(r#"rel="prev" href="/archive/2024/01">← January 2024</a>"#));
+    assert!(!html.contains(r#"data-role="archive-next""#));
+
+    let (status, html) = get(&site, "/archive/2024").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains(r#"data-role="archive-title">2024</h1>"#));
+    for slug in ["march-early", "march-late", "january"] {
+        assert!(lists(&html, slug), "{slug}");
+    }
+    assert!(!lists(&html, "november"));
+    assert!(html.contains(r#"rel="prev" href="/archive/2023">← 2023</a>"#));
+
+    let (status, html) = get(&site, "/archive/2023").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(lists(&html, "november"));
+    assert!(html.contains(r#"rel="next" href="/archive/2024">2024 →</a>"#));
+    assert!(!html.contains(r#"data-role="archive-previous""#));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn empty_archive_periods_render_an_empty_state(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    seed_posts(&app).await;
+    let site = app.router();
+
+    let (status, html) = get(&site, "/archive/2024/02").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains("No posts from February 2024"));
+    assert!(!html.contains(r#"data-entry=""#));
+    assert!(html.contains(r#"rel="prev" href="/archive/2024/01">← January 2024</a>"#));
+    assert!(html.contains(r#"rel="next" href="/archive/2024/03">March 2024 →</a>"#));
+
+    let (status, html) = get(&site, "/archive/1999").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains("No posts from 1999"));
+    assert!(html.contains(r#"rel="next" href="/archive/2023">2023 →</a>"#));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn malformed_archive_periods_are_not_found(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    seed_posts(&app).await;
+    let site = app.router();
+
+    for uri in [
+        "/archive/24",
+        "/archive/02024",
+        "/archive/0000",
+        "/archive/year",
+        "/archive/2024/3",
+        "/archive/2024/00",
+        "/archive/2024/13",
+        "/archive/2024/march",
+        "/archive/%2B2024",
+    ] {
+        let (status, _) = get(&site, uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{uri}");
+    }
+}
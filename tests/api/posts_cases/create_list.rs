@@ -4,10 +4,11 @@ use super::*;
 
 #[sqlx::test(migrations = "./migrations")]
 async fn api_can_create_and_list_posts(pool: PgPool) {
-    let (state, token) = build_state(pool).await;
-    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
     let post_payload = PostCreateRequest {
         title: "handler-post".into(),
+        slug: None,
         excerpt: "excerpt".into(),
         body_markdown: "# body".into(),
         summary_markdown: None,
@@ -20,9 +21,9 @@ async fn api_can_create_and_list_posts(pool: PgPool) {
 
     let (status, created_post) = response_json(
         handlers::create_post(
-            State(state.clone()),
+            app.state(),
             Extension(principal.clone()),
-            Json(post_payload),
+            StrictJson(post_payload),
         )
         .await
         .expect("create post via handler"),
@@ -35,7 +36,7 @@ async fn api_can_create_and_list_posts(pool: PgPool) {
 
     let (status, found_by_id) = response_json(
         handlers::get_post_by_id(
-            State(state.clone()),
+            app.state(),
             Extension(principal.clone()),
             Path(created_post_id.parse().unwrap()),
         )
@@ -48,7 +49,7 @@ async fn api_can_create_and_list_posts(pool: PgPool) {
 
     let (status, found_by_slug) = response_json(
         handlers::get_post(
-            State(state.clone()),
+            app.state(),
             Extension(principal.clone()),
             Path(created_post_slug.clone()),
         )
@@ -63,7 +64,7 @@ async fn api_can_create_and_list_posts(pool: PgPool) {
     );
 
     let _list = handlers::list_posts(
-        State(state.clone()),
+        app.state(),
         Extension(principal.clone()),
         Query(handlers::PostListQuery {
             status: None,
@@ -0,0 +1,79 @@
+use super::*;
+
+use soffio::application::repos::TagsRepo;
+use soffio::domain::types::PostStatus;
+
+async fn tag_slugs(app: &TestApp, post_id: Uuid) -> Vec<String> {
+    let mut slugs: Vec<String> = app
+        .api
+        .db
+        .list_for_post(post_id)
+        .await
+        .expect("list tags")
+        .into_iter()
+        .map(|tag| tag.slug)
+        .collect();
+    slugs.sort();
+    slugs
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn duplicates_are_drafts_with_copy_slugs_and_the_same_tags(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let source = FixturePost::published()
+        .slug("launch-notes")
+        .title("Launch notes")
+        .excerpt("Handwritten excerpt")
+        .body("## What shipped\n\nEverything.")
+        .summary("- shipped")
+        .pinned()
+        .with_tags(["release", "rust"])
+        .create(&app)
+        .await;
+
+    let (status, body) = response_json(
+        handlers::duplicate_post(app.state(), Extension(principal.clone()), Path(source.id))
+            .await
+            .expect("duplicate via api"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(string_field(&body, "slug"), "launch-notes-copy");
+    assert_eq!(string_field(&body, "title"), "Launch notes (Copy)");
+    assert_eq!(string_field(&body, "status"), "draft");
+    assert_eq!(body["pinned"], false);
+    assert!(body["published_at"].is_null());
+    let copy_id = uuid_field(&body, "id");
+    assert_ne!(copy_id, source.id);
+
+    let copy = app
+        .api
+        .posts
+        .load_post(copy_id)
+        .await
+        .expect("load copy")
+        .expect("copy exists");
+    assert_eq!(copy.body_markdown, source.body_markdown);
+    assert_eq!(copy.excerpt, "Handwritten excerpt");
+    assert_eq!(copy.summary_markdown.as_deref(), Some("- shipped"));
+    assert_eq!(tag_slugs(&app, copy.id).await, ["release", "rust"]);
+
+    let second = app
+        .api
+        .posts
+        .duplicate_post("test", source.id)
+        .await
+        .expect("duplicate again");
+    assert_eq!(second.slug, "launch-notes-copy-2");
+    assert_eq!(second.status, PostStatus::Draft);
+    assert_eq!(tag_slugs(&app, second.id).await, ["release", "rust"]);
+
+    let missing = handlers::duplicate_post(app.state(), Extension(principal), Path(Uuid::new_v4()))
+        .await
+        .err()
+        .expect("unknown post")
+        .into_response();
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+}
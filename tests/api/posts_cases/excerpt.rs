@@ -0,0 +1,82 @@
+use super::*;
+
+#[sqlx::test(migrations = "./migrations")]
+async fn blank_excerpt_is_derived_from_body(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let long_body = format!("# Heading\n\n{}", "word ".repeat(100));
+    let post = FixturePost::draft()
+        .excerpt("  ")
+        .body(&long_body)
+        .create(&app)
+        .await;
+
+    assert!(post.excerpt_generated);
+    assert!(post.excerpt.starts_with("Heading word word"));
+    assert!(post.excerpt.ends_with('…'));
+    assert!(post.excerpt.chars().count() <= 201);
+
+    // A body edit keeps the derived excerpt in sync.
+    handlers::update_post_body(
+        app.state(),
+        Extension(principal),
+        Path(post.id),
+        Query(handlers::PostStatusQuery { wait_render: false }),
+        if_match("*"),
+        Json(PostBodyRequest {
+            body_markdown: "Short *new* body.".into(),
+        }),
+    )
+    .await
+    .expect("update body");
+    let latest = app.api.posts.load_post(post.id).await.unwrap().unwrap();
+    assert!(latest.excerpt_generated);
+    assert_eq!(latest.excerpt, "Short new body.");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn explicit_excerpt_is_kept_and_overrides_derivation(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let post = FixturePost::draft()
+        .excerpt("Hand written")
+        .body("Body text.")
+        .create(&app)
+        .await;
+    assert!(!post.excerpt_generated);
+    assert_eq!(post.excerpt, "Hand written");
+
+    handlers::update_post_body(
+        app.state(),
+        Extension(principal.clone()),
+        Path(post.id),
+        Query(handlers::PostStatusQuery { wait_render: false }),
+        if_match("*"),
+        Json(PostBodyRequest {
+            body_markdown: "Completely different body.".into(),
+        }),
+    )
+    .await
+    .expect("update body");
+    let latest = app.api.posts.load_post(post.id).await.unwrap().unwrap();
+    assert!(!latest.excerpt_generated);
+    assert_eq!(latest.excerpt, "Hand written");
+
+    // Clearing the excerpt switches back to a derived one.
+    handlers::update_post_excerpt(
+        app.state(),
+        Extension(principal),
+        Path(post.id),
+        if_match("*"),
+        Json(PostExcerptRequest {
+            excerpt: String::new(),
+        }),
+    )
+    .await
+    .expect("clear excerpt");
+    let latest = app.api.posts.load_post(post.id).await.unwrap().unwrap();
+    assert!(latest.excerpt_generated);
+    assert_eq!(latest.excerpt, "Completely different body.");
+}
@@ -0,0 +1,108 @@
+use super::*;
+
+use time::macros::datetime;
+
+async fn create_post(app: &TestApp, slug: &str, published_at: Option<OffsetDateTime>) {
+    let post = match published_at {
+        Some(published_at) => FixturePost::published().published_at(published_at),
+        None => FixturePost::draft(),
+    };
+    post.slug(slug).create(app).await;
+}
+
+async fn patch_settings(app: &TestApp, value: serde_json::Value) {
+    app.api
+        .settings
+        .patch(
+            "test",
+            serde_json::from_value(value).expect("settings patch"),
+        )
+        .await
+        .expect("patch settings");
+}
+
+async fn months(app: &TestApp) -> (serde_json::Value, Vec<(String, u64)>) {
+    let principal = app.principal_with(&[ApiScope::PostRead]).await;
+    let (status, body) = response_json(
+        handlers::post_month_aggregations(app.state(), Extension(principal))
+            .await
+            .expect("month aggregations via handler"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let buckets = body["months"]
+        .as_array()
+        .expect("months array")
+        .iter()
+        .map(|month| {
+            (
+                string_field(month, "month").to_string(),
+                month["count"].as_u64().expect("count"),
+            )
+        })
+        .collect();
+    (body, buckets)
+}
+
+fn buckets(expected: &[(&str, u64)]) -> Vec<(String, u64)> {
+    expected
+        .iter()
+        .map(|(month, count)| (month.to_string(), *count))
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn month_aggregations_bucket_published_posts_in_the_site_timezone(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    create_post(&app, "january", Some(datetime!(2024-01-10 12:00 UTC))).await;
+    create_post(&app, "mid-march", Some(datetime!(2024-03-15 12:00 UTC))).await;
+    create_post(&app, "late-march", Some(datetime!(2024-03-31 20:00 UTC))).await;
+    create_post(&app, "early-may", Some(datetime!(2024-05-01 02:00 UTC))).await;
+    create_post(&app, "draft", None).await;
+
+    patch_settings(&app, serde_json::json!({ "timezone": "UTC" })).await;
+    let (body, counts) = months(&app).await;
+    assert_eq!(body["timezone"], "UTC");
+    assert_eq!(
+        counts,
+        buckets(&[("2024-05", 1), ("2024-03", 2), ("2024-01", 1)])
+    );
+
+    // 20:00 UTC on 31 March is already April in Tokyo.
+    patch_settings(&app, serde_json::json!({ "timezone": "Asia/Tokyo" })).await;
+    let (_, counts) = months(&app).await;
+    assert_eq!(
+        counts,
+        buckets(&[
+            ("2024-05", 1),
+            ("2024-04", 1),
+            ("2024-03", 1),
+            ("2024-01", 1)
+        ])
+    );
+
+    // 02:00 UTC on 1 May is still April in New York.
+    patch_settings(&app, serde_json::json!({ "timezone": "America/New_York" })).await;
+    let (_, counts) = months(&app).await;
+    assert_eq!(
+        counts,
+        buckets(&[("2024-04", 1), ("2024-03", 2), ("2024-01", 1)])
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn month_aggregations_keep_the_most_recent_months_within_the_limit(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    create_post(&app, "january", Some(datetime!(2024-01-10 12:00 UTC))).await;
+    create_post(&app, "february", Some(datetime!(2024-02-10 12:00 UTC))).await;
+    create_post(&app, "march", Some(datetime!(2024-03-10 12:00 UTC))).await;
+
+    patch_settings(
+        &app,
+        serde_json::json!({ "timezone": "UTC", "month_filter_limit": 2 }),
+    )
+    .await;
+    let (body, counts) = months(&app).await;
+    assert_eq!(body["month_filter_limit"], 2);
+    assert_eq!(counts, buckets(&[("2024-03", 1), ("2024-02", 1)]));
+}
@@ -1,89 +1,149 @@
 use super::*;
 
+use soffio::infra::http::api::etag::etag_for;
+
 #[sqlx::test(migrations = "./migrations")]
 async fn api_can_partial_update_post(pool: PgPool) {
-    let (state, token) = build_state(pool).await;
-    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
 
-    let post = state
-        .posts
-        .create_post(
-            "test",
-            soffio::application::admin::posts::CreatePostCommand {
-                title: "partial".into(),
-                excerpt: "orig".into(),
-                body_markdown: "# body".into(),
-                summary_markdown: Some("sum".into()),
-                status: soffio::domain::types::PostStatus::Draft,
-                pinned: false,
-                scheduled_at: None,
-                published_at: None,
-                archived_at: None,
-            },
-        )
-        .await
-        .expect("create post via service");
+    let post = FixturePost::draft()
+        .title("partial")
+        .excerpt("orig")
+        .summary("sum")
+        .create(&app)
+        .await;
 
     handlers::update_post_pin(
-        State(state.clone()),
+        app.state(),
         Extension(principal.clone()),
         axum::extract::Path(post.id),
-        Json(PostPinRequest { pinned: true }),
+        Json(PostPinRequest {
+            pinned: true,
+            pin_order: None,
+        }),
     )
     .await
     .expect("pin post");
-    let mut latest = state.posts.load_post(post.id).await.unwrap().unwrap();
+    let mut latest = app.api.posts.load_post(post.id).await.unwrap().unwrap();
     assert!(latest.pinned);
 
     handlers::update_post_title(
-        State(state.clone()),
+        app.state(),
         Extension(principal.clone()),
         axum::extract::Path(post.id),
+        if_match("*"),
         Json(PostTitleRequest {
             title: "new title".into(),
         }),
     )
     .await
     .expect("update title");
-    latest = state.posts.load_post(post.id).await.unwrap().unwrap();
+    latest = app.api.posts.load_post(post.id).await.unwrap().unwrap();
     assert_eq!(latest.title, "new title");
 
     handlers::update_post_excerpt(
-        State(state.clone()),
+        app.state(),
         Extension(principal.clone()),
         axum::extract::Path(post.id),
+        if_match("*"),
         Json(PostExcerptRequest {
             excerpt: "new excerpt".into(),
         }),
     )
     .await
     .expect("update excerpt");
-    latest = state.posts.load_post(post.id).await.unwrap().unwrap();
+    latest = app.api.posts.load_post(post.id).await.unwrap().unwrap();
     assert_eq!(latest.excerpt, "new excerpt");
 
-    handlers::update_post_body(
-        State(state.clone()),
-        Extension(principal.clone()),
-        axum::extract::Path(post.id),
-        Json(PostBodyRequest {
-            body_markdown: "## changed".into(),
-        }),
+    let (_, updated) = response_json(
+        handlers::update_post_body(
+            app.state(),
+            Extension(principal.clone()),
+            axum::extract::Path(post.id),
+            Query(handlers::PostStatusQuery { wait_render: true }),
+            if_match("*"),
+            Json(PostBodyRequest {
+                body_markdown: "## changed".into(),
+            }),
+        )
+        .await
+        .expect("update body"),
     )
-    .await
-    .expect("update body");
-    latest = state.posts.load_post(post.id).await.unwrap().unwrap();
+    .await;
+    assert_eq!(updated["render_progress"]["state"], "rendered", "{updated}");
+    latest = app.api.posts.load_post(post.id).await.unwrap().unwrap();
     assert_eq!(latest.body_markdown, "## changed");
 
     handlers::update_post_summary(
-        State(state.clone()),
+        app.state(),
         Extension(principal),
         axum::extract::Path(post.id),
+        if_match("*"),
         Json(PostSummaryRequest {
             summary_markdown: Some("updated summary".into()),
         }),
     )
     .await
     .expect("update summary");
-    latest = state.posts.load_post(post.id).await.unwrap().unwrap();
+    latest = app.api.posts.load_post(post.id).await.unwrap().unwrap();
     assert_eq!(latest.summary_markdown.as_deref(), Some("updated summary"));
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_partial_post_updates_honour_if_match(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let post = FixturePost::draft().title("partial").create(&app).await;
+    let current = etag_for(post.updated_at);
+
+    let title = |title: &str| {
+        Json(PostTitleRequest {
+            title: title.into(),
+        })
+    };
+
+    let response = handlers::update_post_title(
+        app.state(),
+        Extension(principal.clone()),
+        axum::extract::Path(post.id),
+        if_match(&current),
+        title("first"),
+    )
+    .await
+    .expect("conditional title update")
+    .into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key(axum::http::header::ETAG));
+
+    // The tag read before the first update is now stale.
+    let stale = handlers::update_post_title(
+        app.state(),
+        Extension(principal.clone()),
+        axum::extract::Path(post.id),
+        if_match(&current),
+        title("second"),
+    )
+    .await
+    .err()
+    .expect("stale title update rejected")
+    .into_response();
+    assert_eq!(stale.status(), StatusCode::PRECONDITION_FAILED);
+
+    let missing = handlers::update_post_title(
+        app.state(),
+        Extension(principal),
+        axum::extract::Path(post.id),
+        axum::http::HeaderMap::new(),
+        title("third"),
+    )
+    .await
+    .err()
+    .expect("missing If-Match rejected")
+    .into_response();
+    assert_eq!(missing.status(), StatusCode::PRECONDITION_REQUIRED);
+
+    let latest = app.api.posts.load_post(post.id).await.unwrap().unwrap();
+    assert_eq!(latest.title, "first");
+}
@@ -0,0 +1,135 @@
+use super::*;
+
+use soffio::application::api_keys::ApiPrincipal;
+use time::Duration;
+
+async fn create_post(app: &TestApp, slug: &str, published_at: OffsetDateTime) -> Uuid {
+    FixturePost::published()
+        .slug(slug)
+        .published_at(published_at)
+        .create(app)
+        .await
+        .id
+}
+
+async fn pin(
+    app: &TestApp,
+    principal: &ApiPrincipal,
+    id: Uuid,
+    pinned: bool,
+    pin_order: Option<i32>,
+) -> Result<serde_json::Value, StatusCode> {
+    match handlers::update_post_pin(
+        app.state(),
+        Extension(principal.clone()),
+        Path(id),
+        Json(PostPinRequest { pinned, pin_order }),
+    )
+    .await
+    {
+        Ok(response) => Ok(response_json(response).await.1),
+        Err(err) => Err(err.into_response().status()),
+    }
+}
+
+/// Page through the post listing two at a time and collect the slugs.
+async fn listed_slugs(app: &TestApp, principal: &ApiPrincipal) -> Vec<String> {
+    let mut slugs = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (status, page) = response_json(
+            handlers::list_posts(
+                app.state(),
+                Extension(principal.clone()),
+                Query(handlers::PostListQuery {
+                    status: None,
+                    search: None,
+                    tag: None,
+                    month: None,
+                    cursor: cursor.take(),
+                    limit: Some(2),
+                }),
+            )
+            .await
+            .expect("list posts via handler"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        slugs.extend(
+            page["items"]
+                .as_array()
+                .expect("items")
+                .iter()
+                .map(|item| string_field(item, "slug").to_string()),
+        );
+        match page["next_cursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => return slugs,
+        }
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn pinned_posts_follow_their_pin_order(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let now = OffsetDateTime::now_utc();
+    let newest = create_post(&app, "newest", now).await;
+    let middle = create_post(&app, "middle", now - Duration::days(1)).await;
+    let oldest = create_post(&app, "oldest", now - Duration::days(2)).await;
+    create_post(&app, "recent", now - Duration::hours(1)).await;
+    create_post(&app, "older", now - Duration::days(3)).await;
+
+    let pinned = pin(&app, &principal, newest, true, Some(2))
+        .await
+        .expect("pin newest");
+    assert_eq!(pinned["pin_order"], 2);
+    pin(&app, &principal, oldest, true, Some(0))
+        .await
+        .expect("pin oldest");
+    pin(&app, &principal, middle, true, Some(1))
+        .await
+        .expect("pin middle");
+
+    assert_eq!(
+        listed_slugs(&app, &principal).await,
+        ["oldest", "middle", "newest", "recent", "older"]
+    );
+
+    // Re-pinning without an order keeps the position; unpinning resets it.
+    let repinned = pin(&app, &principal, newest, true, None)
+        .await
+        .expect("re-pin newest");
+    assert_eq!(repinned["pin_order"], 2);
+    let unpinned = pin(&app, &principal, oldest, false, None)
+        .await
+        .expect("unpin oldest");
+    assert_eq!(unpinned["pin_order"], 0);
+
+    assert_eq!(
+        listed_slugs(&app, &principal).await,
+        ["middle", "newest", "recent", "oldest", "older"]
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn pin_order_rejects_negative_and_unpinned_orders(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let post = create_post(&app, "post", OffsetDateTime::now_utc()).await;
+
+    assert_eq!(
+        pin(&app, &principal, post, true, Some(-1)).await.err(),
+        Some(StatusCode::BAD_REQUEST)
+    );
+    assert_eq!(
+        pin(&app, &principal, post, false, Some(1)).await.err(),
+        Some(StatusCode::BAD_REQUEST)
+    );
+    let latest = app.api.posts.load_post(post).await.unwrap().unwrap();
+    assert!(!latest.pinned);
+    assert_eq!(latest.pin_order, 0);
+}
@@ -0,0 +1,133 @@
+use super::*;
+
+use soffio::application::actor::SYSTEM_ACTOR;
+use soffio::application::repos::{JobQueryFilter, PostsWriteRepo};
+use soffio::domain::types::PostStatus;
+
+async fn scheduled_post(app: &TestApp, title: &str) -> (String, Uuid, OffsetDateTime) {
+    let post = FixturePost::draft().title(title).create(app).await;
+    let scheduled = PostsWriteRepo::schedule_post_publication(
+        app.api.db.as_ref(),
+        post.id,
+        OffsetDateTime::now_utc() - time::Duration::minutes(1),
+    )
+    .await
+    .expect("schedule post");
+    let publish_at = scheduled.scheduled_at.expect("scheduled_at set");
+    (post.slug, post.id, publish_at)
+}
+
+async fn status_audits(app: &TestApp, id: Uuid) -> usize {
+    app.api
+        .audit
+        .list_recent(100)
+        .await
+        .expect("list audit")
+        .iter()
+        .filter(|entry| {
+            entry.action == "post.status" && entry.entity_id.as_deref() == Some(&id.to_string())
+        })
+        .count()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn publish_post_job_runs_once_per_payload(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let (slug, id, publish_at) = scheduled_post(&app, "publish-twice").await;
+    let jobs_before = app
+        .api
+        .jobs
+        .type_counts(&JobQueryFilter::default())
+        .await
+        .expect("count jobs");
+
+    let first = app
+        .api
+        .posts
+        .publish_scheduled_by_slug(SYSTEM_ACTOR, &slug, Some(publish_at))
+        .await
+        .expect("first run")
+        .expect("first run publishes");
+    assert_eq!(first.status, PostStatus::Published);
+    assert_eq!(first.published_at, Some(publish_at));
+
+    let second = app
+        .api
+        .posts
+        .publish_scheduled_by_slug(SYSTEM_ACTOR, &slug, Some(publish_at))
+        .await
+        .expect("second run");
+    assert!(second.is_none(), "retry should be a no-op");
+
+    let stored = app
+        .api
+        .posts
+        .load_post(id)
+        .await
+        .expect("load post")
+        .expect("post exists");
+    assert_eq!(stored.updated_at, first.updated_at);
+    assert_eq!(status_audits(&app, id).await, 1);
+
+    let jobs_after = app
+        .api
+        .jobs
+        .type_counts(&JobQueryFilter::default())
+        .await
+        .expect("count jobs");
+    assert_eq!(jobs_after.render_post, jobs_before.render_post);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn concurrent_publish_post_jobs_have_one_winner(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let (slug, id, publish_at) = scheduled_post(&app, "publish-race").await;
+
+    let (left, right) = tokio::join!(
+        app.api
+            .posts
+            .publish_scheduled_by_slug(SYSTEM_ACTOR, &slug, Some(publish_at)),
+        app.api
+            .posts
+            .publish_scheduled_by_slug(SYSTEM_ACTOR, &slug, Some(publish_at)),
+    );
+    let winners = [left.expect("left run"), right.expect("right run")]
+        .into_iter()
+        .flatten()
+        .count();
+
+    assert_eq!(winners, 1);
+    assert_eq!(status_audits(&app, id).await, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn publish_post_job_skips_a_superseded_schedule(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let (slug, id, stale_publish_at) = scheduled_post(&app, "rescheduled").await;
+    let rescheduled = PostsWriteRepo::schedule_post_publication(
+        app.api.db.as_ref(),
+        id,
+        OffsetDateTime::now_utc() + time::Duration::days(1),
+    )
+    .await
+    .expect("reschedule post");
+
+    let outcome = app
+        .api
+        .posts
+        .publish_scheduled_by_slug(SYSTEM_ACTOR, &slug, Some(stale_publish_at))
+        .await
+        .expect("stale run");
+    assert!(outcome.is_none());
+
+    let stored = app
+        .api
+        .posts
+        .load_post(id)
+        .await
+        .expect("load post")
+        .expect("post exists");
+    assert_eq!(stored.status, PostStatus::Draft);
+    assert_eq!(stored.scheduled_at, rescheduled.scheduled_at);
+    assert_eq!(status_audits(&app, id).await, 0);
+}
@@ -0,0 +1,366 @@
+use super::*;
+
+use apalis::prelude::Data;
+use soffio::application::jobs::JobWorkerContext;
+use soffio::application::render::{ContentWarning, RenderPostJobPayload, process_render_post_job};
+
+const V1: &str = "## Notes\n\nfirst draft";
+const V2: &str = "## Notes\n\nsecond draft";
+
+/// Create a post, then edit its body to `V2` the way a second save would,
+/// returning the payloads the two saves enqueue.
+async fn post_edited_twice(
+    app: &TestApp,
+    title: &str,
+) -> (Uuid, RenderPostJobPayload, RenderPostJobPayload) {
+    let post = FixturePost::draft().title(title).body(V1).create(app).await;
+    sqlx::query("UPDATE posts SET body_markdown = $2 WHERE id = $1")
+        .bind(post.id)
+        .bind(V2)
+        .execute(app.api.db.pool())
+        .await
+        .expect("store second draft");
+
+    let v1 = RenderPostJobPayload::new(post.slug.clone(), V1.into(), None);
+    let v2 = RenderPostJobPayload::new(post.slug, V2.into(), None);
+    (post.id, v1, v2)
+}
+
+async fn rendered_body(app: &TestApp, id: Uuid) -> String {
+    app.api
+        .posts
+        .load_sections(id)
+        .await
+        .expect("load sections")
+        .into_iter()
+        .map(|section| section.body_html)
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn latest_render_wins_when_the_older_one_finishes_last(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let ctx = app.job_context();
+    let (id, v1, v2) = post_edited_twice(&app, "older-last").await;
+
+    process_render_post_job(v2, Data::new(ctx.clone()))
+        .await
+        .expect("render v2");
+    process_render_post_job(v1, Data::new(ctx))
+        .await
+        .expect("stale v1 render completes as a no-op");
+
+    let body = rendered_body(&app, id).await;
+    assert!(body.contains("second draft"), "{body}");
+    assert!(!body.contains("first draft"), "{body}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn latest_render_wins_when_the_older_one_finishes_first(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let ctx = app.job_context();
+    let (id, v1, v2) = post_edited_twice(&app, "older-first").await;
+
+    process_render_post_job(v1, Data::new(ctx.clone()))
+        .await
+        .expect("stale v1 render completes as a no-op");
+    process_render_post_job(v2, Data::new(ctx))
+        .await
+        .expect("render v2");
+
+    let body = rendered_body(&app, id).await;
+    assert!(body.contains("second draft"), "{body}");
+    assert!(!body.contains("first draft"), "{body}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn concurrent_and_duplicate_renders_settle_on_the_latest(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let ctx = app.job_context();
+    let (id, v1, v2) = post_edited_twice(&app, "concurrent").await;
+
+    let (first, second, duplicate) = tokio::join!(
+        process_render_post_job(v1, Data::new(ctx.clone())),
+        process_render_post_job(v2.clone(), Data::new(ctx.clone())),
+        process_render_post_job(v2, Data::new(ctx)),
+    );
+    first.expect("render v1");
+    second.expect("render v2");
+    duplicate.expect("duplicate v2 reports the shared outcome");
+
+    let body = rendered_body(&app, id).await;
+    assert!(body.contains("second draft"), "{body}");
+    assert!(!body.contains("first draft"), "{body}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn render_leaves_the_post_version_alone(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let post = FixturePost::draft()
+        .title("versioned")
+        .body(V1)
+        .create(&app)
+        .await;
+
+    process_render_post_job(
+        RenderPostJobPayload::new(post.slug, V1.into(), None),
+        Data::new(app.job_context()),
+    )
+    .await
+    .expect("render");
+
+    let stored = app
+        .api
+        .posts
+        .load_post(post.id)
+        .await
+        .expect("load post")
+        .expect("post exists");
+    assert!(!rendered_body(&app, post.id).await.is_empty());
+    assert_eq!(stored.updated_at, post.updated_at);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn rerender_replaces_a_stale_artifact(pool: PgPool) {
+    use soffio::application::repos::JobsRepo;
+
+    let app = TestApp::new(pool).await;
+    let ctx = app.job_context();
+    let (id, _, v2) = post_edited_twice(&app, "rerender").await;
+    process_render_post_job(v2, Data::new(ctx.clone()))
+        .await
+        .expect("initial render");
+    sqlx::query("UPDATE post_sections SET body_html = '<p>stale</p>' WHERE post_id = $1")
+        .bind(id)
+        .execute(app.api.db.pool())
+        .await
+        .expect("simulate a stale artifact");
+
+    let post = app
+        .api
+        .posts
+        .load_post(id)
+        .await
+        .expect("load post")
+        .expect("post exists");
+    let job_id = app
+        .api
+        .posts
+        .rerender_post("test", &post)
+        .await
+        .expect("queue re-render");
+
+    let job = app
+        .api
+        .db
+        .find_job(&job_id)
+        .await
+        .expect("find job")
+        .expect("job queued");
+    let payload: RenderPostJobPayload =
+        serde_json::from_value(job.payload).expect("render payload");
+    assert_eq!(payload.slug, post.slug);
+    process_render_post_job(payload, Data::new(ctx))
+        .await
+        .expect("re-render");
+
+    let body = rendered_body(&app, id).await;
+    assert!(body.contains("second draft"), "{body}");
+    assert!(!body.contains("stale"), "{body}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn render_failure_is_reported_until_a_render_succeeds(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+    let ctx = app.job_context();
+    let (id, _v1, v2) = post_edited_twice(&app, "render-failure").await;
+
+    app.api
+        .db
+        .record_post_render_failure(&v2.slug, "markdown parsing failed: boom")
+        .await
+        .expect("record failure");
+
+    let (status, failed) = response_json(
+        handlers::get_post_by_id(app.state(), Extension(principal.clone()), Path(id))
+            .await
+            .expect("get failed post"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(string_field(&failed, "render_status"), "failed");
+    assert_eq!(
+        string_field(&failed, "render_error"),
+        "markdown parsing failed: boom"
+    );
+    assert!(!failed["render_failed_at"].is_null(), "{failed}");
+
+    process_render_post_job(v2, Data::new(ctx))
+        .await
+        .expect("render succeeds");
+
+    let (_, recovered) = response_json(
+        handlers::get_post_by_id(app.state(), Extension(principal), Path(id))
+            .await
+            .expect("get recovered post"),
+    )
+    .await;
+    assert_eq!(string_field(&recovered, "render_status"), "ok");
+    assert!(recovered["render_error"].is_null(), "{recovered}");
+    assert!(recovered["render_failed_at"].is_null(), "{recovered}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn render_records_warnings_for_broken_internal_links(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let ctx = app.job_context();
+    let body = "## Links\n\nSee [myself](/posts/render-warnings) and [gone](/posts/missing-post).";
+    let post = FixturePost::draft()
+        .slug("render-warnings")
+        .title("Render warnings")
+        .body(body)
+        .create(&app)
+        .await;
+
+    process_render_post_job(
+        RenderPostJobPayload::new(post.slug.clone(), body.into(), None),
+        Data::new(ctx),
+    )
+    .await
+    .expect("render succeeds");
+
+    let warnings = app
+        .api
+        .db
+        .load_post_render_warnings(post.id)
+        .await
+        .expect("load warnings");
+    assert_eq!(
+        warnings,
+        vec![ContentWarning::BrokenLink {
+            href: "/posts/missing-post".into()
+        }]
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn corrupt_render_warnings_are_reported(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let post = FixturePost::draft().create(&app).await;
+    sqlx::query("UPDATE posts SET render_warnings = '{\"kind\": 1}' WHERE id = $1")
+        .bind(post.id)
+        .execute(app.api.db.pool())
+        .await
+        .expect("corrupt warnings");
+
+    let err = app
+        .api
+        .db
+        .load_post_render_warnings(post.id)
+        .await
+        .expect_err("corrupt warnings rejected");
+    assert!(
+        matches!(err, soffio::application::repos::RepoError::Integrity { .. }),
+        "{err:?}"
+    );
+}
+
+const OUTLINE: &str = "## Setup\n\nInstall it.\n\n### Linux\n\nUse apt.\n\n## Usage\n\nRun it.\n";
+
+/// Render `OUTLINE` in full, store `edited` as the post's body and return the
+/// section ids by anchor before the edit is rendered.
+async fn rendered_outline(
+    app: &TestApp,
+    ctx: &JobWorkerContext,
+    slug: &str,
+    edited: &str,
+) -> (Uuid, Vec<(String, Uuid)>) {
+    let post = FixturePost::draft()
+        .slug(slug)
+        .body(OUTLINE)
+        .create(app)
+        .await;
+    process_render_post_job(
+        RenderPostJobPayload::new(post.slug.clone(), OUTLINE.into(), None),
+        Data::new(ctx.clone()),
+    )
+    .await
+    .expect("initial render");
+    sqlx::query("UPDATE posts SET body_markdown = $2 WHERE id = $1")
+        .bind(post.id)
+        .bind(edited)
+        .execute(app.api.db.pool())
+        .await
+        .expect("store edit");
+
+    (post.id, section_ids(app, post.id).await)
+}
+
+async fn section_ids(app: &TestApp, id: Uuid) -> Vec<(String, Uuid)> {
+    let mut ids: Vec<(String, Uuid)> = app
+        .api
+        .posts
+        .load_sections(id)
+        .await
+        .expect("load sections")
+        .into_iter()
+        .map(|section| (section.anchor_slug, section.id))
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn body_edit_rerenders_only_the_changed_section(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let ctx = app.job_context();
+    let edited = OUTLINE.replace("Run it.", "Run it twice.");
+    let (id, before) = rendered_outline(&app, &ctx, "incremental-edit", &edited).await;
+
+    process_render_post_job(
+        RenderPostJobPayload::new("incremental-edit".into(), edited.clone(), None)
+            .with_previous_body_markdown(OUTLINE.into()),
+        Data::new(ctx),
+    )
+    .await
+    .expect("incremental render");
+
+    let after = section_ids(&app, id).await;
+    let anchors: Vec<&str> = after.iter().map(|(anchor, _)| anchor.as_str()).collect();
+    assert_eq!(anchors, ["linux", "setup", "usage"]);
+    assert_eq!(after[0], before[0]);
+    assert_eq!(after[1], before[1]);
+    assert_ne!(after[2].1, before[2].1);
+
+    let body = rendered_body(&app, id).await;
+    assert!(body.contains("Run it twice."), "{body}");
+    assert!(body.contains("Use apt."), "{body}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn body_edit_renders_in_full_when_stored_sections_do_not_match(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let ctx = app.job_context();
+    let edited = OUTLINE.replace("Run it.", "Run it twice.");
+    let (id, before) = rendered_outline(&app, &ctx, "incremental-mismatch", &edited).await;
+
+    // The stored sections were rendered from `OUTLINE`, not from this body.
+    let claimed = OUTLINE.replace("Install it.", "Install it first.");
+    process_render_post_job(
+        RenderPostJobPayload::new("incremental-mismatch".into(), edited, None)
+            .with_previous_body_markdown(claimed),
+        Data::new(ctx),
+    )
+    .await
+    .expect("full render");
+
+    let after = section_ids(&app, id).await;
+    assert_eq!(after.len(), before.len());
+    for ((anchor, new_id), (_, old_id)) in after.iter().zip(&before) {
+        assert_ne!(new_id, old_id, "{anchor}");
+    }
+    let body = rendered_body(&app, id).await;
+    assert!(body.contains("Install it."), "{body}");
+    assert!(body.contains("Run it twice."), "{body}");
+}
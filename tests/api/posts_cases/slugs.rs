@@ -0,0 +1,200 @@
+use super::*;
+
+use soffio::application::admin::posts::{AdminPostError, CreatePostCommand};
+use soffio::domain::types::PostStatus;
+
+fn command(title: &str, slug: Option<&str>) -> CreatePostCommand {
+    CreatePostCommand {
+        slug: slug.map(str::to_string),
+        title: title.into(),
+        excerpt: String::new(),
+        body_markdown: "# body".into(),
+        summary_markdown: None,
+        status: PostStatus::Draft,
+        pinned: false,
+        scheduled_at: None,
+        published_at: None,
+        archived_at: None,
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn concurrent_creates_with_same_title_get_distinct_slugs(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+
+    let (first, second) = tokio::join!(
+        app.api
+            .posts
+            .create_post("test", command("Same Title", None)),
+        app.api
+            .posts
+            .create_post("test", command("Same Title", None)),
+    );
+    let first = first.expect("first create");
+    let second = second.expect("second create");
+
+    let mut slugs = vec![first.slug, second.slug];
+    slugs.sort();
+    assert_eq!(slugs, vec!["same-title", "same-title-2"]);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn explicit_slug_conflict_is_reported_not_suffixed(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    FixturePost::draft()
+        .slug("taken")
+        .title("Original")
+        .create(&app)
+        .await;
+
+    let err = app
+        .api
+        .posts
+        .create_post("test", command("Another", Some("taken")))
+        .await
+        .expect_err("explicit slug collides");
+    assert!(matches!(err, AdminPostError::SlugConflict { ref slug } if slug == "taken"));
+
+    let conflict = handlers::create_post(
+        app.state(),
+        Extension(principal.clone()),
+        StrictJson(PostCreateRequest {
+            title: "Via API".into(),
+            slug: Some("taken".into()),
+            excerpt: String::new(),
+            body_markdown: "# body".into(),
+            summary_markdown: None,
+            status: PostStatus::Draft,
+            pinned: false,
+            scheduled_at: None,
+            published_at: None,
+            archived_at: None,
+        }),
+    )
+    .await
+    .err()
+    .expect("api reports the conflict")
+    .into_response();
+    assert_eq!(conflict.status(), StatusCode::CONFLICT);
+    let body = axum::body::to_bytes(conflict.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    assert!(String::from_utf8_lossy(&body).contains("taken"));
+
+    let invalid = app
+        .api
+        .posts
+        .create_post("test", command("Shouty", Some("Not A Slug")))
+        .await
+        .expect_err("malformed slug rejected");
+    assert!(matches!(invalid, AdminPostError::InvalidSlug(_)));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn slug_strategy_shapes_title_derived_slugs(pool: PgPool) {
+    use soffio::domain::slug::SlugStrategy;
+    use time::macros::datetime;
+
+    let app = TestApp::new(pool).await;
+    let posts = (*app.api.posts).clone().with_slug_strategy(SlugStrategy {
+        transliterate: true,
+        allow_unicode: false,
+        date_prefix: true,
+        hash_suffix: false,
+    });
+    let mut published = command("Привет мир", None);
+    published.status = PostStatus::Published;
+    published.published_at = Some(datetime!(2026-03-07 09:30 UTC));
+
+    let first = posts
+        .create_post("test", published.clone())
+        .await
+        .expect("create first post");
+    let second = posts
+        .create_post("test", published)
+        .await
+        .expect("create second post");
+    let explicit = posts
+        .create_post("test", command("Привет мир", Some("hello")))
+        .await
+        .expect("create with explicit slug");
+
+    assert_eq!(first.slug, "2026-03-07-privet-mir");
+    assert_eq!(second.slug, "2026-03-07-privet-mir-2");
+    assert_eq!(explicit.slug, "hello");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn unicode_slugs_are_percent_encoded_and_routed(pool: PgPool) {
+    use axum::http::header::LOCATION;
+    use soffio::domain::routes::post_path;
+    use soffio::domain::slug::SlugStrategy;
+
+    let app = TestApp::new(pool).await;
+    let site = app.router();
+    let unicode = (*app.api.posts).clone().with_slug_strategy(SlugStrategy {
+        allow_unicode: true,
+        ..SlugStrategy::default()
+    });
+    let mut published = command("東京の夜、ラーメン！", None);
+    published.status = PostStatus::Published;
+    published.published_at = Some(OffsetDateTime::now_utc());
+
+    let ascii = app
+        .api
+        .posts
+        .create_post("test", published.clone())
+        .await
+        .expect("create with ascii slug");
+    let post = unicode
+        .create_post("test", published)
+        .await
+        .expect("create with unicode slug");
+    assert!(ascii.slug.is_ascii(), "{}", ascii.slug);
+    assert_eq!(post.slug, "東京の夜-ラーメン");
+
+    let path = post_path(&post.slug);
+    assert_eq!(
+        path,
+        "/posts/%E6%9D%B1%E4%BA%AC%E3%81%AE%E5%A4%9C-%E3%83%A9%E3%83%BC%E3%83%A1%E3%83%B3"
+    );
+    for (uri, status) in [
+        (path.clone(), StatusCode::OK),
+        ("/".to_string(), StatusCode::OK),
+        (format!("/fr{path}"), StatusCode::TEMPORARY_REDIRECT),
+    ] {
+        let request = Request::builder()
+            .uri(&uri)
+            .body(Body::empty())
+            .expect("build request");
+        let response = site.clone().oneshot(request).await.expect("public request");
+        assert_eq!(response.status(), status, "{uri}");
+        if status == StatusCode::TEMPORARY_REDIRECT {
+            assert_eq!(response.headers()[LOCATION], path.as_str());
+            continue;
+        }
+        let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+            .await
+            .expect("read body");
+        let html = String::from_utf8(body.to_vec()).expect("utf-8 body");
+        assert!(html.contains("東京の夜、ラーメン！"), "{uri}");
+        if uri == "/" {
+            assert!(html.contains(&format!(r#"href="{path}""#)));
+        }
+    }
+
+    let explicit = unicode
+        .create_post("test", command("Explicit", Some("ラーメン")))
+        .await
+        .expect("create with explicit unicode slug");
+    assert_eq!(explicit.slug, "ラーメン");
+    let rejected = app
+        .api
+        .posts
+        .create_post("test", command("Explicit", Some("ラーメン-2")))
+        .await
+        .expect_err("unicode slug rejected in ascii mode");
+    assert!(matches!(rejected, AdminPostError::InvalidSlug(_)));
+}
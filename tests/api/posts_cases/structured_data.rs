@@ -0,0 +1,97 @@
+use super::*;
+
+use serde_json::Value;
+use soffio::application::repos::{CreateTagParams, TagsWriteRepo};
+
+async fn ld_json(app: &Router, uri: &str) -> Value {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    let html = String::from_utf8(body.to_vec()).expect("utf-8 body");
+    let start = html
+        .find(r#"<script type="application/ld+json">"#)
+        .map(|index| index + r#"<script type="application/ld+json">"#.len())
+        .unwrap_or_else(|| panic!("{uri} has no structured data"));
+    let end = start + html[start..].find("</script>").expect("closing script tag");
+    serde_json::from_str(&html[start..end]).expect("structured data is valid JSON")
+}
+
+fn node<'a>(data: &'a Value, kind: &str) -> &'a Value {
+    assert_eq!(data["@context"], "https://schema.org");
+    data["@graph"]
+        .as_array()
+        .expect("graph")
+        .iter()
+        .find(|node| node["@type"] == kind)
+        .unwrap_or_else(|| panic!("no {kind} node in {data}"))
+}
+
+fn assert_rfc3339(value: &Value) {
+    let text = value.as_str().expect("date is a string");
+    chrono::DateTime::parse_from_rfc3339(text).expect("RFC 3339 date");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn post_and_archive_pages_embed_structured_data(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    sqlx::query("UPDATE site_settings SET public_site_url = 'https://example.com/'")
+        .execute(app.api.db.pool())
+        .await
+        .expect("set public site url");
+
+    let post = FixturePost::published()
+        .title("Structured </script> data")
+        .body("## Intro\n\nthree plain words")
+        .create(&app)
+        .await;
+    let tag = app
+        .api
+        .db
+        .create_tag(CreateTagParams {
+            slug: "rust".into(),
+            name: "Rust".into(),
+            description: None,
+            pinned: false,
+        })
+        .await
+        .expect("create tag");
+    sqlx::query("INSERT INTO post_tags (post_id, tag_id) VALUES ($1, $2)")
+        .bind(post.id)
+        .bind(tag.id)
+        .execute(app.api.db.pool())
+        .await
+        .expect("tag post");
+    let site = app.router();
+
+    let data = ld_json(&site, &format!("/posts/{}", post.slug)).await;
+    let posting = node(&data, "BlogPosting");
+    assert_eq!(posting["headline"], "Structured </script> data");
+    assert_eq!(
+        posting["url"],
+        format!("https://example.com/posts/{}", post.slug)
+    );
+    assert_rfc3339(&posting["datePublished"]);
+    assert_rfc3339(&posting["dateModified"]);
+    assert_eq!(posting["wordCount"], 4);
+    assert_eq!(posting["keywords"][0], "Rust");
+
+    let data = ld_json(&site, "/").await;
+    assert_eq!(node(&data, "WebSite")["url"], "https://example.com/");
+    let blog = node(&data, "Blog");
+    assert_rfc3339(&blog["blogPost"][0]["datePublished"]);
+
+    let data = ld_json(&site, "/tags/rust").await;
+    let crumbs = node(&data, "BreadcrumbList")["itemListElement"]
+        .as_array()
+        .expect("crumbs");
+    assert_eq!(crumbs.len(), 2);
+    assert_eq!(crumbs[1]["position"], 2);
+    assert_eq!(crumbs[1]["name"], "Rust");
+    assert_eq!(crumbs[1]["item"], "https://example.com/tags/rust");
+}
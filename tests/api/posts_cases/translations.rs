@@ -0,0 +1,141 @@
+use super::*;
+
+use axum::http::header::LOCATION;
+use soffio::application::api_keys::ApiPrincipal;
+
+async fn create_published_post(app: &TestApp, title: &str) -> (Uuid, String) {
+    let post = FixturePost::published().title(title).create(app).await;
+    (post.id, post.slug)
+}
+
+async fn set_translation(
+    app: &TestApp,
+    principal: &ApiPrincipal,
+    id: Uuid,
+    lang: Option<&str>,
+    translation_group: Option<Uuid>,
+) -> axum::response::Response {
+    match handlers::update_post_translation(
+        app.state(),
+        Extension(principal.clone()),
+        Path(id),
+        Json(PostTranslationRequest {
+            lang: lang.map(str::to_string),
+            translation_group,
+        }),
+    )
+    .await
+    {
+        Ok(response) => response.into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+async fn get_html(app: &Router, uri: &str) -> (StatusCode, String) {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    (
+        status,
+        String::from_utf8(body.to_vec()).expect("utf-8 body"),
+    )
+}
+
+async fn location(app: &Router, uri: &str) -> String {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT, "{uri}");
+    response.headers()[LOCATION]
+        .to_str()
+        .expect("location header")
+        .to_string()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn translated_posts_emit_hreflang_alternates(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+    let site = app.router();
+    let group = Uuid::new_v4();
+
+    let (en_id, en_slug) = create_published_post(&app, "Hello world").await;
+    let (de_id, de_slug) = create_published_post(&app, "Hallo Welt").await;
+    let (_, plain_slug) = create_published_post(&app, "Untranslated").await;
+
+    let response = set_translation(&app, &principal, en_id, Some("en"), Some(group)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let (_, updated) = response_json(response).await;
+    assert_eq!(string_field(&updated, "lang"), "en");
+    assert_eq!(uuid_field(&updated, "translation_group"), group);
+    let response = set_translation(&app, &principal, de_id, Some("DE"), Some(group)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (status, english) = get_html(&site, &format!("/posts/{en_slug}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(english.contains(r#"<html lang="en""#));
+    assert!(english.contains(r#"<link rel="alternate" hreflang="en" href="#));
+    assert!(english.contains(r#"<link rel="alternate" hreflang="de" href="#));
+    assert!(english.contains(&format!(r#"/posts/{de_slug}">"#)));
+
+    let (status, german) = get_html(&site, &format!("/posts/{de_slug}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(german.contains(r#"<html lang="de""#));
+    assert!(german.contains(&format!(r#"/posts/{en_slug}">"#)));
+
+    let (status, plain) = get_html(&site, &format!("/posts/{plain_slug}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(plain.contains(r#"<html lang="en""#));
+    assert!(!plain.contains("hreflang"));
+
+    assert_eq!(
+        location(&site, &format!("/posts/{en_slug}?lang=de")).await,
+        format!("/posts/{de_slug}")
+    );
+    assert_eq!(
+        location(&site, &format!("/de/posts/{en_slug}")).await,
+        format!("/posts/{de_slug}")
+    );
+    assert_eq!(
+        location(&site, &format!("/fr/posts/{en_slug}")).await,
+        format!("/posts/{en_slug}")
+    );
+    let (status, _) = get_html(&site, &format!("/posts/{en_slug}?lang=fr")).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn translation_groups_reject_duplicates_and_missing_lang(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+    let group = Uuid::new_v4();
+
+    let (first, _) = create_published_post(&app, "First").await;
+    let (second, _) = create_published_post(&app, "Second").await;
+
+    let response = set_translation(&app, &principal, first, Some("en"), Some(group)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let duplicate = set_translation(&app, &principal, second, Some("en"), Some(group)).await;
+    assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+
+    let missing_lang = set_translation(&app, &principal, second, None, Some(group)).await;
+    assert_eq!(missing_lang.status(), StatusCode::BAD_REQUEST);
+
+    let invalid = set_translation(&app, &principal, second, Some("english"), None).await;
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+
+    let cleared = set_translation(&app, &principal, first, None, None).await;
+    assert_eq!(cleared.status(), StatusCode::OK);
+    let (_, cleared) = response_json(cleared).await;
+    assert!(cleared["lang"].is_null());
+    assert!(cleared["translation_group"].is_null());
+}
@@ -0,0 +1,203 @@
+use super::*;
+
+use soffio::application::api_keys::ApiPrincipal;
+
+async fn create_post(app: &TestApp, principal: &ApiPrincipal, title: &str) -> (Uuid, String) {
+    let (status, created) = response_json(
+        handlers::create_post(
+            app.state(),
+            Extension(principal.clone()),
+            StrictJson(PostCreateRequest {
+                title: title.into(),
+                slug: None,
+                excerpt: "excerpt".into(),
+                body_markdown: "# body".into(),
+                summary_markdown: None,
+                status: soffio::domain::types::PostStatus::Draft,
+                pinned: false,
+                scheduled_at: None,
+                published_at: None,
+                archived_at: None,
+            }),
+        )
+        .await
+        .expect("create post via handler"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    (
+        uuid_field(&created, "id"),
+        string_field(&created, "slug").to_string(),
+    )
+}
+
+async fn trashed_ids(app: &TestApp, principal: &ApiPrincipal) -> Vec<Uuid> {
+    let (status, trash) = response_json(
+        handlers::list_trashed_posts(
+            app.state(),
+            Extension(principal.clone()),
+            Query(handlers::TrashListQuery {
+                cursor: None,
+                limit: None,
+            }),
+        )
+        .await
+        .expect("list trash"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    trash["items"]
+        .as_array()
+        .expect("items array")
+        .iter()
+        .map(|item| uuid_field(item, "id"))
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_post_delete_moves_to_trash_and_restores(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+    let (id, slug) = create_post(&app, &principal, "trash-me").await;
+
+    let response = handlers::delete_post(app.state(), Extension(principal.clone()), Path(id))
+        .await
+        .expect("delete post")
+        .into_response();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let hidden = handlers::get_post_by_id(app.state(), Extension(principal.clone()), Path(id))
+        .await
+        .err()
+        .expect("trashed post is hidden")
+        .into_response();
+    assert_eq!(hidden.status(), StatusCode::NOT_FOUND);
+    assert_eq!(trashed_ids(&app, &principal).await, vec![id]);
+
+    let (status, restored) = response_json(
+        handlers::restore_post(app.state(), Extension(principal.clone()), Path(id))
+            .await
+            .expect("restore post"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(string_field(&restored, "slug"), slug);
+
+    let (status, _) = response_json(
+        handlers::get_post(app.state(), Extension(principal.clone()), Path(slug))
+            .await
+            .expect("restored post is visible"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(trashed_ids(&app, &principal).await.is_empty());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_post_restore_conflicts_when_slug_was_reused(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+    let (original, slug) = create_post(&app, &principal, "reused").await;
+
+    handlers::delete_post(app.state(), Extension(principal.clone()), Path(original))
+        .await
+        .expect("delete post");
+
+    let (replacement, replacement_slug) = create_post(&app, &principal, "reused").await;
+    assert_eq!(replacement_slug, slug, "trashing frees the slug");
+
+    let conflict =
+        handlers::restore_post(app.state(), Extension(principal.clone()), Path(original))
+            .await
+            .err()
+            .expect("restore conflicts with the new post")
+            .into_response();
+    assert_eq!(conflict.status(), StatusCode::CONFLICT);
+
+    assert_eq!(trashed_ids(&app, &principal).await, vec![original]);
+    let (_, current) = response_json(
+        handlers::get_post(app.state(), Extension(principal.clone()), Path(slug))
+            .await
+            .expect("replacement keeps the slug"),
+    )
+    .await;
+    assert_eq!(uuid_field(&current, "id"), replacement);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_post_purge_only_removes_trashed_posts(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+    let (id, _slug) = create_post(&app, &principal, "purge-me").await;
+
+    let live = handlers::purge_post(app.state(), Extension(principal.clone()), Path(id))
+        .await
+        .err()
+        .expect("live posts cannot be purged")
+        .into_response();
+    assert_eq!(live.status(), StatusCode::NOT_FOUND);
+
+    handlers::delete_post(app.state(), Extension(principal.clone()), Path(id))
+        .await
+        .expect("delete post");
+    let response = handlers::purge_post(app.state(), Extension(principal.clone()), Path(id))
+        .await
+        .expect("purge post")
+        .into_response();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    assert!(trashed_ids(&app, &principal).await.is_empty());
+    let gone = handlers::restore_post(app.state(), Extension(principal.clone()), Path(id))
+        .await
+        .err()
+        .expect("purged posts cannot be restored")
+        .into_response();
+    assert_eq!(gone.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_post_trash_pages_with_a_cursor(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+    let mut trashed = Vec::new();
+    for title in ["first", "second", "third"] {
+        let (id, _) = create_post(&app, &principal, title).await;
+        handlers::delete_post(app.state(), Extension(principal.clone()), Path(id))
+            .await
+            .expect("delete post");
+        trashed.push(id);
+    }
+
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (status, page) = response_json(
+            handlers::list_trashed_posts(
+                app.state(),
+                Extension(principal.clone()),
+                Query(handlers::TrashListQuery {
+                    cursor: cursor.take(),
+                    limit: Some(2),
+                }),
+            )
+            .await
+            .expect("list trash"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        seen.extend(
+            page["items"]
+                .as_array()
+                .expect("items array")
+                .iter()
+                .map(|item| uuid_field(item, "id")),
+        );
+        match page["next_cursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+    }
+
+    trashed.reverse();
+    assert_eq!(seen, trashed, "most recently trashed first, each once");
+}
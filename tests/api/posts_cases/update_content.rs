@@ -1,29 +1,18 @@
 use super::*;
 
+use soffio::infra::http::api::etag::etag_for;
+
 #[sqlx::test(migrations = "./migrations")]
 async fn api_can_update_post_content(pool: PgPool) {
-    let (state, token) = build_state(pool).await;
-    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
 
-    // Create a post via service to get the ID
-    let post = state
-        .posts
-        .create_post(
-            "test",
-            soffio::application::admin::posts::CreatePostCommand {
-                title: "original-title".into(),
-                excerpt: "original".into(),
-                body_markdown: "# original".into(),
-                summary_markdown: None,
-                status: soffio::domain::types::PostStatus::Draft,
-                pinned: false,
-                scheduled_at: None,
-                published_at: None,
-                archived_at: None,
-            },
-        )
-        .await
-        .expect("create post via service");
+    let post = FixturePost::draft()
+        .title("original-title")
+        .excerpt("original")
+        .body("# original")
+        .create(&app)
+        .await;
 
     // Update the post via handler
     let update_payload = PostUpdateRequest {
@@ -36,11 +25,89 @@ async fn api_can_update_post_content(pool: PgPool) {
     };
 
     let _updated = handlers::update_post(
-        State(state.clone()),
+        app.state(),
         Extension(principal.clone()),
         axum::extract::Path(post.id),
-        Json(update_payload),
+        if_match("*"),
+        StrictJson(update_payload),
     )
     .await
     .expect("update post via handler");
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_post_update_honours_if_match(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
+
+    let post = FixturePost::draft()
+        .title("versioned")
+        .body("# v1")
+        .create(&app)
+        .await;
+    let current = etag_for(post.updated_at);
+
+    let payload = |body: &str| PostUpdateRequest {
+        slug: post.slug.clone(),
+        title: "versioned".into(),
+        excerpt: "excerpt".into(),
+        body_markdown: body.into(),
+        summary_markdown: None,
+        pinned: false,
+    };
+
+    let response = handlers::update_post(
+        app.state(),
+        Extension(principal.clone()),
+        axum::extract::Path(post.id),
+        if_match(&current),
+        StrictJson(payload("# v2")),
+    )
+    .await
+    .expect("conditional update")
+    .into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+    let next = response
+        .headers()
+        .get(axum::http::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .expect("etag on update")
+        .to_string();
+    assert_ne!(next, current);
+
+    // The tag read before the first update is now stale.
+    let stale = handlers::update_post(
+        app.state(),
+        Extension(principal.clone()),
+        axum::extract::Path(post.id),
+        if_match(&current),
+        StrictJson(payload("# v3")),
+    )
+    .await
+    .err()
+    .expect("stale update rejected")
+    .into_response();
+    assert_eq!(stale.status(), StatusCode::PRECONDITION_FAILED);
+
+    let missing = handlers::update_post(
+        app.state(),
+        Extension(principal.clone()),
+        axum::extract::Path(post.id),
+        axum::http::HeaderMap::new(),
+        StrictJson(payload("# v3")),
+    )
+    .await
+    .err()
+    .expect("missing precondition rejected")
+    .into_response();
+    assert_eq!(missing.status(), StatusCode::PRECONDITION_REQUIRED);
+
+    let stored = app
+        .api
+        .posts
+        .load_post(post.id)
+        .await
+        .expect("load post")
+        .expect("post exists");
+    assert_eq!(stored.body_markdown, "# v2");
+}
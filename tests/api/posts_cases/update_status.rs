@@ -2,28 +2,10 @@ use super::*;
 
 #[sqlx::test(migrations = "./migrations")]
 async fn api_can_update_post_status(pool: PgPool) {
-    let (state, token) = build_state(pool).await;
-    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let app = TestApp::new(pool).await;
+    let principal = app.principal();
 
-    // Create a post via service to get the ID
-    let post = state
-        .posts
-        .create_post(
-            "test",
-            soffio::application::admin::posts::CreatePostCommand {
-                title: "status-test".into(),
-                excerpt: "excerpt".into(),
-                body_markdown: "# body".into(),
-                summary_markdown: None,
-                status: soffio::domain::types::PostStatus::Draft,
-                pinned: false,
-                scheduled_at: None,
-                published_at: None,
-                archived_at: None,
-            },
-        )
-        .await
-        .expect("create post via service");
+    let post = FixturePost::draft().title("status-test").create(&app).await;
 
     // Update status to published via handler
     let status_payload = PostStatusRequest {
@@ -33,12 +15,19 @@ async fn api_can_update_post_status(pool: PgPool) {
         archived_at: None,
     };
 
-    let _updated = handlers::update_post_status(
-        State(state.clone()),
-        Extension(principal.clone()),
-        axum::extract::Path(post.id),
-        Json(status_payload),
+    let (status, updated) = response_json(
+        handlers::update_post_status(
+            app.state(),
+            Extension(principal.clone()),
+            axum::extract::Path(post.id),
+            Query(handlers::PostStatusQuery { wait_render: true }),
+            Json(status_payload),
+        )
+        .await
+        .expect("update post status via handler"),
     )
-    .await
-    .expect("update post status via handler");
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(string_field(&updated, "status"), "published");
+    assert_eq!(updated["render_progress"]["state"], "rendered", "{updated}");
 }
@@ -0,0 +1,106 @@
+use super::*;
+
+use soffio::application::api_keys::ApiPrincipal;
+use soffio::application::render::RenderPreviewService;
+use soffio::application::render::render_service;
+
+const EMBED_MARKDOWN: &str = "# Clip\n\nhttps://www.youtube.com/watch?v=dQw4w9WgXcQ\n\n\
+<iframe src=\"https://evil.example/frame\"></iframe>\n\n<script>alert(1)</script>\n";
+
+async fn preview(
+    state: &ApiState,
+    principal: &ApiPrincipal,
+    markdown: &str,
+    sanitized: bool,
+) -> (StatusCode, serde_json::Value) {
+    response_json(
+        handlers::render_preview(
+            State(state.clone()),
+            Extension(principal.clone()),
+            Json(RenderPreviewRequest {
+                markdown: markdown.to_string(),
+                target: RenderPreviewTarget::Post,
+                sanitized,
+            }),
+        )
+        .await,
+    )
+    .await
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn render_preview_reports_what_the_sanitizer_strips(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+
+    let (status, sanitized) = preview(&state, &principal, EMBED_MARKDOWN, true).await;
+    assert_eq!(status, StatusCode::OK);
+    let html = string_field(&sanitized, "html");
+    assert!(html.contains("https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ"));
+    assert!(!html.contains("evil.example"));
+    assert!(!html.contains("<script"));
+    assert_eq!(sanitized["sanitized"], true);
+    assert!(sanitized.get("stripped").is_none());
+    assert!(
+        sanitized["sections"]
+            .as_array()
+            .is_some_and(|sections| !sections.is_empty())
+    );
+
+    let (status, raw) = preview(&state, &principal, EMBED_MARKDOWN, false).await;
+    assert_eq!(status, StatusCode::OK);
+    let html = string_field(&raw, "html");
+    assert!(html.contains("https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ"));
+    assert!(html.contains(r#"<iframe src="https://evil.example/frame">"#));
+    assert!(html.contains("<script>alert(1)</script>"));
+    assert_eq!(raw["sanitized"], false);
+    assert_eq!(raw["stripped"]["elements"]["script"], 1);
+    assert_eq!(raw["stripped"]["attributes"]["iframe[src]"], 1);
+    assert_eq!(
+        raw["sections"][0]["heading_text"],
+        sanitized["sections"][0]["heading_text"]
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn render_preview_enforces_size_cap_scope_and_rate_limit(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+
+    let state = ApiState {
+        render_preview: Arc::new(RenderPreviewService::new(render_service(), 16)),
+        render_preview_rate_limiter: Arc::new(
+            soffio::infra::http::api::rate_limit::ApiRateLimiter::new(
+                std::time::Duration::from_secs(60),
+                2,
+            ),
+        ),
+        ..state
+    };
+
+    let (status, body) = preview(&state, &principal, "short", true).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(string_field(&body, "html").contains("short"));
+
+    let (status, body) = preview(&state, &principal, &"x".repeat(17), true).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["error"]["code"], "payload_too_large");
+
+    let (status, _) = preview(&state, &principal, "again", true).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    let issued = state
+        .api_keys
+        .issue(IssueApiKeyCommand {
+            name: "no-preview".to_string(),
+            description: None,
+            scopes: vec![ApiScope::PostRead],
+            expires_in: None,
+            created_by: "tests".to_string(),
+        })
+        .await
+        .unwrap();
+    let limited = state.api_keys.authenticate(&issued.token).await.unwrap();
+    let (status, _) = preview(&state, &limited, "short", true).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,94 @@
+use super::*;
+
+use axum::routing::post;
+use soffio::application::pagination::PageRequest;
+use soffio::application::repos::JobQueryFilter;
+use soffio::infra::http::middleware::set_request_context;
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_request_id_round_trips_into_jobs_and_audit(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state
+        .api_keys
+        .authenticate(&token)
+        .await
+        .expect("authenticate token");
+
+    let app = Router::new()
+        .route("/api/v1/posts", post(handlers::create_post))
+        .with_state(state.clone())
+        .layer(middleware::from_fn(set_request_context));
+
+    let payload = serde_json::json!({
+        "title": "traced-post",
+        "excerpt": "excerpt",
+        "body_markdown": "# body",
+        "status": "draft",
+        "pinned": false,
+    });
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/posts")
+        .header("content-type", "application/json")
+        .header("x-request-id", "edge-123")
+        .body(Body::from(payload.to_string()))
+        .expect("build request");
+    request.extensions_mut().insert(principal);
+
+    let response = app.oneshot(request).await.expect("send request");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(
+        response
+            .headers()
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok()),
+        Some("edge-123")
+    );
+
+    let jobs = state
+        .jobs
+        .list_jobs(&JobQueryFilter::default(), PageRequest::new(50, None))
+        .await
+        .expect("list jobs");
+    assert!(
+        !jobs.items.is_empty(),
+        "creating a post enqueues render jobs"
+    );
+    assert!(
+        jobs.items
+            .iter()
+            .all(|job| job.payload.get("request_id").and_then(|v| v.as_str()) == Some("edge-123"))
+    );
+
+    let audit = state.audit.list_recent(10).await.expect("list audit");
+    assert!(
+        audit
+            .iter()
+            .any(|entry| entry.request_id.as_deref() == Some("edge-123"))
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_request_id_is_generated_when_header_is_invalid(pool: PgPool) {
+    let (state, _token) = build_state(pool).await;
+
+    let app = Router::new()
+        .route("/ping", get(|| async { StatusCode::OK }))
+        .with_state(state)
+        .layer(middleware::from_fn(set_request_context));
+
+    let request = Request::builder()
+        .uri("/ping")
+        .header("x-request-id", "bad id with spaces")
+        .body(Body::empty())
+        .expect("build request");
+
+    let response = app.oneshot(request).await.expect("send request");
+    let echoed = response
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .expect("request id header");
+    assert_ne!(echoed, "bad id with spaces");
+    assert!(Uuid::parse_str(echoed).is_ok());
+}
@@ -0,0 +1,179 @@
+use super::*;
+
+use soffio::application::admin::pages::CreatePageCommand;
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::api_keys::ApiPrincipal;
+use soffio::domain::types::{PageStatus, PostStatus};
+use time::macros::datetime;
+
+async fn create_post(
+    state: &ApiState,
+    slug: &str,
+    status: PostStatus,
+    scheduled_at: Option<OffsetDateTime>,
+    published_at: Option<OffsetDateTime>,
+) -> Uuid {
+    state
+        .posts
+        .create_post(
+            "test",
+            CreatePostCommand {
+                slug: Some(slug.into()),
+                title: slug.into(),
+                excerpt: "excerpt".into(),
+                body_markdown: "# body".into(),
+                summary_markdown: None,
+                status,
+                pinned: false,
+                scheduled_at,
+                published_at,
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("create post")
+        .id
+}
+
+async fn schedule(
+    state: &ApiState,
+    principal: &ApiPrincipal,
+    from: &str,
+    to: &str,
+) -> (StatusCode, serde_json::Value) {
+    let response = handlers::get_schedule(
+        State(state.clone()),
+        Extension(principal.clone()),
+        Query(handlers::ScheduleQuery {
+            from: from.into(),
+            to: to.into(),
+        }),
+    )
+    .await
+    .into_response();
+    response_json(response).await
+}
+
+/// `(date, [(kind, title, state)])` of one schedule day.
+type ScheduleDay = (String, Vec<(String, String, String)>);
+
+/// Every day of a schedule response.
+fn days(body: &serde_json::Value) -> Vec<ScheduleDay> {
+    body.as_array()
+        .expect("days array")
+        .iter()
+        .map(|day| {
+            let items = day["items"]
+                .as_array()
+                .expect("items array")
+                .iter()
+                .map(|item| {
+                    (
+                        string_field(item, "kind").to_string(),
+                        string_field(item, "title").to_string(),
+                        string_field(item, "state").to_string(),
+                    )
+                })
+                .collect();
+            (string_field(day, "date").to_string(), items)
+        })
+        .collect()
+}
+
+fn entry(kind: &str, title: &str, state: &str) -> (String, String, String) {
+    (kind.to_string(), title.to_string(), state.to_string())
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn schedule_buckets_items_by_day_in_the_site_timezone(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    state
+        .settings
+        .patch(
+            "test",
+            serde_json::from_value(serde_json::json!({ "timezone": "Asia/Tokyo" }))
+                .expect("settings patch"),
+        )
+        .await
+        .expect("set timezone");
+
+    // 18:00 on Oct 31 and 00:30 on Nov 1 in Tokyo.
+    create_post(
+        &state,
+        "halloween",
+        PostStatus::Published,
+        None,
+        Some(datetime!(2030-10-31 09:00 UTC)),
+    )
+    .await;
+    create_post(
+        &state,
+        "midnight",
+        PostStatus::Draft,
+        Some(datetime!(2030-10-31 15:30 UTC)),
+        None,
+    )
+    .await;
+    create_post(&state, "unscheduled", PostStatus::Draft, None, None).await;
+    state
+        .pages
+        .create_page(
+            "test",
+            CreatePageCommand {
+                slug: Some("launch".into()),
+                title: "launch".into(),
+                body_markdown: "# body".into(),
+                status: PageStatus::Draft,
+                scheduled_at: Some(datetime!(2030-11-01 02:00 UTC)),
+                published_at: None,
+                archived_at: None,
+                template: None,
+            },
+        )
+        .await
+        .expect("create page");
+
+    let (status, body) = schedule(&state, &principal, "2030-10-31", "2030-11-01").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        days(&body),
+        [
+            (
+                "2030-10-31".to_string(),
+                vec![entry("post", "halloween", "published")]
+            ),
+            (
+                "2030-11-01".to_string(),
+                vec![
+                    entry("post", "midnight", "scheduled"),
+                    entry("page", "launch", "scheduled"),
+                ]
+            ),
+        ]
+    );
+    assert_eq!(
+        string_field(&body[1]["items"][0], "at"),
+        "2030-10-31T15:30:00Z"
+    );
+
+    let (_, november) = schedule(&state, &principal, "2030-11-01", "2030-11-30").await;
+    assert_eq!(days(&november).len(), 1);
+    assert_eq!(days(&november)[0].0, "2030-11-01");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn schedule_rejects_bad_dates_and_ranges(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+
+    for (from, to) in [
+        ("2030-11-01", "2030-10-31"),
+        ("2030-01-01", "2031-06-01"),
+        ("2030-11", "2030-11-30"),
+        ("2030-11-01", "tomorrow"),
+    ] {
+        let (status, _) = schedule(&state, &principal, from, to).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{from}..{to}");
+    }
+}
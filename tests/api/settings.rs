@@ -1,7 +1,34 @@
 use super::*;
 
+#[path = "settings_cases/base_path.rs"]
+mod base_path;
+
+#[path = "settings_cases/config_reload.rs"]
+mod config_reload;
+
+#[path = "settings_cases/favicon.rs"]
+mod favicon;
+
+#[path = "settings_cases/feed_content.rs"]
+mod feed_content;
+
 #[path = "settings_cases/get_patch.rs"]
 mod get_patch;
 
+#[path = "settings_cases/homepage.rs"]
+mod homepage;
+
+#[path = "settings_cases/homepage_layout.rs"]
+mod homepage_layout;
+
+#[path = "settings_cases/maintenance.rs"]
+mod maintenance;
+
+#[path = "settings_cases/security_headers.rs"]
+mod security_headers;
+
 #[path = "settings_cases/toc_favicon.rs"]
 mod toc_favicon;
+
+#[path = "settings_cases/validation.rs"]
+mod validation;
@@ -0,0 +1,121 @@
+use super::*;
+
+use soffio::domain::routes::BasePath;
+
+async fn published_post(app: &TestApp) -> String {
+    sqlx::query("UPDATE site_settings SET public_site_url = 'https://example.com/'")
+        .execute(app.api.db.pool())
+        .await
+        .expect("set public site url");
+
+    FixturePost::published()
+        .title("Mounted")
+        .create(app)
+        .await
+        .slug
+}
+
+async fn mounted_at_blog(pool: PgPool) -> TestApp {
+    TestApp::builder(pool)
+        .base_path(BasePath::parse("/blog").expect("base path"))
+        .build()
+        .await
+}
+
+async fn get_body(app: &Router, uri: &str) -> String {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    String::from_utf8(body.to_vec()).expect("utf-8 body")
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn generated_urls_include_the_base_path(pool: PgPool) {
+    let app = mounted_at_blog(pool).await;
+    let slug = published_post(&app).await;
+    let site = app.router();
+    let post_url = format!("https://example.com/blog/posts/{slug}");
+
+    let rss = get_body(&site, "/blog/rss.xml").await;
+    assert!(rss.contains(&post_url), "{rss}");
+    let atom = get_body(&site, "/blog/atom.xml").await;
+    assert!(atom.contains(&post_url), "{atom}");
+    let sitemap = get_body(&site, "/blog/sitemap.xml").await;
+    assert!(
+        sitemap.contains(&format!("<loc>{post_url}</loc>")),
+        "{sitemap}"
+    );
+
+    let page = get_body(&site, &format!("/blog/posts/{slug}")).await;
+    assert!(page.contains(&format!(r#"<link rel="canonical" href="{post_url}">"#)));
+    assert!(page.contains(&format!(r#"<meta property="og:url" content="{post_url}">"#)));
+    let robots = get_body(&site, "/blog/robots.txt").await;
+    assert!(robots.contains("Sitemap: https://example.com/blog/sitemap.xml"));
+    assert!(page.contains(r#"href="/blog/static/public/app."#));
+
+    let index = get_body(&site, "/blog/").await;
+    assert!(index.contains(&format!(r#"href="/blog/posts/{slug}""#)));
+
+    let request = Request::builder()
+        .uri(format!("/posts/{slug}"))
+        .body(Body::empty())
+        .expect("build request");
+    let response = site.oneshot(request).await.expect("public request");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn generated_urls_omit_an_empty_base_path(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let slug = published_post(&app).await;
+    let site = app.router();
+    let post_url = format!("https://example.com/posts/{slug}");
+
+    for uri in ["/rss.xml", "/atom.xml", "/sitemap.xml", "/"] {
+        let body = get_body(&site, uri).await;
+        assert!(!body.contains("/blog"), "{uri}");
+    }
+    let rss = get_body(&site, "/rss.xml").await;
+    assert!(rss.contains(&post_url));
+
+    let page = get_body(&site, &format!("/posts/{slug}")).await;
+    assert!(page.contains(&format!(r#"<link rel="canonical" href="{post_url}">"#)));
+    assert!(page.contains(r#"href="/static/public/app."#));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn generated_urls_degrade_without_a_public_site_url(pool: PgPool) {
+    let app = mounted_at_blog(pool).await;
+    let slug = published_post(&app).await;
+    sqlx::query("UPDATE site_settings SET public_site_url = ''")
+        .execute(app.api.db.pool())
+        .await
+        .expect("clear public site url");
+    let site = app.router();
+
+    for uri in ["/blog/sitemap.xml", "/blog/rss.xml", "/blog/atom.xml"] {
+        let request = Request::builder()
+            .uri(uri)
+            .body(Body::empty())
+            .expect("build request");
+        let response = site.clone().oneshot(request).await.expect("public request");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{uri}");
+    }
+
+    let robots = get_body(&site, "/blog/robots.txt").await;
+    assert!(robots.contains("Allow: /blog/"), "{robots}");
+    assert!(!robots.contains("Sitemap:"), "{robots}");
+
+    let page = get_body(&site, &format!("/blog/posts/{slug}")).await;
+    assert!(page.contains(&format!(
+        r#"<link rel="canonical" href="/blog/posts/{slug}">"#
+    )));
+    assert!(!page.contains("og:url"));
+    assert!(!page.contains("application/ld+json"));
+}
@@ -0,0 +1,142 @@
+use super::*;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use clap::Parser;
+use soffio::config::{self, CliArgs};
+use soffio::infra::http::ApiRateLimiter;
+use soffio::infra::reload::{ConfigReloader, ReloadTargets};
+
+const BEFORE: &str = "\
+[server]
+public_port = 3000
+
+[uploads]
+max_request_bytes = 1048576
+
+[api_rate_limit]
+window_seconds = 60
+max_requests = 100
+";
+
+const AFTER: &str = "\
+[server]
+public_port = 3100
+
+[uploads]
+max_request_bytes = 2048
+
+[rate_limit]
+max_requests = 90
+
+[api_rate_limit]
+window_seconds = 30
+max_requests = 5
+";
+
+fn limiter() -> Arc<ApiRateLimiter> {
+    Arc::new(ApiRateLimiter::new(Duration::from_secs(60), 100))
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn reload_applies_safe_settings_and_defers_the_rest(pool: PgPool) {
+    let (state, _token) = build_state(pool).await;
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("reload.toml");
+    std::fs::write(&path, BEFORE).expect("write config");
+
+    let cli = CliArgs::parse_from([
+        "soffio",
+        "--config-file",
+        path.to_str().expect("utf-8 path"),
+        "serve",
+    ]);
+    let settings = config::load(&cli).expect("initial config");
+    let api_rate_limiter = limiter();
+    let upload_limit = Arc::new(AtomicU64::new(settings.uploads.max_request_bytes.get()));
+    let reloader = ConfigReloader::new(
+        cli,
+        settings,
+        ReloadTargets {
+            log_level: None,
+            api_rate_limiter: api_rate_limiter.clone(),
+            comment_rate_limiter: limiter(),
+            render_preview_rate_limiter: limiter(),
+            upload_limit_bytes: upload_limit.clone(),
+            cache_consume_interval_ms: Arc::new(AtomicU64::new(0)),
+            tls: Vec::new(),
+        },
+        state.audit.clone(),
+    );
+
+    std::fs::write(&path, AFTER).expect("edit config");
+    let diff = reloader.reload("admin").await.expect("reload");
+
+    let applied: Vec<_> = diff.applied.iter().map(|change| change.key).collect();
+    assert_eq!(
+        applied,
+        [
+            "uploads.max_request_bytes",
+            "api_rate_limit.window_seconds",
+            "api_rate_limit.max_requests",
+        ]
+    );
+    let deferred: Vec<_> = diff.deferred.iter().map(|change| change.key).collect();
+    assert_eq!(deferred, ["server.public_addr"]);
+    let inert: Vec<_> = diff.inert.iter().map(|change| change.key).collect();
+    assert_eq!(inert, ["rate_limit.max_requests"]);
+    assert_eq!(api_rate_limiter.limit(), 5);
+    assert_eq!(api_rate_limiter.retry_after_secs(), 30);
+    assert_eq!(upload_limit.load(Ordering::Relaxed), 2048);
+
+    let audit = state.audit.list_recent(10).await.expect("list audit");
+    let entry = audit
+        .iter()
+        .find(|entry| entry.action == "config.reload")
+        .expect("reload audited");
+    assert_eq!(entry.actor, "admin");
+    let payload = entry.payload_text.as_deref().expect("payload");
+    assert!(payload.contains("server.public_addr"), "{payload}");
+
+    // Applied changes are now running; the listener change still needs a restart.
+    let diff = reloader.reload("sighup").await.expect("second reload");
+    assert!(diff.applied.is_empty());
+    assert_eq!(diff.deferred.len(), 1);
+    assert!(diff.inert.is_empty());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn invalid_config_is_rejected_without_changes(pool: PgPool) {
+    let (state, _token) = build_state(pool).await;
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("reload.toml");
+    std::fs::write(&path, BEFORE).expect("write config");
+
+    let cli = CliArgs::parse_from([
+        "soffio",
+        "--config-file",
+        path.to_str().expect("utf-8 path"),
+        "serve",
+    ]);
+    let settings = config::load(&cli).expect("initial config");
+    let api_rate_limiter = limiter();
+    let reloader = ConfigReloader::new(
+        cli,
+        settings,
+        ReloadTargets {
+            log_level: None,
+            api_rate_limiter: api_rate_limiter.clone(),
+            comment_rate_limiter: limiter(),
+            render_preview_rate_limiter: limiter(),
+            upload_limit_bytes: Arc::new(AtomicU64::new(0)),
+            cache_consume_interval_ms: Arc::new(AtomicU64::new(0)),
+            tls: Vec::new(),
+        },
+        state.audit.clone(),
+    );
+
+    std::fs::write(&path, "[api_rate_limit]\nmax_requests = 0\n").expect("edit config");
+    assert!(reloader.reload("admin").await.is_err());
+    assert_eq!(api_rate_limiter.limit(), 100);
+}
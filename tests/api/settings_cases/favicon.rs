@@ -0,0 +1,173 @@
+use super::*;
+
+use bytes::Bytes;
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+const ICO: &[u8] = b"\x00\x00\x01\x00\x01\x00\x10\x10";
+
+async fn stored_upload(
+    state: &ApiState,
+    filename: &str,
+    content_type: &str,
+    contents: &'static [u8],
+) -> Uuid {
+    let stored = state
+        .upload_storage
+        .store(filename, Bytes::from_static(contents))
+        .await
+        .expect("store upload");
+    let record = UploadRecord {
+        id: Uuid::new_v4(),
+        filename: filename.to_string(),
+        content_type: content_type.to_string(),
+        size_bytes: stored.size_bytes,
+        checksum: stored.checksum,
+        stored_path: stored.stored_path,
+        metadata: Default::default(),
+        created_at: OffsetDateTime::now_utc(),
+        last_verified_at: None,
+        checksum_mismatch: false,
+    };
+    state
+        .uploads
+        .register_upload("tests", record.clone())
+        .await
+        .expect("register upload");
+    record.id
+}
+
+fn patch(value: serde_json::Value) -> SettingsPatchRequest {
+    serde_json::from_value(value).expect("settings patch")
+}
+
+/// Status, content type and body of a GET to `uri`.
+async fn get(app: &Router, uri: &str) -> (StatusCode, String, Vec<u8>) {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    (status, content_type, body.to_vec())
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn icon_routes_serve_the_svg_without_a_favicon_upload(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    let app = test_app.router();
+    let svg = state.settings.load().await.expect("settings").favicon_svg;
+
+    for uri in ["/favicon.ico", "/favicon.svg"] {
+        let (status, content_type, body) = get(&app, uri).await;
+        assert_eq!(status, StatusCode::OK, "{uri}");
+        assert_eq!(content_type, "image/svg+xml; charset=utf-8", "{uri}");
+        assert_eq!(body, svg.as_bytes(), "{uri}");
+    }
+    let (status, _, _) = get(&app, "/apple-touch-icon.png").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, _, home) = get(&app, "/").await;
+    let home = String::from_utf8(home).expect("utf-8 page");
+    assert!(home.contains(r#"<link rel="icon" href="/favicon.svg" type="image/svg+xml">"#));
+    assert!(!home.contains(r#"href="/favicon.ico""#));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn png_favicon_upload_is_served_with_its_content_type(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    let id = stored_upload(&state, "favicon.png", "image/png", PNG).await;
+    state
+        .settings
+        .patch(
+            "tests",
+            patch(serde_json::json!({ "favicon_upload_id": id })),
+        )
+        .await
+        .expect("set favicon upload");
+    let app = test_app.router();
+
+    for uri in ["/favicon.ico", "/apple-touch-icon.png"] {
+        let (status, content_type, body) = get(&app, uri).await;
+        assert_eq!(status, StatusCode::OK, "{uri}");
+        assert_eq!(content_type, "image/png", "{uri}");
+        assert_eq!(body, PNG, "{uri}");
+    }
+    let (status, content_type, _) = get(&app, "/favicon.svg").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/svg+xml; charset=utf-8");
+
+    let (_, _, home) = get(&app, "/").await;
+    let home = String::from_utf8(home).expect("utf-8 page");
+    assert!(home.contains(r#"<link rel="icon" href="/favicon.ico">"#));
+    assert!(!home.contains(r#"href="/favicon.svg""#));
+
+    state
+        .settings
+        .patch(
+            "tests",
+            patch(serde_json::json!({ "favicon_upload_id": null })),
+        )
+        .await
+        .expect("clear favicon upload");
+    let (_, content_type, _) = get(&app, "/favicon.ico").await;
+    assert_eq!(content_type, "image/svg+xml; charset=utf-8");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn ico_favicon_is_not_offered_as_a_touch_icon(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    let id = stored_upload(&state, "favicon.ico", "image/x-icon", ICO).await;
+    state
+        .settings
+        .patch(
+            "tests",
+            patch(serde_json::json!({ "favicon_upload_id": id })),
+        )
+        .await
+        .expect("set favicon upload");
+    let app = test_app.router();
+
+    let (status, content_type, body) = get(&app, "/favicon.ico").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/x-icon");
+    assert_eq!(body, ICO);
+    let (status, _, _) = get(&app, "/apple-touch-icon.png").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn favicon_upload_must_be_a_png_or_ico_image(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let text = stored_upload(&state, "notes.txt", "text/plain", b"not an icon").await;
+
+    for id in [text, Uuid::new_v4()] {
+        let response = handlers::patch_settings(
+            State(state.clone()),
+            Extension(principal.clone()),
+            StrictJson(patch(serde_json::json!({ "favicon_upload_id": id }))),
+        )
+        .await
+        .err()
+        .expect("unusable favicon upload is rejected")
+        .into_response();
+        let (status, body) = response_json(response).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+        assert_eq!(body["error"]["fields"][0]["field"], "favicon_upload_id");
+    }
+
+    let latest = state.settings.load().await.expect("load settings");
+    assert_eq!(latest.favicon_upload_id, None);
+}
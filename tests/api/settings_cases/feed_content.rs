@@ -0,0 +1,142 @@
+use super::*;
+
+use apalis::prelude::Data;
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::render::{RenderPostJobPayload, process_render_post_job};
+use soffio::domain::types::PostStatus;
+
+const BODY: &str = "## Setup\n\nSee [the setup](#setup) and [about](/about).\n\n![A cat](/uploads/2026/10/cat.png)\n";
+
+async fn rendered_post(app: &TestApp, title: &str, body: &str, summary: Option<&str>) -> String {
+    let post = app
+        .api
+        .posts
+        .create_post(
+            "test",
+            CreatePostCommand {
+                slug: None,
+                title: title.into(),
+                excerpt: "plain excerpt".into(),
+                body_markdown: body.into(),
+                summary_markdown: summary.map(str::to_string),
+                status: PostStatus::Published,
+                pinned: false,
+                scheduled_at: None,
+                published_at: Some(OffsetDateTime::now_utc()),
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("create post via service");
+    process_render_post_job(
+        RenderPostJobPayload::new(post.slug.clone(), body.into(), summary.map(str::to_string)),
+        Data::new(app.job_context()),
+    )
+    .await
+    .expect("render post");
+    post.slug
+}
+
+async fn patch_feed_settings(state: &ApiState, patch: serde_json::Value) {
+    state
+        .settings
+        .patch("test", serde_json::from_value(patch).expect("patch"))
+        .await
+        .expect("patch feed settings");
+}
+
+async fn get_body(app: &Router, uri: &str) -> String {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    String::from_utf8(body.to_vec()).expect("utf-8 body")
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn full_mode_embeds_absolute_html_and_an_image_enclosure(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    sqlx::query("UPDATE site_settings SET public_site_url = 'https://example.com/'")
+        .execute(state.db.pool())
+        .await
+        .expect("set public site url");
+    sqlx::query(
+        "INSERT INTO uploads (id, filename, content_type, size_bytes, checksum, stored_path) \
+         VALUES ($1, 'cat.png', 'image/png', 2048, 'cat-checksum', '2026/10/cat.png')",
+    )
+    .bind(Uuid::new_v4())
+    .execute(state.db.pool())
+    .await
+    .expect("insert upload");
+    let slug = rendered_post(&test_app, "Cats", BODY, None).await;
+    patch_feed_settings(&state, serde_json::json!({ "feed_content_mode": "full" })).await;
+    let app = test_app.router();
+    let post_url = format!("https://example.com/posts/{slug}");
+
+    let rss = get_body(&app, "/rss.xml").await;
+    assert!(
+        rss.contains(r#"xmlns:content="http://purl.org/rss/1.0/modules/content/""#),
+        "{rss}"
+    );
+    assert!(rss.contains("<content:encoded><![CDATA["), "{rss}");
+    assert!(
+        rss.contains(&format!(r##"href="{post_url}#setup""##)),
+        "{rss}"
+    );
+    assert!(rss.contains(r#"href="https://example.com/about""#), "{rss}");
+    assert!(
+        rss.contains(r#"src="https://example.com/uploads/2026/10/cat.png""#),
+        "{rss}"
+    );
+    assert!(
+        rss.contains(r#"<enclosure url="https://example.com/uploads/2026/10/cat.png" length="2048" type="image/png"/>"#),
+        "{rss}"
+    );
+    assert!(!rss.contains(r#"href="/about""#), "{rss}");
+
+    let atom = get_body(&app, "/atom.xml").await;
+    assert!(atom.contains(r#"<content type="html">"#), "{atom}");
+    assert!(
+        atom.contains(&format!("href=&quot;{post_url}#setup&quot;")),
+        "{atom}"
+    );
+    assert!(
+        atom.contains(r#"<link rel="enclosure" href="https://example.com/uploads/2026/10/cat.png" type="image/png" length="2048"/>"#),
+        "{atom}"
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn item_limit_bounds_the_feed_and_summary_mode_uses_the_summary(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    for title in ["First", "Second"] {
+        rendered_post(&test_app, title, "body", None).await;
+    }
+    rendered_post(&test_app, "Third", "body", Some("The **key** point")).await;
+    let app = test_app.router();
+
+    let rss = get_body(&app, "/rss.xml").await;
+    assert_eq!(rss.matches("<item>").count(), 3, "{rss}");
+    assert!(rss.contains("plain excerpt"), "{rss}");
+    assert!(!rss.contains("<content:encoded>"), "{rss}");
+
+    patch_feed_settings(
+        &state,
+        serde_json::json!({ "feed_content_mode": "summary", "feed_item_limit": 2 }),
+    )
+    .await;
+
+    let rss = get_body(&app, "/rss.xml").await;
+    assert_eq!(rss.matches("<item>").count(), 2, "{rss}");
+    assert!(rss.contains("<strong>key</strong>"), "{rss}");
+    let atom = get_body(&app, "/atom.xml").await;
+    assert_eq!(atom.matches("<entry>").count(), 2, "{atom}");
+    assert!(atom.contains(r#"<summary type="html">"#), "{atom}");
+}
@@ -31,12 +31,22 @@ async fn api_can_get_and_patch_settings(pool: PgPool) {
         public_site_url: None,
         global_toc_enabled: None,
         favicon_svg: None,
+        favicon_upload_id: None,
+        maintenance_enabled: None,
+        maintenance_html: None,
+        maintenance_retry_after_secs: None,
+        date_format: None,
+        feed_content_mode: None,
+        feed_item_limit: None,
+        homepage_mode: None,
+        homepage_page_id: None,
+        homepage_layout: None,
     };
 
     let _patched = handlers::patch_settings(
         State(state.clone()),
         Extension(principal.clone()),
-        Json(patch_payload),
+        StrictJson(patch_payload),
     )
     .await
     .expect("patch settings via handler");
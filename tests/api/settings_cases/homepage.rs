@@ -0,0 +1,174 @@
+use super::*;
+
+use soffio::application::api_keys::ApiPrincipal;
+use soffio::domain::types::PageStatus;
+
+fn patch(value: serde_json::Value) -> SettingsPatchRequest {
+    serde_json::from_value(value).expect("settings patch")
+}
+
+async fn create_page(
+    state: &ApiState,
+    principal: &ApiPrincipal,
+    slug: &str,
+    status: PageStatus,
+) -> Uuid {
+    let response = handlers::create_page(
+        State(state.clone()),
+        Extension(principal.clone()),
+        StrictJson(PageCreateRequest {
+            slug: Some(slug.into()),
+            title: format!("{slug} page"),
+            body_markdown: "# Welcome\n\nLanding copy.".into(),
+            status,
+            scheduled_at: None,
+            published_at: None,
+            archived_at: None,
+            template: None,
+        }),
+    )
+    .await
+    .expect("create page")
+    .into_response();
+    let (status, page) = response_json(response).await;
+    assert_eq!(status, StatusCode::CREATED, "{page}");
+    uuid_field(&page, "id")
+}
+
+async fn patch_rejection(
+    state: &ApiState,
+    principal: &ApiPrincipal,
+    value: serde_json::Value,
+) -> serde_json::Value {
+    let response = handlers::patch_settings(
+        State(state.clone()),
+        Extension(principal.clone()),
+        StrictJson(patch(value)),
+    )
+    .await
+    .err()
+    .expect("homepage patch is rejected")
+    .into_response();
+    let (status, body) = response_json(response).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    body
+}
+
+async fn get_body(app: &Router, uri: &str) -> String {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    String::from_utf8(body.to_vec()).expect("utf-8 body")
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn page_mode_serves_the_page_at_root_and_the_feed_at_posts(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let (state, token) = (test_app.api.clone(), test_app.token().to_string());
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let id = create_page(&state, &principal, "welcome", PageStatus::Published).await;
+
+    let (status, settings) = response_json(
+        handlers::patch_settings(
+            State(state.clone()),
+            Extension(principal.clone()),
+            StrictJson(patch(serde_json::json!({
+                "homepage_mode": "page",
+                "homepage_page_id": id,
+            }))),
+        )
+        .await
+        .expect("patch homepage")
+        .into_response(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{settings}");
+    assert_eq!(settings["homepage_mode"], "page");
+    assert_eq!(uuid_field(&settings, "homepage_page_id"), id);
+
+    let app = test_app.router();
+    let root = get_body(&app, "/").await;
+    assert!(root.contains("Landing copy."));
+    assert!(root.contains(r#"<link rel="canonical" href="http://localhost:3000/">"#));
+
+    let posts = get_body(&app, "/posts").await;
+    assert!(!posts.contains("Landing copy."));
+    assert!(posts.contains(r#"<link rel="canonical" href="http://localhost:3000/posts">"#));
+
+    let page = get_body(&app, "/welcome").await;
+    assert!(page.contains(r#"<link rel="canonical" href="http://localhost:3000/">"#));
+
+    let sitemap = get_body(&app, "/sitemap.xml").await;
+    assert!(sitemap.contains("<loc>http://localhost:3000/</loc>"));
+    assert!(sitemap.contains("<loc>http://localhost:3000/posts</loc>"));
+    assert!(!sitemap.contains("<loc>http://localhost:3000/welcome</loc>"));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn page_mode_requires_a_published_page(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let draft = create_page(&state, &principal, "draft", PageStatus::Draft).await;
+
+    for value in [
+        serde_json::json!({ "homepage_mode": "page" }),
+        serde_json::json!({ "homepage_mode": "page", "homepage_page_id": draft }),
+        serde_json::json!({ "homepage_mode": "page", "homepage_page_id": Uuid::new_v4() }),
+    ] {
+        let body = patch_rejection(&state, &principal, value).await;
+        assert_eq!(body["error"]["fields"][0]["field"], "homepage_page_id");
+    }
+
+    let settings = state.settings.load().await.expect("load settings");
+    assert_eq!(settings.homepage_mode, HomepageMode::Feed);
+    assert_eq!(settings.homepage_page_id, None);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn root_falls_back_to_the_feed_when_the_page_goes_away(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let (state, token) = (test_app.api.clone(), test_app.token().to_string());
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let id = create_page(&state, &principal, "welcome", PageStatus::Published).await;
+    state
+        .settings
+        .patch(
+            "test",
+            patch(serde_json::json!({
+                "homepage_mode": "page",
+                "homepage_page_id": id,
+            })),
+        )
+        .await
+        .expect("patch homepage");
+    let app = test_app.router();
+    assert!(get_body(&app, "/").await.contains("Landing copy."));
+
+    handlers::update_page_status(
+        State(state.clone()),
+        Extension(principal.clone()),
+        Path(id),
+        Json(PageStatusRequest {
+            status: PageStatus::Draft,
+            scheduled_at: None,
+            published_at: None,
+            archived_at: None,
+        }),
+    )
+    .await
+    .expect("unpublish page");
+    let root = get_body(&app, "/").await;
+    assert!(!root.contains("Landing copy."));
+    assert!(root.contains(r#"<link rel="canonical" href="http://localhost:3000/">"#));
+
+    handlers::delete_page(State(state.clone()), Extension(principal), Path(id))
+        .await
+        .expect("delete page");
+    assert!(!get_body(&app, "/").await.contains("Landing copy."));
+}
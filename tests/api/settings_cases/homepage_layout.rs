@@ -0,0 +1,140 @@
+use super::*;
+
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::feed::FeedFilter;
+use soffio::domain::types::PostStatus;
+use soffio::presentation::views::PageContext;
+use time::Duration;
+
+async fn create_post(state: &ApiState, slug: &str, published_at: OffsetDateTime) -> Uuid {
+    state
+        .posts
+        .create_post(
+            "test",
+            CreatePostCommand {
+                slug: Some(slug.into()),
+                title: slug.into(),
+                excerpt: "excerpt".into(),
+                body_markdown: "# body".into(),
+                summary_markdown: None,
+                status: PostStatus::Published,
+                pinned: false,
+                scheduled_at: None,
+                published_at: Some(published_at),
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("create post")
+        .id
+}
+
+async fn patch_settings(state: &ApiState, value: serde_json::Value) {
+    state
+        .settings
+        .patch(
+            "test",
+            serde_json::from_value(value).expect("settings patch"),
+        )
+        .await
+        .expect("patch settings");
+}
+
+async fn feed_page(app: &TestApp, cursor: Option<&str>) -> PageContext {
+    app.http
+        .feed
+        .page_context(FeedFilter::All, cursor)
+        .await
+        .expect("feed page")
+}
+
+fn featured_slug(page: &PageContext) -> Option<&str> {
+    page.featured.as_ref().map(|card| card.slug.as_str())
+}
+
+fn listed_slugs(page: &PageContext) -> Vec<&str> {
+    page.posts.iter().map(|card| card.slug.as_str()).collect()
+}
+
+async fn get_body(app: &Router, uri: &str) -> String {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    let body = axum::body::to_bytes(response.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    String::from_utf8(body.to_vec()).expect("utf-8 body")
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn magazine_layout_features_the_pinned_or_newest_post(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    let now = OffsetDateTime::now_utc();
+    create_post(&state, "newest", now).await;
+    create_post(&state, "older", now - Duration::days(1)).await;
+    let oldest = create_post(&state, "oldest", now - Duration::days(2)).await;
+
+    patch_settings(&state, serde_json::json!({ "homepage_layout": "magazine" })).await;
+
+    let page = feed_page(&test_app, None).await;
+    assert_eq!(page.layout, "magazine");
+    assert_eq!(featured_slug(&page), Some("newest"));
+    assert_eq!(listed_slugs(&page), ["older", "oldest"]);
+    assert_eq!(page.post_count, 3);
+
+    // A pinned post takes the spot from the newest one.
+    state
+        .posts
+        .update_pin_state("test", oldest, true, None)
+        .await
+        .expect("pin oldest");
+    let page = feed_page(&test_app, None).await;
+    assert_eq!(featured_slug(&page), Some("oldest"));
+    assert_eq!(listed_slugs(&page), ["newest", "older"]);
+
+    // Only the first page features a post.
+    patch_settings(&state, serde_json::json!({ "homepage_size": 2 })).await;
+    let first = feed_page(&test_app, None).await;
+    assert_eq!(featured_slug(&first), Some("oldest"));
+    assert_eq!(listed_slugs(&first), ["newest"]);
+    let cursor = first.next_cursor.expect("second page");
+    let second = feed_page(&test_app, Some(&cursor)).await;
+    assert_eq!(featured_slug(&second), None);
+    assert_eq!(listed_slugs(&second), ["older"]);
+
+    let root = get_body(&test_app.router(), "/").await;
+    assert!(root.contains(r#"aria-label="Featured post""#));
+    assert!(root.contains(r#"data-layout="magazine""#));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn list_and_grid_layouts_feature_nothing(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    let now = OffsetDateTime::now_utc();
+    create_post(&state, "newest", now).await;
+    create_post(&state, "older", now - Duration::days(1)).await;
+
+    let page = feed_page(&test_app, None).await;
+    assert_eq!(page.layout, "list");
+    assert_eq!(featured_slug(&page), None);
+    assert_eq!(listed_slugs(&page), ["newest", "older"]);
+    let root = get_body(&test_app.router(), "/").await;
+    assert!(!root.contains("data-layout=\"list\""));
+    assert!(!root.contains(r#"aria-label="Featured post""#));
+
+    patch_settings(&state, serde_json::json!({ "homepage_layout": "grid" })).await;
+    let page = feed_page(&test_app, None).await;
+    assert_eq!(page.layout, "grid");
+    assert_eq!(featured_slug(&page), None);
+    assert_eq!(listed_slugs(&page), ["newest", "older"]);
+    assert!(
+        get_body(&test_app.router(), "/")
+            .await
+            .contains(r#"data-layout="grid""#)
+    );
+}
@@ -0,0 +1,81 @@
+use super::*;
+
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER};
+
+fn get(uri: &str, token: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().method("GET").uri(uri);
+    if let Some(token) = token {
+        builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+    builder.body(Body::empty()).expect("build request")
+}
+
+async fn set_maintenance(app: &Router, token: &str, enabled: bool) {
+    let payload = serde_json::json!({
+        "maintenance_enabled": enabled,
+        "maintenance_html": "<h1>Back soon</h1>",
+        "maintenance_retry_after_secs": 120,
+    });
+    let request = Request::builder()
+        .method("PATCH")
+        .uri("/api/v1/site/settings")
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(payload.to_string()))
+        .expect("build request");
+
+    let response = app.clone().oneshot(request).await.expect("patch settings");
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn maintenance_mode_closes_public_site_but_not_api(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let token = test_app.token().to_string();
+    let app = test_app.router();
+
+    set_maintenance(&app, &token, true).await;
+
+    let page = app.clone().oneshot(get("/", None)).await.expect("index");
+    assert_eq!(page.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(page.headers()[RETRY_AFTER], "120");
+    let body = axum::body::to_bytes(page.into_body(), 1_048_576)
+        .await
+        .expect("read body");
+    assert_eq!(&body[..], b"<h1>Back soon</h1>");
+
+    let api = app
+        .clone()
+        .oneshot(get("/api/v1/site/settings", Some(&token)))
+        .await
+        .expect("api call");
+    assert_eq!(api.status(), StatusCode::OK);
+
+    let health = app
+        .clone()
+        .oneshot(get("/_health/db", None))
+        .await
+        .expect("health");
+    assert_eq!(health.status(), StatusCode::NO_CONTENT);
+
+    let ready = app
+        .clone()
+        .oneshot(get("/readyz", None))
+        .await
+        .expect("readiness");
+    assert_eq!(ready.status(), StatusCode::OK);
+    let (_, report) = response_json(ready).await;
+    assert_ne!(report["level"], "fail");
+    let names: Vec<&str> = report["checks"]
+        .as_array()
+        .expect("checks")
+        .iter()
+        .filter_map(|check| check["name"].as_str())
+        .collect();
+    assert_eq!(names, ["database", "migrations", "uploads"]);
+
+    set_maintenance(&app, &token, false).await;
+
+    let page = app.oneshot(get("/", None)).await.expect("index");
+    assert_eq!(page.status(), StatusCode::OK);
+}
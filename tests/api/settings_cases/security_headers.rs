@@ -0,0 +1,108 @@
+use super::*;
+
+use apalis::prelude::Data;
+use axum::http::HeaderMap;
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::render::{RenderPostJobPayload, process_render_post_job};
+use soffio::domain::types::PostStatus;
+
+const BODY: &str = "## Diagram\n\nbody";
+
+async fn published_post(state: &ApiState) -> String {
+    let post = state
+        .posts
+        .create_post(
+            "test",
+            CreatePostCommand {
+                slug: None,
+                title: "Headers".into(),
+                excerpt: "excerpt".into(),
+                body_markdown: BODY.into(),
+                summary_markdown: None,
+                status: PostStatus::Published,
+                pinned: false,
+                scheduled_at: None,
+                published_at: Some(OffsetDateTime::now_utc()),
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("create post via service");
+    post.slug
+}
+
+async fn get_headers(app: &Router, uri: &str) -> HeaderMap {
+    let request = Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("build request");
+    let response = app.clone().oneshot(request).await.expect("public request");
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    response.headers().clone()
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> &'a str {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_else(|| panic!("missing {name}"))
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn public_responses_carry_security_headers(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    let slug = published_post(&state).await;
+    let app = test_app.router();
+
+    for uri in [
+        format!("/posts/{slug}"),
+        "/rss.xml".to_string(),
+        "/static/public/app.css".to_string(),
+    ] {
+        let headers = get_headers(&app, &uri).await;
+        let policy = header(&headers, "content-security-policy");
+        assert!(policy.contains("default-src 'self'"), "{uri}: {policy}");
+        assert!(policy.contains("object-src 'none'"), "{uri}: {policy}");
+        assert_eq!(header(&headers, "x-content-type-options"), "nosniff");
+        assert_eq!(
+            header(&headers, "referrer-policy"),
+            "strict-origin-when-cross-origin"
+        );
+        assert_eq!(
+            header(&headers, "strict-transport-security"),
+            "max-age=31536000"
+        );
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn mermaid_posts_widen_the_policy_for_inline_styles(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    let slug = published_post(&state).await;
+    process_render_post_job(
+        RenderPostJobPayload::new(slug.clone(), BODY.into(), None),
+        Data::new(test_app.job_context()),
+    )
+    .await
+    .expect("render post");
+    // Rendering a real diagram needs the Mermaid CLI; the flag is what the
+    // handler reads.
+    sqlx::query("UPDATE post_sections SET contains_mermaid = TRUE")
+        .execute(state.db.pool())
+        .await
+        .expect("mark sections as containing mermaid");
+    let app = test_app.router();
+
+    let headers = get_headers(&app, &format!("/posts/{slug}")).await;
+    let policy = header(&headers, "content-security-policy");
+    assert!(
+        policy.contains("style-src-elem 'self' 'unsafe-inline'"),
+        "{policy}"
+    );
+
+    let headers = get_headers(&app, "/").await;
+    let policy = header(&headers, "content-security-policy");
+    assert!(!policy.contains("style-src-elem"), "{policy}");
+}
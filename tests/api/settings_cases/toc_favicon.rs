@@ -23,12 +23,22 @@ async fn api_settings_patch_includes_toc_and_favicon(pool: PgPool) {
         public_site_url: None,
         global_toc_enabled: Some(true),
         favicon_svg: Some("<svg></svg>".into()),
+        favicon_upload_id: None,
+        maintenance_enabled: None,
+        maintenance_html: None,
+        maintenance_retry_after_secs: None,
+        date_format: None,
+        feed_content_mode: None,
+        feed_item_limit: None,
+        homepage_mode: None,
+        homepage_page_id: None,
+        homepage_layout: None,
     };
 
     handlers::patch_settings(
         State(state.clone()),
         Extension(principal),
-        Json(patch_payload),
+        StrictJson(patch_payload),
     )
     .await
     .expect("patch settings toc/favicon");
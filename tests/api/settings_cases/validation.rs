@@ -0,0 +1,260 @@
+use super::*;
+
+use axum::extract::FromRequest;
+
+fn patch(value: serde_json::Value) -> SettingsPatchRequest {
+    serde_json::from_value(value).expect("settings patch")
+}
+
+fn rejected_fields(body: &serde_json::Value) -> Vec<&str> {
+    body["error"]["fields"]
+        .as_array()
+        .expect("field errors")
+        .iter()
+        .map(|error| error["field"].as_str().expect("field name"))
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn patch_rejects_invalid_fields_and_keeps_settings(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let before = state.settings.load().await.expect("load settings");
+
+    let response = handlers::patch_settings(
+        State(state.clone()),
+        Extension(principal),
+        StrictJson(patch(serde_json::json!({
+            "brand_title": "Renamed",
+            "homepage_size": -1,
+            "public_site_url": "ftp://example.com",
+            "timezone": "Mars/Olympus",
+            "favicon_svg": "<svg onload=\"alert(1)\"></svg>",
+            "meta_title": "t".repeat(121),
+        }))),
+    )
+    .await
+    .err()
+    .expect("invalid patch is rejected")
+    .into_response();
+    let (status, body) = response_json(response).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(string_field(&body["error"], "code"), "settings_error");
+    assert_eq!(
+        rejected_fields(&body),
+        [
+            "homepage_size",
+            "public_site_url",
+            "favicon_svg",
+            "timezone",
+            "meta_title"
+        ]
+    );
+
+    let after = state.settings.load().await.expect("reload settings");
+    assert_eq!(after.brand_title, before.brand_title);
+    assert_eq!(after.homepage_size, before.homepage_size);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn partial_patch_ignores_fields_it_does_not_set(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    // A value stored before validation existed must not block unrelated edits.
+    sqlx::query("UPDATE site_settings SET public_site_url = 'not a url'")
+        .execute(state.db.pool())
+        .await
+        .expect("store legacy value");
+
+    let (status, body) = response_json(
+        handlers::patch_settings(
+            State(state.clone()),
+            Extension(principal),
+            StrictJson(patch(serde_json::json!({ "brand_title": "Renamed" }))),
+        )
+        .await
+        .expect("partial patch succeeds")
+        .into_response(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(string_field(&body, "brand_title"), "Renamed");
+    assert_eq!(string_field(&body, "public_site_url"), "not a url");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn validate_reports_problems_without_persisting(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let before = state.settings.load().await.expect("load settings");
+
+    let (status, body) = response_json(
+        handlers::validate_settings(
+            Extension(principal.clone()),
+            StrictJson(patch(serde_json::json!({
+                "admin_page_size": 500,
+                "brand_href": "about",
+            }))),
+        )
+        .await
+        .expect("validate invalid patch")
+        .into_response(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["valid"], false);
+    let fields: Vec<&str> = body["errors"]
+        .as_array()
+        .expect("errors")
+        .iter()
+        .map(|error| error["field"].as_str().expect("field name"))
+        .collect();
+    assert_eq!(fields, ["admin_page_size", "brand_href"]);
+
+    let (status, body) = response_json(
+        handlers::validate_settings(
+            Extension(principal),
+            StrictJson(patch(serde_json::json!({
+                "brand_title": "Dry run",
+                "homepage_size": 12,
+            }))),
+        )
+        .await
+        .expect("validate valid patch")
+        .into_response(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["valid"], true);
+    assert!(body["errors"].as_array().expect("errors").is_empty());
+
+    let after = state.settings.load().await.expect("reload settings");
+    assert_eq!(after.brand_title, before.brand_title);
+    assert_eq!(after.homepage_size, before.homepage_size);
+    assert_eq!(after.admin_page_size, before.admin_page_size);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn validate_requires_settings_write_scope(pool: PgPool) {
+    let (state, _token) = build_state(pool).await;
+    let issued = state
+        .api_keys
+        .issue(IssueApiKeyCommand {
+            name: "reader".into(),
+            description: None,
+            scopes: vec![ApiScope::SettingsRead],
+            expires_in: None,
+            created_by: "tests".into(),
+        })
+        .await
+        .expect("issue key");
+    let principal = state
+        .api_keys
+        .authenticate(&issued.token)
+        .await
+        .expect("authenticate");
+
+    let status = handlers::validate_settings(
+        Extension(principal),
+        StrictJson(patch(serde_json::json!({}))),
+    )
+    .await
+    .err()
+    .expect("reader cannot validate")
+    .into_response()
+    .status();
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+async fn extract_patch(
+    body: serde_json::Value,
+) -> Result<StrictJson<SettingsPatchRequest>, axum::response::Response> {
+    let request = Request::builder()
+        .method("PATCH")
+        .uri("/api/v1/site/settings")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("request");
+    StrictJson::from_request(request, &())
+        .await
+        .map_err(IntoResponse::into_response)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn patch_rejects_unknown_fields_by_name(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+    let before = state.settings.load().await.expect("load settings");
+
+    let rejection = extract_patch(serde_json::json!({
+        "brand_titel": "Typo",
+        "homepage_size": 12,
+        "footer": "Unknown",
+    }))
+    .await
+    .expect_err("unknown fields are rejected");
+    let (status, body) = response_json(rejection).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(string_field(&body["error"], "code"), "invalid_input");
+    assert_eq!(
+        string_field(&body["error"], "hint"),
+        "unknown fields: brand_titel, footer"
+    );
+    assert_eq!(rejected_fields(&body), ["brand_titel", "footer"]);
+
+    // Known fields keep their partial-update semantics.
+    let payload = extract_patch(serde_json::json!({ "homepage_size": 12 }))
+        .await
+        .expect("known fields are accepted");
+    let (status, body) = response_json(
+        handlers::patch_settings(State(state.clone()), Extension(principal), payload)
+            .await
+            .expect("partial patch succeeds")
+            .into_response(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["homepage_size"], 12);
+    assert_eq!(string_field(&body, "brand_title"), before.brand_title);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn patch_stores_timezone_in_canonical_form(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+
+    for (value, canonical) in [
+        ("America/New_York", "America/New_York"),
+        ("europe/berlin", "Europe/Berlin"),
+    ] {
+        handlers::patch_settings(
+            State(state.clone()),
+            Extension(principal.clone()),
+            StrictJson(patch(serde_json::json!({ "timezone": value }))),
+        )
+        .await
+        .expect("known timezone is accepted");
+        let stored = state.settings.load().await.expect("reload settings");
+        assert_eq!(stored.timezone.name(), canonical, "{value}");
+    }
+
+    let response = handlers::patch_settings(
+        State(state.clone()),
+        Extension(principal),
+        StrictJson(patch(serde_json::json!({ "timezone": "Mars/Phobos" }))),
+    )
+    .await
+    .err()
+    .expect("unknown timezone is rejected")
+    .into_response();
+    let (status, body) = response_json(response).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(rejected_fields(&body), ["timezone"]);
+
+    let stored = state.settings.load().await.expect("reload settings");
+    assert_eq!(stored.timezone.name(), "Europe/Berlin");
+}
@@ -0,0 +1,75 @@
+use super::*;
+
+use std::path::PathBuf;
+
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::repos::SettingsRepo;
+use soffio::domain::types::PostStatus;
+use soffio::infra::http::export_static_site;
+use url::Url;
+
+async fn create_post(state: &ApiState, title: &str, status: PostStatus) -> String {
+    let published_at = (status == PostStatus::Published).then(OffsetDateTime::now_utc);
+    state
+        .posts
+        .create_post(
+            "test",
+            CreatePostCommand {
+                slug: None,
+                title: title.into(),
+                excerpt: "excerpt".into(),
+                body_markdown: "# body".into(),
+                summary_markdown: None,
+                status,
+                pinned: false,
+                scheduled_at: None,
+                published_at,
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("create post via service")
+        .slug
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn static_export_writes_published_posts_feeds_and_sitemap(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let state = test_app.api.clone();
+    let first = create_post(&state, "First static post", PostStatus::Published).await;
+    let second = create_post(&state, "Second static post", PostStatus::Published).await;
+    let draft = create_post(&state, "Unfinished draft", PostStatus::Draft).await;
+
+    let settings = state.db.load_site_settings().await.expect("site settings");
+    let site_url = Url::parse(&settings.public_site_url).expect("site url");
+    let dir = tempfile::tempdir().expect("output dir");
+
+    let report = export_static_site(test_app.router(), &site_url, dir.path())
+        .await
+        .expect("export static site");
+
+    assert_eq!(report.written.first(), Some(&PathBuf::from("index.html")));
+    for slug in [&first, &second] {
+        let page = dir.path().join("posts").join(slug).join("index.html");
+        let html = std::fs::read_to_string(&page).expect("post page");
+        assert!(html.contains("static post"), "{}", page.display());
+    }
+    assert!(!dir.path().join("posts").join(&draft).exists());
+    assert!(dir.path().join("rss.xml").is_file());
+    assert!(dir.path().join("atom.xml").is_file());
+
+    let sitemap = std::fs::read_to_string(dir.path().join("sitemap.xml")).expect("sitemap");
+    assert!(sitemap.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(sitemap.trim_end().ends_with("</urlset>"));
+    assert_eq!(
+        sitemap.matches("<url>").count(),
+        sitemap.matches("</url>").count()
+    );
+    for slug in [&first, &second] {
+        assert!(
+            sitemap.contains(&format!("/posts/{slug}</loc>")),
+            "{sitemap}"
+        );
+    }
+    assert!(!sitemap.contains(&draft), "{sitemap}");
+}
@@ -11,3 +11,9 @@ mod delete;
 
 #[path = "tags_cases/partial.rs"]
 mod partial;
+
+#[path = "tags_cases/assign.rs"]
+mod assign;
+
+#[path = "tags_cases/aggregations.rs"]
+mod aggregations;
@@ -0,0 +1,130 @@
+use super::*;
+
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::admin::tags::CreateTagCommand;
+use soffio::application::repos::TagAssignmentMode;
+use soffio::domain::types::PostStatus;
+
+async fn create_post(state: &ApiState, slug: &str, status: PostStatus) -> Uuid {
+    let published = status == PostStatus::Published;
+    state
+        .posts
+        .create_post(
+            "test",
+            CreatePostCommand {
+                slug: Some(slug.into()),
+                title: slug.into(),
+                excerpt: "excerpt".into(),
+                body_markdown: "# body".into(),
+                summary_markdown: None,
+                status,
+                pinned: false,
+                scheduled_at: None,
+                published_at: published.then(OffsetDateTime::now_utc),
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("create post")
+        .id
+}
+
+async fn create_tag(state: &ApiState, name: &str, pinned: bool, posts: &[Uuid]) {
+    let id = state
+        .tags
+        .create_tag(
+            "test",
+            CreateTagCommand {
+                name: name.into(),
+                description: None,
+                pinned,
+            },
+        )
+        .await
+        .expect("create tag")
+        .id;
+    if !posts.is_empty() {
+        state
+            .tags
+            .assign_tag("test", id, posts, TagAssignmentMode::Add)
+            .await
+            .expect("assign tag");
+    }
+}
+
+async fn aggregations(state: &ApiState, token: &str) -> serde_json::Value {
+    let principal = state.api_keys.authenticate(token).await.unwrap();
+    let (status, body) = response_json(
+        handlers::tag_aggregations(State(state.clone()), Extension(principal))
+            .await
+            .expect("tag aggregations via handler"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    body
+}
+
+fn counts(body: &serde_json::Value) -> Vec<(String, i64)> {
+    body["tags"]
+        .as_array()
+        .expect("tags array")
+        .iter()
+        .map(|tag| {
+            (
+                string_field(tag, "slug").to_string(),
+                tag["count"].as_i64().expect("count"),
+            )
+        })
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn aggregations_count_published_posts_within_the_filter_limit(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+
+    let first = create_post(&state, "first", PostStatus::Published).await;
+    let second = create_post(&state, "second", PostStatus::Published).await;
+    let third = create_post(&state, "third", PostStatus::Published).await;
+    let draft = create_post(&state, "draft", PostStatus::Draft).await;
+
+    create_tag(&state, "Featured", true, &[first]).await;
+    create_tag(&state, "Busy", false, &[first, second, third, draft]).await;
+    create_tag(&state, "Middle", false, &[second, third]).await;
+    create_tag(&state, "Rare", false, &[third, draft]).await;
+    create_tag(&state, "Unused", false, &[]).await;
+
+    let body = aggregations(&state, &token).await;
+    assert_eq!(
+        counts(&body),
+        [
+            ("featured".to_string(), 1),
+            ("busy".to_string(), 3),
+            ("middle".to_string(), 2),
+            ("rare".to_string(), 1),
+            ("unused".to_string(), 0),
+        ]
+    );
+    assert_eq!(body["tags"][0]["pinned"], true);
+
+    // Pinned tags are always listed; the limit caps the rest.
+    state
+        .settings
+        .patch(
+            "test",
+            serde_json::from_value(serde_json::json!({ "tag_filter_limit": 2 }))
+                .expect("settings patch"),
+        )
+        .await
+        .expect("patch settings");
+
+    let body = aggregations(&state, &token).await;
+    assert_eq!(body["tag_filter_limit"], 2);
+    assert_eq!(
+        counts(&body),
+        [
+            ("featured".to_string(), 1),
+            ("busy".to_string(), 3),
+            ("middle".to_string(), 2),
+        ]
+    );
+}
@@ -0,0 +1,211 @@
+use super::*;
+
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::admin::tags::CreateTagCommand;
+use soffio::application::api_keys::ApiPrincipal;
+use soffio::application::repos::TagsRepo;
+use soffio::domain::types::PostStatus;
+
+async fn create_post(state: &ApiState, slug: &str) -> Uuid {
+    state
+        .posts
+        .create_post(
+            "test",
+            CreatePostCommand {
+                slug: Some(slug.into()),
+                title: slug.into(),
+                excerpt: "excerpt".into(),
+                body_markdown: "# body".into(),
+                summary_markdown: None,
+                status: PostStatus::Published,
+                pinned: false,
+                scheduled_at: None,
+                published_at: Some(OffsetDateTime::now_utc()),
+                archived_at: None,
+            },
+        )
+        .await
+        .expect("create post")
+        .id
+}
+
+async fn create_tag(state: &ApiState, name: &str) -> Uuid {
+    state
+        .tags
+        .create_tag(
+            "test",
+            CreateTagCommand {
+                name: name.into(),
+                description: None,
+                pinned: false,
+            },
+        )
+        .await
+        .expect("create tag")
+        .id
+}
+
+async fn assign(
+    state: &ApiState,
+    principal: &ApiPrincipal,
+    tag_id: Uuid,
+    post_ids: Vec<Uuid>,
+    mode: TagAssignMode,
+) -> Vec<(Uuid, String)> {
+    let (status, body) = response_json(
+        handlers::assign_tag(
+            State(state.clone()),
+            Extension(principal.clone()),
+            Path(tag_id),
+            Json(TagAssignRequest { post_ids, mode }),
+        )
+        .await
+        .expect("assign tag via handler"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(uuid_field(&body, "tag_id"), tag_id);
+
+    body["results"]
+        .as_array()
+        .expect("results array")
+        .iter()
+        .map(|result| {
+            (
+                uuid_field(result, "post_id"),
+                string_field(result, "status").to_string(),
+            )
+        })
+        .collect()
+}
+
+async fn has_tag(state: &ApiState, post_id: Uuid, tag_id: Uuid) -> bool {
+    state
+        .db
+        .list_for_post(post_id)
+        .await
+        .expect("list tags")
+        .iter()
+        .any(|tag| tag.id == tag_id)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn assign_adds_the_tag_to_every_listed_post(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+
+    let tag = create_tag(&state, "bulk").await;
+    let first = create_post(&state, "first").await;
+    let second = create_post(&state, "second").await;
+
+    let results = assign(
+        &state,
+        &principal,
+        tag,
+        vec![first, second, first],
+        TagAssignMode::Add,
+    )
+    .await;
+    assert_eq!(
+        results,
+        [(first, "added".to_string()), (second, "added".to_string())]
+    );
+    assert!(has_tag(&state, first, tag).await);
+    assert!(has_tag(&state, second, tag).await);
+
+    let again = assign(&state, &principal, tag, vec![first], TagAssignMode::Add).await;
+    assert_eq!(again, [(first, "unchanged".to_string())]);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn assign_removes_the_tag_from_every_listed_post(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+
+    let tag = create_tag(&state, "bulk").await;
+    let tagged = create_post(&state, "tagged").await;
+    let untagged = create_post(&state, "untagged").await;
+    assign(&state, &principal, tag, vec![tagged], TagAssignMode::Add).await;
+
+    let results = assign(
+        &state,
+        &principal,
+        tag,
+        vec![tagged, untagged],
+        TagAssignMode::Remove,
+    )
+    .await;
+    assert_eq!(
+        results,
+        [
+            (tagged, "removed".to_string()),
+            (untagged, "unchanged".to_string())
+        ]
+    );
+    assert!(!has_tag(&state, tagged, tag).await);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn assign_reports_unknown_and_trashed_posts_without_failing(pool: PgPool) {
+    let (state, token) = build_state(pool).await;
+    let principal = state.api_keys.authenticate(&token).await.unwrap();
+
+    let tag = create_tag(&state, "bulk").await;
+    let live = create_post(&state, "live").await;
+    let trashed = create_post(&state, "trashed").await;
+    state
+        .posts
+        .delete_post("test", trashed, "trashed")
+        .await
+        .expect("trash post");
+    let missing = Uuid::new_v4();
+
+    let results = assign(
+        &state,
+        &principal,
+        tag,
+        vec![missing, live, trashed],
+        TagAssignMode::Add,
+    )
+    .await;
+    assert_eq!(
+        results,
+        [
+            (missing, "not_found".to_string()),
+            (live, "added".to_string()),
+            (trashed, "not_found".to_string()),
+        ]
+    );
+    assert!(has_tag(&state, live, tag).await);
+    assert!(!has_tag(&state, trashed, tag).await);
+
+    let unknown_tag = handlers::assign_tag(
+        State(state.clone()),
+        Extension(principal.clone()),
+        Path(Uuid::new_v4()),
+        Json(TagAssignRequest {
+            post_ids: vec![live],
+            mode: TagAssignMode::Add,
+        }),
+    )
+    .await
+    .err()
+    .expect("unknown tag is rejected")
+    .into_response();
+    assert_eq!(unknown_tag.status(), StatusCode::NOT_FOUND);
+
+    let empty = handlers::assign_tag(
+        State(state.clone()),
+        Extension(principal),
+        Path(tag),
+        Json(TagAssignRequest {
+            post_ids: Vec::new(),
+            mode: TagAssignMode::Add,
+        }),
+    )
+    .await
+    .err()
+    .expect("empty post ids are rejected")
+    .into_response();
+    assert_eq!(empty.status(), StatusCode::BAD_REQUEST);
+}
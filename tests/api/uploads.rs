@@ -2,3 +2,9 @@ use super::*;
 
 #[path = "uploads_cases/list.rs"]
 mod list;
+
+#[path = "uploads_cases/upload.rs"]
+mod upload;
+
+#[path = "uploads_cases/verify.rs"]
+mod verify;
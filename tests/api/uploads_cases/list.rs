@@ -16,6 +16,8 @@ async fn api_can_list_uploads(pool: PgPool) {
         stored_path: "uploads/demo.txt".into(),
         metadata: soffio::domain::uploads::UploadMetadata::default(),
         created_at: OffsetDateTime::now_utc(),
+        last_verified_at: None,
+        checksum_mismatch: false,
     };
     state
         .uploads
@@ -43,10 +45,34 @@ async fn api_can_list_uploads(pool: PgPool) {
             search: None,
             content_type: None,
             month: None,
+            checksum: None,
             cursor: None,
             limit: Some(10),
         }),
     )
     .await
     .expect("list uploads via handler");
+
+    for (checksum, expected) in [("ABCD", 1), ("ffff", 0)] {
+        let (status, listed) = response_json(
+            handlers::list_uploads(
+                State(state.clone()),
+                Extension(principal.clone()),
+                Query(handlers::UploadListQuery {
+                    search: None,
+                    content_type: None,
+                    month: None,
+                    checksum: Some(checksum.into()),
+                    cursor: None,
+                    limit: Some(10),
+                }),
+            )
+            .await
+            .expect("list uploads by checksum"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let items = listed["items"].as_array().expect("items");
+        assert_eq!(items.len(), expected, "{checksum}: {listed}");
+    }
 }
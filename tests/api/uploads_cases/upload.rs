@@ -0,0 +1,132 @@
+use super::*;
+
+use std::sync::atomic::Ordering;
+
+// ============ Upload Limits and Content Types ============
+
+const BOUNDARY: &str = "soffio-test-boundary";
+
+const PNG_HEAD: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+const PDF_HEAD: &[u8] = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n";
+
+fn multipart_body(filename: &str, content_type: &str, contents: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\nContent-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(contents);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+/// `content_length` off leaves the limit to the multipart stream itself.
+fn upload_request(token: &str, body: Vec<u8>, content_length: bool) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/api/v1/uploads")
+        .header("authorization", format!("Bearer {token}"))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        );
+    if content_length {
+        builder = builder.header("content-length", body.len());
+    }
+    builder.body(Body::from(body)).expect("build request")
+}
+
+async fn listed_uploads(app: &Router, token: &str) -> usize {
+    let request = Request::builder()
+        .uri("/api/v1/uploads")
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .expect("build request");
+    let (status, listed) = response_json(app.clone().oneshot(request).await.expect("list")).await;
+    assert_eq!(status, StatusCode::OK);
+    listed["items"].as_array().expect("items").len()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn upload_over_the_limit_answers_413_in_the_api_shape(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let (state, token) = (test_app.api.clone(), test_app.token().to_string());
+    state.upload_limit.store(1024, Ordering::Relaxed);
+    let app = test_app.router();
+
+    let mut contents = PNG_HEAD.to_vec();
+    contents.resize(4 * 1024, 0);
+
+    for content_length in [true, false] {
+        let body = multipart_body("big.png", "image/png", &contents);
+        let response = app
+            .clone()
+            .oneshot(upload_request(&token, body, content_length))
+            .await
+            .expect("upload");
+        let (status, body) = response_json(response).await;
+        assert_eq!(
+            status,
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "content-length {content_length}: {body}"
+        );
+        assert_eq!(body["error"]["code"], "payload_too_large");
+    }
+
+    assert_eq!(listed_uploads(&app, &token).await, 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn upload_with_spoofed_content_type_is_rejected(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let token = test_app.token().to_string();
+    let app = test_app.router();
+
+    let body = multipart_body("cat.png", "image/png", PDF_HEAD);
+    let response = app
+        .clone()
+        .oneshot(upload_request(&token, body, true))
+        .await
+        .expect("upload");
+    let (status, body) = response_json(response).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE, "{body}");
+    assert_eq!(body["error"]["code"], "upload_error");
+    assert_eq!(
+        body["error"]["hint"],
+        "declared as image/png but the contents are application/pdf"
+    );
+    assert_eq!(listed_uploads(&app, &token).await, 0);
+
+    // An undeclared type is not a mismatch; the sniffed one is recorded.
+    let body = multipart_body("cat.png", "application/octet-stream", PNG_HEAD);
+    let response = app
+        .clone()
+        .oneshot(upload_request(&token, body, true))
+        .await
+        .expect("upload");
+    let (status, body) = response_json(response).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(string_field(&body, "content_type"), "image/png");
+    assert_eq!(listed_uploads(&app, &token).await, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn unrecognised_contents_declared_as_an_image_are_rejected(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let token = test_app.token().to_string();
+    let app = test_app.router();
+
+    let body = multipart_body("cat.png", "image/png", b"<script>alert(1)</script>");
+    let response = app
+        .clone()
+        .oneshot(upload_request(&token, body, true))
+        .await
+        .expect("upload");
+    let (status, body) = response_json(response).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE, "{body}");
+    assert_eq!(body["error"]["code"], "upload_error");
+    assert_eq!(
+        body["error"]["hint"],
+        "declared as image/png but the contents are application/octet-stream"
+    );
+    assert_eq!(listed_uploads(&app, &token).await, 0);
+}
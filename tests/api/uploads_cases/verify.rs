@@ -0,0 +1,167 @@
+use super::*;
+
+use bytes::Bytes;
+
+// ============ Checksum Verification ============
+
+/// Store `contents` and register it as a new upload.
+async fn stored_upload(state: &ApiState, contents: &'static [u8]) -> UploadRecord {
+    let stored = state
+        .upload_storage
+        .store("report.txt", Bytes::from_static(contents))
+        .await
+        .expect("store upload");
+    let record = UploadRecord {
+        id: Uuid::new_v4(),
+        filename: "report.txt".to_string(),
+        content_type: "text/plain".to_string(),
+        size_bytes: stored.size_bytes,
+        checksum: stored.checksum,
+        stored_path: stored.stored_path,
+        metadata: Default::default(),
+        created_at: OffsetDateTime::now_utc(),
+        last_verified_at: None,
+        checksum_mismatch: false,
+    };
+    state
+        .uploads
+        .register_upload("tests", record.clone())
+        .await
+        .expect("register upload");
+    record
+}
+
+async fn verify(app: &Router, token: &str, id: Uuid) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/v1/uploads/{id}/verify"))
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .expect("build request");
+    response_json(app.clone().oneshot(request).await.expect("verify")).await
+}
+
+async fn listed_with_checksum(app: &Router, token: &str, checksum: &str) -> Vec<String> {
+    let request = Request::builder()
+        .uri(format!("/api/v1/uploads?checksum={checksum}"))
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .expect("build request");
+    let (status, listed) = response_json(app.clone().oneshot(request).await.expect("list")).await;
+    assert_eq!(status, StatusCode::OK);
+    listed["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .map(|item| string_field(item, "id").to_string())
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn verify_detects_a_corrupted_file(pool: PgPool) {
+    let test_app = TestApp::new(pool).await;
+    let (state, token) = (test_app.api.clone(), test_app.token().to_string());
+    let app = test_app.router();
+    let record = stored_upload(&state, b"quarterly report, final draft\n").await;
+
+    let (status, body) = verify(&app, &token, record.id).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["matches"], true);
+    assert_eq!(string_field(&body, "actual_checksum"), record.checksum);
+
+    let path = state
+        .upload_storage
+        .absolute_path(&record.stored_path)
+        .expect("stored path");
+    tokio::fs::write(&path, b"quarterly report, tampered\n")
+        .await
+        .expect("corrupt stored file");
+
+    let (status, body) = verify(&app, &token, record.id).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["matches"], false);
+    assert_eq!(string_field(&body, "expected_checksum"), record.checksum);
+    assert_ne!(string_field(&body, "actual_checksum"), record.checksum);
+
+    let flagged = state
+        .uploads
+        .find_upload(record.id)
+        .await
+        .expect("find upload")
+        .expect("upload exists");
+    assert!(flagged.checksum_mismatch);
+    assert!(flagged.last_verified_at.is_some());
+
+    // The corrupted upload no longer answers checksum lookups, so the intact
+    // file can be uploaded again.
+    assert!(
+        listed_with_checksum(&app, &token, &record.checksum)
+            .await
+            .is_empty()
+    );
+    let replacement = stored_upload(&state, b"quarterly report, final draft\n").await;
+    assert_eq!(
+        listed_with_checksum(&app, &token, &record.checksum).await,
+        vec![replacement.id.to_string()]
+    );
+
+    let summary = state
+        .uploads
+        .integrity_summary()
+        .await
+        .expect("integrity summary");
+    assert_eq!(summary.mismatched, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn verify_all_reports_corrupted_and_missing_files(pool: PgPool) {
+    let (state, _token) = build_state(pool).await;
+
+    let intact = stored_upload(&state, b"first report\n").await;
+    let corrupted = stored_upload(&state, b"second report\n").await;
+    let missing = stored_upload(&state, b"third report\n").await;
+    let storage = &state.upload_storage;
+    tokio::fs::write(
+        storage.absolute_path(&corrupted.stored_path).expect("path"),
+        b"bit rot",
+    )
+    .await
+    .expect("corrupt stored file");
+    storage
+        .delete(&missing.stored_path)
+        .await
+        .expect("delete stored file");
+
+    let report = state
+        .uploads
+        .verify_all("tests", storage, 2)
+        .await
+        .expect("verify all");
+
+    assert_eq!(report.checked, 3);
+    assert_eq!(report.matched, 1);
+    assert_eq!(report.mismatched, 2);
+    assert_eq!(report.missing, 1);
+    let mut expected = vec![corrupted.id, missing.id];
+    expected.sort_unstable();
+    assert_eq!(report.mismatched_ids, expected);
+
+    let intact = state
+        .uploads
+        .find_upload(intact.id)
+        .await
+        .expect("find upload")
+        .expect("upload exists");
+    assert!(!intact.checksum_mismatch);
+    assert!(intact.last_verified_at.is_some());
+
+    let audit = state.audit.list_recent(10).await.expect("list audit");
+    let entry = audit
+        .iter()
+        .find(|entry| entry.action == "upload.verify_all")
+        .expect("integrity report audited");
+    let payload: serde_json::Value =
+        serde_json::from_str(entry.payload_text.as_deref().expect("payload")).expect("json");
+    assert_eq!(payload["mismatched"], 2);
+    assert_eq!(payload["mismatched_ids"].as_array().map(Vec::len), Some(2));
+}
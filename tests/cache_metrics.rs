@@ -16,7 +16,7 @@ use soffio::cache::{
     response_cache_layer,
 };
 use soffio::domain::entities::PostRecord;
-use soffio::domain::types::PostStatus;
+use soffio::domain::types::{PostStatus, RenderStatus};
 use soffio::infra::db::PostgresRepositories;
 use sqlx::PgPool;
 use time::OffsetDateTime;
@@ -29,14 +29,21 @@ fn sample_post(id: Uuid, slug: &str) -> PostRecord {
         slug: slug.to_string(),
         title: "Metrics Test Post".to_string(),
         excerpt: "".to_string(),
+        excerpt_generated: false,
         body_markdown: "".to_string(),
         status: PostStatus::Published,
         pinned: false,
+        pin_order: 0,
         scheduled_at: None,
         published_at: Some(OffsetDateTime::now_utc()),
         archived_at: None,
         summary_markdown: None,
         summary_html: None,
+        lang: None,
+        translation_group: None,
+        render_status: RenderStatus::Ok,
+        render_error: None,
+        render_failed_at: None,
         created_at: OffsetDateTime::now_utc(),
         updated_at: OffsetDateTime::now_utc(),
     }
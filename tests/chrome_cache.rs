@@ -0,0 +1,195 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use soffio::application::admin::chrome::AdminChromeService;
+use soffio::application::chrome::ChromeService;
+use soffio::application::pagination::{CursorPage, NavigationCursor, PageRequest};
+use soffio::application::repos::{NavigationQueryFilter, NavigationRepo, RepoError, SettingsRepo};
+use soffio::cache::{
+    CacheConfig, CacheConsumer, CacheRegistry, CacheTrigger, EventQueue, L0Store, L1Store,
+};
+use soffio::domain::entities::{NavigationItemRecord, SiteSettingsRecord};
+use soffio::infra::db::PostgresRepositories;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const LOADS: usize = 20;
+
+/// Settings and navigation repos that count the reads the chrome makes.
+struct CountingRepos {
+    inner: Arc<PostgresRepositories>,
+    settings_loads: AtomicUsize,
+    navigation_loads: AtomicUsize,
+}
+
+impl CountingRepos {
+    fn new(inner: Arc<PostgresRepositories>) -> Arc<Self> {
+        Arc::new(Self {
+            inner,
+            settings_loads: AtomicUsize::new(0),
+            navigation_loads: AtomicUsize::new(0),
+        })
+    }
+
+    fn settings_loads(&self) -> usize {
+        self.settings_loads.load(Ordering::SeqCst)
+    }
+
+    fn navigation_loads(&self) -> usize {
+        self.navigation_loads.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl SettingsRepo for CountingRepos {
+    async fn load_site_settings(&self) -> Result<SiteSettingsRecord, RepoError> {
+        self.settings_loads.fetch_add(1, Ordering::SeqCst);
+        self.inner.load_site_settings().await
+    }
+
+    async fn upsert_site_settings(&self, settings: SiteSettingsRecord) -> Result<(), RepoError> {
+        self.inner.upsert_site_settings(settings).await
+    }
+}
+
+#[async_trait]
+impl NavigationRepo for CountingRepos {
+    async fn list_navigation(
+        &self,
+        visibility: Option<bool>,
+        filter: &NavigationQueryFilter,
+        page: PageRequest<NavigationCursor>,
+    ) -> Result<CursorPage<NavigationItemRecord>, RepoError> {
+        self.navigation_loads.fetch_add(1, Ordering::SeqCst);
+        NavigationRepo::list_navigation(self.inner.as_ref(), visibility, filter, page).await
+    }
+
+    async fn count_navigation(
+        &self,
+        visibility: Option<bool>,
+        filter: &NavigationQueryFilter,
+    ) -> Result<u64, RepoError> {
+        NavigationRepo::count_navigation(self.inner.as_ref(), visibility, filter).await
+    }
+
+    async fn count_external_navigation(
+        &self,
+        visibility: Option<bool>,
+        filter: &NavigationQueryFilter,
+    ) -> Result<u64, RepoError> {
+        NavigationRepo::count_external_navigation(self.inner.as_ref(), visibility, filter).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<NavigationItemRecord>, RepoError> {
+        NavigationRepo::find_by_id(self.inner.as_ref(), id).await
+    }
+
+    async fn list_visible_links_to_page(
+        &self,
+        page_id: Uuid,
+    ) -> Result<Vec<NavigationItemRecord>, RepoError> {
+        NavigationRepo::list_visible_links_to_page(self.inner.as_ref(), page_id).await
+    }
+}
+
+/// A cache trigger whose consumer invalidates `l0`, as in `serve`.
+fn cache_trigger(repos: Arc<PostgresRepositories>) -> (CacheTrigger, Arc<L0Store>) {
+    let config = CacheConfig::default();
+    let l0 = Arc::new(L0Store::new(&config));
+    let queue = Arc::new(EventQueue::new_with_limit(config.max_event_queue_len));
+    let consumer = Arc::new(CacheConsumer::new(
+        config.clone(),
+        l0.clone(),
+        Arc::new(L1Store::new(&config)),
+        Arc::new(CacheRegistry::new()),
+        queue.clone(),
+        repos,
+    ));
+    (CacheTrigger::new(config, queue, consumer), l0)
+}
+
+fn chrome_service(repos: &Arc<CountingRepos>, l0: Option<Arc<L0Store>>) -> ChromeService {
+    ChromeService::new(repos.clone(), repos.clone(), repos.inner.clone(), l0)
+        .with_cursor_keys(repos.inner.cursor_keys().clone())
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn uncached_chrome_reads_settings_and_navigation_on_every_load(pool: PgPool) {
+    let repos = CountingRepos::new(Arc::new(PostgresRepositories::new(pool)));
+    let chrome = chrome_service(&repos, None);
+
+    for _ in 0..LOADS {
+        chrome.load().await.expect("load chrome");
+    }
+
+    assert_eq!(repos.settings_loads(), LOADS);
+    assert_eq!(repos.navigation_loads(), LOADS);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn cached_chrome_reads_once_between_invalidations(pool: PgPool) {
+    let postgres = Arc::new(PostgresRepositories::new(pool));
+    let repos = CountingRepos::new(postgres.clone());
+    let (trigger, l0) = cache_trigger(postgres.clone());
+    let chrome = chrome_service(&repos, Some(l0));
+
+    for _ in 0..LOADS {
+        chrome.load().await.expect("load chrome");
+    }
+    assert_eq!(repos.settings_loads(), 1);
+    assert_eq!(repos.navigation_loads(), 1);
+
+    // A settings write is picked up on the next load, then cached again.
+    let mut settings = postgres.load_site_settings().await.expect("load settings");
+    settings.brand_title = "Renamed".to_string();
+    postgres
+        .upsert_site_settings(settings)
+        .await
+        .expect("save settings");
+    trigger.site_settings_updated().await;
+
+    for _ in 0..LOADS {
+        let loaded = chrome.load().await.expect("load chrome");
+        assert_eq!(loaded.brand.title, "Renamed");
+    }
+    assert_eq!(repos.settings_loads(), 2);
+    assert_eq!(repos.navigation_loads(), 1);
+
+    trigger.navigation_updated().await;
+    for _ in 0..LOADS {
+        chrome.load().await.expect("load chrome");
+    }
+    assert_eq!(repos.settings_loads(), 2);
+    assert_eq!(repos.navigation_loads(), 2);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn admin_chrome_shares_cached_settings_across_active_paths(pool: PgPool) {
+    let postgres = Arc::new(PostgresRepositories::new(pool));
+    let repos = CountingRepos::new(postgres.clone());
+    let (trigger, l0) = cache_trigger(postgres);
+    let chrome = AdminChromeService::new(repos.clone()).with_cache(Some(l0));
+
+    for _ in 0..LOADS {
+        for (path, label) in [("/posts", "Posts"), ("/tags", "Tags")] {
+            let loaded = chrome.load(path).await.expect("load admin chrome");
+            let active: Vec<&str> = loaded
+                .navigation
+                .items
+                .iter()
+                .filter(|item| item.is_active)
+                .map(|item| item.label.as_str())
+                .collect();
+            assert_eq!(active, [label]);
+            assert!(loaded.meta.title.ends_with(label), "{}", loaded.meta.title);
+        }
+    }
+    assert_eq!(repos.settings_loads(), 1);
+
+    trigger.site_settings_updated().await;
+    for _ in 0..LOADS {
+        chrome.load("/").await.expect("load admin chrome");
+    }
+    assert_eq!(repos.settings_loads(), 2);
+}
@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+
+use soffio::application::repos::{PageQueryFilter, PostQueryFilter};
+use soffio::domain::types::{PageStatus, PostStatus};
+use soffio::infra::db::PostgresRepositories;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn seed_post(
+    pool: &PgPool,
+    slug: &str,
+    status: &str,
+    month: &str,
+    tag: Option<&str>,
+) -> Uuid {
+    let id = Uuid::new_v4();
+    let at = format!("{month}-15T12:00:00Z");
+    sqlx::query(
+        r#"
+        INSERT INTO posts (id, slug, title, excerpt, body_markdown, status, published_at, updated_at)
+        VALUES (
+            $1, $2, $2, '', '', $3::post_status,
+            CASE WHEN $3 = 'published' THEN $4::timestamptz END,
+            $4::timestamptz
+        )
+        "#,
+    )
+    .bind(id)
+    .bind(slug)
+    .bind(status)
+    .bind(&at)
+    .execute(pool)
+    .await
+    .expect("insert post");
+
+    if let Some(tag) = tag {
+        sqlx::query(
+            "INSERT INTO tags (id, slug, name) VALUES ($1, $2, $2) ON CONFLICT (slug) DO NOTHING",
+        )
+        .bind(Uuid::new_v4())
+        .bind(tag)
+        .execute(pool)
+        .await
+        .expect("insert tag");
+        sqlx::query(
+            "INSERT INTO post_tags (post_id, tag_id) SELECT $1, id FROM tags WHERE slug = $2",
+        )
+        .bind(id)
+        .bind(tag)
+        .execute(pool)
+        .await
+        .expect("tag post");
+    }
+    id
+}
+
+async fn seed_page(pool: &PgPool, slug: &str, status: &str, month: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    let at = format!("{month}-15T12:00:00Z");
+    sqlx::query(
+        r#"
+        INSERT INTO pages (id, slug, title, body_markdown, rendered_html, status, published_at, updated_at)
+        VALUES (
+            $1, $2, $2, '', '', $3::page_status,
+            CASE WHEN $3 = 'published' THEN $4::timestamptz END,
+            $4::timestamptz
+        )
+        "#,
+    )
+    .bind(id)
+    .bind(slug)
+    .bind(status)
+    .bind(&at)
+    .execute(pool)
+    .await
+    .expect("insert page");
+    id
+}
+
+fn set(ids: &[Uuid]) -> HashSet<Uuid> {
+    ids.iter().copied().collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn post_ids_follow_status_tag_month_and_search_exactly(pool: PgPool) {
+    let rust_draft = seed_post(&pool, "rust-draft", "draft", "2026-03", Some("rust")).await;
+    let rust_live = seed_post(&pool, "rust-live", "published", "2026-03", Some("rust")).await;
+    let rust_old = seed_post(&pool, "rust-old", "published", "2026-01", Some("rust")).await;
+    let go_live = seed_post(&pool, "go-live", "published", "2026-03", Some("go")).await;
+    seed_post(&pool, "untagged", "archived", "2026-03", None).await;
+
+    let repos = PostgresRepositories::new(pool.clone());
+    let by_tag = PostQueryFilter {
+        tag: Some("rust".into()),
+        ..PostQueryFilter::default()
+    };
+    let ids = repos
+        .list_admin_post_ids(None, &by_tag, 500)
+        .await
+        .expect("ids by tag");
+    assert_eq!(set(&ids), set(&[rust_draft, rust_live, rust_old]));
+
+    let ids = repos
+        .list_admin_post_ids(Some(PostStatus::Published), &by_tag, 500)
+        .await
+        .expect("ids by tag and status");
+    assert_eq!(set(&ids), set(&[rust_live, rust_old]));
+
+    let by_month = PostQueryFilter {
+        tag: Some("rust".into()),
+        month: Some("2026-03".into()),
+        ..PostQueryFilter::default()
+    };
+    let ids = repos
+        .list_admin_post_ids(Some(PostStatus::Published), &by_month, 500)
+        .await
+        .expect("ids by tag, status and month");
+    assert_eq!(ids, vec![rust_live]);
+
+    let by_search = PostQueryFilter {
+        search: Some("-live".into()),
+        ..PostQueryFilter::default()
+    };
+    let ids = repos
+        .list_admin_post_ids(None, &by_search, 500)
+        .await
+        .expect("ids by search");
+    assert_eq!(set(&ids), set(&[rust_live, go_live]));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn post_ids_stop_at_the_limit(pool: PgPool) {
+    for index in 0..4 {
+        seed_post(&pool, &format!("post-{index}"), "draft", "2026-03", None).await;
+    }
+
+    let repos = PostgresRepositories::new(pool.clone());
+    let ids = repos
+        .list_admin_post_ids(None, &PostQueryFilter::default(), 3)
+        .await
+        .expect("capped ids");
+    assert_eq!(ids.len(), 3);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn page_ids_follow_status_month_and_search_exactly(pool: PgPool) {
+    let about = seed_page(&pool, "about", "published", "2026-03").await;
+    let about_draft = seed_page(&pool, "about-team", "draft", "2026-03").await;
+    seed_page(&pool, "about-old", "published", "2025-12").await;
+    seed_page(&pool, "contact", "published", "2026-03").await;
+
+    let repos = PostgresRepositories::new(pool.clone());
+    let filter = PageQueryFilter {
+        search: Some("about".into()),
+        month: Some("2026-03".into()),
+    };
+
+    let ids = repos
+        .list_admin_page_ids(None, &filter, 500)
+        .await
+        .expect("ids by search and month");
+    assert_eq!(set(&ids), set(&[about, about_draft]));
+
+    let ids = repos
+        .list_admin_page_ids(Some(PageStatus::Draft), &filter, 500)
+        .await
+        .expect("ids by status");
+    assert_eq!(ids, vec![about_draft]);
+}
@@ -0,0 +1,168 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use soffio::application::feed::{FeedFilter, FeedService};
+use soffio::cache::{CacheConfig, L0Store};
+use soffio::infra::db::PostgresRepositories;
+use sqlx::PgPool;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use tracing::instrument::WithSubscriber;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use uuid::Uuid;
+
+const POSTS: usize = 25;
+
+/// Counts the statements sqlx logs under its `sqlx::query` target.
+struct StatementCounter(Arc<AtomicUsize>);
+
+impl<S: Subscriber> Layer<S> for StatementCounter {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() == "sqlx::query" {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+async fn count_statements<F: Future>(future: F) -> (F::Output, usize) {
+    let count = Arc::new(AtomicUsize::new(0));
+    let subscriber = tracing_subscriber::registry().with(StatementCounter(count.clone()));
+    let output = future.with_subscriber(subscriber).await;
+    (output, count.load(Ordering::SeqCst))
+}
+
+/// `POSTS` published posts, each tagged `shared` and a tag of its own, all
+/// on one feed page.
+async fn seed_feed(pool: &PgPool) {
+    sqlx::query("INSERT INTO tags (id, slug, name) VALUES ($1, 'shared', 'Shared')")
+        .bind(Uuid::new_v4())
+        .execute(pool)
+        .await
+        .expect("insert shared tag");
+
+    for index in 0..POSTS {
+        let id = Uuid::new_v4();
+        let slug = format!("post-{index:02}");
+        sqlx::query(
+            r#"
+            INSERT INTO posts (id, slug, title, excerpt, body_markdown, status, published_at, updated_at)
+            VALUES ($1, $2, $2, '', '', 'published', now() - make_interval(hours => $3), now())
+            "#,
+        )
+        .bind(id)
+        .bind(&slug)
+        .bind(i32::try_from(index).expect("index fits"))
+        .execute(pool)
+        .await
+        .expect("insert post");
+        sqlx::query("INSERT INTO tags (id, slug, name) VALUES ($1, $2, $2)")
+            .bind(Uuid::new_v4())
+            .bind(format!("tag-{index:02}"))
+            .execute(pool)
+            .await
+            .expect("insert tag");
+        sqlx::query(
+            r#"
+            INSERT INTO post_tags (post_id, tag_id)
+            SELECT $1, id FROM tags WHERE slug IN ('shared', $2)
+            "#,
+        )
+        .bind(id)
+        .bind(format!("tag-{index:02}"))
+        .execute(pool)
+        .await
+        .expect("tag post");
+    }
+
+    sqlx::query("UPDATE site_settings SET homepage_size = $1")
+        .bind(i32::try_from(POSTS).expect("page size fits"))
+        .execute(pool)
+        .await
+        .expect("set page size");
+}
+
+/// A single-connection pool, so sqlx's per-connection type lookups are
+/// done by the warm-up page and not counted against the pages under test.
+async fn single_connection_pool(
+    pool_opts: PgPoolOptions,
+    connect_opts: PgConnectOptions,
+) -> PgPool {
+    pool_opts
+        .max_connections(1)
+        .connect_with(connect_opts)
+        .await
+        .expect("connect test database")
+}
+
+fn feed_service(pool: PgPool, cache: Option<Arc<L0Store>>) -> FeedService {
+    let repos = Arc::new(PostgresRepositories::new(pool));
+    let keys = repos.cursor_keys().clone();
+    FeedService::new(repos.clone(), repos.clone(), repos.clone(), repos, cache)
+        .with_cursor_keys(keys)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn feed_page_fetches_tags_for_all_posts_at_once(
+    pool_opts: PgPoolOptions,
+    connect_opts: PgConnectOptions,
+) {
+    let pool = single_connection_pool(pool_opts, connect_opts).await;
+    seed_feed(&pool).await;
+    let feed = feed_service(pool, None);
+
+    feed.page_context(FeedFilter::All, None)
+        .await
+        .expect("warm feed page");
+    let (page, statements) = count_statements(feed.page_context(FeedFilter::All, None)).await;
+    let page = page.expect("feed page");
+    assert_eq!(page.posts.len(), POSTS);
+    for card in &page.posts {
+        let labels: Vec<&str> = card
+            .badges
+            .iter()
+            .map(|badge| badge.label.as_str())
+            .collect();
+        assert!(labels.contains(&"#Shared"), "{}: {labels:?}", card.slug);
+        assert_eq!(labels.len(), 2, "{}", card.slug);
+    }
+    // Settings, the page, tag and month counts together, and the tags of
+    // every post on the page.
+    assert!(
+        (1..=4).contains(&statements),
+        "{statements} statements for {POSTS} posts"
+    );
+
+    let (page, statements) =
+        count_statements(feed.page_context(FeedFilter::Tag("shared".to_string()), None)).await;
+    assert_eq!(page.expect("tag page").posts.len(), POSTS);
+    assert!(
+        (1..=4).contains(&statements),
+        "{statements} statements for {POSTS} posts"
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn cached_feed_page_issues_a_bounded_number_of_queries(
+    pool_opts: PgPoolOptions,
+    connect_opts: PgConnectOptions,
+) {
+    let pool = single_connection_pool(pool_opts, connect_opts).await;
+    seed_feed(&pool).await;
+    let cache = Arc::new(L0Store::new(&CacheConfig::default()));
+    let feed = feed_service(pool, Some(cache));
+
+    feed.page_context(FeedFilter::All, None)
+        .await
+        .expect("warm feed page");
+    let (page, statements) = count_statements(feed.page_context(FeedFilter::All, None)).await;
+    assert_eq!(page.expect("feed page").posts.len(), POSTS);
+    assert!(
+        (1..=4).contains(&statements),
+        "{statements} statements for {POSTS} posts"
+    );
+
+    let (payload, statements) = count_statements(feed.append_payload(FeedFilter::All, None)).await;
+    assert_eq!(payload.expect("append payload").cards.len(), POSTS);
+    assert!(statements <= 4, "{statements} statements for {POSTS} posts");
+}
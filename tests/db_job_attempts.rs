@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use apalis::prelude::{Attempt, TaskId};
+use soffio::application::admin::audit::AdminAuditService;
+use soffio::application::admin::jobs::{AdminJobService, JobEntity, JobEntityKind};
+use soffio::application::jobs::{job_failed, record_failed_attempt};
+use soffio::application::repos::{AuditRepo, JobsRepo, NewJobRecord};
+use soffio::domain::entities::AuditLogRecord;
+use soffio::domain::types::{ActorType, JobType};
+use soffio::infra::db::PostgresRepositories;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use time::macros::datetime;
+use uuid::Uuid;
+
+async fn enqueue(repos: &PostgresRepositories, payload: serde_json::Value) -> String {
+    repos
+        .enqueue_job(NewJobRecord {
+            job_type: JobType::RenderPost,
+            payload,
+            run_at: datetime!(2026-05-01 12:00:00 UTC),
+            max_attempts: 3,
+            priority: 0,
+        })
+        .await
+        .expect("enqueue job")
+}
+
+async fn append_audit(repos: &PostgresRepositories, action: &str, request_id: Option<&str>) {
+    repos
+        .append_log(AuditLogRecord {
+            id: Uuid::new_v4(),
+            actor: "admin".to_string(),
+            actor_type: ActorType::Admin,
+            actor_key_id: None,
+            action: action.to_string(),
+            entity_type: "post".to_string(),
+            entity_id: Some("hello".to_string()),
+            payload_text: None,
+            changes: None,
+            request_id: request_id.map(str::to_string),
+            created_at: OffsetDateTime::now_utc(),
+        })
+        .await
+        .expect("append audit entry");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn failed_runs_are_recorded_and_successes_are_not(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let job_id = enqueue(&repos, serde_json::json!({ "slug": "hello" })).await;
+    let task_id: TaskId = job_id.parse().expect("job ids are ulids");
+
+    for (attempt, message) in [(1, "first failure\n  at render"), (2, "second failure")] {
+        let result = record_failed_attempt(
+            &repos,
+            &task_id,
+            &Attempt::new_with_value(attempt),
+            Err(job_failed(std::io::Error::other(message))),
+        )
+        .await;
+        assert!(result.is_err(), "the job's own error is passed through");
+    }
+    record_failed_attempt(&repos, &task_id, &Attempt::new_with_value(3), Ok(()))
+        .await
+        .expect("a successful run stays successful");
+
+    let attempts = repos
+        .list_job_attempts(&job_id)
+        .await
+        .expect("list attempts");
+    let summary: Vec<(i32, bool)> = attempts
+        .iter()
+        .map(|attempt| (attempt.attempt, attempt.job_id == job_id))
+        .collect();
+    assert_eq!(summary, vec![(1, true), (2, true)]);
+    assert!(attempts[0].error.contains("first failure\n  at render"));
+    assert!(attempts[1].error.contains("second failure"));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn job_detail_collects_timeline_attempts_entity_and_request_audit(pool: PgPool) {
+    let repos = Arc::new(PostgresRepositories::new(pool.clone()));
+    let job_id = enqueue(
+        &repos,
+        serde_json::json!({ "slug": "hello", "request_id": "req-42" }),
+    )
+    .await;
+    sqlx::query(
+        "UPDATE apalis.jobs SET status = 'Failed', lock_at = $2, done_at = $3, last_error = 'boom' WHERE id = $1",
+    )
+    .bind(&job_id)
+    .bind(datetime!(2026-05-01 12:00:01.500 UTC))
+    .bind(datetime!(2026-05-01 12:00:04 UTC))
+    .execute(&pool)
+    .await
+    .expect("finish job");
+    repos
+        .record_job_attempt(&job_id, 1, "boom")
+        .await
+        .expect("record attempt");
+
+    append_audit(&repos, "post.create", Some("req-42")).await;
+    append_audit(&repos, "post.update", Some("req-other")).await;
+    append_audit(&repos, "post.delete", None).await;
+
+    let audit = AdminAuditService::new(repos.clone());
+    let jobs = AdminJobService::new(repos.clone(), audit);
+    let detail = jobs.load_job_detail(&job_id).await.expect("job detail");
+
+    assert_eq!(detail.job.id, job_id);
+    assert_eq!(detail.timeline.wait_ms, Some(1_500));
+    assert_eq!(detail.timeline.run_ms, Some(2_500));
+    assert_eq!(detail.attempt_history.len(), 1);
+    assert_eq!(
+        detail.entity,
+        Some(JobEntity {
+            kind: JobEntityKind::Post,
+            slug: "hello".to_string(),
+        })
+    );
+    assert_eq!(detail.request_id.as_deref(), Some("req-42"));
+    let actions: Vec<&str> = detail
+        .related_audit
+        .iter()
+        .map(|entry| entry.action.as_str())
+        .collect();
+    assert_eq!(actions, vec!["post.create"]);
+
+    let json = serde_json::to_value(&detail).expect("serialize detail");
+    assert_eq!(json["id"], job_id.as_str());
+    assert_eq!(json["entity"]["kind"], "post");
+    assert_eq!(json["attempt_history"][0]["error"], "boom");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn attempts_go_away_with_their_job(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let job_id = enqueue(&repos, serde_json::json!({ "slug": "hello" })).await;
+    repos
+        .record_job_attempt(&job_id, 1, "boom")
+        .await
+        .expect("record attempt");
+
+    sqlx::query("DELETE FROM apalis.jobs WHERE id = $1")
+        .bind(&job_id)
+        .execute(&pool)
+        .await
+        .expect("delete job");
+
+    let attempts = repos
+        .list_job_attempts(&job_id)
+        .await
+        .expect("list attempts");
+    assert!(attempts.is_empty());
+}
@@ -0,0 +1,189 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use soffio::application::admin::audit::AdminAuditService;
+use soffio::application::admin::posts::AdminPostService;
+use soffio::application::admin::tags::AdminTagService;
+use soffio::application::site::import_markdown_posts;
+use soffio::infra::db::PostgresRepositories;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use time::macros::datetime;
+use uuid::Uuid;
+
+const FIXTURES: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/markdown_import"
+);
+
+fn services(pool: &PgPool) -> (AdminPostService, AdminTagService) {
+    let repos = Arc::new(PostgresRepositories::new(pool.clone()));
+    let audit = AdminAuditService::new(repos.clone());
+    let posts = AdminPostService::new(
+        repos.clone(),
+        repos.clone(),
+        repos.clone(),
+        repos.clone(),
+        repos.clone(),
+        audit.clone(),
+    );
+    let tags = AdminTagService::new(repos.clone(), repos, audit);
+    (posts, tags)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn fixture_directory_imports_posts_and_skips_the_malformed_file(pool: PgPool) {
+    sqlx::query("INSERT INTO tags (id, slug, name) VALUES ($1, 'rust', 'Rust')")
+        .bind(Uuid::new_v4())
+        .execute(&pool)
+        .await
+        .expect("seed tag");
+    let (posts, tags) = services(&pool);
+
+    let report = import_markdown_posts(&posts, &tags, "tester", Path::new(FIXTURES))
+        .await
+        .expect("import fixtures");
+
+    assert_eq!(report.created, vec!["hello-world", "notes-on-drafts"]);
+    assert_eq!(report.tags_created, vec!["Web"]);
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].path.ends_with("broken.md"));
+    assert!(
+        report.skipped[0].reason.contains("invalid front matter"),
+        "{}",
+        report.skipped[0].reason
+    );
+
+    let rows: Vec<(String, String, String, Option<OffsetDateTime>)> =
+        sqlx::query_as("SELECT slug, title, status::text, published_at FROM posts ORDER BY slug")
+            .fetch_all(&pool)
+            .await
+            .expect("list posts");
+    assert_eq!(
+        rows,
+        vec![
+            (
+                "hello-world".to_string(),
+                "Hello, world".to_string(),
+                "published".to_string(),
+                Some(datetime!(2024-03-01 00:00 UTC)),
+            ),
+            (
+                "notes-on-drafts".to_string(),
+                "Notes on drafts".to_string(),
+                "draft".to_string(),
+                None,
+            ),
+        ]
+    );
+
+    let links: Vec<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT p.slug, t.slug
+        FROM post_tags pt
+        JOIN posts p ON p.id = pt.post_id
+        JOIN tags t ON t.id = pt.tag_id
+        ORDER BY p.slug, t.slug
+        "#,
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("list post tags");
+    assert_eq!(
+        links,
+        vec![
+            ("hello-world".to_string(), "rust".to_string()),
+            ("hello-world".to_string(), "web".to_string()),
+            ("notes-on-drafts".to_string(), "web".to_string()),
+        ]
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn reimporting_skips_posts_whose_slug_is_taken(pool: PgPool) {
+    let (posts, tags) = services(&pool);
+    import_markdown_posts(&posts, &tags, "tester", Path::new(FIXTURES))
+        .await
+        .expect("first import");
+
+    let report = import_markdown_posts(&posts, &tags, "tester", Path::new(FIXTURES))
+        .await
+        .expect("second import");
+
+    assert!(report.tags_created.is_empty());
+    let skipped: Vec<&str> = report
+        .skipped
+        .iter()
+        .map(|file| file.reason.as_str())
+        .collect();
+    assert!(
+        skipped.contains(&"slug `hello-world` is already in use"),
+        "{skipped:?}"
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn a_missing_directory_is_a_validation_error(pool: PgPool) {
+    let (posts, tags) = services(&pool);
+    let err = import_markdown_posts(&posts, &tags, "tester", Path::new("/nonexistent/soffio"))
+        .await
+        .expect_err("missing directory");
+    assert!(err.to_string().contains("cannot read directory"), "{err}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn unusable_files_are_skipped_without_stopping_the_batch(pool: PgPool) {
+    let dir = tempfile::tempdir().expect("temp dir");
+    std::fs::write(
+        dir.path().join("a-bad-tag.md"),
+        "---\ntitle: Bad tag\ntags: [Fresh, \"???\"]\n---\nBody\n",
+    )
+    .expect("write bad tag file");
+    std::fs::write(
+        dir.path().join("b-latin1.md"),
+        b"---\ntitle: Caf\xe9\n---\nBody\n",
+    )
+    .expect("write non-UTF-8 file");
+    std::fs::write(
+        dir.path().join("c-good.md"),
+        "---\ntitle: Good\ntags: [Web]\n---\nBody\n",
+    )
+    .expect("write good file");
+    let (posts, tags) = services(&pool);
+
+    let report = import_markdown_posts(&posts, &tags, "tester", dir.path())
+        .await
+        .expect("import continues past bad files");
+
+    assert_eq!(report.created, vec!["good"]);
+    assert_eq!(report.tags_created, vec!["Web"]);
+    let skipped: Vec<(&str, &str)> = report
+        .skipped
+        .iter()
+        .map(|file| {
+            (
+                file.path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or(""),
+                file.reason.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(skipped.len(), 2, "{skipped:?}");
+    assert_eq!(skipped[0].0, "a-bad-tag.md");
+    assert!(skipped[0].1.contains("tag `???`"), "{skipped:?}");
+    assert_eq!(skipped[1].0, "b-latin1.md");
+    assert!(skipped[1].1.contains("cannot read file"), "{skipped:?}");
+
+    let slugs: Vec<String> = sqlx::query_scalar("SELECT slug FROM posts ORDER BY slug")
+        .fetch_all(&pool)
+        .await
+        .expect("list posts");
+    assert_eq!(slugs, vec!["good"]);
+    let tag_slugs: Vec<String> = sqlx::query_scalar("SELECT slug FROM tags ORDER BY slug")
+        .fetch_all(&pool)
+        .await
+        .expect("list tags");
+    assert_eq!(tag_slugs, vec!["web"]);
+}
@@ -0,0 +1,111 @@
+use sqlx::PgPool;
+
+use soffio::infra::db::{MigrationState, migration_status, pending_migrations};
+
+async fn latest_version(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations")
+        .fetch_one(pool)
+        .await
+        .expect("latest version")
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn fully_migrated_database_has_no_divergence(pool: PgPool) {
+    let statuses = migration_status(&pool).await.expect("status");
+
+    assert!(!statuses.is_empty());
+    assert!(
+        statuses
+            .iter()
+            .all(|status| status.state == MigrationState::Applied)
+    );
+    assert!(statuses.iter().all(|status| status.applied_at.is_some()));
+    assert!(
+        statuses
+            .windows(2)
+            .all(|pair| pair[0].version < pair[1].version)
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn database_one_migration_behind_lists_it_as_pending(pool: PgPool) {
+    assert!(pending_migrations(&pool).await.expect("pending").is_empty());
+
+    let version = latest_version(&pool).await;
+    sqlx::query("DELETE FROM _sqlx_migrations WHERE version = $1")
+        .bind(version)
+        .execute(&pool)
+        .await
+        .expect("forget latest migration");
+
+    let pending = pending_migrations(&pool).await.expect("pending");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].version, version);
+    assert_eq!(pending[0].state, MigrationState::Pending);
+    assert!(pending[0].applied_at.is_none());
+
+    let statuses = migration_status(&pool).await.expect("status");
+    assert!(
+        statuses
+            .iter()
+            .filter(|status| status.version != version)
+            .all(|status| status.state == MigrationState::Applied)
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn tampered_checksum_is_flagged(pool: PgPool) {
+    let version = latest_version(&pool).await;
+    sqlx::query("UPDATE _sqlx_migrations SET checksum = '\\x00'::bytea WHERE version = $1")
+        .bind(version)
+        .execute(&pool)
+        .await
+        .expect("tamper checksum");
+
+    let statuses = migration_status(&pool).await.expect("status");
+    let tampered = statuses
+        .iter()
+        .find(|status| status.version == version)
+        .expect("tampered version listed");
+
+    assert_eq!(tampered.state, MigrationState::ChecksumMismatch);
+    assert!(tampered.state.is_divergent());
+    assert_eq!(
+        statuses
+            .iter()
+            .filter(|status| status.state.is_divergent())
+            .count(),
+        1
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn missing_and_foreign_versions_are_reported(pool: PgPool) {
+    let version = latest_version(&pool).await;
+    sqlx::query("DELETE FROM _sqlx_migrations WHERE version = $1")
+        .bind(version)
+        .execute(&pool)
+        .await
+        .expect("forget latest migration");
+    sqlx::query(
+        "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+         VALUES (99990101000000, 'from a newer binary', TRUE, '\\x00'::bytea, 0)",
+    )
+    .execute(&pool)
+    .await
+    .expect("record foreign migration");
+
+    let statuses = migration_status(&pool).await.expect("status");
+    let state_of = |version: i64| {
+        statuses
+            .iter()
+            .find(|status| status.version == version)
+            .map(|status| status.state)
+    };
+
+    assert_eq!(state_of(version), Some(MigrationState::Pending));
+    assert!(!MigrationState::Pending.is_divergent());
+    assert_eq!(state_of(99990101000000), Some(MigrationState::Unknown));
+    let foreign = statuses.last().expect("foreign version sorts last");
+    assert_eq!(foreign.description, "from a newer binary");
+}
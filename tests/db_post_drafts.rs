@@ -0,0 +1,147 @@
+use soffio::infra::db::{PostDraftContent, PostDraftKey, PostgresRepositories};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn seed_post(pool: &PgPool, slug: &str) -> Uuid {
+    let post_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO posts (id, slug, title, excerpt, body_markdown) VALUES ($1, $2, $2, '', $2)",
+    )
+    .bind(post_id)
+    .bind(slug)
+    .execute(pool)
+    .await
+    .expect("insert post");
+    post_id
+}
+
+fn content(body: &str) -> PostDraftContent {
+    PostDraftContent {
+        title: "Draft title".into(),
+        slug: "draft-title".into(),
+        excerpt: String::new(),
+        body_markdown: body.into(),
+        summary_markdown: Some("Summary".into()),
+    }
+}
+
+async fn live_body(pool: &PgPool, post_id: Uuid) -> String {
+    sqlx::query_scalar("SELECT body_markdown FROM posts WHERE id = $1")
+        .bind(post_id)
+        .fetch_one(pool)
+        .await
+        .expect("load post body")
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn autosave_replaces_the_previous_one_without_touching_the_post(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let post_id = seed_post(&pool, "hello").await;
+    let key = PostDraftKey::Post(post_id);
+
+    assert_eq!(repos.load_post_draft(key).await.expect("load"), None);
+
+    let first = repos
+        .save_post_draft(key, "alice", &content("first"))
+        .await
+        .expect("first autosave");
+    let second = repos
+        .save_post_draft(key, "bob", &content("second"))
+        .await
+        .expect("second autosave");
+    assert!(second >= first);
+
+    let draft = repos
+        .load_post_draft(key)
+        .await
+        .expect("load")
+        .expect("autosave stored");
+    assert_eq!(draft.content, content("second"));
+    assert_eq!(draft.author, "bob");
+    assert_eq!(draft.saved_at, second);
+    assert_eq!(live_body(&pool, post_id).await, "hello");
+
+    let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM post_drafts")
+        .fetch_one(&pool)
+        .await
+        .expect("count autosaves");
+    assert_eq!(rows, 1);
+
+    repos.clear_post_draft(key).await.expect("clear");
+    assert_eq!(repos.load_post_draft(key).await.expect("reload"), None);
+    repos.clear_post_draft(key).await.expect("clear again");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn new_post_autosaves_are_kept_per_admin(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let post_id = seed_post(&pool, "hello").await;
+
+    for (author, body) in [("alice", "alice's"), ("bob", "bob's")] {
+        repos
+            .save_post_draft(PostDraftKey::NewPost(author), author, &content(body))
+            .await
+            .expect("new-post autosave");
+    }
+    repos
+        .save_post_draft(PostDraftKey::Post(post_id), "alice", &content("edit"))
+        .await
+        .expect("post autosave");
+
+    let alice = repos
+        .load_post_draft(PostDraftKey::NewPost("alice"))
+        .await
+        .expect("load")
+        .expect("alice's autosave");
+    assert_eq!(alice.content.body_markdown, "alice's");
+
+    repos
+        .clear_post_draft(PostDraftKey::NewPost("alice"))
+        .await
+        .expect("clear alice's");
+    assert_eq!(
+        repos
+            .load_post_draft(PostDraftKey::NewPost("alice"))
+            .await
+            .expect("reload"),
+        None
+    );
+    assert!(
+        repos
+            .load_post_draft(PostDraftKey::NewPost("bob"))
+            .await
+            .expect("load bob's")
+            .is_some()
+    );
+    assert!(
+        repos
+            .load_post_draft(PostDraftKey::Post(post_id))
+            .await
+            .expect("load post's")
+            .is_some()
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn purging_a_post_drops_its_autosave(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let post_id = seed_post(&pool, "hello").await;
+    repos
+        .save_post_draft(PostDraftKey::Post(post_id), "alice", &content("edit"))
+        .await
+        .expect("autosave");
+
+    sqlx::query("DELETE FROM posts WHERE id = $1")
+        .bind(post_id)
+        .execute(&pool)
+        .await
+        .expect("purge post");
+
+    assert_eq!(
+        repos
+            .load_post_draft(PostDraftKey::Post(post_id))
+            .await
+            .expect("load"),
+        None
+    );
+}
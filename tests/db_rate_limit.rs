@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use soffio::infra::http::{PostgresRateLimiter, RateLimiter};
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = "./migrations")]
+async fn concurrent_requests_never_exceed_the_limit(pool: PgPool) {
+    let limiter = Arc::new(PostgresRateLimiter::new(
+        pool.clone(),
+        Duration::from_secs(3_600),
+        20,
+    ));
+
+    let tasks: Vec<_> = (0..50)
+        .map(|_| {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.allow("key", "/api/v1/posts").await })
+        })
+        .collect();
+    let mut remaining = Vec::new();
+    for task in tasks {
+        let (allowed, left) = task.await.expect("join");
+        if allowed {
+            remaining.push(left);
+        }
+    }
+
+    // Each allowed request spent exactly one token: no double counting.
+    remaining.sort_unstable();
+    assert_eq!(remaining, (0..20).collect::<Vec<u32>>());
+
+    let tokens: f64 =
+        sqlx::query_scalar("SELECT tokens FROM rate_limit_buckets WHERE bucket_key = $1")
+            .bind("key:/api/v1/posts")
+            .fetch_one(&pool)
+            .await
+            .expect("bucket row");
+    assert!(tokens < 1.0, "{tokens}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn nodes_share_buckets(pool: PgPool) {
+    let node_a = PostgresRateLimiter::new(pool.clone(), Duration::from_secs(3_600), 5);
+    let node_b = PostgresRateLimiter::new(pool, Duration::from_secs(3_600), 5);
+
+    for _ in 0..3 {
+        assert!(node_a.allow("key", "route").await.0);
+    }
+    assert_eq!(node_b.allow("key", "route").await, (true, 1));
+    assert_eq!(node_b.allow("key", "route").await, (true, 0));
+    assert_eq!(node_a.allow("key", "route").await, (false, 0));
+    assert!(node_b.allow("other", "route").await.0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn denied_requests_do_not_consume_refills(pool: PgPool) {
+    let limiter = PostgresRateLimiter::new(pool, Duration::from_secs(1), 2);
+    assert!(limiter.allow("key", "route").await.0);
+    assert!(limiter.allow("key", "route").await.0);
+    for _ in 0..5 {
+        assert_eq!(limiter.allow("key", "route").await, (false, 0));
+    }
+
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(limiter.allow("key", "route").await, (true, 0));
+    assert_eq!(limiter.allow("key", "route").await, (false, 0));
+}
@@ -0,0 +1,84 @@
+use soffio::application::repos::{CreateTagParams, TagsRepo, TagsWriteRepo};
+use soffio::infra::db::{ConnectionLimits, PostgresRepositories};
+use sqlx::PgPool;
+
+/// Attach a "replica" pool to the same database whose connections resolve
+/// `tags` to an empty copy in a marker schema, so reads reveal which pool ran.
+async fn repositories_with_marked_replica(pool: &PgPool) -> PostgresRepositories {
+    sqlx::query("CREATE SCHEMA replica_marker")
+        .execute(pool)
+        .await
+        .expect("create marker schema");
+    sqlx::query("CREATE TABLE replica_marker.tags (LIKE public.tags INCLUDING ALL)")
+        .execute(pool)
+        .await
+        .expect("create marker table");
+
+    let options = (*pool.connect_options())
+        .clone()
+        .options([("search_path", "replica_marker,public")]);
+    let read_pool = PostgresRepositories::connect_with(options, 1, ConnectionLimits::default())
+        .await
+        .expect("connect read pool");
+
+    PostgresRepositories::new(pool.clone()).with_read_pool(read_pool)
+}
+
+fn tag_params(slug: &str) -> CreateTagParams {
+    CreateTagParams {
+        slug: slug.to_string(),
+        name: slug.to_string(),
+        description: None,
+        pinned: false,
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn site_reads_use_the_read_pool_and_writes_the_primary(pool: PgPool) {
+    let primary = PostgresRepositories::new(pool.clone());
+    let public = repositories_with_marked_replica(&pool).await;
+
+    let created = TagsWriteRepo::create_tag(&public, tag_params("rust"))
+        .await
+        .expect("write through the primary");
+
+    let from_primary = TagsRepo::find_by_slug(&primary, "rust")
+        .await
+        .expect("primary read");
+    assert_eq!(from_primary.map(|tag| tag.id), Some(created.id));
+
+    let from_replica = TagsRepo::find_by_slug(&public, "rust")
+        .await
+        .expect("replica read");
+    assert!(from_replica.is_none(), "read should hit the marker schema");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn transactions_stay_on_the_primary(pool: PgPool) {
+    let public = repositories_with_marked_replica(&pool).await;
+
+    let mut tx = public.begin().await.expect("begin");
+    let search_path: String = sqlx::query_scalar("SHOW search_path")
+        .fetch_one(&mut *tx)
+        .await
+        .expect("read search_path");
+    tx.rollback().await.expect("rollback");
+
+    assert!(!search_path.contains("replica_marker"));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn without_a_read_pool_reads_use_the_primary(pool: PgPool) {
+    let primary = PostgresRepositories::new(pool);
+
+    TagsWriteRepo::create_tag(&primary, tag_params("go"))
+        .await
+        .expect("create tag");
+
+    assert!(
+        TagsRepo::find_by_slug(&primary, "go")
+            .await
+            .expect("read")
+            .is_some()
+    );
+}
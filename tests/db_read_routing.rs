@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use soffio::application::repos::{CreateTagParams, RepoError, TagsRepo, TagsWriteRepo};
+use soffio::infra::db::{ConnectionLimits, PostgresRepositories, on_primary};
+use sqlx::PgPool;
+
+/// Repositories whose replica is a separate one-connection pool, so holding
+/// that connection shows whether a call went to the replica.
+async fn with_replica(pool: &PgPool) -> (PostgresRepositories, PgPool) {
+    let options = (*pool.connect_options()).clone();
+    let replica = PostgresRepositories::connect_with(
+        options,
+        1,
+        ConnectionLimits {
+            acquire_timeout: Some(Duration::from_millis(200)),
+            ..ConnectionLimits::default()
+        },
+    )
+    .await
+    .expect("connect replica pool");
+    let repos = PostgresRepositories::new(pool.clone()).with_read_pool(replica.clone());
+    (repos, replica)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn reads_use_the_replica_and_writes_the_primary(pool: PgPool) {
+    let (repos, replica) = with_replica(&pool).await;
+
+    TagsRepo::list_with_counts(&repos)
+        .await
+        .expect("read through the replica");
+    assert_eq!(replica.size(), 1, "the read opened a replica connection");
+
+    let held = replica
+        .acquire()
+        .await
+        .expect("hold the replica connection");
+
+    let tag = repos
+        .create_tag(CreateTagParams {
+            slug: "routed".into(),
+            name: "Routed".into(),
+            description: None,
+            pinned: false,
+        })
+        .await
+        .expect("write goes to the primary");
+
+    let err = TagsRepo::list_with_counts(&repos)
+        .await
+        .expect_err("read waits for the held replica connection");
+    assert!(matches!(err, RepoError::Timeout));
+
+    let tags = on_primary(TagsRepo::list_with_counts(&repos))
+        .await
+        .expect("forced read goes to the primary");
+    assert!(tags.iter().any(|entry| entry.id == tag.id));
+
+    drop(held);
+}
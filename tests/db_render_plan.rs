@@ -0,0 +1,202 @@
+use futures::TryStreamExt;
+use soffio::infra::db::{PostgresRepositories, RenderPlan, RenderSelection};
+use sqlx::PgPool;
+use time::macros::datetime;
+use uuid::Uuid;
+
+async fn seed_post(pool: &PgPool, slug: &str, rendered: bool) -> Uuid {
+    let post_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO posts (id, slug, title, excerpt, body_markdown) VALUES ($1, $2, $2, '', $2)",
+    )
+    .bind(post_id)
+    .bind(slug)
+    .execute(pool)
+    .await
+    .expect("insert post");
+
+    if rendered {
+        sqlx::query(
+            r#"
+            INSERT INTO post_sections
+                (id, post_id, position, level, heading_html, heading_text, body_html, anchor_slug)
+            VALUES ($1, $2, 0, 1, 'Intro', 'Intro', '<p>rendered</p>', 'intro')
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(post_id)
+        .execute(pool)
+        .await
+        .expect("insert section");
+    }
+    post_id
+}
+
+async fn seed_page(pool: &PgPool, slug: &str, rendered_html: &str, failed: bool) {
+    sqlx::query(
+        r#"
+        INSERT INTO pages (id, slug, title, body_markdown, rendered_html, render_status)
+        VALUES ($1, $2, $2, $2, $3, $4::render_status)
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(slug)
+    .bind(rendered_html)
+    .bind(if failed { "failed" } else { "ok" })
+    .execute(pool)
+    .await
+    .expect("insert page");
+}
+
+async fn rendered_artifacts(pool: &PgPool) -> (i64, Vec<String>) {
+    let sections = sqlx::query_scalar("SELECT COUNT(*) FROM post_sections")
+        .fetch_one(pool)
+        .await
+        .expect("count sections");
+    let pages = sqlx::query_scalar("SELECT rendered_html FROM pages ORDER BY slug")
+        .fetch_all(pool)
+        .await
+        .expect("page html");
+    (sections, pages)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn render_plan_counts_content_without_rendering_it(pool: PgPool) {
+    seed_post(&pool, "fresh", true).await;
+    seed_post(&pool, "imported", false).await;
+    seed_page(&pool, "about", "<p>about</p>", false).await;
+    seed_page(&pool, "broken", "<p>old</p>", true).await;
+    seed_page(&pool, "blank", "", false).await;
+
+    let before = rendered_artifacts(&pool).await;
+    let repos = PostgresRepositories::new(pool.clone());
+
+    let posts = repos
+        .post_render_plan(&RenderSelection::default())
+        .await
+        .expect("post plan");
+    assert_eq!(
+        posts,
+        RenderPlan {
+            total: 2,
+            stale: vec!["imported".to_string()],
+        }
+    );
+
+    let pages = repos
+        .page_render_plan(&RenderSelection::default())
+        .await
+        .expect("page plan");
+    assert_eq!(pages.total, 3);
+    assert_eq!(pages.stale, vec!["blank".to_string(), "broken".to_string()]);
+
+    assert_eq!(rendered_artifacts(&pool).await, before);
+}
+
+async fn touch(pool: &PgPool, table: &str, slug: &str, updated_at: &str) {
+    sqlx::query(&format!(
+        "UPDATE {table} SET updated_at = $2::timestamptz WHERE slug = $1"
+    ))
+    .bind(slug)
+    .bind(updated_at)
+    .execute(pool)
+    .await
+    .expect("set updated_at");
+}
+
+async fn streamed_slugs(repos: &PostgresRepositories, selection: &RenderSelection) -> Vec<String> {
+    let posts: Vec<_> = repos
+        .stream_all_posts(selection)
+        .try_collect()
+        .await
+        .expect("stream posts");
+    let pages: Vec<_> = repos
+        .stream_all_pages(selection)
+        .try_collect()
+        .await
+        .expect("stream pages");
+    posts
+        .into_iter()
+        .map(|post| post.slug)
+        .chain(pages.into_iter().map(|page| page.slug))
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn since_selects_only_content_updated_after_the_cutoff(pool: PgPool) {
+    seed_post(&pool, "old-post", true).await;
+    seed_post(&pool, "new-post", true).await;
+    seed_page(&pool, "old-page", "<p>old</p>", false).await;
+    seed_page(&pool, "new-page", "<p>new</p>", false).await;
+    touch(&pool, "posts", "old-post", "2026-01-01T00:00:00Z").await;
+    touch(&pool, "posts", "new-post", "2026-05-01T00:00:00Z").await;
+    touch(&pool, "pages", "old-page", "2026-01-01T00:00:00Z").await;
+    touch(&pool, "pages", "new-page", "2026-05-01T00:00:00Z").await;
+
+    let repos = PostgresRepositories::new(pool.clone());
+    let selection = RenderSelection {
+        updated_since: Some(datetime!(2026-03-01 00:00 UTC)),
+        ..RenderSelection::default()
+    };
+
+    assert_eq!(
+        streamed_slugs(&repos, &selection).await,
+        vec!["new-post".to_string(), "new-page".to_string()]
+    );
+    let plan = repos.post_render_plan(&selection).await.expect("post plan");
+    assert_eq!(plan.total, 1);
+    let plan = repos.page_render_plan(&selection).await.expect("page plan");
+    assert_eq!(plan.total, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn slugs_select_exactly_the_named_content(pool: PgPool) {
+    seed_post(&pool, "first", true).await;
+    seed_post(&pool, "second", false).await;
+    seed_post(&pool, "third", true).await;
+    seed_page(&pool, "about", "<p>about</p>", false).await;
+    seed_page(&pool, "contact", "<p>contact</p>", false).await;
+
+    let repos = PostgresRepositories::new(pool.clone());
+    let selection = RenderSelection {
+        slugs: vec!["second".into(), "third".into(), "about".into()],
+        ..RenderSelection::default()
+    };
+
+    assert_eq!(
+        streamed_slugs(&repos, &selection).await,
+        vec![
+            "second".to_string(),
+            "third".to_string(),
+            "about".to_string()
+        ]
+    );
+    let plan = repos.post_render_plan(&selection).await.expect("post plan");
+    assert_eq!(
+        plan,
+        RenderPlan {
+            total: 2,
+            stale: vec!["second".to_string()],
+        }
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn existing_slugs_omit_unknown_ones(pool: PgPool) {
+    seed_post(&pool, "hello", true).await;
+    seed_page(&pool, "about", "<p>about</p>", false).await;
+
+    let repos = PostgresRepositories::new(pool.clone());
+    let wanted = vec!["hello".to_string(), "about".to_string(), "nope".to_string()];
+
+    let posts = repos
+        .existing_post_slugs(&wanted)
+        .await
+        .expect("post slugs");
+    assert_eq!(posts, vec!["hello".to_string()]);
+    let pages = repos
+        .existing_page_slugs(&wanted)
+        .await
+        .expect("page slugs");
+    assert_eq!(pages, vec!["about".to_string()]);
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use axum::{http::StatusCode, response::IntoResponse};
+use soffio::application::repos::{RepoError, TagsRepo};
+use soffio::infra::db::{ConnectionLimits, PostgresRepositories, map_sqlx_error};
+use soffio::infra::http::repo_error_to_http;
+use sqlx::PgPool;
+
+async fn limited_repositories(pool: &PgPool, statement_timeout: Duration) -> PostgresRepositories {
+    let options = (*pool.connect_options()).clone();
+    let limited = PostgresRepositories::connect_with(
+        options,
+        1,
+        ConnectionLimits {
+            acquire_timeout: Some(Duration::from_millis(200)),
+            statement_timeout: Some(statement_timeout),
+            slow_query_threshold: Some(Duration::from_millis(10)),
+        },
+    )
+    .await
+    .expect("connect limited pool");
+    PostgresRepositories::new(limited)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn statement_timeout_maps_to_repo_timeout(pool: PgPool) {
+    let repos = limited_repositories(&pool, Duration::from_millis(100)).await;
+
+    let err = sqlx::query("SELECT pg_sleep(1)")
+        .execute(repos.pool())
+        .await
+        .expect_err("statement should time out");
+
+    assert!(matches!(map_sqlx_error(err), RepoError::Timeout));
+
+    // The connection stays usable for statements within the limit.
+    sqlx::query("SELECT pg_sleep(0.01)")
+        .execute(repos.pool())
+        .await
+        .expect("short statement succeeds");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn timed_out_read_surfaces_service_unavailable(pool: PgPool) {
+    let repos = limited_repositories(&pool, Duration::from_millis(200)).await;
+
+    // Hold an exclusive lock so the read blocks until the timeout fires.
+    let mut blocker = pool.begin().await.expect("begin blocker");
+    sqlx::query("LOCK TABLE tags IN ACCESS EXCLUSIVE MODE")
+        .execute(&mut *blocker)
+        .await
+        .expect("lock tags");
+
+    let err = TagsRepo::list_with_counts(&repos)
+        .await
+        .expect_err("read should time out");
+    blocker.rollback().await.expect("release lock");
+
+    assert!(matches!(err, RepoError::Timeout));
+    let response = repo_error_to_http("tests::db_timeouts", err).into_response();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn exhausted_pool_surfaces_repo_timeout(pool: PgPool) {
+    let repos = limited_repositories(&pool, Duration::from_secs(5)).await;
+
+    // The limited pool has one connection; holding it starves the read.
+    let held = repos
+        .pool()
+        .acquire()
+        .await
+        .expect("hold the only connection");
+
+    let err = TagsRepo::list_with_counts(&repos)
+        .await
+        .expect_err("read should time out waiting for a connection");
+    drop(held);
+
+    assert!(matches!(err, RepoError::Timeout));
+    TagsRepo::list_with_counts(&repos)
+        .await
+        .expect("read succeeds once the connection is free");
+}
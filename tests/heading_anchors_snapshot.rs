@@ -0,0 +1,74 @@
+use soffio::application::render::{
+    RenderOutput, RenderRequest, RenderService, RenderTarget, render_service,
+};
+
+fn render_post(markdown: &str) -> RenderOutput {
+    let request = RenderRequest::new(
+        RenderTarget::PostBody {
+            slug: "heading-anchors".into(),
+        },
+        markdown.to_string(),
+    );
+    render_service().render(&request).expect("render succeeds")
+}
+
+fn anchors(output: &RenderOutput) -> Vec<String> {
+    output
+        .sections
+        .as_deref()
+        .expect("post sections")
+        .iter()
+        .map(|section| section.anchor_slug.clone())
+        .collect()
+}
+
+#[test]
+fn heading_anchor_snapshot_matches() {
+    let output = render_post(include_str!("fixtures/heading_anchors.md"));
+
+    let expected = include_str!("fixtures/heading_anchors_post.html");
+    assert_eq!(expected.trim_end(), output.html.trim_end());
+}
+
+#[test]
+fn sections_share_the_heading_anchor_ids() {
+    let output = render_post(include_str!("fixtures/heading_anchors.md"));
+
+    assert_eq!(
+        anchors(&output),
+        [
+            "setup",
+            "using-cargo-test",
+            "setup-2",
+            "setup-2-2",
+            "the-new-api"
+        ]
+    );
+    let sections = output.sections.as_deref().expect("post sections");
+    assert_eq!(sections[1].heading_text, "Using cargo test");
+    assert_eq!(sections[4].heading_text, "The new API");
+    for section in sections {
+        let link = format!("href=\"#{}\"", section.anchor_slug);
+        assert!(
+            section.heading_html.contains(&link),
+            "{}",
+            section.anchor_slug
+        );
+    }
+}
+
+#[test]
+fn heading_anchors_survive_unrelated_edits() {
+    let markdown = include_str!("fixtures/heading_anchors.md");
+    let edited = markdown
+        .replace(
+            "First pass.",
+            "A rewritten first paragraph.\n\n```rust\nfn main() {}\n```",
+        )
+        .replace("Done.", "Done, with a [link](https://example.com).");
+
+    assert_eq!(
+        anchors(&render_post(markdown)),
+        anchors(&render_post(&edited))
+    );
+}
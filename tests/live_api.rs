@@ -156,6 +156,41 @@ async fn patch_json(
     Ok(())
 }
 
+/// POST a single-field post or page update, which requires an `If-Match`
+/// precondition.
+async fn post_json_if_match(
+    client: &Client,
+    base: &str,
+    key: &str,
+    path: &str,
+    expected: &[StatusCode],
+    if_match: &str,
+    payload: Value,
+) -> TestResult<()> {
+    let _ = request(client, base, Method::POST, path, key, expected, |r| {
+        r.header("If-Match", if_match).json(&payload)
+    })
+    .await?;
+    Ok(())
+}
+
+/// PATCH a post or page, which requires an `If-Match` precondition.
+async fn patch_json_if_match(
+    client: &Client,
+    base: &str,
+    key: &str,
+    path: &str,
+    expected: &[StatusCode],
+    if_match: &str,
+    payload: Value,
+) -> TestResult<()> {
+    let _ = request(client, base, Method::PATCH, path, key, expected, |r| {
+        r.header("If-Match", if_match).json(&payload)
+    })
+    .await?;
+    Ok(())
+}
+
 async fn post_multipart(
     client: &Client,
     base: &str,
@@ -112,12 +112,13 @@ pub(super) async fn exercise(ctx: &LiveApiContext<'_>) -> TestResult<PageBootstr
     )
     .await?;
 
-    patch_json(
+    patch_json_if_match(
         ctx.client,
         ctx.base,
         &ctx.config.keys.write,
         &format!("/api/v1/pages/{page_id}"),
         &[StatusCode::OK],
+        "*",
         json!({
             "slug": page_slug,
             "title": format!("Test Page {} updated", ctx.suffix),
@@ -126,12 +127,13 @@ pub(super) async fn exercise(ctx: &LiveApiContext<'_>) -> TestResult<PageBootstr
     )
     .await?;
 
-    post_json(
+    post_json_if_match(
         ctx.client,
         ctx.base,
         &ctx.config.keys.write,
         &format!("/api/v1/pages/{page_id}/body"),
         &[StatusCode::OK],
+        "*",
         json!({"body_markdown": "Updated body partial"}),
     )
     .await?;
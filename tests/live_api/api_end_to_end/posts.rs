@@ -84,12 +84,13 @@ pub(super) async fn exercise(
     )
     .await?;
 
-    patch_json(
+    patch_json_if_match(
         ctx.client,
         ctx.base,
         &ctx.config.keys.write,
         &format!("/api/v1/posts/{post_id}"),
         &[StatusCode::OK],
+        "*",
         json!({
             "slug": post_slug,
             "title": format!("Test Post {} updated", ctx.suffix),
@@ -109,22 +110,24 @@ pub(super) async fn exercise(
     )
     .await?;
 
-    post_json(
+    post_json_if_match(
         ctx.client,
         ctx.base,
         &ctx.config.keys.write,
         &format!("/api/v1/posts/{post_id}/body"),
         &[StatusCode::OK],
+        "*",
         json!({"body_markdown": "## body live"}),
     )
     .await?;
 
-    post_json(
+    post_json_if_match(
         ctx.client,
         ctx.base,
         &ctx.config.keys.write,
         &format!("/api/v1/posts/{post_id}/title"),
         &[StatusCode::OK],
+        "*",
         json!({"title": format!("Post {} partial", ctx.suffix)}),
     )
     .await?;
@@ -76,7 +76,10 @@ async fn live_api_post_body_renders_immediately() -> TestResult<()> {
         &format!("/api/v1/posts/{post_id}/body"),
         &config.keys.write,
         &[StatusCode::OK],
-        |r| r.json(&json!({"body_markdown": updated_body})),
+        |r| {
+            r.header("If-Match", "*")
+                .json(&json!({"body_markdown": updated_body}))
+        },
     )
     .await?;
 
@@ -128,12 +128,13 @@ async fn live_api_snapshots_cover_flow() -> TestResult<()> {
     let mutated_slug = format!("{post_slug}-mut");
     let mutated_title = format!("{original_title} changed");
     let mutated_body = "# mutated body";
-    patch_json(
+    patch_json_if_match(
         &client,
         &base,
         &config.keys.write,
         &format!("/api/v1/posts/{post_id}"),
         &[StatusCode::OK],
+        "*",
         json!({
             "slug": mutated_slug,
             "title": mutated_title,
@@ -169,7 +169,7 @@ async fn live_cache_precision_unrelated_post_unaffected() -> TestResult<()> {
         &config.keys.write,
         &[StatusCode::OK],
         |r| {
-            r.json(
+            r.header("If-Match", "*").json(
                 &json!({"body_markdown": format!("# Post A Updated\n\n{post_a_updated_marker}")}),
             )
         },
@@ -270,7 +270,10 @@ async fn live_cache_consistency_atom_feed() -> TestResult<()> {
         &format!("/api/v1/posts/{post_id}/title"),
         &config.keys.write,
         &[StatusCode::OK],
-        |r| r.json(&json!({"title": &updated_title})),
+        |r| {
+            r.header("If-Match", "*")
+                .json(&json!({"title": &updated_title}))
+        },
     )
     .await?;
 
@@ -227,7 +227,10 @@ async fn live_cache_consistency_feed() -> TestResult<()> {
         &format!("/api/v1/posts/{post_id}/title"),
         &config.keys.write,
         &[StatusCode::OK],
-        |r| r.json(&json!({"title": &updated_title})),
+        |r| {
+            r.header("If-Match", "*")
+                .json(&json!({"title": &updated_title}))
+        },
     )
     .await?;
 
@@ -67,7 +67,10 @@ async fn live_cache_consistency_post_update() -> TestResult<()> {
         &format!("/api/v1/posts/{post_id}/body"),
         &config.keys.write,
         &[StatusCode::OK],
-        |r| r.json(&json!({"body_markdown": format!("# Updated\n\n{updated_content}")})),
+        |r| {
+            r.header("If-Match", "*")
+                .json(&json!({"body_markdown": format!("# Updated\n\n{updated_content}")}))
+        },
     )
     .await?;
 
@@ -0,0 +1,354 @@
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use soffio::application::repos::UploadsRepo;
+use soffio::application::site::{ImportMode, TableCounts, export_site, import_site};
+use soffio::domain::entities::UploadRecord;
+use soffio::domain::uploads::UploadMetadata;
+use soffio::infra::db::PostgresRepositories;
+use soffio::infra::uploads::UploadStorage;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+const PNG_BYTES: &[u8] = &[
+    0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0xff, 0x10,
+];
+const PDF_BYTES: &[u8] = &[b'%', b'P', b'D', b'F', 0x00, 0x01, 0x02, 0xfe, 0xff, 0x7f];
+
+async fn store_upload(
+    repos: &PostgresRepositories,
+    storage: &UploadStorage,
+    filename: &str,
+    content_type: &str,
+    bytes: &'static [u8],
+) -> UploadRecord {
+    let stored = storage
+        .store(filename, Bytes::from_static(bytes))
+        .await
+        .expect("store upload file");
+    let record = UploadRecord {
+        id: Uuid::new_v4(),
+        filename: filename.into(),
+        content_type: content_type.into(),
+        size_bytes: stored.size_bytes,
+        checksum: stored.checksum,
+        stored_path: stored.stored_path,
+        metadata: UploadMetadata::default(),
+        created_at: OffsetDateTime::now_utc(),
+        last_verified_at: None,
+        checksum_mismatch: false,
+    };
+    repos
+        .insert_upload(record.clone())
+        .await
+        .expect("insert upload row");
+    record
+}
+
+async fn seed_post(pool: &PgPool, slug: &str, title: &str, tag_slug: &str) {
+    let post_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO posts (id, slug, title, excerpt, body_markdown) VALUES ($1, $2, $3, '', $3)",
+    )
+    .bind(post_id)
+    .bind(slug)
+    .bind(title)
+    .execute(pool)
+    .await
+    .expect("insert post");
+    sqlx::query(
+        r#"
+        INSERT INTO tags (id, slug, name) VALUES ($1, $2, $2)
+        ON CONFLICT (slug) DO NOTHING
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(tag_slug)
+    .execute(pool)
+    .await
+    .expect("insert tag");
+    sqlx::query("INSERT INTO post_tags (post_id, tag_id) SELECT $1, id FROM tags WHERE slug = $2")
+        .bind(post_id)
+        .bind(tag_slug)
+        .execute(pool)
+        .await
+        .expect("link tag");
+}
+
+async fn post_titles(pool: &PgPool) -> Vec<(String, String)> {
+    sqlx::query_as("SELECT slug, title FROM posts ORDER BY slug")
+        .fetch_all(pool)
+        .await
+        .expect("list posts")
+}
+
+async fn post_tag_slugs(pool: &PgPool, post_slug: &str) -> Vec<String> {
+    sqlx::query_scalar(
+        r#"
+        SELECT t.slug FROM post_tags pt
+        JOIN posts p ON p.id = pt.post_id
+        JOIN tags t ON t.id = pt.tag_id
+        WHERE p.slug = $1
+        ORDER BY t.slug
+        "#,
+    )
+    .bind(post_slug)
+    .fetch_all(pool)
+    .await
+    .expect("list post tags")
+}
+
+/// Exports `alpha` and `gamma`, then edits `alpha`, drops `gamma` and adds a
+/// local-only `local` post so each mode has a conflict, a gap and an extra row.
+async fn diverged_site(pool: &PgPool, dir: &std::path::Path) -> std::path::PathBuf {
+    let repos = PostgresRepositories::new(pool.clone());
+    seed_post(pool, "alpha", "Alpha", "rust").await;
+    seed_post(pool, "gamma", "Gamma", "rust").await;
+    let archive_path = dir.join("site.toml");
+    export_site(&repos, &archive_path, None)
+        .await
+        .expect("export site");
+
+    sqlx::query("UPDATE posts SET title = 'Alpha edited' WHERE slug = 'alpha'")
+        .execute(pool)
+        .await
+        .expect("edit alpha");
+    sqlx::query(
+        "DELETE FROM post_tags WHERE post_id IN (SELECT id FROM posts WHERE slug = 'alpha')",
+    )
+    .execute(pool)
+    .await
+    .expect("untag alpha");
+    sqlx::query("DELETE FROM posts WHERE slug = 'gamma'")
+        .execute(pool)
+        .await
+        .expect("drop gamma");
+    seed_post(pool, "local", "Local", "notes").await;
+    archive_path
+}
+
+fn counts(created: usize, updated: usize, skipped: usize) -> TableCounts {
+    TableCounts {
+        created,
+        updated,
+        skipped,
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn export_and_import_round_trip_upload_files(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let workdir = tempfile::tempdir().expect("tempdir");
+    let storage = UploadStorage::new(workdir.path().join("storage")).expect("storage");
+    let image = store_upload(&repos, &storage, "cat.png", "image/png", PNG_BYTES).await;
+    let document = store_upload(&repos, &storage, "doc.pdf", "application/pdf", PDF_BYTES).await;
+
+    let archive_path = workdir.path().join("site.toml");
+    export_site(&repos, &archive_path, Some(&storage))
+        .await
+        .expect("export site");
+    assert!(
+        workdir
+            .path()
+            .join("site-uploads")
+            .join(&image.stored_path)
+            .is_file()
+    );
+
+    // Lose one file and every row; the other file survives in storage.
+    storage
+        .delete(&document.stored_path)
+        .await
+        .expect("delete file");
+    sqlx::query("DELETE FROM uploads")
+        .execute(&pool)
+        .await
+        .expect("clear uploads");
+
+    let report = import_site(&repos, &storage, &archive_path, ImportMode::Fresh)
+        .await
+        .expect("import site");
+    assert_eq!(report.uploads_copied, 1);
+    assert_eq!(report.uploads_present, 1);
+    assert!(report.upload_failures.is_empty(), "{report:?}");
+
+    for (record, bytes) in [(&image, PNG_BYTES), (&document, PDF_BYTES)] {
+        let restored = repos
+            .find_upload(record.id)
+            .await
+            .expect("find upload")
+            .expect("upload row restored");
+        assert_eq!(restored.stored_path, record.stored_path);
+        assert_eq!(restored.checksum, record.checksum);
+
+        let contents = storage.read(&record.stored_path).await.expect("read file");
+        assert_eq!(contents.as_ref(), bytes);
+        assert_eq!(sha256_hex(&contents), record.checksum);
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn import_reports_upload_files_missing_from_the_archive(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let workdir = tempfile::tempdir().expect("tempdir");
+    let storage = UploadStorage::new(workdir.path().join("storage")).expect("storage");
+    let image = store_upload(&repos, &storage, "cat.png", "image/png", PNG_BYTES).await;
+    let document = store_upload(&repos, &storage, "doc.pdf", "application/pdf", PDF_BYTES).await;
+
+    let archive_path = workdir.path().join("site.toml");
+    export_site(&repos, &archive_path, Some(&storage))
+        .await
+        .expect("export site");
+    std::fs::remove_file(
+        workdir
+            .path()
+            .join("site-uploads")
+            .join(&document.stored_path),
+    )
+    .expect("remove archived file");
+
+    let target = UploadStorage::new(workdir.path().join("restored")).expect("target storage");
+    let report = import_site(&repos, &target, &archive_path, ImportMode::Fresh)
+        .await
+        .expect("import continues past missing files");
+
+    assert_eq!(report.uploads_copied, 1);
+    assert_eq!(report.upload_failures.len(), 1);
+    assert_eq!(report.upload_failures[0].stored_path, document.stored_path);
+    assert_eq!(
+        target
+            .read(&image.stored_path)
+            .await
+            .expect("read file")
+            .as_ref(),
+        PNG_BYTES
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn export_fails_on_corrupt_upload_metadata(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let workdir = tempfile::tempdir().expect("tempdir");
+    let storage = UploadStorage::new(workdir.path().join("storage")).expect("storage");
+    let image = store_upload(&repos, &storage, "cat.png", "image/png", PNG_BYTES).await;
+    sqlx::query("UPDATE uploads SET metadata = '\"broken\"' WHERE id = $1")
+        .bind(image.id)
+        .execute(&pool)
+        .await
+        .expect("corrupt metadata");
+
+    let archive_path = workdir.path().join("site.toml");
+    let err = export_site(&repos, &archive_path, Some(&storage))
+        .await
+        .expect_err("corrupt metadata rejected");
+    assert!(err.to_string().contains(&image.id.to_string()), "{err}");
+    assert!(!archive_path.exists());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn fresh_import_refuses_a_site_with_content(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let workdir = tempfile::tempdir().expect("tempdir");
+    let storage = UploadStorage::new(workdir.path().join("storage")).expect("storage");
+    let archive_path = diverged_site(&pool, workdir.path()).await;
+
+    import_site(&repos, &storage, &archive_path, ImportMode::Fresh)
+        .await
+        .expect_err("existing content rejected");
+
+    assert_eq!(
+        post_titles(&pool).await,
+        vec![
+            ("alpha".to_string(), "Alpha edited".to_string()),
+            ("local".to_string(), "Local".to_string()),
+        ]
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn merge_skip_import_keeps_existing_rows(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let workdir = tempfile::tempdir().expect("tempdir");
+    let storage = UploadStorage::new(workdir.path().join("storage")).expect("storage");
+    let archive_path = diverged_site(&pool, workdir.path()).await;
+
+    let report = import_site(&repos, &storage, &archive_path, ImportMode::MergeSkip)
+        .await
+        .expect("merge-skip import");
+
+    assert_eq!(report.table("posts"), Some(&counts(1, 0, 1)));
+    assert_eq!(report.table("tags"), Some(&counts(0, 0, 1)));
+    assert_eq!(report.table("post_tags"), Some(&counts(1, 0, 1)));
+    assert_eq!(report.table("site_settings"), Some(&counts(0, 0, 1)));
+    assert_eq!(
+        post_titles(&pool).await,
+        vec![
+            ("alpha".to_string(), "Alpha edited".to_string()),
+            ("gamma".to_string(), "Gamma".to_string()),
+            ("local".to_string(), "Local".to_string()),
+        ]
+    );
+    assert!(post_tag_slugs(&pool, "alpha").await.is_empty());
+    assert_eq!(post_tag_slugs(&pool, "gamma").await, vec!["rust"]);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn merge_overwrite_import_replaces_conflicting_rows(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let workdir = tempfile::tempdir().expect("tempdir");
+    let storage = UploadStorage::new(workdir.path().join("storage")).expect("storage");
+    let archive_path = diverged_site(&pool, workdir.path()).await;
+
+    let report = import_site(&repos, &storage, &archive_path, ImportMode::MergeOverwrite)
+        .await
+        .expect("merge-overwrite import");
+
+    assert_eq!(report.table("posts"), Some(&counts(1, 1, 0)));
+    assert_eq!(report.table("tags"), Some(&counts(0, 1, 0)));
+    assert_eq!(report.table("post_tags"), Some(&counts(2, 0, 0)));
+    assert_eq!(report.table("site_settings"), Some(&counts(0, 1, 0)));
+    assert_eq!(
+        post_titles(&pool).await,
+        vec![
+            ("alpha".to_string(), "Alpha".to_string()),
+            ("gamma".to_string(), "Gamma".to_string()),
+            ("local".to_string(), "Local".to_string()),
+        ]
+    );
+    assert_eq!(post_tag_slugs(&pool, "alpha").await, vec!["rust"]);
+    assert_eq!(post_tag_slugs(&pool, "local").await, vec!["notes"]);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn wipe_import_replaces_all_content(pool: PgPool) {
+    let repos = PostgresRepositories::new(pool.clone());
+    let workdir = tempfile::tempdir().expect("tempdir");
+    let storage = UploadStorage::new(workdir.path().join("storage")).expect("storage");
+    let archive_path = diverged_site(&pool, workdir.path()).await;
+
+    let report = import_site(&repos, &storage, &archive_path, ImportMode::Wipe)
+        .await
+        .expect("wipe import");
+
+    assert_eq!(report.table("posts"), Some(&counts(2, 0, 0)));
+    assert_eq!(report.table("tags"), Some(&counts(1, 0, 0)));
+    assert_eq!(
+        post_titles(&pool).await,
+        vec![
+            ("alpha".to_string(), "Alpha".to_string()),
+            ("gamma".to_string(), "Gamma".to_string()),
+        ]
+    );
+    let notes_tags: i64 = sqlx::query_scalar("SELECT count(*) FROM tags WHERE slug = 'notes'")
+        .fetch_one(&pool)
+        .await
+        .expect("count tags");
+    assert_eq!(notes_tags, 0);
+}
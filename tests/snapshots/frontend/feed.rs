@@ -73,7 +73,7 @@ async fn snapshot_index_page() {
         .await
         .expect("page context");
     let view = apply_layout(context).await;
-    let html = IndexTemplate { view }.render().expect("render index");
+    let html = redact_asset_hashes(&IndexTemplate { view }.render().expect("render index"));
     insta::assert_snapshot!("page_index", html);
 }
 
@@ -86,14 +86,14 @@ async fn snapshot_post_detail() {
         .expect("detail fetch")
         .expect("detail exists");
     let view = apply_layout(detail).await;
-    let html = PostTemplate { view }.render().expect("render post detail");
+    let html = redact_asset_hashes(&PostTemplate { view }.render().expect("render post detail"));
     insta::assert_snapshot!("post_incremental_build_pipeline", html);
 }
 
 #[tokio::test]
 async fn snapshot_error_page() {
     let view = apply_layout(ErrorPageView::not_found()).await;
-    let html = ErrorTemplate { view }.render().expect("render error page");
+    let html = redact_asset_hashes(&ErrorTemplate { view }.render().expect("render error page"));
     insta::assert_snapshot!("page_error_not_found", html);
 }
 
@@ -118,6 +118,6 @@ async fn snapshot_about_page() {
         .expect("about page fetch")
         .expect("about page present");
     let view = apply_layout(page).await;
-    let html = PageTemplate { view }.render().expect("render about page");
+    let html = redact_asset_hashes(&PageTemplate { view }.render().expect("render about page"));
     insta::assert_snapshot!("page_about", html);
 }
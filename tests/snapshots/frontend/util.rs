@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use axum::body::Body;
+use chrono_tz::Tz;
 use http_body_util::BodyExt;
 use std::collections::{BTreeMap, BTreeSet, HashMap, hash_map::DefaultHasher};
 use std::hash::{Hash, Hasher};
@@ -12,32 +13,45 @@ pub use soffio::application::feed as feed_render;
 pub use soffio::application::feed::{FeedFilter, FeedService};
 use soffio::application::page::PageService;
 use soffio::application::pagination::{
-    CursorPage, NavigationCursor, PageCursor, PageRequest, PostCursor, TagCursor,
+    CursorKeys, CursorPage, NavigationCursor, PageCursor, PageRequest, PostCursor, TagCursor,
 };
 use soffio::application::repos::{
-    NavigationQueryFilter, NavigationRepo, PageQueryFilter, PagesRepo, PostListScope,
-    PostQueryFilter, PostTagCount, PostsRepo, RepoError, SectionsRepo, SettingsRepo, TagListRecord,
-    TagQueryFilter, TagWithCount, TagsRepo,
+    FeedAggregates, NavigationQueryFilter, NavigationRepo, PageQueryFilter, PagesRepo,
+    PostListScope, PostQueryFilter, PostTagCount, PostsRepo, RepoError, SectionsRepo, SettingsRepo,
+    TagListRecord, TagQueryFilter, TagWithCount, TagsRepo,
 };
 use soffio::domain::entities::{
-    NavigationItemRecord, PageRecord, PostRecord, PostSectionRecord, SiteSettingsRecord, TagRecord,
+    CalendarRecord, NavigationItemRecord, PageRecord, PostRecord, PostSectionRecord,
+    ScheduledRecord, SiteSettingsRecord, TagRecord, TranslationRecord,
 };
-use soffio::domain::types::{NavigationDestinationType, PageStatus, PostStatus};
+use soffio::domain::types::{NavigationDestinationType, PageStatus, PostStatus, RenderStatus};
 pub use soffio::domain::{navigation, pages, posts};
 use soffio::presentation::views::LayoutContext;
 
+/// Cursors are signed with a fixed secret so the tokens in rendered pages
+/// are the same on every run.
+fn cursor_keys() -> CursorKeys {
+    CursorKeys::new(b"soffio-snapshot-tests".to_vec())
+}
+
+/// The fixture repo behind every service.
+fn static_repo() -> Arc<StaticContentRepo> {
+    Arc::new(StaticContentRepo::new())
+}
+
 pub fn feed_service() -> FeedService {
-    let repo = Arc::new(StaticContentRepo::new());
+    let repo = static_repo();
     FeedService::new(repo.clone(), repo.clone(), repo.clone(), repo, None)
+        .with_cursor_keys(cursor_keys())
 }
 
 pub fn chrome_service() -> ChromeService {
-    let repo = Arc::new(StaticContentRepo::new());
-    ChromeService::new(repo.clone(), repo, None)
+    let repo = static_repo();
+    ChromeService::new(repo.clone(), repo.clone(), repo, None).with_cursor_keys(cursor_keys())
 }
 
 pub fn page_service() -> PageService {
-    let repo = Arc::new(StaticContentRepo::new());
+    let repo = static_repo();
     PageService::new(repo, None)
 }
 
@@ -47,6 +61,32 @@ pub async fn apply_layout<T>(content: T) -> LayoutContext<T> {
     LayoutContext::new(layout, content)
 }
 
+/// Replace the content hashes in static asset URLs with `[hash]`, so page
+/// snapshots survive edits to the assets themselves.
+pub fn redact_asset_hashes(html: &str) -> String {
+    let mut redacted = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find("/static/") {
+        let (before, tail) = rest.split_at(start);
+        let end = tail.find(['"', '\'', ' ']).unwrap_or(tail.len());
+        let url: Vec<&str> = tail[..end]
+            .split('.')
+            .map(|part| {
+                let hashed = part.len() == 8
+                    && part
+                        .bytes()
+                        .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte));
+                if hashed { "[hash]" } else { part }
+            })
+            .collect();
+        redacted.push_str(before);
+        redacted.push_str(&url.join("."));
+        rest = &tail[end..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
 pub async fn body_to_string(body: Body) -> String {
     let bytes = body.collect().await.expect("collect body").to_bytes();
     String::from_utf8(bytes.to_vec()).expect("utf8 body")
@@ -89,6 +129,7 @@ impl StaticContentRepo {
                 (None, Some(month)) => posts::month_key_for(post.date) == *month,
                 (None, None) => true,
             })
+            .filter(|post| filter.year.is_none_or(|year| post.date.year() == year))
             .collect()
     }
 
@@ -123,9 +164,11 @@ impl StaticContentRepo {
             slug: post.slug.to_string(),
             title: post.title.to_string(),
             excerpt: post.excerpt.to_string(),
+            excerpt_generated: false,
             body_markdown: String::new(),
             status: PostStatus::Published,
             pinned: false,
+            pin_order: 0,
             scheduled_at: None,
             published_at: Some(published),
             archived_at: None,
@@ -138,6 +181,11 @@ impl StaticContentRepo {
                     .join("");
                 format!("<ul>{}</ul>", items)
             }),
+            lang: None,
+            translation_group: None,
+            render_status: RenderStatus::Ok,
+            render_error: None,
+            render_failed_at: None,
             created_at: published,
             updated_at: published,
         }
@@ -258,6 +306,12 @@ impl StaticContentRepo {
                 scheduled_at: None,
                 published_at: Some(OffsetDateTime::UNIX_EPOCH),
                 archived_at: None,
+                lang: None,
+                translation_group: None,
+                template: "default".to_string(),
+                render_status: RenderStatus::Ok,
+                render_error: None,
+                render_failed_at: None,
                 created_at: OffsetDateTime::UNIX_EPOCH,
                 updated_at: OffsetDateTime::UNIX_EPOCH,
             })
@@ -27,9 +27,9 @@ impl NavigationRepo for StaticContentRepo {
             .take(limit)
             .cloned()
             .collect::<Vec<_>>();
-        let next_cursor = records
-            .get(start + limit)
-            .map(|nav| NavigationCursor::new(nav.sort_order, nav.created_at, nav.id).encode());
+        let next_cursor = records.get(start + limit).map(|nav| {
+            NavigationCursor::new(nav.sort_order, nav.created_at, nav.id).encode(&cursor_keys())
+        });
 
         Ok(CursorPage::new(slice, next_cursor))
     }
@@ -69,4 +69,15 @@ impl NavigationRepo for StaticContentRepo {
             .into_iter()
             .find(|record| record.id == id))
     }
+
+    async fn list_visible_links_to_page(
+        &self,
+        page_id: Uuid,
+    ) -> Result<Vec<NavigationItemRecord>, RepoError> {
+        Ok(self
+            .navigation_records()
+            .into_iter()
+            .filter(|record| record.visible && record.destination_page_id == Some(page_id))
+            .collect())
+    }
 }
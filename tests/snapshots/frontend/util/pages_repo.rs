@@ -26,7 +26,7 @@ impl PagesRepo for StaticContentRepo {
             .collect::<Vec<_>>();
         let next_cursor = pages
             .get(start + limit)
-            .map(|p| PageCursor::new(p.created_at, p.id).encode());
+            .map(|p| PageCursor::new(p.created_at, p.id).encode(&cursor_keys()));
 
         Ok(CursorPage::new(slice, next_cursor))
     }
@@ -60,4 +60,22 @@ impl PagesRepo for StaticContentRepo {
     ) -> Result<Vec<posts::MonthCount>, RepoError> {
         Ok(Vec::new())
     }
+    async fn list_page_translations(
+        &self,
+        _translation_group: Uuid,
+    ) -> Result<Vec<TranslationRecord>, RepoError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_scheduled_pages(&self, _limit: u32) -> Result<Vec<ScheduledRecord>, RepoError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_calendar_pages(
+        &self,
+        _from: OffsetDateTime,
+        _to: OffsetDateTime,
+    ) -> Result<Vec<CalendarRecord>, RepoError> {
+        Ok(Vec::new())
+    }
 }
@@ -35,8 +35,9 @@ impl PostsRepo for StaticContentRepo {
                 post.date.with_time(time!(00:00:00)).assume_utc(),
                 Self::post_uuid(post.slug),
                 false,
+                0,
             )
-            .encode()
+            .encode(&cursor_keys())
         });
 
         Ok(CursorPage::new(records, next_cursor))
@@ -102,6 +103,28 @@ impl PostsRepo for StaticContentRepo {
         Ok(idx as u64)
     }
 
+    async fn list_local_month_counts(
+        &self,
+        scope: PostListScope,
+        filter: &PostQueryFilter,
+        _time_zone: Tz,
+    ) -> Result<Vec<posts::MonthCount>, RepoError> {
+        // Fixture posts carry dates without a time of day.
+        PostsRepo::list_month_counts(self, scope, filter).await
+    }
+
+    async fn list_public_aggregates(&self) -> Result<FeedAggregates, RepoError> {
+        Ok(FeedAggregates {
+            tags: TagsRepo::list_with_counts(self).await?,
+            months: PostsRepo::list_month_counts(
+                self,
+                PostListScope::Public,
+                &PostQueryFilter::default(),
+            )
+            .await?,
+        })
+    }
+
     async fn list_tag_counts(
         &self,
         scope: PostListScope,
@@ -123,4 +146,22 @@ impl PostsRepo for StaticContentRepo {
             })
             .collect())
     }
+    async fn list_post_translations(
+        &self,
+        _translation_group: Uuid,
+    ) -> Result<Vec<TranslationRecord>, RepoError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_scheduled_posts(&self, _limit: u32) -> Result<Vec<ScheduledRecord>, RepoError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_calendar_posts(
+        &self,
+        _from: OffsetDateTime,
+        _to: OffsetDateTime,
+    ) -> Result<Vec<CalendarRecord>, RepoError> {
+        Ok(Vec::new())
+    }
 }
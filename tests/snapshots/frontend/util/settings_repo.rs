@@ -17,11 +17,21 @@ impl SettingsRepo for StaticContentRepo {
             public_site_url: "http://localhost:3000/".to_string(),
             favicon_svg: "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 16 16\"></svg>"
                 .to_string(),
+            favicon_upload_id: None,
             timezone: chrono_tz::Asia::Shanghai,
+            date_format: "%B %-d, %Y".to_string(),
             meta_title: "Soffio".to_string(),
             meta_description: "Whispers on motion, balance, and form.".to_string(),
             og_title: "Soffio".to_string(),
             og_description: "Traces of motion, balance, and form in continual drift.".to_string(),
+            maintenance_enabled: false,
+            maintenance_html: "<p>Maintenance</p>".to_string(),
+            maintenance_retry_after_secs: 300,
+            feed_content_mode: soffio::domain::types::FeedContentMode::Excerpt,
+            feed_item_limit: 20,
+            homepage_mode: soffio::domain::types::HomepageMode::Feed,
+            homepage_page_id: None,
+            homepage_layout: soffio::domain::types::HomepageLayout::List,
             updated_at: OffsetDateTime::UNIX_EPOCH,
         })
     }
@@ -68,7 +68,7 @@ impl TagsRepo for StaticContentRepo {
 
         let next_cursor = tags
             .get(start + limit)
-            .map(|tag| TagCursor::new(tag.pinned, tag.created_at, tag.id).encode());
+            .map(|tag| TagCursor::new(tag.pinned, tag.created_at, tag.id).encode(&cursor_keys()));
 
         Ok(CursorPage::new(records, next_cursor))
     }
@@ -126,6 +126,20 @@ impl TagsRepo for StaticContentRepo {
         Ok(post.tags.iter().map(|slug| self.tag_record(slug)).collect())
     }
 
+    async fn tags_for_posts(
+        &self,
+        post_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<TagRecord>>, RepoError> {
+        let mut tags = HashMap::new();
+        for &post_id in post_ids {
+            let post_tags = self.list_for_post(post_id).await?;
+            if !post_tags.is_empty() {
+                tags.insert(post_id, post_tags);
+            }
+        }
+        Ok(tags)
+    }
+
     async fn list_with_counts(&self) -> Result<Vec<TagWithCount>, RepoError> {
         let mut counts: BTreeMap<&str, i64> = BTreeMap::new();
         for post in posts::all() {
@@ -1,263 +1,19 @@
-use std::collections::HashMap;
-use std::sync::Arc;
-
-use async_trait::async_trait;
 use axum::body::to_bytes;
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header::IF_MATCH};
 use axum::response::IntoResponse;
 use sqlx::PgPool;
-use time::OffsetDateTime;
-use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use soffio::application::admin::audit::AdminAuditService;
-use soffio::application::admin::navigation::AdminNavigationService;
-use soffio::application::admin::pages::AdminPageService;
-use soffio::application::admin::posts::AdminPostService;
-use soffio::application::admin::settings::AdminSettingsService;
-use soffio::application::admin::snapshots::AdminSnapshotService;
-use soffio::application::admin::tags::AdminTagService;
-use soffio::application::admin::uploads::AdminUploadService;
-use soffio::application::api_keys::{ApiKeyService, IssueApiKeyCommand};
-use soffio::application::chrome::ChromeService;
-use soffio::application::feed::FeedService;
-use soffio::application::page::PageService;
-use soffio::application::pagination::{CursorPage, JobCursor, PageRequest};
-use soffio::application::repos::{
-    ApiKeysRepo, AuditRepo, JobQueryFilter, JobsRepo, NavigationRepo, NavigationWriteRepo,
-    NewJobRecord, PagesRepo, PagesWriteRepo, PostsRepo, PostsWriteRepo, RepoError, SectionsRepo,
-    SettingsRepo, TagsRepo, TagsWriteRepo, UpdateJobStateParams, UploadsRepo,
-};
-use soffio::domain::api_keys::ApiScope;
-use soffio::domain::entities::JobRecord;
-use soffio::domain::types::JobState;
-use soffio::infra::db::PostgresRepositories;
 use soffio::infra::http::api::state::ApiState;
-use soffio::infra::uploads::UploadStorage;
-
-#[derive(Default)]
-struct ImmediateJobsRepo {
-    jobs: Mutex<HashMap<String, JobRecord>>,
-}
-
-#[async_trait]
-impl JobsRepo for ImmediateJobsRepo {
-    async fn enqueue_job(&self, job: NewJobRecord) -> Result<String, RepoError> {
-        let id = uuid::Uuid::new_v4().to_string();
-        let record = JobRecord {
-            id: id.clone(),
-            job_type: job.job_type,
-            payload: job.payload,
-            state: JobState::Done,
-            attempts: 1,
-            max_attempts: job.max_attempts,
-            run_at: job.run_at,
-            lock_at: None,
-            lock_by: None,
-            done_at: Some(OffsetDateTime::now_utc()),
-            last_error: None,
-            priority: job.priority,
-        };
-        self.jobs.lock().await.insert(id.clone(), record);
-        Ok(id)
-    }
-
-    async fn update_job_state(&self, params: UpdateJobStateParams) -> Result<(), RepoError> {
-        let mut jobs = self.jobs.lock().await;
-        if let Some(job) = jobs.get_mut(&params.id) {
-            job.state = params.state;
-            job.last_error = params.last_error.clone();
-            if let Some(attempts) = params.attempts {
-                job.attempts = attempts;
-            }
-            if let Some(run_at) = params.run_at {
-                job.run_at = run_at;
-            }
-            if let Some(priority) = params.priority {
-                job.priority = priority;
-            }
-            job.done_at = Some(OffsetDateTime::now_utc());
-        }
-        Ok(())
-    }
 
-    async fn find_job(&self, id: &str) -> Result<Option<JobRecord>, RepoError> {
-        Ok(self.jobs.lock().await.get(id).cloned())
-    }
-
-    async fn list_jobs(
-        &self,
-        _filter: &JobQueryFilter,
-        _page: PageRequest<JobCursor>,
-    ) -> Result<CursorPage<JobRecord>, RepoError> {
-        let jobs = self.jobs.lock().await;
-        Ok(CursorPage {
-            items: jobs.values().cloned().collect(),
-            next_cursor: None,
-        })
-    }
-
-    async fn count_jobs(&self, filter: &JobQueryFilter) -> Result<u64, RepoError> {
-        let jobs = self.jobs.lock().await;
-        let count = jobs
-            .values()
-            .filter(|job| {
-                if let Some(state) = filter.state
-                    && job.state != state
-                {
-                    return false;
-                }
-                if let Some(job_type) = filter.job_type
-                    && job.job_type != job_type
-                {
-                    return false;
-                }
-                if let Some(search) = &filter.search {
-                    let search_lower = search.to_lowercase();
-                    let payload_str = job.payload.to_string().to_lowercase();
-                    let last_error_str = job
-                        .last_error
-                        .as_ref()
-                        .map(|e| e.to_lowercase())
-                        .unwrap_or_default();
-                    if !payload_str.contains(&search_lower)
-                        && !last_error_str.contains(&search_lower)
-                    {
-                        return false;
-                    }
-                }
-                true
-            })
-            .count();
-        Ok(count as u64)
-    }
-}
+use super::test_app::TestApp;
 
+/// The state and all-scope token of a fresh [`TestApp`], for tests written
+/// before it; new tests should use [`TestApp`] directly.
 pub async fn build_state(pool: PgPool) -> (ApiState, String) {
-    let repos = Arc::new(PostgresRepositories::new(pool));
-
-    let posts_repo: Arc<dyn PostsRepo> = repos.clone();
-    let posts_write_repo: Arc<dyn PostsWriteRepo> = repos.clone();
-    let sections_repo: Arc<dyn SectionsRepo> = repos.clone();
-    let tags_repo: Arc<dyn TagsRepo> = repos.clone();
-    let tags_write_repo: Arc<dyn TagsWriteRepo> = repos.clone();
-    let settings_repo: Arc<dyn SettingsRepo> = repos.clone();
-    let navigation_repo: Arc<dyn NavigationRepo> = repos.clone();
-    let navigation_write_repo: Arc<dyn NavigationWriteRepo> = repos.clone();
-    let pages_repo: Arc<dyn PagesRepo> = repos.clone();
-    let pages_write_repo: Arc<dyn PagesWriteRepo> = repos.clone();
-    let uploads_repo: Arc<dyn UploadsRepo> = repos.clone();
-    let audit_repo: Arc<dyn AuditRepo> = repos.clone();
-    let jobs_repo: Arc<dyn JobsRepo> = Arc::new(ImmediateJobsRepo::default());
-    let api_keys_repo: Arc<dyn ApiKeysRepo> = repos.clone();
-
-    let _feed_service = Arc::new(FeedService::new(
-        posts_repo.clone(),
-        sections_repo.clone(),
-        tags_repo.clone(),
-        settings_repo.clone(),
-        None,
-    ));
-    let _page_service = Arc::new(PageService::new(pages_repo.clone(), None));
-    let _chrome_service = Arc::new(ChromeService::new(
-        navigation_repo.clone(),
-        settings_repo.clone(),
-        None,
-    ));
-
-    let audit_service = AdminAuditService::new(audit_repo.clone());
-    let admin_post_service = Arc::new(AdminPostService::new(
-        posts_repo.clone(),
-        posts_write_repo.clone(),
-        sections_repo.clone(),
-        jobs_repo.clone(),
-        tags_repo.clone(),
-        audit_service.clone(),
-    ));
-    let admin_page_service = Arc::new(AdminPageService::new(
-        pages_repo.clone(),
-        pages_write_repo.clone(),
-        jobs_repo.clone(),
-        audit_service.clone(),
-        settings_repo.clone(),
-    ));
-    let admin_tag_service = Arc::new(AdminTagService::new(
-        tags_repo.clone(),
-        tags_write_repo.clone(),
-        audit_service.clone(),
-    ));
-    let admin_navigation_service = Arc::new(AdminNavigationService::new(
-        navigation_repo.clone(),
-        navigation_write_repo.clone(),
-        pages_repo.clone(),
-        audit_service.clone(),
-    ));
-    let admin_settings_service = Arc::new(AdminSettingsService::new(
-        settings_repo.clone(),
-        audit_service.clone(),
-    ));
-    let admin_upload_service = Arc::new(AdminUploadService::new(
-        uploads_repo.clone(),
-        audit_service.clone(),
-    ));
-    let admin_job_service = Arc::new(soffio::application::admin::jobs::AdminJobService::new(
-        jobs_repo.clone(),
-        audit_service.clone(),
-    ));
-    let admin_audit_service = Arc::new(audit_service);
-    let api_key_service = Arc::new(ApiKeyService::new(api_keys_repo.clone()));
-
-    let upload_storage =
-        Arc::new(UploadStorage::new(std::path::PathBuf::from("uploads")).expect("upload storage"));
-
-    let api_state = ApiState {
-        api_keys: api_key_service.clone(),
-        posts: admin_post_service,
-        pages: admin_page_service,
-        tags: admin_tag_service,
-        navigation: admin_navigation_service,
-        uploads: admin_upload_service,
-        settings: admin_settings_service,
-        jobs: admin_job_service,
-        audit: admin_audit_service,
-        snapshots: Arc::new(AdminSnapshotService::new(repos.clone())),
-        db: repos.clone(),
-        upload_storage,
-        rate_limiter: Arc::new(soffio::infra::http::api::rate_limit::ApiRateLimiter::new(
-            std::time::Duration::from_secs(60),
-            200,
-        )),
-    };
-
-    let issued = api_key_service
-        .issue(IssueApiKeyCommand {
-            name: "test".to_string(),
-            description: None,
-            scopes: vec![
-                ApiScope::PostRead,
-                ApiScope::PostWrite,
-                ApiScope::PageRead,
-                ApiScope::PageWrite,
-                ApiScope::TagRead,
-                ApiScope::TagWrite,
-                ApiScope::NavigationRead,
-                ApiScope::NavigationWrite,
-                ApiScope::UploadRead,
-                ApiScope::UploadWrite,
-                ApiScope::SettingsRead,
-                ApiScope::SettingsWrite,
-                ApiScope::JobRead,
-                ApiScope::AuditRead,
-                ApiScope::SnapshotRead,
-                ApiScope::SnapshotWrite,
-            ],
-            expires_in: None,
-            created_by: "tests".to_string(),
-        })
-        .await
-        .unwrap();
-
-    (api_state, issued.token)
+    let app = TestApp::new(pool).await;
+    let token = app.token().to_string();
+    (app.api, token)
 }
 
 pub async fn response_json(resp: impl IntoResponse) -> (StatusCode, serde_json::Value) {
@@ -280,3 +36,13 @@ pub fn string_field<'a>(value: &'a serde_json::Value, key: &str) -> &'a str {
 pub fn uuid_field(value: &serde_json::Value, key: &str) -> Uuid {
     Uuid::parse_str(string_field(value, key)).expect("uuid field")
 }
+
+/// Headers carrying an `If-Match` precondition for content updates.
+pub fn if_match(tag: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        IF_MATCH,
+        HeaderValue::from_str(tag).expect("if-match header"),
+    );
+    headers
+}
@@ -0,0 +1,249 @@
+//! Fixture builders for content a test needs but is not about.
+//!
+//! Each builder starts from a status, overrides only what the test cares
+//! about, and creates the record through the admin services of a
+//! [`TestApp`], so audit entries, excerpts, and slugs come out as they
+//! would from the API.
+
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use soffio::application::admin::pages::CreatePageCommand;
+use soffio::application::admin::posts::CreatePostCommand;
+use soffio::application::repos::{CreateTagParams, TagsRepo, TagsWriteRepo};
+use soffio::domain::entities::{PageRecord, PostRecord};
+use soffio::domain::types::{PageStatus, PostStatus};
+
+use super::test_app::TestApp;
+
+/// Actor recorded for fixture writes.
+pub const ACTOR: &str = "test";
+
+/// A post to create, e.g.
+/// `FixturePost::published().with_tags(["rust"]).create(&app)`.
+///
+/// The title defaults to the slug when one is given; without either the
+/// service derives a unique slug from a generic title.
+pub struct FixturePost {
+    slug: Option<String>,
+    title: Option<String>,
+    excerpt: String,
+    body_markdown: String,
+    summary_markdown: Option<String>,
+    status: PostStatus,
+    pinned: bool,
+    published_at: Option<OffsetDateTime>,
+    tags: Vec<String>,
+}
+
+impl FixturePost {
+    /// A post published now.
+    pub fn published() -> Self {
+        Self::with_status(PostStatus::Published, Some(OffsetDateTime::now_utc()))
+    }
+
+    pub fn draft() -> Self {
+        Self::with_status(PostStatus::Draft, None)
+    }
+
+    fn with_status(status: PostStatus, published_at: Option<OffsetDateTime>) -> Self {
+        Self {
+            slug: None,
+            title: None,
+            excerpt: "excerpt".to_string(),
+            body_markdown: "# body".to_string(),
+            summary_markdown: None,
+            status,
+            pinned: false,
+            published_at,
+            tags: Vec::new(),
+        }
+    }
+
+    pub fn slug(mut self, slug: &str) -> Self {
+        self.slug = Some(slug.to_string());
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// An empty excerpt is derived from the body.
+    pub fn excerpt(mut self, excerpt: &str) -> Self {
+        self.excerpt = excerpt.to_string();
+        self
+    }
+
+    pub fn body(mut self, markdown: &str) -> Self {
+        self.body_markdown = markdown.to_string();
+        self
+    }
+
+    pub fn summary(mut self, markdown: &str) -> Self {
+        self.summary_markdown = Some(markdown.to_string());
+        self
+    }
+
+    pub fn pinned(mut self) -> Self {
+        self.pinned = true;
+        self
+    }
+
+    pub fn published_at(mut self, published_at: OffsetDateTime) -> Self {
+        self.published_at = Some(published_at);
+        self
+    }
+
+    /// Tags the post, creating tags that do not exist yet with the slug as
+    /// their name.
+    pub fn with_tags<I, S>(mut self, slugs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags.extend(slugs.into_iter().map(Into::into));
+        self
+    }
+
+    pub async fn create(self, app: &TestApp) -> PostRecord {
+        let title = self
+            .title
+            .or_else(|| self.slug.clone())
+            .unwrap_or_else(|| "Fixture post".to_string());
+        let post = app
+            .api
+            .posts
+            .create_post(
+                ACTOR,
+                CreatePostCommand {
+                    slug: self.slug,
+                    title,
+                    excerpt: self.excerpt,
+                    body_markdown: self.body_markdown,
+                    summary_markdown: self.summary_markdown,
+                    status: self.status,
+                    pinned: self.pinned,
+                    scheduled_at: None,
+                    published_at: self.published_at,
+                    archived_at: None,
+                },
+            )
+            .await
+            .expect("create fixture post");
+
+        if !self.tags.is_empty() {
+            let mut tag_ids = Vec::with_capacity(self.tags.len());
+            for slug in &self.tags {
+                tag_ids.push(fixture_tag(app, slug).await);
+            }
+            app.api
+                .posts
+                .replace_tags(ACTOR, &post, &tag_ids)
+                .await
+                .expect("tag fixture post");
+        }
+        post
+    }
+}
+
+async fn fixture_tag(app: &TestApp, slug: &str) -> Uuid {
+    let repos = app.api.db.as_ref();
+    if let Some(tag) = TagsRepo::find_by_slug(repos, slug)
+        .await
+        .expect("find fixture tag")
+    {
+        return tag.id;
+    }
+    TagsWriteRepo::create_tag(
+        repos,
+        CreateTagParams {
+            slug: slug.to_string(),
+            name: slug.to_string(),
+            description: None,
+            pinned: false,
+        },
+    )
+    .await
+    .expect("create fixture tag")
+    .id
+}
+
+/// A page to create, e.g. `FixturePage::draft().title("About").create(&app)`.
+///
+/// The title defaults to the slug when one is given.
+pub struct FixturePage {
+    slug: Option<String>,
+    title: Option<String>,
+    body_markdown: String,
+    status: PageStatus,
+    published_at: Option<OffsetDateTime>,
+    template: Option<String>,
+}
+
+impl FixturePage {
+    /// A page published now.
+    pub fn published() -> Self {
+        Self::with_status(PageStatus::Published, Some(OffsetDateTime::now_utc()))
+    }
+
+    pub fn draft() -> Self {
+        Self::with_status(PageStatus::Draft, None)
+    }
+
+    fn with_status(status: PageStatus, published_at: Option<OffsetDateTime>) -> Self {
+        Self {
+            slug: None,
+            title: None,
+            body_markdown: "# body".to_string(),
+            status,
+            published_at,
+            template: None,
+        }
+    }
+
+    pub fn slug(mut self, slug: &str) -> Self {
+        self.slug = Some(slug.to_string());
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn body(mut self, markdown: &str) -> Self {
+        self.body_markdown = markdown.to_string();
+        self
+    }
+
+    pub fn template(mut self, template: &str) -> Self {
+        self.template = Some(template.to_string());
+        self
+    }
+
+    pub async fn create(self, app: &TestApp) -> PageRecord {
+        let title = self
+            .title
+            .or_else(|| self.slug.clone())
+            .unwrap_or_else(|| "Fixture page".to_string());
+        app.api
+            .pages
+            .create_page(
+                ACTOR,
+                CreatePageCommand {
+                    slug: self.slug,
+                    title,
+                    body_markdown: self.body_markdown,
+                    status: self.status,
+                    scheduled_at: None,
+                    published_at: self.published_at,
+                    archived_at: None,
+                    template: self.template,
+                },
+            )
+            .await
+            .expect("create fixture page")
+    }
+}
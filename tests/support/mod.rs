@@ -1 +1,25 @@
+//! Shared support for the database-backed integration tests.
+//!
+//! New tests start from a [`test_app::TestApp`] over the `sqlx::test` pool,
+//! create the content they need with the [`fixtures`] builders, and call
+//! handlers with a principal holding just the scopes under test:
+//!
+//! ```ignore
+//! #[sqlx::test(migrations = "./migrations")]
+//! async fn readers_cannot_publish(pool: PgPool) {
+//!     let app = TestApp::new(pool).await;
+//!     let post = FixturePost::draft().with_tags(["rust"]).create(&app).await;
+//!     let reader = app.principal_with(&[ApiScope::PostRead]).await;
+//!     // handlers::update_post_status(app.state(), Extension(reader), ...)
+//! }
+//! ```
+//!
+//! [`api_harness`] holds the request and response helpers, and
+//! `build_state` for tests written before [`test_app::TestApp`].
+
+// Each test binary that includes this module uses only part of it.
+#![allow(dead_code)]
+
 pub mod api_harness;
+pub mod fixtures;
+pub mod test_app;
@@ -0,0 +1,200 @@
+//! [`TestApp`]: the application services wired over one `sqlx::test`
+//! database the way `serve` wires them, minus the listeners.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use clap::Parser;
+use sqlx::PgPool;
+
+use soffio::application::api_keys::{ApiPrincipal, IssueApiKeyCommand};
+use soffio::application::jobs::JobWorkerContext;
+use soffio::config::{self, CliArgs};
+use soffio::domain::api_keys::ApiScope;
+use soffio::domain::routes::BasePath;
+use soffio::infra::context::{ReloadSource, Repositories, build_application_context};
+use soffio::infra::db::PostgresRepositories;
+use soffio::infra::http::api::state::ApiState;
+use soffio::infra::http::{AdminState, HttpState, build_admin_router, build_public_router};
+use soffio::infra::job_monitor::spawn_job_monitor;
+
+/// Every scope, held by the key behind [`TestApp::principal`].
+pub const ALL_SCOPES: [ApiScope; 18] = [
+    ApiScope::PostRead,
+    ApiScope::PostWrite,
+    ApiScope::PageRead,
+    ApiScope::PageWrite,
+    ApiScope::TagRead,
+    ApiScope::TagWrite,
+    ApiScope::NavigationRead,
+    ApiScope::NavigationWrite,
+    ApiScope::UploadRead,
+    ApiScope::UploadWrite,
+    ApiScope::SettingsRead,
+    ApiScope::SettingsWrite,
+    ApiScope::JobRead,
+    ApiScope::AuditRead,
+    ApiScope::SnapshotRead,
+    ApiScope::SnapshotWrite,
+    ApiScope::CommentWrite,
+    ApiScope::RenderPreview,
+];
+
+/// The API, public site and admin site over one test database.
+///
+/// Services are built by `serve`'s own wiring, with the caches off, and its
+/// job workers run in the background for the rest of the test. Tests can also
+/// run a processor inline with [`TestApp::job_context`]. Handlers take
+/// [`TestApp::state`] and a principal from [`TestApp::principal`] or
+/// [`TestApp::principal_with`].
+pub struct TestApp {
+    pub api: ApiState,
+    pub http: HttpState,
+    pub admin: AdminState,
+    jobs: JobWorkerContext,
+    token: String,
+    principal: ApiPrincipal,
+}
+
+/// Options for [`TestApp`]; [`TestApp::new`] takes the defaults.
+pub struct TestAppBuilder {
+    pool: PgPool,
+    base_path: BasePath,
+}
+
+impl TestApp {
+    pub fn builder(pool: PgPool) -> TestAppBuilder {
+        TestAppBuilder {
+            pool,
+            base_path: BasePath::default(),
+        }
+    }
+
+    pub async fn new(pool: PgPool) -> Self {
+        Self::builder(pool).build().await
+    }
+
+    /// API state for calling handlers directly.
+    pub fn state(&self) -> State<ApiState> {
+        State(self.api.clone())
+    }
+
+    /// Token of the key behind [`TestApp::principal`].
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// A principal holding every scope.
+    pub fn principal(&self) -> ApiPrincipal {
+        self.principal.clone()
+    }
+
+    /// A principal holding only `scopes`, from a freshly issued key.
+    pub async fn principal_with(&self, scopes: &[ApiScope]) -> ApiPrincipal {
+        let name = scopes
+            .iter()
+            .map(|scope| scope.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let issued = self
+            .api
+            .api_keys
+            .issue(IssueApiKeyCommand {
+                name,
+                description: None,
+                scopes: scopes.to_vec(),
+                expires_in: None,
+                created_by: "tests".to_string(),
+            })
+            .await
+            .expect("issue key");
+        self.api
+            .api_keys
+            .authenticate(&issued.token)
+            .await
+            .expect("authenticate key")
+    }
+
+    /// Public site plus the API, merged the way the public listener serves
+    /// them.
+    pub fn router(&self) -> axum::Router {
+        build_public_router(self.http.clone(), self.api.clone())
+    }
+
+    /// The admin site, behind its session gate, as the admin listener serves
+    /// it.
+    pub fn admin_router(&self) -> axum::Router {
+        build_admin_router(self.admin.clone())
+    }
+
+    /// Job worker context sharing the app's database and admin services.
+    pub fn job_context(&self) -> JobWorkerContext {
+        self.jobs.clone()
+    }
+}
+
+impl TestAppBuilder {
+    /// Mounts the public site, and the links it generates, under `base_path`.
+    pub fn base_path(mut self, base_path: BasePath) -> Self {
+        self.base_path = base_path;
+        self
+    }
+
+    pub async fn build(self) -> TestApp {
+        let repos = Arc::new(PostgresRepositories::new(self.pool));
+        let repositories = Repositories {
+            http: repos.clone(),
+            public: repos.clone(),
+            jobs: repos.clone(),
+        };
+        let cli = CliArgs::parse_from(["soffio", "serve"]);
+        let mut settings = config::load(&cli).expect("default settings");
+        settings.cache.enable_l0_cache = false;
+        settings.cache.enable_l1_cache = false;
+        settings.server.base_path = self.base_path;
+        settings.uploads.directory = std::env::temp_dir().join("soffio-test-uploads");
+        let reload = ReloadSource {
+            cli,
+            log_level: None,
+            tls: Vec::new(),
+        };
+        let context = build_application_context(repositories, &settings, reload)
+            .expect("application context");
+
+        // The workers stop with the test's runtime.
+        spawn_job_monitor(
+            repos,
+            context.job_context.clone(),
+            context.api_keys.clone(),
+            None,
+            None,
+            &settings.jobs,
+            &settings.cache,
+        );
+
+        let api_key_service = context.api_keys;
+        let issued = api_key_service
+            .issue(IssueApiKeyCommand {
+                name: "test".to_string(),
+                description: None,
+                scopes: ALL_SCOPES.to_vec(),
+                expires_in: None,
+                created_by: "tests".to_string(),
+            })
+            .await
+            .expect("issue key");
+        let principal = api_key_service
+            .authenticate(&issued.token)
+            .await
+            .expect("authenticate key");
+
+        TestApp {
+            api: context.api_state,
+            http: context.http_state,
+            admin: context.admin_state,
+            jobs: context.job_context,
+            token: issued.token,
+            principal,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+use soffio::config::TlsSettings;
+use soffio::infra::listener::Listener;
+use soffio::infra::tls::TlsConfigHandle;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::{
+    ClientConfig, RootCertStore,
+    crypto::ring,
+    pki_types::{CertificateDer, ServerName, pem::PemObject},
+};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/tls")
+        .join(name)
+}
+
+fn client_trusting(cert: &Path) -> TlsConnector {
+    let mut roots = RootCertStore::empty();
+    for cert in CertificateDer::pem_file_iter(cert).expect("read cert") {
+        roots.add(cert.expect("parse cert")).expect("trust cert");
+    }
+    let config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .expect("protocol versions")
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    TlsConnector::from(Arc::new(config))
+}
+
+#[tokio::test]
+async fn public_listener_completes_a_tls_handshake_with_a_self_signed_cert() {
+    let tls = TlsConfigHandle::load(&TlsSettings {
+        cert_path: fixture("cert.pem"),
+        key_path: fixture("key.pem"),
+        client_ca_path: None,
+    })
+    .expect("load certificate");
+    let tcp = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = tcp.local_addr().expect("addr");
+    let Listener::Tls(listener) = Listener::Tcp(tcp).with_tls(Some(tls)).expect("wrap") else {
+        panic!("expected a TLS listener");
+    };
+
+    let router = Router::new().route("/", get(|| async { "secure hello" }));
+    let server = tokio::spawn(async move { axum::serve(listener, router).await });
+
+    let stream = TcpStream::connect(addr).await.expect("connect");
+    let server_name = ServerName::try_from("localhost").expect("server name");
+    let mut stream = client_trusting(&fixture("cert.pem"))
+        .connect(server_name, stream)
+        .await
+        .expect("TLS handshake");
+
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .expect("send request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .await
+        .expect("read response");
+
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.ends_with("secure hello"), "{response}");
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn clients_that_do_not_trust_the_cert_fail_the_handshake() {
+    let tls = TlsConfigHandle::load(&TlsSettings {
+        cert_path: fixture("cert.pem"),
+        key_path: fixture("key.pem"),
+        client_ca_path: None,
+    })
+    .expect("load certificate");
+    let tcp = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = tcp.local_addr().expect("addr");
+    let Listener::Tls(listener) = Listener::Tcp(tcp).with_tls(Some(tls)).expect("wrap") else {
+        panic!("expected a TLS listener");
+    };
+    let server = tokio::spawn(async move { axum::serve(listener, Router::new()).await });
+
+    let stream = TcpStream::connect(addr).await.expect("connect");
+    let server_name = ServerName::try_from("localhost").expect("server name");
+    let result = client_trusting(&fixture("expired-cert.pem"))
+        .connect(server_name, stream)
+        .await;
+    assert!(result.is_err());
+
+    server.abort();
+}